@@ -5,8 +5,65 @@ use std::time::{SystemTime, UNIX_EPOCH};
 const APP_HOME_DIR_NAME: &str = ".ccgui";
 const LEGACY_APP_HOME_DIR_NAMES: &[&str] = &[".mossx", ".codemoss"];
 const MIGRATION_SENTINEL_FILENAME: &str = ".migration.json";
-
+const DATA_DIR_POINTER_FILENAME: &str = "data-dir.json";
+/// Holds the Tauri app data (`settings.json`, `workspaces.json`, ...) inside
+/// a relocated data directory.
+pub(crate) const RELOCATED_APP_DATA_DIR_NAME: &str = "app-data";
+
+/// The directory app data lives in: the relocated data directory when one
+/// is configured, otherwise the default home. A configured directory that
+/// is unreachable (e.g. an unmounted drive) is an error rather than a
+/// silent fallback, so a second copy of the data never starts to diverge.
 pub(crate) fn app_home_dir() -> Result<PathBuf, String> {
+    let default_home = default_app_home_dir()?;
+    match configured_data_dir(&default_home) {
+        Some(path) if path.is_dir() => Ok(path),
+        Some(path) => Err(data_dir_unavailable_error(&path)),
+        None => Ok(default_home),
+    }
+}
+
+/// Where the Tauri app data lives: `app-data/` in the configured data
+/// directory, otherwise `tauri_data_dir`. An unreachable configured
+/// directory is still returned; [`ensure_data_dir_writable`] refuses writes
+/// into it.
+pub(crate) fn resolve_app_data_dir(tauri_data_dir: &Path) -> PathBuf {
+    default_app_home_dir()
+        .ok()
+        .and_then(|home| configured_data_dir(&home))
+        .map_or_else(
+            || tauri_data_dir.to_path_buf(),
+            |path| path.join(RELOCATED_APP_DATA_DIR_NAME),
+        )
+}
+
+/// Refuses writes below a configured data directory that is unreachable.
+pub(crate) fn ensure_data_dir_writable(path: &Path) -> Result<(), String> {
+    match default_app_home_dir() {
+        Ok(default_home) => check_data_dir_writable(&default_home, path),
+        Err(_) => Ok(()),
+    }
+}
+
+fn check_data_dir_writable(default_home: &Path, path: &Path) -> Result<(), String> {
+    match configured_data_dir(default_home) {
+        Some(data_dir) if !data_dir.is_dir() && path.starts_with(&data_dir) => {
+            Err(data_dir_unavailable_error(&data_dir))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn data_dir_unavailable_error(path: &Path) -> String {
+    format!(
+        "Data directory {} is unavailable. Reconnect it, or reset the data directory in settings.",
+        path.display()
+    )
+}
+
+/// `~/.ccgui`. It always stays in place because it holds the pointer to a
+/// relocated data directory.
+pub(crate) fn default_app_home_dir() -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("Unable to resolve home directory")?;
     ensure_app_home_dir_from_home(&home)
 }
@@ -37,7 +94,51 @@ pub(crate) fn agent_file_path() -> Result<PathBuf, String> {
 
 pub(crate) fn workspace_root_candidates() -> Result<Vec<PathBuf>, String> {
     let home = dirs::home_dir().ok_or("Unable to resolve home directory")?;
-    Ok(workspace_root_candidates_from_home(&home))
+    let mut roots = workspace_root_candidates_from_home(&home);
+    if let Some(relocated) = relocated_app_home_dir(&home.join(APP_HOME_DIR_NAME)) {
+        roots.insert(0, relocated.join("workspace"));
+    }
+    Ok(roots)
+}
+
+/// Data directory recorded in the default home's pointer file, whether or
+/// not it currently exists.
+pub(crate) fn configured_data_dir(default_home: &Path) -> Option<PathBuf> {
+    let raw = fs::read_to_string(default_home.join(DATA_DIR_POINTER_FILENAME)).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&raw).ok()?;
+    let path = value.get("path")?.as_str()?.trim();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// The configured data directory, if it is reachable.
+fn relocated_app_home_dir(default_home: &Path) -> Option<PathBuf> {
+    configured_data_dir(default_home).filter(|path| path.is_dir())
+}
+
+/// Points the app at `target`, or back at the default home when `None`.
+/// Written through a temp file so a crash never leaves a half-written
+/// pointer behind.
+pub(crate) fn write_data_dir_pointer(
+    default_home: &Path,
+    target: Option<&Path>,
+) -> Result<(), String> {
+    let pointer_path = default_home.join(DATA_DIR_POINTER_FILENAME);
+    let Some(target) = target else {
+        return match fs::remove_file(&pointer_path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.to_string()),
+            _ => Ok(()),
+        };
+    };
+    fs::create_dir_all(default_home).map_err(|error| error.to_string())?;
+    let payload = serde_json::json!({ "path": target.to_string_lossy() }).to_string();
+    let temp_path = default_home.join(format!("{DATA_DIR_POINTER_FILENAME}.tmp"));
+    fs::write(&temp_path, payload).map_err(|error| error.to_string())?;
+    fs::rename(&temp_path, &pointer_path).map_err(|error| error.to_string())
+}
+
+pub(crate) fn is_data_dir_pointer(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == DATA_DIR_POINTER_FILENAME)
 }
 
 pub(crate) fn prepare_app_data_dir(current_data_dir: &Path) -> Result<(), String> {
@@ -111,7 +212,7 @@ fn legacy_app_data_candidates(parent_dir: &Path) -> Vec<PathBuf> {
     .collect()
 }
 
-fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<(), String> {
+pub(crate) fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<(), String> {
     if source.is_file() {
         copy_file(source, destination)?;
         return Ok(());
@@ -249,6 +350,51 @@ mod tests {
         std::fs::remove_dir_all(&base).ok();
     }
 
+    #[test]
+    fn data_dir_pointer_redirects_only_to_existing_dirs() {
+        let base = std::env::temp_dir().join(format!("ccgui-data-dir-{}", Uuid::new_v4()));
+        let default_home = base.join(".ccgui");
+        let target = base.join("synced").join("ccgui");
+        std::fs::create_dir_all(&default_home).expect("create default home");
+
+        assert_eq!(relocated_app_home_dir(&default_home), None);
+        write_data_dir_pointer(&default_home, Some(&target)).expect("write pointer");
+        assert_eq!(configured_data_dir(&default_home), Some(target.clone()));
+        assert_eq!(relocated_app_home_dir(&default_home), None);
+
+        std::fs::create_dir_all(&target).expect("create target");
+        assert_eq!(relocated_app_home_dir(&default_home), Some(target));
+
+        write_data_dir_pointer(&default_home, None).expect("clear pointer");
+        assert_eq!(configured_data_dir(&default_home), None);
+        write_data_dir_pointer(&default_home, None).expect("clear missing pointer");
+
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    #[test]
+    fn refuses_writes_below_an_unreachable_data_dir() {
+        let base = std::env::temp_dir().join(format!("ccgui-data-dir-{}", Uuid::new_v4()));
+        let default_home = base.join(".ccgui");
+        let target = base.join("drive").join("ccgui");
+        let settings = target
+            .join(RELOCATED_APP_DATA_DIR_NAME)
+            .join("settings.json");
+        std::fs::create_dir_all(&default_home).expect("create default home");
+        write_data_dir_pointer(&default_home, Some(&target)).expect("write pointer");
+
+        let error = check_data_dir_writable(&default_home, &settings).expect_err("unmounted");
+        assert!(error.contains("unavailable"));
+        assert!(check_data_dir_writable(&default_home, &default_home.join("x.json")).is_ok());
+        std::fs::create_dir_all(&target).expect("mount target");
+        assert!(check_data_dir_writable(&default_home, &settings).is_ok());
+
+        write_data_dir_pointer(&default_home, None).expect("clear pointer");
+        assert!(check_data_dir_writable(&default_home, &settings).is_ok());
+
+        std::fs::remove_dir_all(&base).ok();
+    }
+
     #[test]
     fn workspace_root_candidates_put_ccgui_first_and_keep_legacy_paths() {
         let home = PathBuf::from("/Users/demo");
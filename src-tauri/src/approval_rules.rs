@@ -0,0 +1,697 @@
+//! Remembered command approval rules.
+//!
+//! `remember_approval_rule` appends `prefix_rule` entries to
+//! `<CODEX_HOME>/rules/default.rules`, which Codex enforces itself. This
+//! module lists those rules across every Codex home the workspaces use and
+//! keeps what the rules format has no room for (ids, creation times, hit
+//! counts) in `~/.ccgui/approval-rules.json`. A rule is `global` when it
+//! lives in the default Codex home and `workspace` when a workspace, or the
+//! parent of a worktree, points at its own home.
+//!
+//! Hits are counted from the Codex event stream: a completed
+//! `commandExecution` that never asked for approval, in a turn whose approval
+//! policy would otherwise have asked, counts against every rule whose pattern
+//! prefixes one of its simple commands. Codex loads rules when its
+//! app-server starts, so deleting rules restarts the runtimes of the affected
+//! workspaces; workspaces with a turn in flight are reported back instead,
+//! with the threads that keep the old rules until their runtime restarts.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::app_paths;
+use crate::backend::app_server::RuntimeShutdownSource;
+use crate::backend::events::AppServerEvent;
+use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
+use crate::rules;
+use crate::sandbox_report::{command_text, effective_words, simple_commands};
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+use crate::types::WorkspaceEntry;
+
+const STORE_FILE_NAME: &str = "approval-rules.json";
+/// How long a prompted command is remembered when its `item/completed`
+/// never arrives, e.g. because the runtime died mid-turn.
+const PROMPTED_ITEM_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ApprovalRuleScope {
+    Workspace,
+    Global,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleRecord {
+    id: String,
+    rules_path: PathBuf,
+    pattern: Vec<String>,
+    created_at_ms: i64,
+    #[serde(default)]
+    hit_count: u64,
+    #[serde(default)]
+    last_hit_at_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRule {
+    id: String,
+    pattern: Vec<String>,
+    scope: ApprovalRuleScope,
+    /// Workspaces whose Codex home holds the rule.
+    workspace_ids: Vec<String>,
+    rules_path: PathBuf,
+    /// When the app first saw the rule: when it was remembered, or when it
+    /// was first listed for rules written by hand.
+    created_at_ms: i64,
+    hit_count: u64,
+    last_hit_at_ms: Option<i64>,
+}
+
+/// Outcome of deleting rules. Codex only reloads rules when its app-server
+/// restarts, so each affected workspace is either restarted or listed with
+/// the threads that still run with the deleted rules.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRuleRemoval {
+    removed: usize,
+    restarted_workspace_ids: Vec<String>,
+    pending_restarts: Vec<PendingRuntimeRestart>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PendingRuntimeRestart {
+    workspace_id: String,
+    /// Threads with a turn in flight; empty when the restart itself failed.
+    thread_ids: Vec<String>,
+    error: Option<String>,
+}
+
+/// One rules file and the workspaces that resolve to it.
+#[derive(Debug, Clone)]
+struct RulesHome {
+    rules_path: PathBuf,
+    scope: ApprovalRuleScope,
+    workspace_ids: Vec<String>,
+}
+
+fn store_path() -> Result<PathBuf, String> {
+    Ok(app_paths::app_home_dir()?.join(STORE_FILE_NAME))
+}
+
+fn read_records(path: &Path) -> Result<Vec<RuleRecord>, String> {
+    Ok(read_json_file(path)?.unwrap_or_default())
+}
+
+fn write_records(path: &Path, records: &[RuleRecord]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(records).map_err(|error| error.to_string())?;
+    write_string_atomically(path, &data)
+}
+
+fn rules_homes(workspaces: &HashMap<String, WorkspaceEntry>) -> Vec<RulesHome> {
+    let global_path = resolve_default_codex_home().map(|home| rules::default_rules_path(&home));
+    let mut homes: Vec<RulesHome> = global_path
+        .iter()
+        .map(|path| RulesHome {
+            rules_path: path.clone(),
+            scope: ApprovalRuleScope::Global,
+            workspace_ids: Vec::new(),
+        })
+        .collect();
+
+    let mut entries: Vec<&WorkspaceEntry> = workspaces.values().collect();
+    entries.sort_by(|left, right| left.id.cmp(&right.id));
+    for entry in entries {
+        let parent = entry
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id));
+        let Some(home) = resolve_workspace_codex_home(entry, parent) else {
+            continue;
+        };
+        let rules_path = rules::default_rules_path(&home);
+        match homes.iter_mut().find(|home| home.rules_path == rules_path) {
+            Some(home) => home.workspace_ids.push(entry.id.clone()),
+            None => homes.push(RulesHome {
+                scope: if global_path.as_ref() == Some(&rules_path) {
+                    ApprovalRuleScope::Global
+                } else {
+                    ApprovalRuleScope::Workspace
+                },
+                rules_path,
+                workspace_ids: vec![entry.id.clone()],
+            }),
+        }
+    }
+    homes
+}
+
+async fn state_rules_homes(state: &AppState) -> Vec<RulesHome> {
+    rules_homes(&*state.workspaces.lock().await)
+}
+
+/// Matches the rules read from each scanned file to the stored records,
+/// minting ids for rules seen for the first time and forgetting records of
+/// rules no longer in their file. Returns whether anything changed.
+fn sync_records(
+    records: &mut Vec<RuleRecord>,
+    scanned: &[(PathBuf, Vec<Vec<String>>)],
+    now_ms: i64,
+) -> bool {
+    let before = records.len();
+    records.retain(|record| {
+        scanned
+            .iter()
+            .find(|(path, _)| *path == record.rules_path)
+            .is_none_or(|(_, patterns)| patterns.contains(&record.pattern))
+    });
+    let mut changed = records.len() != before;
+    for (path, patterns) in scanned {
+        for pattern in patterns {
+            let known = records
+                .iter()
+                .any(|record| record.rules_path == *path && record.pattern == *pattern);
+            if !known {
+                records.push(RuleRecord {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    rules_path: path.clone(),
+                    pattern: pattern.clone(),
+                    created_at_ms: now_ms,
+                    hit_count: 0,
+                    last_hit_at_ms: None,
+                });
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+fn scan_homes(homes: &[RulesHome]) -> Result<Vec<(PathBuf, Vec<Vec<String>>)>, String> {
+    homes
+        .iter()
+        .map(|home| {
+            rules::read_prefix_rules(&home.rules_path)
+                .map(|patterns| (home.rules_path.clone(), patterns))
+        })
+        .collect()
+}
+
+fn list_rules(homes: &[RulesHome], store_path: &Path) -> Result<Vec<ApprovalRule>, String> {
+    let scanned = scan_homes(homes)?;
+    let records = with_storage_lock(store_path, || {
+        let mut records = read_records(store_path)?;
+        if sync_records(
+            &mut records,
+            &scanned,
+            chrono::Utc::now().timestamp_millis(),
+        ) {
+            write_records(store_path, &records)?;
+        }
+        Ok(records)
+    })?;
+
+    let mut listed = Vec::new();
+    for (home, (_, patterns)) in homes.iter().zip(&scanned) {
+        for pattern in patterns {
+            let Some(record) = records
+                .iter()
+                .find(|record| record.rules_path == home.rules_path && record.pattern == *pattern)
+            else {
+                continue;
+            };
+            listed.push(ApprovalRule {
+                id: record.id.clone(),
+                pattern: record.pattern.clone(),
+                scope: home.scope,
+                workspace_ids: home.workspace_ids.clone(),
+                rules_path: record.rules_path.clone(),
+                created_at_ms: record.created_at_ms,
+                hit_count: record.hit_count,
+                last_hit_at_ms: record.last_hit_at_ms,
+            });
+        }
+    }
+    Ok(listed)
+}
+
+/// Removes `rules` from their rules files and forgets their records.
+fn delete_rules(rules: &[ApprovalRule], store_path: &Path) -> Result<usize, String> {
+    let mut by_path: HashMap<&Path, Vec<Vec<String>>> = HashMap::new();
+    for rule in rules {
+        by_path
+            .entry(rule.rules_path.as_path())
+            .or_default()
+            .push(rule.pattern.clone());
+    }
+    let mut removed = 0;
+    for (path, patterns) in by_path {
+        removed += rules::remove_prefix_rules(path, &patterns)?;
+    }
+    with_storage_lock(store_path, || {
+        let mut records = read_records(store_path)?;
+        records.retain(|record| !rules.iter().any(|rule| rule.id == record.id));
+        write_records(store_path, &records)
+    })?;
+    Ok(removed)
+}
+
+/// Patterns that prefix one of the simple commands in `command`, looking
+/// through `bash -lc` style wrappers.
+fn matching_patterns<'a>(command: &str, patterns: &'a [Vec<String>]) -> Vec<&'a Vec<String>> {
+    let commands = simple_commands(command);
+    patterns
+        .iter()
+        .filter(|pattern| {
+            commands
+                .iter()
+                .any(|words| effective_words(words).starts_with(pattern))
+        })
+        .collect()
+}
+
+fn record_hits(rules_path: &Path, command: &str, store_path: &Path) -> Result<(), String> {
+    let patterns = rules::read_prefix_rules(rules_path)?;
+    let matched = matching_patterns(command, &patterns);
+    if matched.is_empty() {
+        return Ok(());
+    }
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let scanned = [(rules_path.to_path_buf(), patterns.clone())];
+    with_storage_lock(store_path, || {
+        let mut records = read_records(store_path)?;
+        sync_records(&mut records, &scanned, now_ms);
+        for record in records.iter_mut() {
+            if record.rules_path == rules_path && matched.contains(&&record.pattern) {
+                record.hit_count += 1;
+                record.last_hit_at_ms = Some(now_ms);
+            }
+        }
+        write_records(store_path, &records)
+    })
+}
+
+/// Gives rules just appended to `rules_path` an id and creation time.
+pub(crate) async fn note_remembered_rules(rules_path: PathBuf) {
+    let result = tokio::task::spawn_blocking(move || {
+        let patterns = rules::read_prefix_rules(&rules_path)?;
+        let store_path = store_path()?;
+        with_storage_lock(&store_path, || {
+            let mut records = read_records(&store_path)?;
+            let scanned = [(rules_path, patterns)];
+            if sync_records(
+                &mut records,
+                &scanned,
+                chrono::Utc::now().timestamp_millis(),
+            ) {
+                write_records(&store_path, &records)?;
+            }
+            Ok(())
+        })
+    })
+    .await;
+    if let Ok(Err(error)) = result {
+        log::warn!("[approval-rules] failed to record remembered rule: {error}");
+    }
+}
+
+/// Command items that asked for approval, keyed by workspace and item id,
+/// until the item completes or [`PROMPTED_ITEM_TTL`] passes.
+static PROMPTED_ITEMS: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+
+fn prompted_items() -> std::sync::MutexGuard<'static, HashMap<String, Instant>> {
+    PROMPTED_ITEMS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn item_key(workspace_id: &str, item_id: &str) -> String {
+    format!("{workspace_id}\u{0}{item_id}")
+}
+
+fn note_prompted_item(items: &mut HashMap<String, Instant>, key: String, now: Instant) {
+    items.retain(|_, prompted_at| now.duration_since(*prompted_at) < PROMPTED_ITEM_TTL);
+    items.insert(key, now);
+}
+
+/// Whether Codex would have asked before running a command that no rule
+/// allowed. `never` covers read-only and full-access turns.
+fn policy_prompts(approval_policy: &str) -> bool {
+    approval_policy != "never"
+}
+
+/// Counts a hit for each rule that let a Codex command run without asking.
+pub(crate) fn observe_app_server_event(app: &AppHandle, event: &AppServerEvent) {
+    let Some(method) = event.message.get("method").and_then(Value::as_str) else {
+        return;
+    };
+    let Some(params) = event.message.get("params") else {
+        return;
+    };
+    if method == "item/commandExecution/requestApproval" {
+        if let Some(item_id) = params.get("itemId").and_then(Value::as_str) {
+            note_prompted_item(
+                &mut prompted_items(),
+                item_key(&event.workspace_id, item_id),
+                Instant::now(),
+            );
+        }
+        return;
+    }
+    if method != "item/completed" {
+        return;
+    }
+    let Some(item) = params.get("item") else {
+        return;
+    };
+    if item.get("type").and_then(Value::as_str) != Some("commandExecution") {
+        return;
+    }
+    if let Some(item_id) = item.get("id").and_then(Value::as_str) {
+        if prompted_items()
+            .remove(&item_key(&event.workspace_id, item_id))
+            .is_some()
+        {
+            return;
+        }
+    }
+    let Some(thread_id) = params.get("threadId").and_then(Value::as_str) else {
+        return;
+    };
+    let Some(command) = command_text(item) else {
+        return;
+    };
+
+    let app = app.clone();
+    let workspace_id = event.workspace_id.clone();
+    let thread_id = thread_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let session = state.sessions.lock().await.get(&workspace_id).cloned();
+        let Some(session) = session else {
+            return;
+        };
+        let prompts = session
+            .thread_approval_policy(&thread_id)
+            .await
+            .is_some_and(|policy| policy_prompts(&policy));
+        if !prompts {
+            return;
+        }
+        let homes = state_rules_homes(&state).await;
+        let Some(home) = homes
+            .into_iter()
+            .find(|home| home.workspace_ids.contains(&workspace_id))
+        else {
+            return;
+        };
+        let result = tokio::task::spawn_blocking(move || {
+            record_hits(&home.rules_path, &command, &store_path()?)
+        })
+        .await;
+        if let Ok(Err(error)) = result {
+            log::warn!("[approval-rules] failed to record rule hit: {error}");
+        }
+    });
+}
+
+/// Remembered approval rules across every Codex home in use, global home
+/// first, each in file order.
+#[tauri::command]
+pub(crate) async fn list_approval_rules(
+    state: State<'_, AppState>,
+) -> Result<Vec<ApprovalRule>, String> {
+    let homes = state_rules_homes(&state).await;
+    let store_path = store_path()?;
+    tokio::task::spawn_blocking(move || list_rules(&homes, &store_path))
+        .await
+        .map_err(|error| error.to_string())?
+}
+
+/// Workspaces whose Codex home held any of `rules`, in id order.
+fn affected_workspace_ids(rules: &[ApprovalRule]) -> Vec<String> {
+    rules
+        .iter()
+        .flat_map(|rule| rule.workspace_ids.iter().cloned())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Restarts the connected Codex runtimes of `workspace_ids` so they reload
+/// their rules. Runtimes with a turn in flight are left running.
+async fn reload_rules(
+    app: &AppHandle,
+    state: &AppState,
+    workspace_ids: &[String],
+    removed: usize,
+) -> ApprovalRuleRemoval {
+    let mut removal = ApprovalRuleRemoval {
+        removed,
+        ..ApprovalRuleRemoval::default()
+    };
+    if removed == 0 {
+        return removal;
+    }
+    let _reload_guard = state.codex_runtime_reload_lock.lock().await;
+    let connected: Vec<_> = {
+        let sessions = state.sessions.lock().await;
+        workspace_ids
+            .iter()
+            .filter_map(|id| {
+                sessions
+                    .get(id)
+                    .map(|session| (id.clone(), session.clone()))
+            })
+            .collect()
+    };
+    let mut idle = Vec::new();
+    for (workspace_id, session) in connected {
+        let thread_ids = session.active_thread_ids().await;
+        if thread_ids.is_empty() {
+            idle.push(workspace_id);
+        } else {
+            removal.pending_restarts.push(PendingRuntimeRestart {
+                workspace_id,
+                thread_ids,
+                error: None,
+            });
+        }
+    }
+    if idle.is_empty() {
+        return removal;
+    }
+
+    let staged = match crate::settings::spawn_reloaded_codex_sessions(state, app, Some(&idle)).await
+    {
+        Ok(staged) => staged,
+        Err(error) => {
+            log::warn!("[approval-rules] failed to restart Codex runtimes: {error}");
+            removal
+                .pending_restarts
+                .extend(idle.into_iter().map(|workspace_id| PendingRuntimeRestart {
+                    workspace_id,
+                    thread_ids: Vec::new(),
+                    error: Some(error.clone()),
+                }));
+            return removal;
+        }
+    };
+    for (workspace_id, new_session) in staged {
+        match crate::runtime::replace_workspace_session_with_source(
+            &state.sessions,
+            Some(&state.runtime_manager),
+            workspace_id.clone(),
+            new_session,
+            "approval-rules-changed",
+            RuntimeShutdownSource::SettingsRestart,
+        )
+        .await
+        {
+            Ok(()) => removal.restarted_workspace_ids.push(workspace_id),
+            Err(error) => removal.pending_restarts.push(PendingRuntimeRestart {
+                workspace_id,
+                thread_ids: Vec::new(),
+                error: Some(error),
+            }),
+        }
+    }
+    removal
+}
+
+async fn remove_rules(
+    app: &AppHandle,
+    state: &AppState,
+    select: impl FnOnce(Vec<ApprovalRule>) -> Result<Vec<ApprovalRule>, String> + Send + 'static,
+) -> Result<ApprovalRuleRemoval, String> {
+    let homes = state_rules_homes(state).await;
+    let store_path = store_path()?;
+    let (removed, workspace_ids) = tokio::task::spawn_blocking(move || {
+        let rules = select(list_rules(&homes, &store_path)?)?;
+        let removed = delete_rules(&rules, &store_path)?;
+        Ok::<_, String>((removed, affected_workspace_ids(&rules)))
+    })
+    .await
+    .map_err(|error| error.to_string())??;
+    Ok(reload_rules(app, state, &workspace_ids, removed).await)
+}
+
+/// Removes one remembered rule, so matching commands ask for approval again.
+#[tauri::command]
+pub(crate) async fn delete_approval_rule(
+    rule_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ApprovalRuleRemoval, String> {
+    remove_rules(&app, &state, move |rules| {
+        rules
+            .into_iter()
+            .find(|rule| rule.id == rule_id)
+            .map(|rule| vec![rule])
+            .ok_or_else(|| "approval rule not found".to_string())
+    })
+    .await
+}
+
+/// Removes every remembered rule in `scope`.
+#[tauri::command]
+pub(crate) async fn clear_approval_rules(
+    scope: ApprovalRuleScope,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ApprovalRuleRemoval, String> {
+    remove_rules(&app, &state, move |mut rules| {
+        rules.retain(|rule| rule.scope == scope);
+        Ok(rules)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    fn pattern(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn matches_rule_prefixes_through_shell_wrappers() {
+        let patterns = vec![pattern(&["git", "status"]), pattern(&["npm", "test"])];
+        assert_eq!(
+            matching_patterns(
+                "/bin/zsh -lc 'cd web && npm test -- --watch=false'",
+                &patterns
+            ),
+            vec![&patterns[1]]
+        );
+        assert!(matching_patterns("git stash", &patterns).is_empty());
+        assert!(matching_patterns("npm", &patterns).is_empty());
+    }
+
+    #[test]
+    fn only_turns_that_would_prompt_count_hits() {
+        assert!(policy_prompts("on-request"));
+        assert!(policy_prompts("untrusted"));
+        assert!(!policy_prompts("never"));
+    }
+
+    #[test]
+    fn prompted_items_that_never_complete_expire() {
+        let mut items = HashMap::new();
+        let start = Instant::now();
+        note_prompted_item(&mut items, item_key("ws-1", "stuck"), start);
+        note_prompted_item(
+            &mut items,
+            item_key("ws-1", "recent"),
+            start + PROMPTED_ITEM_TTL / 2,
+        );
+        note_prompted_item(
+            &mut items,
+            item_key("ws-1", "later"),
+            start + PROMPTED_ITEM_TTL,
+        );
+        let mut keys: Vec<_> = items.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![item_key("ws-1", "later"), item_key("ws-1", "recent")]
+        );
+    }
+
+    #[test]
+    fn deleting_rules_affects_every_workspace_sharing_their_home() {
+        let rule = |id: &str, workspace_ids: &[&str]| ApprovalRule {
+            id: id.to_string(),
+            pattern: pattern(&["git", "status"]),
+            scope: ApprovalRuleScope::Global,
+            workspace_ids: workspace_ids.iter().map(|id| id.to_string()).collect(),
+            rules_path: PathBuf::from("/codex/rules/default.rules"),
+            created_at_ms: 0,
+            hit_count: 0,
+            last_hit_at_ms: None,
+        };
+        assert_eq!(
+            affected_workspace_ids(&[rule("a", &["ws-2", "ws-1"]), rule("b", &["ws-1", "ws-3"])]),
+            vec!["ws-1", "ws-2", "ws-3"]
+        );
+        assert!(affected_workspace_ids(&[]).is_empty());
+    }
+
+    #[test]
+    fn ids_stay_stable_and_deleted_rules_leave_the_rules_file() {
+        let dir = temp_dir();
+        let rules_path = dir.join("codex").join("rules").join("default.rules");
+        let store_path = dir.join(STORE_FILE_NAME);
+        rules::append_prefix_rule(&rules_path, &pattern(&["git", "status"])).expect("append");
+        rules::append_prefix_rule(&rules_path, &pattern(&["cargo", "test"])).expect("append");
+        let homes = vec![RulesHome {
+            rules_path: rules_path.clone(),
+            scope: ApprovalRuleScope::Workspace,
+            workspace_ids: vec!["ws-1".to_string()],
+        }];
+
+        let listed = list_rules(&homes, &store_path).expect("list");
+        assert_eq!(listed.len(), 2);
+        record_hits(&rules_path, "bash -lc 'cargo test -p app'", &store_path).expect("hit");
+        let relisted = list_rules(&homes, &store_path).expect("relist");
+        assert_eq!(
+            relisted.iter().map(|rule| &rule.id).collect::<Vec<_>>(),
+            listed.iter().map(|rule| &rule.id).collect::<Vec<_>>()
+        );
+        assert_eq!(relisted[0].hit_count, 0);
+        assert_eq!(relisted[1].hit_count, 1);
+        assert!(relisted[1].last_hit_at_ms.is_some());
+
+        assert_eq!(
+            delete_rules(&relisted[1..], &store_path).expect("delete"),
+            1
+        );
+        assert_eq!(
+            rules::read_prefix_rules(&rules_path).expect("read"),
+            vec![pattern(&["git", "status"])]
+        );
+        let remaining = list_rules(&homes, &store_path).expect("list after delete");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, listed[0].id);
+        assert_eq!(read_records(&store_path).expect("records").len(), 1);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -16,7 +16,7 @@ use tokio::process::{Child, ChildStdin};
 use tokio::sync::{mpsc, oneshot, Mutex};
 use tokio::time::timeout;
 
-use crate::backend::events::{AppServerEvent, EventSink, TerminalOutput};
+use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
 use crate::codex::collaboration_policy::strict_local_collaboration_profile_enabled;
 use crate::codex::thread_mode_state::ThreadModeState;
 use crate::runtime::{RuntimeEndedRecord, RuntimeManager};
@@ -50,6 +50,7 @@ enum DeferredStartupEventMode {
 enum DeferredStartupEvent {
     AppServer(AppServerEvent),
     Terminal(TerminalOutput),
+    TerminalExit(TerminalExit),
 }
 
 struct DeferredStartupEventState {
@@ -95,6 +96,7 @@ impl<E: EventSink> DeferredStartupEventSink<E> {
         match event {
             DeferredStartupEvent::AppServer(event) => self.inner.emit_app_server_event(event),
             DeferredStartupEvent::Terminal(event) => self.inner.emit_terminal_output(event),
+            DeferredStartupEvent::TerminalExit(event) => self.inner.emit_terminal_exit(event),
         }
     }
 }
@@ -147,6 +149,30 @@ impl<E: EventSink> EventSink for DeferredStartupEventSink<E> {
             self.inner.emit_terminal_output(event);
         }
     }
+
+    fn emit_terminal_exit(&self, event: TerminalExit) {
+        let mut forward_event = Some(event);
+        {
+            let mut state = self
+                .state
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            match state.mode {
+                DeferredStartupEventMode::Buffering => {
+                    if let Some(event) = forward_event.take() {
+                        state.events.push(DeferredStartupEvent::TerminalExit(event));
+                    }
+                }
+                DeferredStartupEventMode::Forwarding => {}
+                DeferredStartupEventMode::Discarding => {
+                    forward_event = None;
+                }
+            }
+        }
+        if let Some(event) = forward_event {
+            self.inner.emit_terminal_exit(event);
+        }
+    }
 }
 
 #[allow(unused_imports)]
@@ -389,6 +415,8 @@ pub(crate) struct WorkspaceSession {
     /// Callbacks for background threads - events for these threadIds are sent through the channel
     pub(crate) background_thread_callbacks: Mutex<HashMap<String, mpsc::UnboundedSender<Value>>>,
     pub(crate) thread_mode_state: ThreadModeState,
+    /// Approval policy sent with each thread's latest `turn/start`.
+    thread_approval_policies: Mutex<HashMap<String, String>>,
     pub(crate) mode_enforcement_enabled: AtomicBool,
     pub(crate) collaboration_mode_supported: AtomicBool,
     auto_compaction_threshold_percent: f64,
@@ -738,6 +766,21 @@ impl WorkspaceSession {
         self.plan_turn_state.lock().await.remove(thread_id);
     }
 
+    pub(crate) async fn set_thread_approval_policy(&self, thread_id: &str, policy: &str) {
+        self.thread_approval_policies
+            .lock()
+            .await
+            .insert(thread_id.to_string(), policy.to_string());
+    }
+
+    pub(crate) async fn thread_approval_policy(&self, thread_id: &str) -> Option<String> {
+        self.thread_approval_policies
+            .lock()
+            .await
+            .get(thread_id)
+            .cloned()
+    }
+
     pub(crate) async fn consume_local_user_input_request(&self, request_id: &str) -> bool {
         self.local_user_input_requests
             .lock()
@@ -948,6 +991,10 @@ async fn spawn_workspace_session_once<E: EventSink>(
     if launch_options.launch_mode == CodexAppServerLaunchMode::SessionHooksDisabled {
         command.env("CODEX_NON_INTERACTIVE", "1");
     }
+    crate::shared::provider_tls::apply_active_provider_tls_env(
+        &mut command,
+        crate::shared::provider_tls::TlsProviderEngine::Codex,
+    );
     command.stdin(std::process::Stdio::piped());
     command.stdout(std::process::Stdio::piped());
     command.stderr(std::process::Stdio::piped());
@@ -972,6 +1019,7 @@ async fn spawn_workspace_session_once<E: EventSink>(
         next_id: AtomicU64::new(1),
         background_thread_callbacks: Mutex::new(HashMap::new()),
         thread_mode_state: ThreadModeState::default(),
+        thread_approval_policies: Mutex::new(HashMap::new()),
         mode_enforcement_enabled: AtomicBool::new(true),
         collaboration_mode_supported: AtomicBool::new(true),
         auto_compaction_threshold_percent,
@@ -1107,6 +1155,7 @@ pub(crate) async fn make_test_workspace_session(id: &str) -> Arc<WorkspaceSessio
         next_id: AtomicU64::new(1),
         background_thread_callbacks: Mutex::new(HashMap::new()),
         thread_mode_state: crate::codex::thread_mode_state::ThreadModeState::default(),
+        thread_approval_policies: Mutex::new(HashMap::new()),
         mode_enforcement_enabled: AtomicBool::new(false),
         collaboration_mode_supported: AtomicBool::new(false),
         auto_compaction_threshold_percent: AUTO_COMPACTION_THRESHOLD_PERCENT,
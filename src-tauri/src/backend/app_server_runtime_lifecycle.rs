@@ -38,6 +38,13 @@ impl WorkspaceSession {
         super::event_helpers::apply_runtime_event_activity(&mut active_turns, value);
     }
 
+    /// Threads with a turn in flight.
+    pub(crate) async fn active_thread_ids(&self) -> Vec<String> {
+        let mut thread_ids: Vec<String> = self.active_turns.lock().await.keys().cloned().collect();
+        thread_ids.sort();
+        thread_ids
+    }
+
     pub(crate) fn mark_shutdown_requested(&self, source: RuntimeShutdownSource) {
         self.manual_shutdown_requested.store(true, Ordering::SeqCst);
         let mut shutdown_source = self
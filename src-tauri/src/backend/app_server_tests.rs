@@ -15,7 +15,7 @@ use super::{
     MODE_BLOCKED_REASON_CODE_PLAN_READONLY, MODE_BLOCKED_REASON_CODE_REQUEST_USER_INPUT,
     MODE_BLOCKED_SUGGESTION,
 };
-use crate::backend::events::{AppServerEvent, EventSink, TerminalOutput};
+use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
 use crate::runtime::RuntimeManager;
 use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
 use serde_json::{json, Value};
@@ -49,6 +49,8 @@ impl EventSink for TestEventSink {
     }
 
     fn emit_terminal_output(&self, _event: TerminalOutput) {}
+
+    fn emit_terminal_exit(&self, _event: TerminalExit) {}
 }
 
 #[test]
@@ -155,6 +157,7 @@ async fn make_workspace_session(id: &str) -> Arc<WorkspaceSession> {
         next_id: AtomicU64::new(1),
         background_thread_callbacks: Mutex::new(HashMap::new()),
         thread_mode_state: crate::codex::thread_mode_state::ThreadModeState::default(),
+        thread_approval_policies: Mutex::new(HashMap::new()),
         mode_enforcement_enabled: AtomicBool::new(false),
         collaboration_mode_supported: AtomicBool::new(false),
         auto_compaction_threshold_percent: AUTO_COMPACTION_THRESHOLD_PERCENT,
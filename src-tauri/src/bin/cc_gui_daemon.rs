@@ -1,3 +1,5 @@
+#[path = "cc_gui_daemon/access_tokens.rs"]
+mod access_tokens;
 #[allow(dead_code)]
 #[path = "../app_paths.rs"]
 mod app_paths;
@@ -66,6 +68,7 @@ mod state {
 #[allow(dead_code)]
 #[path = "../local_usage.rs"]
 mod local_usage;
+#[allow(dead_code)]
 #[path = "../rules.rs"]
 mod rules;
 #[allow(dead_code)]
@@ -164,11 +167,12 @@ use tokio::process::Command;
 use tokio::sync::{broadcast, mpsc, oneshot, Mutex};
 use uuid::Uuid;
 
+use access_tokens::{AccessTokenStore, ClientGrant};
 use backend::app_server::{spawn_workspace_session, WorkspaceSession};
-use backend::events::{AppServerEvent, EventSink, TerminalOutput};
+use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
 use shared::{
     codex_core, files_core, git_core, proxy_core, settings_core, thread_titles_core,
-    workspaces_core, worktree_core,
+    workspace_read_only, workspaces_core, worktree_core,
 };
 use storage::{read_settings, read_workspaces};
 use types::{
@@ -177,14 +181,14 @@ use types::{
     GitHistoryCommit, GitHistoryResponse, GitHubIssue, GitHubIssuesResponse, GitHubPullRequest,
     GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogEntry,
     GitLogResponse, GitPrWorkflowDefaults, GitPrWorkflowResult, GitPrWorkflowStage,
-    GitPushPreviewResponse, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    GitPushPreviewResponse, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings,
+    WorktreeNamingContext, WorktreeSetupStatus,
 };
 use utils::normalize_git_path;
 use web_service_runtime::WebServiceRuntime;
 use workspace_settings::apply_workspace_settings_update;
 
 const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:4732";
-const EVENT_FORWARDER_TIMEOUT_SECS: u64 = 30 * 60;
 const GEMINI_POST_COMPLETION_REASONING_GRACE_MS: u64 = 8_000;
 
 #[derive(Debug, Clone, Serialize)]
@@ -302,6 +306,8 @@ enum DaemonEvent {
     AppServer(AppServerEvent),
     #[allow(dead_code)]
     TerminalOutput(TerminalOutput),
+    #[allow(dead_code)]
+    TerminalExit(TerminalExit),
     CliInstaller(Value),
 }
 
@@ -313,6 +319,10 @@ impl EventSink for DaemonEventSink {
     fn emit_terminal_output(&self, event: TerminalOutput) {
         let _ = self.tx.send(DaemonEvent::TerminalOutput(event));
     }
+
+    fn emit_terminal_exit(&self, event: TerminalExit) {
+        let _ = self.tx.send(DaemonEvent::TerminalExit(event));
+    }
 }
 
 impl DaemonEventSink {
@@ -340,6 +350,7 @@ struct DaemonState {
     codex_login_cancels: Mutex<HashMap<String, oneshot::Sender<()>>>,
     engine_manager: engine::EngineManager,
     active_engine: Mutex<engine::EngineType>,
+    access_tokens: Mutex<AccessTokenStore>,
 }
 
 fn default_data_dir() -> PathBuf {
@@ -466,6 +477,10 @@ fn build_event_notification(event: DaemonEvent) -> Option<String> {
             "method": "terminal-output",
             "params": payload,
         }),
+        DaemonEvent::TerminalExit(payload) => json!({
+            "method": "terminal-exit",
+            "params": payload,
+        }),
         DaemonEvent::CliInstaller(payload) => json!({
             "method": "cli-installer-event",
             "params": payload,
@@ -713,6 +728,23 @@ fn parse_file_write_request(params: &Value) -> Result<FileWriteRequest, String>
     serde_json::from_value(params.clone()).map_err(|err| err.to_string())
 }
 
+/// Rejects workspace write methods aimed at a read-only workspace before
+/// they are dispatched.
+async fn ensure_rpc_workspace_writable(
+    state: &DaemonState,
+    method: &str,
+    params: &Value,
+) -> Result<(), String> {
+    if !workspace_read_only::is_workspace_write_command(method) {
+        return Ok(());
+    }
+    let Ok(workspace_id) = parse_string(params, "workspaceId") else {
+        return Ok(());
+    };
+    workspace_read_only::ensure_workspace_id_writable(&state.workspaces, &workspace_id, method)
+        .await
+}
+
 async fn handle_rpc_request(
     state: &DaemonState,
     method: &str,
@@ -741,15 +773,30 @@ async fn handle_rpc_request(
             let workspace = state.add_workspace(path, codex_bin, client_version).await?;
             serde_json::to_value(workspace).map_err(|err| err.to_string())
         }
+        "preview_worktree_branch_name" => {
+            let parent_id = parse_string(&params, "parentId")?;
+            let naming: WorktreeNamingContext =
+                serde_json::from_value(params.get("naming").cloned().unwrap_or(Value::Null))
+                    .map_err(|err| format!("invalid worktree naming: {err}"))?;
+            let template = parse_optional_string(&params, "template");
+            let branch = state
+                .preview_worktree_branch_name(parent_id, naming, template)
+                .await?;
+            Ok(Value::String(branch))
+        }
         "add_worktree" => {
             let parent_id = parse_string(&params, "parentId")?;
             let branch = parse_string(&params, "branch")?;
+            let naming: Option<WorktreeNamingContext> =
+                serde_json::from_value(params.get("naming").cloned().unwrap_or(Value::Null))
+                    .map_err(|err| format!("invalid worktree naming: {err}"))?;
             let base_ref = parse_optional_string(&params, "baseRef");
             let publish_to_origin = parse_optional_bool(&params, "publishToOrigin").unwrap_or(true);
             let workspace = state
                 .add_worktree(
                     parent_id,
                     branch,
+                    naming,
                     base_ref,
                     publish_to_origin,
                     client_version,
@@ -989,6 +1036,9 @@ async fn handle_rpc_request(
             let body = parse_optional_string(&params, "body");
             let comment_after_create = parse_optional_bool(&params, "commentAfterCreate");
             let comment_body = parse_optional_string(&params, "commentBody");
+            let draft = parse_optional_bool(&params, "draft");
+            let labels = parse_optional_string_array(&params, "labels");
+            let reviewers = parse_optional_string_array(&params, "reviewers");
             let response = state
                 .create_git_pr_workflow(
                     workspace_id,
@@ -1000,6 +1050,9 @@ async fn handle_rpc_request(
                     body,
                     comment_after_create,
                     comment_body,
+                    draft,
+                    labels,
+                    reviewers,
                 )
                 .await?;
             serde_json::to_value(response).map_err(|err| err.to_string())
@@ -1410,6 +1463,8 @@ async fn handle_rpc_request(
             let agent = parse_optional_string(&params, "agent");
             let variant = parse_optional_string(&params, "variant");
             let custom_spec_root = parse_optional_string(&params, "customSpecRoot");
+            let cwd = parse_optional_string(&params, "cwd");
+            let timeout_secs = parse_optional_u64(&params, "timeoutSecs");
             state
                 .engine_send_message(
                     workspace_id,
@@ -1427,6 +1482,8 @@ async fn handle_rpc_request(
                     agent,
                     variant,
                     custom_spec_root,
+                    cwd,
+                    timeout_secs,
                 )
                 .await
         }
@@ -1447,6 +1504,7 @@ async fn handle_rpc_request(
             let agent = parse_optional_string(&params, "agent");
             let variant = parse_optional_string(&params, "variant");
             let custom_spec_root = parse_optional_string(&params, "customSpecRoot");
+            let cwd = parse_optional_string(&params, "cwd");
             state
                 .engine_send_message_sync(
                     workspace_id,
@@ -1463,6 +1521,7 @@ async fn handle_rpc_request(
                     agent,
                     variant,
                     custom_spec_root,
+                    cwd,
                 )
                 .await
         }
@@ -1540,6 +1599,16 @@ async fn handle_rpc_request(
                 .await?;
             Ok(json!({ "ok": true }))
         }
+        "engine_list_sessions" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let engine = parse_optional_string(&params, "engine")
+                .as_deref()
+                .and_then(|value| parse_engine_type_string(Some(value)));
+            let limit = parse_optional_u32(&params, "limit").map(|value| value as usize);
+            state
+                .engine_list_sessions(workspace_id, engine, limit)
+                .await
+        }
         "list_gemini_sessions" => {
             let workspace_path = parse_string(&params, "workspacePath")?;
             let limit = parse_optional_u32(&params, "limit").map(|value| value as usize);
@@ -1944,10 +2013,10 @@ async fn handle_client(
         }
     });
 
-    let mut authenticated = config.token.is_none();
+    let mut grant = config.token.is_none().then_some(ClientGrant::Full);
     let mut events_task: Option<tokio::task::JoinHandle<()>> = None;
 
-    if authenticated {
+    if grant.is_some() {
         let rx = events.subscribe();
         let out_tx_events = out_tx.clone();
         events_task = Some(tokio::spawn(forward_events(rx, out_tx_events)));
@@ -1972,7 +2041,7 @@ async fn handle_client(
             .to_string();
         let params = message.get("params").cloned().unwrap_or(Value::Null);
 
-        if !authenticated {
+        let Some(client_grant) = grant.as_ref() else {
             if method != "auth" {
                 if let Some(response) = build_error_response(id, "unauthorized") {
                     let _ = out_tx.send(response);
@@ -1982,15 +2051,23 @@ async fn handle_client(
 
             let expected = config.token.clone().unwrap_or_default();
             let provided = parse_auth_token(&params).unwrap_or_default();
-            if expected != provided {
+            let authenticated = if expected == provided {
+                Some(ClientGrant::Full)
+            } else {
+                state.access_tokens.lock().await.authenticate(&provided)
+            };
+            let Some(authenticated) = authenticated else {
                 if let Some(response) = build_error_response(id, "invalid token") {
                     let _ = out_tx.send(response);
                 }
                 continue;
-            }
+            };
 
-            authenticated = true;
-            if let Some(response) = build_result_response(id, json!({ "ok": true })) {
+            let scopes = state.access_tokens.lock().await.scopes_for(&authenticated);
+            grant = Some(authenticated);
+            if let Some(response) =
+                build_result_response(id, json!({ "ok": true, "scopes": scopes }))
+            {
                 let _ = out_tx.send(response);
             }
 
@@ -1999,10 +2076,25 @@ async fn handle_client(
             events_task = Some(tokio::spawn(forward_events(rx, out_tx_events)));
 
             continue;
-        }
+        };
 
-        let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
-        let result = handle_rpc_request(&state, &method, params, client_version).await;
+        let management = {
+            let mut access_tokens = state.access_tokens.lock().await;
+            match access_tokens.authorize(client_grant, &method) {
+                Ok(()) => access_tokens.handle_management_rpc(client_grant, &method, &params),
+                Err(message) => Some(Err(message)),
+            }
+        };
+        let result = match management {
+            Some(result) => result,
+            None => match ensure_rpc_workspace_writable(&state, &method, &params).await {
+                Ok(()) => {
+                    let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
+                    handle_rpc_request(&state, &method, params, client_version).await
+                }
+                Err(message) => Err(message),
+            },
+        };
         let response = match result {
             Ok(result) => build_result_response(id, result),
             Err(message) => build_error_response(id, &message),
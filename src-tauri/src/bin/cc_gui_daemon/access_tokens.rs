@@ -0,0 +1,509 @@
+//! Scoped access tokens for the daemon RPC.
+//!
+//! The `--token` passed at startup keeps full access. Additional tokens can be
+//! minted with a subset of scopes for automation and remote clients; every
+//! RPC method maps to the scope it needs and the dispatch layer rejects calls
+//! the connection's token does not cover. Only a SHA-256 of each minted token
+//! is persisted, and revocation takes effect on open connections as well.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::rpc_params::{parse_string, parse_string_array};
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const ACCESS_TOKENS_FILENAME: &str = "access-tokens.json";
+const MINTED_TOKEN_PREFIX: &str = "ccgd_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum TokenScope {
+    ReadOnly,
+    GitWrite,
+    EngineRun,
+    SettingsAdmin,
+}
+
+impl TokenScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read-only",
+            Self::GitWrite => "git-write",
+            Self::EngineRun => "engine-run",
+            Self::SettingsAdmin => "settings-admin",
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim() {
+            "read-only" => Ok(Self::ReadOnly),
+            "git-write" => Ok(Self::GitWrite),
+            "engine-run" => Ok(Self::EngineRun),
+            "settings-admin" => Ok(Self::SettingsAdmin),
+            other => Err(format!("unknown token scope: {other}")),
+        }
+    }
+}
+
+/// Scope an RPC method requires. Methods that are not listed fall back to
+/// `settings-admin` so new endpoints are closed to narrow tokens by default.
+pub(crate) fn required_scope(method: &str) -> TokenScope {
+    match method {
+        "ping"
+        | "list_workspaces"
+        | "is_workspace_path_dir"
+        | "worktree_setup_status"
+        | "list_workspace_files"
+        | "list_workspace_directory_children"
+        | "list_external_absolute_directory_children"
+        | "read_workspace_file"
+        | "list_external_spec_tree"
+        | "read_external_spec_file"
+        | "read_external_absolute_file"
+        | "get_git_status"
+        | "list_git_roots"
+        | "get_git_diffs"
+        | "get_git_file_full_diff"
+        | "get_git_log"
+        | "get_git_commit_history"
+        | "get_git_push_preview"
+        | "get_git_pr_workflow_defaults"
+        | "resolve_git_commit_ref"
+        | "get_git_commit_details"
+        | "get_git_commit_diff"
+        | "get_git_remote"
+        | "get_github_issues"
+        | "get_github_pull_requests"
+        | "get_github_pull_request_diff"
+        | "get_github_pull_request_comments"
+        | "list_git_branches"
+        | "get_git_branch_compare_commits"
+        | "get_git_branch_diff_between_branches"
+        | "get_git_branch_file_diff_between_branches"
+        | "get_git_worktree_diff_against_branch"
+        | "get_git_worktree_file_diff_against_branch"
+        | "codex_doctor"
+        | "claude_doctor"
+        | "cli_install_plan"
+        | "get_codex_unified_exec_external_status"
+        | "detect_engines"
+        | "get_active_engine"
+        | "get_engine_status"
+        | "get_engine_models"
+        | "get_codex_config_path"
+        | "get_config_model"
+        | "list_claude_sessions"
+        | "load_claude_session"
+        | "hydrate_claude_deferred_image"
+        | "list_gemini_sessions"
+        | "engine_list_sessions"
+        | "list_workspace_sessions"
+        | "list_global_codex_sessions"
+        | "list_project_related_codex_sessions"
+        | "get_workspace_session_projection_summary"
+        | "list_workspace_session_folders"
+        | "load_gemini_session"
+        | "opencode_session_list"
+        | "resume_thread"
+        | "list_threads"
+        | "list_mcp_server_status"
+        | "model_list"
+        | "collaboration_mode_list"
+        | "account_rate_limits"
+        | "account_read"
+        | "skills_list"
+        | "list_thread_titles"
+        | "preview_worktree_branch_name" => TokenScope::ReadOnly,
+        "add_worktree"
+        | "worktree_setup_mark_ran"
+        | "remove_worktree"
+        | "rename_worktree"
+        | "rename_worktree_upstream"
+        | "write_external_spec_file"
+        | "create_git_pr_workflow"
+        | "stage_git_file"
+        | "stage_git_all"
+        | "unstage_git_file"
+        | "revert_git_file"
+        | "revert_git_all"
+        | "commit_git"
+        | "push_git"
+        | "pull_git"
+        | "sync_git"
+        | "git_pull"
+        | "git_push"
+        | "git_sync"
+        | "git_fetch"
+        | "update_git_branch"
+        | "cherry_pick_commit"
+        | "revert_commit"
+        | "reset_git_commit"
+        | "checkout_git_branch"
+        | "create_git_branch"
+        | "create_git_branch_from_branch"
+        | "create_git_branch_from_commit"
+        | "delete_git_branch"
+        | "rename_git_branch"
+        | "merge_git_branch"
+        | "rebase_git_branch" => TokenScope::GitWrite,
+        "connect_workspace"
+        | "switch_engine"
+        | "engine_send_message"
+        | "engine_send_message_sync"
+        | "engine_interrupt"
+        | "engine_interrupt_turn"
+        | "start_thread"
+        | "fork_claude_session"
+        | "fork_claude_session_from_message"
+        | "delete_claude_session"
+        | "archive_workspace_sessions"
+        | "unarchive_workspace_sessions"
+        | "delete_workspace_sessions"
+        | "create_workspace_session_folder"
+        | "rename_workspace_session_folder"
+        | "move_workspace_session_folder"
+        | "delete_workspace_session_folder"
+        | "assign_workspace_session_folder"
+        | "delete_gemini_session"
+        | "fork_thread"
+        | "rewind_codex_thread"
+        | "archive_thread"
+        | "delete_codex_session"
+        | "delete_codex_sessions"
+        | "send_user_message"
+        | "turn_interrupt"
+        | "thread_compact"
+        | "start_review"
+        | "set_thread_title"
+        | "rename_thread_title_key"
+        | "generate_thread_title"
+        | "respond_to_server_request" => TokenScope::EngineRun,
+        _ => TokenScope::SettingsAdmin,
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScopedAccessToken {
+    id: String,
+    label: String,
+    scopes: BTreeSet<TokenScope>,
+    token_hash: String,
+    created_at: u64,
+    #[serde(default)]
+    last_used_at: Option<u64>,
+    #[serde(default)]
+    revoked_at: Option<u64>,
+}
+
+impl ScopedAccessToken {
+    fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+
+    /// Every scope implies `read-only`; the write scopes are independent.
+    fn allows(&self, scope: TokenScope) -> bool {
+        self.is_active() && (scope == TokenScope::ReadOnly || self.scopes.contains(&scope))
+    }
+
+    fn summary(&self) -> Value {
+        json!({
+            "id": self.id,
+            "label": self.label,
+            "scopes": self.scopes.iter().map(|scope| scope.as_str()).collect::<Vec<_>>(),
+            "createdAt": self.created_at,
+            "lastUsedAt": self.last_used_at,
+            "revokedAt": self.revoked_at,
+        })
+    }
+}
+
+/// What an authenticated connection may do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ClientGrant {
+    /// The startup token, or no auth at all in `--insecure-no-auth` mode.
+    Full,
+    Scoped {
+        token_id: String,
+    },
+}
+
+pub(crate) struct AccessTokenStore {
+    path: PathBuf,
+    tokens: Vec<ScopedAccessToken>,
+}
+
+impl AccessTokenStore {
+    pub(crate) fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(ACCESS_TOKENS_FILENAME);
+        let tokens = match read_json_file::<Vec<ScopedAccessToken>>(&path) {
+            Ok(tokens) => tokens.unwrap_or_default(),
+            Err(error) => {
+                eprintln!("[access-tokens] failed to load {}: {error}", path.display());
+                Vec::new()
+            }
+        };
+        Self { path, tokens }
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        let data = serde_json::to_string_pretty(&self.tokens)
+            .map_err(|error| format!("failed to serialize {}: {error}", self.path.display()))?;
+        with_storage_lock(&self.path, || write_string_atomically(&self.path, &data))
+    }
+
+    /// Resolves the token presented in `auth`; `None` when it is unknown or
+    /// revoked.
+    pub(crate) fn authenticate(&mut self, token: &str) -> Option<ClientGrant> {
+        let token_hash = hash_token(token);
+        let entry = self
+            .tokens
+            .iter_mut()
+            .find(|entry| entry.is_active() && entry.token_hash == token_hash)?;
+        entry.last_used_at = Some(now_millis());
+        let grant = ClientGrant::Scoped {
+            token_id: entry.id.clone(),
+        };
+        if let Err(error) = self.persist() {
+            eprintln!("[access-tokens] failed to record token use: {error}");
+        }
+        Some(grant)
+    }
+
+    fn find(&self, token_id: &str) -> Option<&ScopedAccessToken> {
+        self.tokens.iter().find(|entry| entry.id == token_id)
+    }
+
+    /// Checks `method` against the grant. Looked up on every call so a
+    /// revoked token stops working on connections that are already open.
+    pub(crate) fn authorize(&self, grant: &ClientGrant, method: &str) -> Result<(), String> {
+        let ClientGrant::Scoped { token_id } = grant else {
+            return Ok(());
+        };
+        let scope = required_scope(method);
+        match self.find(token_id) {
+            Some(entry) if entry.allows(scope) => Ok(()),
+            Some(entry) if entry.is_active() => Err(format!(
+                "permission denied: `{method}` requires the `{}` scope",
+                scope.as_str()
+            )),
+            _ => Err("token revoked".to_string()),
+        }
+    }
+
+    pub(crate) fn scopes_for(&self, grant: &ClientGrant) -> Vec<&'static str> {
+        match grant {
+            ClientGrant::Full => vec![
+                TokenScope::ReadOnly.as_str(),
+                TokenScope::GitWrite.as_str(),
+                TokenScope::EngineRun.as_str(),
+                TokenScope::SettingsAdmin.as_str(),
+            ],
+            ClientGrant::Scoped { token_id } => {
+                let mut scopes = vec![TokenScope::ReadOnly.as_str()];
+                if let Some(entry) = self.find(token_id) {
+                    scopes.extend(
+                        entry
+                            .scopes
+                            .iter()
+                            .filter(|scope| **scope != TokenScope::ReadOnly)
+                            .map(|scope| scope.as_str()),
+                    );
+                }
+                scopes
+            }
+        }
+    }
+
+    fn mint(
+        &mut self,
+        grant: &ClientGrant,
+        label: String,
+        scopes: BTreeSet<TokenScope>,
+    ) -> Result<Value, String> {
+        let label = label.trim().to_string();
+        if label.is_empty() {
+            return Err("token label is required".to_string());
+        }
+        if scopes.is_empty() {
+            return Err("at least one scope is required".to_string());
+        }
+        // A scoped admin cannot hand out more than it holds.
+        if let ClientGrant::Scoped { token_id } = grant {
+            let holder = self.find(token_id).ok_or("token revoked")?;
+            if let Some(scope) = scopes.iter().find(|scope| !holder.allows(**scope)) {
+                return Err(format!(
+                    "permission denied: cannot grant the `{}` scope",
+                    scope.as_str()
+                ));
+            }
+        }
+        let secret = format!(
+            "{MINTED_TOKEN_PREFIX}{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        );
+        let entry = ScopedAccessToken {
+            id: Uuid::new_v4().to_string(),
+            label,
+            scopes,
+            token_hash: hash_token(&secret),
+            created_at: now_millis(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        let summary = entry.summary();
+        self.tokens.push(entry);
+        if let Err(error) = self.persist() {
+            self.tokens.pop();
+            return Err(error);
+        }
+        Ok(json!({ "token": summary, "secret": secret }))
+    }
+
+    fn revoke(&mut self, token_id: &str) -> Result<bool, String> {
+        let Some(entry) = self
+            .tokens
+            .iter_mut()
+            .find(|entry| entry.id == token_id && entry.is_active())
+        else {
+            return Ok(false);
+        };
+        entry.revoked_at = Some(now_millis());
+        self.persist()?;
+        Ok(true)
+    }
+
+    /// Token management RPCs. They need the connection's grant, so they are
+    /// served here instead of in `handle_rpc_request`.
+    pub(crate) fn handle_management_rpc(
+        &mut self,
+        grant: &ClientGrant,
+        method: &str,
+        params: &Value,
+    ) -> Option<Result<Value, String>> {
+        let result = match method {
+            "list_access_tokens" => Ok(Value::Array(
+                self.tokens.iter().map(ScopedAccessToken::summary).collect(),
+            )),
+            "mint_access_token" => parse_string(params, "label").and_then(|label| {
+                let scopes = parse_string_array(params, "scopes")?
+                    .iter()
+                    .map(|scope| TokenScope::parse(scope))
+                    .collect::<Result<BTreeSet<_>, _>>()?;
+                self.mint(grant, label, scopes)
+            }),
+            "revoke_access_token" => parse_string(params, "id")
+                .and_then(|token_id| self.revoke(&token_id))
+                .map(|revoked| json!({ "revoked": revoked })),
+            _ => return None,
+        };
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> AccessTokenStore {
+        let dir = std::env::temp_dir().join(format!("cc-gui-access-tokens-{}", Uuid::new_v4()));
+        AccessTokenStore::load(&dir)
+    }
+
+    fn mint(store: &mut AccessTokenStore, grant: &ClientGrant, scopes: &[&str]) -> Value {
+        store
+            .handle_management_rpc(
+                grant,
+                "mint_access_token",
+                &json!({ "label": "ci", "scopes": scopes }),
+            )
+            .expect("management method")
+            .expect("mint token")
+    }
+
+    #[test]
+    fn scoped_tokens_are_enforced_per_method_and_revocable() {
+        let mut store = temp_store();
+        let minted = mint(&mut store, &ClientGrant::Full, &["git-write"]);
+        let secret = minted["secret"].as_str().expect("secret");
+        let token_id = minted["token"]["id"].as_str().expect("id").to_string();
+        assert!(secret.starts_with(MINTED_TOKEN_PREFIX));
+
+        let grant = store.authenticate(secret).expect("known token");
+        assert_eq!(store.authorize(&grant, "get_git_status"), Ok(()));
+        assert_eq!(store.authorize(&grant, "commit_git"), Ok(()));
+        assert!(store
+            .authorize(&grant, "engine_send_message")
+            .unwrap_err()
+            .contains("engine-run"));
+        assert!(store.authorize(&grant, "some_new_method").is_err());
+        assert!(store.authenticate("wrong").is_none());
+
+        let reloaded = AccessTokenStore::load(store.path.parent().expect("dir"));
+        assert_eq!(reloaded.tokens.len(), 1);
+        assert_ne!(reloaded.tokens[0].token_hash, secret);
+
+        store
+            .handle_management_rpc(
+                &ClientGrant::Full,
+                "revoke_access_token",
+                &json!({ "id": token_id }),
+            )
+            .expect("management method")
+            .expect("revoke");
+        assert_eq!(
+            store.authorize(&grant, "get_git_status"),
+            Err("token revoked".to_string())
+        );
+        assert!(store.authenticate(secret).is_none());
+        let _ = std::fs::remove_dir_all(store.path.parent().expect("dir"));
+    }
+
+    #[test]
+    fn scoped_admins_cannot_escalate_when_minting() {
+        let mut store = temp_store();
+        let minted = mint(&mut store, &ClientGrant::Full, &["settings-admin"]);
+        let admin = store
+            .authenticate(minted["secret"].as_str().expect("secret"))
+            .expect("known token");
+        assert_eq!(store.authorize(&admin, "mint_access_token"), Ok(()));
+
+        let narrower = store.handle_management_rpc(
+            &admin,
+            "mint_access_token",
+            &json!({ "label": "reader", "scopes": ["read-only"] }),
+        );
+        assert!(matches!(narrower, Some(Ok(_))));
+        let escalated = store.handle_management_rpc(
+            &admin,
+            "mint_access_token",
+            &json!({ "label": "runner", "scopes": ["engine-run"] }),
+        );
+        assert!(matches!(escalated, Some(Err(message)) if message.contains("engine-run")));
+        let invalid = store.handle_management_rpc(
+            &ClientGrant::Full,
+            "mint_access_token",
+            &json!({ "label": "x", "scopes": ["root"] }),
+        );
+        assert!(matches!(invalid, Some(Err(_))));
+        let _ = std::fs::remove_dir_all(store.path.parent().expect("dir"));
+    }
+}
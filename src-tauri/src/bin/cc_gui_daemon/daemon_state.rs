@@ -271,6 +271,7 @@ impl DaemonState {
             codex_login_cancels: Mutex::new(HashMap::new()),
             engine_manager: engine::EngineManager::new(),
             active_engine: Mutex::new(active_engine),
+            access_tokens: Mutex::new(AccessTokenStore::load(&config.data_dir)),
         }
     }
 
@@ -314,10 +315,33 @@ impl DaemonState {
         .await
     }
 
+    pub(super) async fn preview_worktree_branch_name(
+        &self,
+        parent_id: String,
+        naming: WorktreeNamingContext,
+        template: Option<String>,
+    ) -> Result<String, String> {
+        workspaces_core::preview_worktree_branch_name_core(
+            &parent_id,
+            template,
+            naming,
+            &self.data_dir,
+            &self.workspaces,
+            |value| worktree_core::sanitize_worktree_name(value),
+            |root, branch_name| {
+                let root = root.clone();
+                let branch_name = branch_name.to_string();
+                async move { git_core::git_branch_exists(&root, &branch_name).await }
+            },
+        )
+        .await
+    }
+
     pub(super) async fn add_worktree(
         &self,
         parent_id: String,
         branch: String,
+        naming: Option<WorktreeNamingContext>,
         base_ref: Option<String>,
         publish_to_origin: bool,
         client_version: String,
@@ -326,6 +350,7 @@ impl DaemonState {
         workspaces_core::add_worktree_core(
             parent_id,
             branch,
+            naming,
             base_ref,
             publish_to_origin,
             &self.data_dir,
@@ -804,6 +829,18 @@ impl DaemonState {
                 },
             )
             .await;
+        self.engine_manager
+            .claude_manager
+            .set_warm_pool_config(engine::claude::ClaudeWarmPoolConfig::from(
+                &settings.engine_warm_pool,
+            ))
+            .await;
+        self.engine_manager
+            .claude_manager
+            .set_user_input_timeout(std::time::Duration::from_secs(
+                settings.claude_question_timeout_secs,
+            ))
+            .await;
         self.engine_manager
             .set_engine_config(
                 engine::EngineType::Codex,
@@ -925,6 +962,21 @@ impl DaemonState {
             .ok_or_else(|| "Workspace not found".to_string())
     }
 
+    async fn resolve_turn_cwd(
+        &self,
+        workspace_id: &str,
+        cwd: Option<&str>,
+    ) -> Result<Option<String>, String> {
+        if cwd.is_none_or(|value| value.trim().is_empty()) {
+            return Ok(None);
+        }
+        let workspace_root = self.workspace_path_for_engine(workspace_id).await?;
+        Ok(
+            shared::workspaces_core::resolve_turn_cwd_core(&workspace_root, cwd)?
+                .map(|path| path.to_string_lossy().to_string()),
+        )
+    }
+
     pub(super) async fn engine_send_message(
         &self,
         workspace_id: String,
@@ -942,6 +994,8 @@ impl DaemonState {
         agent: Option<String>,
         variant: Option<String>,
         custom_spec_root: Option<String>,
+        cwd: Option<String>,
+        timeout_secs: Option<u64>,
     ) -> Result<Value, String> {
         self.sync_engine_configs().await;
         let active_engine = self.get_active_engine().await;
@@ -953,8 +1007,12 @@ impl DaemonState {
                 .to_string());
         }
         let normalized_custom_spec_root = normalize_custom_spec_root(custom_spec_root);
+        let turn_cwd = self.resolve_turn_cwd(&workspace_id, cwd.as_deref()).await?;
 
         match effective_engine {
+            engine::EngineType::Codex if turn_cwd.is_some() => {
+                Err("Codex does not support a per-turn working directory".to_string())
+            }
             engine::EngineType::Codex => {
                 let target_thread_id = thread_id.ok_or_else(|| {
                     "threadId is required for codex engine_send_message".to_string()
@@ -1002,6 +1060,14 @@ impl DaemonState {
                 } else {
                     Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
                 };
+                let continued_session_id = normalized_fork_session_id
+                    .clone()
+                    .or_else(|| resolved_session_id.clone().filter(|_| continue_session));
+                let turn_cwd = engine::turn_cwds::resolve_turn_cwd(
+                    &workspace_id,
+                    continued_session_id.as_deref(),
+                    turn_cwd,
+                )?;
 
                 let sanitized_model = model
                     .as_ref()
@@ -1048,10 +1114,19 @@ impl DaemonState {
                     variant: None,
                     collaboration_mode: None,
                     custom_spec_root: normalized_custom_spec_root.clone(),
+                    cwd: turn_cwd.clone(),
+                    timeout_secs,
                 };
 
                 let turn_id = format!("claude-turn-{}", uuid::Uuid::new_v4());
                 let thread_id = thread_id.unwrap_or_else(|| turn_id.clone());
+                engine::turn_cwds::record_turn_cwd(
+                    &workspace_id,
+                    engine::EngineType::Claude,
+                    &turn_id,
+                    response_session_id.as_deref(),
+                    turn_cwd.as_deref(),
+                );
                 let assistant_item_id = format!("claude-item-{}", uuid::Uuid::new_v4());
                 let reasoning_item_id = format!("claude-reasoning-{}", uuid::Uuid::new_v4());
 
@@ -1061,28 +1136,39 @@ impl DaemonState {
                 let assistant_item_id_clone = assistant_item_id.clone();
                 let reasoning_item_id_clone = reasoning_item_id.clone();
                 let turn_id_for_forwarder = turn_id.clone();
+                let workspace_id_for_forwarder = workspace_id.clone();
+                let forwarder = self.engine_manager.forwarders.register(
+                    engine::EngineType::Claude,
+                    &workspace_id,
+                    &turn_id,
+                );
                 let mut accumulated_agent_text = String::new();
                 tokio::spawn(async move {
-                    let deadline = tokio::time::Instant::now()
-                        + std::time::Duration::from_secs(EVENT_FORWARDER_TIMEOUT_SECS);
                     let mut post_completion_grace_deadline: Option<tokio::time::Instant> = None;
                     loop {
-                        let active_deadline = post_completion_grace_deadline
-                            .map(|grace| if grace < deadline { grace } else { deadline })
-                            .unwrap_or(deadline);
-                        let recv_result =
-                            tokio::time::timeout_at(active_deadline, receiver.recv()).await;
-                        let turn_event = match recv_result {
-                            Ok(Ok(event)) => event,
-                            Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) => break,
-                            Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(_))) => {
-                                continue;
+                        let turn_event = match forwarder
+                            .recv(&mut receiver, post_completion_grace_deadline)
+                            .await
+                        {
+                            engine::forwarder_registry::ForwarderRecv::Event(event) => event,
+                            engine::forwarder_registry::ForwarderRecv::Closed
+                            | engine::forwarder_registry::ForwarderRecv::GraceElapsed => break,
+                            engine::forwarder_registry::ForwarderRecv::Lagged(_) => continue,
+                            engine::forwarder_registry::ForwarderRecv::Reaped(reason) => {
+                                if post_completion_grace_deadline.is_none() {
+                                    event_sink.emit_app_server_event(forwarder.reaped_event(
+                                        &workspace_id_for_forwarder,
+                                        &current_thread_id,
+                                        reason,
+                                    ));
+                                }
+                                break;
                             }
-                            Err(_) => break,
                         };
                         if turn_event.turn_id != turn_id_for_forwarder {
                             continue;
                         }
+                        forwarder.record_event();
 
                         let is_post_completion_context_usage = post_completion_grace_deadline
                             .is_some()
@@ -1141,6 +1227,7 @@ impl DaemonState {
                                 Some(&turn_id_for_forwarder),
                             )
                         {
+                            engine::turn_cwds::observe_app_server_event(&payload);
                             event_sink.emit_app_server_event(payload);
                         }
 
@@ -1184,8 +1271,32 @@ impl DaemonState {
                     }
                 });
 
+                if let Some(timeout_secs) = timeout_secs.filter(|secs| *secs > 0) {
+                    let session_for_timeout = session.clone();
+                    let turn_for_timeout = turn_id.clone();
+                    self.engine_manager.arm_turn_timeout(
+                        &workspace_id,
+                        &turn_id,
+                        Duration::from_secs(timeout_secs),
+                        async move {
+                            eprintln!(
+                                "[engine_send_message] turn {turn_for_timeout} timed out after {timeout_secs}s"
+                            );
+                            session_for_timeout.emit_turn_timeout(&turn_for_timeout, timeout_secs);
+                            if let Err(error) =
+                                session_for_timeout.interrupt_turn(&turn_for_timeout).await
+                            {
+                                eprintln!(
+                                    "[engine_send_message] failed to interrupt timed out turn {turn_for_timeout}: {error}"
+                                );
+                            }
+                        },
+                    );
+                }
                 let session_clone = session.clone();
                 let turn_id_clone = turn_id.clone();
+                let forwarders = self.engine_manager.forwarders.clone();
+                let turn_timeouts = self.engine_manager.turn_timeouts.clone();
                 tokio::spawn(async move {
                     let send_result = if has_images {
                         session_clone.send_message(params, &turn_id_clone).await
@@ -1194,6 +1305,8 @@ impl DaemonState {
                             .send_message_with_auto_compact_retry(params, &turn_id_clone)
                             .await
                     };
+                    turn_timeouts.disarm(&turn_id_clone);
+                    forwarders.mark_process_exited(&turn_id_clone);
                     if let Err(error) = send_result {
                         eprintln!("Claude send_message failed: {error}");
                     }
@@ -1208,12 +1321,14 @@ impl DaemonState {
                         "turn": {
                             "id": turn_id,
                             "status": "started",
+                            "cwd": turn_cwd,
                         }
                     },
                     "modelResolution": model_resolution,
                     "turn": {
                         "id": turn_id,
                         "status": "started",
+                        "cwd": turn_cwd,
                     }
                 }))
             }
@@ -1232,6 +1347,12 @@ impl DaemonState {
                 } else {
                     Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
                 };
+                let continued_session_id = resolved_session_id.clone().filter(|_| continue_session);
+                let turn_cwd = engine::turn_cwds::resolve_turn_cwd(
+                    &workspace_id,
+                    continued_session_id.as_deref(),
+                    turn_cwd,
+                )?;
                 let sanitized_model = model
                     .as_ref()
                     .map(|value| value.trim())
@@ -1265,10 +1386,19 @@ impl DaemonState {
                     variant,
                     collaboration_mode: None,
                     custom_spec_root: normalized_custom_spec_root.clone(),
+                    cwd: turn_cwd.clone(),
+                    timeout_secs,
                 };
 
                 let turn_id = format!("opencode-turn-{}", uuid::Uuid::new_v4());
                 let thread_id = thread_id.unwrap_or_else(|| turn_id.clone());
+                engine::turn_cwds::record_turn_cwd(
+                    &workspace_id,
+                    engine::EngineType::OpenCode,
+                    &turn_id,
+                    continued_session_id.as_deref(),
+                    turn_cwd.as_deref(),
+                );
                 let item_id = format!("opencode-item-{}", uuid::Uuid::new_v4());
 
                 let mut receiver = session.subscribe();
@@ -1276,22 +1406,32 @@ impl DaemonState {
                 let mut current_thread_id = thread_id.clone();
                 let item_id_clone = item_id.clone();
                 let turn_id_for_forwarder = turn_id.clone();
+                let workspace_id_for_forwarder = workspace_id.clone();
+                let forwarder = self.engine_manager.forwarders.register(
+                    engine::EngineType::OpenCode,
+                    &workspace_id,
+                    &turn_id,
+                );
                 tokio::spawn(async move {
-                    let deadline = tokio::time::Instant::now()
-                        + std::time::Duration::from_secs(EVENT_FORWARDER_TIMEOUT_SECS);
                     loop {
-                        let recv_result = tokio::time::timeout_at(deadline, receiver.recv()).await;
-                        let turn_event = match recv_result {
-                            Ok(Ok(event)) => event,
-                            Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) => break,
-                            Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(_))) => {
-                                continue;
+                        let turn_event = match forwarder.recv(&mut receiver, None).await {
+                            engine::forwarder_registry::ForwarderRecv::Event(event) => event,
+                            engine::forwarder_registry::ForwarderRecv::Closed
+                            | engine::forwarder_registry::ForwarderRecv::GraceElapsed => break,
+                            engine::forwarder_registry::ForwarderRecv::Lagged(_) => continue,
+                            engine::forwarder_registry::ForwarderRecv::Reaped(reason) => {
+                                event_sink.emit_app_server_event(forwarder.reaped_event(
+                                    &workspace_id_for_forwarder,
+                                    &current_thread_id,
+                                    reason,
+                                ));
+                                break;
                             }
-                            Err(_) => break,
                         };
                         if turn_event.turn_id != turn_id_for_forwarder {
                             continue;
                         }
+                        forwarder.record_event();
 
                         let event = turn_event.event;
                         let is_terminal = event.is_terminal();
@@ -1304,6 +1444,7 @@ impl DaemonState {
                                 Some(&turn_id_for_forwarder),
                             )
                         {
+                            engine::turn_cwds::observe_app_server_event(&payload);
                             event_sink.emit_app_server_event(payload);
                         }
 
@@ -1327,10 +1468,37 @@ impl DaemonState {
                     }
                 });
 
+                if let Some(timeout_secs) = timeout_secs.filter(|secs| *secs > 0) {
+                    let session_for_timeout = session.clone();
+                    let turn_for_timeout = turn_id.clone();
+                    self.engine_manager.arm_turn_timeout(
+                        &workspace_id,
+                        &turn_id,
+                        Duration::from_secs(timeout_secs),
+                        async move {
+                            eprintln!(
+                                "[engine_send_message] turn {turn_for_timeout} timed out after {timeout_secs}s"
+                            );
+                            session_for_timeout.emit_turn_timeout(&turn_for_timeout, timeout_secs);
+                            if let Err(error) =
+                                session_for_timeout.interrupt_turn(&turn_for_timeout).await
+                            {
+                                eprintln!(
+                                    "[engine_send_message] failed to interrupt timed out turn {turn_for_timeout}: {error}"
+                                );
+                            }
+                        },
+                    );
+                }
                 let session_clone = session.clone();
                 let turn_id_clone = turn_id.clone();
+                let forwarders = self.engine_manager.forwarders.clone();
+                let turn_timeouts = self.engine_manager.turn_timeouts.clone();
                 tokio::spawn(async move {
-                    if let Err(error) = session_clone.send_message(params, &turn_id_clone).await {
+                    let send_result = session_clone.send_message(params, &turn_id_clone).await;
+                    turn_timeouts.disarm(&turn_id_clone);
+                    forwarders.mark_process_exited(&turn_id_clone);
+                    if let Err(error) = send_result {
                         eprintln!("OpenCode send_message failed: {error}");
                         session_clone.emit_error(&turn_id_clone, error);
                     }
@@ -1342,11 +1510,13 @@ impl DaemonState {
                         "turn": {
                             "id": turn_id,
                             "status": "started",
+                            "cwd": turn_cwd,
                         }
                     },
                     "turn": {
                         "id": turn_id,
                         "status": "started",
+                        "cwd": turn_cwd,
                     }
                 }))
             }
@@ -1365,6 +1535,12 @@ impl DaemonState {
                 } else {
                     Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
                 };
+                let continued_session_id = resolved_session_id.clone().filter(|_| continue_session);
+                let turn_cwd = engine::turn_cwds::resolve_turn_cwd(
+                    &workspace_id,
+                    continued_session_id.as_deref(),
+                    turn_cwd,
+                )?;
                 let sanitized_model = model
                     .as_ref()
                     .map(|value| value.trim())
@@ -1397,10 +1573,19 @@ impl DaemonState {
                     variant: None,
                     collaboration_mode: None,
                     custom_spec_root: normalized_custom_spec_root.clone(),
+                    cwd: turn_cwd.clone(),
+                    timeout_secs,
                 };
 
                 let turn_id = format!("gemini-turn-{}", uuid::Uuid::new_v4());
                 let thread_id = thread_id.unwrap_or_else(|| turn_id.clone());
+                engine::turn_cwds::record_turn_cwd(
+                    &workspace_id,
+                    engine::EngineType::Gemini,
+                    &turn_id,
+                    continued_session_id.as_deref(),
+                    turn_cwd.as_deref(),
+                );
                 let item_id = format!("gemini-item-{}", uuid::Uuid::new_v4());
 
                 let mut receiver = session.subscribe();
@@ -1408,29 +1593,40 @@ impl DaemonState {
                 let mut current_thread_id = thread_id.clone();
                 let item_id_clone = item_id.clone();
                 let turn_id_for_forwarder = turn_id.clone();
+                let workspace_id_for_forwarder = workspace_id.clone();
+                let forwarder = self.engine_manager.forwarders.register(
+                    engine::EngineType::Gemini,
+                    &workspace_id,
+                    &turn_id,
+                );
                 let mut accumulated_agent_text = String::new();
                 tokio::spawn(async move {
-                    let deadline = tokio::time::Instant::now()
-                        + std::time::Duration::from_secs(EVENT_FORWARDER_TIMEOUT_SECS);
                     let mut render_state = GeminiRenderRoutingState::default();
                     let mut post_completion_grace_deadline: Option<tokio::time::Instant> = None;
                     loop {
-                        let active_deadline = post_completion_grace_deadline
-                            .map(|grace| if grace < deadline { grace } else { deadline })
-                            .unwrap_or(deadline);
-                        let recv_result =
-                            tokio::time::timeout_at(active_deadline, receiver.recv()).await;
-                        let turn_event = match recv_result {
-                            Ok(Ok(event)) => event,
-                            Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) => break,
-                            Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(_))) => {
-                                continue;
+                        let turn_event = match forwarder
+                            .recv(&mut receiver, post_completion_grace_deadline)
+                            .await
+                        {
+                            engine::forwarder_registry::ForwarderRecv::Event(event) => event,
+                            engine::forwarder_registry::ForwarderRecv::Closed
+                            | engine::forwarder_registry::ForwarderRecv::GraceElapsed => break,
+                            engine::forwarder_registry::ForwarderRecv::Lagged(_) => continue,
+                            engine::forwarder_registry::ForwarderRecv::Reaped(reason) => {
+                                if post_completion_grace_deadline.is_none() {
+                                    event_sink.emit_app_server_event(forwarder.reaped_event(
+                                        &workspace_id_for_forwarder,
+                                        &current_thread_id,
+                                        reason,
+                                    ));
+                                }
+                                break;
                             }
-                            Err(_) => break,
                         };
                         if turn_event.turn_id != turn_id_for_forwarder {
                             continue;
                         }
+                        forwarder.record_event();
 
                         let event = turn_event.event;
                         let is_terminal = event.is_terminal();
@@ -1493,6 +1689,7 @@ impl DaemonState {
                                 Some(&turn_id_for_forwarder),
                             )
                         {
+                            engine::turn_cwds::observe_app_server_event(&payload);
                             event_sink.emit_app_server_event(payload);
                         }
 
@@ -1525,10 +1722,37 @@ impl DaemonState {
                     }
                 });
 
+                if let Some(timeout_secs) = timeout_secs.filter(|secs| *secs > 0) {
+                    let session_for_timeout = session.clone();
+                    let turn_for_timeout = turn_id.clone();
+                    self.engine_manager.arm_turn_timeout(
+                        &workspace_id,
+                        &turn_id,
+                        Duration::from_secs(timeout_secs),
+                        async move {
+                            eprintln!(
+                                "[engine_send_message] turn {turn_for_timeout} timed out after {timeout_secs}s"
+                            );
+                            session_for_timeout.emit_turn_timeout(&turn_for_timeout, timeout_secs);
+                            if let Err(error) =
+                                session_for_timeout.interrupt_turn(&turn_for_timeout).await
+                            {
+                                eprintln!(
+                                    "[engine_send_message] failed to interrupt timed out turn {turn_for_timeout}: {error}"
+                                );
+                            }
+                        },
+                    );
+                }
                 let session_clone = session.clone();
                 let turn_id_clone = turn_id.clone();
+                let forwarders = self.engine_manager.forwarders.clone();
+                let turn_timeouts = self.engine_manager.turn_timeouts.clone();
                 tokio::spawn(async move {
-                    if let Err(error) = session_clone.send_message(params, &turn_id_clone).await {
+                    let send_result = session_clone.send_message(params, &turn_id_clone).await;
+                    turn_timeouts.disarm(&turn_id_clone);
+                    forwarders.mark_process_exited(&turn_id_clone);
+                    if let Err(error) = send_result {
                         eprintln!("Gemini send_message failed: {error}");
                     }
                 });
@@ -1539,11 +1763,13 @@ impl DaemonState {
                         "turn": {
                             "id": turn_id,
                             "status": "started",
+                            "cwd": turn_cwd,
                         }
                     },
                     "turn": {
                         "id": turn_id,
                         "status": "started",
+                        "cwd": turn_cwd,
                     }
                 }))
             }
@@ -1567,6 +1793,7 @@ impl DaemonState {
         agent: Option<String>,
         variant: Option<String>,
         custom_spec_root: Option<String>,
+        cwd: Option<String>,
     ) -> Result<Value, String> {
         self.sync_engine_configs().await;
         if text.trim().is_empty() {
@@ -1575,6 +1802,7 @@ impl DaemonState {
         let active_engine = self.get_active_engine().await;
         let effective_engine = engine.unwrap_or(active_engine);
         let normalized_custom_spec_root = normalize_custom_spec_root(custom_spec_root);
+        let turn_cwd = self.resolve_turn_cwd(&workspace_id, cwd.as_deref()).await?;
 
         match effective_engine {
             engine::EngineType::Codex => Err(
@@ -1607,6 +1835,14 @@ impl DaemonState {
                 } else {
                     None
                 };
+                let continued_session_id = normalized_fork_session_id
+                    .clone()
+                    .or_else(|| resolved_session_id.clone().filter(|_| continue_session));
+                let turn_cwd = engine::turn_cwds::resolve_turn_cwd(
+                    &workspace_id,
+                    continued_session_id.as_deref(),
+                    turn_cwd,
+                )?;
                 let sanitized_model = model
                     .as_ref()
                     .map(|value| value.trim())
@@ -1633,6 +1869,8 @@ impl DaemonState {
                     variant: None,
                     collaboration_mode: None,
                     custom_spec_root: normalized_custom_spec_root.clone(),
+                    cwd: turn_cwd.clone(),
+                    timeout_secs: None,
                 };
                 let turn_id = format!("claude-sync-{}", uuid::Uuid::new_v4());
                 let response = tokio::time::timeout(std::time::Duration::from_secs(900), async {
@@ -1667,6 +1905,12 @@ impl DaemonState {
                 } else {
                     Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
                 };
+                let continued_session_id = resolved_session_id.clone().filter(|_| continue_session);
+                let turn_cwd = engine::turn_cwds::resolve_turn_cwd(
+                    &workspace_id,
+                    continued_session_id.as_deref(),
+                    turn_cwd,
+                )?;
                 let sanitized_model = model
                     .as_ref()
                     .map(|value| value.trim())
@@ -1694,6 +1938,8 @@ impl DaemonState {
                     variant,
                     collaboration_mode: None,
                     custom_spec_root: normalized_custom_spec_root.clone(),
+                    cwd: turn_cwd.clone(),
+                    timeout_secs: None,
                 };
                 let turn_id = format!("opencode-sync-{}", uuid::Uuid::new_v4());
                 let response = tokio::time::timeout(
@@ -1722,6 +1968,12 @@ impl DaemonState {
                 } else {
                     Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
                 };
+                let continued_session_id = resolved_session_id.clone().filter(|_| continue_session);
+                let turn_cwd = engine::turn_cwds::resolve_turn_cwd(
+                    &workspace_id,
+                    continued_session_id.as_deref(),
+                    turn_cwd,
+                )?;
                 let sanitized_model = model
                     .as_ref()
                     .map(|value| value.trim())
@@ -1747,6 +1999,8 @@ impl DaemonState {
                     variant: None,
                     collaboration_mode: None,
                     custom_spec_root: normalized_custom_spec_root.clone(),
+                    cwd: turn_cwd.clone(),
+                    timeout_secs: None,
                 };
                 let turn_id = format!("gemini-sync-{}", uuid::Uuid::new_v4());
                 let response = tokio::time::timeout(
@@ -1765,6 +2019,8 @@ impl DaemonState {
 
     pub(super) async fn engine_interrupt(&self, workspace_id: String) -> Result<(), String> {
         self.sync_engine_configs().await;
+        self.engine_manager
+            .disarm_workspace_turn_timeouts(&workspace_id);
         let active_engine = self.get_active_engine().await;
         match active_engine {
             engine::EngineType::Claude => {
@@ -1805,6 +2061,7 @@ impl DaemonState {
         engine: Option<engine::EngineType>,
     ) -> Result<(), String> {
         self.sync_engine_configs().await;
+        self.engine_manager.disarm_turn_timeout(&turn_id);
         let active_engine = self.get_active_engine().await;
         let target_engine = engine.unwrap_or(active_engine);
         match target_engine {
@@ -2164,6 +2421,50 @@ impl DaemonState {
         .await
     }
 
+    /// Session history for a workspace, mirroring the app's
+    /// `engine_list_sessions`.
+    pub(super) async fn engine_list_sessions(
+        &self,
+        workspace_id: String,
+        engine: Option<engine::EngineType>,
+        limit: Option<usize>,
+    ) -> Result<Value, String> {
+        self.sync_engine_configs().await;
+        let target_engine = match engine {
+            Some(engine) => engine,
+            None => self.get_active_engine().await,
+        };
+        let settings = self.app_settings.lock().await.clone();
+        if !engine::engine_enabled_in_settings(&settings, target_engine) {
+            return Err(engine::engine_disabled_diagnostic(target_engine)
+                .unwrap_or("Engine is disabled in CLI validation settings")
+                .to_string());
+        }
+        match target_engine {
+            engine::EngineType::Claude => {
+                let workspace_path = self.workspace_path_for_engine(&workspace_id).await?;
+                self.list_claude_sessions(workspace_path.to_string_lossy().to_string(), limit)
+                    .await
+            }
+            engine::EngineType::Gemini => {
+                let workspace_path = self.workspace_path_for_engine(&workspace_id).await?;
+                self.list_gemini_sessions(workspace_path.to_string_lossy().to_string(), limit)
+                    .await
+            }
+            engine::EngineType::OpenCode => {
+                let mut sessions = self.opencode_session_list(workspace_id).await?;
+                if let Some(limit) = limit {
+                    sessions.truncate(limit);
+                }
+                serde_json::to_value(sessions).map_err(|error| error.to_string())
+            }
+            engine::EngineType::Codex => Err(
+                "engine_list_sessions for codex is not supported in daemon mode; use list_threads"
+                    .to_string(),
+            ),
+        }
+    }
+
     pub(super) async fn list_gemini_sessions(
         &self,
         workspace_path: String,
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+#[allow(dead_code)]
+#[path = "../../engine/adapter.rs"]
+pub(crate) mod adapter;
 #[allow(dead_code)]
 #[path = "../../engine/claude.rs"]
 pub mod claude;
@@ -17,6 +20,9 @@ pub(crate) mod claude_history_subagents;
 pub(crate) mod claude_message_content;
 #[path = "../../engine/events.rs"]
 pub mod events;
+#[allow(dead_code)]
+#[path = "../../engine/forwarder_registry.rs"]
+pub(crate) mod forwarder_registry;
 #[path = "../../engine/gemini.rs"]
 pub mod gemini;
 #[path = "../../engine/gemini_history.rs"]
@@ -31,6 +37,9 @@ pub mod opencode;
 #[allow(dead_code)]
 #[path = "../../engine/status.rs"]
 pub mod status;
+#[allow(dead_code)]
+#[path = "../../engine/turn_cwds.rs"]
+pub(crate) mod turn_cwds;
 
 pub use manager::EngineManager;
 
@@ -485,6 +494,8 @@ pub struct EngineStatus {
     pub default_model: Option<String>,
     pub features: EngineFeatures,
     pub error: Option<String>,
+    #[serde(default)]
+    pub detecting: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -633,6 +644,7 @@ pub(crate) fn disabled_engine_status(engine_type: EngineType) -> EngineStatus {
         default_model: None,
         features,
         error: engine_disabled_diagnostic(engine_type).map(str::to_string),
+        detecting: false,
     }
 }
 
@@ -652,6 +664,10 @@ pub struct SendMessageParams {
     pub variant: Option<String>,
     pub collaboration_mode: Option<Value>,
     pub custom_spec_root: Option<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
 }
 
 impl Default for SendMessageParams {
@@ -670,6 +686,8 @@ impl Default for SendMessageParams {
             variant: None,
             collaboration_mode: None,
             custom_spec_root: None,
+            cwd: None,
+            timeout_secs: None,
         }
     }
 }
@@ -530,6 +530,7 @@ fn parse_pr_diff_text(diff_text: &str) -> Vec<GitHubPullRequestDiff> {
             path: normalize_git_path(path),
             status: status.to_string(),
             diff,
+            truncated: false,
         });
         chunks.clear();
     };
@@ -699,6 +700,7 @@ impl DaemonState {
                     status: stage.to_string(),
                     additions: 0,
                     deletions: 0,
+                    submodule: None,
                 });
             }
             if let Some(stage) = workdir_status {
@@ -707,6 +709,7 @@ impl DaemonState {
                     status: stage.to_string(),
                     additions: 0,
                     deletions: 0,
+                    submodule: None,
                 });
             }
             if index_status.is_some() || workdir_status.is_some() {
@@ -715,6 +718,7 @@ impl DaemonState {
                     status: workdir_status.or(index_status).unwrap_or("--").to_string(),
                     additions: 0,
                     deletions: 0,
+                    submodule: None,
                 });
             }
         }
@@ -763,20 +767,27 @@ impl DaemonState {
                 let patch =
                     git2::Patch::from_diff(&diff, index).map_err(|error| error.to_string())?;
                 if let Some(mut patch) = patch {
-                    let content =
-                        crate::git_utils::diff_patch_to_string(&mut patch).unwrap_or_default();
-                    if content.trim().is_empty() {
+                    let Ok(decoded) = crate::git_utils::diff_patch_to_text(&mut patch) else {
+                        continue;
+                    };
+                    if decoded.text.trim().is_empty() {
                         continue;
                     }
                     results.push(GitFileDiff {
                         path: normalized_path,
-                        diff: content,
+                        diff: decoded.text,
                         is_binary: false,
                         is_image: false,
                         old_image_data: None,
                         new_image_data: None,
                         old_image_mime: None,
                         new_image_mime: None,
+                        summary_only: false,
+                        summary_reason: None,
+                        size_bytes: None,
+                        encoding: (decoded.encoding != "UTF-8")
+                            .then(|| decoded.encoding.to_string()),
+                        encoding_fallback: decoded.fallback,
                     });
                 } else {
                     results.push(GitFileDiff {
@@ -788,6 +799,11 @@ impl DaemonState {
                         new_image_data: None,
                         old_image_mime: None,
                         new_image_mime: None,
+                        summary_only: false,
+                        summary_reason: None,
+                        size_bytes: None,
+                        encoding: None,
+                        encoding_fallback: false,
                     });
                 }
             }
@@ -977,6 +993,7 @@ impl DaemonState {
         Ok(GitHistoryResponse {
             snapshot_id,
             total,
+            total_is_estimated: false,
             offset,
             limit,
             has_more,
@@ -1603,6 +1620,7 @@ impl DaemonState {
                 ahead,
                 behind,
                 upstream,
+                worktree_path: None,
             });
         }
         branches.sort_by(|left, right| right.last_commit.cmp(&left.last_commit));
@@ -1636,6 +1654,7 @@ impl DaemonState {
                 ahead: 0,
                 behind: 0,
                 upstream: None,
+                worktree_path: None,
             });
         }
         remote_branches.sort_by(|left, right| left.name.cmp(&right.name));
@@ -1932,6 +1951,9 @@ impl DaemonState {
             title,
             body,
             comment_body,
+            labels: Vec::new(),
+            reviewers: Vec::new(),
+            draft: false,
             can_create: disabled_reason.is_none(),
             disabled_reason,
         })
@@ -1949,6 +1971,9 @@ impl DaemonState {
         body: Option<String>,
         comment_after_create: Option<bool>,
         comment_body: Option<String>,
+        draft: Option<bool>,
+        labels: Option<Vec<String>>,
+        reviewers: Option<Vec<String>>,
     ) -> Result<GitPrWorkflowResult, String> {
         let repo_root = self.git_repo_root(&workspace_id).await?;
         let upstream_repo = upstream_repo.trim().to_string();
@@ -1985,6 +2010,24 @@ impl DaemonState {
             args.push("--body".to_string());
             args.push(body_text.clone());
         }
+        if draft.unwrap_or(false) {
+            args.push("--draft".to_string());
+        }
+        for (flag, values) in [("--label", labels), ("--reviewer", reviewers)] {
+            for value in values.unwrap_or_default() {
+                let value = value.trim();
+                if value.is_empty() {
+                    continue;
+                }
+                args.push(flag.to_string());
+                // gh splits these flags as CSV.
+                if value.contains(',') || value.contains('"') {
+                    args.push(format!("\"{}\"", value.replace('"', "\"\"")));
+                } else {
+                    args.push(value.to_string());
+                }
+            }
+        }
         let output = crate::utils::async_command("gh")
             .args(args.iter().map(String::as_str))
             .current_dir(&repo_root)
@@ -0,0 +1,310 @@
+//! `mossx` companion CLI. Talks to the running app through the loopback
+//! socket announced in `~/.ccgui/cli-bridge.json`, launching the app first
+//! when it is not running.
+
+#[allow(dead_code)]
+#[path = "../app_paths.rs"]
+mod app_paths;
+
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+const DISCOVERY_FILE: &str = "cli-bridge.json";
+const APP_BUNDLE_ID: &str = "com.zhukunpenglinyutong.ccgui";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(30);
+const LAUNCH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CliBridgeDiscovery {
+    port: u16,
+    token: String,
+}
+
+enum CliCommand {
+    Status,
+    Open {
+        path: PathBuf,
+    },
+    Send {
+        prompt: String,
+        workspace_id: Option<String>,
+        engine: Option<String>,
+        model: Option<String>,
+    },
+}
+
+struct CliOptions {
+    command: CliCommand,
+    json: bool,
+    launch: bool,
+}
+
+fn usage() -> &'static str {
+    "\
+USAGE:\n  mossx status [--json]\n  mossx open [path]\n  mossx send [--workspace <id>] [--engine <claude|codex|gemini|opencode>] [--model <model>] <prompt>\n\n\
+OPTIONS:\n  --json         Print the raw JSON response\n  --no-launch    Fail instead of starting the app when it is not running\n  -h, --help     Show this help\n\n\
+`send` runs in the workspace containing the current directory unless --workspace is given.\n"
+}
+
+fn parse_args(args: Vec<String>) -> Result<CliOptions, String> {
+    let mut args = args.into_iter();
+    let subcommand = args.next().ok_or("missing command")?;
+    let mut json = false;
+    let mut launch = true;
+    let mut workspace_id = None;
+    let mut engine = None;
+    let mut model = None;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--no-launch" => launch = false,
+            "--workspace" => {
+                workspace_id = Some(args.next().ok_or("--workspace requires a value")?)
+            }
+            "--engine" => engine = Some(args.next().ok_or("--engine requires a value")?),
+            "--model" => model = Some(args.next().ok_or("--model requires a value")?),
+            "--" => positional.extend(args.by_ref()),
+            _ if arg.starts_with("--") => return Err(format!("unknown option: {arg}")),
+            _ => positional.push(arg),
+        }
+    }
+
+    let command = match subcommand.as_str() {
+        "status" => CliCommand::Status,
+        "open" => CliCommand::Open {
+            path: positional
+                .first()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(".")),
+        },
+        "send" => {
+            let prompt = positional.join(" ");
+            if prompt.trim().is_empty() {
+                return Err("send requires a prompt".to_string());
+            }
+            CliCommand::Send {
+                prompt,
+                workspace_id,
+                engine,
+                model,
+            }
+        }
+        other => return Err(format!("unknown command: {other}")),
+    };
+    Ok(CliOptions {
+        command,
+        json,
+        launch,
+    })
+}
+
+struct BridgeClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    next_id: u64,
+}
+
+impl BridgeClient {
+    fn connect(discovery: &CliBridgeDiscovery) -> Result<Self, String> {
+        let address = SocketAddr::from(([127, 0, 0, 1], discovery.port));
+        let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+            .map_err(|error| error.to_string())?;
+        let writer = stream.try_clone().map_err(|error| error.to_string())?;
+        let mut client = Self {
+            reader: BufReader::new(stream),
+            writer,
+            next_id: 1,
+        };
+        client.request("auth", json!({ "token": discovery.token }))?;
+        Ok(client)
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut payload = json!({ "id": id, "method": method, "params": params }).to_string();
+        payload.push('\n');
+        self.writer
+            .write_all(payload.as_bytes())
+            .map_err(|error| error.to_string())?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = self
+                .reader
+                .read_line(&mut line)
+                .map_err(|error| error.to_string())?;
+            if read == 0 {
+                return Err("connection closed by the app".to_string());
+            }
+            let Ok(message) = serde_json::from_str::<Value>(line.trim()) else {
+                continue;
+            };
+            if message.get("id").and_then(Value::as_u64) != Some(id) {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                return Err(error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("request failed")
+                    .to_string());
+            }
+            return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+        }
+    }
+}
+
+fn read_discovery() -> Option<CliBridgeDiscovery> {
+    let path = app_paths::app_home_dir().ok()?.join(DISCOVERY_FILE);
+    let raw = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn try_connect() -> Option<BridgeClient> {
+    BridgeClient::connect(&read_discovery()?).ok()
+}
+
+/// Starts the desktop app: the `cc-gui` binary next to this one when present,
+/// otherwise the installed app bundle on macOS.
+fn launch_app() -> Result<(), String> {
+    let sibling = env::current_exe()
+        .ok()
+        .map(|exe| exe.with_file_name(format!("cc-gui{}", env::consts::EXE_SUFFIX)))
+        .filter(|path| path.is_file());
+    let mut command = match sibling {
+        Some(path) => Command::new(path),
+        None if cfg!(target_os = "macos") => {
+            let mut command = Command::new("open");
+            command.args(["-g", "-b", APP_BUNDLE_ID]);
+            command
+        }
+        None => return Err("could not find the ccgui app next to mossx".to_string()),
+    };
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|error| format!("failed to launch the app: {error}"))
+}
+
+fn connect_or_launch(launch: bool) -> Result<BridgeClient, String> {
+    if let Some(client) = try_connect() {
+        return Ok(client);
+    }
+    if !launch {
+        return Err("the app is not running".to_string());
+    }
+    launch_app()?;
+    let deadline = Instant::now() + LAUNCH_TIMEOUT;
+    while Instant::now() < deadline {
+        std::thread::sleep(LAUNCH_POLL_INTERVAL);
+        if let Some(client) = try_connect() {
+            return Ok(client);
+        }
+    }
+    Err("timed out waiting for the app to start".to_string())
+}
+
+fn current_dir_string() -> Result<String, String> {
+    env::current_dir()
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|error| error.to_string())
+}
+
+fn print_result(command: &CliCommand, result: &Value, json: bool) {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(result).unwrap_or_default()
+        );
+        return;
+    }
+    let field = |key: &str| result.get(key).and_then(Value::as_str).unwrap_or("-");
+    match command {
+        CliCommand::Status => {
+            println!(
+                "ccgui {} running (pid {})",
+                field("version"),
+                result.get("pid").and_then(Value::as_u64).unwrap_or(0)
+            );
+            for workspace in result
+                .get("workspaces")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let value = |key: &str| workspace.get(key).and_then(Value::as_str).unwrap_or("");
+                println!("  {}  {}  {}", value("id"), value("name"), value("path"));
+            }
+        }
+        CliCommand::Open { .. } => println!("Opened {}", field("path")),
+        CliCommand::Send { .. } => println!(
+            "Started {} turn in workspace {} (thread {})",
+            field("engine"),
+            field("workspaceId"),
+            field("threadId")
+        ),
+    }
+}
+
+fn run(options: CliOptions) -> Result<(), String> {
+    let mut client = connect_or_launch(options.launch)?;
+    let result = match &options.command {
+        CliCommand::Status => client.request("status", Value::Null)?,
+        CliCommand::Open { path } => {
+            let path = std::fs::canonicalize(path)
+                .map_err(|error| format!("{}: {error}", path.display()))?;
+            client.request("open", json!({ "path": path.to_string_lossy() }))?
+        }
+        CliCommand::Send {
+            prompt,
+            workspace_id,
+            engine,
+            model,
+        } => client.request(
+            "send",
+            json!({
+                "prompt": prompt,
+                "workspaceId": workspace_id,
+                "path": current_dir_string()?,
+                "engine": engine.as_deref().map(str::to_lowercase),
+                "model": model,
+            }),
+        )?,
+    };
+    print_result(&options.command, &result, options.json);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print!("{}", usage());
+        return;
+    }
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("mossx: {error}\n\n{}", usage());
+            std::process::exit(2);
+        }
+    };
+    if let Err(error) = run(options) {
+        eprintln!("mossx: {error}");
+        std::process::exit(1);
+    }
+}
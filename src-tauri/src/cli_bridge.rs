@@ -0,0 +1,394 @@
+//! Local socket for the `mossx` companion CLI.
+//!
+//! On startup the app binds a loopback port and writes it, with a random
+//! token, to `~/.ccgui/cli-bridge.json` (owner-only on Unix). The CLI speaks
+//! the daemon's newline-delimited JSON-RPC: `auth` first, then `status`,
+//! `open` or `send`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use uuid::Uuid;
+
+use crate::app_paths;
+use crate::engine::{self, resolve_engine_type, EngineType};
+use crate::state::AppState;
+use crate::types::WorkspaceEntry;
+
+const DISCOVERY_FILE: &str = "cli-bridge.json";
+const OPEN_PATHS_EVENT: &str = "open-paths";
+const CLI_TURN_STARTED_EVENT: &str = "cli-turn-started";
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Contents of the discovery file the CLI reads to find the running app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliBridgeDiscovery {
+    pub(crate) port: u16,
+    pub(crate) token: String,
+    pub(crate) pid: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenParams {
+    path: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SendParams {
+    prompt: String,
+    #[serde(default)]
+    workspace_id: Option<String>,
+    /// Directory the CLI was run from; picks the workspace containing it.
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    engine: Option<EngineType>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    access_mode: Option<String>,
+}
+
+fn discovery_path() -> Result<PathBuf, String> {
+    Ok(app_paths::app_home_dir()?.join(DISCOVERY_FILE))
+}
+
+fn write_discovery(path: &Path, discovery: &CliBridgeDiscovery) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(discovery).map_err(|error| error.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        // Created owner-only, so the token is never readable by other users,
+        // not even between creating and renaming the file.
+        app_paths::ensure_data_dir_writable(path)?;
+        let parent = path
+            .parent()
+            .ok_or_else(|| format!("Discovery path has no parent: {}", path.display()))?;
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+        let temp_path = parent.join(format!(".{DISCOVERY_FILE}.{}.tmp", Uuid::new_v4()));
+        let written = std::fs::OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .mode(0o600)
+            .open(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(data.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| std::fs::rename(&temp_path, path));
+        if let Err(error) = written {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(error.to_string());
+        }
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    crate::storage::write_string_atomically(path, &data)
+}
+
+/// Removes the discovery file on exit so the CLI does not try a dead port.
+pub(crate) fn remove_discovery_file() {
+    if let Ok(path) = discovery_path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+pub(crate) async fn start_cli_bridge(app: AppHandle) {
+    let listener = match TcpListener::bind(("127.0.0.1", 0)).await {
+        Ok(listener) => listener,
+        Err(error) => {
+            log::warn!("[cli-bridge] failed to bind: {error}");
+            return;
+        }
+    };
+    let port = match listener.local_addr() {
+        Ok(address) => address.port(),
+        Err(error) => {
+            log::warn!("[cli-bridge] failed to read bound address: {error}");
+            return;
+        }
+    };
+    let discovery = CliBridgeDiscovery {
+        port,
+        token: Uuid::new_v4().simple().to_string(),
+        pid: std::process::id(),
+    };
+    if let Err(error) = discovery_path().and_then(|path| write_discovery(&path, &discovery)) {
+        log::warn!("[cli-bridge] failed to write discovery file: {error}");
+        return;
+    }
+
+    let token = Arc::new(discovery.token);
+    loop {
+        let socket = match listener.accept().await {
+            Ok((socket, _)) => socket,
+            Err(error) => {
+                // Errors such as running out of file descriptors persist for a
+                // while; back off instead of spinning on them.
+                log::warn!("[cli-bridge] failed to accept connection: {error}");
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+        let app = app.clone();
+        let token = Arc::clone(&token);
+        tauri::async_runtime::spawn(async move {
+            handle_connection(socket, app, token).await;
+        });
+    }
+}
+
+async fn handle_connection(socket: TcpStream, app: AppHandle, token: Arc<String>) {
+    let (reader, mut writer) = socket.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let mut authenticated = false;
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        let method = message
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = if authenticated {
+            handle_request(&app, &method, params).await
+        } else if method == "auth"
+            && params.get("token").and_then(Value::as_str) == Some(token.as_str())
+        {
+            authenticated = true;
+            Ok(json!({ "ok": true }))
+        } else {
+            Err("unauthorized".to_string())
+        };
+        let response = match result {
+            Ok(result) => json!({ "id": id, "result": result }),
+            Err(message) => json!({ "id": id, "error": { "message": message } }),
+        };
+        let mut payload = response.to_string();
+        payload.push('\n');
+        if writer.write_all(payload.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+async fn handle_request(app: &AppHandle, method: &str, params: Value) -> Result<Value, String> {
+    match method {
+        "status" => status(app).await,
+        "open" => {
+            let params: OpenParams = serde_json::from_value(params)
+                .map_err(|error| format!("invalid open params: {error}"))?;
+            open_path(app, &params.path).await
+        }
+        "send" => {
+            let params: SendParams = serde_json::from_value(params)
+                .map_err(|error| format!("invalid send params: {error}"))?;
+            send_prompt(app, params).await
+        }
+        _ => Err(format!("unknown method: {method}")),
+    }
+}
+
+async fn status(app: &AppHandle) -> Result<Value, String> {
+    let state = app.state::<AppState>();
+    let mut workspaces: Vec<Value> = state
+        .workspaces
+        .lock()
+        .await
+        .values()
+        .map(|entry| {
+            json!({
+                "id": entry.id,
+                "name": entry.name,
+                "path": entry.path,
+            })
+        })
+        .collect();
+    workspaces.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+    Ok(json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "pid": std::process::id(),
+        "workspaces": workspaces,
+    }))
+}
+
+/// Picks the workspace whose root contains `target`, preferring the deepest
+/// root so nested worktrees win over their parent checkout.
+fn best_workspace_match<'a>(
+    roots: impl IntoIterator<Item = (&'a str, PathBuf)>,
+    target: &Path,
+) -> Option<&'a str> {
+    roots
+        .into_iter()
+        .filter(|(_, root)| target.starts_with(root))
+        .max_by_key(|(_, root)| root.components().count())
+        .map(|(id, _)| id)
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+async fn workspace_for_path(state: &AppState, path: &Path) -> Option<WorkspaceEntry> {
+    let target = canonical(path);
+    let workspaces = state.workspaces.lock().await;
+    let id = best_workspace_match(
+        workspaces
+            .values()
+            .map(|entry| (entry.id.as_str(), canonical(Path::new(&entry.path)))),
+        &target,
+    )?;
+    workspaces.get(id).cloned()
+}
+
+/// Hands the path to the frontend through the same `open-paths` flow used
+/// for paths passed on the command line or dropped on the app icon.
+async fn open_path(app: &AppHandle, path: &str) -> Result<Value, String> {
+    let path = canonical(Path::new(path));
+    if !path.is_dir() {
+        return Err(format!("Not a directory: {}", path.display()));
+    }
+    let path_string = path.to_string_lossy().to_string();
+    let existing = workspace_for_path(&app.state::<AppState>(), &path)
+        .await
+        .filter(|entry| canonical(Path::new(&entry.path)) == path);
+    if let Ok(mut pending) = crate::PENDING_OPEN_PATHS.lock() {
+        pending.push(path_string.clone());
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+        let _ = window.emit(OPEN_PATHS_EVENT, vec![path_string.clone()]);
+    }
+    Ok(json!({
+        "path": path_string,
+        "workspaceId": existing.map(|entry| entry.id),
+    }))
+}
+
+/// Engine a new turn in `entry` uses when the caller does not pick one.
+pub(crate) async fn workspace_default_engine(
+    state: &AppState,
+    entry: &WorkspaceEntry,
+) -> EngineType {
+    let settings = state.app_settings.lock().await.clone();
+    resolve_engine_type(
+        entry.settings.engine_type.as_deref(),
+        settings.default_engine.as_deref(),
+        settings.claude_bin.as_deref(),
+        entry.codex_bin.as_deref().or(settings.codex_bin.as_deref()),
+        None,
+        None,
+    )
+    .await
+}
+
+async fn send_prompt(app: &AppHandle, params: SendParams) -> Result<Value, String> {
+    let prompt = params.prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err("prompt is empty".to_string());
+    }
+    let state = app.state::<AppState>();
+    let entry = match (params.workspace_id.as_deref(), params.path.as_deref()) {
+        (Some(workspace_id), _) => state
+            .workspaces
+            .lock()
+            .await
+            .get(workspace_id)
+            .cloned()
+            .ok_or_else(|| format!("Workspace not found: {workspace_id}"))?,
+        (None, Some(path)) => workspace_for_path(&state, Path::new(path))
+            .await
+            .ok_or_else(|| format!("No workspace contains {path}; run `mossx open` there first"))?,
+        (None, None) => return Err("send requires a workspace id or path".to_string()),
+    };
+    let engine = match params.engine {
+        Some(engine) => engine,
+        None => workspace_default_engine(&state, &entry).await,
+    };
+    let linkage = engine::start_engine_turn(
+        &entry.id,
+        engine,
+        prompt,
+        params.model,
+        params.access_mode,
+        state,
+        app.clone(),
+    )
+    .await?;
+    let _ = app.emit(CLI_TURN_STARTED_EVENT, &linkage);
+    serde_json::to_value(linkage).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best_workspace_match, write_discovery, CliBridgeDiscovery};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn prefers_the_deepest_containing_workspace() {
+        let roots = vec![
+            ("repo", PathBuf::from("/src/repo")),
+            ("worktree", PathBuf::from("/src/repo/.worktrees/feature")),
+            ("other", PathBuf::from("/src/other")),
+        ];
+
+        assert_eq!(
+            best_workspace_match(roots.clone(), Path::new("/src/repo/.worktrees/feature/app")),
+            Some("worktree")
+        );
+        assert_eq!(
+            best_workspace_match(roots.clone(), Path::new("/src/repo/lib")),
+            Some("repo")
+        );
+        assert_eq!(
+            best_workspace_match(roots, Path::new("/src/repository")),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn discovery_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("cli-bridge-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("cli-bridge.json");
+        let discovery = CliBridgeDiscovery {
+            port: 4242,
+            token: "secret".to_string(),
+            pid: 1,
+        };
+
+        write_discovery(&path, &discovery).expect("write discovery");
+        let mode = std::fs::metadata(&path)
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let written: CliBridgeDiscovery =
+            serde_json::from_str(&std::fs::read_to_string(&path).expect("read")).expect("parse");
+        assert_eq!(written.token, "secret");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
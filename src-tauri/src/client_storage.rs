@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant};
 use uuid::Uuid;
@@ -13,6 +16,13 @@ const PANEL_LOCK_PASSWORD_FILENAME: &str = "pwd.txt";
 const CLIENT_STORE_LOCK_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
 const CLIENT_STORE_LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(25);
 const CLIENT_STORE_LOCK_STALE_TIMEOUT: Duration = Duration::from_secs(30);
+const DRAFTS_FILENAME: &str = "drafts.json";
+/// Quiet period after the last keystroke before a draft is written to disk.
+const DRAFT_SAVE_DEBOUNCE: Duration = Duration::from_millis(750);
+const MAX_STORED_DRAFTS: usize = 200;
+/// Key in the `app` store holding the GitHub personal access token used when
+/// the `gh` CLI is unavailable.
+const GITHUB_TOKEN_KEY: &str = "github_token";
 
 fn client_storage_dir() -> Result<PathBuf, String> {
     app_paths::client_storage_dir()
@@ -180,6 +190,159 @@ fn patch_store(filename: &str, patch: &serde_json::Map<String, Value>) -> Result
     })
 }
 
+/// Unsaved composer contents for one session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ComposerDraft {
+    pub(crate) text: String,
+    #[serde(default)]
+    pub(crate) attachments: Vec<String>,
+    pub(crate) updated_at: i64,
+}
+
+impl ComposerDraft {
+    fn is_empty(&self) -> bool {
+        self.text.trim().is_empty() && self.attachments.is_empty()
+    }
+}
+
+/// Drafts waiting out the debounce window, with a generation per session so
+/// only the newest save is flushed.
+fn pending_drafts() -> &'static Mutex<HashMap<String, (u64, ComposerDraft)>> {
+    static PENDING: OnceLock<Mutex<HashMap<String, (u64, ComposerDraft)>>> = OnceLock::new();
+    PENDING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn draft_key(session_id: &str) -> Result<String, String> {
+    let key = session_id.trim();
+    if key.is_empty() {
+        return Err("Draft session id is required".to_string());
+    }
+    Ok(key.to_string())
+}
+
+/// Writes (or removes, when empty) one draft, keeping only the most recently
+/// updated [`MAX_STORED_DRAFTS`] entries.
+fn persist_draft(path: &Path, key: &str, draft: &ComposerDraft) -> Result<(), String> {
+    with_client_store_lock(path, || {
+        let mut drafts = match read_store_unlocked(path)? {
+            Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+        if draft.is_empty() {
+            drafts.remove(key);
+        } else {
+            let value = serde_json::to_value(draft).map_err(|error| error.to_string())?;
+            drafts.insert(key.to_string(), value);
+        }
+        if drafts.len() > MAX_STORED_DRAFTS {
+            let mut by_age = drafts
+                .iter()
+                .map(|(key, value)| {
+                    let updated_at = value.get("updatedAt").and_then(Value::as_i64);
+                    (updated_at.unwrap_or_default(), key.clone())
+                })
+                .collect::<Vec<_>>();
+            by_age.sort();
+            for (_, key) in by_age.into_iter().take(drafts.len() - MAX_STORED_DRAFTS) {
+                drafts.remove(&key);
+            }
+        }
+        write_store_unlocked(path, &Value::Object(drafts))
+    })
+}
+
+fn flush_pending_draft(key: &str, generation: u64) -> Result<(), String> {
+    let draft = {
+        let pending = pending_drafts()
+            .lock()
+            .map_err(|_| "draft queue poisoned".to_string())?;
+        match pending.get(key) {
+            Some((current, draft)) if *current == generation => draft.clone(),
+            _ => return Ok(()),
+        }
+    };
+    persist_draft(&client_storage_dir()?.join(DRAFTS_FILENAME), key, &draft)?;
+    let mut pending = pending_drafts()
+        .lock()
+        .map_err(|_| "draft queue poisoned".to_string())?;
+    if pending
+        .get(key)
+        .is_some_and(|(current, _)| *current == generation)
+    {
+        pending.remove(key);
+    }
+    Ok(())
+}
+
+/// Queues the composer draft for `session_id`; it is written once the user
+/// stops typing for [`DRAFT_SAVE_DEBOUNCE`]. Saving empty text with no
+/// attachments deletes the draft.
+#[tauri::command]
+pub(crate) fn drafts_save(
+    session_id: String,
+    text: String,
+    attachments: Option<Vec<String>>,
+) -> Result<(), String> {
+    let key = draft_key(&session_id)?;
+    let draft = ComposerDraft {
+        text,
+        attachments: attachments.unwrap_or_default(),
+        updated_at: chrono::Utc::now().timestamp_millis(),
+    };
+    let generation = {
+        let mut pending = pending_drafts()
+            .lock()
+            .map_err(|_| "draft queue poisoned".to_string())?;
+        let generation = pending
+            .get(&key)
+            .map_or(0, |(generation, _)| generation.wrapping_add(1));
+        pending.insert(key.clone(), (generation, draft));
+        generation
+    };
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(DRAFT_SAVE_DEBOUNCE).await;
+        if let Err(error) = flush_pending_draft(&key, generation) {
+            log::warn!("[drafts] failed to save draft for {key}: {error}");
+        }
+    });
+    Ok(())
+}
+
+/// Returns the draft for `session_id`, including one still inside the
+/// debounce window.
+#[tauri::command]
+pub(crate) fn drafts_get(session_id: String) -> Result<Option<ComposerDraft>, String> {
+    let key = draft_key(&session_id)?;
+    if let Some((_, draft)) = pending_drafts()
+        .lock()
+        .map_err(|_| "draft queue poisoned".to_string())?
+        .get(&key)
+    {
+        return Ok((!draft.is_empty()).then(|| draft.clone()));
+    }
+    match read_store(DRAFTS_FILENAME)?.get(&key) {
+        Some(value) => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|error| error.to_string()),
+        None => Ok(None),
+    }
+}
+
+/// The GitHub token saved by the frontend, if any.
+pub(crate) fn github_token() -> Option<String> {
+    github_token_from_store(&read_store("app.json").ok()?)
+}
+
+fn github_token_from_store(store: &Value) -> Option<String> {
+    store
+        .get(GITHUB_TOKEN_KEY)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+}
+
 #[tauri::command]
 pub(crate) fn client_panel_lock_password_read() -> Result<Option<String>, String> {
     let path = client_storage_dir()?.join(PANEL_LOCK_PASSWORD_FILENAME);
@@ -221,7 +384,7 @@ pub(crate) fn client_store_patch(store: String, patch: Value) -> Result<(), Stri
 
 #[cfg(test)]
 mod tests {
-    use super::read_store;
+    use super::{persist_draft, read_store, read_store_unlocked, ComposerDraft, MAX_STORED_DRAFTS};
     use serde_json::json;
     use uuid::Uuid;
 
@@ -253,4 +416,34 @@ mod tests {
 
         std::fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn persist_draft_removes_empty_drafts_and_caps_history() {
+        let dir = std::env::temp_dir().join(format!("ccgui-drafts-{}", Uuid::new_v4()));
+        let path = dir.join("drafts.json");
+        for index in 0..=MAX_STORED_DRAFTS {
+            let draft = ComposerDraft {
+                text: format!("draft {index}"),
+                attachments: Vec::new(),
+                updated_at: index as i64,
+            };
+            persist_draft(&path, &format!("session-{index}"), &draft).expect("persist draft");
+        }
+        let stored = read_store_unlocked(&path).expect("read drafts");
+        let stored = stored.as_object().expect("drafts object");
+        assert_eq!(stored.len(), MAX_STORED_DRAFTS);
+        assert!(!stored.contains_key("session-0"));
+
+        let empty = ComposerDraft {
+            text: "  ".to_string(),
+            attachments: Vec::new(),
+            updated_at: 1_000,
+        };
+        persist_draft(&path, "session-1", &empty).expect("clear draft");
+        let stored = read_store_unlocked(&path).expect("read drafts");
+        assert!(stored.get("session-1").is_none());
+        assert_eq!(stored["session-2"]["text"], json!("draft 2"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -1,4 +1,88 @@
-pub(super) fn build_commit_message_prompt(diff: &str, language: Option<&str>) -> String {
+use crate::git::CommitScopeFile;
+
+/// Diff bytes sent to the model; larger diffs are cut per file at hunk
+/// boundaries.
+const COMMIT_MESSAGE_DIFF_MAX_BYTES: usize = 48 * 1024;
+
+/// One `diff --git` section split into its header and hunks.
+struct FileDiffSection {
+    header: String,
+    hunks: Vec<String>,
+}
+
+fn split_diff_sections(diff: &str) -> Vec<FileDiffSection> {
+    let mut sections: Vec<FileDiffSection> = Vec::new();
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") || sections.is_empty() {
+            sections.push(FileDiffSection {
+                header: String::new(),
+                hunks: Vec::new(),
+            });
+        }
+        let section = sections.last_mut().expect("section");
+        if line.starts_with("@@") {
+            section.hunks.push(String::new());
+        }
+        let target = match section.hunks.last_mut() {
+            Some(hunk) => hunk,
+            None => &mut section.header,
+        };
+        target.push_str(line);
+        target.push('\n');
+    }
+    sections
+}
+
+/// Fits `diff` into `max_bytes` without cutting a hunk in half. Each file
+/// gets an equal share of what is left, so one huge file cannot crowd out
+/// the rest; hunks that do not fit are replaced by an omission note.
+fn truncate_diff_for_prompt(diff: &str, max_bytes: usize) -> (String, bool) {
+    if diff.len() <= max_bytes {
+        return (diff.to_string(), false);
+    }
+    let sections = split_diff_sections(diff);
+    let mut output = String::new();
+    let mut remaining = max_bytes;
+    let mut omitted_files = 0usize;
+    for (index, section) in sections.iter().enumerate() {
+        let budget = remaining / (sections.len() - index);
+        if section.header.len() > budget {
+            omitted_files += 1;
+            continue;
+        }
+        let mut used = section.header.len();
+        output.push_str(&section.header);
+        let mut kept = 0usize;
+        for hunk in &section.hunks {
+            if used + hunk.len() > budget {
+                break;
+            }
+            used += hunk.len();
+            output.push_str(hunk);
+            kept += 1;
+        }
+        if kept < section.hunks.len() {
+            output.push_str(&format!(
+                "[... {} of {} hunks omitted]\n",
+                section.hunks.len() - kept,
+                section.hunks.len()
+            ));
+        }
+        remaining = remaining.saturating_sub(used);
+    }
+    if omitted_files > 0 {
+        output.push_str(&format!(
+            "[... diff of {omitted_files} more files omitted; see the file list]\n"
+        ));
+    }
+    (output, true)
+}
+
+pub(super) fn build_commit_message_prompt(
+    diff: &str,
+    files: &[CommitScopeFile],
+    language: Option<&str>,
+) -> String {
     let normalized_language = language
         .map(|value| value.trim().to_ascii_lowercase())
         .unwrap_or_else(|| "zh".to_string());
@@ -14,5 +98,69 @@ Please provide the complete commit message: the title should follow the Conventi
         }
     };
 
-    format!("{intro}\n\nChanges:\n{diff}")
+    let (diff, truncated) = truncate_diff_for_prompt(diff, COMMIT_MESSAGE_DIFF_MAX_BYTES);
+    let mut prompt = intro.to_string();
+    if !files.is_empty() {
+        prompt.push_str(&format!("\n\nChanged files ({}):\n", files.len()));
+        for file in files {
+            prompt.push_str(&format!("{} {}\n", file.status, file.path));
+        }
+    }
+    let changes_label = if truncated {
+        "Changes (truncated; the file list above is complete)"
+    } else {
+        "Changes"
+    };
+    format!("{prompt}\n\n{changes_label}:\n{diff}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_diff(path: &str, hunks: usize, hunk_body: &str) -> String {
+        let mut diff = format!("diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n");
+        for index in 0..hunks {
+            diff.push_str(&format!("@@ -{index},1 +{index},1 @@\n{hunk_body}\n"));
+        }
+        diff
+    }
+
+    #[test]
+    fn truncates_per_file_at_hunk_boundaries() {
+        let small = file_diff("src/small.rs", 1, "-a\n+b");
+        let large = file_diff("src/large.rs", 50, &"+line\n".repeat(40));
+        let diff = format!("{large}{small}");
+        let (truncated, was_truncated) = truncate_diff_for_prompt(&diff, 4096);
+
+        assert!(was_truncated);
+        assert!(truncated.len() <= 4096 + 128);
+        assert!(truncated.contains("src/small.rs"));
+        assert!(truncated.contains("-a\n+b"));
+        assert!(truncated.contains("hunks omitted"));
+        for hunk in truncated.split("@@ -").skip(1) {
+            assert!(hunk.contains(&"+line\n".repeat(40)) || hunk.contains("-a\n+b"));
+        }
+
+        let (unchanged, was_truncated) = truncate_diff_for_prompt(&small, 4096);
+        assert!(!was_truncated);
+        assert_eq!(unchanged, small);
+    }
+
+    #[test]
+    fn prompt_lists_changed_files() {
+        let files = vec![
+            CommitScopeFile {
+                status: "A",
+                path: "src/new.rs".to_string(),
+            },
+            CommitScopeFile {
+                status: "D",
+                path: "src/old.rs".to_string(),
+            },
+        ];
+        let prompt = build_commit_message_prompt("diff --git a/x b/x\n", &files, Some("en"));
+        assert!(prompt.contains("Changed files (2):\nA src/new.rs\nD src/old.rs\n"));
+        assert!(prompt.ends_with("Changes:\ndiff --git a/x b/x\n"));
+    }
 }
@@ -0,0 +1,571 @@
+//! App-managed Codex CLI versions. Release binaries are downloaded from the
+//! official GitHub releases into `<app home>/codex-versions/<version>`,
+//! verified against a SHA-256 checksum, and pinned per workspace so a team
+//! can stay on a known-good version and roll back after a bad upgrade.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{ACCEPT, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::State;
+use tokio::io::AsyncWriteExt;
+use tokio::time::timeout;
+
+use crate::app_paths;
+use crate::shared::workspace_read_only::ensure_workspace_id_writable;
+use crate::shared::workspaces_core;
+use crate::state::AppState;
+use crate::types::{ManagedCodexPin, WorkspaceInfo};
+
+const MANAGED_CODEX_DIRNAME: &str = "codex-versions";
+const MANIFEST_FILENAME: &str = "manifest.json";
+const CODEX_RELEASES_API: &str = "https://api.github.com/repos/openai/codex/releases/tags";
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(600);
+const VERSION_CHECK_TIMEOUT_SECS: u64 = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ManagedCodexVersion {
+    pub(crate) version: String,
+    pub(crate) asset: String,
+    /// SHA-256 of the downloaded release archive.
+    pub(crate) archive_sha256: String,
+    pub(crate) bin_sha256: String,
+    pub(crate) bin_path: String,
+    pub(crate) installed_at_ms: u64,
+}
+
+#[derive(Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+    /// `sha256:<hex>`, published by GitHub for release assets.
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Release {
+    assets: Vec<ReleaseAsset>,
+}
+
+fn managed_root() -> Result<PathBuf, String> {
+    Ok(app_paths::app_home_dir()?.join(MANAGED_CODEX_DIRNAME))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Versions become directory names, so only plain release versions such as
+/// `0.46.0` or `0.47.0-alpha.1` are accepted.
+fn validate_version(version: &str) -> Result<String, String> {
+    let version = version
+        .trim()
+        .trim_start_matches("rust-v")
+        .trim_start_matches('v');
+    let valid = version.starts_with(|ch: char| ch.is_ascii_digit())
+        && version
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '.' || ch == '-');
+    if !valid || version.contains("..") {
+        return Err(format!("Invalid Codex version: {version}"));
+    }
+    Ok(version.to_string())
+}
+
+fn normalize_sha256(value: &str) -> Result<String, String> {
+    let value = value
+        .trim()
+        .trim_start_matches("sha256:")
+        .to_ascii_lowercase();
+    if value.len() != 64 || !value.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Err("Checksum must be a 64-character SHA-256 hex digest.".to_string());
+    }
+    Ok(value)
+}
+
+/// Release asset for this machine, or `None` on platforms without one.
+fn release_asset_name() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => Some("codex-aarch64-apple-darwin.tar.gz"),
+        ("macos", "x86_64") => Some("codex-x86_64-apple-darwin.tar.gz"),
+        ("linux", "aarch64") => Some("codex-aarch64-unknown-linux-musl.tar.gz"),
+        ("linux", "x86_64") => Some("codex-x86_64-unknown-linux-musl.tar.gz"),
+        ("windows", "aarch64") => Some("codex-aarch64-pc-windows-msvc.exe.zip"),
+        ("windows", "x86_64") => Some("codex-x86_64-pc-windows-msvc.exe.zip"),
+        _ => None,
+    }
+}
+
+fn binary_name() -> &'static str {
+    if cfg!(windows) {
+        "codex.exe"
+    } else {
+        "codex"
+    }
+}
+
+fn file_sha256(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|error| format!("{}: {error}", path.display()))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn read_manifest(version_dir: &Path) -> Option<ManagedCodexVersion> {
+    let raw = fs::read_to_string(version_dir.join(MANIFEST_FILENAME)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn list_installed(root: &Path) -> Vec<ManagedCodexVersion> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut versions: Vec<ManagedCodexVersion> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| read_manifest(&entry.path()))
+        .collect();
+    versions.sort_by(|a, b| b.installed_at_ms.cmp(&a.installed_at_ms));
+    versions
+}
+
+/// The installed version, after checking its binary still matches the
+/// checksum recorded at install time.
+fn verified_install(root: &Path, version: &str) -> Result<ManagedCodexVersion, String> {
+    let installed = read_manifest(&root.join(version))
+        .ok_or_else(|| format!("Codex {version} is not installed."))?;
+    let actual = file_sha256(Path::new(&installed.bin_path))?;
+    if actual != installed.bin_sha256 {
+        return Err(format!(
+            "Codex {version} failed its checksum check; reinstall it before switching."
+        ));
+    }
+    Ok(installed)
+}
+
+async fn fetch_release_asset(version: &str, asset_name: &str) -> Result<ReleaseAsset, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to configure HTTP client: {e}"))?;
+    let response = client
+        .get(format!("{CODEX_RELEASES_API}/rust-v{version}"))
+        .header(ACCEPT, "application/vnd.github+json")
+        .header(USER_AGENT, "ccgui")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to look up Codex {version}: {e}"))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to look up Codex {version}: {e}"))?;
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!("Codex {version} was not found in the releases."));
+    }
+    if !status.is_success() {
+        return Err(format!("Failed to look up Codex {version} ({status})."));
+    }
+    let release: Release =
+        serde_json::from_str(&body).map_err(|e| format!("Unexpected release response: {e}"))?;
+    release
+        .assets
+        .into_iter()
+        .find(|asset| asset.name == asset_name)
+        .ok_or_else(|| format!("Codex {version} has no {asset_name} download."))
+}
+
+/// Streams `url` into `destination`, returning the SHA-256 of what was
+/// written.
+async fn download(url: &str, destination: &Path) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to configure HTTP client: {e}"))?;
+    let mut response = client
+        .get(url)
+        .header(USER_AGENT, "ccgui")
+        .send()
+        .await
+        .map_err(|e| format!("Download failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Download failed ({}).", response.status()));
+    }
+    let mut file = tokio::fs::File::create(destination)
+        .await
+        .map_err(|e| format!("Failed to write download: {e}"))?;
+    let mut hasher = Sha256::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Download failed: {e}"))?
+    {
+        hasher.update(&chunk);
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write download: {e}"))?;
+    }
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to write download: {e}"))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Unpacks the archive with the system `tar` (bsdtar on Windows reads zip
+/// too) and moves the single extracted binary to `codex`.
+async fn extract_binary(archive: &Path, staging: &Path) -> Result<PathBuf, String> {
+    let unpack_dir = staging.join("unpacked");
+    fs::create_dir_all(&unpack_dir).map_err(|e| e.to_string())?;
+    let output = crate::utils::async_command("tar")
+        .arg("-xf")
+        .arg(archive)
+        .arg("-C")
+        .arg(&unpack_dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run tar: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to unpack Codex: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let extracted = fs::read_dir(&unpack_dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with("codex"))
+        })
+        .ok_or("The Codex archive did not contain a codex binary.")?;
+    let binary = staging.join(binary_name());
+    fs::rename(&extracted, &binary).map_err(|e| e.to_string())?;
+    let _ = fs::remove_dir_all(&unpack_dir);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&binary, fs::Permissions::from_mode(0o755))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(binary)
+}
+
+async fn check_binary_version(binary: &Path, version: &str) -> Result<(), String> {
+    let output = timeout(
+        Duration::from_secs(VERSION_CHECK_TIMEOUT_SECS),
+        crate::utils::async_command(binary)
+            .arg("--version")
+            .output(),
+    )
+    .await
+    .map_err(|_| "The downloaded Codex binary did not respond to --version.".to_string())?
+    .map_err(|e| format!("Failed to run the downloaded Codex binary: {e}"))?;
+    let reported = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || !reported.contains(version) {
+        return Err(format!(
+            "The downloaded binary reports `{}`, expected Codex {version}.",
+            reported.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Downloads, verifies and unpacks into a staging directory, then renames it
+/// into place so a failed install never leaves a half-written version.
+async fn install_version(
+    root: &Path,
+    version: &str,
+    expected_sha256: Option<String>,
+) -> Result<ManagedCodexVersion, String> {
+    if let Some(installed) = read_manifest(&root.join(version)) {
+        match &expected_sha256 {
+            Some(expected) if *expected != installed.archive_sha256 => {
+                return Err(format!(
+                    "Codex {version} is already installed with checksum {}, which does not match the one given.",
+                    installed.archive_sha256
+                ));
+            }
+            _ => return verified_install(root, version),
+        }
+    }
+    let asset_name =
+        release_asset_name().ok_or("Managed Codex versions are not available on this platform.")?;
+    let asset = fetch_release_asset(version, asset_name).await?;
+    let published = asset.digest.as_deref().map(normalize_sha256).transpose()?;
+    if published.is_none() && expected_sha256.is_none() {
+        return Err(format!(
+            "Codex {version} has no published checksum; provide the expected SHA-256 to install it."
+        ));
+    }
+
+    let staging = root.join(format!(".staging-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+    let result = async {
+        let archive = staging.join(&asset.name);
+        let actual = download(&asset.browser_download_url, &archive).await?;
+        for expected in [published.as_ref(), expected_sha256.as_ref()]
+            .into_iter()
+            .flatten()
+        {
+            if *expected != actual {
+                return Err(format!(
+                    "Checksum mismatch for {}: expected {expected}, got {actual}.",
+                    asset.name
+                ));
+            }
+        }
+        let binary = extract_binary(&archive, &staging).await?;
+        let _ = fs::remove_file(&archive);
+        check_binary_version(&binary, version).await?;
+        let version_dir = root.join(version);
+        let installed = ManagedCodexVersion {
+            version: version.to_string(),
+            asset: asset.name.clone(),
+            archive_sha256: actual,
+            bin_sha256: file_sha256(&binary)?,
+            bin_path: version_dir
+                .join(binary_name())
+                .to_string_lossy()
+                .to_string(),
+            installed_at_ms: now_ms(),
+        };
+        let manifest = serde_json::to_string_pretty(&installed).map_err(|e| e.to_string())?;
+        fs::write(staging.join(MANIFEST_FILENAME), manifest).map_err(|e| e.to_string())?;
+        fs::rename(&staging, &version_dir).map_err(|e| e.to_string())?;
+        Ok(installed)
+    }
+    .await;
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+/// The pin that replaces `current` when switching to `installed`.
+fn next_pin(
+    current: Option<&ManagedCodexPin>,
+    current_codex_bin: Option<String>,
+    installed: &ManagedCodexVersion,
+) -> ManagedCodexPin {
+    ManagedCodexPin {
+        version: installed.version.clone(),
+        bin_sha256: installed.bin_sha256.clone(),
+        previous_version: current.map(|pin| pin.version.clone()),
+        previous_codex_bin: match current {
+            Some(pin) => pin.previous_codex_bin.clone(),
+            None => current_codex_bin,
+        },
+    }
+}
+
+/// Stores the pin and points `codex_bin` at `codex_bin`; sessions pick the
+/// binary up the next time they start.
+async fn apply_pin(
+    state: &State<'_, AppState>,
+    workspace_id: &str,
+    pin: Option<ManagedCodexPin>,
+    codex_bin: Option<String>,
+) -> Result<WorkspaceInfo, String> {
+    {
+        let mut workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get_mut(workspace_id)
+            .ok_or("workspace not found")?;
+        entry.settings.managed_codex = pin;
+    }
+    workspaces_core::update_workspace_codex_bin_core(
+        workspace_id.to_string(),
+        codex_bin,
+        &state.workspaces,
+        &state.sessions,
+        &state.storage_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn list_managed_codex_versions() -> Result<Vec<ManagedCodexVersion>, String> {
+    Ok(list_installed(&managed_root()?))
+}
+
+/// Installs a Codex release. `sha256` pins the expected archive checksum;
+/// without it the checksum GitHub publishes for the asset is required.
+#[tauri::command]
+pub(crate) async fn install_managed_codex_version(
+    version: String,
+    sha256: Option<String>,
+) -> Result<ManagedCodexVersion, String> {
+    let version = validate_version(&version)?;
+    let expected = sha256
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| normalize_sha256(&value))
+        .transpose()?;
+    let root = managed_root()?;
+    fs::create_dir_all(&root).map_err(|e| e.to_string())?;
+    install_version(&root, &version, expected).await
+}
+
+#[tauri::command]
+pub(crate) async fn pin_workspace_codex_version(
+    workspace_id: String,
+    version: String,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceInfo, String> {
+    ensure_workspace_id_writable(
+        &state.workspaces,
+        &workspace_id,
+        "pin_workspace_codex_version",
+    )
+    .await?;
+    let version = validate_version(&version)?;
+    let installed = verified_install(&managed_root()?, &version)?;
+    let (current_pin, current_bin) = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces.get(&workspace_id).ok_or("workspace not found")?;
+        (
+            entry.settings.managed_codex.clone(),
+            entry.codex_bin.clone(),
+        )
+    };
+    if current_pin.as_ref().map(|pin| pin.version.as_str()) == Some(version.as_str()) {
+        return Err(format!("Workspace is already pinned to Codex {version}."));
+    }
+    let pin = next_pin(current_pin.as_ref(), current_bin, &installed);
+    apply_pin(&state, &workspace_id, Some(pin), Some(installed.bin_path)).await
+}
+
+/// Undoes the last pin: back to the previously pinned version, or to the
+/// manual binary setting once no managed version came before.
+#[tauri::command]
+pub(crate) async fn rollback_workspace_codex_version(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceInfo, String> {
+    ensure_workspace_id_writable(
+        &state.workspaces,
+        &workspace_id,
+        "rollback_workspace_codex_version",
+    )
+    .await?;
+    let current_pin = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces.get(&workspace_id).ok_or("workspace not found")?;
+        entry.settings.managed_codex.clone()
+    }
+    .ok_or("Workspace is not pinned to a managed Codex version.")?;
+    match current_pin.previous_version.as_deref() {
+        Some(previous) => {
+            let installed = verified_install(&managed_root()?, previous)?;
+            let pin = ManagedCodexPin {
+                version: installed.version.clone(),
+                bin_sha256: installed.bin_sha256.clone(),
+                previous_version: None,
+                previous_codex_bin: current_pin.previous_codex_bin,
+            };
+            apply_pin(&state, &workspace_id, Some(pin), Some(installed.bin_path)).await
+        }
+        None => apply_pin(&state, &workspace_id, None, current_pin.previous_codex_bin).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_versions_and_checksums() {
+        assert_eq!(validate_version(" rust-v0.46.0 "), Ok("0.46.0".to_string()));
+        assert_eq!(
+            validate_version("v0.47.0-alpha.1"),
+            Ok("0.47.0-alpha.1".to_string())
+        );
+        assert!(validate_version("../0.46.0").is_err());
+        assert!(validate_version("latest").is_err());
+
+        let digest = "A".repeat(64);
+        assert_eq!(
+            normalize_sha256(&format!("sha256:{digest}")),
+            Ok("a".repeat(64))
+        );
+        assert!(normalize_sha256("abc").is_err());
+    }
+
+    #[test]
+    fn pins_remember_one_step_of_history() {
+        let installed = |version: &str| ManagedCodexVersion {
+            version: version.to_string(),
+            asset: "codex.tar.gz".to_string(),
+            archive_sha256: "a".repeat(64),
+            bin_sha256: format!("bin-{version}"),
+            bin_path: format!("/codex-versions/{version}/codex"),
+            installed_at_ms: 0,
+        };
+        let first = next_pin(
+            None,
+            Some("/usr/local/bin/codex".to_string()),
+            &installed("0.45.0"),
+        );
+        assert_eq!(first.previous_version, None);
+        assert_eq!(
+            first.previous_codex_bin.as_deref(),
+            Some("/usr/local/bin/codex")
+        );
+
+        let second = next_pin(
+            Some(&first),
+            Some("/codex-versions/0.45.0/codex".to_string()),
+            &installed("0.46.0"),
+        );
+        assert_eq!(second.version, "0.46.0");
+        assert_eq!(second.previous_version.as_deref(), Some("0.45.0"));
+        assert_eq!(
+            second.previous_codex_bin.as_deref(),
+            Some("/usr/local/bin/codex")
+        );
+    }
+
+    #[test]
+    fn switching_requires_an_intact_binary() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        let version_dir = root.join("0.46.0");
+        fs::create_dir_all(&version_dir).expect("create version dir");
+        let binary = version_dir.join(binary_name());
+        fs::write(&binary, "codex binary").expect("write binary");
+        let installed = ManagedCodexVersion {
+            version: "0.46.0".to_string(),
+            asset: "codex.tar.gz".to_string(),
+            archive_sha256: "a".repeat(64),
+            bin_sha256: file_sha256(&binary).expect("hash binary"),
+            bin_path: binary.to_string_lossy().to_string(),
+            installed_at_ms: 1,
+        };
+        fs::write(
+            version_dir.join(MANIFEST_FILENAME),
+            serde_json::to_string(&installed).expect("serialize manifest"),
+        )
+        .expect("write manifest");
+        fs::create_dir_all(root.join(".staging-leftover")).expect("create staging dir");
+
+        assert_eq!(list_installed(&root), vec![installed.clone()]);
+        assert_eq!(verified_install(&root, "0.46.0"), Ok(installed));
+        assert!(verified_install(&root, "0.47.0").is_err());
+
+        fs::write(&binary, "tampered").expect("tamper binary");
+        assert!(verified_install(&root, "0.46.0")
+            .expect_err("tampered binary")
+            .contains("checksum"));
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -15,6 +15,7 @@ pub(crate) mod config;
 mod doctor;
 pub(crate) mod home;
 mod installer;
+pub(crate) mod managed_versions;
 mod mcp_config;
 mod model_selection;
 pub(crate) mod rewind;
@@ -28,8 +29,9 @@ use self::commit_message::build_commit_message_prompt;
 pub(crate) use self::doctor::{run_claude_doctor_with_settings, run_codex_doctor_with_settings};
 pub(crate) use self::home::resolve_workspace_codex_home;
 pub(crate) use self::installer::{
-    build_cli_install_plan_with_backend, run_cli_installer_with_progress, CliInstallAction,
-    CliInstallBackend, CliInstallEngine, CliInstallProgressEvent, CliInstallStrategy,
+    build_cli_install_plan_with_backend, package_name_for_engine, run_cli_installer_with_progress,
+    CliInstallAction, CliInstallBackend, CliInstallEngine, CliInstallOutputStream,
+    CliInstallProgressEvent, CliInstallProgressPhase, CliInstallStrategy,
 };
 use self::mcp_config::{
     list_global_mcp_servers as list_global_mcp_servers_impl, GlobalMcpServerEntry,
@@ -233,7 +235,7 @@ pub(crate) async fn start_thread_with_runtime_retry(
     app: &AppHandle,
 ) -> Result<Value, String> {
     let normalized_model = normalize_model_id(model);
-    run_start_thread_with_hook_safe_fallback_and_recovery_probe(
+    let started = run_start_thread_with_hook_safe_fallback_and_recovery_probe(
         workspace_id,
         || ensure_codex_session(workspace_id, state, app),
         || async {
@@ -252,7 +254,18 @@ pub(crate) async fn start_thread_with_runtime_retry(
             )
         },
     )
-    .await
+    .await;
+    let thread_id = started.as_ref().ok().and_then(|response| {
+        let result = response.get("result").unwrap_or(response);
+        result
+            .pointer("/thread/id")
+            .or_else(|| result.get("threadId"))
+            .and_then(Value::as_str)
+    });
+    if let Some(thread_id) = thread_id {
+        crate::session_context_pack::mark_fresh_codex_thread(thread_id);
+    }
+    started
 }
 
 fn emit_manual_compaction_event(
@@ -706,6 +719,16 @@ pub(crate) async fn list_threads(
     build_unified_codex_thread_page(&state, &workspace_id, cursor, limit, has_session).await
 }
 
+/// First page of the unified Codex thread list, for the engine adapter.
+pub(crate) async fn codex_thread_history(
+    state: &AppState,
+    workspace_id: &str,
+    limit: Option<u32>,
+) -> Result<Value, String> {
+    let has_session = state.sessions.lock().await.contains_key(workspace_id);
+    build_unified_codex_thread_page(state, workspace_id, None, limit, has_session).await
+}
+
 #[tauri::command]
 pub(crate) async fn list_global_mcp_servers() -> Result<Vec<GlobalMcpServerEntry>, String> {
     list_global_mcp_servers_impl().await
@@ -988,6 +1011,9 @@ pub(crate) async fn send_user_message(
     // Ensure Codex session exists before sending message
     // This handles the case where user switches from Claude to Codex engine
     ensure_codex_session(&workspace_id, &state, &app).await?;
+    let privacy =
+        crate::privacy_zones::enforce_for_turn(&state, &app, &workspace_id, text, images).await;
+    let (text, images) = (privacy.text, privacy.images);
     let effective_model = if normalized_model.is_some() {
         normalized_model
     } else {
@@ -998,6 +1024,20 @@ pub(crate) async fn send_user_message(
         settings.codex_mode_enforcement_enabled
     };
 
+    let context_pack_launch = crate::session_context_pack::ContextPackLaunch {
+        engine: crate::engine::EngineType::Codex,
+        prompt: text.clone(),
+        model: effective_model.clone(),
+        effort: effort.clone(),
+        access_mode: access_mode.clone(),
+        images: images.clone().unwrap_or_default(),
+        agent: None,
+        variant: None,
+        custom_spec_root: custom_spec_root.clone(),
+        cwd: None,
+        fork_session_id: None,
+    };
+    crate::session_cost::note_thread_model(&workspace_id, &thread_id, effective_model.as_deref());
     let response = codex_core::send_user_message_core(
         &state.sessions,
         workspace_id.clone(),
@@ -1013,6 +1053,12 @@ pub(crate) async fn send_user_message(
         mode_enforcement_enabled,
     )
     .await?;
+    crate::session_context_pack::record_codex_first_message(
+        &app,
+        &workspace_id,
+        &thread_id,
+        context_pack_launch,
+    );
 
     if resume_source.as_deref() == Some("queue-fusion-cutover") {
         let session = {
@@ -1086,7 +1132,10 @@ pub(crate) async fn send_user_message(
         },
     );
 
-    Ok(response)
+    Ok(crate::privacy_zones::attach_privacy_notice(
+        response,
+        privacy.notice.as_ref(),
+    ))
 }
 
 #[tauri::command]
@@ -1424,6 +1473,18 @@ pub(crate) async fn respond_to_server_request(
         .as_str()
         .map(|value| value.starts_with("ccgui-plan-"))
         .unwrap_or(false);
+    // Only the API engine runs commands itself; CLI engines would silently run
+    // the original command instead of the edited one.
+    if crate::shared::approval_audit::edited_command_from_result(&result)?.is_some()
+        && !crate::engine::api_engine::is_approval_request_id(&request_id)
+    {
+        return Err(
+            "This engine cannot run an edited command; decline and ask for the change instead."
+                .to_string(),
+        );
+    }
+    crate::outbound_notifications::mark_server_request_resolved(&workspace_id, &request_id);
+    crate::user_input_policy::mark_request_resolved(&workspace_id, &request_id);
     if remote_backend::is_remote_mode(&*state).await {
         remote_backend::call_remote(
             &*state,
@@ -1441,6 +1502,12 @@ pub(crate) async fn respond_to_server_request(
         return Ok(());
     }
 
+    if let Some(result) =
+        crate::engine::api_engine::respond_to_approval_request(&request_id, &result)
+    {
+        return result;
+    }
+
     // Prefer request-id based Claude routing so AskUserQuestion responses
     // are delivered to the correct waiting Claude turn even when global
     // active-engine state is stale.
@@ -1496,21 +1563,27 @@ pub(crate) async fn get_commit_message_prompt(
     workspace_id: String,
     language: Option<String>,
     selected_paths: Option<Vec<String>>,
+    staged_only: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
     // Get the diff from git
-    let diff = crate::git::get_workspace_diff_for_commit_scope(
+    let scope = crate::git::get_workspace_diff_for_commit_scope(
         &workspace_id,
         &state,
         selected_paths.as_deref(),
+        staged_only.unwrap_or(false),
     )
     .await?;
 
-    if diff.trim().is_empty() {
+    if scope.diff.trim().is_empty() {
         return Err("No changes to generate commit message for".to_string());
     }
 
-    Ok(build_commit_message_prompt(&diff, language.as_deref()))
+    Ok(build_commit_message_prompt(
+        &scope.diff,
+        &scope.files,
+        language.as_deref(),
+    ))
 }
 
 #[tauri::command]
@@ -1519,7 +1592,12 @@ pub(crate) async fn remember_approval_rule(
     command: Vec<String>,
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
-    codex_core::remember_approval_rule_core(&state.workspaces, workspace_id, command).await
+    let result =
+        codex_core::remember_approval_rule_core(&state.workspaces, workspace_id, command).await?;
+    if let Some(rules_path) = result.get("rulesPath").and_then(Value::as_str) {
+        crate::approval_rules::note_remembered_rules(PathBuf::from(rules_path)).await;
+    }
+    Ok(result)
 }
 
 #[tauri::command]
@@ -1547,22 +1625,24 @@ pub(crate) async fn generate_commit_message(
     workspace_id: String,
     language: Option<String>,
     selected_paths: Option<Vec<String>>,
+    staged_only: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<String, String> {
     // Get the diff from git
-    let diff = crate::git::get_workspace_diff_for_commit_scope(
+    let scope = crate::git::get_workspace_diff_for_commit_scope(
         &workspace_id,
         &state,
         selected_paths.as_deref(),
+        staged_only.unwrap_or(false),
     )
     .await?;
 
-    if diff.trim().is_empty() {
+    if scope.diff.trim().is_empty() {
         return Err("No changes to generate commit message for".to_string());
     }
 
-    let prompt = build_commit_message_prompt(&diff, language.as_deref());
+    let prompt = build_commit_message_prompt(&scope.diff, &scope.files, language.as_deref());
 
     // Get the session – requires a running Codex CLI process
     let session = {
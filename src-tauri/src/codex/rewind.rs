@@ -9,7 +9,7 @@ use crate::shared::codex_core;
 use crate::types::WorkspaceEntry;
 
 #[derive(Clone, Debug)]
-struct UserMessageCandidate {
+pub(crate) struct UserMessageCandidate {
     id: String,
     text: String,
 }
@@ -76,7 +76,7 @@ fn extract_user_message_text(item: &Value) -> String {
     content_parts.join("\n\n")
 }
 
-fn collect_user_messages_from_thread(value: &Value) -> Vec<UserMessageCandidate> {
+pub(crate) fn collect_user_messages_from_thread(value: &Value) -> Vec<UserMessageCandidate> {
     let mut user_messages = Vec::new();
     let turns = value
         .get("result")
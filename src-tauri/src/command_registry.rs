@@ -9,6 +9,17 @@ pub(crate) fn invoke_handler(
         crate::settings::restore_codex_unified_exec_official_default,
         crate::settings::set_codex_unified_exec_official_override,
         crate::settings::reload_codex_runtime_config,
+        crate::settings::list_access_mode_presets,
+        crate::settings::get_workspace_access_mode_preset,
+        crate::settings::list_config_versions,
+        crate::settings::restore_previous_version,
+        crate::store_migrations::store_migration_report,
+        crate::store_migrations::run_store_migrations,
+        crate::state_store::get_state_store_status,
+        crate::state_store::repair_state_store,
+        crate::state_store::migrate_state_store,
+        crate::data_dir::get_data_dir_status,
+        crate::data_dir::migrate_data_dir,
         crate::email::get_email_sender_settings,
         crate::email::update_email_sender_settings,
         crate::email::send_test_email,
@@ -22,12 +33,21 @@ pub(crate) fn invoke_handler(
         crate::runtime::commands::mutate_runtime_pool,
         crate::runtime::commands::ensure_runtime_ready,
         crate::runtime::commands::note_web_service_reconnected,
+        crate::session_resources::get_session_resources,
+        crate::session_resources::start_session_resources_monitor,
+        crate::session_resources::stop_session_resources_monitor,
         crate::web_service::start_web_server,
         crate::web_service::stop_web_server,
         crate::web_service::get_web_server_status,
         crate::web_service::get_daemon_status,
         crate::web_service::start_daemon,
         crate::web_service::stop_daemon,
+        crate::webhooks::get_webhook_status,
+        crate::webhooks::update_webhook_settings,
+        crate::webhooks::rotate_webhook_token,
+        crate::preview_server::start_preview_server,
+        crate::preview_server::stop_preview_server,
+        crate::preview_server::get_preview_server_status,
         // Agents
         crate::agents::agent_list,
         crate::agents::agent_add,
@@ -52,6 +72,8 @@ pub(crate) fn invoke_handler(
         crate::engine::switch_engine,
         crate::engine::get_engine_status,
         crate::engine::get_all_engine_statuses,
+        crate::engine::get_engine_warm_start_metrics,
+        crate::engine::api_engine::get_api_engine_status,
         crate::engine::set_engine_config,
         crate::engine::get_engine_config,
         crate::engine::is_engine_available,
@@ -61,6 +83,8 @@ pub(crate) fn invoke_handler(
         crate::engine::opencode_agents_list,
         crate::engine::opencode_session_list,
         crate::engine::opencode_delete_session,
+        crate::engine::opencode_rename_session,
+        crate::engine::opencode_generate_session_title,
         crate::engine::opencode_stats,
         crate::engine::opencode_export_session,
         crate::engine::opencode_import_session,
@@ -78,32 +102,53 @@ pub(crate) fn invoke_handler(
         crate::engine::opencode_lsp_references,
         crate::engine::engine_send_message,
         crate::engine::engine_send_message_sync,
+        crate::engine::prompt_preflight::engine_prompt_preflight,
+        crate::engine::turn_cwds::engine_session_turn_cwds,
         crate::engine::engine_interrupt,
         crate::engine::engine_interrupt_turn,
+        crate::engine::claude_respond_to_question,
+        crate::engine::engine_forwarder_stats,
+        crate::engine::engine_list_sessions,
         crate::engine::list_claude_sessions,
         crate::engine::load_claude_session,
         crate::engine::hydrate_claude_deferred_image,
         crate::engine::fork_claude_session,
         crate::engine::fork_claude_session_from_message,
         crate::engine::delete_claude_session,
+        crate::engine::export_claude_session,
         crate::engine::list_gemini_sessions,
         crate::engine::load_gemini_session,
         crate::engine::delete_gemini_session,
         crate::local_usage::list_codex_session_summaries,
         crate::local_usage::load_codex_session,
+        crate::engine::search_sessions,
         // Codex
         crate::codex::get_config_model,
         crate::codex::codex_doctor,
         crate::codex::claude_doctor,
         crate::codex::cli_install_plan,
         crate::codex::cli_install_run,
+        crate::codex::managed_versions::list_managed_codex_versions,
+        crate::codex::managed_versions::install_managed_codex_version,
+        crate::codex::managed_versions::pin_workspace_codex_version,
+        crate::codex::managed_versions::rollback_workspace_codex_version,
+        crate::onboarding::onboarding_diagnostics,
+        crate::onboarding::onboarding_install,
+        crate::onboarding::get_onboarding_state,
+        crate::onboarding::update_onboarding_progress,
+        crate::onboarding::complete_onboarding,
+        crate::onboarding::reset_onboarding,
         crate::codex::start_thread,
         crate::codex::send_user_message,
         crate::codex::turn_interrupt,
         crate::codex::thread_compact,
         crate::codex::start_review,
         crate::codex::respond_to_server_request,
+        crate::user_input_policy::respond_to_user_input_batch,
         crate::codex::remember_approval_rule,
+        crate::approval_rules::list_approval_rules,
+        crate::approval_rules::delete_approval_rule,
+        crate::approval_rules::clear_approval_rules,
         crate::codex::get_commit_message_prompt,
         crate::codex::generate_commit_message,
         crate::codex::list_thread_titles,
@@ -135,12 +180,14 @@ pub(crate) fn invoke_handler(
         crate::shared_sessions::sync_shared_session_snapshot,
         crate::shared_sessions::send_shared_session_message,
         crate::shared_sessions::delete_shared_session,
+        crate::shared_sessions::import_web_conversation_export,
         // Workspaces
         crate::workspaces::list_workspaces,
         crate::workspaces::is_workspace_path_dir,
         crate::workspaces::ensure_workspace_path_dir,
         crate::workspaces::add_workspace,
         crate::workspaces::add_clone,
+        crate::workspaces::preview_worktree_branch_name,
         crate::workspaces::add_worktree,
         crate::workspaces::worktree_setup_status,
         crate::workspaces::worktree_setup_mark_ran,
@@ -161,6 +208,7 @@ pub(crate) fn invoke_handler(
         crate::workspaces::read_external_spec_file,
         crate::workspaces::read_external_absolute_file,
         crate::workspaces::resolve_file_preview_handle,
+        crate::workspaces::preview_file,
         crate::workspaces::read_local_image_data_url,
         crate::workspaces::write_workspace_file,
         crate::workspaces::create_workspace_directory,
@@ -176,26 +224,56 @@ pub(crate) fn invoke_handler(
         crate::workspaces::configure_detached_external_change_monitor,
         crate::workspaces::clear_detached_external_change_monitor,
         crate::workspaces::get_open_app_icon,
+        crate::workspaces::workspace_snapshot,
+        crate::workspaces::preload_workspace_snapshots,
         // Git
+        crate::file_attribution::file_attribution,
+        crate::file_attribution::workspace_attribution_summary,
+        crate::file_mentions::file_sessions,
         crate::git::get_git_status,
+        crate::git::git_status_watch_start,
+        crate::git::git_status_watch_stop,
         crate::git::list_git_roots,
         crate::git::get_git_diffs,
+        crate::git::get_git_diff_stats,
         crate::git::get_git_file_full_diff,
+        crate::git::run_git_diff_action,
+        crate::git::get_git_blame,
+        crate::git::get_git_blame_context,
         crate::git::get_git_log,
         crate::git::get_git_commit_history,
+        crate::git::get_git_file_history,
+        crate::git::get_git_reflog,
+        crate::git::recover_git_commit,
+        crate::git::get_file_at_ref,
+        crate::git::compare_file_with_ref,
         crate::git::get_git_commit_details,
+        crate::git::export_review_bundle,
         crate::git::get_git_push_preview,
         crate::git::resolve_git_commit_ref,
         crate::git::get_git_commit_diff,
         crate::git::get_git_remote,
+        crate::git::list_git_remotes,
+        crate::git::add_git_remote,
+        crate::git::remove_git_remote,
+        crate::git::rename_git_remote,
         crate::git::get_git_pr_workflow_defaults,
         crate::git::create_git_pr_workflow,
+        crate::git::get_branch_pr_status,
+        crate::git::start_branch_pr_status_polling,
+        crate::git::stop_branch_pr_status_polling,
         crate::git::stage_git_file,
+        crate::git::get_git_file_hunks,
+        crate::git::stage_git_hunk,
         crate::git::stage_git_all,
         crate::git::unstage_git_file,
         crate::git::revert_git_file,
         crate::git::revert_git_all,
         crate::git::commit_git,
+        crate::git::fixup_git_commit,
+        crate::git::get_git_signing_status,
+        crate::git::get_git_identity,
+        crate::git::set_git_identity,
         crate::git::push_git,
         crate::git::pull_git,
         crate::git::sync_git,
@@ -208,18 +286,47 @@ pub(crate) fn invoke_handler(
         crate::git::revert_commit,
         crate::git::reset_git_commit,
         crate::git::get_github_issues,
+        crate::repro_sandbox::create_issue_repro_sandbox,
+        crate::repro_sandbox::list_issue_repro_sandboxes,
+        crate::repro_sandbox::link_issue_repro_sandbox_session,
+        crate::repro_sandbox::delete_issue_repro_sandbox,
         crate::git::get_github_pull_requests,
         crate::git::get_github_pull_request_diff,
+        crate::git::get_github_pull_request_files,
         crate::git::get_github_pull_request_comments,
+        crate::git::checkout_github_pull_request,
         crate::git::list_git_branches,
+        crate::git::list_git_worktrees,
+        crate::git::commit_agent_batch,
+        crate::git::review_agent_branch,
+        crate::git::squash_merge_agent_branch,
+        crate::git::commit_turn_changes,
         crate::git::checkout_git_branch,
         crate::git::create_git_branch,
         crate::git::create_git_branch_from_branch,
         crate::git::create_git_branch_from_commit,
         crate::git::delete_git_branch,
+        crate::git::list_git_tags,
+        crate::git::list_git_submodules,
+        crate::git::update_git_submodules,
+        crate::git::create_git_tag,
+        crate::git::delete_git_tag,
+        crate::git::push_git_tag,
         crate::git::rename_git_branch,
         crate::git::merge_git_branch,
         crate::git::rebase_git_branch,
+        crate::git::get_git_operation_state,
+        crate::git::continue_git_operation,
+        crate::git::abort_git_operation,
+        crate::git::skip_git_operation,
+        crate::git::get_git_conflict_files,
+        crate::git::get_git_conflict_versions,
+        crate::git::resolve_git_conflict,
+        crate::git::stash_git_changes,
+        crate::git::list_git_stashes,
+        crate::git::apply_git_stash,
+        crate::git::pop_git_stash,
+        crate::git::drop_git_stash,
         crate::git::get_git_branch_compare_commits,
         crate::git::get_git_branch_diff_between_branches,
         crate::git::get_git_branch_file_diff_between_branches,
@@ -232,6 +339,9 @@ pub(crate) fn invoke_handler(
         crate::prompts::prompts_update,
         crate::prompts::prompts_delete,
         crate::prompts::prompts_move,
+        crate::prompts::prompts_export,
+        crate::prompts::prompts_import,
+        crate::prompts::prompts_render,
         crate::prompts::prompts_workspace_dir,
         crate::prompts::prompts_global_dir,
         // Note cards
@@ -242,6 +352,7 @@ pub(crate) fn invoke_handler(
         crate::note_cards::note_card_archive,
         crate::note_cards::note_card_restore,
         crate::note_cards::note_card_delete,
+        crate::note_cards::note_card_backlinks,
         // Project memory
         crate::project_memory::commands::project_memory_get_settings,
         crate::project_memory::commands::project_memory_update_settings,
@@ -258,6 +369,7 @@ pub(crate) fn invoke_handler(
         crate::terminal::terminal_write,
         crate::terminal::terminal_resize,
         crate::terminal::terminal_close,
+        crate::terminal::terminal_snapshot,
         crate::runtime_log::runtime_log_detect_profiles,
         crate::runtime_log::runtime_log_start,
         crate::runtime_log::runtime_log_stop,
@@ -270,12 +382,42 @@ pub(crate) fn invoke_handler(
         crate::session_management::archive_workspace_sessions,
         crate::session_management::unarchive_workspace_sessions,
         crate::session_management::delete_workspace_sessions,
+        crate::session_housekeeping::analyze_session_housekeeping,
+        crate::session_housekeeping::run_session_housekeeping,
+        crate::session_checkpoints::checkpoint_create,
+        crate::session_checkpoints::checkpoint_list,
+        crate::session_checkpoints::checkpoint_restore,
+        crate::session_checkpoints::checkpoint_delete,
         crate::session_management::list_workspace_session_folders,
         crate::session_management::create_workspace_session_folder,
         crate::session_management::rename_workspace_session_folder,
         crate::session_management::move_workspace_session_folder,
         crate::session_management::delete_workspace_session_folder,
         crate::session_management::assign_workspace_session_folder,
+        crate::session_issues::export_session_to_issue,
+        crate::session_issues::create_session_issue,
+        crate::session_bookmarks::bookmark_add,
+        crate::session_bookmarks::bookmark_remove,
+        crate::session_bookmarks::bookmark_list,
+        crate::session_context_pack::get_session_context_pack,
+        crate::session_context_pack::rerun_session_from_context_pack,
+        crate::privacy_zones::check_privacy_zones,
+        crate::sandbox_report::list_sandbox_reports,
+        crate::session_stats::session_stats,
+        crate::session_cost::get_session_cost,
+        crate::prompt_ab_test::ab_test_run,
+        crate::prompt_ab_test::ab_test_results,
+        crate::saved_filters::list_saved_filters,
+        crate::saved_filters::save_saved_filter,
+        crate::saved_filters::delete_saved_filter,
+        crate::saved_filters::evaluate_saved_filter,
+        crate::outbound_notifications::get_outbound_notification_settings,
+        crate::outbound_notifications::update_outbound_notification_settings,
+        crate::outbound_notifications::send_outbound_notification_test,
+        crate::outbound_notifications::notify_task_finished,
+        crate::flaky_tests::record_test_run,
+        crate::flaky_tests::analyze_flaky_tests,
+        crate::flaky_tests::flaky_test_prompt,
         // Dictation
         crate::dictation::dictation_model_status,
         crate::dictation::dictation_download_model,
@@ -288,12 +430,15 @@ pub(crate) fn invoke_handler(
         // Local usage
         crate::local_usage::local_usage_snapshot,
         crate::local_usage::local_usage_statistics,
+        crate::local_usage::local_usage_query,
         // Client storage
         crate::client_storage::client_store_read,
         crate::client_storage::client_store_write,
         crate::client_storage::client_store_patch,
         crate::client_storage::client_panel_lock_password_read,
         crate::client_storage::client_panel_lock_password_write,
+        crate::client_storage::drafts_save,
+        crate::client_storage::drafts_get,
         // Input history
         crate::input_history::input_history_read,
         crate::input_history::input_history_record,
@@ -314,10 +459,16 @@ pub(crate) fn invoke_handler(
         crate::vendors::vendor_update_codex_provider,
         crate::vendors::vendor_delete_codex_provider,
         crate::vendors::vendor_switch_codex_provider,
+        crate::vendors::vendor_check_provider_health,
+        crate::vendors::vendor_export,
+        crate::vendors::vendor_import,
         crate::vendors::vendor_get_gemini_settings,
         crate::vendors::vendor_save_gemini_settings,
         crate::vendors::vendor_gemini_preflight,
         // Open paths
+        crate::linux_file_drop::resolve_dropped_paths,
+        crate::linux_file_drop::install_file_manager_integration,
+        crate::linux_file_drop::remove_file_manager_integration,
         crate::get_pending_open_paths
     ]
 }
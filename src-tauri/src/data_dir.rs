@@ -0,0 +1,553 @@
+//! Relocating the app data to a user-chosen directory: the app home (config,
+//! client stores, caches and archives) and, under `app-data/`, the Tauri app
+//! data dir (`settings.json`, `workspaces.json`, runtime state). The default
+//! home keeps a pointer file that `app_paths` follows, so switching is a
+//! single atomic write made only after the copy has been verified. While the
+//! configured directory is unreachable, reads and writes of app data fail
+//! instead of falling back to a second copy.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+
+use crate::app_paths;
+use crate::storage::{read_json_file, write_string_atomically};
+
+/// Tauri- and webview-owned entries of the app data dir that stay where the
+/// platform expects them.
+const PINNED_APP_DATA_ENTRIES: &[&str] = &[".window-state.json", "EBWebView"];
+/// Previous data directories, kept in the default home, that are removed on
+/// the next launch.
+const STAGED_REMOVALS_FILENAME: &str = "data-dir-removals.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DataDirStatus {
+    /// Directory data is read from and written to right now.
+    pub(crate) data_path: String,
+    pub(crate) default_path: String,
+    /// Relocated directory recorded in the pointer file, if any.
+    pub(crate) configured_path: Option<String>,
+    /// False when the configured directory is unreachable (e.g. an
+    /// unmounted drive). App data cannot be read or written until it is
+    /// back or the data directory is reset to the default.
+    pub(crate) available: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DataDirMigrationReport {
+    pub(crate) previous_path: String,
+    pub(crate) data_path: String,
+    pub(crate) file_count: usize,
+    pub(crate) total_bytes: u64,
+    /// Set when the data was moved but the old copy could not be staged for
+    /// removal; the new location is in use either way.
+    pub(crate) cleanup_error: Option<String>,
+}
+
+fn data_dir_status(default_home: &Path) -> DataDirStatus {
+    let configured = app_paths::configured_data_dir(default_home);
+    let available = configured.as_ref().map_or(true, |path| path.is_dir());
+    let data_path = configured
+        .clone()
+        .unwrap_or_else(|| default_home.to_path_buf());
+    DataDirStatus {
+        data_path: data_path.to_string_lossy().to_string(),
+        default_path: default_home.to_string_lossy().to_string(),
+        configured_path: configured.map(|path| path.to_string_lossy().to_string()),
+        available,
+    }
+}
+
+/// Canonical form of `path`, resolving the parent when the path itself does
+/// not exist yet.
+fn normalize_path(path: &Path) -> PathBuf {
+    if let Ok(resolved) = path.canonicalize() {
+        return resolved;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => normalize_path(parent).join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// The Tauri app data dir, followed into the relocated data directory.
+pub(crate) fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let tauri_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    Ok(app_paths::resolve_app_data_dir(&tauri_data_dir))
+}
+
+/// Moves the Tauri app data into a data directory relocated before it held
+/// `app-data/`. Runs at startup, before anything reads the app data.
+pub(crate) fn adopt_app_data(app: &AppHandle) -> Result<(), String> {
+    let tauri_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let default_home = app_paths::default_app_home_dir()?;
+    adopt_app_data_into(&default_home, &tauri_data_dir)
+}
+
+fn adopt_app_data_into(default_home: &Path, tauri_data_dir: &Path) -> Result<(), String> {
+    let Some(data_dir) = app_paths::configured_data_dir(default_home).filter(|dir| dir.is_dir())
+    else {
+        return Ok(());
+    };
+    let app_data = data_dir.join(app_paths::RELOCATED_APP_DATA_DIR_NAME);
+    if app_data.exists() || data_entries(tauri_data_dir)?.is_empty() {
+        return Ok(());
+    }
+    if let Err(error) = copy_data(tauri_data_dir, &app_data) {
+        let _ = fs::remove_dir_all(&app_data);
+        return Err(error);
+    }
+    remove_data_entries(tauri_data_dir)
+}
+
+/// Entries of `dir` that belong to the app data, skipping the pointer and
+/// staged-removal files the default home keeps for itself, the relocated app
+/// data (moved on its own) and entries pinned to the Tauri app data dir.
+fn data_entries(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error.to_string()),
+    };
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|error| error.to_string())?.path();
+        let skipped = app_paths::is_data_dir_pointer(&path)
+            || path.file_name().is_some_and(|name| {
+                name == app_paths::RELOCATED_APP_DATA_DIR_NAME
+                    || name == STAGED_REMOVALS_FILENAME
+                    || PINNED_APP_DATA_ENTRIES.iter().any(|pinned| name == *pinned)
+            });
+        if !skipped {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+fn file_digest(path: &Path) -> Result<[u8; 32], String> {
+    let mut file = fs::File::open(path).map_err(|error| error.to_string())?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).map_err(|error| error.to_string())?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Size and digest of every file under `root`, keyed by relative path.
+/// Symlinks are followed, matching how `copy_dir_recursive` copies them.
+fn collect_file_digests(
+    root: &Path,
+    dir: &Path,
+    files: &mut BTreeMap<PathBuf, (u64, [u8; 32])>,
+) -> Result<(), String> {
+    for path in data_entries(dir)? {
+        let metadata = fs::metadata(&path).map_err(|error| error.to_string())?;
+        if metadata.is_dir() {
+            collect_file_digests(root, &path, files)?;
+        } else if metadata.is_file() {
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            files.insert(relative, (metadata.len(), file_digest(&path)?));
+        }
+    }
+    Ok(())
+}
+
+fn copy_data(source: &Path, destination: &Path) -> Result<(usize, u64), String> {
+    for path in data_entries(source)? {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        app_paths::copy_dir_recursive(&path, &destination.join(name))?;
+    }
+    let mut expected = BTreeMap::new();
+    collect_file_digests(source, source, &mut expected)?;
+    let mut copied = BTreeMap::new();
+    collect_file_digests(destination, destination, &mut copied)?;
+    if let Some((path, _)) = expected
+        .iter()
+        .find(|(path, entry)| copied.get(*path) != Some(entry))
+    {
+        return Err(format!(
+            "Verification failed: {} differs after copying.",
+            path.display()
+        ));
+    }
+    let total_bytes = expected.values().map(|(size, _)| size).sum();
+    Ok((expected.len(), total_bytes))
+}
+
+fn remove_data_entries(dir: &Path) -> Result<(), String> {
+    for path in data_entries(dir)? {
+        let result = if fs::symlink_metadata(&path).is_ok_and(|metadata| metadata.is_dir()) {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        result.map_err(|error| format!("{}: {error}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Where the Tauri app data of the data directory `home` lives.
+fn app_data_location(home: &Path, default_home: &Path, tauri_data_dir: &Path) -> PathBuf {
+    if home == default_home {
+        tauri_data_dir.to_path_buf()
+    } else {
+        home.join(app_paths::RELOCATED_APP_DATA_DIR_NAME)
+    }
+}
+
+/// Moves the data in `current` to `target` (the default home when `None`):
+/// copy, verify every file by size and SHA-256, then switch the pointer.
+/// The Tauri app data moves along, between `tauri_data_dir` and `app-data/`
+/// in the relocated directory. Any failure before the switch removes the
+/// partial copy and leaves the current directory in use. Running components
+/// may still write to the old copy until the app restarts, so it is only
+/// staged here and removed on the next launch.
+fn relocate_data_dir(
+    default_home: &Path,
+    tauri_data_dir: &Path,
+    current: &Path,
+    target: Option<&Path>,
+) -> Result<DataDirMigrationReport, String> {
+    let destination = normalize_path(target.unwrap_or(default_home));
+    let current = normalize_path(current);
+    let default_home = normalize_path(default_home);
+    let tauri_data_dir = normalize_path(tauri_data_dir);
+    if target.is_some_and(|path| !path.is_absolute()) {
+        return Err("Data directory must be an absolute path.".to_string());
+    }
+    if destination == current {
+        return Err("Data is already stored in that directory.".to_string());
+    }
+    if destination.starts_with(&current)
+        || current.starts_with(&destination)
+        || destination.starts_with(&tauri_data_dir)
+    {
+        return Err("Data directory cannot be inside the current one, or contain it.".to_string());
+    }
+    if destination.is_file() {
+        return Err(format!("{} is a file.", destination.display()));
+    }
+    if staged_removals(&default_home)?.contains(&destination) {
+        return Err(format!(
+            "{} is removed on the next launch; restart the app before moving data back into it.",
+            destination.display()
+        ));
+    }
+    let destination_app_data = app_data_location(&destination, &default_home, &tauri_data_dir);
+    let occupied = !data_entries(&destination)?.is_empty()
+        || (destination != default_home && destination_app_data.exists());
+    if occupied {
+        return Err(format!(
+            "{} is not empty; choose an empty or new directory.",
+            destination.display()
+        ));
+    }
+    let current_app_data = app_data_location(&current, &default_home, &tauri_data_dir);
+
+    let created = !destination.exists();
+    let switched = fs::create_dir_all(&destination)
+        .map_err(|error| error.to_string())
+        .and_then(|_| {
+            let (home_files, home_bytes) = copy_data(&current, &destination)?;
+            let (app_files, app_bytes) = copy_data(&current_app_data, &destination_app_data)?;
+            Ok((home_files + app_files, home_bytes + app_bytes))
+        })
+        .and_then(|totals| {
+            let pointer = (destination != default_home).then_some(destination.as_path());
+            app_paths::write_data_dir_pointer(&default_home, pointer)?;
+            Ok(totals)
+        });
+    let (file_count, total_bytes) = match switched {
+        Ok(totals) => totals,
+        Err(error) => {
+            let rollback = if created {
+                fs::remove_dir_all(&destination).map_err(|error| error.to_string())
+            } else if destination == default_home {
+                remove_data_entries(&destination)
+                    .and_then(|_| remove_copied_entries(&current_app_data, &destination_app_data))
+            } else {
+                remove_data_entries(&destination).and_then(|_| {
+                    match fs::remove_dir_all(&destination_app_data) {
+                        Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                            Err(error.to_string())
+                        }
+                        _ => Ok(()),
+                    }
+                })
+            };
+            return Err(match rollback {
+                Ok(()) => format!("{error} The partial copy was removed."),
+                Err(rollback_error) => {
+                    format!("{error} Removing the partial copy also failed: {rollback_error}")
+                }
+            });
+        }
+    };
+
+    let cleanup = stage_removal(&default_home, &current);
+    Ok(DataDirMigrationReport {
+        previous_path: current.to_string_lossy().to_string(),
+        data_path: destination.to_string_lossy().to_string(),
+        file_count,
+        total_bytes,
+        cleanup_error: cleanup.err(),
+    })
+}
+
+fn staged_removals(default_home: &Path) -> Result<Vec<PathBuf>, String> {
+    Ok(read_json_file(&default_home.join(STAGED_REMOVALS_FILENAME))?.unwrap_or_default())
+}
+
+fn stage_removal(default_home: &Path, previous: &Path) -> Result<(), String> {
+    let mut staged = staged_removals(default_home)?;
+    if !staged.iter().any(|path| path == previous) {
+        staged.push(previous.to_path_buf());
+    }
+    let data = serde_json::to_string_pretty(&staged).map_err(|error| error.to_string())?;
+    write_string_atomically(&default_home.join(STAGED_REMOVALS_FILENAME), &data)
+}
+
+/// Removes the data directories a relocation left behind. Runs at startup,
+/// before anything reads the app data, so nothing still writes to them.
+pub(crate) fn remove_staged_data_dirs(app: &AppHandle) -> Result<(), String> {
+    let tauri_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let default_home = app_paths::default_app_home_dir()?;
+    remove_staged_data_dirs_in(
+        &normalize_path(&default_home),
+        &normalize_path(&tauri_data_dir),
+    )
+}
+
+fn remove_staged_data_dirs_in(default_home: &Path, tauri_data_dir: &Path) -> Result<(), String> {
+    let staged = staged_removals(default_home)?;
+    if staged.is_empty() {
+        return Ok(());
+    }
+    let current = normalize_path(
+        &app_paths::configured_data_dir(default_home).unwrap_or_else(|| default_home.to_path_buf()),
+    );
+    let mut result = Ok(());
+    for previous in staged {
+        // In use again, e.g. the default home after resetting an unreachable
+        // data directory.
+        if normalize_path(&previous) == current {
+            continue;
+        }
+        let removed = if previous == default_home {
+            remove_data_entries(default_home).and_then(|_| remove_data_entries(tauri_data_dir))
+        } else {
+            match fs::remove_dir_all(&previous) {
+                Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                    Err(format!("{}: {error}", previous.display()))
+                }
+                _ => Ok(()),
+            }
+        };
+        if let Err(error) = removed {
+            result = Err(error);
+        }
+    }
+    fs::remove_file(default_home.join(STAGED_REMOVALS_FILENAME))
+        .map_err(|error| error.to_string())?;
+    result
+}
+
+/// Removes from `destination` the entries that were copied from `source`,
+/// leaving anything else in it alone.
+fn remove_copied_entries(source: &Path, destination: &Path) -> Result<(), String> {
+    for path in data_entries(source)? {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let copied = destination.join(name);
+        let result = if fs::symlink_metadata(&copied).is_ok_and(|metadata| metadata.is_dir()) {
+            fs::remove_dir_all(&copied)
+        } else {
+            fs::remove_file(&copied)
+        };
+        match result {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                return Err(format!("{}: {error}", copied.display()))
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn get_data_dir_status() -> Result<DataDirStatus, String> {
+    Ok(data_dir_status(&app_paths::default_app_home_dir()?))
+}
+
+/// Moves the app data to `target`, or back to the default home when `target`
+/// is omitted. The app should be restarted afterwards so every component
+/// picks up the new location. When the configured directory is unreachable
+/// nothing can be copied, so only resetting to the default home is allowed;
+/// it starts from empty data there.
+#[tauri::command]
+pub(crate) async fn migrate_data_dir(
+    target: Option<String>,
+    app: AppHandle,
+) -> Result<DataDirMigrationReport, String> {
+    let default_home = app_paths::default_app_home_dir()?;
+    let tauri_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let target = target
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    let current = match app_paths::app_home_dir() {
+        Ok(current) => current,
+        Err(error) if target.is_none() => {
+            let previous = app_paths::configured_data_dir(&default_home).unwrap_or_default();
+            app_paths::write_data_dir_pointer(&default_home, None)?;
+            return Ok(DataDirMigrationReport {
+                previous_path: previous.to_string_lossy().to_string(),
+                data_path: default_home.to_string_lossy().to_string(),
+                file_count: 0,
+                total_bytes: 0,
+                cleanup_error: Some(error),
+            });
+        }
+        Err(error) => return Err(error),
+    };
+    tokio::task::spawn_blocking(move || {
+        relocate_data_dir(&default_home, &tauri_data_dir, &current, target.as_deref())
+    })
+    .await
+    .map_err(|error| error.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn relocates_data_and_moves_it_back() {
+        let base = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        let default_home = base.join(".ccgui");
+        let tauri_data_dir = base.join("app-support").join("ccgui");
+        let target = base.join("synced").join("ccgui");
+        fs::create_dir_all(default_home.join("client")).expect("create client dir");
+        fs::create_dir_all(&tauri_data_dir).expect("create tauri data dir");
+        fs::write(tauri_data_dir.join("settings.json"), "{}").expect("write settings");
+        fs::write(tauri_data_dir.join(".window-state.json"), "{}").expect("write window");
+        fs::write(default_home.join("config.json"), "{\"theme\":\"dark\"}").expect("write config");
+        fs::write(default_home.join("client").join("app.json"), "{}").expect("write store");
+
+        let occupied = base.join("occupied");
+        fs::create_dir_all(&occupied).expect("create occupied dir");
+        fs::write(occupied.join("other.txt"), "keep").expect("write other");
+        assert!(relocate_data_dir(
+            &default_home,
+            &tauri_data_dir,
+            &default_home,
+            Some(&occupied)
+        )
+        .is_err());
+        assert!(relocate_data_dir(
+            &default_home,
+            &tauri_data_dir,
+            &default_home,
+            Some(Path::new("relative"))
+        )
+        .is_err());
+        assert_eq!(
+            fs::read_to_string(occupied.join("other.txt")).expect("read other"),
+            "keep"
+        );
+
+        let report =
+            relocate_data_dir(&default_home, &tauri_data_dir, &default_home, Some(&target))
+                .expect("relocate");
+        assert_eq!(report.file_count, 3);
+        assert_eq!(report.cleanup_error, None);
+        assert_eq!(
+            fs::read_to_string(target.join("config.json")).expect("read moved config"),
+            "{\"theme\":\"dark\"}"
+        );
+        assert!(default_home.join("config.json").exists());
+        assert!(relocate_data_dir(&default_home, &tauri_data_dir, &target, None).is_err());
+        remove_staged_data_dirs_in(&normalize_path(&default_home), &tauri_data_dir)
+            .expect("remove staged");
+        assert!(!default_home.join("config.json").exists());
+        assert!(target.join("app-data").join("settings.json").exists());
+        assert!(!tauri_data_dir.join("settings.json").exists());
+        assert!(tauri_data_dir.join(".window-state.json").exists());
+        let status = data_dir_status(&default_home);
+        assert_eq!(status.data_path, normalize_path(&target).to_string_lossy());
+        assert!(status.available);
+
+        let target = normalize_path(&target);
+        relocate_data_dir(&default_home, &tauri_data_dir, &target, None).expect("move back");
+        assert!(default_home.join("client").join("app.json").exists());
+        assert!(tauri_data_dir.join("settings.json").exists());
+        assert!(target.exists());
+        remove_staged_data_dirs_in(&normalize_path(&default_home), &tauri_data_dir)
+            .expect("remove staged again");
+        assert!(!target.exists());
+        assert!(default_home.join("client").join("app.json").exists());
+        assert_eq!(data_dir_status(&default_home).configured_path, None);
+
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn adopts_app_data_into_a_directory_relocated_without_it() {
+        let base = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        let default_home = base.join(".ccgui");
+        let tauri_data_dir = base.join("app-support").join("ccgui");
+        let target = base.join("synced").join("ccgui");
+        fs::create_dir_all(&default_home).expect("create default home");
+        fs::create_dir_all(&target).expect("create target");
+        fs::create_dir_all(&tauri_data_dir).expect("create tauri data dir");
+        fs::write(tauri_data_dir.join("workspaces.json"), "[]").expect("write workspaces");
+        app_paths::write_data_dir_pointer(&default_home, Some(&target)).expect("pointer");
+
+        adopt_app_data_into(&default_home, &tauri_data_dir).expect("adopt");
+        assert_eq!(
+            fs::read_to_string(target.join("app-data").join("workspaces.json"))
+                .expect("read adopted workspaces"),
+            "[]"
+        );
+        assert!(!tauri_data_dir.join("workspaces.json").exists());
+
+        fs::write(tauri_data_dir.join("workspaces.json"), "[1]").expect("write stale");
+        adopt_app_data_into(&default_home, &tauri_data_dir).expect("adopt again");
+        assert_eq!(
+            fs::read_to_string(target.join("app-data").join("workspaces.json"))
+                .expect("read kept workspaces"),
+            "[]"
+        );
+
+        let _ = fs::remove_dir_all(base);
+    }
+}
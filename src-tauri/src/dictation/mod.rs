@@ -1,9 +1,3 @@
-#[cfg(not(target_os = "windows"))]
-#[path = "real.rs"]
-mod imp;
+mod real;
 
-#[cfg(target_os = "windows")]
-#[path = "stub.rs"]
-mod imp;
-
-pub(crate) use imp::*;
+pub(crate) use real::*;
@@ -21,6 +21,11 @@ use objc2_av_foundation::{AVAuthorizationStatus, AVCaptureDevice, AVMediaTypeAud
 
 const DEFAULT_MODEL_ID: &str = "base";
 const MAX_CAPTURE_SECONDS: u32 = 120;
+/// How often interim text is produced while listening.
+const PARTIAL_INTERVAL: Duration = Duration::from_millis(1500);
+/// Trailing audio transcribed for interim text; Whisper decodes 30s at most.
+const PARTIAL_WINDOW_SECONDS: u32 = 30;
+const PARTIAL_MIN_SECONDS: u32 = 1;
 
 #[cfg(target_os = "macos")]
 static MIC_PERMISSION_REQUESTED: AtomicBool = AtomicBool::new(false);
@@ -104,7 +109,49 @@ async fn request_microphone_permission_with_completion(app: &AppHandle) -> Resul
     }
 }
 
-#[cfg(not(target_os = "macos"))]
+/// Microphone consent stores checked on Windows; desktop (unpackaged) apps
+/// need both the global switch and the "let desktop apps" switch enabled.
+#[cfg(target_os = "windows")]
+const WINDOWS_MICROPHONE_CONSENT_KEYS: &[&str] = &[
+    r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone",
+    r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone",
+    r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone\NonPackaged",
+];
+
+/// Whether a `reg query ... /v Value` output reports the consent as denied.
+#[cfg(target_os = "windows")]
+fn windows_consent_denied(output: &str) -> bool {
+    output.lines().any(|line| {
+        let mut parts = line.split_whitespace();
+        parts.next() == Some("Value")
+            && parts.next() == Some("REG_SZ")
+            && parts.next() == Some("Deny")
+    })
+}
+
+/// Checks the Windows microphone privacy settings. There is no prompt for
+/// desktop apps, so a denied setting is reported as an error explaining where
+/// to re-enable it. A key that cannot be read counts as allowed.
+#[cfg(target_os = "windows")]
+async fn request_microphone_permission(_app: &AppHandle) -> Result<bool, String> {
+    for key in WINDOWS_MICROPHONE_CONSENT_KEYS {
+        let output = crate::utils::async_command("reg")
+            .args(["query", key, "/v", "Value"])
+            .output()
+            .await;
+        let Ok(output) = output else {
+            continue;
+        };
+        if output.status.success()
+            && windows_consent_denied(&String::from_utf8_lossy(&output.stdout))
+        {
+            return Err("Microphone access is turned off in Windows privacy settings. Open Settings > Privacy & security > Microphone, enable \"Microphone access\" and \"Let desktop apps access your microphone\", then try again.".to_string());
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
 async fn request_microphone_permission(_app: &AppHandle) -> Result<bool, String> {
     // On non-macOS platforms, assume permission is granted
     // (Linux doesn't have the same permission model)
@@ -116,6 +163,7 @@ struct DictationModelInfo {
     filename: &'static str,
     url: &'static str,
     sha256: &'static str,
+    multilingual: bool,
 }
 
 const MODEL_CATALOG: &[DictationModelInfo] = &[
@@ -124,30 +172,35 @@ const MODEL_CATALOG: &[DictationModelInfo] = &[
         filename: "ggml-tiny.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
         sha256: "be07e048e1e599ad46341c8d2a135645097a538221678b7acdd1b1919c6e1b21",
+        multilingual: true,
     },
     DictationModelInfo {
         id: "base",
         filename: "ggml-base.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin",
         sha256: "60ed5bc3dd14eea856493d334349b405782ddcaf0028d4b5df4088345fba2efe",
+        multilingual: true,
     },
     DictationModelInfo {
         id: "small",
         filename: "ggml-small.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
         sha256: "1be3a9b2063867b937e64e2ec7483364a79917e157fa98c5d94b5c1fffea987b",
+        multilingual: true,
     },
     DictationModelInfo {
         id: "medium",
         filename: "ggml-medium.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin",
         sha256: "6c14d5adee5f86394037b4e4e8b59f1673b6cee10e3cf0b11bbdbee79c156208",
+        multilingual: true,
     },
     DictationModelInfo {
         id: "large-v3",
         filename: "ggml-large-v3.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3.bin",
         sha256: "64d182b440b98d5203c4f9bd541544d84c605196c4f7b845dfa11fb23594d1e2",
+        multilingual: true,
     },
 ];
 
@@ -178,6 +231,23 @@ pub(crate) struct DictationModelStatus {
     pub(crate) path: Option<String>,
 }
 
+/// One catalog entry with its own download state, so the settings view can
+/// show every model rather than only the selected one.
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct DictationModelVariant {
+    pub(crate) id: String,
+    pub(crate) multilingual: bool,
+    pub(crate) state: DictationModelState,
+    pub(crate) progress: Option<DictationDownloadProgress>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct DictationModelStatusReport {
+    #[serde(flatten)]
+    pub(crate) status: DictationModelStatus,
+    pub(crate) models: Vec<DictationModelVariant>,
+}
+
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum DictationSessionState {
@@ -196,6 +266,28 @@ pub(crate) enum DictationEvent {
     Canceled { message: String },
 }
 
+/// Interim text emitted as `dictation-partial` while listening. `stable` is
+/// set once two consecutive passes agree.
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct DictationPartial {
+    pub(crate) text: String,
+    pub(crate) stable: bool,
+}
+
+/// The background interim-transcription task of a session; dropping it (on
+/// stop, cancel, or a microphone error) ends the task.
+pub(crate) struct PartialTranscription {
+    stop: Arc<AtomicBool>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for PartialTranscription {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.task.abort();
+    }
+}
+
 pub(crate) struct DictationSessionHandle {
     pub(crate) stop: mpsc::Sender<()>,
     pub(crate) stopped: oneshot::Receiver<()>,
@@ -203,6 +295,8 @@ pub(crate) struct DictationSessionHandle {
     pub(crate) sample_rate: u32,
     pub(crate) model_id: String,
     pub(crate) preferred_language: Option<String>,
+    pub(crate) language: Option<String>,
+    pub(crate) partial: Option<PartialTranscription>,
 }
 
 pub(crate) struct DictationState {
@@ -212,6 +306,8 @@ pub(crate) struct DictationState {
     pub(crate) session_state: DictationSessionState,
     pub(crate) session: Option<DictationSessionHandle>,
     pub(crate) processing_cancel: Option<Arc<AtomicBool>>,
+    /// Model being transcribed with while `session_state` is `Processing`.
+    pub(crate) processing_model_id: Option<String>,
     pub(crate) cached_context: Option<CachedWhisperContext>,
 }
 
@@ -229,14 +325,14 @@ impl Default for DictationState {
             session_state: DictationSessionState::Idle,
             session: None,
             processing_cancel: None,
+            processing_model_id: None,
             cached_context: None,
         }
     }
 }
 
 fn model_dir(app: &AppHandle) -> PathBuf {
-    app.path()
-        .app_data_dir()
+    crate::data_dir::app_data_dir(app)
         .unwrap_or_else(|_| std::env::current_dir().unwrap_or_else(|_| ".".into()))
         .join("models")
         .join("whisper")
@@ -286,6 +382,115 @@ fn emit_event(app: &AppHandle, event: DictationEvent) {
     let _ = app.emit("dictation-event", event);
 }
 
+fn emit_partial(app: &AppHandle, partial: DictationPartial) {
+    let _ = app.emit("dictation-partial", partial);
+}
+
+/// The Whisper context for `model_id`, loaded once and cached until another
+/// model is used or the model is removed.
+async fn load_whisper_context(
+    app: &AppHandle,
+    model_id: &str,
+) -> Result<Arc<WhisperContext>, String> {
+    let state_handle = app.state::<AppState>();
+    {
+        let dictation = state_handle.dictation.lock().await;
+        if let Some(cached) = dictation
+            .cached_context
+            .as_ref()
+            .filter(|cached| cached.model_id == model_id)
+        {
+            return Ok(Arc::clone(&cached.context));
+        }
+    }
+    let path = model_path(app, model_id)?.to_string_lossy().into_owned();
+    let context = tokio::task::spawn_blocking(move || {
+        WhisperContext::new_with_params(&path, WhisperContextParameters::default())
+    })
+    .await
+    .map_err(|error| format!("Failed to load Whisper model: {error}"))?
+    .map_err(|error| format!("Failed to load Whisper model: {error}"))?;
+    let context = Arc::new(context);
+    let mut dictation = state_handle.dictation.lock().await;
+    dictation.cached_context = Some(CachedWhisperContext {
+        model_id: model_id.to_string(),
+        context: Arc::clone(&context),
+    });
+    Ok(context)
+}
+
+/// Re-transcribes the trailing window of captured audio every
+/// `PARTIAL_INTERVAL`. Passes run one at a time and the next one waits at
+/// least as long as the last took, so a slow machine skips ticks instead of
+/// queueing them. Errors are left to the final pass on stop.
+fn spawn_partial_transcription(
+    app: AppHandle,
+    audio: Arc<Mutex<Vec<f32>>>,
+    sample_rate: u32,
+    model_id: String,
+    language: Option<String>,
+    preferred_language: Option<String>,
+) -> PartialTranscription {
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = Arc::clone(&stop);
+    let task = tokio::spawn(async move {
+        let window_samples = (sample_rate as usize).saturating_mul(PARTIAL_WINDOW_SECONDS as usize);
+        let min_samples = (sample_rate as usize).saturating_mul(PARTIAL_MIN_SECONDS as usize);
+        let mut delay = PARTIAL_INTERVAL;
+        let mut last_len = 0usize;
+        let mut last_text = String::new();
+        loop {
+            tokio::time::sleep(delay).await;
+            if stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            let window = {
+                let guard = audio.lock().unwrap();
+                if guard.len() < min_samples || guard.len() == last_len {
+                    None
+                } else {
+                    last_len = guard.len();
+                    Some(guard[guard.len().saturating_sub(window_samples)..].to_vec())
+                }
+            };
+            let Some(window) = window else {
+                delay = PARTIAL_INTERVAL;
+                continue;
+            };
+            let Ok(context) = load_whisper_context(&app, &model_id).await else {
+                break;
+            };
+            let started = Instant::now();
+            let language = language.clone();
+            let preferred = preferred_language.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                transcribe_audio(window, sample_rate, &context, language, preferred)
+            })
+            .await;
+            delay = PARTIAL_INTERVAL.max(started.elapsed());
+            if stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            let Ok(Ok(text)) = result else {
+                continue;
+            };
+            if text.is_empty() {
+                continue;
+            }
+            let stable = text == last_text;
+            emit_partial(
+                &app,
+                DictationPartial {
+                    text: text.clone(),
+                    stable,
+                },
+            );
+            last_text = text;
+        }
+    });
+    PartialTranscription { stop, task }
+}
+
 async fn clear_processing_cancel(app: &AppHandle, cancel_flag: &Arc<AtomicBool>) -> bool {
     let state_handle = app.state::<AppState>();
     let mut dictation = state_handle.dictation.lock().await;
@@ -332,6 +537,62 @@ async fn resolve_model_id(state: &State<'_, AppState>, model_id: Option<String>)
     }
 }
 
+/// Whether an active dictation session records or transcribes with the model.
+fn model_in_use(dictation: &DictationState, model_id: &str) -> bool {
+    match dictation.session_state {
+        DictationSessionState::Idle => false,
+        DictationSessionState::Listening => dictation
+            .session
+            .as_ref()
+            .is_some_and(|session| session.model_id == model_id),
+        DictationSessionState::Processing => {
+            dictation.processing_model_id.as_deref() == Some(model_id)
+        }
+    }
+}
+
+fn model_variants(app: &AppHandle, dictation: &DictationState) -> Vec<DictationModelVariant> {
+    let dir = model_dir(app);
+    MODEL_CATALOG
+        .iter()
+        .map(|info| {
+            let downloading = dictation.model_status.state == DictationModelState::Downloading
+                && dictation.model_status.model_id == info.id;
+            let state = if downloading {
+                DictationModelState::Downloading
+            } else if dir.join(info.filename).exists() {
+                DictationModelState::Ready
+            } else {
+                DictationModelState::Missing
+            };
+            DictationModelVariant {
+                id: info.id.to_string(),
+                multilingual: info.multilingual,
+                state,
+                progress: if downloading {
+                    dictation.model_status.progress.clone()
+                } else {
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+/// Rejects language codes Whisper does not know before capture starts.
+fn normalize_language(language: Option<String>) -> Result<Option<String>, String> {
+    let Some(language) = language
+        .map(|language| language.trim().to_ascii_lowercase())
+        .filter(|language| !language.is_empty() && language != "auto")
+    else {
+        return Ok(None);
+    };
+    if get_lang_id(&language).is_none() {
+        return Err(format!("Unsupported dictation language: {language}"));
+    }
+    Ok(Some(language))
+}
+
 async fn refresh_status(
     app: &AppHandle,
     state: &State<'_, AppState>,
@@ -371,9 +632,14 @@ pub(crate) async fn dictation_model_status(
     app: AppHandle,
     state: State<'_, AppState>,
     model_id: Option<String>,
-) -> Result<DictationModelStatus, String> {
+) -> Result<DictationModelStatusReport, String> {
     let model_id = resolve_model_id(&state, model_id).await;
-    Ok(refresh_status(&app, &state, &model_id).await)
+    let status = refresh_status(&app, &state, &model_id).await;
+    let models = {
+        let dictation = state.dictation.lock().await;
+        model_variants(&app, &dictation)
+    };
+    Ok(DictationModelStatusReport { status, models })
 }
 
 #[tauri::command]
@@ -714,6 +980,14 @@ pub(crate) async fn dictation_remove_model(
 ) -> Result<DictationModelStatus, String> {
     let model_id = resolve_model_id(&state, model_id).await;
     let model_path = model_path(&app, &model_id)?;
+    {
+        let dictation = state.dictation.lock().await;
+        if model_in_use(&dictation, &model_id) {
+            return Err(format!(
+                "Dictation model \"{model_id}\" is in use by the current dictation session. Stop or cancel dictation before removing it."
+            ));
+        }
+    }
     if model_path.exists() {
         tokio::fs::remove_file(&model_path)
             .await
@@ -739,10 +1013,37 @@ pub(crate) async fn dictation_remove_model(
 #[tauri::command]
 pub(crate) async fn dictation_start(
     preferred_language: Option<String>,
+    language: Option<String>,
+    model_id: Option<String>,
+    partial_results: Option<bool>,
     app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<DictationSessionState, String> {
-    let model_id = resolve_model_id(&state, None).await;
+    let language = match normalize_language(language) {
+        Ok(language) => language,
+        Err(message) => {
+            emit_event(
+                &app,
+                DictationEvent::Error {
+                    message: message.clone(),
+                },
+            );
+            return Err(message);
+        }
+    };
+    if let Some(requested) = model_id.as_deref() {
+        if model_info(requested).is_none() {
+            let message = format!("Unknown dictation model: {requested}");
+            emit_event(
+                &app,
+                DictationEvent::Error {
+                    message: message.clone(),
+                },
+            );
+            return Err(message);
+        }
+    }
+    let model_id = resolve_model_id(&state, model_id).await;
     let model_status = refresh_status(&app, &state, &model_id).await;
     if model_status.state != DictationModelState::Ready {
         let message = "Dictation model is not downloaded yet.".to_string();
@@ -837,6 +1138,16 @@ pub(crate) async fn dictation_start(
         }
     };
 
+    let partial = partial_results.unwrap_or(true).then(|| {
+        spawn_partial_transcription(
+            app.clone(),
+            Arc::clone(&audio),
+            sample_rate,
+            model_id.clone(),
+            language.clone(),
+            preferred_clone.clone(),
+        )
+    });
     {
         let mut dictation = state.dictation.lock().await;
         dictation.session_state = DictationSessionState::Listening;
@@ -847,6 +1158,8 @@ pub(crate) async fn dictation_start(
             sample_rate,
             model_id: model_id.clone(),
             preferred_language: preferred_clone,
+            language,
+            partial,
         });
     }
 
@@ -871,7 +1184,7 @@ pub(crate) async fn dictation_stop(
     state: State<'_, AppState>,
 ) -> Result<DictationSessionState, String> {
     let cancel_flag = Arc::new(AtomicBool::new(false));
-    let (audio, sample_rate, model_id, preferred_language, stopped, stop_tx) = {
+    let (audio, sample_rate, model_id, preferred_language, language, stopped, stop_tx) = {
         let mut dictation = state.dictation.lock().await;
         if dictation.session_state != DictationSessionState::Listening {
             let message = "Dictation is not currently listening.".to_string();
@@ -889,11 +1202,15 @@ pub(crate) async fn dictation_stop(
             .session
             .take()
             .ok_or_else(|| "Dictation session is unavailable.".to_string())?;
+        // No more interim passes once the final transcription starts.
+        drop(session.partial);
+        dictation.processing_model_id = Some(session.model_id.clone());
         (
             session.audio,
             session.sample_rate,
             session.model_id,
             session.preferred_language,
+            session.language,
             session.stopped,
             session.stop,
         )
@@ -921,89 +1238,27 @@ pub(crate) async fn dictation_stop(
             return;
         }
 
-        let state_handle = app_handle.state::<AppState>();
-        let cached_context = {
-            let dictation = state_handle.dictation.lock().await;
-            dictation
-                .cached_context
-                .as_ref()
-                .filter(|cached| cached.model_id == model_id)
-                .map(|cached| Arc::clone(&cached.context))
-        };
-
-        let context = if let Some(context) = cached_context {
-            context
-        } else {
-            let model_path = match model_path(&app_handle, &model_id) {
-                Ok(path) => path,
-                Err(error) => {
-                    emit_event(&app_handle, DictationEvent::Error { message: error });
-                    let mut dictation = state_handle.dictation.lock().await;
-                    dictation.session_state = DictationSessionState::Idle;
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::State {
-                            state: DictationSessionState::Idle,
-                        },
-                    );
-                    return;
-                }
-            };
-            let path = model_path.to_string_lossy().into_owned();
-            let created = tokio::task::spawn_blocking(move || {
-                WhisperContext::new_with_params(&path, WhisperContextParameters::default())
-            })
-            .await;
-            let context = match created {
-                Ok(Ok(context)) => context,
-                Ok(Err(error)) => {
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::Error {
-                            message: format!("Failed to load Whisper model: {error}"),
-                        },
-                    );
-                    let mut dictation = state_handle.dictation.lock().await;
-                    dictation.session_state = DictationSessionState::Idle;
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::State {
-                            state: DictationSessionState::Idle,
-                        },
-                    );
-                    return;
-                }
-                Err(error) => {
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::Error {
-                            message: format!("Failed to load Whisper model: {error}"),
-                        },
-                    );
-                    let mut dictation = state_handle.dictation.lock().await;
-                    dictation.session_state = DictationSessionState::Idle;
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::State {
-                            state: DictationSessionState::Idle,
-                        },
-                    );
-                    return;
-                }
-            };
-            let context = Arc::new(context);
-            let mut dictation = state_handle.dictation.lock().await;
-            dictation.cached_context = Some(CachedWhisperContext {
-                model_id: model_id.clone(),
-                context: Arc::clone(&context),
-            });
-            context
+        let context = match load_whisper_context(&app_handle, &model_id).await {
+            Ok(context) => context,
+            Err(message) => {
+                emit_event(&app_handle, DictationEvent::Error { message });
+                let state_handle = app_handle.state::<AppState>();
+                let mut dictation = state_handle.dictation.lock().await;
+                dictation.session_state = DictationSessionState::Idle;
+                emit_event(
+                    &app_handle,
+                    DictationEvent::State {
+                        state: DictationSessionState::Idle,
+                    },
+                );
+                return;
+            }
         };
 
         let preferred = preferred_language.clone();
 
         let result = tokio::task::spawn_blocking(move || {
-            transcribe_audio(samples, sample_rate, &context, preferred)
+            transcribe_audio(samples, sample_rate, &context, language, preferred)
         })
         .await;
 
@@ -1087,6 +1342,7 @@ pub(crate) async fn dictation_cancel(
             .session
             .take()
             .ok_or_else(|| "Dictation session is unavailable.".to_string())?;
+        drop(session.partial);
         (session.audio, session.stopped, session.stop)
     };
 
@@ -1332,6 +1588,7 @@ fn transcribe_audio(
     samples: Vec<f32>,
     sample_rate: u32,
     context: &WhisperContext,
+    language: Option<String>,
     preferred_language: Option<String>,
 ) -> Result<String, String> {
     if samples.is_empty() {
@@ -1394,7 +1651,9 @@ fn transcribe_audio(
     params.set_translate(false);
     params.set_no_context(true);
     params.set_single_segment(false);
-    let mut forced_language: Option<String> = None;
+    // An explicit language is always used; the preferred one only breaks ties.
+    let mut forced_language = language;
+    let preferred_language = preferred_language.filter(|_| forced_language.is_none());
     if let Some(preferred) = preferred_language.clone() {
         if let Some(pref_id) = get_lang_id(&preferred) {
             if state.pcm_to_mel(&audio, threads).is_ok() {
@@ -1414,7 +1673,7 @@ fn transcribe_audio(
     }
 
     if let Some(language) = forced_language.as_deref() {
-        // Use the preferred language only when detection is ambiguous.
+        // Explicit, or preferred because detection was ambiguous.
         params.set_language(Some(language));
     } else {
         // Auto-detect language while still running transcription.
@@ -301,6 +301,40 @@ async fn send_conversation_completion_email_core(
     send_email(settings, &secret, send_request).await
 }
 
+/// Sends a plain-text notification through the configured sender. Used by
+/// backend notifiers that have no conversation metadata to attach.
+pub(crate) async fn send_notification_email(
+    settings: EmailSenderSettings,
+    settings_path: &Path,
+    recipient: Option<&str>,
+    subject: &str,
+    text_body: &str,
+) -> Result<EmailSendResult, String> {
+    let secret_store = FileEmailSecretStore::from_settings_path(settings_path);
+    send_notification_email_core(settings, recipient, subject, text_body, &secret_store)
+        .await
+        .map_err(encode_email_error)
+}
+
+async fn send_notification_email_core(
+    settings: EmailSenderSettings,
+    recipient: Option<&str>,
+    subject: &str,
+    text_body: &str,
+    secret_store: &impl EmailSecretStore,
+) -> Result<EmailSendResult, EmailSendError> {
+    let recipient =
+        resolve_requested_recipient(recipient, settings.recipient_email.as_str()).to_string();
+    validate_recipient(&recipient)?;
+    let send_request = EmailSendRequest {
+        to: recipient,
+        subject: validate_email_subject(subject)?,
+        text_body: validate_email_text_body(text_body)?,
+    };
+    let secret = secret_store.get()?.ok_or_else(missing_secret)?;
+    send_email(settings, &secret, send_request).await
+}
+
 fn prepare_conversation_completion_email(
     settings: EmailSenderSettings,
     request: SendConversationCompletionEmailRequest,
@@ -0,0 +1,110 @@
+//! Engine adapter interface
+//!
+//! Every CLI integration implements [`EngineAdapter`] and is registered with
+//! the [`EngineManager`](super::EngineManager) at startup. Commands look up the
+//! adapter for the requested engine instead of matching on each engine type,
+//! so a new CLI needs an `EngineType` variant, an adapter and one registration
+//! line.
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use serde_json::Value;
+use tauri::AppHandle;
+
+use super::{EngineStatus, EngineType, ModelInfo, SendMessageParams};
+use crate::state::AppState;
+
+pub(crate) type EngineFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Host handles an adapter needs to act on a workspace.
+#[derive(Clone, Copy)]
+pub(crate) struct EngineContext<'a> {
+    pub(crate) app: &'a AppHandle,
+    pub(crate) state: &'a AppState,
+    pub(crate) workspace_id: &'a str,
+}
+
+impl EngineContext<'_> {
+    pub(crate) async fn workspace_path(&self) -> Result<PathBuf, String> {
+        let workspaces = self.state.workspaces.lock().await;
+        workspaces
+            .get(self.workspace_id)
+            .map(|entry| PathBuf::from(&entry.path))
+            .ok_or_else(|| "Workspace not found".to_string())
+    }
+}
+
+pub(crate) trait EngineAdapter: Send + Sync {
+    fn engine_type(&self) -> EngineType;
+
+    /// Probe the CLI for install state, version and models. `bin` overrides
+    /// the default binary lookup.
+    fn detect<'a>(&'a self, bin: Option<&'a str>) -> EngineFuture<'a, EngineStatus>;
+
+    /// List models live from the CLI. `None` means the models reported by
+    /// [`detect`](Self::detect) are authoritative and may be served from cache.
+    fn models<'a>(&'a self, _bin: Option<&'a str>) -> EngineFuture<'a, Option<Vec<ModelInfo>>> {
+        Box::pin(async { None })
+    }
+
+    /// Run one prompt to completion and return `{ engine, text, .. }`.
+    fn send<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        params: SendMessageParams,
+    ) -> EngineFuture<'a, Result<Value, String>>;
+
+    /// Continue an existing session with a new prompt.
+    fn resume<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        session_id: String,
+        mut params: SendMessageParams,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        params.continue_session = true;
+        params.session_id = Some(session_id);
+        self.send(ctx, params)
+    }
+
+    /// Start a streaming turn: spawn the CLI, forward its events as
+    /// `app-server-event`s and return `{ engine, turn: { id, status }, .. }`
+    /// without waiting for the turn to finish.
+    fn start_turn<'a>(
+        &'a self,
+        _ctx: EngineContext<'a>,
+        _params: SendMessageParams,
+        _thread_id: Option<String>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        let engine_type = self.engine_type();
+        Box::pin(async move {
+            Err(format!(
+                "{} does not support streaming turns",
+                engine_type.display_name()
+            ))
+        })
+    }
+
+    /// Stop `turn_id`, or everything in flight for the workspace when `None`.
+    fn interrupt<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        turn_id: Option<&'a str>,
+    ) -> EngineFuture<'a, Result<(), String>>;
+
+    /// Session history for the workspace.
+    fn history<'a>(
+        &'a self,
+        _ctx: EngineContext<'a>,
+        _limit: Option<usize>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        let engine_type = self.engine_type();
+        Box::pin(async move {
+            Err(format!(
+                "{} does not expose session history through the engine adapter",
+                engine_type.display_name()
+            ))
+        })
+    }
+}
@@ -0,0 +1,920 @@
+//! Direct API engine: talks to Anthropic or OpenAI-compatible endpoints over
+//! HTTP instead of driving a CLI, and runs tool calls through a built-in
+//! executor that honours the workspace access mode.
+//!
+//! Events are translated with the same `EngineEvent` -> `app-server-event`
+//! mapping the CLI engines use, so approvals and tool cards render through the
+//! existing frontend paths. Turns go through `engine_send_message` and the
+//! engine interrupt commands, which hand threads with the `api:` prefix to
+//! this module. Conversations are kept under
+//! `~/.ccgui/api-engine/threads/<id>.json`.
+
+#[path = "api_engine/providers.rs"]
+mod providers;
+#[path = "api_engine/tools.rs"]
+mod tools;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+use tokio::sync::oneshot;
+
+use self::providers::{
+    build_request, drain_sse_data_lines, resolve_api_key, ApiMessage, StreamAccumulator,
+    StreamDelta,
+};
+use self::tools::{
+    execute_tool, tool_gate, tool_kind, tool_specs, ToolGate, ToolKind, ToolOutcome,
+};
+use super::events::{engine_event_to_app_server_event_with_turn_context, EngineEvent};
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+use crate::shared::access_presets::AccessModePreset;
+use crate::shared::approval_audit::{
+    edited_command_from_result, record_approval_decision, ApprovalAuditEntry,
+};
+use crate::shared::workspace_read_only::cap_access_mode_for_workspace;
+use crate::state::AppState;
+use crate::types::{ApiEngineProvider, ApiEngineSettings};
+
+const THREAD_ID_PREFIX: &str = "api:";
+const TURN_ID_PREFIX: &str = "api-turn-";
+const APPROVAL_ID_PREFIX: &str = "api-approval-";
+const ANTHROPIC_DEFAULT_MODEL: &str = "claude-sonnet-4-5";
+const OPENAI_DEFAULT_MODEL: &str = "gpt-4.1";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiConversation {
+    thread_id: String,
+    workspace_id: String,
+    #[serde(default)]
+    messages: Vec<ApiMessage>,
+}
+
+struct PendingApproval {
+    thread_id: String,
+    workspace_id: String,
+    tool_name: String,
+    /// Command a `run_command` call asked for; only these may be edited.
+    original_command: Option<String>,
+    sender: oneshot::Sender<ApprovalAnswer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ApprovalAnswer {
+    approved: bool,
+    /// Replacement the user typed in the approval dialog.
+    edited_command: Option<String>,
+}
+
+struct ActiveTurn {
+    workspace_id: String,
+    turn_id: String,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct ApiEngineRuntime {
+    /// Running turns, keyed by thread id.
+    active_turns: StdMutex<HashMap<String, ActiveTurn>>,
+    pending_approvals: StdMutex<HashMap<String, PendingApproval>>,
+}
+
+fn runtime() -> &'static ApiEngineRuntime {
+    static RUNTIME: OnceLock<ApiEngineRuntime> = OnceLock::new();
+    RUNTIME.get_or_init(ApiEngineRuntime::default)
+}
+
+/// Threads the frontend opened on the API engine rather than a CLI engine.
+pub(crate) fn is_api_thread_id(thread_id: &str) -> bool {
+    thread_id.starts_with(THREAD_ID_PREFIX)
+}
+
+pub(crate) fn is_api_turn_id(turn_id: &str) -> bool {
+    turn_id.starts_with(TURN_ID_PREFIX)
+}
+
+/// Thread ids come from the frontend, so only accept the shape we expect
+/// before using them as file names.
+fn conversation_key(thread_id: &str) -> Result<&str, String> {
+    let key = thread_id
+        .strip_prefix(THREAD_ID_PREFIX)
+        .unwrap_or(thread_id);
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+    {
+        return Err(format!("invalid API engine thread id: {thread_id}"));
+    }
+    Ok(key)
+}
+
+fn conversation_path(thread_id: &str) -> Result<PathBuf, String> {
+    let key = conversation_key(thread_id)?;
+    Ok(crate::app_paths::app_home_dir()?
+        .join("api-engine")
+        .join("threads")
+        .join(format!("{key}.json")))
+}
+
+fn load_conversation(thread_id: &str) -> Result<Option<ApiConversation>, String> {
+    let path = conversation_path(thread_id)?;
+    crate::storage::with_storage_lock(&path, || crate::storage::read_json_file(&path))
+}
+
+fn save_conversation(conversation: &ApiConversation) -> Result<(), String> {
+    let path = conversation_path(&conversation.thread_id)?;
+    let content = serde_json::to_string_pretty(conversation).map_err(|error| error.to_string())?;
+    crate::storage::with_storage_lock(&path, || {
+        crate::storage::write_string_atomically(&path, &content)
+    })
+}
+
+fn default_model(provider: ApiEngineProvider) -> &'static str {
+    match provider {
+        ApiEngineProvider::Anthropic => ANTHROPIC_DEFAULT_MODEL,
+        ApiEngineProvider::OpenAi => OPENAI_DEFAULT_MODEL,
+    }
+}
+
+fn system_prompt(workspace_root: &Path, access_mode: AccessModePreset) -> String {
+    format!(
+        "You are a coding assistant working in the project at {}. Use the provided tools to \
+         inspect and change files; paths are relative to the project root. Access mode: {}. \
+         Platform: {}.",
+        workspace_root.display(),
+        serde_json::to_value(access_mode)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default(),
+        std::env::consts::OS,
+    )
+}
+
+pub(crate) fn is_approval_request_id(request_id: &Value) -> bool {
+    request_id
+        .as_str()
+        .is_some_and(|key| key.starts_with(APPROVAL_ID_PREFIX))
+}
+
+/// Resolves an approval answer from `respond_to_server_request`. Returns
+/// `None` when the request id does not belong to the API engine. An accepted
+/// `run_command` may carry an `editedCommand` that runs instead of the
+/// requested one.
+pub(crate) fn respond_to_approval_request(
+    request_id: &Value,
+    result: &Value,
+) -> Option<Result<(), String>> {
+    if !is_approval_request_id(request_id) {
+        return None;
+    }
+    let request_key = request_id.as_str()?;
+    let decision = match result {
+        Value::String(value) => value.trim().to_ascii_lowercase(),
+        Value::Object(map) => map
+            .get("decision")
+            .and_then(Value::as_str)
+            .map(|value| value.trim().to_ascii_lowercase())
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+    if decision != "accept" && decision != "decline" {
+        return Some(Err(format!(
+            "unsupported API engine approval result: {decision}"
+        )));
+    }
+    let approved = decision == "accept";
+    let edited_command = match edited_command_from_result(result) {
+        Ok(command) => command.filter(|_| approved),
+        Err(error) => return Some(Err(error)),
+    };
+    let Ok(mut pending_approvals) = runtime().pending_approvals.lock() else {
+        return Some(Err("API engine state lock poisoned".to_string()));
+    };
+    let Some(pending) = pending_approvals.get(request_key) else {
+        return Some(Err(format!(
+            "unknown request_id for API engine approval: {request_key}"
+        )));
+    };
+    if edited_command.is_some() && pending.original_command.is_none() {
+        return Some(Err(format!(
+            "{} is not a shell command and cannot be edited",
+            pending.tool_name
+        )));
+    }
+    let pending = pending_approvals.remove(request_key)?;
+    drop(pending_approvals);
+
+    let edited_command = edited_command
+        .filter(|command| pending.original_command.as_deref() != Some(command.as_str()));
+    if pending.original_command.is_some() {
+        record_approval_decision(&ApprovalAuditEntry {
+            recorded_at_ms: chrono::Utc::now().timestamp_millis(),
+            workspace_id: pending.workspace_id.clone(),
+            engine: "api".to_string(),
+            request_id: request_key.to_string(),
+            tool_name: pending.tool_name.clone(),
+            decision,
+            original_command: pending.original_command.clone(),
+            edited_command: edited_command.clone(),
+        });
+    }
+    let _ = pending.sender.send(ApprovalAnswer {
+        approved,
+        edited_command,
+    });
+    Some(Ok(()))
+}
+
+struct TurnContext {
+    sink: TauriEventSink,
+    workspace_id: String,
+    workspace_root: PathBuf,
+    thread_id: String,
+    turn_id: String,
+    settings: ApiEngineSettings,
+    api_key: String,
+    model: String,
+    access_mode: AccessModePreset,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TurnContext {
+    fn emit(&self, event: EngineEvent, item_id: &str) {
+        if let Some(payload) = engine_event_to_app_server_event_with_turn_context(
+            &event,
+            &self.thread_id,
+            item_id,
+            Some(&self.turn_id),
+        ) {
+            self.sink.emit_app_server_event(payload);
+        }
+    }
+
+    fn emit_agent_message_completed(&self, item_id: &str, text: &str) {
+        self.sink.emit_app_server_event(AppServerEvent {
+            workspace_id: self.workspace_id.clone(),
+            message: json!({
+                "method": "item/completed",
+                "params": {
+                    "threadId": &self.thread_id,
+                    "item": {
+                        "id": item_id,
+                        "type": "agentMessage",
+                        "text": text,
+                        "status": "completed",
+                    }
+                }
+            }),
+        });
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Streams one model response into `accumulator`, forwarding deltas.
+async fn stream_response(
+    context: &TurnContext,
+    client: &reqwest::Client,
+    history: &[ApiMessage],
+    accumulator: &mut StreamAccumulator,
+    assistant_item_id: &str,
+    reasoning_item_id: &str,
+) -> Result<(), String> {
+    let request = build_request(
+        &context.settings,
+        &context.api_key,
+        &context.model,
+        &system_prompt(&context.workspace_root, context.access_mode),
+        history,
+        &tool_specs(),
+    );
+    let mut builder = client
+        .post(&request.url)
+        .header("content-type", "application/json");
+    for (name, value) in &request.headers {
+        builder = builder.header(*name, value);
+    }
+    let body = serde_json::to_vec(&request.body).map_err(|error| error.to_string())?;
+    let mut response = builder
+        .body(body)
+        .send()
+        .await
+        .map_err(|error| format!("API request failed: {error}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let detail = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<Value>(&detail)
+            .ok()
+            .and_then(|value| value["error"]["message"].as_str().map(str::to_string))
+            .unwrap_or(detail);
+        return Err(format!("API request failed ({status}): {message}"));
+    }
+
+    let mut pending_bytes: Vec<u8> = Vec::new();
+    let mut lines = String::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|error| format!("API stream failed: {error}"))?
+    {
+        if context.is_cancelled() {
+            return Err("interrupted".to_string());
+        }
+        pending_bytes.extend_from_slice(&chunk);
+        // Only decode complete lines so multi-byte characters split across
+        // chunks survive.
+        let Some(last_newline) = pending_bytes.iter().rposition(|byte| *byte == b'\n') else {
+            continue;
+        };
+        let complete: Vec<u8> = pending_bytes.drain(..=last_newline).collect();
+        lines.push_str(&String::from_utf8_lossy(&complete));
+        for payload in drain_sse_data_lines(&mut lines) {
+            let Ok(event) = serde_json::from_str::<Value>(&payload) else {
+                continue;
+            };
+            for delta in accumulator.apply(&event)? {
+                match delta {
+                    StreamDelta::Text(text) => context.emit(
+                        EngineEvent::TextDelta {
+                            workspace_id: context.workspace_id.clone(),
+                            text,
+                        },
+                        assistant_item_id,
+                    ),
+                    StreamDelta::Reasoning(text) => context.emit(
+                        EngineEvent::ReasoningDelta {
+                            workspace_id: context.workspace_id.clone(),
+                            text,
+                        },
+                        reasoning_item_id,
+                    ),
+                    StreamDelta::ToolCallStarted { id, name } => context.emit(
+                        EngineEvent::ToolInputUpdated {
+                            workspace_id: context.workspace_id.clone(),
+                            tool_id: id.clone(),
+                            tool_name: Some(name),
+                            input: None,
+                        },
+                        &id,
+                    ),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn request_approval(
+    context: &TurnContext,
+    tool_id: &str,
+    tool_name: &str,
+    input: &Value,
+) -> ApprovalAnswer {
+    let request_id = format!("{APPROVAL_ID_PREFIX}{}", uuid::Uuid::new_v4());
+    let original_command = (tool_kind(tool_name) == Some(ToolKind::Command))
+        .then(|| input.get("command").and_then(Value::as_str))
+        .flatten()
+        .map(str::to_string);
+    let mut event_input = input.clone();
+    if original_command.is_some() {
+        // Lets the approval dialog offer an editable command field.
+        event_input["commandEditable"] = Value::Bool(true);
+    }
+    let (sender, receiver) = oneshot::channel();
+    if let Ok(mut pending) = runtime().pending_approvals.lock() {
+        pending.insert(
+            request_id.clone(),
+            PendingApproval {
+                thread_id: context.thread_id.clone(),
+                workspace_id: context.workspace_id.clone(),
+                tool_name: tool_name.to_string(),
+                original_command,
+                sender,
+            },
+        );
+    }
+    context.emit(
+        EngineEvent::ApprovalRequest {
+            workspace_id: context.workspace_id.clone(),
+            request_id: Value::String(request_id),
+            tool_name: tool_name.to_string(),
+            input: Some(event_input),
+            message: Some(format!("Allow {tool_name}?")),
+        },
+        tool_id,
+    );
+    // A dropped sender (interrupt) counts as a decline.
+    receiver.await.unwrap_or(ApprovalAnswer {
+        approved: false,
+        edited_command: None,
+    })
+}
+
+async fn run_tool_call(context: &TurnContext, id: &str, name: &str, input: &Value) -> ToolOutcome {
+    context.emit(
+        EngineEvent::ToolStarted {
+            workspace_id: context.workspace_id.clone(),
+            tool_id: id.to_string(),
+            tool_name: name.to_string(),
+            input: Some(input.clone()),
+        },
+        id,
+    );
+    let mut executed_input = input.clone();
+    let outcome = match tool_kind(name).map(|kind| tool_gate(context.access_mode, kind)) {
+        None => ToolOutcome {
+            content: format!("unknown tool: {name}"),
+            is_error: true,
+        },
+        Some(ToolGate::Deny) => ToolOutcome {
+            content: "blocked: the workspace is in read-only mode".to_string(),
+            is_error: true,
+        },
+        Some(ToolGate::Ask) => {
+            let answer = request_approval(context, id, name, input).await;
+            match answer {
+                ApprovalAnswer {
+                    approved: false, ..
+                } => ToolOutcome {
+                    content: "the user declined this tool call".to_string(),
+                    is_error: true,
+                },
+                ApprovalAnswer {
+                    edited_command: Some(command),
+                    ..
+                } => {
+                    executed_input["command"] = Value::String(command.clone());
+                    let outcome =
+                        execute_tool(&context.workspace_root, name, &executed_input).await;
+                    // Tell the model what actually ran so it does not assume
+                    // its own command's side effects.
+                    ToolOutcome {
+                        content: format!(
+                            "the user edited the command before approving it; ran `{command}` instead\n{}",
+                            outcome.content
+                        ),
+                        is_error: outcome.is_error,
+                    }
+                }
+                ApprovalAnswer { .. } => execute_tool(&context.workspace_root, name, input).await,
+            }
+        }
+        Some(_) => execute_tool(&context.workspace_root, name, input).await,
+    };
+    context.emit(
+        EngineEvent::ToolCompleted {
+            workspace_id: context.workspace_id.clone(),
+            tool_id: id.to_string(),
+            tool_name: Some(name.to_string()),
+            output: Some(json!({ "_input": executed_input, "_output": outcome.content })),
+            error: outcome.is_error.then(|| outcome.content.clone()),
+        },
+        id,
+    );
+    outcome
+}
+
+async fn run_turn(context: TurnContext, mut conversation: ApiConversation) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|error| format!("Failed to configure API client: {error}"))?;
+    let mut input_tokens = 0i64;
+    let mut output_tokens = 0i64;
+    let mut final_text = String::new();
+
+    for round in 0..context.settings.max_tool_rounds.max(1) {
+        let assistant_item_id = format!("{}-assistant-{round}", context.turn_id);
+        let reasoning_item_id = format!("{}-reasoning-{round}", context.turn_id);
+        let mut accumulator = StreamAccumulator::new(context.settings.provider);
+        stream_response(
+            &context,
+            &client,
+            &conversation.messages,
+            &mut accumulator,
+            &assistant_item_id,
+            &reasoning_item_id,
+        )
+        .await?;
+        input_tokens += accumulator.input_tokens.unwrap_or(0);
+        output_tokens += accumulator.output_tokens.unwrap_or(0);
+        if !accumulator.text.trim().is_empty() {
+            context.emit_agent_message_completed(&assistant_item_id, &accumulator.text);
+            final_text = accumulator.text.clone();
+        }
+
+        let tool_calls = accumulator.tool_calls();
+        let mut round_messages = vec![ApiMessage::Assistant {
+            text: accumulator.text.clone(),
+            tool_calls: tool_calls.clone(),
+        }];
+        for call in &tool_calls {
+            if context.is_cancelled() {
+                return Err("interrupted".to_string());
+            }
+            let outcome = run_tool_call(&context, &call.id, &call.name, &call.arguments).await;
+            round_messages.push(ApiMessage::ToolResult {
+                call_id: call.id.clone(),
+                content: outcome.content,
+                is_error: outcome.is_error,
+            });
+        }
+        // Rounds are committed whole so a failed turn never leaves tool calls
+        // without results in the stored history.
+        conversation.messages.extend(round_messages);
+        save_conversation(&conversation)?;
+
+        if tool_calls.is_empty() {
+            context.emit(
+                EngineEvent::UsageUpdate {
+                    workspace_id: context.workspace_id.clone(),
+                    input_tokens: Some(input_tokens),
+                    output_tokens: Some(output_tokens),
+                    cached_tokens: None,
+                    model_context_window: None,
+                    context_used_tokens: accumulator
+                        .input_tokens
+                        .map(|input| input + accumulator.output_tokens.unwrap_or(0)),
+                    context_usage_source: None,
+                    context_usage_freshness: None,
+                    context_used_percent: None,
+                    context_remaining_percent: None,
+                    context_tool_usages: None,
+                    context_tool_usages_truncated: None,
+                    context_category_usages: None,
+                },
+                &assistant_item_id,
+            );
+            context.emit(
+                EngineEvent::TurnCompleted {
+                    workspace_id: context.workspace_id.clone(),
+                    result: Some(json!({
+                        "text": final_text,
+                        "model": context.model,
+                        "stopReason": accumulator.stop_reason,
+                    })),
+                },
+                &assistant_item_id,
+            );
+            return Ok(());
+        }
+    }
+    Err(format!(
+        "stopped after {} tool rounds without a final answer",
+        context.settings.max_tool_rounds
+    ))
+}
+
+fn finish_turn(thread_id: &str, cancelled: &Arc<AtomicBool>) {
+    if let Ok(mut active) = runtime().active_turns.lock() {
+        if active
+            .get(thread_id)
+            .is_some_and(|turn| Arc::ptr_eq(&turn.cancelled, cancelled))
+        {
+            active.remove(thread_id);
+        }
+    }
+}
+
+/// Starts a turn on the API engine thread `thread_id` and streams it to the
+/// frontend via app-server-event. A thread id without a stored conversation
+/// starts a new one, the way Claude accepts frontend-issued session ids.
+/// Returns immediately with the thread and turn ids.
+pub(crate) async fn start_turn(
+    app: &AppHandle,
+    state: &AppState,
+    workspace_id: &str,
+    text: String,
+    thread_id: &str,
+    model: Option<String>,
+    access_mode: Option<String>,
+) -> Result<Value, String> {
+    let workspace_id = workspace_id.to_string();
+    let settings = state.app_settings.lock().await.api_engine.clone();
+    if !settings.enabled {
+        return Err("API engine is disabled in settings".to_string());
+    }
+    let api_key = resolve_api_key(&settings)
+        .ok_or_else(|| "API engine has no API key configured".to_string())?;
+    let workspace = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "Workspace not found".to_string())?;
+    let access_mode = AccessModePreset::resolve(
+        cap_access_mode_for_workspace(
+            access_mode.or_else(|| workspace.settings.access_mode_preset.clone()),
+            workspace.settings.read_only,
+        )
+        .as_deref(),
+    );
+    let model = model
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .or_else(|| Some(settings.model.trim()).filter(|value| !value.is_empty()))
+        .unwrap_or(default_model(settings.provider))
+        .to_string();
+
+    let mut conversation = match load_conversation(thread_id)? {
+        Some(conversation) if conversation.workspace_id == workspace_id => conversation,
+        Some(_) => return Err(format!("API engine thread not found: {thread_id}")),
+        None => ApiConversation {
+            thread_id: thread_id.to_string(),
+            workspace_id: workspace_id.clone(),
+            messages: Vec::new(),
+        },
+    };
+    let turn_id = format!("{TURN_ID_PREFIX}{}", uuid::Uuid::new_v4());
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut active = runtime()
+            .active_turns
+            .lock()
+            .map_err(|_| "API engine state lock poisoned".to_string())?;
+        if active.contains_key(&conversation.thread_id) {
+            return Err("A turn is already running in this thread".to_string());
+        }
+        active.insert(
+            conversation.thread_id.clone(),
+            ActiveTurn {
+                workspace_id: workspace_id.clone(),
+                turn_id: turn_id.clone(),
+                cancelled: Arc::clone(&cancelled),
+            },
+        );
+    }
+    conversation.messages.push(ApiMessage::User { text });
+
+    let thread_id = conversation.thread_id.clone();
+    let context = TurnContext {
+        sink: TauriEventSink::new(app.clone()),
+        workspace_id: workspace_id.clone(),
+        workspace_root: PathBuf::from(&workspace.path),
+        thread_id: thread_id.clone(),
+        turn_id: turn_id.clone(),
+        settings,
+        api_key,
+        model,
+        access_mode,
+        cancelled: Arc::clone(&cancelled),
+    };
+    context.emit(
+        EngineEvent::TurnStarted {
+            workspace_id: workspace_id.clone(),
+            turn_id: turn_id.clone(),
+            cwd: None,
+        },
+        &turn_id,
+    );
+
+    tokio::spawn(async move {
+        let sink = context.sink.clone();
+        let thread_id = context.thread_id.clone();
+        let turn_id = context.turn_id.clone();
+        let workspace_id = context.workspace_id.clone();
+        if let Err(error) = run_turn(context, conversation).await {
+            log::warn!(
+                "[api-engine] turn failed (thread_id={}, turn_id={}): {}",
+                thread_id,
+                turn_id,
+                error
+            );
+            let event = EngineEvent::TurnError {
+                workspace_id,
+                error,
+                code: None,
+            };
+            if let Some(payload) = engine_event_to_app_server_event_with_turn_context(
+                &event,
+                &thread_id,
+                &turn_id,
+                Some(&turn_id),
+            ) {
+                sink.emit_app_server_event(payload);
+            }
+        }
+        finish_turn(&thread_id, &cancelled);
+    });
+
+    Ok(json!({
+        "engine": "api",
+        "threadId": thread_id,
+        "turnId": turn_id,
+        "result": {
+            "turn": {
+                "id": turn_id,
+                "status": "started",
+            },
+        },
+    }))
+}
+
+/// Stops the running turns selected by `select` and declines their pending
+/// approvals.
+fn interrupt_where(select: impl Fn(&ActiveTurn) -> bool) -> Result<(), String> {
+    let thread_ids: Vec<String> = runtime()
+        .active_turns
+        .lock()
+        .map_err(|_| "API engine state lock poisoned".to_string())?
+        .iter()
+        .filter(|(_, turn)| select(turn))
+        .map(|(thread_id, turn)| {
+            turn.cancelled.store(true, Ordering::SeqCst);
+            thread_id.clone()
+        })
+        .collect();
+    if let Ok(mut pending) = runtime().pending_approvals.lock() {
+        pending.retain(|_, approval| !thread_ids.contains(&approval.thread_id));
+    }
+    Ok(())
+}
+
+/// Stops one API engine turn; used by `engine_interrupt_turn`.
+pub(crate) fn interrupt_turn(turn_id: &str) -> Result<(), String> {
+    interrupt_where(|turn| turn.turn_id == turn_id)
+}
+
+/// Stops every API engine turn of a workspace; used by `engine_interrupt`.
+pub(crate) fn interrupt_workspace(workspace_id: &str) -> Result<(), String> {
+    interrupt_where(|turn| turn.workspace_id == workspace_id)
+}
+
+#[tauri::command]
+pub(crate) async fn get_api_engine_status(state: State<'_, AppState>) -> Result<Value, String> {
+    let settings = state.app_settings.lock().await.api_engine.clone();
+    let active_turns = runtime()
+        .active_turns
+        .lock()
+        .map(|active| active.len())
+        .unwrap_or(0);
+    Ok(json!({
+        "enabled": settings.enabled,
+        "provider": settings.provider,
+        "model": if settings.model.trim().is_empty() {
+            default_model(settings.provider).to_string()
+        } else {
+            settings.model.trim().to_string()
+        },
+        "hasApiKey": resolve_api_key(&settings).is_some(),
+        "activeTurns": active_turns,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thread_ids_are_validated_before_touching_disk() {
+        assert_eq!(
+            conversation_key("api:0b6e-41aa").expect("valid"),
+            "0b6e-41aa"
+        );
+        assert!(conversation_key("api:../../etc").is_err());
+        assert!(conversation_key("api:").is_err());
+    }
+
+    #[test]
+    fn interrupting_a_turn_declines_only_its_approvals() {
+        let running = Arc::new(AtomicBool::new(false));
+        let other = Arc::new(AtomicBool::new(false));
+        {
+            let mut active = runtime().active_turns.lock().expect("lock");
+            active.insert(
+                "api:interrupt-a".to_string(),
+                ActiveTurn {
+                    workspace_id: "ws-interrupt".to_string(),
+                    turn_id: "api-turn-interrupt-a".to_string(),
+                    cancelled: Arc::clone(&running),
+                },
+            );
+            active.insert(
+                "api:interrupt-b".to_string(),
+                ActiveTurn {
+                    workspace_id: "ws-interrupt".to_string(),
+                    turn_id: "api-turn-interrupt-b".to_string(),
+                    cancelled: Arc::clone(&other),
+                },
+            );
+        }
+        let (sender, receiver) = oneshot::channel();
+        runtime().pending_approvals.lock().expect("lock").insert(
+            "api-approval-interrupt-a".to_string(),
+            PendingApproval {
+                thread_id: "api:interrupt-a".to_string(),
+                workspace_id: "ws-interrupt".to_string(),
+                tool_name: "run_command".to_string(),
+                original_command: Some("ls".to_string()),
+                sender,
+            },
+        );
+
+        interrupt_turn("api-turn-interrupt-a").expect("interrupt turn");
+        assert!(running.load(Ordering::SeqCst));
+        assert!(!other.load(Ordering::SeqCst));
+        assert!(receiver.blocking_recv().is_err());
+
+        interrupt_workspace("ws-interrupt").expect("interrupt workspace");
+        assert!(other.load(Ordering::SeqCst));
+        let mut active = runtime().active_turns.lock().expect("lock");
+        active.remove("api:interrupt-a");
+        active.remove("api:interrupt-b");
+    }
+
+    #[test]
+    fn approval_responses_only_claim_api_engine_requests() {
+        assert!(respond_to_approval_request(&json!("claude-1"), &json!("accept")).is_none());
+        assert!(respond_to_approval_request(&json!(7), &json!("accept")).is_none());
+
+        let (sender, receiver) = oneshot::channel();
+        runtime().pending_approvals.lock().expect("lock").insert(
+            "api-approval-test".to_string(),
+            PendingApproval {
+                thread_id: "api:t".to_string(),
+                workspace_id: "ws".to_string(),
+                tool_name: "write_file".to_string(),
+                original_command: None,
+                sender,
+            },
+        );
+        let result = respond_to_approval_request(
+            &json!("api-approval-test"),
+            &json!({ "decision": "accept" }),
+        );
+        assert_eq!(result, Some(Ok(())));
+        assert_eq!(
+            receiver.blocking_recv(),
+            Ok(ApprovalAnswer {
+                approved: true,
+                edited_command: None,
+            })
+        );
+    }
+
+    #[test]
+    fn only_command_approvals_accept_edited_commands() {
+        let (file_sender, _file_receiver) = oneshot::channel();
+        let (command_sender, command_receiver) = oneshot::channel();
+        {
+            let mut pending = runtime().pending_approvals.lock().expect("lock");
+            pending.insert(
+                "api-approval-edit-file".to_string(),
+                PendingApproval {
+                    thread_id: "api:t".to_string(),
+                    workspace_id: "ws".to_string(),
+                    tool_name: "write_file".to_string(),
+                    original_command: None,
+                    sender: file_sender,
+                },
+            );
+            pending.insert(
+                "api-approval-edit-command".to_string(),
+                PendingApproval {
+                    thread_id: "api:t".to_string(),
+                    workspace_id: "ws".to_string(),
+                    tool_name: "run_command".to_string(),
+                    original_command: Some("cargo test".to_string()),
+                    sender: command_sender,
+                },
+            );
+        }
+        let edited = json!({ "decision": "accept", "editedCommand": "cargo test -p core" });
+
+        let rejected = respond_to_approval_request(&json!("api-approval-edit-file"), &edited);
+        assert!(matches!(rejected, Some(Err(_))));
+        assert!(runtime()
+            .pending_approvals
+            .lock()
+            .expect("lock")
+            .contains_key("api-approval-edit-file"));
+
+        let accepted = respond_to_approval_request(&json!("api-approval-edit-command"), &edited);
+        assert_eq!(accepted, Some(Ok(())));
+        assert_eq!(
+            command_receiver.blocking_recv(),
+            Ok(ApprovalAnswer {
+                approved: true,
+                edited_command: Some("cargo test -p core".to_string()),
+            })
+        );
+        runtime()
+            .pending_approvals
+            .lock()
+            .expect("lock")
+            .remove("api-approval-edit-file");
+    }
+}
@@ -0,0 +1,585 @@
+//! Request building and streaming-response parsing for the provider APIs.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::types::{ApiEngineProvider, ApiEngineSettings};
+
+const ANTHROPIC_DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+const OPENAI_DEFAULT_BASE_URL: &str = "https://api.openai.com";
+const ANTHROPIC_API_VERSION: &str = "2023-06-01";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApiToolCall {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) arguments: Value,
+}
+
+/// Provider-neutral conversation history, persisted per thread.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "role", rename_all = "camelCase")]
+pub(crate) enum ApiMessage {
+    User {
+        text: String,
+    },
+    Assistant {
+        text: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tool_calls: Vec<ApiToolCall>,
+    },
+    ToolResult {
+        call_id: String,
+        content: String,
+        #[serde(default)]
+        is_error: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ApiToolSpec {
+    pub(crate) name: &'static str,
+    pub(crate) description: &'static str,
+    pub(crate) parameters: Value,
+}
+
+pub(crate) struct ApiRequest {
+    pub(crate) url: String,
+    pub(crate) headers: Vec<(&'static str, String)>,
+    pub(crate) body: Value,
+}
+
+/// Incremental output surfaced while a response streams in.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum StreamDelta {
+    Text(String),
+    Reasoning(String),
+    ToolCallStarted { id: String, name: String },
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+/// Folds one provider's server-sent events into text, tool calls and usage.
+#[derive(Debug)]
+pub(crate) struct StreamAccumulator {
+    provider: ApiEngineProvider,
+    pub(crate) text: String,
+    tool_calls: Vec<PendingToolCall>,
+    /// Anthropic content block index -> position in `tool_calls`.
+    block_tools: Vec<(i64, usize)>,
+    pub(crate) input_tokens: Option<i64>,
+    pub(crate) output_tokens: Option<i64>,
+    pub(crate) stop_reason: Option<String>,
+}
+
+fn trimmed_base_url(settings: &ApiEngineSettings) -> String {
+    let base = settings.base_url.trim().trim_end_matches('/');
+    if !base.is_empty() {
+        return base.to_string();
+    }
+    match settings.provider {
+        ApiEngineProvider::Anthropic => ANTHROPIC_DEFAULT_BASE_URL.to_string(),
+        ApiEngineProvider::OpenAi => OPENAI_DEFAULT_BASE_URL.to_string(),
+    }
+}
+
+/// Accepts base URLs with or without the trailing `/v1`.
+fn endpoint(settings: &ApiEngineSettings, path: &str) -> String {
+    let base = trimmed_base_url(settings);
+    if base.ends_with("/v1") {
+        format!("{base}/{path}")
+    } else {
+        format!("{base}/v1/{path}")
+    }
+}
+
+pub(crate) fn resolve_api_key(settings: &ApiEngineSettings) -> Option<String> {
+    let configured = settings.api_key.trim();
+    if !configured.is_empty() {
+        return Some(configured.to_string());
+    }
+    let env_key = match settings.provider {
+        ApiEngineProvider::Anthropic => "ANTHROPIC_API_KEY",
+        ApiEngineProvider::OpenAi => "OPENAI_API_KEY",
+    };
+    std::env::var(env_key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn anthropic_messages(history: &[ApiMessage]) -> Vec<Value> {
+    let mut messages: Vec<Value> = Vec::new();
+    for message in history {
+        match message {
+            ApiMessage::User { text } => {
+                messages.push(json!({ "role": "user", "content": text }));
+            }
+            ApiMessage::Assistant { text, tool_calls } => {
+                let mut content = Vec::new();
+                if !text.is_empty() {
+                    content.push(json!({ "type": "text", "text": text }));
+                }
+                for call in tool_calls {
+                    content.push(json!({
+                        "type": "tool_use",
+                        "id": call.id,
+                        "name": call.name,
+                        "input": call.arguments,
+                    }));
+                }
+                messages.push(json!({ "role": "assistant", "content": content }));
+            }
+            ApiMessage::ToolResult {
+                call_id,
+                content,
+                is_error,
+            } => {
+                let block = json!({
+                    "type": "tool_result",
+                    "tool_use_id": call_id,
+                    "content": content,
+                    "is_error": is_error,
+                });
+                // Results for one assistant turn travel in a single user message.
+                let appended = messages.last_mut().is_some_and(|last| {
+                    let is_tool_result_message = last["role"] == "user"
+                        && last["content"]
+                            .as_array()
+                            .and_then(|blocks| blocks.first())
+                            .is_some_and(|first| first["type"] == "tool_result");
+                    if is_tool_result_message {
+                        if let Some(blocks) = last["content"].as_array_mut() {
+                            blocks.push(block.clone());
+                        }
+                    }
+                    is_tool_result_message
+                });
+                if !appended {
+                    messages.push(json!({ "role": "user", "content": [block] }));
+                }
+            }
+        }
+    }
+    messages
+}
+
+fn openai_messages(system: &str, history: &[ApiMessage]) -> Vec<Value> {
+    let mut messages = vec![json!({ "role": "system", "content": system })];
+    for message in history {
+        match message {
+            ApiMessage::User { text } => {
+                messages.push(json!({ "role": "user", "content": text }));
+            }
+            ApiMessage::Assistant { text, tool_calls } => {
+                let mut entry = json!({ "role": "assistant", "content": text });
+                if !tool_calls.is_empty() {
+                    entry["tool_calls"] = Value::Array(
+                        tool_calls
+                            .iter()
+                            .map(|call| {
+                                json!({
+                                    "id": call.id,
+                                    "type": "function",
+                                    "function": {
+                                        "name": call.name,
+                                        "arguments": call.arguments.to_string(),
+                                    }
+                                })
+                            })
+                            .collect(),
+                    );
+                }
+                messages.push(entry);
+            }
+            ApiMessage::ToolResult {
+                call_id, content, ..
+            } => {
+                messages.push(json!({
+                    "role": "tool",
+                    "tool_call_id": call_id,
+                    "content": content,
+                }));
+            }
+        }
+    }
+    messages
+}
+
+pub(crate) fn build_request(
+    settings: &ApiEngineSettings,
+    api_key: &str,
+    model: &str,
+    system: &str,
+    history: &[ApiMessage],
+    tools: &[ApiToolSpec],
+) -> ApiRequest {
+    match settings.provider {
+        ApiEngineProvider::Anthropic => ApiRequest {
+            url: endpoint(settings, "messages"),
+            headers: vec![
+                ("x-api-key", api_key.to_string()),
+                ("anthropic-version", ANTHROPIC_API_VERSION.to_string()),
+            ],
+            body: json!({
+                "model": model,
+                "max_tokens": settings.max_output_tokens,
+                "system": system,
+                "stream": true,
+                "messages": anthropic_messages(history),
+                "tools": tools
+                    .iter()
+                    .map(|tool| json!({
+                        "name": tool.name,
+                        "description": tool.description,
+                        "input_schema": tool.parameters,
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+        },
+        ApiEngineProvider::OpenAi => ApiRequest {
+            url: endpoint(settings, "chat/completions"),
+            headers: vec![("authorization", format!("Bearer {api_key}"))],
+            body: json!({
+                "model": model,
+                "max_tokens": settings.max_output_tokens,
+                "stream": true,
+                "stream_options": { "include_usage": true },
+                "messages": openai_messages(system, history),
+                "tools": tools
+                    .iter()
+                    .map(|tool| json!({
+                        "type": "function",
+                        "function": {
+                            "name": tool.name,
+                            "description": tool.description,
+                            "parameters": tool.parameters,
+                        }
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+        },
+    }
+}
+
+/// Extracts complete `data:` payloads from an SSE buffer, leaving any partial
+/// line in place for the next chunk.
+pub(crate) fn drain_sse_data_lines(buffer: &mut String) -> Vec<String> {
+    let mut payloads = Vec::new();
+    while let Some(newline) = buffer.find('\n') {
+        let line = buffer[..newline].trim_end_matches('\r').to_string();
+        buffer.drain(..=newline);
+        if let Some(data) = line.strip_prefix("data:") {
+            let data = data.trim();
+            if !data.is_empty() && data != "[DONE]" {
+                payloads.push(data.to_string());
+            }
+        }
+    }
+    payloads
+}
+
+fn provider_error_message(event: &Value) -> Option<String> {
+    let error = event.get("error")?;
+    Some(
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string()),
+    )
+}
+
+impl StreamAccumulator {
+    pub(crate) fn new(provider: ApiEngineProvider) -> Self {
+        Self {
+            provider,
+            text: String::new(),
+            tool_calls: Vec::new(),
+            block_tools: Vec::new(),
+            input_tokens: None,
+            output_tokens: None,
+            stop_reason: None,
+        }
+    }
+
+    /// Applies one SSE payload. Returns the deltas worth showing, or the
+    /// provider's error message.
+    pub(crate) fn apply(&mut self, event: &Value) -> Result<Vec<StreamDelta>, String> {
+        if let Some(message) = provider_error_message(event) {
+            return Err(message);
+        }
+        match self.provider {
+            ApiEngineProvider::Anthropic => Ok(self.apply_anthropic(event)),
+            ApiEngineProvider::OpenAi => Ok(self.apply_openai(event)),
+        }
+    }
+
+    fn apply_anthropic(&mut self, event: &Value) -> Vec<StreamDelta> {
+        let mut deltas = Vec::new();
+        match event.get("type").and_then(Value::as_str).unwrap_or("") {
+            "message_start" => {
+                self.input_tokens = event["message"]["usage"]["input_tokens"].as_i64();
+            }
+            "content_block_start" => {
+                let block = &event["content_block"];
+                if block["type"] == "tool_use" {
+                    let call = PendingToolCall {
+                        id: block["id"].as_str().unwrap_or_default().to_string(),
+                        name: block["name"].as_str().unwrap_or_default().to_string(),
+                        arguments: String::new(),
+                    };
+                    deltas.push(StreamDelta::ToolCallStarted {
+                        id: call.id.clone(),
+                        name: call.name.clone(),
+                    });
+                    let index = event["index"].as_i64().unwrap_or(-1);
+                    self.block_tools.push((index, self.tool_calls.len()));
+                    self.tool_calls.push(call);
+                }
+            }
+            "content_block_delta" => {
+                let delta = &event["delta"];
+                match delta["type"].as_str().unwrap_or("") {
+                    "text_delta" => {
+                        let text = delta["text"].as_str().unwrap_or_default();
+                        self.text.push_str(text);
+                        deltas.push(StreamDelta::Text(text.to_string()));
+                    }
+                    "thinking_delta" => {
+                        let text = delta["thinking"].as_str().unwrap_or_default();
+                        deltas.push(StreamDelta::Reasoning(text.to_string()));
+                    }
+                    "input_json_delta" => {
+                        let index = event["index"].as_i64().unwrap_or(-1);
+                        if let Some((_, position)) =
+                            self.block_tools.iter().find(|(block, _)| *block == index)
+                        {
+                            self.tool_calls[*position]
+                                .arguments
+                                .push_str(delta["partial_json"].as_str().unwrap_or_default());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            "message_delta" => {
+                if let Some(output) = event["usage"]["output_tokens"].as_i64() {
+                    self.output_tokens = Some(output);
+                }
+                if let Some(reason) = event["delta"]["stop_reason"].as_str() {
+                    self.stop_reason = Some(reason.to_string());
+                }
+            }
+            _ => {}
+        }
+        deltas
+    }
+
+    fn apply_openai(&mut self, event: &Value) -> Vec<StreamDelta> {
+        let mut deltas = Vec::new();
+        if let Some(usage) = event.get("usage").filter(|usage| usage.is_object()) {
+            self.input_tokens = usage["prompt_tokens"].as_i64();
+            self.output_tokens = usage["completion_tokens"].as_i64();
+        }
+        let Some(choice) = event["choices"]
+            .as_array()
+            .and_then(|choices| choices.first())
+        else {
+            return deltas;
+        };
+        let delta = &choice["delta"];
+        if let Some(text) = delta["content"].as_str().filter(|text| !text.is_empty()) {
+            self.text.push_str(text);
+            deltas.push(StreamDelta::Text(text.to_string()));
+        }
+        if let Some(text) = delta["reasoning_content"]
+            .as_str()
+            .filter(|text| !text.is_empty())
+        {
+            deltas.push(StreamDelta::Reasoning(text.to_string()));
+        }
+        for call in delta["tool_calls"].as_array().into_iter().flatten() {
+            let index = call["index"].as_u64().unwrap_or(0) as usize;
+            while self.tool_calls.len() <= index {
+                self.tool_calls.push(PendingToolCall::default());
+            }
+            let pending = &mut self.tool_calls[index];
+            if let Some(id) = call["id"].as_str() {
+                pending.id = id.to_string();
+            }
+            if let Some(name) = call["function"]["name"].as_str() {
+                pending.name.push_str(name);
+                deltas.push(StreamDelta::ToolCallStarted {
+                    id: pending.id.clone(),
+                    name: pending.name.clone(),
+                });
+            }
+            if let Some(arguments) = call["function"]["arguments"].as_str() {
+                pending.arguments.push_str(arguments);
+            }
+        }
+        if let Some(reason) = choice["finish_reason"].as_str() {
+            self.stop_reason = Some(reason.to_string());
+        }
+        deltas
+    }
+
+    /// Tool calls with their JSON arguments parsed. Arguments that fail to
+    /// parse are passed through as `{"_raw": ...}` so the tool reports it.
+    pub(crate) fn tool_calls(&self) -> Vec<ApiToolCall> {
+        self.tool_calls
+            .iter()
+            .filter(|call| !call.name.is_empty())
+            .enumerate()
+            .map(|(position, call)| {
+                let arguments = if call.arguments.trim().is_empty() {
+                    Value::Object(Map::new())
+                } else {
+                    serde_json::from_str(&call.arguments)
+                        .unwrap_or_else(|_| json!({ "_raw": call.arguments }))
+                };
+                ApiToolCall {
+                    id: if call.id.is_empty() {
+                        format!("call_{position}")
+                    } else {
+                        call.id.clone()
+                    },
+                    name: call.name.clone(),
+                    arguments,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(provider: ApiEngineProvider, base_url: &str) -> ApiEngineSettings {
+        ApiEngineSettings {
+            provider,
+            base_url: base_url.to_string(),
+            ..ApiEngineSettings::default()
+        }
+    }
+
+    #[test]
+    fn builds_provider_requests_with_grouped_tool_results() {
+        let history = vec![
+            ApiMessage::User {
+                text: "list files".to_string(),
+            },
+            ApiMessage::Assistant {
+                text: String::new(),
+                tool_calls: vec![
+                    ApiToolCall {
+                        id: "t1".to_string(),
+                        name: "list_directory".to_string(),
+                        arguments: json!({}),
+                    },
+                    ApiToolCall {
+                        id: "t2".to_string(),
+                        name: "read_file".to_string(),
+                        arguments: json!({ "path": "a.txt" }),
+                    },
+                ],
+            },
+            ApiMessage::ToolResult {
+                call_id: "t1".to_string(),
+                content: "a.txt".to_string(),
+                is_error: false,
+            },
+            ApiMessage::ToolResult {
+                call_id: "t2".to_string(),
+                content: "hello".to_string(),
+                is_error: false,
+            },
+        ];
+
+        let anthropic = build_request(
+            &settings(ApiEngineProvider::Anthropic, ""),
+            "key",
+            "claude-sonnet-4-5",
+            "sys",
+            &history,
+            &[],
+        );
+        assert_eq!(anthropic.url, "https://api.anthropic.com/v1/messages");
+        let messages = anthropic.body["messages"].as_array().expect("messages");
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[2]["content"].as_array().map(Vec::len), Some(2));
+
+        let openai = build_request(
+            &settings(ApiEngineProvider::OpenAi, "http://localhost:11434/v1/"),
+            "key",
+            "gpt",
+            "sys",
+            &history,
+            &[],
+        );
+        assert_eq!(openai.url, "http://localhost:11434/v1/chat/completions");
+        let messages = openai.body["messages"].as_array().expect("messages");
+        assert_eq!(messages.len(), 5);
+        assert_eq!(
+            messages[2]["tool_calls"][1]["function"]["arguments"],
+            "{\"path\":\"a.txt\"}"
+        );
+        assert_eq!(messages[4]["tool_call_id"], "t2");
+    }
+
+    #[test]
+    fn accumulates_streamed_text_and_tool_calls() {
+        let mut buffer = String::from(
+            "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\ndata: {\"type\":\"content_blo",
+        );
+        let payloads = drain_sse_data_lines(&mut buffer);
+        assert_eq!(payloads.len(), 1);
+        assert!(buffer.starts_with("data: {\"type\":\"content_blo"));
+
+        let mut anthropic = StreamAccumulator::new(ApiEngineProvider::Anthropic);
+        for event in [
+            json!({ "type": "message_start", "message": { "usage": { "input_tokens": 12 } } }),
+            json!({ "type": "content_block_start", "index": 1, "content_block": { "type": "tool_use", "id": "tu_1", "name": "read_file" } }),
+            json!({ "type": "content_block_delta", "index": 1, "delta": { "type": "input_json_delta", "partial_json": "{\"path\":" } }),
+            json!({ "type": "content_block_delta", "index": 1, "delta": { "type": "input_json_delta", "partial_json": "\"a.txt\"}" } }),
+            json!({ "type": "message_delta", "delta": { "stop_reason": "tool_use" }, "usage": { "output_tokens": 7 } }),
+        ] {
+            anthropic.apply(&event).expect("apply");
+        }
+        assert_eq!(
+            anthropic.tool_calls()[0].arguments,
+            json!({ "path": "a.txt" })
+        );
+        assert_eq!(
+            (anthropic.input_tokens, anthropic.output_tokens),
+            (Some(12), Some(7))
+        );
+
+        let mut openai = StreamAccumulator::new(ApiEngineProvider::OpenAi);
+        for event in [
+            json!({ "choices": [{ "delta": { "content": "Let me look." } }] }),
+            json!({ "choices": [{ "delta": { "tool_calls": [{ "index": 0, "id": "c1", "function": { "name": "run_command", "arguments": "{\"command\"" } }] } }] }),
+            json!({ "choices": [{ "delta": { "tool_calls": [{ "index": 0, "function": { "arguments": ":\"ls\"}" } }] }, "finish_reason": "tool_calls" }] }),
+        ] {
+            openai.apply(&event).expect("apply");
+        }
+        assert_eq!(openai.text, "Let me look.");
+        let calls = openai.tool_calls();
+        assert_eq!(
+            (calls[0].id.as_str(), calls[0].name.as_str()),
+            ("c1", "run_command")
+        );
+        assert_eq!(calls[0].arguments, json!({ "command": "ls" }));
+
+        let error = json!({ "type": "error", "error": { "message": "overloaded" } });
+        assert_eq!(openai.apply(&error), Err("overloaded".to_string()));
+    }
+}
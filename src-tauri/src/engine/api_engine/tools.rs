@@ -0,0 +1,465 @@
+//! Built-in tools for the API engine, confined to the workspace root.
+
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use ignore::WalkBuilder;
+use serde_json::{json, Value};
+
+use super::providers::ApiToolSpec;
+use crate::shared::access_presets::AccessModePreset;
+
+const MAX_READ_BYTES: usize = 256 * 1024;
+const MAX_COMMAND_OUTPUT_BYTES: usize = 64 * 1024;
+const MAX_SEARCH_MATCHES: usize = 200;
+const MAX_LIST_ENTRIES: usize = 500;
+const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 120;
+const MAX_COMMAND_TIMEOUT_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ToolKind {
+    Read,
+    Write,
+    Command,
+}
+
+/// What the turn loop must do before running a tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ToolGate {
+    Allow,
+    Ask,
+    Deny,
+}
+
+pub(crate) struct ToolOutcome {
+    pub(crate) content: String,
+    pub(crate) is_error: bool,
+}
+
+impl ToolOutcome {
+    fn ok(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            is_error: false,
+        }
+    }
+
+    fn error(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            is_error: true,
+        }
+    }
+}
+
+pub(crate) fn tool_specs() -> Vec<ApiToolSpec> {
+    vec![
+        ApiToolSpec {
+            name: "read_file",
+            description: "Read a UTF-8 text file relative to the workspace root.",
+            parameters: json!({
+                "type": "object",
+                "properties": { "path": { "type": "string" } },
+                "required": ["path"],
+            }),
+        },
+        ApiToolSpec {
+            name: "list_directory",
+            description: "List entries of a directory relative to the workspace root.",
+            parameters: json!({
+                "type": "object",
+                "properties": { "path": { "type": "string" } },
+            }),
+        },
+        ApiToolSpec {
+            name: "search_files",
+            description: "Search workspace files (respecting .gitignore) for a regular expression.",
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "pattern": { "type": "string" },
+                    "path": { "type": "string" },
+                },
+                "required": ["pattern"],
+            }),
+        },
+        ApiToolSpec {
+            name: "write_file",
+            description: "Create or overwrite a file relative to the workspace root.",
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string" },
+                    "content": { "type": "string" },
+                },
+                "required": ["path", "content"],
+            }),
+        },
+        ApiToolSpec {
+            name: "edit_file",
+            description: "Replace exactly one occurrence of old_string with new_string in a file.",
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string" },
+                    "old_string": { "type": "string" },
+                    "new_string": { "type": "string" },
+                },
+                "required": ["path", "old_string", "new_string"],
+            }),
+        },
+        ApiToolSpec {
+            name: "run_command",
+            description: "Run a shell command in the workspace root and return its output.",
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "command": { "type": "string" },
+                    "timeout_secs": { "type": "integer" },
+                },
+                "required": ["command"],
+            }),
+        },
+    ]
+}
+
+pub(crate) fn tool_kind(name: &str) -> Option<ToolKind> {
+    match name {
+        "read_file" | "list_directory" | "search_files" => Some(ToolKind::Read),
+        "write_file" | "edit_file" => Some(ToolKind::Write),
+        "run_command" => Some(ToolKind::Command),
+        _ => None,
+    }
+}
+
+/// Maps the access mode onto the same approval rules the CLI engines use.
+pub(crate) fn tool_gate(access_mode: AccessModePreset, kind: ToolKind) -> ToolGate {
+    match (access_mode, kind) {
+        (_, ToolKind::Read) | (AccessModePreset::FullAuto, _) => ToolGate::Allow,
+        (AccessModePreset::ReadOnly, _) => ToolGate::Deny,
+        (AccessModePreset::AskBeforeWrite, _) => ToolGate::Ask,
+        (AccessModePreset::AutoEdit, ToolKind::Write) => ToolGate::Allow,
+        (AccessModePreset::AutoEdit, ToolKind::Command) => ToolGate::Ask,
+    }
+}
+
+/// Resolves a model-supplied path against the workspace root, rejecting
+/// anything that would escape it.
+pub(crate) fn resolve_workspace_path(root: &Path, requested: &str) -> Result<PathBuf, String> {
+    let requested = requested.trim();
+    let relative = Path::new(if requested.is_empty() { "." } else { requested });
+    let relative = if relative.is_absolute() {
+        relative
+            .strip_prefix(root)
+            .map_err(|_| format!("path is outside the workspace: {requested}"))?
+    } else {
+        relative
+    };
+    let mut resolved = root.to_path_buf();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if resolved == root || !resolved.pop() {
+                    return Err(format!("path is outside the workspace: {requested}"));
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(format!("path is outside the workspace: {requested}"));
+            }
+        }
+    }
+    // Symlinks inside the workspace must not lead out of it either. Paths
+    // that do not exist yet are judged by their nearest existing ancestor,
+    // so writes cannot create files through a link that points outside.
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|error| format!("failed to resolve workspace root: {error}"))?;
+    let existing = resolved
+        .ancestors()
+        .find(|ancestor| ancestor.symlink_metadata().is_ok())
+        .unwrap_or(root);
+    let canonical = existing
+        .canonicalize()
+        .map_err(|error| format!("failed to resolve {requested}: {error}"))?;
+    if !canonical.starts_with(&canonical_root) {
+        return Err(format!("path is outside the workspace: {requested}"));
+    }
+    Ok(resolved)
+}
+
+fn string_arg<'a>(arguments: &'a Value, key: &str) -> Result<&'a str, String> {
+    arguments
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("missing string argument `{key}`"))
+}
+
+fn truncate_output(mut text: String, limit: usize) -> String {
+    if text.len() <= limit {
+        return text;
+    }
+    let mut cut = limit;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    text.truncate(cut);
+    text.push_str("\n[output truncated]");
+    text
+}
+
+pub(crate) async fn execute_tool(root: &Path, name: &str, arguments: &Value) -> ToolOutcome {
+    let result = match name {
+        "read_file" => read_file(root, arguments),
+        "list_directory" => list_directory(root, arguments),
+        "search_files" => search_files(root, arguments),
+        "write_file" => write_file(root, arguments),
+        "edit_file" => edit_file(root, arguments),
+        "run_command" => run_command(root, arguments).await,
+        _ => Err(format!("unknown tool: {name}")),
+    };
+    match result {
+        Ok(outcome) => outcome,
+        Err(error) => ToolOutcome::error(error),
+    }
+}
+
+fn read_file(root: &Path, arguments: &Value) -> Result<ToolOutcome, String> {
+    let path = resolve_workspace_path(root, string_arg(arguments, "path")?)?;
+    let bytes = std::fs::read(&path).map_err(|error| format!("failed to read file: {error}"))?;
+    let text = String::from_utf8_lossy(&bytes).to_string();
+    Ok(ToolOutcome::ok(truncate_output(text, MAX_READ_BYTES)))
+}
+
+fn list_directory(root: &Path, arguments: &Value) -> Result<ToolOutcome, String> {
+    let requested = arguments.get("path").and_then(Value::as_str).unwrap_or(".");
+    let path = resolve_workspace_path(root, requested)?;
+    let mut entries = std::fs::read_dir(&path)
+        .map_err(|error| format!("failed to list directory: {error}"))?
+        .filter_map(Result::ok)
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                format!("{name}/")
+            } else {
+                name
+            }
+        })
+        .collect::<Vec<_>>();
+    entries.sort();
+    let total = entries.len();
+    entries.truncate(MAX_LIST_ENTRIES);
+    let mut listing = entries.join("\n");
+    if total > MAX_LIST_ENTRIES {
+        listing.push_str(&format!("\n[{} more entries]", total - MAX_LIST_ENTRIES));
+    }
+    Ok(ToolOutcome::ok(listing))
+}
+
+fn search_files(root: &Path, arguments: &Value) -> Result<ToolOutcome, String> {
+    let pattern = regex::Regex::new(string_arg(arguments, "pattern")?)
+        .map_err(|error| format!("invalid pattern: {error}"))?;
+    let requested = arguments.get("path").and_then(Value::as_str).unwrap_or(".");
+    let start = resolve_workspace_path(root, requested)?;
+    let mut matches = Vec::new();
+    'files: for entry in WalkBuilder::new(&start).build().filter_map(Result::ok) {
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let display = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        for (line_index, line) in text.lines().enumerate() {
+            if pattern.is_match(line) {
+                matches.push(format!("{display}:{}: {}", line_index + 1, line.trim()));
+                if matches.len() >= MAX_SEARCH_MATCHES {
+                    matches.push("[match limit reached]".to_string());
+                    break 'files;
+                }
+            }
+        }
+    }
+    if matches.is_empty() {
+        return Ok(ToolOutcome::ok("no matches"));
+    }
+    Ok(ToolOutcome::ok(matches.join("\n")))
+}
+
+fn write_file(root: &Path, arguments: &Value) -> Result<ToolOutcome, String> {
+    let path = resolve_workspace_path(root, string_arg(arguments, "path")?)?;
+    let content = string_arg(arguments, "content")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create directory: {error}"))?;
+    }
+    std::fs::write(&path, content).map_err(|error| format!("failed to write file: {error}"))?;
+    Ok(ToolOutcome::ok(format!("wrote {} bytes", content.len())))
+}
+
+fn edit_file(root: &Path, arguments: &Value) -> Result<ToolOutcome, String> {
+    let path = resolve_workspace_path(root, string_arg(arguments, "path")?)?;
+    let old_string = string_arg(arguments, "old_string")?;
+    let new_string = string_arg(arguments, "new_string")?;
+    if old_string.is_empty() {
+        return Err("old_string must not be empty".to_string());
+    }
+    let text =
+        std::fs::read_to_string(&path).map_err(|error| format!("failed to read file: {error}"))?;
+    match text.matches(old_string).count() {
+        0 => Err("old_string was not found in the file".to_string()),
+        1 => {
+            std::fs::write(&path, text.replacen(old_string, new_string, 1))
+                .map_err(|error| format!("failed to write file: {error}"))?;
+            Ok(ToolOutcome::ok("edit applied"))
+        }
+        count => Err(format!(
+            "old_string matches {count} times; include more context to make it unique"
+        )),
+    }
+}
+
+async fn run_command(root: &Path, arguments: &Value) -> Result<ToolOutcome, String> {
+    let command = string_arg(arguments, "command")?;
+    let timeout_secs = arguments
+        .get("timeout_secs")
+        .and_then(Value::as_u64)
+        .unwrap_or(DEFAULT_COMMAND_TIMEOUT_SECS)
+        .clamp(1, MAX_COMMAND_TIMEOUT_SECS);
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = crate::utils::async_command("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    };
+    #[cfg(not(windows))]
+    let mut cmd = {
+        let mut cmd = crate::utils::async_command("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    };
+    cmd.current_dir(root)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+    let output = tokio::time::timeout(Duration::from_secs(timeout_secs), cmd.output())
+        .await
+        .map_err(|_| format!("command timed out after {timeout_secs}s"))?
+        .map_err(|error| format!("failed to run command: {error}"))?;
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(&stderr);
+    }
+    let code = output
+        .status
+        .code()
+        .map(|code| code.to_string())
+        .unwrap_or_else(|| "signal".to_string());
+    text.push_str(&format!("\n[exit code: {code}]"));
+    Ok(ToolOutcome {
+        content: truncate_output(text, MAX_COMMAND_OUTPUT_BYTES),
+        is_error: !output.status.success(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_paths_cannot_escape_the_root() {
+        let root = std::env::temp_dir().join(format!("api-engine-tools-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("src")).expect("create workspace");
+
+        assert_eq!(
+            resolve_workspace_path(&root, "src/../a.txt").expect("inside"),
+            root.join("a.txt")
+        );
+        assert_eq!(
+            resolve_workspace_path(&root, &root.join("src").to_string_lossy()).expect("absolute"),
+            root.join("src")
+        );
+        assert!(resolve_workspace_path(&root, "../outside.txt").is_err());
+        assert!(resolve_workspace_path(&root, "/etc/passwd").is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn writes_cannot_follow_symlinks_out_of_the_root() {
+        let base = std::env::temp_dir().join(format!("api-engine-link-{}", uuid::Uuid::new_v4()));
+        let root = base.join("workspace");
+        let outside = base.join("outside");
+        std::fs::create_dir_all(&root).expect("create workspace");
+        std::fs::create_dir_all(&outside).expect("create outside");
+        std::os::unix::fs::symlink(&outside, root.join("link")).expect("link out");
+        std::os::unix::fs::symlink(outside.join("gone"), root.join("dangling"))
+            .expect("dangling link");
+
+        assert!(resolve_workspace_path(&root, "link/new.txt").is_err());
+        assert!(resolve_workspace_path(&root, "link/nested/new.txt").is_err());
+        assert!(resolve_workspace_path(&root, "dangling").is_err());
+        assert!(resolve_workspace_path(&root, "fresh/new.txt").is_ok());
+
+        let outcome = execute_tool(
+            &root,
+            "write_file",
+            &json!({ "path": "link/new.txt", "content": "escaped" }),
+        )
+        .await;
+        assert!(outcome.is_error);
+        assert!(!outside.join("new.txt").exists());
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn access_modes_gate_writes_and_commands() {
+        use AccessModePreset::*;
+        assert_eq!(tool_gate(ReadOnly, ToolKind::Read), ToolGate::Allow);
+        assert_eq!(tool_gate(ReadOnly, ToolKind::Write), ToolGate::Deny);
+        assert_eq!(tool_gate(AskBeforeWrite, ToolKind::Write), ToolGate::Ask);
+        assert_eq!(tool_gate(AutoEdit, ToolKind::Write), ToolGate::Allow);
+        assert_eq!(tool_gate(AutoEdit, ToolKind::Command), ToolGate::Ask);
+        assert_eq!(tool_gate(FullAuto, ToolKind::Command), ToolGate::Allow);
+    }
+
+    #[tokio::test]
+    async fn edit_file_requires_a_unique_match() {
+        let root = std::env::temp_dir().join(format!("api-engine-edit-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).expect("create workspace");
+        std::fs::write(root.join("a.txt"), "one two two").expect("seed");
+
+        let ambiguous = execute_tool(
+            &root,
+            "edit_file",
+            &json!({ "path": "a.txt", "old_string": "two", "new_string": "2" }),
+        )
+        .await;
+        assert!(ambiguous.is_error);
+
+        let applied = execute_tool(
+            &root,
+            "edit_file",
+            &json!({ "path": "a.txt", "old_string": "one", "new_string": "1" }),
+        )
+        .await;
+        assert!(!applied.is_error);
+        assert_eq!(
+            std::fs::read_to_string(root.join("a.txt")).expect("read"),
+            "1 two two"
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -7,7 +7,7 @@ use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::sync::Mutex as StdMutex;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
@@ -20,6 +20,7 @@ use tokio::time::sleep;
 use super::claude_message_content::{build_message_content, format_ask_user_answer};
 use super::events::EngineEvent;
 use super::{EngineConfig, EngineType, SendMessageParams};
+use crate::shared::access_presets::AccessModePreset;
 #[path = "claude/approval.rs"]
 mod approval;
 #[path = "claude/event_conversion.rs"]
@@ -30,6 +31,8 @@ mod manager;
 #[path = "claude_stream_helpers.rs"]
 mod stream_helpers;
 mod user_input;
+#[path = "claude/warm_pool.rs"]
+mod warm_pool;
 use approval::{
     classify_claude_mode_blocked_tool, command_can_apply_as_local_file_action,
     extract_claude_command_string, looks_like_claude_permission_denial_message,
@@ -53,6 +56,8 @@ use stream_helpers::{
     is_claude_stream_control_line, looks_like_claude_runtime_error, merge_text_chunks,
     parse_claude_stream_json_line, tool_input_signature,
 };
+use warm_pool::{command_signature, ClaudeWarmPool};
+pub(crate) use warm_pool::{ClaudeWarmPoolConfig, WarmStartMetrics};
 
 #[derive(Debug, Clone)]
 pub struct ClaudeTurnEvent {
@@ -130,6 +135,8 @@ const CLAUDE_STREAM_FIRST_EVENT_TIMEOUT: Duration = Duration::from_secs(90);
 #[cfg(test)]
 const CLAUDE_STREAM_FIRST_EVENT_TIMEOUT: Duration = Duration::from_secs(10);
 const CLAUDE_STREAM_DIAGNOSTIC_SAMPLE_LIMIT: usize = 800;
+/// How long a turn waits for an AskUserQuestion answer unless configured.
+pub(crate) const DEFAULT_USER_INPUT_TIMEOUT: Duration = Duration::from_secs(300);
 const CLAUDE_REASONING_EFFORTS: &[&str] = &["low", "medium", "high", "xhigh", "max"];
 
 #[derive(Debug, Default)]
@@ -263,6 +270,10 @@ pub struct ClaudeSession {
     user_input_notify_by_turn: StdMutex<HashMap<String, Arc<Notify>>>,
     /// Per-turn formatted AskUserQuestion answer for kill+resume mechanism
     user_input_answer_by_turn: StdMutex<HashMap<String, String>>,
+    /// Seconds a turn waits for an AskUserQuestion answer before it is cancelled
+    user_input_timeout_secs: AtomicU64,
+    /// Pre-spawned process for the next turn, when warm start is enabled
+    warm_pool: Arc<ClaudeWarmPool>,
 }
 
 impl ClaudeSession {
@@ -519,6 +530,8 @@ impl ClaudeSession {
             approval_resume_message_by_turn: StdMutex::new(HashMap::new()),
             user_input_notify_by_turn: StdMutex::new(HashMap::new()),
             user_input_answer_by_turn: StdMutex::new(HashMap::new()),
+            user_input_timeout_secs: AtomicU64::new(DEFAULT_USER_INPUT_TIMEOUT.as_secs()),
+            warm_pool: Arc::new(ClaudeWarmPool::default()),
         }
     }
 
@@ -543,6 +556,20 @@ impl ClaudeSession {
 
     pub(crate) fn mark_disposed(&self) {
         self.disposed.store(true, Ordering::SeqCst);
+        self.warm_pool.clear();
+    }
+
+    pub(crate) fn set_warm_pool_config(&self, config: ClaudeWarmPoolConfig) {
+        self.warm_pool.set_config(config);
+    }
+
+    pub(crate) fn set_user_input_timeout(&self, timeout: Duration) {
+        self.user_input_timeout_secs
+            .store(timeout.as_secs().max(1), Ordering::SeqCst);
+    }
+
+    pub(crate) fn user_input_timeout(&self) -> Duration {
+        Duration::from_secs(self.user_input_timeout_secs.load(Ordering::SeqCst))
     }
 
     /// Emit a TurnError event to notify the frontend when an error occurs
@@ -598,6 +625,16 @@ impl ClaudeSession {
         );
     }
 
+    pub fn emit_turn_timeout(&self, turn_id: &str, timeout_secs: u64) {
+        self.emit_turn_event(
+            turn_id,
+            EngineEvent::TurnTimeout {
+                workspace_id: self.workspace_id.clone(),
+                timeout_secs,
+            },
+        );
+    }
+
     async fn fail_send_setup_and_terminate_child(
         &self,
         turn_id: &str,
@@ -642,8 +679,14 @@ impl ClaudeSession {
         // Use build_command_for_binary to properly handle .cmd/.bat files on Windows
         let mut cmd = crate::backend::app_server::build_command_for_binary(&bin);
 
-        // Set working directory
-        cmd.current_dir(&self.workspace_path);
+        // Set working directory (per-turn override is validated by the caller)
+        cmd.current_dir(
+            params
+                .cwd
+                .as_deref()
+                .map(std::path::Path::new)
+                .unwrap_or(&self.workspace_path),
+        );
 
         // Print mode (non-interactive)
         cmd.arg("-p");
@@ -674,27 +717,13 @@ impl ClaudeSession {
         }
 
         // Access mode / permission handling
-        // Maps UI access modes to Claude Code CLI permission flags
-        match params.access_mode.as_deref() {
-            Some("full-access") => {
-                // Full access: bypass all permission checks
-                cmd.arg("--dangerously-skip-permissions");
-            }
-            Some("read-only") => {
-                // Read-only / Plan mode: only allow planning, no execution
-                cmd.arg("--permission-mode");
-                cmd.arg("plan");
-            }
-            Some("default") => {
-                // Default mode: each tool use requires explicit permission
-                cmd.arg("--permission-mode");
-                cmd.arg("default");
-            }
-            _ => {
-                // "current" mode: auto-accept edits but still prompt for dangerous ops
-                cmd.arg("--permission-mode");
-                cmd.arg("acceptEdits");
-            }
+        // Maps access mode presets (and legacy UI values) to Claude Code CLI permission flags
+        let preset = AccessModePreset::resolve(params.access_mode.as_deref());
+        if preset == AccessModePreset::FullAuto {
+            cmd.arg("--dangerously-skip-permissions");
+        } else {
+            cmd.arg("--permission-mode");
+            cmd.arg(preset.claude_permission_mode());
         }
 
         // Model selection
@@ -774,6 +803,10 @@ impl ClaudeSession {
         if params.disable_thinking {
             cmd.env("CLAUDE_CODE_DISABLE_THINKING", "1");
         }
+        crate::shared::provider_tls::apply_active_provider_tls_env(
+            &mut cmd,
+            crate::shared::provider_tls::TlsProviderEngine::Claude,
+        );
 
         cmd
     }
@@ -784,7 +817,7 @@ impl ClaudeSession {
         params: SendMessageParams,
         turn_id: &str,
     ) -> Result<String, String> {
-        match self
+        let result = match self
             .send_message_attempt(params.clone(), turn_id, true)
             .await
         {
@@ -793,10 +826,15 @@ impl ClaudeSession {
                     "[claude] --include-hook-events unsupported, retrying without hook events: {}",
                     error
                 );
-                self.send_message_attempt(params, turn_id, false).await
+                self.send_message_attempt(params.clone(), turn_id, false)
+                    .await
             }
             result => result,
+        };
+        if result.is_ok() {
+            self.prewarm_next_turn(&params).await;
         }
+        result
     }
 
     async fn send_message_attempt(
@@ -837,17 +875,27 @@ impl ClaudeSession {
             return Err(error_msg);
         }
 
-        let use_stream_json_input = Self::should_use_stream_json_input(&params);
+        // Warm processes are always started in stream-json input mode, so
+        // turns that may reuse one must send their prompt over stdin too.
+        let use_stream_json_input =
+            self.warm_pool.is_enabled() || Self::should_use_stream_json_input(&params);
 
         let mut cmd = self.build_command(&params, use_stream_json_input, include_hook_events);
         Self::configure_spawn_command(&mut cmd);
+        let warm_child = self.warm_pool.take_matching(&command_signature(&cmd));
+        let warm_started = warm_child.is_some();
+        self.warm_pool.record_start(warm_started);
 
         // Spawn the process
         let mut stream_startup_timing = ClaudeStreamStartupTiming {
             process_spawn_started_at_ms: Some(unix_timestamp_ms()),
             ..ClaudeStreamStartupTiming::default()
         };
-        let mut child = match cmd.spawn() {
+        let spawned = match warm_child {
+            Some(child) => Ok(child),
+            None => cmd.spawn(),
+        };
+        let mut child = match spawned {
             Ok(child) => child,
             Err(e) => {
                 let error_msg = format!("Failed to spawn claude: {}", e);
@@ -1007,6 +1055,7 @@ impl ClaudeSession {
             EngineEvent::TurnStarted {
                 workspace_id: self.workspace_id.clone(),
                 turn_id: turn_id.to_string(),
+                cwd: params.cwd.clone(),
             },
         );
 
@@ -1321,6 +1370,13 @@ impl ClaudeSession {
         }
 
         self.flush_buffered_text_delta(turn_id, &mut pending_text_delta);
+        if let (Some(started_at_ms), Some(first_event_at_ms)) = (
+            stream_startup_timing.process_spawn_started_at_ms,
+            stream_startup_timing.first_valid_stream_event_at_ms,
+        ) {
+            self.warm_pool
+                .record_first_event(warm_started, started_at_ms, first_event_at_ms);
+        }
 
         // Wait for process to complete
         let mut child = {
@@ -1624,10 +1680,15 @@ impl ClaudeSession {
             .lock()
             .unwrap_or_else(|e| e.into_inner())
             .clear();
-        self.user_input_notify_by_turn
+        // Wake turns still waiting on a question so they wind down now.
+        for (_, notify) in self
+            .user_input_notify_by_turn
             .lock()
             .unwrap_or_else(|e| e.into_inner())
-            .clear();
+            .drain()
+        {
+            notify.notify_one();
+        }
         self.user_input_answer_by_turn
             .lock()
             .unwrap_or_else(|e| e.into_inner())
@@ -2098,3 +2159,6 @@ mod tests_core;
 #[cfg(test)]
 #[path = "claude/tests_path_approval.rs"]
 mod tests_path_approval;
+#[cfg(test)]
+#[path = "claude/tests_warm_pool.rs"]
+mod tests_warm_pool;
@@ -271,6 +271,10 @@ impl ClaudeSession {
         if let Some(home) = &self.home_dir {
             cmd.env("CLAUDE_HOME", home);
         }
+        crate::shared::provider_tls::apply_active_provider_tls_env(
+            &mut cmd,
+            crate::shared::provider_tls::TlsProviderEngine::Claude,
+        );
 
         let output =
             match tokio::time::timeout(std::time::Duration::from_secs(30), cmd.output()).await {
@@ -4,6 +4,8 @@ use super::*;
 pub struct ClaudeSessionManager {
     sessions: Mutex<HashMap<String, Arc<ClaudeSession>>>,
     default_config: RwLock<EngineConfig>,
+    warm_pool_config: RwLock<ClaudeWarmPoolConfig>,
+    user_input_timeout: RwLock<Duration>,
 }
 
 impl ClaudeSessionManager {
@@ -11,6 +13,8 @@ impl ClaudeSessionManager {
         Self {
             sessions: Mutex::new(HashMap::new()),
             default_config: RwLock::new(EngineConfig::default()),
+            warm_pool_config: RwLock::new(ClaudeWarmPoolConfig::default()),
+            user_input_timeout: RwLock::new(DEFAULT_USER_INPUT_TIMEOUT),
         }
     }
 
@@ -19,6 +23,33 @@ impl ClaudeSessionManager {
         *self.default_config.write().await = config;
     }
 
+    /// Apply warm-start settings to new and existing sessions
+    pub async fn set_warm_pool_config(&self, config: ClaudeWarmPoolConfig) {
+        *self.warm_pool_config.write().await = config;
+        let sessions = self.sessions.lock().await;
+        for session in sessions.values() {
+            session.set_warm_pool_config(config);
+        }
+    }
+
+    /// Apply the AskUserQuestion answer timeout to new and existing sessions
+    pub async fn set_user_input_timeout(&self, timeout: Duration) {
+        *self.user_input_timeout.write().await = timeout;
+        let sessions = self.sessions.lock().await;
+        for session in sessions.values() {
+            session.set_user_input_timeout(timeout);
+        }
+    }
+
+    /// Warm vs cold start counters per workspace
+    pub async fn warm_start_metrics(&self) -> Vec<(String, WarmStartMetrics)> {
+        let sessions = self.sessions.lock().await;
+        sessions
+            .iter()
+            .map(|(workspace_id, session)| (workspace_id.clone(), session.warm_start_metrics()))
+            .collect()
+    }
+
     /// Get or create a session for a workspace
     pub async fn get_or_create_session(
         &self,
@@ -37,6 +68,8 @@ impl ClaudeSessionManager {
             workspace_path.to_path_buf(),
             Some(config),
         ));
+        session.set_warm_pool_config(*self.warm_pool_config.read().await);
+        session.set_user_input_timeout(*self.user_input_timeout.read().await);
 
         sessions.insert(workspace_id.to_string(), session.clone());
         session
@@ -67,6 +100,7 @@ impl ClaudeSessionManager {
     pub async fn interrupt_all(&self) {
         let sessions = self.sessions.lock().await;
         for session in sessions.values() {
+            session.warm_pool.clear();
             let _ = session.interrupt().await;
         }
     }
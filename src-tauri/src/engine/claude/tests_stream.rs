@@ -278,6 +278,59 @@ async fn respond_to_user_input_rejects_mismatched_request_id() {
     assert!(session.has_any_pending_user_input());
 }
 
+#[tokio::test]
+async fn unanswered_user_input_cancels_turn_after_timeout() {
+    let session = ClaudeSession::new("test-workspace".to_string(), test_workspace_path(), None);
+    session.set_user_input_timeout(Duration::from_secs(1));
+    if let Ok(mut pending) = session.pending_user_inputs.lock() {
+        pending.insert("ask-timeout".to_string(), "turn-1".to_string());
+    }
+
+    let err = session
+        .handle_ask_user_question_resume("turn-1", &SendMessageParams::default(), &None, false)
+        .await
+        .expect_err("unanswered question cancels the turn");
+
+    assert!(err.contains("not answered within 1s"));
+    assert!(!session.has_any_pending_user_input());
+}
+
+#[tokio::test]
+async fn interrupt_turn_releases_pending_user_input_wait() {
+    let session = Arc::new(ClaudeSession::new(
+        "test-workspace".to_string(),
+        test_workspace_path(),
+        None,
+    ));
+    if let Ok(mut pending) = session.pending_user_inputs.lock() {
+        pending.insert("ask-interrupt".to_string(), "turn-1".to_string());
+    }
+    let waiter = {
+        let session = session.clone();
+        tokio::spawn(async move {
+            session
+                .handle_ask_user_question_resume(
+                    "turn-1",
+                    &SendMessageParams::default(),
+                    &None,
+                    false,
+                )
+                .await
+                .map(|lines| lines.is_none())
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    session.interrupt_turn("turn-1").await.expect("interrupt");
+    let resumed = tokio::time::timeout(Duration::from_secs(5), waiter)
+        .await
+        .expect("waiter released before the question timeout")
+        .expect("waiter task");
+
+    assert_eq!(resumed, Ok(true));
+    assert!(!session.has_any_pending_user_input());
+}
+
 #[test]
 fn build_command_adds_external_spec_root_when_configured() {
     let session = ClaudeSession::new("test-workspace".to_string(), test_workspace_path(), None);
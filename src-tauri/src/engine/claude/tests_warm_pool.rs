@@ -0,0 +1,54 @@
+use super::warm_pool::{command_signature, ClaudeWarmPool};
+use super::*;
+
+#[test]
+fn command_signature_tracks_args_cwd_and_env() {
+    let build = |resume: &str, env: Option<&str>| {
+        let mut cmd = Command::new("claude");
+        cmd.arg("-p")
+            .arg("--resume")
+            .arg(resume)
+            .current_dir("/tmp");
+        if let Some(value) = env {
+            cmd.env("CLAUDE_CODE_DISABLE_THINKING", value);
+        }
+        command_signature(&cmd)
+    };
+    assert_eq!(build("s-1", None), build("s-1", None));
+    assert_ne!(build("s-1", None), build("s-2", None));
+    assert_ne!(build("s-1", None), build("s-1", Some("1")));
+}
+
+#[test]
+fn next_turn_params_resume_the_finished_session() {
+    let params = SendMessageParams {
+        text: "hello".to_string(),
+        model: Some("sonnet".to_string()),
+        images: Some(vec!["/tmp/a.png".to_string()]),
+        session_id: Some("new-id".to_string()),
+        fork_session_id: Some("parent".to_string()),
+        ..SendMessageParams::default()
+    };
+    let next = ClaudeSession::next_turn_params(&params, "s-1".to_string());
+    assert!(next.continue_session);
+    assert_eq!(next.session_id.as_deref(), Some("s-1"));
+    assert_eq!(next.model.as_deref(), Some("sonnet"));
+    assert!(next.images.is_none() && next.fork_session_id.is_none());
+    assert!(next.text.is_empty());
+}
+
+#[tokio::test]
+async fn metrics_count_warm_and_cold_starts() {
+    let pool = ClaudeWarmPool::default();
+    pool.record_start(false);
+    pool.record_start(true);
+    pool.record_first_event(false, 1_000, 3_000);
+    pool.record_first_event(true, 1_000, 1_200);
+    assert!(pool.take_matching("anything").is_none());
+
+    let metrics = pool.metrics();
+    assert_eq!((metrics.warm_starts, metrics.cold_starts), (1, 1));
+    assert_eq!(metrics.avg_cold_first_event_ms, Some(2_000));
+    assert_eq!(metrics.avg_warm_first_event_ms, Some(200));
+    assert!(!metrics.idle_ready);
+}
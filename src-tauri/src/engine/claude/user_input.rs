@@ -49,8 +49,14 @@ impl ClaudeSession {
         if let Ok(mut pending) = self.pending_approval_requests.lock() {
             pending.retain(|_, value| value != turn_id);
         }
-        if let Ok(mut notifies) = self.user_input_notify_by_turn.lock() {
-            notifies.remove(turn_id);
+        if let Some(notify) = self
+            .user_input_notify_by_turn
+            .lock()
+            .ok()
+            .and_then(|mut notifies| notifies.remove(turn_id))
+        {
+            // Wake a turn still waiting on the question so it can wind down.
+            notify.notify_one();
         }
         if let Ok(mut answers) = self.user_input_answer_by_turn.lock() {
             answers.remove(turn_id);
@@ -372,13 +378,35 @@ impl ClaudeSession {
             .unwrap_or(false)
     }
 
+    /// Terminate the turn's process after its AskUserQuestion went unanswered
+    /// and return the message the turn is cancelled with.
+    async fn cancel_unanswered_user_input(&self, turn_id: &str, timeout: Duration) -> String {
+        self.clear_pending_user_inputs_for_turn(turn_id);
+        let existing_child = {
+            let mut active = self.active_processes.lock().await;
+            active.remove(turn_id)
+        };
+        if let Some(mut child) = existing_child {
+            if let Err(error) = self.terminate_child_process(turn_id, &mut child).await {
+                log::debug!(
+                    "[claude] Failed to terminate unanswered AskUserQuestion process (turn={}): {}",
+                    turn_id,
+                    error
+                );
+            }
+        }
+        unanswered_user_input_message(timeout)
+    }
+
     /// Handle the AskUserQuestion flow: wait for user response, then kill the
     /// current CLI process and restart it with `--resume` carrying the user's
     /// actual answer.
     ///
     /// Returns the new stdout `Lines` reader if successfully resumed.
-    /// `Ok(None)` means we should continue reading from the current process.
-    /// `Err` means resume failed after the original process was already terminated.
+    /// `Ok(None)` means we should continue reading from the current process,
+    /// which is also how an interrupt that cleared the question wakes us.
+    /// `Err` means the turn is over: resume failed after the original process
+    /// was terminated, or nobody answered within the configured timeout.
     pub(super) async fn handle_ask_user_question_resume(
         &self,
         turn_id: &str,
@@ -387,18 +415,23 @@ impl ClaudeSession {
         include_hook_events: bool,
     ) -> Result<Option<tokio::io::Lines<BufReader<tokio::process::ChildStdout>>>, String> {
         let notify = self.get_or_create_user_input_notify(turn_id);
-        log::info!("AskUserQuestion detected, waiting for user (up to 5 min)…");
+        let timeout = self.user_input_timeout();
+        log::info!(
+            "AskUserQuestion detected, waiting for user (up to {}s)…",
+            timeout.as_secs()
+        );
         let user_answered = tokio::select! {
             _ = notify.notified() => true,
-            _ = tokio::time::sleep(
-                std::time::Duration::from_secs(300)
-            ) => false,
+            _ = tokio::time::sleep(timeout) => false,
         };
 
         if !user_answered {
-            log::info!("AskUserQuestion timed out (5 min), resuming original");
-            self.clear_pending_user_inputs_for_turn(turn_id);
-            return Ok(None);
+            log::info!(
+                "AskUserQuestion timed out ({}s), cancelling turn={}",
+                timeout.as_secs(),
+                turn_id
+            );
+            return Err(self.cancel_unanswered_user_input(turn_id, timeout).await);
         }
 
         // Grab the formatted answer for this turn only.
@@ -630,3 +663,10 @@ impl ClaudeSession {
         Ok(())
     }
 }
+
+fn unanswered_user_input_message(timeout: Duration) -> String {
+    format!(
+        "Claude asked a question that was not answered within {}s, so the turn was cancelled. Send a new message to continue.",
+        timeout.as_secs()
+    )
+}
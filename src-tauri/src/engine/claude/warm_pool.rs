@@ -0,0 +1,268 @@
+//! Optional warm-start pool for Claude CLI processes.
+//!
+//! After a turn finishes, the session pre-spawns the process the next turn in
+//! the same conversation would use (`--resume <session>`, stream-json input)
+//! and leaves it blocked on stdin. The next `send_message` whose command line
+//! matches takes it over and only has to write the prompt; anything else, or
+//! an idle timeout, discards it.
+
+use super::*;
+use crate::types::EngineWarmPoolSettings;
+use serde::Serialize;
+use std::sync::atomic::AtomicU64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ClaudeWarmPoolConfig {
+    pub(crate) enabled: bool,
+    pub(crate) idle_timeout: Duration,
+}
+
+impl Default for ClaudeWarmPoolConfig {
+    fn default() -> Self {
+        Self::from(&EngineWarmPoolSettings::default())
+    }
+}
+
+impl From<&EngineWarmPoolSettings> for ClaudeWarmPoolConfig {
+    fn from(settings: &EngineWarmPoolSettings) -> Self {
+        Self {
+            enabled: settings.enabled,
+            idle_timeout: Duration::from_secs(settings.idle_timeout_secs.max(1)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WarmStartMetrics {
+    pub(crate) warm_starts: u64,
+    pub(crate) cold_starts: u64,
+    /// Processes spawned ahead of a turn.
+    pub(crate) prewarmed: u64,
+    /// Warm processes killed by the idle timeout.
+    pub(crate) expired: u64,
+    /// Warm processes dropped because the next turn needed other arguments.
+    pub(crate) discarded: u64,
+    pub(crate) avg_warm_first_event_ms: Option<u64>,
+    pub(crate) avg_cold_first_event_ms: Option<u64>,
+    /// Whether a warm process is currently waiting.
+    pub(crate) idle_ready: bool,
+    #[serde(skip)]
+    warm_first_event_total_ms: u64,
+    #[serde(skip)]
+    warm_first_event_samples: u64,
+    #[serde(skip)]
+    cold_first_event_total_ms: u64,
+    #[serde(skip)]
+    cold_first_event_samples: u64,
+}
+
+struct WarmClaudeProcess {
+    signature: String,
+    generation: u64,
+    child: Child,
+}
+
+#[derive(Default)]
+pub(crate) struct ClaudeWarmPool {
+    config: StdMutex<ClaudeWarmPoolConfig>,
+    slot: StdMutex<Option<WarmClaudeProcess>>,
+    generation: AtomicU64,
+    metrics: StdMutex<WarmStartMetrics>,
+}
+
+/// Identity of a spawn: everything that must match for a warm process to
+/// stand in for a fresh one.
+pub(crate) fn command_signature(cmd: &Command) -> String {
+    let cmd = cmd.as_std();
+    let mut signature = cmd.get_program().to_string_lossy().to_string();
+    for arg in cmd.get_args() {
+        signature.push('\u{1f}');
+        signature.push_str(&arg.to_string_lossy());
+    }
+    signature.push('\u{1e}');
+    if let Some(dir) = cmd.get_current_dir() {
+        signature.push_str(&dir.to_string_lossy());
+    }
+    let mut envs = cmd
+        .get_envs()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                key.to_string_lossy(),
+                value
+                    .map(|value| value.to_string_lossy())
+                    .unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>();
+    envs.sort();
+    for env in envs {
+        signature.push('\u{1e}');
+        signature.push_str(&env);
+    }
+    signature
+}
+
+impl ClaudeWarmPool {
+    pub(crate) fn config(&self) -> ClaudeWarmPoolConfig {
+        self.config.lock().map(|config| *config).unwrap_or_default()
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.config().enabled
+    }
+
+    pub(crate) fn set_config(&self, config: ClaudeWarmPoolConfig) {
+        if let Ok(mut current) = self.config.lock() {
+            *current = config;
+        }
+        if !config.enabled {
+            self.clear();
+        }
+    }
+
+    /// Drops the idle process, if any. Warm children are spawned with
+    /// `kill_on_drop`, so this also terminates them.
+    pub(crate) fn clear(&self) {
+        if let Ok(mut slot) = self.slot.lock() {
+            slot.take();
+        }
+    }
+
+    /// Hands over the idle process when it was spawned with exactly this
+    /// command line and is still alive.
+    pub(crate) fn take_matching(&self, signature: &str) -> Option<Child> {
+        let mut taken = self.slot.lock().ok()?.take()?;
+        let alive = matches!(taken.child.try_wait(), Ok(None));
+        if alive && taken.signature == signature {
+            return Some(taken.child);
+        }
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.discarded += 1;
+        }
+        None
+    }
+
+    /// Parks `child` as the idle process and arms its idle timeout.
+    pub(crate) fn park(self: &Arc<Self>, signature: String, child: Child) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let Ok(mut slot) = self.slot.lock() else {
+            return;
+        };
+        *slot = Some(WarmClaudeProcess {
+            signature,
+            generation,
+            child,
+        });
+        drop(slot);
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.prewarmed += 1;
+        }
+
+        let pool = Arc::clone(self);
+        let idle_timeout = self.config().idle_timeout;
+        tokio::spawn(async move {
+            tokio::time::sleep(idle_timeout).await;
+            let expired = pool.slot.lock().ok().and_then(|mut slot| {
+                if slot.as_ref()?.generation == generation {
+                    slot.take()
+                } else {
+                    None
+                }
+            });
+            if expired.is_some() {
+                if let Ok(mut metrics) = pool.metrics.lock() {
+                    metrics.expired += 1;
+                }
+            }
+        });
+    }
+
+    pub(crate) fn record_start(&self, warm: bool) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            if warm {
+                metrics.warm_starts += 1;
+            } else {
+                metrics.cold_starts += 1;
+            }
+        }
+    }
+
+    /// Records time from spawn (or warm hand-off) to the first stream event.
+    pub(crate) fn record_first_event(&self, warm: bool, started_at_ms: u64, first_event_ms: u64) {
+        let elapsed = first_event_ms.saturating_sub(started_at_ms);
+        let Ok(mut metrics) = self.metrics.lock() else {
+            return;
+        };
+        if warm {
+            metrics.warm_first_event_total_ms += elapsed;
+            metrics.warm_first_event_samples += 1;
+        } else {
+            metrics.cold_first_event_total_ms += elapsed;
+            metrics.cold_first_event_samples += 1;
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> WarmStartMetrics {
+        let mut metrics = self
+            .metrics
+            .lock()
+            .map(|metrics| metrics.clone())
+            .unwrap_or_default();
+        metrics.avg_warm_first_event_ms = (metrics.warm_first_event_samples > 0)
+            .then(|| metrics.warm_first_event_total_ms / metrics.warm_first_event_samples);
+        metrics.avg_cold_first_event_ms = (metrics.cold_first_event_samples > 0)
+            .then(|| metrics.cold_first_event_total_ms / metrics.cold_first_event_samples);
+        metrics.idle_ready = self.slot.lock().is_ok_and(|slot| slot.is_some());
+        metrics
+    }
+}
+
+impl ClaudeSession {
+    /// Params the next turn of the same conversation is expected to use.
+    pub(super) fn next_turn_params(
+        params: &SendMessageParams,
+        session_id: String,
+    ) -> SendMessageParams {
+        SendMessageParams {
+            text: String::new(),
+            images: None,
+            continue_session: true,
+            session_id: Some(session_id),
+            fork_session_id: None,
+            ..params.clone()
+        }
+    }
+
+    /// Spawns the follow-up process for the conversation that just finished a
+    /// turn, so the next message can skip CLI startup.
+    pub(crate) async fn prewarm_next_turn(&self, params: &SendMessageParams) {
+        if !self.warm_pool.is_enabled() || self.is_disposed() {
+            return;
+        }
+        let Some(session_id) = self.get_session_id().await else {
+            return;
+        };
+        let next_params = Self::next_turn_params(params, session_id);
+        if Self::normalized_fork_session_id(&next_params).is_err() {
+            return;
+        }
+        let mut cmd = self.build_command(&next_params, true, true);
+        Self::configure_spawn_command(&mut cmd);
+        cmd.kill_on_drop(true);
+        let signature = command_signature(&cmd);
+        match cmd.spawn() {
+            Ok(child) => self.warm_pool.park(signature, child),
+            Err(error) => log::debug!(
+                "[claude] warm-start prespawn failed (workspace={}): {}",
+                self.workspace_id,
+                error
+            ),
+        }
+    }
+
+    pub(crate) fn warm_start_metrics(&self) -> WarmStartMetrics {
+        self.warm_pool.metrics()
+    }
+}
@@ -17,6 +17,9 @@ use super::super::EngineType;
 use super::{extract_turn_result_text, should_prefer_turn_result_text};
 
 pub(crate) const CLAUDE_RUNTIME_SYNC_HEARTBEAT_SECS: u64 = 2;
+/// Tauri event announcing a Claude AskUserQuestion prompt; answered with
+/// `claude_respond_to_question`.
+pub(crate) const CLAUDE_USER_QUESTION_EVENT: &str = "claude-user-question";
 
 pub(crate) type ClaudeForwarderFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
 
@@ -128,6 +131,10 @@ impl ClaudeForwarderState {
         }
     }
 
+    pub(crate) fn current_thread_id(&self) -> &str {
+        &self.current_thread_id
+    }
+
     fn should_queue_runtime_sync(&mut self, now: Instant) -> bool {
         let should_queue = self
             .last_runtime_sync_queued_at
@@ -170,6 +177,44 @@ fn unix_timestamp_ms() -> u64 {
         .unwrap_or(0)
 }
 
+/// `claude-user-question` payload for an AskUserQuestion request: the
+/// question id to answer with, the prompt text, and each question with its
+/// option list. `None` for any other event.
+pub(crate) fn claude_user_question_payload(
+    event: &EngineEvent,
+    thread_id: &str,
+    turn_id: &str,
+    timeout: Duration,
+) -> Option<Value> {
+    let EngineEvent::RequestUserInput {
+        workspace_id,
+        request_id,
+        questions,
+    } = event
+    else {
+        return None;
+    };
+    let questions = questions.as_array()?;
+    let prompt = questions
+        .iter()
+        .filter_map(|question| question.get("question").and_then(Value::as_str))
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(json!({
+        "workspaceId": workspace_id,
+        "sessionId": thread_id.strip_prefix("claude:"),
+        "threadId": thread_id,
+        "turnId": turn_id,
+        "questionId": request_id,
+        "prompt": prompt,
+        "questions": questions,
+        "timeoutSecs": timeout.as_secs(),
+        "expiresAtMs": unix_timestamp_ms().saturating_add(timeout.as_millis() as u64),
+    }))
+}
+
 fn saturating_gap_ms(later: u64, earlier: Option<u64>) -> Option<u64> {
     earlier.map(|earlier_ms| later.saturating_sub(earlier_ms))
 }
@@ -36,7 +36,7 @@ const CLAUDE_ATTRIBUTION_STRICT_MATCH: &str = "strict-match";
 const CLAUDE_ATTRIBUTION_REASON_PROJECT_DIRECTORY: &str = "claude-project-directory";
 const CLAUDE_ATTRIBUTION_REASON_TRANSCRIPT_CWD: &str = "claude-transcript-cwd";
 const CLAUDE_ATTRIBUTION_REASON_GIT_ROOT: &str = "claude-git-root";
-fn normalize_session_id(session_id: &str) -> Result<String, String> {
+pub(super) fn normalize_session_id(session_id: &str) -> Result<String, String> {
     normalize_claude_session_id(session_id)
 }
 
@@ -100,7 +100,7 @@ pub(crate) fn encode_project_path(path: &str) -> String {
 }
 
 /// Get the Claude projects base directory (`<effective-claude-home>/projects`).
-fn claude_projects_dir(config: Option<&EngineConfig>) -> Option<PathBuf> {
+pub(super) fn claude_projects_dir(config: Option<&EngineConfig>) -> Option<PathBuf> {
     crate::claude_home::resolve_claude_projects_dir(config)
 }
 
@@ -151,7 +151,7 @@ fn is_encoded_workspace_prefix_match(candidate: &str, encoded_workspace: &str) -
         .is_some_and(|next| *next == b'-')
 }
 
-fn claude_project_dirs_for_path(base_dir: &Path, workspace_path: &Path) -> Vec<PathBuf> {
+pub(crate) fn claude_project_dirs_for_path(base_dir: &Path, workspace_path: &Path) -> Vec<PathBuf> {
     let mut dirs = Vec::new();
     let mut seen = HashSet::new();
     let mut encoded_workspace_paths = Vec::new();
@@ -195,7 +195,7 @@ fn claude_project_dirs_for_path(base_dir: &Path, workspace_path: &Path) -> Vec<P
     dirs
 }
 
-fn all_claude_project_dirs(base_dir: &Path) -> Vec<PathBuf> {
+pub(crate) fn all_claude_project_dirs(base_dir: &Path) -> Vec<PathBuf> {
     let mut dirs = Vec::new();
     let Ok(entries) = std::fs::read_dir(base_dir) else {
         return dirs;
@@ -727,7 +727,7 @@ fn rewrite_session_id_fields(value: &mut Value, source_session_id: &str, forked_
     }
 }
 
-fn resolve_session_file_path(
+pub(super) fn resolve_session_file_path(
     base_dir: &Path,
     workspace_path: &Path,
     session_id: &str,
@@ -0,0 +1,238 @@
+//! Renders a loaded Claude session for sharing, as Markdown or as the
+//! normalized message list.
+
+use base64::Engine;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::claude_history::ClaudeSessionMessage;
+
+/// Longest tool-input summary shown on a collapsed tool line.
+const TOOL_SUMMARY_MAX_CHARS: usize = 120;
+
+/// How Markdown exports reference image attachments.
+pub(crate) enum ClaudeExportImages {
+    /// Inline `data:` URIs.
+    Embed,
+    /// Write decoded images into `dir` and link them by `link_prefix/<file>`.
+    Files { dir: PathBuf, link_prefix: String },
+}
+
+fn image_extension(media_type: &str) -> &'static str {
+    match media_type {
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        _ => "png",
+    }
+}
+
+/// Splits `data:<media>;base64,<payload>` into its media type and payload.
+fn parse_data_uri(src: &str) -> Option<(&str, &str)> {
+    let rest = src.strip_prefix("data:")?;
+    let (media_type, payload) = rest.split_once(";base64,")?;
+    Some((media_type, payload))
+}
+
+/// Markdown image reference for `src`, writing it to disk first when images
+/// are exported as files. Remote URLs are linked as they are.
+fn image_reference(src: &str, index: usize, images: &ClaudeExportImages) -> Result<String, String> {
+    let ClaudeExportImages::Files { dir, link_prefix } = images else {
+        return Ok(format!("![image {index}]({src})"));
+    };
+    let Some((media_type, payload)) = parse_data_uri(src) else {
+        return Ok(format!("![image {index}]({src})"));
+    };
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(payload.trim())
+        .map_err(|error| format!("Failed to decode session image: {error}"))?;
+    std::fs::create_dir_all(dir)
+        .map_err(|error| format!("Failed to write export file: {error}"))?;
+    let file_name = format!("image-{index}.{}", image_extension(media_type));
+    std::fs::write(dir.join(&file_name), bytes)
+        .map_err(|error| format!("Failed to write export file: {error}"))?;
+    Ok(format!("![image {index}]({link_prefix}/{file_name})"))
+}
+
+fn single_line(text: &str, max_chars: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= max_chars {
+        return line;
+    }
+    let truncated: String = line.chars().take(max_chars).collect();
+    format!("{truncated}…")
+}
+
+/// The most telling input field of a tool call, e.g. the command or path.
+fn tool_input_summary(input: Option<&Value>) -> Option<String> {
+    let input = input?;
+    [
+        "command",
+        "file_path",
+        "path",
+        "pattern",
+        "url",
+        "query",
+        "description",
+    ]
+    .iter()
+    .find_map(|key| input.get(key).and_then(Value::as_str))
+    .map(|value| single_line(value, TOOL_SUMMARY_MAX_CHARS))
+    .filter(|value| !value.is_empty())
+}
+
+fn tool_line(message: &ClaudeSessionMessage) -> String {
+    let tool_type = message.tool_type.as_deref().unwrap_or("tool");
+    match tool_type {
+        "result" | "error" => {
+            let lines = message.text.lines().count();
+            let label = if tool_type == "error" {
+                "Error"
+            } else {
+                "Result"
+            };
+            let plural = if lines == 1 { "" } else { "s" };
+            format!("> ↳ {label} ({lines} line{plural})")
+        }
+        _ => match tool_input_summary(message.tool_input.as_ref()) {
+            Some(summary) => format!("> **Tool:** `{tool_type}` — `{summary}`"),
+            None => format!("> **Tool:** `{tool_type}`"),
+        },
+    }
+}
+
+fn role_heading(role: &str) -> &'static str {
+    match role {
+        "user" => "## User",
+        "system" => "## System",
+        _ => "## Assistant",
+    }
+}
+
+/// Renders turns under a heading per role change. Message text is copied
+/// verbatim so fenced code blocks survive; tool calls and results collapse to
+/// one-line summaries and reasoning folds into a `<details>` block.
+/// `image_sources` holds each message's resolved image sources, by index.
+pub(crate) fn render_claude_session_markdown(
+    session_id: &str,
+    messages: &[ClaudeSessionMessage],
+    image_sources: &[Vec<String>],
+    images: &ClaudeExportImages,
+) -> Result<String, String> {
+    let mut output = format!("# Claude session {session_id}\n");
+    let mut current_role: Option<&str> = None;
+    let mut image_index = 0usize;
+    for (message, sources) in messages.iter().zip(image_sources) {
+        let heading = role_heading(&message.role);
+        if current_role != Some(heading) {
+            output.push('\n');
+            output.push_str(heading);
+            output.push('\n');
+            current_role = Some(heading);
+        }
+        output.push('\n');
+        match message.kind.as_str() {
+            "tool" => output.push_str(&tool_line(message)),
+            "reasoning" => {
+                output.push_str("<details>\n<summary>Thinking</summary>\n\n");
+                output.push_str(message.text.trim_end());
+                output.push_str("\n\n</details>");
+            }
+            _ => output.push_str(message.text.trim_end()),
+        }
+        output.push('\n');
+        for src in sources {
+            image_index += 1;
+            output.push('\n');
+            output.push_str(&image_reference(src, image_index, images)?);
+            output.push('\n');
+        }
+    }
+    Ok(output)
+}
+
+/// `<stem>-images` next to the Markdown file, and the relative link prefix
+/// to reach it.
+pub(crate) fn export_images_dir(markdown_path: &Path) -> (PathBuf, String) {
+    let stem = markdown_path
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .unwrap_or("claude-session");
+    let link_prefix = format!("{stem}-images");
+    let dir = markdown_path
+        .parent()
+        .map(|parent| parent.join(&link_prefix))
+        .unwrap_or_else(|| PathBuf::from(&link_prefix));
+    (dir, link_prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(role: &str, kind: &str, text: &str) -> ClaudeSessionMessage {
+        ClaudeSessionMessage {
+            id: format!("{role}-{kind}"),
+            role: role.to_string(),
+            text: text.to_string(),
+            images: None,
+            deferred_images: None,
+            timestamp: None,
+            kind: kind.to_string(),
+            tool_type: None,
+            title: None,
+            tool_input: None,
+            tool_output: None,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn renders_markdown_with_collapsed_tools_and_image_files() {
+        let mut tool = message("assistant", "tool", "{}");
+        tool.tool_type = Some("Bash".to_string());
+        tool.tool_input = Some(json!({ "command": "cargo test\n  --workspace" }));
+        let mut result = message("assistant", "tool", "ok\nok\nok");
+        result.tool_type = Some("result".to_string());
+        let messages = vec![
+            message("user", "message", "Why does this fail?"),
+            message("assistant", "reasoning", "Look at the test."),
+            tool,
+            result,
+            message("assistant", "message", "Fixed:\n```rust\nfn a() {}\n```"),
+        ];
+        let mut sources = vec![Vec::new(); messages.len()];
+        sources[0].push("data:image/png;base64,iVBORw0KGgo=".to_string());
+
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        let (dir, link_prefix) = export_images_dir(&root.join("session.md"));
+        let markdown = render_claude_session_markdown(
+            "abc",
+            &messages,
+            &sources,
+            &ClaudeExportImages::Files { dir, link_prefix },
+        )
+        .expect("render");
+
+        assert_eq!(
+            markdown,
+            "# Claude session abc\n\n## User\n\nWhy does this fail?\n\n![image 1](session-images/image-1.png)\n\n## Assistant\n\n<details>\n<summary>Thinking</summary>\n\nLook at the test.\n\n</details>\n\n> **Tool:** `Bash` — `cargo test --workspace`\n\n> ↳ Result (3 lines)\n\nFixed:\n```rust\nfn a() {}\n```\n"
+        );
+        assert_eq!(
+            std::fs::read(root.join("session-images").join("image-1.png")).expect("image"),
+            vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]
+        );
+
+        let embedded = render_claude_session_markdown(
+            "abc",
+            &messages[..1],
+            &sources[..1],
+            &ClaudeExportImages::Embed,
+        )
+        .expect("render embedded");
+        assert!(embedded.contains("![image 1](data:image/png;base64,iVBORw0KGgo=)"));
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,162 @@
+//! Transcript cursors for session checkpoints: where a Claude session
+//! ended when a checkpoint was taken, and the prompt to fork from to rewind
+//! it back there.
+
+use serde_json::Value;
+use std::path::Path;
+use tokio::fs;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use super::claude_history::{claude_projects_dir, normalize_session_id, resolve_session_file_path};
+use super::claude_history_entries::{
+    classify_claude_history_entry, ClaudeHistoryEntryClassification,
+};
+use super::EngineConfig;
+
+fn claude_entry_uuid(entry: &Value) -> Option<&str> {
+    entry.get("uuid").and_then(Value::as_str)
+}
+
+/// A user-typed prompt, as opposed to a tool result or hidden record.
+fn is_user_prompt_entry(entry: &Value) -> bool {
+    let Some(message) = entry.get("message") else {
+        return false;
+    };
+    if message.get("role").and_then(Value::as_str) != Some("user") {
+        return false;
+    }
+    if matches!(
+        classify_claude_history_entry(entry),
+        ClaudeHistoryEntryClassification::Hidden(_)
+    ) {
+        return false;
+    }
+    match message.get("content") {
+        Some(Value::String(_)) => true,
+        Some(Value::Array(parts)) => !parts
+            .iter()
+            .any(|part| part.get("type").and_then(Value::as_str) == Some("tool_result")),
+        _ => false,
+    }
+}
+
+/// Id of the first user prompt after the entry `cursor` (from the start
+/// when `None`); forking from it rewinds the session back to `cursor`.
+fn user_prompt_after_cursor(
+    entries: &[Value],
+    cursor: Option<&str>,
+) -> Result<Option<String>, String> {
+    let start = match cursor {
+        Some(cursor) => {
+            entries
+                .iter()
+                .position(|entry| claude_entry_uuid(entry) == Some(cursor))
+                .ok_or_else(|| format!("Transcript entry {cursor} is no longer in the session"))?
+                + 1
+        }
+        None => 0,
+    };
+    Ok(entries[start..]
+        .iter()
+        .find(|entry| is_user_prompt_entry(entry))
+        .and_then(|entry| {
+            claude_entry_uuid(entry).or_else(|| {
+                entry
+                    .get("message")
+                    .and_then(|message| message.get("id"))
+                    .and_then(Value::as_str)
+            })
+        })
+        .map(str::to_string))
+}
+
+async fn read_claude_session_entries(
+    base_dir: &Path,
+    workspace_path: &Path,
+    session_id: &str,
+) -> Result<Vec<Value>, String> {
+    let session_file = resolve_session_file_path(base_dir, workspace_path, session_id)?;
+    let file = fs::File::open(&session_file)
+        .await
+        .map_err(|e| format!("Failed to open session file: {}", e))?;
+    let mut reader = BufReader::new(file).lines();
+    let mut entries = Vec::new();
+    while let Ok(Some(line)) = reader.next_line().await {
+        if let Ok(entry) = serde_json::from_str::<Value>(&line) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// Uuid of the last transcript entry, used as a checkpoint cursor; `None`
+/// for an empty transcript.
+pub async fn claude_session_cursor_with_config(
+    workspace_path: &Path,
+    session_id: &str,
+    config: Option<&EngineConfig>,
+) -> Result<Option<String>, String> {
+    let normalized_session_id = normalize_session_id(session_id)?;
+    let base_dir = claude_projects_dir(config).ok_or("Cannot determine Claude home directory")?;
+    let entries =
+        read_claude_session_entries(&base_dir, workspace_path, &normalized_session_id).await?;
+    Ok(entries
+        .iter()
+        .rev()
+        .find_map(claude_entry_uuid)
+        .map(str::to_string))
+}
+
+/// The user prompt to fork from so the session ends at `cursor`, or `None`
+/// when nothing was prompted since.
+pub async fn claude_user_prompt_after_cursor_with_config(
+    workspace_path: &Path,
+    session_id: &str,
+    cursor: Option<&str>,
+    config: Option<&EngineConfig>,
+) -> Result<Option<String>, String> {
+    let normalized_session_id = normalize_session_id(session_id)?;
+    let base_dir = claude_projects_dir(config).ok_or("Cannot determine Claude home directory")?;
+    let entries =
+        read_claude_session_entries(&base_dir, workspace_path, &normalized_session_id).await?;
+    user_prompt_after_cursor(&entries, cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::user_prompt_after_cursor;
+    use serde_json::json;
+
+    #[test]
+    fn user_prompt_after_cursor_skips_tool_results() {
+        let entries = vec![
+            json!({ "uuid": "u1", "message": { "role": "user", "content": "first" } }),
+            json!({ "uuid": "a1", "message": { "role": "assistant", "content": "reply" } }),
+            json!({
+                "uuid": "t1",
+                "message": {
+                    "role": "user",
+                    "content": [{ "type": "tool_result", "tool_use_id": "x", "content": "ok" }]
+                }
+            }),
+            json!({
+                "uuid": "u2",
+                "message": { "role": "user", "content": [{ "type": "text", "text": "second" }] }
+            }),
+        ];
+
+        assert_eq!(
+            user_prompt_after_cursor(&entries, None).expect("from start"),
+            Some("u1".to_string())
+        );
+        assert_eq!(
+            user_prompt_after_cursor(&entries, Some("a1")).expect("after reply"),
+            Some("u2".to_string())
+        );
+        assert_eq!(
+            user_prompt_after_cursor(&entries, Some("u2")).expect("at the end"),
+            None
+        );
+        assert!(user_prompt_after_cursor(&entries, Some("gone")).is_err());
+    }
+}
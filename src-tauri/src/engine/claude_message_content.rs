@@ -30,6 +30,47 @@ pub(super) fn format_ask_user_answer(result: &Value) -> String {
     }
 }
 
+/// Turn a `claude_respond_to_question` answer into the `answers` result
+/// `format_ask_user_answer` reads. A string or string list answers the first
+/// question; an object maps question ids (`q-0`, ...) to a string or list.
+/// Results already in `{ "answers": ... }` form pass through.
+pub(super) fn ask_user_answer_result(answer: &Value) -> Result<Value, String> {
+    fn labels(value: &Value) -> Option<Vec<Value>> {
+        match value {
+            Value::String(text) if !text.trim().is_empty() => {
+                Some(vec![Value::String(text.trim().to_string())])
+            }
+            Value::Array(items) => {
+                let labels: Vec<Value> = items.iter().filter_map(labels).flatten().collect();
+                (!labels.is_empty()).then_some(labels)
+            }
+            _ => None,
+        }
+    }
+
+    let answers: serde_json::Map<String, Value> = match answer {
+        Value::Object(map) if map.get("answers").is_some_and(Value::is_object) => {
+            return Ok(answer.clone());
+        }
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(question_id, value)| {
+                let labels = labels(value)?;
+                Some((question_id.clone(), json!({ "answers": labels })))
+            })
+            .collect(),
+        other => labels(other)
+            .map(|labels| {
+                std::iter::once(("q-0".to_string(), json!({ "answers": labels }))).collect()
+            })
+            .unwrap_or_default(),
+    };
+    if answers.is_empty() {
+        return Err("AskUserQuestion answer is empty".to_string());
+    }
+    Ok(json!({ "answers": Value::Object(answers) }))
+}
+
 /// Build message content with images for stream-json input
 pub(super) fn build_message_content(params: &SendMessageParams) -> Result<Value, String> {
     let mut content = Vec::new();
@@ -226,8 +267,11 @@ fn normalize_base64_payload(input: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{build_message_content, normalize_image_path};
+    use super::{
+        ask_user_answer_result, build_message_content, format_ask_user_answer, normalize_image_path,
+    };
     use crate::engine::SendMessageParams;
+    use serde_json::json;
     use std::time::{SystemTime, UNIX_EPOCH};
 
     fn temp_file_path(name: &str) -> std::path::PathBuf {
@@ -238,6 +282,24 @@ mod tests {
         std::env::temp_dir().join(format!("moss-x-claude-image-{}-{}", timestamp, name))
     }
 
+    #[test]
+    fn ask_user_answer_result_accepts_labels_and_per_question_maps() {
+        let single = ask_user_answer_result(&json!(" Yes ")).expect("string answer");
+        assert_eq!(single, json!({"answers": {"q-0": {"answers": ["Yes"]}}}));
+
+        let mapped = ask_user_answer_result(&json!({"q-0": ["A", "B"], "q-1": "Other"}))
+            .expect("mapped answer");
+        assert_eq!(
+            format_ask_user_answer(&mapped),
+            "The user answered the AskUserQuestion: A, B; Other. Please continue based on this selection."
+        );
+
+        let raw = json!({"answers": {"q-0": {"answers": ["C"]}}});
+        assert_eq!(ask_user_answer_result(&raw).expect("raw result"), raw);
+        assert!(ask_user_answer_result(&json!("  ")).is_err());
+        assert!(ask_user_answer_result(&json!({"q-0": 3})).is_err());
+    }
+
     #[test]
     fn build_message_content_supports_file_uri_images() {
         let image_path = temp_file_path("file-uri.png");
@@ -172,6 +172,7 @@ impl CodexSessionAdapter {
                 Some(EngineEvent::TurnStarted {
                     workspace_id: self.workspace_id.clone(),
                     turn_id: turn_id.to_string(),
+                    cwd: None,
                 })
             }
             "item/agentMessage/delta" => {
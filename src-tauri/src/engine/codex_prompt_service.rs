@@ -8,6 +8,7 @@ use tokio::time::timeout;
 use crate::backend::app_server::WorkspaceSession;
 use crate::backend::events::AppServerEvent;
 use crate::engine::error_mapper::extract_error_message;
+use crate::shared::access_presets::{AccessModePreset, CodexSandboxKind};
 use crate::state::AppState;
 
 pub(crate) fn normalize_custom_spec_root(custom_spec_root: Option<&str>) -> Option<String> {
@@ -130,15 +131,16 @@ pub(crate) async fn run_codex_prompt_sync(
             writable_roots.push(spec_root);
         }
     }
-    let sandbox_policy = match access_mode.as_str() {
-        "full-access" => json!({ "type": "dangerFullAccess" }),
-        "current" => json!({
-            "type": "workspaceWrite",
-            "writableRoots": writable_roots,
-            "networkAccess": true
-        }),
-        _ => json!({ "type": "readOnly" }),
-    };
+    let sandbox_policy =
+        match AccessModePreset::parse(&access_mode).map(|preset| preset.codex_sandbox()) {
+            Some(CodexSandboxKind::DangerFullAccess) => json!({ "type": "dangerFullAccess" }),
+            Some(CodexSandboxKind::WorkspaceWrite) => json!({
+                "type": "workspaceWrite",
+                "writableRoots": writable_roots,
+                "networkAccess": true
+            }),
+            _ => json!({ "type": "readOnly" }),
+        };
     let turn_result = session
         .send_request(
             "turn/start",
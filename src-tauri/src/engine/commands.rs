@@ -15,27 +15,35 @@ use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 use tokio::time::timeout;
 
 use crate::backend::events::AppServerEvent;
 use crate::remote_backend;
+use crate::shared::access_presets::AccessModePreset;
+use crate::shared::workspace_read_only::cap_access_mode_for_workspace;
+use crate::shared::workspaces_core::resolve_turn_cwd_core;
 use crate::state::AppState;
 use crate::types::WorkspaceEntry;
 
+use super::adapter::EngineContext;
 use super::codex_prompt_service::{normalize_custom_spec_root, run_codex_prompt_sync};
 use super::events::{engine_event_to_app_server_event_with_turn_context, EngineEvent};
+use super::forwarder_registry::{EventForwarderSnapshot, ForwarderRecv};
+use super::manager::{failed_probe_status, DETECTION_TIMEOUT_BUDGET};
+use super::model_routing::{route_turn_model, ModelRouteOverride, ModelRoutingDecision};
 use super::remote_bridge::{
     call_remote_typed, remote_detect_engines_request, remote_engine_interrupt_request,
     remote_engine_send_message_sync_request,
 };
-use super::status::{detect_gemini_status, load_opencode_models};
 use super::{
     engine_disabled_diagnostic, engine_enabled_in_settings, EngineConfig, EngineStatus, EngineType,
 };
 
+#[path = "commands_adapters.rs"]
+mod adapters;
 #[path = "claude_forwarder.rs"]
 mod claude_forwarder;
 #[path = "commands_opencode.rs"]
@@ -44,16 +52,15 @@ mod commands_opencode;
 mod opencode_helpers;
 #[path = "commands_parse_helpers.rs"]
 mod parse_helpers;
+pub(crate) use adapters::register_builtin_engine_adapters;
 use claude_forwarder::{
-    handle_claude_forwarder_event, ClaudeForwarderRuntimeContext, ClaudeForwarderState,
+    claude_user_question_payload, handle_claude_forwarder_event, ClaudeForwarderRuntimeContext,
+    ClaudeForwarderState, CLAUDE_USER_QUESTION_EVENT,
 };
 pub use commands_opencode::*;
 use opencode_helpers::*;
 use parse_helpers::*;
 
-/// Maximum lifetime for an event forwarder task. Prevents orphaned tasks from
-/// leaking memory when the underlying process hangs or is killed externally.
-const EVENT_FORWARDER_TIMEOUT_SECS: u64 = 30 * 60;
 /// Gemini may emit fallback reasoning shortly after turn/completed.
 /// Keep the forwarder alive briefly so realtime reasoning is not dropped.
 const GEMINI_POST_COMPLETION_REASONING_GRACE_MS: u64 = 8_000;
@@ -217,7 +224,7 @@ pub struct OpenCodeSessionEntry {
     pub updated_at: Option<i64>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCodeProviderOption {
     pub id: String,
@@ -227,12 +234,22 @@ pub struct OpenCodeProviderOption {
     pub recommended: bool,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 struct OpenCodeMcpToggleState {
     global_enabled: bool,
     server_enabled: HashMap<String, bool>,
 }
 
+impl Default for OpenCodeMcpToggleState {
+    fn default() -> Self {
+        Self {
+            global_enabled: true,
+            server_enabled: HashMap::new(),
+        }
+    }
+}
+
 const OPENCODE_CACHE_TTL: Duration = Duration::from_secs(30);
 static OPENCODE_COMMANDS_CACHE: OnceLock<Mutex<Option<(Instant, Vec<OpenCodeCommandEntry>)>>> =
     OnceLock::new();
@@ -414,11 +431,8 @@ fn opencode_session_candidate_paths(
     candidates
 }
 
-fn delete_opencode_session_files(
-    workspace_path: &Path,
-    session_id: &str,
-    config: Option<&EngineConfig>,
-) -> Result<(), String> {
+/// Trimmed session id, rejected when it could escape the storage directories.
+fn normalize_opencode_session_id(session_id: &str) -> Result<&str, String> {
     let normalized_session_id = session_id.trim();
     if normalized_session_id.is_empty()
         || normalized_session_id.contains('/')
@@ -427,6 +441,15 @@ fn delete_opencode_session_files(
     {
         return Err("[SESSION_NOT_FOUND] Invalid OpenCode session id".to_string());
     }
+    Ok(normalized_session_id)
+}
+
+fn delete_opencode_session_files(
+    workspace_path: &Path,
+    session_id: &str,
+    config: Option<&EngineConfig>,
+) -> Result<(), String> {
+    let normalized_session_id = normalize_opencode_session_id(session_id)?;
 
     let mut deleted_any = false;
 
@@ -476,7 +499,7 @@ fn delete_opencode_session_files(
     ))
 }
 
-fn opencode_data_candidate_roots(
+pub(crate) fn opencode_data_candidate_roots(
     workspace_path: &Path,
     config: Option<&EngineConfig>,
 ) -> Vec<PathBuf> {
@@ -599,6 +622,121 @@ fn delete_opencode_session_from_datastore(
     Ok(deleted_any)
 }
 
+fn rename_opencode_session_files(
+    workspace_path: &Path,
+    session_id: &str,
+    title: &str,
+    config: Option<&EngineConfig>,
+) -> Result<(), String> {
+    let normalized_session_id = normalize_opencode_session_id(session_id)?;
+
+    let mut renamed_any = false;
+    for data_root in opencode_data_candidate_roots(workspace_path, config) {
+        if rename_opencode_session_in_datastore(&data_root, normalized_session_id, title)? {
+            renamed_any = true;
+        }
+    }
+
+    if renamed_any {
+        return Ok(());
+    }
+
+    Err(format!(
+        "[SESSION_NOT_FOUND] OpenCode session not found: {}",
+        normalized_session_id
+    ))
+}
+
+/// Update the session title in `opencode.db`, or in the
+/// `storage/session/<project>/<id>.json` file when the database has no row.
+fn rename_opencode_session_in_datastore(
+    data_root: &Path,
+    session_id: &str,
+    title: &str,
+) -> Result<bool, String> {
+    let db_path = data_root.join("opencode.db");
+    if db_path.exists() {
+        let connection = Connection::open(&db_path).map_err(|error| {
+            format!(
+                "[IO_ERROR] Failed to open OpenCode datastore {}: {}",
+                db_path.display(),
+                error
+            )
+        })?;
+        let updated_rows = connection
+            .execute(
+                "UPDATE session SET title = ?1 WHERE id = ?2",
+                params![title, session_id],
+            )
+            .map_err(|error| {
+                format!(
+                    "[IO_ERROR] Failed to rename OpenCode session {} in {}: {}",
+                    session_id,
+                    db_path.display(),
+                    error
+                )
+            })?;
+        if updated_rows > 0 {
+            return Ok(true);
+        }
+    }
+
+    let session_root = data_root.join("storage").join("session");
+    if !session_root.is_dir() {
+        return Ok(false);
+    }
+    let reader = fs::read_dir(&session_root).map_err(|error| {
+        format!(
+            "[IO_ERROR] Failed to read OpenCode session directory {}: {}",
+            session_root.display(),
+            error
+        )
+    })?;
+    for entry in reader {
+        let entry = entry.map_err(|error| {
+            format!(
+                "[IO_ERROR] Failed to read OpenCode session entry under {}: {}",
+                session_root.display(),
+                error
+            )
+        })?;
+        let session_path = entry.path().join(format!("{session_id}.json"));
+        if !session_path.is_file() {
+            continue;
+        }
+        let raw = fs::read_to_string(&session_path).map_err(|error| {
+            format!(
+                "[IO_ERROR] Failed to read OpenCode session file {}: {}",
+                session_path.display(),
+                error
+            )
+        })?;
+        let mut session: Value = serde_json::from_str(&raw).map_err(|error| {
+            format!(
+                "[IO_ERROR] Invalid OpenCode session file {}: {}",
+                session_path.display(),
+                error
+            )
+        })?;
+        let Some(fields) = session.as_object_mut() else {
+            continue;
+        };
+        fields.insert("title".to_string(), Value::String(title.to_string()));
+        let serialized =
+            serde_json::to_string_pretty(&session).map_err(|error| error.to_string())?;
+        fs::write(&session_path, serialized).map_err(|error| {
+            format!(
+                "[IO_ERROR] Failed to write OpenCode session file {}: {}",
+                session_path.display(),
+                error
+            )
+        })?;
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
 fn slugify_provider_label(value: &str) -> String {
     let mut out = String::new();
     let mut last_dash = false;
@@ -832,7 +970,11 @@ async fn fetch_opencode_provider_catalog_from_auth_picker(
     providers
 }
 
-/// Detect all installed engines and their capabilities
+/// Detect all installed engines and their capabilities.
+///
+/// Returns once every engine answered or the detection budget ran out.
+/// Engines still probing come back with `detecting` set and are published
+/// through `engine-status-updated` when their probe finishes.
 #[tauri::command]
 pub async fn detect_engines(
     state: State<'_, AppState>,
@@ -844,9 +986,27 @@ pub async fn detect_engines(
     }
     let manager = &state.engine_manager;
     let settings = read_app_settings_snapshot(&state).await;
-    Ok(manager
-        .detect_engines_with_gates(settings.gemini_enabled, settings.opencode_enabled)
-        .await)
+    let detection = manager
+        .detect_engines_within_budget(
+            settings.gemini_enabled,
+            settings.opencode_enabled,
+            DETECTION_TIMEOUT_BUDGET,
+        )
+        .await;
+    for (engine_type, probe) in detection.pending {
+        let app = app.clone();
+        tokio::spawn(async move {
+            let status = probe
+                .await
+                .unwrap_or_else(|error| failed_probe_status(engine_type, error));
+            app.state::<AppState>()
+                .engine_manager
+                .cache_engine_status(status.clone())
+                .await;
+            let _ = app.emit("engine-status-updated", &status);
+        });
+    }
+    Ok(detection.statuses)
 }
 
 /// Get the currently active engine
@@ -914,6 +1074,28 @@ pub async fn get_all_engine_statuses(
     Ok(manager.get_all_statuses().await)
 }
 
+/// Warm vs cold process starts per workspace (Claude warm-start pool)
+#[tauri::command]
+pub async fn get_engine_warm_start_metrics(
+    state: State<'_, AppState>,
+) -> Result<Vec<Value>, String> {
+    let metrics = state
+        .engine_manager
+        .claude_manager
+        .warm_start_metrics()
+        .await;
+    Ok(metrics
+        .into_iter()
+        .map(|(workspace_id, metrics)| {
+            json!({
+                "workspaceId": workspace_id,
+                "engine": EngineType::Claude,
+                "metrics": metrics,
+            })
+        })
+        .collect())
+}
+
 /// Set engine configuration
 #[tauri::command]
 pub async fn set_engine_config(
@@ -985,75 +1167,253 @@ pub async fn get_engine_models(
     let settings = read_app_settings_snapshot(&state).await;
     ensure_engine_enabled(&settings, engine_type)?;
 
-    match engine_type {
-        EngineType::OpenCode => {
-            let config = manager.get_engine_config(EngineType::OpenCode).await;
-            let custom_bin = config
-                .as_ref()
-                .and_then(|cfg| cfg.bin_path.as_ref())
-                .map(|s| s.as_str());
-            let fresh_models = load_opencode_models(custom_bin).await.unwrap_or_default();
-
-            if !fresh_models.is_empty() {
-                return Ok(fresh_models);
+    let adapter = manager.require_adapter(engine_type)?;
+    let custom_bin = manager
+        .get_engine_config(engine_type)
+        .await
+        .and_then(|config| config.bin_path);
+    if let Some(fresh_models) = adapter.models(custom_bin.as_deref()).await {
+        if !fresh_models.is_empty() {
+            return Ok(fresh_models);
+        }
+        if let Some(cached) = manager.get_engine_status(engine_type).await {
+            if !cached.models.is_empty() {
+                return Ok(cached.models);
             }
+        }
+        return Ok(fresh_models);
+    }
 
-            if let Some(cached) = manager.get_engine_status(EngineType::OpenCode).await {
-                if !cached.models.is_empty() {
-                    return Ok(cached.models);
-                }
-            }
+    if force_refresh {
+        let status = manager
+            .refresh_engine_status_with_gates(
+                engine_type,
+                settings.gemini_enabled,
+                settings.opencode_enabled,
+            )
+            .await;
+        return Ok(status.models);
+    }
 
-            Ok(fresh_models)
+    if let Some(status) = manager.get_engine_status(engine_type).await {
+        if !status.models.is_empty() {
+            return Ok(status.models);
         }
-        EngineType::Gemini => {
-            let config = manager.get_engine_config(EngineType::Gemini).await;
-            let custom_bin = config
-                .as_ref()
-                .and_then(|cfg| cfg.bin_path.as_ref())
-                .map(|s| s.as_str());
-            let fresh_status = detect_gemini_status(custom_bin).await;
-
-            if !fresh_status.models.is_empty() {
-                return Ok(fresh_status.models);
-            }
+    }
+
+    let status = manager
+        .refresh_engine_status_with_gates(
+            engine_type,
+            settings.gemini_enabled,
+            settings.opencode_enabled,
+        )
+        .await;
+    Ok(status.models)
+}
+
+/// Falls back to the workspace's default access mode preset when the
+/// request does not name one, capped for read-only workspaces.
+async fn resolve_workspace_access_mode(
+    state: &AppState,
+    workspace_id: &str,
+    access_mode: Option<String>,
+) -> Option<String> {
+    let app_default = state.app_settings.lock().await.default_access_mode.clone();
+    let workspaces = state.workspaces.lock().await;
+    let Some(entry) = workspaces.get(workspace_id) else {
+        return access_mode;
+    };
+    // Same precedence `get_workspace_access_mode_preset` reports to the UI.
+    let access_mode = access_mode.or_else(|| {
+        entry
+            .settings
+            .access_mode_preset
+            .clone()
+            .filter(|preset| AccessModePreset::parse(preset).is_some())
+            .or(Some(app_default))
+    });
+    cap_access_mode_for_workspace(access_mode, entry.settings.read_only)
+}
+
+/// Codex app-server threads are bound to the workspace root.
+pub(crate) const CODEX_TURN_CWD_UNSUPPORTED: &str =
+    "Codex does not support a per-turn working directory";
+
+/// The API engine runs in-process and has no daemon counterpart.
+const API_ENGINE_REMOTE_UNSUPPORTED: &str = "The API engine is not available in remote mode";
+
+/// Runs a turn on an `api:` thread through the same privacy and access-mode
+/// handling as the CLI engines.
+#[allow(clippy::too_many_arguments)]
+async fn send_api_engine_message(
+    app: &AppHandle,
+    state: &AppState,
+    workspace_id: &str,
+    text: String,
+    thread_id: &str,
+    model: Option<String>,
+    access_mode: Option<String>,
+    images: Option<Vec<String>>,
+    cwd: Option<String>,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(state).await {
+        return Err(API_ENGINE_REMOTE_UNSUPPORTED.to_string());
+    }
+    if cwd.is_some_and(|value| !value.trim().is_empty()) {
+        return Err("The API engine does not support a per-turn working directory".to_string());
+    }
+    let privacy =
+        crate::privacy_zones::enforce_for_turn(state, app, workspace_id, text, images).await;
+    if privacy
+        .images
+        .as_ref()
+        .is_some_and(|entries| entries.iter().any(|entry| !entry.trim().is_empty()))
+    {
+        return Err("The API engine does not accept image attachments".to_string());
+    }
+    let access_mode = resolve_workspace_access_mode(state, workspace_id, access_mode).await;
+    let response = super::api_engine::start_turn(
+        app,
+        state,
+        workspace_id,
+        privacy.text,
+        thread_id,
+        model,
+        access_mode,
+    )
+    .await?;
+    Ok(crate::privacy_zones::attach_privacy_notice(
+        response,
+        privacy.notice.as_ref(),
+    ))
+}
+
+/// Validates an optional per-turn working directory against the workspace
+/// root and returns its canonical form.
+async fn resolve_turn_cwd(
+    state: &AppState,
+    workspace_id: &str,
+    cwd: Option<&str>,
+) -> Result<Option<String>, String> {
+    if cwd.is_none_or(|value| value.trim().is_empty()) {
+        return Ok(None);
+    }
+    let workspace_root = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .map(|entry| PathBuf::from(&entry.path))
+            .ok_or_else(|| "Workspace not found".to_string())?
+    };
+    Ok(resolve_turn_cwd_core(&workspace_root, cwd)?.map(|path| path.to_string_lossy().to_string()))
+}
+
+/// Applies the model routing policy to an outgoing prompt.
+async fn route_model_for_turn(
+    state: &AppState,
+    engine: Option<EngineType>,
+    text: &str,
+    images: Option<&[String]>,
+    model: Option<String>,
+    model_route: Option<ModelRouteOverride>,
+) -> (Option<String>, Option<ModelRoutingDecision>) {
+    let engine = match engine {
+        Some(engine) => engine,
+        None => state.engine_manager.get_active_engine().await,
+    };
+    let policy = state.app_settings.lock().await.model_routing.clone();
+    let (model, decision) = route_turn_model(&policy, engine, text, images, model, model_route);
+    if let Some(decision) = &decision {
+        log::info!(
+            "[model_routing] engine={:?} decision={:?}",
+            engine,
+            decision
+        );
+    }
+    (model, decision)
+}
+
+/// Records the routing decision in the turn metadata of a send response.
+fn attach_model_routing(mut response: Value, decision: Option<&ModelRoutingDecision>) -> Value {
+    let Some(decision) = decision.and_then(|decision| serde_json::to_value(decision).ok()) else {
+        return response;
+    };
+    if let Some(result) = response.get_mut("result").and_then(Value::as_object_mut) {
+        result.insert("modelRouting".to_string(), decision.clone());
+    }
+    if let Some(response) = response.as_object_mut() {
+        response.insert("modelRouting".to_string(), decision);
+    }
+    response
+}
 
-            if let Some(cached) = manager.get_engine_status(EngineType::Gemini).await {
-                if !cached.models.is_empty() {
-                    return Ok(cached.models);
+/// Arms the per-turn timeout of a streaming turn. On expiry the UI gets a
+/// `turn/timeout` event and the turn is interrupted like `engine_interrupt_turn`.
+fn arm_engine_turn_timeout(
+    app: &AppHandle,
+    engine: EngineType,
+    workspace_id: &str,
+    turn_id: &str,
+    timeout_secs: Option<u64>,
+) {
+    let Some(timeout_secs) = timeout_secs.filter(|secs| *secs > 0) else {
+        return;
+    };
+    let app_for_timeout = app.clone();
+    let workspace = workspace_id.to_string();
+    let turn = turn_id.to_string();
+    let on_timeout = async move {
+        let app = app_for_timeout;
+        let state = app.state::<AppState>();
+        let manager = &state.engine_manager;
+        log::warn!(
+            "[engine_send_message] turn {} timed out after {}s engine={:?} workspace_id={}",
+            turn,
+            timeout_secs,
+            engine,
+            workspace
+        );
+        match engine {
+            EngineType::Claude => {
+                if let Some(session) = manager.claude_manager.get_session(&workspace).await {
+                    session.emit_turn_timeout(&turn, timeout_secs);
                 }
             }
-
-            Ok(fresh_status.models)
-        }
-        EngineType::Claude | EngineType::Codex => {
-            if force_refresh {
-                let status = manager
-                    .refresh_engine_status_with_gates(
-                        engine_type,
-                        settings.gemini_enabled,
-                        settings.opencode_enabled,
-                    )
-                    .await;
-                return Ok(status.models);
+            EngineType::OpenCode => {
+                if let Some(session) = manager.get_opencode_session(&workspace).await {
+                    session.emit_turn_timeout(&turn, timeout_secs);
+                }
             }
-
-            if let Some(status) = manager.get_engine_status(engine_type).await {
-                if !status.models.is_empty() {
-                    return Ok(status.models);
+            EngineType::Gemini => {
+                if let Some(session) = manager.get_gemini_session(&workspace).await {
+                    session.emit_turn_timeout(&turn, timeout_secs);
                 }
             }
-
-            let status = manager
-                .refresh_engine_status_with_gates(
-                    engine_type,
-                    settings.gemini_enabled,
-                    settings.opencode_enabled,
-                )
-                .await;
-            Ok(status.models)
+            EngineType::Codex => {}
         }
-    }
+        let ctx = EngineContext {
+            app: &app,
+            state: &state,
+            workspace_id: &workspace,
+        };
+        let interrupted = match manager.require_adapter(engine) {
+            Ok(adapter) => adapter.interrupt(ctx, Some(&turn)).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = interrupted {
+            log::warn!(
+                "[engine_send_message] failed to interrupt timed out turn {}: {}",
+                turn,
+                error
+            );
+        }
+    };
+    app.state::<AppState>().engine_manager.arm_turn_timeout(
+        workspace_id,
+        turn_id,
+        Duration::from_secs(timeout_secs),
+        on_timeout,
+    );
 }
 
 /// Send a message using the active engine
@@ -1076,9 +1436,31 @@ pub async fn engine_send_message(
     agent: Option<String>,
     variant: Option<String>,
     custom_spec_root: Option<String>,
+    cwd: Option<String>,
+    timeout_secs: Option<u64>,
+    model_route: Option<ModelRouteOverride>,
     app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
+    if let Some(api_thread_id) = thread_id
+        .as_deref()
+        .filter(|thread_id| super::api_engine::is_api_thread_id(thread_id))
+    {
+        return send_api_engine_message(
+            &app,
+            &state,
+            &workspace_id,
+            text,
+            api_thread_id,
+            model,
+            access_mode,
+            images,
+            cwd,
+        )
+        .await;
+    }
+    let (model, model_routing) =
+        route_model_for_turn(&state, engine, &text, images.as_deref(), model, model_route).await;
     if remote_backend::is_remote_mode(&*state).await {
         let images = images.map(|paths| {
             paths
@@ -1106,9 +1488,12 @@ pub async fn engine_send_message(
                 "agent": agent,
                 "variant": variant,
                 "customSpecRoot": custom_spec_root,
+                "cwd": cwd,
+                "timeoutSecs": timeout_secs,
             }),
         )
-        .await;
+        .await
+        .map(|response| attach_model_routing(response, model_routing.as_ref()));
     }
 
     let manager = &state.engine_manager;
@@ -1117,6 +1502,7 @@ pub async fn engine_send_message(
     let effective_engine = requested_engine.unwrap_or(active_engine);
     let settings = read_app_settings_snapshot(&state).await;
     ensure_engine_enabled(&settings, effective_engine)?;
+    let adapter = manager.require_adapter(effective_engine)?;
     log::info!(
         "[engine_send_message] engine={:?} active_engine={:?} workspace_id={} model={:?} continue_session={} thread_id={:?} session_id={:?} fork_session_id={:?} agent={:?} variant={:?}",
         effective_engine,
@@ -1139,604 +1525,65 @@ pub async fn engine_send_message(
             );
         }
     }
+    let privacy =
+        crate::privacy_zones::enforce_for_turn(&state, &app, &workspace_id, text, images).await;
+    let (text, images) = (privacy.text, privacy.images);
     let normalized_custom_spec_root = normalize_custom_spec_root(custom_spec_root.as_deref());
-
-    match effective_engine {
-        EngineType::Claude => {
-            let workspace_entry = {
-                let workspaces = state.workspaces.lock().await;
-                workspaces
-                    .get(&workspace_id)
-                    .cloned()
-                    .ok_or_else(|| "Workspace not found".to_string())?
-            };
-            let workspace_path = std::path::PathBuf::from(&workspace_entry.path);
-            state
-                .runtime_manager
-                .record_starting(&workspace_entry, "claude", "engine-send-message")
-                .await;
-
-            let session = manager
-                .get_claude_session(&workspace_id, &workspace_path)
-                .await;
-
-            let has_images = images
-                .as_ref()
-                .is_some_and(|entries| entries.iter().any(|entry| !entry.trim().is_empty()));
-            let normalized_fork_session_id = fork_session_id
-                .as_ref()
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .map(str::to_string);
-            if fork_session_id.is_some() && normalized_fork_session_id.is_none() {
-                return Err("forkSessionId is required for Claude fork session".to_string());
-            }
-            let continue_session_for_send = continue_session;
-
-            // Resolve session id according to mode:
-            // 1) continue_session=true  -> explicit session_id or tracked session id
-            // 2) continue_session=false -> force a fresh unique session id so concurrent
-            //    Claude turns never collapse into one shared persisted session.
-            let resolved_session_id = if normalized_fork_session_id.is_some() {
-                None
-            } else if continue_session {
-                if session_id.is_some() {
-                    session_id
-                } else {
-                    session.get_session_id().await
-                }
-            } else {
-                Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
-            };
-
-            let sanitized_model = model
-                .as_ref()
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .and_then(|value| {
-                    if is_valid_claude_model_for_passthrough(value) {
-                        Some(value.to_string())
-                    } else {
-                        None
-                    }
-                });
-            if model.is_some() && sanitized_model.is_none() {
-                log::warn!(
-                    "[engine_send_message] dropped invalid claude model={:?}, fallback to default",
-                    model
-                );
-            }
-            let model_resolution = json!({
-                "requestedModel": model.as_deref(),
-                "runtimeModel": sanitized_model.as_deref(),
-                "willPassToCli": sanitized_model.is_some(),
-                "fallbackReason": if model.is_some() && sanitized_model.is_none() {
-                    Some("invalid-shape")
-                } else if model.is_none() {
-                    Some("not-requested")
-                } else {
-                    None
-                },
-            });
-
-            let response_session_id = resolved_session_id.clone();
-            let params = super::SendMessageParams {
-                text,
-                model: sanitized_model,
-                effort,
-                disable_thinking: disable_thinking.unwrap_or(false),
-                access_mode,
-                images,
-                continue_session: continue_session_for_send,
-                session_id: resolved_session_id,
-                fork_session_id: normalized_fork_session_id,
-                agent: None,
-                variant: None,
-                collaboration_mode: None,
+    let access_mode = resolve_workspace_access_mode(&state, &workspace_id, access_mode).await;
+    let turn_cwd = resolve_turn_cwd(&state, &workspace_id, cwd.as_deref()).await?;
+    if turn_cwd.is_some() && effective_engine == EngineType::Codex {
+        return Err(CODEX_TURN_CWD_UNSUPPORTED.to_string());
+    }
+    // Codex threads are captured in `send_user_message`.
+    let context_pack_launch =
+        (!continue_session && effective_engine != EngineType::Codex).then(|| {
+            crate::session_context_pack::ContextPackLaunch {
+                engine: effective_engine,
+                prompt: text.clone(),
+                model: model.clone(),
+                effort: effort.clone(),
+                access_mode: access_mode.clone(),
+                images: images.clone().unwrap_or_default(),
+                agent: agent.clone(),
+                variant: variant.clone(),
                 custom_spec_root: normalized_custom_spec_root.clone(),
-            };
-
-            // Generate unique render item ids for Claude's assistant/reasoning lanes.
-            // The conversation curtain keeps message/reasoning as separate items.
-            // Reusing one id across kinds causes realtime assistant text to be
-            // overwritten by reasoning snapshots in the normalized assembler path.
-            let turn_id = format!("claude-turn-{}", uuid::Uuid::new_v4());
-            let thread_id = thread_id.unwrap_or_else(|| turn_id.clone());
-            let assistant_item_id = format!("claude-item-{}", uuid::Uuid::new_v4());
-            let reasoning_item_id = format!("claude-reasoning-{}", uuid::Uuid::new_v4());
-
-            // Subscribe to session events BEFORE spawning send_message
-            let mut receiver = session.subscribe();
-            let app_clone = app.clone();
-            let turn_id_for_forwarder = turn_id.clone();
-            let runtime_manager = state.runtime_manager.clone();
-            let workspace_entry_for_forwarder = workspace_entry.clone();
-            let session_for_forwarder = session.clone();
-
-            // Spawn event forwarder: reads from broadcast channel and emits Tauri events.
-            tokio::spawn(async move {
-                let turn_source = format!("turn:{turn_id_for_forwarder}");
-                let stream_source = format!("stream:{turn_id_for_forwarder}");
-                let runtime_context = ClaudeForwarderRuntimeContext {
-                    runtime_manager,
-                    workspace_entry: workspace_entry_for_forwarder,
-                    session: session_for_forwarder,
-                    turn_source,
-                    stream_source,
-                };
-                let mut forwarder_state = ClaudeForwarderState::new(
-                    thread_id,
-                    assistant_item_id,
-                    reasoning_item_id,
-                    turn_id_for_forwarder.clone(),
-                );
-                let deadline = tokio::time::Instant::now()
-                    + std::time::Duration::from_secs(EVENT_FORWARDER_TIMEOUT_SECS);
-                let mut post_completion_grace_deadline: Option<tokio::time::Instant> = None;
-                loop {
-                    let active_deadline = post_completion_grace_deadline
-                        .map(|grace| std::cmp::min(grace, deadline))
-                        .unwrap_or(deadline);
-                    let recv_result =
-                        tokio::time::timeout_at(active_deadline, receiver.recv()).await;
-                    let turn_event = match recv_result {
-                        Ok(Ok(event)) => event,
-                        Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) => break,
-                        Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped))) => {
-                            log::warn!(
-                                "Claude event forwarder lagged; skipped {} events for turn {}",
-                                skipped,
-                                turn_id_for_forwarder
-                            );
-                            continue;
-                        }
-                        Err(_) => break, // timeout reached
-                    };
-                    if turn_event.turn_id != turn_id_for_forwarder {
-                        continue;
-                    }
-
-                    let is_post_completion_context_usage = post_completion_grace_deadline.is_some()
-                        && matches!(
-                            &turn_event.event,
-                            EngineEvent::UsageUpdate {
-                                context_usage_source,
-                                ..
-                            } if context_usage_source.as_deref() == Some("context_command")
-                        );
-                    let is_turn_completed =
-                        matches!(turn_event.event, EngineEvent::TurnCompleted { .. });
-                    let event = turn_event.event;
-                    let stream_timing = turn_event.stream_timing;
-                    let did_finish = handle_claude_forwarder_event(
-                        event,
-                        stream_timing.as_ref(),
-                        &mut forwarder_state,
-                        &runtime_context,
-                        &mut |payload| {
-                            let _ = app_clone.emit("app-server-event", payload);
-                        },
-                    )
-                    .await;
-                    if did_finish {
-                        if is_turn_completed {
-                            post_completion_grace_deadline = Some(
-                                tokio::time::Instant::now()
-                                    + std::time::Duration::from_millis(
-                                        CLAUDE_POST_COMPLETION_USAGE_GRACE_MS,
-                                    ),
-                            );
-                            continue;
-                        }
-                        break;
-                    }
-                    if is_post_completion_context_usage {
-                        break;
-                    }
-                }
-            });
-
-            // Spawn the message sender: drives the Claude CLI process
-            let session_clone = session.clone();
-            let turn_id_clone = turn_id.clone();
-            let runtime_manager_for_sender = state.runtime_manager.clone();
-            let workspace_entry_for_sender = workspace_entry.clone();
-            tokio::spawn(async move {
-                let send_result = if has_images {
-                    session_clone.send_message(params, &turn_id_clone).await
-                } else {
-                    session_clone
-                        .send_message_with_auto_compact_retry(params, &turn_id_clone)
-                        .await
-                };
-                if let Err(e) = send_result {
-                    log::error!("Claude send_message failed: {}", e);
-                    runtime_manager_for_sender
-                        .record_failure(
-                            &workspace_entry_for_sender,
-                            "claude",
-                            "engine-send-message",
-                            e,
-                        )
-                        .await;
-                }
-            });
-
-            // Return immediately with turn info (frontend will receive streaming events)
-            Ok(json!({
-                "engine": "claude",
-                "sessionId": response_session_id.clone(),
-                "result": {
-                    "sessionId": response_session_id.clone(),
-                    "modelResolution": model_resolution.clone(),
-                    "turn": {
-                        "id": turn_id,
-                        "status": "started"
-                    },
-                },
-                "modelResolution": model_resolution,
-                "turn": {
-                    "id": turn_id,
-                    "status": "started"
-                }
-            }))
-        }
-        EngineType::Codex => {
-            // For Codex, delegate to existing send_user_message command
-            // The frontend should use the existing command for now
-            Ok(json!({
-                "delegateTo": "send_user_message",
-                "engine": "codex",
-            }))
-        }
-        EngineType::OpenCode => {
-            let workspace_path = {
-                let workspaces = state.workspaces.lock().await;
-                workspaces
-                    .get(&workspace_id)
-                    .map(|w| std::path::PathBuf::from(&w.path))
-                    .ok_or_else(|| "Workspace not found".to_string())?
-            };
-
-            let session = manager
-                .get_or_create_opencode_session(&workspace_id, &workspace_path)
-                .await;
-
-            let resolved_session_id = if continue_session {
-                if session_id.is_some() {
-                    session_id
-                } else {
-                    session.get_session_id().await
-                }
-            } else {
-                Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
-            };
-
-            let sanitized_model = model
-                .as_ref()
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .and_then(|value| {
-                    if is_likely_legacy_claude_model_id(value) {
-                        None
-                    } else {
-                        Some(value.to_string())
-                    }
-                });
-            if model.is_some() && sanitized_model.is_none() {
-                log::warn!(
-                    "[engine_send_message] dropped invalid opencode model={:?}, fallback to default",
-                    model
-                );
+                cwd: turn_cwd.clone(),
+                fork_session_id: fork_session_id.clone(),
             }
-            let model_for_send =
-                sanitized_model.or_else(|| Some("openai/gpt-5.3-codex".to_string()));
-
-            let params = super::SendMessageParams {
-                text,
-                model: model_for_send,
-                effort,
-                disable_thinking: false,
-                access_mode,
-                images,
-                continue_session,
-                session_id: resolved_session_id,
-                fork_session_id: None,
-                agent,
-                variant,
-                collaboration_mode: None,
-                custom_spec_root: normalized_custom_spec_root.clone(),
-            };
-
-            let turn_id = format!("opencode-turn-{}", uuid::Uuid::new_v4());
-            let thread_id = thread_id.unwrap_or_else(|| turn_id.clone());
-            let item_id = format!("opencode-item-{}", uuid::Uuid::new_v4());
-
-            let mut receiver = session.subscribe();
-            let app_clone = app.clone();
-            let mut current_thread_id = thread_id.clone();
-            let item_id_clone = item_id.clone();
-            let turn_id_for_forwarder = turn_id.clone();
-            // Spawn event forwarder (same pattern as Claude forwarder above).
-            tokio::spawn(async move {
-                let deadline = tokio::time::Instant::now()
-                    + std::time::Duration::from_secs(EVENT_FORWARDER_TIMEOUT_SECS);
-                loop {
-                    let recv_result = tokio::time::timeout_at(deadline, receiver.recv()).await;
-                    let turn_event = match recv_result {
-                        Ok(Ok(event)) => event,
-                        Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) => break,
-                        Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped))) => {
-                            log::warn!(
-                                "OpenCode event forwarder lagged; skipped {} events for turn {}",
-                                skipped,
-                                turn_id_for_forwarder
-                            );
-                            continue;
-                        }
-                        Err(_) => break,
-                    };
-                    if turn_event.turn_id != turn_id_for_forwarder {
-                        continue;
-                    }
-
-                    let event = turn_event.event;
-                    let is_terminal = event.is_terminal();
-
-                    if let Some(payload) = engine_event_to_app_server_event_with_turn_context(
-                        &event,
-                        &current_thread_id,
-                        &item_id_clone,
-                        Some(&turn_id_for_forwarder),
-                    ) {
-                        let _ = app_clone.emit("app-server-event", payload);
-                    }
-
-                    if let EngineEvent::SessionStarted {
-                        session_id, engine, ..
-                    } = &event
-                    {
-                        if !session_id.is_empty() && session_id != "pending" {
-                            if matches!(engine, EngineType::OpenCode) {
-                                current_thread_id = format!("opencode:{}", session_id);
-                            }
-                        }
-                    }
-
-                    if is_terminal {
-                        break;
-                    }
-                }
-            });
-
-            let session_clone = session.clone();
-            let turn_id_clone = turn_id.clone();
-            tokio::spawn(async move {
-                if let Err(e) = session_clone.send_message(params, &turn_id_clone).await {
-                    log::error!("OpenCode send_message failed: {}", e);
-                    session_clone.emit_error(&turn_id_clone, e);
-                }
-            });
-
-            Ok(json!({
-                "engine": "opencode",
-                "result": {
-                    "turn": {
-                        "id": turn_id,
-                        "status": "started"
-                    },
-                },
-                "turn": {
-                    "id": turn_id,
-                    "status": "started"
-                }
-            }))
-        }
-        EngineType::Gemini => {
-            let workspace_path = {
-                let workspaces = state.workspaces.lock().await;
-                workspaces
-                    .get(&workspace_id)
-                    .map(|w| std::path::PathBuf::from(&w.path))
-                    .ok_or_else(|| "Workspace not found".to_string())?
-            };
-
-            let session = manager
-                .get_or_create_gemini_session(&workspace_id, &workspace_path)
-                .await;
-
-            let resolved_session_id = if continue_session {
-                if session_id.is_some() {
-                    session_id
-                } else {
-                    session.get_session_id().await
-                }
-            } else {
-                Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
-            };
-
-            let sanitized_model = model
-                .as_ref()
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .and_then(|value| {
-                    if is_likely_foreign_model_for_gemini(value) {
-                        None
-                    } else {
-                        Some(value.to_string())
-                    }
-                });
-            if model.is_some() && sanitized_model.is_none() {
-                log::warn!(
-                    "[engine_send_message] dropped invalid gemini model={:?}, fallback to default",
-                    model
-                );
-            }
-
-            let params = super::SendMessageParams {
-                text,
-                model: sanitized_model,
-                effort,
-                disable_thinking: false,
-                access_mode,
-                images,
-                continue_session,
-                session_id: resolved_session_id,
-                fork_session_id: None,
-                agent: None,
-                variant: None,
-                collaboration_mode: None,
-                custom_spec_root: normalized_custom_spec_root.clone(),
-            };
-
-            let turn_id = format!("gemini-turn-{}", uuid::Uuid::new_v4());
-            let thread_id = thread_id.unwrap_or_else(|| turn_id.clone());
-            let item_id = format!("gemini-item-{}", uuid::Uuid::new_v4());
-
-            let mut receiver = session.subscribe();
-            let app_clone = app.clone();
-            let mut current_thread_id = thread_id.clone();
-            let item_id_clone = item_id.clone();
-            let turn_id_for_forwarder = turn_id.clone();
-            let mut accumulated_agent_text = String::new();
-            tokio::spawn(async move {
-                let deadline = tokio::time::Instant::now()
-                    + std::time::Duration::from_secs(EVENT_FORWARDER_TIMEOUT_SECS);
-                let mut render_state = GeminiRenderRoutingState::default();
-                let mut post_completion_grace_deadline: Option<tokio::time::Instant> = None;
-                loop {
-                    let active_deadline = post_completion_grace_deadline
-                        .map(|grace| std::cmp::min(grace, deadline))
-                        .unwrap_or(deadline);
-                    let recv_result =
-                        tokio::time::timeout_at(active_deadline, receiver.recv()).await;
-                    let turn_event = match recv_result {
-                        Ok(Ok(event)) => event,
-                        Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) => break,
-                        Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped))) => {
-                            log::warn!(
-                                "Gemini event forwarder lagged; skipped {} events for turn {}",
-                                skipped,
-                                turn_id_for_forwarder
-                            );
-                            continue;
-                        }
-                        Err(_) => break,
-                    };
-                    if turn_event.turn_id != turn_id_for_forwarder {
-                        continue;
-                    }
-
-                    let event = turn_event.event;
-                    let is_terminal = event.is_terminal();
-                    let render_lane = match &event {
-                        EngineEvent::TextDelta { .. } => GeminiRenderLane::Text,
-                        EngineEvent::ReasoningDelta { .. } => GeminiRenderLane::Reasoning,
-                        EngineEvent::ToolStarted { .. }
-                        | EngineEvent::ToolCompleted { .. }
-                        | EngineEvent::ToolInputUpdated { .. }
-                        | EngineEvent::ToolOutputDelta { .. } => GeminiRenderLane::Tool,
-                        _ => GeminiRenderLane::Other,
-                    };
-                    let routed_item_id =
-                        next_gemini_routed_item_id(&mut render_state, render_lane, &item_id_clone);
-
-                    if let EngineEvent::TextDelta { text, .. } = &event {
-                        render_state.saw_text_delta = true;
-                        accumulated_agent_text.push_str(text);
-                    }
-
-                    if let EngineEvent::TurnCompleted { result, .. } = &event {
-                        let fallback_text =
-                            extract_turn_result_text(result.as_ref()).unwrap_or_default();
-                        let completed_text = if should_prefer_turn_result_text(result.as_ref()) {
-                            fallback_text
-                        } else if accumulated_agent_text.trim().is_empty() {
-                            fallback_text
-                        } else {
-                            accumulated_agent_text.clone()
-                        };
-                        // Preserve realtime interleaving for Gemini: when text deltas
-                        // already streamed, don't collapse them back into a single
-                        // synthetic completed assistant message.
-                        if !completed_text.trim().is_empty() && !render_state.saw_text_delta {
-                            let synthetic = AppServerEvent {
-                                workspace_id: event.workspace_id().to_string(),
-                                message: json!({
-                                    "method": "item/completed",
-                                    "params": {
-                                        "threadId": &current_thread_id,
-                                        "item": {
-                                            "id": &routed_item_id,
-                                            "type": "agentMessage",
-                                            "text": completed_text,
-                                            "status": "completed",
-                                        }
-                                    }
-                                }),
-                            };
-                            let _ = app_clone.emit("app-server-event", synthetic);
-                        }
-                    }
-
-                    if let Some(payload) = engine_event_to_app_server_event_with_turn_context(
-                        &event,
-                        &current_thread_id,
-                        &routed_item_id,
-                        Some(&turn_id_for_forwarder),
-                    ) {
-                        let _ = app_clone.emit("app-server-event", payload);
-                    }
-
-                    if let EngineEvent::SessionStarted {
-                        session_id, engine, ..
-                    } = &event
-                    {
-                        if !session_id.is_empty() && session_id != "pending" {
-                            if matches!(engine, EngineType::Gemini) {
-                                current_thread_id = format!("gemini:{}", session_id);
-                            }
-                        }
-                    }
-
-                    if is_terminal {
-                        if matches!(event, EngineEvent::TurnCompleted { .. }) {
-                            post_completion_grace_deadline = Some(
-                                tokio::time::Instant::now()
-                                    + std::time::Duration::from_millis(
-                                        GEMINI_POST_COMPLETION_REASONING_GRACE_MS,
-                                    ),
-                            );
-                            continue;
-                        }
-                        break;
-                    }
-                }
-            });
+        });
 
-            let session_clone = session.clone();
-            let turn_id_clone = turn_id.clone();
-            tokio::spawn(async move {
-                if let Err(e) = session_clone.send_message(params, &turn_id_clone).await {
-                    log::error!("Gemini send_message failed: {}", e);
-                }
-            });
-
-            Ok(json!({
-                "engine": "gemini",
-                "result": {
-                    "turn": {
-                        "id": turn_id,
-                        "status": "started"
-                    },
-                },
-                "turn": {
-                    "id": turn_id,
-                    "status": "started"
-                }
-            }))
+    let ctx = EngineContext {
+        app: &app,
+        state: &state,
+        workspace_id: &workspace_id,
+    };
+    let params = super::SendMessageParams {
+        text,
+        model,
+        effort,
+        disable_thinking: disable_thinking.unwrap_or(false),
+        access_mode,
+        images,
+        continue_session,
+        session_id,
+        fork_session_id,
+        agent,
+        variant,
+        collaboration_mode: None,
+        custom_spec_root: normalized_custom_spec_root,
+        cwd: turn_cwd,
+        timeout_secs,
+    };
+    let response = adapter.start_turn(ctx, params, thread_id).await;
+    if let (Some(launch), Ok(response)) = (context_pack_launch, response.as_ref()) {
+        if let Some(turn_id) = response.pointer("/turn/id").and_then(Value::as_str) {
+            crate::session_context_pack::queue_session_launch(&workspace_id, turn_id, launch);
         }
     }
+    response.map(|response| {
+        let response = attach_model_routing(response, model_routing.as_ref());
+        crate::privacy_zones::attach_privacy_notice(response, privacy.notice.as_ref())
+    })
 }
 
 /// Send a message and wait for the final plain-text response from the selected engine.
@@ -1756,12 +1603,16 @@ pub async fn engine_send_message_sync(
     agent: Option<String>,
     variant: Option<String>,
     custom_spec_root: Option<String>,
+    cwd: Option<String>,
+    model_route: Option<ModelRouteOverride>,
     app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
     if text.trim().is_empty() {
         return Err("Prompt text cannot be empty".to_string());
     }
+    let (model, model_routing) =
+        route_model_for_turn(&state, engine, &text, images.as_deref(), model, model_route).await;
     if remote_backend::is_remote_mode(&*state).await {
         let (method, params) = remote_engine_send_message_sync_request(
             workspace_id,
@@ -1778,246 +1629,71 @@ pub async fn engine_send_message_sync(
             agent,
             variant,
             custom_spec_root,
+            cwd,
         );
-        return remote_backend::call_remote(&*state, app, method, params).await;
+        return remote_backend::call_remote(&*state, app, method, params)
+            .await
+            .map(|response| attach_model_routing(response, model_routing.as_ref()));
     }
 
     let manager = &state.engine_manager;
     let active_engine = manager.get_active_engine().await;
     let effective_engine = engine.unwrap_or(active_engine);
+    let adapter = manager.require_adapter(effective_engine)?;
+    let privacy =
+        crate::privacy_zones::enforce_for_turn(&state, &app, &workspace_id, text, images).await;
+    let (text, images) = (privacy.text, privacy.images);
     let normalized_custom_spec_root = normalize_custom_spec_root(custom_spec_root.as_deref());
-
-    match effective_engine {
-        EngineType::Claude => {
-            let workspace_path = {
-                let workspaces = state.workspaces.lock().await;
-                workspaces
-                    .get(&workspace_id)
-                    .map(|w| std::path::PathBuf::from(&w.path))
-                    .ok_or_else(|| "Workspace not found".to_string())?
-            };
-            let session = manager
-                .get_claude_session(&workspace_id, &workspace_path)
-                .await;
-
-            let has_images = images
-                .as_ref()
-                .is_some_and(|entries| entries.iter().any(|entry| !entry.trim().is_empty()));
-            let normalized_fork_session_id = fork_session_id
-                .as_ref()
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .map(str::to_string);
-            if fork_session_id.is_some() && normalized_fork_session_id.is_none() {
-                return Err("forkSessionId is required for Claude fork session".to_string());
-            }
-            let continue_session_for_send = continue_session;
-
-            let resolved_session_id = if normalized_fork_session_id.is_some() {
-                None
-            } else if session_id.is_some() {
-                session_id
-            } else if continue_session {
-                session.get_session_id().await
-            } else {
-                None
-            };
-
-            let sanitized_model = model
-                .as_ref()
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .and_then(|value| {
-                    if is_valid_claude_model_for_passthrough(value) {
-                        Some(value.to_string())
-                    } else {
-                        None
-                    }
-                });
-
-            let response_session_id = resolved_session_id.clone();
-            let params = super::SendMessageParams {
-                text,
-                model: sanitized_model,
-                effort,
-                disable_thinking: disable_thinking.unwrap_or(false),
-                access_mode,
-                images,
-                continue_session: continue_session_for_send,
-                session_id: resolved_session_id,
-                fork_session_id: normalized_fork_session_id,
-                agent: None,
-                variant: None,
-                collaboration_mode: None,
-                custom_spec_root: normalized_custom_spec_root.clone(),
-            };
-
-            let turn_id = format!("claude-sync-{}", uuid::Uuid::new_v4());
-            let response = timeout(Duration::from_secs(900), async {
-                if has_images {
-                    session.send_message(params, &turn_id).await
-                } else {
-                    session
-                        .send_message_with_auto_compact_retry(params, &turn_id)
-                        .await
-                }
-            })
-            .await
-            .map_err(|_| "Claude response timed out".to_string())??;
-
-            Ok(json!({
-                "engine": "claude",
-                "sessionId": response_session_id,
-                "text": response
-            }))
-        }
-        EngineType::OpenCode => {
-            let workspace_path = {
-                let workspaces = state.workspaces.lock().await;
-                workspaces
-                    .get(&workspace_id)
-                    .map(|w| std::path::PathBuf::from(&w.path))
-                    .ok_or_else(|| "Workspace not found".to_string())?
-            };
-
-            let session = manager
-                .get_or_create_opencode_session(&workspace_id, &workspace_path)
-                .await;
-            let resolved_session_id = if continue_session {
-                if session_id.is_some() {
-                    session_id
-                } else {
-                    session.get_session_id().await
-                }
-            } else {
-                Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
-            };
-
-            let sanitized_model = model
-                .as_ref()
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .and_then(|value| {
-                    if is_likely_legacy_claude_model_id(value) {
-                        None
-                    } else {
-                        Some(value.to_string())
-                    }
-                });
-            let model_for_send =
-                sanitized_model.or_else(|| Some("openai/gpt-5.3-codex".to_string()));
-
-            let params = super::SendMessageParams {
-                text,
-                model: model_for_send,
-                effort,
-                disable_thinking: false,
-                access_mode,
-                images,
-                continue_session,
-                session_id: resolved_session_id,
-                fork_session_id: None,
-                agent,
-                variant,
-                collaboration_mode: None,
-                custom_spec_root: normalized_custom_spec_root.clone(),
-            };
-
-            let turn_id = format!("opencode-sync-{}", uuid::Uuid::new_v4());
-            let response = timeout(
-                Duration::from_secs(900),
-                session.send_message(params, &turn_id),
-            )
-            .await
-            .map_err(|_| "OpenCode response timed out".to_string())??;
-
-            Ok(json!({
-                "engine": "opencode",
-                "text": response
-            }))
-        }
-        EngineType::Codex => {
-            let response = run_codex_prompt_sync(
-                &workspace_id,
-                &text,
-                model,
-                effort,
-                access_mode,
-                normalized_custom_spec_root.clone(),
-                &app,
-                &state,
-            )
-            .await?;
-
-            Ok(json!({
-                "engine": "codex",
-                "text": response
-            }))
-        }
-        EngineType::Gemini => {
-            let workspace_path = {
-                let workspaces = state.workspaces.lock().await;
-                workspaces
-                    .get(&workspace_id)
-                    .map(|w| std::path::PathBuf::from(&w.path))
-                    .ok_or_else(|| "Workspace not found".to_string())?
-            };
-
-            let session = manager
-                .get_or_create_gemini_session(&workspace_id, &workspace_path)
-                .await;
-            let resolved_session_id = if continue_session {
-                if session_id.is_some() {
-                    session_id
-                } else {
-                    session.get_session_id().await
-                }
-            } else {
-                Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
-            };
-
-            let sanitized_model = model
-                .as_ref()
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .and_then(|value| {
-                    if is_likely_foreign_model_for_gemini(value) {
-                        None
-                    } else {
-                        Some(value.to_string())
-                    }
-                });
-
-            let params = super::SendMessageParams {
-                text,
-                model: sanitized_model,
-                effort,
-                disable_thinking: false,
-                access_mode,
-                images,
-                continue_session,
-                session_id: resolved_session_id,
-                fork_session_id: None,
-                agent: None,
-                variant: None,
-                collaboration_mode: None,
-                custom_spec_root: normalized_custom_spec_root.clone(),
-            };
-
-            let turn_id = format!("gemini-sync-{}", uuid::Uuid::new_v4());
-            let response = timeout(
-                Duration::from_secs(900),
-                session.send_message(params, &turn_id),
-            )
-            .await
-            .map_err(|_| "Gemini response timed out".to_string())??;
-
-            Ok(json!({
-                "engine": "gemini",
-                "text": response
-            }))
+    let access_mode = resolve_workspace_access_mode(&state, &workspace_id, access_mode).await;
+    let turn_cwd = resolve_turn_cwd(&state, &workspace_id, cwd.as_deref()).await?;
+    let continued_session_id = fork_session_id
+        .clone()
+        .or_else(|| session_id.clone().filter(|_| continue_session));
+    let turn_cwd = super::turn_cwds::resolve_turn_cwd(
+        &workspace_id,
+        continued_session_id.as_deref(),
+        turn_cwd,
+    )?;
+    let ctx = EngineContext {
+        app: &app,
+        state: &state,
+        workspace_id: &workspace_id,
+    };
+    let params = super::SendMessageParams {
+        text,
+        model,
+        effort,
+        disable_thinking: disable_thinking.unwrap_or(false),
+        access_mode,
+        images,
+        continue_session,
+        session_id: None,
+        fork_session_id,
+        agent,
+        variant,
+        collaboration_mode: None,
+        custom_spec_root: normalized_custom_spec_root,
+        cwd: turn_cwd,
+        timeout_secs: None,
+    };
+    let response = match session_id {
+        Some(session_id) if continue_session => adapter.resume(ctx, session_id, params).await,
+        session_id => {
+            adapter
+                .send(
+                    ctx,
+                    super::SendMessageParams {
+                        session_id,
+                        ..params
+                    },
+                )
+                .await
         }
-    }
+    };
+    response.map(|response| {
+        let response = attach_model_routing(response, model_routing.as_ref());
+        crate::privacy_zones::attach_privacy_notice(response, privacy.notice.as_ref())
+    })
 }
 
 /// Interrupt the current operation for the active engine
@@ -2032,38 +1708,19 @@ pub async fn engine_interrupt(
         let _: Value = call_remote_typed(&*state, &app, method, params).await?;
         return Ok(());
     }
+    super::api_engine::interrupt_workspace(&workspace_id)?;
     let manager = &state.engine_manager;
+    manager.disarm_workspace_turn_timeouts(&workspace_id);
     let active_engine = manager.get_active_engine().await;
-
-    match active_engine {
-        EngineType::Claude => {
-            if let Some(session) = manager.claude_manager.get_session(&workspace_id).await {
-                session.interrupt().await?;
-            }
-            Ok(())
-        }
-        EngineType::Codex => {
-            // Codex interrupts are handled via turn_interrupt RPC from the frontend.
-            // This path is a fallback; log for diagnostic visibility.
-            log::info!(
-                "engine_interrupt called for Codex workspace: {}",
-                workspace_id
-            );
-            Ok(())
-        }
-        EngineType::OpenCode => {
-            if let Some(session) = manager.get_opencode_session(&workspace_id).await {
-                session.interrupt().await?;
-            }
-            Ok(())
-        }
-        EngineType::Gemini => {
-            if let Some(session) = manager.get_gemini_session(&workspace_id).await {
-                session.interrupt().await?;
-            }
-            Ok(())
-        }
-    }
+    let ctx = EngineContext {
+        app: &app,
+        state: &state,
+        workspace_id: &workspace_id,
+    };
+    manager
+        .require_adapter(active_engine)?
+        .interrupt(ctx, None)
+        .await
 }
 
 /// Interrupt a specific turn for the active engine.
@@ -2075,6 +1732,9 @@ pub async fn engine_interrupt_turn(
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
+    if super::api_engine::is_api_turn_id(&turn_id) {
+        return super::api_engine::interrupt_turn(&turn_id);
+    }
     if remote_backend::is_remote_mode(&*state).await {
         let _: Value = call_remote_typed(
             &*state,
@@ -2090,33 +1750,110 @@ pub async fn engine_interrupt_turn(
         return Ok(());
     }
     let manager = &state.engine_manager;
+    manager.disarm_turn_timeout(&turn_id);
     let active_engine = manager.get_active_engine().await;
     let target_engine = engine.unwrap_or(active_engine);
+    let ctx = EngineContext {
+        app: &app,
+        state: &state,
+        workspace_id: &workspace_id,
+    };
+    manager
+        .require_adapter(target_engine)?
+        .interrupt(ctx, Some(&turn_id))
+        .await
+}
 
-    match target_engine {
-        EngineType::Claude => {
-            if let Some(session) = manager.claude_manager.get_session(&workspace_id).await {
-                session.interrupt_turn(&turn_id).await?;
-            }
-            Ok(())
-        }
-        EngineType::Codex => {
-            // Codex interrupts are handled via turn_interrupt RPC from the frontend.
-            Ok(())
-        }
-        EngineType::OpenCode => {
-            if let Some(session) = manager.get_opencode_session(&workspace_id).await {
-                session.interrupt_turn(&turn_id).await?;
-            }
-            Ok(())
+/// Answer a Claude AskUserQuestion prompt announced by `claude-user-question`.
+///
+/// Print-mode Claude resolves the tool call on its own, so the answer is
+/// delivered by resuming the session with it once the running process stops.
+/// `answer` is a label, a list of labels, or an object of question id to
+/// label(s).
+#[tauri::command]
+pub async fn claude_respond_to_question(
+    session_id: String,
+    question_id: String,
+    answer: Value,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("claude_respond_to_question is not supported in remote mode yet.".to_string());
+    }
+    let session_id = session_id.trim();
+    let session_id = session_id.strip_prefix("claude:").unwrap_or(session_id);
+    let request_id = Value::String(question_id.trim().to_string());
+    let result = super::claude_message_content::ask_user_answer_result(&answer)?;
+
+    // Question ids are unique per tool call; the session id only picks
+    // between workspaces if two ever collide.
+    let mut owner = None;
+    let mut fallback = None;
+    for (workspace_id, session) in state.engine_manager.claude_manager.list_sessions().await {
+        if !session.has_pending_user_input(&request_id) {
+            continue;
         }
-        EngineType::Gemini => {
-            if let Some(session) = manager.get_gemini_session(&workspace_id).await {
-                session.interrupt_turn(&turn_id).await?;
-            }
-            Ok(())
+        if session.get_session_id().await.as_deref() == Some(session_id) {
+            owner = Some((workspace_id, session));
+            break;
         }
+        fallback.get_or_insert((workspace_id, session));
+    }
+    let (workspace_id, session) = owner.or(fallback).ok_or_else(|| {
+        format!(
+            "No pending Claude question {} in session {}",
+            question_id.trim(),
+            session_id
+        )
+    })?;
+    crate::outbound_notifications::mark_server_request_resolved(&workspace_id, &request_id);
+    crate::user_input_policy::mark_request_resolved(&workspace_id, &request_id);
+    session.respond_to_user_input(request_id, result).await
+}
+
+/// Live event forwarders of this process, for debugging turns whose events
+/// stopped arriving.
+#[tauri::command]
+pub async fn engine_forwarder_stats(
+    state: State<'_, AppState>,
+) -> Result<EventForwarderSnapshot, String> {
+    Ok(state.engine_manager.forwarders.snapshot())
+}
+
+/// List session history for a workspace through the engine's adapter.
+#[tauri::command]
+pub async fn engine_list_sessions(
+    workspace_id: String,
+    engine: Option<EngineType>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&state).await {
+        return call_remote_typed(
+            &state,
+            &app,
+            "engine_list_sessions",
+            json!({ "workspaceId": workspace_id, "engine": engine, "limit": limit }),
+        )
+        .await;
     }
+    let manager = &state.engine_manager;
+    let target_engine = match engine {
+        Some(engine) => engine,
+        None => manager.get_active_engine().await,
+    };
+    let settings = read_app_settings_snapshot(&state).await;
+    ensure_engine_enabled(&settings, target_engine)?;
+    let ctx = EngineContext {
+        app: &app,
+        state: &state,
+        workspace_id: &workspace_id,
+    };
+    manager
+        .require_adapter(target_engine)?
+        .history(ctx, limit)
+        .await
 }
 
 #[cfg(test)]
@@ -0,0 +1,1347 @@
+use std::sync::Arc;
+
+use super::super::adapter::{EngineAdapter, EngineContext, EngineFuture};
+use super::super::manager::EngineManager;
+use super::super::status::{detect_claude_status, detect_codex_status};
+use super::super::SendMessageParams;
+use super::*;
+
+/// Maximum time a synchronous prompt may run before it is abandoned.
+const SYNC_SEND_TIMEOUT: Duration = Duration::from_secs(900);
+
+/// Registers the adapters compiled into this build. Optional engines are
+/// gated by Cargo features (`engine-gemini`, `engine-opencode`, both on by
+/// default); an additional CLI adapter adds its own feature and one line here.
+pub(crate) fn register_builtin_engine_adapters(manager: &EngineManager) {
+    manager.register_adapter(Arc::new(ClaudeAdapter));
+    manager.register_adapter(Arc::new(CodexAdapter));
+    #[cfg(feature = "engine-gemini")]
+    manager.register_adapter(Arc::new(GeminiAdapter));
+    #[cfg(feature = "engine-opencode")]
+    manager.register_adapter(Arc::new(OpenCodeAdapter));
+}
+
+pub(crate) struct ClaudeAdapter;
+
+impl EngineAdapter for ClaudeAdapter {
+    fn engine_type(&self) -> EngineType {
+        EngineType::Claude
+    }
+
+    fn detect<'a>(&'a self, bin: Option<&'a str>) -> EngineFuture<'a, EngineStatus> {
+        Box::pin(detect_claude_status(bin))
+    }
+
+    fn send<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        params: SendMessageParams,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let workspace_path = ctx.workspace_path().await?;
+            let session = ctx
+                .state
+                .engine_manager
+                .get_claude_session(ctx.workspace_id, &workspace_path)
+                .await;
+
+            let has_images = params
+                .images
+                .as_ref()
+                .is_some_and(|entries| entries.iter().any(|entry| !entry.trim().is_empty()));
+            let normalized_fork_session_id = params
+                .fork_session_id
+                .as_ref()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(str::to_string);
+            if params.fork_session_id.is_some() && normalized_fork_session_id.is_none() {
+                return Err("forkSessionId is required for Claude fork session".to_string());
+            }
+
+            let resolved_session_id = if normalized_fork_session_id.is_some() {
+                None
+            } else if params.session_id.is_some() {
+                params.session_id
+            } else if params.continue_session {
+                session.get_session_id().await
+            } else {
+                None
+            };
+
+            let sanitized_model = params
+                .model
+                .as_ref()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .and_then(|value| {
+                    if is_valid_claude_model_for_passthrough(value) {
+                        Some(value.to_string())
+                    } else {
+                        None
+                    }
+                });
+
+            let response_session_id = resolved_session_id.clone();
+            let params = SendMessageParams {
+                model: sanitized_model,
+                session_id: resolved_session_id,
+                fork_session_id: normalized_fork_session_id,
+                agent: None,
+                variant: None,
+                collaboration_mode: None,
+                ..params
+            };
+
+            let turn_id = format!("claude-sync-{}", uuid::Uuid::new_v4());
+            let response = timeout(SYNC_SEND_TIMEOUT, async {
+                if has_images {
+                    session.send_message(params, &turn_id).await
+                } else {
+                    session
+                        .send_message_with_auto_compact_retry(params, &turn_id)
+                        .await
+                }
+            })
+            .await
+            .map_err(|_| "Claude response timed out".to_string())??;
+
+            Ok(json!({
+                "engine": "claude",
+                "sessionId": response_session_id,
+                "text": response
+            }))
+        })
+    }
+
+    fn interrupt<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        turn_id: Option<&'a str>,
+    ) -> EngineFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let manager = &ctx.state.engine_manager;
+            if let Some(session) = manager.claude_manager.get_session(ctx.workspace_id).await {
+                match turn_id {
+                    Some(turn_id) => session.interrupt_turn(turn_id).await?,
+                    None => session.interrupt().await?,
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn history<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        limit: Option<usize>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let workspace_path = ctx.workspace_path().await?;
+            let config = ctx
+                .state
+                .engine_manager
+                .get_engine_config(EngineType::Claude)
+                .await;
+            let sessions = super::super::claude_history::list_claude_sessions_with_config(
+                &workspace_path,
+                limit,
+                config.as_ref(),
+            )
+            .await?;
+            serde_json::to_value(sessions).map_err(|error| error.to_string())
+        })
+    }
+
+    fn start_turn<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        params: SendMessageParams,
+        thread_id: Option<String>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let SendMessageParams {
+                text,
+                model,
+                effort,
+                disable_thinking,
+                access_mode,
+                images,
+                continue_session,
+                session_id,
+                fork_session_id,
+                custom_spec_root: normalized_custom_spec_root,
+                cwd: turn_cwd,
+                timeout_secs,
+                ..
+            } = params;
+            let (app, state) = (ctx.app, ctx.state);
+            let manager = &state.engine_manager;
+            let workspace_id = ctx.workspace_id.to_string();
+
+            let workspace_entry = {
+                let workspaces = state.workspaces.lock().await;
+                workspaces
+                    .get(&workspace_id)
+                    .cloned()
+                    .ok_or_else(|| "Workspace not found".to_string())?
+            };
+            let workspace_path = std::path::PathBuf::from(&workspace_entry.path);
+            state
+                .runtime_manager
+                .record_starting(&workspace_entry, "claude", "engine-send-message")
+                .await;
+
+            let session = manager
+                .get_claude_session(&workspace_id, &workspace_path)
+                .await;
+
+            let has_images = images
+                .as_ref()
+                .is_some_and(|entries| entries.iter().any(|entry| !entry.trim().is_empty()));
+            let normalized_fork_session_id = fork_session_id
+                .as_ref()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(str::to_string);
+            if fork_session_id.is_some() && normalized_fork_session_id.is_none() {
+                return Err("forkSessionId is required for Claude fork session".to_string());
+            }
+            let continue_session_for_send = continue_session;
+
+            // Resolve session id according to mode:
+            // 1) continue_session=true  -> explicit session_id or tracked session id
+            // 2) continue_session=false -> force a fresh unique session id so concurrent
+            //    Claude turns never collapse into one shared persisted session.
+            let resolved_session_id = if normalized_fork_session_id.is_some() {
+                None
+            } else if continue_session {
+                if session_id.is_some() {
+                    session_id
+                } else {
+                    session.get_session_id().await
+                }
+            } else {
+                Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
+            };
+            let continued_session_id = normalized_fork_session_id
+                .clone()
+                .or_else(|| resolved_session_id.clone().filter(|_| continue_session));
+            let turn_cwd = crate::engine::turn_cwds::resolve_turn_cwd(
+                &workspace_id,
+                continued_session_id.as_deref(),
+                turn_cwd,
+            )?;
+
+            let sanitized_model = model
+                .as_ref()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .and_then(|value| {
+                    if is_valid_claude_model_for_passthrough(value) {
+                        Some(value.to_string())
+                    } else {
+                        None
+                    }
+                });
+            if model.is_some() && sanitized_model.is_none() {
+                log::warn!(
+                    "[engine_send_message] dropped invalid claude model={:?}, fallback to default",
+                    model
+                );
+            }
+            let model_resolution = json!({
+                "requestedModel": model.as_deref(),
+                "runtimeModel": sanitized_model.as_deref(),
+                "willPassToCli": sanitized_model.is_some(),
+                "fallbackReason": if model.is_some() && sanitized_model.is_none() {
+                    Some("invalid-shape")
+                } else if model.is_none() {
+                    Some("not-requested")
+                } else {
+                    None
+                },
+            });
+
+            let response_session_id = resolved_session_id.clone();
+            let model_for_cost = sanitized_model.clone();
+            let params = SendMessageParams {
+                text,
+                model: sanitized_model,
+                effort,
+                disable_thinking,
+                access_mode,
+                images,
+                continue_session: continue_session_for_send,
+                session_id: resolved_session_id,
+                fork_session_id: normalized_fork_session_id,
+                agent: None,
+                variant: None,
+                collaboration_mode: None,
+                custom_spec_root: normalized_custom_spec_root.clone(),
+                cwd: turn_cwd.clone(),
+                timeout_secs,
+            };
+
+            // Generate unique render item ids for Claude's assistant/reasoning lanes.
+            // The conversation curtain keeps message/reasoning as separate items.
+            // Reusing one id across kinds causes realtime assistant text to be
+            // overwritten by reasoning snapshots in the normalized assembler path.
+            let turn_id = format!("claude-turn-{}", uuid::Uuid::new_v4());
+            let thread_id = thread_id.unwrap_or_else(|| turn_id.clone());
+            crate::engine::turn_cwds::record_turn_cwd(
+                &workspace_id,
+                EngineType::Claude,
+                &turn_id,
+                response_session_id.as_deref(),
+                turn_cwd.as_deref(),
+            );
+            let assistant_item_id = format!("claude-item-{}", uuid::Uuid::new_v4());
+            let reasoning_item_id = format!("claude-reasoning-{}", uuid::Uuid::new_v4());
+
+            // Subscribe to session events BEFORE spawning send_message
+            let mut receiver = session.subscribe();
+            let app_clone = app.clone();
+            let turn_id_for_forwarder = turn_id.clone();
+            let runtime_manager = state.runtime_manager.clone();
+            let workspace_entry_for_forwarder = workspace_entry.clone();
+            let session_for_forwarder = session.clone();
+            let workspace_id_for_forwarder = workspace_id.clone();
+            let forwarder =
+                manager
+                    .forwarders
+                    .register(EngineType::Claude, &workspace_id, &turn_id);
+
+            // Spawn event forwarder: reads from broadcast channel and emits Tauri events.
+            tokio::spawn(async move {
+                let turn_source = format!("turn:{turn_id_for_forwarder}");
+                let stream_source = format!("stream:{turn_id_for_forwarder}");
+                let runtime_context = ClaudeForwarderRuntimeContext {
+                    runtime_manager,
+                    workspace_entry: workspace_entry_for_forwarder,
+                    session: session_for_forwarder,
+                    turn_source,
+                    stream_source,
+                };
+                let mut forwarder_state = ClaudeForwarderState::new(
+                    thread_id,
+                    assistant_item_id,
+                    reasoning_item_id,
+                    turn_id_for_forwarder.clone(),
+                );
+                let mut post_completion_grace_deadline: Option<tokio::time::Instant> = None;
+                loop {
+                    let turn_event = match forwarder
+                        .recv(&mut receiver, post_completion_grace_deadline)
+                        .await
+                    {
+                        ForwarderRecv::Event(event) => event,
+                        ForwarderRecv::Closed | ForwarderRecv::GraceElapsed => break,
+                        ForwarderRecv::Lagged(skipped) => {
+                            log::warn!(
+                                "Claude event forwarder lagged; skipped {} events for turn {}",
+                                skipped,
+                                turn_id_for_forwarder
+                            );
+                            continue;
+                        }
+                        ForwarderRecv::Reaped(reason) => {
+                            // After turn/completed the turn is already settled.
+                            if post_completion_grace_deadline.is_none() {
+                                let _ = app_clone.emit(
+                                    "app-server-event",
+                                    forwarder.reaped_event(
+                                        &workspace_id_for_forwarder,
+                                        forwarder_state.current_thread_id(),
+                                        reason,
+                                    ),
+                                );
+                            }
+                            break;
+                        }
+                    };
+                    if turn_event.turn_id != turn_id_for_forwarder {
+                        continue;
+                    }
+                    forwarder.record_event();
+
+                    let is_post_completion_context_usage = post_completion_grace_deadline.is_some()
+                        && matches!(
+                            &turn_event.event,
+                            EngineEvent::UsageUpdate {
+                                context_usage_source,
+                                ..
+                            } if context_usage_source.as_deref() == Some("context_command")
+                        );
+                    let is_turn_completed =
+                        matches!(turn_event.event, EngineEvent::TurnCompleted { .. });
+                    let event = turn_event.event;
+                    let stream_timing = turn_event.stream_timing;
+                    if let Some(question) = claude_user_question_payload(
+                        &event,
+                        forwarder_state.current_thread_id(),
+                        &turn_id_for_forwarder,
+                        runtime_context.session.user_input_timeout(),
+                    ) {
+                        let _ = app_clone.emit(CLAUDE_USER_QUESTION_EVENT, question);
+                    }
+                    let did_finish = handle_claude_forwarder_event(
+                        event,
+                        stream_timing.as_ref(),
+                        &mut forwarder_state,
+                        &runtime_context,
+                        &mut |mut payload| {
+                            crate::session_cost::annotate_app_server_event(
+                                &mut payload,
+                                model_for_cost.as_deref(),
+                            );
+                            crate::file_attribution::observe_app_server_event(&app_clone, &payload);
+                            crate::file_mentions::observe_app_server_event(&app_clone, &payload);
+                            crate::session_context_pack::observe_app_server_event(
+                                &app_clone, &payload,
+                            );
+                            crate::engine::turn_cwds::observe_app_server_event(&payload);
+                            crate::outbound_notifications::observe_app_server_event(
+                                &app_clone, &payload,
+                            );
+                            crate::user_input_policy::observe_app_server_event(
+                                &app_clone, &payload,
+                            );
+                            crate::sandbox_report::observe_app_server_event(&app_clone, &payload);
+                            crate::session_stats::observe_app_server_event(&app_clone, &payload);
+                            let _ = app_clone.emit("app-server-event", payload);
+                        },
+                    )
+                    .await;
+                    if did_finish {
+                        if is_turn_completed {
+                            post_completion_grace_deadline = Some(
+                                tokio::time::Instant::now()
+                                    + std::time::Duration::from_millis(
+                                        CLAUDE_POST_COMPLETION_USAGE_GRACE_MS,
+                                    ),
+                            );
+                            continue;
+                        }
+                        break;
+                    }
+                    if is_post_completion_context_usage {
+                        break;
+                    }
+                }
+            });
+
+            // Spawn the message sender: drives the Claude CLI process
+            let session_clone = session.clone();
+            let turn_id_clone = turn_id.clone();
+            let runtime_manager_for_sender = state.runtime_manager.clone();
+            let workspace_entry_for_sender = workspace_entry.clone();
+            let app_for_sender = app.clone();
+            arm_engine_turn_timeout(
+                &app,
+                EngineType::Claude,
+                &workspace_id,
+                &turn_id,
+                timeout_secs,
+            );
+            tokio::spawn(async move {
+                let send_result = if has_images {
+                    session_clone.send_message(params, &turn_id_clone).await
+                } else {
+                    session_clone
+                        .send_message_with_auto_compact_retry(params, &turn_id_clone)
+                        .await
+                };
+                let state = app_for_sender.state::<AppState>();
+                state.engine_manager.disarm_turn_timeout(&turn_id_clone);
+                state
+                    .engine_manager
+                    .forwarders
+                    .mark_process_exited(&turn_id_clone);
+                if let Err(e) = send_result {
+                    log::error!("Claude send_message failed: {}", e);
+                    runtime_manager_for_sender
+                        .record_failure(
+                            &workspace_entry_for_sender,
+                            "claude",
+                            "engine-send-message",
+                            e,
+                        )
+                        .await;
+                }
+            });
+
+            // Return immediately with turn info (frontend will receive streaming events)
+            Ok(json!({
+                "engine": "claude",
+                "sessionId": response_session_id.clone(),
+                "result": {
+                    "sessionId": response_session_id.clone(),
+                    "modelResolution": model_resolution.clone(),
+                    "turn": {
+                        "id": turn_id,
+                        "status": "started",
+                        "cwd": turn_cwd,
+                    },
+                },
+                "modelResolution": model_resolution,
+                "turn": {
+                    "id": turn_id,
+                    "status": "started",
+                    "cwd": turn_cwd,
+                }
+            }))
+        })
+    }
+}
+
+pub(crate) struct CodexAdapter;
+
+impl EngineAdapter for CodexAdapter {
+    fn engine_type(&self) -> EngineType {
+        EngineType::Codex
+    }
+
+    fn detect<'a>(&'a self, bin: Option<&'a str>) -> EngineFuture<'a, EngineStatus> {
+        Box::pin(detect_codex_status(bin))
+    }
+
+    fn send<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        params: SendMessageParams,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            if params.cwd.is_some() {
+                return Err(CODEX_TURN_CWD_UNSUPPORTED.to_string());
+            }
+            let response = run_codex_prompt_sync(
+                ctx.workspace_id,
+                &params.text,
+                params.model,
+                params.effort,
+                params.access_mode,
+                params.custom_spec_root,
+                ctx.app,
+                ctx.state,
+            )
+            .await?;
+
+            Ok(json!({
+                "engine": "codex",
+                "text": response
+            }))
+        })
+    }
+
+    fn interrupt<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        turn_id: Option<&'a str>,
+    ) -> EngineFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            // Codex interrupts are handled via turn_interrupt RPC from the frontend.
+            // This path is a fallback; log for diagnostic visibility.
+            if turn_id.is_none() {
+                log::info!(
+                    "engine_interrupt called for Codex workspace: {}",
+                    ctx.workspace_id
+                );
+            }
+            Ok(())
+        })
+    }
+
+    fn history<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        limit: Option<usize>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let limit = limit.map(|value| u32::try_from(value).unwrap_or(u32::MAX));
+            crate::codex::codex_thread_history(ctx.state, ctx.workspace_id, limit).await
+        })
+    }
+
+    fn start_turn<'a>(
+        &'a self,
+        _ctx: EngineContext<'a>,
+        _params: SendMessageParams,
+        _thread_id: Option<String>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        // Codex turns stream through `send_user_message`.
+        Box::pin(async {
+            Ok(json!({
+                "delegateTo": "send_user_message",
+                "engine": "codex",
+            }))
+        })
+    }
+}
+
+#[cfg(feature = "engine-gemini")]
+pub(crate) struct GeminiAdapter;
+
+#[cfg(feature = "engine-gemini")]
+impl EngineAdapter for GeminiAdapter {
+    fn engine_type(&self) -> EngineType {
+        EngineType::Gemini
+    }
+
+    fn detect<'a>(&'a self, bin: Option<&'a str>) -> EngineFuture<'a, EngineStatus> {
+        Box::pin(super::super::status::detect_gemini_status(bin))
+    }
+
+    fn models<'a>(
+        &'a self,
+        bin: Option<&'a str>,
+    ) -> EngineFuture<'a, Option<Vec<super::super::ModelInfo>>> {
+        Box::pin(async move { Some(super::super::status::detect_gemini_status(bin).await.models) })
+    }
+
+    fn send<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        params: SendMessageParams,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let workspace_path = ctx.workspace_path().await?;
+            let session = ctx
+                .state
+                .engine_manager
+                .get_or_create_gemini_session(ctx.workspace_id, &workspace_path)
+                .await;
+            let resolved_session_id = if params.continue_session {
+                if params.session_id.is_some() {
+                    params.session_id
+                } else {
+                    session.get_session_id().await
+                }
+            } else {
+                Some(
+                    params
+                        .session_id
+                        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                )
+            };
+
+            let sanitized_model = params
+                .model
+                .as_ref()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .and_then(|value| {
+                    if is_likely_foreign_model_for_gemini(value) {
+                        None
+                    } else {
+                        Some(value.to_string())
+                    }
+                });
+
+            let params = SendMessageParams {
+                model: sanitized_model,
+                disable_thinking: false,
+                session_id: resolved_session_id,
+                fork_session_id: None,
+                agent: None,
+                variant: None,
+                collaboration_mode: None,
+                ..params
+            };
+
+            let turn_id = format!("gemini-sync-{}", uuid::Uuid::new_v4());
+            let response = timeout(SYNC_SEND_TIMEOUT, session.send_message(params, &turn_id))
+                .await
+                .map_err(|_| "Gemini response timed out".to_string())??;
+
+            Ok(json!({
+                "engine": "gemini",
+                "text": response
+            }))
+        })
+    }
+
+    fn interrupt<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        turn_id: Option<&'a str>,
+    ) -> EngineFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let manager = &ctx.state.engine_manager;
+            if let Some(session) = manager.get_gemini_session(ctx.workspace_id).await {
+                match turn_id {
+                    Some(turn_id) => session.interrupt_turn(turn_id).await?,
+                    None => session.interrupt().await?,
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn history<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        limit: Option<usize>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let workspace_path = ctx.workspace_path().await?;
+            let config = ctx
+                .state
+                .engine_manager
+                .get_engine_config(EngineType::Gemini)
+                .await;
+            let sessions = super::super::gemini_history::list_gemini_sessions(
+                &workspace_path,
+                limit,
+                config.as_ref().and_then(|item| item.home_dir.as_deref()),
+            )
+            .await?;
+            serde_json::to_value(sessions).map_err(|error| error.to_string())
+        })
+    }
+
+    fn start_turn<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        params: SendMessageParams,
+        thread_id: Option<String>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let SendMessageParams {
+                text,
+                model,
+                effort,
+                access_mode,
+                images,
+                continue_session,
+                session_id,
+                custom_spec_root: normalized_custom_spec_root,
+                cwd: turn_cwd,
+                timeout_secs,
+                ..
+            } = params;
+            let (app, state) = (ctx.app, ctx.state);
+            let manager = &state.engine_manager;
+            let workspace_id = ctx.workspace_id.to_string();
+
+            let workspace_path = {
+                let workspaces = state.workspaces.lock().await;
+                workspaces
+                    .get(&workspace_id)
+                    .map(|w| std::path::PathBuf::from(&w.path))
+                    .ok_or_else(|| "Workspace not found".to_string())?
+            };
+
+            let session = manager
+                .get_or_create_gemini_session(&workspace_id, &workspace_path)
+                .await;
+
+            let resolved_session_id = if continue_session {
+                if session_id.is_some() {
+                    session_id
+                } else {
+                    session.get_session_id().await
+                }
+            } else {
+                Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
+            };
+            let continued_session_id = resolved_session_id.clone().filter(|_| continue_session);
+            let turn_cwd = crate::engine::turn_cwds::resolve_turn_cwd(
+                &workspace_id,
+                continued_session_id.as_deref(),
+                turn_cwd,
+            )?;
+
+            let sanitized_model = model
+                .as_ref()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .and_then(|value| {
+                    if is_likely_foreign_model_for_gemini(value) {
+                        None
+                    } else {
+                        Some(value.to_string())
+                    }
+                });
+            if model.is_some() && sanitized_model.is_none() {
+                log::warn!(
+                    "[engine_send_message] dropped invalid gemini model={:?}, fallback to default",
+                    model
+                );
+            }
+
+            let model_for_cost = sanitized_model.clone();
+            let params = SendMessageParams {
+                text,
+                model: sanitized_model,
+                effort,
+                disable_thinking: false,
+                access_mode,
+                images,
+                continue_session,
+                session_id: resolved_session_id,
+                fork_session_id: None,
+                agent: None,
+                variant: None,
+                collaboration_mode: None,
+                custom_spec_root: normalized_custom_spec_root.clone(),
+                cwd: turn_cwd.clone(),
+                timeout_secs,
+            };
+
+            let turn_id = format!("gemini-turn-{}", uuid::Uuid::new_v4());
+            let thread_id = thread_id.unwrap_or_else(|| turn_id.clone());
+            crate::engine::turn_cwds::record_turn_cwd(
+                &workspace_id,
+                EngineType::Gemini,
+                &turn_id,
+                continued_session_id.as_deref(),
+                turn_cwd.as_deref(),
+            );
+            let item_id = format!("gemini-item-{}", uuid::Uuid::new_v4());
+
+            let mut receiver = session.subscribe();
+            let app_clone = app.clone();
+            let mut current_thread_id = thread_id.clone();
+            let item_id_clone = item_id.clone();
+            let turn_id_for_forwarder = turn_id.clone();
+            let mut accumulated_agent_text = String::new();
+            let workspace_id_for_forwarder = workspace_id.clone();
+            let forwarder =
+                manager
+                    .forwarders
+                    .register(EngineType::Gemini, &workspace_id, &turn_id);
+            tokio::spawn(async move {
+                let mut render_state = GeminiRenderRoutingState::default();
+                let mut post_completion_grace_deadline: Option<tokio::time::Instant> = None;
+                loop {
+                    let turn_event = match forwarder
+                        .recv(&mut receiver, post_completion_grace_deadline)
+                        .await
+                    {
+                        ForwarderRecv::Event(event) => event,
+                        ForwarderRecv::Closed | ForwarderRecv::GraceElapsed => break,
+                        ForwarderRecv::Lagged(skipped) => {
+                            log::warn!(
+                                "Gemini event forwarder lagged; skipped {} events for turn {}",
+                                skipped,
+                                turn_id_for_forwarder
+                            );
+                            continue;
+                        }
+                        ForwarderRecv::Reaped(reason) => {
+                            if post_completion_grace_deadline.is_none() {
+                                let _ = app_clone.emit(
+                                    "app-server-event",
+                                    forwarder.reaped_event(
+                                        &workspace_id_for_forwarder,
+                                        &current_thread_id,
+                                        reason,
+                                    ),
+                                );
+                            }
+                            break;
+                        }
+                    };
+                    if turn_event.turn_id != turn_id_for_forwarder {
+                        continue;
+                    }
+                    forwarder.record_event();
+
+                    let event = turn_event.event;
+                    let is_terminal = event.is_terminal();
+                    let render_lane = match &event {
+                        EngineEvent::TextDelta { .. } => GeminiRenderLane::Text,
+                        EngineEvent::ReasoningDelta { .. } => GeminiRenderLane::Reasoning,
+                        EngineEvent::ToolStarted { .. }
+                        | EngineEvent::ToolCompleted { .. }
+                        | EngineEvent::ToolInputUpdated { .. }
+                        | EngineEvent::ToolOutputDelta { .. } => GeminiRenderLane::Tool,
+                        _ => GeminiRenderLane::Other,
+                    };
+                    let routed_item_id =
+                        next_gemini_routed_item_id(&mut render_state, render_lane, &item_id_clone);
+
+                    if let EngineEvent::TextDelta { text, .. } = &event {
+                        render_state.saw_text_delta = true;
+                        accumulated_agent_text.push_str(text);
+                    }
+
+                    if let EngineEvent::TurnCompleted { result, .. } = &event {
+                        let fallback_text =
+                            extract_turn_result_text(result.as_ref()).unwrap_or_default();
+                        let completed_text = if should_prefer_turn_result_text(result.as_ref()) {
+                            fallback_text
+                        } else if accumulated_agent_text.trim().is_empty() {
+                            fallback_text
+                        } else {
+                            accumulated_agent_text.clone()
+                        };
+                        // Preserve realtime interleaving for Gemini: when text deltas
+                        // already streamed, don't collapse them back into a single
+                        // synthetic completed assistant message.
+                        if !completed_text.trim().is_empty() && !render_state.saw_text_delta {
+                            let synthetic = AppServerEvent {
+                                workspace_id: event.workspace_id().to_string(),
+                                message: json!({
+                                    "method": "item/completed",
+                                    "params": {
+                                        "threadId": &current_thread_id,
+                                        "item": {
+                                            "id": &routed_item_id,
+                                            "type": "agentMessage",
+                                            "text": completed_text,
+                                            "status": "completed",
+                                        }
+                                    }
+                                }),
+                            };
+                            let _ = app_clone.emit("app-server-event", synthetic);
+                        }
+                    }
+
+                    if let Some(mut payload) = engine_event_to_app_server_event_with_turn_context(
+                        &event,
+                        &current_thread_id,
+                        &routed_item_id,
+                        Some(&turn_id_for_forwarder),
+                    ) {
+                        crate::session_cost::annotate_app_server_event(
+                            &mut payload,
+                            model_for_cost.as_deref(),
+                        );
+                        crate::file_attribution::observe_app_server_event(&app_clone, &payload);
+                        crate::file_mentions::observe_app_server_event(&app_clone, &payload);
+                        crate::session_context_pack::observe_app_server_event(&app_clone, &payload);
+                        crate::engine::turn_cwds::observe_app_server_event(&payload);
+                        crate::outbound_notifications::observe_app_server_event(
+                            &app_clone, &payload,
+                        );
+                        crate::user_input_policy::observe_app_server_event(&app_clone, &payload);
+                        crate::sandbox_report::observe_app_server_event(&app_clone, &payload);
+                        crate::session_stats::observe_app_server_event(&app_clone, &payload);
+                        let _ = app_clone.emit("app-server-event", payload);
+                    }
+
+                    if let EngineEvent::SessionStarted {
+                        session_id, engine, ..
+                    } = &event
+                    {
+                        if !session_id.is_empty() && session_id != "pending" {
+                            if matches!(engine, EngineType::Gemini) {
+                                current_thread_id = format!("gemini:{}", session_id);
+                            }
+                        }
+                    }
+
+                    if is_terminal {
+                        if matches!(event, EngineEvent::TurnCompleted { .. }) {
+                            post_completion_grace_deadline = Some(
+                                tokio::time::Instant::now()
+                                    + std::time::Duration::from_millis(
+                                        GEMINI_POST_COMPLETION_REASONING_GRACE_MS,
+                                    ),
+                            );
+                            continue;
+                        }
+                        break;
+                    }
+                }
+            });
+
+            let session_clone = session.clone();
+            let turn_id_clone = turn_id.clone();
+            let app_for_sender = app.clone();
+            arm_engine_turn_timeout(
+                &app,
+                EngineType::Gemini,
+                &workspace_id,
+                &turn_id,
+                timeout_secs,
+            );
+            tokio::spawn(async move {
+                let send_result = session_clone.send_message(params, &turn_id_clone).await;
+                let state = app_for_sender.state::<AppState>();
+                state.engine_manager.disarm_turn_timeout(&turn_id_clone);
+                state
+                    .engine_manager
+                    .forwarders
+                    .mark_process_exited(&turn_id_clone);
+                if let Err(e) = send_result {
+                    log::error!("Gemini send_message failed: {}", e);
+                }
+            });
+
+            Ok(json!({
+                "engine": "gemini",
+                "result": {
+                    "turn": {
+                        "id": turn_id,
+                        "status": "started",
+                        "cwd": turn_cwd,
+                    },
+                },
+                "turn": {
+                    "id": turn_id,
+                    "status": "started",
+                    "cwd": turn_cwd,
+                }
+            }))
+        })
+    }
+}
+
+#[cfg(feature = "engine-opencode")]
+pub(crate) struct OpenCodeAdapter;
+
+#[cfg(feature = "engine-opencode")]
+impl EngineAdapter for OpenCodeAdapter {
+    fn engine_type(&self) -> EngineType {
+        EngineType::OpenCode
+    }
+
+    fn detect<'a>(&'a self, bin: Option<&'a str>) -> EngineFuture<'a, EngineStatus> {
+        Box::pin(super::super::status::detect_opencode_status(bin))
+    }
+
+    fn models<'a>(
+        &'a self,
+        bin: Option<&'a str>,
+    ) -> EngineFuture<'a, Option<Vec<super::super::ModelInfo>>> {
+        Box::pin(async move {
+            Some(
+                super::super::status::load_opencode_models(bin)
+                    .await
+                    .unwrap_or_default(),
+            )
+        })
+    }
+
+    fn send<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        params: SendMessageParams,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let workspace_path = ctx.workspace_path().await?;
+            let session = ctx
+                .state
+                .engine_manager
+                .get_or_create_opencode_session(ctx.workspace_id, &workspace_path)
+                .await;
+            let resolved_session_id = if params.continue_session {
+                if params.session_id.is_some() {
+                    params.session_id
+                } else {
+                    session.get_session_id().await
+                }
+            } else {
+                Some(
+                    params
+                        .session_id
+                        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                )
+            };
+
+            let sanitized_model = params
+                .model
+                .as_ref()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .and_then(|value| {
+                    if is_likely_legacy_claude_model_id(value) {
+                        None
+                    } else {
+                        Some(value.to_string())
+                    }
+                });
+            let model_for_send =
+                sanitized_model.or_else(|| Some("openai/gpt-5.3-codex".to_string()));
+
+            let params = SendMessageParams {
+                model: model_for_send,
+                disable_thinking: false,
+                session_id: resolved_session_id,
+                fork_session_id: None,
+                collaboration_mode: None,
+                ..params
+            };
+
+            let turn_id = format!("opencode-sync-{}", uuid::Uuid::new_v4());
+            let response = timeout(SYNC_SEND_TIMEOUT, session.send_message(params, &turn_id))
+                .await
+                .map_err(|_| "OpenCode response timed out".to_string())??;
+
+            Ok(json!({
+                "engine": "opencode",
+                "text": response
+            }))
+        })
+    }
+
+    fn interrupt<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        turn_id: Option<&'a str>,
+    ) -> EngineFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let manager = &ctx.state.engine_manager;
+            if let Some(session) = manager.get_opencode_session(ctx.workspace_id).await {
+                match turn_id {
+                    Some(turn_id) => session.interrupt_turn(turn_id).await?,
+                    None => session.interrupt().await?,
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn history<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        limit: Option<usize>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let mut sessions = opencode_session_list_core(
+                &ctx.state.workspaces,
+                &ctx.state.engine_manager,
+                ctx.workspace_id,
+            )
+            .await?;
+            if let Some(limit) = limit {
+                sessions.truncate(limit);
+            }
+            serde_json::to_value(sessions).map_err(|error| error.to_string())
+        })
+    }
+
+    fn start_turn<'a>(
+        &'a self,
+        ctx: EngineContext<'a>,
+        params: SendMessageParams,
+        thread_id: Option<String>,
+    ) -> EngineFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let SendMessageParams {
+                text,
+                model,
+                effort,
+                access_mode,
+                images,
+                continue_session,
+                session_id,
+                agent,
+                variant,
+                custom_spec_root: normalized_custom_spec_root,
+                cwd: turn_cwd,
+                timeout_secs,
+                ..
+            } = params;
+            let (app, state) = (ctx.app, ctx.state);
+            let manager = &state.engine_manager;
+            let workspace_id = ctx.workspace_id.to_string();
+
+            let workspace_path = {
+                let workspaces = state.workspaces.lock().await;
+                workspaces
+                    .get(&workspace_id)
+                    .map(|w| std::path::PathBuf::from(&w.path))
+                    .ok_or_else(|| "Workspace not found".to_string())?
+            };
+
+            let session = manager
+                .get_or_create_opencode_session(&workspace_id, &workspace_path)
+                .await;
+
+            let resolved_session_id = if continue_session {
+                if session_id.is_some() {
+                    session_id
+                } else {
+                    session.get_session_id().await
+                }
+            } else {
+                Some(session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
+            };
+            let continued_session_id = resolved_session_id.clone().filter(|_| continue_session);
+            let turn_cwd = crate::engine::turn_cwds::resolve_turn_cwd(
+                &workspace_id,
+                continued_session_id.as_deref(),
+                turn_cwd,
+            )?;
+
+            let sanitized_model = model
+                .as_ref()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .and_then(|value| {
+                    if is_likely_legacy_claude_model_id(value) {
+                        None
+                    } else {
+                        Some(value.to_string())
+                    }
+                });
+            if model.is_some() && sanitized_model.is_none() {
+                log::warn!(
+                    "[engine_send_message] dropped invalid opencode model={:?}, fallback to default",
+                    model
+                );
+            }
+            let model_for_send =
+                sanitized_model.or_else(|| Some("openai/gpt-5.3-codex".to_string()));
+            let model_for_cost = model_for_send.clone();
+
+            let params = SendMessageParams {
+                text,
+                model: model_for_send,
+                effort,
+                disable_thinking: false,
+                access_mode,
+                images,
+                continue_session,
+                session_id: resolved_session_id,
+                fork_session_id: None,
+                agent,
+                variant,
+                collaboration_mode: None,
+                custom_spec_root: normalized_custom_spec_root.clone(),
+                cwd: turn_cwd.clone(),
+                timeout_secs,
+            };
+
+            let turn_id = format!("opencode-turn-{}", uuid::Uuid::new_v4());
+            let thread_id = thread_id.unwrap_or_else(|| turn_id.clone());
+            crate::engine::turn_cwds::record_turn_cwd(
+                &workspace_id,
+                EngineType::OpenCode,
+                &turn_id,
+                continued_session_id.as_deref(),
+                turn_cwd.as_deref(),
+            );
+            let item_id = format!("opencode-item-{}", uuid::Uuid::new_v4());
+
+            let mut receiver = session.subscribe();
+            let app_clone = app.clone();
+            let mut current_thread_id = thread_id.clone();
+            let item_id_clone = item_id.clone();
+            let turn_id_for_forwarder = turn_id.clone();
+            let workspace_id_for_forwarder = workspace_id.clone();
+            let forwarder =
+                manager
+                    .forwarders
+                    .register(EngineType::OpenCode, &workspace_id, &turn_id);
+            // Spawn event forwarder (same pattern as Claude forwarder above).
+            tokio::spawn(async move {
+                loop {
+                    let turn_event = match forwarder.recv(&mut receiver, None).await {
+                        ForwarderRecv::Event(event) => event,
+                        ForwarderRecv::Closed | ForwarderRecv::GraceElapsed => break,
+                        ForwarderRecv::Lagged(skipped) => {
+                            log::warn!(
+                                "OpenCode event forwarder lagged; skipped {} events for turn {}",
+                                skipped,
+                                turn_id_for_forwarder
+                            );
+                            continue;
+                        }
+                        ForwarderRecv::Reaped(reason) => {
+                            let _ = app_clone.emit(
+                                "app-server-event",
+                                forwarder.reaped_event(
+                                    &workspace_id_for_forwarder,
+                                    &current_thread_id,
+                                    reason,
+                                ),
+                            );
+                            break;
+                        }
+                    };
+                    if turn_event.turn_id != turn_id_for_forwarder {
+                        continue;
+                    }
+                    forwarder.record_event();
+
+                    let event = turn_event.event;
+                    let is_terminal = event.is_terminal();
+
+                    if let Some(mut payload) = engine_event_to_app_server_event_with_turn_context(
+                        &event,
+                        &current_thread_id,
+                        &item_id_clone,
+                        Some(&turn_id_for_forwarder),
+                    ) {
+                        crate::session_cost::annotate_app_server_event(
+                            &mut payload,
+                            model_for_cost.as_deref(),
+                        );
+                        crate::file_attribution::observe_app_server_event(&app_clone, &payload);
+                        crate::file_mentions::observe_app_server_event(&app_clone, &payload);
+                        crate::session_context_pack::observe_app_server_event(&app_clone, &payload);
+                        crate::engine::turn_cwds::observe_app_server_event(&payload);
+                        crate::outbound_notifications::observe_app_server_event(
+                            &app_clone, &payload,
+                        );
+                        crate::user_input_policy::observe_app_server_event(&app_clone, &payload);
+                        crate::sandbox_report::observe_app_server_event(&app_clone, &payload);
+                        crate::session_stats::observe_app_server_event(&app_clone, &payload);
+                        let _ = app_clone.emit("app-server-event", payload);
+                    }
+
+                    if let EngineEvent::SessionStarted {
+                        session_id, engine, ..
+                    } = &event
+                    {
+                        if !session_id.is_empty() && session_id != "pending" {
+                            if matches!(engine, EngineType::OpenCode) {
+                                current_thread_id = format!("opencode:{}", session_id);
+                            }
+                        }
+                    }
+
+                    if is_terminal {
+                        break;
+                    }
+                }
+            });
+
+            let session_clone = session.clone();
+            let turn_id_clone = turn_id.clone();
+            let app_for_sender = app.clone();
+            arm_engine_turn_timeout(
+                &app,
+                EngineType::OpenCode,
+                &workspace_id,
+                &turn_id,
+                timeout_secs,
+            );
+            tokio::spawn(async move {
+                let send_result = session_clone.send_message(params, &turn_id_clone).await;
+                let state = app_for_sender.state::<AppState>();
+                state.engine_manager.disarm_turn_timeout(&turn_id_clone);
+                state
+                    .engine_manager
+                    .forwarders
+                    .mark_process_exited(&turn_id_clone);
+                if let Err(e) = send_result {
+                    log::error!("OpenCode send_message failed: {}", e);
+                    session_clone.emit_error(&turn_id_clone, e);
+                }
+            });
+
+            Ok(json!({
+                "engine": "opencode",
+                "result": {
+                    "turn": {
+                        "id": turn_id,
+                        "status": "started",
+                        "cwd": turn_cwd,
+                    },
+                },
+                "turn": {
+                    "id": turn_id,
+                    "status": "started",
+                    "cwd": turn_cwd,
+                }
+            }))
+        })
+    }
+}
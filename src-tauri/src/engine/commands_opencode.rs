@@ -220,6 +220,121 @@ pub(crate) async fn opencode_delete_session_core(
     }))
 }
 
+#[tauri::command]
+pub async fn opencode_rename_session(
+    workspace_id: String,
+    session_id: String,
+    title: String,
+    state: State<'_, AppState>,
+) -> Result<Value, String> {
+    ensure_opencode_enabled(&state).await?;
+    opencode_rename_session_core(
+        &state.workspaces,
+        &state.engine_manager,
+        &workspace_id,
+        &session_id,
+        &title,
+    )
+    .await
+}
+
+pub(crate) async fn opencode_rename_session_core(
+    workspaces: &tokio::sync::Mutex<HashMap<String, WorkspaceEntry>>,
+    manager: &crate::engine::manager::EngineManager,
+    workspace_id: &str,
+    session_id: &str,
+    title: &str,
+) -> Result<Value, String> {
+    let normalized_title = crate::shared::thread_titles_core::normalize_title(title);
+    if normalized_title.is_empty() {
+        return Err("Title is required".to_string());
+    }
+    let workspace_path = {
+        let workspaces = workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .map(|w| PathBuf::from(&w.path))
+            .ok_or_else(|| "[WORKSPACE_NOT_CONNECTED] Workspace not found".to_string())?
+    };
+    let config = manager.get_engine_config(EngineType::OpenCode).await;
+    rename_opencode_session_files(
+        &workspace_path,
+        session_id,
+        &normalized_title,
+        config.as_ref(),
+    )?;
+    Ok(json!({
+        "sessionId": session_id.trim(),
+        "title": normalized_title,
+    }))
+}
+
+/// Summarize the opening messages of an OpenCode session into a title and
+/// save it, like `generate_thread_title` does for Codex threads.
+#[tauri::command]
+pub async fn opencode_generate_session_title(
+    workspace_id: String,
+    session_id: String,
+    preferred_language: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Value, String> {
+    ensure_opencode_enabled(&state).await?;
+    let session_id = normalize_opencode_session_id(&session_id)?.to_string();
+    let workspace_path = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .map(|w| PathBuf::from(&w.path))
+            .ok_or_else(|| "[WORKSPACE_NOT_CONNECTED] Workspace not found".to_string())?
+    };
+    let config = state
+        .engine_manager
+        .get_engine_config(EngineType::OpenCode)
+        .await;
+
+    let export =
+        fetch_opencode_session_export(config.as_ref(), &workspace_path, &session_id).await?;
+    let transcript = build_opencode_title_transcript(&export, 4)
+        .ok_or_else(|| "Session has no messages to generate a title from".to_string())?;
+
+    let language_instruction = match preferred_language
+        .unwrap_or_else(|| "en".to_string())
+        .trim()
+        .to_lowercase()
+        .as_str()
+    {
+        "zh" | "zh-cn" | "zh-hans" | "chinese" => "Output language: Simplified Chinese.",
+        _ => "Output language: English.",
+    };
+    let prompt = format!(
+        "Generate a concise title for a coding chat thread from its opening messages. \
+Return only the title text, no quotes, no punctuation-only output, no markdown. \
+Keep it between 3 and 8 words.\n\
+{language_instruction}\n\nOpening messages:\n{transcript}"
+    );
+
+    let generated = run_opencode_title_prompt(config.as_ref(), &workspace_path, &prompt).await?;
+    let normalized = generated
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("")
+        .trim_matches('"')
+        .to_string();
+    if normalized.is_empty() {
+        return Err("No session title was generated".to_string());
+    }
+
+    opencode_rename_session_core(
+        &state.workspaces,
+        &state.engine_manager,
+        &workspace_id,
+        &session_id,
+        &normalized,
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn opencode_stats(
     workspace_id: String,
@@ -433,10 +548,14 @@ pub async fn opencode_provider_health(
     load_opencode_provider_health(&workspace_id, provider, &state).await
 }
 
+/// Returns the cached provider catalog right away and refreshes it in the
+/// background when it is missing or stale. `refresh` waits for a fresh scan.
 #[tauri::command]
 pub async fn opencode_provider_catalog(
     workspace_id: String,
+    refresh: Option<bool>,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Vec<OpenCodeProviderOption>, String> {
     ensure_opencode_enabled(&state).await?;
     let workspace_path = {
@@ -448,55 +567,21 @@ pub async fn opencode_provider_catalog(
     };
     let manager = &state.engine_manager;
     let config = manager.get_engine_config(EngineType::OpenCode).await;
-    let mut providers =
-        fetch_opencode_provider_catalog_from_auth_picker(&workspace_path, config.as_ref()).await;
-    if providers.is_empty() {
-        providers = fetch_opencode_provider_catalog_preview(&workspace_path, config.as_ref()).await;
-    }
-    let dynamic_provider_ids =
-        fetch_opencode_provider_ids_from_models(&workspace_path, config.as_ref()).await;
-    for provider_id in dynamic_provider_ids {
-        let normalized_id = slugify_provider_label(&provider_id);
-        if normalized_id.is_empty() {
-            continue;
-        }
-        if let Some(existing) = providers.iter_mut().find(|item| item.id == normalized_id) {
-            if existing.label.is_empty() {
-                existing.label = provider_label_from_id(&provider_id);
-            }
-            continue;
-        }
-        providers.push(OpenCodeProviderOption {
-            id: normalized_id,
-            label: provider_label_from_id(&provider_id),
-            description: None,
-            category: "other".to_string(),
-            recommended: false,
-        });
+    if refresh.unwrap_or(false) {
+        return Ok(refresh_opencode_provider_catalog(&workspace_path, config.as_ref()).await);
     }
-    let fallback = fallback_opencode_provider_catalog();
-    for item in fallback {
-        if let Some(existing) = providers.iter_mut().find(|p| p.id == item.id) {
-            if existing.category != "popular" && item.category == "popular" {
-                existing.category = "popular".to_string();
-            }
-            existing.recommended = existing.recommended || item.recommended;
-            if existing.description.is_none() && item.description.is_some() {
-                existing.description = item.description;
-            }
-        } else {
-            providers.push(item);
-        }
+    let cached = read_provider_catalog_cache();
+    let stale = cached
+        .as_ref()
+        .is_none_or(|cache| cache.is_stale(provider_catalog_now_ms()));
+    let providers = merge_opencode_provider_catalog(
+        cached.map(|cache| cache.providers).unwrap_or_default(),
+        &[],
+        &[],
+    );
+    if stale {
+        spawn_provider_catalog_refresh(app, workspace_path, config, providers.clone());
     }
-    providers.sort_by(|a, b| {
-        let score_a = if a.category == "popular" { 0 } else { 1 };
-        let score_b = if b.category == "popular" { 0 } else { 1 };
-        score_a
-            .cmp(&score_b)
-            .then_with(|| b.recommended.cmp(&a.recommended))
-            .then_with(|| a.label.cmp(&b.label))
-    });
-    providers.dedup_by(|a, b| a.id == b.id);
     Ok(providers)
 }
 
@@ -625,11 +710,11 @@ async fn load_opencode_provider_health(
     })
 }
 
-/// Remove MCP toggle state for a workspace to free memory.
+/// Forget the MCP toggles of a removed workspace.
 pub(crate) fn clear_mcp_toggle_state(workspace_id: &str) {
-    if let Some(cache) = OPENCODE_MCP_TOGGLE_STATE.get() {
-        if let Ok(mut guard) = cache.lock() {
-            guard.remove(workspace_id);
+    if let Ok(mut guard) = mcp_toggle_states().lock() {
+        if guard.remove(workspace_id).is_some() {
+            persist_mcp_toggle_states(&guard);
         }
     }
 }
@@ -643,16 +728,10 @@ pub async fn opencode_mcp_toggle(
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
     ensure_opencode_enabled(&state).await?;
-    let cache = OPENCODE_MCP_TOGGLE_STATE.get_or_init(|| Mutex::new(HashMap::new()));
-    let mut guard = cache
+    let mut guard = mcp_toggle_states()
         .lock()
         .map_err(|_| "opencode mcp toggle lock poisoned".to_string())?;
-    let entry = guard
-        .entry(workspace_id.clone())
-        .or_insert_with(|| OpenCodeMcpToggleState {
-            global_enabled: true,
-            server_enabled: HashMap::new(),
-        });
+    let entry = guard.entry(workspace_id.clone()).or_default();
     if let Some(global) = global_enabled {
         entry.global_enabled = global;
     }
@@ -664,12 +743,14 @@ pub async fn opencode_mcp_toggle(
                 .insert(normalized, enabled.unwrap_or(true));
         }
     }
-    Ok(json!({
+    let response = json!({
         "workspaceId": workspace_id,
         "mcpEnabled": entry.global_enabled,
         "serverStates": entry.server_enabled,
         "managedToggles": true,
-    }))
+    });
+    persist_mcp_toggle_states(&guard);
+    Ok(response)
 }
 
 #[tauri::command]
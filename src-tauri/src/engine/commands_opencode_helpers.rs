@@ -75,6 +75,134 @@ pub(super) async fn fetch_opencode_provider_ids_from_models(
     parse_opencode_models_provider_ids(&stdout)
 }
 
+/// Upper bound for `opencode debug config`, which only resolves config files.
+const OPENCODE_DEBUG_CONFIG_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(super) async fn fetch_opencode_provider_catalog_from_debug_config(
+    workspace_path: &PathBuf,
+    config: Option<&EngineConfig>,
+) -> Vec<OpenCodeProviderOption> {
+    let mut cmd = match build_opencode_command(config) {
+        Ok(value) => value,
+        Err(_) => return Vec::new(),
+    };
+    cmd.current_dir(workspace_path);
+    cmd.arg("debug");
+    cmd.arg("config");
+    cmd.stdin(Stdio::null());
+    cmd.kill_on_drop(true);
+    let output = match tokio::time::timeout(OPENCODE_DEBUG_CONFIG_TIMEOUT, cmd.output()).await {
+        Ok(Ok(value)) => value,
+        _ => return Vec::new(),
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    parse_opencode_debug_config_providers(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Providers from `providers.json` in the configured or `OPENCODE_HOME`
+/// directory; the first readable file wins.
+pub(super) fn read_opencode_providers_json(
+    config: Option<&EngineConfig>,
+) -> Vec<OpenCodeProviderOption> {
+    let mut roots = Vec::new();
+    if let Some(home) = config.and_then(|item| item.home_dir.as_ref()) {
+        roots.push(PathBuf::from(home));
+    }
+    if let Some(home) = std::env::var_os("OPENCODE_HOME") {
+        roots.push(PathBuf::from(home));
+    }
+    roots
+        .iter()
+        .filter_map(|root| fs::read_to_string(root.join("providers.json")).ok())
+        .map(|content| parse_opencode_providers_json(&content))
+        .find(|providers| !providers.is_empty())
+        .unwrap_or_default()
+}
+
+const OPENCODE_TITLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(super) async fn fetch_opencode_session_export(
+    config: Option<&EngineConfig>,
+    workspace_path: &Path,
+    session_id: &str,
+) -> Result<Value, String> {
+    let mut cmd = build_opencode_command(config)?;
+    cmd.current_dir(workspace_path);
+    cmd.arg("export");
+    cmd.arg(session_id);
+    cmd.stdin(Stdio::null());
+    cmd.kill_on_drop(true);
+    let output = tokio::time::timeout(OPENCODE_TITLE_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| "Timeout waiting for opencode export".to_string())?
+        .map_err(|e| format!("Failed to execute opencode export: {}", e))?;
+    if !output.status.success() {
+        let stderr = strip_ansi_codes(&String::from_utf8_lossy(&output.stderr));
+        return Err(format!("opencode export failed: {}", stderr.trim()));
+    }
+    let stdout = strip_ansi_codes(&String::from_utf8_lossy(&output.stdout));
+    let json_text = extract_json_object_from_text(&stdout)
+        .ok_or_else(|| "opencode export did not return JSON payload".to_string())?;
+    serde_json::from_str(&json_text).map_err(|e| format!("Invalid opencode export payload: {}", e))
+}
+
+/// Run `prompt` in a throwaway OpenCode session and return its reply text.
+/// The helper session is deleted afterwards so it never shows up in the list.
+pub(super) async fn run_opencode_title_prompt(
+    config: Option<&EngineConfig>,
+    workspace_path: &Path,
+    prompt: &str,
+) -> Result<String, String> {
+    let mut cmd = build_opencode_command(config)?;
+    cmd.current_dir(workspace_path);
+    cmd.arg("run");
+    cmd.arg("--format");
+    cmd.arg("json");
+    cmd.arg(prompt);
+    cmd.stdin(Stdio::null());
+    cmd.kill_on_drop(true);
+    let output = tokio::time::timeout(OPENCODE_TITLE_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| "Timeout waiting for session title generation".to_string())?
+        .map_err(|e| format!("Failed to execute opencode run: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut helper_session_id = None;
+    let mut generated = String::new();
+    for line in stdout.lines() {
+        let Ok(event) = serde_json::from_str::<Value>(line.trim()) else {
+            continue;
+        };
+        if helper_session_id.is_none() {
+            helper_session_id = crate::engine::opencode::extract_session_id(&event);
+        }
+        if let Some(EngineEvent::TextDelta { text, .. }) =
+            crate::engine::opencode::parse_opencode_event("", &event)
+        {
+            generated.push_str(&text);
+        }
+    }
+
+    if let Some(helper_session_id) = helper_session_id {
+        if let Ok(mut cleanup) = build_opencode_command(config) {
+            cleanup.current_dir(workspace_path);
+            cleanup.arg("session");
+            cleanup.arg("delete");
+            cleanup.arg(&helper_session_id);
+            cleanup.stdin(Stdio::null());
+            let _ = cleanup.output().await;
+        }
+    }
+
+    if !output.status.success() {
+        let stderr = strip_ansi_codes(&String::from_utf8_lossy(&output.stderr));
+        return Err(format!("opencode run failed: {}", stderr.trim()));
+    }
+    Ok(generated)
+}
+
 pub(super) fn build_provider_prefill_query(provider_id: &str) -> Option<String> {
     let normalized = slugify_provider_label(provider_id);
     if normalized.is_empty() {
@@ -110,31 +238,250 @@ pub(super) fn open_terminal_with_command(command: &str) -> Result<(), String> {
     Ok(())
 }
 
+const OPENCODE_MCP_TOGGLES_FILENAME: &str = "opencode-mcp-toggles.json";
+
+fn mcp_toggles_path() -> Result<PathBuf, String> {
+    Ok(crate::app_paths::app_home_dir()?.join(OPENCODE_MCP_TOGGLES_FILENAME))
+}
+
+/// Per-workspace toggles saved at `path`. Missing or unreadable data yields no
+/// overrides, i.e. every workspace starts globally enabled.
+pub(super) fn load_mcp_toggle_states(path: &Path) -> HashMap<String, OpenCodeMcpToggleState> {
+    match crate::storage::read_json_file::<HashMap<String, OpenCodeMcpToggleState>>(path) {
+        Ok(states) => states.unwrap_or_default(),
+        Err(error) => {
+            log::warn!("[opencode] ignoring unreadable MCP toggle state: {error}");
+            HashMap::new()
+        }
+    }
+}
+
+pub(super) fn save_mcp_toggle_states(
+    path: &Path,
+    states: &HashMap<String, OpenCodeMcpToggleState>,
+) -> Result<(), String> {
+    crate::storage::write_json_file(path, states)
+}
+
+/// The toggle map, loaded from disk on first use.
+pub(super) fn mcp_toggle_states() -> &'static Mutex<HashMap<String, OpenCodeMcpToggleState>> {
+    OPENCODE_MCP_TOGGLE_STATE.get_or_init(|| {
+        let states = mcp_toggles_path()
+            .map(|path| load_mcp_toggle_states(&path))
+            .unwrap_or_default();
+        Mutex::new(states)
+    })
+}
+
+/// Writes the current toggle map through to disk; failures keep the
+/// in-memory state and are only logged.
+pub(super) fn persist_mcp_toggle_states(states: &HashMap<String, OpenCodeMcpToggleState>) {
+    let result = mcp_toggles_path().and_then(|path| save_mcp_toggle_states(&path, states));
+    if let Err(error) = result {
+        log::warn!("[opencode] failed to persist MCP toggle state: {error}");
+    }
+}
+
+/// Applies a workspace's toggles to the servers reported by `opencode mcp`.
+pub(super) fn merge_mcp_toggle_state(
+    toggles: &OpenCodeMcpToggleState,
+    servers: Vec<OpenCodeMcpServerState>,
+) -> Vec<OpenCodeMcpServerState> {
+    servers
+        .into_iter()
+        .map(|mut item| {
+            let override_enabled = toggles.server_enabled.get(&item.name).copied();
+            item.enabled = toggles.global_enabled && override_enabled.unwrap_or(item.enabled);
+            item
+        })
+        .collect()
+}
+
 pub(super) fn apply_mcp_toggle_state(
     workspace_id: &str,
     servers: Vec<OpenCodeMcpServerState>,
 ) -> (bool, Vec<OpenCodeMcpServerState>, HashMap<String, bool>) {
-    let cache = OPENCODE_MCP_TOGGLE_STATE.get_or_init(|| Mutex::new(HashMap::new()));
-    let mut guard = match cache.lock() {
+    let mut guard = match mcp_toggle_states().lock() {
         Ok(value) => value,
         Err(_) => return (true, servers, HashMap::new()),
     };
-    let entry = guard
-        .entry(workspace_id.to_string())
-        .or_insert_with(|| OpenCodeMcpToggleState {
-            global_enabled: true,
-            server_enabled: HashMap::new(),
+    let entry = guard.entry(workspace_id.to_string()).or_default();
+    let merged = merge_mcp_toggle_state(entry, servers);
+    (entry.global_enabled, merged, entry.server_enabled.clone())
+}
+
+const OPENCODE_PROVIDER_CATALOG_FILENAME: &str = "opencode-provider-catalog.json";
+const OPENCODE_PROVIDER_CATALOG_TTL_MS: u64 = 6 * 60 * 60 * 1000;
+const OPENCODE_PROVIDER_CATALOG_UPDATED_EVENT: &str = "opencode-provider-catalog-updated";
+static OPENCODE_PROVIDER_CATALOG_REFRESHING: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Last successfully discovered provider catalog, persisted so opening the
+/// provider settings never has to wait for the interactive CLI.
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct OpenCodeProviderCatalogCache {
+    pub(super) updated_at: u64,
+    pub(super) providers: Vec<OpenCodeProviderOption>,
+}
+
+impl OpenCodeProviderCatalogCache {
+    pub(super) fn is_stale(&self, now_ms: u64) -> bool {
+        now_ms.saturating_sub(self.updated_at) >= OPENCODE_PROVIDER_CATALOG_TTL_MS
+    }
+}
+
+pub(super) fn provider_catalog_now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn provider_catalog_cache_path() -> Result<PathBuf, String> {
+    Ok(crate::app_paths::app_home_dir()?.join(OPENCODE_PROVIDER_CATALOG_FILENAME))
+}
+
+pub(super) fn read_provider_catalog_cache() -> Option<OpenCodeProviderCatalogCache> {
+    let path = provider_catalog_cache_path().ok()?;
+    match crate::storage::read_json_file::<OpenCodeProviderCatalogCache>(&path) {
+        Ok(cache) => cache,
+        Err(error) => {
+            log::warn!("[opencode] ignoring unreadable provider catalog cache: {error}");
+            None
+        }
+    }
+}
+
+fn write_provider_catalog_cache(cache: &OpenCodeProviderCatalogCache) -> Result<(), String> {
+    let path = provider_catalog_cache_path()?;
+    let data = serde_json::to_string_pretty(cache).map_err(|error| error.to_string())?;
+    crate::storage::with_storage_lock(&path, || {
+        crate::storage::write_string_atomically(&path, &data)
+    })
+}
+
+/// Merges everything known about providers into one ordered list: the picker
+/// output, provider ids seen in `opencode models`, and the built-in fallback,
+/// so the result is never empty. When the picker suddenly returns far fewer
+/// entries than last time, the previous catalog is kept underneath it since a
+/// rendering hiccup is likelier than providers disappearing.
+pub(super) fn merge_opencode_provider_catalog(
+    discovered: Vec<OpenCodeProviderOption>,
+    dynamic_provider_ids: &[String],
+    previous: &[OpenCodeProviderOption],
+) -> Vec<OpenCodeProviderOption> {
+    let mut providers = discovered;
+    if providers.len() * 2 < previous.len() {
+        for item in previous {
+            if !providers.iter().any(|existing| existing.id == item.id) {
+                providers.push(item.clone());
+            }
+        }
+    }
+    for provider_id in dynamic_provider_ids {
+        let normalized_id = slugify_provider_label(provider_id);
+        if normalized_id.is_empty() {
+            continue;
+        }
+        if let Some(existing) = providers.iter_mut().find(|item| item.id == normalized_id) {
+            if existing.label.is_empty() {
+                existing.label = provider_label_from_id(provider_id);
+            }
+            continue;
+        }
+        providers.push(OpenCodeProviderOption {
+            id: normalized_id,
+            label: provider_label_from_id(provider_id),
+            description: None,
+            category: "other".to_string(),
+            recommended: false,
         });
-    let global_enabled = entry.global_enabled;
-    let server_enabled_map = entry.server_enabled.clone();
-    let merged = servers
-        .into_iter()
-        .map(|mut item| {
-            let override_enabled = server_enabled_map.get(&item.name).copied();
-            let effective_enabled = global_enabled && override_enabled.unwrap_or(item.enabled);
-            item.enabled = effective_enabled;
-            item
-        })
-        .collect::<Vec<_>>();
-    (global_enabled, merged, server_enabled_map)
+    }
+    for item in fallback_opencode_provider_catalog() {
+        if let Some(existing) = providers.iter_mut().find(|p| p.id == item.id) {
+            if existing.category != "popular" && item.category == "popular" {
+                existing.category = "popular".to_string();
+            }
+            existing.recommended = existing.recommended || item.recommended;
+            if existing.description.is_none() && item.description.is_some() {
+                existing.description = item.description;
+            }
+        } else {
+            providers.push(item);
+        }
+    }
+    providers.sort_by(|a, b| {
+        let score_a = if a.category == "popular" { 0 } else { 1 };
+        let score_b = if b.category == "popular" { 0 } else { 1 };
+        score_a
+            .cmp(&score_b)
+            .then_with(|| b.recommended.cmp(&a.recommended))
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    providers.dedup_by(|a, b| a.id == b.id);
+    providers
+}
+
+/// Queries OpenCode for providers and updates the cache. The resolved config,
+/// `providers.json` and `opencode models` are the primary sources; the slow
+/// auth-picker scrape only runs when they all come back empty. A run that
+/// discovers nothing leaves the existing cache untouched.
+pub(super) async fn refresh_opencode_provider_catalog(
+    workspace_path: &PathBuf,
+    config: Option<&EngineConfig>,
+) -> Vec<OpenCodeProviderOption> {
+    let (config_providers, dynamic_provider_ids) = tokio::join!(
+        fetch_opencode_provider_catalog_from_debug_config(workspace_path, config),
+        fetch_opencode_provider_ids_from_models(workspace_path, config),
+    );
+    let mut discovered = combine_opencode_provider_sources(vec![
+        config_providers,
+        read_opencode_providers_json(config),
+    ]);
+    if discovered.is_empty() && dynamic_provider_ids.is_empty() {
+        discovered = fetch_opencode_provider_catalog_from_auth_picker(workspace_path, config).await;
+        if discovered.is_empty() {
+            discovered = fetch_opencode_provider_catalog_preview(workspace_path, config).await;
+        }
+    }
+    let previous = read_provider_catalog_cache();
+    let previous_providers = previous
+        .as_ref()
+        .map(|cache| cache.providers.as_slice())
+        .unwrap_or_default();
+    if discovered.is_empty() && dynamic_provider_ids.is_empty() {
+        return merge_opencode_provider_catalog(Vec::new(), &[], previous_providers);
+    }
+    let providers =
+        merge_opencode_provider_catalog(discovered, &dynamic_provider_ids, previous_providers);
+    let cache = OpenCodeProviderCatalogCache {
+        updated_at: provider_catalog_now_ms(),
+        providers: providers.clone(),
+    };
+    if let Err(error) = write_provider_catalog_cache(&cache) {
+        log::warn!("[opencode] failed to persist provider catalog: {error}");
+    }
+    providers
+}
+
+/// Refreshes the catalog in the background (at most one refresh at a time) and
+/// emits `opencode-provider-catalog-updated` when the list changed.
+pub(super) fn spawn_provider_catalog_refresh(
+    app: AppHandle,
+    workspace_path: PathBuf,
+    config: Option<EngineConfig>,
+    current: Vec<OpenCodeProviderOption>,
+) {
+    use std::sync::atomic::Ordering;
+    if OPENCODE_PROVIDER_CATALOG_REFRESHING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let providers = refresh_opencode_provider_catalog(&workspace_path, config.as_ref()).await;
+        OPENCODE_PROVIDER_CATALOG_REFRESHING.store(false, Ordering::Release);
+        if providers != current {
+            let _ = app.emit(OPENCODE_PROVIDER_CATALOG_UPDATED_EVENT, &providers);
+        }
+    });
 }
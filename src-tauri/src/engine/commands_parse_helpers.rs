@@ -173,6 +173,123 @@ pub(super) fn parse_opencode_debug_config_agents(stdout: &str) -> Vec<OpenCodeAg
     entries
 }
 
+/// Builds one provider option from a `provider` config entry, taking the
+/// display name from `name` when present.
+fn provider_option_from_config(id: &str, entry: &Value) -> Option<OpenCodeProviderOption> {
+    let normalized_id = slugify_provider_label(id);
+    if normalized_id.is_empty() {
+        return None;
+    }
+    let label = entry
+        .get("name")
+        .and_then(|item| item.as_str())
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| provider_label_from_id(id));
+    let description = entry
+        .get("description")
+        .and_then(|item| item.as_str())
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(ToOwned::to_owned);
+    Some(OpenCodeProviderOption {
+        id: normalized_id,
+        label,
+        description,
+        category: "other".to_string(),
+        recommended: false,
+    })
+}
+
+fn config_provider_id_list(value: &Value, key: &str) -> Option<Vec<String>> {
+    value
+        .get(key)
+        .and_then(|item| item.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .map(slugify_provider_label)
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+}
+
+/// Providers declared in the resolved config printed by `opencode debug
+/// config`, honoring its `enabled_providers` / `disabled_providers` lists.
+pub(super) fn parse_opencode_debug_config_providers(stdout: &str) -> Vec<OpenCodeProviderOption> {
+    let clean = strip_ansi_codes(stdout);
+    let Some(value) = extract_json_object_from_text(&clean)
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+    else {
+        return Vec::new();
+    };
+    let Some(provider_map) = value.get("provider").and_then(|item| item.as_object()) else {
+        return Vec::new();
+    };
+    let enabled = config_provider_id_list(&value, "enabled_providers");
+    let disabled = config_provider_id_list(&value, "disabled_providers").unwrap_or_default();
+
+    let mut providers = provider_map
+        .iter()
+        .filter_map(|(id, entry)| provider_option_from_config(id, entry))
+        .filter(|option| {
+            enabled
+                .as_ref()
+                .is_none_or(|enabled| enabled.contains(&option.id))
+                && !disabled.contains(&option.id)
+        })
+        .collect::<Vec<_>>();
+    providers.sort_by(|a, b| a.label.cmp(&b.label));
+    providers.dedup_by(|a, b| a.id == b.id);
+    providers
+}
+
+/// Providers listed in a `providers.json` file, either as an object keyed by
+/// provider id or as an array of `{ "id", "name" }` entries.
+pub(super) fn parse_opencode_providers_json(content: &str) -> Vec<OpenCodeProviderOption> {
+    let Ok(value) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+    let value = value.get("providers").unwrap_or(&value);
+    let mut providers = match value {
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(id, entry)| provider_option_from_config(id, entry))
+            .collect::<Vec<_>>(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|entry| {
+                let id = entry.get("id").and_then(|item| item.as_str())?;
+                provider_option_from_config(id, entry)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    providers.sort_by(|a, b| a.label.cmp(&b.label));
+    providers.dedup_by(|a, b| a.id == b.id);
+    providers
+}
+
+/// Combines provider lists in precedence order: the first source to name a
+/// provider decides its label, later sources only fill a missing description.
+pub(super) fn combine_opencode_provider_sources(
+    sources: Vec<Vec<OpenCodeProviderOption>>,
+) -> Vec<OpenCodeProviderOption> {
+    let mut providers: Vec<OpenCodeProviderOption> = Vec::new();
+    for item in sources.into_iter().flatten() {
+        if let Some(existing) = providers.iter_mut().find(|p| p.id == item.id) {
+            if existing.description.is_none() {
+                existing.description = item.description;
+            }
+            continue;
+        }
+        providers.push(item);
+    }
+    providers
+}
+
 pub(super) fn merge_opencode_agents(
     mut primary: Vec<OpenCodeAgentEntry>,
     supplemental: Vec<OpenCodeAgentEntry>,
@@ -583,3 +700,58 @@ pub(super) fn parse_opencode_session_list(stdout: &str) -> Vec<OpenCodeSessionEn
     }
     entries
 }
+
+const OPENCODE_TITLE_MESSAGE_MAX_CHARS: usize = 1_000;
+
+/// Opening user/assistant text of an `opencode export` payload, one
+/// `Role: text` block per message, for title generation.
+pub(super) fn build_opencode_title_transcript(
+    export: &Value,
+    max_messages: usize,
+) -> Option<String> {
+    let messages = export.get("messages")?.as_array()?;
+    let mut blocks = Vec::new();
+    for message in messages {
+        if blocks.len() >= max_messages {
+            break;
+        }
+        let label = match message.pointer("/info/role").and_then(Value::as_str) {
+            Some("user") => "User",
+            Some("assistant") => "Assistant",
+            _ => continue,
+        };
+        let text = message
+            .get("parts")
+            .and_then(Value::as_array)
+            .map(|parts| {
+                parts
+                    .iter()
+                    .filter(|part| part.get("type").and_then(Value::as_str) == Some("text"))
+                    .filter(|part| {
+                        !part
+                            .get("synthetic")
+                            .and_then(Value::as_bool)
+                            .unwrap_or(false)
+                    })
+                    .filter_map(|part| part.get("text").and_then(Value::as_str))
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default();
+        if text.is_empty() {
+            continue;
+        }
+        let clipped = text
+            .chars()
+            .take(OPENCODE_TITLE_MESSAGE_MAX_CHARS)
+            .collect::<String>();
+        blocks.push(format!("{label}: {clipped}"));
+    }
+    if blocks.is_empty() {
+        None
+    } else {
+        Some(blocks.join("\n\n"))
+    }
+}
@@ -1,25 +1,30 @@
 use super::super::claude::ClaudeStreamTiming;
 use super::claude_forwarder::{
-    handle_claude_forwarder_event, ClaudeForwarderFuture, ClaudeForwarderRuntimeOps,
-    ClaudeForwarderState, CLAUDE_RUNTIME_SYNC_HEARTBEAT_SECS,
+    claude_user_question_payload, handle_claude_forwarder_event, ClaudeForwarderFuture,
+    ClaudeForwarderRuntimeOps, ClaudeForwarderState, CLAUDE_RUNTIME_SYNC_HEARTBEAT_SECS,
 };
 use super::{
-    build_provider_prefill_query, delete_opencode_session_files,
+    build_opencode_title_transcript, build_provider_prefill_query,
+    combine_opencode_provider_sources, delete_opencode_session_files,
     delete_opencode_session_from_datastore, extract_turn_result_text,
     is_likely_foreign_model_for_gemini, is_likely_legacy_claude_model_id,
-    is_valid_claude_model_for_passthrough, merge_opencode_agents, next_gemini_routed_item_id,
+    is_valid_claude_model_for_passthrough, load_mcp_toggle_states, merge_mcp_toggle_state,
+    merge_opencode_agents, merge_opencode_provider_catalog, next_gemini_routed_item_id,
     normalize_provider_key, opencode_data_candidate_roots, opencode_session_candidate_paths,
     parse_imported_session_id, parse_json_value, parse_opencode_agent_list,
     parse_opencode_auth_providers, parse_opencode_debug_config_agents,
-    parse_opencode_help_commands, parse_opencode_mcp_servers, parse_opencode_session_list,
-    parse_opencode_updated_at, provider_keys_match, EngineConfig, GeminiRenderLane,
-    GeminiRenderRoutingState, OpenCodeAgentEntry,
+    parse_opencode_debug_config_providers, parse_opencode_help_commands,
+    parse_opencode_mcp_servers, parse_opencode_providers_json, parse_opencode_session_list,
+    parse_opencode_updated_at, provider_keys_match, rename_opencode_session_files,
+    rename_opencode_session_in_datastore, save_mcp_toggle_states, EngineConfig, GeminiRenderLane,
+    GeminiRenderRoutingState, OpenCodeAgentEntry, OpenCodeMcpToggleState, OpenCodeProviderOption,
 };
 use crate::backend::events::AppServerEvent;
 use crate::engine::events::EngineEvent;
 use chrono::{Local, TimeZone};
 use rusqlite::{params, Connection};
 use serde_json::json;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex as StdMutex};
 use std::time::{Duration, Instant};
@@ -107,6 +112,7 @@ async fn claude_forwarder_queues_turn_start_sync_after_emitting_turn_started() {
         EngineEvent::TurnStarted {
             workspace_id: "ws-1".to_string(),
             turn_id: "turn-1".to_string(),
+            cwd: None,
         },
         None,
         &mut state,
@@ -647,6 +653,73 @@ fn parse_opencode_mcp_servers_empty() {
     assert!(servers.is_empty());
 }
 
+#[test]
+fn mcp_toggle_states_round_trip_and_tolerate_corrupt_files() {
+    let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+    let path = root.join("opencode-mcp-toggles.json");
+    assert!(load_mcp_toggle_states(&path).is_empty());
+
+    let mut states = HashMap::new();
+    states.insert(
+        "ws-1".to_string(),
+        OpenCodeMcpToggleState {
+            global_enabled: false,
+            server_enabled: HashMap::from([("github".to_string(), false)]),
+        },
+    );
+    save_mcp_toggle_states(&path, &states).expect("save toggles");
+    assert_eq!(load_mcp_toggle_states(&path), states);
+
+    std::fs::write(&path, "{ not json").expect("corrupt toggles");
+    assert!(load_mcp_toggle_states(&path).is_empty());
+    std::fs::write(&path, r#"{ "ws-2": {} }"#).expect("partial toggles");
+    assert_eq!(
+        load_mcp_toggle_states(&path).get("ws-2"),
+        Some(&OpenCodeMcpToggleState::default())
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn merge_mcp_toggle_state_overrides_live_mcp_output() {
+    let servers = parse_opencode_mcp_servers(
+        r#"
+┌  MCP Servers
+│
+●  filesystem connected
+●  github connected
+○  browser disabled
+└
+"#,
+    );
+    let toggles = OpenCodeMcpToggleState {
+        global_enabled: true,
+        server_enabled: HashMap::from([
+            ("github".to_string(), false),
+            ("browser".to_string(), true),
+        ]),
+    };
+
+    let merged = merge_mcp_toggle_state(&toggles, servers.clone());
+    let enabled: Vec<_> = merged
+        .iter()
+        .map(|item| (item.name.as_str(), item.enabled))
+        .collect();
+    assert_eq!(
+        enabled,
+        vec![("browser", true), ("filesystem", true), ("github", false)]
+    );
+
+    let all_off = OpenCodeMcpToggleState {
+        global_enabled: false,
+        ..toggles
+    };
+    assert!(merge_mcp_toggle_state(&all_off, servers)
+        .iter()
+        .all(|item| !item.enabled));
+}
+
 #[test]
 fn parse_opencode_session_list_rows() {
     let output = r#"
@@ -824,6 +897,101 @@ fn delete_opencode_session_from_datastore_removes_session_and_storage_json() {
     let _ = std::fs::remove_dir_all(&base);
 }
 
+#[test]
+fn rename_opencode_session_updates_sqlite_then_json_fallback() {
+    let base = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+    let session_dir = base.join("storage").join("session").join("project-1");
+    std::fs::create_dir_all(&session_dir).expect("should create session storage");
+    let db_path = base.join("opencode.db");
+    {
+        let connection = Connection::open(&db_path).expect("should create sqlite database");
+        connection
+            .execute_batch(
+                r#"
+                    CREATE TABLE session (
+                        id TEXT PRIMARY KEY,
+                        title TEXT NOT NULL
+                    );
+                    INSERT INTO session (id, title) VALUES ('ses_in_db', 'Untitled');
+                    "#,
+            )
+            .expect("should create session table and seed row");
+    }
+    let session_file = session_dir.join("ses_in_json.json");
+    std::fs::write(&session_file, r#"{"id":"ses_in_json","title":"Untitled"}"#)
+        .expect("should write session file");
+
+    assert_eq!(
+        rename_opencode_session_in_datastore(&base, "ses_in_db", "Fix login flow"),
+        Ok(true)
+    );
+    let db_title = Connection::open(&db_path)
+        .expect("should reopen sqlite database")
+        .query_row(
+            "SELECT title FROM session WHERE id = ?1",
+            params!["ses_in_db"],
+            |row| row.get::<_, String>(0),
+        )
+        .expect("should read renamed title");
+    assert_eq!(db_title, "Fix login flow");
+
+    assert_eq!(
+        rename_opencode_session_in_datastore(&base, "ses_in_json", "Add dark mode"),
+        Ok(true)
+    );
+    let saved: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&session_file).expect("should read session file"),
+    )
+    .expect("should parse session file");
+    assert_eq!(saved["title"], "Add dark mode");
+    assert_eq!(saved["id"], "ses_in_json");
+
+    assert_eq!(
+        rename_opencode_session_in_datastore(&base, "ses_missing", "Nope"),
+        Ok(false)
+    );
+
+    let _ = std::fs::remove_dir_all(&base);
+}
+
+#[test]
+fn rename_opencode_session_files_rejects_invalid_session_id() {
+    let workspace = PathBuf::from("/tmp/workspace");
+    let result = rename_opencode_session_files(&workspace, "../ses_escape", "Title", None);
+    assert_eq!(
+        result,
+        Err("[SESSION_NOT_FOUND] Invalid OpenCode session id".to_string())
+    );
+}
+
+#[test]
+fn build_opencode_title_transcript_keeps_opening_text_messages() {
+    let export = json!({
+        "info": { "id": "ses_1" },
+        "messages": [
+            { "info": { "role": "user" }, "parts": [
+                { "type": "text", "text": "Why does login fail?" },
+                { "type": "text", "text": "ignored", "synthetic": true },
+                { "type": "file", "text": "ignored" }
+            ] },
+            { "info": { "role": "assistant" }, "parts": [
+                { "type": "tool", "state": {} },
+                { "type": "text", "text": "The token expires early." }
+            ] },
+            { "info": { "role": "user" }, "parts": [{ "type": "text", "text": "Fix it" }] }
+        ]
+    });
+
+    assert_eq!(
+        build_opencode_title_transcript(&export, 2).as_deref(),
+        Some("User: Why does login fail?\n\nAssistant: The token expires early.")
+    );
+    assert_eq!(
+        build_opencode_title_transcript(&json!({ "messages": [] }), 2),
+        None
+    );
+}
+
 #[test]
 fn remote_claude_doctor_request_normalizes_explicit_bin() {
     let (method, params) = crate::codex::remote_claude_doctor_request(Some(
@@ -840,3 +1008,150 @@ fn send_message_params_default_keeps_claude_thinking_enabled() {
 
     assert!(!params.disable_thinking);
 }
+
+fn provider_option(id: &str, label: &str) -> OpenCodeProviderOption {
+    OpenCodeProviderOption {
+        id: id.to_string(),
+        label: label.to_string(),
+        description: None,
+        category: "other".to_string(),
+        recommended: false,
+    }
+}
+
+#[test]
+fn parse_opencode_debug_config_providers_reads_provider_map() {
+    let output = concat!(
+        "\u{1b}[2mresolved config\u{1b}[0m\n",
+        r#"{
+  "provider": {
+    "deepinfra": { "name": "DeepInfra", "models": {} },
+    "my_local": { "options": { "baseURL": "http://localhost:1234/v1" } },
+    "legacy": { "name": "Legacy" }
+  },
+  "disabled_providers": ["legacy"]
+}"#
+    );
+    let providers = parse_opencode_debug_config_providers(output);
+
+    let ids: Vec<_> = providers.iter().map(|item| item.id.as_str()).collect();
+    assert_eq!(ids, vec!["deepinfra", "my-local"]);
+    assert_eq!(providers[0].label, "DeepInfra");
+    assert_eq!(providers[1].label, "My_local");
+
+    let enabled_only = parse_opencode_debug_config_providers(
+        r#"{ "provider": { "a": {}, "b": {} }, "enabled_providers": ["b"] }"#,
+    );
+    assert_eq!(enabled_only.len(), 1);
+    assert_eq!(enabled_only[0].id, "b");
+    assert!(parse_opencode_debug_config_providers("not json").is_empty());
+}
+
+#[test]
+fn opencode_provider_sources_merge_in_precedence_order() {
+    let from_config = parse_opencode_debug_config_providers(
+        r#"{ "provider": { "deepinfra": { "name": "DeepInfra (config)" } } }"#,
+    );
+    let from_file = parse_opencode_providers_json(
+        r#"[
+            { "id": "deepinfra", "name": "Deep Infra", "description": "Hosted models" },
+            { "id": "groq", "name": "Groq" }
+        ]"#,
+    );
+    let discovered = combine_opencode_provider_sources(vec![from_config, from_file]);
+
+    assert_eq!(discovered.len(), 2);
+    assert_eq!(discovered[0].label, "DeepInfra (config)");
+    assert_eq!(discovered[0].description.as_deref(), Some("Hosted models"));
+
+    let providers = merge_opencode_provider_catalog(
+        discovered,
+        &["groq".to_string(), "openrouter".to_string()],
+        &[],
+    );
+    let groq = providers
+        .iter()
+        .find(|item| item.id == "groq")
+        .expect("groq");
+    assert_eq!(groq.label, "Groq");
+    assert!(providers.iter().any(|item| item.id == "openrouter"));
+    assert!(providers.iter().any(|item| item.id == "anthropic"));
+    assert_eq!(providers[0].category, "popular");
+}
+
+#[test]
+fn merge_opencode_provider_catalog_never_returns_empty_list() {
+    let providers = merge_opencode_provider_catalog(Vec::new(), &[], &[]);
+
+    assert!(!providers.is_empty());
+    assert!(providers.iter().any(|item| item.id == "anthropic"));
+    assert_eq!(providers[0].category, "popular");
+}
+
+#[test]
+fn merge_opencode_provider_catalog_keeps_previous_entries_after_partial_scan() {
+    let previous: Vec<_> = (0..6)
+        .map(|index| provider_option(&format!("custom-{index}"), &format!("Custom {index}")))
+        .collect();
+    let discovered = vec![provider_option("custom-0", "Custom 0")];
+
+    let providers =
+        merge_opencode_provider_catalog(discovered, &["deepinfra".to_string()], &previous);
+
+    for index in 0..6 {
+        let id = format!("custom-{index}");
+        assert_eq!(providers.iter().filter(|item| item.id == id).count(), 1);
+    }
+    assert!(providers.iter().any(|item| item.id == "deepinfra"));
+}
+
+#[test]
+fn claude_user_question_payload_carries_prompt_and_options() {
+    let event = EngineEvent::RequestUserInput {
+        workspace_id: "ws-1".to_string(),
+        request_id: json!("ask-0123"),
+        questions: json!([
+            {
+                "id": "q-0",
+                "header": "Scope",
+                "question": "Which files should change?",
+                "multiSelect": false,
+                "options": [{"label": "Only src", "description": ""}],
+            },
+            {"id": "q-1", "header": "", "question": "  Run tests? ", "options": null},
+        ]),
+    };
+
+    let payload = claude_user_question_payload(
+        &event,
+        "claude:session-1",
+        "claude-turn-1",
+        Duration::from_secs(120),
+    )
+    .expect("question payload");
+    assert_eq!(payload["sessionId"], "session-1");
+    assert_eq!(payload["questionId"], "ask-0123");
+    assert_eq!(payload["prompt"], "Which files should change?\nRun tests?");
+    assert_eq!(
+        payload["questions"][0]["options"][0]["label"],
+        json!("Only src")
+    );
+    assert_eq!(payload["timeoutSecs"], 120);
+
+    let pending = claude_user_question_payload(
+        &event,
+        "claude-turn-1",
+        "claude-turn-1",
+        Duration::from_secs(120),
+    )
+    .expect("question payload before session start");
+    assert!(pending["sessionId"].is_null());
+
+    let other = EngineEvent::TextDelta {
+        workspace_id: "ws-1".to_string(),
+        text: "hello".to_string(),
+    };
+    assert!(
+        claude_user_question_payload(&other, "claude:s", "t", Duration::from_secs(1)).is_none()
+    );
+}
@@ -47,6 +47,10 @@ pub enum EngineEvent {
     TurnStarted {
         workspace_id: String,
         turn_id: String,
+        /// Directory the CLI process runs in, when it differs from the
+        /// workspace root.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cwd: Option<String>,
     },
 
     /// Text content delta (streaming)
@@ -138,6 +142,13 @@ pub enum EngineEvent {
         code: Option<String>,
     },
 
+    /// Turn exceeded its `timeout_secs` and was interrupted
+    #[serde(rename = "turn:timeout")]
+    TurnTimeout {
+        workspace_id: String,
+        timeout_secs: u64,
+    },
+
     /// Session ended
     #[serde(rename = "session:ended")]
     SessionEnded {
@@ -210,6 +221,7 @@ impl EngineEvent {
             EngineEvent::RequestUserInput { workspace_id, .. } => workspace_id,
             EngineEvent::TurnCompleted { workspace_id, .. } => workspace_id,
             EngineEvent::TurnError { workspace_id, .. } => workspace_id,
+            EngineEvent::TurnTimeout { workspace_id, .. } => workspace_id,
             EngineEvent::SessionEnded { workspace_id, .. } => workspace_id,
             EngineEvent::UsageUpdate { workspace_id, .. } => workspace_id,
             EngineEvent::ProcessingHeartbeat { workspace_id, .. } => workspace_id,
@@ -221,7 +233,9 @@ impl EngineEvent {
     pub fn is_terminal(&self) -> bool {
         matches!(
             self,
-            EngineEvent::TurnCompleted { .. } | EngineEvent::TurnError { .. }
+            EngineEvent::TurnCompleted { .. }
+                | EngineEvent::TurnError { .. }
+                | EngineEvent::TurnTimeout { .. }
         )
     }
 }
@@ -414,13 +428,19 @@ pub fn engine_event_to_app_server_event_with_turn_context(
                 },
             }
         }),
-        EngineEvent::TurnStarted { turn_id, .. } => json!({
-            "method": "turn/started",
-            "params": {
+        EngineEvent::TurnStarted { turn_id, cwd, .. } => {
+            let mut params = json!({
                 "turnId": turn_id,
                 "threadId": thread_id,
+            });
+            if let Some(cwd) = cwd {
+                params["cwd"] = json!(cwd);
             }
-        }),
+            json!({
+                "method": "turn/started",
+                "params": params,
+            })
+        }
         EngineEvent::TextDelta { text, .. } => json!({
             "method": "item/agentMessage/delta",
             "params": {
@@ -610,6 +630,15 @@ pub fn engine_event_to_app_server_event_with_turn_context(
                 "code": code,
             }
         }),
+        EngineEvent::TurnTimeout { timeout_secs, .. } => json!({
+            "method": "turn/timeout",
+            "params": {
+                "threadId": thread_id,
+                "turnId": turn_id_context,
+                "timeoutSecs": timeout_secs,
+                "error": format!("The model did not respond within {timeout_secs} seconds."),
+            }
+        }),
         EngineEvent::UsageUpdate {
             input_tokens,
             output_tokens,
@@ -898,6 +927,7 @@ mod tests {
         let event = EngineEvent::TurnStarted {
             workspace_id: "ws-test".to_string(),
             turn_id: "turn-1".to_string(),
+            cwd: None,
         };
 
         assert_eq!(event.workspace_id(), "ws-test");
@@ -916,6 +946,12 @@ mod tests {
             text: "test".to_string(),
         };
         assert!(!delta.is_terminal());
+
+        let timeout = EngineEvent::TurnTimeout {
+            workspace_id: "ws-1".to_string(),
+            timeout_secs: 60,
+        };
+        assert!(timeout.is_terminal());
     }
 
     #[test]
@@ -925,6 +961,7 @@ mod tests {
                 EngineEvent::TurnStarted {
                     workspace_id: "ws-contract".to_string(),
                     turn_id: "turn-contract-1".to_string(),
+                    cwd: None,
                 },
                 "turn/started",
             ),
@@ -966,6 +1003,13 @@ mod tests {
                 },
                 "turn/error",
             ),
+            (
+                EngineEvent::TurnTimeout {
+                    workspace_id: "ws-contract".to_string(),
+                    timeout_secs: 600,
+                },
+                "turn/timeout",
+            ),
             (
                 EngineEvent::UsageUpdate {
                     workspace_id: "ws-contract".to_string(),
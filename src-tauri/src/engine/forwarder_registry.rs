@@ -0,0 +1,301 @@
+//! Lifecycle tracking for the per-turn event forwarders spawned by
+//! `engine_send_message`.
+//!
+//! A forwarder lives until its turn reaches a terminal event, the engine
+//! process exits, or the turn goes quiet for [`FORWARDER_IDLE_TIMEOUT`].
+//! The last two cases are "reaps": the UI is told the turn disconnected.
+
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, Notify};
+
+use super::EngineType;
+use crate::backend::events::AppServerEvent;
+
+/// How long a forwarder may go without a single event before it is reaped.
+pub(crate) const FORWARDER_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Why a forwarder stopped without seeing its turn finish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ForwarderReapReason {
+    IdleTimeout,
+    ProcessExited,
+}
+
+impl ForwarderReapReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            ForwarderReapReason::IdleTimeout => "idle-timeout",
+            ForwarderReapReason::ProcessExited => "process-exited",
+        }
+    }
+
+    fn message(self) -> String {
+        match self {
+            ForwarderReapReason::IdleTimeout => format!(
+                "No events arrived from the engine for {} minutes; the turn was disconnected.",
+                FORWARDER_IDLE_TIMEOUT.as_secs() / 60
+            ),
+            ForwarderReapReason::ProcessExited => {
+                "The engine process exited before the turn finished.".to_string()
+            }
+        }
+    }
+}
+
+/// What a forwarder should do next.
+pub(crate) enum ForwarderRecv<T> {
+    Event(T),
+    Lagged(u64),
+    Closed,
+    /// The caller-supplied grace deadline passed.
+    GraceElapsed,
+    Reaped(ForwarderReapReason),
+}
+
+/// One live forwarder as reported by `engine_forwarder_stats`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventForwarderStats {
+    pub turn_id: String,
+    pub engine: EngineType,
+    pub workspace_id: String,
+    pub started_at_ms: u64,
+    pub age_ms: u64,
+    pub last_event_at_ms: Option<u64>,
+    pub event_count: u64,
+    pub process_exited: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventForwarderSnapshot {
+    pub active: usize,
+    pub forwarders: Vec<EventForwarderStats>,
+}
+
+struct ForwarderEntry {
+    engine: EngineType,
+    workspace_id: String,
+    started_at: Instant,
+    started_at_ms: u64,
+    last_event_at_ms: Option<u64>,
+    event_count: u64,
+    process_exited: bool,
+    process_exit: Arc<Notify>,
+}
+
+type ForwarderEntries = Arc<Mutex<HashMap<String, ForwarderEntry>>>;
+
+/// Live forwarders keyed by turn ID. Clones share the same registry.
+#[derive(Clone, Default)]
+pub(crate) struct ForwarderRegistry {
+    entries: ForwarderEntries,
+}
+
+impl ForwarderRegistry {
+    /// Track the forwarder of `turn_id` until the returned lease is dropped.
+    /// Register before spawning the engine process so its exit is never missed.
+    pub(crate) fn register(
+        &self,
+        engine: EngineType,
+        workspace_id: &str,
+        turn_id: &str,
+    ) -> ForwarderLease {
+        let process_exit = Arc::new(Notify::new());
+        lock_entries(&self.entries).insert(
+            turn_id.to_string(),
+            ForwarderEntry {
+                engine,
+                workspace_id: workspace_id.to_string(),
+                started_at: Instant::now(),
+                started_at_ms: unix_timestamp_ms(),
+                last_event_at_ms: None,
+                event_count: 0,
+                process_exited: false,
+                process_exit: process_exit.clone(),
+            },
+        );
+        ForwarderLease {
+            entries: self.entries.clone(),
+            turn_id: turn_id.to_string(),
+            process_exit,
+        }
+    }
+
+    /// Tell the forwarder of `turn_id` that its engine process is gone; it
+    /// drains what is already buffered and then stops.
+    pub(crate) fn mark_process_exited(&self, turn_id: &str) {
+        if let Some(entry) = lock_entries(&self.entries).get_mut(turn_id) {
+            entry.process_exited = true;
+            entry.process_exit.notify_one();
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> EventForwarderSnapshot {
+        let mut forwarders = lock_entries(&self.entries)
+            .iter()
+            .map(|(turn_id, entry)| EventForwarderStats {
+                turn_id: turn_id.clone(),
+                engine: entry.engine,
+                workspace_id: entry.workspace_id.clone(),
+                started_at_ms: entry.started_at_ms,
+                age_ms: entry.started_at.elapsed().as_millis() as u64,
+                last_event_at_ms: entry.last_event_at_ms,
+                event_count: entry.event_count,
+                process_exited: entry.process_exited,
+            })
+            .collect::<Vec<_>>();
+        forwarders.sort_by_key(|stats| stats.started_at_ms);
+        EventForwarderSnapshot {
+            active: forwarders.len(),
+            forwarders,
+        }
+    }
+}
+
+/// Registration of one running forwarder; dropping it unregisters.
+pub(crate) struct ForwarderLease {
+    entries: ForwarderEntries,
+    turn_id: String,
+    process_exit: Arc<Notify>,
+}
+
+impl ForwarderLease {
+    /// Record that an event for this turn was forwarded.
+    pub(crate) fn record_event(&self) {
+        if let Some(entry) = lock_entries(&self.entries).get_mut(&self.turn_id) {
+            entry.event_count = entry.event_count.saturating_add(1);
+            entry.last_event_at_ms = Some(unix_timestamp_ms());
+        }
+    }
+
+    /// Wait for the next broadcast event, the grace deadline, the idle
+    /// timeout, or the engine process exiting, whichever comes first.
+    /// Events already buffered are always delivered before an exit reap.
+    pub(crate) async fn recv<T: Clone>(
+        &self,
+        receiver: &mut broadcast::Receiver<T>,
+        grace_deadline: Option<tokio::time::Instant>,
+    ) -> ForwarderRecv<T> {
+        let idle_deadline = tokio::time::Instant::now() + FORWARDER_IDLE_TIMEOUT;
+        let deadline = grace_deadline
+            .map(|grace| grace.min(idle_deadline))
+            .unwrap_or(idle_deadline);
+        tokio::select! {
+            biased;
+            result = tokio::time::timeout_at(deadline, receiver.recv()) => match result {
+                Ok(Ok(event)) => ForwarderRecv::Event(event),
+                Ok(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+                    ForwarderRecv::Lagged(skipped)
+                }
+                Ok(Err(broadcast::error::RecvError::Closed)) => ForwarderRecv::Closed,
+                Err(_) if grace_deadline.is_some_and(|grace| grace <= deadline) => {
+                    ForwarderRecv::GraceElapsed
+                }
+                Err(_) => ForwarderRecv::Reaped(ForwarderReapReason::IdleTimeout),
+            },
+            _ = self.process_exit.notified() => {
+                ForwarderRecv::Reaped(ForwarderReapReason::ProcessExited)
+            }
+        }
+    }
+
+    /// App-server event telling the UI this turn's stream is gone.
+    pub(crate) fn reaped_event(
+        &self,
+        workspace_id: &str,
+        thread_id: &str,
+        reason: ForwarderReapReason,
+    ) -> AppServerEvent {
+        log::warn!(
+            "[engine-forwarder] reaped turn={} workspace_id={} reason={}",
+            self.turn_id,
+            workspace_id,
+            reason.as_str()
+        );
+        AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: json!({
+                "method": "turn/disconnected",
+                "params": {
+                    "threadId": thread_id,
+                    "turnId": &self.turn_id,
+                    "reason": reason.as_str(),
+                    "error": reason.message(),
+                }
+            }),
+        }
+    }
+}
+
+impl Drop for ForwarderLease {
+    fn drop(&mut self) {
+        lock_entries(&self.entries).remove(&self.turn_id);
+    }
+}
+
+fn lock_entries(
+    entries: &ForwarderEntries,
+) -> std::sync::MutexGuard<'_, HashMap<String, ForwarderEntry>> {
+    entries
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn unix_timestamp_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis().min(u128::from(u64::MAX)) as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn process_exit_reaps_after_buffered_events() {
+        let registry = ForwarderRegistry::default();
+        let (sender, mut receiver) = broadcast::channel::<u32>(8);
+        let lease = registry.register(EngineType::Claude, "ws-1", "turn-1");
+
+        sender.send(1).expect("send");
+        registry.mark_process_exited("turn-1");
+        registry.mark_process_exited("unknown-turn");
+
+        assert!(matches!(
+            lease.recv(&mut receiver, None).await,
+            ForwarderRecv::Event(1)
+        ));
+        lease.record_event();
+        let snapshot = registry.snapshot();
+        assert_eq!(snapshot.active, 1);
+        assert_eq!(snapshot.forwarders[0].event_count, 1);
+        assert!(snapshot.forwarders[0].last_event_at_ms.is_some());
+        assert!(snapshot.forwarders[0].process_exited);
+
+        assert!(matches!(
+            lease.recv(&mut receiver, None).await,
+            ForwarderRecv::Reaped(ForwarderReapReason::ProcessExited)
+        ));
+        drop(lease);
+        assert_eq!(registry.snapshot().active, 0);
+    }
+
+    #[tokio::test]
+    async fn grace_deadline_ends_a_quiet_forwarder() {
+        let registry = ForwarderRegistry::default();
+        let (_sender, mut receiver) = broadcast::channel::<u32>(8);
+        let lease = registry.register(EngineType::Gemini, "ws-1", "turn-1");
+        let grace = tokio::time::Instant::now() + Duration::from_millis(10);
+
+        assert!(matches!(
+            lease.recv(&mut receiver, Some(grace)).await,
+            ForwarderRecv::GraceElapsed
+        ));
+    }
+}
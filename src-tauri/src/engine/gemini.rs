@@ -19,6 +19,7 @@ use super::gemini_history::{load_gemini_session, GeminiSessionMessage};
 use super::gemini_proxy_guard::apply_dead_loopback_proxy_guard;
 use super::{EngineConfig, EngineType, SendMessageParams};
 use crate::app_paths;
+use crate::shared::access_presets::AccessModePreset;
 #[path = "gemini_event_parsing.rs"]
 mod event_parsing;
 use self::event_parsing::{
@@ -120,6 +121,16 @@ impl GeminiSession {
         );
     }
 
+    pub fn emit_turn_timeout(&self, turn_id: &str, timeout_secs: u64) {
+        self.emit_turn_event(
+            turn_id,
+            EngineEvent::TurnTimeout {
+                workspace_id: self.workspace_id.clone(),
+                timeout_secs,
+            },
+        );
+    }
+
     fn with_external_spec_hint(text: &str, custom_spec_root: Option<&str>) -> String {
         let Some(spec_root) = custom_spec_root
             .map(str::trim)
@@ -648,13 +659,10 @@ impl GeminiSession {
     fn resolve_approval_mode(access_mode: Option<&str>) -> Option<&'static str> {
         let normalized = access_mode.map(str::trim).filter(|value| !value.is_empty());
         match normalized {
-            Some("full-access") => Some("yolo"),
-            Some("read-only") => Some("plan"),
-            Some("default") => Some("default"),
             // "current" should respect Gemini CLI's own active/default policy.
             Some("current") | None => None,
-            // Keep compatibility for unknown/legacy values.
-            Some(_) => Some("auto_edit"),
+            // Unknown/legacy values resolve to the auto-edit preset.
+            Some(value) => Some(AccessModePreset::resolve(Some(value)).gemini_approval_mode()),
         }
     }
 
@@ -728,7 +736,13 @@ impl GeminiSession {
         };
 
         let mut cmd = crate::backend::app_server::build_command_for_binary(&bin);
-        cmd.current_dir(&self.workspace_path);
+        cmd.current_dir(
+            params
+                .cwd
+                .as_deref()
+                .map(std::path::Path::new)
+                .unwrap_or(&self.workspace_path),
+        );
         cmd.arg("--output-format");
         cmd.arg("stream-json");
 
@@ -925,6 +939,7 @@ impl GeminiSession {
             EngineEvent::TurnStarted {
                 workspace_id: self.workspace_id.clone(),
                 turn_id: turn_id.to_string(),
+                cwd: params.cwd.clone(),
             },
         );
 
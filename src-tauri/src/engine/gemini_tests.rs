@@ -323,6 +323,36 @@ fn build_command_routes_long_prompt_to_stdin() {
     let _ = std::fs::remove_dir_all(&workspace_path);
 }
 
+#[test]
+fn build_command_resumes_continued_sessions() {
+    let workspace_path = unique_temp_path("moss-x-gemini-workspace");
+    std::fs::create_dir_all(&workspace_path).expect("create workspace");
+    let session = GeminiSession::new("workspace-1".to_string(), workspace_path.clone(), None);
+    let mut params = SendMessageParams::default();
+    params.text = "follow up".to_string();
+    params.continue_session = true;
+    params.session_id = Some("gemini-session-1".to_string());
+
+    let args = command_args(&session.build_command(&params).command);
+    let resume_idx = args
+        .iter()
+        .position(|value| value == "--resume")
+        .expect("continued session should pass --resume");
+    assert_eq!(
+        args.get(resume_idx + 1).map(String::as_str),
+        Some("gemini-session-1")
+    );
+
+    params.continue_session = false;
+    let args = command_args(&session.build_command(&params).command);
+    assert!(
+        !args.iter().any(|value| value == "--resume"),
+        "fresh session should not resume"
+    );
+
+    let _ = std::fs::remove_dir_all(&workspace_path);
+}
+
 #[test]
 fn parse_result_error_maps_to_turn_error() {
     let payload = json!({
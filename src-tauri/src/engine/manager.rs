@@ -4,19 +4,88 @@
 //! session management, and configuration.
 
 use std::collections::HashMap;
+use std::future::Future;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, RwLock};
+use tokio::task::{AbortHandle, JoinHandle};
 
+use super::adapter::EngineAdapter;
 use super::claude::{ClaudeSession, ClaudeSessionManager};
+use super::forwarder_registry::ForwarderRegistry;
 use super::gemini::GeminiSession;
 use super::opencode::OpenCodeSession;
 use super::status::{
-    detect_all_engines, detect_claude_status, detect_codex_status, detect_gemini_status,
-    detect_opencode_status,
+    detect_claude_status, detect_codex_status, detect_gemini_status, detect_opencode_status,
 };
 use super::{disabled_engine_status, EngineConfig, EngineStatus, EngineType};
 
+/// How long a settled turn is remembered, so a timer armed for it after it
+/// was interrupted or finished stays disarmed.
+const SETTLED_TURN_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Order in which engines are probed and reported.
+const DETECTION_ORDER: [EngineType; 4] = [
+    EngineType::Claude,
+    EngineType::Codex,
+    EngineType::Gemini,
+    EngineType::OpenCode,
+];
+
+/// How long a detection pass waits for each engine before reporting it as
+/// still detecting. Probes run concurrently, so this bounds the whole pass.
+pub(crate) const DETECTION_TIMEOUT_BUDGET: Duration = Duration::from_secs(3);
+
+/// Result of a budgeted detection pass: a status for every engine, plus the
+/// probes that overran the budget and are still running.
+pub(crate) struct EngineDetection {
+    pub(crate) statuses: Vec<EngineStatus>,
+    pub(crate) pending: Vec<(EngineType, JoinHandle<EngineStatus>)>,
+}
+
+/// Status for a probe whose task panicked or was cancelled.
+pub(crate) fn failed_probe_status(
+    engine_type: EngineType,
+    error: tokio::task::JoinError,
+) -> EngineStatus {
+    EngineStatus {
+        error: Some(format!("Engine detection failed: {error}")),
+        ..disabled_engine_status(engine_type)
+    }
+}
+
+/// How the status of an engine is probed.
+enum EngineProbe {
+    Adapter(Arc<dyn EngineAdapter>),
+    /// Built-in CLI probe, used by hosts that register no adapters (the daemon).
+    Builtin,
+    /// The engine's adapter is not compiled into this build.
+    Unavailable,
+}
+
+impl EngineProbe {
+    async fn run(self, engine_type: EngineType, bin: Option<String>) -> EngineStatus {
+        let bin = bin.as_deref();
+        match self {
+            EngineProbe::Adapter(adapter) => adapter.detect(bin).await,
+            EngineProbe::Builtin => match engine_type {
+                EngineType::Claude => detect_claude_status(bin).await,
+                EngineType::Codex => detect_codex_status(bin).await,
+                EngineType::Gemini => detect_gemini_status(bin).await,
+                EngineType::OpenCode => detect_opencode_status(bin).await,
+            },
+            EngineProbe::Unavailable => EngineStatus {
+                error: Some(format!(
+                    "{} is not available in this build",
+                    engine_type.display_name()
+                )),
+                ..disabled_engine_status(engine_type)
+            },
+        }
+    }
+}
+
 /// Unified engine manager
 pub struct EngineManager {
     /// Currently active engine type (global default)
@@ -36,6 +105,104 @@ pub struct EngineManager {
 
     /// Engine configurations
     engine_configs: RwLock<HashMap<EngineType, EngineConfig>>,
+
+    /// Registered engine adapters
+    adapters: std::sync::RwLock<HashMap<EngineType, Arc<dyn EngineAdapter>>>,
+
+    /// Armed per-turn timeouts
+    pub(crate) turn_timeouts: TurnTimeouts,
+
+    /// Live per-turn event forwarders
+    pub(crate) forwarders: ForwarderRegistry,
+}
+
+/// A pending turn timeout and the workspace its turn runs in.
+struct TurnTimeout {
+    workspace_id: String,
+    abort: AbortHandle,
+}
+
+#[derive(Default)]
+struct TurnTimeoutState {
+    /// Armed timeouts keyed by turn ID.
+    armed: HashMap<String, TurnTimeout>,
+    /// Turns disarmed because they finished or were interrupted.
+    settled: HashMap<String, Instant>,
+}
+
+/// Per-turn timeouts keyed by turn ID. Clones share the same timers, so
+/// the task driving a turn can disarm it when the process exits.
+#[derive(Clone, Default)]
+pub(crate) struct TurnTimeouts {
+    state: Arc<std::sync::Mutex<TurnTimeoutState>>,
+}
+
+impl TurnTimeouts {
+    fn lock(&self) -> std::sync::MutexGuard<'_, TurnTimeoutState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Run `on_timeout` if the turn is still armed after `timeout`.
+    /// Re-arming a turn replaces its previous timer; a turn that already
+    /// settled is not armed again.
+    pub(crate) fn arm<F>(&self, workspace_id: &str, turn_id: &str, timeout: Duration, on_timeout: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let timeouts = self.clone();
+        let key = turn_id.to_string();
+        let mut guard = self.lock();
+        if guard.settled.contains_key(turn_id) {
+            return;
+        }
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            // Whoever removes the entry first wins: a disarm that got here
+            // before us means the turn already finished or was interrupted.
+            let still_armed = timeouts.lock().armed.remove(&key).is_some();
+            if still_armed {
+                on_timeout.await;
+            }
+        });
+        if let Some(previous) = guard.armed.insert(
+            turn_id.to_string(),
+            TurnTimeout {
+                workspace_id: workspace_id.to_string(),
+                abort: task.abort_handle(),
+            },
+        ) {
+            previous.abort.abort();
+        }
+    }
+
+    /// Cancel the timeout of a turn that completed or was interrupted.
+    pub(crate) fn disarm(&self, turn_id: &str) {
+        let mut guard = self.lock();
+        let now = Instant::now();
+        guard
+            .settled
+            .retain(|_, settled_at| now.duration_since(*settled_at) < SETTLED_TURN_TTL);
+        guard.settled.insert(turn_id.to_string(), now);
+        if let Some(timeout) = guard.armed.remove(turn_id) {
+            timeout.abort.abort();
+        }
+    }
+
+    /// Cancel the timeouts of every turn running in a workspace.
+    pub(crate) fn disarm_workspace(&self, workspace_id: &str) {
+        let turn_ids: Vec<String> = self
+            .lock()
+            .armed
+            .iter()
+            .filter(|(_, timeout)| timeout.workspace_id == workspace_id)
+            .map(|(turn_id, _)| turn_id.clone())
+            .collect();
+        for turn_id in turn_ids {
+            self.disarm(&turn_id);
+        }
+    }
 }
 
 impl EngineManager {
@@ -48,6 +215,52 @@ impl EngineManager {
             opencode_sessions: Mutex::new(HashMap::new()),
             gemini_sessions: Mutex::new(HashMap::new()),
             engine_configs: RwLock::new(HashMap::new()),
+            adapters: std::sync::RwLock::new(HashMap::new()),
+            turn_timeouts: TurnTimeouts::default(),
+            forwarders: ForwarderRegistry::default(),
+        }
+    }
+
+    /// Register the adapter for an engine, replacing any previous one.
+    pub(crate) fn register_adapter(&self, adapter: Arc<dyn EngineAdapter>) {
+        let mut adapters = match self.adapters.write() {
+            Ok(adapters) => adapters,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        adapters.insert(adapter.engine_type(), adapter);
+    }
+
+    /// Get the adapter registered for an engine
+    pub(crate) fn adapter(&self, engine_type: EngineType) -> Option<Arc<dyn EngineAdapter>> {
+        let adapters = match self.adapters.read() {
+            Ok(adapters) => adapters,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        adapters.get(&engine_type).cloned()
+    }
+
+    /// Get the adapter for an engine or a user-facing error if it is missing
+    pub(crate) fn require_adapter(
+        &self,
+        engine_type: EngineType,
+    ) -> Result<Arc<dyn EngineAdapter>, String> {
+        self.adapter(engine_type).ok_or_else(|| {
+            format!(
+                "{} is not available in this build",
+                engine_type.display_name()
+            )
+        })
+    }
+
+    fn engine_probe(&self, engine_type: EngineType) -> EngineProbe {
+        let adapters = match self.adapters.read() {
+            Ok(adapters) => adapters,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        match adapters.get(&engine_type) {
+            Some(adapter) => EngineProbe::Adapter(adapter.clone()),
+            None if adapters.is_empty() => EngineProbe::Builtin,
+            None => EngineProbe::Unavailable,
         }
     }
 
@@ -95,17 +308,15 @@ impl EngineManager {
         gemini_enabled: bool,
         opencode_enabled: bool,
     ) -> EngineStatus {
-        let configs = self.engine_configs.read().await;
-        let config = configs.get(&engine_type);
-        let bin = config.and_then(|c| c.bin_path.as_deref());
+        let bin = self
+            .get_engine_config(engine_type)
+            .await
+            .and_then(|config| config.bin_path);
 
         let status = match engine_type {
-            EngineType::Claude => detect_claude_status(bin).await,
-            EngineType::Codex => detect_codex_status(bin).await,
             EngineType::Gemini if !gemini_enabled => disabled_engine_status(engine_type),
             EngineType::OpenCode if !opencode_enabled => disabled_engine_status(engine_type),
-            EngineType::Gemini => detect_gemini_status(bin).await,
-            EngineType::OpenCode => detect_opencode_status(bin).await,
+            _ => self.engine_probe(engine_type).run(engine_type, bin).await,
         };
 
         // Cache the result
@@ -131,52 +342,105 @@ impl EngineManager {
         gemini_enabled: bool,
         opencode_enabled: bool,
     ) -> Vec<EngineStatus> {
-        let (claude_bin, codex_bin, gemini_bin, opencode_bin) = {
-            let configs = self.engine_configs.read().await;
-            (
-                configs
-                    .get(&EngineType::Claude)
-                    .and_then(|c| c.bin_path.clone()),
-                configs
-                    .get(&EngineType::Codex)
-                    .and_then(|c| c.bin_path.clone()),
-                configs
-                    .get(&EngineType::Gemini)
-                    .and_then(|c| c.bin_path.clone()),
-                configs
-                    .get(&EngineType::OpenCode)
-                    .and_then(|c| c.bin_path.clone()),
-            )
-        };
+        let probes = self.spawn_probes(gemini_enabled, opencode_enabled).await;
+        let mut statuses = Vec::with_capacity(probes.len());
+        for (engine_type, probe) in probes {
+            let status = match probe {
+                Some(probe) => probe
+                    .await
+                    .unwrap_or_else(|error| failed_probe_status(engine_type, error)),
+                None => disabled_engine_status(engine_type),
+            };
+            statuses.push(status);
+        }
+        self.cache_engine_statuses(&statuses).await;
+        statuses
+    }
 
-        let statuses = detect_all_engines(
-            claude_bin.as_deref(),
-            codex_bin.as_deref(),
-            gemini_enabled.then_some(gemini_bin.as_deref()).flatten(),
-            opencode_enabled
-                .then_some(opencode_bin.as_deref())
-                .flatten(),
-        )
-        .await;
-
-        let statuses = statuses
-            .into_iter()
-            .map(|status| match status.engine_type {
-                EngineType::Gemini if !gemini_enabled => disabled_engine_status(EngineType::Gemini),
-                EngineType::OpenCode if !opencode_enabled => {
-                    disabled_engine_status(EngineType::OpenCode)
+    /// Like `detect_engines_with_gates`, but stops waiting after `budget`.
+    /// Engines that have not answered yet are reported with `detecting` set,
+    /// keeping their last known status when there is one; their probes are
+    /// handed back so the caller can publish the final result later.
+    pub(crate) async fn detect_engines_within_budget(
+        &self,
+        gemini_enabled: bool,
+        opencode_enabled: bool,
+        budget: Duration,
+    ) -> EngineDetection {
+        let probes = self.spawn_probes(gemini_enabled, opencode_enabled).await;
+        let deadline = tokio::time::Instant::now() + budget;
+        let mut statuses = Vec::with_capacity(probes.len());
+        let mut finished = Vec::new();
+        let mut pending = Vec::new();
+        for (engine_type, probe) in probes {
+            let Some(mut probe) = probe else {
+                statuses.push(disabled_engine_status(engine_type));
+                continue;
+            };
+            match tokio::time::timeout_at(deadline, &mut probe).await {
+                Ok(result) => {
+                    let status =
+                        result.unwrap_or_else(|error| failed_probe_status(engine_type, error));
+                    finished.push(status.clone());
+                    statuses.push(status);
                 }
-                _ => status,
+                Err(_) => {
+                    let previous = self.get_engine_status(engine_type).await;
+                    statuses.push(EngineStatus {
+                        detecting: true,
+                        ..previous.unwrap_or_else(|| EngineStatus {
+                            error: None,
+                            ..disabled_engine_status(engine_type)
+                        })
+                    });
+                    pending.push((engine_type, probe));
+                }
+            }
+        }
+        self.cache_engine_statuses(&finished).await;
+        EngineDetection { statuses, pending }
+    }
+
+    /// Start a probe for every engine in detection order; disabled optional
+    /// engines get no probe.
+    async fn spawn_probes(
+        &self,
+        gemini_enabled: bool,
+        opencode_enabled: bool,
+    ) -> Vec<(EngineType, Option<JoinHandle<EngineStatus>>)> {
+        let configs = self.engine_configs.read().await.clone();
+        DETECTION_ORDER
+            .into_iter()
+            .map(|engine_type| {
+                let enabled = match engine_type {
+                    EngineType::Gemini => gemini_enabled,
+                    EngineType::OpenCode => opencode_enabled,
+                    EngineType::Claude | EngineType::Codex => true,
+                };
+                let probe = enabled.then(|| {
+                    let bin = configs
+                        .get(&engine_type)
+                        .and_then(|config| config.bin_path.clone());
+                    tokio::spawn(self.engine_probe(engine_type).run(engine_type, bin))
+                });
+                (engine_type, probe)
             })
-            .collect::<Vec<_>>();
+            .collect()
+    }
 
-        // Cache results
+    async fn cache_engine_statuses(&self, statuses: &[EngineStatus]) {
         let mut cached = self.engine_statuses.write().await;
-        for status in &statuses {
+        for status in statuses {
             cached.insert(status.engine_type, status.clone());
         }
+    }
 
-        statuses
+    /// Cache a status that finished after its detection pass returned.
+    pub(crate) async fn cache_engine_status(&self, status: EngineStatus) {
+        self.engine_statuses
+            .write()
+            .await
+            .insert(status.engine_type, status);
     }
 
     /// Get cached engine status
@@ -314,6 +578,32 @@ impl EngineManager {
         sessions.remove(workspace_id);
     }
 
+    // ==================== Turn Timeouts ====================
+
+    /// Run `on_timeout` if the turn is still armed after `timeout`.
+    pub(crate) fn arm_turn_timeout<F>(
+        &self,
+        workspace_id: &str,
+        turn_id: &str,
+        timeout: Duration,
+        on_timeout: F,
+    ) where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.turn_timeouts
+            .arm(workspace_id, turn_id, timeout, on_timeout);
+    }
+
+    /// Cancel the timeout of a turn that completed or was interrupted.
+    pub(crate) fn disarm_turn_timeout(&self, turn_id: &str) {
+        self.turn_timeouts.disarm(turn_id);
+    }
+
+    /// Cancel the timeouts of every turn running in a workspace.
+    pub(crate) fn disarm_workspace_turn_timeouts(&self, workspace_id: &str) {
+        self.turn_timeouts.disarm_workspace(workspace_id);
+    }
+
     // ==================== Utility Methods ====================
 
     /// Check if an engine is available (installed and ready)
@@ -345,7 +635,166 @@ impl Default for EngineManager {
 
 #[cfg(test)]
 mod tests {
+    use super::super::adapter::{EngineContext, EngineFuture};
+    use super::super::{EngineFeatures, SendMessageParams};
     use super::*;
+    use serde_json::Value;
+
+    struct FakeAdapter {
+        delay: Duration,
+    }
+
+    impl EngineAdapter for FakeAdapter {
+        fn engine_type(&self) -> EngineType {
+            EngineType::Claude
+        }
+
+        fn detect<'a>(&'a self, bin: Option<&'a str>) -> EngineFuture<'a, EngineStatus> {
+            Box::pin(async move {
+                tokio::time::sleep(self.delay).await;
+                EngineStatus {
+                    engine_type: EngineType::Claude,
+                    installed: true,
+                    version: Some("fake".to_string()),
+                    bin_path: bin.map(str::to_string),
+                    home_dir: None,
+                    models: Vec::new(),
+                    default_model: None,
+                    features: EngineFeatures::claude(),
+                    error: None,
+                    detecting: false,
+                }
+            })
+        }
+
+        fn send<'a>(
+            &'a self,
+            _ctx: EngineContext<'a>,
+            _params: SendMessageParams,
+        ) -> EngineFuture<'a, Result<Value, String>> {
+            Box::pin(async { Err("unsupported".to_string()) })
+        }
+
+        fn interrupt<'a>(
+            &'a self,
+            _ctx: EngineContext<'a>,
+            _turn_id: Option<&'a str>,
+        ) -> EngineFuture<'a, Result<(), String>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn detection_goes_through_registered_adapters() {
+        let manager = EngineManager::new();
+        manager.register_adapter(Arc::new(FakeAdapter {
+            delay: Duration::ZERO,
+        }));
+        manager
+            .set_engine_config(
+                EngineType::Claude,
+                EngineConfig {
+                    bin_path: Some("/opt/fake/claude".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        let statuses = manager.detect_engines_with_gates(false, false).await;
+        let engines = statuses
+            .iter()
+            .map(|status| status.engine_type)
+            .collect::<Vec<_>>();
+        assert_eq!(engines, DETECTION_ORDER.to_vec());
+
+        assert!(statuses[0].installed);
+        assert_eq!(statuses[0].bin_path.as_deref(), Some("/opt/fake/claude"));
+        assert!(!statuses[1].installed);
+        assert_eq!(
+            statuses[1].error.as_deref(),
+            Some("Codex is not available in this build")
+        );
+        assert_eq!(
+            statuses[2].error.as_deref(),
+            Some(super::super::GEMINI_DISABLED_DIAGNOSTIC)
+        );
+
+        assert!(manager.require_adapter(EngineType::Claude).is_ok());
+        assert!(manager.require_adapter(EngineType::Codex).is_err());
+    }
+
+    #[tokio::test]
+    async fn budgeted_detection_reports_slow_engines_as_detecting() {
+        let manager = EngineManager::new();
+        manager.register_adapter(Arc::new(FakeAdapter {
+            delay: Duration::from_millis(200),
+        }));
+
+        let detection = manager
+            .detect_engines_within_budget(false, false, Duration::from_millis(20))
+            .await;
+        assert!(detection.statuses[0].detecting);
+        assert!(!detection.statuses[0].installed);
+        assert!(!detection.statuses[1].detecting);
+        assert!(manager
+            .get_engine_status(EngineType::Claude)
+            .await
+            .is_none());
+
+        let (engine_type, probe) = detection.pending.into_iter().next().expect("pending");
+        assert_eq!(engine_type, EngineType::Claude);
+        let status = probe.await.expect("probe finished");
+        assert!(status.installed);
+        manager.cache_engine_status(status).await;
+
+        let detection = manager
+            .detect_engines_within_budget(false, false, Duration::from_millis(20))
+            .await;
+        assert!(detection.statuses[0].detecting);
+        assert!(detection.statuses[0].installed);
+    }
+
+    #[tokio::test]
+    async fn turn_timeout_fires_only_while_armed() {
+        let manager = EngineManager::new();
+        let (fired_tx, mut fired_rx) = tokio::sync::mpsc::unbounded_channel();
+        for turn_id in ["finished", "interrupted", "stalled"] {
+            let fired_tx = fired_tx.clone();
+            manager.arm_turn_timeout("ws-1", turn_id, Duration::from_millis(20), async move {
+                let _ = fired_tx.send(turn_id);
+            });
+        }
+        manager.disarm_turn_timeout("finished");
+        manager.arm_turn_timeout("ws-2", "other", Duration::from_millis(20), async {});
+        manager.disarm_workspace_turn_timeouts("ws-2");
+        manager.disarm_turn_timeout("interrupted");
+        drop(fired_tx);
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert_eq!(fired_rx.recv().await, Some("stalled"));
+        assert_eq!(fired_rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn turn_timeout_armed_after_interrupt_does_not_fire() {
+        let manager = EngineManager::new();
+        let (fired_tx, mut fired_rx) = tokio::sync::mpsc::unbounded_channel();
+        manager.disarm_turn_timeout("interrupted-early");
+        manager.arm_turn_timeout("ws-1", "interrupted-early", Duration::from_millis(20), {
+            let fired_tx = fired_tx.clone();
+            async move {
+                let _ = fired_tx.send("interrupted-early");
+            }
+        });
+        manager.arm_turn_timeout("ws-1", "running", Duration::from_millis(20), async {});
+        manager.disarm_workspace_turn_timeouts("ws-1");
+        manager.arm_turn_timeout("ws-1", "running", Duration::from_millis(20), async move {
+            let _ = fired_tx.send("running");
+        });
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert_eq!(fired_rx.recv().await, None);
+    }
 
     #[tokio::test]
     async fn default_engine_is_claude() {
@@ -7,38 +7,50 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+pub(crate) mod adapter;
+pub(crate) mod api_engine;
 pub mod claude;
 pub mod claude_history;
 #[cfg(test)]
 mod claude_history_delete_tests;
 pub(crate) mod claude_history_entries;
+pub(crate) mod claude_history_export;
 #[cfg(test)]
 mod claude_history_issue529_tests;
 pub(crate) mod claude_history_large_payload;
 #[cfg(test)]
 mod claude_history_large_payload_tests;
+pub(crate) mod claude_history_rewind;
 pub(crate) mod claude_history_subagents;
 pub(crate) mod claude_message_content;
 pub(crate) mod codex_prompt_service;
 pub mod commands;
 pub(crate) mod error_mapper;
 pub mod events;
+pub(crate) mod forwarder_registry;
 pub mod gemini;
 pub mod gemini_history;
 pub(crate) mod gemini_proxy_guard;
 pub mod manager;
+pub(crate) mod model_routing;
 pub mod opencode;
+pub(crate) mod prompt_preflight;
 pub(crate) mod remote_bridge;
 pub mod rewind_commands;
 pub mod session_history_commands;
+pub mod session_search;
 pub mod status;
+pub(crate) mod turn_cwds;
+pub(crate) mod turn_launch;
 
 // Re-exports for convenience
 pub use commands::*;
 pub use manager::EngineManager;
 pub use rewind_commands::*;
 pub use session_history_commands::*;
+pub use session_search::*;
 pub use status::resolve_engine_type;
+pub(crate) use turn_launch::{start_engine_turn, EngineTurnLinkage};
 
 /// Supported engine types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -123,6 +135,7 @@ pub(crate) fn disabled_engine_status(engine_type: EngineType) -> EngineStatus {
         default_model: None,
         features,
         error: engine_disabled_diagnostic(engine_type).map(str::to_string),
+        detecting: false,
     }
 }
 
@@ -154,6 +167,10 @@ pub struct EngineStatus {
     pub features: EngineFeatures,
     /// Error message if detection failed
     pub error: Option<String>,
+    /// Detection overran its budget and is still running; the final status
+    /// arrives as an `engine-status-updated` event
+    #[serde(default)]
+    pub detecting: bool,
 }
 
 /// Model information
@@ -335,6 +352,14 @@ pub struct SendMessageParams {
     pub collaboration_mode: Option<Value>,
     /// Optional external OpenSpec root to expose for the session.
     pub custom_spec_root: Option<String>,
+    /// Working directory for this turn; must resolve inside the workspace root.
+    /// `None` runs in the workspace root.
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Interrupt the turn if it has not finished after this many seconds.
+    /// `None` leaves the turn unbounded.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
 }
 
 impl Default for SendMessageParams {
@@ -353,6 +378,8 @@ impl Default for SendMessageParams {
             variant: None,
             collaboration_mode: None,
             custom_spec_root: None,
+            cwd: None,
+            timeout_secs: None,
         }
     }
 }
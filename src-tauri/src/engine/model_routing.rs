@@ -0,0 +1,304 @@
+//! Per-turn choice between a fast and a premium model.
+//!
+//! When routing is enabled in settings, each outgoing prompt is classified by
+//! size, code content and whether it likely needs tools, and sent to the
+//! engine's fast or premium model accordingly. A message can force a tier or
+//! opt out of routing; the decision is returned with the turn metadata.
+
+use serde::{Deserialize, Serialize};
+
+use super::EngineType;
+use crate::types::{ModelRoutingSettings, ModelRoutingTiers};
+
+/// Prompt wording that usually means the agent will read, edit or run
+/// something in the workspace.
+const TOOL_INTENT_MARKERS: &[&str] = &[
+    "refactor",
+    "implement",
+    "fix ",
+    "debug",
+    "run ",
+    "build",
+    "test",
+    "install",
+    "migrate",
+    "rename",
+    "edit ",
+    "create a file",
+    "update the",
+    "commit",
+    "search the",
+    "find where",
+    "重构",
+    "实现",
+    "修复",
+    "运行",
+    "测试",
+    "修改",
+];
+const CODE_LINE_MARKERS: &[&str] = &[
+    "fn ",
+    "def ",
+    "class ",
+    "import ",
+    "const ",
+    "let ",
+    "function ",
+    "return ",
+    "#include",
+    "=>",
+    "};",
+    "</",
+];
+
+/// Per-message routing override sent by the composer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ModelRouteOverride {
+    /// Follow the routing policy (the default).
+    Auto,
+    Fast,
+    Premium,
+    /// Keep the model picked in the composer for this message.
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ModelTier {
+    Fast,
+    Premium,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptProfile {
+    chars: usize,
+    has_code: bool,
+    needs_tools: bool,
+    has_images: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelRoutingDecision {
+    tier: ModelTier,
+    model: String,
+    requested_model: Option<String>,
+    /// `true` when the tier came from the per-message override.
+    overridden: bool,
+    reasons: Vec<&'static str>,
+    profile: PromptProfile,
+}
+
+fn engine_key(engine: EngineType) -> &'static str {
+    match engine {
+        EngineType::Claude => "claude",
+        EngineType::Codex => "codex",
+        EngineType::Gemini => "gemini",
+        EngineType::OpenCode => "opencode",
+    }
+}
+
+fn looks_like_code(text: &str) -> bool {
+    if text.contains("```") {
+        return true;
+    }
+    let code_lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            CODE_LINE_MARKERS
+                .iter()
+                .any(|marker| line.starts_with(marker) || line.ends_with(marker))
+                || (line.ends_with(';') || line.ends_with('{'))
+        })
+        .count();
+    code_lines >= 2
+}
+
+fn classify_prompt(text: &str, has_images: bool) -> PromptProfile {
+    let lowered = text.to_lowercase();
+    let mentions_path = lowered.split_whitespace().any(|word| {
+        word.starts_with('@')
+            || (word.contains('/') && !word.starts_with("http"))
+            || word.rsplit_once('.').is_some_and(|(stem, ext)| {
+                !stem.is_empty()
+                    && matches!(
+                        ext,
+                        "rs" | "ts" | "tsx" | "js" | "py" | "go" | "java" | "json" | "toml" | "md"
+                    )
+            })
+    });
+    PromptProfile {
+        chars: text.chars().count(),
+        has_code: looks_like_code(text),
+        needs_tools: mentions_path
+            || TOOL_INTENT_MARKERS
+                .iter()
+                .any(|marker| lowered.contains(marker)),
+        has_images,
+    }
+}
+
+fn policy_tier(
+    policy: &ModelRoutingSettings,
+    profile: &PromptProfile,
+) -> (ModelTier, Vec<&'static str>) {
+    let mut reasons = Vec::new();
+    if profile.chars > policy.fast_max_chars {
+        reasons.push("long-prompt");
+    }
+    if policy.premium_for_code && profile.has_code {
+        reasons.push("contains-code");
+    }
+    if policy.premium_for_tools && profile.needs_tools {
+        reasons.push("needs-tools");
+    }
+    if profile.has_images {
+        reasons.push("has-images");
+    }
+    if reasons.is_empty() {
+        (ModelTier::Fast, vec!["short-chat"])
+    } else {
+        (ModelTier::Premium, reasons)
+    }
+}
+
+fn tier_model(tiers: &ModelRoutingTiers, tier: ModelTier) -> Option<String> {
+    let model = match tier {
+        ModelTier::Fast => &tiers.fast_model,
+        ModelTier::Premium => &tiers.premium_model,
+    };
+    Some(model.trim().to_string()).filter(|model| !model.is_empty())
+}
+
+/// Picks the model for one turn. Returns the requested model unchanged, with
+/// no decision, when routing does not apply: disabled without a forced tier,
+/// `off` override, or no model configured for the chosen tier.
+pub(crate) fn route_turn_model(
+    policy: &ModelRoutingSettings,
+    engine: EngineType,
+    text: &str,
+    images: Option<&[String]>,
+    requested_model: Option<String>,
+    route: Option<ModelRouteOverride>,
+) -> (Option<String>, Option<ModelRoutingDecision>) {
+    let route = route.unwrap_or(ModelRouteOverride::Auto);
+    let forced = match route {
+        ModelRouteOverride::Off => return (requested_model, None),
+        ModelRouteOverride::Auto if !policy.enabled => return (requested_model, None),
+        ModelRouteOverride::Auto => None,
+        ModelRouteOverride::Fast => Some(ModelTier::Fast),
+        ModelRouteOverride::Premium => Some(ModelTier::Premium),
+    };
+    let Some(tiers) = policy.engines.get(engine_key(engine)) else {
+        return (requested_model, None);
+    };
+    let has_images = images.is_some_and(|paths| paths.iter().any(|path| !path.trim().is_empty()));
+    let profile = classify_prompt(text, has_images);
+    let (tier, reasons) = match forced {
+        Some(tier) => (tier, vec!["override"]),
+        None => policy_tier(policy, &profile),
+    };
+    let Some(model) = tier_model(tiers, tier) else {
+        return (requested_model, None);
+    };
+    let decision = ModelRoutingDecision {
+        tier,
+        model: model.clone(),
+        requested_model,
+        overridden: forced.is_some(),
+        reasons,
+        profile,
+    };
+    (Some(model), Some(decision))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ModelRoutingSettings {
+        let mut policy = ModelRoutingSettings {
+            enabled: true,
+            ..ModelRoutingSettings::default()
+        };
+        policy.engines.insert(
+            "claude".to_string(),
+            ModelRoutingTiers {
+                fast_model: "claude-haiku-4-5".to_string(),
+                premium_model: "claude-opus-4-1".to_string(),
+            },
+        );
+        policy
+    }
+
+    fn route(
+        policy: &ModelRoutingSettings,
+        text: &str,
+        route: Option<ModelRouteOverride>,
+    ) -> (Option<String>, Option<ModelRoutingDecision>) {
+        route_turn_model(
+            policy,
+            EngineType::Claude,
+            text,
+            None,
+            Some("picked".to_string()),
+            route,
+        )
+    }
+
+    #[test]
+    fn routes_short_chat_to_fast_and_work_to_premium() {
+        let policy = policy();
+        let (model, decision) = route(&policy, "What does CRDT stand for?", None);
+        assert_eq!(model.as_deref(), Some("claude-haiku-4-5"));
+        let decision = decision.expect("decision");
+        assert_eq!(decision.tier, ModelTier::Fast);
+        assert_eq!(decision.requested_model.as_deref(), Some("picked"));
+
+        let (model, decision) = route(
+            &policy,
+            "Refactor src/engine/mod.rs to drop the clone",
+            None,
+        );
+        assert_eq!(model.as_deref(), Some("claude-opus-4-1"));
+        assert_eq!(decision.expect("decision").reasons, vec!["needs-tools"]);
+
+        let code = "Why does this fail?\n```rust\nlet x = 1;\n```";
+        let (_, decision) = route(&policy, code, None);
+        assert!(decision
+            .expect("decision")
+            .reasons
+            .contains(&"contains-code"));
+    }
+
+    #[test]
+    fn override_forces_tier_or_keeps_requested_model() {
+        let mut policy = policy();
+        let (model, decision) = route(&policy, "hi", Some(ModelRouteOverride::Off));
+        assert_eq!(model.as_deref(), Some("picked"));
+        assert!(decision.is_none());
+
+        policy.enabled = false;
+        assert!(route(&policy, "hi", None).1.is_none());
+        let (model, decision) = route(
+            &policy,
+            "Implement the parser",
+            Some(ModelRouteOverride::Fast),
+        );
+        assert_eq!(model.as_deref(), Some("claude-haiku-4-5"));
+        assert!(decision.expect("decision").overridden);
+
+        let (model, decision) = route_turn_model(
+            &policy,
+            EngineType::Gemini,
+            "hi",
+            None,
+            None,
+            Some(ModelRouteOverride::Premium),
+        );
+        assert!(model.is_none() && decision.is_none());
+    }
+}
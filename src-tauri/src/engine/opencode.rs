@@ -184,6 +184,16 @@ impl OpenCodeSession {
         );
     }
 
+    pub fn emit_turn_timeout(&self, turn_id: &str, timeout_secs: u64) {
+        self.emit_turn_event(
+            turn_id,
+            EngineEvent::TurnTimeout {
+                workspace_id: self.workspace_id.clone(),
+                timeout_secs,
+            },
+        );
+    }
+
     async fn set_session_id(&self, id: Option<String>) {
         *self.session_id.write().await = id;
     }
@@ -234,7 +244,13 @@ impl OpenCodeSession {
                 .to_string();
 
         let mut cmd = crate::backend::app_server::build_command_for_binary(&bin);
-        cmd.current_dir(&self.workspace_path);
+        cmd.current_dir(
+            params
+                .cwd
+                .as_deref()
+                .map(std::path::Path::new)
+                .unwrap_or(&self.workspace_path),
+        );
         cmd.arg("run");
         cmd.arg("--format");
         cmd.arg("json");
@@ -395,6 +411,7 @@ impl OpenCodeSession {
             EngineEvent::TurnStarted {
                 workspace_id: self.workspace_id.clone(),
                 turn_id: turn_id.to_string(),
+                cwd: effective_params.cwd.clone(),
             },
         );
 
@@ -688,7 +705,7 @@ impl OpenCodeSession {
     }
 }
 
-fn extract_session_id(event: &Value) -> Option<String> {
+pub(crate) fn extract_session_id(event: &Value) -> Option<String> {
     fn find_session_id(node: &Value) -> Option<String> {
         match node {
             Value::Object(map) => {
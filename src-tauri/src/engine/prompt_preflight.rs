@@ -0,0 +1,406 @@
+//! Static pre-send lint of a prompt against known provider constraints.
+//!
+//! The checks run before a message is dispatched so the composer can show a
+//! clear warning instead of an opaque provider 4xx (context overflow, images
+//! sent to a text-only model, too many attachments).
+
+use serde::Serialize;
+use tauri::State;
+
+use super::EngineType;
+use crate::state::AppState;
+
+/// Share of the context window above which a warning is raised.
+const CONTEXT_WARNING_RATIO: f64 = 0.8;
+/// Rough per-image token cost used for the estimate (a ~1.1 MP image).
+const ESTIMATED_TOKENS_PER_IMAGE: u64 = 1_600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ModelConstraints {
+    context_window: u64,
+    supports_images: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EngineAttachmentLimits {
+    max_images: usize,
+    max_image_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PreflightSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PreflightIssue {
+    severity: PreflightSeverity,
+    code: &'static str,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptPreflightReport {
+    engine: EngineType,
+    model: Option<String>,
+    estimated_tokens: u64,
+    context_window: Option<u64>,
+    supports_images: Option<bool>,
+    /// `false` when at least one issue is an error; the send would fail.
+    ok: bool,
+    issues: Vec<PreflightIssue>,
+}
+
+/// Known context windows and modalities, matched by model id prefix or
+/// family. Unknown models skip the model-specific checks.
+fn model_constraints(model: &str) -> Option<ModelConstraints> {
+    let id = model.trim().to_ascii_lowercase();
+    let id = id.rsplit('/').next().unwrap_or(&id);
+    let constraints = |context_window, supports_images| {
+        Some(ModelConstraints {
+            context_window,
+            supports_images,
+        })
+    };
+    let family = id.strip_suffix("[1m]").unwrap_or(id);
+    if id.starts_with("claude") || matches!(family, "opus" | "sonnet" | "haiku") {
+        let context_window = if id.contains("[1m]") {
+            1_000_000
+        } else {
+            200_000
+        };
+        return constraints(context_window, true);
+    }
+    if id.starts_with("gemini") {
+        return constraints(1_048_576, true);
+    }
+    if id.starts_with("gpt-4.1") {
+        return constraints(1_047_576, true);
+    }
+    if id.starts_with("gpt-5") {
+        return constraints(400_000, true);
+    }
+    if id.starts_with("o3") || id.starts_with("o4") || id.starts_with("codex-mini") {
+        return constraints(200_000, true);
+    }
+    if id.starts_with("gpt-4o") {
+        return constraints(128_000, true);
+    }
+    if id.starts_with("glm-4.5v") {
+        return constraints(65_536, true);
+    }
+    if id.starts_with("glm") || id.starts_with("deepseek") {
+        return constraints(128_000, false);
+    }
+    if id.starts_with("kimi") {
+        return constraints(131_072, false);
+    }
+    if id.starts_with("qwen") && id.contains("coder") {
+        return constraints(262_144, false);
+    }
+    None
+}
+
+fn engine_attachment_limits(engine: EngineType) -> EngineAttachmentLimits {
+    match engine {
+        EngineType::Claude => EngineAttachmentLimits {
+            max_images: 100,
+            max_image_bytes: 5 * 1024 * 1024,
+        },
+        EngineType::Codex | EngineType::OpenCode => EngineAttachmentLimits {
+            max_images: 50,
+            max_image_bytes: 20 * 1024 * 1024,
+        },
+        EngineType::Gemini => EngineAttachmentLimits {
+            max_images: 100,
+            max_image_bytes: 20 * 1024 * 1024,
+        },
+    }
+}
+
+/// Approximates tokens as ~4 characters for Latin text and one token per
+/// CJK/wide character, which is close enough for a preflight threshold.
+fn estimate_text_tokens(text: &str) -> u64 {
+    let mut narrow_chars = 0u64;
+    let mut wide_chars = 0u64;
+    for ch in text.chars() {
+        if (ch as u32) >= 0x2E80 {
+            wide_chars += 1;
+        } else {
+            narrow_chars += 1;
+        }
+    }
+    wide_chars + narrow_chars.div_ceil(4)
+}
+
+/// Size in bytes of an image reference, which is either a `data:` URL or a
+/// local path.
+fn image_size(image: &str) -> Result<u64, String> {
+    if let Some(rest) = image.strip_prefix("data:") {
+        let payload = rest.split_once(',').map(|(_, data)| data).unwrap_or("");
+        return Ok(payload.len() as u64 * 3 / 4);
+    }
+    std::fs::metadata(image)
+        .map(|metadata| metadata.len())
+        .map_err(|error| error.to_string())
+}
+
+fn format_bytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+fn build_report(
+    engine: EngineType,
+    model: Option<String>,
+    text: &str,
+    images: &[String],
+    context_tokens: u64,
+) -> PromptPreflightReport {
+    let mut issues = Vec::new();
+    let mut push = |severity, code, message: String| {
+        issues.push(PreflightIssue {
+            severity,
+            code,
+            message,
+        });
+    };
+    let images: Vec<&str> = images
+        .iter()
+        .map(|image| image.trim())
+        .filter(|image| !image.is_empty())
+        .collect();
+    if text.trim().is_empty() && images.is_empty() {
+        push(
+            PreflightSeverity::Error,
+            "empty-prompt",
+            "The message is empty.".to_string(),
+        );
+    }
+
+    let constraints = model.as_deref().and_then(model_constraints);
+    let estimated_tokens = context_tokens
+        .saturating_add(estimate_text_tokens(text))
+        .saturating_add(images.len() as u64 * ESTIMATED_TOKENS_PER_IMAGE);
+    match (&model, constraints) {
+        (_, Some(constraints)) => {
+            let window = constraints.context_window;
+            if estimated_tokens > window {
+                push(
+                    PreflightSeverity::Error,
+                    "context-overflow",
+                    format!(
+                        "Estimated {estimated_tokens} tokens exceed the model context window of {window}."
+                    ),
+                );
+            } else if estimated_tokens as f64 > window as f64 * CONTEXT_WARNING_RATIO {
+                push(
+                    PreflightSeverity::Warning,
+                    "context-near-limit",
+                    format!(
+                        "Estimated {estimated_tokens} tokens use more than {}% of the {window}-token context window.",
+                        (CONTEXT_WARNING_RATIO * 100.0) as u32
+                    ),
+                );
+            }
+            if !images.is_empty() && !constraints.supports_images {
+                push(
+                    PreflightSeverity::Error,
+                    "images-unsupported",
+                    format!(
+                        "{} does not accept image input; remove the {} attached image(s).",
+                        model.as_deref().unwrap_or("The selected model"),
+                        images.len()
+                    ),
+                );
+            }
+        }
+        (Some(model), None) => push(
+            PreflightSeverity::Info,
+            "unknown-model",
+            format!("No known limits for model {model}; only generic checks were run."),
+        ),
+        (None, None) => {}
+    }
+
+    let limits = engine_attachment_limits(engine);
+    if images.len() > limits.max_images {
+        push(
+            PreflightSeverity::Error,
+            "too-many-attachments",
+            format!(
+                "{} images attached; {} accepts at most {} per message.",
+                images.len(),
+                engine.display_name(),
+                limits.max_images
+            ),
+        );
+    }
+    for image in &images {
+        let label = if image.starts_with("data:") {
+            "pasted image".to_string()
+        } else {
+            (*image).to_string()
+        };
+        match image_size(image) {
+            Ok(size) if size > limits.max_image_bytes => push(
+                PreflightSeverity::Error,
+                "image-too-large",
+                format!(
+                    "{label} is {}; {} rejects images over {}.",
+                    format_bytes(size),
+                    engine.display_name(),
+                    format_bytes(limits.max_image_bytes)
+                ),
+            ),
+            Ok(_) => {}
+            Err(error) => push(
+                PreflightSeverity::Error,
+                "image-unreadable",
+                format!("{label} cannot be read: {error}"),
+            ),
+        }
+    }
+
+    PromptPreflightReport {
+        engine,
+        ok: !issues
+            .iter()
+            .any(|issue| issue.severity == PreflightSeverity::Error),
+        model,
+        estimated_tokens,
+        context_window: constraints.map(|item| item.context_window),
+        supports_images: constraints.map(|item| item.supports_images),
+        issues,
+    }
+}
+
+/// Lints a prompt before dispatch. `context_tokens` is the conversation's
+/// current context usage as last reported by the engine.
+#[tauri::command]
+pub(crate) async fn engine_prompt_preflight(
+    text: String,
+    engine: Option<EngineType>,
+    model: Option<String>,
+    images: Option<Vec<String>>,
+    context_tokens: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<PromptPreflightReport, String> {
+    let manager = &state.engine_manager;
+    let engine = match engine {
+        Some(engine) => engine,
+        None => manager.get_active_engine().await,
+    };
+    let model = match model
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    {
+        Some(model) => Some(model),
+        None => manager.get_engine_status(engine).await.and_then(|status| {
+            status
+                .models
+                .iter()
+                .find(|model| model.default)
+                .map(|model| model.id.clone())
+        }),
+    };
+    let images = images.unwrap_or_default();
+    Ok(build_report(
+        engine,
+        model,
+        &text,
+        &images,
+        context_tokens.unwrap_or(0),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(report: &PromptPreflightReport) -> Vec<&'static str> {
+        report.issues.iter().map(|issue| issue.code).collect()
+    }
+
+    #[test]
+    fn estimates_latin_and_cjk_text() {
+        assert_eq!(estimate_text_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_text_tokens("你好"), 2);
+        assert_eq!(estimate_text_tokens(""), 0);
+    }
+
+    #[test]
+    fn flags_context_overflow_and_near_limit() {
+        let report = build_report(
+            EngineType::Claude,
+            Some("claude-sonnet-4-5".into()),
+            "hello",
+            &[],
+            199_999,
+        );
+        assert!(!report.ok);
+        assert_eq!(codes(&report), vec!["context-overflow"]);
+
+        let report = build_report(
+            EngineType::Claude,
+            Some("claude-sonnet-4-5[1m]".into()),
+            "hello",
+            &[],
+            900_000,
+        );
+        assert!(report.ok);
+        assert_eq!(codes(&report), vec!["context-near-limit"]);
+
+        let report = build_report(
+            EngineType::Claude,
+            Some("opus[1m]".into()),
+            "hello",
+            &[],
+            300_000,
+        );
+        assert_eq!(report.context_window, Some(1_000_000));
+        assert!(report.ok);
+    }
+
+    #[test]
+    fn rejects_images_for_text_only_models_and_missing_files() {
+        let report = build_report(
+            EngineType::OpenCode,
+            Some("deepseek/deepseek-chat".into()),
+            "describe",
+            &["data:image/png;base64,AAAA".to_string()],
+            0,
+        );
+        assert_eq!(codes(&report), vec!["images-unsupported"]);
+
+        let report = build_report(
+            EngineType::Codex,
+            Some("gpt-5-codex".into()),
+            "",
+            &["/definitely/missing/image.png".to_string()],
+            0,
+        );
+        assert_eq!(report.supports_images, Some(true));
+        assert_eq!(codes(&report), vec!["image-unreadable"]);
+    }
+
+    #[test]
+    fn oversized_images_block_the_send() {
+        let image = format!("data:image/png;base64,{}", "A".repeat(8 * 1024 * 1024));
+        let report = build_report(EngineType::Claude, None, "look", &[image], 0);
+        assert_eq!(codes(&report), vec!["image-too-large"]);
+        assert!(!report.ok);
+    }
+
+    #[test]
+    fn limits_attachment_count_per_engine() {
+        let images = vec!["data:image/png;base64,AAAA".to_string(); 51];
+        let report = build_report(EngineType::Codex, None, "look", &images, 0);
+        assert_eq!(codes(&report), vec!["too-many-attachments"]);
+        assert_eq!(report.context_window, None);
+    }
+}
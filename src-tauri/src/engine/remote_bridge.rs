@@ -36,6 +36,7 @@ pub(crate) fn remote_engine_send_message_sync_request(
     agent: Option<String>,
     variant: Option<String>,
     custom_spec_root: Option<String>,
+    cwd: Option<String>,
 ) -> (&'static str, Value) {
     let images = images.map(|paths| {
         paths
@@ -60,6 +61,7 @@ pub(crate) fn remote_engine_send_message_sync_request(
             "agent": agent,
             "variant": variant,
             "customSpecRoot": custom_spec_root,
+            "cwd": cwd,
         }),
     )
 }
@@ -102,6 +104,7 @@ mod tests {
             None,
             None,
             Some("/tmp/spec-root".to_string()),
+            Some("packages/app".to_string()),
         );
 
         assert_eq!(method, "engine_send_message_sync");
@@ -112,6 +115,7 @@ mod tests {
         assert_eq!(params["disableThinking"], true);
         assert_eq!(params["images"], json!(["/home/demo/shot.png"]));
         assert_eq!(params["customSpecRoot"], "/tmp/spec-root");
+        assert_eq!(params["cwd"], "packages/app");
     }
 
     #[test]
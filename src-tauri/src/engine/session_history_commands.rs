@@ -4,6 +4,9 @@ use tauri::{AppHandle, State};
 use crate::remote_backend;
 use crate::state::AppState;
 
+use super::claude_history_export::{
+    export_images_dir, render_claude_session_markdown, ClaudeExportImages,
+};
 use super::remote_bridge::call_remote_typed;
 use super::EngineType;
 
@@ -182,6 +185,85 @@ pub async fn delete_claude_session(
         .await
 }
 
+/// Export a Claude Code session as Markdown or as its normalized message
+/// list (JSON). Writes to the downloads dir unless `output_path` is given.
+/// Markdown exports write images next to the file unless `embed_images`.
+#[tauri::command]
+pub async fn export_claude_session(
+    session_id: String,
+    workspace_id: String,
+    format: String,
+    output_path: Option<String>,
+    embed_images: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Exporting Claude sessions is not available in remote mode".to_string());
+    }
+    let extension = match format.as_str() {
+        "markdown" => "md",
+        "json" => "json",
+        _ => return Err(format!("Unsupported export format: {format}")),
+    };
+    let workspace_path = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .map(|w| std::path::PathBuf::from(&w.path))
+            .ok_or_else(|| "Workspace not found".to_string())?
+    };
+    let config = state
+        .engine_manager
+        .get_engine_config(EngineType::Claude)
+        .await;
+    let session = super::claude_history::load_claude_session_with_config(
+        &workspace_path,
+        &session_id,
+        config.as_ref(),
+    )
+    .await?;
+    let file_name = format!("claude-{session_id}.{extension}");
+    let target_path = if let Some(path) = output_path {
+        std::path::PathBuf::from(path)
+    } else if let Some(downloads) = dirs::download_dir() {
+        downloads.join(file_name)
+    } else {
+        std::path::PathBuf::from(file_name)
+    };
+
+    let contents = if extension == "json" {
+        serde_json::to_string_pretty(&session.messages).map_err(|error| error.to_string())?
+    } else {
+        let mut image_sources = Vec::with_capacity(session.messages.len());
+        for message in &session.messages {
+            let mut sources = message.images.clone().unwrap_or_default();
+            for deferred in message.deferred_images.iter().flatten() {
+                let image = super::claude_history::hydrate_claude_deferred_image_with_config(
+                    &workspace_path,
+                    deferred.locator.clone(),
+                    config.as_ref(),
+                )
+                .await?;
+                sources.push(image.src);
+            }
+            image_sources.push(sources);
+        }
+        let images = if embed_images.unwrap_or(false) {
+            ClaudeExportImages::Embed
+        } else {
+            let (dir, link_prefix) = export_images_dir(&target_path);
+            ClaudeExportImages::Files { dir, link_prefix }
+        };
+        render_claude_session_markdown(&session_id, &session.messages, &image_sources, &images)?
+    };
+    std::fs::write(&target_path, contents)
+        .map_err(|e| format!("Failed to write export file: {}", e))?;
+    Ok(json!({
+        "sessionId": session_id,
+        "filePath": target_path.to_string_lossy().to_string(),
+    }))
+}
+
 /// List Gemini CLI session history for a workspace path.
 #[tauri::command]
 pub async fn list_gemini_sessions(
@@ -0,0 +1,623 @@
+//! Full-text search over the local Claude, Codex and OpenCode session
+//! histories.
+//!
+//! Files are read line by line, newest first, and each engine stops scanning
+//! once it has `limit` hits. A session contributes at most one hit: the first
+//! message that matches.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tauri::State;
+use tokio::task;
+
+use crate::local_usage::{
+    collect_jsonl_files, extract_cwd, path_matches_workspace, read_timestamp_ms,
+    resolve_sessions_roots,
+};
+use crate::state::AppState;
+
+use super::EngineType;
+
+const SESSION_SEARCH_DEFAULT_LIMIT: usize = 20;
+const SESSION_SEARCH_MAX_LIMIT: usize = 200;
+/// Lines this long are inlined images or tool payloads, not conversation.
+const SESSION_SEARCH_MAX_LINE_BYTES: usize = 512_000;
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+const SNIPPET_MAX_CHARS: usize = 160;
+const SESSION_TITLE_MAX_CHARS: usize = 80;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionSearchMatch {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSearchHit {
+    pub engine: EngineType,
+    pub session_id: String,
+    pub title: Option<String>,
+    pub snippet: String,
+    /// Match ranges in `snippet`, in UTF-16 code units.
+    pub matches: Vec<SessionSearchMatch>,
+    pub timestamp: Option<i64>,
+}
+
+/// Case-insensitive matcher for the search query.
+struct QueryMatcher {
+    needle: Vec<char>,
+}
+
+fn chars_eq_ignore_case(left: char, right: char) -> bool {
+    left == right || left.to_lowercase().eq(right.to_lowercase())
+}
+
+impl QueryMatcher {
+    fn new(query: &str) -> Option<Self> {
+        let needle = query
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .collect::<Vec<_>>();
+        (!needle.is_empty()).then_some(Self { needle })
+    }
+
+    /// Byte range of the first match at or after `from`.
+    fn find(&self, text: &str, from: usize) -> Option<(usize, usize)> {
+        for (offset, _) in text[from..].char_indices() {
+            let start = from + offset;
+            let mut haystack = text[start..].char_indices();
+            let mut end = start;
+            let matched = self.needle.iter().all(|expected| match haystack.next() {
+                Some((index, actual)) if chars_eq_ignore_case(actual, *expected) => {
+                    end = start + index + actual.len_utf8();
+                    true
+                }
+                _ => false,
+            });
+            if matched {
+                return Some((start, end));
+            }
+        }
+        None
+    }
+
+    /// A window of `text` around the first match, with every match inside it.
+    fn snippet(&self, text: &str) -> Option<(String, Vec<SessionSearchMatch>)> {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let (match_start, match_end) = self.find(&text, 0)?;
+        let start = text[..match_start]
+            .char_indices()
+            .rev()
+            .nth(SNIPPET_CONTEXT_CHARS - 1)
+            .map(|(index, _)| index)
+            .unwrap_or(0);
+        let end = text[start..]
+            .char_indices()
+            .nth(SNIPPET_MAX_CHARS)
+            .map(|(index, _)| (start + index).max(match_end))
+            .unwrap_or(text.len());
+
+        let mut snippet = String::new();
+        if start > 0 {
+            snippet.push('…');
+        }
+        let body_offset = snippet.len();
+        snippet.push_str(&text[start..end]);
+        let mut matches = Vec::new();
+        let mut from = body_offset;
+        while let Some((found_start, found_end)) = self.find(&snippet, from) {
+            matches.push(SessionSearchMatch {
+                start: snippet[..found_start].encode_utf16().count(),
+                end: snippet[..found_end].encode_utf16().count(),
+            });
+            from = found_end;
+        }
+        if end < text.len() {
+            snippet.push('…');
+        }
+        Some((snippet, matches))
+    }
+}
+
+fn session_title(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= SESSION_TITLE_MAX_CHARS {
+        return Some(text);
+    }
+    let truncated: String = text.chars().take(SESSION_TITLE_MAX_CHARS).collect();
+    Some(format!("{truncated}…"))
+}
+
+/// Text of a message `content` field: a plain string or an array of text
+/// parts. Tool calls and results are not searched.
+fn message_text(content: &Value) -> Option<String> {
+    if let Some(text) = content.as_str() {
+        return Some(text.to_string());
+    }
+    let parts = content
+        .as_array()?
+        .iter()
+        .filter(|part| {
+            matches!(
+                part.get("type").and_then(Value::as_str),
+                Some("text" | "input_text" | "output_text")
+            )
+        })
+        .filter_map(|part| part.get("text").and_then(Value::as_str))
+        .collect::<Vec<_>>();
+    (!parts.is_empty()).then(|| parts.join("\n"))
+}
+
+/// Parsed JSON lines of a history file. A file that cannot be read or holds
+/// a line that is not JSON is logged and abandoned at that point.
+fn history_lines(path: &Path) -> impl Iterator<Item = Value> + '_ {
+    let reader = match File::open(path) {
+        Ok(file) => Some(BufReader::new(file)),
+        Err(error) => {
+            log::warn!(
+                "Skipping unreadable session history {}: {}",
+                path.display(),
+                error
+            );
+            None
+        }
+    };
+    reader
+        .into_iter()
+        .flat_map(|reader| reader.lines())
+        .map_while(move |line| match line {
+            Ok(line) => Some(line),
+            Err(error) => {
+                log::warn!(
+                    "Skipping unreadable session history {}: {}",
+                    path.display(),
+                    error
+                );
+                None
+            }
+        })
+        .filter(|line| !line.trim().is_empty() && line.len() <= SESSION_SEARCH_MAX_LINE_BYTES)
+        .map_while(move |line| match serde_json::from_str::<Value>(&line) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                log::warn!(
+                    "Skipping corrupt session history {}: {}",
+                    path.display(),
+                    error
+                );
+                None
+            }
+        })
+}
+
+fn read_json_file(path: &Path) -> Option<Value> {
+    let data = fs::read_to_string(path)
+        .map_err(|error| error.to_string())
+        .and_then(|data| serde_json::from_str(&data).map_err(|error| error.to_string()));
+    match data {
+        Ok(value) => Some(value),
+        Err(error) => {
+            log::warn!(
+                "Skipping corrupt session history {}: {}",
+                path.display(),
+                error
+            );
+            None
+        }
+    }
+}
+
+fn sort_newest_first(paths: &mut [PathBuf]) {
+    paths.sort_by_cached_key(|path| {
+        std::cmp::Reverse(
+            fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH),
+        )
+    });
+}
+
+fn json_files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+/// Top-level Claude transcripts; subagent transcripts are not searched.
+fn claude_search_files(base_dir: &Path, workspace_path: Option<&Path>) -> Vec<PathBuf> {
+    let dirs = match workspace_path {
+        Some(workspace_path) => {
+            super::claude_history::claude_project_dirs_for_path(base_dir, workspace_path)
+        }
+        None => super::claude_history::all_claude_project_dirs(base_dir),
+    };
+    let mut files = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        files.extend(entries.flatten().map(|entry| entry.path()).filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(".jsonl") && !name.starts_with("agent-"))
+        }));
+    }
+    sort_newest_first(&mut files);
+    files
+}
+
+fn search_claude_file(path: &Path, matcher: &QueryMatcher) -> Option<SessionSearchHit> {
+    let session_id = path.file_stem()?.to_str()?.to_string();
+    let mut title = None;
+    for entry in history_lines(path) {
+        let role = entry.get("type").and_then(Value::as_str);
+        if !matches!(role, Some("user" | "assistant")) {
+            continue;
+        }
+        let Some(text) = entry
+            .get("message")
+            .and_then(|message| message.get("content"))
+            .and_then(message_text)
+        else {
+            continue;
+        };
+        if role == Some("user") && title.is_none() {
+            title = session_title(&text);
+        }
+        if let Some((snippet, matches)) = matcher.snippet(&text) {
+            return Some(SessionSearchHit {
+                engine: EngineType::Claude,
+                session_id,
+                title,
+                snippet,
+                matches,
+                timestamp: read_timestamp_ms(&entry),
+            });
+        }
+    }
+    None
+}
+
+fn codex_search_files(roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut seen = HashSet::new();
+    for root in roots {
+        collect_jsonl_files(root, &mut files, &mut seen);
+    }
+    sort_newest_first(&mut files);
+    files
+}
+
+fn search_codex_file(
+    path: &Path,
+    workspace_path: Option<&Path>,
+    matcher: &QueryMatcher,
+) -> Option<SessionSearchHit> {
+    let mut session_id = path.file_stem()?.to_str()?.to_string();
+    let mut title = None;
+    for entry in history_lines(path) {
+        let payload = entry.get("payload");
+        match entry.get("type").and_then(Value::as_str) {
+            Some("session_meta") => {
+                if let Some(id) = payload.and_then(|payload| payload.get("id")) {
+                    session_id = id.as_str().unwrap_or(&session_id).to_string();
+                }
+                if let (Some(filter), Some(cwd)) = (workspace_path, extract_cwd(&entry)) {
+                    if !path_matches_workspace(&cwd, filter) {
+                        return None;
+                    }
+                }
+            }
+            Some("response_item") => {
+                let Some(payload) = payload.filter(|payload| {
+                    payload.get("type").and_then(Value::as_str) == Some("message")
+                }) else {
+                    continue;
+                };
+                let role = payload.get("role").and_then(Value::as_str);
+                if !matches!(role, Some("user" | "assistant")) {
+                    continue;
+                }
+                // Injected instructions and environment context are wrapped
+                // in tags; only search what the user and model wrote.
+                let Some(text) = payload
+                    .get("content")
+                    .and_then(message_text)
+                    .filter(|text| !text.trim_start().starts_with('<'))
+                else {
+                    continue;
+                };
+                if role == Some("user") && title.is_none() {
+                    title = session_title(&text);
+                }
+                if let Some((snippet, matches)) = matcher.snippet(&text) {
+                    return Some(SessionSearchHit {
+                        engine: EngineType::Codex,
+                        session_id,
+                        title,
+                        snippet,
+                        matches,
+                        timestamp: read_timestamp_ms(&entry),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+struct OpenCodeStoredSession {
+    id: String,
+    title: Option<String>,
+    updated: i64,
+    storage: PathBuf,
+}
+
+/// Sessions from OpenCode's file storage (`storage/session/<project>/*.json`).
+fn opencode_sessions(
+    roots: &[PathBuf],
+    workspace_path: Option<&Path>,
+) -> Vec<OpenCodeStoredSession> {
+    let mut sessions = Vec::new();
+    let mut seen = HashSet::new();
+    for root in roots {
+        let storage = root.join("storage");
+        let Ok(projects) = fs::read_dir(storage.join("session")) else {
+            continue;
+        };
+        for project in projects.flatten() {
+            for path in json_files_in(&project.path()) {
+                let Some(session) = read_json_file(&path) else {
+                    continue;
+                };
+                let Some(id) = session.get("id").and_then(Value::as_str) else {
+                    continue;
+                };
+                if let Some(filter) = workspace_path {
+                    let directory = session
+                        .get("directory")
+                        .and_then(Value::as_str)
+                        .unwrap_or("");
+                    if !path_matches_workspace(directory, filter) {
+                        continue;
+                    }
+                }
+                if !seen.insert(id.to_string()) {
+                    continue;
+                }
+                let time = session.get("time");
+                sessions.push(OpenCodeStoredSession {
+                    id: id.to_string(),
+                    title: session
+                        .get("title")
+                        .and_then(Value::as_str)
+                        .and_then(session_title),
+                    updated: time
+                        .and_then(|time| time.get("updated").or_else(|| time.get("created")))
+                        .and_then(Value::as_i64)
+                        .unwrap_or(0),
+                    storage: storage.clone(),
+                });
+            }
+        }
+    }
+    sessions.sort_by(|left, right| right.updated.cmp(&left.updated));
+    sessions
+}
+
+fn search_opencode_session(
+    session: OpenCodeStoredSession,
+    matcher: &QueryMatcher,
+) -> Option<SessionSearchHit> {
+    for message_path in json_files_in(&session.storage.join("message").join(&session.id)) {
+        let Some(message) = read_json_file(&message_path) else {
+            continue;
+        };
+        let Some(message_id) = message.get("id").and_then(Value::as_str) else {
+            continue;
+        };
+        let parts = json_files_in(&session.storage.join("part").join(message_id))
+            .iter()
+            .filter_map(|path| read_json_file(path))
+            .collect::<Vec<_>>();
+        let Some(text) = message_text(&Value::Array(parts)) else {
+            continue;
+        };
+        if let Some((snippet, matches)) = matcher.snippet(&text) {
+            return Some(SessionSearchHit {
+                engine: EngineType::OpenCode,
+                session_id: session.id,
+                title: session.title,
+                snippet,
+                matches,
+                timestamp: message
+                    .get("time")
+                    .and_then(|time| time.get("created"))
+                    .and_then(Value::as_i64),
+            });
+        }
+    }
+    None
+}
+
+fn take_hits<T>(
+    items: impl IntoIterator<Item = T>,
+    limit: usize,
+    search: impl Fn(T) -> Option<SessionSearchHit>,
+) -> Vec<SessionSearchHit> {
+    items.into_iter().filter_map(search).take(limit).collect()
+}
+
+/// Searches message text across local session histories. Engines without a
+/// local history store (Gemini) are ignored; remote sessions are not searched.
+#[tauri::command]
+pub async fn search_sessions(
+    query: String,
+    engines: Option<Vec<EngineType>>,
+    workspace_id: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SessionSearchHit>, String> {
+    let matcher = QueryMatcher::new(&query).ok_or("Search query is required.")?;
+    let limit = limit
+        .unwrap_or(SESSION_SEARCH_DEFAULT_LIMIT)
+        .clamp(1, SESSION_SEARCH_MAX_LIMIT);
+    let engines = engines
+        .unwrap_or_else(|| vec![EngineType::Claude, EngineType::Codex, EngineType::OpenCode]);
+    let (workspace_path, codex_roots) = {
+        let workspaces = state.workspaces.lock().await;
+        let workspace_path = match workspace_id {
+            Some(workspace_id) => Some(PathBuf::from(
+                &workspaces
+                    .get(&workspace_id)
+                    .ok_or("workspace not found")?
+                    .path,
+            )),
+            None => None,
+        };
+        let codex_roots = resolve_sessions_roots(&workspaces, workspace_path.as_deref());
+        (workspace_path, codex_roots)
+    };
+    let claude_base_dir = crate::claude_home::resolve_claude_projects_dir(
+        state
+            .engine_manager
+            .get_engine_config(EngineType::Claude)
+            .await
+            .as_ref(),
+    );
+    let opencode_roots = super::commands::opencode_data_candidate_roots(
+        workspace_path.as_deref().unwrap_or(Path::new("")),
+        state
+            .engine_manager
+            .get_engine_config(EngineType::OpenCode)
+            .await
+            .as_ref(),
+    )
+    .into_iter()
+    .filter(|root| root.is_absolute())
+    .collect::<Vec<_>>();
+
+    let mut hits = task::spawn_blocking(move || {
+        let workspace_path = workspace_path.as_deref();
+        let mut hits: HashMap<EngineType, Vec<SessionSearchHit>> = HashMap::new();
+        for engine in engines {
+            if hits.contains_key(&engine) {
+                continue;
+            }
+            let engine_hits = match engine {
+                EngineType::Claude => claude_base_dir
+                    .as_deref()
+                    .map(|base_dir| {
+                        take_hits(
+                            claude_search_files(base_dir, workspace_path),
+                            limit,
+                            |path| search_claude_file(&path, &matcher),
+                        )
+                    })
+                    .unwrap_or_default(),
+                EngineType::Codex => take_hits(codex_search_files(&codex_roots), limit, |path| {
+                    search_codex_file(&path, workspace_path, &matcher)
+                }),
+                EngineType::OpenCode => take_hits(
+                    opencode_sessions(&opencode_roots, workspace_path),
+                    limit,
+                    |session| search_opencode_session(session, &matcher),
+                ),
+                EngineType::Gemini => Vec::new(),
+            };
+            hits.insert(engine, engine_hits);
+        }
+        hits.into_values().flatten().collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|_| "session search failed".to_string())?;
+    hits.sort_by(|left, right| right.timestamp.cmp(&left.timestamp));
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_snippets_with_utf16_match_offsets() {
+        let matcher = QueryMatcher::new("  Retry   logic ").expect("matcher");
+        let text = format!(
+            "{} 🙂 the retry\nLOGIC fails; retry logic again",
+            "word ".repeat(20)
+        );
+        let (snippet, matches) = matcher.snippet(&text).expect("snippet");
+        assert!(snippet.starts_with('…'));
+        let utf16 = snippet.encode_utf16().collect::<Vec<_>>();
+        let matched = matches
+            .iter()
+            .map(|range| String::from_utf16(&utf16[range.start..range.end]).expect("utf16"))
+            .collect::<Vec<_>>();
+        assert_eq!(matched, vec!["retry LOGIC", "retry logic"]);
+        assert!(matcher.snippet("no match here").is_none());
+        assert!(QueryMatcher::new("   ").is_none());
+    }
+
+    #[test]
+    fn searches_claude_and_codex_files_skipping_corrupt_ones() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create dir");
+        let claude = root.join("claude-session.jsonl");
+        fs::write(
+            &claude,
+            [
+                r#"{"type":"user","message":{"role":"user","content":"Fix the flaky test"},"timestamp":"2026-01-02T03:04:05Z"}"#,
+                r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash"},{"type":"text","text":"The flaky test races the watcher."}]},"timestamp":"2026-01-02T03:05:05Z"}"#,
+            ]
+            .join("\n"),
+        )
+        .expect("write claude");
+        let corrupt = root.join("corrupt.jsonl");
+        fs::write(&corrupt, "{not json\n").expect("write corrupt");
+        let codex = root.join("rollout-1.jsonl");
+        fs::write(
+            &codex,
+            [
+                r#"{"type":"session_meta","payload":{"id":"thread-1","cwd":"/work/app"}}"#,
+                r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"<environment_context>watcher</environment_context>"}]}}"#,
+                r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Why does the watcher race?"}]},"timestamp":"2026-01-03T00:00:00Z"}"#,
+            ]
+            .join("\n"),
+        )
+        .expect("write codex");
+
+        let matcher = QueryMatcher::new("watcher").expect("matcher");
+        let hit = search_claude_file(&claude, &matcher).expect("claude hit");
+        assert_eq!(hit.session_id, "claude-session");
+        assert_eq!(hit.title.as_deref(), Some("Fix the flaky test"));
+        assert_eq!(hit.snippet, "The flaky test races the watcher.");
+        assert_eq!(hit.timestamp, Some(1_767_323_105_000));
+        assert!(search_claude_file(&corrupt, &matcher).is_none());
+
+        let hit = search_codex_file(&codex, None, &matcher).expect("codex hit");
+        assert_eq!(hit.session_id, "thread-1");
+        assert_eq!(hit.snippet, "Why does the watcher race?");
+        assert!(search_codex_file(&codex, Some(Path::new("/work/other")), &matcher).is_none());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -119,6 +119,7 @@ fn not_installed_status(engine_type: EngineType, error: Option<String>) -> Engin
         default_model: None,
         features: EngineFeatures::default(),
         error,
+        detecting: false,
     }
 }
 
@@ -160,6 +161,7 @@ pub async fn detect_claude_status(custom_bin: Option<&str>) -> EngineStatus {
         default_model,
         features: EngineFeatures::claude(),
         error: None,
+        detecting: false,
     }
 }
 
@@ -201,6 +203,7 @@ pub async fn detect_codex_status(custom_bin: Option<&str>) -> EngineStatus {
         default_model,
         features: EngineFeatures::codex(),
         error: None,
+        detecting: false,
     }
 }
 
@@ -278,6 +281,7 @@ async fn detect_opencode_status_with_options(
         default_model,
         features: EngineFeatures::opencode(),
         error: models_error,
+        detecting: false,
     }
 }
 
@@ -323,6 +327,7 @@ pub async fn detect_gemini_status(custom_bin: Option<&str>) -> EngineStatus {
         default_model,
         features: EngineFeatures::gemini(),
         error: None,
+        detecting: false,
     }
 }
 
@@ -745,24 +750,6 @@ fn format_opencode_model_name(provider: &str, model_id: &str) -> String {
     format!("{}/{}", provider_name, model_name)
 }
 
-/// Detect all supported engines
-pub async fn detect_all_engines(
-    claude_bin: Option<&str>,
-    codex_bin: Option<&str>,
-    gemini_bin: Option<&str>,
-    opencode_bin: Option<&str>,
-) -> Vec<EngineStatus> {
-    // Run detections in parallel
-    let (claude_status, codex_status, gemini_status, opencode_status) = tokio::join!(
-        detect_claude_status(claude_bin),
-        detect_codex_status(codex_bin),
-        detect_gemini_status(gemini_bin),
-        detect_opencode_status(opencode_bin),
-    );
-
-    vec![claude_status, codex_status, gemini_status, opencode_status]
-}
-
 /// Detect available engines and return the preferred default engine.
 /// Priority: Claude > Codex > OpenCode (user can override in settings)
 pub async fn detect_preferred_engine(
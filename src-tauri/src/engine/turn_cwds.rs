@@ -0,0 +1,276 @@
+//! Working directories of engine turns.
+//!
+//! Claude, Gemini and OpenCode keep their sessions per project directory, so
+//! a session can only be resumed from the directory it started in. Each turn
+//! records where its CLI process ran; continued turns run in the session's
+//! starting directory and may not move elsewhere. The records also let the
+//! history show where each turn executed.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::EngineType;
+use crate::app_paths;
+use crate::backend::events::AppServerEvent;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const TURN_CWDS_DIRNAME: &str = "turn-cwds";
+const MAX_TURNS_PER_WORKSPACE: usize = 5_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnCwdRecord {
+    pub(crate) engine: EngineType,
+    pub(crate) turn_id: String,
+    /// Raw engine session id; filled in from `thread/started` when the
+    /// engine only reports it once the turn runs.
+    #[serde(default)]
+    pub(crate) session_id: Option<String>,
+    /// Directory the CLI ran in; `None` is the workspace root.
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    pub(crate) started_at_ms: u64,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_millis(0))
+        .as_millis() as u64
+}
+
+fn is_safe_storage_id(value: &str) -> bool {
+    !value.is_empty()
+        && value != "."
+        && value != ".."
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn store_path(workspace_id: &str) -> Result<PathBuf, String> {
+    if !is_safe_storage_id(workspace_id) {
+        return Err(format!("Invalid workspace id: {workspace_id}"));
+    }
+    Ok(app_paths::app_home_dir()?
+        .join(TURN_CWDS_DIRNAME)
+        .join(format!("{workspace_id}.json")))
+}
+
+fn read_records(path: &Path) -> Result<Vec<TurnCwdRecord>, String> {
+    Ok(read_json_file::<Vec<TurnCwdRecord>>(path)?.unwrap_or_default())
+}
+
+fn update_records(
+    path: &Path,
+    update: impl FnOnce(&mut Vec<TurnCwdRecord>) -> bool,
+) -> Result<(), String> {
+    with_storage_lock(path, || {
+        let mut records = read_records(path)?;
+        if !update(&mut records) {
+            return Ok(());
+        }
+        if records.len() > MAX_TURNS_PER_WORKSPACE {
+            let excess = records.len() - MAX_TURNS_PER_WORKSPACE;
+            records.drain(..excess);
+        }
+        let data = serde_json::to_string_pretty(&records)
+            .map_err(|error| format!("failed to serialize {}: {error}", path.display()))?;
+        write_string_atomically(path, &data)
+    })
+}
+
+/// The directory `session_id` started in, from its earliest recorded turn.
+/// Sessions without records started in the workspace root.
+fn session_start_cwd(records: &[TurnCwdRecord], session_id: &str) -> Option<String> {
+    records
+        .iter()
+        .filter(|record| record.session_id.as_deref() == Some(session_id))
+        .min_by_key(|record| record.started_at_ms)
+        .and_then(|record| record.cwd.clone())
+}
+
+/// Keeps a continued turn in the directory its session started in.
+fn continued_turn_cwd(
+    session_id: &str,
+    start_cwd: Option<String>,
+    requested: Option<String>,
+) -> Result<Option<String>, String> {
+    match requested {
+        None => Ok(start_cwd),
+        Some(requested) if Some(&requested) == start_cwd.as_ref() => Ok(Some(requested)),
+        Some(requested) => Err(format!(
+            "Session {session_id} runs in {}; a continued session cannot move to `{requested}`. Start a new session to work there.",
+            start_cwd
+                .map(|cwd| format!("`{cwd}`"))
+                .unwrap_or_else(|| "the workspace root".to_string())
+        )),
+    }
+}
+
+/// Working directory for a turn. `continued_session_id` is the session the
+/// turn resumes or forks, if any.
+pub(crate) fn resolve_turn_cwd(
+    workspace_id: &str,
+    continued_session_id: Option<&str>,
+    requested: Option<String>,
+) -> Result<Option<String>, String> {
+    let Some(session_id) = continued_session_id else {
+        return Ok(requested);
+    };
+    let records = read_records(&store_path(workspace_id)?)?;
+    continued_turn_cwd(
+        session_id,
+        session_start_cwd(&records, session_id),
+        requested,
+    )
+}
+
+/// Records where a turn's CLI process runs.
+pub(crate) fn record_turn_cwd(
+    workspace_id: &str,
+    engine: EngineType,
+    turn_id: &str,
+    session_id: Option<&str>,
+    cwd: Option<&str>,
+) {
+    let record = TurnCwdRecord {
+        engine,
+        turn_id: turn_id.to_string(),
+        session_id: session_id.map(str::to_string),
+        cwd: cwd.map(str::to_string),
+        started_at_ms: now_millis(),
+    };
+    let result = store_path(workspace_id).and_then(|path| {
+        update_records(&path, |records| {
+            records.push(record);
+            true
+        })
+    });
+    if let Err(error) = result {
+        log::warn!("[turn-cwds] failed to record turn {turn_id}: {error}");
+    }
+}
+
+/// Attaches the session id an engine reported for `turn_id`.
+pub(crate) fn bind_turn_session(workspace_id: &str, turn_id: &str, session_id: &str) {
+    let result = store_path(workspace_id).and_then(|path| {
+        update_records(&path, |records| {
+            let Some(record) = records
+                .iter_mut()
+                .rev()
+                .find(|record| record.turn_id == turn_id)
+            else {
+                return false;
+            };
+            if record.session_id.as_deref() == Some(session_id) {
+                return false;
+            }
+            record.session_id = Some(session_id.to_string());
+            true
+        })
+    });
+    if let Err(error) = result {
+        log::warn!("[turn-cwds] failed to bind turn {turn_id}: {error}");
+    }
+}
+
+/// Binds the session id an engine reports in `thread/started` to the turn
+/// that started it, so forks and fresh Gemini/OpenCode sessions can be
+/// continued from the right directory.
+pub(crate) fn observe_app_server_event(event: &AppServerEvent) {
+    if event.message.get("method").and_then(Value::as_str) != Some("thread/started") {
+        return;
+    }
+    let params = event.message.get("params").unwrap_or(&Value::Null);
+    let field = |key: &str| {
+        params
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let (Some(turn_id), Some(session_id)) = (field("turnId"), field("sessionId")) else {
+        return;
+    };
+    if session_id == "pending" {
+        return;
+    }
+    let workspace_id = event.workspace_id.clone();
+    tokio::task::spawn_blocking(move || bind_turn_session(&workspace_id, &turn_id, &session_id));
+}
+
+/// Strips the engine prefix of a catalog session id (`claude:<id>`).
+fn raw_session_id(session_id: &str) -> &str {
+    ["claude:", "gemini:", "opencode:"]
+        .iter()
+        .find_map(|prefix| session_id.strip_prefix(prefix))
+        .unwrap_or(session_id)
+}
+
+/// Where each recorded turn of a session executed, oldest first.
+#[tauri::command]
+pub(crate) async fn engine_session_turn_cwds(
+    workspace_id: String,
+    session_id: String,
+) -> Result<Vec<TurnCwdRecord>, String> {
+    let session_id = raw_session_id(&session_id);
+    let mut records: Vec<TurnCwdRecord> = read_records(&store_path(&workspace_id)?)?
+        .into_iter()
+        .filter(|record| record.session_id.as_deref() == Some(session_id))
+        .collect();
+    records.sort_by_key(|record| record.started_at_ms);
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(session_id: &str, cwd: Option<&str>, started_at_ms: u64) -> TurnCwdRecord {
+        TurnCwdRecord {
+            engine: EngineType::Claude,
+            turn_id: format!("turn-{started_at_ms}"),
+            session_id: Some(session_id.to_string()),
+            cwd: cwd.map(str::to_string),
+            started_at_ms,
+        }
+    }
+
+    #[test]
+    fn continued_turns_stay_in_the_starting_directory() {
+        let records = vec![
+            record("s-1", Some("/repo/packages/app"), 1),
+            record("s-1", Some("/repo/packages/app"), 2),
+            record("s-2", None, 3),
+        ];
+        let start = session_start_cwd(&records, "s-1");
+        assert_eq!(start.as_deref(), Some("/repo/packages/app"));
+        assert_eq!(
+            continued_turn_cwd("s-1", start.clone(), None).expect("inherit"),
+            Some("/repo/packages/app".to_string())
+        );
+        assert_eq!(
+            continued_turn_cwd("s-1", start.clone(), Some("/repo/packages/app".to_string()))
+                .expect("same directory"),
+            Some("/repo/packages/app".to_string())
+        );
+        assert!(continued_turn_cwd("s-1", start, Some("/repo/docs".to_string())).is_err());
+
+        let root_start = session_start_cwd(&records, "s-2");
+        assert_eq!(root_start, None);
+        assert!(continued_turn_cwd("s-2", root_start, Some("/repo/docs".to_string())).is_err());
+        assert_eq!(session_start_cwd(&records, "unknown"), None);
+    }
+
+    #[test]
+    fn catalog_session_ids_resolve_to_raw_ids() {
+        assert_eq!(raw_session_id("claude:abc"), "abc");
+        assert_eq!(raw_session_id("opencode:ses_1"), "ses_1");
+        assert_eq!(raw_session_id("abc"), "abc");
+    }
+}
@@ -0,0 +1,141 @@
+//! Starts a fresh engine turn on behalf of backend features (repro sandboxes,
+//! diff quick actions) and reports the identifiers the frontend needs to
+//! attach the streamed result to its originating action.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, State};
+
+use super::EngineType;
+use crate::codex;
+use crate::shared::codex_core;
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EngineTurnLinkage {
+    pub(crate) engine: EngineType,
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) session_id: Option<String>,
+    pub(crate) turn_id: Option<String>,
+}
+
+fn response_string_field(value: &Value, keys: &[&str]) -> Option<String> {
+    let result = value.get("result").unwrap_or(value);
+    keys.iter().find_map(|key| {
+        let mut current = result;
+        for segment in key.split('.') {
+            current = current.get(segment)?;
+        }
+        current
+            .as_str()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    })
+}
+
+pub(crate) async fn start_engine_turn(
+    workspace_id: &str,
+    engine: EngineType,
+    prompt: String,
+    model: Option<String>,
+    access_mode: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<EngineTurnLinkage, String> {
+    if engine == EngineType::Codex {
+        let started =
+            codex::start_thread_with_runtime_retry(workspace_id, model.clone(), &state, &app)
+                .await?;
+        let thread_id = response_string_field(&started, &["thread.id", "threadId"])
+            .ok_or("Failed to create Codex thread")?;
+        let mode_enforcement_enabled = {
+            let settings = state.app_settings.lock().await;
+            settings.codex_mode_enforcement_enabled
+        };
+        let prompt =
+            crate::privacy_zones::enforce_for_turn(&state, &app, workspace_id, prompt, None)
+                .await
+                .text;
+        let response = codex_core::send_user_message_core(
+            &state.sessions,
+            workspace_id.to_string(),
+            thread_id.clone(),
+            prompt,
+            model,
+            None,
+            access_mode,
+            None,
+            None,
+            None,
+            None,
+            mode_enforcement_enabled,
+        )
+        .await?;
+        return Ok(EngineTurnLinkage {
+            engine,
+            workspace_id: workspace_id.to_string(),
+            thread_id: Some(thread_id),
+            session_id: None,
+            turn_id: response_string_field(&response, &["turn.id", "turnId"]),
+        });
+    }
+
+    let response = super::engine_send_message(
+        workspace_id.to_string(),
+        prompt,
+        Some(engine),
+        model,
+        None,
+        None,
+        access_mode,
+        None,
+        false,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        app,
+        state,
+    )
+    .await?;
+    Ok(EngineTurnLinkage {
+        engine,
+        workspace_id: workspace_id.to_string(),
+        thread_id: response_string_field(&response, &["threadId"]),
+        session_id: response_string_field(&response, &["sessionId"]),
+        turn_id: response_string_field(&response, &["turn.id", "turnId"]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::response_string_field;
+    use serde_json::json;
+
+    #[test]
+    fn reads_nested_fields_from_result_envelope() {
+        let response = json!({
+            "result": { "thread": { "id": " t-1 " }, "turn": { "id": "turn-1" } }
+        });
+        assert_eq!(
+            response_string_field(&response, &["thread.id", "threadId"]).as_deref(),
+            Some("t-1")
+        );
+        assert_eq!(
+            response_string_field(&response, &["turn.id"]).as_deref(),
+            Some("turn-1")
+        );
+        assert_eq!(
+            response_string_field(&json!({ "sessionId": "" }), &["sessionId"]),
+            None
+        );
+    }
+}
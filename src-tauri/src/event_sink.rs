@@ -1,6 +1,6 @@
 use tauri::{AppHandle, Emitter};
 
-use crate::backend::events::{AppServerEvent, EventSink, TerminalOutput};
+use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
@@ -14,7 +14,15 @@ impl TauriEventSink {
 }
 
 impl EventSink for TauriEventSink {
-    fn emit_app_server_event(&self, event: AppServerEvent) {
+    fn emit_app_server_event(&self, mut event: AppServerEvent) {
+        crate::session_cost::annotate_app_server_event(&mut event, None);
+        crate::approval_rules::observe_app_server_event(&self.app, &event);
+        crate::file_attribution::observe_app_server_event(&self.app, &event);
+        crate::file_mentions::observe_app_server_event(&self.app, &event);
+        crate::outbound_notifications::observe_app_server_event(&self.app, &event);
+        crate::sandbox_report::observe_app_server_event(&self.app, &event);
+        crate::session_stats::observe_app_server_event(&self.app, &event);
+        crate::user_input_policy::observe_app_server_event(&self.app, &event);
         let _ = self.app.emit("app-server-event", event);
     }
 
@@ -24,4 +32,8 @@ impl EventSink for TauriEventSink {
             let _ = self.app.emit("runtime-log:line-appended", event);
         }
     }
+
+    fn emit_terminal_exit(&self, event: TerminalExit) {
+        let _ = self.app.emit("terminal-exit", event);
+    }
 }
@@ -0,0 +1,589 @@
+//! AI-vs-human change attribution for workspace files.
+//!
+//! When a turn starts the dirty files of the workspace are fingerprinted; when
+//! it completes they are fingerprinted again and every file whose fingerprint
+//! changed is recorded as modified by that turn. A file is reported as
+//! AI-authored while its content still matches what the turn left behind, and
+//! as human-edited once it diverges.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use git2::{Repository, Status, StatusOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager, State};
+
+use crate::app_paths;
+use crate::backend::events::AppServerEvent;
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const ATTRIBUTION_DIR_NAME: &str = "file-attribution";
+const MAX_HASHED_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_RECORDS_PER_WORKSPACE: usize = 5_000;
+/// Turns that never report completion (crashed engine, dropped event) are
+/// forgotten after this long.
+const PENDING_TURN_TTL: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// Fingerprint of every dirty file, keyed by repo-relative path. `None` marks
+/// a deleted file.
+type DirtySnapshot = HashMap<String, Option<String>>;
+
+struct PendingTurn {
+    repo_root: PathBuf,
+    snapshot: DirtySnapshot,
+    started_at: Instant,
+}
+
+static PENDING_TURNS: OnceLock<Mutex<HashMap<String, PendingTurn>>> = OnceLock::new();
+/// Last queued boundary job per thread; the next one waits for it.
+static TURN_JOBS: OnceLock<Mutex<HashMap<String, JoinHandle<()>>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AiChangeRecord {
+    pub(crate) engine: String,
+    pub(crate) thread_id: String,
+    #[serde(default)]
+    pub(crate) turn_id: Option<String>,
+    pub(crate) modified_at_ms: i64,
+    /// Content fingerprint right after the turn; `None` when the turn deleted
+    /// the file.
+    #[serde(default)]
+    pub(crate) content_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum FileAuthor {
+    Ai,
+    Human,
+    Unmodified,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileAttribution {
+    path: String,
+    author: FileAuthor,
+    /// The last AI turn that touched the file, even if a human edited it since.
+    last_ai_change: Option<AiChangeRecord>,
+    human_edited_after_ai: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceAttributionSummary {
+    ai_files: Vec<FileAttribution>,
+    human_files: Vec<FileAttribution>,
+    ai_count: usize,
+    human_count: usize,
+}
+
+fn pending_turns() -> std::sync::MutexGuard<'static, HashMap<String, PendingTurn>> {
+    let pending = PENDING_TURNS.get_or_init(|| Mutex::new(HashMap::new()));
+    match pending.lock() {
+        Ok(pending) => pending,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Runs `job` once the previous job queued for `key` has finished, so the
+/// boundaries of one thread are processed in event order.
+fn queue_turn_job<F>(key: String, job: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let mut jobs = TURN_JOBS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    jobs.retain(|_, job| !job.inner().is_finished());
+    let previous = jobs.remove(&key);
+    let handle = tauri::async_runtime::spawn(async move {
+        if let Some(previous) = previous {
+            let _ = previous.await;
+        }
+        job.await;
+    });
+    jobs.insert(key, handle);
+}
+
+/// Records a started turn, replacing an unfinished turn of the same thread
+/// and dropping turns that outlived [`PENDING_TURN_TTL`].
+fn begin_pending_turn(pending: &mut HashMap<String, PendingTurn>, key: String, turn: PendingTurn) {
+    pending.retain(|_, turn| turn.started_at.elapsed() < PENDING_TURN_TTL);
+    pending.insert(key, turn);
+}
+
+fn index_path(workspace_id: &str) -> Result<PathBuf, String> {
+    if workspace_id.is_empty()
+        || !workspace_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err("invalid workspace id".to_string());
+    }
+    Ok(app_paths::app_home_dir()?
+        .join(ATTRIBUTION_DIR_NAME)
+        .join(format!("{workspace_id}.json")))
+}
+
+fn read_index(path: &Path) -> Result<HashMap<String, AiChangeRecord>, String> {
+    Ok(read_json_file(path)?.unwrap_or_default())
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    let mut hasher = Sha256::new();
+    if metadata.len() > MAX_HASHED_FILE_BYTES {
+        // Large files are fingerprinted by size and mtime only.
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+        hasher.update(format!("{}:{mtime}", metadata.len()));
+    } else {
+        hasher.update(std::fs::read(path).ok()?);
+    }
+    Some(format!("{:x}", hasher.finalize()))
+}
+
+fn snapshot_dirty_files(repo_root: &Path) -> Result<DirtySnapshot, String> {
+    let repo = Repository::open(repo_root).map_err(|error| error.to_string())?;
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|error| error.to_string())?;
+    let mut snapshot = DirtySnapshot::new();
+    for entry in statuses.iter() {
+        let Some(path) = entry.path() else {
+            continue;
+        };
+        let hash = if entry
+            .status()
+            .intersects(Status::WT_DELETED | Status::INDEX_DELETED)
+        {
+            None
+        } else {
+            hash_file(&repo_root.join(path))
+        };
+        snapshot.insert(path.to_string(), hash);
+    }
+    Ok(snapshot)
+}
+
+/// Paths whose fingerprint differs between the two snapshots. Files missing
+/// from a snapshot are clean (matching HEAD) at that point.
+fn changed_paths(before: &DirtySnapshot, after: &DirtySnapshot) -> Vec<String> {
+    let mut changed: Vec<String> = after
+        .iter()
+        .filter(|(path, hash)| before.get(*path) != Some(hash))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    changed.sort();
+    changed
+}
+
+fn engine_for_thread(thread_id: &str) -> &'static str {
+    match thread_id.split_once(':').map(|(prefix, _)| prefix) {
+        Some("claude") | Some("claude-pending") => "claude",
+        Some("gemini") => "gemini",
+        Some("opencode") => "opencode",
+        _ => "codex",
+    }
+}
+
+fn record_turn_changes(
+    index_path: &Path,
+    repo_root: &Path,
+    before: &DirtySnapshot,
+    after: &DirtySnapshot,
+    thread_id: &str,
+    turn_id: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let changed = changed_paths(before, after);
+    if changed.is_empty() {
+        return Ok(changed);
+    }
+    let modified_at_ms = chrono::Utc::now().timestamp_millis();
+    with_storage_lock(index_path, || {
+        let mut index = read_index(index_path)?;
+        for path in &changed {
+            let content_hash = match after.get(path) {
+                Some(hash) => hash.clone(),
+                None => hash_file(&repo_root.join(path)),
+            };
+            index.insert(
+                path.clone(),
+                AiChangeRecord {
+                    engine: engine_for_thread(thread_id).to_string(),
+                    thread_id: thread_id.to_string(),
+                    turn_id: turn_id.map(str::to_string),
+                    modified_at_ms,
+                    content_hash,
+                },
+            );
+        }
+        if index.len() > MAX_RECORDS_PER_WORKSPACE {
+            let mut by_age: Vec<(String, i64)> = index
+                .iter()
+                .map(|(path, record)| (path.clone(), record.modified_at_ms))
+                .collect();
+            by_age.sort_by_key(|(_, modified_at_ms)| *modified_at_ms);
+            for (path, _) in by_age.iter().take(index.len() - MAX_RECORDS_PER_WORKSPACE) {
+                index.remove(path);
+            }
+        }
+        let data = serde_json::to_string_pretty(&index).map_err(|error| error.to_string())?;
+        write_string_atomically(index_path, &data)
+    })?;
+    Ok(changed)
+}
+
+fn attribute(
+    path: &str,
+    record: Option<&AiChangeRecord>,
+    current_hash: Option<String>,
+    dirty: bool,
+) -> FileAttribution {
+    let (author, human_edited_after_ai) = match record {
+        Some(record) if record.content_hash == current_hash => (FileAuthor::Ai, false),
+        Some(_) => (FileAuthor::Human, true),
+        None if dirty => (FileAuthor::Human, false),
+        None => (FileAuthor::Unmodified, false),
+    };
+    FileAttribution {
+        path: path.to_string(),
+        author,
+        last_ai_change: record.cloned(),
+        human_edited_after_ai,
+    }
+}
+
+/// Threads recorded as the last AI author of a file at or below `path_prefix`.
+pub(crate) fn threads_touching_path(
+    workspace_id: &str,
+    path_prefix: &str,
+) -> Result<HashSet<String>, String> {
+    let prefix = path_prefix.trim().trim_matches('/').replace('\\', "/");
+    let index = read_index(&index_path(workspace_id)?)?;
+    Ok(index
+        .into_iter()
+        .filter(|(path, _)| {
+            prefix.is_empty()
+                || path == &prefix
+                || path
+                    .strip_prefix(&prefix)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .map(|(_, record)| record.thread_id)
+        .collect())
+}
+
+fn turn_key(workspace_id: &str, thread_id: &str) -> String {
+    format!("{workspace_id}\u{0}{thread_id}")
+}
+
+fn params_string(params: &Value, pointers: &[&str]) -> Option<String> {
+    pointers.iter().find_map(|pointer| {
+        params
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// Watches the app-server event stream for turn boundaries and snapshots the
+/// workspace around each turn. The git status and hashing run in the
+/// background, queued per thread so a turn's start and completion still apply
+/// in event order without stalling the event stream.
+pub(crate) fn observe_app_server_event(app: &AppHandle, event: &AppServerEvent) {
+    let method = event.message.get("method").and_then(Value::as_str);
+    let is_start = method == Some("turn/started");
+    if !is_start && !matches!(method, Some("turn/completed") | Some("turn/error")) {
+        return;
+    }
+    let params = event.message.get("params").unwrap_or(&Value::Null);
+    let Some(thread_id) = params_string(params, &["/threadId", "/thread/id"]) else {
+        return;
+    };
+    let turn_id = params_string(params, &["/turnId", "/turn/id"]);
+    let workspace_id = event.workspace_id.clone();
+    let key = turn_key(&workspace_id, &thread_id);
+    let app = app.clone();
+
+    if is_start {
+        queue_turn_job(key.clone(), start_turn(app, workspace_id, key));
+        return;
+    }
+    let completed = method == Some("turn/completed");
+    queue_turn_job(
+        key.clone(),
+        finish_turn(app, workspace_id, thread_id, turn_id, key, completed),
+    );
+}
+
+async fn start_turn(app: AppHandle, workspace_id: String, key: String) {
+    let snapshot = match workspace_repo_root(&workspace_id, &app.state::<AppState>()).await {
+        Ok(repo_root) => Some(
+            tokio::task::spawn_blocking(move || {
+                snapshot_dirty_files(&repo_root).map(|snapshot| (repo_root, snapshot))
+            })
+            .await
+            .unwrap_or_else(|error| Err(error.to_string())),
+        ),
+        Err(_) => None,
+    };
+    let mut pending = pending_turns();
+    match snapshot {
+        Some(Ok((repo_root, snapshot))) => begin_pending_turn(
+            &mut pending,
+            key,
+            PendingTurn {
+                repo_root,
+                snapshot,
+                started_at: Instant::now(),
+            },
+        ),
+        Some(Err(error)) => {
+            pending.remove(&key);
+            log::warn!("[file-attribution] failed to snapshot turn start: {error}");
+        }
+        None => {
+            pending.remove(&key);
+        }
+    }
+}
+
+/// Records the files a finished turn changed, then hands a completed turn's
+/// files to the guarded auto-commit.
+async fn finish_turn(
+    app: AppHandle,
+    workspace_id: String,
+    thread_id: String,
+    turn_id: Option<String>,
+    key: String,
+    completed: bool,
+) {
+    let Some(pending) = pending_turns().remove(&key) else {
+        return;
+    };
+    let Ok(index_path) = index_path(&workspace_id) else {
+        return;
+    };
+    let recorded_workspace_id = workspace_id.clone();
+    let recorded_thread_id = thread_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let after = snapshot_dirty_files(&pending.repo_root)?;
+        let changed = record_turn_changes(
+            &index_path,
+            &pending.repo_root,
+            &pending.snapshot,
+            &after,
+            &recorded_thread_id,
+            turn_id.as_deref(),
+        )?;
+        crate::file_mentions::record_turn_modifications(
+            &recorded_workspace_id,
+            &recorded_thread_id,
+            turn_id.as_deref(),
+            &changed,
+        )?;
+        Ok::<_, String>(changed)
+    })
+    .await;
+    let changed = match result {
+        Ok(Ok(changed)) => changed,
+        Ok(Err(error)) => {
+            log::warn!("[file-attribution] failed to record turn changes: {error}");
+            return;
+        }
+        Err(_) => return,
+    };
+    if completed && !changed.is_empty() {
+        // Guarded auto-commit: a no-op unless the workspace enabled it.
+        let state = app.state::<AppState>();
+        if let Err(error) = crate::git::commit_agent_batch_for_workspace(
+            &state,
+            &workspace_id,
+            &changed,
+            None,
+            Some(&thread_id),
+        )
+        .await
+        {
+            log::warn!("[file-attribution] failed to auto-commit turn changes: {error}");
+        }
+    }
+}
+
+async fn workspace_repo_root(
+    workspace_id: &str,
+    state: &State<'_, AppState>,
+) -> Result<PathBuf, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    resolve_git_root(&entry)
+}
+
+#[tauri::command]
+pub(crate) async fn file_attribution(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<FileAttribution, String> {
+    let repo_root = workspace_repo_root(&workspace_id, &state).await?;
+    let index_path = index_path(&workspace_id)?;
+    tokio::task::spawn_blocking(move || {
+        let path = path.trim().trim_start_matches("./").replace('\\', "/");
+        if path.is_empty() || path.split('/').any(|segment| segment == "..") {
+            return Err("Invalid path".to_string());
+        }
+        let index = read_index(&index_path)?;
+        let dirty = snapshot_dirty_files(&repo_root)?.contains_key(&path);
+        Ok(attribute(
+            &path,
+            index.get(&path),
+            hash_file(&repo_root.join(&path)),
+            dirty,
+        ))
+    })
+    .await
+    .map_err(|error| error.to_string())?
+}
+
+/// Attribution for every currently changed file in the workspace.
+#[tauri::command]
+pub(crate) async fn workspace_attribution_summary(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceAttributionSummary, String> {
+    let repo_root = workspace_repo_root(&workspace_id, &state).await?;
+    let index_path = index_path(&workspace_id)?;
+    tokio::task::spawn_blocking(move || {
+        let index = read_index(&index_path)?;
+        let mut dirty: Vec<(String, Option<String>)> =
+            snapshot_dirty_files(&repo_root)?.into_iter().collect();
+        dirty.sort();
+        let (ai_files, human_files): (Vec<_>, Vec<_>) = dirty
+            .into_iter()
+            .map(|(path, hash)| attribute(&path, index.get(&path), hash, true))
+            .partition(|item| item.author == FileAuthor::Ai);
+        Ok(WorkspaceAttributionSummary {
+            ai_count: ai_files.len(),
+            human_count: human_files.len(),
+            ai_files,
+            human_files,
+        })
+    })
+    .await
+    .map_err(|error| error.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn starting_a_turn_drops_expired_pending_turns() {
+        let turn = |started_at: Instant| PendingTurn {
+            repo_root: PathBuf::from("/repo"),
+            snapshot: DirtySnapshot::new(),
+            started_at,
+        };
+        let mut pending = HashMap::new();
+        let Some(expired_at) =
+            Instant::now().checked_sub(PENDING_TURN_TTL + Duration::from_secs(1))
+        else {
+            return;
+        };
+        pending.insert("ws\u{0}stale".to_string(), turn(expired_at));
+        pending.insert("ws\u{0}live".to_string(), turn(Instant::now()));
+
+        begin_pending_turn(
+            &mut pending,
+            "ws\u{0}next".to_string(),
+            turn(Instant::now()),
+        );
+
+        let mut keys: Vec<_> = pending.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["ws\u{0}live", "ws\u{0}next"]);
+    }
+
+    #[test]
+    fn records_turn_changes_and_detects_later_human_edits() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).expect("create root");
+        Repository::init(&root).expect("init repo");
+        std::fs::write(root.join("human.txt"), "mine\n").expect("write");
+        let before = snapshot_dirty_files(&root).expect("before");
+
+        std::fs::write(root.join("ai.rs"), "fn main() {}\n").expect("write");
+        let after = snapshot_dirty_files(&root).expect("after");
+        assert_eq!(changed_paths(&before, &after), vec!["ai.rs".to_string()]);
+
+        let index_path = root.join("attribution.json");
+        let recorded = record_turn_changes(
+            &index_path,
+            &root,
+            &before,
+            &after,
+            "claude:session-1",
+            Some("turn-1"),
+        )
+        .expect("record");
+        assert_eq!(recorded, vec!["ai.rs".to_string()]);
+
+        let index = read_index(&index_path).expect("index");
+        let record = index.get("ai.rs").expect("ai record");
+        assert_eq!(record.engine, "claude");
+        let ai = attribute("ai.rs", Some(record), hash_file(&root.join("ai.rs")), true);
+        assert_eq!(ai.author, FileAuthor::Ai);
+
+        std::fs::write(root.join("ai.rs"), "fn main() { todo!() }\n").expect("edit");
+        let edited = attribute("ai.rs", Some(record), hash_file(&root.join("ai.rs")), true);
+        assert_eq!(edited.author, FileAuthor::Human);
+        assert!(edited.human_edited_after_ai);
+
+        let human = attribute("human.txt", None, hash_file(&root.join("human.txt")), true);
+        assert_eq!(human.author, FileAuthor::Human);
+        assert_eq!(
+            attribute("README.md", None, None, false).author,
+            FileAuthor::Unmodified
+        );
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
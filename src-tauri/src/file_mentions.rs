@@ -0,0 +1,523 @@
+//! Cross-session index of the files each conversation read or modified.
+//!
+//! Tool items on the app-server event stream (reads, edits, patches) and the
+//! per-turn diffs recorded by [`file_attribution`](crate::file_attribution)
+//! are folded into `~/.ccgui/file-mentions/<workspace>.json`, keyed by
+//! repo-relative path, so a file can be traced back to the sessions and turns
+//! that explain it.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::app_paths;
+use crate::backend::events::AppServerEvent;
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+use crate::types::WorkspaceEntry;
+
+const MENTIONS_DIR_NAME: &str = "file-mentions";
+const MAX_MENTIONS_PER_FILE: usize = 100;
+const MAX_INDEXED_FILES: usize = 20_000;
+const DEFAULT_SESSION_LIMIT: usize = 50;
+/// Tool argument keys that carry a file path.
+const PATH_ARGUMENT_KEYS: &[&str] = &[
+    "file_path",
+    "filePath",
+    "path",
+    "notebook_path",
+    "absolute_path",
+];
+
+/// Latest turn id per workspace thread, for item events that omit it.
+static ACTIVE_TURNS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum MentionKind {
+    Read,
+    Modified,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct FileMention {
+    thread_id: String,
+    #[serde(default)]
+    turn_id: Option<String>,
+    engine: String,
+    kind: MentionKind,
+    at_ms: i64,
+}
+
+type MentionIndex = HashMap<String, Vec<FileMention>>;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileSessionTurn {
+    turn_id: Option<String>,
+    kind: MentionKind,
+    at_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileSession {
+    thread_id: String,
+    engine: String,
+    last_at_ms: i64,
+    read_count: usize,
+    modified_count: usize,
+    /// Newest first.
+    turns: Vec<FileSessionTurn>,
+}
+
+fn active_turns() -> std::sync::MutexGuard<'static, HashMap<String, String>> {
+    let turns = ACTIVE_TURNS.get_or_init(|| Mutex::new(HashMap::new()));
+    match turns.lock() {
+        Ok(turns) => turns,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn index_path(workspace_id: &str) -> Result<PathBuf, String> {
+    if workspace_id.is_empty()
+        || !workspace_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err("invalid workspace id".to_string());
+    }
+    Ok(app_paths::app_home_dir()?
+        .join(MENTIONS_DIR_NAME)
+        .join(format!("{workspace_id}.json")))
+}
+
+fn read_index(path: &Path) -> Result<MentionIndex, String> {
+    Ok(read_json_file(path)?.unwrap_or_default())
+}
+
+fn engine_for_thread(thread_id: &str) -> &'static str {
+    match thread_id.split_once(':').map(|(prefix, _)| prefix) {
+        Some("claude") | Some("claude-pending") => "claude",
+        Some("gemini") => "gemini",
+        Some("opencode") => "opencode",
+        _ => "codex",
+    }
+}
+
+/// Index root: the git root when the workspace is a repository, so keys line
+/// up with file attribution, otherwise the workspace folder.
+fn index_root(entry: &WorkspaceEntry) -> PathBuf {
+    resolve_git_root(entry).unwrap_or_else(|_| PathBuf::from(&entry.path))
+}
+
+/// Repo-relative, `/`-separated form of `path`, or `None` when it points
+/// outside `root`.
+fn relative_path(root: &Path, path: &str) -> Option<String> {
+    let path = path.trim().replace('\\', "/");
+    let relative = if Path::new(&path).is_absolute() {
+        let root = root.to_string_lossy().replace('\\', "/");
+        path.strip_prefix(root.trim_end_matches('/'))?
+            .trim_start_matches('/')
+            .to_string()
+    } else {
+        path.trim_start_matches("./").to_string()
+    };
+    if relative.is_empty() || relative.split('/').any(|segment| segment == "..") {
+        return None;
+    }
+    Some(relative)
+}
+
+fn tool_arguments(item: &Value) -> Option<Value> {
+    ["arguments", "input"]
+        .iter()
+        .find_map(|key| match item.get(*key)? {
+            Value::String(raw) => serde_json::from_str(raw).ok(),
+            value @ Value::Object(_) => Some(value.clone()),
+            _ => None,
+        })
+}
+
+/// Paths named by a tool item. File changes count as modifications; any
+/// other tool that takes a path argument counts as a read.
+fn mentioned_paths(item: &Value) -> Vec<(String, MentionKind)> {
+    let kind = match item.get("type").and_then(Value::as_str) {
+        Some("fileChange") => MentionKind::Modified,
+        Some("mcpToolCall") => MentionKind::Read,
+        _ => return Vec::new(),
+    };
+    let mut paths = Vec::new();
+    if let Some(changes) = item.get("changes").and_then(Value::as_array) {
+        paths.extend(
+            changes
+                .iter()
+                .filter_map(|change| change.get("path").and_then(Value::as_str))
+                .map(str::to_string),
+        );
+    }
+    if let Some(arguments) = tool_arguments(item) {
+        paths.extend(
+            PATH_ARGUMENT_KEYS
+                .iter()
+                .filter_map(|key| arguments.get(*key).and_then(Value::as_str))
+                .map(str::to_string),
+        );
+    }
+    paths.sort();
+    paths.dedup();
+    paths.into_iter().map(|path| (path, kind)).collect()
+}
+
+/// Appends mentions, refreshing the timestamp of a repeated
+/// (thread, turn, kind) entry instead of duplicating it.
+fn record_mentions(
+    index_path: &Path,
+    thread_id: &str,
+    turn_id: Option<&str>,
+    paths: &[(String, MentionKind)],
+    at_ms: i64,
+) -> Result<(), String> {
+    if paths.is_empty() {
+        return Ok(());
+    }
+    with_storage_lock(index_path, || {
+        let mut index = read_index(index_path)?;
+        for (path, kind) in paths {
+            let mentions = index.entry(path.clone()).or_default();
+            match mentions.iter_mut().find(|mention| {
+                mention.thread_id == thread_id
+                    && mention.turn_id.as_deref() == turn_id
+                    && mention.kind == *kind
+            }) {
+                Some(mention) => mention.at_ms = at_ms,
+                None => mentions.push(FileMention {
+                    thread_id: thread_id.to_string(),
+                    turn_id: turn_id.map(str::to_string),
+                    engine: engine_for_thread(thread_id).to_string(),
+                    kind: *kind,
+                    at_ms,
+                }),
+            }
+            mentions.sort_by_key(|mention| std::cmp::Reverse(mention.at_ms));
+            mentions.truncate(MAX_MENTIONS_PER_FILE);
+        }
+        if index.len() > MAX_INDEXED_FILES {
+            let mut by_age: Vec<(i64, String)> = index
+                .iter()
+                .map(|(path, mentions)| {
+                    let newest = mentions.first().map_or(0, |mention| mention.at_ms);
+                    (newest, path.clone())
+                })
+                .collect();
+            by_age.sort();
+            for (_, path) in by_age.into_iter().take(index.len() - MAX_INDEXED_FILES) {
+                index.remove(&path);
+            }
+        }
+        let data = serde_json::to_string_pretty(&index).map_err(|error| error.to_string())?;
+        write_string_atomically(index_path, &data)
+    })
+}
+
+/// Records files changed by a turn; called by file attribution with
+/// repo-relative paths once the turn's diff is known.
+pub(crate) fn record_turn_modifications(
+    workspace_id: &str,
+    thread_id: &str,
+    turn_id: Option<&str>,
+    paths: &[String],
+) -> Result<(), String> {
+    let paths: Vec<(String, MentionKind)> = paths
+        .iter()
+        .map(|path| (path.clone(), MentionKind::Modified))
+        .collect();
+    record_mentions(
+        &index_path(workspace_id)?,
+        thread_id,
+        turn_id,
+        &paths,
+        chrono::Utc::now().timestamp_millis(),
+    )
+}
+
+/// Paths modified by `turn_id` and the thread the turn belongs to.
+fn collect_turn_modifications(index: MentionIndex, turn_id: &str) -> (Vec<String>, Option<String>) {
+    let mut thread_id = None;
+    let mut paths: Vec<String> = index
+        .into_iter()
+        .filter_map(|(path, mentions)| {
+            let mention = mentions.into_iter().find(|mention| {
+                mention.kind == MentionKind::Modified && mention.turn_id.as_deref() == Some(turn_id)
+            })?;
+            thread_id.get_or_insert(mention.thread_id);
+            Some(path)
+        })
+        .collect();
+    paths.sort();
+    (paths, thread_id)
+}
+
+/// Repo-relative paths recorded as modified by `turn_id`, with its thread.
+pub(crate) fn turn_modified_paths(
+    workspace_id: &str,
+    turn_id: &str,
+) -> Result<(Vec<String>, Option<String>), String> {
+    let index = read_index(&index_path(workspace_id)?)?;
+    Ok(collect_turn_modifications(index, turn_id))
+}
+
+fn turn_key(workspace_id: &str, thread_id: &str) -> String {
+    format!("{workspace_id}\u{0}{thread_id}")
+}
+
+fn params_string(params: &Value, pointers: &[&str]) -> Option<String> {
+    pointers.iter().find_map(|pointer| {
+        params
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// Tracks turn boundaries and indexes the paths named by tool items.
+pub(crate) fn observe_app_server_event(app: &AppHandle, event: &AppServerEvent) {
+    let Some(method) = event.message.get("method").and_then(Value::as_str) else {
+        return;
+    };
+    let Some(params) = event.message.get("params") else {
+        return;
+    };
+    let Some(thread_id) = params_string(params, &["/threadId", "/thread/id"]) else {
+        return;
+    };
+    let key = turn_key(&event.workspace_id, &thread_id);
+    let turn_id = params_string(params, &["/turnId", "/turn/id"]);
+    match method {
+        "turn/started" => {
+            if let Some(turn_id) = turn_id {
+                active_turns().insert(key, turn_id);
+            }
+            return;
+        }
+        "turn/completed" | "turn/error" => {
+            active_turns().remove(&key);
+            return;
+        }
+        "item/started" | "item/completed" => {}
+        _ => return,
+    }
+    let mentioned = params.get("item").map(mentioned_paths).unwrap_or_default();
+    if mentioned.is_empty() {
+        return;
+    }
+    let turn_id = turn_id.or_else(|| active_turns().get(&key).cloned());
+    let workspace_id = event.workspace_id.clone();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let entry = {
+            let state = app.state::<AppState>();
+            let workspaces = state.workspaces.lock().await;
+            workspaces.get(&workspace_id).cloned()
+        };
+        let Some(entry) = entry else {
+            return;
+        };
+        let Ok(index_path) = index_path(&workspace_id) else {
+            return;
+        };
+        let result = tokio::task::spawn_blocking(move || {
+            let root = index_root(&entry);
+            let paths: Vec<(String, MentionKind)> = mentioned
+                .into_iter()
+                .filter_map(|(path, kind)| Some((relative_path(&root, &path)?, kind)))
+                .collect();
+            record_mentions(
+                &index_path,
+                &thread_id,
+                turn_id.as_deref(),
+                &paths,
+                chrono::Utc::now().timestamp_millis(),
+            )
+        })
+        .await;
+        if let Ok(Err(error)) = result {
+            log::warn!("[file-mentions] failed to index tool paths: {error}");
+        }
+    });
+}
+
+fn group_by_session(mentions: Vec<FileMention>) -> Vec<FileSession> {
+    let mut sessions: Vec<FileSession> = Vec::new();
+    for mention in mentions {
+        let turn = FileSessionTurn {
+            turn_id: mention.turn_id,
+            kind: mention.kind,
+            at_ms: mention.at_ms,
+        };
+        let session = match sessions
+            .iter_mut()
+            .find(|session| session.thread_id == mention.thread_id)
+        {
+            Some(session) => session,
+            None => {
+                sessions.push(FileSession {
+                    thread_id: mention.thread_id,
+                    engine: mention.engine,
+                    last_at_ms: 0,
+                    read_count: 0,
+                    modified_count: 0,
+                    turns: Vec::new(),
+                });
+                sessions.last_mut().expect("session just pushed")
+            }
+        };
+        match turn.kind {
+            MentionKind::Read => session.read_count += 1,
+            MentionKind::Modified => session.modified_count += 1,
+        }
+        session.last_at_ms = session.last_at_ms.max(turn.at_ms);
+        session.turns.push(turn);
+    }
+    for session in &mut sessions {
+        session
+            .turns
+            .sort_by_key(|turn| std::cmp::Reverse(turn.at_ms));
+    }
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.last_at_ms));
+    sessions
+}
+
+/// Sessions that read or modified `path`, most recent first, with the turns
+/// that touched it.
+#[tauri::command]
+pub(crate) async fn file_sessions(
+    workspace_id: String,
+    path: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<FileSession>, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let index_path = index_path(&workspace_id)?;
+    let limit = limit.unwrap_or(DEFAULT_SESSION_LIMIT).max(1);
+    tokio::task::spawn_blocking(move || {
+        let path = relative_path(&index_root(&entry), &path).ok_or("Invalid path")?;
+        let mentions = read_index(&index_path)?.remove(&path).unwrap_or_default();
+        let mut sessions = group_by_session(mentions);
+        sessions.truncate(limit);
+        Ok(sessions)
+    })
+    .await
+    .map_err(|error| error.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn extracts_paths_from_tool_items() {
+        let read = json!({
+            "type": "mcpToolCall",
+            "tool": "Read",
+            "arguments": { "file_path": "/repo/src/main.rs" },
+        });
+        assert_eq!(
+            mentioned_paths(&read),
+            vec![("/repo/src/main.rs".to_string(), MentionKind::Read)]
+        );
+        let patch = json!({
+            "type": "fileChange",
+            "changes": [{ "path": "src/lib.rs", "kind": "update" }],
+            "input": "{\"path\":\"src/lib.rs\"}",
+        });
+        assert_eq!(
+            mentioned_paths(&patch),
+            vec![("src/lib.rs".to_string(), MentionKind::Modified)]
+        );
+        let command = json!({ "type": "commandExecution", "arguments": { "path": "x" } });
+        assert!(mentioned_paths(&command).is_empty());
+
+        let root = Path::new("/repo");
+        assert_eq!(
+            relative_path(root, "/repo/src/main.rs").as_deref(),
+            Some("src/main.rs")
+        );
+        assert_eq!(
+            relative_path(root, "./src/lib.rs").as_deref(),
+            Some("src/lib.rs")
+        );
+        assert_eq!(relative_path(root, "/elsewhere/main.rs"), None);
+        assert_eq!(relative_path(root, "../secret"), None);
+    }
+
+    #[test]
+    fn groups_mentions_by_session_newest_first() {
+        let dir = std::env::temp_dir().join(format!("moss-x-mentions-{}", Uuid::new_v4()));
+        let index_path = dir.join("ws.json");
+        let read = vec![("src/main.rs".to_string(), MentionKind::Read)];
+        let modified = vec![("src/main.rs".to_string(), MentionKind::Modified)];
+        record_mentions(&index_path, "claude:a", Some("t1"), &read, 10).expect("record");
+        record_mentions(&index_path, "claude:a", Some("t1"), &read, 20).expect("record");
+        record_mentions(&index_path, "thread-b", Some("t2"), &modified, 30).expect("record");
+        record_mentions(&index_path, "claude:a", Some("t3"), &modified, 40).expect("record");
+
+        let mut index = read_index(&index_path).expect("index");
+        let sessions = group_by_session(index.remove("src/main.rs").expect("mentions"));
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].thread_id, "claude:a");
+        assert_eq!(sessions[0].engine, "claude");
+        assert_eq!(sessions[0].last_at_ms, 40);
+        assert_eq!((sessions[0].read_count, sessions[0].modified_count), (1, 1));
+        assert_eq!(sessions[0].turns[0].turn_id.as_deref(), Some("t3"));
+        assert_eq!(sessions[0].turns[1].at_ms, 20);
+        assert_eq!(sessions[1].engine, "codex");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn collects_files_modified_by_a_turn() {
+        let dir = std::env::temp_dir().join(format!("moss-x-mentions-{}", Uuid::new_v4()));
+        let index_path = dir.join("ws.json");
+        let both = vec![
+            ("src/a.rs".to_string(), MentionKind::Modified),
+            ("src/b.rs".to_string(), MentionKind::Modified),
+        ];
+        let read = vec![("src/c.rs".to_string(), MentionKind::Read)];
+        record_mentions(&index_path, "claude:a", Some("t1"), &both, 10).expect("record");
+        record_mentions(&index_path, "claude:a", Some("t1"), &read, 10).expect("record");
+        record_mentions(&index_path, "claude:a", Some("t2"), &both[..1], 20).expect("record");
+
+        let index = read_index(&index_path).expect("index");
+        assert_eq!(
+            collect_turn_modifications(index.clone(), "t1"),
+            (
+                vec!["src/a.rs".to_string(), "src/b.rs".to_string()],
+                Some("claude:a".to_string())
+            )
+        );
+        assert_eq!(
+            collect_turn_modifications(index, "missing"),
+            (Vec::new(), None)
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
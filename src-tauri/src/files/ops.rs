@@ -2,6 +2,15 @@ use std::path::PathBuf;
 
 use crate::files::io::{read_text_file_within, write_text_file_within, TextFileResponse};
 use crate::files::policy::FilePolicy;
+use crate::shared::config_journal::record_config_write;
+
+/// Engine config files whose previous versions are kept in the undo journal.
+fn journaled_config_kind(policy: FilePolicy) -> Option<&'static str> {
+    match policy.filename {
+        "config.toml" => Some("codex-config"),
+        _ => None,
+    }
+}
 
 pub(crate) fn read_with_policy(
     root: &PathBuf,
@@ -22,6 +31,9 @@ pub(crate) fn write_with_policy(
     policy: FilePolicy,
     content: &str,
 ) -> Result<(), String> {
+    if let Some(kind) = journaled_config_kind(policy) {
+        record_config_write(kind, &root.join(policy.filename), content);
+    }
     write_text_file_within(
         root,
         policy.filename,
@@ -0,0 +1,729 @@
+//! Per-workspace test result history and heuristic flaky-test detection.
+//!
+//! Test runs are recorded from `run_workspace_command` output (cargo, go and
+//! pytest result lines are recognised) or reported explicitly by the task
+//! runner through `record_test_run`. A test is flagged as flaky when its
+//! outcome flips back and forth across recent runs, or when it both passed
+//! and failed at the same commit.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ignore::WalkBuilder;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::app_paths;
+use crate::engine::{self, EngineTurnLinkage, EngineType};
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const TEST_HISTORY_DIRNAME: &str = "test-history";
+const MAX_RECORDED_RUNS: usize = 200;
+const MAX_RESULTS_PER_RUN: usize = 5_000;
+const DEFAULT_ANALYSIS_WINDOW: usize = 20;
+const MAX_SOURCE_SCAN_FILES: usize = 5_000;
+const MAX_SOURCE_FILE_BYTES: u64 = 1_000_000;
+const MAX_SOURCE_EXCERPT_LINES: usize = 80;
+const MAX_PROMPT_HISTORY_RUNS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum TestOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl TestOutcome {
+    fn symbol(self) -> char {
+        match self {
+            TestOutcome::Passed => 'P',
+            TestOutcome::Failed => 'F',
+            TestOutcome::Skipped => 'S',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TestCaseResult {
+    pub(crate) test_id: String,
+    pub(crate) outcome: TestOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TestRunRecord {
+    pub(crate) id: String,
+    pub(crate) recorded_at: u64,
+    pub(crate) source: String,
+    #[serde(default)]
+    pub(crate) command: Option<String>,
+    #[serde(default)]
+    pub(crate) head_sha: Option<String>,
+    pub(crate) results: Vec<TestCaseResult>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecordTestRunRequest {
+    #[serde(default)]
+    pub(crate) source: Option<String>,
+    #[serde(default)]
+    pub(crate) command: Option<String>,
+    /// Raw runner output to parse when `results` is not given.
+    #[serde(default)]
+    pub(crate) output: Option<String>,
+    #[serde(default)]
+    pub(crate) results: Option<Vec<TestCaseResult>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FlakyTestReport {
+    pub(crate) test_id: String,
+    pub(crate) runs: usize,
+    pub(crate) passes: usize,
+    pub(crate) failures: usize,
+    pub(crate) flips: usize,
+    /// Share of consecutive runs whose outcome changed, from 0.0 to 1.0.
+    pub(crate) flakiness: f64,
+    pub(crate) mixed_on_same_commit: bool,
+    pub(crate) last_outcome: TestOutcome,
+    pub(crate) last_failed_at: Option<u64>,
+    /// Outcomes oldest to newest, one `P`/`F` per run.
+    pub(crate) history: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FlakyTestPrompt {
+    pub(crate) test_id: String,
+    pub(crate) prompt: String,
+    pub(crate) source_path: Option<String>,
+    pub(crate) turn: Option<EngineTurnLinkage>,
+}
+
+struct SourceExcerpt {
+    path: String,
+    start_line: usize,
+    text: String,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_millis(0))
+        .as_millis() as u64
+}
+
+fn is_safe_storage_id(value: &str) -> bool {
+    !value.is_empty()
+        && value != "."
+        && value != ".."
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn history_path(workspace_id: &str) -> Result<PathBuf, String> {
+    if !is_safe_storage_id(workspace_id) {
+        return Err(format!("Invalid workspace id: {workspace_id}"));
+    }
+    Ok(app_paths::app_home_dir()?
+        .join(TEST_HISTORY_DIRNAME)
+        .join(format!("{workspace_id}.json")))
+}
+
+fn read_history(path: &Path) -> Result<Vec<TestRunRecord>, String> {
+    Ok(read_json_file::<Vec<TestRunRecord>>(path)?.unwrap_or_default())
+}
+
+fn append_run(path: &Path, run: TestRunRecord) -> Result<(), String> {
+    with_storage_lock(path, || {
+        let mut runs = read_history(path)?;
+        runs.push(run);
+        if runs.len() > MAX_RECORDED_RUNS {
+            let overflow = runs.len() - MAX_RECORDED_RUNS;
+            runs.drain(..overflow);
+        }
+        let data = serde_json::to_string_pretty(&runs)
+            .map_err(|error| format!("failed to serialize {}: {error}", path.display()))?;
+        write_string_atomically(path, &data)
+    })
+}
+
+fn result_line_patterns() -> &'static [(Regex, usize, usize)] {
+    static PATTERNS: OnceLock<Vec<(Regex, usize, usize)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        // (pattern, test id group, outcome group)
+        vec![
+            (
+                Regex::new(r"^test (\S+) \.\.\. (ok|FAILED|ignored)").expect("cargo pattern"),
+                1,
+                2,
+            ),
+            (
+                Regex::new(r"^\s*--- (PASS|FAIL|SKIP): (\S+)").expect("go pattern"),
+                2,
+                1,
+            ),
+            (
+                Regex::new(r"^(\S+::\S+) (PASSED|FAILED|ERROR|SKIPPED|XFAIL|XPASS)\b")
+                    .expect("pytest verbose pattern"),
+                1,
+                2,
+            ),
+            (
+                Regex::new(r"^(FAILED|ERROR) (\S+::\S+)").expect("pytest summary pattern"),
+                2,
+                1,
+            ),
+        ]
+    })
+}
+
+fn parse_outcome(value: &str) -> Option<TestOutcome> {
+    match value {
+        "ok" | "PASS" | "PASSED" | "XPASS" => Some(TestOutcome::Passed),
+        "FAILED" | "FAIL" | "ERROR" => Some(TestOutcome::Failed),
+        "ignored" | "SKIP" | "SKIPPED" | "XFAIL" => Some(TestOutcome::Skipped),
+        _ => None,
+    }
+}
+
+/// Extracts per-test outcomes from cargo, go and pytest output. A test that
+/// shows up more than once keeps its worst outcome.
+pub(crate) fn parse_test_output(output: &str) -> Vec<TestCaseResult> {
+    let mut order: Vec<String> = Vec::new();
+    let mut outcomes: HashMap<String, TestOutcome> = HashMap::new();
+    for line in output.lines() {
+        for (pattern, id_group, outcome_group) in result_line_patterns() {
+            let Some(captures) = pattern.captures(line) else {
+                continue;
+            };
+            let Some(outcome) = parse_outcome(&captures[*outcome_group]) else {
+                continue;
+            };
+            let test_id = captures[*id_group].to_string();
+            match outcomes.get(&test_id) {
+                None => {
+                    order.push(test_id.clone());
+                    outcomes.insert(test_id, outcome);
+                }
+                Some(TestOutcome::Failed) => {}
+                Some(_) if outcome == TestOutcome::Failed => {
+                    outcomes.insert(test_id, outcome);
+                }
+                Some(_) => {}
+            }
+            break;
+        }
+    }
+    order
+        .into_iter()
+        .take(MAX_RESULTS_PER_RUN)
+        .map(|test_id| {
+            let outcome = outcomes[&test_id];
+            TestCaseResult { test_id, outcome }
+        })
+        .collect()
+}
+
+fn head_sha(workspace_path: &Path) -> Option<String> {
+    let repo = git2::Repository::discover(workspace_path).ok()?;
+    let head = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(head.id().to_string())
+}
+
+/// Flags tests whose outcome flips at least twice in the last `window` runs,
+/// or that both passed and failed at the same commit. A single pass-to-fail
+/// transition is treated as a regression (or fix), not flakiness.
+pub(crate) fn analyze_runs(runs: &[TestRunRecord], window: usize) -> Vec<FlakyTestReport> {
+    let recent = &runs[runs.len().saturating_sub(window.max(2))..];
+    let mut order: Vec<&str> = Vec::new();
+    let mut per_test: HashMap<&str, Vec<(&TestRunRecord, TestOutcome)>> = HashMap::new();
+    for run in recent {
+        for result in &run.results {
+            if result.outcome == TestOutcome::Skipped {
+                continue;
+            }
+            per_test
+                .entry(result.test_id.as_str())
+                .or_insert_with(|| {
+                    order.push(result.test_id.as_str());
+                    Vec::new()
+                })
+                .push((run, result.outcome));
+        }
+    }
+
+    let mut reports: Vec<FlakyTestReport> = order
+        .into_iter()
+        .filter_map(|test_id| {
+            let observations = &per_test[test_id];
+            let passes = observations
+                .iter()
+                .filter(|(_, outcome)| *outcome == TestOutcome::Passed)
+                .count();
+            let failures = observations.len() - passes;
+            if passes == 0 || failures == 0 {
+                return None;
+            }
+            let flips = observations
+                .windows(2)
+                .filter(|pair| pair[0].1 != pair[1].1)
+                .count();
+            let mut passed_at: HashSet<&str> = HashSet::new();
+            let mut failed_at: HashSet<&str> = HashSet::new();
+            for (run, outcome) in observations {
+                if let Some(sha) = run.head_sha.as_deref() {
+                    match outcome {
+                        TestOutcome::Passed => passed_at.insert(sha),
+                        _ => failed_at.insert(sha),
+                    };
+                }
+            }
+            let mixed_on_same_commit = !passed_at.is_disjoint(&failed_at);
+            if flips < 2 && !mixed_on_same_commit {
+                return None;
+            }
+            let (_, last_outcome) = observations[observations.len() - 1];
+            Some(FlakyTestReport {
+                test_id: test_id.to_string(),
+                runs: observations.len(),
+                passes,
+                failures,
+                flips,
+                flakiness: flips as f64 / (observations.len() - 1) as f64,
+                mixed_on_same_commit,
+                last_outcome,
+                last_failed_at: observations
+                    .iter()
+                    .rev()
+                    .find(|(_, outcome)| *outcome == TestOutcome::Failed)
+                    .map(|(run, _)| run.recorded_at),
+                history: observations
+                    .iter()
+                    .map(|(_, outcome)| outcome.symbol())
+                    .collect(),
+            })
+        })
+        .collect();
+    reports.sort_by(|a, b| {
+        b.flakiness
+            .total_cmp(&a.flakiness)
+            .then_with(|| b.failures.cmp(&a.failures))
+            .then_with(|| a.test_id.cmp(&b.test_id))
+    });
+    reports
+}
+
+/// Splits a test id into an optional source file hint and the bare test name.
+fn test_name_parts(test_id: &str) -> (Option<&str>, &str) {
+    if let Some((file, rest)) = test_id.split_once("::") {
+        if file.ends_with(".py") {
+            return (Some(file), rest.rsplit("::").next().unwrap_or(rest));
+        }
+    }
+    let name = test_id.rsplit("::").next().unwrap_or(test_id);
+    // Go subtests are reported as `TestParent/case`.
+    let name = name.split('/').next().unwrap_or(name);
+    (None, name)
+}
+
+fn definition_line(content: &str, name: &str) -> Option<usize> {
+    let needles = [
+        format!("fn {name}("),
+        format!("def {name}("),
+        format!("func {name}("),
+    ];
+    content
+        .lines()
+        .position(|line| needles.iter().any(|needle| line.contains(needle.as_str())))
+}
+
+fn excerpt_from(path: &str, content: &str, line: usize) -> SourceExcerpt {
+    let start = line.saturating_sub(5);
+    let text = content
+        .lines()
+        .skip(start)
+        .take(MAX_SOURCE_EXCERPT_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    SourceExcerpt {
+        path: path.to_string(),
+        start_line: start + 1,
+        text,
+    }
+}
+
+fn find_test_source(workspace_root: &Path, test_id: &str) -> Option<SourceExcerpt> {
+    let (file_hint, name) = test_name_parts(test_id);
+    if let Some(file) = file_hint {
+        let content = std::fs::read_to_string(workspace_root.join(file)).ok()?;
+        let line = definition_line(&content, name)?;
+        return Some(excerpt_from(file, &content, line));
+    }
+    let walker = WalkBuilder::new(workspace_root).hidden(true).build();
+    for entry in walker.flatten().take(MAX_SOURCE_SCAN_FILES) {
+        let path = entry.path();
+        let is_source = matches!(
+            path.extension().and_then(|value| value.to_str()),
+            Some("rs" | "go" | "py")
+        );
+        if !is_source
+            || entry
+                .metadata()
+                .map(|metadata| metadata.len() > MAX_SOURCE_FILE_BYTES)
+                .unwrap_or(true)
+        {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        if let Some(line) = definition_line(&content, name) {
+            let relative = path
+                .strip_prefix(workspace_root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            return Some(excerpt_from(&relative, &content, line));
+        }
+    }
+    None
+}
+
+fn build_prompt(
+    test_id: &str,
+    report: Option<&FlakyTestReport>,
+    runs: &[TestRunRecord],
+    source: Option<&SourceExcerpt>,
+) -> String {
+    let mut prompt = format!(
+        "The test `{test_id}` looks flaky: it fails intermittently without related code changes. \
+Find the source of nondeterminism (timing, ordering, shared state, randomness, environment or \
+external dependencies) and make the test reliable without weakening what it checks.\n\n"
+    );
+    if let Some(report) = report {
+        prompt.push_str(&format!(
+            "Recent outcomes (oldest to newest, P = pass, F = fail): {}\n\
+{} passes, {} failures, {} outcome flips across {} runs{}.\n\n",
+            report.history,
+            report.passes,
+            report.failures,
+            report.flips,
+            report.runs,
+            if report.mixed_on_same_commit {
+                "; it both passed and failed at the same commit"
+            } else {
+                ""
+            }
+        ));
+    }
+    let observations: Vec<String> = runs
+        .iter()
+        .rev()
+        .filter_map(|run| {
+            let result = run
+                .results
+                .iter()
+                .find(|result| result.test_id == test_id)?;
+            Some(format!(
+                "- {:?} at {} (commit {}, via {})",
+                result.outcome,
+                chrono::DateTime::from_timestamp_millis(run.recorded_at as i64)
+                    .map(|time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+                    .unwrap_or_else(|| run.recorded_at.to_string()),
+                run.head_sha
+                    .as_deref()
+                    .map(|sha| &sha[..sha.len().min(8)])
+                    .unwrap_or("unknown"),
+                run.command.as_deref().unwrap_or(run.source.as_str()),
+            ))
+        })
+        .take(MAX_PROMPT_HISTORY_RUNS)
+        .collect();
+    if !observations.is_empty() {
+        prompt.push_str("Run history (newest first):\n");
+        prompt.push_str(&observations.join("\n"));
+        prompt.push_str("\n\n");
+    }
+    match source {
+        Some(source) => prompt.push_str(&format!(
+            "Test source ({} from line {}):\n```\n{}\n```\n",
+            source.path, source.start_line, source.text
+        )),
+        None => prompt.push_str("The test source could not be located automatically.\n"),
+    }
+    prompt
+}
+
+async fn workspace_path(state: &AppState, workspace_id: &str) -> Result<PathBuf, String> {
+    let workspaces = state.workspaces.lock().await;
+    workspaces
+        .get(workspace_id)
+        .map(|entry| PathBuf::from(&entry.path))
+        .ok_or_else(|| format!("Workspace not found: {workspace_id}"))
+}
+
+fn record_results(
+    workspace_id: &str,
+    workspace_root: &Path,
+    source: String,
+    command: Option<String>,
+    mut results: Vec<TestCaseResult>,
+) -> Result<TestRunRecord, String> {
+    results.retain(|result| !result.test_id.trim().is_empty());
+    results.truncate(MAX_RESULTS_PER_RUN);
+    let run = TestRunRecord {
+        id: Uuid::new_v4().to_string(),
+        recorded_at: now_millis(),
+        source,
+        command,
+        head_sha: head_sha(workspace_root),
+        results,
+    };
+    append_run(&history_path(workspace_id)?, run.clone())?;
+    Ok(run)
+}
+
+/// Records test results found in a workspace command's output. Commands that
+/// print no recognisable test lines are ignored.
+pub(crate) fn record_command_output(
+    workspace_id: &str,
+    workspace_root: &Path,
+    command: &[String],
+    output: &str,
+) {
+    let results = parse_test_output(output);
+    if results.is_empty() {
+        return;
+    }
+    if let Err(error) = record_results(
+        workspace_id,
+        workspace_root,
+        "workspace-command".to_string(),
+        Some(command.join(" ")),
+        results,
+    ) {
+        log::warn!("[flaky-tests] failed to record test run: {error}");
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn record_test_run(
+    workspace_id: String,
+    request: RecordTestRunRequest,
+    state: State<'_, AppState>,
+) -> Result<TestRunRecord, String> {
+    let root = workspace_path(&state, &workspace_id).await?;
+    let results = match request.results {
+        Some(results) => results,
+        None => parse_test_output(request.output.as_deref().unwrap_or_default()),
+    };
+    if results.is_empty() {
+        return Err("No test results found in the run".to_string());
+    }
+    let source = request
+        .source
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "task-runner".to_string());
+    record_results(&workspace_id, &root, source, request.command, results)
+}
+
+#[tauri::command]
+pub(crate) async fn analyze_flaky_tests(
+    workspace_id: String,
+    window_runs: Option<usize>,
+) -> Result<Vec<FlakyTestReport>, String> {
+    let runs = read_history(&history_path(&workspace_id)?)?;
+    Ok(analyze_runs(
+        &runs,
+        window_runs.unwrap_or(DEFAULT_ANALYSIS_WINDOW),
+    ))
+}
+
+/// Builds a prompt with the test's run history and source. When `engine` is
+/// given, the prompt is also sent as a new engine turn.
+#[tauri::command]
+pub(crate) async fn flaky_test_prompt(
+    workspace_id: String,
+    test_id: String,
+    engine: Option<EngineType>,
+    model: Option<String>,
+    access_mode: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<FlakyTestPrompt, String> {
+    let root = workspace_path(&state, &workspace_id).await?;
+    let runs = read_history(&history_path(&workspace_id)?)?;
+    if !runs
+        .iter()
+        .any(|run| run.results.iter().any(|result| result.test_id == test_id))
+    {
+        return Err(format!("No recorded runs for test {test_id}"));
+    }
+    let report = analyze_runs(&runs, MAX_RECORDED_RUNS)
+        .into_iter()
+        .find(|report| report.test_id == test_id);
+    let source = {
+        let root = root.clone();
+        let test_id = test_id.clone();
+        tokio::task::spawn_blocking(move || find_test_source(&root, &test_id))
+            .await
+            .map_err(|error| error.to_string())?
+    };
+    let prompt = build_prompt(&test_id, report.as_ref(), &runs, source.as_ref());
+    let turn = match engine {
+        Some(engine) => Some(
+            engine::start_engine_turn(
+                &workspace_id,
+                engine,
+                prompt.clone(),
+                model,
+                access_mode,
+                state,
+                app,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+    Ok(FlakyTestPrompt {
+        test_id,
+        prompt,
+        source_path: source.map(|source| source.path),
+        turn,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze_runs, parse_test_output, TestCaseResult, TestOutcome, TestRunRecord};
+
+    fn run(index: u64, sha: &str, outcomes: &[(&str, TestOutcome)]) -> TestRunRecord {
+        TestRunRecord {
+            id: format!("run-{index}"),
+            recorded_at: index,
+            source: "test".to_string(),
+            command: None,
+            head_sha: Some(sha.to_string()),
+            results: outcomes
+                .iter()
+                .map(|(test_id, outcome)| TestCaseResult {
+                    test_id: test_id.to_string(),
+                    outcome: *outcome,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parses_cargo_go_and_pytest_output() {
+        let output = "\
+test storage::tests::round_trip ... ok
+test net::tests::retries ... FAILED
+test slow::tests::huge ... ignored
+--- FAIL: TestFetch (0.01s)
+    --- PASS: TestFetch/cached (0.00s)
+tests/test_api.py::test_login PASSED [ 50%]
+FAILED tests/test_api.py::test_logout - AssertionError
+";
+        let results = parse_test_output(output);
+        let find = |id: &str| {
+            results
+                .iter()
+                .find(|result| result.test_id == id)
+                .map(|result| result.outcome)
+        };
+
+        assert_eq!(
+            find("storage::tests::round_trip"),
+            Some(TestOutcome::Passed)
+        );
+        assert_eq!(find("net::tests::retries"), Some(TestOutcome::Failed));
+        assert_eq!(find("slow::tests::huge"), Some(TestOutcome::Skipped));
+        assert_eq!(find("TestFetch"), Some(TestOutcome::Failed));
+        assert_eq!(find("TestFetch/cached"), Some(TestOutcome::Passed));
+        assert_eq!(
+            find("tests/test_api.py::test_login"),
+            Some(TestOutcome::Passed)
+        );
+        assert_eq!(
+            find("tests/test_api.py::test_logout"),
+            Some(TestOutcome::Failed)
+        );
+    }
+
+    #[test]
+    fn flags_intermittent_failures_but_not_regressions() {
+        use TestOutcome::{Failed, Passed};
+        let runs = vec![
+            run(
+                1,
+                "a",
+                &[
+                    ("flaky", Passed),
+                    ("regressed", Passed),
+                    ("same_sha", Passed),
+                ],
+            ),
+            run(
+                2,
+                "b",
+                &[
+                    ("flaky", Failed),
+                    ("regressed", Passed),
+                    ("same_sha", Passed),
+                ],
+            ),
+            run(
+                3,
+                "c",
+                &[
+                    ("flaky", Passed),
+                    ("regressed", Failed),
+                    ("same_sha", Passed),
+                ],
+            ),
+            run(
+                4,
+                "d",
+                &[
+                    ("flaky", Failed),
+                    ("regressed", Failed),
+                    ("same_sha", Passed),
+                ],
+            ),
+            run(
+                5,
+                "d",
+                &[
+                    ("flaky", Passed),
+                    ("regressed", Failed),
+                    ("same_sha", Failed),
+                ],
+            ),
+        ];
+
+        let reports = analyze_runs(&runs, 20);
+        let ids: Vec<&str> = reports
+            .iter()
+            .map(|report| report.test_id.as_str())
+            .collect();
+
+        assert_eq!(ids, vec!["flaky", "same_sha"]);
+        assert_eq!(reports[0].history, "PFPFP");
+        assert_eq!(reports[0].flips, 4);
+        assert!(reports[1].mixed_on_same_commit);
+        assert_eq!(reports[1].last_failed_at, Some(5));
+    }
+}
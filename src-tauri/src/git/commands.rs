@@ -1,10 +1,82 @@
 use super::*;
 
+#[path = "commands_agent_branch.rs"]
+mod commands_agent_branch;
+#[path = "commands_blame.rs"]
+mod commands_blame;
+#[path = "commands_blame_context.rs"]
+mod commands_blame_context;
 #[path = "commands_branch.rs"]
 mod commands_branch;
+#[path = "commands_branch_status.rs"]
+mod commands_branch_status;
+#[path = "commands_commit.rs"]
+mod commands_commit;
+#[path = "commands_conflicts.rs"]
+mod commands_conflicts;
+#[path = "commands_diff_actions.rs"]
+mod commands_diff_actions;
+#[path = "commands_diff_stats.rs"]
+mod commands_diff_stats;
+#[path = "commands_file_at_ref.rs"]
+mod commands_file_at_ref;
+#[path = "commands_file_history.rs"]
+mod commands_file_history;
+#[path = "commands_hunks.rs"]
+mod commands_hunks;
+#[path = "commands_identity.rs"]
+mod commands_identity;
+#[path = "commands_operation.rs"]
+mod commands_operation;
+#[path = "commands_pr_checkout.rs"]
+mod commands_pr_checkout;
+#[path = "commands_pr_files.rs"]
+mod commands_pr_files;
 #[path = "commands_pr_workflow.rs"]
 mod commands_pr_workflow;
+#[path = "commands_reflog.rs"]
+mod commands_reflog;
+#[path = "commands_remotes.rs"]
+mod commands_remotes;
+#[path = "commands_review_bundle.rs"]
+mod commands_review_bundle;
+#[path = "commands_signing.rs"]
+mod commands_signing;
+#[path = "commands_stash.rs"]
+mod commands_stash;
+#[path = "commands_status_watch.rs"]
+mod commands_status_watch;
+#[path = "commands_submodules.rs"]
+mod commands_submodules;
+#[path = "commands_tags.rs"]
+mod commands_tags;
+#[path = "commands_worktrees.rs"]
+mod commands_worktrees;
+pub(crate) use commands_agent_branch::*;
+pub(crate) use commands_blame::*;
+pub(crate) use commands_blame_context::*;
 pub(crate) use commands_branch::*;
+pub(crate) use commands_branch_status::*;
+pub(crate) use commands_commit::*;
+pub(crate) use commands_conflicts::*;
+pub(crate) use commands_diff_actions::*;
+pub(crate) use commands_diff_stats::*;
+pub(crate) use commands_file_at_ref::*;
+pub(crate) use commands_file_history::*;
+pub(crate) use commands_hunks::*;
+pub(crate) use commands_identity::*;
+pub(crate) use commands_operation::*;
+pub(crate) use commands_pr_checkout::*;
+pub(crate) use commands_pr_files::*;
+pub(crate) use commands_reflog::*;
+pub(crate) use commands_remotes::*;
+pub(crate) use commands_review_bundle::*;
+pub(crate) use commands_signing::*;
+pub(crate) use commands_stash::*;
+pub(crate) use commands_status_watch::*;
+pub(crate) use commands_submodules::*;
+pub(crate) use commands_tags::*;
+pub(crate) use commands_worktrees::*;
 
 #[tauri::command]
 pub(crate) async fn get_git_status(
@@ -19,10 +91,15 @@ pub(crate) async fn get_git_status(
     drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    if !path_has_git_repository_marker(&repo_root) {
+    git_status_snapshot(&repo_root)
+}
+
+/// The `get_git_status` payload for the repository at `repo_root`.
+pub(super) fn git_status_snapshot(repo_root: &Path) -> Result<serde_json::Value, String> {
+    if !path_has_git_repository_marker(repo_root) {
         return Ok(empty_git_status_snapshot(false));
     }
-    let repo = open_repository_at_root(&repo_root)?;
+    let repo = open_repository_at_root(repo_root)?;
 
     let branch_name = repo
         .head()
@@ -45,6 +122,7 @@ pub(crate) async fn get_git_status(
 
     let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
     let index = repo.index().ok();
+    let submodules = submodule_ids_by_path(&repo);
 
     let mut files = Vec::new();
     let mut staged_files = Vec::new();
@@ -52,7 +130,11 @@ pub(crate) async fn get_git_status(
     let mut total_additions = 0i64;
     let mut total_deletions = 0i64;
     for entry in statuses.iter() {
-        let path = entry.path().unwrap_or("");
+        // Non-UTF-8 paths used to be dropped; list them by their lossy name
+        // and skip the per-path lookups that need the exact bytes.
+        let lossy_path = String::from_utf8_lossy(entry.path_bytes());
+        let path_is_utf8 = matches!(lossy_path, std::borrow::Cow::Borrowed(_));
+        let path = lossy_path.as_ref();
         if path.is_empty() {
             continue;
         }
@@ -79,8 +161,29 @@ pub(crate) async fn get_git_status(
                 | Status::WT_RENAMED
                 | Status::WT_TYPECHANGE,
         );
+        if let Some(submodule) = submodules.get(&normalized_path) {
+            // A submodule has no line diff; report the commits it moved
+            // between instead.
+            let submodule_status = |heads| GitFileStatus {
+                path: normalized_path.clone(),
+                status: SUBMODULE_STATUS.to_string(),
+                additions: 0,
+                deletions: 0,
+                submodule: Some(heads),
+            };
+            if include_index {
+                staged_files.push(submodule_status(submodule.staged()));
+            }
+            if include_workdir {
+                unstaged_files.push(submodule_status(submodule.unstaged()));
+            }
+            if include_index || include_workdir {
+                files.push(submodule_status(submodule.combined()));
+            }
+            continue;
+        }
         let should_compute_path_diff_stats =
-            should_compute_diff_stats && !should_skip_diff_stats(&repo_root, path);
+            should_compute_diff_stats && path_is_utf8 && !should_skip_diff_stats(repo_root, path);
         let mut combined_additions = 0i64;
         let mut combined_deletions = 0i64;
 
@@ -96,6 +199,7 @@ pub(crate) async fn get_git_status(
                     status: status_str.to_string(),
                     additions,
                     deletions,
+                    submodule: None,
                 });
             }
             combined_additions += additions;
@@ -116,6 +220,7 @@ pub(crate) async fn get_git_status(
                     status: status_str.to_string(),
                     additions,
                     deletions,
+                    submodule: None,
                 });
             }
             combined_additions += additions;
@@ -133,6 +238,7 @@ pub(crate) async fn get_git_status(
                 status: status_str.to_string(),
                 additions: combined_additions,
                 deletions: combined_deletions,
+                submodule: None,
             });
         }
     }
@@ -166,6 +272,7 @@ pub(crate) async fn stage_git_file(
     path: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "stage_git_file").await?;
     let entry = {
         let workspaces = state.workspaces.lock().await;
         workspaces
@@ -188,6 +295,7 @@ pub(crate) async fn stage_git_all(
     workspace_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "stage_git_all").await?;
     let entry = {
         let workspaces = state.workspaces.lock().await;
         workspaces
@@ -206,6 +314,7 @@ pub(crate) async fn unstage_git_file(
     path: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "unstage_git_file").await?;
     let entry = {
         let workspaces = state.workspaces.lock().await;
         workspaces
@@ -227,6 +336,7 @@ pub(crate) async fn revert_git_file(
     path: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "revert_git_file").await?;
     let entry = {
         let workspaces = state.workspaces.lock().await;
         workspaces
@@ -256,6 +366,7 @@ pub(crate) async fn revert_git_all(
     workspace_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "revert_git_all").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces.get(&workspace_id).ok_or("workspace not found")?;
     let repo_root = resolve_git_root(entry)?;
@@ -271,16 +382,28 @@ pub(crate) async fn revert_git_all(
 pub(crate) async fn commit_git(
     workspace_id: String,
     message: String,
+    sign: Option<bool>,
+    amend: Option<bool>,
+    force: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "commit_git").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    run_git_command(&repo_root, &["commit", "-m", &message]).await
+    commit_in_repo(
+        &repo_root,
+        &message,
+        amend.unwrap_or(false),
+        force.unwrap_or(false),
+        sign,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -297,6 +420,7 @@ pub(crate) async fn push_git(
     cc: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "push_git").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -413,6 +537,7 @@ pub(crate) async fn pull_git(
     no_verify: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "pull_git").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -452,6 +577,7 @@ pub(crate) async fn sync_git(
     workspace_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "sync_git").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -508,6 +634,7 @@ pub(crate) async fn git_fetch(
     remote: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "git_fetch").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -532,6 +659,7 @@ pub(crate) async fn cherry_pick_commit(
     commit_hash: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "cherry_pick_commit").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -548,6 +676,7 @@ pub(crate) async fn revert_commit(
     commit_hash: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "revert_commit").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -565,6 +694,7 @@ pub(crate) async fn reset_git_commit(
     mode: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "reset_git_commit").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -609,6 +739,7 @@ pub(crate) async fn list_git_roots(
 pub(crate) async fn get_workspace_diff(
     workspace_id: &str,
     state: &State<'_, AppState>,
+    staged_only: bool,
 ) -> Result<String, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
@@ -618,18 +749,15 @@ pub(crate) async fn get_workspace_diff(
     drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    collect_workspace_diff(&repo_root)
+    collect_workspace_diff(&repo_root, staged_only)
 }
 
 pub(crate) async fn get_workspace_diff_for_commit_scope(
     workspace_id: &str,
     state: &State<'_, AppState>,
     selected_paths: Option<&[String]>,
-) -> Result<String, String> {
-    if selected_paths.is_none() {
-        return get_workspace_diff(workspace_id, state).await;
-    }
-
+    staged_only: bool,
+) -> Result<CommitScopeDiff, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(workspace_id)
@@ -638,165 +766,284 @@ pub(crate) async fn get_workspace_diff_for_commit_scope(
     drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    collect_commit_scope_diff(&repo_root, selected_paths)
+    let diff = if selected_paths.is_none() {
+        get_workspace_diff(workspace_id, state, staged_only).await?
+    } else {
+        collect_commit_scope_diff(&repo_root, selected_paths, staged_only)?
+    };
+    let files = collect_commit_scope_files(&repo_root, selected_paths, staged_only)?;
+    Ok(CommitScopeDiff { diff, files })
 }
 
-#[tauri::command]
-pub(crate) async fn get_git_diffs(
-    workspace_id: String,
-    state: State<'_, AppState>,
+/// Limits for one `get_git_diffs` call. Files past `max_files`, and patches
+/// over `max_bytes_per_file` when set, are listed summary-only so the list
+/// still counts every changed file; their full diff loads on demand through
+/// `get_git_file_full_diff`.
+pub(super) struct WorktreeDiffLimits {
+    pub(super) paths: Vec<String>,
+    pub(super) max_files: usize,
+    pub(super) max_bytes_per_file: Option<usize>,
+    pub(super) max_payload_bytes: usize,
+}
+
+pub(super) fn collect_worktree_diffs(
+    repo_root: &Path,
+    limits: &WorktreeDiffLimits,
 ) -> Result<Vec<GitFileDiff>, String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
+    let repo = open_repository_at_root(repo_root)?;
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
 
-    let repo_root = resolve_git_root(&entry)?;
-    tokio::task::spawn_blocking(move || {
-        let repo = open_repository_at_root(&repo_root)?;
-        let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    for path in &limits.paths {
+        options.pathspec(path);
+    }
 
-        let mut options = DiffOptions::new();
-        options
-            .include_untracked(true)
-            .recurse_untracked_dirs(true)
-            .show_untracked_content(true);
+    let diff = match head_tree.as_ref() {
+        Some(tree) => repo
+            .diff_tree_to_workdir_with_index(Some(tree), Some(&mut options))
+            .map_err(|e| e.to_string())?,
+        None => repo
+            .diff_tree_to_workdir_with_index(None, Some(&mut options))
+            .map_err(|e| e.to_string())?,
+    };
 
-        let diff = match head_tree.as_ref() {
-            Some(tree) => repo
-                .diff_tree_to_workdir_with_index(Some(tree), Some(&mut options))
-                .map_err(|e| e.to_string())?,
-            None => repo
-                .diff_tree_to_workdir_with_index(None, Some(&mut options))
-                .map_err(|e| e.to_string())?,
+    let mut results = Vec::new();
+    let mut total_diff_bytes = 0usize;
+    let mut included_deltas = 0usize;
+    for (index, delta) in diff.deltas().enumerate() {
+        let old_path = delta.old_file().path();
+        let new_path = delta.new_file().path();
+        let display_path = new_path.or(old_path);
+        let Some(display_path) = display_path else {
+            continue;
         };
+        let old_path_str = old_path.map(|path| path.to_string_lossy());
+        let new_path_str = new_path.map(|path| path.to_string_lossy());
+        let display_path_str = display_path.to_string_lossy();
+        let normalized_path = normalize_git_path(&display_path_str);
+        if included_deltas >= limits.max_files {
+            let size = delta.new_file().size().max(delta.old_file().size());
+            results.push(summary_only_file_diff(
+                normalized_path,
+                "file-limit",
+                (size > 0).then_some(size),
+            ));
+            continue;
+        }
+        let max_file_size = delta
+            .new_file()
+            .size()
+            .max(delta.old_file().size())
+            .max(worktree_file_size(repo_root, &display_path_str).unwrap_or(0));
+
+        if is_heavy_diff_path(&display_path_str) {
+            results.push(summary_only_file_diff(
+                normalized_path,
+                "generated",
+                Some(max_file_size),
+            ));
+            included_deltas += 1;
+            continue;
+        }
+        if max_file_size > GIT_DIFF_PREVIEW_SKIP_FILE_SIZE_BYTES {
+            results.push(summary_only_file_diff(
+                normalized_path,
+                "too-large",
+                Some(max_file_size),
+            ));
+            included_deltas += 1;
+            continue;
+        }
 
-        let mut results = Vec::new();
-        let mut total_diff_bytes = 0usize;
-        let mut included_deltas = 0usize;
-        for (index, delta) in diff.deltas().enumerate() {
-            if included_deltas >= GIT_DIFF_PREVIEW_MAX_FILES {
-                break;
-            }
+        let old_image_mime = old_path_str.as_deref().and_then(image_mime_type);
+        let new_image_mime = new_path_str.as_deref().and_then(image_mime_type);
+        let is_image = old_image_mime.is_some() || new_image_mime.is_some();
 
-            let old_path = delta.old_file().path();
-            let new_path = delta.new_file().path();
-            let display_path = new_path.or(old_path);
-            let Some(display_path) = display_path else {
-                continue;
+        if is_image {
+            let is_deleted = delta.status() == git2::Delta::Deleted;
+            let is_added = delta.status() == git2::Delta::Added;
+
+            let old_image_data = if !is_added && old_image_mime.is_some() {
+                head_tree
+                    .as_ref()
+                    .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
+                    .and_then(|entry| repo.find_blob(entry.id()).ok())
+                    .and_then(blob_to_base64)
+            } else {
+                None
             };
-            let old_path_str = old_path.map(|path| path.to_string_lossy());
-            let new_path_str = new_path.map(|path| path.to_string_lossy());
-            let display_path_str = display_path.to_string_lossy();
-            let normalized_path = normalize_git_path(&display_path_str);
-            let max_file_size = delta.new_file().size().max(delta.old_file().size());
-
-            if is_heavy_diff_path(&display_path_str)
-                || max_file_size > GIT_DIFF_PREVIEW_SKIP_FILE_SIZE_BYTES
-                || is_large_worktree_file(
-                    &repo_root,
-                    &display_path_str,
-                    GIT_DIFF_PREVIEW_SKIP_FILE_SIZE_BYTES,
-                )
-            {
-                continue;
-            }
 
-            let old_image_mime = old_path_str.as_deref().and_then(image_mime_type);
-            let new_image_mime = new_path_str.as_deref().and_then(image_mime_type);
-            let is_image = old_image_mime.is_some() || new_image_mime.is_some();
-
-            if is_image {
-                let is_deleted = delta.status() == git2::Delta::Deleted;
-                let is_added = delta.status() == git2::Delta::Added;
-
-                let old_image_data = if !is_added && old_image_mime.is_some() {
-                    head_tree
-                        .as_ref()
-                        .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
-                        .and_then(|entry| repo.find_blob(entry.id()).ok())
-                        .and_then(blob_to_base64)
-                } else {
-                    None
-                };
-
-                let new_image_data = if !is_deleted && new_image_mime.is_some() {
-                    match new_path {
-                        Some(path) => {
-                            let full_path = repo_root.join(path);
-                            read_image_base64(&full_path)
-                        }
-                        None => None,
+            let new_image_data = if !is_deleted && new_image_mime.is_some() {
+                match new_path {
+                    Some(path) => {
+                        let full_path = repo_root.join(path);
+                        read_image_base64(&full_path)
                     }
-                } else {
-                    None
-                };
-
-                results.push(GitFileDiff {
-                    path: normalized_path,
-                    diff: String::new(),
-                    is_binary: true,
-                    is_image: true,
-                    old_image_data,
-                    new_image_data,
-                    old_image_mime: old_image_mime.map(str::to_string),
-                    new_image_mime: new_image_mime.map(str::to_string),
-                });
-                included_deltas += 1;
-                continue;
-            }
-
-            let patch = match git2::Patch::from_diff(&diff, index) {
-                Ok(patch) => patch,
-                Err(_) => continue,
-            };
-            let Some(mut patch) = patch else {
-                continue;
-            };
-            let content = match diff_patch_to_string(&mut patch) {
-                Ok(content) => content,
-                Err(_) => continue,
+                    None => None,
+                }
+            } else {
+                None
             };
-            if content.trim().is_empty() {
-                continue;
-            }
-
-            if total_diff_bytes >= GIT_DIFF_PREVIEW_MAX_TOTAL_BYTES {
-                break;
-            }
-            let remaining_budget = GIT_DIFF_PREVIEW_MAX_TOTAL_BYTES - total_diff_bytes;
-            let per_file_budget = remaining_budget.min(GIT_DIFF_PREVIEW_MAX_BYTES_PER_FILE);
-            if per_file_budget == 0 {
-                break;
-            }
-            let trimmed_content = truncate_diff_preview(
-                content,
-                GIT_DIFF_PREVIEW_MAX_LINES_PER_FILE,
-                per_file_budget,
-            );
-            if trimmed_content.trim().is_empty() {
-                continue;
-            }
-            total_diff_bytes += trimmed_content.len();
 
             results.push(GitFileDiff {
                 path: normalized_path,
-                diff: trimmed_content,
-                is_binary: false,
-                is_image: false,
-                old_image_data: None,
-                new_image_data: None,
-                old_image_mime: None,
-                new_image_mime: None,
+                diff: String::new(),
+                is_binary: true,
+                is_image: true,
+                old_image_data,
+                new_image_data,
+                old_image_mime: old_image_mime.map(str::to_string),
+                new_image_mime: new_image_mime.map(str::to_string),
+                summary_only: false,
+                summary_reason: None,
+                size_bytes: Some(max_file_size),
+                encoding: None,
+                encoding_fallback: false,
             });
             included_deltas += 1;
+            continue;
         }
 
-        Ok(results)
-    })
-    .await
-    .map_err(|e| e.to_string())?
+        if total_diff_bytes >= limits.max_payload_bytes {
+            results.push(summary_only_file_diff(
+                normalized_path,
+                "payload-limit",
+                Some(max_file_size),
+            ));
+            included_deltas += 1;
+            continue;
+        }
+
+        let patch = match git2::Patch::from_diff(&diff, index) {
+            Ok(patch) => patch,
+            Err(_) => continue,
+        };
+        let Some(mut patch) = patch else {
+            continue;
+        };
+        let (content, encoding, encoding_fallback) = if patch.delta().flags().is_binary() {
+            match utf16_text_patch(&repo, repo_root, &delta) {
+                Some((content, encoding)) => (content, Some(encoding.to_string()), false),
+                None => {
+                    results.push(GitFileDiff {
+                        path: normalized_path,
+                        diff: String::new(),
+                        is_binary: true,
+                        is_image: false,
+                        old_image_data: None,
+                        new_image_data: None,
+                        old_image_mime: None,
+                        new_image_mime: None,
+                        summary_only: false,
+                        summary_reason: None,
+                        size_bytes: Some(max_file_size),
+                        encoding: None,
+                        encoding_fallback: false,
+                    });
+                    included_deltas += 1;
+                    continue;
+                }
+            }
+        } else {
+            let decoded = match diff_patch_to_text(&mut patch) {
+                Ok(decoded) => decoded,
+                Err(_) => continue,
+            };
+            let encoding = (decoded.encoding != "UTF-8").then(|| decoded.encoding.to_string());
+            (decoded.text, encoding, decoded.fallback)
+        };
+        if content.trim().is_empty() {
+            continue;
+        }
+        if limits
+            .max_bytes_per_file
+            .is_some_and(|max_bytes| content.len() > max_bytes)
+        {
+            results.push(summary_only_file_diff(
+                normalized_path,
+                "file-size-limit",
+                Some(max_file_size),
+            ));
+            included_deltas += 1;
+            continue;
+        }
+
+        let remaining_budget = limits.max_payload_bytes - total_diff_bytes;
+        let per_file_budget = remaining_budget.min(GIT_DIFF_PREVIEW_MAX_BYTES_PER_FILE);
+        let trimmed_content = truncate_diff_preview(
+            content,
+            GIT_DIFF_PREVIEW_MAX_LINES_PER_FILE,
+            per_file_budget,
+        );
+        if trimmed_content.trim().is_empty() {
+            continue;
+        }
+        total_diff_bytes += trimmed_content.len();
+
+        results.push(GitFileDiff {
+            path: normalized_path,
+            diff: trimmed_content,
+            is_binary: false,
+            is_image: false,
+            old_image_data: None,
+            new_image_data: None,
+            old_image_mime: None,
+            new_image_mime: None,
+            summary_only: false,
+            summary_reason: None,
+            size_bytes: Some(max_file_size),
+            encoding,
+            encoding_fallback,
+        });
+        included_deltas += 1;
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_diffs(
+    workspace_id: String,
+    paths: Option<Vec<String>>,
+    max_files: Option<usize>,
+    max_bytes_per_file: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitFileDiff>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+
+    let repo_root = resolve_git_root(&entry)?;
+    drop(workspaces);
+    let max_payload_bytes = {
+        let settings = state.app_settings.lock().await;
+        git_diff_payload_budget(settings.git_diff_max_payload_kb)
+    };
+    let limits = WorktreeDiffLimits {
+        paths: paths
+            .unwrap_or_default()
+            .iter()
+            .map(|path| {
+                normalize_git_path(path)
+                    .trim()
+                    .trim_matches('/')
+                    .to_string()
+            })
+            .filter(|path| !path.is_empty())
+            .collect(),
+        max_files: max_files.unwrap_or(GIT_DIFF_PREVIEW_MAX_FILES),
+        max_bytes_per_file,
+        max_payload_bytes,
+    };
+    tokio::task::spawn_blocking(move || collect_worktree_diffs(&repo_root, &limits))
+        .await
+        .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
@@ -994,6 +1241,7 @@ pub(crate) async fn get_git_commit_history(
     snapshot_id: Option<String>,
     offset: Option<usize>,
     limit: Option<usize>,
+    include_total: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<GitHistoryResponse, String> {
     let workspaces = state.workspaces.lock().await;
@@ -1004,7 +1252,58 @@ pub(crate) async fn get_git_commit_history(
     drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    let repo = open_repository_at_root(&repo_root)?;
+    // The walk can cover hundreds of thousands of commits; keep it off the
+    // async executor so other commands are not stalled behind it.
+    tokio::task::spawn_blocking(move || {
+        load_commit_history(
+            &repo_root,
+            HistoryRequest {
+                branch,
+                query,
+                author,
+                date_from,
+                date_to,
+                snapshot_id,
+                offset: offset.unwrap_or(0),
+                limit: limit.unwrap_or(100).clamp(1, 500),
+                include_total: include_total.unwrap_or(true),
+            },
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+struct HistoryRequest {
+    branch: Option<String>,
+    query: Option<String>,
+    author: Option<String>,
+    date_from: Option<i64>,
+    date_to: Option<i64>,
+    snapshot_id: Option<String>,
+    offset: usize,
+    limit: usize,
+    /// When `false` the walk stops as soon as the page and one extra match
+    /// are found, and `total` is only a lower bound.
+    include_total: bool,
+}
+
+fn load_commit_history(
+    repo_root: &Path,
+    request: HistoryRequest,
+) -> Result<GitHistoryResponse, String> {
+    let HistoryRequest {
+        branch,
+        query,
+        author,
+        date_from,
+        date_to,
+        snapshot_id,
+        offset,
+        limit,
+        include_total,
+    } = request;
+    let repo = open_repository_at_root(repo_root)?;
     let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
     revwalk
         .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
@@ -1046,8 +1345,6 @@ pub(crate) async fn get_git_commit_history(
         revwalk.push_head().map_err(|e| e.to_string())?;
     }
 
-    let offset = offset.unwrap_or(0);
-    let limit = limit.unwrap_or(100).clamp(1, 500);
     let provided_snapshot_id = snapshot_id.and_then(|value| {
         let trimmed = value.trim();
         if trimmed.is_empty() {
@@ -1080,8 +1377,14 @@ pub(crate) async fn get_git_commit_history(
         }
     }
 
-    let mut filtered = Vec::new();
+    let mut window = HistoryWindow::new(offset, limit, include_total);
+    let mut exhausted = true;
     for oid_result in revwalk {
+        if window.is_done() {
+            exhausted = false;
+            break;
+        }
+        window.note_walked();
         let oid = oid_result.map_err(|e| e.to_string())?;
         let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
         let commit_time = commit.time().seconds();
@@ -1118,33 +1421,31 @@ pub(crate) async fn get_git_commit_history(
             }
         }
 
-        let short_sha: String = sha.chars().take(7).collect();
-        let parents = commit
-            .parents()
-            .map(|parent| parent.id().to_string())
-            .collect();
-        let refs = refs_map.get(&oid).cloned().unwrap_or_default();
-        filtered.push(GitHistoryCommit {
+        window.push_match(|| GitHistoryCommit {
+            short_sha: sha.chars().take(7).collect(),
             sha,
-            short_sha,
             summary,
             message,
             author: author_name,
             author_email,
             timestamp: commit_time,
-            parents,
-            refs,
+            parents: commit
+                .parents()
+                .map(|parent| parent.id().to_string())
+                .collect(),
+            refs: refs_map.get(&oid).cloned().unwrap_or_default(),
         });
     }
 
-    let (commits, total, has_more) = paginate_history_commits(filtered, offset, limit);
+    let result = window.finish(exhausted);
     Ok(GitHistoryResponse {
         snapshot_id: current_snapshot_id,
-        total,
+        total: result.total,
+        total_is_estimated: result.total_is_estimated,
         offset,
         limit,
-        has_more,
-        commits,
+        has_more: result.has_more,
+        commits: result.page,
     })
 }
 
@@ -1195,6 +1496,17 @@ pub(crate) async fn get_git_commit_details(
     let repo = open_repository_at_root(&repo_root)?;
     let oid = Oid::from_str(commit_hash.trim()).map_err(|e| e.to_string())?;
     let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+    let max_lines = max_diff_lines.unwrap_or(MAX_COMMIT_DIFF_LINES).max(200);
+    build_commit_details(&repo, &commit, max_lines)
+}
+
+/// Per-file diffs and stats of `commit` against its first parent. Diffs longer
+/// than `max_lines` are truncated; binary files carry no diff text.
+fn build_commit_details(
+    repo: &Repository,
+    commit: &git2::Commit,
+    max_lines: usize,
+) -> Result<GitCommitDetails, String> {
     let commit_tree = commit.tree().map_err(|e| e.to_string())?;
     let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
     let mut options = DiffOptions::new();
@@ -1202,7 +1514,6 @@ pub(crate) async fn get_git_commit_details(
         .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), Some(&mut options))
         .map_err(|e| e.to_string())?;
 
-    let max_lines = max_diff_lines.unwrap_or(MAX_COMMIT_DIFF_LINES).max(200);
     let mut files = Vec::new();
     let mut total_additions = 0i64;
     let mut total_deletions = 0i64;
@@ -1236,6 +1547,9 @@ pub(crate) async fn get_git_commit_details(
         let mut is_binary = false;
 
         match git2::Patch::from_diff(&diff, index) {
+            Ok(Some(patch)) if patch.delta().flags().is_binary() => {
+                is_binary = true;
+            }
             Ok(Some(mut patch)) => {
                 if let Ok((_, added, deleted)) = patch.line_stats() {
                     additions = added as i64;
@@ -1525,6 +1839,8 @@ pub(crate) async fn get_git_pr_workflow_defaults(
     } else {
         None
     };
+    let labels = commands_pr_workflow::infer_pr_labels(&head_branch);
+    let draft = commands_pr_workflow::infer_pr_draft(&head_branch);
 
     Ok(GitPrWorkflowDefaults {
         upstream_repo,
@@ -1534,6 +1850,9 @@ pub(crate) async fn get_git_pr_workflow_defaults(
         title,
         body,
         comment_body,
+        labels,
+        reviewers: Vec::new(),
+        draft,
         can_create: disabled_reason.is_none(),
         disabled_reason,
     })
@@ -1550,8 +1869,14 @@ pub(crate) async fn create_git_pr_workflow(
     body: Option<String>,
     comment_after_create: Option<bool>,
     comment_body: Option<String>,
+    draft: Option<bool>,
+    labels: Option<Vec<String>>,
+    reviewers: Option<Vec<String>>,
+    override_range_gate: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<GitPrWorkflowResult, String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "create_git_pr_workflow")
+        .await?;
     commands_pr_workflow::create_git_pr_workflow_impl(
         workspace_id,
         upstream_repo,
@@ -1562,160 +1887,154 @@ pub(crate) async fn create_git_pr_workflow(
         body,
         comment_after_create,
         comment_body,
+        commands_pr_workflow::PrCreateMetadata::new(draft, labels, reviewers),
+        override_range_gate,
         state,
     )
     .await
 }
 
-#[tauri::command]
-pub(crate) async fn get_github_issues(
-    workspace_id: String,
-    state: State<'_, AppState>,
-) -> Result<GitHubIssuesResponse, String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
-
-    let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+async fn gh_open_total(repo_root: &Path, repo_name: &str, qualifier: &str) -> Option<usize> {
+    let search_query = format!("repo:{repo_name} {qualifier} is:open").replace(' ', "+");
+    let endpoint = format!("/search/issues?q={search_query}");
+    let output = run_gh(repo_root, &["api", &endpoint, "--jq", ".total_count"])
+        .await
+        .ok()?;
+    String::from_utf8_lossy(&output)
+        .trim()
+        .parse::<usize>()
+        .ok()
+}
 
-    let output = crate::utils::async_command("gh")
-        .args([
+async fn gh_issues(repo_root: &Path, repo_name: &str) -> Result<GitHubIssuesResponse, GhError> {
+    let limit = GITHUB_LIST_LIMIT.to_string();
+    let output = run_gh(
+        repo_root,
+        &[
             "issue",
             "list",
             "--repo",
-            &repo_name,
+            repo_name,
             "--limit",
-            "50",
+            &limit,
             "--json",
             "number,title,url,updatedAt",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
-
+        ],
+    )
+    .await?;
     let issues: Vec<GitHubIssue> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
-
-    let search_query = format!("repo:{repo_name} is:issue is:open");
-    let search_query = search_query.replace(' ', "+");
-    let total = match crate::utils::async_command("gh")
-        .args([
-            "api",
-            &format!("/search/issues?q={search_query}"),
-            "--jq",
-            ".total_count",
-        ])
-        .current_dir(&repo_root)
-        .output()
+        serde_json::from_slice(&output).map_err(|e| GhError::Failed(e.to_string()))?;
+    let total = gh_open_total(repo_root, repo_name, "is:issue")
         .await
-    {
-        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
-            .trim()
-            .parse::<usize>()
-            .unwrap_or(issues.len()),
-        _ => issues.len(),
-    };
+        .unwrap_or(issues.len());
+    Ok(GitHubIssuesResponse { total, issues })
+}
 
+async fn rest_issues_response(repo_name: &str) -> Result<GitHubIssuesResponse, String> {
+    let issues = rest_issues(repo_name).await?;
+    let total = rest_open_total(repo_name, "is:issue")
+        .await
+        .unwrap_or(issues.len());
     Ok(GitHubIssuesResponse { total, issues })
 }
 
 #[tauri::command]
-pub(crate) async fn get_github_pull_requests(
+pub(crate) async fn get_github_issues(
     workspace_id: String,
     state: State<'_, AppState>,
-) -> Result<GitHubPullRequestsResponse, String> {
+) -> Result<GitHubIssuesResponse, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
     let repo_name = github_repo_from_path(&repo_root)?;
 
-    let output = crate::utils::async_command("gh")
-        .args([
+    gh_or_rest(
+        gh_issues(&repo_root, &repo_name).await,
+        rest_issues_response(&repo_name),
+    )
+    .await
+}
+
+async fn gh_pull_requests(
+    repo_root: &Path,
+    repo_name: &str,
+) -> Result<GitHubPullRequestsResponse, GhError> {
+    let limit = GITHUB_LIST_LIMIT.to_string();
+    let output = run_gh(
+        repo_root,
+        &[
             "pr",
             "list",
             "--repo",
-            &repo_name,
+            repo_name,
             "--state",
             "open",
             "--limit",
-            "50",
+            &limit,
             "--json",
             "number,title,url,updatedAt,createdAt,body,headRefName,baseRefName,isDraft,author",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
-
+        ],
+    )
+    .await?;
     let pull_requests: Vec<GitHubPullRequest> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
-
-    let search_query = format!("repo:{repo_name} is:pr is:open");
-    let search_query = search_query.replace(' ', "+");
-    let total = match crate::utils::async_command("gh")
-        .args([
-            "api",
-            &format!("/search/issues?q={search_query}"),
-            "--jq",
-            ".total_count",
-        ])
-        .current_dir(&repo_root)
-        .output()
+        serde_json::from_slice(&output).map_err(|e| GhError::Failed(e.to_string()))?;
+    let total = gh_open_total(repo_root, repo_name, "is:pr")
         .await
-    {
-        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
-            .trim()
-            .parse::<usize>()
-            .unwrap_or(pull_requests.len()),
-        _ => pull_requests.len(),
-    };
+        .unwrap_or(pull_requests.len());
+    Ok(GitHubPullRequestsResponse {
+        total,
+        pull_requests,
+    })
+}
 
+async fn rest_pull_requests_response(
+    repo_name: &str,
+) -> Result<GitHubPullRequestsResponse, String> {
+    let pull_requests = rest_pull_requests(repo_name).await?;
+    let total = rest_open_total(repo_name, "is:pr")
+        .await
+        .unwrap_or(pull_requests.len());
     Ok(GitHubPullRequestsResponse {
         total,
         pull_requests,
     })
 }
 
+#[tauri::command]
+pub(crate) async fn get_github_pull_requests(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequestsResponse, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+
+    gh_or_rest(
+        gh_pull_requests(&repo_root, &repo_name).await,
+        rest_pull_requests_response(&repo_name),
+    )
+    .await
+}
+
+/// The PR's per-file diffs. With `path` only that file's patch is fetched;
+/// without it the whole diff is returned, capped, with `truncated` set on
+/// entries whose patch was dropped.
 #[tauri::command]
 pub(crate) async fn get_github_pull_request_diff(
     workspace_id: String,
     pr_number: u64,
+    path: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<Vec<GitHubPullRequestDiff>, String> {
     let workspaces = state.workspaces.lock().await;
@@ -1723,41 +2042,37 @@ pub(crate) async fn get_github_pull_request_diff(
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
     let repo_name = github_repo_from_path(&repo_root)?;
+    if let Some(path) = path.filter(|path| !path.trim().is_empty()) {
+        let diff = commands_pr_files::pull_request_file_diff_for_path(
+            &repo_root, &repo_name, pr_number, &path,
+        )
+        .await?;
+        return Ok(vec![diff]);
+    }
 
-    let output = crate::utils::async_command("gh")
-        .args([
+    let pr_number_arg = pr_number.to_string();
+    let gh_diff = run_gh(
+        &repo_root,
+        &[
             "pr",
             "diff",
-            &pr_number.to_string(),
+            &pr_number_arg,
             "--repo",
             &repo_name,
             "--color",
             "never",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
-
-    let diff_text = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_pr_diff(&diff_text))
+        ],
+    )
+    .await
+    .map(|output| String::from_utf8_lossy(&output).to_string());
+    let diff_text = gh_or_rest(gh_diff, rest_pull_request_diff(&repo_name, pr_number)).await?;
+    Ok(commands_pr_files::cap_pull_request_diffs(parse_pr_diff(
+        &diff_text,
+    )))
 }
 
 #[tauri::command]
@@ -1771,36 +2086,24 @@ pub(crate) async fn get_github_pull_request_comments(
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
     let repo_name = github_repo_from_path(&repo_root)?;
 
-    let comments_endpoint = format!("/repos/{repo_name}/issues/{pr_number}/comments?per_page=30");
+    let comments_endpoint =
+        format!("/repos/{repo_name}/issues/{pr_number}/comments?per_page={GITHUB_COMMENT_LIMIT}");
     let jq_filter = r#"[.[] | {id, body, createdAt: .created_at, url: .html_url, author: (if .user then {login: .user.login} else null end)}]"#;
 
-    let output = crate::utils::async_command("gh")
-        .args(["api", &comments_endpoint, "--jq", jq_filter])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
+    let gh_comments =
+        match run_gh(&repo_root, &["api", &comments_endpoint, "--jq", jq_filter]).await {
+            Ok(output) => serde_json::from_slice::<Vec<GitHubPullRequestComment>>(&output)
+                .map_err(|e| GhError::Failed(e.to_string())),
+            Err(error) => Err(error),
         };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
-
-    let comments: Vec<GitHubPullRequestComment> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
-
-    Ok(comments)
+    gh_or_rest(
+        gh_comments,
+        rest_pull_request_comments(&repo_name, pr_number),
+    )
+    .await
 }
@@ -0,0 +1,933 @@
+//! Guarded auto-commit mode: approved agent edit batches are committed to a
+//! dedicated `mossx/agent` branch without touching the checked-out branch,
+//! index or working tree, and later reviewed and squash-merged in one go.
+//!
+//! `commit_turn_changes` covers the one-commit-per-task workflow instead: it
+//! commits exactly the files a single engine turn modified onto the current
+//! branch, leaving every other local change where it was.
+//!
+//! Session checkpoints reuse the scratch index to snapshot the whole working
+//! tree into a commit under `refs/mossx/checkpoints/`, which is restored
+//! file by file later without touching branches or the user's index.
+
+use super::*;
+
+use serde::Serialize;
+
+use crate::shared::workspace_read_only::ensure_workspace_writable;
+use crate::types::{GitLogEntry, WorkspaceEntry};
+
+const AGENT_BRANCH_NAME: &str = "mossx/agent";
+const AGENT_SIGNATURE_NAME: &str = "MossX Agent";
+const AGENT_SIGNATURE_EMAIL: &str = "agent@mossx.local";
+const AGENT_COMMIT_TITLE_MAX_CHARS: usize = 72;
+const AGENT_COMMIT_MAX_LISTED_FILES: usize = 20;
+const AGENT_BRANCH_MAX_REVIEW_COMMITS: usize = 500;
+const INDEX_RESET_CHUNK_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentBranchCommit {
+    branch: String,
+    sha: String,
+    summary: String,
+    files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentBranchReview {
+    branch: String,
+    exists: bool,
+    /// Branch the agent commits would be squash-merged into.
+    current_branch: Option<String>,
+    base_sha: Option<String>,
+    /// Agent commits not yet on the current branch, newest first.
+    commits: Vec<GitLogEntry>,
+    files: Vec<GitFileStatus>,
+    total_additions: i64,
+    total_deletions: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnCommitResult {
+    sha: String,
+    turn_id: String,
+    thread_id: Option<String>,
+    files: Vec<String>,
+    /// Files the turn modified that already match HEAD, so nothing was
+    /// committed for them.
+    unchanged: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentBranchMergeResult {
+    branch: String,
+    sha: String,
+    commit_count: usize,
+    files: Vec<String>,
+}
+
+fn agent_branch_ref() -> String {
+    format!("refs/heads/{AGENT_BRANCH_NAME}")
+}
+
+fn agent_signature(repo: &Repository) -> Result<git2::Signature<'static>, String> {
+    repo.signature()
+        .or_else(|_| git2::Signature::now(AGENT_SIGNATURE_NAME, AGENT_SIGNATURE_EMAIL))
+        .map_err(|error| error.to_string())
+}
+
+fn find_agent_tip(repo: &Repository) -> Option<git2::Commit<'_>> {
+    repo.find_reference(&agent_branch_ref())
+        .ok()?
+        .peel_to_commit()
+        .ok()
+}
+
+/// Rejects absolute paths and parent traversal so a batch can only commit
+/// files inside the repository.
+fn normalize_batch_path(path: &str) -> Option<String> {
+    let normalized = normalize_git_path(path.trim())
+        .trim_start_matches("./")
+        .to_string();
+    if normalized.is_empty()
+        || normalized.starts_with('/')
+        || normalized.contains(':')
+        || normalized.split('/').any(|segment| segment == "..")
+    {
+        return None;
+    }
+    Some(normalized)
+}
+
+fn first_line(value: &str) -> Option<&str> {
+    value.lines().map(str::trim).find(|line| !line.is_empty())
+}
+
+fn truncate_title(title: &str) -> String {
+    if title.chars().count() <= AGENT_COMMIT_TITLE_MAX_CHARS {
+        return title.to_string();
+    }
+    let kept: String = title
+        .chars()
+        .take(AGENT_COMMIT_TITLE_MAX_CHARS.saturating_sub(3))
+        .collect();
+    format!("{}...", kept.trim_end())
+}
+
+fn push_file_list(message: &mut String, paths: &[String]) {
+    for path in paths.iter().take(AGENT_COMMIT_MAX_LISTED_FILES) {
+        message.push_str(&format!("\n- {path}"));
+    }
+    if paths.len() > AGENT_COMMIT_MAX_LISTED_FILES {
+        message.push_str(&format!(
+            "\n- ... and {} more",
+            paths.len() - AGENT_COMMIT_MAX_LISTED_FILES
+        ));
+    }
+}
+
+fn agent_commit_message(
+    summary: Option<&str>,
+    paths: &[String],
+    thread_id: Option<&str>,
+) -> String {
+    let title = match summary.and_then(first_line) {
+        Some(line) => truncate_title(line),
+        None => match paths {
+            [only] => truncate_title(&format!("Agent: update {only}")),
+            _ => format!("Agent: update {} files", paths.len()),
+        },
+    };
+    let mut message = format!("{title}\n\nFiles:");
+    push_file_list(&mut message, paths);
+    if let Some(thread_id) = thread_id {
+        message.push_str(&format!("\n\nThread: {thread_id}"));
+    }
+    message.push('\n');
+    message
+}
+
+fn turn_commit_message(
+    message: Option<&str>,
+    paths: &[String],
+    turn_id: &str,
+    thread_id: Option<&str>,
+) -> String {
+    let mut message = match message.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(message) => format!("{message}\n"),
+        None => agent_commit_message(None, paths, thread_id),
+    };
+    message.push_str(&format!("\nTurn: {turn_id}\n"));
+    message
+}
+
+fn squash_commit_message(commits: &[GitLogEntry]) -> String {
+    let mut message = match commits {
+        [only] => format!("{}\n", only.summary),
+        _ => format!("Apply agent changes ({} commits)\n", commits.len()),
+    };
+    if commits.len() > 1 {
+        message.push('\n');
+        for commit in commits.iter().rev() {
+            message.push_str(&format!("- {}\n", commit.summary));
+        }
+    }
+    message
+}
+
+fn changed_paths(
+    repo: &Repository,
+    old_tree: Option<&git2::Tree>,
+    new_tree: &git2::Tree,
+) -> Result<Vec<String>, String> {
+    let diff = repo
+        .diff_tree_to_tree(old_tree, Some(new_tree), None)
+        .map_err(|error| error.to_string())?;
+    Ok(diff
+        .deltas()
+        .filter_map(|delta| {
+            delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .map(|path| normalize_git_path(path.to_string_lossy().as_ref()))
+        })
+        .collect())
+}
+
+/// Runs git against a scratch index so staging the batch never disturbs the
+/// user's own index.
+async fn run_git_with_index(
+    repo_root: &Path,
+    index_file: &Path,
+    args: &[&str],
+) -> Result<String, String> {
+    let git_bin = resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
+    let mut command = crate::utils::async_command(git_bin);
+    command
+        .args(args)
+        .current_dir(repo_root)
+        .env("PATH", git_env_path())
+        .env("GIT_INDEX_FILE", index_file)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // `git commit` may wait on a signing prompt; do not let it outlive
+        // the timeout below.
+        .kill_on_drop(true);
+
+    let output = match timeout(
+        Duration::from_secs(GIT_COMMAND_TIMEOUT_SECS),
+        command.output(),
+    )
+    .await
+    {
+        Ok(result) => result.map_err(|e| format!("Failed to run git: {e}"))?,
+        Err(_) => {
+            return Err(format!(
+                "Git command timed out after {GIT_COMMAND_TIMEOUT_SECS}s: git {}",
+                args.join(" ")
+            ))
+        }
+    };
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.trim().is_empty() {
+        return Err("Git command failed.".to_string());
+    }
+    Err(stderr.trim().to_string())
+}
+
+async fn write_batch_tree(
+    repo_root: &Path,
+    index_file: &Path,
+    parent: Option<&str>,
+    paths: &[String],
+) -> Result<String, String> {
+    if let Some(parent) = parent {
+        run_git_with_index(repo_root, index_file, &["read-tree", parent]).await?;
+    }
+    let mut args = vec!["add", "-A", "--"];
+    args.extend(paths.iter().map(String::as_str));
+    run_git_with_index(repo_root, index_file, &args).await?;
+    run_git_with_index(repo_root, index_file, &["write-tree"]).await
+}
+
+async fn resolve_workspace_repo_root(
+    state: &AppState,
+    workspace_id: &str,
+) -> Result<(PathBuf, WorkspaceEntry), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+    Ok((resolve_git_root(&entry)?, entry))
+}
+
+/// Commits the files of an approved edit batch onto `mossx/agent` when the
+/// workspace has auto-commit enabled. Returns `None` when the mode is off or
+/// the batch left the files unchanged.
+#[tauri::command]
+pub(crate) async fn commit_agent_batch(
+    workspace_id: String,
+    paths: Vec<String>,
+    summary: Option<String>,
+    thread_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Option<AgentBranchCommit>, String> {
+    commit_agent_batch_for_workspace(
+        &state,
+        &workspace_id,
+        &paths,
+        summary.as_deref(),
+        thread_id.as_deref(),
+    )
+    .await
+}
+
+/// Shared by the command and by turn completion, which commits the files the
+/// turn modified.
+pub(crate) async fn commit_agent_batch_for_workspace(
+    state: &AppState,
+    workspace_id: &str,
+    paths: &[String],
+    summary: Option<&str>,
+    thread_id: Option<&str>,
+) -> Result<Option<AgentBranchCommit>, String> {
+    let (repo_root, entry) = resolve_workspace_repo_root(state, workspace_id).await?;
+    if !entry.settings.agent_auto_commit {
+        return Ok(None);
+    }
+    ensure_workspace_writable(&entry, "commit_agent_batch")?;
+
+    let mut batch: Vec<String> = paths
+        .iter()
+        .filter_map(|path| normalize_batch_path(path))
+        .collect();
+    batch.sort();
+    batch.dedup();
+
+    let (parent_sha, index_file) = {
+        let repo = open_repository_at_root(&repo_root)?;
+        let parent = match find_agent_tip(&repo) {
+            Some(tip) => Some(tip),
+            None => repo.head().ok().and_then(|head| head.peel_to_commit().ok()),
+        };
+        let parent_tree = parent.as_ref().and_then(|commit| commit.tree().ok());
+        // `git add` fails on paths that exist nowhere or are ignored and
+        // untracked, so keep only the ones it can stage.
+        batch.retain(|path| {
+            let tracked = parent_tree
+                .as_ref()
+                .map(|tree| tree.get_path(Path::new(path)).is_ok())
+                .unwrap_or(false);
+            tracked
+                || (repo_root.join(path).exists()
+                    && !repo.is_path_ignored(Path::new(path)).unwrap_or(false))
+        });
+        let index_file = repo
+            .path()
+            .join(format!("mossx-agent-index-{}", uuid::Uuid::new_v4()));
+        (parent.map(|commit| commit.id().to_string()), index_file)
+    };
+    if batch.is_empty() {
+        return Ok(None);
+    }
+
+    let tree_sha = write_batch_tree(&repo_root, &index_file, parent_sha.as_deref(), &batch).await;
+    let _ = fs::remove_file(&index_file);
+    let tree_sha = tree_sha?;
+
+    let repo = open_repository_at_root(&repo_root)?;
+    let tree = repo
+        .find_tree(Oid::from_str(&tree_sha).map_err(|error| error.to_string())?)
+        .map_err(|error| error.to_string())?;
+    let parent = match parent_sha.as_deref() {
+        Some(sha) => Some(
+            repo.find_commit(Oid::from_str(sha).map_err(|error| error.to_string())?)
+                .map_err(|error| error.to_string())?,
+        ),
+        None => None,
+    };
+    let parent_tree = parent.as_ref().and_then(|commit| commit.tree().ok());
+    let files = changed_paths(&repo, parent_tree.as_ref(), &tree)?;
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    let message = agent_commit_message(summary, &files, thread_id);
+    let signature = agent_signature(&repo)?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let oid = repo
+        .commit(
+            Some(&agent_branch_ref()),
+            &signature,
+            &signature,
+            &message,
+            &tree,
+            &parents,
+        )
+        .map_err(|error| error.to_string())?;
+    Ok(Some(AgentBranchCommit {
+        branch: AGENT_BRANCH_NAME.to_string(),
+        sha: oid.to_string(),
+        summary: first_line(&message).unwrap_or_default().to_string(),
+        files,
+    }))
+}
+
+/// Lists the agent commits not yet on the current branch and the combined
+/// file changes they would bring in.
+#[tauri::command]
+pub(crate) async fn review_agent_branch(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<AgentBranchReview, String> {
+    let (repo_root, _) = resolve_workspace_repo_root(&state, &workspace_id).await?;
+    let repo = open_repository_at_root(&repo_root)?;
+    let head = repo.head().ok();
+    let current_branch = head
+        .as_ref()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(str::to_string));
+    let head_commit = head.and_then(|head| head.peel_to_commit().ok());
+
+    let mut review = AgentBranchReview {
+        branch: AGENT_BRANCH_NAME.to_string(),
+        exists: false,
+        current_branch,
+        base_sha: None,
+        commits: Vec::new(),
+        files: Vec::new(),
+        total_additions: 0,
+        total_deletions: 0,
+    };
+    let Some(tip) = find_agent_tip(&repo) else {
+        return Ok(review);
+    };
+    review.exists = true;
+
+    let base = head_commit
+        .as_ref()
+        .and_then(|head| repo.merge_base(head.id(), tip.id()).ok());
+    review.base_sha = base.map(|oid| oid.to_string());
+
+    let mut revwalk = repo.revwalk().map_err(|error| error.to_string())?;
+    revwalk.push(tip.id()).map_err(|error| error.to_string())?;
+    if let Some(head) = head_commit.as_ref() {
+        revwalk.hide(head.id()).map_err(|error| error.to_string())?;
+    }
+    for oid in revwalk.take(AGENT_BRANCH_MAX_REVIEW_COMMITS) {
+        let oid = oid.map_err(|error| error.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|error| error.to_string())?;
+        review.commits.push(commit_to_entry(commit));
+    }
+
+    let base_tree = match base {
+        Some(oid) => Some(
+            repo.find_commit(oid)
+                .and_then(|commit| commit.tree())
+                .map_err(|error| error.to_string())?,
+        ),
+        None => None,
+    };
+    let tip_tree = tip.tree().map_err(|error| error.to_string())?;
+    let diff = repo
+        .diff_tree_to_tree(base_tree.as_ref(), Some(&tip_tree), None)
+        .map_err(|error| error.to_string())?;
+    for (index, delta) in diff.deltas().enumerate() {
+        let Some(path) = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|path| normalize_git_path(path.to_string_lossy().as_ref()))
+        else {
+            continue;
+        };
+        let (additions, deletions) = match git2::Patch::from_diff(&diff, index) {
+            Ok(Some(patch)) => patch
+                .line_stats()
+                .map(|(_, added, deleted)| (added as i64, deleted as i64))
+                .unwrap_or((0, 0)),
+            _ => (0, 0),
+        };
+        review.total_additions += additions;
+        review.total_deletions += deletions;
+        review.files.push(GitFileStatus {
+            path,
+            status: status_for_delta(delta.status()).to_string(),
+            additions,
+            deletions,
+            submodule: None,
+        });
+    }
+    Ok(review)
+}
+
+/// Squash-merges `mossx/agent` into the current branch as a single commit
+/// and deletes the agent branch. The working tree already holds the agent's
+/// edits, so only the index entries of the merged paths are refreshed.
+#[tauri::command]
+pub(crate) async fn squash_merge_agent_branch(
+    workspace_id: String,
+    message: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<AgentBranchMergeResult, String> {
+    let (repo_root, entry) = resolve_workspace_repo_root(&state, &workspace_id).await?;
+    ensure_workspace_writable(&entry, "squash_merge_agent_branch")?;
+
+    let (result, merged_paths) = {
+        let repo = open_repository_at_root(&repo_root)?;
+        let head = repo
+            .head()
+            .map_err(|_| "Current branch has no commits yet.")?;
+        if !head.is_branch() {
+            return Err("Check out a branch before merging the agent branch.".to_string());
+        }
+        let branch = head.shorthand().unwrap_or_default().to_string();
+        if branch == AGENT_BRANCH_NAME {
+            return Err(format!(
+                "Check out the branch to merge into; {AGENT_BRANCH_NAME} is checked out."
+            ));
+        }
+        let head_commit = head.peel_to_commit().map_err(|error| error.to_string())?;
+        let tip =
+            find_agent_tip(&repo).ok_or_else(|| format!("{AGENT_BRANCH_NAME} does not exist."))?;
+
+        let mut revwalk = repo.revwalk().map_err(|error| error.to_string())?;
+        revwalk.push(tip.id()).map_err(|error| error.to_string())?;
+        revwalk
+            .hide(head_commit.id())
+            .map_err(|error| error.to_string())?;
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let oid = oid.map_err(|error| error.to_string())?;
+            let commit = repo.find_commit(oid).map_err(|error| error.to_string())?;
+            commits.push(commit_to_entry(commit));
+        }
+        if commits.is_empty() {
+            return Err(format!("{AGENT_BRANCH_NAME} has no changes to merge."));
+        }
+
+        let mut merged = repo
+            .merge_commits(&head_commit, &tip, None)
+            .map_err(|error| error.to_string())?;
+        if merged.has_conflicts() {
+            let conflicted: Vec<String> = merged
+                .conflicts()
+                .map_err(|error| error.to_string())?
+                .filter_map(Result::ok)
+                .filter_map(|conflict| conflict.our.or(conflict.their))
+                .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+                .collect();
+            return Err(format!(
+                "{AGENT_BRANCH_NAME} conflicts with {branch} in: {}",
+                conflicted.join(", ")
+            ));
+        }
+        let tree_oid = merged
+            .write_tree_to(&repo)
+            .map_err(|error| error.to_string())?;
+        let tree = repo
+            .find_tree(tree_oid)
+            .map_err(|error| error.to_string())?;
+        let head_tree = head_commit.tree().map_err(|error| error.to_string())?;
+        let files = changed_paths(&repo, Some(&head_tree), &tree)?;
+
+        let message = message
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| squash_commit_message(&commits));
+        let signature = agent_signature(&repo)?;
+        let oid = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &message,
+                &tree,
+                &[&head_commit],
+            )
+            .map_err(|error| error.to_string())?;
+        repo.find_reference(&agent_branch_ref())
+            .and_then(|mut reference| reference.delete())
+            .map_err(|error| error.to_string())?;
+        (
+            AgentBranchMergeResult {
+                branch,
+                sha: oid.to_string(),
+                commit_count: commits.len(),
+                files: files.clone(),
+            },
+            files,
+        )
+    };
+
+    reset_index_paths(&repo_root, &merged_paths).await?;
+    Ok(result)
+}
+
+async fn reset_index_paths(repo_root: &Path, paths: &[String]) -> Result<(), String> {
+    for chunk in paths.chunks(INDEX_RESET_CHUNK_SIZE) {
+        let mut args = vec!["reset", "-q", "--"];
+        args.extend(chunk.iter().map(String::as_str));
+        run_git_command(repo_root, &args).await?;
+    }
+    Ok(())
+}
+
+/// Stages `paths` on top of HEAD in the scratch index and commits it with the
+/// `git commit` CLI, so hooks, signing and the user's identity apply as they
+/// do for `commit_git`. Returns the files the commit changed.
+async fn commit_turn_index(
+    repo_root: &Path,
+    index_file: &Path,
+    head_sha: Option<&str>,
+    paths: &[String],
+    message: Option<&str>,
+    turn_id: &str,
+    thread_id: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let tree_sha = write_batch_tree(repo_root, index_file, head_sha, paths).await?;
+    let files = {
+        let repo = open_repository_at_root(repo_root)?;
+        let tree = repo
+            .find_tree(Oid::from_str(&tree_sha).map_err(|error| error.to_string())?)
+            .map_err(|error| error.to_string())?;
+        let head_tree = match head_sha {
+            Some(sha) => Some(
+                repo.find_commit(Oid::from_str(sha).map_err(|error| error.to_string())?)
+                    .and_then(|commit| commit.tree())
+                    .map_err(|error| error.to_string())?,
+            ),
+            None => None,
+        };
+        changed_paths(&repo, head_tree.as_ref(), &tree)?
+    };
+    if files.is_empty() {
+        return Err(format!(
+            "The changes from turn {turn_id} are already committed."
+        ));
+    }
+
+    ensure_git_identity(repo_root)?;
+    let signing = read_signing_status(repo_root).ok();
+    let message = turn_commit_message(message, &files, turn_id, thread_id);
+    run_git_with_index(repo_root, index_file, &["commit", "-q", "-m", &message])
+        .await
+        .map_err(
+            |error| match signing.as_ref().filter(|status| status.enabled) {
+                Some(status) => signing_error_hint(&status.format, &error).unwrap_or(error),
+                None => error,
+            },
+        )?;
+    Ok(files)
+}
+
+/// Commits exactly the files recorded as modified by `turn_id` onto the
+/// current branch, with their current working tree content. Other working
+/// tree and staged changes are left as they were; the committed files' index
+/// entries are refreshed to the new HEAD.
+#[tauri::command]
+pub(crate) async fn commit_turn_changes(
+    workspace_id: String,
+    turn_id: String,
+    message: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TurnCommitResult, String> {
+    let (repo_root, entry) = resolve_workspace_repo_root(&state, &workspace_id).await?;
+    ensure_workspace_writable(&entry, "commit_turn_changes")?;
+    let turn_id = turn_id.trim().to_string();
+    if turn_id.is_empty() {
+        return Err("turnId is required".to_string());
+    }
+
+    let (recorded, thread_id) = crate::file_mentions::turn_modified_paths(&workspace_id, &turn_id)?;
+    let mut paths: Vec<String> = recorded
+        .iter()
+        .filter_map(|path| normalize_batch_path(path))
+        .collect();
+    paths.sort();
+    paths.dedup();
+    if paths.is_empty() {
+        return Err(format!("No file changes were recorded for turn {turn_id}."));
+    }
+
+    let (head_sha, index_file) = {
+        let repo = open_repository_at_root(&repo_root)?;
+        let head = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let head_tree = head.as_ref().and_then(|commit| commit.tree().ok());
+        paths.retain(|path| {
+            let tracked = head_tree
+                .as_ref()
+                .map(|tree| tree.get_path(Path::new(path)).is_ok())
+                .unwrap_or(false);
+            tracked
+                || (repo_root.join(path).exists()
+                    && !repo.is_path_ignored(Path::new(path)).unwrap_or(false))
+        });
+        let index_file = repo
+            .path()
+            .join(format!("mossx-turn-index-{}", uuid::Uuid::new_v4()));
+        (head.map(|commit| commit.id().to_string()), index_file)
+    };
+    if paths.is_empty() {
+        return Err(format!("Turn {turn_id} left no committable changes."));
+    }
+
+    let committed = commit_turn_index(
+        &repo_root,
+        &index_file,
+        head_sha.as_deref(),
+        &paths,
+        message.as_deref(),
+        &turn_id,
+        thread_id.as_deref(),
+    )
+    .await;
+    let _ = fs::remove_file(&index_file);
+    let files = committed?;
+
+    let sha = open_repository_at_root(&repo_root)?
+        .head()
+        .ok()
+        .and_then(|head| head.target())
+        .map(|oid| oid.to_string())
+        .ok_or("Commit succeeded but HEAD could not be resolved.")?;
+    let unchanged = paths
+        .iter()
+        .filter(|path| !files.contains(path))
+        .cloned()
+        .collect();
+    let result = TurnCommitResult {
+        sha,
+        turn_id,
+        thread_id,
+        files: files.clone(),
+        unchanged,
+    };
+
+    reset_index_paths(&repo_root, &files).await?;
+    Ok(result)
+}
+
+fn checkpoint_ref(checkpoint_id: &str) -> String {
+    format!("refs/mossx/checkpoints/{checkpoint_id}")
+}
+
+/// Writes the current working tree, untracked but not ignored files
+/// included, as a tree object. Returns the HEAD sha it was built on and the
+/// tree sha.
+async fn write_working_tree(repo_root: &Path) -> Result<(Option<String>, String), String> {
+    let (head_sha, index_file) = {
+        let repo = open_repository_at_root(repo_root)?;
+        let head_sha = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .map(|commit| commit.id().to_string());
+        let index_file = repo
+            .path()
+            .join(format!("mossx-checkpoint-index-{}", uuid::Uuid::new_v4()));
+        (head_sha, index_file)
+    };
+    let tree_sha = write_batch_tree(
+        repo_root,
+        &index_file,
+        head_sha.as_deref(),
+        &[".".to_string()],
+    )
+    .await;
+    let _ = fs::remove_file(&index_file);
+    Ok((head_sha, tree_sha?))
+}
+
+/// Snapshots the working tree into a commit under
+/// `refs/mossx/checkpoints/<checkpoint_id>` and returns its sha.
+pub(crate) async fn snapshot_working_tree(
+    repo_root: &Path,
+    checkpoint_id: &str,
+    message: &str,
+) -> Result<String, String> {
+    let (head_sha, tree_sha) = write_working_tree(repo_root).await?;
+    let repo = open_repository_at_root(repo_root)?;
+    let tree = repo
+        .find_tree(Oid::from_str(&tree_sha).map_err(|error| error.to_string())?)
+        .map_err(|error| error.to_string())?;
+    let head = match head_sha.as_deref() {
+        Some(sha) => Some(
+            repo.find_commit(Oid::from_str(sha).map_err(|error| error.to_string())?)
+                .map_err(|error| error.to_string())?,
+        ),
+        None => None,
+    };
+    let parents: Vec<&git2::Commit> = head.iter().collect();
+    let signature = agent_signature(&repo)?;
+    let oid = repo
+        .commit(
+            Some(&checkpoint_ref(checkpoint_id)),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .map_err(|error| error.to_string())?;
+    Ok(oid.to_string())
+}
+
+/// Restores every file that changed since the snapshot: modified and
+/// deleted files get their snapshot content back, files created since are
+/// removed. Symlinks and submodules are left alone. Returns the restored
+/// paths.
+pub(crate) async fn restore_working_tree_snapshot(
+    repo_root: &Path,
+    snapshot_sha: &str,
+) -> Result<Vec<String>, String> {
+    let (_, current_tree_sha) = write_working_tree(repo_root).await?;
+    let repo = open_repository_at_root(repo_root)?;
+    let snapshot_tree = repo
+        .find_commit(Oid::from_str(snapshot_sha).map_err(|error| error.to_string())?)
+        .and_then(|commit| commit.tree())
+        .map_err(|error| format!("Checkpoint snapshot {snapshot_sha} is missing: {error}"))?;
+    let current_tree = repo
+        .find_tree(Oid::from_str(&current_tree_sha).map_err(|error| error.to_string())?)
+        .map_err(|error| error.to_string())?;
+    let diff = repo
+        .diff_tree_to_tree(Some(&snapshot_tree), Some(&current_tree), None)
+        .map_err(|error| error.to_string())?;
+
+    let mut restored = Vec::new();
+    for delta in diff.deltas() {
+        let Some(path) = delta.old_file().path().or_else(|| delta.new_file().path()) else {
+            continue;
+        };
+        let target = repo_root.join(path);
+        let is_symlink = fs::symlink_metadata(&target)
+            .map(|metadata| metadata.file_type().is_symlink())
+            .unwrap_or(false);
+        if delta.status() == git2::Delta::Added {
+            match fs::remove_file(&target) {
+                Ok(()) => {}
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+                Err(error) => return Err(format!("Failed to remove {}: {error}", path.display())),
+            }
+        } else {
+            let mode = delta.old_file().mode();
+            if !matches!(mode, git2::FileMode::Blob | git2::FileMode::BlobExecutable) {
+                continue;
+            }
+            let blob = repo
+                .find_blob(delta.old_file().id())
+                .map_err(|error| error.to_string())?;
+            if is_symlink {
+                let _ = fs::remove_file(&target);
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+            }
+            fs::write(&target, blob.content())
+                .map_err(|error| format!("Failed to restore {}: {error}", path.display()))?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let permissions = if mode == git2::FileMode::BlobExecutable {
+                    0o755
+                } else {
+                    0o644
+                };
+                let _ = fs::set_permissions(&target, fs::Permissions::from_mode(permissions));
+            }
+        }
+        restored.push(normalize_git_path(path.to_string_lossy().as_ref()));
+    }
+    Ok(restored)
+}
+
+/// Drops the ref keeping a checkpoint snapshot alive; the objects are left
+/// to git's garbage collection.
+pub(crate) fn delete_working_tree_snapshot(
+    repo_root: &Path,
+    checkpoint_id: &str,
+) -> Result<(), String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let result = match repo.find_reference(&checkpoint_ref(checkpoint_id)) {
+        Ok(mut reference) => reference.delete().map_err(|error| error.to_string()),
+        Err(error) if error.code() == git2::ErrorCode::NotFound => Ok(()),
+        Err(error) => Err(error.to_string()),
+    };
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        agent_commit_message, normalize_batch_path, squash_commit_message, turn_commit_message,
+    };
+    use crate::types::GitLogEntry;
+
+    #[test]
+    fn batch_paths_stay_inside_the_repository() {
+        assert_eq!(
+            normalize_batch_path("./src\\main.rs"),
+            Some("src/main.rs".to_string())
+        );
+        assert_eq!(normalize_batch_path("../outside.txt"), None);
+        assert_eq!(normalize_batch_path("/etc/passwd"), None);
+        assert_eq!(normalize_batch_path("C:/Windows/win.ini"), None);
+        assert_eq!(normalize_batch_path("  "), None);
+    }
+
+    #[test]
+    fn generates_agent_commit_messages() {
+        let one = vec!["src/lib.rs".to_string()];
+        assert!(agent_commit_message(None, &one, None).starts_with("Agent: update src/lib.rs\n"));
+
+        let many: Vec<String> = (0..25).map(|index| format!("file-{index}.rs")).collect();
+        let message = agent_commit_message(Some("\n  Fix login flow\nmore"), &many, Some("t-1"));
+        assert!(message.starts_with("Fix login flow\n\nFiles:\n- file-0.rs"));
+        assert!(message.contains("- ... and 5 more"));
+        assert!(message.ends_with("Thread: t-1\n"));
+
+        let commits = vec![
+            GitLogEntry {
+                sha: "b".to_string(),
+                summary: "Second".to_string(),
+                author: String::new(),
+                timestamp: 2,
+            },
+            GitLogEntry {
+                sha: "a".to_string(),
+                summary: "First".to_string(),
+                author: String::new(),
+                timestamp: 1,
+            },
+        ];
+        assert_eq!(
+            squash_commit_message(&commits),
+            "Apply agent changes (2 commits)\n\n- First\n- Second\n"
+        );
+    }
+
+    #[test]
+    fn generates_turn_commit_messages() {
+        let files = vec!["src/lib.rs".to_string()];
+        assert_eq!(
+            turn_commit_message(Some("  Add login form  "), &files, "turn-1", None),
+            "Add login form\n\nTurn: turn-1\n"
+        );
+        assert_eq!(
+            turn_commit_message(Some(" "), &files, "turn-2", Some("claude:abc")),
+            "Agent: update src/lib.rs\n\nFiles:\n- src/lib.rs\n\nThread: claude:abc\n\nTurn: turn-2\n"
+        );
+    }
+}
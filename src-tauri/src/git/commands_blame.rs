@@ -0,0 +1,204 @@
+use super::*;
+
+use serde::Serialize;
+
+const SHORT_SHA_LEN: usize = 7;
+
+/// A run of consecutive lines last touched by the same commit. `sha` is the
+/// full hash accepted by `get_git_commit_details`; uncommitted lines carry
+/// the all-zero hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitBlameRange {
+    pub(crate) start_line: usize,
+    pub(crate) end_line: usize,
+    pub(crate) sha: String,
+    pub(crate) short_sha: String,
+    pub(crate) author: String,
+    pub(crate) author_email: String,
+    pub(crate) timestamp: i64,
+    pub(crate) summary: String,
+    pub(crate) uncommitted: bool,
+}
+
+/// Blames `path` at `rev`, or at the working tree when `rev` is `None` so
+/// local edits show up as uncommitted ranges. Files without history yield
+/// no ranges.
+fn blame_ranges(
+    repo_root: &Path,
+    path: &str,
+    rev: Option<&str>,
+) -> Result<Vec<GitBlameRange>, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let commit = match rev {
+        Some(rev) => repo
+            .revparse_single(rev)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|e| format!("Unknown revision {rev}: {}", e.message()))?,
+        None => match repo.head().and_then(|head| head.peel_to_commit()) {
+            Ok(commit) => commit,
+            Err(_) => return Ok(Vec::new()),
+        },
+    };
+    let in_commit = commit
+        .tree()
+        .map_err(|e| e.to_string())?
+        .get_path(Path::new(path))
+        .is_ok();
+    if !in_commit {
+        return Ok(Vec::new());
+    }
+
+    let mut options = git2::BlameOptions::new();
+    options.newest_commit(commit.id());
+    let blame = repo
+        .blame_file(Path::new(path), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    let blame = match rev {
+        Some(_) => blame,
+        None => match fs::read(repo_root.join(path)) {
+            Ok(contents) => blame.blame_buffer(&contents).map_err(|e| e.to_string())?,
+            // Deleted in the working tree: fall back to the committed blame.
+            Err(_) => blame,
+        },
+    };
+
+    let mut commits: HashMap<Oid, (String, String, i64, String)> = HashMap::new();
+    let mut ranges = Vec::new();
+    for hunk in blame.iter() {
+        let line_count = hunk.lines_in_hunk();
+        if line_count == 0 {
+            continue;
+        }
+        let oid = hunk.final_commit_id();
+        let sha = oid.to_string();
+        let uncommitted = oid.is_zero();
+        let (author, author_email, timestamp, summary) = if uncommitted {
+            (String::new(), String::new(), 0, String::new())
+        } else {
+            commits
+                .entry(oid)
+                .or_insert_with(|| match repo.find_commit(oid) {
+                    Ok(commit) => (
+                        commit.author().name().unwrap_or("").to_string(),
+                        commit.author().email().unwrap_or("").to_string(),
+                        commit.time().seconds(),
+                        commit.summary().unwrap_or("").to_string(),
+                    ),
+                    Err(_) => (String::new(), String::new(), 0, String::new()),
+                })
+                .clone()
+        };
+        let start_line = hunk.final_start_line();
+        ranges.push(GitBlameRange {
+            start_line,
+            end_line: start_line + line_count - 1,
+            short_sha: sha.chars().take(SHORT_SHA_LEN).collect(),
+            sha,
+            author,
+            author_email,
+            timestamp,
+            summary,
+            uncommitted,
+        });
+    }
+    Ok(ranges)
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_blame(
+    workspace_id: String,
+    path: String,
+    rev: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitBlameRange>, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(&path).trim().to_string();
+    if path.is_empty() {
+        return Err("path is required".to_string());
+    }
+    let rev = trim_optional(rev);
+    tokio::task::spawn_blocking(move || blame_ranges(&repo_root, &path, rev.as_deref()))
+        .await
+        .map_err(|e| format!("Blame task failed: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_file(repo: &Repository, path: &str, contents: &str, message: &str) -> Oid {
+        let root = repo.workdir().expect("workdir").to_path_buf();
+        fs::write(root.join(path), contents).expect("write file");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new(path)).expect("add path");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+        let signature = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .expect("commit")
+    }
+
+    #[test]
+    fn blames_commits_revisions_and_local_edits() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create repo dir");
+        let repo = Repository::init(&root).expect("init repo");
+        let first = commit_file(&repo, "a.txt", "one\ntwo\nthree\n", "feat: add a");
+        let second = commit_file(&repo, "a.txt", "one\nTWO\nthree\n", "fix: shout two");
+        fs::write(root.join("a.txt"), "one\nTWO\nthree\nfour\n").expect("edit file");
+
+        let head = blame_ranges(&root, "a.txt", Some("HEAD")).expect("blame head");
+        let lines: Vec<(usize, usize, String)> = head
+            .iter()
+            .map(|range| (range.start_line, range.end_line, range.sha.clone()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (1, 1, first.to_string()),
+                (2, 2, second.to_string()),
+                (3, 3, first.to_string()),
+            ]
+        );
+        assert_eq!(head[1].summary, "fix: shout two");
+        assert_eq!(head[1].author, "Test");
+        assert_eq!(head[1].short_sha, second.to_string()[..7]);
+
+        let at_first = blame_ranges(&root, "a.txt", Some(&first.to_string())).expect("blame rev");
+        assert_eq!(at_first.len(), 1);
+        assert_eq!((at_first[0].start_line, at_first[0].end_line), (1, 3));
+
+        let worktree = blame_ranges(&root, "a.txt", None).expect("blame worktree");
+        let last = worktree.last().expect("last range");
+        assert!(last.uncommitted);
+        assert_eq!((last.start_line, last.end_line), (4, 4));
+
+        fs::write(root.join("new.txt"), "untracked\n").expect("write untracked");
+        assert!(blame_ranges(&root, "new.txt", None)
+            .expect("blame untracked")
+            .is_empty());
+        assert!(blame_ranges(&root, "a.txt", Some("missing-rev")).is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
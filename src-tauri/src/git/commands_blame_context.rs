@@ -0,0 +1,531 @@
+use super::*;
+
+use serde::Serialize;
+
+use super::commands_review_bundle::markdown_fence;
+
+const BLAME_CONTEXT_MAX_LINES: u32 = 400;
+const BLAME_CONTEXT_MAX_COMMITS: usize = 8;
+const BLAME_CONTEXT_MAX_MESSAGE_CHARS: usize = 2_000;
+const BLAME_CONTEXT_MAX_PR_BODY_CHARS: usize = 4_000;
+const UNCOMMITTED_SHA: &str = "0000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitBlamePullRequest {
+    number: u64,
+    title: String,
+    url: String,
+    body: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitBlameCommitContext {
+    sha: String,
+    short_sha: String,
+    author: String,
+    timestamp: i64,
+    summary: String,
+    message: String,
+    /// Inclusive `(start, end)` ranges of the selected lines this commit
+    /// last touched.
+    line_ranges: Vec<(u32, u32)>,
+    pull_request: Option<GitBlamePullRequest>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitBlameContext {
+    path: String,
+    start_line: u32,
+    end_line: u32,
+    commits: Vec<GitBlameCommitContext>,
+    /// Selected lines that only exist in the working tree.
+    uncommitted_lines: usize,
+    /// Commits beyond the cap that were left out of the context.
+    omitted_commits: usize,
+    /// Why pull requests could not be looked up (no GitHub remote, `gh`
+    /// missing or not authenticated); commits are still included.
+    pull_request_error: Option<String>,
+    /// Markdown block to put in front of the question.
+    prompt: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct BlameCommitInfo {
+    author: String,
+    timestamp: i64,
+    summary: String,
+}
+
+#[derive(Debug, Default)]
+struct ParsedBlame {
+    /// `(sha, final line number, content)` in file order.
+    lines: Vec<(String, u32, String)>,
+    commits: HashMap<String, BlameCommitInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhCommitPullRequest {
+    number: u64,
+    title: String,
+    html_url: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    merged_at: Option<String>,
+}
+
+fn parse_blame_header(line: &str) -> Option<(String, u32)> {
+    let mut parts = line.split(' ');
+    let sha = parts.next()?;
+    if sha.len() != 40 || !sha.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    let _original_line = parts.next()?;
+    let final_line = parts.next()?.parse::<u32>().ok()?;
+    Some((sha.to_string(), final_line))
+}
+
+/// Parses `git blame --porcelain` output. Commit details are only printed
+/// the first time a commit appears, so they are keyed by sha.
+fn parse_blame_porcelain(output: &str) -> ParsedBlame {
+    let mut parsed = ParsedBlame::default();
+    let mut current: Option<(String, u32)> = None;
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            if let Some((sha, final_line)) = current.take() {
+                parsed.lines.push((sha, final_line, content.to_string()));
+            }
+            continue;
+        }
+        if let Some(header) = parse_blame_header(line) {
+            parsed.commits.entry(header.0.clone()).or_default();
+            current = Some(header);
+            continue;
+        }
+        let Some((sha, _)) = current.as_ref() else {
+            continue;
+        };
+        let Some(info) = parsed.commits.get_mut(sha) else {
+            continue;
+        };
+        if let Some(author) = line.strip_prefix("author ") {
+            info.author = author.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            info.timestamp = time.trim().parse().unwrap_or(0);
+        } else if let Some(summary) = line.strip_prefix("summary ") {
+            info.summary = summary.to_string();
+        }
+    }
+    parsed
+}
+
+/// Collapses sorted line numbers into inclusive ranges.
+fn collapse_line_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for &line in lines {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == line => *end = line,
+            _ => ranges.push((line, line)),
+        }
+    }
+    ranges
+}
+
+fn format_line_ranges(ranges: &[(u32, u32)]) -> String {
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn truncate_chars(value: &str, max_chars: usize) -> String {
+    let value = value.trim();
+    if value.chars().count() <= max_chars {
+        return value.to_string();
+    }
+    let truncated: String = value.chars().take(max_chars).collect();
+    format!("{}\n[truncated]", truncated.trim_end())
+}
+
+fn format_blame_date(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Commits of the range that are part of history, most lines first and
+/// newest first among equals.
+fn select_blame_commits(
+    repo: &Repository,
+    parsed: &ParsedBlame,
+) -> (Vec<GitBlameCommitContext>, usize) {
+    let mut lines_by_sha: HashMap<&str, Vec<u32>> = HashMap::new();
+    for (sha, line, _) in &parsed.lines {
+        if sha != UNCOMMITTED_SHA {
+            lines_by_sha.entry(sha.as_str()).or_default().push(*line);
+        }
+    }
+    let mut commits: Vec<GitBlameCommitContext> = lines_by_sha
+        .into_iter()
+        .map(|(sha, mut lines)| {
+            lines.sort_unstable();
+            let info = parsed.commits.get(sha).cloned().unwrap_or_default();
+            let message = Oid::from_str(sha)
+                .ok()
+                .and_then(|oid| repo.find_commit(oid).ok())
+                .and_then(|commit| commit.message().map(str::to_string))
+                .unwrap_or_else(|| info.summary.clone());
+            GitBlameCommitContext {
+                sha: sha.to_string(),
+                short_sha: sha.chars().take(7).collect(),
+                author: info.author,
+                timestamp: info.timestamp,
+                summary: info.summary,
+                message: truncate_chars(&message, BLAME_CONTEXT_MAX_MESSAGE_CHARS),
+                line_ranges: collapse_line_ranges(&lines),
+                pull_request: None,
+            }
+        })
+        .collect();
+    commits.sort_by(|left, right| {
+        let left_lines: u32 = left.line_ranges.iter().map(|(s, e)| e - s + 1).sum();
+        let right_lines: u32 = right.line_ranges.iter().map(|(s, e)| e - s + 1).sum();
+        right_lines
+            .cmp(&left_lines)
+            .then(right.timestamp.cmp(&left.timestamp))
+    });
+    let omitted = commits.len().saturating_sub(BLAME_CONTEXT_MAX_COMMITS);
+    commits.truncate(BLAME_CONTEXT_MAX_COMMITS);
+    (commits, omitted)
+}
+
+/// Prefers the merged pull request when a commit belongs to several.
+fn pick_pull_request(pulls: Vec<GhCommitPullRequest>) -> Option<GitBlamePullRequest> {
+    let index = pulls
+        .iter()
+        .position(|pull| pull.merged_at.is_some())
+        .unwrap_or(0);
+    pulls
+        .into_iter()
+        .nth(index)
+        .map(|pull| GitBlamePullRequest {
+            number: pull.number,
+            title: pull.title,
+            url: pull.html_url,
+            body: truncate_chars(
+                pull.body.as_deref().unwrap_or(""),
+                BLAME_CONTEXT_MAX_PR_BODY_CHARS,
+            ),
+        })
+}
+
+async fn fetch_commit_pull_request(
+    repo_root: &Path,
+    repo_name: &str,
+    sha: &str,
+) -> Result<Option<GitBlamePullRequest>, String> {
+    let output = crate::utils::async_command("gh")
+        .args(["api", &format!("repos/{repo_name}/commits/{sha}/pulls")])
+        .current_dir(repo_root)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.trim();
+        if detail.is_empty() {
+            return Err("GitHub CLI command failed.".to_string());
+        }
+        return Err(detail.to_string());
+    }
+    let pulls: Vec<GhCommitPullRequest> =
+        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    Ok(pick_pull_request(pulls))
+}
+
+fn build_blame_context_prompt(
+    path: &str,
+    start_line: u32,
+    end_line: u32,
+    code: &[(String, u32, String)],
+    commits: &[GitBlameCommitContext],
+    uncommitted_lines: usize,
+    question: Option<&str>,
+) -> String {
+    let mut prompt = format!(
+        "## History of `{path}` lines {start_line}-{end_line}\n\nThe commits below last changed the selected code. Use their messages and pull request descriptions to explain why the code is the way it is; say so when the history does not explain it.\n"
+    );
+
+    let code_text = code
+        .iter()
+        .map(|(_, line, content)| format!("{line:>5} | {content}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let fence = markdown_fence(&code_text);
+    prompt.push_str(&format!(
+        "\n### Selected code\n{fence}\n{code_text}\n{fence}\n"
+    ));
+    if uncommitted_lines > 0 {
+        prompt.push_str(&format!(
+            "\n{uncommitted_lines} of the selected lines are not committed yet.\n"
+        ));
+    }
+
+    for commit in commits {
+        prompt.push_str(&format!(
+            "\n### {} {}\nAuthor: {}, {}\nLines: {}\n",
+            commit.short_sha,
+            commit.summary,
+            commit.author,
+            format_blame_date(commit.timestamp),
+            format_line_ranges(&commit.line_ranges)
+        ));
+        let fence = markdown_fence(&commit.message);
+        prompt.push_str(&format!(
+            "\nCommit message:\n{fence}\n{}\n{fence}\n",
+            commit.message
+        ));
+        if let Some(pull) = commit.pull_request.as_ref() {
+            prompt.push_str(&format!(
+                "\nPull request #{}: {} ({})\n",
+                pull.number, pull.title, pull.url
+            ));
+            if !pull.body.is_empty() {
+                let fence = markdown_fence(&pull.body);
+                prompt.push_str(&format!("{fence}\n{}\n{fence}\n", pull.body));
+            }
+        }
+    }
+
+    if let Some(question) = question.map(str::trim).filter(|value| !value.is_empty()) {
+        prompt.push_str(&format!("\n## Question\n{question}\n"));
+    }
+    prompt
+}
+
+/// Gathers the commits that last touched `startLine..=endLine` of a file,
+/// their full messages and linked pull requests, and bundles them into a
+/// prompt block for "why is this code like this?" questions.
+#[tauri::command]
+pub(crate) async fn get_git_blame_context(
+    workspace_id: String,
+    path: String,
+    start_line: u32,
+    end_line: u32,
+    question: Option<String>,
+    include_pull_requests: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitBlameContext, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let normalized_path = normalize_git_path(&path).trim_matches('/').to_string();
+    if normalized_path.is_empty() {
+        return Err("File path is required.".to_string());
+    }
+    if normalized_path
+        .split('/')
+        .any(|segment| segment == ".." || segment == ".")
+    {
+        return Err("Invalid file path".to_string());
+    }
+    if start_line == 0 || end_line < start_line {
+        return Err("Invalid line range.".to_string());
+    }
+    let end_line = end_line.min(start_line + BLAME_CONTEXT_MAX_LINES - 1);
+
+    let blame_args = vec![
+        "blame".to_string(),
+        "--porcelain".to_string(),
+        "-L".to_string(),
+        format!("{start_line},{end_line}"),
+        "--".to_string(),
+        normalized_path.clone(),
+    ];
+    let output = run_token_isolated_command(&repo_root, "git", &blame_args, &[]).await?;
+    if !output.success {
+        if output.stderr.contains("no such path") {
+            return Err(format!("{normalized_path} is not tracked by git."));
+        }
+        if output.stderr.contains("has only") {
+            return Err(format!(
+                "Line range {start_line}-{end_line} is outside {normalized_path}."
+            ));
+        }
+        return Err(summarize_command_failure(&output));
+    }
+    let parsed = parse_blame_porcelain(&output.stdout);
+    let uncommitted_lines = parsed
+        .lines
+        .iter()
+        .filter(|(sha, _, _)| sha == UNCOMMITTED_SHA)
+        .count();
+
+    let (mut commits, omitted_commits) = {
+        let repo = open_repository_at_root(&repo_root)?;
+        select_blame_commits(&repo, &parsed)
+    };
+
+    let mut pull_request_error = None;
+    if include_pull_requests.unwrap_or(true) && !commits.is_empty() {
+        match github_repo_from_path(&repo_root) {
+            Ok(repo_name) => {
+                for commit in commits.iter_mut() {
+                    match fetch_commit_pull_request(&repo_root, &repo_name, &commit.sha).await {
+                        Ok(pull) => commit.pull_request = pull,
+                        Err(error) => {
+                            // One failure (gh missing, auth, rate limit)
+                            // means the rest would fail the same way.
+                            pull_request_error = Some(error);
+                            break;
+                        }
+                    }
+                }
+            }
+            Err(error) => pull_request_error = Some(error),
+        }
+    }
+
+    let prompt = build_blame_context_prompt(
+        &normalized_path,
+        start_line,
+        end_line,
+        &parsed.lines,
+        &commits,
+        uncommitted_lines,
+        question.as_deref(),
+    );
+    Ok(GitBlameContext {
+        path: normalized_path,
+        start_line,
+        end_line,
+        commits,
+        uncommitted_lines,
+        omitted_commits,
+        pull_request_error,
+        prompt,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA_A: &str = "1111111111111111111111111111111111111111";
+    const SHA_B: &str = "2222222222222222222222222222222222222222";
+
+    fn porcelain() -> String {
+        format!(
+            "{SHA_A} 1 10 2\nauthor Alice\nauthor-time 1700000000\nsummary Add parser\nfilename src/lib.rs\n\tfn parse() {{\n{SHA_A} 2 11\n\t    todo!()\n{UNCOMMITTED_SHA} 3 12 1\nauthor Not Committed Yet\nauthor-time 1800000000\nsummary Version of src/lib.rs from src/lib.rs\nfilename src/lib.rs\n\t    // wip\n{SHA_B} 9 13 1\nauthor Bob\nauthor-time 1710000000\nsummary Close parser\nprevious {SHA_A} src/lib.rs\nfilename src/lib.rs\n\t}}\n"
+        )
+    }
+
+    #[test]
+    fn parses_porcelain_blame() {
+        let parsed = parse_blame_porcelain(&porcelain());
+
+        let lines: Vec<(&str, u32)> = parsed
+            .lines
+            .iter()
+            .map(|(sha, line, _)| (sha.as_str(), *line))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![(SHA_A, 10), (SHA_A, 11), (UNCOMMITTED_SHA, 12), (SHA_B, 13)]
+        );
+        assert_eq!(parsed.lines[1].2, "    todo!()");
+        assert_eq!(
+            parsed.commits.get(SHA_B),
+            Some(&BlameCommitInfo {
+                author: "Bob".to_string(),
+                timestamp: 1_710_000_000,
+                summary: "Close parser".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn collapses_and_formats_line_ranges() {
+        let ranges = collapse_line_ranges(&[3, 4, 5, 9, 11, 12]);
+        assert_eq!(ranges, vec![(3, 5), (9, 9), (11, 12)]);
+        assert_eq!(format_line_ranges(&ranges), "3-5, 9, 11-12");
+    }
+
+    #[test]
+    fn prefers_merged_pull_request() {
+        let pulls = vec![
+            GhCommitPullRequest {
+                number: 1,
+                title: "Draft".to_string(),
+                html_url: "https://github.com/o/r/pull/1".to_string(),
+                body: None,
+                merged_at: None,
+            },
+            GhCommitPullRequest {
+                number: 2,
+                title: "Parser".to_string(),
+                html_url: "https://github.com/o/r/pull/2".to_string(),
+                body: Some("Why: faster parsing".to_string()),
+                merged_at: Some("2024-01-01T00:00:00Z".to_string()),
+            },
+        ];
+        let pull = pick_pull_request(pulls).expect("pull request");
+        assert_eq!(pull.number, 2);
+        assert_eq!(pull.body, "Why: faster parsing");
+        assert!(pick_pull_request(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn prompt_includes_code_commits_and_question() {
+        let parsed = parse_blame_porcelain(&porcelain());
+        let commits = vec![GitBlameCommitContext {
+            sha: SHA_A.to_string(),
+            short_sha: "1111111".to_string(),
+            author: "Alice".to_string(),
+            timestamp: 1_700_000_000,
+            summary: "Add parser".to_string(),
+            message: "Add parser\n\nHand-rolled to avoid a dependency.".to_string(),
+            line_ranges: vec![(10, 11)],
+            pull_request: Some(GitBlamePullRequest {
+                number: 7,
+                title: "Parser".to_string(),
+                url: "https://github.com/o/r/pull/7".to_string(),
+                body: "Keeps startup fast.".to_string(),
+            }),
+        }];
+
+        let prompt = build_blame_context_prompt(
+            "src/lib.rs",
+            10,
+            13,
+            &parsed.lines,
+            &commits,
+            1,
+            Some("Why not use a crate?"),
+        );
+
+        assert!(prompt.contains("## History of `src/lib.rs` lines 10-13"));
+        assert!(prompt.contains("   10 | fn parse() {"));
+        assert!(prompt.contains("1 of the selected lines are not committed yet."));
+        assert!(prompt.contains("### 1111111 Add parser\nAuthor: Alice, 2023-11-14\nLines: 10-11"));
+        assert!(prompt.contains("Hand-rolled to avoid a dependency."));
+        assert!(prompt.contains("Pull request #7: Parser (https://github.com/o/r/pull/7)"));
+        assert!(prompt.ends_with("## Question\nWhy not use a crate?\n"));
+    }
+}
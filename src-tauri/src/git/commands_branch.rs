@@ -1,5 +1,7 @@
 use super::*;
 
+use super::commands_stash::{pop_auto_stash, push_auto_stash, AutoStashPopFailure};
+
 #[tauri::command]
 pub(crate) async fn list_git_branches(
     workspace_id: String,
@@ -17,6 +19,15 @@ pub(crate) async fn list_git_branches(
         .ok()
         .filter(|head| head.is_branch())
         .and_then(|head| head.shorthand().map(|name| name.to_string()));
+    let repo_root_normalized = normalize_compare_path(&repo_root);
+    let worktree_paths: HashMap<String, String> = list_worktrees(&repo)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|worktree| {
+            normalize_compare_path(Path::new(&worktree.path)) != repo_root_normalized
+        })
+        .filter_map(|worktree| worktree.branch.map(|branch| (branch, worktree.path)))
+        .collect();
 
     let mut legacy_branches = Vec::new();
     let mut local_branches = Vec::new();
@@ -68,6 +79,7 @@ pub(crate) async fn list_git_branches(
             ahead,
             behind,
             upstream,
+            worktree_path: worktree_paths.get(&name).cloned(),
         });
     }
     legacy_branches.sort_by(|a, b| b.last_commit.cmp(&a.last_commit));
@@ -101,6 +113,7 @@ pub(crate) async fn list_git_branches(
             ahead: 0,
             behind: 0,
             upstream: None,
+            worktree_path: None,
         });
     }
     remote_branches.sort_by(|a, b| a.name.cmp(&b.name));
@@ -433,6 +446,7 @@ pub(crate) async fn update_git_branch(
     branch_name: String,
     state: State<'_, AppState>,
 ) -> Result<GitBranchUpdateResult, String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "update_git_branch").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -478,7 +492,7 @@ fn has_uncommitted_changes(repo: &Repository) -> Result<bool, String> {
     Ok(!statuses.is_empty())
 }
 
-fn ensure_checkout_precondition_clean(repo: &Repository) -> Result<(), String> {
+pub(super) fn ensure_checkout_precondition_clean(repo: &Repository) -> Result<(), String> {
     if has_uncommitted_changes(repo)? {
         return Err(
             "Working tree has uncommitted changes. Commit/stash/discard changes first.".to_string(),
@@ -487,6 +501,30 @@ fn ensure_checkout_precondition_clean(repo: &Repository) -> Result<(), String> {
     Ok(())
 }
 
+/// Prefix of the structured error returned when a checkout's auto-stash
+/// could not be restored; the payload names the stash still holding the
+/// user's changes.
+pub(crate) const CHECKOUT_AUTO_STASH_ERROR_PREFIX: &str = "CHECKOUT_AUTO_STASH:";
+
+fn checkout_auto_stash_error(
+    code: &str,
+    branch: &str,
+    stash_sha: &str,
+    failure: &AutoStashPopFailure,
+    message: String,
+) -> String {
+    let payload = json!({
+        "code": code,
+        "branch": branch,
+        "stashRef": failure.stash_ref,
+        "stashSha": stash_sha,
+        "conflicts": failure.conflicts,
+        "detail": failure.detail,
+        "message": message,
+    });
+    format!("{CHECKOUT_AUTO_STASH_ERROR_PREFIX}{payload}")
+}
+
 fn verify_checkout_postcondition(repo_root: &Path, expected_branch: &str) -> Result<(), String> {
     let expected_local_branch = normalize_local_branch_ref(expected_branch);
     let current_branch = current_local_branch(repo_root)?;
@@ -534,13 +572,16 @@ async fn checkout_remote_branch_with_tracking(
 pub(crate) async fn checkout_git_branch(
     workspace_id: String,
     name: String,
+    auto_stash: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "checkout_git_branch").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
     let trimmed_name = name.trim();
     if trimmed_name.is_empty() {
         return Err("Branch name cannot be empty.".to_string());
@@ -557,11 +598,15 @@ pub(crate) async fn checkout_git_branch(
 
     let repo_root = resolve_git_root(&entry)?;
     let normalized_local_name = normalize_local_branch_ref(trimmed_name);
-    let checkout_target = {
+    let auto_stash = auto_stash.unwrap_or(false);
+    let (checkout_target, needs_stash) = {
         let repo = open_repository_at_root(&repo_root)?;
-        ensure_checkout_precondition_clean(&repo)?;
+        let needs_stash = auto_stash && has_uncommitted_changes(&repo)?;
+        if !needs_stash {
+            ensure_checkout_precondition_clean(&repo)?;
+        }
 
-        if !normalized_local_name.is_empty()
+        let target = if !normalized_local_name.is_empty()
             && repo
                 .find_branch(normalized_local_name.as_str(), BranchType::Local)
                 .is_ok()
@@ -583,10 +628,24 @@ pub(crate) async fn checkout_git_branch(
             } else {
                 CheckoutTarget::Missing
             }
-        }
+        };
+        (target, needs_stash)
+    };
+    if matches!(checkout_target, CheckoutTarget::Missing) {
+        return Err(format!("Branch not found: {trimmed_name}"));
+    }
+
+    let stash_sha = if needs_stash {
+        push_auto_stash(
+            &repo_root,
+            &format!("Auto-stash before checkout of {trimmed_name}"),
+        )
+        .await?
+    } else {
+        None
     };
 
-    match checkout_target {
+    let checkout_result = match checkout_target {
         CheckoutTarget::ExistingLocal(local_branch) => {
             checkout_existing_local_branch(&repo_root, local_branch.as_str()).await
         }
@@ -602,6 +661,44 @@ pub(crate) async fn checkout_git_branch(
             .await
         }
         CheckoutTarget::Missing => Err(format!("Branch not found: {trimmed_name}")),
+    };
+
+    let Some(stash_sha) = stash_sha else {
+        return checkout_result;
+    };
+    match checkout_result {
+        Ok(()) => pop_auto_stash(&repo_root, &stash_sha).await.map_err(|failure| {
+            let message = format!(
+                "Switched to {trimmed_name}, but your stashed changes could not be restored: {} Your changes are still saved in {}.",
+                failure.detail, failure.stash_ref
+            );
+            checkout_auto_stash_error(
+                "checkout_stash_pop_failed",
+                trimmed_name,
+                &stash_sha,
+                &failure,
+                message,
+            )
+        }),
+        Err(checkout_error) => {
+            // Put the changes back on the branch they came from.
+            match pop_auto_stash(&repo_root, &stash_sha).await {
+                Ok(()) => Err(checkout_error),
+                Err(failure) => {
+                    let message = format!(
+                        "Checkout of {trimmed_name} failed ({checkout_error}) and your stashed changes could not be restored: {} Your changes are still saved in {}.",
+                        failure.detail, failure.stash_ref
+                    );
+                    Err(checkout_auto_stash_error(
+                        "checkout_stash_restore_failed",
+                        trimmed_name,
+                        &stash_sha,
+                        &failure,
+                        message,
+                    ))
+                }
+            }
+        }
     }
 }
 
@@ -611,6 +708,7 @@ pub(crate) async fn create_git_branch(
     name: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "create_git_branch").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -633,6 +731,12 @@ pub(crate) async fn create_git_branch_from_branch(
     source_branch: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(
+        &state.workspaces,
+        &workspace_id,
+        "create_git_branch_from_branch",
+    )
+    .await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -677,6 +781,12 @@ pub(crate) async fn create_git_branch_from_commit(
     commit_hash: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(
+        &state.workspaces,
+        &workspace_id,
+        "create_git_branch_from_commit",
+    )
+    .await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -700,6 +810,7 @@ pub(crate) async fn delete_git_branch(
     remove_occupied_worktree: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "delete_git_branch").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -762,6 +873,7 @@ pub(crate) async fn rename_git_branch(
     new_name: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "rename_git_branch").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -782,6 +894,7 @@ pub(crate) async fn merge_git_branch(
     name: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "merge_git_branch").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -801,6 +914,7 @@ pub(crate) async fn rebase_git_branch(
     onto_branch: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "rebase_git_branch").await?;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -1153,6 +1267,15 @@ mod tests {
             detached_external_change_runtime: tokio::sync::Mutex::new(
                 crate::workspaces::DetachedExternalChangeRuntime::default(),
             ),
+            branch_status_runtime: tokio::sync::Mutex::new(
+                crate::git::BranchStatusRuntime::default(),
+            ),
+            git_status_watch_runtime: tokio::sync::Mutex::new(
+                crate::git::GitStatusWatchRuntime::default(),
+            ),
+            workspace_preload: tokio::sync::Mutex::new(
+                crate::workspaces::WorkspacePreloadRuntime::default(),
+            ),
             runtime_manager: std::sync::Arc::new(crate::runtime::RuntimeManager::new(&data_dir)),
             engine_manager: crate::engine::EngineManager::new(),
         }
@@ -1449,6 +1572,88 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn checkout_with_auto_stash_carries_changes_to_target_branch() {
+        let (local_root, _writer_root) =
+            setup_tracked_branch_fixture_with("checkout-auto-stash", "feature/stash-target");
+        run_git_sync(local_root.as_path(), &["config", "user.name", "Test User"]);
+        run_git_sync(
+            local_root.as_path(),
+            &["config", "user.email", "test@example.com"],
+        );
+        write_file(local_root.as_path(), "README.md", "local edit\n");
+        write_file(local_root.as_path(), "notes.txt", "scratch\n");
+        let app_state = build_test_app_state("ws-auto-stash", local_root.as_path());
+
+        let blocked = checkout_git_branch(
+            "ws-auto-stash".to_string(),
+            "feature/stash-target".to_string(),
+            None,
+            tauri_state(&app_state),
+        )
+        .await
+        .expect_err("dirty checkout without auto-stash");
+        assert!(blocked.contains("uncommitted changes"));
+
+        checkout_git_branch(
+            "ws-auto-stash".to_string(),
+            "feature/stash-target".to_string(),
+            Some(true),
+            tauri_state(&app_state),
+        )
+        .await
+        .expect("checkout with auto-stash");
+
+        assert_eq!(
+            current_local_branch(local_root.as_path()).expect("current branch"),
+            Some("feature/stash-target".to_string())
+        );
+        assert_eq!(
+            fs::read_to_string(local_root.join("README.md")).expect("read README"),
+            "local edit\n"
+        );
+        assert!(local_root.join("notes.txt").exists());
+        assert_eq!(run_git_sync(local_root.as_path(), &["stash", "list"]), "");
+    }
+
+    #[tokio::test]
+    async fn checkout_with_auto_stash_keeps_stash_when_pop_fails() {
+        let (local_root, _writer_root) =
+            setup_tracked_branch_fixture_with("checkout-auto-stash-conflict", "feature/clash");
+        run_git_sync(local_root.as_path(), &["config", "user.name", "Test User"]);
+        run_git_sync(
+            local_root.as_path(),
+            &["config", "user.email", "test@example.com"],
+        );
+        // Untracked on main, tracked on the target branch.
+        write_file(local_root.as_path(), "feature.txt", "local copy\n");
+        let app_state = build_test_app_state("ws-auto-stash-conflict", local_root.as_path());
+
+        let error = checkout_git_branch(
+            "ws-auto-stash-conflict".to_string(),
+            "feature/clash".to_string(),
+            Some(true),
+            tauri_state(&app_state),
+        )
+        .await
+        .expect_err("stash pop should fail");
+
+        let payload: serde_json::Value = serde_json::from_str(
+            error
+                .strip_prefix(CHECKOUT_AUTO_STASH_ERROR_PREFIX)
+                .expect("structured auto-stash error"),
+        )
+        .expect("auto-stash error payload");
+        assert_eq!(payload["code"], "checkout_stash_pop_failed");
+        assert_eq!(payload["stashRef"], "stash@{0}");
+        assert_eq!(
+            current_local_branch(local_root.as_path()).expect("current branch"),
+            Some("feature/clash".to_string())
+        );
+        assert!(run_git_sync(local_root.as_path(), &["stash", "list"])
+            .contains("Auto-stash before checkout of feature/clash"));
+    }
+
     #[test]
     fn stale_update_ref_error_detection_matches_git_output() {
         let raw = "fatal: cannot lock ref 'refs/heads/feature/update-target': is at abcdef but expected 123456";
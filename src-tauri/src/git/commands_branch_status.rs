@@ -0,0 +1,572 @@
+//! Pull/merge request status of the current branch, for GitHub (`gh`) and
+//! GitLab (`glab`) remotes, reported in one schema and polled in the
+//! background while the UI asks for it.
+
+use super::*;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{oneshot, Mutex};
+
+const BRANCH_PR_STATUS_CHANGED_EVENT: &str = "branch-pr-status-changed";
+const BRANCH_STATUS_MIN_POLL_INTERVAL_SECS: u64 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum BranchHostingProvider {
+    GitHub,
+    GitLab,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BranchPrStatus {
+    workspace_id: String,
+    branch: Option<String>,
+    provider: Option<BranchHostingProvider>,
+    exists: bool,
+    number: Option<u64>,
+    url: Option<String>,
+    title: Option<String>,
+    /// `open`, `merged` or `closed`.
+    state: Option<String>,
+    is_draft: bool,
+    /// `approved`, `changes-requested`, `review-required` or `none`.
+    review_state: String,
+    /// `passing`, `failing`, `pending` or `none`.
+    ci_state: String,
+    /// `mergeable`, `conflicting`, `blocked`, `behind` or `unknown`.
+    mergeable: String,
+    error: Option<String>,
+    checked_at: i64,
+}
+
+struct BranchStatusPoller {
+    stop_tx: Option<oneshot::Sender<()>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[derive(Default)]
+pub(crate) struct BranchStatusRuntime {
+    pollers: HashMap<String, BranchStatusPoller>,
+    last_status: HashMap<String, BranchPrStatus>,
+}
+
+impl BranchPrStatus {
+    fn empty(workspace_id: &str, branch: Option<String>) -> Self {
+        Self {
+            workspace_id: workspace_id.to_string(),
+            branch,
+            review_state: "none".to_string(),
+            ci_state: "none".to_string(),
+            mergeable: "unknown".to_string(),
+            checked_at: chrono::Utc::now().timestamp_millis(),
+            ..Self::default()
+        }
+    }
+
+    /// Equality that ignores when the status was fetched.
+    fn same_as(&self, other: &BranchPrStatus) -> bool {
+        Self {
+            checked_at: 0,
+            ..self.clone()
+        } == Self {
+            checked_at: 0,
+            ..other.clone()
+        }
+    }
+}
+
+/// Extracts the host from an ssh (`git@host:path`, `ssh://git@host/path`)
+/// or http(s) remote URL.
+fn remote_host(remote_url: &str) -> Option<String> {
+    let trimmed = remote_url.trim();
+    let rest = match trimmed.split_once("://") {
+        Some((_, rest)) => rest,
+        None => trimmed.split_once(':').map(|(host, _)| host)?,
+    };
+    let authority = rest.split('/').next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+fn hosting_provider_for_remote(remote_url: &str) -> Option<BranchHostingProvider> {
+    let host = remote_host(remote_url)?;
+    if host.contains("github") {
+        Some(BranchHostingProvider::GitHub)
+    } else if host.contains("gitlab") {
+        Some(BranchHostingProvider::GitLab)
+    } else {
+        None
+    }
+}
+
+fn aggregate_ci_states<'a>(states: impl Iterator<Item = &'a str>) -> String {
+    let mut seen_any = false;
+    let mut pending = false;
+    for state in states {
+        seen_any = true;
+        match state {
+            "FAILURE" | "ERROR" | "CANCELLED" | "TIMED_OUT" | "ACTION_REQUIRED"
+            | "STARTUP_FAILURE" => return "failing".to_string(),
+            "SUCCESS" | "NEUTRAL" | "SKIPPED" => {}
+            _ => pending = true,
+        }
+    }
+    match (seen_any, pending) {
+        (false, _) => "none",
+        (true, true) => "pending",
+        (true, false) => "passing",
+    }
+    .to_string()
+}
+
+/// Parses `gh pr view --json ...` output.
+fn parse_github_pr_status(status: &mut BranchPrStatus, pr: &serde_json::Value) {
+    status.exists = true;
+    status.number = pr.get("number").and_then(|value| value.as_u64());
+    status.url = pr
+        .get("url")
+        .and_then(|value| value.as_str())
+        .map(str::to_string);
+    status.title = pr
+        .get("title")
+        .and_then(|value| value.as_str())
+        .map(str::to_string);
+    status.state = pr
+        .get("state")
+        .and_then(|value| value.as_str())
+        .map(str::to_ascii_lowercase);
+    status.is_draft = pr
+        .get("isDraft")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    status.review_state = match pr.get("reviewDecision").and_then(|value| value.as_str()) {
+        Some("APPROVED") => "approved",
+        Some("CHANGES_REQUESTED") => "changes-requested",
+        Some("REVIEW_REQUIRED") => "review-required",
+        _ => "none",
+    }
+    .to_string();
+    // Check runs report `status` + `conclusion`, commit statuses a `state`.
+    status.ci_state = aggregate_ci_states(
+        pr.get("statusCheckRollup")
+            .and_then(|value| value.as_array())
+            .into_iter()
+            .flatten()
+            .map(|check| {
+                let completed = check
+                    .get("status")
+                    .and_then(|value| value.as_str())
+                    .is_none_or(|value| value == "COMPLETED");
+                if !completed {
+                    return "PENDING";
+                }
+                check
+                    .get("conclusion")
+                    .and_then(|value| value.as_str())
+                    .filter(|value| !value.is_empty())
+                    .or_else(|| check.get("state").and_then(|value| value.as_str()))
+                    .unwrap_or("PENDING")
+            }),
+    );
+    let merge_state = pr
+        .get("mergeStateStatus")
+        .and_then(|value| value.as_str())
+        .unwrap_or("");
+    status.mergeable = match (
+        pr.get("mergeable").and_then(|value| value.as_str()),
+        merge_state,
+    ) {
+        (Some("CONFLICTING"), _) | (_, "DIRTY") => "conflicting",
+        (_, "BLOCKED") | (_, "DRAFT") => "blocked",
+        (_, "BEHIND") => "behind",
+        (Some("MERGEABLE"), _) => "mergeable",
+        _ => "unknown",
+    }
+    .to_string();
+}
+
+/// Parses `glab mr view --output json` output.
+fn parse_gitlab_mr_status(status: &mut BranchPrStatus, mr: &serde_json::Value) {
+    status.exists = true;
+    status.number = mr.get("iid").and_then(|value| value.as_u64());
+    status.url = mr
+        .get("web_url")
+        .and_then(|value| value.as_str())
+        .map(str::to_string);
+    status.title = mr
+        .get("title")
+        .and_then(|value| value.as_str())
+        .map(str::to_string);
+    status.state = mr
+        .get("state")
+        .and_then(|value| value.as_str())
+        .map(|state| match state {
+            "opened" | "locked" => "open".to_string(),
+            other => other.to_string(),
+        });
+    status.is_draft = ["draft", "work_in_progress"]
+        .iter()
+        .any(|key| mr.get(*key).and_then(|value| value.as_bool()) == Some(true));
+    let merge_status = mr
+        .get("detailed_merge_status")
+        .and_then(|value| value.as_str())
+        .unwrap_or("");
+    status.review_state = match merge_status {
+        "not_approved" => "review-required",
+        "requested_changes" => "changes-requested",
+        _ => "none",
+    }
+    .to_string();
+    let pipeline_status = mr
+        .get("head_pipeline")
+        .filter(|value| !value.is_null())
+        .or_else(|| mr.get("pipeline"))
+        .and_then(|pipeline| pipeline.get("status"))
+        .and_then(|value| value.as_str());
+    status.ci_state = match pipeline_status {
+        None => "none",
+        Some("success") => "passing",
+        Some("failed") | Some("canceled") => "failing",
+        Some("skipped") | Some("manual") => "none",
+        Some(_) => "pending",
+    }
+    .to_string();
+    let has_conflicts = mr
+        .get("has_conflicts")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    status.mergeable = match merge_status {
+        _ if has_conflicts => "conflicting",
+        "conflict" | "broken_status" => "conflicting",
+        "need_rebase" => "behind",
+        "mergeable" => "mergeable",
+        "" | "checking" | "unchecked" | "preparing" => "unknown",
+        _ => "blocked",
+    }
+    .to_string();
+}
+
+fn is_missing_request_error(stderr: &str) -> bool {
+    let lowered = stderr.to_ascii_lowercase();
+    lowered.contains("no pull requests found")
+        || lowered.contains("no open merge request")
+        || lowered.contains("no merge request")
+        || lowered.contains("404 not found")
+}
+
+fn current_branch_and_remote(repo_root: &Path) -> Result<(Option<String>, Option<String>), String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let branch = repo
+        .head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(str::to_string));
+    let remotes = repo.remotes().map_err(|e| e.to_string())?;
+    let remote_name = if remotes.iter().any(|remote| remote == Some("origin")) {
+        Some("origin".to_string())
+    } else {
+        remotes.iter().flatten().next().map(str::to_string)
+    };
+    let remote_url = remote_name.and_then(|name| {
+        repo.find_remote(&name)
+            .ok()
+            .and_then(|remote| remote.url().map(str::to_string))
+    });
+    Ok((branch, remote_url))
+}
+
+async fn fetch_branch_pr_status(workspace_id: &str, repo_root: &Path) -> BranchPrStatus {
+    let (branch, remote_url) = match current_branch_and_remote(repo_root) {
+        Ok(result) => result,
+        Err(error) => {
+            let mut status = BranchPrStatus::empty(workspace_id, None);
+            status.error = Some(error);
+            return status;
+        }
+    };
+    let mut status = BranchPrStatus::empty(workspace_id, branch.clone());
+    let Some(branch) = branch else {
+        return status;
+    };
+    status.provider = remote_url.as_deref().and_then(hosting_provider_for_remote);
+    let Some(provider) = status.provider else {
+        status.error = Some(if remote_url.is_none() {
+            "No git remote configured.".to_string()
+        } else {
+            "Remote is not hosted on GitHub or GitLab.".to_string()
+        });
+        return status;
+    };
+
+    let (program, args) = match provider {
+        BranchHostingProvider::GitHub => (
+            "gh",
+            vec![
+                "pr".to_string(),
+                "view".to_string(),
+                branch,
+                "--json".to_string(),
+                "number,url,title,state,isDraft,reviewDecision,statusCheckRollup,mergeable,mergeStateStatus"
+                    .to_string(),
+            ],
+        ),
+        BranchHostingProvider::GitLab => (
+            "glab",
+            vec![
+                "mr".to_string(),
+                "view".to_string(),
+                branch,
+                "--output".to_string(),
+                "json".to_string(),
+            ],
+        ),
+    };
+    let output = match run_token_isolated_command(repo_root, program, &args, &[]).await {
+        Ok(output) => output,
+        Err(error) => {
+            status.error = Some(error);
+            return status;
+        }
+    };
+    if !output.success {
+        if !is_missing_request_error(&output.stderr) {
+            status.error = Some(summarize_command_failure(&output));
+        }
+        return status;
+    }
+    match serde_json::from_str::<serde_json::Value>(&output.stdout) {
+        Ok(value) => match provider {
+            BranchHostingProvider::GitHub => parse_github_pr_status(&mut status, &value),
+            BranchHostingProvider::GitLab => parse_gitlab_mr_status(&mut status, &value),
+        },
+        Err(error) => {
+            status.error = Some(format!("Unexpected {program} output: {error}"));
+        }
+    }
+    status
+}
+
+/// Stores the latest status and emits a change event when it differs from the
+/// previous one.
+async fn record_branch_pr_status(app: &AppHandle, status: &BranchPrStatus) {
+    let state = app.state::<AppState>();
+    let changed = {
+        let mut runtime = state.branch_status_runtime.lock().await;
+        let changed = runtime
+            .last_status
+            .get(&status.workspace_id)
+            .is_none_or(|previous| !previous.same_as(status));
+        runtime
+            .last_status
+            .insert(status.workspace_id.clone(), status.clone());
+        changed
+    };
+    if changed {
+        let _ = app.emit(BRANCH_PR_STATUS_CHANGED_EVENT, status);
+    }
+}
+
+async fn workspace_repo_root(state: &AppState, workspace_id: &str) -> Result<PathBuf, String> {
+    let entry = state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .cloned()
+        .ok_or("workspace not found")?;
+    resolve_git_root(&entry)
+}
+
+async fn run_branch_status_poll_loop(
+    app: AppHandle,
+    workspace_id: String,
+    mut stop_rx: oneshot::Receiver<()>,
+) {
+    loop {
+        let state = app.state::<AppState>();
+        let Ok(repo_root) = workspace_repo_root(&state, &workspace_id).await else {
+            break;
+        };
+        let status = fetch_branch_pr_status(&workspace_id, &repo_root).await;
+        record_branch_pr_status(&app, &status).await;
+        // Re-read every round so interval changes apply without a restart.
+        let interval_secs = state
+            .app_settings
+            .lock()
+            .await
+            .branch_status_poll_interval_secs
+            .max(BRANCH_STATUS_MIN_POLL_INTERVAL_SECS);
+        tokio::select! {
+            _ = &mut stop_rx => break,
+            _ = tokio::time::sleep(Duration::from_secs(interval_secs)) => {}
+        }
+    }
+}
+
+async fn stop_poller(runtime: &Mutex<BranchStatusRuntime>, workspace_id: &str) {
+    let removed = runtime.lock().await.pollers.remove(workspace_id);
+    if let Some(mut poller) = removed {
+        if let Some(stop_tx) = poller.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+        poller.task.abort();
+        let _ = poller.task.await;
+    }
+}
+
+/// Fetches the PR/MR status of the workspace's current branch now.
+#[tauri::command]
+pub(crate) async fn get_branch_pr_status(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BranchPrStatus, String> {
+    let repo_root = workspace_repo_root(&state, &workspace_id).await?;
+    let status = fetch_branch_pr_status(&workspace_id, &repo_root).await;
+    record_branch_pr_status(&app, &status).await;
+    Ok(status)
+}
+
+/// Starts (or restarts) background polling for a workspace. Changes are
+/// emitted as `branch-pr-status-changed`.
+#[tauri::command]
+pub(crate) async fn start_branch_pr_status_polling(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    workspace_repo_root(&state, &workspace_id).await?;
+    stop_poller(&state.branch_status_runtime, &workspace_id).await;
+    let (stop_tx, stop_rx) = oneshot::channel();
+    let task = tokio::spawn(run_branch_status_poll_loop(
+        app.clone(),
+        workspace_id.clone(),
+        stop_rx,
+    ));
+    state.branch_status_runtime.lock().await.pollers.insert(
+        workspace_id,
+        BranchStatusPoller {
+            stop_tx: Some(stop_tx),
+            task,
+        },
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn stop_branch_pr_status_polling(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    stop_poller(&state.branch_status_runtime, &workspace_id).await;
+    state
+        .branch_status_runtime
+        .lock()
+        .await
+        .last_status
+        .remove(&workspace_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_hosting_provider_from_remote_urls() {
+        for (url, expected) in [
+            (
+                "git@github.com:o/r.git",
+                Some(BranchHostingProvider::GitHub),
+            ),
+            (
+                "https://github.example.com/o/r",
+                Some(BranchHostingProvider::GitHub),
+            ),
+            (
+                "ssh://git@gitlab.com:2222/group/sub/r.git",
+                Some(BranchHostingProvider::GitLab),
+            ),
+            (
+                "https://user@gitlab.internal/g/r.git",
+                Some(BranchHostingProvider::GitLab),
+            ),
+            ("https://bitbucket.org/o/r.git", None),
+        ] {
+            assert_eq!(hosting_provider_for_remote(url), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn github_and_gitlab_statuses_share_one_schema() {
+        let mut github = BranchPrStatus::empty("ws", Some("feature".to_string()));
+        parse_github_pr_status(
+            &mut github,
+            &json!({
+                "number": 12,
+                "url": "https://github.com/o/r/pull/12",
+                "title": "Add feature",
+                "state": "OPEN",
+                "isDraft": false,
+                "reviewDecision": "CHANGES_REQUESTED",
+                "statusCheckRollup": [
+                    { "status": "COMPLETED", "conclusion": "SUCCESS" },
+                    { "status": "IN_PROGRESS", "conclusion": "" },
+                    { "state": "SUCCESS" }
+                ],
+                "mergeable": "MERGEABLE",
+                "mergeStateStatus": "BLOCKED"
+            }),
+        );
+        assert_eq!(
+            (
+                github.state.as_deref(),
+                github.review_state.as_str(),
+                github.ci_state.as_str(),
+                github.mergeable.as_str()
+            ),
+            (Some("open"), "changes-requested", "pending", "blocked")
+        );
+
+        let mut gitlab = BranchPrStatus::empty("ws", Some("feature".to_string()));
+        parse_gitlab_mr_status(
+            &mut gitlab,
+            &json!({
+                "iid": 7,
+                "web_url": "https://gitlab.com/g/r/-/merge_requests/7",
+                "title": "Draft: feature",
+                "state": "opened",
+                "draft": true,
+                "detailed_merge_status": "not_approved",
+                "head_pipeline": { "status": "failed" },
+                "has_conflicts": false
+            }),
+        );
+        assert_eq!(
+            (
+                gitlab.number,
+                gitlab.state.as_deref(),
+                gitlab.is_draft,
+                gitlab.review_state.as_str(),
+                gitlab.ci_state.as_str(),
+                gitlab.mergeable.as_str()
+            ),
+            (
+                Some(7),
+                Some("open"),
+                true,
+                "review-required",
+                "failing",
+                "blocked"
+            )
+        );
+
+        let mut refreshed = gitlab.clone();
+        refreshed.checked_at += 60_000;
+        assert!(refreshed.same_as(&gitlab));
+        assert!(is_missing_request_error(
+            "no pull requests found for branch \"feature\""
+        ));
+    }
+}
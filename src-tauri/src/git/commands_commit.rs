@@ -0,0 +1,237 @@
+use super::*;
+
+/// Returns the upstream (`remote/branch`) that already contains HEAD, or
+/// `None` when HEAD is unpushed, detached, or the branch tracks nothing.
+fn pushed_head_upstream(repo_root: &Path) -> Result<Option<String>, String> {
+    let Some((remote, branch)) = upstream_remote_and_branch(repo_root)? else {
+        return Ok(None);
+    };
+    let repo = open_repository_at_root(repo_root)?;
+    let Some(head_oid) = repo.head().ok().and_then(|head| head.target()) else {
+        return Ok(None);
+    };
+    let Some(upstream_oid) = repo
+        .find_reference(&format!("refs/remotes/{remote}/{branch}"))
+        .ok()
+        .and_then(|reference| reference.target())
+    else {
+        return Ok(None);
+    };
+    let (ahead, _behind) = repo
+        .graph_ahead_behind(head_oid, upstream_oid)
+        .map_err(|e| e.to_string())?;
+    Ok((ahead == 0).then(|| format!("{remote}/{branch}")))
+}
+
+/// Refuses to rewrite a HEAD commit the upstream already has unless `force`.
+fn ensure_amend_allowed(repo_root: &Path, force: bool) -> Result<(), String> {
+    if force {
+        return Ok(());
+    }
+    match pushed_head_upstream(repo_root)? {
+        Some(upstream) => Err(format!(
+            "HEAD has already been pushed to {upstream}; amending would rewrite published history. Create a new commit instead, or retry with force to amend anyway."
+        )),
+        None => Ok(()),
+    }
+}
+
+/// `git commit` arguments. An amend without a message keeps the existing
+/// one via `--no-edit`.
+fn commit_args<'a>(message: &'a str, amend: bool, sign: Option<bool>) -> Vec<&'a str> {
+    let mut args = vec!["commit"];
+    args.extend_from_slice(commit_sign_args(sign));
+    if amend {
+        args.push("--amend");
+        if message.trim().is_empty() {
+            args.push("--no-edit");
+            return args;
+        }
+    }
+    args.extend_from_slice(&["-m", message]);
+    args
+}
+
+/// Runs `git commit` once an author identity is known, turning signing
+/// failures into actionable hints when the commit was meant to be signed.
+async fn run_commit(repo_root: &Path, args: &[&str], sign: Option<bool>) -> Result<(), String> {
+    ensure_git_identity(repo_root)?;
+    let signing = read_signing_status(repo_root).ok();
+    run_git_command(repo_root, args).await.map_err(|error| {
+        let signs = sign.unwrap_or_else(|| signing.as_ref().is_some_and(|status| status.enabled));
+        if !signs {
+            return error;
+        }
+        let format = signing
+            .as_ref()
+            .map(|status| status.format.as_str())
+            .unwrap_or("openpgp");
+        signing_error_hint(format, &error).unwrap_or(error)
+    })
+}
+
+pub(super) async fn commit_in_repo(
+    repo_root: &Path,
+    message: &str,
+    amend: bool,
+    force: bool,
+    sign: Option<bool>,
+) -> Result<(), String> {
+    if amend {
+        ensure_amend_allowed(repo_root, force)?;
+    }
+    run_commit(repo_root, &commit_args(message, amend, sign), sign).await
+}
+
+/// Creates a `fixup!` commit from the staged changes, to be folded into
+/// `target_sha` by a later `rebase --autosquash`.
+#[tauri::command]
+pub(crate) async fn fixup_git_commit(
+    workspace_id: String,
+    target_sha: String,
+    sign: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "fixup_git_commit").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let target = {
+        let repo = open_repository_at_root(&repo_root)?;
+        let commit = repo
+            .revparse_single(target_sha.trim())
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| format!("Commit not found: {}", target_sha.trim()))?;
+        commit.id().to_string()
+    };
+    let fixup = format!("--fixup={target}");
+    let mut args = vec!["commit"];
+    args.extend_from_slice(commit_sign_args(sign));
+    args.push(&fixup);
+    run_commit(&repo_root, &args, sign).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_repo(root: &Path) -> Repository {
+        fs::create_dir_all(root).expect("create repo dir");
+        let repo = Repository::init(root).expect("init repo");
+        let mut config = repo.config().expect("repo config");
+        config.set_str("user.name", "Test").expect("set name");
+        config
+            .set_str("user.email", "test@example.com")
+            .expect("set email");
+        config
+            .set_bool("commit.gpgsign", false)
+            .expect("disable signing");
+        repo
+    }
+
+    fn head_commit(repo: &Repository) -> (Oid, String, usize) {
+        let commit = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .expect("head commit");
+        (
+            commit.id(),
+            commit.message().unwrap_or("").trim().to_string(),
+            commit.parent_count(),
+        )
+    }
+
+    #[tokio::test]
+    async fn amends_with_new_message_or_keeps_the_old_one() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        let repo = init_repo(&root);
+        fs::write(root.join("a.txt"), "one\n").expect("write a");
+        run_git_command(&root, &["add", "a.txt"])
+            .await
+            .expect("add a");
+        commit_in_repo(&root, "first", false, false, None)
+            .await
+            .expect("commit");
+        let (first, _, _) = head_commit(&repo);
+
+        fs::write(root.join("a.txt"), "two\n").expect("edit a");
+        run_git_command(&root, &["add", "a.txt"])
+            .await
+            .expect("stage a");
+        commit_in_repo(&root, "reworded", true, false, None)
+            .await
+            .expect("amend with message");
+        let (amended, message, parents) = head_commit(&repo);
+        assert_ne!(amended, first);
+        assert_eq!(message, "reworded");
+        assert_eq!(parents, 0);
+
+        fs::write(root.join("b.txt"), "b\n").expect("write b");
+        run_git_command(&root, &["add", "b.txt"])
+            .await
+            .expect("add b");
+        commit_in_repo(&root, "  ", true, false, None)
+            .await
+            .expect("amend no-edit");
+        let (no_edit, message, parents) = head_commit(&repo);
+        assert_ne!(no_edit, amended);
+        assert_eq!(message, "reworded");
+        assert_eq!(parents, 0);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[tokio::test]
+    async fn refuses_to_amend_a_pushed_head_unless_forced() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        let remote_root = root.join("remote.git");
+        let work_root = root.join("work");
+        Repository::init_bare(&remote_root).expect("init remote");
+        let repo = init_repo(&work_root);
+        fs::write(work_root.join("a.txt"), "one\n").expect("write a");
+        run_git_command(&work_root, &["add", "a.txt"])
+            .await
+            .expect("add a");
+        commit_in_repo(&work_root, "first", false, false, None)
+            .await
+            .expect("commit");
+        let remote_url = remote_root.to_string_lossy().to_string();
+        run_git_command(&work_root, &["remote", "add", "origin", &remote_url])
+            .await
+            .expect("add remote");
+        run_git_command(&work_root, &["push", "-u", "origin", "HEAD"])
+            .await
+            .expect("push");
+
+        let error = commit_in_repo(&work_root, "rewrite", true, false, None)
+            .await
+            .expect_err("pushed head is guarded");
+        assert!(error.contains("already been pushed"), "{error}");
+        assert_eq!(head_commit(&repo).1, "first");
+
+        fs::write(work_root.join("a.txt"), "two\n").expect("edit a");
+        run_git_command(&work_root, &["add", "a.txt"])
+            .await
+            .expect("stage a");
+        commit_in_repo(&work_root, "second", false, false, None)
+            .await
+            .expect("commit local");
+        commit_in_repo(&work_root, "second, reworded", true, false, None)
+            .await
+            .expect("unpushed head can be amended");
+        assert_eq!(head_commit(&repo).1, "second, reworded");
+
+        run_git_command(&work_root, &["push"]).await.expect("push");
+        commit_in_repo(&work_root, "forced", true, true, None)
+            .await
+            .expect("force amends pushed head");
+        assert_eq!(head_commit(&repo).1, "forced");
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,348 @@
+use super::*;
+
+use serde::Serialize;
+
+/// Index stages git records for an unmerged path.
+const STAGE_BASE: i32 = 1;
+const STAGE_OURS: i32 = 2;
+const STAGE_THEIRS: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum GitConflictKind {
+    BothModified,
+    BothAdded,
+    BothDeleted,
+    AddedByUs,
+    AddedByThem,
+    DeletedByUs,
+    DeletedByThem,
+}
+
+impl GitConflictKind {
+    fn from_stages(base: bool, ours: bool, theirs: bool) -> Self {
+        match (base, ours, theirs) {
+            (true, true, true) => Self::BothModified,
+            (false, true, true) => Self::BothAdded,
+            (true, false, true) => Self::DeletedByUs,
+            (true, true, false) => Self::DeletedByThem,
+            (false, true, false) => Self::AddedByUs,
+            (false, false, true) => Self::AddedByThem,
+            _ => Self::BothDeleted,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitConflictFile {
+    pub(crate) path: String,
+    pub(crate) kind: GitConflictKind,
+}
+
+/// One side of a conflict. `text` is set for UTF-8 content, `base64` for
+/// binary content; both are `None` when the blob exceeds the size cap.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitConflictVersion {
+    pub(crate) oid: String,
+    pub(crate) mode: u32,
+    pub(crate) size: usize,
+    pub(crate) is_binary: bool,
+    pub(crate) too_large: bool,
+    pub(crate) text: Option<String>,
+    pub(crate) base64: Option<String>,
+}
+
+/// A side is `None` when it has no blob, e.g. `ours` for a path deleted on
+/// the current branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitConflictVersions {
+    pub(crate) path: String,
+    pub(crate) kind: GitConflictKind,
+    pub(crate) base: Option<GitConflictVersion>,
+    pub(crate) ours: Option<GitConflictVersion>,
+    pub(crate) theirs: Option<GitConflictVersion>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum GitConflictSide {
+    Ours,
+    Theirs,
+}
+
+/// `"ours"` / `"theirs"`, or `{ "content": "..." }` with the merged file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum GitConflictResolution {
+    Side(GitConflictSide),
+    Merged { content: String },
+}
+
+fn conflict_files(repo: &Repository) -> Result<Vec<GitConflictFile>, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let conflicts = index.conflicts().map_err(|e| e.to_string())?;
+    let mut files = Vec::new();
+    for conflict in conflicts {
+        let conflict = conflict.map_err(|e| e.to_string())?;
+        let path = [&conflict.our, &conflict.their, &conflict.ancestor]
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|entry| normalize_git_path(&String::from_utf8_lossy(&entry.path)));
+        let Some(path) = path else {
+            continue;
+        };
+        files.push(GitConflictFile {
+            path,
+            kind: GitConflictKind::from_stages(
+                conflict.ancestor.is_some(),
+                conflict.our.is_some(),
+                conflict.their.is_some(),
+            ),
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn conflict_version(repo: &Repository, entry: &git2::IndexEntry) -> GitConflictVersion {
+    let mut version = GitConflictVersion {
+        oid: entry.id.to_string(),
+        mode: entry.mode,
+        size: entry.file_size as usize,
+        is_binary: false,
+        too_large: false,
+        text: None,
+        base64: None,
+    };
+    // Gitlinks (submodules) have no blob; the oid is all there is to show.
+    let Ok(blob) = repo.find_blob(entry.id) else {
+        return version;
+    };
+    let content = blob.content();
+    version.size = content.len();
+    version.is_binary = blob.is_binary() || std::str::from_utf8(content).is_err();
+    if content.len() > MAX_IMAGE_BYTES {
+        version.too_large = true;
+    } else if version.is_binary {
+        version.base64 = encode_image_base64(content);
+    } else {
+        version.text = Some(String::from_utf8_lossy(content).into_owned());
+    }
+    version
+}
+
+fn conflict_versions(repo: &Repository, path: &str) -> Result<GitConflictVersions, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let stage = |stage: i32| {
+        index
+            .get_path(Path::new(path), stage)
+            .map(|entry| conflict_version(repo, &entry))
+    };
+    let (base, ours, theirs) = (stage(STAGE_BASE), stage(STAGE_OURS), stage(STAGE_THEIRS));
+    if base.is_none() && ours.is_none() && theirs.is_none() {
+        return Err(format!("{path} is not in conflict."));
+    }
+    Ok(GitConflictVersions {
+        path: path.to_string(),
+        kind: GitConflictKind::from_stages(base.is_some(), ours.is_some(), theirs.is_some()),
+        base,
+        ours,
+        theirs,
+    })
+}
+
+/// Resolves one conflicted path and stages the result. A side without a
+/// blob resolves to deleting the path.
+async fn apply_conflict_resolution(
+    repo_root: &Path,
+    path: &str,
+    resolution: GitConflictResolution,
+) -> Result<(), String> {
+    let versions = conflict_versions(&open_repository_at_root(repo_root)?, path)?;
+    match resolution {
+        GitConflictResolution::Side(side) => {
+            let (flag, version) = match side {
+                GitConflictSide::Ours => ("--ours", &versions.ours),
+                GitConflictSide::Theirs => ("--theirs", &versions.theirs),
+            };
+            if version.is_some() {
+                run_git_command(repo_root, &["checkout", flag, "--", path]).await?;
+                run_git_command(repo_root, &["add", "--", path]).await
+            } else {
+                run_git_command(repo_root, &["rm", "--quiet", "--force", "--", path]).await
+            }
+        }
+        GitConflictResolution::Merged { content } => {
+            let target = repo_root.join(path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to write {path}: {e}"))?;
+            }
+            fs::write(&target, content).map_err(|e| format!("Failed to write {path}: {e}"))?;
+            run_git_command(repo_root, &["add", "--", path]).await
+        }
+    }
+}
+
+fn conflict_path_arg(path: &str) -> Result<String, String> {
+    let path = normalize_git_path(path).trim().to_string();
+    if path.is_empty() {
+        return Err("path is required".to_string());
+    }
+    Ok(path)
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_conflict_files(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitConflictFile>, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    conflict_files(&open_repository_at_root(&repo_root)?)
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_conflict_versions(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<GitConflictVersions, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let path = conflict_path_arg(&path)?;
+    conflict_versions(&open_repository_at_root(&repo_root)?, &path)
+}
+
+#[tauri::command]
+pub(crate) async fn resolve_git_conflict(
+    workspace_id: String,
+    path: String,
+    resolution: GitConflictResolution,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "resolve_git_conflict").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let path = conflict_path_arg(&path)?;
+    apply_conflict_resolution(&repo_root, &path, resolution).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn git(repo_root: &Path, args: &[&str]) -> Result<(), String> {
+        let mut full_args = vec![
+            "-c",
+            "user.name=TestUser",
+            "-c",
+            "user.email=test@example.com",
+        ];
+        full_args.extend_from_slice(args);
+        run_git_command(repo_root, &full_args).await
+    }
+
+    #[tokio::test]
+    async fn reads_and_resolves_conflict_stages() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create repo dir");
+        git(&root, &["init", "-q", "-b", "main"])
+            .await
+            .expect("init");
+        fs::write(root.join("a.txt"), "base\n").expect("write a");
+        fs::write(root.join("gone.txt"), "keep me\n").expect("write gone");
+        git(&root, &["add", "-A"]).await.expect("add");
+        git(&root, &["commit", "-qm", "base"])
+            .await
+            .expect("commit base");
+        git(&root, &["checkout", "-qb", "feature"])
+            .await
+            .expect("branch");
+        fs::write(root.join("a.txt"), "feature\n").expect("write feature");
+        fs::write(root.join("gone.txt"), "edited\n").expect("edit gone");
+        git(&root, &["commit", "-qam", "feature"])
+            .await
+            .expect("commit feature");
+        git(&root, &["checkout", "-q", "main"])
+            .await
+            .expect("checkout main");
+        fs::write(root.join("a.txt"), "main\n").expect("write main");
+        git(&root, &["rm", "-q", "gone.txt"])
+            .await
+            .expect("rm gone");
+        git(&root, &["commit", "-qam", "main"])
+            .await
+            .expect("commit main");
+        assert!(git(&root, &["merge", "feature"]).await.is_err());
+
+        let repo = open_repository_at_root(&root).expect("open repo");
+        assert_eq!(
+            conflict_files(&repo).expect("conflicts"),
+            vec![
+                GitConflictFile {
+                    path: "a.txt".to_string(),
+                    kind: GitConflictKind::BothModified,
+                },
+                GitConflictFile {
+                    path: "gone.txt".to_string(),
+                    kind: GitConflictKind::DeletedByUs,
+                },
+            ]
+        );
+
+        let versions = conflict_versions(&repo, "a.txt").expect("versions");
+        let text = |version: &Option<GitConflictVersion>| {
+            version.as_ref().and_then(|version| version.text.clone())
+        };
+        assert_eq!(text(&versions.base).as_deref(), Some("base\n"));
+        assert_eq!(text(&versions.ours).as_deref(), Some("main\n"));
+        assert_eq!(text(&versions.theirs).as_deref(), Some("feature\n"));
+        let deleted = conflict_versions(&repo, "gone.txt").expect("delete/modify");
+        assert!(deleted.ours.is_none());
+        assert_eq!(text(&deleted.theirs).as_deref(), Some("edited\n"));
+
+        let merged: GitConflictResolution =
+            serde_json::from_value(json!({ "content": "merged\n" })).expect("merged resolution");
+        apply_conflict_resolution(&root, "a.txt", merged)
+            .await
+            .expect("resolve merged");
+        let ours: GitConflictResolution =
+            serde_json::from_value(json!("ours")).expect("ours resolution");
+        apply_conflict_resolution(&root, "gone.txt", ours)
+            .await
+            .expect("resolve deleted side");
+
+        let repo = open_repository_at_root(&root).expect("reopen repo");
+        assert!(conflict_files(&repo).expect("conflicts").is_empty());
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).expect("read merged"),
+            "merged\n"
+        );
+        assert!(!root.join("gone.txt").exists());
+        assert!(conflict_versions(&repo, "a.txt").is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
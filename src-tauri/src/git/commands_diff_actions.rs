@@ -0,0 +1,415 @@
+use super::*;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::engine::{self, EngineTurnLinkage, EngineType};
+
+const DIFF_ACTION_MAX_DIFF_LINES: usize = 1_500;
+const DIFF_ACTION_MAX_DIFF_BYTES: usize = 96 * 1024;
+const DIFF_ACTION_CONTEXT_PADDING_LINES: usize = 30;
+const DIFF_ACTION_MAX_EXCERPT_LINES: usize = 400;
+const DIFF_ACTION_MAX_SOURCE_BYTES: u64 = 1024 * 1024;
+const DIFF_ACTION_MAX_TEST_FILES: usize = 10;
+const DIFF_ACTION_RECENT_SUBJECTS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum GitDiffAction {
+    Explain,
+    WriteTest,
+    CommitMessage,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitDiffActionLinkage {
+    action_id: String,
+    action: GitDiffAction,
+    path: String,
+    used_selection: bool,
+    diff_truncated: bool,
+    #[serde(flatten)]
+    turn: EngineTurnLinkage,
+}
+
+#[derive(Debug, Default)]
+struct DiffActionContext {
+    diff: String,
+    diff_truncated: bool,
+    used_selection: bool,
+    excerpt: Option<String>,
+    test_files: Vec<String>,
+    recent_subjects: Vec<String>,
+}
+
+/// Returns the new-side `(start, end)` line ranges of every hunk header.
+fn parse_new_hunk_ranges(diff: &str) -> Vec<(usize, usize)> {
+    diff.lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("@@ ")?;
+            let new_range = rest.split_whitespace().find(|part| part.starts_with('+'))?;
+            let mut parts = new_range.trim_start_matches('+').splitn(2, ',');
+            let start = parts.next()?.parse::<usize>().ok()?;
+            let count = parts
+                .next()
+                .map(|value| value.parse::<usize>().ok())
+                .unwrap_or(Some(1))?;
+            Some((
+                start.max(1),
+                (start + count.saturating_sub(1)).max(start.max(1)),
+            ))
+        })
+        .collect()
+}
+
+fn build_numbered_excerpt(source: &str, ranges: &[(usize, usize)]) -> Option<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    if lines.is_empty() {
+        return None;
+    }
+    let (first, last) = if ranges.is_empty() {
+        (1, lines.len())
+    } else {
+        let first = ranges.iter().map(|(start, _)| *start).min()?;
+        let last = ranges.iter().map(|(_, end)| *end).max()?;
+        (
+            first
+                .saturating_sub(DIFF_ACTION_CONTEXT_PADDING_LINES)
+                .max(1),
+            (last + DIFF_ACTION_CONTEXT_PADDING_LINES).min(lines.len()),
+        )
+    };
+    if first > lines.len() {
+        return None;
+    }
+    let last = last.min(first + DIFF_ACTION_MAX_EXCERPT_LINES - 1);
+    Some(
+        (first..=last)
+            .map(|number| format!("{number:>5} | {}", lines[number - 1]))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+fn read_source_excerpt(repo_root: &Path, path: &str, diff: &str) -> Option<String> {
+    let full_path = repo_root.join(path);
+    let metadata = fs::metadata(&full_path).ok()?;
+    if !metadata.is_file() || metadata.len() > DIFF_ACTION_MAX_SOURCE_BYTES {
+        return None;
+    }
+    let bytes = fs::read(&full_path).ok()?;
+    let source = String::from_utf8(bytes).ok()?;
+    build_numbered_excerpt(&source, &parse_new_hunk_ranges(diff))
+}
+
+fn is_test_like_name(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower.contains("test") || lower.contains("spec")
+}
+
+fn find_nearby_test_files(repo_root: &Path, path: &str) -> Vec<String> {
+    let relative_parent = Path::new(path).parent().unwrap_or(Path::new(""));
+    let mut directories = vec![relative_parent.to_path_buf()];
+    for name in ["tests", "test", "__tests__"] {
+        directories.push(relative_parent.join(name));
+    }
+    directories.push(PathBuf::from("tests"));
+
+    let mut found = Vec::new();
+    for directory in directories {
+        let Ok(entries) = fs::read_dir(repo_root.join(&directory)) else {
+            continue;
+        };
+        let mut names: Vec<String> = entries
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_type()
+                    .map(|kind| kind.is_file())
+                    .unwrap_or(false)
+            })
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .filter(|name| is_test_like_name(name))
+            .collect();
+        names.sort();
+        for name in names {
+            let relative = normalize_git_path(&directory.join(&name).to_string_lossy());
+            if !found.contains(&relative) {
+                found.push(relative);
+            }
+            if found.len() >= DIFF_ACTION_MAX_TEST_FILES {
+                return found;
+            }
+        }
+    }
+    found
+}
+
+fn recent_commit_subjects(repo: &Repository) -> Vec<String> {
+    let Ok(mut revwalk) = repo.revwalk() else {
+        return Vec::new();
+    };
+    if revwalk.push_head().is_err() {
+        return Vec::new();
+    }
+    revwalk
+        .flatten()
+        .filter_map(|oid| repo.find_commit(oid).ok())
+        .filter(|commit| commit.parent_count() <= 1)
+        .filter_map(|commit| commit.summary().map(str::to_string))
+        .take(DIFF_ACTION_RECENT_SUBJECTS)
+        .collect()
+}
+
+fn collect_diff_action_context(
+    repo_root: &Path,
+    action: GitDiffAction,
+    path: &str,
+    selection: Option<String>,
+) -> Result<DiffActionContext, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let selection = selection.filter(|value| !value.trim().is_empty());
+    let used_selection = selection.is_some();
+    let raw_diff = match selection {
+        Some(selection) => selection,
+        None => {
+            let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+            collect_worktree_diff(&repo, head_tree.as_ref(), Some(&[path.to_string()]))?
+        }
+    };
+    if raw_diff.trim().is_empty() {
+        return Err(format!("No changes found for {path}"));
+    }
+    let diff = truncate_diff_preview(
+        raw_diff.clone(),
+        DIFF_ACTION_MAX_DIFF_LINES,
+        DIFF_ACTION_MAX_DIFF_BYTES,
+    );
+    let diff_truncated = diff.ends_with("[diff truncated for performance]");
+
+    let mut context = DiffActionContext {
+        diff,
+        diff_truncated,
+        used_selection,
+        ..DiffActionContext::default()
+    };
+    match action {
+        GitDiffAction::Explain => {
+            context.excerpt = read_source_excerpt(repo_root, path, &raw_diff);
+        }
+        GitDiffAction::WriteTest => {
+            context.excerpt = read_source_excerpt(repo_root, path, &raw_diff);
+            context.test_files = find_nearby_test_files(repo_root, path);
+        }
+        GitDiffAction::CommitMessage => {
+            context.recent_subjects = recent_commit_subjects(&repo);
+        }
+    }
+    Ok(context)
+}
+
+fn build_diff_action_prompt(
+    action: GitDiffAction,
+    path: &str,
+    context: &DiffActionContext,
+) -> String {
+    let scope = if context.used_selection {
+        "the selected hunk"
+    } else {
+        "the uncommitted change"
+    };
+    let mut prompt = match action {
+        GitDiffAction::Explain => format!(
+            "Explain {scope} in `{path}`. Describe what it does, why it was likely made, and any risks or follow-ups a reviewer should check. Do not modify any files.\n"
+        ),
+        GitDiffAction::WriteTest => format!(
+            "Write tests that cover {scope} in `{path}`. Follow the project's existing test layout and conventions, exercise the new behavior and its edge cases, and run the tests if possible.\n"
+        ),
+        GitDiffAction::CommitMessage => format!(
+            "Suggest a commit message for {scope} in `{path}`. Reply with the message only (subject line, blank line, optional body). Do not modify any files or run git commands.\n"
+        ),
+    };
+
+    prompt.push_str("\n## Diff\n```diff\n");
+    prompt.push_str(context.diff.trim_end());
+    prompt.push_str("\n```\n");
+    if let Some(excerpt) = context.excerpt.as_deref() {
+        prompt.push_str(&format!(
+            "\n## Current `{path}` around the change\n```\n{excerpt}\n```\n"
+        ));
+    }
+    if !context.test_files.is_empty() {
+        prompt.push_str("\n## Existing test files nearby\n");
+        for file in &context.test_files {
+            prompt.push_str(&format!("- {file}\n"));
+        }
+    }
+    if !context.recent_subjects.is_empty() {
+        prompt.push_str("\n## Recent commit subjects (match this style)\n");
+        for subject in &context.recent_subjects {
+            prompt.push_str(&format!("- {subject}\n"));
+        }
+    }
+    prompt
+}
+
+/// Runs a quick action ("explain", "write test", "suggest commit message")
+/// against a file or a selected hunk and starts an engine turn for it.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_git_diff_action(
+    workspace_id: String,
+    action: GitDiffAction,
+    path: String,
+    hunk: Option<String>,
+    engine: Option<EngineType>,
+    model: Option<String>,
+    access_mode: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitDiffActionLinkage, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let normalized_path = normalize_git_path(&path).trim_matches('/').to_string();
+    if normalized_path.is_empty() {
+        return Err("File path is required.".to_string());
+    }
+    if normalized_path
+        .split('/')
+        .any(|segment| segment == ".." || segment == ".")
+    {
+        return Err("Invalid file path".to_string());
+    }
+    let context_root = repo_root.clone();
+    let context_path = normalized_path.clone();
+    let context = tokio::task::spawn_blocking(move || {
+        collect_diff_action_context(&context_root, action, &context_path, hunk)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    let prompt = build_diff_action_prompt(action, &normalized_path, &context);
+
+    let engine = match engine {
+        Some(engine) => engine,
+        None => state.engine_manager.get_active_engine().await,
+    };
+    let turn = engine::start_engine_turn(
+        &workspace_id,
+        engine,
+        prompt,
+        model,
+        access_mode,
+        state,
+        app,
+    )
+    .await?;
+    Ok(GitDiffActionLinkage {
+        action_id: uuid::Uuid::new_v4().to_string(),
+        action,
+        path: normalized_path,
+        used_selection: context.used_selection,
+        diff_truncated: context.diff_truncated,
+        turn,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_repo_with_change() -> (PathBuf, Repository) {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(root.join("src")).expect("create repo dir");
+        let repo = Repository::init(&root).expect("init repo");
+        fs::write(root.join("src/lib.rs"), "fn a() {}\n").expect("write file");
+        {
+            let mut index = repo.index().expect("index");
+            index.add_path(Path::new("src/lib.rs")).expect("add path");
+            index.write().expect("write index");
+            let tree_id = index.write_tree().expect("write tree");
+            let tree = repo.find_tree(tree_id).expect("find tree");
+            let signature = git2::Signature::now("Test", "test@example.com").expect("signature");
+            repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "feat: add a",
+                &tree,
+                &[],
+            )
+            .expect("commit");
+        }
+        fs::write(root.join("src/lib.rs"), "fn a() {}\nfn b() {}\n").expect("modify file");
+        fs::write(root.join("src/lib_test.rs"), "").expect("write test file");
+        (root, repo)
+    }
+
+    #[test]
+    fn parses_new_side_hunk_ranges() {
+        let diff = "@@ -1,2 +1,3 @@\n a\n+b\n@@ -10 +11 @@\n-x\n+y\n";
+        assert_eq!(parse_new_hunk_ranges(diff), vec![(1, 3), (11, 11)]);
+    }
+
+    #[test]
+    fn numbered_excerpt_is_padded_and_bounded() {
+        let source = (1..=100)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let excerpt = build_numbered_excerpt(&source, &[(50, 52)]).expect("excerpt");
+        assert!(excerpt.starts_with("   20 | line 20"));
+        assert!(excerpt.ends_with("   82 | line 82"));
+    }
+
+    #[test]
+    fn collects_context_for_each_action() {
+        let (root, _repo) = create_repo_with_change();
+
+        let commit =
+            collect_diff_action_context(&root, GitDiffAction::CommitMessage, "src/lib.rs", None)
+                .expect("commit message context");
+        assert!(commit.diff.contains("+fn b() {}"));
+        assert!(!commit.used_selection);
+        assert_eq!(commit.recent_subjects, vec!["feat: add a".to_string()]);
+
+        let tests =
+            collect_diff_action_context(&root, GitDiffAction::WriteTest, "src/lib.rs", None)
+                .expect("write test context");
+        assert_eq!(tests.test_files, vec!["src/lib_test.rs".to_string()]);
+        assert!(tests
+            .excerpt
+            .as_deref()
+            .unwrap_or("")
+            .contains("2 | fn b() {}"));
+
+        let selected = collect_diff_action_context(
+            &root,
+            GitDiffAction::Explain,
+            "src/lib.rs",
+            Some("@@ -1 +1,2 @@\n fn a() {}\n+fn b() {}\n".to_string()),
+        )
+        .expect("explain context");
+        assert!(selected.used_selection);
+        let prompt = build_diff_action_prompt(GitDiffAction::Explain, "src/lib.rs", &selected);
+        assert!(prompt.contains("Explain the selected hunk in `src/lib.rs`"));
+        assert!(prompt.contains("```diff"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn rejects_files_without_changes() {
+        let (root, _repo) = create_repo_with_change();
+        let error = collect_diff_action_context(&root, GitDiffAction::Explain, "README.md", None)
+            .expect_err("no diff");
+        assert!(error.contains("No changes found"));
+        let _ = fs::remove_dir_all(&root);
+    }
+}
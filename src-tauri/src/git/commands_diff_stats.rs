@@ -0,0 +1,447 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+const DIFF_STATS_MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+const DIFF_STATS_LARGEST_FILES: usize = 10;
+const ROOT_DIRECTORY: &str = ".";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitDiffFileStat {
+    path: String,
+    old_path: Option<String>,
+    status: String,
+    additions: i64,
+    deletions: i64,
+    is_binary: bool,
+    /// Lockfiles, vendored/build output and very large files are listed
+    /// without line counts to keep the call cheap.
+    stats_skipped: bool,
+    language: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitDiffBucketStat {
+    /// Directory path (`.` for the repository root) or language name.
+    key: String,
+    files: usize,
+    additions: i64,
+    deletions: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitDiffStats {
+    /// The compared range, or `None` for uncommitted changes against HEAD.
+    range: Option<String>,
+    files_changed: usize,
+    additions: i64,
+    deletions: i64,
+    binary_files: usize,
+    files: Vec<GitDiffFileStat>,
+    /// Every directory containing a changed file, including ancestors, so
+    /// the UI can drill down level by level.
+    directories: Vec<GitDiffBucketStat>,
+    languages: Vec<GitDiffBucketStat>,
+    largest_files: Vec<GitDiffFileStat>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffStatsTarget {
+    Worktree,
+    /// `git diff <ref>`: the ref against the working tree.
+    RefToWorktree(String),
+    /// `git diff a..b`.
+    Range(String, String),
+    /// `git diff a...b`: the merge base of both against `b`.
+    MergeBaseRange(String, String),
+}
+
+fn parse_diff_stats_target(range: Option<&str>) -> Result<DiffStatsTarget, String> {
+    let Some(range) = range.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(DiffStatsTarget::Worktree);
+    };
+    let or_head = |value: &str| {
+        if value.is_empty() {
+            "HEAD".to_string()
+        } else {
+            value.to_string()
+        }
+    };
+    if let Some((from, to)) = range.split_once("...") {
+        if to.starts_with('.') {
+            return Err(format!("Invalid range: {range}"));
+        }
+        return Ok(DiffStatsTarget::MergeBaseRange(or_head(from), or_head(to)));
+    }
+    if let Some((from, to)) = range.split_once("..") {
+        if to.starts_with('.') {
+            return Err(format!("Invalid range: {range}"));
+        }
+        return Ok(DiffStatsTarget::Range(or_head(from), or_head(to)));
+    }
+    Ok(DiffStatsTarget::RefToWorktree(range.to_string()))
+}
+
+/// Language bucket for a changed file, from its name or extension.
+fn language_for_path(path: &str) -> &'static str {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let lower = file_name.to_ascii_lowercase();
+    match lower.as_str() {
+        "dockerfile" | "containerfile" => return "Dockerfile",
+        "makefile" | "gnumakefile" => return "Makefile",
+        "cmakelists.txt" => return "CMake",
+        _ => {}
+    }
+    let Some((_, extension)) = lower.rsplit_once('.') else {
+        return "Other";
+    };
+    match extension {
+        "rs" => "Rust",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "py" | "pyi" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => "C++",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "scala" => "Scala",
+        "dart" => "Dart",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "css" | "scss" | "sass" | "less" => "CSS",
+        "html" | "htm" => "HTML",
+        "sql" => "SQL",
+        "sh" | "bash" | "zsh" | "fish" => "Shell",
+        "ps1" | "psm1" => "PowerShell",
+        "md" | "mdx" | "markdown" => "Markdown",
+        "json" | "jsonc" | "json5" => "JSON",
+        "yml" | "yaml" => "YAML",
+        "toml" => "TOML",
+        "xml" => "XML",
+        "proto" => "Protocol Buffers",
+        _ => "Other",
+    }
+}
+
+fn parent_directories(path: &str) -> Vec<String> {
+    let segments: Vec<&str> = path.split('/').collect();
+    let mut directories = vec![ROOT_DIRECTORY.to_string()];
+    for depth in 1..segments.len() {
+        directories.push(segments[..depth].join("/"));
+    }
+    directories
+}
+
+fn add_to_bucket(bucket: &mut GitDiffBucketStat, file: &GitDiffFileStat) {
+    bucket.files += 1;
+    bucket.additions += file.additions;
+    bucket.deletions += file.deletions;
+}
+
+fn summarize_diff_stats(range: Option<String>, mut files: Vec<GitDiffFileStat>) -> GitDiffStats {
+    files.sort_by(|left, right| left.path.cmp(&right.path));
+
+    let mut directories: BTreeMap<String, GitDiffBucketStat> = BTreeMap::new();
+    let mut languages: HashMap<&'static str, GitDiffBucketStat> = HashMap::new();
+    for file in &files {
+        for directory in parent_directories(&file.path) {
+            let bucket =
+                directories
+                    .entry(directory.clone())
+                    .or_insert_with(|| GitDiffBucketStat {
+                        key: directory,
+                        ..GitDiffBucketStat::default()
+                    });
+            add_to_bucket(bucket, file);
+        }
+        let language = language_for_path(&file.path);
+        let bucket = languages
+            .entry(language)
+            .or_insert_with(|| GitDiffBucketStat {
+                key: language.to_string(),
+                ..GitDiffBucketStat::default()
+            });
+        add_to_bucket(bucket, file);
+    }
+    let mut languages: Vec<GitDiffBucketStat> = languages.into_values().collect();
+    languages.sort_by(|left, right| {
+        (right.additions + right.deletions)
+            .cmp(&(left.additions + left.deletions))
+            .then(right.files.cmp(&left.files))
+            .then(left.key.cmp(&right.key))
+    });
+
+    let mut largest_files: Vec<GitDiffFileStat> = files
+        .iter()
+        .filter(|file| file.additions + file.deletions > 0)
+        .cloned()
+        .collect();
+    largest_files.sort_by(|left, right| {
+        (right.additions + right.deletions)
+            .cmp(&(left.additions + left.deletions))
+            .then(left.path.cmp(&right.path))
+    });
+    largest_files.truncate(DIFF_STATS_LARGEST_FILES);
+
+    GitDiffStats {
+        range,
+        files_changed: files.len(),
+        additions: files.iter().map(|file| file.additions).sum(),
+        deletions: files.iter().map(|file| file.deletions).sum(),
+        binary_files: files.iter().filter(|file| file.is_binary).count(),
+        files,
+        directories: directories.into_values().collect(),
+        languages,
+        largest_files,
+    }
+}
+
+fn resolve_tree<'repo>(repo: &'repo Repository, spec: &str) -> Result<git2::Tree<'repo>, String> {
+    repo.revparse_single(spec)
+        .and_then(|object| object.peel_to_tree())
+        .map_err(|_| format!("Commit or ref not found: {spec}"))
+}
+
+fn diff_for_target<'repo>(
+    repo: &'repo Repository,
+    target: &DiffStatsTarget,
+) -> Result<git2::Diff<'repo>, String> {
+    let mut options = DiffOptions::new();
+    let diff = match target {
+        DiffStatsTarget::Worktree | DiffStatsTarget::RefToWorktree(_) => {
+            options
+                .include_untracked(true)
+                .recurse_untracked_dirs(true)
+                .show_untracked_content(true);
+            let tree = match target {
+                DiffStatsTarget::RefToWorktree(spec) => Some(resolve_tree(repo, spec)?),
+                _ => repo.head().ok().and_then(|head| head.peel_to_tree().ok()),
+            };
+            repo.diff_tree_to_workdir_with_index(tree.as_ref(), Some(&mut options))
+        }
+        DiffStatsTarget::Range(from, to) => {
+            let from_tree = resolve_tree(repo, from)?;
+            let to_tree = resolve_tree(repo, to)?;
+            repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut options))
+        }
+        DiffStatsTarget::MergeBaseRange(from, to) => {
+            let resolve_commit = |spec: &str| {
+                repo.revparse_single(spec)
+                    .and_then(|object| object.peel_to_commit())
+                    .map_err(|_| format!("Commit or ref not found: {spec}"))
+            };
+            let from_commit = resolve_commit(from)?;
+            let to_commit = resolve_commit(to)?;
+            let base = repo
+                .merge_base(from_commit.id(), to_commit.id())
+                .map_err(|_| format!("No common ancestor between {from} and {to}"))?;
+            let base_tree = repo
+                .find_commit(base)
+                .and_then(|commit| commit.tree())
+                .map_err(|e| e.to_string())?;
+            let to_tree = to_commit.tree().map_err(|e| e.to_string())?;
+            repo.diff_tree_to_tree(Some(&base_tree), Some(&to_tree), Some(&mut options))
+        }
+    };
+    let mut diff = diff.map_err(|e| e.to_string())?;
+    diff.find_similar(None).map_err(|e| e.to_string())?;
+    Ok(diff)
+}
+
+fn collect_diff_file_stats(
+    repo_root: &Path,
+    diff: &git2::Diff<'_>,
+    worktree: bool,
+) -> Vec<GitDiffFileStat> {
+    let mut files = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let old_path = delta
+            .old_file()
+            .path()
+            .map(|path| normalize_git_path(path.to_string_lossy().as_ref()));
+        let new_path = delta
+            .new_file()
+            .path()
+            .map(|path| normalize_git_path(path.to_string_lossy().as_ref()));
+        let Some(path) = new_path.clone().or_else(|| old_path.clone()) else {
+            continue;
+        };
+        let stats_skipped = is_heavy_diff_path(&path)
+            || delta.old_file().size().max(delta.new_file().size()) > DIFF_STATS_MAX_FILE_BYTES
+            || (worktree && is_large_worktree_file(repo_root, &path, DIFF_STATS_MAX_FILE_BYTES));
+
+        let mut additions = 0i64;
+        let mut deletions = 0i64;
+        let mut is_binary = delta.flags().is_binary();
+        if !stats_skipped && !is_binary {
+            match git2::Patch::from_diff(diff, index) {
+                Ok(Some(patch)) if patch.delta().flags().is_binary() => is_binary = true,
+                Ok(Some(patch)) => {
+                    if let Ok((_, added, deleted)) = patch.line_stats() {
+                        additions = added as i64;
+                        deletions = deleted as i64;
+                    }
+                }
+                Ok(None) | Err(_) => is_binary = true,
+            }
+        }
+        files.push(GitDiffFileStat {
+            language: language_for_path(&path).to_string(),
+            old_path: old_path.filter(|old| *old != path),
+            path,
+            status: status_for_delta(delta.status()).to_string(),
+            additions,
+            deletions,
+            is_binary,
+            stats_skipped,
+        });
+    }
+    files
+}
+
+/// Per-file, per-directory and per-language line counts for uncommitted
+/// changes (no `range`) or a git range (`ref`, `a..b`, `a...b`), without
+/// patch text.
+#[tauri::command]
+pub(crate) async fn get_git_diff_stats(
+    workspace_id: String,
+    range: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitDiffStats, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let target = parse_diff_stats_target(range.as_deref())?;
+    let range = match target {
+        DiffStatsTarget::Worktree => None,
+        _ => range.map(|value| value.trim().to_string()),
+    };
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository_at_root(&repo_root)?;
+        let diff = diff_for_target(&repo, &target)?;
+        let worktree = matches!(
+            target,
+            DiffStatsTarget::Worktree | DiffStatsTarget::RefToWorktree(_)
+        );
+        let files = collect_diff_file_stats(&repo_root, &diff, worktree);
+        Ok::<_, String>(summarize_diff_stats(range, files))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, additions: i64, deletions: i64) -> GitDiffFileStat {
+        GitDiffFileStat {
+            path: path.to_string(),
+            old_path: None,
+            status: "M".to_string(),
+            additions,
+            deletions,
+            is_binary: false,
+            stats_skipped: false,
+            language: language_for_path(path).to_string(),
+        }
+    }
+
+    fn bucket(key: &str, files: usize, additions: i64, deletions: i64) -> GitDiffBucketStat {
+        GitDiffBucketStat {
+            key: key.to_string(),
+            files,
+            additions,
+            deletions,
+        }
+    }
+
+    #[test]
+    fn parses_diff_stats_targets() {
+        assert_eq!(parse_diff_stats_target(None), Ok(DiffStatsTarget::Worktree));
+        assert_eq!(
+            parse_diff_stats_target(Some("  ")),
+            Ok(DiffStatsTarget::Worktree)
+        );
+        assert_eq!(
+            parse_diff_stats_target(Some("main")),
+            Ok(DiffStatsTarget::RefToWorktree("main".to_string()))
+        );
+        assert_eq!(
+            parse_diff_stats_target(Some("v1.0..")),
+            Ok(DiffStatsTarget::Range(
+                "v1.0".to_string(),
+                "HEAD".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_diff_stats_target(Some("main...feature")),
+            Ok(DiffStatsTarget::MergeBaseRange(
+                "main".to_string(),
+                "feature".to_string()
+            ))
+        );
+        assert!(parse_diff_stats_target(Some("a....b")).is_err());
+    }
+
+    #[test]
+    fn maps_paths_to_languages() {
+        assert_eq!(language_for_path("src-tauri/src/lib.rs"), "Rust");
+        assert_eq!(language_for_path("src/App.TSX"), "TypeScript");
+        assert_eq!(language_for_path("docker/Dockerfile"), "Dockerfile");
+        assert_eq!(language_for_path("LICENSE"), "Other");
+    }
+
+    #[test]
+    fn summarizes_directories_languages_and_largest_files() {
+        let stats = summarize_diff_stats(
+            Some("main..feature".to_string()),
+            vec![
+                file("src/git/mod.rs", 10, 2),
+                file("README.md", 1, 1),
+                file("src/lib.rs", 3, 0),
+                file("src/App.tsx", 0, 0),
+            ],
+        );
+
+        assert_eq!(stats.files_changed, 4);
+        assert_eq!((stats.additions, stats.deletions), (14, 3));
+        assert_eq!(
+            stats.directories,
+            vec![
+                bucket(".", 4, 14, 3),
+                bucket("src", 3, 13, 2),
+                bucket("src/git", 1, 10, 2),
+            ]
+        );
+        assert_eq!(
+            stats.languages,
+            vec![
+                bucket("Rust", 2, 13, 2),
+                bucket("Markdown", 1, 1, 1),
+                bucket("TypeScript", 1, 0, 0),
+            ]
+        );
+        let largest: Vec<&str> = stats
+            .largest_files
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect();
+        assert_eq!(largest, vec!["src/git/mod.rs", "src/lib.rs", "README.md"]);
+    }
+}
@@ -0,0 +1,294 @@
+use super::*;
+
+use serde::Serialize;
+
+/// Larger files are reported as `too_large` without their content.
+const FILE_AT_REF_MAX_BYTES: usize = 2 * 1024 * 1024;
+
+/// One side of a comparison. `text` is set for text files within the size
+/// limit; `encoding` is what it was decoded from so edits can be written back
+/// the same way.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitFileContent {
+    pub(crate) path: String,
+    pub(crate) exists: bool,
+    pub(crate) size: usize,
+    pub(crate) is_binary: bool,
+    pub(crate) too_large: bool,
+    pub(crate) text: Option<String>,
+    pub(crate) encoding: Option<String>,
+}
+
+impl GitFileContent {
+    fn missing(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            ..Self::default()
+        }
+    }
+
+    fn from_bytes(path: &str, bytes: &[u8], is_binary: bool) -> Self {
+        let mut content = Self {
+            path: path.to_string(),
+            exists: true,
+            size: bytes.len(),
+            is_binary: is_binary || bytes.contains(&0),
+            ..Self::default()
+        };
+        if bytes.len() > FILE_AT_REF_MAX_BYTES {
+            content.too_large = true;
+        } else if !content.is_binary {
+            let decoded = decode_text_lossless(bytes);
+            content.text = Some(decoded.text);
+            content.encoding = Some(decoded.encoding.to_string());
+        }
+        content
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitFileAtRef {
+    pub(crate) ref_name: String,
+    pub(crate) commit_sha: String,
+    /// Set when the file had a different path at `ref_name`; `content.path`
+    /// is that older path.
+    pub(crate) renamed_from: Option<String>,
+    #[serde(flatten)]
+    pub(crate) content: GitFileContent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitFileRefComparison {
+    pub(crate) base: GitFileAtRef,
+    pub(crate) working: GitFileContent,
+}
+
+/// The path `path` (as it is in the index) had in `tree`, following renames
+/// when `follow_renames` is set.
+fn path_in_tree(
+    repo: &Repository,
+    tree: &git2::Tree,
+    path: &str,
+    follow_renames: bool,
+) -> Result<Option<String>, String> {
+    if tree.get_path(Path::new(path)).is_ok() {
+        return Ok(Some(path.to_string()));
+    }
+    if !follow_renames {
+        return Ok(None);
+    }
+    let mut diff = repo
+        .diff_tree_to_index(Some(tree), None, None)
+        .map_err(|e| e.to_string())?;
+    let mut find_options = git2::DiffFindOptions::new();
+    find_options.renames(true);
+    diff.find_similar(Some(&mut find_options))
+        .map_err(|e| e.to_string())?;
+    Ok(diff.deltas().find_map(|delta| {
+        if delta.status() != git2::Delta::Renamed {
+            return None;
+        }
+        let new_path = delta.new_file().path()?.to_string_lossy();
+        if normalize_git_path(&new_path) != path {
+            return None;
+        }
+        delta
+            .old_file()
+            .path()
+            .map(|old| normalize_git_path(&old.to_string_lossy()))
+    }))
+}
+
+fn file_at_ref(
+    repo_root: &Path,
+    path: &str,
+    ref_name: &str,
+    follow_renames: bool,
+) -> Result<GitFileAtRef, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let commit = repo
+        .revparse_single(ref_name)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|_| format!("Unknown ref: {ref_name}"))?;
+    let tree = commit.tree().map_err(|e| e.to_string())?;
+    let resolved = path_in_tree(&repo, &tree, path, follow_renames)?;
+    let content = match resolved.as_deref() {
+        Some(resolved) => {
+            let entry = tree
+                .get_path(Path::new(resolved))
+                .map_err(|e| e.to_string())?;
+            match entry
+                .to_object(&repo)
+                .ok()
+                .and_then(|object| object.into_blob().ok())
+            {
+                Some(blob) => {
+                    GitFileContent::from_bytes(resolved, blob.content(), blob.is_binary())
+                }
+                // Directories and submodules have no blob to compare.
+                None => GitFileContent::missing(resolved),
+            }
+        }
+        None => GitFileContent::missing(path),
+    };
+    Ok(GitFileAtRef {
+        ref_name: ref_name.to_string(),
+        commit_sha: commit.id().to_string(),
+        renamed_from: resolved.filter(|resolved| resolved != path),
+        content,
+    })
+}
+
+fn working_file(repo_root: &Path, path: &str) -> Result<GitFileContent, String> {
+    let full_path = repo_root.join(path);
+    if !full_path.is_file() {
+        return Ok(GitFileContent::missing(path));
+    }
+    let size = fs::metadata(&full_path).map_err(|e| e.to_string())?.len();
+    if size > FILE_AT_REF_MAX_BYTES as u64 {
+        return Ok(GitFileContent {
+            path: path.to_string(),
+            exists: true,
+            size: size as usize,
+            too_large: true,
+            ..GitFileContent::default()
+        });
+    }
+    let bytes = fs::read(&full_path).map_err(|e| e.to_string())?;
+    Ok(GitFileContent::from_bytes(path, &bytes, false))
+}
+
+async fn resolve_file_ref_args(
+    state: &State<'_, AppState>,
+    workspace_id: &str,
+    path: &str,
+    ref_name: &str,
+) -> Result<(PathBuf, String, String), String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(path)
+        .trim()
+        .trim_matches('/')
+        .to_string();
+    if path.is_empty() {
+        return Err("path is required".to_string());
+    }
+    let ref_name = ref_name.trim().to_string();
+    if ref_name.is_empty() {
+        return Err("ref is required".to_string());
+    }
+    Ok((repo_root, path, ref_name))
+}
+
+/// Returns the file as it was at any commit, branch, tag or revision
+/// expression (`main`, `HEAD~2`), without checking anything out.
+#[tauri::command]
+pub(crate) async fn get_file_at_ref(
+    workspace_id: String,
+    path: String,
+    ref_name: String,
+    follow_renames: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitFileAtRef, String> {
+    let (repo_root, path, ref_name) =
+        resolve_file_ref_args(&state, &workspace_id, &path, &ref_name).await?;
+    let follow_renames = follow_renames.unwrap_or(true);
+    tokio::task::spawn_blocking(move || file_at_ref(&repo_root, &path, &ref_name, follow_renames))
+        .await
+        .map_err(|e| format!("File lookup task failed: {e}"))?
+}
+
+/// The file at `ref_name` alongside its working copy, for side-by-side views
+/// where the working side stays editable.
+#[tauri::command]
+pub(crate) async fn compare_file_with_ref(
+    workspace_id: String,
+    path: String,
+    ref_name: String,
+    follow_renames: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitFileRefComparison, String> {
+    let (repo_root, path, ref_name) =
+        resolve_file_ref_args(&state, &workspace_id, &path, &ref_name).await?;
+    let follow_renames = follow_renames.unwrap_or(true);
+    tokio::task::spawn_blocking(move || {
+        Ok(GitFileRefComparison {
+            base: file_at_ref(&repo_root, &path, &ref_name, follow_renames)?,
+            working: working_file(&repo_root, &path)?,
+        })
+    })
+    .await
+    .map_err(|e| format!("File compare task failed: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_index(repo: &Repository, message: &str) -> Oid {
+        let mut index = repo.index().expect("index");
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .expect("add all");
+        index
+            .update_all(["*"].iter(), None)
+            .expect("update removed");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+        let signature = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .expect("commit")
+    }
+
+    #[test]
+    fn reads_older_versions_across_renames() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create repo dir");
+        let repo = Repository::init(&root).expect("init repo");
+        let body = "line one\nline two\nline three\nline four\n";
+        fs::write(root.join("old.txt"), body).expect("write old");
+        let first = commit_index(&repo, "add old");
+        fs::rename(root.join("old.txt"), root.join("new.txt")).expect("rename");
+        commit_index(&repo, "rename to new");
+        fs::write(root.join("new.txt"), format!("{body}edited\n")).expect("edit working copy");
+
+        let base = file_at_ref(&root, "new.txt", "HEAD~1", true).expect("file at ref");
+        assert_eq!(base.commit_sha, first.to_string());
+        assert_eq!(base.renamed_from.as_deref(), Some("old.txt"));
+        assert_eq!(base.content.path, "old.txt");
+        assert_eq!(base.content.text.as_deref(), Some(body));
+        assert_eq!(base.content.encoding.as_deref(), Some("UTF-8"));
+
+        let unfollowed = file_at_ref(&root, "new.txt", "HEAD~1", false).expect("unfollowed");
+        assert!(!unfollowed.content.exists);
+        assert_eq!(unfollowed.renamed_from, None);
+
+        let working = working_file(&root, "new.txt").expect("working copy");
+        assert_eq!(working.text, Some(format!("{body}edited\n")));
+        assert!(!working_file(&root, "old.txt").expect("missing").exists);
+
+        assert!(file_at_ref(&root, "new.txt", "no-such-branch", true).is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
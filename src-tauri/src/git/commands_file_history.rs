@@ -0,0 +1,290 @@
+use super::*;
+
+use serde::Serialize;
+
+/// A commit that touched the file, with the path the file had in that
+/// commit. `old_path` is set on renames when renames are followed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitFileHistoryEntry {
+    #[serde(flatten)]
+    pub(crate) commit: GitHistoryCommit,
+    pub(crate) path: String,
+    pub(crate) old_path: Option<String>,
+    pub(crate) status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitFileHistoryResponse {
+    pub(crate) path: String,
+    pub(crate) total: usize,
+    pub(crate) total_is_estimated: bool,
+    pub(crate) offset: usize,
+    pub(crate) limit: usize,
+    pub(crate) has_more: bool,
+    pub(crate) entries: Vec<GitFileHistoryEntry>,
+}
+
+fn tree_entry_id(tree: Option<&git2::Tree>, path: &str) -> Option<Oid> {
+    tree?.get_path(Path::new(path)).ok().map(|entry| entry.id())
+}
+
+/// Finds the path `path` was renamed from in `commit`, comparing against the
+/// first parent with rename detection. Only called when the path appears in
+/// a commit, so the full-tree diff stays rare.
+fn rename_source(
+    repo: &Repository,
+    parent_tree: Option<&git2::Tree>,
+    tree: &git2::Tree,
+    path: &str,
+) -> Result<Option<String>, String> {
+    let mut diff = repo
+        .diff_tree_to_tree(parent_tree, Some(tree), None)
+        .map_err(|e| e.to_string())?;
+    let mut find_options = git2::DiffFindOptions::new();
+    find_options.renames(true);
+    diff.find_similar(Some(&mut find_options))
+        .map_err(|e| e.to_string())?;
+    Ok(diff.deltas().find_map(|delta| {
+        if delta.status() != git2::Delta::Renamed {
+            return None;
+        }
+        let new_path = delta.new_file().path()?.to_string_lossy();
+        if normalize_git_path(&new_path) != path {
+            return None;
+        }
+        delta
+            .old_file()
+            .path()
+            .map(|old| normalize_git_path(&old.to_string_lossy()))
+    }))
+}
+
+/// Walks history from HEAD and keeps the commits whose tree differs from
+/// their first parent at the tracked path. Walking backwards, a followed
+/// rename switches the tracked path to the old name. Files deleted at HEAD
+/// are found through the commit that deleted them.
+fn file_history(
+    repo_root: &Path,
+    path: &str,
+    offset: usize,
+    limit: usize,
+    follow_renames: bool,
+) -> Result<GitFileHistoryResponse, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(|e| e.to_string())?;
+    if revwalk.push_head().is_err() {
+        // Unborn branch: nothing has been committed yet.
+        return Ok(GitFileHistoryResponse {
+            path: path.to_string(),
+            total: 0,
+            total_is_estimated: false,
+            offset,
+            limit,
+            has_more: false,
+            entries: Vec::new(),
+        });
+    }
+    let refs_map = collect_commit_refs_map(&repo);
+
+    let mut tracked_path = path.to_string();
+    let mut window = HistoryWindow::new(offset, limit, true);
+    let mut exhausted = true;
+    for oid_result in revwalk {
+        if window.is_done() {
+            exhausted = false;
+            break;
+        }
+        window.note_walked();
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        let tree = commit.tree().map_err(|e| e.to_string())?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree().map_err(|e| e.to_string())?),
+            Err(_) => None,
+        };
+        let current = tree_entry_id(Some(&tree), &tracked_path);
+        let previous = tree_entry_id(parent_tree.as_ref(), &tracked_path);
+        let (status, old_path) = match (previous, current) {
+            (Some(before), Some(after)) if before == after => continue,
+            (None, None) => continue,
+            (Some(_), Some(_)) => ("M", None),
+            (Some(_), None) => ("D", None),
+            (None, Some(_)) => {
+                let source = if follow_renames {
+                    rename_source(&repo, parent_tree.as_ref(), &tree, &tracked_path)?
+                } else {
+                    None
+                };
+                match source {
+                    Some(source) => ("R", Some(source)),
+                    None => ("A", None),
+                }
+            }
+        };
+
+        let entry_path = tracked_path.clone();
+        let entry_old_path = old_path.clone();
+        window.push_match(|| {
+            let sha = oid.to_string();
+            GitFileHistoryEntry {
+                commit: GitHistoryCommit {
+                    short_sha: sha.chars().take(7).collect(),
+                    sha,
+                    summary: commit.summary().unwrap_or("").to_string(),
+                    message: commit.message().unwrap_or("").to_string(),
+                    author: commit.author().name().unwrap_or("").to_string(),
+                    author_email: commit.author().email().unwrap_or("").to_string(),
+                    timestamp: commit.time().seconds(),
+                    parents: commit
+                        .parents()
+                        .map(|parent| parent.id().to_string())
+                        .collect(),
+                    refs: refs_map.get(&oid).cloned().unwrap_or_default(),
+                },
+                path: entry_path,
+                old_path: entry_old_path,
+                status: status.to_string(),
+            }
+        });
+        if let Some(old_path) = old_path {
+            tracked_path = old_path;
+        }
+    }
+
+    let result = window.finish(exhausted);
+    Ok(GitFileHistoryResponse {
+        path: path.to_string(),
+        total: result.total,
+        total_is_estimated: result.total_is_estimated,
+        offset,
+        limit,
+        has_more: result.has_more,
+        entries: result.page,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_file_history(
+    workspace_id: String,
+    path: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    follow_renames: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitFileHistoryResponse, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(&path)
+        .trim()
+        .trim_matches('/')
+        .to_string();
+    if path.is_empty() {
+        return Err("path is required".to_string());
+    }
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(100).clamp(1, 500);
+    let follow_renames = follow_renames.unwrap_or(true);
+    tokio::task::spawn_blocking(move || {
+        file_history(&repo_root, &path, offset, limit, follow_renames)
+    })
+    .await
+    .map_err(|e| format!("File history task failed: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_index(repo: &Repository, message: &str) -> Oid {
+        let mut index = repo.index().expect("index");
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .expect("add all");
+        index
+            .update_all(["*"].iter(), None)
+            .expect("update removed");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+        let signature = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .expect("commit")
+    }
+
+    #[test]
+    fn follows_renames_and_finds_deleted_files() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create repo dir");
+        let repo = Repository::init(&root).expect("init repo");
+        let body = "line one\nline two\nline three\nline four\n";
+        fs::write(root.join("old.txt"), body).expect("write old");
+        fs::write(root.join("other.txt"), "other\n").expect("write other");
+        let added = commit_index(&repo, "add old");
+        fs::write(root.join("other.txt"), "other changed\n").expect("edit other");
+        commit_index(&repo, "touch other");
+        fs::rename(root.join("old.txt"), root.join("new.txt")).expect("rename");
+        let renamed = commit_index(&repo, "rename to new");
+        fs::write(root.join("new.txt"), format!("{body}line five\n")).expect("edit new");
+        let edited = commit_index(&repo, "edit new");
+        fs::remove_file(root.join("new.txt")).expect("delete new");
+        let deleted = commit_index(&repo, "delete new");
+
+        let history = file_history(&root, "new.txt", 0, 10, true).expect("history");
+        let summary: Vec<(String, &str, &str)> = history
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.commit.sha.clone(),
+                    entry.status.as_str(),
+                    entry.path.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (deleted.to_string(), "D", "new.txt"),
+                (edited.to_string(), "M", "new.txt"),
+                (renamed.to_string(), "R", "new.txt"),
+                (added.to_string(), "A", "old.txt"),
+            ]
+        );
+        assert_eq!(history.entries[2].old_path.as_deref(), Some("old.txt"));
+        assert_eq!(history.total, 4);
+        assert!(!history.has_more);
+
+        let unfollowed = file_history(&root, "new.txt", 0, 10, false).expect("unfollowed");
+        assert_eq!(unfollowed.total, 3);
+        assert_eq!(unfollowed.entries[2].status, "A");
+
+        let page = file_history(&root, "new.txt", 1, 2, true).expect("page");
+        assert_eq!(page.entries.len(), 2);
+        assert_eq!(page.entries[0].commit.sha, edited.to_string());
+        assert!(page.has_more);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,499 @@
+use super::*;
+
+use serde::Serialize;
+
+/// Returned when the selected hunk is missing from a fresh diff or no longer
+/// applies to the index; the caller should reload the hunks.
+pub(crate) const GIT_HUNK_STALE_ERROR: &str =
+    "GIT_HUNK_STALE: hunk no longer applies, refresh the diff and try again";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitFileHunk {
+    /// Derived from the file and the hunk's lines, so it survives line
+    /// number shifts caused by staging other hunks of the same file.
+    pub(crate) id: String,
+    pub(crate) path: String,
+    pub(crate) header: String,
+    pub(crate) old_start: u32,
+    pub(crate) old_lines: u32,
+    pub(crate) new_start: u32,
+    pub(crate) new_lines: u32,
+    pub(crate) lines: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitFileHunks {
+    pub(crate) path: String,
+    pub(crate) staged: Vec<GitFileHunk>,
+    pub(crate) unstaged: Vec<GitFileHunk>,
+}
+
+/// One `diff --git` section of the raw diff, with hunk lines kept verbatim
+/// (including `\r`) so a synthesized patch matches the file byte for byte.
+#[derive(Debug, Clone)]
+struct DiffSection {
+    path: String,
+    header: Vec<String>,
+    renamed_to: Option<String>,
+    hunks: Vec<(GitFileHunk, Vec<String>)>,
+}
+
+fn parse_hunk_range(range: &str) -> Option<(u32, u32)> {
+    let mut parts = range.splitn(2, ',');
+    let start = parts.next()?.parse::<u32>().ok()?;
+    let count = match parts.next() {
+        Some(count) => count.parse::<u32>().ok()?,
+        None => 1,
+    };
+    Some((start, count))
+}
+
+fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let (old_start, old_lines) = parse_hunk_range(old)?;
+    let (new_start, new_lines) = parse_hunk_range(new)?;
+    Some((old_start, old_lines, new_start, new_lines))
+}
+
+/// FNV-1a, so identifiers do not depend on the std hasher's seed or version.
+fn hunk_id(path: &str, body: &[String]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in path
+        .bytes()
+        .chain(std::iter::once(0))
+        .chain(body.iter().flat_map(|line| line.bytes()))
+    {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+fn strip_diff_path(raw: &str, prefix: &str) -> Option<String> {
+    let raw = raw.trim_end_matches(['\r', '\n']);
+    if raw == "/dev/null" {
+        return None;
+    }
+    Some(normalize_git_path(raw.strip_prefix(prefix).unwrap_or(raw)))
+}
+
+fn finish_section(section: Option<DiffSection>, sections: &mut Vec<DiffSection>) {
+    let Some(mut section) = section else {
+        return;
+    };
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (hunk, body) in section.hunks.iter_mut() {
+        let base = hunk_id(&section.path, body);
+        let occurrence = seen.entry(base.clone()).or_insert(0);
+        hunk.id = if *occurrence == 0 {
+            base
+        } else {
+            format!("{base}-{occurrence}")
+        };
+        *occurrence += 1;
+        hunk.path = section.path.clone();
+    }
+    sections.push(section);
+}
+
+fn parse_diff_sections(diff: &str) -> Vec<DiffSection> {
+    let mut sections = Vec::new();
+    let mut current: Option<DiffSection> = None;
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("diff --git ") {
+            finish_section(current.take(), &mut sections);
+            current = Some(DiffSection {
+                path: String::new(),
+                header: vec![line.to_string()],
+                renamed_to: None,
+                hunks: Vec::new(),
+            });
+            continue;
+        }
+        let Some(section) = current.as_mut() else {
+            continue;
+        };
+        if line.starts_with("@@ ") {
+            let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(line) else {
+                continue;
+            };
+            section.hunks.push((
+                GitFileHunk {
+                    id: String::new(),
+                    path: String::new(),
+                    header: line.trim_end_matches(['\r', '\n']).to_string(),
+                    old_start,
+                    old_lines,
+                    new_start,
+                    new_lines,
+                    lines: Vec::new(),
+                },
+                Vec::new(),
+            ));
+            continue;
+        }
+        if let Some((hunk, body)) = section.hunks.last_mut() {
+            if matches!(line.as_bytes().first(), Some(b' ' | b'+' | b'-' | b'\\')) {
+                hunk.lines
+                    .push(line.trim_end_matches(['\r', '\n']).to_string());
+                body.push(line.to_string());
+            }
+            continue;
+        }
+        if let Some(path) = line.strip_prefix("+++ ") {
+            if let Some(path) = strip_diff_path(path, "b/") {
+                section.path = path;
+            }
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            if section.path.is_empty() {
+                section.path = strip_diff_path(path, "a/").unwrap_or_default();
+            }
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            section.renamed_to = Some(normalize_git_path(path.trim_end_matches(['\r', '\n'])));
+        }
+        section.header.push(line.to_string());
+    }
+    finish_section(current, &mut sections);
+    sections
+}
+
+/// Builds a patch holding only `hunk`. Renames are rewritten as an in-place
+/// edit of the new path, so applying one hunk never moves the file.
+fn build_hunk_patch(section: &DiffSection, body: &[String], header: &str) -> String {
+    let mut patch = String::new();
+    match section.renamed_to.as_deref() {
+        Some(path) => {
+            patch.push_str(&format!(
+                "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n"
+            ));
+        }
+        None => {
+            for line in &section.header {
+                patch.push_str(line);
+            }
+        }
+    }
+    patch.push_str(header);
+    patch.push('\n');
+    for line in body {
+        patch.push_str(line);
+    }
+    if !patch.ends_with('\n') {
+        patch.push('\n');
+    }
+    patch
+}
+
+fn find_hunk<'a>(
+    sections: &'a [DiffSection],
+    hunk_header: &str,
+) -> Option<(&'a DiffSection, &'a GitFileHunk, &'a [String])> {
+    let wanted = hunk_header.trim();
+    sections.iter().find_map(|section| {
+        section
+            .hunks
+            .iter()
+            .find(|(hunk, _)| hunk.id == wanted || hunk.header == wanted)
+            .map(|(hunk, body)| (section, hunk, body.as_slice()))
+    })
+}
+
+/// Runs a diff and keeps stdout untrimmed. `git diff --no-index` exits with 1
+/// when the inputs differ, which is the expected case here.
+async fn run_git_diff_raw(repo_root: &Path, args: &[&str]) -> Result<String, String> {
+    let git_bin = resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
+    let mut command = crate::utils::async_command(git_bin);
+    command
+        .args(["-c", "core.quotePath=false"])
+        .args(args)
+        .current_dir(repo_root)
+        .env("PATH", git_env_path())
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let output = match timeout(
+        Duration::from_secs(GIT_COMMAND_TIMEOUT_SECS),
+        command.output(),
+    )
+    .await
+    {
+        Ok(result) => result.map_err(|e| format!("Failed to run git: {e}"))?,
+        Err(_) => {
+            return Err(format!(
+                "Git command timed out after {GIT_COMMAND_TIMEOUT_SECS}s: git {}",
+                args.join(" ")
+            ))
+        }
+    };
+    let no_index = args.contains(&"--no-index");
+    if !(output.status.success() || (no_index && output.status.code() == Some(1))) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.trim().is_empty() {
+            return Err("Git command failed.".to_string());
+        }
+        return Err(stderr.trim().to_string());
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|_| "Hunk staging is not supported for files that are not UTF-8.".to_string())
+}
+
+fn is_untracked_path(repo_root: &Path, path: &str) -> bool {
+    open_repository_at_root(repo_root)
+        .ok()
+        .and_then(|repo| repo.status_file(Path::new(path)).ok())
+        .is_some_and(|status| status.contains(Status::WT_NEW))
+}
+
+async fn load_diff_sections(
+    repo_root: &Path,
+    paths: &[String],
+    staged: bool,
+) -> Result<Vec<DiffSection>, String> {
+    let mut args = vec![
+        "diff",
+        "--no-color",
+        "--no-ext-diff",
+        "--no-textconv",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        "-U3",
+        "-M",
+    ];
+    if staged {
+        args.push("--cached");
+    }
+    args.push("--");
+    args.extend(paths.iter().map(String::as_str));
+    let mut sections = parse_diff_sections(&run_git_diff_raw(repo_root, &args).await?);
+
+    if !staged {
+        for path in paths {
+            if sections.iter().any(|section| &section.path == path)
+                || !is_untracked_path(repo_root, path)
+            {
+                continue;
+            }
+            let diff = run_git_diff_raw(
+                repo_root,
+                &[
+                    "diff",
+                    "--no-color",
+                    "--no-ext-diff",
+                    "--no-textconv",
+                    "--src-prefix=a/",
+                    "--dst-prefix=b/",
+                    "-U3",
+                    "--no-index",
+                    "--",
+                    "/dev/null",
+                    path,
+                ],
+            )
+            .await?;
+            sections.extend(parse_diff_sections(&diff));
+        }
+    }
+    Ok(sections)
+}
+
+fn collect_hunks(sections: Vec<DiffSection>) -> Vec<GitFileHunk> {
+    sections
+        .into_iter()
+        .flat_map(|section| section.hunks.into_iter().map(|(hunk, _)| hunk))
+        .collect()
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_file_hunks(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<GitFileHunks, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let paths = action_paths_for_file(&repo_root, &path);
+    if paths.is_empty() {
+        return Err("path is required".to_string());
+    }
+    let staged = load_diff_sections(&repo_root, &paths, true).await?;
+    let unstaged = load_diff_sections(&repo_root, &paths, false).await?;
+    Ok(GitFileHunks {
+        path: normalize_git_path(&path),
+        staged: collect_hunks(staged),
+        unstaged: collect_hunks(unstaged),
+    })
+}
+
+async fn apply_hunk_to_index(
+    repo_root: &Path,
+    paths: &[String],
+    hunk_header: &str,
+    stage: bool,
+) -> Result<(), String> {
+    let sections = load_diff_sections(repo_root, paths, !stage).await?;
+    let Some((section, hunk, body)) = find_hunk(&sections, hunk_header) else {
+        return Err(GIT_HUNK_STALE_ERROR.to_string());
+    };
+    let patch = build_hunk_patch(section, body, &hunk.header);
+
+    let patch_path =
+        std::env::temp_dir().join(format!("mossx-hunk-{}.patch", uuid::Uuid::new_v4()));
+    fs::write(&patch_path, patch.as_bytes())
+        .map_err(|e| format!("Failed to write hunk patch: {e}"))?;
+    let patch_arg = patch_path.to_string_lossy().to_string();
+    let mut args = vec!["apply", "--cached", "--whitespace=nowarn"];
+    if !stage {
+        args.push("-R");
+    }
+    args.push(&patch_arg);
+
+    let mut check_args = args.clone();
+    check_args.insert(2, "--check");
+    let result = match run_git_command(repo_root, &check_args).await {
+        Ok(()) => run_git_command(repo_root, &args).await,
+        Err(_) => Err(GIT_HUNK_STALE_ERROR.to_string()),
+    };
+    let _ = fs::remove_file(&patch_path);
+    result
+}
+
+#[tauri::command]
+pub(crate) async fn stage_git_hunk(
+    workspace_id: String,
+    path: String,
+    hunk_header: String,
+    stage: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "stage_git_hunk").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let paths = action_paths_for_file(&repo_root, &path);
+    if paths.is_empty() {
+        return Err("path is required".to_string());
+    }
+    // Like `git add -p`, an untracked file needs an intent-to-add entry before
+    // one of its hunks can land in the index.
+    let intent_paths: Vec<String> = if stage {
+        paths
+            .iter()
+            .filter(|path| is_untracked_path(&repo_root, path))
+            .cloned()
+            .collect()
+    } else {
+        Vec::new()
+    };
+    for path in &intent_paths {
+        run_git_command(&repo_root, &["add", "-N", "--", path]).await?;
+    }
+
+    let result = apply_hunk_to_index(&repo_root, &paths, &hunk_header, stage).await;
+    if result.is_err() {
+        for path in &intent_paths {
+            let _ = run_git_command(&repo_root, &["reset", "-q", "--", path]).await;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RENAME_DIFF: &str = "diff --git a/f.txt b/g.txt\n\
+similarity index 84%\n\
+rename from f.txt\n\
+rename to g.txt\n\
+index 988f966..9ecff16 100644\n\
+--- a/f.txt\n\
++++ b/g.txt\n\
+@@ -1,4 +1,4 @@\n\
+-a\n\
++A\n \
+b\n \
+c\n \
+d\n\
+@@ -10,4 +10,4 @@ i\n \
+j\n \
+k\n \
+l\n\
+-m\n\
++M\n";
+
+    #[test]
+    fn parses_hunks_with_stable_ids() {
+        let sections = parse_diff_sections(RENAME_DIFF);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].path, "g.txt");
+        assert_eq!(sections[0].renamed_to.as_deref(), Some("g.txt"));
+        let hunks = collect_hunks(sections.clone());
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[1].header, "@@ -10,4 +10,4 @@ i");
+        assert_eq!(
+            (
+                hunks[1].old_start,
+                hunks[1].old_lines,
+                hunks[1].new_start,
+                hunks[1].new_lines
+            ),
+            (10, 4, 10, 4)
+        );
+        assert_eq!(hunks[1].lines, vec![" j", " k", " l", "-m", "+M"]);
+
+        // Shifting line numbers must not change the identifier.
+        let shifted = RENAME_DIFF.replace("@@ -10,4 +10,4 @@ i", "@@ -12,4 +12,4 @@ i");
+        let shifted_hunks = collect_hunks(parse_diff_sections(&shifted));
+        assert_eq!(shifted_hunks[1].id, hunks[1].id);
+        assert_ne!(hunks[0].id, hunks[1].id);
+    }
+
+    #[test]
+    fn rename_patch_edits_new_path_in_place() {
+        let sections = parse_diff_sections(RENAME_DIFF);
+        let (section, hunk, body) =
+            find_hunk(&sections, "@@ -10,4 +10,4 @@ i").expect("hunk by header");
+        let patch = build_hunk_patch(section, body, &hunk.header);
+        assert_eq!(
+            patch,
+            "diff --git a/g.txt b/g.txt\n--- a/g.txt\n+++ b/g.txt\n\
+@@ -10,4 +10,4 @@ i\n j\n k\n l\n-m\n+M\n"
+        );
+        assert!(find_hunk(&sections, &hunk.id).is_some());
+        assert!(find_hunk(&sections, "@@ -99,1 +99,1 @@").is_none());
+    }
+
+    #[test]
+    fn keeps_crlf_bytes_in_patch() {
+        let diff = "diff --git a/c.txt b/c.txt\n\
+index 1..2 100644\n\
+--- a/c.txt\n\
++++ b/c.txt\n\
+@@ -1,3 +1,3 @@\n x\r\n-y\r\n+Y\r\n z\r\n";
+        let sections = parse_diff_sections(diff);
+        let (section, hunk, body) = find_hunk(&sections, "@@ -1,3 +1,3 @@").expect("hunk");
+        assert_eq!(hunk.lines, vec![" x", "-y", "+Y", " z"]);
+        let patch = build_hunk_patch(section, body, &hunk.header);
+        assert!(patch.ends_with("@@ -1,3 +1,3 @@\n x\r\n-y\r\n+Y\r\n z\r\n"));
+        assert!(patch.starts_with("diff --git a/c.txt b/c.txt\nindex 1..2 100644\n"));
+    }
+}
@@ -0,0 +1,270 @@
+use super::*;
+
+use git2::ConfigLevel;
+use serde::Serialize;
+
+/// Prefix of the error `commit_git` returns when no author identity is
+/// configured; the frontend opens the identity dialog on it.
+pub(crate) const GIT_IDENTITY_MISSING_ERROR_PREFIX: &str = "GIT_IDENTITY_MISSING:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum GitConfigScope {
+    System,
+    Global,
+    Local,
+}
+
+impl GitConfigScope {
+    fn from_level(level: ConfigLevel) -> Self {
+        match level {
+            ConfigLevel::ProgramData | ConfigLevel::System => Self::System,
+            ConfigLevel::XDG | ConfigLevel::Global => Self::Global,
+            _ => Self::Local,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitIdentityValue {
+    pub(crate) value: String,
+    /// The config file the effective value comes from.
+    pub(crate) scope: GitConfigScope,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitIdentity {
+    pub(crate) name: Option<GitIdentityValue>,
+    pub(crate) email: Option<GitIdentityValue>,
+}
+
+fn identity_value(config: &git2::Config, key: &str) -> Option<GitIdentityValue> {
+    let entry = config.get_entry(key).ok()?;
+    let value = entry.value()?.trim().to_string();
+    if value.is_empty() {
+        return None;
+    }
+    Some(GitIdentityValue {
+        value,
+        scope: GitConfigScope::from_level(entry.level()),
+    })
+}
+
+fn identity_from_config(config: &git2::Config) -> GitIdentity {
+    GitIdentity {
+        name: identity_value(config, "user.name"),
+        email: identity_value(config, "user.email"),
+    }
+}
+
+fn read_identity(repo_root: &Path) -> Result<GitIdentity, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let config = repo
+        .config()
+        .and_then(|mut config| config.snapshot())
+        .map_err(|e| e.to_string())?;
+    Ok(identity_from_config(&config))
+}
+
+/// Config keys git will ask for, taking the author/committer environment
+/// variables git also honours into account.
+fn missing_identity_keys(
+    identity: &GitIdentity,
+    env: impl Fn(&str) -> Option<String>,
+) -> Vec<&'static str> {
+    let from_env = |keys: &[&str]| {
+        keys.iter()
+            .all(|key| env(key).is_some_and(|value| !value.trim().is_empty()))
+    };
+    let mut missing = Vec::new();
+    if identity.name.is_none() && !from_env(&["GIT_AUTHOR_NAME", "GIT_COMMITTER_NAME"]) {
+        missing.push("user.name");
+    }
+    if identity.email.is_none()
+        && !from_env(&["GIT_AUTHOR_EMAIL", "GIT_COMMITTER_EMAIL"])
+        && !from_env(&["EMAIL"])
+    {
+        missing.push("user.email");
+    }
+    missing
+}
+
+/// Fails with [`GIT_IDENTITY_MISSING_ERROR_PREFIX`] instead of letting git
+/// stop with "Please tell me who you are".
+pub(super) fn ensure_git_identity(repo_root: &Path) -> Result<(), String> {
+    let identity = read_identity(repo_root)?;
+    let missing = missing_identity_keys(&identity, |key| std::env::var(key).ok());
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "{GIT_IDENTITY_MISSING_ERROR_PREFIX} set {} before committing.",
+        missing.join(" and ")
+    ))
+}
+
+fn validate_identity(name: &str, email: &str) -> Result<(String, String), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Name cannot be empty.".to_string());
+    }
+    let email = email.trim();
+    if email.is_empty() || !email.contains('@') {
+        return Err("Enter a valid email address.".to_string());
+    }
+    Ok((name.to_string(), email.to_string()))
+}
+
+/// The global config file, created when the user has none yet.
+fn global_config() -> Result<git2::Config, String> {
+    let path = match git2::Config::find_global() {
+        Ok(path) => path,
+        Err(_) => {
+            let home = dirs::home_dir().ok_or("Unable to resolve home directory")?;
+            let path = home.join(".gitconfig");
+            if !path.exists() {
+                fs::write(&path, "").map_err(|e| e.to_string())?;
+            }
+            path
+        }
+    };
+    git2::Config::open(&path).map_err(|e| e.to_string())
+}
+
+fn write_identity(mut config: git2::Config, name: &str, email: &str) -> Result<(), String> {
+    config
+        .set_str("user.name", name)
+        .and_then(|_| config.set_str("user.email", email))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_identity(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitIdentity, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    read_identity(&repo_root)
+}
+
+/// Writes user.name and user.email to the repository (`local`) or the
+/// user's (`global`) config and returns the resulting effective identity.
+#[tauri::command]
+pub(crate) async fn set_git_identity(
+    workspace_id: String,
+    name: String,
+    email: String,
+    scope: GitConfigScope,
+    state: State<'_, AppState>,
+) -> Result<GitIdentity, String> {
+    let (name, email) = validate_identity(&name, &email)?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let config = match scope {
+        GitConfigScope::Local => {
+            ensure_workspace_id_writable(&state.workspaces, &workspace_id, "set_git_identity")
+                .await?;
+            open_repository_at_root(&repo_root)?
+                .config()
+                .and_then(|config| config.open_level(ConfigLevel::Local))
+                .map_err(|e| e.to_string())?
+        }
+        GitConfigScope::Global => global_config()?,
+        GitConfigScope::System => {
+            return Err("Identity can only be set in the local or global config.".to_string())
+        }
+    };
+    write_identity(config, &name, &email)?;
+    read_identity(&repo_root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_effective_identity_and_what_is_missing() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create config dir");
+        let global_path = root.join("global");
+        let local_path = root.join("local");
+        fs::write(
+            &global_path,
+            "[user]\n\tname = Global Name\n\temail = global@example.com\n",
+        )
+        .expect("write global");
+        fs::write(&local_path, "[user]\n\tname = Local Name\n").expect("write local");
+
+        let mut config = git2::Config::new().expect("config");
+        config
+            .add_file(&global_path, ConfigLevel::Global, false)
+            .expect("add global");
+        config
+            .add_file(&local_path, ConfigLevel::Local, false)
+            .expect("add local");
+        let identity = identity_from_config(&config);
+        assert_eq!(
+            identity.name,
+            Some(GitIdentityValue {
+                value: "Local Name".to_string(),
+                scope: GitConfigScope::Local,
+            })
+        );
+        assert_eq!(
+            identity.email.as_ref().map(|email| email.scope),
+            Some(GitConfigScope::Global)
+        );
+        assert!(missing_identity_keys(&identity, |_| None).is_empty());
+
+        let empty = GitIdentity {
+            name: None,
+            email: None,
+        };
+        assert_eq!(
+            missing_identity_keys(&empty, |_| None),
+            vec!["user.name", "user.email"]
+        );
+        assert_eq!(
+            missing_identity_keys(&empty, |key| (key == "EMAIL")
+                .then(|| "env@example.com".to_string())),
+            vec!["user.name"]
+        );
+
+        let mut local = git2::Config::open(&local_path).expect("open local");
+        local.set_str("user.email", "x").expect("seed email");
+        write_identity(local, "New Name", "new@example.com").expect("write identity");
+        let reread = git2::Config::open(&local_path).expect("reopen local");
+        assert_eq!(
+            identity_from_config(&reread).email.map(|email| email.value),
+            Some("new@example.com".to_string())
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn validates_identity_loosely() {
+        assert_eq!(
+            validate_identity("  Ada  ", " ada@example.com "),
+            Ok(("Ada".to_string(), "ada@example.com".to_string()))
+        );
+        assert!(validate_identity(" ", "ada@example.com").is_err());
+        assert!(validate_identity("Ada", "").is_err());
+        assert!(validate_identity("Ada", "not-an-email").is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use super::*;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum GitOperationKind {
+    Rebase,
+    Merge,
+    CherryPick,
+}
+
+impl GitOperationKind {
+    fn subcommand(self) -> &'static str {
+        match self {
+            Self::Rebase => "rebase",
+            Self::Merge => "merge",
+            Self::CherryPick => "cherry-pick",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitOperationStop {
+    pub(crate) sha: String,
+    pub(crate) short_sha: String,
+    pub(crate) summary: String,
+}
+
+/// What an interrupted rebase, merge or cherry-pick is waiting on. `kind` is
+/// `None` when the repository is not in the middle of one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitOperationState {
+    pub(crate) kind: Option<GitOperationKind>,
+    /// The commit being replayed (rebase, cherry-pick) or merged in.
+    pub(crate) stopped_at: Option<GitOperationStop>,
+    /// Branch being rebased.
+    pub(crate) branch: Option<String>,
+    /// Commit a rebase replays onto.
+    pub(crate) onto: Option<String>,
+    pub(crate) step: Option<u32>,
+    pub(crate) total_steps: Option<u32>,
+    pub(crate) conflicted_files: Vec<String>,
+    pub(crate) can_continue: bool,
+    pub(crate) can_skip: bool,
+}
+
+fn read_state_file(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let value = content.lines().next()?.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+fn read_state_number(path: &Path) -> Option<u32> {
+    read_state_file(path)?.parse().ok()
+}
+
+fn operation_stop(repo: &Repository, sha: Option<String>) -> Option<GitOperationStop> {
+    let sha = sha?;
+    let summary = Oid::from_str(&sha)
+        .ok()
+        .and_then(|oid| repo.find_commit(oid).ok())
+        .and_then(|commit| commit.summary().map(str::to_string))
+        .unwrap_or_default();
+    Some(GitOperationStop {
+        short_sha: sha.chars().take(7).collect(),
+        sha,
+        summary,
+    })
+}
+
+fn conflicted_paths(repo: &Repository) -> Result<Vec<String>, String> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(false).include_ignored(false);
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    let mut paths: Vec<String> = statuses
+        .iter()
+        .filter(|entry| entry.status().contains(Status::CONFLICTED))
+        .filter_map(|entry| entry.path().map(normalize_git_path))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Reads the sequencer state files git leaves in the git dir (the per-worktree
+/// one for linked worktrees). `git am` sessions, which also use
+/// `rebase-apply`, are not reported.
+fn detect_git_operation(repo: &Repository) -> Result<GitOperationState, String> {
+    let git_dir = repo.path();
+    let rebase_merge = git_dir.join("rebase-merge");
+    let rebase_apply = git_dir.join("rebase-apply");
+    let mut state = GitOperationState::default();
+
+    if rebase_merge.is_dir() || (rebase_apply.is_dir() && !rebase_apply.join("applying").exists()) {
+        let (dir, step_file, total_file, stop_file) = if rebase_merge.is_dir() {
+            (&rebase_merge, "msgnum", "end", "stopped-sha")
+        } else {
+            (&rebase_apply, "next", "last", "original-commit")
+        };
+        state.kind = Some(GitOperationKind::Rebase);
+        state.branch = read_state_file(&dir.join("head-name"))
+            .filter(|name| name != "detached HEAD")
+            .map(|name| {
+                name.strip_prefix("refs/heads/")
+                    .map(str::to_string)
+                    .unwrap_or(name)
+            });
+        state.onto = read_state_file(&dir.join("onto"));
+        state.step = read_state_number(&dir.join(step_file));
+        state.total_steps = read_state_number(&dir.join(total_file));
+        let stopped = read_state_file(&git_dir.join("REBASE_HEAD"))
+            .or_else(|| read_state_file(&dir.join(stop_file)));
+        state.stopped_at = operation_stop(repo, stopped);
+    } else if let Some(sha) = read_state_file(&git_dir.join("CHERRY_PICK_HEAD")) {
+        state.kind = Some(GitOperationKind::CherryPick);
+        state.stopped_at = operation_stop(repo, Some(sha));
+    } else if let Some(sha) = read_state_file(&git_dir.join("MERGE_HEAD")) {
+        state.kind = Some(GitOperationKind::Merge);
+        state.stopped_at = operation_stop(repo, Some(sha));
+    }
+
+    if let Some(kind) = state.kind {
+        state.conflicted_files = conflicted_paths(repo)?;
+        state.can_continue = state.conflicted_files.is_empty();
+        state.can_skip = kind != GitOperationKind::Merge;
+    }
+    Ok(state)
+}
+
+async fn workspace_repo_root(
+    state: &State<'_, AppState>,
+    workspace_id: &str,
+    operation: &str,
+) -> Result<PathBuf, String> {
+    ensure_workspace_id_writable(&state.workspaces, workspace_id, operation).await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    resolve_git_root(&entry)
+}
+
+fn require_operation(state: &GitOperationState) -> Result<GitOperationKind, String> {
+    state
+        .kind
+        .ok_or_else(|| "No rebase, merge or cherry-pick is in progress.".to_string())
+}
+
+/// Runs `git <kind> --<action>` with the editor disabled so continuing keeps
+/// the prepared commit message instead of waiting on an invisible editor.
+async fn run_operation_action(
+    repo_root: &Path,
+    kind: GitOperationKind,
+    action: &str,
+) -> Result<GitOperationState, String> {
+    let args = vec![kind.subcommand().to_string(), format!("--{action}")];
+    let output =
+        run_token_isolated_command(repo_root, "git", &args, &[("GIT_EDITOR", "true")]).await?;
+    if !output.success {
+        return Err(summarize_command_failure(&output));
+    }
+    let repo = open_repository_at_root(repo_root)?;
+    detect_git_operation(&repo)
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_operation_state(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitOperationState, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = open_repository_at_root(&repo_root)?;
+    detect_git_operation(&repo)
+}
+
+#[tauri::command]
+pub(crate) async fn continue_git_operation(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitOperationState, String> {
+    let repo_root = workspace_repo_root(&state, &workspace_id, "continue_git_operation").await?;
+    let current = detect_git_operation(&open_repository_at_root(&repo_root)?)?;
+    let kind = require_operation(&current)?;
+    if !current.conflicted_files.is_empty() {
+        return Err(format!(
+            "{} file(s) still have conflicts: {}. Resolve and stage them before continuing.",
+            current.conflicted_files.len(),
+            current.conflicted_files.join(", ")
+        ));
+    }
+    run_operation_action(&repo_root, kind, "continue").await
+}
+
+#[tauri::command]
+pub(crate) async fn abort_git_operation(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitOperationState, String> {
+    let repo_root = workspace_repo_root(&state, &workspace_id, "abort_git_operation").await?;
+    let current = detect_git_operation(&open_repository_at_root(&repo_root)?)?;
+    let kind = require_operation(&current)?;
+    run_operation_action(&repo_root, kind, "abort").await
+}
+
+#[tauri::command]
+pub(crate) async fn skip_git_operation(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitOperationState, String> {
+    let repo_root = workspace_repo_root(&state, &workspace_id, "skip_git_operation").await?;
+    let current = detect_git_operation(&open_repository_at_root(&repo_root)?)?;
+    let kind = require_operation(&current)?;
+    if !current.can_skip {
+        return Err("A merge cannot be skipped; abort it instead.".to_string());
+    }
+    run_operation_action(&repo_root, kind, "skip").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn git(repo_root: &Path, args: &[&str]) -> Result<(), String> {
+        let mut full_args = vec![
+            "-c",
+            "user.name=TestUser",
+            "-c",
+            "user.email=test@example.com",
+        ];
+        full_args.extend_from_slice(args);
+        run_git_command(repo_root, &full_args).await
+    }
+
+    #[tokio::test]
+    async fn reports_and_aborts_conflicted_rebase() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create repo dir");
+        git(&root, &["init", "-q", "-b", "main"])
+            .await
+            .expect("init");
+        fs::write(root.join("a.txt"), "base\n").expect("write base");
+        git(&root, &["add", "-A"]).await.expect("add");
+        git(&root, &["commit", "-qm", "base"])
+            .await
+            .expect("commit base");
+        git(&root, &["checkout", "-qb", "feature"])
+            .await
+            .expect("branch");
+        fs::write(root.join("a.txt"), "feature\n").expect("write feature");
+        git(&root, &["commit", "-qam", "feature change"])
+            .await
+            .expect("commit feature");
+        git(&root, &["checkout", "-q", "main"])
+            .await
+            .expect("checkout main");
+        fs::write(root.join("a.txt"), "main\n").expect("write main");
+        git(&root, &["commit", "-qam", "main change"])
+            .await
+            .expect("commit main");
+        git(&root, &["checkout", "-q", "feature"])
+            .await
+            .expect("checkout feature");
+
+        let repo = open_repository_at_root(&root).expect("open repo");
+        assert_eq!(detect_git_operation(&repo).expect("idle state").kind, None);
+        assert!(git(&root, &["rebase", "main"]).await.is_err());
+
+        let state = detect_git_operation(&repo).expect("rebase state");
+        assert_eq!(state.kind, Some(GitOperationKind::Rebase));
+        assert_eq!(state.branch.as_deref(), Some("feature"));
+        assert_eq!(state.conflicted_files, vec!["a.txt".to_string()]);
+        assert!(!state.can_continue);
+        assert!(state.can_skip);
+        assert_eq!(
+            state.stopped_at.map(|stop| stop.summary),
+            Some("feature change".to_string())
+        );
+
+        let after = run_operation_action(&root, GitOperationKind::Rebase, "abort")
+            .await
+            .expect("abort rebase");
+        assert_eq!(after, GitOperationState::default());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
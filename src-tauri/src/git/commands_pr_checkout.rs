@@ -0,0 +1,119 @@
+use super::*;
+
+/// Turns a failed `gh pr checkout` into a message that says what to do next.
+fn pr_checkout_failure_message(pr_number: u64, repo_name: &str, raw: &str) -> String {
+    let normalized = raw.to_lowercase();
+    if is_auth_related_error(raw) {
+        return format!(
+            "GitHub CLI is not authenticated. Run `env -u GH_TOKEN -u GITHUB_TOKEN gh auth status -h github.com` and finish login. ({raw})"
+        );
+    }
+    if normalized.contains("could not resolve to a pullrequest")
+        || normalized.contains("no pull requests found")
+    {
+        return format!("Pull request #{pr_number} was not found in {repo_name}.");
+    }
+    // Fork PRs are fetched from the fork itself; these mean the fork or its
+    // head branch is gone or not readable with the current credentials.
+    if normalized.contains("couldn't find remote ref")
+        || normalized.contains("could not read from remote repository")
+        || normalized.contains("repository not found")
+        || normalized.contains("could not find remote")
+    {
+        return format!(
+            "Could not fetch the head branch of PR #{pr_number}. The fork may have been deleted, made private, or its branch removed; add the fork as a remote (`git remote add <owner> <url>`) and retry. ({raw})"
+        );
+    }
+    raw.to_string()
+}
+
+/// Checks out a pull request's head with `gh pr checkout`. Returns the local
+/// branch now checked out, or `None` when `detach` left HEAD detached.
+#[tauri::command]
+pub(crate) async fn checkout_github_pull_request(
+    workspace_id: String,
+    pr_number: u64,
+    detach: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    ensure_workspace_id_writable(
+        &state.workspaces,
+        &workspace_id,
+        "checkout_github_pull_request",
+    )
+    .await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    {
+        let repo = open_repository_at_root(&repo_root)?;
+        ensure_checkout_precondition_clean(&repo)?;
+    }
+
+    let mut args = vec![
+        "pr".to_string(),
+        "checkout".to_string(),
+        pr_number.to_string(),
+        "--repo".to_string(),
+        repo_name.clone(),
+    ];
+    if detach.unwrap_or(false) {
+        args.push("--detach".to_string());
+    }
+    let output = run_token_isolated_command(&repo_root, "gh", &args, &[])
+        .await
+        .map_err(|error| format!("{error} Install GitHub CLI and run `gh auth login` first."))?;
+    if !output.success {
+        return Err(pr_checkout_failure_message(
+            pr_number,
+            &repo_name,
+            &summarize_command_failure(&output),
+        ));
+    }
+
+    let repo = open_repository_at_root(&repo_root)?;
+    let head = repo.head().map_err(|e| e.to_string())?;
+    if !head.is_branch() {
+        return Ok(None);
+    }
+    Ok(head.shorthand().map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_pr_checkout_failures_to_actionable_messages() {
+        assert_eq!(
+            pr_checkout_failure_message(
+                7,
+                "octo/repo",
+                "GraphQL: Could not resolve to a PullRequest with the number of 7."
+            ),
+            "Pull request #7 was not found in octo/repo."
+        );
+        assert!(pr_checkout_failure_message(
+            7,
+            "octo/repo",
+            "fatal: couldn't find remote ref refs/heads/patch-1"
+        )
+        .starts_with("Could not fetch the head branch of PR #7."));
+        assert!(pr_checkout_failure_message(
+            7,
+            "octo/repo",
+            "To get started with GitHub CLI, please run: gh auth login\nnot logged into any GitHub hosts"
+        )
+        .starts_with("GitHub CLI is not authenticated."));
+        assert_eq!(
+            pr_checkout_failure_message(7, "octo/repo", "something else"),
+            "something else"
+        );
+    }
+}
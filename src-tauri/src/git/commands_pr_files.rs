@@ -0,0 +1,264 @@
+use super::*;
+
+/// Files whose patches `get_github_pull_request_diff` returns inline when no
+/// path is given; later files are listed without a patch.
+const PR_DIFF_MAX_FILES: usize = 300;
+/// Total patch bytes `get_github_pull_request_diff` returns inline.
+const PR_DIFF_MAX_BYTES: usize = 4 * 1024 * 1024;
+/// Largest patch `get_github_pull_request_files` includes per file.
+const PR_FILE_PATCH_MAX_BYTES: usize = 64 * 1024;
+const PR_FILES_DEFAULT_PER_PAGE: u32 = 100;
+/// GitHub lists at most this many files per pull request.
+const PR_FILES_MAX_TOTAL: u32 = 3000;
+
+/// Keeps the whole-PR diff within the IPC budget: past the file or byte cap
+/// entries keep their path and status but lose the patch.
+pub(super) fn cap_pull_request_diffs(
+    mut diffs: Vec<GitHubPullRequestDiff>,
+) -> Vec<GitHubPullRequestDiff> {
+    let mut total_bytes = 0usize;
+    for (index, entry) in diffs.iter_mut().enumerate() {
+        total_bytes = total_bytes.saturating_add(entry.diff.len());
+        if index >= PR_DIFF_MAX_FILES || total_bytes > PR_DIFF_MAX_BYTES {
+            entry.diff.clear();
+            entry.truncated = true;
+        }
+    }
+    diffs
+}
+
+fn pull_request_file_status(status: &str) -> &'static str {
+    match status {
+        "added" => "A",
+        "removed" => "D",
+        "renamed" => "R",
+        _ => "M",
+    }
+}
+
+/// Rebuilds the `diff --git` header GitHub strips from per-file patches so
+/// the entry goes through the same parser as `gh pr diff` output.
+fn pull_request_file_patch_text(file: &RestPullRequestFile, patch: &str) -> String {
+    let path = file.filename.as_str();
+    let old_path = file.previous_filename.as_deref().unwrap_or(path);
+    let header = match file.status.as_str() {
+        "added" => format!("diff --git a/{path} b/{path}\nnew file mode 100644\n--- /dev/null\n+++ b/{path}"),
+        "removed" => format!("diff --git a/{path} b/{path}\ndeleted file mode 100644\n--- a/{path}\n+++ /dev/null"),
+        "renamed" => format!("diff --git a/{old_path} b/{path}\nrename from {old_path}\nrename to {path}\n--- a/{old_path}\n+++ b/{path}"),
+        _ => format!("diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}"),
+    };
+    format!("{header}\n{patch}")
+}
+
+fn pull_request_file_diff(file: &RestPullRequestFile) -> GitHubPullRequestDiff {
+    let Some(patch) = file.patch.as_deref() else {
+        // GitHub omits patches for binary and oversized files.
+        return GitHubPullRequestDiff {
+            path: normalize_git_path(&file.filename),
+            status: pull_request_file_status(&file.status).to_string(),
+            diff: String::new(),
+            truncated: true,
+        };
+    };
+    let text = pull_request_file_patch_text(file, patch);
+    parse_patch_diff_entries(&text)
+        .into_iter()
+        .next()
+        .map(|entry| GitHubPullRequestDiff {
+            path: entry.path,
+            status: entry.status,
+            diff: entry.diff,
+            truncated: false,
+        })
+        .unwrap_or_else(|| GitHubPullRequestDiff {
+            path: normalize_git_path(&file.filename),
+            status: pull_request_file_status(&file.status).to_string(),
+            diff: text,
+            truncated: false,
+        })
+}
+
+fn pull_request_files_from_rest(items: Vec<RestPullRequestFile>) -> Vec<GitHubPullRequestFile> {
+    items
+        .into_iter()
+        .map(|file| GitHubPullRequestFile {
+            status: pull_request_file_status(&file.status).to_string(),
+            path: normalize_git_path(&file.filename),
+            previous_path: file.previous_filename.as_deref().map(normalize_git_path),
+            additions: file.additions,
+            deletions: file.deletions,
+            patch: file
+                .patch
+                .filter(|patch| patch.len() <= PR_FILE_PATCH_MAX_BYTES),
+        })
+        .collect()
+}
+
+async fn fetch_pull_request_files(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+    page: u32,
+    per_page: u32,
+) -> Result<Vec<RestPullRequestFile>, String> {
+    let endpoint =
+        format!("repos/{repo_name}/pulls/{pr_number}/files?page={page}&per_page={per_page}");
+    let gh = run_gh(repo_root, &["api", &endpoint])
+        .await
+        .and_then(|stdout| {
+            serde_json::from_slice(&stdout)
+                .map_err(|e| GhError::Failed(format!("Unexpected gh api output: {e}")))
+        });
+    gh_or_rest(
+        gh,
+        rest_pull_request_files(repo_name, pr_number, page, per_page),
+    )
+    .await
+}
+
+/// The patch of a single file in the PR, found by walking the file list.
+pub(super) async fn pull_request_file_diff_for_path(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+    path: &str,
+) -> Result<GitHubPullRequestDiff, String> {
+    let target = normalize_git_path(path.trim());
+    for page in 1..=PR_FILES_MAX_TOTAL / PR_FILES_DEFAULT_PER_PAGE {
+        let files = fetch_pull_request_files(
+            repo_root,
+            repo_name,
+            pr_number,
+            page,
+            PR_FILES_DEFAULT_PER_PAGE,
+        )
+        .await?;
+        let exhausted = files.len() < PR_FILES_DEFAULT_PER_PAGE as usize;
+        if let Some(file) = files.iter().find(|file| {
+            normalize_git_path(&file.filename) == target
+                || file
+                    .previous_filename
+                    .as_deref()
+                    .is_some_and(|previous| normalize_git_path(previous) == target)
+        }) {
+            return Ok(pull_request_file_diff(file));
+        }
+        if exhausted {
+            break;
+        }
+    }
+    Err(format!("{target} is not changed in PR #{pr_number}."))
+}
+
+/// One page of the PR's changed files with stats, and patches small enough
+/// to send inline.
+#[tauri::command]
+pub(crate) async fn get_github_pull_request_files(
+    workspace_id: String,
+    pr_number: u64,
+    page: Option<u32>,
+    per_page: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequestFilesResponse, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let page = page.unwrap_or(1).max(1);
+    let per_page = per_page.unwrap_or(PR_FILES_DEFAULT_PER_PAGE).clamp(1, 100);
+
+    let items = fetch_pull_request_files(&repo_root, &repo_name, pr_number, page, per_page).await?;
+    let has_more = items.len() == per_page as usize
+        && u64::from(page) * u64::from(per_page) < u64::from(PR_FILES_MAX_TOTAL);
+    Ok(GitHubPullRequestFilesResponse {
+        page,
+        per_page,
+        has_more,
+        files: pull_request_files_from_rest(items),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rest_file(filename: &str, status: &str, patch: Option<&str>) -> RestPullRequestFile {
+        RestPullRequestFile {
+            filename: filename.to_string(),
+            status: status.to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: patch.map(str::to_string),
+            previous_filename: None,
+        }
+    }
+
+    #[test]
+    fn caps_whole_pull_request_diffs() {
+        let diffs = (0..(PR_DIFF_MAX_FILES + 2))
+            .map(|index| GitHubPullRequestDiff {
+                path: format!("src/file-{index}.rs"),
+                status: "M".to_string(),
+                diff: "@@ -1 +1 @@\n-a\n+b".to_string(),
+                truncated: false,
+            })
+            .collect::<Vec<_>>();
+        let capped = cap_pull_request_diffs(diffs);
+        assert_eq!(capped.len(), PR_DIFF_MAX_FILES + 2);
+        assert!(!capped[PR_DIFF_MAX_FILES - 1].truncated);
+        assert!(capped[PR_DIFF_MAX_FILES].truncated);
+        assert!(capped[PR_DIFF_MAX_FILES].diff.is_empty());
+        assert_eq!(
+            capped[PR_DIFF_MAX_FILES + 1].path,
+            format!("src/file-{}.rs", PR_DIFF_MAX_FILES + 1)
+        );
+
+        let huge = vec![GitHubPullRequestDiff {
+            path: "a.txt".to_string(),
+            status: "A".to_string(),
+            diff: "x".repeat(PR_DIFF_MAX_BYTES + 1),
+            truncated: false,
+        }];
+        assert!(cap_pull_request_diffs(huge)[0].truncated);
+    }
+
+    #[test]
+    fn parses_per_file_patches_like_pr_diff_output() {
+        let added = pull_request_file_diff(&rest_file(
+            "new.rs",
+            "added",
+            Some("@@ -0,0 +1 @@\n+fn a() {}"),
+        ));
+        assert_eq!(added.path, "new.rs");
+        assert_eq!(added.status, "A");
+        assert!(added.diff.contains("+fn a() {}"));
+        assert!(!added.truncated);
+
+        let mut renamed = rest_file("src/new name.rs", "renamed", Some("@@ -1 +1 @@\n-a\n+b"));
+        renamed.previous_filename = Some("src/old.rs".to_string());
+        let renamed = pull_request_file_diff(&renamed);
+        assert_eq!(renamed.status, "R");
+        assert!(renamed.diff.contains("rename from src/old.rs"));
+
+        let binary = pull_request_file_diff(&rest_file("logo.png", "modified", None));
+        assert!(binary.truncated);
+        assert!(binary.diff.is_empty());
+
+        let files = pull_request_files_from_rest(vec![
+            rest_file("small.rs", "modified", Some("@@ -1 +1 @@\n-a\n+b")),
+            rest_file(
+                "big.rs",
+                "removed",
+                Some(&"-x\n".repeat(PR_FILE_PATCH_MAX_BYTES)),
+            ),
+        ]);
+        assert!(files[0].patch.is_some());
+        assert_eq!(files[1].status, "D");
+        assert_eq!(files[1].patch, None);
+    }
+}
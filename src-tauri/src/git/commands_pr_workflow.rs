@@ -1,5 +1,136 @@
 use super::*;
 
+/// Draft state, labels and reviewers requested for a new PR.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct PrCreateMetadata {
+    pub(super) draft: bool,
+    pub(super) labels: Vec<String>,
+    pub(super) reviewers: Vec<String>,
+}
+
+impl PrCreateMetadata {
+    pub(super) fn new(
+        draft: Option<bool>,
+        labels: Option<Vec<String>>,
+        reviewers: Option<Vec<String>>,
+    ) -> Self {
+        Self {
+            draft: draft.unwrap_or(false),
+            labels: normalize_metadata_values(labels),
+            reviewers: normalize_metadata_values(reviewers),
+        }
+    }
+
+    fn has_assignments(&self) -> bool {
+        !self.labels.is_empty() || !self.reviewers.is_empty()
+    }
+
+    fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.labels.is_empty() {
+            parts.push(format!("labels: {}", self.labels.join(", ")));
+        }
+        if !self.reviewers.is_empty() {
+            parts.push(format!("reviewers: {}", self.reviewers.join(", ")));
+        }
+        parts.join("; ")
+    }
+}
+
+fn normalize_metadata_values(values: Option<Vec<String>>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for value in values.unwrap_or_default() {
+        let value = value.trim();
+        if !value.is_empty() && !normalized.iter().any(|existing| existing == value) {
+            normalized.push(value.to_string());
+        }
+    }
+    normalized
+}
+
+/// `gh` parses `--label`/`--reviewer` values as CSV, so a value containing
+/// a comma or quote has to be quoted to stay one entry.
+fn gh_list_flag_value(value: &str) -> String {
+    if value.contains(',') || value.contains('"') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// `gh pr create` arguments; labels and reviewers are only included when
+/// `with_assignments` is set so a failed assignment can be retried without.
+fn pr_create_args(
+    upstream_repo: &str,
+    base_branch: &str,
+    head_spec: &str,
+    title: &str,
+    body: &str,
+    metadata: &PrCreateMetadata,
+    with_assignments: bool,
+) -> Vec<String> {
+    let mut args = vec![
+        "pr".to_string(),
+        "create".to_string(),
+        "--repo".to_string(),
+        upstream_repo.to_string(),
+        "--base".to_string(),
+        base_branch.to_string(),
+        "--head".to_string(),
+        head_spec.to_string(),
+        "--title".to_string(),
+        title.to_string(),
+        "--body".to_string(),
+        body.to_string(),
+    ];
+    if metadata.draft {
+        args.push("--draft".to_string());
+    }
+    if with_assignments {
+        for label in &metadata.labels {
+            args.push("--label".to_string());
+            args.push(gh_list_flag_value(label));
+        }
+        for reviewer in &metadata.reviewers {
+            args.push("--reviewer".to_string());
+            args.push(gh_list_flag_value(reviewer));
+        }
+    }
+    args
+}
+
+/// Whether `gh pr create` failed only because a label or reviewer could not
+/// be resolved in the upstream repository.
+fn is_pr_assignment_error(raw: &str) -> bool {
+    let normalized = raw.to_lowercase();
+    normalized.contains("could not add label")
+        || normalized.contains("could not request reviewer")
+        || normalized.contains("could not resolve to a user")
+        || normalized.contains("could not resolve to a team")
+}
+
+/// Labels suggested by the branch prefix, e.g. `fix/...` -> `bug`.
+pub(super) fn infer_pr_labels(head_branch: &str) -> Vec<String> {
+    let Some((prefix, _)) = head_branch.trim().split_once('/') else {
+        return Vec::new();
+    };
+    let label = match prefix.to_ascii_lowercase().as_str() {
+        "fix" | "bugfix" | "hotfix" | "bug" => "bug",
+        "feat" | "feature" => "enhancement",
+        "docs" | "doc" => "documentation",
+        _ => return Vec::new(),
+    };
+    vec![label.to_string()]
+}
+
+/// Branches named `wip/...` or `draft/...` default to draft PRs.
+pub(super) fn infer_pr_draft(head_branch: &str) -> bool {
+    head_branch
+        .trim()
+        .split_once('/')
+        .is_some_and(|(prefix, _)| matches!(prefix.to_ascii_lowercase().as_str(), "wip" | "draft"))
+}
+
 pub(super) async fn create_git_pr_workflow_impl(
     workspace_id: String,
     upstream_repo: String,
@@ -10,6 +141,8 @@ pub(super) async fn create_git_pr_workflow_impl(
     body: Option<String>,
     comment_after_create: Option<bool>,
     comment_body: Option<String>,
+    metadata: PrCreateMetadata,
+    override_range_gate: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<GitPrWorkflowResult, String> {
     let workspaces = state.workspaces.lock().await;
@@ -236,7 +369,8 @@ pub(super) async fn create_git_pr_workflow_impl(
         .filter(|line| !line.is_empty())
         .map(ToOwned::to_owned)
         .collect::<Vec<_>>();
-    match evaluate_pr_range_gate(&changed_paths) {
+    let range_gate = PrRangeGateConfig::from_settings(&entry.settings);
+    match evaluate_pr_range_gate(&changed_paths, &range_gate) {
         PrRangeGateDecision::Pass { changed_files } => {
             update_workflow_stage(
                 &mut stages,
@@ -248,6 +382,24 @@ pub(super) async fn create_git_pr_workflow_impl(
                 Some(truncate_debug_text(&range_output.stderr, 1200)),
             );
         }
+        // An empty range has nothing to open a PR for, so it cannot be
+        // overridden.
+        PrRangeGateDecision::Blocked { category, reason }
+            if override_range_gate.unwrap_or(false) && category != "range-empty" =>
+        {
+            update_workflow_stage(
+                &mut stages,
+                "precheck",
+                "success",
+                format!(
+                    "Precheck passed with warning ({category}, overridden): {reason} Changed files: {}.",
+                    changed_paths.len()
+                ),
+                Some(range_output.command),
+                Some(truncate_debug_text(&range_output.stdout, 1600)),
+                Some(truncate_debug_text(&range_output.stderr, 1200)),
+            );
+        }
         PrRangeGateDecision::Blocked { category, reason } => {
             update_workflow_stage(
                 &mut stages,
@@ -264,7 +416,7 @@ pub(super) async fn create_git_pr_workflow_impl(
                 message: reason,
                 error_category: Some(category),
                 next_action_hint: Some(
-                    "Fix branch base/range first, then retry workflow to avoid oversized PR."
+                    "Fix branch base/range first, or retry with the range gate overridden if the range is intended."
                         .to_string(),
                 ),
                 pr_url: None,
@@ -505,6 +657,15 @@ pub(super) async fn create_git_pr_workflow_impl(
             Some(truncate_debug_text(&existing_pr_output.stdout, 1200)),
             Some(truncate_debug_text(&existing_pr_output.stderr, 600)),
         );
+        update_workflow_stage(
+            &mut stages,
+            "metadata",
+            "skipped",
+            "Skipped because workflow reused existing PR.".to_string(),
+            None,
+            None,
+            None,
+        );
         update_workflow_stage(
             &mut stages,
             "comment",
@@ -522,21 +683,16 @@ pub(super) async fn create_git_pr_workflow_impl(
     } else {
         body
     };
-    let create_pr_args = vec![
-        "pr".to_string(),
-        "create".to_string(),
-        "--repo".to_string(),
-        upstream_repo.clone(),
-        "--base".to_string(),
-        base_branch.clone(),
-        "--head".to_string(),
-        head_spec.clone(),
-        "--title".to_string(),
-        title.clone(),
-        "--body".to_string(),
-        body_value,
-    ];
-    let create_output =
+    let create_pr_args = pr_create_args(
+        &upstream_repo,
+        &base_branch,
+        &head_spec,
+        &title,
+        &body_value,
+        &metadata,
+        true,
+    );
+    let mut create_output =
         match run_token_isolated_command(&repo_root, "gh", &create_pr_args, &[]).await {
             Ok(output) => output,
             Err(error) => {
@@ -554,6 +710,43 @@ pub(super) async fn create_git_pr_workflow_impl(
                 ));
             }
         };
+    // Unknown labels or reviewers should not cost the user the PR: create it
+    // without them and report the assignment failure in the metadata stage.
+    let mut assignment_failure = None;
+    if !create_output.success
+        && metadata.has_assignments()
+        && is_pr_assignment_error(&summarize_command_failure(&create_output))
+    {
+        let retry_args = pr_create_args(
+            &upstream_repo,
+            &base_branch,
+            &head_spec,
+            &title,
+            &body_value,
+            &metadata,
+            false,
+        );
+        match run_token_isolated_command(&repo_root, "gh", &retry_args, &[]).await {
+            Ok(retry_output) => {
+                assignment_failure = Some(create_output);
+                create_output = retry_output;
+            }
+            Err(error) => {
+                update_workflow_stage(
+                    &mut stages,
+                    "create",
+                    "failed",
+                    error.clone(),
+                    None,
+                    None,
+                    None,
+                );
+                return Ok(build_failed_pr_workflow_result(
+                    stages, "create", error, None,
+                ));
+            }
+        }
+    }
     if !create_output.success {
         let raw = summarize_command_failure(&create_output);
         update_workflow_stage(
@@ -628,6 +821,40 @@ pub(super) async fn create_git_pr_workflow_impl(
         Some(truncate_debug_text(&create_output.stderr, 1200)),
     );
 
+    match assignment_failure {
+        Some(failed_output) => update_workflow_stage(
+            &mut stages,
+            "metadata",
+            "failed",
+            format!(
+                "PR created without {}: {}",
+                metadata.summary(),
+                summarize_command_failure(&failed_output)
+            ),
+            Some(failed_output.command),
+            Some(truncate_debug_text(&failed_output.stdout, 1200)),
+            Some(truncate_debug_text(&failed_output.stderr, 1200)),
+        ),
+        None if metadata.has_assignments() => update_workflow_stage(
+            &mut stages,
+            "metadata",
+            "success",
+            format!("Assigned {}.", metadata.summary()),
+            None,
+            None,
+            None,
+        ),
+        None => update_workflow_stage(
+            &mut stages,
+            "metadata",
+            "skipped",
+            "No labels or reviewers requested.".to_string(),
+            None,
+            None,
+            None,
+        ),
+    }
+
     if !comment_enabled {
         update_workflow_stage(
             &mut stages,
@@ -748,3 +975,81 @@ pub(super) async fn create_git_pr_workflow_impl(
         "PR workflow completed.".to_string(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pr_create_args_carry_draft_labels_and_reviewers() {
+        let metadata = PrCreateMetadata::new(
+            Some(true),
+            Some(vec![
+                " good first issue ".to_string(),
+                "bug".to_string(),
+                "bug".to_string(),
+                "a,b".to_string(),
+                "say \"hi\"".to_string(),
+                "  ".to_string(),
+            ]),
+            Some(vec!["octocat".to_string(), "org/team".to_string()]),
+        );
+        assert_eq!(
+            metadata.labels,
+            vec!["good first issue", "bug", "a,b", "say \"hi\""]
+        );
+
+        let args = pr_create_args("o/r", "main", "me:feat", "Title", "Body", &metadata, true);
+        assert_eq!(
+            &args[12..],
+            [
+                "--draft",
+                "--label",
+                "good first issue",
+                "--label",
+                "bug",
+                "--label",
+                "\"a,b\"",
+                "--label",
+                "\"say \"\"hi\"\"\"",
+                "--reviewer",
+                "octocat",
+                "--reviewer",
+                "org/team",
+            ]
+        );
+        assert!(build_token_isolated_command_display("gh", &args)
+            .contains("--label 'good first issue'"));
+
+        let retry = pr_create_args("o/r", "main", "me:feat", "Title", "Body", &metadata, false);
+        assert_eq!(&retry[12..], ["--draft"]);
+        let plain = pr_create_args(
+            "o/r",
+            "main",
+            "me:feat",
+            "Title",
+            "Body",
+            &PrCreateMetadata::default(),
+            true,
+        );
+        assert_eq!(plain.len(), 12);
+    }
+
+    #[test]
+    fn detects_assignment_failures_and_infers_defaults() {
+        assert!(is_pr_assignment_error(
+            "could not add label: 'needs triage' not found"
+        ));
+        assert!(is_pr_assignment_error(
+            "GraphQL: Could not resolve to a User with the login of 'ghost'."
+        ));
+        assert!(!is_pr_assignment_error("HTTP 403: Resource not accessible"));
+
+        assert_eq!(infer_pr_labels("fix/login-crash"), vec!["bug"]);
+        assert_eq!(infer_pr_labels("Feature/search"), vec!["enhancement"]);
+        assert!(infer_pr_labels("chore/deps").is_empty());
+        assert!(infer_pr_labels("main").is_empty());
+        assert!(infer_pr_draft("wip/spike"));
+        assert!(!infer_pr_draft("fix/wip"));
+    }
+}
@@ -0,0 +1,209 @@
+use super::*;
+
+use serde::Serialize;
+
+/// One movement of HEAD, newest first. `commit_exists` is false once the
+/// commit has been garbage-collected and can no longer be recovered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitReflogEntry {
+    pub(crate) index: usize,
+    pub(crate) sha: String,
+    pub(crate) short_sha: String,
+    pub(crate) previous_sha: Option<String>,
+    pub(crate) message: String,
+    pub(crate) committer: String,
+    pub(crate) committer_email: String,
+    pub(crate) timestamp: i64,
+    pub(crate) commit_exists: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitReflogResponse {
+    pub(crate) total: usize,
+    pub(crate) offset: usize,
+    pub(crate) limit: usize,
+    pub(crate) has_more: bool,
+    pub(crate) entries: Vec<GitReflogEntry>,
+}
+
+fn reflog_page(repo_root: &Path, offset: usize, limit: usize) -> Result<GitReflogResponse, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    // A disabled or never-written reflog is just an empty history.
+    let Ok(reflog) = repo.reflog("HEAD") else {
+        return Ok(GitReflogResponse {
+            total: 0,
+            offset,
+            limit,
+            has_more: false,
+            entries: Vec::new(),
+        });
+    };
+    let total = reflog.len();
+    let entries: Vec<GitReflogEntry> = reflog
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(limit)
+        .map(|(index, entry)| {
+            let sha = entry.id_new().to_string();
+            let previous = entry.id_old();
+            let committer = entry.committer();
+            GitReflogEntry {
+                index,
+                short_sha: sha.chars().take(7).collect(),
+                sha,
+                previous_sha: (!previous.is_zero()).then(|| previous.to_string()),
+                message: entry.message().unwrap_or("").to_string(),
+                committer: committer.name().unwrap_or("").to_string(),
+                committer_email: committer.email().unwrap_or("").to_string(),
+                timestamp: committer.when().seconds(),
+                commit_exists: repo.find_commit(entry.id_new()).is_ok(),
+            }
+        })
+        .collect();
+    Ok(GitReflogResponse {
+        total,
+        offset,
+        limit,
+        has_more: offset.saturating_add(entries.len()) < total,
+        entries,
+    })
+}
+
+/// Points a new branch at `sha` without checking it out, so a commit that
+/// is only reachable from the reflog survives the next gc.
+fn recover_commit(repo_root: &Path, sha: &str, branch_name: &str) -> Result<String, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let branch_name = validate_local_branch_name(branch_name)?;
+    let oid = Oid::from_str(sha.trim()).map_err(|_| format!("Invalid commit: {}", sha.trim()))?;
+    let commit = repo
+        .find_commit(oid)
+        .map_err(|_| format!("Commit {} no longer exists in this repository.", sha.trim()))?;
+    if repo.find_branch(&branch_name, BranchType::Local).is_ok() {
+        return Err(format!("Branch '{branch_name}' already exists."));
+    }
+    repo.branch(&branch_name, &commit, false)
+        .map_err(|e| e.to_string())?;
+    Ok(branch_name)
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_reflog(
+    workspace_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<GitReflogResponse, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(100).clamp(1, 500);
+    tokio::task::spawn_blocking(move || reflog_page(&repo_root, offset, limit))
+        .await
+        .map_err(|e| format!("Reflog task failed: {e}"))?
+}
+
+/// Returns the name of the created branch.
+#[tauri::command]
+pub(crate) async fn recover_git_commit(
+    workspace_id: String,
+    sha: String,
+    new_branch_name: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "recover_git_commit").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    recover_commit(&repo_root, &sha, &new_branch_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn recovers_a_commit_lost_to_a_hard_reset() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create repo dir");
+        let repo = Repository::init(&root).expect("init repo");
+        assert_eq!(reflog_page(&root, 0, 10).expect("empty reflog").total, 0);
+
+        let commit = |message: &'static str| {
+            let root = root.clone();
+            async move {
+                fs::write(root.join("a.txt"), message).expect("write a");
+                run_git_command(&root, &["add", "a.txt"])
+                    .await
+                    .expect("add a");
+                run_git_command(
+                    &root,
+                    &[
+                        "-c",
+                        "user.name=Test",
+                        "-c",
+                        "user.email=test@example.com",
+                        "-c",
+                        "commit.gpgsign=false",
+                        "commit",
+                        "-m",
+                        message,
+                    ],
+                )
+                .await
+                .expect("commit");
+            }
+        };
+        commit("first").await;
+        commit("second").await;
+        let lost = repo
+            .head()
+            .ok()
+            .and_then(|head| head.target())
+            .expect("head")
+            .to_string();
+        run_git_command(&root, &["reset", "--hard", "HEAD~1"])
+            .await
+            .expect("reset");
+
+        let page = reflog_page(&root, 0, 2).expect("reflog");
+        assert_eq!(page.total, 3);
+        assert!(page.has_more);
+        assert_eq!(page.entries[0].previous_sha.as_deref(), Some(lost.as_str()));
+        assert_eq!(page.entries[1].sha, lost);
+        assert!(page.entries[1].commit_exists);
+        let last = reflog_page(&root, 2, 2).expect("last page");
+        assert_eq!(last.entries.len(), 1);
+        assert_eq!(last.entries[0].previous_sha, None);
+        assert!(!last.has_more);
+
+        assert!(recover_commit(&root, &lost, "bad..name").is_err());
+        assert_eq!(
+            recover_commit(&root, &lost, "rescued").expect("recover"),
+            "rescued"
+        );
+        let rescued = repo
+            .find_branch("rescued", BranchType::Local)
+            .expect("rescued branch");
+        assert_eq!(
+            rescued.get().target().map(|oid| oid.to_string()),
+            Some(lost.clone())
+        );
+        assert!(recover_commit(&root, &lost, "rescued").is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,293 @@
+use super::*;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitRemoteInfo {
+    pub(crate) name: String,
+    pub(crate) fetch_url: Option<String>,
+    /// Only set when it differs from `fetch_url`.
+    pub(crate) push_url: Option<String>,
+    /// The current branch's upstream lives on this remote.
+    pub(crate) is_upstream: bool,
+    /// HEAD versus `refs/remotes/<name>/<current branch>`; `None` when that
+    /// ref does not exist.
+    pub(crate) ahead: Option<usize>,
+    pub(crate) behind: Option<usize>,
+}
+
+fn list_remotes(repo_root: &Path) -> Result<Vec<GitRemoteInfo>, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let branch = current_local_branch(repo_root)?;
+    let upstream_remote = upstream_remote_and_branch(repo_root)?.map(|(remote, _)| remote);
+    let head_oid = repo.head().ok().and_then(|head| head.target());
+
+    let names = repo.remotes().map_err(|e| e.to_string())?;
+    let mut remotes = Vec::new();
+    for name in names.iter().flatten() {
+        let remote = repo.find_remote(name).map_err(|e| e.to_string())?;
+        let fetch_url = remote.url().map(str::to_string);
+        let push_url = remote
+            .pushurl()
+            .map(str::to_string)
+            .filter(|push_url| Some(push_url) != fetch_url.as_ref());
+        let tracking_oid = branch.as_deref().and_then(|branch| {
+            repo.refname_to_id(&format!("refs/remotes/{name}/{branch}"))
+                .ok()
+        });
+        let (ahead, behind) = match (head_oid, tracking_oid) {
+            (Some(local), Some(remote)) => repo
+                .graph_ahead_behind(local, remote)
+                .map(|(ahead, behind)| (Some(ahead), Some(behind)))
+                .unwrap_or((None, None)),
+            _ => (None, None),
+        };
+        remotes.push(GitRemoteInfo {
+            name: name.to_string(),
+            fetch_url,
+            push_url,
+            is_upstream: upstream_remote.as_deref() == Some(name),
+            ahead,
+            behind,
+        });
+    }
+    Ok(remotes)
+}
+
+fn add_remote(repo_root: &Path, name: &str, url: &str) -> Result<(), String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let name = validate_remote_name(name)?;
+    let url = validate_remote_url(url)?;
+    if repo.find_remote(&name).is_ok() {
+        return Err(format!("Remote '{name}' already exists."));
+    }
+    repo.remote(&name, &url).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn remove_remote(repo_root: &Path, name: &str) -> Result<(), String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let name = name.trim();
+    repo.find_remote(name)
+        .map_err(|_| format!("Remote '{name}' does not exist."))?;
+    repo.remote_delete(name).map_err(|e| e.to_string())
+}
+
+/// Renames the remote along with its remote-tracking refs and the branches
+/// that track it.
+fn rename_remote(repo_root: &Path, name: &str, new_name: &str) -> Result<(), String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let name = name.trim();
+    let new_name = validate_remote_name(new_name)?;
+    repo.find_remote(name)
+        .map_err(|_| format!("Remote '{name}' does not exist."))?;
+    if repo.find_remote(&new_name).is_ok() {
+        return Err(format!("Remote '{new_name}' already exists."));
+    }
+    repo.remote_rename(name, &new_name)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn list_git_remotes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitRemoteInfo>, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    list_remotes(&repo_root)
+}
+
+#[tauri::command]
+pub(crate) async fn add_git_remote(
+    workspace_id: String,
+    name: String,
+    url: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "add_git_remote").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    add_remote(&repo_root, &name, &url)
+}
+
+#[tauri::command]
+pub(crate) async fn remove_git_remote(
+    workspace_id: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "remove_git_remote").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    remove_remote(&repo_root, &name)
+}
+
+#[tauri::command]
+pub(crate) async fn rename_git_remote(
+    workspace_id: String,
+    name: String,
+    new_name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "rename_git_remote").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    rename_remote(&repo_root, &name, &new_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_remote_urls() {
+        for url in [
+            "https://github.com/octo/repo.git",
+            "ssh://git@github.com/octo/repo.git",
+            "git@github.com:octo/repo.git",
+            "/srv/git/repo.git",
+        ] {
+            assert_eq!(
+                validate_remote_url(&format!("  {url} ")),
+                Ok(url.to_string())
+            );
+        }
+        for url in [
+            "",
+            "   ",
+            "github.com/octo repo",
+            "--upload-pack=x",
+            "ftp://host/repo",
+        ] {
+            assert!(validate_remote_url(url).is_err(), "{url}");
+        }
+        assert!(validate_remote_name("bad name").is_err());
+    }
+
+    #[tokio::test]
+    async fn manages_remotes_and_reports_ahead_behind() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create repo dir");
+        let repo = Repository::init(&root).expect("init repo");
+        for message in ["first", "second"] {
+            fs::write(root.join("a.txt"), message).expect("write a");
+            run_git_command(&root, &["add", "a.txt"])
+                .await
+                .expect("add a");
+            run_git_command(
+                &root,
+                &[
+                    "-c",
+                    "user.name=Test",
+                    "-c",
+                    "user.email=test@example.com",
+                    "-c",
+                    "commit.gpgsign=false",
+                    "commit",
+                    "-m",
+                    message,
+                ],
+            )
+            .await
+            .expect("commit");
+        }
+        let branch = current_local_branch(&root)
+            .expect("branch")
+            .expect("on a branch");
+
+        add_remote(&root, "origin", "git@github.com:me/repo.git").expect("add origin");
+        add_remote(&root, "upstream", "https://github.com/octo/repo.git").expect("add upstream");
+        assert!(add_remote(&root, "origin", "https://example.com/x.git").is_err());
+        assert!(add_remote(&root, "other", " ").is_err());
+        repo.remote_set_pushurl("upstream", Some("git@github.com:octo/repo.git"))
+            .expect("set push url");
+        run_git_command(
+            &root,
+            &[
+                "update-ref",
+                &format!("refs/remotes/origin/{branch}"),
+                "HEAD~1",
+            ],
+        )
+        .await
+        .expect("create tracking ref");
+        run_git_command(
+            &root,
+            &["config", &format!("branch.{branch}.remote"), "origin"],
+        )
+        .await
+        .expect("set branch remote");
+        run_git_command(
+            &root,
+            &[
+                "config",
+                &format!("branch.{branch}.merge"),
+                &format!("refs/heads/{branch}"),
+            ],
+        )
+        .await
+        .expect("set branch merge");
+
+        let remotes = list_remotes(&root).expect("list remotes");
+        let origin = remotes
+            .iter()
+            .find(|remote| remote.name == "origin")
+            .expect("origin");
+        assert!(origin.is_upstream);
+        assert_eq!((origin.ahead, origin.behind), (Some(1), Some(0)));
+        assert_eq!(origin.push_url, None);
+        let upstream = remotes
+            .iter()
+            .find(|remote| remote.name == "upstream")
+            .expect("upstream");
+        assert!(!upstream.is_upstream);
+        assert_eq!(upstream.ahead, None);
+        assert_eq!(
+            upstream.push_url.as_deref(),
+            Some("git@github.com:octo/repo.git")
+        );
+
+        rename_remote(&root, "origin", "fork").expect("rename origin");
+        assert!(repo
+            .refname_to_id(&format!("refs/remotes/fork/{branch}"))
+            .is_ok());
+        assert!(rename_remote(&root, "fork", "upstream").is_err());
+        remove_remote(&root, "upstream").expect("remove upstream");
+        assert!(remove_remote(&root, "upstream").is_err());
+        let names = list_remotes(&root)
+            .expect("list remotes")
+            .into_iter()
+            .map(|remote| remote.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["fork"]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
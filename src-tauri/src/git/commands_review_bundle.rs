@@ -0,0 +1,494 @@
+use super::*;
+
+use serde::Serialize;
+
+const REVIEW_BUNDLE_MAX_COMMITS: usize = 200;
+const REVIEW_BUNDLE_MAX_DIFF_LINES_PER_FILE: usize = 2_000;
+/// Diff text budget for the whole bundle; files past it keep their stats only.
+const REVIEW_BUNDLE_MAX_DIFF_BYTES: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ReviewBundleFormat {
+    Html,
+    Markdown,
+}
+
+impl ReviewBundleFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ReviewBundleFormat::Html => "html",
+            ReviewBundleFormat::Markdown => "md",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReviewBundle {
+    format: ReviewBundleFormat,
+    file_name: String,
+    /// Where the bundle was written, when an output path was given.
+    output_path: Option<String>,
+    content: String,
+    commit_count: usize,
+    file_count: usize,
+    total_additions: i64,
+    total_deletions: i64,
+    /// The range had more commits than the bundle holds.
+    commits_truncated: bool,
+    /// Some diffs were shortened or dropped to respect the size limits.
+    diffs_truncated: bool,
+}
+
+struct BundleRange {
+    from: String,
+    to: String,
+    commits: Vec<GitCommitDetails>,
+    commits_truncated: bool,
+    diffs_truncated: bool,
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// A backtick fence longer than any backtick run inside `content`.
+pub(super) fn markdown_fence(content: &str) -> String {
+    let longest = content
+        .split(|ch| ch != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn format_time(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default()
+}
+
+/// Why a file has no diff text in the bundle, if it has none.
+fn omitted_diff_note(file: &GitCommitFileChange) -> Option<&'static str> {
+    if file.is_image {
+        Some("Image file, not shown.")
+    } else if file.is_binary {
+        Some("Binary file, not shown.")
+    } else if file.diff.is_empty() && (file.additions > 0 || file.deletions > 0) {
+        Some("Diff omitted to keep the bundle within its size limit.")
+    } else {
+        None
+    }
+}
+
+/// Drops diff text once the bundle-wide budget is spent.
+fn apply_diff_budget(commits: &mut [GitCommitDetails]) -> bool {
+    let mut remaining = REVIEW_BUNDLE_MAX_DIFF_BYTES;
+    let mut truncated = false;
+    for file in commits
+        .iter_mut()
+        .flat_map(|commit| commit.files.iter_mut())
+    {
+        truncated |= file.truncated;
+        if file.diff.len() > remaining {
+            file.diff.clear();
+            truncated = true;
+            remaining = 0;
+        } else {
+            remaining -= file.diff.len();
+        }
+    }
+    truncated
+}
+
+fn collect_range(repo: &Repository, from: &str, to: &str) -> Result<BundleRange, String> {
+    let resolve = |spec: &str| {
+        repo.revparse_single(spec)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| format!("Commit or ref not found: {spec}"))
+    };
+    let from_commit = resolve(from)?;
+    let to_commit = resolve(to)?;
+
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk.push(to_commit.id()).map_err(|e| e.to_string())?;
+    revwalk.hide(from_commit.id()).map_err(|e| e.to_string())?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)
+        .map_err(|e| e.to_string())?;
+    let oids = revwalk
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    if oids.is_empty() {
+        return Err(format!("No commits between {from} and {to}"));
+    }
+    let commits_truncated = oids.len() > REVIEW_BUNDLE_MAX_COMMITS;
+
+    let mut commits = Vec::new();
+    for oid in oids.into_iter().take(REVIEW_BUNDLE_MAX_COMMITS) {
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        commits.push(build_commit_details(
+            repo,
+            &commit,
+            REVIEW_BUNDLE_MAX_DIFF_LINES_PER_FILE,
+        )?);
+    }
+    let diffs_truncated = apply_diff_budget(&mut commits);
+    Ok(BundleRange {
+        from: from.to_string(),
+        to: to.to_string(),
+        commits,
+        commits_truncated,
+        diffs_truncated,
+    })
+}
+
+fn range_totals(range: &BundleRange) -> (usize, i64, i64) {
+    let mut paths = HashSet::new();
+    let (mut additions, mut deletions) = (0, 0);
+    for commit in &range.commits {
+        additions += commit.total_additions;
+        deletions += commit.total_deletions;
+        paths.extend(commit.files.iter().map(|file| file.path.as_str()));
+    }
+    (paths.len(), additions, deletions)
+}
+
+fn truncation_notes(range: &BundleRange) -> Vec<String> {
+    let mut notes = Vec::new();
+    if range.commits_truncated {
+        notes.push(format!(
+            "Only the first {REVIEW_BUNDLE_MAX_COMMITS} commits of the range are included."
+        ));
+    }
+    if range.diffs_truncated {
+        notes.push("Some diffs were shortened or omitted to limit the bundle size.".to_string());
+    }
+    notes
+}
+
+fn render_markdown(range: &BundleRange) -> String {
+    let (file_count, additions, deletions) = range_totals(range);
+    let mut out = format!(
+        "# Review bundle: `{}..{}`\n\n{} commits, {} files changed, +{} -{}\n",
+        range.from,
+        range.to,
+        range.commits.len(),
+        file_count,
+        additions,
+        deletions
+    );
+    for note in truncation_notes(range) {
+        out.push_str(&format!("\n> {note}\n"));
+    }
+    out.push_str("\n| Commit | Author | Date | Summary |\n| --- | --- | --- | --- |\n");
+    for commit in &range.commits {
+        out.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            short_sha(&commit.sha),
+            commit.author.replace('|', "\\|"),
+            format_time(commit.author_time),
+            commit.summary.replace('|', "\\|")
+        ));
+    }
+    for commit in &range.commits {
+        out.push_str(&format!(
+            "\n## {} {}\n\n{} <{}> · {} · +{} -{}\n",
+            short_sha(&commit.sha),
+            commit.summary,
+            commit.author,
+            commit.author_email,
+            format_time(commit.author_time),
+            commit.total_additions,
+            commit.total_deletions
+        ));
+        let body = commit.message.trim().strip_prefix(commit.summary.trim());
+        if let Some(body) = body.map(str::trim).filter(|body| !body.is_empty()) {
+            let fence = markdown_fence(body);
+            out.push_str(&format!("\n{fence}text\n{body}\n{fence}\n"));
+        }
+        for file in &commit.files {
+            out.push_str(&format!(
+                "\n### `{}` ({}, +{} -{})\n",
+                file.path, file.status, file.additions, file.deletions
+            ));
+            if let Some(note) = omitted_diff_note(file) {
+                out.push_str(&format!("\n_{note}_\n"));
+            } else if !file.diff.is_empty() {
+                let fence = markdown_fence(&file.diff);
+                out.push_str(&format!("\n{fence}diff\n{}\n{fence}\n", file.diff));
+                if file.truncated {
+                    out.push_str(&format!(
+                        "\n_Diff truncated to {} of {} lines._\n",
+                        REVIEW_BUNDLE_MAX_DIFF_LINES_PER_FILE, file.line_count
+                    ));
+                }
+            }
+        }
+    }
+    out
+}
+
+fn diff_line_class(line: &str) -> &'static str {
+    if line.starts_with("@@") {
+        "hunk"
+    } else if line.starts_with('+') && !line.starts_with("+++") {
+        "add"
+    } else if line.starts_with('-') && !line.starts_with("---") {
+        "del"
+    } else {
+        "ctx"
+    }
+}
+
+const REVIEW_BUNDLE_HTML_STYLE: &str = "body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;margin:24px;color:#1f2328}\
+table{border-collapse:collapse}td,th{border:1px solid #d0d7de;padding:4px 8px;text-align:left}\
+code,pre{font-family:SFMono-Regular,Consolas,Menlo,monospace;font-size:12px}\
+pre{border:1px solid #d0d7de;border-radius:6px;overflow-x:auto;padding:0;margin:8px 0}\
+pre span{display:block;padding:0 8px;white-space:pre}.add{background:#e6ffec}.del{background:#ffebe9}\
+.hunk{background:#ddf4ff;color:#57606a}.note{color:#57606a;font-style:italic}\
+.commit{border-top:2px solid #d0d7de;margin-top:32px}";
+
+fn render_html(range: &BundleRange) -> String {
+    let (file_count, additions, deletions) = range_totals(range);
+    let title = format!("Review bundle: {}..{}", range.from, range.to);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head><body>\n<h1>{}</h1>\n<p>{} commits, {} files changed, +{} -{}</p>\n",
+        escape_html(&title),
+        REVIEW_BUNDLE_HTML_STYLE,
+        escape_html(&title),
+        range.commits.len(),
+        file_count,
+        additions,
+        deletions
+    );
+    for note in truncation_notes(range) {
+        out.push_str(&format!("<p class=\"note\">{}</p>\n", escape_html(&note)));
+    }
+    out.push_str("<table><tr><th>Commit</th><th>Author</th><th>Date</th><th>Summary</th></tr>\n");
+    for commit in &range.commits {
+        out.push_str(&format!(
+            "<tr><td><a href=\"#c{sha}\"><code>{short}</code></a></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&commit.author),
+            format_time(commit.author_time),
+            escape_html(&commit.summary),
+            sha = commit.sha,
+            short = short_sha(&commit.sha),
+        ));
+    }
+    out.push_str("</table>\n");
+    for commit in &range.commits {
+        out.push_str(&format!(
+            "<div class=\"commit\" id=\"c{}\">\n<h2><code>{}</code> {}</h2>\n<p>{} &lt;{}&gt; · {} · +{} -{}</p>\n",
+            commit.sha,
+            short_sha(&commit.sha),
+            escape_html(&commit.summary),
+            escape_html(&commit.author),
+            escape_html(&commit.author_email),
+            format_time(commit.author_time),
+            commit.total_additions,
+            commit.total_deletions
+        ));
+        let body = commit.message.trim().strip_prefix(commit.summary.trim());
+        if let Some(body) = body.map(str::trim).filter(|body| !body.is_empty()) {
+            out.push_str(&format!("<pre><span>{}</span></pre>\n", escape_html(body)));
+        }
+        for file in &commit.files {
+            out.push_str(&format!(
+                "<h3><code>{}</code> ({}, +{} -{})</h3>\n",
+                escape_html(&file.path),
+                escape_html(&file.status),
+                file.additions,
+                file.deletions
+            ));
+            if let Some(note) = omitted_diff_note(file) {
+                out.push_str(&format!("<p class=\"note\">{note}</p>\n"));
+            } else if !file.diff.is_empty() {
+                out.push_str("<pre>");
+                for line in file.diff.lines() {
+                    out.push_str(&format!(
+                        "<span class=\"{}\">{}</span>",
+                        diff_line_class(line),
+                        escape_html(line)
+                    ));
+                }
+                out.push_str("</pre>\n");
+                if file.truncated {
+                    out.push_str(&format!(
+                        "<p class=\"note\">Diff truncated to {} of {} lines.</p>\n",
+                        REVIEW_BUNDLE_MAX_DIFF_LINES_PER_FILE, file.line_count
+                    ));
+                }
+            }
+        }
+        out.push_str("</div>\n");
+    }
+    out.push_str("</body></html>\n");
+    out
+}
+
+fn bundle_file_name(range: &BundleRange, format: ReviewBundleFormat) -> String {
+    let sanitize = |value: &str| {
+        value
+            .chars()
+            .map(|ch| {
+                if ch.is_ascii_alphanumeric() || ch == '-' || ch == '.' {
+                    ch
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>()
+    };
+    format!(
+        "review-{}..{}.{}",
+        sanitize(&range.from),
+        sanitize(&range.to),
+        format.extension()
+    )
+}
+
+/// Exports the commits in `from..to` (reachable from `to`, not from `from`)
+/// as a self-contained HTML or markdown document with per-file diffs and
+/// stats. Writes it to `output_path` when given.
+#[tauri::command]
+pub(crate) async fn export_review_bundle(
+    workspace_id: String,
+    from: String,
+    to: String,
+    format: Option<ReviewBundleFormat>,
+    output_path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ReviewBundle, String> {
+    let from = from.trim().to_string();
+    let to = to.trim().to_string();
+    if from.is_empty() || to.is_empty() {
+        return Err("Both ends of the commit range are required".to_string());
+    }
+    let format = format.unwrap_or(ReviewBundleFormat::Html);
+    let output_path = trim_optional(output_path);
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository_at_root(&repo_root)?;
+        let range = collect_range(&repo, &from, &to)?;
+        let content = match format {
+            ReviewBundleFormat::Html => render_html(&range),
+            ReviewBundleFormat::Markdown => render_markdown(&range),
+        };
+        if let Some(output_path) = &output_path {
+            let path = Path::new(output_path);
+            if let Some(parent) = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+            {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            fs::write(path, &content).map_err(|e| e.to_string())?;
+        }
+        let (file_count, total_additions, total_deletions) = range_totals(&range);
+        Ok(ReviewBundle {
+            format,
+            file_name: bundle_file_name(&range, format),
+            output_path,
+            commit_count: range.commits.len(),
+            file_count,
+            total_additions,
+            total_deletions,
+            commits_truncated: range.commits_truncated,
+            diffs_truncated: range.diffs_truncated,
+            content,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_file(repo: &Repository, root: &Path, name: &str, content: &[u8], message: &str) {
+        fs::write(root.join(name), content).expect("write file");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new(name)).expect("add path");
+        let tree_id = index.write_tree().expect("write tree");
+        index.write().expect("write index");
+        let tree = repo.find_tree(tree_id).expect("find tree");
+        let signature = git2::Signature::now("Reviewer", "reviewer@example.com").expect("sig");
+        let parents = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect::<Vec<_>>();
+        let parent_refs = parents.iter().collect::<Vec<_>>();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parent_refs,
+        )
+        .expect("commit");
+    }
+
+    #[test]
+    fn review_bundle_covers_range_and_escapes_content() {
+        let root = std::env::temp_dir().join(format!("moss-x-bundle-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create root");
+        let repo = Repository::init(&root).expect("init repo");
+        commit_file(&repo, &root, "base.txt", b"base\n", "base");
+        let base = repo.head().expect("head").peel_to_commit().expect("commit");
+        commit_file(&repo, &root, "a.html", b"<b>```code```</b>\n", "Add <a>");
+        commit_file(
+            &repo,
+            &root,
+            "logo.bin",
+            &[0, 159, 146, 150, 0],
+            "Add binary",
+        );
+
+        let range = collect_range(&repo, &base.id().to_string(), "HEAD").expect("range");
+        assert_eq!(range.commits.len(), 2);
+        assert_eq!(range.commits[0].summary, "Add <a>");
+        assert!(!range.commits_truncated);
+
+        let markdown = render_markdown(&range);
+        assert!(markdown.contains("2 commits, 2 files changed"));
+        assert!(markdown.contains("````diff\n"));
+        assert!(markdown.contains("_Binary file, not shown._"));
+
+        let html = render_html(&range);
+        assert!(html.contains("Add &lt;a&gt;"));
+        assert!(html.contains("<span class=\"add\">+&lt;b&gt;"));
+        assert!(!html.contains("<b>```"));
+
+        assert!(collect_range(&repo, "HEAD", "HEAD").is_err());
+        let _ = fs::remove_dir_all(root);
+    }
+}
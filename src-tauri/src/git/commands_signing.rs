@@ -0,0 +1,178 @@
+use super::*;
+
+use serde::Serialize;
+
+/// Commit signing setup as git resolves it for the repository (local config
+/// layered over global and system).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitSigningStatus {
+    /// `commit.gpgsign`: whether commits are signed by default.
+    pub(crate) enabled: bool,
+    /// `gpg.format`: `openpgp`, `ssh` or `x509`.
+    pub(crate) format: String,
+    pub(crate) signing_key: Option<String>,
+    /// Signing program override for the configured format, if any.
+    pub(crate) program: Option<String>,
+}
+
+fn config_string(config: &git2::Config, name: &str) -> Option<String> {
+    config
+        .get_string(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn signing_status_from_config(config: &git2::Config) -> GitSigningStatus {
+    let format = config_string(config, "gpg.format").unwrap_or_else(|| "openpgp".to_string());
+    let program = match format.as_str() {
+        "ssh" => config_string(config, "gpg.ssh.program"),
+        "x509" => config_string(config, "gpg.x509.program"),
+        _ => config_string(config, "gpg.openpgp.program")
+            .or_else(|| config_string(config, "gpg.program")),
+    };
+    GitSigningStatus {
+        enabled: config.get_bool("commit.gpgsign").unwrap_or(false),
+        signing_key: config_string(config, "user.signingkey"),
+        format,
+        program,
+    }
+}
+
+pub(super) fn read_signing_status(repo_root: &Path) -> Result<GitSigningStatus, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let config = repo
+        .config()
+        .and_then(|mut config| config.snapshot())
+        .map_err(|e| e.to_string())?;
+    Ok(signing_status_from_config(&config))
+}
+
+/// Arguments that make `git commit` sign or not sign regardless of config.
+pub(super) fn commit_sign_args(sign: Option<bool>) -> &'static [&'static str] {
+    match sign {
+        Some(true) => &["-S"],
+        Some(false) => &["--no-gpg-sign"],
+        None => &[],
+    }
+}
+
+/// Turns a signing failure in `git commit` output into an explanation of what
+/// to fix. Returns `None` when the failure is unrelated to signing.
+pub(super) fn signing_error_hint(format: &str, raw: &str) -> Option<String> {
+    let lower = raw.to_lowercase();
+    if lower.contains("timed out") {
+        return Some(
+            "Commit signing did not finish in time; the signer is probably waiting for a passphrase prompt the GUI cannot show. Unlock the key in your agent (or configure a graphical pinentry) and retry."
+                .to_string(),
+        );
+    }
+    let is_signing_failure = lower.contains("failed to sign")
+        || lower.contains("gpg: signing failed")
+        || lower.contains("gpg: skipped")
+        || lower.contains("ssh-keygen")
+        || lower.contains("couldn't load public key")
+        || lower.contains("load key");
+    if !is_signing_failure {
+        return None;
+    }
+    let hint = if format == "ssh" {
+        if lower.contains("agent refused")
+            || lower.contains("communication with agent failed")
+            || lower.contains("no identities")
+            || lower.contains("ssh_auth_sock")
+        {
+            "SSH agent not available in GUI session: start ssh-agent, add the signing key with `ssh-add`, and make sure SSH_AUTH_SOCK is set for the app."
+        } else if lower.contains("no such file") || lower.contains("couldn't load public key") {
+            "SSH signing key not found: point `user.signingkey` at an existing public key file."
+        } else {
+            "SSH commit signing failed: check `user.signingkey` and that ssh-keygen can sign with it."
+        }
+    } else if lower.contains("no secret key") || lower.contains("unusable secret key") {
+        "GPG signing key not found: check that `user.signingkey` matches a secret key in your keyring."
+    } else if lower.contains("inappropriate ioctl")
+        || lower.contains("no pinentry")
+        || lower.contains("no agent running")
+        || lower.contains("cannot open '/dev/tty'")
+        || lower.contains("operation cancelled")
+    {
+        "GPG agent not available in GUI session: the passphrase prompt needs a terminal. Configure a graphical pinentry (pinentry-program in gpg-agent.conf) or unlock the key in a terminal first, then retry."
+    } else {
+        "GPG commit signing failed: check `user.signingkey` and that gpg can sign with it (`gpg --sign` in a terminal)."
+    };
+    let detail = raw
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    Some(format!("{hint}\n\nDetails: {detail}"))
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_signing_status(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitSigningStatus, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    read_signing_status(&repo_root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_signing_config_for_each_format() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create config dir");
+        let path = root.join("config");
+        fs::write(
+            &path,
+            "[commit]\n\tgpgsign = true\n[gpg]\n\tformat = ssh\n[gpg \"ssh\"]\n\tprogram = /opt/ssh-keygen\n[user]\n\tsigningkey = ~/.ssh/id_ed25519.pub\n",
+        )
+        .expect("write config");
+        let config = git2::Config::open(&path).expect("open config");
+        assert_eq!(
+            signing_status_from_config(&config),
+            GitSigningStatus {
+                enabled: true,
+                format: "ssh".to_string(),
+                signing_key: Some("~/.ssh/id_ed25519.pub".to_string()),
+                program: Some("/opt/ssh-keygen".to_string()),
+            }
+        );
+
+        fs::write(&path, "[gpg]\n\tprogram = gpg2\n").expect("rewrite config");
+        let config = git2::Config::open(&path).expect("reopen config");
+        let status = signing_status_from_config(&config);
+        assert!(!status.enabled);
+        assert_eq!(status.format, "openpgp");
+        assert_eq!(status.program.as_deref(), Some("gpg2"));
+        assert_eq!(status.signing_key, None);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn signing_failures_map_to_hints() {
+        let gpg = "error: gpg failed to sign the data\nfatal: failed to write commit object\ngpg: signing failed: Inappropriate ioctl for device";
+        let hint = signing_error_hint("openpgp", gpg).expect("gpg hint");
+        assert!(hint.starts_with("GPG agent not available in GUI session"));
+        assert!(hint.ends_with("Details: error: gpg failed to sign the data"));
+
+        let ssh = "error: Couldn't load public key /missing.pub: No such file or directory\nfatal: failed to write commit object";
+        assert!(signing_error_hint("ssh", ssh)
+            .expect("ssh hint")
+            .starts_with("SSH signing key not found"));
+
+        assert!(signing_error_hint("openpgp", "nothing to commit, working tree clean").is_none());
+    }
+}
@@ -0,0 +1,435 @@
+use super::*;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitStashEntry {
+    index: usize,
+    message: String,
+    branch: Option<String>,
+    timestamp: i64,
+}
+
+/// Branch recorded in a stash message (`WIP on main: ...`, `On main: ...`);
+/// `None` for stashes made on a detached HEAD.
+fn stash_branch_from_message(message: &str) -> Option<String> {
+    let rest = message
+        .strip_prefix("WIP on ")
+        .or_else(|| message.strip_prefix("On "))?;
+    let (branch, _) = rest.split_once(':')?;
+    let branch = branch.trim();
+    if branch.is_empty() || branch == "(no branch)" {
+        return None;
+    }
+    Some(branch.to_string())
+}
+
+fn stash_ref(index: usize) -> String {
+    format!("stash@{{{index}}}")
+}
+
+fn collect_conflicted_paths(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if !line.starts_with("CONFLICT") {
+                return None;
+            }
+            line.rsplit_once(" in ")
+                .map(|(_, path)| path.trim().to_string())
+        })
+        .filter(|path| !path.is_empty())
+        .collect()
+}
+
+/// Paths git lists (tab-indented) under "would be overwritten by ..." before
+/// aborting.
+fn collect_overwritten_paths(output: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut in_list = false;
+    for line in output.lines() {
+        if line.contains("would be overwritten by") {
+            in_list = true;
+            continue;
+        }
+        if in_list {
+            if let Some(path) = line.strip_prefix('\t') {
+                paths.push(path.trim().to_string());
+            } else {
+                in_list = false;
+            }
+        }
+    }
+    paths
+}
+
+fn collect_existing_untracked_paths(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_suffix(" already exists, no checkout"))
+        .map(|path| path.trim().to_string())
+        .collect()
+}
+
+/// Turns the output of a failed `git stash apply|pop` into a message the git
+/// panel can show as-is.
+fn describe_stash_apply_failure(index: usize, pop: bool, stdout: &str, stderr: &str) -> String {
+    let combined = format!("{stdout}\n{stderr}");
+    let conflicted = collect_conflicted_paths(&combined);
+    if !conflicted.is_empty() {
+        let kept = if pop {
+            " The stash entry was kept; drop it once the conflicts are resolved."
+        } else {
+            ""
+        };
+        return format!(
+            "Stash applied with conflicts in {}. Resolve the conflicts and stage the files.{kept}",
+            conflicted.join(", ")
+        );
+    }
+    let overwritten = collect_overwritten_paths(&combined);
+    if !overwritten.is_empty() {
+        return format!(
+            "Applying the stash would overwrite local changes to {}. Commit, stash or discard them first.",
+            overwritten.join(", ")
+        );
+    }
+    let existing = collect_existing_untracked_paths(&combined);
+    if !existing.is_empty() {
+        return format!(
+            "The stash contains untracked files that already exist in the working tree: {}. Move or delete them first.",
+            existing.join(", ")
+        );
+    }
+    if combined.contains("is not a valid reference")
+        || combined.contains("is not a stash-like commit")
+        || combined.contains("No stash entries found")
+    {
+        return format!("Stash entry {index} not found.");
+    }
+    describe_git_failure(stdout, stderr, "Git stash failed.")
+}
+
+fn describe_stash_push_failure(stdout: &str, stderr: &str) -> String {
+    let combined = format!("{stdout}\n{stderr}");
+    if combined.contains("Please tell me who you are") {
+        return "Git user identity is not configured. Set user.name and user.email before stashing."
+            .to_string();
+    }
+    if combined.contains("You do not have the initial commit yet") {
+        return "Cannot stash before the first commit.".to_string();
+    }
+    describe_git_failure(stdout, stderr, "Git stash failed.")
+}
+
+fn describe_git_failure(stdout: &str, stderr: &str, fallback: &str) -> String {
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    if detail.is_empty() {
+        fallback.to_string()
+    } else {
+        detail.to_string()
+    }
+}
+
+async fn stash_repo_root(
+    state: &State<'_, AppState>,
+    workspace_id: &str,
+    operation: Option<&str>,
+) -> Result<PathBuf, String> {
+    if let Some(operation) = operation {
+        ensure_workspace_id_writable(&state.workspaces, workspace_id, operation).await?;
+    }
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    resolve_git_root(&entry)
+}
+
+fn git_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+async fn run_stash_apply(repo_root: &Path, index: usize, pop: bool) -> Result<(), String> {
+    let reference = stash_ref(index);
+    let subcommand = if pop { "pop" } else { "apply" };
+    let output = run_token_isolated_command(
+        repo_root,
+        "git",
+        &git_args(&["stash", subcommand, &reference]),
+        &[],
+    )
+    .await?;
+    if output.success {
+        return Ok(());
+    }
+    Err(describe_stash_apply_failure(
+        index,
+        pop,
+        &output.stdout,
+        &output.stderr,
+    ))
+}
+
+/// Why an auto-stash could not be popped; the stash entry is left in place.
+#[derive(Debug)]
+pub(super) struct AutoStashPopFailure {
+    pub(super) stash_ref: String,
+    pub(super) conflicts: Vec<String>,
+    pub(super) detail: String,
+}
+
+/// Stashes all changes, untracked files included, so an operation can run on
+/// a clean tree. Returns the stash commit, or `None` when there was nothing
+/// to stash.
+pub(super) async fn push_auto_stash(
+    repo_root: &Path,
+    message: &str,
+) -> Result<Option<String>, String> {
+    let output = run_token_isolated_command(
+        repo_root,
+        "git",
+        &git_args(&["stash", "push", "--include-untracked", "--message", message]),
+        &[],
+    )
+    .await?;
+    if !output.success {
+        return Err(describe_stash_push_failure(&output.stdout, &output.stderr));
+    }
+    if output.stdout.contains("No local changes to save") {
+        return Ok(None);
+    }
+    let repo = open_repository_at_root(repo_root)?;
+    let oid = repo
+        .refname_to_id("refs/stash")
+        .map_err(|e| format!("Failed to read the new stash entry: {e}"))?;
+    Ok(Some(oid.to_string()))
+}
+
+/// Current index of a stash commit; indices shift as stashes come and go.
+fn stash_index_of(repo_root: &Path, stash_sha: &str) -> Result<Option<usize>, String> {
+    let mut repo = open_repository_at_root(repo_root)?;
+    let mut found = None;
+    repo.stash_foreach(|index, _, oid| {
+        if oid.to_string() == stash_sha {
+            found = Some(index);
+            return false;
+        }
+        true
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(found)
+}
+
+/// Pops the stash made by [`push_auto_stash`].
+pub(super) async fn pop_auto_stash(
+    repo_root: &Path,
+    stash_sha: &str,
+) -> Result<(), AutoStashPopFailure> {
+    let short_sha: String = stash_sha.chars().take(7).collect();
+    let index = match stash_index_of(repo_root, stash_sha) {
+        Ok(Some(index)) => index,
+        Ok(None) => {
+            return Err(AutoStashPopFailure {
+                stash_ref: short_sha.clone(),
+                conflicts: Vec::new(),
+                detail: format!("Stash entry {short_sha} no longer exists."),
+            })
+        }
+        Err(detail) => {
+            return Err(AutoStashPopFailure {
+                stash_ref: short_sha,
+                conflicts: Vec::new(),
+                detail,
+            })
+        }
+    };
+    let reference = stash_ref(index);
+    let output = match run_token_isolated_command(
+        repo_root,
+        "git",
+        &git_args(&["stash", "pop", &reference]),
+        &[],
+    )
+    .await
+    {
+        Ok(output) => output,
+        Err(detail) => {
+            return Err(AutoStashPopFailure {
+                stash_ref: reference,
+                conflicts: Vec::new(),
+                detail,
+            })
+        }
+    };
+    if output.success {
+        return Ok(());
+    }
+    Err(AutoStashPopFailure {
+        conflicts: collect_conflicted_paths(&format!("{}\n{}", output.stdout, output.stderr)),
+        detail: describe_stash_apply_failure(index, true, &output.stdout, &output.stderr),
+        stash_ref: reference,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn stash_git_changes(
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let repo_root = stash_repo_root(&state, &workspace_id, Some("stash_git_changes")).await?;
+    let mut args = git_args(&["stash", "push"]);
+    if include_untracked.unwrap_or(false) {
+        args.push("--include-untracked".to_string());
+    }
+    if let Some(message) = trim_optional(message) {
+        args.push("--message".to_string());
+        args.push(message);
+    }
+    let output = run_token_isolated_command(&repo_root, "git", &args, &[]).await?;
+    if !output.success {
+        return Err(describe_stash_push_failure(&output.stdout, &output.stderr));
+    }
+    if output.stdout.contains("No local changes to save") {
+        return Err("No local changes to stash.".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn list_git_stashes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitStashEntry>, String> {
+    let repo_root = stash_repo_root(&state, &workspace_id, None).await?;
+    let mut repo = open_repository_at_root(&repo_root)?;
+    let mut stashes = Vec::new();
+    repo.stash_foreach(|index, message, oid| {
+        stashes.push((index, message.to_string(), *oid));
+        true
+    })
+    .map_err(|e| e.to_string())?;
+
+    Ok(stashes
+        .into_iter()
+        .map(|(index, message, oid)| GitStashEntry {
+            index,
+            branch: stash_branch_from_message(&message),
+            timestamp: repo
+                .find_commit(oid)
+                .map(|commit| commit.time().seconds())
+                .unwrap_or(0),
+            message,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub(crate) async fn apply_git_stash(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let repo_root = stash_repo_root(&state, &workspace_id, Some("apply_git_stash")).await?;
+    run_stash_apply(&repo_root, index, false).await
+}
+
+#[tauri::command]
+pub(crate) async fn pop_git_stash(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let repo_root = stash_repo_root(&state, &workspace_id, Some("pop_git_stash")).await?;
+    run_stash_apply(&repo_root, index, true).await
+}
+
+#[tauri::command]
+pub(crate) async fn drop_git_stash(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let repo_root = stash_repo_root(&state, &workspace_id, Some("drop_git_stash")).await?;
+    let output = run_token_isolated_command(
+        &repo_root,
+        "git",
+        &git_args(&["stash", "drop", &stash_ref(index)]),
+        &[],
+    )
+    .await?;
+    if output.success {
+        return Ok(());
+    }
+    if output.stderr.contains("is not a valid reference")
+        || output.stderr.contains("No stash entries found")
+    {
+        return Err(format!("Stash entry {index} not found."));
+    }
+    Err(describe_git_failure(
+        &output.stdout,
+        &output.stderr,
+        "Git stash drop failed.",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stash_branch_is_parsed_from_message() {
+        assert_eq!(
+            stash_branch_from_message("WIP on main: 1a2b3c4 Fix parser"),
+            Some("main".to_string())
+        );
+        assert_eq!(
+            stash_branch_from_message("On feature/login: before rebase"),
+            Some("feature/login".to_string())
+        );
+        assert_eq!(
+            stash_branch_from_message("WIP on (no branch): 1a2b3c4 detached"),
+            None
+        );
+        assert_eq!(stash_branch_from_message("custom message"), None);
+    }
+
+    #[test]
+    fn stash_conflicts_are_described_with_paths() {
+        let stdout = "Auto-merging src/lib.rs\nCONFLICT (content): Merge conflict in src/lib.rs\n";
+        let stderr = "The stash entry is kept in case you need it again.";
+
+        let message = describe_stash_apply_failure(0, true, stdout, stderr);
+
+        assert_eq!(
+            message,
+            "Stash applied with conflicts in src/lib.rs. Resolve the conflicts and stage the files. The stash entry was kept; drop it once the conflicts are resolved."
+        );
+    }
+
+    #[test]
+    fn stash_overwrite_and_missing_entry_errors_are_readable() {
+        let stderr = "error: Your local changes to the following files would be overwritten by merge:\n\tREADME.md\n\tsrc/main.rs\nPlease commit your changes or stash them before you merge.\nAborting";
+        assert_eq!(
+            describe_stash_apply_failure(1, false, "", stderr),
+            "Applying the stash would overwrite local changes to README.md, src/main.rs. Commit, stash or discard them first."
+        );
+        assert_eq!(
+            describe_stash_apply_failure(4, false, "", "error: stash@{4} is not a valid reference"),
+            "Stash entry 4 not found."
+        );
+        assert_eq!(
+            describe_stash_apply_failure(0, false, "", "notes.txt already exists, no checkout\nerror: could not restore untracked files from stash"),
+            "The stash contains untracked files that already exist in the working tree: notes.txt. Move or delete them first."
+        );
+    }
+}
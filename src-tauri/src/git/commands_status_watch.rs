@@ -0,0 +1,362 @@
+//! Background git status for a workspace: a filesystem watcher on the
+//! worktree and git dir recomputes the status after changes settle and emits
+//! it only when it differs, so the UI does not have to poll `get_git_status`.
+
+use super::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use notify::{Config as NotifyConfig, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, oneshot, Mutex};
+
+const GIT_STATUS_CHANGED_EVENT: &str = "git-status-changed";
+/// Quiet period after the last filesystem event before the status is
+/// recomputed; checkouts and builds touch many files in bursts.
+const GIT_STATUS_WATCH_DEBOUNCE_MS: u64 = 400;
+
+struct GitStatusWatcher {
+    stop_tx: Option<oneshot::Sender<()>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl GitStatusWatcher {
+    async fn stop(mut self) {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+        self.task.abort();
+        let _ = self.task.await;
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct GitStatusWatchRuntime {
+    watchers: HashMap<String, GitStatusWatcher>,
+}
+
+/// Directories a status watcher listens to: the worktree plus any git dirs
+/// outside it (linked worktrees keep theirs in the main repository).
+struct WatchedRepo {
+    repo_root: PathBuf,
+    git_dirs: Vec<PathBuf>,
+}
+
+impl WatchedRepo {
+    fn open(repo_root: &Path) -> Result<Self, String> {
+        let repo = open_repository_at_root(repo_root)?;
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let mut git_dirs = vec![canonical(repo.path())];
+        let common_dir = canonical(repo.commondir());
+        if !git_dirs.contains(&common_dir) {
+            git_dirs.push(common_dir);
+        }
+        Ok(Self {
+            repo_root: canonical(repo_root),
+            git_dirs,
+        })
+    }
+
+    /// Whether a change at `path` can affect the status. Object writes and
+    /// reflog/lock churn inside the git dir are skipped (the ref or index
+    /// update that follows is what matters), as are ignored worktree files.
+    fn is_relevant_change(&self, path: &Path, is_ignored: impl Fn(&Path) -> bool) -> bool {
+        if let Some(relative) = self
+            .git_dirs
+            .iter()
+            .find_map(|git_dir| path.strip_prefix(git_dir).ok())
+        {
+            let first = relative
+                .components()
+                .next()
+                .map(|component| component.as_os_str().to_string_lossy().to_string());
+            let is_lock = relative
+                .extension()
+                .is_some_and(|extension| extension == "lock");
+            return !is_lock && !matches!(first.as_deref(), Some("objects") | Some("logs"));
+        }
+        match path.strip_prefix(&self.repo_root) {
+            Ok(relative) if relative.as_os_str().is_empty() => true,
+            Ok(relative) => !is_ignored(relative),
+            // Paths reported in an unexpected form are re-checked rather than
+            // dropped.
+            Err(_) => true,
+        }
+    }
+
+    /// The worktree plus git dirs not already covered by a recursive watch.
+    fn watch_dirs(&self) -> Vec<&Path> {
+        let mut dirs = vec![self.repo_root.as_path()];
+        for git_dir in &self.git_dirs {
+            if !dirs.iter().any(|dir| git_dir.starts_with(dir)) {
+                dirs.retain(|dir| *dir == self.repo_root || !dir.starts_with(git_dir));
+                dirs.push(git_dir);
+            }
+        }
+        dirs
+    }
+}
+
+fn status_hash(snapshot: &serde_json::Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    snapshot.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Recomputes the status when any of `paths` is relevant; `None` when the
+/// batch only touched ignored files or git internals.
+fn refresh_status(
+    watched: &WatchedRepo,
+    paths: &[PathBuf],
+) -> Result<Option<serde_json::Value>, String> {
+    let repo = open_repository_at_root(&watched.repo_root)?;
+    let relevant = paths.iter().any(|path| {
+        watched.is_relevant_change(path, |relative| {
+            repo.is_path_ignored(relative).unwrap_or(false)
+        })
+    });
+    if !relevant {
+        return Ok(None);
+    }
+    git_status_snapshot(&watched.repo_root).map(Some)
+}
+
+fn create_status_watcher(
+    watched: &WatchedRepo,
+) -> Result<
+    (
+        RecommendedWatcher,
+        mpsc::UnboundedReceiver<notify::Result<Event>>,
+    ),
+    String,
+> {
+    let (event_tx, event_rx) = mpsc::unbounded_channel::<notify::Result<Event>>();
+    let mut watcher = RecommendedWatcher::new(
+        move |result| {
+            let _ = event_tx.send(result);
+        },
+        NotifyConfig::default(),
+    )
+    .map_err(|err| format!("Failed to initialize git status watcher: {err}"))?;
+    for dir in watched.watch_dirs() {
+        watcher
+            .watch(dir, RecursiveMode::Recursive)
+            .map_err(|err| format!("Failed to watch {}: {err}", dir.display()))?;
+    }
+    Ok((watcher, event_rx))
+}
+
+async fn run_status_watch_loop(
+    app: AppHandle,
+    workspace_id: String,
+    watched: Arc<WatchedRepo>,
+    watcher: RecommendedWatcher,
+    mut event_rx: mpsc::UnboundedReceiver<notify::Result<Event>>,
+    mut last_hash: u64,
+    mut stop_rx: oneshot::Receiver<()>,
+) {
+    let debounce = Duration::from_millis(GIT_STATUS_WATCH_DEBOUNCE_MS);
+    'watch: loop {
+        let mut pending = Vec::new();
+        tokio::select! {
+            _ = &mut stop_rx => break,
+            event = event_rx.recv() => match event {
+                Some(Ok(event)) => pending.extend(event.paths),
+                Some(Err(_)) => continue,
+                None => break,
+            },
+        }
+        loop {
+            tokio::select! {
+                _ = &mut stop_rx => break 'watch,
+                event = event_rx.recv() => match event {
+                    Some(Ok(event)) => pending.extend(event.paths),
+                    Some(Err(_)) => {}
+                    None => break 'watch,
+                },
+                _ = tokio::time::sleep(debounce) => break,
+            }
+        }
+
+        let refresh_target = Arc::clone(&watched);
+        let refreshed =
+            tokio::task::spawn_blocking(move || refresh_status(&refresh_target, &pending)).await;
+        let Ok(Ok(Some(mut snapshot))) = refreshed else {
+            continue;
+        };
+        let hash = status_hash(&snapshot);
+        if hash == last_hash {
+            continue;
+        }
+        last_hash = hash;
+        if let Some(object) = snapshot.as_object_mut() {
+            object.insert("workspaceId".to_string(), json!(workspace_id));
+        }
+        let _ = app.emit(GIT_STATUS_CHANGED_EVENT, snapshot);
+    }
+    drop(watcher);
+}
+
+async fn stop_watcher(runtime: &Mutex<GitStatusWatchRuntime>, workspace_id: &str) {
+    let removed = runtime.lock().await.watchers.remove(workspace_id);
+    if let Some(watcher) = removed {
+        watcher.stop().await;
+    }
+}
+
+/// Tears down the workspace's status watcher, if any. Called when the
+/// workspace is removed.
+pub(crate) async fn stop_git_status_watch_for_workspace(state: &AppState, workspace_id: &str) {
+    stop_watcher(&state.git_status_watch_runtime, workspace_id).await;
+}
+
+/// Recomputes the workspace's status and emits it as `git-status-changed`
+/// right away, for refreshes requested outside the UI.
+pub(crate) async fn emit_git_status_for_workspace(
+    state: &AppState,
+    app: &AppHandle,
+    workspace_id: &str,
+) -> Result<(), String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let mut snapshot = if path_has_git_repository_marker(&repo_root) {
+        tokio::task::spawn_blocking(move || git_status_snapshot(&repo_root))
+            .await
+            .map_err(|e| format!("Git status task failed: {e}"))??
+    } else {
+        empty_git_status_snapshot(false)
+    };
+    if let Some(object) = snapshot.as_object_mut() {
+        object.insert("workspaceId".to_string(), json!(workspace_id));
+    }
+    app.emit(GIT_STATUS_CHANGED_EVENT, snapshot)
+        .map_err(|error| error.to_string())
+}
+
+/// Starts (or restarts) watching a workspace and returns its current status.
+/// Later changes are emitted as `git-status-changed`, with the
+/// `get_git_status` payload plus `workspaceId`.
+#[tauri::command]
+pub(crate) async fn git_status_watch_start(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<serde_json::Value, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    stop_watcher(&state.git_status_watch_runtime, &workspace_id).await;
+    if !path_has_git_repository_marker(&repo_root) {
+        return Ok(empty_git_status_snapshot(false));
+    }
+
+    let (watched, snapshot) = tokio::task::spawn_blocking(move || {
+        let watched = WatchedRepo::open(&repo_root)?;
+        let snapshot = git_status_snapshot(&watched.repo_root)?;
+        Ok::<_, String>((watched, snapshot))
+    })
+    .await
+    .map_err(|e| format!("Git status task failed: {e}"))??;
+    let (watcher, event_rx) = create_status_watcher(&watched)?;
+    let (stop_tx, stop_rx) = oneshot::channel();
+    let task = tokio::spawn(run_status_watch_loop(
+        app,
+        workspace_id.clone(),
+        Arc::new(watched),
+        watcher,
+        event_rx,
+        status_hash(&snapshot),
+        stop_rx,
+    ));
+    let watcher = GitStatusWatcher {
+        stop_tx: Some(stop_tx),
+        task,
+    };
+    // Concurrent starts race between the stop above and this insert, and
+    // the workspace may have been removed meanwhile: register under the
+    // workspaces lock so removal cannot slip in, and stop whatever watcher
+    // the insert displaces.
+    let displaced = {
+        let workspaces = state.workspaces.lock().await;
+        if !workspaces.contains_key(&workspace_id) {
+            drop(workspaces);
+            watcher.stop().await;
+            return Err("workspace not found".to_string());
+        }
+        state
+            .git_status_watch_runtime
+            .lock()
+            .await
+            .watchers
+            .insert(workspace_id, watcher)
+    };
+    if let Some(displaced) = displaced {
+        displaced.stop().await;
+    }
+    Ok(snapshot)
+}
+
+#[tauri::command]
+pub(crate) async fn git_status_watch_stop(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    stop_watcher(&state.git_status_watch_runtime, &workspace_id).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_ignored_files_and_git_internals() {
+        let watched = WatchedRepo {
+            repo_root: PathBuf::from("/repo"),
+            git_dirs: vec![PathBuf::from("/repo/.git")],
+        };
+        let is_ignored = |path: &Path| path.starts_with("target");
+        for (path, expected) in [
+            ("/repo/src/main.rs", true),
+            ("/repo/target/debug/app", false),
+            ("/repo/.git/index", true),
+            ("/repo/.git/HEAD", true),
+            ("/repo/.git/refs/heads/main", true),
+            ("/repo/.git/index.lock", false),
+            ("/repo/.git/objects/ab/cdef", false),
+            ("/repo/.git/logs/HEAD", false),
+            ("/elsewhere/file", true),
+        ] {
+            assert_eq!(
+                watched.is_relevant_change(Path::new(path), is_ignored),
+                expected,
+                "{path}"
+            );
+        }
+
+        let linked = WatchedRepo {
+            repo_root: PathBuf::from("/linked"),
+            git_dirs: vec![
+                PathBuf::from("/repo/.git/worktrees/linked"),
+                PathBuf::from("/repo/.git"),
+            ],
+        };
+        assert_eq!(
+            linked.watch_dirs(),
+            vec![Path::new("/linked"), Path::new("/repo/.git")]
+        );
+        assert_eq!(watched.watch_dirs(), vec![Path::new("/repo")]);
+    }
+}
@@ -0,0 +1,227 @@
+use super::*;
+
+use serde::Serialize;
+
+use crate::types::GitSubmoduleHeads;
+
+/// Status code `get_git_status` reports for a changed submodule in place of
+/// the usual file codes.
+pub(super) const SUBMODULE_STATUS: &str = "S";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitSubmodule {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) url: Option<String>,
+    /// Commit the superproject records for the submodule at HEAD.
+    pub(crate) recorded_sha: Option<String>,
+    /// Commit checked out in the submodule; unset until it is initialized.
+    pub(crate) current_sha: Option<String>,
+    pub(crate) initialized: bool,
+}
+
+/// The commits a submodule points at in HEAD, the index and its checkout.
+pub(super) struct SubmoduleIds {
+    head: Option<Oid>,
+    index: Option<Oid>,
+    workdir: Option<Oid>,
+}
+
+fn heads(old: Option<Oid>, new: Option<Oid>) -> GitSubmoduleHeads {
+    GitSubmoduleHeads {
+        old_sha: old.map(|oid| oid.to_string()),
+        new_sha: new.map(|oid| oid.to_string()),
+    }
+}
+
+impl SubmoduleIds {
+    pub(super) fn staged(&self) -> GitSubmoduleHeads {
+        heads(self.head, self.index)
+    }
+
+    pub(super) fn unstaged(&self) -> GitSubmoduleHeads {
+        heads(self.index, self.workdir)
+    }
+
+    pub(super) fn combined(&self) -> GitSubmoduleHeads {
+        heads(self.head, self.workdir.or(self.index))
+    }
+}
+
+/// Submodules keyed by their normalized path. A broken `.gitmodules` yields
+/// an empty map so status still lists the remaining files.
+pub(super) fn submodule_ids_by_path(repo: &Repository) -> HashMap<String, SubmoduleIds> {
+    let Ok(submodules) = repo.submodules() else {
+        return HashMap::new();
+    };
+    submodules
+        .iter()
+        .map(|submodule| {
+            (
+                normalize_git_path(&submodule.path().to_string_lossy()),
+                SubmoduleIds {
+                    head: submodule.head_id(),
+                    index: submodule.index_id(),
+                    workdir: submodule.workdir_id(),
+                },
+            )
+        })
+        .collect()
+}
+
+fn list_submodules(repo: &Repository) -> Result<Vec<GitSubmodule>, String> {
+    let submodules = repo.submodules().map_err(|e| e.to_string())?;
+    let mut entries: Vec<GitSubmodule> = submodules
+        .iter()
+        .map(|submodule| {
+            let current_sha = submodule.workdir_id().map(|oid| oid.to_string());
+            GitSubmodule {
+                name: submodule.name().unwrap_or("").to_string(),
+                path: normalize_git_path(&submodule.path().to_string_lossy()),
+                url: submodule.url().map(str::to_string),
+                recorded_sha: submodule
+                    .head_id()
+                    .or_else(|| submodule.index_id())
+                    .map(|oid| oid.to_string()),
+                initialized: current_sha.is_some(),
+                current_sha,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+fn submodule_update_args(init: bool, recursive: bool) -> Vec<&'static str> {
+    let mut args = vec!["submodule", "update"];
+    if init {
+        args.push("--init");
+    }
+    if recursive {
+        args.push("--recursive");
+    }
+    args
+}
+
+#[tauri::command]
+pub(crate) async fn list_git_submodules(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitSubmodule>, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    list_submodules(&open_repository_at_root(&repo_root)?)
+}
+
+#[tauri::command]
+pub(crate) async fn update_git_submodules(
+    workspace_id: String,
+    init: Option<bool>,
+    recursive: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "update_git_submodules").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let args = submodule_update_args(init.unwrap_or(true), recursive.unwrap_or(true));
+    run_git_command(&repo_root, &args).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_all(repo: &Repository, message: &str) -> Oid {
+        let mut index = repo.index().expect("index");
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .expect("add all");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+        let signature = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .expect("commit")
+    }
+
+    #[test]
+    fn lists_submodules_and_reports_moved_heads() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        let upstream_root = root.join("upstream");
+        let super_root = root.join("super");
+        fs::create_dir_all(&upstream_root).expect("create upstream dir");
+        fs::create_dir_all(&super_root).expect("create super dir");
+
+        let upstream = Repository::init(&upstream_root).expect("init upstream");
+        fs::write(upstream_root.join("lib.txt"), "v1\n").expect("write lib");
+        let recorded = commit_all(&upstream, "v1");
+
+        let superproject = Repository::init(&super_root).expect("init super");
+        fs::write(super_root.join("README.md"), "super\n").expect("write readme");
+        let url = upstream_root.to_string_lossy().to_string();
+        let mut submodule = superproject
+            .submodule(&url, Path::new("vendor/lib"), true)
+            .expect("add submodule");
+        submodule.clone(None).expect("clone submodule");
+        submodule.add_finalize().expect("finalize submodule");
+        commit_all(&superproject, "add submodule");
+
+        let checkout = Repository::open(super_root.join("vendor/lib")).expect("open checkout");
+        fs::write(super_root.join("vendor/lib/lib.txt"), "v2\n").expect("edit lib");
+        let moved = commit_all(&checkout, "v2");
+
+        let listed = list_submodules(&superproject).expect("list submodules");
+        assert_eq!(
+            listed,
+            vec![GitSubmodule {
+                name: "vendor/lib".to_string(),
+                path: "vendor/lib".to_string(),
+                url: Some(url),
+                recorded_sha: Some(recorded.to_string()),
+                current_sha: Some(moved.to_string()),
+                initialized: true,
+            }]
+        );
+
+        let ids = submodule_ids_by_path(&superproject);
+        let lib = ids.get("vendor/lib").expect("submodule ids");
+        assert_eq!(
+            lib.unstaged(),
+            GitSubmoduleHeads {
+                old_sha: Some(recorded.to_string()),
+                new_sha: Some(moved.to_string()),
+            }
+        );
+        assert_eq!(lib.staged().old_sha, lib.staged().new_sha);
+
+        assert_eq!(
+            submodule_update_args(true, false),
+            vec!["submodule", "update", "--init"]
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,263 @@
+use super::*;
+
+use serde::Serialize;
+
+const SHORT_SHA_LEN: usize = 7;
+const DEFAULT_TAG_REMOTE: &str = "origin";
+
+/// A tag and the commit it points at. Tagger fields are only set for
+/// annotated tags; `timestamp` falls back to the commit time otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitTag {
+    pub(crate) name: String,
+    pub(crate) target_sha: String,
+    pub(crate) short_sha: String,
+    pub(crate) annotated: bool,
+    pub(crate) tagger: Option<String>,
+    pub(crate) tagger_email: Option<String>,
+    pub(crate) message: Option<String>,
+    pub(crate) timestamp: i64,
+}
+
+fn tag_ref_name(name: &str) -> String {
+    format!("refs/tags/{name}")
+}
+
+/// Newest first, so release tags lead the list.
+fn list_tags(repo: &Repository) -> Result<Vec<GitTag>, String> {
+    let names = repo.tag_names(None).map_err(|e| e.to_string())?;
+    let mut tags = Vec::new();
+    for name in names.iter().flatten() {
+        let Ok(reference) = repo.find_reference(&tag_ref_name(name)) else {
+            continue;
+        };
+        let annotation = reference.target().and_then(|oid| repo.find_tag(oid).ok());
+        let commit = reference.peel_to_commit().ok();
+        let target_sha = match (&commit, &annotation) {
+            (Some(commit), _) => commit.id().to_string(),
+            (None, Some(tag)) => tag.target_id().to_string(),
+            (None, None) => reference
+                .target()
+                .map(|oid| oid.to_string())
+                .unwrap_or_default(),
+        };
+        let tagger = annotation.as_ref().and_then(|tag| tag.tagger());
+        let timestamp = tagger
+            .as_ref()
+            .map(|signature| signature.when().seconds())
+            .or_else(|| commit.as_ref().map(|commit| commit.time().seconds()))
+            .unwrap_or(0);
+        tags.push(GitTag {
+            name: name.to_string(),
+            short_sha: target_sha.chars().take(SHORT_SHA_LEN).collect(),
+            target_sha,
+            annotated: annotation.is_some(),
+            tagger: tagger
+                .as_ref()
+                .and_then(|signature| signature.name().map(str::to_string)),
+            tagger_email: tagger
+                .as_ref()
+                .and_then(|signature| signature.email().map(str::to_string)),
+            message: annotation
+                .as_ref()
+                .and_then(|tag| tag.message())
+                .map(|message| message.trim().to_string())
+                .filter(|message| !message.is_empty()),
+            timestamp,
+        });
+    }
+    tags.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then(a.name.cmp(&b.name)));
+    Ok(tags)
+}
+
+/// Tags `target` (any revision, default `HEAD`). An empty message creates a
+/// lightweight tag, anything else an annotated one.
+fn create_tag(
+    repo: &Repository,
+    name: &str,
+    target: Option<&str>,
+    message: Option<&str>,
+) -> Result<(), String> {
+    let name = validate_tag_name(name)?;
+    if repo.find_reference(&tag_ref_name(&name)).is_ok() {
+        return Err(format!("Tag already exists: {name}"));
+    }
+    let rev = target.unwrap_or("HEAD");
+    let object = repo
+        .revparse_single(rev)
+        .and_then(|object| object.peel(git2::ObjectType::Commit))
+        .map_err(|e| format!("Unknown revision {rev}: {}", e.message()))?;
+    let created = match message.map(str::trim).filter(|message| !message.is_empty()) {
+        Some(message) => {
+            let tagger = repo.signature().map_err(|e| e.to_string())?;
+            repo.tag(&name, &object, &tagger, message, false)
+        }
+        None => repo.tag_lightweight(&name, &object, false),
+    };
+    created.map(|_| ()).map_err(|e| e.to_string())
+}
+
+fn delete_tag(repo: &Repository, name: &str) -> Result<(), String> {
+    let name = validate_tag_name(name)?;
+    if repo.find_reference(&tag_ref_name(&name)).is_err() {
+        return Err(format!("Tag not found: {name}"));
+    }
+    repo.tag_delete(&name).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn list_git_tags(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitTag>, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    list_tags(&open_repository_at_root(&repo_root)?)
+}
+
+#[tauri::command]
+pub(crate) async fn create_git_tag(
+    workspace_id: String,
+    name: String,
+    target: Option<String>,
+    message: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "create_git_tag").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = open_repository_at_root(&repo_root)?;
+    let target = trim_optional(target);
+    create_tag(&repo, &name, target.as_deref(), message.as_deref())
+}
+
+#[tauri::command]
+pub(crate) async fn delete_git_tag(
+    workspace_id: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "delete_git_tag").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    delete_tag(&open_repository_at_root(&repo_root)?, &name)
+}
+
+#[tauri::command]
+pub(crate) async fn push_git_tag(
+    workspace_id: String,
+    name: String,
+    remote: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_workspace_id_writable(&state.workspaces, &workspace_id, "push_git_tag").await?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let name = validate_tag_name(&name)?;
+    let remote = trim_optional(remote).unwrap_or_else(|| DEFAULT_TAG_REMOTE.to_string());
+    if remote.starts_with('-') {
+        return Err(format!("Invalid remote name: {remote}"));
+    }
+    let refspec = tag_ref_name(&name);
+    {
+        let repo = open_repository_at_root(&repo_root)?;
+        if repo.find_reference(&refspec).is_err() {
+            return Err(format!("Tag not found: {name}"));
+        }
+    }
+    run_git_command(&repo_root, &["push", &remote, &refspec]).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_lists_and_deletes_tags() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create repo dir");
+        let repo = Repository::init(&root).expect("init repo");
+        let signature = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let tree = repo
+            .find_tree(repo.index().expect("index").write_tree().expect("tree"))
+            .expect("find tree");
+        let first = repo
+            .commit(Some("HEAD"), &signature, &signature, "first", &tree, &[])
+            .expect("first commit");
+        let first_commit = repo.find_commit(first).expect("find first");
+        let second = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "second",
+                &tree,
+                &[&first_commit],
+            )
+            .expect("second commit");
+        let mut config = repo.config().expect("config");
+        config.set_str("user.name", "Tagger").expect("user.name");
+        config
+            .set_str("user.email", "tagger@example.com")
+            .expect("user.email");
+
+        create_tag(&repo, "v1.0.0", Some(&first.to_string()), None).expect("lightweight");
+        create_tag(&repo, " v1.1.0 ", None, Some("Release 1.1\n")).expect("annotated");
+        assert!(create_tag(&repo, "v1.0.0", None, None).is_err());
+        assert!(create_tag(&repo, "bad..name", None, None).is_err());
+        assert!(create_tag(&repo, "v2", Some("missing-rev"), None).is_err());
+
+        let tags = list_tags(&repo).expect("list tags");
+        let by_name = |name: &str| {
+            tags.iter()
+                .find(|tag| tag.name == name)
+                .cloned()
+                .expect("tag present")
+        };
+        let lightweight = by_name("v1.0.0");
+        assert!(!lightweight.annotated);
+        assert_eq!(lightweight.target_sha, first.to_string());
+        assert_eq!(lightweight.tagger, None);
+        let annotated = by_name("v1.1.0");
+        assert!(annotated.annotated);
+        assert_eq!(annotated.target_sha, second.to_string());
+        assert_eq!(annotated.tagger.as_deref(), Some("Tagger"));
+        assert_eq!(annotated.message.as_deref(), Some("Release 1.1"));
+
+        delete_tag(&repo, "v1.0.0").expect("delete tag");
+        assert!(delete_tag(&repo, "v1.0.0").is_err());
+        let remaining: Vec<String> = list_tags(&repo)
+            .expect("list after delete")
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect();
+        assert_eq!(remaining, vec!["v1.1.0".to_string()]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
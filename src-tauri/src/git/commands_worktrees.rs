@@ -0,0 +1,191 @@
+use super::*;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitWorktreeInfo {
+    pub(crate) path: String,
+    /// Checked-out branch, `None` when HEAD is detached.
+    pub(crate) branch: Option<String>,
+    pub(crate) head_sha: Option<String>,
+    /// The repository's own working directory rather than a linked worktree.
+    pub(crate) is_main: bool,
+    pub(crate) locked: bool,
+    pub(crate) lock_reason: Option<String>,
+    /// The worktree directory is gone and `git worktree prune` would drop it.
+    pub(crate) prunable: bool,
+}
+
+fn display_worktree_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        path.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Branch and commit named by the HEAD file in `admin_dir`. Reading the file
+/// directly keeps this working for worktrees whose directory is missing.
+fn worktree_head(repo: &Repository, admin_dir: &Path) -> (Option<String>, Option<String>) {
+    let Ok(head) = fs::read_to_string(admin_dir.join("HEAD")) else {
+        return (None, None);
+    };
+    let head = head.trim();
+    match head.strip_prefix("ref:").map(str::trim) {
+        Some(reference) => (
+            reference.strip_prefix("refs/heads/").map(str::to_string),
+            repo.refname_to_id(reference)
+                .ok()
+                .map(|oid| oid.to_string()),
+        ),
+        None => (None, Oid::from_str(head).ok().map(|oid| oid.to_string())),
+    }
+}
+
+/// The main working directory followed by every linked worktree.
+pub(super) fn list_worktrees(repo: &Repository) -> Result<Vec<GitWorktreeInfo>, String> {
+    let common_dir = repo.commondir().to_path_buf();
+    let main_workdir = if repo.is_worktree() {
+        Repository::open(&common_dir)
+            .ok()
+            .and_then(|main| main.workdir().map(Path::to_path_buf))
+    } else {
+        repo.workdir().map(Path::to_path_buf)
+    };
+
+    let mut worktrees = Vec::new();
+    if let Some(workdir) = main_workdir {
+        let (branch, head_sha) = worktree_head(repo, &common_dir);
+        worktrees.push(GitWorktreeInfo {
+            path: display_worktree_path(&workdir),
+            branch,
+            head_sha,
+            is_main: true,
+            locked: false,
+            lock_reason: None,
+            prunable: false,
+        });
+    }
+
+    let names = repo.worktrees().map_err(|e| e.to_string())?;
+    for name in names.iter().flatten() {
+        let Ok(worktree) = repo.find_worktree(name) else {
+            continue;
+        };
+        let (branch, head_sha) = worktree_head(repo, &common_dir.join("worktrees").join(name));
+        let lock_reason = match worktree.is_locked() {
+            Ok(git2::WorktreeLockStatus::Locked(reason)) => Some(reason.unwrap_or_default()),
+            _ => None,
+        };
+        let locked = lock_reason.is_some();
+        worktrees.push(GitWorktreeInfo {
+            path: display_worktree_path(worktree.path()),
+            branch,
+            head_sha,
+            is_main: false,
+            locked,
+            lock_reason: lock_reason.filter(|reason| !reason.trim().is_empty()),
+            prunable: !locked && (worktree.validate().is_err() || !worktree.path().exists()),
+        });
+    }
+    Ok(worktrees)
+}
+
+#[tauri::command]
+pub(crate) async fn list_git_worktrees(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitWorktreeInfo>, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = open_repository_at_root(&repo_root)?;
+    list_worktrees(&repo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn lists_main_and_linked_worktrees_with_their_heads() {
+        let base = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        let root = base.join("main");
+        fs::create_dir_all(&root).expect("create repo dir");
+        let repo = Repository::init(&root).expect("init repo");
+        fs::write(root.join("a.txt"), "a\n").expect("write a");
+        run_git_command(&root, &["add", "a.txt"])
+            .await
+            .expect("add a");
+        run_git_command(
+            &root,
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "-c",
+                "commit.gpgsign=false",
+                "commit",
+                "-m",
+                "first",
+            ],
+        )
+        .await
+        .expect("commit");
+        let linked = base.join("linked");
+        let gone = base.join("gone");
+        let linked_path = linked.to_string_lossy().to_string();
+        let gone_path = gone.to_string_lossy().to_string();
+        run_git_command(&root, &["worktree", "add", "-b", "feature", &linked_path])
+            .await
+            .expect("add linked worktree");
+        run_git_command(
+            &root,
+            &["worktree", "lock", "--reason", "usb", &linked_path],
+        )
+        .await
+        .expect("lock worktree");
+        run_git_command(&root, &["worktree", "add", "--detach", &gone_path])
+            .await
+            .expect("add detached worktree");
+        fs::remove_dir_all(&gone).expect("remove detached worktree dir");
+
+        let head_sha = repo
+            .head()
+            .ok()
+            .and_then(|head| head.target())
+            .map(|oid| oid.to_string());
+        let mut worktrees = list_worktrees(&repo).expect("list worktrees");
+        worktrees.sort_by(|left, right| left.path.cmp(&right.path));
+        assert_eq!(worktrees.len(), 3);
+
+        let gone_entry = &worktrees[0];
+        assert!(gone_entry.path.ends_with("/gone"));
+        assert_eq!(gone_entry.branch, None);
+        assert_eq!(gone_entry.head_sha, head_sha);
+        assert!(gone_entry.prunable);
+
+        let linked_entry = &worktrees[1];
+        assert!(linked_entry.path.ends_with("/linked"));
+        assert_eq!(linked_entry.branch.as_deref(), Some("feature"));
+        assert!(linked_entry.locked);
+        assert_eq!(linked_entry.lock_reason.as_deref(), Some("usb"));
+        assert!(!linked_entry.prunable);
+
+        let main_entry = &worktrees[2];
+        assert!(main_entry.is_main);
+        assert_eq!(main_entry.head_sha, head_sha);
+        assert!(main_entry.branch.is_some());
+
+        let _ = fs::remove_dir_all(base);
+    }
+}
@@ -0,0 +1,441 @@
+//! GitHub access for the issue and pull request panels.
+//!
+//! The `gh` CLI is tried first; when it is missing or fails, the same data is
+//! fetched from the REST API, authenticated with the token stored under
+//! `github_token` in the `app` client store (public repositories also work
+//! without one).
+
+use std::future::Future;
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, ACCEPT, AUTHORIZATION, USER_AGENT};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::types::{
+    GitHubIssue, GitHubPullRequest, GitHubPullRequestAuthor, GitHubPullRequestComment,
+};
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+const GITHUB_API_TIMEOUT: Duration = Duration::from_secs(20);
+const GITHUB_API_VERSION: &str = "2022-11-28";
+const GITHUB_JSON_ACCEPT: &str = "application/vnd.github+json";
+const GITHUB_DIFF_ACCEPT: &str = "application/vnd.github.v3.diff";
+pub(super) const GITHUB_LIST_LIMIT: usize = 50;
+pub(super) const GITHUB_COMMENT_LIMIT: usize = 30;
+
+/// Why a `gh` invocation did not produce output.
+pub(super) enum GhError {
+    /// The binary could not be started, usually because it is not installed.
+    Unavailable(String),
+    /// `gh` ran and reported an error.
+    Failed(String),
+}
+
+/// Runs `gh` in `repo_root` and returns its stdout on success.
+pub(super) async fn run_gh(repo_root: &Path, args: &[&str]) -> Result<Vec<u8>, GhError> {
+    let output = crate::utils::async_command("gh")
+        .args(args)
+        .current_dir(repo_root)
+        .output()
+        .await
+        .map_err(|e| GhError::Unavailable(format!("Failed to run gh: {e}")))?;
+
+    if output.status.success() {
+        return Ok(output.stdout);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    if detail.is_empty() {
+        return Err(GhError::Failed("GitHub CLI command failed.".to_string()));
+    }
+    Err(GhError::Failed(detail.to_string()))
+}
+
+/// Returns the `gh` result, or falls back to `rest` when `gh` failed. If both
+/// fail, both errors are reported, the REST error first when `gh` could not
+/// even be started.
+pub(super) async fn gh_or_rest<T>(
+    gh: Result<T, GhError>,
+    rest: impl Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    let gh_error = match gh {
+        Ok(value) => return Ok(value),
+        Err(error) => error,
+    };
+    match (rest.await, gh_error) {
+        (Ok(value), _) => Ok(value),
+        (Err(rest_error), GhError::Unavailable(gh_error)) => {
+            Err(format!("{rest_error}\n{gh_error}"))
+        }
+        (Err(rest_error), GhError::Failed(gh_error)) => Err(format!(
+            "{gh_error}\nGitHub API fallback failed: {rest_error}"
+        )),
+    }
+}
+
+fn rate_limit_message(headers: &HeaderMap) -> Option<String> {
+    let remaining = headers
+        .get("x-ratelimit-remaining")
+        .and_then(|value| value.to_str().ok())?;
+    if remaining.trim() != "0" {
+        return None;
+    }
+    let reset = headers
+        .get("x-ratelimit-reset")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+        .map(|reset| {
+            format!(
+                " It resets at {}.",
+                reset.with_timezone(&chrono::Local).format("%H:%M")
+            )
+        })
+        .unwrap_or_default();
+    Some(format!(
+        "GitHub API rate limit exceeded.{reset} Store a GitHub personal access token to raise the limit."
+    ))
+}
+
+/// Maps a failed response to a message the panel can show as is.
+fn response_error(status: StatusCode, headers: &HeaderMap, body: &str) -> String {
+    if matches!(
+        status,
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    ) {
+        if let Some(message) = rate_limit_message(headers) {
+            return message;
+        }
+    }
+    match status {
+        StatusCode::UNAUTHORIZED => {
+            "GitHub rejected the stored token; it may be expired or revoked.".to_string()
+        }
+        StatusCode::NOT_FOUND => {
+            "GitHub repository not found, or the token cannot access it.".to_string()
+        }
+        _ => {
+            let detail = serde_json::from_str::<serde_json::Value>(body)
+                .ok()
+                .and_then(|value| value.get("message")?.as_str().map(str::to_string))
+                .unwrap_or_else(|| body.trim().to_string());
+            if detail.is_empty() {
+                format!("GitHub API request failed ({status}).")
+            } else {
+                format!("GitHub API request failed ({status}): {detail}")
+            }
+        }
+    }
+}
+
+async fn api_get_text(path: &str, accept: &str) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(GITHUB_API_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to configure HTTP client: {e}"))?;
+    let mut request = client
+        .get(format!("{GITHUB_API_BASE}{path}"))
+        .header(ACCEPT, accept)
+        .header(USER_AGENT, "ccgui")
+        .header("X-GitHub-Api-Version", GITHUB_API_VERSION);
+    if let Some(token) = crate::client_storage::github_token() {
+        request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("GitHub API request failed: {e}"))?;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("GitHub API request failed: {e}"))?;
+    if !status.is_success() {
+        return Err(response_error(status, &headers, &body));
+    }
+    Ok(body)
+}
+
+async fn api_get<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let body = api_get_text(path, GITHUB_JSON_ACCEPT).await?;
+    serde_json::from_str(&body).map_err(|e| format!("Unexpected GitHub API response: {e}"))
+}
+
+#[derive(Deserialize)]
+struct RestUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct RestIssue {
+    number: u64,
+    title: String,
+    html_url: String,
+    updated_at: String,
+    /// Present when the "issue" is actually a pull request.
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct RestBranchRef {
+    #[serde(rename = "ref")]
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RestPullRequest {
+    number: u64,
+    title: String,
+    html_url: String,
+    updated_at: String,
+    created_at: String,
+    #[serde(default)]
+    body: Option<String>,
+    head: RestBranchRef,
+    base: RestBranchRef,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    user: Option<RestUser>,
+}
+
+#[derive(Deserialize)]
+struct RestComment {
+    id: u64,
+    #[serde(default)]
+    body: Option<String>,
+    created_at: String,
+    #[serde(default)]
+    html_url: String,
+    #[serde(default)]
+    user: Option<RestUser>,
+}
+
+/// One entry of `/pulls/{n}/files`; shared with the `gh api` path, which
+/// returns the same JSON.
+#[derive(Deserialize)]
+pub(super) struct RestPullRequestFile {
+    pub(super) filename: String,
+    pub(super) status: String,
+    #[serde(default)]
+    pub(super) additions: u64,
+    #[serde(default)]
+    pub(super) deletions: u64,
+    #[serde(default)]
+    pub(super) patch: Option<String>,
+    #[serde(default)]
+    pub(super) previous_filename: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RestSearchTotal {
+    total_count: usize,
+}
+
+fn author(user: Option<RestUser>) -> Option<GitHubPullRequestAuthor> {
+    user.map(|user| GitHubPullRequestAuthor { login: user.login })
+}
+
+fn issues_from_rest(items: Vec<RestIssue>) -> Vec<GitHubIssue> {
+    items
+        .into_iter()
+        .filter(|item| item.pull_request.is_none())
+        .map(|item| GitHubIssue {
+            number: item.number,
+            title: item.title,
+            url: item.html_url,
+            updated_at: item.updated_at,
+        })
+        .collect()
+}
+
+fn pull_requests_from_rest(items: Vec<RestPullRequest>) -> Vec<GitHubPullRequest> {
+    items
+        .into_iter()
+        .map(|item| GitHubPullRequest {
+            number: item.number,
+            title: item.title,
+            url: item.html_url,
+            updated_at: item.updated_at,
+            created_at: item.created_at,
+            body: item.body.unwrap_or_default(),
+            head_ref_name: item.head.name,
+            base_ref_name: item.base.name,
+            is_draft: item.draft,
+            author: author(item.user),
+        })
+        .collect()
+}
+
+fn comments_from_rest(items: Vec<RestComment>) -> Vec<GitHubPullRequestComment> {
+    items
+        .into_iter()
+        .map(|item| GitHubPullRequestComment {
+            id: item.id,
+            body: item.body.unwrap_or_default(),
+            created_at: item.created_at,
+            url: item.html_url,
+            author: author(item.user),
+        })
+        .collect()
+}
+
+/// Open items matching `qualifier` (`is:issue` or `is:pr`) via the search
+/// API, which counts beyond the listed page.
+pub(super) async fn rest_open_total(repo_name: &str, qualifier: &str) -> Result<usize, String> {
+    let search: RestSearchTotal = api_get(&format!(
+        "/search/issues?q=repo:{repo_name}+{qualifier}+is:open&per_page=1"
+    ))
+    .await?;
+    Ok(search.total_count)
+}
+
+/// Open issues. The issues endpoint also returns pull requests, so the page
+/// can hold fewer than the limit once those are dropped.
+pub(super) async fn rest_issues(repo_name: &str) -> Result<Vec<GitHubIssue>, String> {
+    let items: Vec<RestIssue> = api_get(&format!(
+        "/repos/{repo_name}/issues?state=open&per_page={GITHUB_LIST_LIMIT}"
+    ))
+    .await?;
+    Ok(issues_from_rest(items))
+}
+
+pub(super) async fn rest_pull_requests(repo_name: &str) -> Result<Vec<GitHubPullRequest>, String> {
+    let items: Vec<RestPullRequest> = api_get(&format!(
+        "/repos/{repo_name}/pulls?state=open&per_page={GITHUB_LIST_LIMIT}"
+    ))
+    .await?;
+    Ok(pull_requests_from_rest(items))
+}
+
+pub(super) async fn rest_pull_request_diff(
+    repo_name: &str,
+    pr_number: u64,
+) -> Result<String, String> {
+    api_get_text(
+        &format!("/repos/{repo_name}/pulls/{pr_number}"),
+        GITHUB_DIFF_ACCEPT,
+    )
+    .await
+}
+
+pub(super) async fn rest_pull_request_files(
+    repo_name: &str,
+    pr_number: u64,
+    page: u32,
+    per_page: u32,
+) -> Result<Vec<RestPullRequestFile>, String> {
+    api_get(&format!(
+        "/repos/{repo_name}/pulls/{pr_number}/files?page={page}&per_page={per_page}"
+    ))
+    .await
+}
+
+pub(super) async fn rest_pull_request_comments(
+    repo_name: &str,
+    pr_number: u64,
+) -> Result<Vec<GitHubPullRequestComment>, String> {
+    let items: Vec<RestComment> = api_get(&format!(
+        "/repos/{repo_name}/issues/{pr_number}/comments?per_page={GITHUB_COMMENT_LIMIT}"
+    ))
+    .await?;
+    Ok(comments_from_rest(items))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn maps_rest_payloads_to_panel_types() {
+        let issues: Vec<RestIssue> = serde_json::from_str(
+            r#"[
+                {"number": 1, "title": "Bug", "html_url": "https://github.com/o/r/issues/1", "updated_at": "2024-01-02T00:00:00Z"},
+                {"number": 2, "title": "PR", "html_url": "https://github.com/o/r/pull/2", "updated_at": "2024-01-03T00:00:00Z", "pull_request": {}}
+            ]"#,
+        )
+        .expect("issues");
+        let issues = issues_from_rest(issues);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].url, "https://github.com/o/r/issues/1");
+
+        let pulls: Vec<RestPullRequest> = serde_json::from_str(
+            r#"[{
+                "number": 7, "title": "Feature", "html_url": "https://github.com/o/r/pull/7",
+                "updated_at": "2024-01-02T00:00:00Z", "created_at": "2024-01-01T00:00:00Z",
+                "body": null, "head": {"ref": "feature"}, "base": {"ref": "main"},
+                "draft": true, "user": {"login": "octo"}
+            }]"#,
+        )
+        .expect("pulls");
+        let pulls = pull_requests_from_rest(pulls);
+        assert_eq!(pulls[0].body, "");
+        assert_eq!(pulls[0].head_ref_name, "feature");
+        assert_eq!(pulls[0].base_ref_name, "main");
+        assert!(pulls[0].is_draft);
+        assert_eq!(
+            pulls[0].author.as_ref().map(|a| a.login.as_str()),
+            Some("octo")
+        );
+
+        let comments: Vec<RestComment> = serde_json::from_str(
+            r#"[{"id": 9, "body": "LGTM", "created_at": "2024-01-02T00:00:00Z", "html_url": "u", "user": null}]"#,
+        )
+        .expect("comments");
+        let comments = comments_from_rest(comments);
+        assert_eq!(comments[0].body, "LGTM");
+        assert!(comments[0].author.is_none());
+    }
+
+    #[test]
+    fn rate_limited_responses_get_a_clear_message() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1700000000"));
+        let message = response_error(StatusCode::FORBIDDEN, &headers, "{}");
+        assert!(message.starts_with("GitHub API rate limit exceeded."));
+        assert!(message.contains("resets at"));
+
+        let forbidden = response_error(
+            StatusCode::FORBIDDEN,
+            &HeaderMap::new(),
+            r#"{"message": "Resource not accessible"}"#,
+        );
+        assert_eq!(
+            forbidden,
+            "GitHub API request failed (403 Forbidden): Resource not accessible"
+        );
+    }
+
+    #[tokio::test]
+    async fn fallback_errors_keep_the_gh_reason() {
+        let missing = gh_or_rest::<()>(
+            Err(GhError::Unavailable(
+                "Failed to run gh: not found".to_string(),
+            )),
+            async { Err("GitHub API request failed (404 Not Found)".to_string()) },
+        )
+        .await;
+        assert_eq!(
+            missing.unwrap_err(),
+            "GitHub API request failed (404 Not Found)\nFailed to run gh: not found"
+        );
+
+        let fallback = gh_or_rest(Err(GhError::Failed("auth required".to_string())), async {
+            Ok(7)
+        })
+        .await;
+        assert_eq!(fallback, Ok(7));
+    }
+}
@@ -11,22 +11,33 @@ use tauri::State;
 use tokio::time::{timeout, Duration};
 
 use crate::git_utils::{
-    checkout_branch, commit_to_entry, diff_patch_to_string, diff_stats_for_path, image_mime_type,
-    list_git_roots as scan_git_roots, parse_github_repo, path_has_git_repository_marker,
-    resolve_git_root,
+    checkout_branch, commit_to_entry, diff_patch_to_string, diff_patch_to_text,
+    diff_stats_for_path, image_mime_type, list_git_roots as scan_git_roots, parse_github_repo,
+    path_has_git_repository_marker, resolve_git_root,
 };
+use crate::shared::workspace_read_only::ensure_workspace_id_writable;
 use crate::state::AppState;
+use crate::text_encoding::decode_text_lossless;
 use crate::types::{
     BranchInfo, GitBranchCompareCommitSets, GitBranchListItem, GitBranchUpdateResult,
     GitCommitDetails, GitCommitDiff, GitCommitFileChange, GitFileDiff, GitFileStatus,
     GitHistoryCommit, GitHistoryResponse, GitHubIssue, GitHubIssuesResponse, GitHubPullRequest,
-    GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse,
+    GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestFile,
+    GitHubPullRequestFilesResponse, GitHubPullRequestsResponse, GitLogResponse,
     GitPrExistingPullRequest, GitPrWorkflowDefaults, GitPrWorkflowResult, GitPrWorkflowStage,
-    GitPushPreviewResponse,
+    GitPushPreviewResponse, WorkspaceSettings,
 };
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
-use validation::validate_local_branch_name;
+use github_api::{
+    gh_or_rest, rest_issues, rest_open_total, rest_pull_request_comments, rest_pull_request_diff,
+    rest_pull_request_files, rest_pull_requests, run_gh, GhError, RestPullRequestFile,
+    GITHUB_COMMENT_LIMIT, GITHUB_LIST_LIMIT,
+};
+use validation::{
+    validate_local_branch_name, validate_remote_name, validate_remote_url, validate_tag_name,
+};
 
+mod github_api;
 mod validation;
 
 const INDEX_SKIP_WORKTREE_FLAG: u16 = 0x4000;
@@ -38,7 +49,8 @@ const PR_RANGE_SUSPICIOUS_THRESHOLD: usize = 32;
 const GIT_STATUS_DIFF_STATS_FILE_LIMIT: usize = 120;
 const GIT_STATUS_DIFF_STATS_MAX_FILE_BYTES: u64 = 256 * 1024;
 const GIT_DIFF_PREVIEW_MAX_FILES: usize = 200;
-const GIT_DIFF_PREVIEW_MAX_TOTAL_BYTES: usize = 2 * 1024 * 1024;
+const GIT_DIFF_PAYLOAD_MIN_KB: u32 = 256;
+const GIT_DIFF_PAYLOAD_MAX_KB: u32 = 64 * 1024;
 const GIT_DIFF_PREVIEW_MAX_BYTES_PER_FILE: usize = 256 * 1024;
 const GIT_DIFF_PREVIEW_MAX_LINES_PER_FILE: usize = 2_500;
 const GIT_DIFF_PREVIEW_SKIP_FILE_SIZE_BYTES: u64 = 1024 * 1024;
@@ -151,12 +163,91 @@ fn is_heavy_diff_path(path: &str) -> bool {
     })
 }
 
+fn worktree_file_size(repo_root: &Path, path: &str) -> Option<u64> {
+    fs::metadata(repo_root.join(path))
+        .ok()
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+}
+
 fn is_large_worktree_file(repo_root: &Path, path: &str, limit_bytes: u64) -> bool {
-    let candidate = repo_root.join(path);
-    match fs::metadata(candidate) {
-        Ok(metadata) => metadata.is_file() && metadata.len() > limit_bytes,
-        Err(_) => false,
+    worktree_file_size(repo_root, path).is_some_and(|size| size > limit_bytes)
+}
+
+/// Total diff text budget for one `get_git_diffs` response, from the
+/// `gitDiffMaxPayloadKb` setting.
+fn git_diff_payload_budget(max_payload_kb: u32) -> usize {
+    max_payload_kb.clamp(GIT_DIFF_PAYLOAD_MIN_KB, GIT_DIFF_PAYLOAD_MAX_KB) as usize * 1024
+}
+
+/// Entry that lists a changed file without its diff text.
+fn summary_only_file_diff(path: String, reason: &str, size_bytes: Option<u64>) -> GitFileDiff {
+    GitFileDiff {
+        path,
+        diff: String::new(),
+        is_binary: false,
+        is_image: false,
+        old_image_data: None,
+        new_image_data: None,
+        old_image_mime: None,
+        new_image_mime: None,
+        summary_only: true,
+        summary_reason: Some(reason.to_string()),
+        size_bytes,
+        encoding: None,
+        encoding_fallback: false,
+    }
+}
+
+fn decode_utf16_side(bytes: &[u8]) -> Option<(String, &'static str)> {
+    if bytes.is_empty() {
+        return Some((String::new(), ""));
+    }
+    let decoded = decode_text_lossless(bytes);
+    (!decoded.fallback && decoded.encoding.starts_with("UTF-16"))
+        .then_some((decoded.text, decoded.encoding))
+}
+
+/// git treats UTF-16 files as binary because of their NUL bytes. When both
+/// sides carry a UTF-16 BOM, render the diff of their decoded text instead.
+fn utf16_text_patch(
+    repo: &Repository,
+    repo_root: &Path,
+    delta: &git2::DiffDelta<'_>,
+) -> Option<(String, &'static str)> {
+    let old_file = delta.old_file();
+    let new_file = delta.new_file();
+    let old_bytes = if old_file.id().is_zero() {
+        Vec::new()
+    } else {
+        repo.find_blob(old_file.id()).ok()?.content().to_vec()
+    };
+    let new_bytes = match new_file.path() {
+        Some(path) if delta.status() != git2::Delta::Deleted => fs::read(repo_root.join(path))
+            .ok()
+            .or_else(|| Some(repo.find_blob(new_file.id()).ok()?.content().to_vec()))?,
+        _ => Vec::new(),
+    };
+    let (old_text, old_encoding) = decode_utf16_side(&old_bytes)?;
+    let (new_text, new_encoding) = decode_utf16_side(&new_bytes)?;
+    let encoding = if new_encoding.is_empty() {
+        old_encoding
+    } else {
+        new_encoding
+    };
+    if encoding.is_empty() {
+        return None;
     }
+    let mut patch = git2::Patch::from_buffers(
+        old_text.as_bytes(),
+        old_file.path(),
+        new_text.as_bytes(),
+        new_file.path(),
+        None,
+    )
+    .ok()?;
+    let content = diff_patch_to_string(&mut patch).ok()?;
+    Some((content, encoding))
 }
 
 fn should_skip_diff_stats(repo_root: &Path, path: &str) -> bool {
@@ -229,15 +320,74 @@ fn open_repository_at_root(repo_root: &Path) -> Result<Repository, String> {
     .map_err(|e| e.to_string())
 }
 
-fn paginate_history_commits(
-    commits: Vec<GitHistoryCommit>,
+/// Commits walked just to count matches once the requested page is full;
+/// past it the total is reported as an estimate.
+const HISTORY_TOTAL_SCAN_LIMIT: usize = 50_000;
+
+/// Collects one page of matching commits while the revwalk streams past, so
+/// the walk can stop early instead of buffering every match.
+struct HistoryWindow<T> {
     offset: usize,
     limit: usize,
-) -> (Vec<GitHistoryCommit>, usize, bool) {
-    let total = commits.len();
-    let page: Vec<GitHistoryCommit> = commits.into_iter().skip(offset).take(limit).collect();
-    let has_more = offset.saturating_add(page.len()) < total;
-    (page, total, has_more)
+    include_total: bool,
+    walked: usize,
+    matched: usize,
+    page: Vec<T>,
+}
+
+struct HistoryWindowResult<T> {
+    page: Vec<T>,
+    total: usize,
+    total_is_estimated: bool,
+    has_more: bool,
+}
+
+impl<T> HistoryWindow<T> {
+    fn new(offset: usize, limit: usize, include_total: bool) -> Self {
+        Self {
+            offset,
+            limit,
+            include_total,
+            walked: 0,
+            matched: 0,
+            page: Vec::new(),
+        }
+    }
+
+    fn page_end(&self) -> usize {
+        self.offset.saturating_add(self.limit)
+    }
+
+    /// Whether the walk has seen enough. Without a total one match past the
+    /// page proves `has_more`; with one, counting stops at the scan limit.
+    fn is_done(&self) -> bool {
+        if !self.include_total {
+            return self.matched > self.page_end();
+        }
+        self.matched >= self.page_end() && self.walked >= HISTORY_TOTAL_SCAN_LIMIT
+    }
+
+    fn note_walked(&mut self) {
+        self.walked += 1;
+    }
+
+    /// Records a matching commit; `build` only runs for commits on the page.
+    fn push_match(&mut self, build: impl FnOnce() -> T) {
+        if self.matched >= self.offset && self.matched < self.page_end() {
+            self.page.push(build());
+        }
+        self.matched += 1;
+    }
+
+    fn finish(self, exhausted: bool) -> HistoryWindowResult<T> {
+        let shown_end = self.offset.saturating_add(self.page.len());
+        HistoryWindowResult {
+            total: self.matched,
+            total_is_estimated: !exhausted,
+            has_more: self.matched > shown_end || !exhausted,
+            page: self.page,
+        }
+    }
 }
 
 fn resolve_ref_to_oid(repo: &Repository, reference: &str) -> Result<Oid, String> {
@@ -366,7 +516,10 @@ async fn run_git_command(repo_root: &Path, args: &[&str]) -> Result<(), String>
         .env("GCM_INTERACTIVE", "never")
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
+        .stderr(Stdio::piped())
+        // A signing prompt or credential helper that never returns must not
+        // outlive the timeout below.
+        .kill_on_drop(true);
 
     let output = match timeout(
         Duration::from_secs(GIT_COMMAND_TIMEOUT_SECS),
@@ -519,7 +672,7 @@ fn upstream_remote_and_branch(repo_root: &Path) -> Result<Option<(String, String
     Ok(upstream_name.and_then(parse_upstream_ref))
 }
 
-fn current_local_branch(repo_root: &Path) -> Result<Option<String>, String> {
+pub(crate) fn current_local_branch(repo_root: &Path) -> Result<Option<String>, String> {
     let repo = open_repository_at_root(repo_root)?;
     let head = match repo.head() {
         Ok(head) => head,
@@ -787,7 +940,12 @@ fn collect_worktree_diff(
     Ok(build_combined_diff(&diff))
 }
 
-fn collect_workspace_diff(repo_root: &Path) -> Result<String, String> {
+const NOTHING_STAGED_ERROR: &str =
+    "Nothing is staged. Stage the changes to describe, or turn off staged-only mode.";
+
+/// The staged diff, or the working tree diff when nothing is staged unless
+/// `staged_only` asks for an error instead.
+fn collect_workspace_diff(repo_root: &Path, staged_only: bool) -> Result<String, String> {
     let repo = open_repository_at_root(repo_root)?;
     let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
 
@@ -795,10 +953,89 @@ fn collect_workspace_diff(repo_root: &Path) -> Result<String, String> {
     if !staged_diff.trim().is_empty() {
         return Ok(staged_diff);
     }
+    if staged_only {
+        return Err(NOTHING_STAGED_ERROR.to_string());
+    }
 
     collect_worktree_diff(&repo, head_tree.as_ref(), None)
 }
 
+/// A changed path with its A/M/D/R/T status, listed in the commit message
+/// prompt so the model sees every file even when the diff is truncated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CommitScopeFile {
+    pub(crate) status: &'static str,
+    pub(crate) path: String,
+}
+
+/// The diff a commit message is generated from, plus its changed files.
+pub(crate) struct CommitScopeDiff {
+    pub(crate) diff: String,
+    pub(crate) files: Vec<CommitScopeFile>,
+}
+
+/// Changed files in the same scope as `collect_commit_scope_diff`: the index
+/// when it has changes (or `staged_only`), else the working tree; with an
+/// explicit selection, the selected paths from either side.
+fn collect_commit_scope_files(
+    repo_root: &Path,
+    selected_paths: Option<&[String]>,
+    staged_only: bool,
+) -> Result<Vec<CommitScopeFile>, String> {
+    let repo = open_repository_at_root(repo_root)?;
+    let mut status_options = StatusOptions::new();
+    status_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .renames_head_to_index(true)
+        .renames_index_to_workdir(true)
+        .include_ignored(false);
+    let statuses = repo
+        .statuses(Some(&mut status_options))
+        .map_err(|e| format!("failed to read git status for commit scope: {e}"))?;
+
+    let selected = selected_paths.map(|paths| {
+        paths
+            .iter()
+            .map(|path| normalize_commit_scope_path(path))
+            .collect::<HashSet<_>>()
+    });
+    let index_only = staged_only
+        || (selected.is_none()
+            && statuses
+                .iter()
+                .any(|entry| status_for_index(entry.status()).is_some()));
+
+    let mut files = Vec::new();
+    for entry in statuses.iter() {
+        let path = normalize_git_path(&String::from_utf8_lossy(entry.path_bytes()));
+        if path.is_empty() {
+            continue;
+        }
+        if let Some(selected) = selected.as_ref() {
+            if !selected.contains(&normalize_commit_scope_path(&path)) {
+                continue;
+            }
+        }
+        let status = entry.status();
+        let file_status = if index_only {
+            status_for_index(status)
+        } else if selected.is_some() {
+            status_for_index(status).or_else(|| status_for_workdir(status))
+        } else {
+            status_for_workdir(status)
+        };
+        if let Some(file_status) = file_status {
+            files.push(CommitScopeFile {
+                status: file_status,
+                path,
+            });
+        }
+    }
+    files.sort_by(|left, right| left.path.cmp(&right.path));
+    Ok(files)
+}
+
 #[derive(Debug, Default, PartialEq, Eq)]
 struct CommitScopeDiffPlan {
     index_paths: Vec<String>,
@@ -899,9 +1136,10 @@ fn build_commit_scope_diff_plan(
 fn collect_commit_scope_diff(
     repo_root: &Path,
     selected_paths: Option<&[String]>,
+    staged_only: bool,
 ) -> Result<String, String> {
     let Some(explicit_selected_paths) = selected_paths else {
-        return collect_workspace_diff(repo_root);
+        return collect_workspace_diff(repo_root, staged_only);
     };
     if explicit_selected_paths.is_empty() {
         return Ok(String::new());
@@ -912,6 +1150,12 @@ fn collect_commit_scope_diff(
     let plan = build_commit_scope_diff_plan(&repo, explicit_selected_paths)?;
 
     let staged_diff = collect_index_diff(&repo, head_tree.as_ref(), Some(&plan.index_paths))?;
+    if staged_only {
+        if staged_diff.trim().is_empty() {
+            return Err(NOTHING_STAGED_ERROR.to_string());
+        }
+        return Ok(staged_diff);
+    }
     let worktree_diff =
         collect_worktree_diff(&repo, head_tree.as_ref(), Some(&plan.worktree_only_paths))?;
 
@@ -926,7 +1170,7 @@ fn collect_commit_scope_diff(
     Ok(segments.join("\n\n"))
 }
 
-fn github_repo_from_path(path: &Path) -> Result<String, String> {
+pub(crate) fn github_repo_from_path(path: &Path) -> Result<String, String> {
     let repo = open_repository_at_root(path)?;
     let remotes = repo.remotes().map_err(|e| e.to_string())?;
     let name = if remotes.iter().any(|remote| remote == Some("origin")) {
@@ -1050,6 +1294,7 @@ fn parse_pr_diff(diff: &str) -> Vec<GitHubPullRequestDiff> {
             path: entry.path,
             status: entry.status,
             diff: entry.diff,
+            truncated: false,
         })
         .collect()
 }
@@ -1074,6 +1319,39 @@ struct GhExistingPrEntry {
     base_ref_name: String,
 }
 
+/// Range gate thresholds, from workspace settings or the built-in defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PrRangeGateConfig {
+    enabled: bool,
+    max_changed_files: usize,
+    suspicious_threshold: usize,
+}
+
+impl Default for PrRangeGateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_changed_files: PR_RANGE_MAX_CHANGED_FILES,
+            suspicious_threshold: PR_RANGE_SUSPICIOUS_THRESHOLD,
+        }
+    }
+}
+
+impl PrRangeGateConfig {
+    fn from_settings(settings: &WorkspaceSettings) -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: settings.pr_range_gate_enabled.unwrap_or(defaults.enabled),
+            max_changed_files: settings
+                .pr_max_changed_files
+                .map_or(defaults.max_changed_files, |value| value as usize),
+            suspicious_threshold: settings
+                .pr_suspicious_threshold
+                .map_or(defaults.suspicious_threshold, |value| value as usize),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum PrRangeGateDecision {
     Pass { changed_files: usize },
@@ -1259,6 +1537,14 @@ fn build_workflow_stages() -> Vec<GitPrWorkflowStage> {
             stdout: None,
             stderr: None,
         },
+        GitPrWorkflowStage {
+            key: "metadata".to_string(),
+            status: "pending".to_string(),
+            detail: "Waiting for label and reviewer assignment.".to_string(),
+            command: None,
+            stdout: None,
+            stderr: None,
+        },
         GitPrWorkflowStage {
             key: "comment".to_string(),
             status: "pending".to_string(),
@@ -1380,7 +1666,10 @@ fn is_suspicious_range_path(path: &str) -> bool {
     normalized == "readme.md" || normalized == "readme.zh-cn.md" || normalized == "license"
 }
 
-fn evaluate_pr_range_gate(changed_paths: &[String]) -> PrRangeGateDecision {
+fn evaluate_pr_range_gate(
+    changed_paths: &[String],
+    config: &PrRangeGateConfig,
+) -> PrRangeGateDecision {
     if changed_paths.is_empty() {
         return PrRangeGateDecision::Blocked {
             category: "range-empty".to_string(),
@@ -1388,13 +1677,18 @@ fn evaluate_pr_range_gate(changed_paths: &[String]) -> PrRangeGateDecision {
                 .to_string(),
         };
     }
-    if changed_paths.len() > PR_RANGE_MAX_CHANGED_FILES {
+    if !config.enabled {
+        return PrRangeGateDecision::Pass {
+            changed_files: changed_paths.len(),
+        };
+    }
+    if changed_paths.len() > config.max_changed_files {
         return PrRangeGateDecision::Blocked {
             category: "range-too-large".to_string(),
             reason: format!(
                 "Range gate blocked: {} changed files exceed threshold {}.",
                 changed_paths.len(),
-                PR_RANGE_MAX_CHANGED_FILES
+                config.max_changed_files
             ),
         };
     }
@@ -1403,7 +1697,7 @@ fn evaluate_pr_range_gate(changed_paths: &[String]) -> PrRangeGateDecision {
         .filter(|path| is_suspicious_range_path(path))
         .cloned()
         .collect::<Vec<_>>();
-    if !suspicious_files.is_empty() && changed_paths.len() >= PR_RANGE_SUSPICIOUS_THRESHOLD {
+    if !suspicious_files.is_empty() && changed_paths.len() >= config.suspicious_threshold {
         return PrRangeGateDecision::Blocked {
             category: "range-suspicious".to_string(),
             reason: format!(
@@ -1577,7 +1871,7 @@ mod tests {
         index.add_path(Path::new("staged.txt")).expect("add path");
         index.write().expect("write index");
 
-        let diff = collect_workspace_diff(&root).expect("collect diff");
+        let diff = collect_workspace_diff(&root, false).expect("collect diff");
         assert!(diff.contains("staged.txt"));
         assert!(diff.contains("staged"));
     }
@@ -1588,7 +1882,7 @@ mod tests {
         let file_path = root.join("unstaged.txt");
         fs::write(&file_path, "unstaged\n").expect("write unstaged file");
 
-        let diff = collect_workspace_diff(&root).expect("collect diff");
+        let diff = collect_workspace_diff(&root, false).expect("collect diff");
         assert!(diff.contains("unstaged.txt"));
         assert!(diff.contains("unstaged"));
     }
@@ -1609,8 +1903,8 @@ mod tests {
         index.write().expect("write index");
 
         let selected_paths = vec!["selected.txt".to_string()];
-        let diff =
-            collect_commit_scope_diff(&root, Some(&selected_paths)).expect("collect scoped diff");
+        let diff = collect_commit_scope_diff(&root, Some(&selected_paths), false)
+            .expect("collect scoped diff");
         assert!(diff.contains("selected.txt"));
         assert!(!diff.contains("ignored.txt"));
     }
@@ -1622,8 +1916,8 @@ mod tests {
         fs::write(root.join("ignored.txt"), "ignored\n").expect("write ignored file");
 
         let selected_paths = vec!["selected.txt".to_string()];
-        let diff =
-            collect_commit_scope_diff(&root, Some(&selected_paths)).expect("collect scoped diff");
+        let diff = collect_commit_scope_diff(&root, Some(&selected_paths), false)
+            .expect("collect scoped diff");
         assert!(diff.contains("selected.txt"));
         assert!(!diff.contains("ignored.txt"));
     }
@@ -1642,8 +1936,8 @@ mod tests {
             .expect("write unstaged tail");
 
         let selected_paths = vec!["hybrid.txt".to_string()];
-        let diff =
-            collect_commit_scope_diff(&root, Some(&selected_paths)).expect("collect scoped diff");
+        let diff = collect_commit_scope_diff(&root, Some(&selected_paths), false)
+            .expect("collect scoped diff");
         assert!(diff.contains("hybrid.txt"));
         assert!(diff.contains("staged only"));
         assert!(!diff.contains("unstaged extra"));
@@ -1659,8 +1953,8 @@ mod tests {
             .expect("write sibling file");
 
         let selected_paths = vec!["src\\feature\\file.ts".to_string()];
-        let diff =
-            collect_commit_scope_diff(&root, Some(&selected_paths)).expect("collect scoped diff");
+        let diff = collect_commit_scope_diff(&root, Some(&selected_paths), false)
+            .expect("collect scoped diff");
         assert!(diff.contains("src/feature/file.ts"));
         assert!(!diff.contains("ignored.ts"));
     }
@@ -1677,7 +1971,7 @@ mod tests {
             .expect("stage staged file");
         index.write().expect("write index");
 
-        let diff = collect_commit_scope_diff(&root, None).expect("collect scoped diff");
+        let diff = collect_commit_scope_diff(&root, None, false).expect("collect scoped diff");
         assert!(diff.contains("staged.txt"));
         assert!(!diff.contains("unstaged.txt"));
     }
@@ -1694,11 +1988,56 @@ mod tests {
         index.write().expect("write index");
 
         let explicit_empty: Vec<String> = Vec::new();
-        let diff =
-            collect_commit_scope_diff(&root, Some(&explicit_empty)).expect("collect scoped diff");
+        let diff = collect_commit_scope_diff(&root, Some(&explicit_empty), false)
+            .expect("collect scoped diff");
         assert!(diff.trim().is_empty());
     }
 
+    #[test]
+    fn staged_only_commit_scope_refuses_to_fall_back_to_workdir() {
+        let (root, repo) = create_temp_repo();
+        fs::write(root.join("unstaged.txt"), "unstaged\n").expect("write unstaged file");
+
+        assert_eq!(
+            collect_commit_scope_diff(&root, None, true),
+            Err(NOTHING_STAGED_ERROR.to_string())
+        );
+        let selected_paths = vec!["unstaged.txt".to_string()];
+        assert!(collect_commit_scope_diff(&root, Some(&selected_paths), true).is_err());
+        assert_eq!(
+            collect_commit_scope_files(&root, None, false).expect("workdir files"),
+            vec![CommitScopeFile {
+                status: "A",
+                path: "unstaged.txt".to_string(),
+            }]
+        );
+
+        fs::write(root.join("staged.txt"), "staged\n").expect("write staged file");
+        let mut index = repo.index().expect("repo index");
+        index
+            .add_path(Path::new("staged.txt"))
+            .expect("stage staged file");
+        index.write().expect("write index");
+
+        let diff = collect_commit_scope_diff(&root, None, true).expect("staged diff");
+        assert!(diff.contains("staged.txt"));
+        assert!(!diff.contains("unstaged.txt"));
+        assert_eq!(
+            collect_commit_scope_files(&root, None, false).expect("staged files"),
+            vec![CommitScopeFile {
+                status: "A",
+                path: "staged.txt".to_string(),
+            }]
+        );
+        let selected_both = vec!["staged.txt".to_string(), "unstaged.txt".to_string()];
+        assert_eq!(
+            collect_commit_scope_files(&root, Some(&selected_both), false)
+                .expect("selected files")
+                .len(),
+            2
+        );
+    }
+
     #[test]
     fn heavy_diff_path_guard_matches_lockfiles_and_generated_dirs() {
         assert!(is_heavy_diff_path("pnpm-lock.yaml"));
@@ -1709,6 +2048,48 @@ mod tests {
         assert!(!is_heavy_diff_path("src/features/git/mod.rs"));
     }
 
+    #[test]
+    fn worktree_diffs_honour_pathspec_and_limits() {
+        let (root, _repo) = create_temp_repo();
+        fs::create_dir_all(root.join("gen")).expect("create gen dir");
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(root.join("gen").join(name), format!("{name}\n")).expect("write gen file");
+        }
+        fs::write(root.join("big.txt"), "x".repeat(4096)).expect("write big file");
+        let limits = |paths: &[&str], max_files, max_bytes_per_file| WorktreeDiffLimits {
+            paths: paths.iter().map(|path| path.to_string()).collect(),
+            max_files,
+            max_bytes_per_file,
+            max_payload_bytes: usize::MAX,
+        };
+
+        let filtered = collect_worktree_diffs(&root, &limits(&["gen"], 10, None)).expect("diffs");
+        assert_eq!(filtered.len(), 3);
+        assert!(filtered.iter().all(|entry| entry.path.starts_with("gen/")));
+
+        let capped = collect_worktree_diffs(&root, &limits(&[], 2, Some(1024))).expect("diffs");
+        assert_eq!(capped.len(), 4);
+        let reasons: Vec<(&str, Option<&str>)> = capped
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry.summary_reason.as_deref()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("big.txt", Some("file-size-limit")),
+                ("gen/a.txt", None),
+                ("gen/b.txt", Some("file-limit")),
+                ("gen/c.txt", Some("file-limit")),
+            ]
+        );
+        assert!(capped
+            .iter()
+            .filter(|entry| entry.summary_only)
+            .all(|entry| entry.diff.is_empty()));
+
+        let _ = fs::remove_dir_all(root);
+    }
+
     #[test]
     fn truncate_diff_preview_respects_line_and_byte_budgets() {
         let mut content = String::new();
@@ -1721,6 +2102,34 @@ mod tests {
         assert!(trimmed.len() <= 80);
     }
 
+    #[test]
+    fn utf16_files_are_diffed_as_decoded_text() {
+        let (root, repo) = create_temp_repo();
+        let utf16 = |text: &str| {
+            let mut bytes = vec![0xFF, 0xFE];
+            bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+            bytes
+        };
+        fs::write(root.join("notes.txt"), utf16("hello\n")).expect("write utf16 file");
+
+        let mut options = DiffOptions::new();
+        options.include_untracked(true).show_untracked_content(true);
+        let diff = repo
+            .diff_tree_to_workdir_with_index(None, Some(&mut options))
+            .expect("diff");
+        let delta = diff.deltas().next().expect("delta");
+        let (content, encoding) = utf16_text_patch(&repo, &root, &delta).expect("utf16 patch");
+        assert_eq!(encoding, "UTF-16LE");
+        assert!(content.contains("+hello"));
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn diff_payload_budget_is_clamped() {
+        assert_eq!(git_diff_payload_budget(0), 256 * 1024);
+        assert_eq!(git_diff_payload_budget(2048), 2 * 1024 * 1024);
+    }
+
     #[test]
     fn action_paths_for_file_expands_renames() {
         let (root, repo) = create_temp_repo();
@@ -1759,26 +2168,47 @@ mod tests {
     }
 
     #[test]
-    fn paginate_history_commits_respects_offset_and_limit() {
-        let commits = (0..5)
-            .map(|index| GitHistoryCommit {
-                sha: format!("sha-{index}"),
-                short_sha: format!("s{index}"),
-                summary: format!("commit-{index}"),
-                message: format!("message-{index}"),
-                author: "tester".to_string(),
-                author_email: "tester@example.com".to_string(),
-                timestamp: 100 + index as i64,
-                parents: Vec::new(),
-                refs: Vec::new(),
-            })
-            .collect::<Vec<_>>();
-        let (page, total, has_more) = paginate_history_commits(commits, 2, 2);
-        assert_eq!(total, 5);
-        assert_eq!(page.len(), 2);
-        assert_eq!(page[0].sha, "sha-2");
-        assert_eq!(page[1].sha, "sha-3");
-        assert!(has_more);
+    fn history_window_collects_page_and_stops_after_proving_more() {
+        let commit = |index: usize| GitHistoryCommit {
+            sha: format!("sha-{index}"),
+            short_sha: format!("s{index}"),
+            summary: format!("commit-{index}"),
+            message: format!("message-{index}"),
+            author: "tester".to_string(),
+            author_email: "tester@example.com".to_string(),
+            timestamp: 100 + index as i64,
+            parents: Vec::new(),
+            refs: Vec::new(),
+        };
+
+        let mut window = HistoryWindow::new(2, 2, false);
+        let mut walked = 0;
+        for index in 0..10 {
+            if window.is_done() {
+                break;
+            }
+            walked += 1;
+            window.note_walked();
+            window.push_match(|| commit(index));
+        }
+        assert_eq!(walked, 5);
+        let result = window.finish(false);
+        assert_eq!(result.page.len(), 2);
+        assert_eq!(result.page[0].sha, "sha-2");
+        assert_eq!(result.page[1].sha, "sha-3");
+        assert!(result.has_more);
+        assert!(result.total_is_estimated);
+
+        let mut window = HistoryWindow::new(2, 2, true);
+        for index in 0..5 {
+            assert!(!window.is_done());
+            window.note_walked();
+            window.push_match(|| commit(index));
+        }
+        let result = window.finish(true);
+        assert_eq!(result.total, 5);
+        assert!(!result.total_is_estimated);
+        assert!(result.has_more);
     }
 
     #[test]
@@ -1852,7 +2282,7 @@ mod tests {
         let paths = (0..(PR_RANGE_MAX_CHANGED_FILES + 1))
             .map(|index| format!("src/file-{index}.ts"))
             .collect::<Vec<_>>();
-        let decision = evaluate_pr_range_gate(&paths);
+        let decision = evaluate_pr_range_gate(&paths, &PrRangeGateConfig::default());
         assert!(matches!(
             decision,
             PrRangeGateDecision::Blocked { category, .. } if category == "range-too-large"
@@ -1865,10 +2295,46 @@ mod tests {
             .map(|index| format!("src/file-{index}.ts"))
             .collect::<Vec<_>>();
         paths.push("README.md".to_string());
-        let decision = evaluate_pr_range_gate(&paths);
+        let decision = evaluate_pr_range_gate(&paths, &PrRangeGateConfig::default());
         assert!(matches!(
             decision,
             PrRangeGateDecision::Blocked { category, .. } if category == "range-suspicious"
         ));
     }
+
+    #[test]
+    fn range_gate_honours_workspace_thresholds() {
+        let paths = (0..300)
+            .map(|index| format!("src/file-{index}.ts"))
+            .chain(std::iter::once("README.md".to_string()))
+            .collect::<Vec<_>>();
+        let mut settings = WorkspaceSettings {
+            pr_max_changed_files: Some(500),
+            ..WorkspaceSettings::default()
+        };
+        assert!(matches!(
+            evaluate_pr_range_gate(&paths, &PrRangeGateConfig::from_settings(&settings)),
+            PrRangeGateDecision::Blocked { category, .. } if category == "range-suspicious"
+        ));
+
+        settings.pr_suspicious_threshold = Some(1000);
+        assert_eq!(
+            evaluate_pr_range_gate(&paths, &PrRangeGateConfig::from_settings(&settings)),
+            PrRangeGateDecision::Pass { changed_files: 301 }
+        );
+
+        let disabled = WorkspaceSettings {
+            pr_range_gate_enabled: Some(false),
+            ..WorkspaceSettings::default()
+        };
+        let config = PrRangeGateConfig::from_settings(&disabled);
+        assert_eq!(
+            evaluate_pr_range_gate(&paths, &config),
+            PrRangeGateDecision::Pass { changed_files: 301 }
+        );
+        assert!(matches!(
+            evaluate_pr_range_gate(&[], &config),
+            PrRangeGateDecision::Blocked { category, .. } if category == "range-empty"
+        ));
+    }
 }
@@ -9,3 +9,56 @@ pub(crate) fn validate_local_branch_name(name: &str) -> Result<String, String> {
     }
     Ok(trimmed.to_string())
 }
+
+pub(crate) fn validate_tag_name(name: &str) -> Result<String, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("Tag name cannot be empty.".to_string());
+    }
+    let full_ref = format!("refs/tags/{trimmed}");
+    if trimmed.starts_with('-') || !git2::Reference::is_valid_name(&full_ref) {
+        return Err(format!("Invalid tag name: {trimmed}"));
+    }
+    Ok(trimmed.to_string())
+}
+
+pub(crate) fn validate_remote_name(name: &str) -> Result<String, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("Remote name cannot be empty.".to_string());
+    }
+    if trimmed.starts_with('-') || !git2::Remote::is_valid_name(trimmed) {
+        return Err(format!("Invalid remote name: {trimmed}"));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Accepts `https://`/`ssh://` style URLs, scp-like `user@host:path`, and
+/// local paths.
+pub(crate) fn validate_remote_url(url: &str) -> Result<String, String> {
+    let trimmed = url.trim();
+    if trimmed.is_empty() {
+        return Err("Remote URL cannot be empty.".to_string());
+    }
+    if trimmed.starts_with('-') || trimmed.chars().any(char::is_whitespace) {
+        return Err(format!("Invalid remote URL: {trimmed}"));
+    }
+    let valid = match trimmed.split_once("://") {
+        Some((scheme, rest)) => {
+            matches!(
+                scheme.to_ascii_lowercase().as_str(),
+                "https" | "http" | "ssh" | "git" | "file"
+            ) && !rest.is_empty()
+        }
+        None => {
+            let is_scp_like = trimmed.split_once(':').is_some_and(|(host, path)| {
+                !host.is_empty() && !host.contains('/') && !path.is_empty()
+            });
+            is_scp_like || std::path::Path::new(trimmed).is_absolute()
+        }
+    };
+    if !valid {
+        return Err(format!("Invalid remote URL: {trimmed}"));
+    }
+    Ok(trimmed.to_string())
+}
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use git2::{DiffOptions, Repository, Tree};
 use ignore::WalkBuilder;
 
+use crate::text_encoding::{decode_text_lossless, DecodedText};
 use crate::types::{GitLogEntry, WorkspaceEntry};
 use crate::utils::normalize_git_path;
 
@@ -81,11 +82,14 @@ pub(crate) fn diff_stats_for_path(
 }
 
 pub(crate) fn diff_patch_to_string(patch: &mut git2::Patch) -> Result<String, git2::Error> {
+    Ok(diff_patch_to_text(patch)?.text)
+}
+
+/// Renders a patch as text, decoding non-UTF-8 content instead of replacing
+/// it, and reports the encoding that was used.
+pub(crate) fn diff_patch_to_text(patch: &mut git2::Patch) -> Result<DecodedText, git2::Error> {
     let buf = patch.to_buf()?;
-    Ok(buf
-        .as_str()
-        .map(|value| value.to_string())
-        .unwrap_or_else(|| String::from_utf8_lossy(&buf).to_string()))
+    Ok(decode_text_lossless(&buf))
 }
 
 #[cfg(test)]
@@ -5,6 +5,14 @@ use crate::app_paths;
 
 const MAX_HISTORY_ITEMS: usize = 200;
 const MAX_COUNT_RECORDS: usize = 200;
+/// Workspaces remembered per item; older ones fall off.
+const MAX_WORKSPACES_PER_ITEM: usize = 20;
+const DEFAULT_PAGE_LIMIT: usize = 50;
+
+// `items` and `counts` stay in the format shared with idea-claude-code-gui.
+// Per-item metadata lives under `meta`, keyed by item text:
+// `{ "workspaceIds": [..], "engine": "claude", "updatedAt": <ms> }`.
+// Files written before it existed simply have no metadata.
 
 fn history_file_path() -> Result<PathBuf, String> {
     app_paths::input_history_file_path()
@@ -13,10 +21,11 @@ fn history_file_path() -> Result<PathBuf, String> {
 fn read_history_file() -> Result<Value, String> {
     let path = history_file_path()?;
     if !path.exists() {
-        return Ok(json!({ "items": [], "counts": {} }));
+        return Ok(json!({ "items": [], "counts": {}, "meta": {} }));
     }
     let data = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let parsed: Value = serde_json::from_str(&data).unwrap_or(json!({ "items": [], "counts": {} }));
+    let parsed: Value =
+        serde_json::from_str(&data).unwrap_or(json!({ "items": [], "counts": {}, "meta": {} }));
     Ok(parsed)
 }
 
@@ -50,37 +59,193 @@ fn cleanup_counts(counts: &Value) -> Value {
     Value::Object(kept)
 }
 
+fn history_items(data: &Value) -> Vec<String> {
+    data.get("items")
+        .and_then(Value::as_array)
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn history_meta(data: &Value) -> serde_json::Map<String, Value> {
+    data.get("meta")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Drops metadata of items no longer in the history.
+fn retain_meta(meta: &mut serde_json::Map<String, Value>, items: &[String]) {
+    let kept: std::collections::HashSet<&str> = items.iter().map(String::as_str).collect();
+    meta.retain(|key, _| kept.contains(key.as_str()));
+}
+
+fn record_meta(
+    meta: &mut serde_json::Map<String, Value>,
+    fragment: &str,
+    workspace_id: Option<&str>,
+    engine: Option<&str>,
+    now_ms: i64,
+) {
+    let entry = meta
+        .entry(fragment.to_string())
+        .or_insert_with(|| json!({}));
+    if !entry.is_object() {
+        *entry = json!({});
+    }
+    let Some(entry) = entry.as_object_mut() else {
+        return;
+    };
+    if let Some(workspace_id) = workspace_id {
+        let mut workspace_ids: Vec<String> = entry
+            .get("workspaceIds")
+            .and_then(Value::as_array)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| id.as_str().map(String::from))
+                    .filter(|id| id != workspace_id)
+                    .collect()
+            })
+            .unwrap_or_default();
+        workspace_ids.push(workspace_id.to_string());
+        if workspace_ids.len() > MAX_WORKSPACES_PER_ITEM {
+            workspace_ids.drain(..workspace_ids.len() - MAX_WORKSPACES_PER_ITEM);
+        }
+        entry.insert("workspaceIds".to_string(), json!(workspace_ids));
+    }
+    if let Some(engine) = engine {
+        entry.insert("engine".to_string(), json!(engine));
+    }
+    entry.insert("updatedAt".to_string(), json!(now_ms));
+}
+
+/// Case-insensitive match score: substring matches rank above fuzzy
+/// (in-order subsequence) matches, earlier substrings above later ones.
+fn match_score(item: &str, query: &str) -> Option<usize> {
+    let item = item.to_lowercase();
+    if let Some(position) = item.find(query) {
+        return Some(position);
+    }
+    let mut chars = item.chars();
+    query
+        .chars()
+        .all(|wanted| chars.any(|c| c == wanted))
+        .then_some(usize::MAX)
+}
+
+/// Items matching `query` and `workspace_id`, best match first and newest
+/// first among equals.
+fn filter_history(
+    items: &[String],
+    meta: &serde_json::Map<String, Value>,
+    query: Option<&str>,
+    workspace_id: Option<&str>,
+) -> Vec<String> {
+    let query = query
+        .map(|query| query.trim().to_lowercase())
+        .filter(|query| !query.is_empty());
+    let mut matches: Vec<(usize, usize, &String)> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            let Some(workspace_id) = workspace_id else {
+                return true;
+            };
+            meta.get(item.as_str())
+                .and_then(|entry| entry.get("workspaceIds"))
+                .and_then(Value::as_array)
+                .is_some_and(|ids| ids.iter().any(|id| id.as_str() == Some(workspace_id)))
+        })
+        .filter_map(|(index, item)| match query.as_deref() {
+            Some(query) => match_score(item, query).map(|score| (score, index, item)),
+            None => Some((0, index, item)),
+        })
+        .collect();
+    matches.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+    matches
+        .into_iter()
+        .map(|(_, _, item)| item.clone())
+        .collect()
+}
+
+/// Without arguments returns the whole history file, oldest item first.
+/// With a query, workspace, or page it returns `{ items, counts, meta,
+/// total }` for just the matching page, best match first.
 #[tauri::command]
-pub(crate) fn input_history_read() -> Result<Value, String> {
-    read_history_file()
+pub(crate) fn input_history_read(
+    query: Option<String>,
+    workspace_id: Option<String>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<Value, String> {
+    let data = read_history_file()?;
+    if query.is_none() && workspace_id.is_none() && limit.is_none() && offset.is_none() {
+        return Ok(data);
+    }
+    let items = history_items(&data);
+    let meta = history_meta(&data);
+    let counts = data
+        .get("counts")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    let matches = filter_history(&items, &meta, query.as_deref(), workspace_id.as_deref());
+    let total = matches.len();
+    let page: Vec<String> = matches
+        .into_iter()
+        .skip(offset.unwrap_or(0))
+        .take(limit.unwrap_or(DEFAULT_PAGE_LIMIT))
+        .collect();
+    let page_counts: serde_json::Map<String, Value> = page
+        .iter()
+        .filter_map(|item| counts.get(item).map(|count| (item.clone(), count.clone())))
+        .collect();
+    let page_meta: serde_json::Map<String, Value> = page
+        .iter()
+        .filter_map(|item| meta.get(item).map(|entry| (item.clone(), entry.clone())))
+        .collect();
+    Ok(json!({
+        "items": page,
+        "counts": page_counts,
+        "meta": page_meta,
+        "total": total,
+    }))
 }
 
 #[tauri::command]
-pub(crate) fn input_history_record(fragments: Vec<String>) -> Result<Value, String> {
+pub(crate) fn input_history_record(
+    fragments: Vec<String>,
+    workspace_id: Option<String>,
+    engine: Option<String>,
+) -> Result<Value, String> {
     if fragments.is_empty() {
         let data = read_history_file()?;
         return Ok(data);
     }
 
     let data = read_history_file()?;
-    let items_val = data.get("items").cloned().unwrap_or(json!([]));
     let counts_val = data.get("counts").cloned().unwrap_or(json!({}));
 
-    let mut items: Vec<String> = items_val
-        .as_array()
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect()
-        })
-        .unwrap_or_default();
+    let mut items = history_items(&data);
+    let mut meta = history_meta(&data);
 
     let mut counts = counts_val.as_object().cloned().unwrap_or_default();
 
     // Increment counts for each fragment
+    let now_ms = chrono::Utc::now().timestamp_millis();
     for fragment in &fragments {
         let current = counts.get(fragment).and_then(|v| v.as_i64()).unwrap_or(0);
         counts.insert(fragment.clone(), json!(current + 1));
+        record_meta(
+            &mut meta,
+            fragment,
+            workspace_id.as_deref(),
+            engine.as_deref(),
+            now_ms,
+        );
     }
 
     // Cleanup counts
@@ -99,10 +264,12 @@ pub(crate) fn input_history_record(fragments: Vec<String>) -> Result<Value, Stri
         let start = items.len() - MAX_HISTORY_ITEMS;
         items = items[start..].to_vec();
     }
+    retain_meta(&mut meta, &items);
 
     let result = json!({
         "items": items,
         "counts": cleaned_counts,
+        "meta": Value::Object(meta),
     });
     write_history_file(&result)?;
     Ok(result)
@@ -111,25 +278,20 @@ pub(crate) fn input_history_record(fragments: Vec<String>) -> Result<Value, Stri
 #[tauri::command]
 pub(crate) fn input_history_delete(item: String) -> Result<Value, String> {
     let data = read_history_file()?;
-    let items_val = data.get("items").cloned().unwrap_or(json!([]));
     let counts_val = data.get("counts").cloned().unwrap_or(json!({}));
 
-    let items: Vec<String> = items_val
-        .as_array()
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .filter(|s| s != &item)
-                .collect()
-        })
-        .unwrap_or_default();
+    let mut items = history_items(&data);
+    items.retain(|s| s != &item);
 
     let mut counts = counts_val.as_object().cloned().unwrap_or_default();
     counts.remove(&item);
+    let mut meta = history_meta(&data);
+    retain_meta(&mut meta, &items);
 
     let result = json!({
         "items": items,
         "counts": Value::Object(counts),
+        "meta": Value::Object(meta),
     });
     write_history_file(&result)?;
     Ok(result)
@@ -137,5 +299,58 @@ pub(crate) fn input_history_delete(item: String) -> Result<Value, String> {
 
 #[tauri::command]
 pub(crate) fn input_history_clear() -> Result<(), String> {
-    write_history_file(&json!({ "items": [], "counts": {} }))
+    write_history_file(&json!({ "items": [], "counts": {}, "meta": {} }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_history_by_query_and_workspace() {
+        let items: Vec<String> = [
+            "fix the build",
+            "Refactor parser",
+            "add tests for parser",
+            "ship it",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let mut meta = serde_json::Map::new();
+        record_meta(
+            &mut meta,
+            "Refactor parser",
+            Some("ws-1"),
+            Some("claude"),
+            1,
+        );
+        record_meta(&mut meta, "add tests for parser", Some("ws-2"), None, 2);
+        record_meta(&mut meta, "add tests for parser", Some("ws-1"), None, 3);
+
+        assert_eq!(
+            filter_history(&items, &meta, Some("PARSER"), None),
+            vec!["Refactor parser", "add tests for parser"]
+        );
+        assert_eq!(
+            filter_history(&items, &meta, Some("fxbld"), None),
+            vec!["fix the build"]
+        );
+        assert_eq!(
+            filter_history(&items, &meta, None, Some("ws-1")),
+            vec!["add tests for parser", "Refactor parser"]
+        );
+        assert_eq!(
+            filter_history(&items, &meta, Some("ship"), Some("ws-1")),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            meta["add tests for parser"]["workspaceIds"],
+            json!(["ws-2", "ws-1"])
+        );
+
+        retain_meta(&mut meta, &items[..2]);
+        assert!(meta.contains_key("Refactor parser"));
+        assert!(!meta.contains_key("add tests for parser"));
+    }
 }
@@ -21,47 +21,75 @@ fn get_pending_open_paths() -> Vec<String> {
 
 mod agents;
 mod app_paths;
+mod approval_rules;
 mod backend;
 mod claude_commands;
 mod claude_home;
+mod cli_bridge;
 mod client_storage;
 mod code_intel;
 mod codex;
 mod command_registry;
 mod computer_use;
+mod data_dir;
 mod diagnostics_bundle;
 mod dictation;
 mod email;
 mod engine;
 mod event_sink;
+mod file_attribution;
+mod file_mentions;
 mod files;
+mod flaky_tests;
 mod git;
 mod git_utils;
 mod input_history;
+mod linux_file_drop;
 mod linux_startup_guard;
 mod local_usage;
 mod menu;
+mod model_pricing;
 mod note_cards;
+mod onboarding;
+mod outbound_notifications;
+mod preview_server;
+mod privacy_zones;
 mod project_memory;
+mod prompt_ab_test;
 mod prompts;
 mod remote_backend;
+mod repro_sandbox;
 mod rules;
 mod runtime;
 mod runtime_log;
+mod sandbox_report;
+mod saved_filters;
+mod session_bookmarks;
+mod session_checkpoints;
+mod session_context_pack;
+mod session_cost;
+mod session_housekeeping;
+mod session_issues;
 mod session_management;
+mod session_resources;
+mod session_stats;
 mod settings;
 mod shared;
 mod shared_sessions;
 mod skills;
 mod startup_guard;
 mod state;
+mod state_store;
 mod storage;
+mod store_migrations;
 mod terminal;
 mod text_encoding;
 mod types;
+mod user_input_policy;
 mod utils;
 mod vendors;
 mod web_service;
+mod webhooks;
 mod window;
 mod workspaces;
 
@@ -129,6 +157,9 @@ pub fn run() {
                 log::warn!("Failed to prepare ccgui home directory: {error}");
             }
             let state = state::AppState::load(&app.handle());
+            if let Some(data_dir) = state.storage_path.parent() {
+                store_migrations::run_startup_migrations(data_dir);
+            }
             app.manage(state);
             {
                 let app_handle = app.handle().clone();
@@ -137,6 +168,8 @@ pub fn run() {
                     state.sync_engine_configs_from_settings().await;
                 });
             }
+            tauri::async_runtime::spawn(webhooks::restore_webhook_listener(app.handle().clone()));
+            tauri::async_runtime::spawn(cli_bridge::start_cli_bridge(app.handle().clone()));
             {
                 let app_handle = app.handle().clone();
                 tauri::async_runtime::spawn(async move {
@@ -183,21 +216,12 @@ pub fn run() {
             }
 
             win_builder = win_builder.on_navigation(|url: &tauri::Url| {
-                let scheme = url.scheme();
-                let host = url.host_str().unwrap_or("");
-
-                // Allow tauri internal protocol
-                if scheme == "tauri" || scheme == "asset" {
-                    return true;
-                }
-
-                // Allow localhost (dev server + memory iframe)
-                // Windows uses http://tauri.localhost/ as the internal webview origin
-                if host == "localhost" || host == "127.0.0.1" || host == "tauri.localhost" {
+                if window::is_in_app_navigation(url) {
                     return true;
                 }
 
                 // External URL → open in system browser, block webview navigation
+                let scheme = url.scheme();
                 if scheme == "http" || scheme == "https" {
                     let _ = tauri_plugin_opener::open_url(url.as_str(), None::<&str>);
                     return false;
@@ -279,12 +303,9 @@ pub fn run() {
                 let _ = window.set_decorations(false);
             }
 
-            // Handle command line arguments (Windows/Linux)
-            let args: Vec<String> = std::env::args().skip(1).collect();
-            let paths: Vec<String> = args
-                .into_iter()
-                .filter(|arg| !arg.starts_with('-') && std::path::Path::new(arg).exists())
-                .collect();
+            // Handle command line arguments (Windows/Linux). Linux file managers
+            // pass `file://` URIs when launching the "Open with" entry.
+            let paths = linux_file_drop::open_paths_from_args(std::env::args().skip(1));
             if !paths.is_empty() {
                 // Store paths for frontend to retrieve later
                 if let Ok(mut pending) = PENDING_OPEN_PATHS.lock() {
@@ -313,6 +334,7 @@ pub fn run() {
                 }
                 crate::terminal::cleanup_all_terminal_sessions(&state).await;
             });
+            cli_bridge::remove_discovery_file();
         }
     });
 }
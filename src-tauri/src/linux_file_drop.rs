@@ -0,0 +1,378 @@
+//! File manager interop on Linux.
+//!
+//! Nautilus and Dolphin hand dropped files and "Open with" launches over as
+//! `text/uri-list` payloads (`file:///home/me/My%20Project`), which the
+//! webview drop event and the plain `Path::exists` argv check both miss.
+//! `parse_uri_list` turns those payloads into local paths, and
+//! `install_file_manager_integration` registers an "Open with ccgui" entry
+//! whose launches feed the pending open paths like any other CLI argument.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+#[cfg(target_os = "linux")]
+const DESKTOP_ENTRY_FILE: &str = "com.zhukunpenglinyutong.ccgui-open.desktop";
+#[cfg(target_os = "linux")]
+const KDE_SERVICE_MENU_FILE: &str = "ccgui-open.desktop";
+#[cfg(target_os = "linux")]
+const NAUTILUS_SCRIPT_FILE: &str = "Open with ccgui";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileManagerIntegrationReport {
+    pub(crate) exec_path: String,
+    pub(crate) written_files: Vec<String>,
+    pub(crate) removed_files: Vec<String>,
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut idx = 0usize;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' && idx + 2 < bytes.len() {
+            let high = (bytes[idx + 1] as char).to_digit(16);
+            let low = (bytes[idx + 2] as char).to_digit(16);
+            if let (Some(high), Some(low)) = (high, low) {
+                out.push(((high << 4) | low) as u8);
+                idx += 3;
+                continue;
+            }
+        }
+        out.push(bytes[idx]);
+        idx += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Converts one `file://` URI or absolute path to a local path. URIs for
+/// other hosts or schemes (`sftp://`, `trash://`) are not local and yield
+/// `None`.
+pub(crate) fn local_path_from_uri(value: &str) -> Option<PathBuf> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let Some(rest) = trimmed
+        .get(..7)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("file://"))
+        .map(|_| &trimmed[7..])
+    else {
+        if trimmed.contains("://") {
+            return None;
+        }
+        return Path::new(trimmed)
+            .is_absolute()
+            .then(|| PathBuf::from(trimmed));
+    };
+    let (host, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => return None,
+    };
+    if !host.is_empty() && !host.eq_ignore_ascii_case("localhost") {
+        return None;
+    }
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    let decoded = percent_decode(path);
+    #[cfg(windows)]
+    {
+        let bytes = decoded.as_bytes();
+        if bytes.len() >= 3 && bytes[2] == b':' && bytes[1].is_ascii_alphabetic() {
+            return Some(PathBuf::from(&decoded[1..]));
+        }
+    }
+    Some(PathBuf::from(decoded))
+}
+
+/// Parses a `text/uri-list` payload (RFC 2483). Also accepts the GNOME
+/// clipboard flavour, whose first line is `copy` or `cut`, and newline
+/// separated plain paths. Duplicates are dropped, order is kept.
+pub(crate) fn parse_uri_list(payload: &str) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for line in payload.lines() {
+        let line = line.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.eq_ignore_ascii_case("copy")
+            || line.eq_ignore_ascii_case("cut")
+        {
+            continue;
+        }
+        if let Some(path) = local_path_from_uri(line) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// Picks the paths to open from launch arguments. File managers launch the
+/// desktop entry with `%U`, so arguments may be `file://` URIs as well as
+/// plain paths; flags and paths that do not exist are ignored.
+pub(crate) fn open_paths_from_args(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    args.into_iter()
+        .filter(|arg| !arg.starts_with('-'))
+        .filter_map(|arg| {
+            let path = local_path_from_uri(&arg).unwrap_or_else(|| PathBuf::from(&arg));
+            path.exists().then(|| path.to_string_lossy().into_owned())
+        })
+        .collect()
+}
+
+/// Resolves a drop payload from the webview (`text/uri-list` or
+/// `text/plain`) to the local paths that exist.
+#[tauri::command]
+pub(crate) fn resolve_dropped_paths(payload: String) -> Vec<String> {
+    parse_uri_list(&payload)
+        .into_iter()
+        .filter(|path| path.exists())
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn launch_exec_path() -> Result<PathBuf, String> {
+    // Inside an AppImage `current_exe` points into the transient mount.
+    if let Some(appimage) = std::env::var_os("APPIMAGE").filter(|value| !value.is_empty()) {
+        return Ok(PathBuf::from(appimage));
+    }
+    std::env::current_exe().map_err(|error| format!("Unable to resolve app binary: {error}"))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn desktop_exec_quote(path: &Path) -> String {
+    let raw = path.to_string_lossy();
+    let mut quoted = String::with_capacity(raw.len() + 2);
+    quoted.push('"');
+    for c in raw.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    // `%` is a field code prefix in Exec lines and must be doubled.
+    quoted.replace('%', "%%")
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn desktop_entry(exec: &Path) -> String {
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=ccgui\n\
+         GenericName=Open with ccgui\n\
+         Comment=Open the folder as a ccgui workspace\n\
+         Exec={} %U\n\
+         Icon=ccgui\n\
+         Terminal=false\n\
+         NoDisplay=true\n\
+         MimeType=inode/directory;\n\
+         Categories=Development;\n",
+        desktop_exec_quote(exec)
+    )
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn kde_service_menu(exec: &Path) -> String {
+    format!(
+        "[Desktop Entry]\n\
+         Type=Service\n\
+         MimeType=inode/directory;\n\
+         Actions=openWithCcgui;\n\
+         X-KDE-Priority=TopLevel\n\
+         \n\
+         [Desktop Action openWithCcgui]\n\
+         Name=Open with ccgui\n\
+         Icon=ccgui\n\
+         Exec={} %U\n",
+        desktop_exec_quote(exec)
+    )
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn nautilus_script(exec: &Path) -> String {
+    let raw = exec.to_string_lossy().replace('\'', "'\\''");
+    format!(
+        "#!/bin/sh\n\
+         # Installed by ccgui. Opens the selected folders as workspaces.\n\
+         if [ -n \"$NAUTILUS_SCRIPT_SELECTED_URIS\" ]; then\n\
+         \x20   printf '%s' \"$NAUTILUS_SCRIPT_SELECTED_URIS\" | xargs -d '\\n' '{raw}' &\n\
+         else\n\
+         \x20   '{raw}' \"$NAUTILUS_SCRIPT_CURRENT_URI\" &\n\
+         fi\n"
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn integration_files() -> Result<Vec<PathBuf>, String> {
+    let data_dir = dirs::data_dir().ok_or("Unable to resolve XDG data dir.")?;
+    Ok(vec![
+        data_dir.join("applications").join(DESKTOP_ENTRY_FILE),
+        data_dir
+            .join("kio")
+            .join("servicemenus")
+            .join(KDE_SERVICE_MENU_FILE),
+        data_dir
+            .join("nautilus")
+            .join("scripts")
+            .join(NAUTILUS_SCRIPT_FILE),
+    ])
+}
+
+#[cfg(target_os = "linux")]
+fn refresh_desktop_database(applications_dir: &Path) {
+    // Best effort: without it the entry still shows up after the next login.
+    let _ = std::process::Command::new("update-desktop-database")
+        .arg(applications_dir)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status();
+}
+
+#[cfg(target_os = "linux")]
+fn install_integration() -> Result<FileManagerIntegrationReport, String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let exec = launch_exec_path()?;
+    let files = integration_files()?;
+    let contents = [
+        desktop_entry(&exec),
+        kde_service_menu(&exec),
+        nautilus_script(&exec),
+    ];
+    let mut written_files = Vec::new();
+    for (path, content) in files.iter().zip(contents.iter()) {
+        crate::storage::write_string_atomically(path, content)?;
+        written_files.push(path.to_string_lossy().to_string());
+    }
+    let script = &files[2];
+    std::fs::set_permissions(script, std::fs::Permissions::from_mode(0o755))
+        .map_err(|error| format!("Failed to mark {} executable: {error}", script.display()))?;
+    if let Some(applications_dir) = files[0].parent() {
+        refresh_desktop_database(applications_dir);
+    }
+    Ok(FileManagerIntegrationReport {
+        exec_path: exec.to_string_lossy().to_string(),
+        written_files,
+        removed_files: Vec::new(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn remove_integration() -> Result<FileManagerIntegrationReport, String> {
+    let files = integration_files()?;
+    let mut removed_files = Vec::new();
+    for path in &files {
+        match std::fs::remove_file(path) {
+            Ok(()) => removed_files.push(path.to_string_lossy().to_string()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(format!("Failed to remove {}: {error}", path.display())),
+        }
+    }
+    if let Some(applications_dir) = files[0].parent() {
+        refresh_desktop_database(applications_dir);
+    }
+    Ok(FileManagerIntegrationReport {
+        exec_path: String::new(),
+        written_files: Vec::new(),
+        removed_files,
+    })
+}
+
+/// Installs the "Open with ccgui" desktop entry, Dolphin service menu and
+/// Nautilus script for the current user.
+#[tauri::command]
+pub(crate) async fn install_file_manager_integration(
+) -> Result<FileManagerIntegrationReport, String> {
+    #[cfg(target_os = "linux")]
+    {
+        tokio::task::spawn_blocking(install_integration)
+            .await
+            .map_err(|error| error.to_string())?
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("File manager integration is only available on Linux.".to_string())
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn remove_file_manager_integration() -> Result<FileManagerIntegrationReport, String>
+{
+    #[cfg(target_os = "linux")]
+    {
+        tokio::task::spawn_blocking(remove_integration)
+            .await
+            .map_err(|error| error.to_string())?
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("File manager integration is only available on Linux.".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn parses_nautilus_and_dolphin_uri_lists() {
+        let payload = "# dragged from Dolphin\r\n\
+                       file:///home/demo/My%20Project\r\n\
+                       file://localhost/home/demo/notes.md\r\n\
+                       sftp://server/home/demo/remote\r\n\
+                       file:///home/demo/My%20Project\r\n";
+        assert_eq!(
+            parse_uri_list(payload),
+            vec![
+                PathBuf::from("/home/demo/My Project"),
+                PathBuf::from("/home/demo/notes.md"),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parses_gnome_copied_files_and_plain_paths() {
+        let payload = "copy\nfile:///tmp/a%23b\n/tmp/plain path\nrelative/path\0";
+        assert_eq!(
+            parse_uri_list(payload),
+            vec![PathBuf::from("/tmp/a#b"), PathBuf::from("/tmp/plain path")]
+        );
+    }
+
+    #[test]
+    fn rejects_remote_file_uris() {
+        assert_eq!(local_path_from_uri("file://fileserver/share/a"), None);
+        assert_eq!(local_path_from_uri("trash:///a"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn launch_args_accept_file_uris() {
+        let dir = std::env::temp_dir().join(format!("moss-x-test-{} dir", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let uri = format!("file://{}", dir.to_string_lossy().replace(' ', "%20"));
+        let paths = open_paths_from_args(vec![
+            "--flag".to_string(),
+            uri,
+            "/definitely/missing".to_string(),
+        ]);
+        assert_eq!(paths, vec![dir.to_string_lossy().into_owned()]);
+    }
+
+    #[test]
+    fn desktop_entries_quote_exec_path() {
+        let entry = desktop_entry(Path::new("/opt/cc gui/100%/ccgui"));
+        assert!(entry.contains("Exec=\"/opt/cc gui/100%%/ccgui\" %U\n"));
+        assert!(entry.contains("MimeType=inode/directory;\n"));
+        let menu = kde_service_menu(Path::new("/usr/bin/ccgui"));
+        assert!(menu.contains("[Desktop Action openWithCcgui]\n"));
+        let script = nautilus_script(Path::new("/home/o'neil/ccgui"));
+        assert!(script.contains("'/home/o'\\''neil/ccgui'"));
+    }
+}
@@ -13,9 +13,9 @@ use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_hom
 use crate::state::AppState;
 use crate::types::{
     LocalUsageDailyCodeChange, LocalUsageDailyUsage, LocalUsageDay, LocalUsageEngineUsage,
-    LocalUsageModel, LocalUsageModelUsage, LocalUsageSessionSummary, LocalUsageSnapshot,
-    LocalUsageStatistics, LocalUsageTotals, LocalUsageTrends, LocalUsageUsageData,
-    LocalUsageWeekData, LocalUsageWeeklyComparison, WorkspaceEntry,
+    LocalUsageModel, LocalUsageModelUsage, LocalUsageQueryResult, LocalUsageSessionSummary,
+    LocalUsageSnapshot, LocalUsageStatistics, LocalUsageTotals, LocalUsageTrends,
+    LocalUsageUsageData, LocalUsageWeekData, LocalUsageWeeklyComparison, WorkspaceEntry,
 };
 
 #[path = "local_usage/codex_rewind.rs"]
@@ -31,6 +31,12 @@ mod session_delete;
 pub(crate) use session_delete::{
     delete_codex_session_for_workspace, delete_codex_sessions_for_workspace,
 };
+#[path = "local_usage/usage_events.rs"]
+mod usage_events;
+use usage_events::{
+    collect_usage_sources, open_usage_events, query_usage_groups, sync_usage_events,
+    usage_events_db_path, usage_range_cutoff_ms, UsageGroupBy, UsageWorkspace,
+};
 
 #[derive(Default, Clone, Copy)]
 struct DailyTotals {
@@ -160,6 +166,65 @@ pub(crate) async fn local_usage_statistics(
     Ok(statistics)
 }
 
+/// Token totals over `range` (`all` or `<days>d`, default `30d`) grouped by
+/// engine, model, workspace or day, from the per-event usage database.
+#[tauri::command]
+pub(crate) async fn local_usage_query(
+    range: Option<String>,
+    group_by: String,
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<LocalUsageQueryResult, String> {
+    let range = range
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "30d".to_string());
+    let group_by = UsageGroupBy::parse(&group_by)?;
+    let workspace_id = workspace_id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    let cutoff_ms = usage_range_cutoff_ms(&range, now_ms)?;
+    let (sessions_roots, workspaces) = {
+        let workspaces = state.workspaces.lock().await;
+        let usage_workspaces = workspaces
+            .values()
+            .map(|entry| UsageWorkspace {
+                id: entry.id.clone(),
+                name: entry.name.clone(),
+                path: PathBuf::from(&entry.path),
+            })
+            .collect::<Vec<_>>();
+        (resolve_sessions_roots(&workspaces, None), usage_workspaces)
+    };
+    let db_path = usage_events_db_path()?;
+    let (groups, totals) = tokio::task::spawn_blocking(move || {
+        let mut connection = open_usage_events(&db_path)?;
+        let sources = collect_usage_sources(&sessions_roots, claude_projects_dir().as_deref());
+        sync_usage_events(&mut connection, &sources)?;
+        query_usage_groups(
+            &connection,
+            cutoff_ms,
+            group_by,
+            &workspaces,
+            workspace_id.as_deref(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+
+    Ok(LocalUsageQueryResult {
+        updated_at: now_ms,
+        range,
+        group_by: group_by.as_str().to_string(),
+        groups,
+        totals,
+    })
+}
+
 pub(crate) async fn list_codex_session_summaries_for_workspace(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: &str,
@@ -783,7 +848,11 @@ fn scan_codex_session_summaries(
     Ok(sessions)
 }
 
-fn collect_jsonl_files(root: &Path, output: &mut Vec<PathBuf>, seen: &mut HashSet<PathBuf>) {
+pub(crate) fn collect_jsonl_files(
+    root: &Path,
+    output: &mut Vec<PathBuf>,
+    seen: &mut HashSet<PathBuf>,
+) {
     let entries = match fs::read_dir(root) {
         Ok(entries) => entries,
         Err(_) => return,
@@ -990,73 +1059,14 @@ fn parse_codex_session_summary(
         }
         saw_session_signal = true;
 
-        let info = payload
+        let Some(delta) = payload
             .and_then(|payload| payload.get("info"))
-            .and_then(|value| value.as_object());
-        let (input, cached, output, used_total) = if let Some(info) = info {
-            if let Some(total) = find_usage_map(info, &["total_token_usage", "totalTokenUsage"]) {
-                (
-                    read_i64(total, &["input_tokens", "inputTokens"]),
-                    read_i64(
-                        total,
-                        &[
-                            "cached_input_tokens",
-                            "cache_read_input_tokens",
-                            "cachedInputTokens",
-                            "cacheReadInputTokens",
-                        ],
-                    ),
-                    read_i64(total, &["output_tokens", "outputTokens"]),
-                    true,
-                )
-            } else if let Some(last) = find_usage_map(info, &["last_token_usage", "lastTokenUsage"])
-            {
-                (
-                    read_i64(last, &["input_tokens", "inputTokens"]),
-                    read_i64(
-                        last,
-                        &[
-                            "cached_input_tokens",
-                            "cache_read_input_tokens",
-                            "cachedInputTokens",
-                            "cacheReadInputTokens",
-                        ],
-                    ),
-                    read_i64(last, &["output_tokens", "outputTokens"]),
-                    false,
-                )
-            } else {
-                continue;
-            }
-        } else {
+            .and_then(|value| value.as_object())
+            .and_then(|info| codex_token_count_delta(info, &mut previous_totals))
+        else {
             continue;
         };
 
-        let mut delta = UsageTotals {
-            input,
-            cached,
-            output,
-        };
-        if used_total {
-            let prev = previous_totals.unwrap_or_default();
-            delta = UsageTotals {
-                input: (input - prev.input).max(0),
-                cached: (cached - prev.cached).max(0),
-                output: (output - prev.output).max(0),
-            };
-            previous_totals = Some(UsageTotals {
-                input,
-                cached,
-                output,
-            });
-        } else {
-            let mut next = previous_totals.unwrap_or_default();
-            next.input += delta.input;
-            next.cached += delta.cached;
-            next.output += delta.output;
-            previous_totals = Some(next);
-        }
-
         if delta.input == 0 && delta.cached == 0 && delta.output == 0 {
             continue;
         }
@@ -1776,78 +1786,14 @@ fn scan_file(
                 continue;
             }
 
-            let info = payload
+            let Some(delta) = payload
                 .and_then(|payload| payload.get("info"))
-                .and_then(|v| v.as_object());
-            let (input, cached, output, used_total) = if let Some(info) = info {
-                if let Some(total) = find_usage_map(info, &["total_token_usage", "totalTokenUsage"])
-                {
-                    (
-                        read_i64(total, &["input_tokens", "inputTokens"]),
-                        read_i64(
-                            total,
-                            &[
-                                "cached_input_tokens",
-                                "cache_read_input_tokens",
-                                "cachedInputTokens",
-                                "cacheReadInputTokens",
-                            ],
-                        ),
-                        read_i64(total, &["output_tokens", "outputTokens"]),
-                        true,
-                    )
-                } else if let Some(last) =
-                    find_usage_map(info, &["last_token_usage", "lastTokenUsage"])
-                {
-                    (
-                        read_i64(last, &["input_tokens", "inputTokens"]),
-                        read_i64(
-                            last,
-                            &[
-                                "cached_input_tokens",
-                                "cache_read_input_tokens",
-                                "cachedInputTokens",
-                                "cacheReadInputTokens",
-                            ],
-                        ),
-                        read_i64(last, &["output_tokens", "outputTokens"]),
-                        false,
-                    )
-                } else {
-                    continue;
-                }
-            } else {
+                .and_then(|v| v.as_object())
+                .and_then(|info| codex_token_count_delta(info, &mut previous_totals))
+            else {
                 continue;
             };
 
-            let mut delta = UsageTotals {
-                input,
-                cached,
-                output,
-            };
-
-            if used_total {
-                let prev = previous_totals.unwrap_or_default();
-                delta = UsageTotals {
-                    input: (input - prev.input).max(0),
-                    cached: (cached - prev.cached).max(0),
-                    output: (output - prev.output).max(0),
-                };
-                previous_totals = Some(UsageTotals {
-                    input,
-                    cached,
-                    output,
-                });
-            } else {
-                // Some streams emit `last_token_usage` deltas between `total_token_usage` snapshots.
-                // Treat those as already-counted to avoid double-counting when the next total arrives.
-                let mut next = previous_totals.unwrap_or_default();
-                next.input += delta.input;
-                next.cached += delta.cached;
-                next.output += delta.output;
-                previous_totals = Some(next);
-            }
-
             if delta.input == 0 && delta.cached == 0 && delta.output == 0 {
                 continue;
             }
@@ -1906,6 +1852,54 @@ fn scan_file(
     Ok(())
 }
 
+/// Token delta carried by a Codex `token_count` payload. Cumulative
+/// `total_token_usage` snapshots are diffed against `previous_totals`.
+/// Some streams emit `last_token_usage` deltas between those snapshots; they
+/// are added to `previous_totals` so the next snapshot does not count them twice.
+fn codex_token_count_delta(
+    info: &serde_json::Map<String, Value>,
+    previous_totals: &mut Option<UsageTotals>,
+) -> Option<UsageTotals> {
+    let (usage, used_total) =
+        if let Some(total) = find_usage_map(info, &["total_token_usage", "totalTokenUsage"]) {
+            (total, true)
+        } else {
+            (
+                find_usage_map(info, &["last_token_usage", "lastTokenUsage"])?,
+                false,
+            )
+        };
+    let current = UsageTotals {
+        input: read_i64(usage, &["input_tokens", "inputTokens"]),
+        cached: read_i64(
+            usage,
+            &[
+                "cached_input_tokens",
+                "cache_read_input_tokens",
+                "cachedInputTokens",
+                "cacheReadInputTokens",
+            ],
+        ),
+        output: read_i64(usage, &["output_tokens", "outputTokens"]),
+    };
+    let prev = previous_totals.unwrap_or_default();
+    if used_total {
+        *previous_totals = Some(current);
+        Some(UsageTotals {
+            input: (current.input - prev.input).max(0),
+            cached: (current.cached - prev.cached).max(0),
+            output: (current.output - prev.output).max(0),
+        })
+    } else {
+        *previous_totals = Some(UsageTotals {
+            input: prev.input + current.input,
+            cached: prev.cached + current.cached,
+            output: prev.output + current.output,
+        });
+        Some(current)
+    }
+}
+
 fn extract_model_from_turn_context(value: &Value) -> Option<String> {
     let payload = value.get("payload").and_then(|value| value.as_object())?;
     if let Some(model) = payload.get("model").and_then(|value| value.as_str()) {
@@ -1955,7 +1949,7 @@ fn read_i64(map: &serde_json::Map<String, Value>, keys: &[&str]) -> i64 {
         .unwrap_or(0)
 }
 
-fn read_timestamp_ms(value: &Value) -> Option<i64> {
+pub(crate) fn read_timestamp_ms(value: &Value) -> Option<i64> {
     let raw = value.get("timestamp")?;
     if let Some(text) = raw.as_str() {
         return DateTime::parse_from_rfc3339(text)
@@ -1994,7 +1988,7 @@ fn day_key_for_timestamp_ms(timestamp_ms: i64) -> Option<String> {
     Some(utc.with_timezone(&Local).format("%Y-%m-%d").to_string())
 }
 
-fn extract_cwd(value: &Value) -> Option<String> {
+pub(crate) fn extract_cwd(value: &Value) -> Option<String> {
     let root = value.as_object()?;
     let payload = root.get("payload").and_then(Value::as_object);
     let session_meta = root
@@ -2207,7 +2201,7 @@ fn merge_codex_session_roots(
     roots
 }
 
-fn resolve_sessions_roots(
+pub(crate) fn resolve_sessions_roots(
     workspaces: &HashMap<String, WorkspaceEntry>,
     workspace_path: Option<&Path>,
 ) -> Vec<PathBuf> {
@@ -1953,3 +1953,89 @@ fn path_matches_workspace_handles_unc_extended_prefix() {
         workspace
     ));
 }
+
+#[test]
+fn usage_events_sync_once_and_group_by_engine_and_workspace() {
+    let base = make_temp_sessions_root();
+    let codex_root = base.join("sessions");
+    let codex_lines = vec![
+        r#"{"timestamp":"2026-01-19T12:00:00.000Z","type":"session_meta","payload":{"cwd":"/tmp/usage-ws-a"}}"#.to_string(),
+        r#"{"timestamp":"2026-01-19T12:00:00.000Z","type":"turn_context","payload":{"cwd":"/tmp/usage-ws-a","model":"gpt-5"}}"#.to_string(),
+        r#"{"timestamp":"2026-01-19T12:00:01.000Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":100,"cached_input_tokens":40,"output_tokens":20}}}}"#.to_string(),
+        r#"{"timestamp":"2026-01-19T12:00:02.000Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":150,"cached_input_tokens":40,"output_tokens":30}}}}"#.to_string(),
+    ];
+    write_session_file(&codex_root, "2026-01-19", &codex_lines);
+    let claude_project = base.join("claude-projects").join("-tmp-usage-ws-b");
+    fs::create_dir_all(&claude_project).expect("create claude project dir");
+    fs::write(
+        claude_project.join("session-b.jsonl"),
+        r#"{"type":"assistant","timestamp":"2026-01-19T13:00:00.000Z","cwd":"/tmp/usage-ws-b","message":{"model":"claude-sonnet-4","usage":{"input_tokens":40,"output_tokens":10,"cache_read_input_tokens":5}}}"#,
+    )
+    .expect("write claude session");
+
+    let mut connection =
+        open_usage_events(&base.join("local-usage.sqlite3")).expect("open usage db");
+    let sources = collect_usage_sources(&[codex_root], Some(&base.join("claude-projects")));
+    assert_eq!(sources.len(), 2);
+    sync_usage_events(&mut connection, &sources).expect("first sync");
+    sync_usage_events(&mut connection, &sources).expect("unchanged sync");
+
+    let (by_engine, totals) =
+        query_usage_groups(&connection, 0, UsageGroupBy::Engine, &[], None).expect("by engine");
+    let engine_tokens = by_engine
+        .iter()
+        .map(|group| (group.key.as_str(), group.total_tokens, group.events))
+        .collect::<Vec<_>>();
+    assert_eq!(engine_tokens, vec![("codex", 180, 2), ("claude", 50, 1)]);
+    assert_eq!(totals.total_tokens, 230);
+    assert_eq!(totals.cached_input_tokens, 45);
+    assert!(totals.cost > 0.0);
+
+    let workspaces = vec![
+        UsageWorkspace {
+            id: "ws-a".to_string(),
+            name: "Project A".to_string(),
+            path: PathBuf::from("/tmp/usage-ws-a"),
+        },
+        UsageWorkspace {
+            id: "ws-b".to_string(),
+            name: "Project B".to_string(),
+            path: PathBuf::from("/tmp/usage-ws-b"),
+        },
+    ];
+    let (by_workspace, _) =
+        query_usage_groups(&connection, 0, UsageGroupBy::Workspace, &workspaces, None)
+            .expect("by workspace");
+    let labels = by_workspace
+        .iter()
+        .map(|group| (group.key.as_str(), group.label.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(labels, vec![("ws-a", "Project A"), ("ws-b", "Project B")]);
+
+    let (models_in_b, totals_in_b) = query_usage_groups(
+        &connection,
+        0,
+        UsageGroupBy::Model,
+        &workspaces,
+        Some("ws-b"),
+    )
+    .expect("models in ws-b");
+    assert_eq!(models_in_b.len(), 1);
+    assert_eq!(models_in_b[0].key, "claude-sonnet-4");
+    assert_eq!(totals_in_b.total_tokens, 50);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn usage_range_cutoff_accepts_all_and_day_counts() {
+    let now_ms = 10 * 24 * 60 * 60 * 1000;
+    assert_eq!(usage_range_cutoff_ms("all", now_ms), Ok(0));
+    assert_eq!(
+        usage_range_cutoff_ms("7d", now_ms),
+        Ok(3 * 24 * 60 * 60 * 1000)
+    );
+    assert!(usage_range_cutoff_ms("0d", now_ms).is_err());
+    assert!(usage_range_cutoff_ms("week", now_ms).is_err());
+    assert!(UsageGroupBy::parse("provider").is_err());
+}
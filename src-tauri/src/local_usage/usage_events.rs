@@ -0,0 +1,580 @@
+//! Per-event token usage behind `local_usage_query`.
+//!
+//! Codex and Claude session files are folded into `local-usage.sqlite3` in
+//! the app data dir, one row per usage event. A file is re-read only when its
+//! size or mtime changed, and rows are keyed by file name so sessions that are
+//! archived (moved) are not counted twice. Rows outlive the session files, so
+//! deleting a session does not rewrite usage history.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use rusqlite::{params, Connection};
+use serde_json::Value;
+
+use crate::types::{LocalUsageGroup, LocalUsageUsageData};
+
+use super::{
+    calculate_usage_cost, claude_cost_rates, codex_cost_rates, codex_token_count_delta,
+    collect_jsonl_files, extract_cwd, extract_model_from_token_count,
+    extract_model_from_turn_context, path_matches_workspace, read_claude_timestamp,
+    read_timestamp_ms, UsageTotals,
+};
+
+const USAGE_DATABASE_FILE: &str = "local-usage.sqlite3";
+const SQLITE_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_USAGE_LINE_BYTES: usize = 512_000;
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum UsageEngine {
+    Codex,
+    Claude,
+}
+
+impl UsageEngine {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Codex => "codex",
+            Self::Claude => "claude",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum UsageGroupBy {
+    Engine,
+    Model,
+    Workspace,
+    Day,
+}
+
+impl UsageGroupBy {
+    pub(super) fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "engine" => Ok(Self::Engine),
+            "model" => Ok(Self::Model),
+            "workspace" => Ok(Self::Workspace),
+            "day" => Ok(Self::Day),
+            other => Err(format!(
+                "Unsupported usage grouping `{other}`; expected engine, model, workspace or day"
+            )),
+        }
+    }
+
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::Engine => "engine",
+            Self::Model => "model",
+            Self::Workspace => "workspace",
+            Self::Day => "day",
+        }
+    }
+
+    /// SQL expression for the group key. Workspace groups are resolved from
+    /// `workspace_path` after the query, so they share one SQL key.
+    fn key_sql(self) -> &'static str {
+        match self {
+            Self::Engine => "engine",
+            Self::Model => "model",
+            Self::Workspace => "''",
+            Self::Day => "date(timestamp_ms / 1000, 'unixepoch', 'localtime')",
+        }
+    }
+}
+
+/// Workspace that usage can be attributed to.
+#[derive(Debug, Clone)]
+pub(super) struct UsageWorkspace {
+    pub(super) id: String,
+    pub(super) name: String,
+    pub(super) path: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct UsageSource {
+    pub(super) engine: UsageEngine,
+    pub(super) path: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct UsageEventRow {
+    pub(super) line: i64,
+    pub(super) timestamp_ms: i64,
+    pub(super) model: String,
+    pub(super) workspace_path: Option<String>,
+    pub(super) input_tokens: i64,
+    pub(super) cached_input_tokens: i64,
+    pub(super) output_tokens: i64,
+    pub(super) cost: Option<f64>,
+}
+
+pub(super) fn usage_events_db_path() -> Result<PathBuf, String> {
+    Ok(crate::app_paths::app_home_dir()?.join(USAGE_DATABASE_FILE))
+}
+
+pub(super) fn open_usage_events(db_path: &Path) -> Result<Connection, String> {
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let connection = Connection::open(db_path).map_err(|error| {
+        format!(
+            "failed to open usage database {}: {error}",
+            db_path.display()
+        )
+    })?;
+    connection
+        .busy_timeout(SQLITE_BUSY_TIMEOUT)
+        .map_err(|error| error.to_string())?;
+    connection
+        .pragma_update(None, "journal_mode", "WAL")
+        .map_err(|error| format!("failed to enable WAL journal: {error}"))?;
+    connection
+        .execute_batch(
+            "CREATE TABLE IF NOT EXISTS usage_sources (
+                source_key TEXT PRIMARY KEY,
+                size INTEGER NOT NULL,
+                modified_ms INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS usage_events (
+                source_key TEXT NOT NULL,
+                line INTEGER NOT NULL,
+                timestamp_ms INTEGER NOT NULL,
+                engine TEXT NOT NULL,
+                model TEXT NOT NULL,
+                workspace_path TEXT,
+                input_tokens INTEGER NOT NULL,
+                cached_input_tokens INTEGER NOT NULL,
+                output_tokens INTEGER NOT NULL,
+                cost REAL,
+                PRIMARY KEY (source_key, line)
+            );
+            CREATE INDEX IF NOT EXISTS usage_events_timestamp
+                ON usage_events (timestamp_ms);",
+        )
+        .map_err(|error| format!("failed to prepare usage database: {error}"))?;
+    Ok(connection)
+}
+
+pub(super) fn collect_usage_sources(
+    codex_roots: &[PathBuf],
+    claude_projects_dir: Option<&Path>,
+) -> Vec<UsageSource> {
+    let mut codex_files = Vec::new();
+    let mut seen = HashSet::new();
+    for root in codex_roots {
+        collect_jsonl_files(root, &mut codex_files, &mut seen);
+    }
+    let mut sources = codex_files
+        .into_iter()
+        .map(|path| UsageSource {
+            engine: UsageEngine::Codex,
+            path,
+        })
+        .collect::<Vec<_>>();
+
+    let Some(projects_dir) = claude_projects_dir else {
+        return sources;
+    };
+    let Ok(projects) = std::fs::read_dir(projects_dir) else {
+        return sources;
+    };
+    for project in projects.flatten() {
+        let Ok(entries) = std::fs::read_dir(project.path()) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_session = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(".jsonl") && !name.starts_with("agent-"));
+            if is_session {
+                sources.push(UsageSource {
+                    engine: UsageEngine::Claude,
+                    path,
+                });
+            }
+        }
+    }
+    sources
+}
+
+/// Re-read every source whose size or mtime changed since the last sync.
+pub(super) fn sync_usage_events(
+    connection: &mut Connection,
+    sources: &[UsageSource],
+) -> Result<(), String> {
+    let known = {
+        let mut statement = connection
+            .prepare("SELECT source_key, size, modified_ms FROM usage_sources")
+            .map_err(|error| error.to_string())?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    (row.get::<_, i64>(1)?, row.get::<_, i64>(2)?),
+                ))
+            })
+            .map_err(|error| error.to_string())?;
+        rows.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(|error| error.to_string())?
+    };
+
+    let mut synced = HashSet::new();
+    for source in sources {
+        let Some(file_name) = source.path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let source_key = format!("{}:{file_name}", source.engine.as_str());
+        if !synced.insert(source_key.clone()) {
+            continue;
+        }
+        let Ok(metadata) = std::fs::metadata(&source.path) else {
+            continue;
+        };
+        let size = metadata.len() as i64;
+        let modified_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or(0);
+        if known.get(&source_key) == Some(&(size, modified_ms)) {
+            continue;
+        }
+
+        let rows = match source.engine {
+            UsageEngine::Codex => parse_codex_usage_events(&source.path),
+            UsageEngine::Claude => parse_claude_usage_events(&source.path),
+        };
+        let transaction = connection
+            .transaction()
+            .map_err(|error| error.to_string())?;
+        transaction
+            .execute(
+                "DELETE FROM usage_events WHERE source_key = ?1",
+                params![source_key],
+            )
+            .map_err(|error| error.to_string())?;
+        {
+            let mut insert = transaction
+                .prepare(
+                    "INSERT OR REPLACE INTO usage_events (
+                        source_key, line, timestamp_ms, engine, model, workspace_path,
+                        input_tokens, cached_input_tokens, output_tokens, cost
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                )
+                .map_err(|error| error.to_string())?;
+            for row in &rows {
+                insert
+                    .execute(params![
+                        source_key,
+                        row.line,
+                        row.timestamp_ms,
+                        source.engine.as_str(),
+                        row.model,
+                        row.workspace_path,
+                        row.input_tokens,
+                        row.cached_input_tokens,
+                        row.output_tokens,
+                        row.cost,
+                    ])
+                    .map_err(|error| error.to_string())?;
+            }
+        }
+        transaction
+            .execute(
+                "INSERT OR REPLACE INTO usage_sources (source_key, size, modified_ms)
+                 VALUES (?1, ?2, ?3)",
+                params![source_key, size, modified_ms],
+            )
+            .map_err(|error| error.to_string())?;
+        transaction
+            .commit()
+            .map_err(|error| format!("failed to record usage of {source_key}: {error}"))?;
+    }
+    Ok(())
+}
+
+/// Token deltas of a Codex rollout file, with the model and cwd in effect
+/// at each `token_count` event.
+pub(super) fn parse_codex_usage_events(path: &Path) -> Vec<UsageEventRow> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    let mut rows = Vec::new();
+    let mut previous_totals: Option<UsageTotals> = None;
+    let mut current_model: Option<String> = None;
+    let mut current_cwd: Option<String> = None;
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let Ok(line) = line else {
+            continue;
+        };
+        if line.len() > MAX_USAGE_LINE_BYTES {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let entry_type = value
+            .get("type")
+            .and_then(|value| value.as_str())
+            .unwrap_or("");
+
+        if entry_type == "session_meta" || entry_type == "turn_context" {
+            if let Some(cwd) = extract_cwd(&value) {
+                current_cwd = Some(cwd);
+            }
+            if entry_type == "turn_context" {
+                if let Some(model) = extract_model_from_turn_context(&value) {
+                    current_model = Some(model);
+                }
+            }
+            continue;
+        }
+        if !(entry_type == "event_msg" || entry_type.is_empty()) {
+            continue;
+        }
+        let payload = value.get("payload").and_then(|value| value.as_object());
+        if payload
+            .and_then(|payload| payload.get("type"))
+            .and_then(|value| value.as_str())
+            != Some("token_count")
+        {
+            continue;
+        }
+        let Some(delta) = payload
+            .and_then(|payload| payload.get("info"))
+            .and_then(|value| value.as_object())
+            .and_then(|info| codex_token_count_delta(info, &mut previous_totals))
+        else {
+            continue;
+        };
+        if delta.input == 0 && delta.cached == 0 && delta.output == 0 {
+            continue;
+        }
+        let Some(timestamp_ms) = read_timestamp_ms(&value) else {
+            continue;
+        };
+
+        let cost = calculate_usage_cost(
+            &LocalUsageUsageData {
+                input_tokens: delta.input,
+                output_tokens: delta.output,
+                cache_read_tokens: delta.cached,
+                ..LocalUsageUsageData::default()
+            },
+            codex_cost_rates(),
+        );
+        rows.push(UsageEventRow {
+            line: index as i64,
+            timestamp_ms,
+            model: current_model
+                .clone()
+                .or_else(|| extract_model_from_token_count(&value))
+                .unwrap_or_else(|| "unknown".to_string()),
+            workspace_path: current_cwd.clone(),
+            input_tokens: delta.input,
+            cached_input_tokens: delta.cached.min(delta.input),
+            output_tokens: delta.output,
+            cost: Some(cost),
+        });
+    }
+    rows
+}
+
+/// Usage of each assistant message in a Claude Code session file.
+pub(super) fn parse_claude_usage_events(path: &Path) -> Vec<UsageEventRow> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    let mut rows = Vec::new();
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let Ok(line) = line else {
+            continue;
+        };
+        if line.len() > MAX_USAGE_LINE_BYTES {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if value.get("type").and_then(|value| value.as_str()) != Some("assistant") {
+            continue;
+        }
+        let Some(message) = value.get("message").and_then(|value| value.as_object()) else {
+            continue;
+        };
+        let Some(usage) = message.get("usage").and_then(|value| value.as_object()) else {
+            continue;
+        };
+        let read_tokens = |key: &str| usage.get(key).and_then(|value| value.as_i64()).unwrap_or(0);
+        let input_tokens = read_tokens("input_tokens");
+        let output_tokens = read_tokens("output_tokens");
+        let cache_read_tokens = read_tokens("cache_read_input_tokens");
+        let cache_write_tokens = read_tokens("cache_creation_input_tokens");
+        if input_tokens == 0 && output_tokens == 0 {
+            continue;
+        }
+        let Some(timestamp_ms) = read_claude_timestamp(&value) else {
+            continue;
+        };
+
+        let model = message
+            .get("model")
+            .and_then(|value| value.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let cost = calculate_usage_cost(
+            &LocalUsageUsageData {
+                input_tokens,
+                output_tokens,
+                cache_write_tokens,
+                cache_read_tokens,
+                ..LocalUsageUsageData::default()
+            },
+            claude_cost_rates(&model),
+        );
+        rows.push(UsageEventRow {
+            line: index as i64,
+            timestamp_ms,
+            model,
+            workspace_path: extract_cwd(&value),
+            input_tokens,
+            cached_input_tokens: cache_read_tokens + cache_write_tokens,
+            output_tokens,
+            cost: Some(cost),
+        });
+    }
+    rows
+}
+
+/// Earliest event timestamp covered by `range` (`all` or `<n>d`).
+pub(super) fn usage_range_cutoff_ms(range: &str, now_ms: i64) -> Result<i64, String> {
+    if range == "all" {
+        return Ok(0);
+    }
+    range
+        .strip_suffix('d')
+        .and_then(|days| days.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .map(|days| now_ms - days * DAY_MS)
+        .ok_or_else(|| format!("Unsupported usage range `{range}`; expected `all` or `<days>d`"))
+}
+
+/// Grouped totals of the events at or after `cutoff_ms`, plus their sum.
+/// With `workspace_id`, only events from that workspace are counted.
+pub(super) fn query_usage_groups(
+    connection: &Connection,
+    cutoff_ms: i64,
+    group_by: UsageGroupBy,
+    workspaces: &[UsageWorkspace],
+    workspace_id: Option<&str>,
+) -> Result<(Vec<LocalUsageGroup>, LocalUsageGroup), String> {
+    let sql = format!(
+        "SELECT {key} AS group_key, workspace_path,
+                SUM(input_tokens), SUM(cached_input_tokens), SUM(output_tokens),
+                SUM(cost), COUNT(*)
+         FROM usage_events
+         WHERE timestamp_ms >= ?1
+         GROUP BY group_key, workspace_path",
+        key = group_by.key_sql()
+    );
+    let mut statement = connection
+        .prepare(&sql)
+        .map_err(|error| error.to_string())?;
+    let rows = statement
+        .query_map(params![cutoff_ms], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                row.get::<_, Option<String>>(1)?,
+                LocalUsageGroup {
+                    input_tokens: row.get(2)?,
+                    cached_input_tokens: row.get(3)?,
+                    output_tokens: row.get(4)?,
+                    cost: row.get::<_, Option<f64>>(5)?.unwrap_or(0.0),
+                    events: row.get(6)?,
+                    ..LocalUsageGroup::default()
+                },
+            ))
+        })
+        .map_err(|error| error.to_string())?;
+
+    let mut workspace_by_path: HashMap<Option<String>, Option<&UsageWorkspace>> = HashMap::new();
+    let mut groups: HashMap<String, LocalUsageGroup> = HashMap::new();
+    let mut totals = LocalUsageGroup {
+        key: "total".to_string(),
+        label: "Total".to_string(),
+        ..LocalUsageGroup::default()
+    };
+    for row in rows {
+        let (key, workspace_path, usage) = row.map_err(|error| error.to_string())?;
+        let workspace = *workspace_by_path
+            .entry(workspace_path.clone())
+            .or_insert_with(|| {
+                workspace_path
+                    .as_deref()
+                    .and_then(|path| workspace_for_path(workspaces, path))
+            });
+        if let Some(filter) = workspace_id {
+            if workspace.map(|workspace| workspace.id.as_str()) != Some(filter) {
+                continue;
+            }
+        }
+        let (key, label) = match group_by {
+            UsageGroupBy::Workspace => match workspace {
+                Some(workspace) => (workspace.id.clone(), workspace.name.clone()),
+                None => ("other".to_string(), "Other".to_string()),
+            },
+            _ => (key.clone(), key),
+        };
+        let group = groups
+            .entry(key.clone())
+            .or_insert_with(|| LocalUsageGroup {
+                key,
+                label,
+                ..LocalUsageGroup::default()
+            });
+        add_group_usage(group, &usage);
+        add_group_usage(&mut totals, &usage);
+    }
+
+    let mut groups = groups.into_values().collect::<Vec<_>>();
+    if group_by == UsageGroupBy::Day {
+        groups.sort_by(|a, b| a.key.cmp(&b.key));
+    } else {
+        groups.sort_by(|a, b| {
+            b.total_tokens
+                .cmp(&a.total_tokens)
+                .then_with(|| a.key.cmp(&b.key))
+        });
+    }
+    Ok((groups, totals))
+}
+
+fn add_group_usage(target: &mut LocalUsageGroup, usage: &LocalUsageGroup) {
+    target.input_tokens += usage.input_tokens;
+    target.cached_input_tokens += usage.cached_input_tokens;
+    target.output_tokens += usage.output_tokens;
+    target.total_tokens = target.input_tokens + target.output_tokens;
+    target.cost += usage.cost;
+    target.events += usage.events;
+}
+
+/// The most specific workspace containing `path`, so worktrees win over
+/// their parent repository.
+fn workspace_for_path<'a>(
+    workspaces: &'a [UsageWorkspace],
+    path: &str,
+) -> Option<&'a UsageWorkspace> {
+    workspaces
+        .iter()
+        .filter(|workspace| path_matches_workspace(path, &workspace.path))
+        .max_by_key(|workspace| workspace.path.as_os_str().len())
+}
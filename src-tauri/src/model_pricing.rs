@@ -0,0 +1,261 @@
+//! Per-model token prices used to estimate what a turn cost.
+//!
+//! Prices are USD per million tokens. The built-in table covers the models
+//! the app ships defaults for; `model-pricing.json` in the app home can add
+//! models or override built-in entries, and is re-read whenever its mtime
+//! changes so prices can be corrected without a release:
+//!
+//! ```json
+//! { "models": { "gpt-5.3-codex": { "input": 1.75, "output": 14, "cachedInput": 0.175 } } }
+//! ```
+//!
+//! A model id matches a table key when it equals the key or extends it with a
+//! `-` suffix (`claude-sonnet-4-5-20250929` matches `claude-sonnet-4`); the
+//! longest matching key wins. Models matching no key have no price, and
+//! callers must report them as unknown rather than free.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+const PRICING_FILE_NAME: &str = "model-pricing.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelPrice {
+    pub(crate) input: f64,
+    pub(crate) output: f64,
+    /// Price of cache-served input; uncached input price when absent.
+    #[serde(default)]
+    pub(crate) cached_input: Option<f64>,
+}
+
+impl ModelPrice {
+    const fn new(input: f64, output: f64, cached_input: f64) -> Self {
+        Self {
+            input,
+            output,
+            cached_input: Some(cached_input),
+        }
+    }
+
+    /// Cost in USD of `input` uncached input, `cached_input` cache-served
+    /// input and `output` output tokens.
+    pub(crate) fn cost_usd(&self, input: u64, cached_input: u64, output: u64) -> f64 {
+        let per_token = |tokens: u64, price: f64| tokens as f64 / 1_000_000.0 * price;
+        per_token(input, self.input)
+            + per_token(cached_input, self.cached_input.unwrap_or(self.input))
+            + per_token(output, self.output)
+    }
+}
+
+const BUILTIN_PRICES: &[(&str, ModelPrice)] = &[
+    ("claude-opus-4-0", ModelPrice::new(15.0, 75.0, 1.5)),
+    ("claude-opus-4-20250514", ModelPrice::new(15.0, 75.0, 1.5)),
+    ("claude-opus-4-1", ModelPrice::new(15.0, 75.0, 1.5)),
+    ("claude-opus-4-5", ModelPrice::new(5.0, 25.0, 0.5)),
+    ("claude-opus-4-6", ModelPrice::new(5.0, 25.0, 0.5)),
+    ("claude-sonnet-4", ModelPrice::new(3.0, 15.0, 0.3)),
+    ("claude-haiku-4", ModelPrice::new(1.0, 5.0, 0.1)),
+    ("claude-3-7-sonnet", ModelPrice::new(3.0, 15.0, 0.3)),
+    ("claude-3-5-sonnet", ModelPrice::new(3.0, 15.0, 0.3)),
+    ("claude-3-5-haiku", ModelPrice::new(0.8, 4.0, 0.08)),
+    ("opus", ModelPrice::new(5.0, 25.0, 0.5)),
+    ("sonnet", ModelPrice::new(3.0, 15.0, 0.3)),
+    ("haiku", ModelPrice::new(1.0, 5.0, 0.1)),
+    ("gpt-5", ModelPrice::new(1.25, 10.0, 0.125)),
+    ("gpt-5-mini", ModelPrice::new(0.25, 2.0, 0.025)),
+    ("gpt-5-nano", ModelPrice::new(0.05, 0.4, 0.005)),
+    ("gpt-5.1", ModelPrice::new(1.25, 10.0, 0.125)),
+    ("gpt-5.1-codex-mini", ModelPrice::new(0.25, 2.0, 0.025)),
+    ("gpt-5.2", ModelPrice::new(1.75, 14.0, 0.175)),
+    ("gpt-5.3-codex", ModelPrice::new(1.75, 14.0, 0.175)),
+    ("gpt-5.4", ModelPrice::new(2.5, 15.0, 0.25)),
+    ("gpt-5.5", ModelPrice::new(5.0, 30.0, 0.5)),
+    ("gpt-4.1", ModelPrice::new(2.0, 8.0, 0.5)),
+    ("gpt-4.1-mini", ModelPrice::new(0.4, 1.6, 0.1)),
+    ("o3", ModelPrice::new(2.0, 8.0, 0.5)),
+    ("o4-mini", ModelPrice::new(1.1, 4.4, 0.275)),
+];
+
+#[derive(Debug, Default, Deserialize)]
+struct PricingFile {
+    #[serde(default)]
+    models: HashMap<String, ModelPrice>,
+}
+
+#[derive(Default)]
+struct PricingOverrides {
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    prices: HashMap<String, ModelPrice>,
+}
+
+impl PricingOverrides {
+    /// Re-reads `path` when it differs from the cached file or its mtime
+    /// changed. A missing file clears the overrides; an unreadable one keeps
+    /// the last good prices.
+    fn refresh(&mut self, path: &Path) {
+        let modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if self.path.as_deref() == Some(path) && self.modified == modified {
+            return;
+        }
+        self.path = Some(path.to_path_buf());
+        self.modified = modified;
+        if modified.is_none() {
+            self.prices.clear();
+            return;
+        }
+        match read_pricing_file(path) {
+            Ok(prices) => self.prices = prices,
+            Err(error) => log::warn!("Ignoring {}: {error}", path.display()),
+        }
+    }
+}
+
+fn read_pricing_file(path: &Path) -> Result<HashMap<String, ModelPrice>, String> {
+    let contents = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let file: PricingFile = serde_json::from_str(&contents).map_err(|error| error.to_string())?;
+    Ok(file
+        .models
+        .into_iter()
+        .map(|(model, price)| (normalize_model(&model), price))
+        .collect())
+}
+
+fn overrides() -> std::sync::MutexGuard<'static, PricingOverrides> {
+    static OVERRIDES: OnceLock<Mutex<PricingOverrides>> = OnceLock::new();
+    OVERRIDES
+        .get_or_init(|| Mutex::new(PricingOverrides::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Lowercased model id without a `provider/` prefix.
+fn normalize_model(model: &str) -> String {
+    let model = model.trim();
+    model
+        .rsplit_once('/')
+        .map_or(model, |(_, name)| name)
+        .to_ascii_lowercase()
+}
+
+fn key_matches(model: &str, key: &str) -> bool {
+    model
+        .strip_prefix(key)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
+
+fn lookup<'a>(
+    model: &str,
+    entries: impl Iterator<Item = (&'a str, &'a ModelPrice)>,
+) -> Option<ModelPrice> {
+    entries
+        .filter(|(key, _)| key_matches(model, key))
+        .max_by_key(|(key, _)| key.len())
+        .map(|(_, price)| *price)
+}
+
+fn price_with_overrides(
+    model: &str,
+    overrides: &HashMap<String, ModelPrice>,
+) -> Option<ModelPrice> {
+    let model = normalize_model(model);
+    if model.is_empty() {
+        return None;
+    }
+    lookup(
+        &model,
+        overrides.iter().map(|(key, price)| (key.as_str(), price)),
+    )
+    .or_else(|| {
+        lookup(
+            &model,
+            BUILTIN_PRICES.iter().map(|(key, price)| (*key, price)),
+        )
+    })
+}
+
+/// Price of `model`, preferring `model-pricing.json` over the built-in
+/// table. `None` when the model is not priced anywhere.
+pub(crate) fn model_price(model: &str) -> Option<ModelPrice> {
+    let mut overrides = overrides();
+    if let Ok(home) = crate::app_paths::app_home_dir() {
+        overrides.refresh(&home.join(PRICING_FILE_NAME));
+    }
+    price_with_overrides(model, &overrides.prices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_dash_bounded_key_wins() {
+        let none = HashMap::new();
+        let price = |model: &str| price_with_overrides(model, &none).map(|price| price.input);
+
+        assert_eq!(price("claude-sonnet-4-5-20250929"), Some(3.0));
+        assert_eq!(price("claude-opus-4-1"), Some(15.0));
+        assert_eq!(price("claude-opus-4-20250514"), Some(15.0));
+        assert_eq!(price("claude-opus-4-5-20251101"), Some(5.0));
+        assert_eq!(price("claude-opus-4-6"), Some(5.0));
+        // Older Opus prices must not cover an unlisted Opus release.
+        assert_eq!(price("claude-opus-4-7"), None);
+        assert_eq!(price("openai/gpt-5-mini"), Some(0.25));
+        assert_eq!(price("gpt-5-codex"), Some(1.25));
+        assert_eq!(price("gpt-5.3-codex"), Some(1.75));
+        assert_eq!(price("gpt-5.4"), Some(2.5));
+        // `gpt-5` must not price a newer `gpt-5.x` release.
+        assert_eq!(price("gpt-5.6"), None);
+        assert_eq!(price(""), None);
+    }
+
+    #[test]
+    fn pricing_file_overrides_reload_when_modified() {
+        let dir = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join(PRICING_FILE_NAME);
+        let mut overrides = PricingOverrides::default();
+
+        fs::write(
+            &path,
+            r#"{"models": {"GPT-5.3-Codex": {"input": 2, "output": 16}}}"#,
+        )
+        .expect("write pricing");
+        overrides.refresh(&path);
+        let price = price_with_overrides("gpt-5.3-codex", &overrides.prices).expect("priced");
+        assert_eq!(price.cost_usd(1_000_000, 1_000_000, 0), 4.0);
+
+        fs::write(
+            &path,
+            r#"{"models": {"gpt-5.3-codex": {"input": 3, "output": 16}}}"#,
+        )
+        .expect("rewrite pricing");
+        let file = fs::File::options()
+            .write(true)
+            .open(&path)
+            .expect("open pricing");
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(5))
+            .expect("bump mtime");
+        overrides.refresh(&path);
+        assert_eq!(
+            price_with_overrides("gpt-5.3-codex", &overrides.prices).map(|price| price.input),
+            Some(3.0)
+        );
+
+        fs::remove_file(&path).expect("remove pricing");
+        overrides.refresh(&path);
+        assert_eq!(
+            price_with_overrides("gpt-5.3-codex", &overrides.prices).map(|price| price.input),
+            Some(1.75)
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+}
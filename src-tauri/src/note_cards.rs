@@ -22,6 +22,10 @@ static MARKDOWN_PREFIX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 });
 static MULTISPACE_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\s+").expect("valid multispace regex"));
+static NOTE_REFERENCE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[\[(session|commit):([^\]|\s]+)(?:\|([^\]]+))?\]\]")
+        .expect("valid note reference regex")
+});
 const MAX_NOTE_ATTACHMENT_BYTES: usize = 20 * 1024 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +48,24 @@ pub(crate) struct NoteCardPreviewAttachment {
     pub absolute_path: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum NoteCardLinkKind {
+    Session,
+    Commit,
+}
+
+/// A backlink from a note to a session or commit. Links come from the
+/// explicit `links` input and from `[[session:<id>]]` / `[[commit:<sha>]]`
+/// references in the body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NoteCardLink {
+    pub kind: NoteCardLinkKind,
+    pub target: String,
+    pub label: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct WorkspaceNoteCard {
@@ -59,6 +81,8 @@ pub(crate) struct WorkspaceNoteCard {
     pub created_at: i64,
     pub updated_at: i64,
     pub archived_at: Option<i64>,
+    #[serde(default)]
+    pub links: Vec<NoteCardLink>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +98,8 @@ pub(crate) struct WorkspaceNoteCardSummary {
     pub archived: bool,
     pub image_count: usize,
     pub preview_attachments: Vec<NoteCardPreviewAttachment>,
+    #[serde(default)]
+    pub links: Vec<NoteCardLink>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +118,8 @@ pub(crate) struct CreateWorkspaceNoteCardInput {
     pub title: Option<String>,
     pub body_markdown: String,
     pub attachment_inputs: Option<Vec<String>>,
+    #[serde(default)]
+    pub links: Option<Vec<NoteCardLink>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,6 +130,8 @@ pub(crate) struct UpdateWorkspaceNoteCardInput {
     pub title: Option<String>,
     pub body_markdown: Option<String>,
     pub attachment_inputs: Option<Vec<String>>,
+    #[serde(default)]
+    pub links: Option<Vec<NoteCardLink>>,
 }
 
 fn with_file_lock<T>(op: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
@@ -333,6 +363,58 @@ fn resolve_note_title(explicit_title: Option<&str>, body_markdown: &str) -> Stri
     format!("Note {}", Utc::now().format("%Y-%m-%d %H:%M"))
 }
 
+/// Merges explicit links with the references found in the body, dropping
+/// blanks and duplicates while keeping first-seen order.
+fn resolve_note_links(explicit_links: &[NoteCardLink], body_markdown: &str) -> Vec<NoteCardLink> {
+    let referenced = NOTE_REFERENCE_REGEX
+        .captures_iter(body_markdown)
+        .map(|captures| NoteCardLink {
+            kind: if &captures[1] == "session" {
+                NoteCardLinkKind::Session
+            } else {
+                NoteCardLinkKind::Commit
+            },
+            target: captures[2].to_string(),
+            label: captures.get(3).map(|label| label.as_str().to_string()),
+        });
+    let mut seen = HashSet::new();
+    explicit_links
+        .iter()
+        .cloned()
+        .chain(referenced)
+        .filter_map(|link| {
+            let target = link.target.trim().to_string();
+            if target.is_empty() || !seen.insert((link.kind, target.to_lowercase())) {
+                return None;
+            }
+            Some(NoteCardLink {
+                kind: link.kind,
+                target,
+                label: link
+                    .label
+                    .map(|label| label.trim().to_string())
+                    .filter(|label| !label.is_empty()),
+            })
+        })
+        .collect()
+}
+
+/// Commits match on either sha being a prefix of the other, so a short sha
+/// finds notes that recorded the full one and vice versa.
+fn note_link_matches(link: &NoteCardLink, kind: NoteCardLinkKind, target: &str) -> bool {
+    if link.kind != kind {
+        return false;
+    }
+    match kind {
+        NoteCardLinkKind::Session => link.target == target,
+        NoteCardLinkKind::Commit => {
+            let linked = link.target.to_lowercase();
+            let target = target.to_lowercase();
+            linked.starts_with(&target) || target.starts_with(&linked)
+        }
+    }
+}
+
 fn summarize_note(note: &WorkspaceNoteCard, archived: bool) -> WorkspaceNoteCardSummary {
     WorkspaceNoteCardSummary {
         id: note.id.clone(),
@@ -355,6 +437,7 @@ fn summarize_note(note: &WorkspaceNoteCard, archived: bool) -> WorkspaceNoteCard
                 absolute_path: attachment.absolute_path.clone(),
             })
             .collect(),
+        links: note.links.clone(),
     }
 }
 
@@ -974,11 +1057,20 @@ fn collect_workspace_note_summaries(
                         continue;
                     }
                     if !normalized_query.is_empty() {
+                        let link_text = note
+                            .links
+                            .iter()
+                            .map(|link| {
+                                format!("{} {}", link.target, link.label.as_deref().unwrap_or(""))
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" ");
                         let haystack = format!(
-                            "{} {} {}",
+                            "{} {} {} {}",
                             note.title.to_lowercase(),
                             note.plain_text_excerpt.to_lowercase(),
                             strip_markdown_to_plain_text(&note.body_markdown).to_lowercase(),
+                            link_text.to_lowercase(),
                         );
                         if !haystack.contains(&normalized_query) {
                             continue;
@@ -1149,6 +1241,7 @@ pub(crate) fn note_card_create(
             created_at: current_ms,
             updated_at: current_ms,
             archived_at: None,
+            links: resolve_note_links(input.links.as_deref().unwrap_or(&[]), &body_markdown),
         };
         let path = note_file_path(&project_dir, &note_id, false);
         write_note_card(&path, &note)?;
@@ -1220,6 +1313,8 @@ pub(crate) fn note_card_update(
             patch.title.as_deref().or(Some(note.title.as_str())),
             &body_markdown,
         );
+        let explicit_links = patch.links.clone().unwrap_or_else(|| note.links.clone());
+        note.links = resolve_note_links(&explicit_links, &body_markdown);
         note.body_markdown = body_markdown.clone();
         note.plain_text_excerpt = build_plain_text_excerpt(&body_markdown);
         note.attachments = attachments;
@@ -1330,10 +1425,103 @@ pub(crate) fn note_card_delete(
     })
 }
 
+/// Notes in the workspace, active or archived, that link to the given
+/// session or commit. Newest first.
+#[tauri::command]
+pub(crate) fn note_card_backlinks(
+    workspace_id: String,
+    workspace_name: Option<String>,
+    workspace_path: Option<String>,
+    kind: NoteCardLinkKind,
+    target: String,
+) -> Result<Vec<WorkspaceNoteCardSummary>, String> {
+    let target = target.trim().to_string();
+    if target.is_empty() {
+        return Ok(Vec::new());
+    }
+    with_file_lock(|| {
+        let base = storage_dir()?;
+        if !base.exists() {
+            return Ok(Vec::new());
+        }
+        let mut items = Vec::new();
+        for archived in [false, true] {
+            let summaries = collect_workspace_note_summaries(
+                &base,
+                Some(workspace_id.as_str()),
+                workspace_name.as_deref(),
+                workspace_path.as_deref(),
+                archived,
+                None,
+            )?;
+            items.extend(summaries.into_iter().filter(|summary| {
+                summary
+                    .links
+                    .iter()
+                    .any(|link| note_link_matches(link, kind, &target))
+            }));
+        }
+        items.sort_by(|left, right| {
+            right
+                .updated_at
+                .cmp(&left.updated_at)
+                .then_with(|| left.id.cmp(&right.id))
+        });
+        Ok(items)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn resolves_links_from_input_and_body_references() {
+        let explicit = vec![NoteCardLink {
+            kind: NoteCardLinkKind::Session,
+            target: " thread-1 ".to_string(),
+            label: Some("  ".to_string()),
+        }];
+        let body = "Fixed in [[commit:ABC1234|the fix]], see [[session:thread-1]] and [[session:thread-2]].";
+        let links = resolve_note_links(&explicit, body);
+        assert_eq!(
+            links,
+            vec![
+                NoteCardLink {
+                    kind: NoteCardLinkKind::Session,
+                    target: "thread-1".to_string(),
+                    label: None,
+                },
+                NoteCardLink {
+                    kind: NoteCardLinkKind::Commit,
+                    target: "ABC1234".to_string(),
+                    label: Some("the fix".to_string()),
+                },
+                NoteCardLink {
+                    kind: NoteCardLinkKind::Session,
+                    target: "thread-2".to_string(),
+                    label: None,
+                },
+            ]
+        );
+
+        assert!(note_link_matches(
+            &links[1],
+            NoteCardLinkKind::Commit,
+            "abc1234def5678"
+        ));
+        assert!(!note_link_matches(
+            &links[1],
+            NoteCardLinkKind::Session,
+            "ABC1234"
+        ));
+        assert!(!note_link_matches(
+            &links[0],
+            NoteCardLinkKind::Session,
+            "thread"
+        ));
+    }
+
     fn test_project_dir(
         base: &Path,
         workspace_id: Option<&str>,
@@ -1386,6 +1574,7 @@ mod tests {
                 created_at: now_ms(),
                 updated_at: now_ms(),
                 archived_at: None,
+                links: Vec::new(),
             };
             let path = note_file_path(&project_dir, &note_id, false);
             write_note_card(&path, &note).expect("write active note");
@@ -1467,6 +1656,7 @@ mod tests {
             created_at: now_ms(),
             updated_at: now_ms(),
             archived_at: None,
+            links: Vec::new(),
         };
 
         let summary = summarize_note(&note, false);
@@ -1508,6 +1698,7 @@ mod tests {
             created_at: now_ms(),
             updated_at: now_ms(),
             archived_at: None,
+            links: Vec::new(),
         };
 
         let note_path = note_file_path(&project_dir, &note_id, false);
@@ -1584,6 +1775,7 @@ mod tests {
             created_at: now_ms(),
             updated_at: now_ms(),
             archived_at: None,
+            links: Vec::new(),
         };
         let note_path = note_file_path(&original_project_dir, &note_id, false);
         write_note_card(&note_path, &note).expect("write original note");
@@ -1726,6 +1918,7 @@ mod tests {
             created_at: now_ms(),
             updated_at: now_ms(),
             archived_at: None,
+            links: Vec::new(),
         };
         write_note_card(&note_file_path(&project_dir, &note.id, false), &note).expect("write note");
 
@@ -1782,6 +1975,7 @@ mod tests {
             created_at: now_ms(),
             updated_at: now_ms(),
             archived_at: None,
+            links: Vec::new(),
         };
         let fallback_note_path = note_file_path(&fallback_project_dir, &note_id, false);
         write_note_card(&fallback_note_path, &valid_note).expect("write valid note");
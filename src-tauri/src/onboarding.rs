@@ -0,0 +1,801 @@
+//! First-run onboarding backend.
+//!
+//! Diagnoses which engine CLIs and developer tools are missing, proposes a
+//! guided install for each (npm for engine CLIs, the platform package manager
+//! for git/gh/node), runs the allowed ones with output streamed over
+//! `onboarding-install-event`, and persists wizard progress in
+//! `~/.ccgui/onboarding.json` so the frontend can resume.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::time::timeout;
+
+use crate::app_paths;
+use crate::backend::app_server::{
+    build_codex_path_env, build_command_for_binary, check_cli_binary, find_cli_binary,
+};
+use crate::codex::{
+    package_name_for_engine, run_cli_installer_with_progress, CliInstallAction, CliInstallEngine,
+    CliInstallOutputStream, CliInstallProgressEvent, CliInstallProgressPhase, CliInstallStrategy,
+};
+use crate::engine::{EngineStatus, EngineType};
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+use crate::types::AppSettings;
+
+const ONBOARDING_STATE_FILE: &str = "onboarding.json";
+const ONBOARDING_INSTALL_EVENT: &str = "onboarding-install-event";
+const INSTALL_TIMEOUT_SECS: u64 = 600;
+const OUTPUT_LINE_LIMIT: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OnboardingTool {
+    Claude,
+    Codex,
+    Gemini,
+    OpenCode,
+    Git,
+    Gh,
+    Node,
+}
+
+const ALL_TOOLS: [OnboardingTool; 7] = [
+    OnboardingTool::Claude,
+    OnboardingTool::Codex,
+    OnboardingTool::Gemini,
+    OnboardingTool::OpenCode,
+    OnboardingTool::Git,
+    OnboardingTool::Gh,
+    OnboardingTool::Node,
+];
+
+impl OnboardingTool {
+    fn engine_type(self) -> Option<EngineType> {
+        match self {
+            Self::Claude => Some(EngineType::Claude),
+            Self::Codex => Some(EngineType::Codex),
+            Self::Gemini => Some(EngineType::Gemini),
+            Self::OpenCode => Some(EngineType::OpenCode),
+            Self::Git | Self::Gh | Self::Node => None,
+        }
+    }
+
+    fn binary(self) -> &'static str {
+        match self {
+            Self::Claude => "claude",
+            Self::Codex => "codex",
+            Self::Gemini => "gemini",
+            Self::OpenCode => "opencode",
+            Self::Git => "git",
+            Self::Gh => "gh",
+            Self::Node => "node",
+        }
+    }
+
+    /// Git is the only hard requirement; one engine CLI is also required but
+    /// any of them satisfies it (see [`OnboardingDiagnostics::ready`]).
+    fn required(self) -> bool {
+        matches!(self, Self::Git)
+    }
+
+    fn npm_package(self) -> Option<&'static str> {
+        match self {
+            Self::Claude => Some(package_name_for_engine(CliInstallEngine::Claude)),
+            Self::Codex => Some(package_name_for_engine(CliInstallEngine::Codex)),
+            Self::Gemini => Some("@google/gemini-cli@latest"),
+            Self::OpenCode => Some("opencode-ai@latest"),
+            Self::Git | Self::Gh | Self::Node => None,
+        }
+    }
+
+    fn cli_install_engine(self) -> Option<CliInstallEngine> {
+        match self {
+            Self::Claude => Some(CliInstallEngine::Claude),
+            Self::Codex => Some(CliInstallEngine::Codex),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum OnboardingInstallMethod {
+    Npm,
+    Homebrew,
+    Winget,
+    Manual,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OnboardingInstallGuide {
+    pub(crate) method: OnboardingInstallMethod,
+    /// Command that would be run, for display and manual copy.
+    pub(crate) command: Vec<String>,
+    /// Whether `onboarding_install` can run the command without elevation.
+    pub(crate) can_run: bool,
+    pub(crate) blockers: Vec<String>,
+    pub(crate) docs_url: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OnboardingToolStatus {
+    pub(crate) tool: OnboardingTool,
+    pub(crate) installed: bool,
+    pub(crate) version: Option<String>,
+    pub(crate) required: bool,
+    pub(crate) error: Option<String>,
+    pub(crate) install: Option<OnboardingInstallGuide>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OnboardingDiagnostics {
+    pub(crate) platform: &'static str,
+    pub(crate) tools: Vec<OnboardingToolStatus>,
+    /// Git is installed and at least one engine CLI is available.
+    pub(crate) ready: bool,
+    pub(crate) state: OnboardingState,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OnboardingState {
+    #[serde(default)]
+    pub(crate) current_step: Option<String>,
+    #[serde(default)]
+    pub(crate) completed_steps: Vec<String>,
+    /// Set when the wizard was finished or skipped.
+    #[serde(default)]
+    pub(crate) completed_at: Option<i64>,
+    #[serde(default)]
+    pub(crate) skipped: bool,
+    #[serde(default)]
+    pub(crate) updated_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum OnboardingInstallPhase {
+    Started,
+    Stdout,
+    Stderr,
+    Finished,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OnboardingInstallEvent {
+    run_id: String,
+    tool: OnboardingTool,
+    phase: OnboardingInstallPhase,
+    message: Option<String>,
+    exit_code: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OnboardingInstallResult {
+    pub(crate) ok: bool,
+    pub(crate) tool: OnboardingTool,
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) details: Option<String>,
+    pub(crate) duration_ms: u128,
+    /// Tool status re-detected after the install.
+    pub(crate) status: OnboardingToolStatus,
+}
+
+/// Installed package managers and runtimes that decide which guides can run.
+#[derive(Debug, Clone, Copy, Default)]
+struct InstallEnvironment {
+    npm: bool,
+    homebrew: bool,
+    winget: bool,
+}
+
+fn current_platform() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "macos"
+    } else if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "linux") {
+        "linux"
+    } else {
+        "unknown"
+    }
+}
+
+fn docs_url_for(tool: OnboardingTool) -> &'static str {
+    match tool {
+        OnboardingTool::Claude => "https://docs.anthropic.com/en/docs/claude-code/setup",
+        OnboardingTool::Codex => "https://github.com/openai/codex",
+        OnboardingTool::Gemini => "https://github.com/google-gemini/gemini-cli",
+        OnboardingTool::OpenCode => "https://opencode.ai/docs",
+        OnboardingTool::Git => "https://git-scm.com/downloads",
+        OnboardingTool::Gh => "https://cli.github.com",
+        OnboardingTool::Node => "https://nodejs.org/en/download",
+    }
+}
+
+fn to_command(parts: &[&str]) -> Vec<String> {
+    parts.iter().map(|part| part.to_string()).collect()
+}
+
+/// Picks the install route for `tool`. Only user-level installs are runnable;
+/// anything needing sudo or an administrator prompt is returned as manual.
+fn install_guide_for(
+    tool: OnboardingTool,
+    platform: &str,
+    env: InstallEnvironment,
+) -> OnboardingInstallGuide {
+    let docs_url = docs_url_for(tool);
+    if let Some(package) = tool.npm_package() {
+        let blockers = if env.npm {
+            Vec::new()
+        } else {
+            vec!["npm is not available; install Node.js first.".to_string()]
+        };
+        return OnboardingInstallGuide {
+            method: OnboardingInstallMethod::Npm,
+            command: to_command(&["npm", "install", "-g", package]),
+            can_run: blockers.is_empty(),
+            blockers,
+            docs_url,
+        };
+    }
+
+    let (homebrew_formula, winget_id, linux_package) = match tool {
+        OnboardingTool::Git => ("git", "Git.Git", "git"),
+        OnboardingTool::Gh => ("gh", "GitHub.cli", "gh"),
+        _ => ("node", "OpenJS.NodeJS.LTS", "nodejs npm"),
+    };
+    match platform {
+        "macos" if env.homebrew => OnboardingInstallGuide {
+            method: OnboardingInstallMethod::Homebrew,
+            command: to_command(&["brew", "install", homebrew_formula]),
+            can_run: true,
+            blockers: Vec::new(),
+            docs_url,
+        },
+        "windows" if env.winget => OnboardingInstallGuide {
+            method: OnboardingInstallMethod::Winget,
+            command: to_command(&[
+                "winget",
+                "install",
+                "--id",
+                winget_id,
+                "-e",
+                "--source",
+                "winget",
+                "--accept-package-agreements",
+                "--accept-source-agreements",
+            ]),
+            can_run: true,
+            blockers: Vec::new(),
+            docs_url,
+        },
+        "macos" => OnboardingInstallGuide {
+            method: OnboardingInstallMethod::Manual,
+            command: to_command(&["brew", "install", homebrew_formula]),
+            can_run: false,
+            blockers: vec!["Homebrew is not installed.".to_string()],
+            docs_url,
+        },
+        "windows" => OnboardingInstallGuide {
+            method: OnboardingInstallMethod::Manual,
+            command: to_command(&["winget", "install", "--id", winget_id, "-e"]),
+            can_run: false,
+            blockers: vec!["winget is not available.".to_string()],
+            docs_url,
+        },
+        _ => OnboardingInstallGuide {
+            method: OnboardingInstallMethod::Manual,
+            command: ["sudo", "apt", "install"]
+                .into_iter()
+                .chain(linux_package.split(' '))
+                .map(str::to_string)
+                .collect(),
+            can_run: false,
+            blockers: vec![
+                "System packages need elevated privileges; run the command in a terminal."
+                    .to_string(),
+            ],
+            docs_url,
+        },
+    }
+}
+
+async fn detect_install_environment(path_env: Option<String>) -> InstallEnvironment {
+    let (npm, homebrew, winget) = tokio::join!(
+        check_cli_binary("npm", path_env.clone()),
+        async {
+            if cfg!(target_os = "macos") {
+                check_cli_binary("brew", path_env.clone()).await
+            } else {
+                Err("not_found".to_string())
+            }
+        },
+        async {
+            if cfg!(target_os = "windows") {
+                check_cli_binary("winget", path_env.clone()).await
+            } else {
+                Err("not_found".to_string())
+            }
+        },
+    );
+    InstallEnvironment {
+        npm: npm.is_ok(),
+        homebrew: homebrew.is_ok(),
+        winget: winget.is_ok(),
+    }
+}
+
+fn tool_status(
+    tool: OnboardingTool,
+    probe: Result<Option<String>, String>,
+    env: InstallEnvironment,
+) -> OnboardingToolStatus {
+    let (installed, version, error) = match probe {
+        Ok(version) => (true, version, None),
+        Err(error) if error == "not_found" => (false, None, None),
+        Err(error) => (false, None, Some(error)),
+    };
+    OnboardingToolStatus {
+        tool,
+        installed,
+        version,
+        required: tool.required(),
+        error,
+        install: (!installed).then(|| install_guide_for(tool, current_platform(), env)),
+    }
+}
+
+/// Engine detection reports a missing CLI through `installed`; its error
+/// text is only surfaced when the CLI exists but failed to run.
+fn engine_status_probe(status: &EngineStatus) -> Result<Option<String>, String> {
+    if status.installed {
+        Ok(status.version.clone())
+    } else {
+        Err("not_found".to_string())
+    }
+}
+
+/// Optional engines switched off in settings are left out of onboarding.
+fn tool_enabled(tool: OnboardingTool, settings: &AppSettings) -> bool {
+    match tool {
+        OnboardingTool::Gemini => settings.gemini_enabled,
+        OnboardingTool::OpenCode => settings.opencode_enabled,
+        _ => true,
+    }
+}
+
+async fn detect_tool(
+    tool: OnboardingTool,
+    state: &AppState,
+    settings: &AppSettings,
+    env: InstallEnvironment,
+) -> OnboardingToolStatus {
+    let probe = match tool.engine_type() {
+        Some(engine_type) => engine_status_probe(
+            &state
+                .engine_manager
+                .refresh_engine_status_with_gates(
+                    engine_type,
+                    settings.gemini_enabled,
+                    settings.opencode_enabled,
+                )
+                .await,
+        ),
+        None => check_cli_binary(tool.binary(), build_codex_path_env(None)).await,
+    };
+    tool_status(tool, probe, env)
+}
+
+async fn collect_tool_statuses(
+    state: &AppState,
+    settings: &AppSettings,
+) -> Vec<OnboardingToolStatus> {
+    let path_env = build_codex_path_env(None);
+    let env = detect_install_environment(path_env.clone()).await;
+    let engine_statuses = state
+        .engine_manager
+        .detect_engines_with_gates(settings.gemini_enabled, settings.opencode_enabled)
+        .await;
+
+    let mut tools = Vec::with_capacity(ALL_TOOLS.len());
+    for tool in ALL_TOOLS
+        .into_iter()
+        .filter(|tool| tool_enabled(*tool, settings))
+    {
+        let probe = match tool.engine_type() {
+            Some(engine_type) => engine_statuses
+                .iter()
+                .find(|status| status.engine_type == engine_type)
+                .map_or_else(|| Err("not_found".to_string()), engine_status_probe),
+            None => check_cli_binary(tool.binary(), path_env.clone()).await,
+        };
+        tools.push(tool_status(tool, probe, env));
+    }
+    tools
+}
+
+fn onboarding_ready(tools: &[OnboardingToolStatus]) -> bool {
+    let required_ok = tools
+        .iter()
+        .filter(|status| status.required)
+        .all(|status| status.installed);
+    let engine_ok = tools
+        .iter()
+        .any(|status| status.tool.engine_type().is_some() && status.installed);
+    required_ok && engine_ok
+}
+
+fn onboarding_state_path() -> Result<PathBuf, String> {
+    Ok(app_paths::app_home_dir()?.join(ONBOARDING_STATE_FILE))
+}
+
+fn read_onboarding_state() -> Result<OnboardingState, String> {
+    Ok(read_json_file(&onboarding_state_path()?)?.unwrap_or_default())
+}
+
+fn write_onboarding_state(state: &OnboardingState) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(state).map_err(|error| error.to_string())?;
+    write_string_atomically(&onboarding_state_path()?, &data)
+}
+
+fn apply_progress(
+    mut state: OnboardingState,
+    current_step: Option<String>,
+    completed_step: Option<String>,
+    now: i64,
+) -> OnboardingState {
+    if let Some(step) = completed_step
+        .map(|step| step.trim().to_string())
+        .filter(|step| !step.is_empty())
+    {
+        if !state.completed_steps.contains(&step) {
+            state.completed_steps.push(step);
+        }
+    }
+    if let Some(step) = current_step
+        .map(|step| step.trim().to_string())
+        .filter(|step| !step.is_empty())
+    {
+        state.current_step = Some(step);
+    }
+    state.updated_at = now;
+    state
+}
+
+fn emit_install_event(app: &AppHandle, event: OnboardingInstallEvent) {
+    let _ = app.emit(ONBOARDING_INSTALL_EVENT, event);
+}
+
+fn truncate_line(line: &str) -> String {
+    if line.chars().count() <= OUTPUT_LINE_LIMIT {
+        return line.to_string();
+    }
+    let mut truncated: String = line.chars().take(OUTPUT_LINE_LIMIT).collect();
+    truncated.push_str(" ...");
+    truncated
+}
+
+fn map_cli_install_event(
+    tool: OnboardingTool,
+    event: CliInstallProgressEvent,
+) -> OnboardingInstallEvent {
+    let phase = match (event.phase, event.stream) {
+        (CliInstallProgressPhase::Started, _) => OnboardingInstallPhase::Started,
+        (CliInstallProgressPhase::Finished, _) => OnboardingInstallPhase::Finished,
+        (CliInstallProgressPhase::Error, _) => OnboardingInstallPhase::Error,
+        (_, Some(CliInstallOutputStream::Stderr)) | (CliInstallProgressPhase::Stderr, _) => {
+            OnboardingInstallPhase::Stderr
+        }
+        _ => OnboardingInstallPhase::Stdout,
+    };
+    OnboardingInstallEvent {
+        run_id: event.run_id,
+        tool,
+        phase,
+        message: event.message,
+        exit_code: event.exit_code,
+    }
+}
+
+async fn pipe_output<R>(
+    stream: Option<R>,
+    app: AppHandle,
+    run_id: String,
+    tool: OnboardingTool,
+    phase: OnboardingInstallPhase,
+) where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let Some(stream) = stream else {
+        return;
+    };
+    let mut lines = BufReader::new(stream).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        emit_install_event(
+            &app,
+            OnboardingInstallEvent {
+                run_id: run_id.clone(),
+                tool,
+                phase,
+                message: Some(truncate_line(&line)),
+                exit_code: None,
+            },
+        );
+    }
+}
+
+/// Runs `command`, streaming each output line, and returns its exit code.
+async fn run_install_command(
+    app: &AppHandle,
+    run_id: &str,
+    tool: OnboardingTool,
+    command: &[String],
+) -> Result<Option<i32>, String> {
+    let (program, args) = command.split_first().ok_or("empty install command")?;
+    let path_env = build_codex_path_env(None);
+    let resolved = find_cli_binary(program, None)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|| program.clone());
+    let mut child_command = build_command_for_binary(&resolved);
+    if let Some(path_env) = &path_env {
+        child_command.env("PATH", path_env);
+    }
+    child_command.args(args);
+    child_command.stdin(std::process::Stdio::null());
+    child_command.stdout(std::process::Stdio::piped());
+    child_command.stderr(std::process::Stdio::piped());
+    child_command.kill_on_drop(true);
+
+    let mut child = child_command
+        .spawn()
+        .map_err(|error| format!("failed to start {program}: {error}"))?;
+    let stdout_task = tokio::spawn(pipe_output(
+        child.stdout.take(),
+        app.clone(),
+        run_id.to_string(),
+        tool,
+        OnboardingInstallPhase::Stdout,
+    ));
+    let stderr_task = tokio::spawn(pipe_output(
+        child.stderr.take(),
+        app.clone(),
+        run_id.to_string(),
+        tool,
+        OnboardingInstallPhase::Stderr,
+    ));
+    let status = match timeout(Duration::from_secs(INSTALL_TIMEOUT_SECS), child.wait()).await {
+        Ok(status) => status.map_err(|error| error.to_string())?,
+        Err(_) => {
+            let _ = child.kill().await;
+            return Err(format!("{program} timed out after {INSTALL_TIMEOUT_SECS}s"));
+        }
+    };
+    let _ = stdout_task.await;
+    let _ = stderr_task.await;
+    Ok(status.code())
+}
+
+/// Detects engine CLIs, git, gh and node, with an install guide for each
+/// missing tool and the persisted wizard progress.
+#[tauri::command]
+pub(crate) async fn onboarding_diagnostics(
+    state: State<'_, AppState>,
+) -> Result<OnboardingDiagnostics, String> {
+    let settings = state.app_settings.lock().await.clone();
+    let tools = collect_tool_statuses(&state, &settings).await;
+    Ok(OnboardingDiagnostics {
+        platform: current_platform(),
+        ready: onboarding_ready(&tools),
+        tools,
+        state: read_onboarding_state()?,
+    })
+}
+
+/// Runs the guided install for `tool` when it can run without elevation.
+/// Output is streamed as `onboarding-install-event` tagged with `run_id`.
+#[tauri::command]
+pub(crate) async fn onboarding_install(
+    tool: OnboardingTool,
+    run_id: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<OnboardingInstallResult, String> {
+    let started = Instant::now();
+    let run_id = run_id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| format!("onboarding-{}-{}", tool.binary(), uuid::Uuid::new_v4()));
+
+    let settings = state.app_settings.lock().await.clone();
+    let (exit_code, details) = if let Some(engine) = tool.cli_install_engine() {
+        let event_app = app.clone();
+        let progress_sink = std::sync::Arc::new(move |event: CliInstallProgressEvent| {
+            emit_install_event(&event_app, map_cli_install_event(tool, event));
+        });
+        let result = run_cli_installer_with_progress(
+            engine,
+            CliInstallAction::InstallLatest,
+            CliInstallStrategy::NpmGlobal,
+            &settings,
+            Some(run_id.clone()),
+            Some(progress_sink),
+        )
+        .await?;
+        (result.exit_code, result.details)
+    } else {
+        let env = detect_install_environment(build_codex_path_env(None)).await;
+        let guide = install_guide_for(tool, current_platform(), env);
+        if !guide.can_run {
+            return Err(format!(
+                "{} cannot be installed automatically: {}",
+                tool.binary(),
+                guide.blockers.join("; ")
+            ));
+        }
+        emit_install_event(
+            &app,
+            OnboardingInstallEvent {
+                run_id: run_id.clone(),
+                tool,
+                phase: OnboardingInstallPhase::Started,
+                message: Some(guide.command.join(" ")),
+                exit_code: None,
+            },
+        );
+        match run_install_command(&app, &run_id, tool, &guide.command).await {
+            Ok(exit_code) => {
+                emit_install_event(
+                    &app,
+                    OnboardingInstallEvent {
+                        run_id: run_id.clone(),
+                        tool,
+                        phase: OnboardingInstallPhase::Finished,
+                        message: None,
+                        exit_code,
+                    },
+                );
+                (exit_code, None)
+            }
+            Err(error) => {
+                emit_install_event(
+                    &app,
+                    OnboardingInstallEvent {
+                        run_id: run_id.clone(),
+                        tool,
+                        phase: OnboardingInstallPhase::Error,
+                        message: Some(error.clone()),
+                        exit_code: None,
+                    },
+                );
+                (None, Some(error))
+            }
+        }
+    };
+
+    let env = detect_install_environment(build_codex_path_env(None)).await;
+    let status = detect_tool(tool, &state, &settings, env).await;
+    Ok(OnboardingInstallResult {
+        ok: exit_code == Some(0) && status.installed,
+        tool,
+        exit_code,
+        details,
+        duration_ms: started.elapsed().as_millis(),
+        status,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn get_onboarding_state() -> Result<OnboardingState, String> {
+    read_onboarding_state()
+}
+
+/// Records wizard progress: the step being shown and/or a step just finished.
+#[tauri::command]
+pub(crate) async fn update_onboarding_progress(
+    current_step: Option<String>,
+    completed_step: Option<String>,
+) -> Result<OnboardingState, String> {
+    let path = onboarding_state_path()?;
+    with_storage_lock(&path, || {
+        let state = apply_progress(
+            read_onboarding_state()?,
+            current_step,
+            completed_step,
+            chrono::Utc::now().timestamp_millis(),
+        );
+        write_onboarding_state(&state)?;
+        Ok(state)
+    })
+}
+
+/// Marks onboarding finished, or skipped when `skipped` is true.
+#[tauri::command]
+pub(crate) async fn complete_onboarding(skipped: Option<bool>) -> Result<OnboardingState, String> {
+    let path = onboarding_state_path()?;
+    with_storage_lock(&path, || {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut state = read_onboarding_state()?;
+        state.current_step = None;
+        state.completed_at = Some(now);
+        state.skipped = skipped.unwrap_or(false);
+        state.updated_at = now;
+        write_onboarding_state(&state)?;
+        Ok(state)
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn reset_onboarding() -> Result<OnboardingState, String> {
+    let path = onboarding_state_path()?;
+    with_storage_lock(&path, || {
+        let state = OnboardingState {
+            updated_at: chrono::Utc::now().timestamp_millis(),
+            ..OnboardingState::default()
+        };
+        write_onboarding_state(&state)?;
+        Ok(state)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_guides_only_run_without_elevation() {
+        let none = InstallEnvironment::default();
+        let all = InstallEnvironment {
+            npm: true,
+            homebrew: true,
+            winget: true,
+        };
+
+        let gemini = install_guide_for(OnboardingTool::Gemini, "linux", all);
+        assert_eq!(gemini.method, OnboardingInstallMethod::Npm);
+        assert!(gemini.can_run);
+        assert!(!install_guide_for(OnboardingTool::Gemini, "linux", none).can_run);
+
+        let gh_mac = install_guide_for(OnboardingTool::Gh, "macos", all);
+        assert_eq!(gh_mac.method, OnboardingInstallMethod::Homebrew);
+        assert_eq!(gh_mac.command, vec!["brew", "install", "gh"]);
+        assert!(gh_mac.can_run);
+
+        let git_win = install_guide_for(OnboardingTool::Git, "windows", all);
+        assert_eq!(git_win.method, OnboardingInstallMethod::Winget);
+        assert!(git_win.command.contains(&"Git.Git".to_string()));
+
+        let git_linux = install_guide_for(OnboardingTool::Git, "linux", all);
+        assert_eq!(git_linux.method, OnboardingInstallMethod::Manual);
+        assert!(!git_linux.can_run);
+        assert_eq!(git_linux.command, vec!["sudo", "apt", "install", "git"]);
+    }
+
+    #[test]
+    fn progress_updates_are_idempotent_and_resume_from_current_step() {
+        let state = apply_progress(
+            OnboardingState::default(),
+            Some("engines".to_string()),
+            Some("welcome".to_string()),
+            1,
+        );
+        let state = apply_progress(state, None, Some("welcome".to_string()), 2);
+        assert_eq!(state.completed_steps, vec!["welcome".to_string()]);
+        assert_eq!(state.current_step.as_deref(), Some("engines"));
+        assert_eq!(state.updated_at, 2);
+    }
+}
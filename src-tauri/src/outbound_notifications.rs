@@ -0,0 +1,614 @@
+//! Optional outbound notifications for long-running work.
+//!
+//! When a queued run or scheduled task finishes, or an approval request has
+//! been waiting longer than the configured number of minutes, a templated
+//! message is sent to a Slack incoming webhook and/or by email through the
+//! SMTP sender configured in settings. Settings live in
+//! `~/.ccgui/outbound-notifications.json`; every event type can be toggled.
+//!
+//! Queued runs and scheduled tasks are driven by the frontend, which reports
+//! their completion through `notify_task_finished`. Pending approvals are
+//! tracked here from app-server events and cleared when the request is
+//! answered or its turn ends.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+
+use crate::app_paths;
+use crate::backend::events::AppServerEvent;
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const SETTINGS_FILE: &str = "outbound-notifications.json";
+const DEFAULT_APPROVAL_PENDING_MINUTES: u32 = 10;
+const MAX_APPROVAL_PENDING_MINUTES: u32 = 24 * 60;
+const SLACK_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_SUMMARY_CHARS: usize = 1500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum NotificationEventKind {
+    RunFinished,
+    ScheduledTaskFinished,
+    ApprovalPending,
+}
+
+impl NotificationEventKind {
+    fn default_template(self) -> &'static str {
+        match self {
+            NotificationEventKind::RunFinished => {
+                "Run finished: {{title}}\nWorkspace: {{workspace}}\nStatus: {{status}}\n\n{{summary}}"
+            }
+            NotificationEventKind::ScheduledTaskFinished => {
+                "Scheduled task finished: {{title}}\nWorkspace: {{workspace}}\nStatus: {{status}}\n\n{{summary}}"
+            }
+            NotificationEventKind::ApprovalPending => {
+                "Approval pending for {{minutes}} min: {{title}}\nWorkspace: {{workspace}}\n\n{{summary}}"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NotificationEventToggles {
+    #[serde(default = "default_true")]
+    pub(crate) run_finished: bool,
+    #[serde(default = "default_true")]
+    pub(crate) scheduled_task_finished: bool,
+    #[serde(default = "default_true")]
+    pub(crate) approval_pending: bool,
+}
+
+impl Default for NotificationEventToggles {
+    fn default() -> Self {
+        Self {
+            run_finished: true,
+            scheduled_task_finished: true,
+            approval_pending: true,
+        }
+    }
+}
+
+impl NotificationEventToggles {
+    fn allows(&self, kind: NotificationEventKind) -> bool {
+        match kind {
+            NotificationEventKind::RunFinished => self.run_finished,
+            NotificationEventKind::ScheduledTaskFinished => self.scheduled_task_finished,
+            NotificationEventKind::ApprovalPending => self.approval_pending,
+        }
+    }
+}
+
+/// Per-event message templates. `None` uses the built-in template. Supported
+/// placeholders: `{{title}}`, `{{workspace}}`, `{{status}}`, `{{summary}}`,
+/// `{{minutes}}`. The first rendered line doubles as the email subject.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NotificationTemplates {
+    #[serde(default)]
+    pub(crate) run_finished: Option<String>,
+    #[serde(default)]
+    pub(crate) scheduled_task_finished: Option<String>,
+    #[serde(default)]
+    pub(crate) approval_pending: Option<String>,
+}
+
+impl NotificationTemplates {
+    fn for_kind(&self, kind: NotificationEventKind) -> &str {
+        let custom = match kind {
+            NotificationEventKind::RunFinished => self.run_finished.as_deref(),
+            NotificationEventKind::ScheduledTaskFinished => self.scheduled_task_finished.as_deref(),
+            NotificationEventKind::ApprovalPending => self.approval_pending.as_deref(),
+        };
+        custom.unwrap_or_else(|| kind.default_template())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SlackNotifierSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) webhook_url: String,
+}
+
+/// Email goes out through the SMTP sender from the email settings; only the
+/// recipient can be overridden here.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EmailNotifierSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) recipient: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OutboundNotificationSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) slack: SlackNotifierSettings,
+    #[serde(default)]
+    pub(crate) email: EmailNotifierSettings,
+    #[serde(default)]
+    pub(crate) events: NotificationEventToggles,
+    #[serde(default)]
+    pub(crate) templates: NotificationTemplates,
+    #[serde(default = "default_approval_pending_minutes")]
+    pub(crate) approval_pending_minutes: u32,
+}
+
+impl Default for OutboundNotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            slack: SlackNotifierSettings::default(),
+            email: EmailNotifierSettings::default(),
+            events: NotificationEventToggles::default(),
+            templates: NotificationTemplates::default(),
+            approval_pending_minutes: DEFAULT_APPROVAL_PENDING_MINUTES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TaskFinishedNotification {
+    pub(crate) kind: NotificationEventKind,
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) status: Option<String>,
+    #[serde(default)]
+    pub(crate) summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum NotificationChannel {
+    Slack,
+    Email,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NotificationDelivery {
+    pub(crate) channel: NotificationChannel,
+    pub(crate) ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct NotificationContext {
+    title: String,
+    workspace: String,
+    status: String,
+    summary: String,
+    minutes: u32,
+}
+
+#[derive(Debug, Clone)]
+struct PendingApproval {
+    thread_id: Option<String>,
+    token: u64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_approval_pending_minutes() -> u32 {
+    DEFAULT_APPROVAL_PENDING_MINUTES
+}
+
+fn pending_approvals() -> std::sync::MutexGuard<'static, HashMap<String, PendingApproval>> {
+    static PENDING: OnceLock<StdMutex<HashMap<String, PendingApproval>>> = OnceLock::new();
+    PENDING
+        .get_or_init(|| StdMutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn next_pending_token() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+fn settings_path() -> Result<PathBuf, String> {
+    Ok(app_paths::app_home_dir()?.join(SETTINGS_FILE))
+}
+
+fn read_notification_settings() -> Result<OutboundNotificationSettings, String> {
+    let path = settings_path()?;
+    Ok(read_json_file(&path)?.unwrap_or_default())
+}
+
+fn normalize_template(template: Option<String>) -> Option<String> {
+    template
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn normalize_settings(
+    mut settings: OutboundNotificationSettings,
+) -> Result<OutboundNotificationSettings, String> {
+    settings.slack.webhook_url = settings.slack.webhook_url.trim().to_string();
+    if settings.slack.enabled && !settings.slack.webhook_url.starts_with("https://") {
+        return Err("Slack webhook URL must start with https://".to_string());
+    }
+    settings.email.recipient = settings
+        .email
+        .recipient
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    settings.templates = NotificationTemplates {
+        run_finished: normalize_template(settings.templates.run_finished),
+        scheduled_task_finished: normalize_template(settings.templates.scheduled_task_finished),
+        approval_pending: normalize_template(settings.templates.approval_pending),
+    };
+    settings.approval_pending_minutes = settings
+        .approval_pending_minutes
+        .clamp(1, MAX_APPROVAL_PENDING_MINUTES);
+    Ok(settings)
+}
+
+fn truncate_summary(summary: &str) -> String {
+    let trimmed = summary.trim();
+    if trimmed.chars().count() <= MAX_SUMMARY_CHARS {
+        return trimmed.to_string();
+    }
+    let mut truncated: String = trimmed.chars().take(MAX_SUMMARY_CHARS).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn render_template(template: &str, context: &NotificationContext) -> String {
+    template
+        .replace("{{title}}", &context.title)
+        .replace("{{workspace}}", &context.workspace)
+        .replace("{{status}}", &context.status)
+        .replace("{{summary}}", &context.summary)
+        .replace("{{minutes}}", &context.minutes.to_string())
+        .trim()
+        .to_string()
+}
+
+/// Splits a rendered message into an email subject (first non-empty line) and
+/// the full text.
+fn subject_and_body(text: &str) -> (String, String) {
+    let subject = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("Moss notification")
+        .to_string();
+    (subject, text.to_string())
+}
+
+async fn post_slack_message(webhook_url: &str, text: &str) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(SLACK_TIMEOUT)
+        .build()
+        .map_err(|error| format!("Failed to configure HTTP client: {error}"))?;
+    let response = client
+        .post(webhook_url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(json!({ "text": text }).to_string())
+        .send()
+        .await
+        .map_err(|error| format!("Slack webhook request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Slack webhook returned {}", response.status()));
+    }
+    Ok(())
+}
+
+async fn deliver(
+    state: &AppState,
+    settings: &OutboundNotificationSettings,
+    kind: NotificationEventKind,
+    context: &NotificationContext,
+) -> Vec<NotificationDelivery> {
+    let text = render_template(settings.templates.for_kind(kind), context);
+    let mut deliveries = Vec::new();
+    if settings.slack.enabled {
+        let result = post_slack_message(&settings.slack.webhook_url, &text).await;
+        deliveries.push(NotificationDelivery {
+            channel: NotificationChannel::Slack,
+            ok: result.is_ok(),
+            error: result.err(),
+        });
+    }
+    if settings.email.enabled {
+        let (subject, body) = subject_and_body(&text);
+        let email_settings = state.app_settings.lock().await.email_sender.clone();
+        let result = crate::email::send_notification_email(
+            email_settings,
+            &state.settings_path,
+            settings.email.recipient.as_deref(),
+            &subject,
+            &body,
+        )
+        .await;
+        deliveries.push(NotificationDelivery {
+            channel: NotificationChannel::Email,
+            ok: result.is_ok(),
+            error: result.err(),
+        });
+    }
+    for delivery in deliveries.iter().filter(|delivery| !delivery.ok) {
+        log::warn!(
+            "[outbound-notifications] {:?} delivery failed: {}",
+            delivery.channel,
+            delivery.error.as_deref().unwrap_or_default()
+        );
+    }
+    deliveries
+}
+
+async fn workspace_label(state: &AppState, workspace_id: Option<&str>) -> String {
+    let Some(workspace_id) = workspace_id else {
+        return String::new();
+    };
+    let workspaces = state.workspaces.lock().await;
+    workspaces
+        .get(workspace_id)
+        .map(|entry| entry.name.clone())
+        .unwrap_or_else(|| workspace_id.to_string())
+}
+
+fn is_approval_request_method(method: &str) -> bool {
+    method.ends_with("/requestApproval") || method == "approval/request"
+}
+
+fn pending_key(workspace_id: &str, request_id: &Value) -> String {
+    match request_id {
+        Value::String(value) => format!("{workspace_id}:{value}"),
+        other => format!("{workspace_id}:{other}"),
+    }
+}
+
+fn approval_summary(params: &Value) -> String {
+    ["message", "reason", "command", "toolName"]
+        .iter()
+        .find_map(|key| match params.get(*key) {
+            Some(Value::String(value)) if !value.trim().is_empty() => {
+                Some(value.trim().to_string())
+            }
+            Some(Value::Array(parts)) if !parts.is_empty() => Some(
+                parts
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Tracks approval requests so a reminder goes out once one has been waiting
+/// longer than the configured threshold; turn completion clears the thread's
+/// outstanding requests.
+pub(crate) fn observe_app_server_event(app: &AppHandle, event: &AppServerEvent) {
+    let Some(method) = event.message.get("method").and_then(Value::as_str) else {
+        return;
+    };
+    let params = event.message.get("params").unwrap_or(&Value::Null);
+    let thread_id = params
+        .get("threadId")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    if matches!(method, "turn/completed" | "turn/error") {
+        if let Some(thread_id) = thread_id {
+            pending_approvals().retain(|key, pending| {
+                !(key.starts_with(&format!("{}:", event.workspace_id))
+                    && pending.thread_id.as_deref() == Some(thread_id.as_str()))
+            });
+        }
+        return;
+    }
+    if !is_approval_request_method(method) {
+        return;
+    }
+    let Some(request_id) = event.message.get("id") else {
+        return;
+    };
+    let key = pending_key(&event.workspace_id, request_id);
+    let token = next_pending_token();
+    pending_approvals().insert(key.clone(), PendingApproval { thread_id, token });
+    let title = params
+        .get("toolName")
+        .and_then(Value::as_str)
+        .unwrap_or(method)
+        .to_string();
+    let summary = approval_summary(params);
+    let workspace_id = event.workspace_id.clone();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let settings = match read_notification_settings() {
+            Ok(settings) => settings,
+            Err(error) => {
+                log::warn!("[outbound-notifications] failed to read settings: {error}");
+                return;
+            }
+        };
+        if !settings.enabled || !settings.events.approval_pending {
+            pending_approvals().remove(&key);
+            return;
+        }
+        let minutes = settings.approval_pending_minutes;
+        tokio::time::sleep(Duration::from_secs(u64::from(minutes) * 60)).await;
+        let still_pending = {
+            let mut pending = pending_approvals();
+            match pending.get(&key) {
+                Some(entry) if entry.token == token => pending.remove(&key).is_some(),
+                _ => false,
+            }
+        };
+        if !still_pending {
+            return;
+        }
+        let state = app.state::<AppState>();
+        let context = NotificationContext {
+            title,
+            workspace: workspace_label(&state, Some(&workspace_id)).await,
+            status: "waiting for approval".to_string(),
+            summary: truncate_summary(&summary),
+            minutes,
+        };
+        deliver(
+            &state,
+            &settings,
+            NotificationEventKind::ApprovalPending,
+            &context,
+        )
+        .await;
+    });
+}
+
+/// Clears the pending-approval reminder for an answered server request.
+pub(crate) fn mark_server_request_resolved(workspace_id: &str, request_id: &Value) {
+    pending_approvals().remove(&pending_key(workspace_id, request_id));
+}
+
+#[tauri::command]
+pub(crate) async fn get_outbound_notification_settings(
+) -> Result<OutboundNotificationSettings, String> {
+    read_notification_settings()
+}
+
+#[tauri::command]
+pub(crate) async fn update_outbound_notification_settings(
+    settings: OutboundNotificationSettings,
+) -> Result<OutboundNotificationSettings, String> {
+    let settings = normalize_settings(settings)?;
+    let path = settings_path()?;
+    let data = serde_json::to_string_pretty(&settings).map_err(|error| error.to_string())?;
+    with_storage_lock(&path, || write_string_atomically(&path, &data))?;
+    Ok(settings)
+}
+
+/// Sends a sample run-finished message through every enabled channel,
+/// ignoring the global and per-event toggles.
+#[tauri::command]
+pub(crate) async fn send_outbound_notification_test(
+    state: State<'_, AppState>,
+) -> Result<Vec<NotificationDelivery>, String> {
+    let settings = read_notification_settings()?;
+    if !settings.slack.enabled && !settings.email.enabled {
+        return Err("No notification channel is enabled".to_string());
+    }
+    let context = NotificationContext {
+        title: "Test notification".to_string(),
+        workspace: "Moss".to_string(),
+        status: "completed".to_string(),
+        summary: "Outbound notifications are configured correctly.".to_string(),
+        minutes: settings.approval_pending_minutes,
+    };
+    Ok(deliver(
+        &state,
+        &settings,
+        NotificationEventKind::RunFinished,
+        &context,
+    )
+    .await)
+}
+
+/// Reports a finished queued run or scheduled task. Returns the deliveries
+/// attempted, which is empty when notifications or this event type are off.
+#[tauri::command]
+pub(crate) async fn notify_task_finished(
+    request: TaskFinishedNotification,
+    state: State<'_, AppState>,
+) -> Result<Vec<NotificationDelivery>, String> {
+    if request.kind == NotificationEventKind::ApprovalPending {
+        return Err("Approval reminders are sent by the backend".to_string());
+    }
+    let settings = read_notification_settings()?;
+    if !settings.enabled || !settings.events.allows(request.kind) {
+        return Ok(Vec::new());
+    }
+    let context = NotificationContext {
+        title: request.title.trim().to_string(),
+        workspace: workspace_label(&state, request.workspace_id.as_deref()).await,
+        status: request
+            .status
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| "completed".to_string()),
+        summary: truncate_summary(request.summary.as_deref().unwrap_or_default()),
+        minutes: settings.approval_pending_minutes,
+    };
+    Ok(deliver(&state, &settings, request.kind, &context).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        normalize_settings, render_template, subject_and_body, NotificationContext,
+        NotificationEventKind, NotificationTemplates, OutboundNotificationSettings,
+    };
+
+    #[test]
+    fn renders_default_and_custom_templates() {
+        let context = NotificationContext {
+            title: "Nightly lint".to_string(),
+            workspace: "api".to_string(),
+            status: "failed".to_string(),
+            summary: "3 errors".to_string(),
+            minutes: 10,
+        };
+        let templates = NotificationTemplates {
+            approval_pending: Some("{{workspace}} waited {{minutes}}m".to_string()),
+            ..NotificationTemplates::default()
+        };
+
+        let text = render_template(
+            templates.for_kind(NotificationEventKind::ScheduledTaskFinished),
+            &context,
+        );
+        let (subject, body) = subject_and_body(&text);
+        assert_eq!(subject, "Scheduled task finished: Nightly lint");
+        assert!(body.contains("Status: failed"));
+        assert!(body.ends_with("3 errors"));
+        assert_eq!(
+            render_template(
+                templates.for_kind(NotificationEventKind::ApprovalPending),
+                &context
+            ),
+            "api waited 10m"
+        );
+    }
+
+    #[test]
+    fn normalize_settings_validates_slack_url_and_clamps_threshold() {
+        let mut settings = OutboundNotificationSettings::default();
+        settings.slack.enabled = true;
+        settings.slack.webhook_url = "http://hooks.example.com/x".to_string();
+        assert!(normalize_settings(settings.clone()).is_err());
+
+        settings.slack.webhook_url = " https://hooks.slack.com/services/T/B/x ".to_string();
+        settings.approval_pending_minutes = 0;
+        settings.templates.run_finished = Some("   ".to_string());
+        let normalized = normalize_settings(settings).expect("valid settings");
+        assert_eq!(
+            normalized.slack.webhook_url,
+            "https://hooks.slack.com/services/T/B/x"
+        );
+        assert_eq!(normalized.approval_pending_minutes, 1);
+        assert_eq!(normalized.templates.run_finished, None);
+    }
+}
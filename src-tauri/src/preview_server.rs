@@ -0,0 +1,485 @@
+//! Local static preview servers for web apps built inside a workspace.
+//!
+//! Each workspace can serve one directory on `127.0.0.1`, which the main
+//! webview's navigation allowlist already lets the preview iframe load.
+//! HTML pages get a small script that long-polls `/__preview/reload` and
+//! reloads the page whenever a file under the served directory changes.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::extract::{Query, State as UrlState};
+use axum::http::{header, HeaderValue, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use notify::{
+    Config as NotifyConfig, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::State;
+use tokio::net::TcpListener;
+use tokio::sync::{oneshot, watch, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::state::AppState;
+
+const RELOAD_ROUTE: &str = "/__preview/reload";
+/// How long a reload poll is held open before the client asks again.
+const RELOAD_POLL_TIMEOUT: Duration = Duration::from_secs(25);
+/// Directories whose changes never trigger a reload.
+const IGNORED_DIRS: &[&str] = &[".git", "node_modules", ".cache", ".turbo", ".parcel-cache"];
+
+const RELOAD_SCRIPT: &str = r#"<script>
+(function () {
+  var version = __VERSION__;
+  function poll() {
+    fetch("/__preview/reload?since=" + version, { cache: "no-store" })
+      .then(function (response) { return response.json(); })
+      .then(function (body) {
+        if (body.version !== version) { location.reload(); } else { poll(); }
+      })
+      .catch(function () { setTimeout(poll, 2000); });
+  }
+  poll();
+})();
+</script>"#;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PreviewServerStatus {
+    workspace_id: String,
+    /// Served directory, relative to the workspace root.
+    directory: String,
+    url: String,
+    port: u16,
+    running: bool,
+    auto_reload: bool,
+    started_at_ms: i64,
+}
+
+struct RunningPreview {
+    status: PreviewServerStatus,
+    shutdown_tx: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+    /// Dropping the watcher stops change notifications.
+    _watcher: Option<RecommendedWatcher>,
+}
+
+#[derive(Clone)]
+struct PreviewState {
+    root: PathBuf,
+    auto_reload: bool,
+    version_rx: watch::Receiver<u64>,
+}
+
+#[derive(Deserialize)]
+struct ReloadQuery {
+    #[serde(default)]
+    since: Option<u64>,
+}
+
+fn preview_runtime() -> &'static Mutex<HashMap<String, RunningPreview>> {
+    static RUNTIME: OnceLock<Mutex<HashMap<String, RunningPreview>>> = OnceLock::new();
+    RUNTIME.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Resolves the directory to serve, which must stay inside the workspace.
+fn resolve_preview_root(workspace_root: &Path, directory: &str) -> Result<PathBuf, String> {
+    let workspace_root = workspace_root
+        .canonicalize()
+        .map_err(|error| format!("Failed to resolve workspace path: {error}"))?;
+    let directory = directory.trim().trim_start_matches(['/', '\\']);
+    let root = workspace_root
+        .join(directory)
+        .canonicalize()
+        .map_err(|error| format!("Preview directory not found: {directory} ({error})"))?;
+    if !root.starts_with(&workspace_root) {
+        return Err(format!(
+            "Preview directory is outside the workspace: {directory}"
+        ));
+    }
+    if !root.is_dir() {
+        return Err(format!("Preview path is not a directory: {directory}"));
+    }
+    Ok(root)
+}
+
+/// Maps a request path onto a file under `root`, refusing anything that
+/// would climb out of it. Directories resolve to their `index.html`.
+fn resolve_request_path(root: &Path, request_path: &str) -> Option<PathBuf> {
+    let mut path = root.to_path_buf();
+    for segment in request_path.split('/') {
+        let segment = percent_decode(segment)?;
+        match Path::new(&segment).components().next() {
+            None => continue,
+            Some(Component::Normal(_)) if !segment.contains(['/', '\\']) => path.push(&segment),
+            _ => return None,
+        }
+    }
+    if path.is_dir() {
+        path.push("index.html");
+    }
+    Some(path)
+}
+
+/// Whether `path` still lies under the canonical `root` once symlinks are
+/// followed. Missing paths pass; reading them reports not found.
+fn stays_inside_root(root: &Path, path: &Path) -> bool {
+    match path.canonicalize() {
+        Ok(canonical) => canonical.starts_with(root),
+        Err(_) => true,
+    }
+}
+
+fn percent_decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = segment.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+fn content_type_for_path(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|value| value.to_str())
+        .map(|value| value.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("html") | Some("htm") => "text/html; charset=utf-8",
+        Some("js") | Some("mjs") => "application/javascript; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("json") | Some("map") => "application/json; charset=utf-8",
+        Some("wasm") => "application/wasm",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("ico") => "image/x-icon",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("ttf") => "font/ttf",
+        Some("mp3") => "audio/mpeg",
+        Some("wav") => "audio/wav",
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("txt") | Some("md") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Adds the reload script before `</body>`, or at the end when the page
+/// has no body tag.
+fn inject_reload_script(html: &str, version: u64) -> String {
+    let script = RELOAD_SCRIPT.replace("__VERSION__", &version.to_string());
+    match html.to_ascii_lowercase().rfind("</body>") {
+        Some(index) => format!("{}{}{}", &html[..index], script, &html[index..]),
+        None => format!("{html}{script}"),
+    }
+}
+
+fn is_reload_relevant(event: &Event, root: &Path) -> bool {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+    event.paths.iter().any(|path| {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        !relative.components().any(|component| {
+            IGNORED_DIRS
+                .iter()
+                .any(|ignored| component.as_os_str() == *ignored)
+        })
+    })
+}
+
+fn create_reload_watcher(
+    root: &Path,
+    version_tx: watch::Sender<u64>,
+) -> Result<RecommendedWatcher, String> {
+    let watched_root = root.to_path_buf();
+    let mut watcher = RecommendedWatcher::new(
+        move |result: notify::Result<Event>| {
+            if let Ok(event) = result {
+                if is_reload_relevant(&event, &watched_root) {
+                    version_tx.send_modify(|version| *version += 1);
+                }
+            }
+        },
+        NotifyConfig::default(),
+    )
+    .map_err(|err| format!("Failed to initialize watcher: {err}"))?;
+    watcher
+        .watch(root, RecursiveMode::Recursive)
+        .map_err(|err| format!("Failed to watch preview directory: {err}"))?;
+    Ok(watcher)
+}
+
+async fn serve_file_route(UrlState(preview): UrlState<PreviewState>, uri: Uri) -> Response {
+    let Some(mut path) = resolve_request_path(&preview.root, uri.path()) else {
+        return (StatusCode::FORBIDDEN, "Forbidden").into_response();
+    };
+    // Client-side routes of single-page apps fall back to the root page.
+    if !path.is_file() && path.extension().is_none() {
+        path = preview.root.join("index.html");
+    }
+    if !stays_inside_root(&preview.root, &path) {
+        return (StatusCode::FORBIDDEN, "Forbidden").into_response();
+    }
+    let bytes = match tokio::fs::read(&path).await {
+        Ok(bytes) => bytes,
+        Err(_) => return (StatusCode::NOT_FOUND, "Not found").into_response(),
+    };
+    let content_type = content_type_for_path(&path);
+    let body = if preview.auto_reload && content_type.starts_with("text/html") {
+        let version = *preview.version_rx.borrow();
+        Body::from(inject_reload_script(
+            &String::from_utf8_lossy(&bytes),
+            version,
+        ))
+    } else {
+        Body::from(bytes)
+    };
+    let mut response = Response::new(body);
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    response
+}
+
+async fn reload_route(
+    UrlState(preview): UrlState<PreviewState>,
+    Query(query): Query<ReloadQuery>,
+) -> Response {
+    let mut version_rx = preview.version_rx.clone();
+    let current = *version_rx.borrow_and_update();
+    if query.since.is_none_or(|since| since == current) {
+        let _ = tokio::time::timeout(RELOAD_POLL_TIMEOUT, version_rx.changed()).await;
+        // Let a burst of writes (e.g. a build) settle before reloading.
+        tokio::time::sleep(Duration::from_millis(150)).await;
+    }
+    let version = *version_rx.borrow();
+    Json(json!({ "version": version })).into_response()
+}
+
+async fn stop_preview(running: RunningPreview) {
+    let _ = running.shutdown_tx.send(());
+    let _ = running.task.await;
+}
+
+fn current_status(running: &RunningPreview) -> PreviewServerStatus {
+    PreviewServerStatus {
+        running: !running.task.is_finished(),
+        ..running.status.clone()
+    }
+}
+
+/// Serves `directory` (relative to the workspace root, default the root)
+/// on a loopback port and returns the URL to load in the preview iframe.
+/// Restarts the workspace's preview if one is already running.
+#[tauri::command]
+pub(crate) async fn start_preview_server(
+    workspace_id: String,
+    directory: Option<String>,
+    port: Option<u16>,
+    auto_reload: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<PreviewServerStatus, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or_else(|| "workspace not found".to_string())?
+    };
+    let directory = directory.unwrap_or_default();
+    let root = resolve_preview_root(Path::new(&entry.path), &directory)?;
+    if let Some(port) = port.filter(|port| *port < 1024) {
+        return Err(format!("Preview port must be 1024 or higher, got {port}"));
+    }
+    let auto_reload = auto_reload.unwrap_or(true);
+
+    let mut runtime = preview_runtime().lock().await;
+    if let Some(previous) = runtime.remove(&workspace_id) {
+        stop_preview(previous).await;
+    }
+
+    // Loopback only, which is also what the webview lets iframes navigate to.
+    let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port.unwrap_or(0));
+    let listener = TcpListener::bind(address)
+        .await
+        .map_err(|error| format!("Failed to bind {address}: {error}"))?;
+    let port = listener
+        .local_addr()
+        .map_err(|error| error.to_string())?
+        .port();
+    let url = format!("http://127.0.0.1:{port}/");
+    let parsed = tauri::Url::parse(&url).map_err(|error| error.to_string())?;
+    if !crate::window::is_in_app_navigation(&parsed) {
+        return Err(format!(
+            "Preview URL is not allowed in the app webview: {url}"
+        ));
+    }
+
+    let (version_tx, version_rx) = watch::channel(0_u64);
+    let watcher = if auto_reload {
+        Some(create_reload_watcher(&root, version_tx)?)
+    } else {
+        None
+    };
+    let router = Router::new()
+        .route(RELOAD_ROUTE, get(reload_route))
+        .fallback(serve_file_route)
+        .with_state(PreviewState {
+            root: root.clone(),
+            auto_reload,
+            version_rx,
+        });
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let task = tokio::spawn(async move {
+        let server = axum::serve(listener, router).with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        });
+        if let Err(error) = server.await {
+            log::warn!("[preview] server exited with error: {error}");
+        }
+    });
+
+    let status = PreviewServerStatus {
+        workspace_id: workspace_id.clone(),
+        directory: directory.trim_matches(['/', '\\']).to_string(),
+        url,
+        port,
+        running: true,
+        auto_reload,
+        started_at_ms: chrono::Utc::now().timestamp_millis(),
+    };
+    runtime.insert(
+        workspace_id,
+        RunningPreview {
+            status: status.clone(),
+            shutdown_tx,
+            task,
+            _watcher: watcher,
+        },
+    );
+    Ok(status)
+}
+
+#[tauri::command]
+pub(crate) async fn stop_preview_server(workspace_id: String) -> Result<bool, String> {
+    let running = preview_runtime().lock().await.remove(&workspace_id);
+    match running {
+        Some(running) => {
+            stop_preview(running).await;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Running previews, or just the given workspace's.
+#[tauri::command]
+pub(crate) async fn get_preview_server_status(
+    workspace_id: Option<String>,
+) -> Result<Vec<PreviewServerStatus>, String> {
+    let runtime = preview_runtime().lock().await;
+    let mut statuses: Vec<PreviewServerStatus> = runtime
+        .iter()
+        .filter(|(id, _)| workspace_id.as_deref().is_none_or(|wanted| wanted == *id))
+        .map(|(_, running)| current_status(running))
+        .collect();
+    statuses.sort_by(|a, b| a.workspace_id.cmp(&b.workspace_id));
+    Ok(statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_paths_stay_inside_the_served_directory() {
+        let root = std::env::temp_dir().join(format!("preview-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("assets")).expect("create root");
+
+        assert_eq!(
+            resolve_request_path(&root, "/assets/app.js"),
+            Some(root.join("assets").join("app.js"))
+        );
+        assert_eq!(
+            resolve_request_path(&root, "/"),
+            Some(root.join("index.html"))
+        );
+        assert_eq!(
+            resolve_request_path(&root, "/assets/"),
+            Some(root.join("assets").join("index.html"))
+        );
+        assert_eq!(resolve_request_path(&root, "/../secret.txt"), None);
+        assert_eq!(
+            resolve_request_path(&root, "/assets/%2e%2e/%2e%2e/etc"),
+            None
+        );
+        assert_eq!(resolve_request_path(&root, "/a%2Fb"), None);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_cannot_serve_files_outside_the_root() {
+        let base = std::env::temp_dir().join(format!("preview-link-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(base.join("site")).expect("create root");
+        std::fs::write(base.join("secret.txt"), "secret").expect("write secret");
+        std::fs::write(base.join("site").join("page.html"), "page").expect("write page");
+        std::os::unix::fs::symlink(base.join("secret.txt"), base.join("site").join("leak.txt"))
+            .expect("link out");
+        let root = base.join("site").canonicalize().expect("canonical root");
+
+        let leak = resolve_request_path(&root, "/leak.txt").expect("lexically inside");
+        assert!(!stays_inside_root(&root, &leak));
+        let page = resolve_request_path(&root, "/page.html").expect("page");
+        assert!(stays_inside_root(&root, &page));
+        assert!(stays_inside_root(&root, &root.join("missing.html")));
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn preview_directory_must_be_inside_the_workspace() {
+        let workspace = std::env::temp_dir().join(format!("preview-ws-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(workspace.join("dist")).expect("create workspace");
+
+        let root = resolve_preview_root(&workspace, "dist").expect("dist resolves");
+        assert!(root.ends_with("dist"));
+        assert!(resolve_preview_root(&workspace, "..").is_err());
+        assert!(resolve_preview_root(&workspace, "missing").is_err());
+
+        let _ = std::fs::remove_dir_all(&workspace);
+    }
+
+    #[test]
+    fn reload_script_is_injected_before_body_end() {
+        let html = inject_reload_script("<html><BODY><p>hi</p></BODY></html>", 7);
+        let script_at = html.find("<script>").expect("script injected");
+        assert!(script_at < html.find("</BODY>").expect("body end kept"));
+        assert!(html.contains("var version = 7;"));
+
+        let fragment = inject_reload_script("<p>hi</p>", 0);
+        assert!(fragment.starts_with("<p>hi</p><script>"));
+    }
+}
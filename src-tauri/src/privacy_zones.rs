@@ -0,0 +1,413 @@
+//! Per-workspace privacy zones.
+//!
+//! `privacyRules` in the workspace settings are gitignore-style patterns,
+//! relative to the workspace root, for files whose contents must never leave
+//! the machine. Outgoing turns pass through [`enforce_for_turn`]: `@path`
+//! mentions and fenced blocks tagged with a private path are replaced by a
+//! placeholder, private image attachments are dropped, and the frontend is
+//! told what was withheld. Context packs skip private files as well.
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::state::AppState;
+use crate::types::WorkspaceEntry;
+
+const WITHHELD_PLACEHOLDER: &str = "[withheld: privacy zone]";
+const PRIVACY_WITHHELD_EVENT: &str = "privacy-content-withheld";
+const MENTION_PUNCTUATION: &str = "`'\"()[]{}<>,;:!?";
+/// Fence info-string prefixes that name the file a code block came from.
+const FENCE_PATH_PREFIXES: &[&str] = &["path=", "file=", "title="];
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WithheldSource {
+    Prompt,
+    Attachment,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WithheldContent {
+    pub(crate) path: String,
+    pub(crate) source: WithheldSource,
+}
+
+/// Payload of the `privacy-content-withheld` event, also attached to the send
+/// response as `privacyWithheld`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PrivacyWithheldNotice {
+    pub(crate) workspace_id: String,
+    pub(crate) items: Vec<WithheldContent>,
+}
+
+pub(crate) struct PrivacyZones {
+    root: PathBuf,
+    canonical_root: Option<PathBuf>,
+    matcher: Gitignore,
+}
+
+impl PrivacyZones {
+    /// Builds the matcher for `rules`; `None` when no rule is set.
+    pub(crate) fn new(root: &Path, rules: &[String]) -> Result<Option<Self>, String> {
+        let mut builder = GitignoreBuilder::new(root);
+        let mut has_rules = false;
+        for rule in rules.iter().map(|rule| rule.trim()) {
+            if rule.is_empty() || rule.starts_with('#') {
+                continue;
+            }
+            builder
+                .add_line(None, rule)
+                .map_err(|error| format!("Invalid privacy rule `{rule}`: {error}"))?;
+            has_rules = true;
+        }
+        if !has_rules {
+            return Ok(None);
+        }
+        let matcher = builder.build().map_err(|error| error.to_string())?;
+        Ok(Some(Self {
+            root: root.to_path_buf(),
+            canonical_root: root.canonicalize().ok(),
+            matcher,
+        }))
+    }
+
+    pub(crate) fn for_workspace(entry: &WorkspaceEntry) -> Option<Self> {
+        match Self::new(Path::new(&entry.path), &entry.settings.privacy_rules) {
+            Ok(zones) => zones,
+            Err(error) => {
+                // A broken rule must not silently open the zone: withhold
+                // every path in the workspace until the rule is fixed.
+                log::warn!("[privacy_zones] workspace {}: {error}", entry.id);
+                Self::new(Path::new(&entry.path), &["*".to_string()])
+                    .ok()
+                    .flatten()
+            }
+        }
+    }
+
+    /// Workspace-relative form of `path`, or `None` when it lies outside the
+    /// workspace.
+    fn relative(&self, path: &str) -> Option<PathBuf> {
+        let path = path.strip_prefix("file://").unwrap_or(path);
+        let path = Path::new(path);
+        let relative = if path.is_absolute() {
+            let canonical = path.canonicalize().ok();
+            [Some(&self.root), self.canonical_root.as_ref()]
+                .into_iter()
+                .flatten()
+                .find_map(|root| {
+                    path.strip_prefix(root)
+                        .ok()
+                        .or_else(|| canonical.as_deref()?.strip_prefix(root).ok())
+                })?
+                .to_path_buf()
+        } else {
+            path.to_path_buf()
+        };
+        let mut normalized = PathBuf::new();
+        for component in relative.components() {
+            match component {
+                Component::Normal(part) => normalized.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !normalized.pop() {
+                        return None;
+                    }
+                }
+                _ => return None,
+            }
+        }
+        (!normalized.as_os_str().is_empty()).then_some(normalized)
+    }
+
+    pub(crate) fn is_private(&self, path: &str) -> bool {
+        self.relative(path).is_some_and(|relative| {
+            self.matcher
+                .matched_path_or_any_parents(&relative, false)
+                .is_ignore()
+        })
+    }
+
+    fn private_token(&self, token: &str) -> Option<String> {
+        let candidate = token
+            .trim_start_matches(|ch: char| ch == '@' || MENTION_PUNCTUATION.contains(ch))
+            .trim_end_matches(|ch: char| ch == '.' || MENTION_PUNCTUATION.contains(ch));
+        if candidate.is_empty()
+            || (candidate.contains("://") && !candidate.starts_with("file://"))
+            || !(candidate.contains('/') || candidate.contains('.'))
+        {
+            return None;
+        }
+        self.is_private(candidate).then(|| candidate.to_string())
+    }
+
+    fn redact_line(&self, line: &str, withheld: &mut Vec<String>) -> String {
+        line.split(' ')
+            .map(|token| match self.private_token(token) {
+                Some(path) => {
+                    let replaced = token
+                        .replacen(&format!("@{path}"), WITHHELD_PLACEHOLDER, 1)
+                        .replacen(&path, WITHHELD_PLACEHOLDER, 1);
+                    withheld.push(path);
+                    replaced
+                }
+                None => token.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Private path named in a fence info string such as
+    /// ```` ```rust path=secrets/key.rs ````.
+    fn private_fence_path(&self, info: &str) -> Option<String> {
+        info.split_whitespace().find_map(|token| {
+            let token = FENCE_PATH_PREFIXES
+                .iter()
+                .find_map(|prefix| token.strip_prefix(prefix))
+                .unwrap_or(token);
+            self.private_token(token)
+        })
+    }
+
+    /// Redacts private mentions and drops the body of fenced blocks that
+    /// carry a private file. Returns the filtered text and withheld paths.
+    pub(crate) fn filter_prompt(&self, text: &str) -> (String, Vec<String>) {
+        let mut withheld = Vec::new();
+        let mut output = String::with_capacity(text.len());
+        let mut open_fence: Option<(String, bool)> = None;
+        for line in text.split_inclusive('\n') {
+            let (content, newline) = match line.strip_suffix('\n') {
+                Some(content) => (content, "\n"),
+                None => (line, ""),
+            };
+            let trimmed = content.trim_start();
+            match open_fence.take() {
+                Some((marker, withholding)) => {
+                    if trimmed.trim_end() == marker {
+                        output.push_str(line);
+                    } else {
+                        if !withholding {
+                            output.push_str(line);
+                        }
+                        open_fence = Some((marker, withholding));
+                    }
+                }
+                None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                    let fence_char = if trimmed.starts_with('~') { '~' } else { '`' };
+                    let info = trimmed.trim_start_matches(fence_char);
+                    let marker = &trimmed[..trimmed.len() - info.len()];
+                    match self.private_fence_path(info) {
+                        Some(path) => {
+                            let indent = &content[..content.len() - trimmed.len()];
+                            output.push_str(&format!(
+                                "{indent}{marker}\n{indent}{WITHHELD_PLACEHOLDER}{newline}"
+                            ));
+                            withheld.push(path);
+                            open_fence = Some((marker.to_string(), true));
+                        }
+                        None => {
+                            output.push_str(line);
+                            open_fence = Some((marker.to_string(), false));
+                        }
+                    }
+                }
+                None => {
+                    output.push_str(&self.redact_line(content, &mut withheld));
+                    output.push_str(newline);
+                }
+            }
+        }
+        let mut seen = HashSet::new();
+        withheld.retain(|path| seen.insert(path.clone()));
+        (output, withheld)
+    }
+
+    /// Splits attachments into the ones allowed to be sent and the withheld
+    /// ones. Inline data URLs are always allowed.
+    pub(crate) fn filter_attachments(&self, paths: Vec<String>) -> (Vec<String>, Vec<String>) {
+        paths
+            .into_iter()
+            .partition(|path| path.starts_with("data:") || !self.is_private(path))
+    }
+}
+
+/// Result of filtering one outgoing turn.
+pub(crate) struct PrivacyFiltered {
+    pub(crate) text: String,
+    pub(crate) images: Option<Vec<String>>,
+    pub(crate) notice: Option<PrivacyWithheldNotice>,
+}
+
+/// Applies the workspace privacy zones to an outgoing prompt and its image
+/// attachments, emitting `privacy-content-withheld` when anything was cut.
+pub(crate) async fn enforce_for_turn(
+    state: &AppState,
+    app: &AppHandle,
+    workspace_id: &str,
+    text: String,
+    images: Option<Vec<String>>,
+) -> PrivacyFiltered {
+    let zones = state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .and_then(PrivacyZones::for_workspace);
+    let Some(zones) = zones else {
+        return PrivacyFiltered {
+            text,
+            images,
+            notice: None,
+        };
+    };
+
+    let (text, prompt_paths) = zones.filter_prompt(&text);
+    let (images, attachment_paths) = match images {
+        Some(images) => {
+            let (allowed, withheld) = zones.filter_attachments(images);
+            (Some(allowed), withheld)
+        }
+        None => (None, Vec::new()),
+    };
+    let items: Vec<WithheldContent> = prompt_paths
+        .into_iter()
+        .map(|path| WithheldContent {
+            path,
+            source: WithheldSource::Prompt,
+        })
+        .chain(attachment_paths.into_iter().map(|path| WithheldContent {
+            path,
+            source: WithheldSource::Attachment,
+        }))
+        .collect();
+    let notice = (!items.is_empty()).then(|| PrivacyWithheldNotice {
+        workspace_id: workspace_id.to_string(),
+        items,
+    });
+    if let Some(notice) = &notice {
+        log::info!(
+            "[privacy_zones] withheld {} item(s) from a turn in workspace {}",
+            notice.items.len(),
+            workspace_id
+        );
+        let _ = app.emit(PRIVACY_WITHHELD_EVENT, notice);
+    }
+    PrivacyFiltered {
+        text,
+        images,
+        notice,
+    }
+}
+
+/// Records the withheld items in a send response as `privacyWithheld`.
+pub(crate) fn attach_privacy_notice(
+    mut response: Value,
+    notice: Option<&PrivacyWithheldNotice>,
+) -> Value {
+    let Some(notice) = notice.and_then(|notice| serde_json::to_value(notice).ok()) else {
+        return response;
+    };
+    if let Some(response) = response.as_object_mut() {
+        response.insert("privacyWithheld".to_string(), notice);
+    }
+    response
+}
+
+/// Returns the subset of `paths` covered by the workspace privacy rules, so
+/// the composer can flag mentions and attachments before sending.
+#[tauri::command]
+pub(crate) async fn check_privacy_zones(
+    workspace_id: String,
+    paths: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let entry = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| format!("Workspace not found: {workspace_id}"))?;
+    let Some(zones) = PrivacyZones::new(Path::new(&entry.path), &entry.settings.privacy_rules)?
+    else {
+        return Ok(Vec::new());
+    };
+    Ok(paths
+        .into_iter()
+        .filter(|path| zones.is_private(path))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PrivacyZones, WITHHELD_PLACEHOLDER};
+    use std::path::Path;
+
+    fn zones(rules: &[&str]) -> PrivacyZones {
+        let rules: Vec<String> = rules.iter().map(|rule| rule.to_string()).collect();
+        PrivacyZones::new(Path::new("/work/app"), &rules)
+            .expect("valid rules")
+            .expect("zones")
+    }
+
+    #[test]
+    fn matches_relative_absolute_and_nested_paths() {
+        let zones = zones(&["secrets/", "*.pem", "!public.pem"]);
+
+        assert!(zones.is_private("secrets/prod.env"));
+        assert!(zones.is_private("/work/app/secrets/nested/key.json"));
+        assert!(zones.is_private("./certs/server.pem"));
+        assert!(!zones.is_private("certs/public.pem"));
+        assert!(!zones.is_private("src/main.rs"));
+        assert!(!zones.is_private("/elsewhere/secrets/prod.env"));
+        assert!(!zones.is_private("../secrets/prod.env"));
+    }
+
+    #[test]
+    fn redacts_mentions_and_private_fenced_blocks() {
+        let zones = zones(&["secrets/"]);
+        let prompt = "Compare @secrets/prod.env with (src/config.rs).\n\
+```env path=secrets/prod.env\nTOKEN=abc\n```\n\
+```rust\nfn main() {}\n```\n";
+
+        let (filtered, withheld) = zones.filter_prompt(prompt);
+
+        assert_eq!(withheld, vec!["secrets/prod.env"]);
+        assert!(!filtered.contains("TOKEN=abc"));
+        assert!(!filtered.contains("secrets/prod.env"));
+        assert!(filtered.contains(&format!(
+            "Compare {WITHHELD_PLACEHOLDER} with (src/config.rs)."
+        )));
+        assert!(filtered.contains("```rust\nfn main() {}\n```\n"));
+    }
+
+    #[test]
+    fn drops_private_attachments_but_keeps_data_urls() {
+        let zones = zones(&["screenshots/private/"]);
+
+        let (allowed, withheld) = zones.filter_attachments(vec![
+            "/work/app/screenshots/private/login.png".to_string(),
+            "/work/app/screenshots/home.png".to_string(),
+            "data:image/png;base64,AAAA".to_string(),
+        ]);
+
+        assert_eq!(
+            allowed,
+            vec![
+                "/work/app/screenshots/home.png".to_string(),
+                "data:image/png;base64,AAAA".to_string()
+            ]
+        );
+        assert_eq!(
+            withheld,
+            vec!["/work/app/screenshots/private/login.png".to_string()]
+        );
+    }
+}
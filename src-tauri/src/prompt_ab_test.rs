@@ -0,0 +1,839 @@
+//! Prompt A/B testing harness.
+//!
+//! A run sends two prompt variants through every (task, model) pair. Each case
+//! executes in its own scratch worktree so the agent's edits can be scored in
+//! isolation: optionally by running a test command and by measuring the diff
+//! it leaves behind. Runs are stored per workspace and summarized per variant
+//! for comparison.
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use git2::{DiffOptions, Repository};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+use crate::app_paths;
+use crate::engine::{self, EngineType};
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const AB_TESTS_DIRNAME: &str = "prompt-ab-tests";
+const AB_TEST_BRANCH_PREFIX: &str = "ab-test/";
+const AB_TEST_PROGRESS_EVENT: &str = "ab-test-progress";
+const INSTRUCTION_PLACEHOLDER: &str = "{{instruction}}";
+const FILES_PLACEHOLDER: &str = "{{files}}";
+const MAX_RUNS_PER_WORKSPACE: usize = 50;
+const MAX_TASKS: usize = 20;
+const MAX_MODELS: usize = 4;
+const MAX_TASK_FILE_BYTES: usize = 32 * 1024;
+const MAX_STORED_OUTPUT_CHARS: usize = 20_000;
+const MAX_TEST_OUTPUT_CHARS: usize = 4_000;
+const DEFAULT_TEST_TIMEOUT_SECS: u64 = 600;
+const MAX_TEST_TIMEOUT_SECS: u64 = 3_600;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptVariant {
+    label: String,
+    /// May use `{{instruction}}` and `{{files}}`; whatever is not referenced
+    /// is appended after the template.
+    template: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestTask {
+    #[serde(default)]
+    id: Option<String>,
+    instruction: String,
+    /// Workspace-relative files inlined into the prompt.
+    #[serde(default)]
+    files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestModel {
+    engine: EngineType,
+    #[serde(default)]
+    model: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestScoring {
+    /// Shell command run in the case worktree after the agent finishes;
+    /// exit code 0 counts as passing.
+    #[serde(default)]
+    test_command: Option<String>,
+    #[serde(default)]
+    test_timeout_secs: Option<u64>,
+    /// Record files changed and lines added/removed against HEAD.
+    #[serde(default)]
+    diff_size: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum AbTestRunStatus {
+    Running,
+    Completed,
+    /// The app stopped before the run finished.
+    Interrupted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestTestScore {
+    passed: bool,
+    #[serde(default)]
+    exit_code: Option<i32>,
+    output_tail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestDiffScore {
+    files_changed: usize,
+    insertions: usize,
+    deletions: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestCaseResult {
+    task_id: String,
+    variant: String,
+    engine: EngineType,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    output: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    duration_ms: u64,
+    #[serde(default)]
+    tests: Option<AbTestTestScore>,
+    #[serde(default)]
+    diff: Option<AbTestDiffScore>,
+    /// Set when the scratch worktree was kept for inspection.
+    #[serde(default)]
+    worktree_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestRun {
+    id: String,
+    workspace_id: String,
+    #[serde(default)]
+    name: Option<String>,
+    status: AbTestRunStatus,
+    variants: Vec<PromptVariant>,
+    tasks: Vec<AbTestTask>,
+    models: Vec<AbTestModel>,
+    #[serde(default)]
+    scoring: AbTestScoring,
+    #[serde(default)]
+    access_mode: Option<String>,
+    total_cases: usize,
+    #[serde(default)]
+    cases: Vec<AbTestCaseResult>,
+    created_at: u64,
+    #[serde(default)]
+    finished_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestVariantSummary {
+    variant: String,
+    cases: usize,
+    failures: usize,
+    tests_run: usize,
+    tests_passed: usize,
+    mean_duration_ms: Option<u64>,
+    mean_diff_lines: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestRunReport {
+    #[serde(flatten)]
+    run: AbTestRun,
+    summaries: Vec<AbTestVariantSummary>,
+}
+
+static ACTIVE_RUNS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+fn active_runs() -> std::sync::MutexGuard<'static, HashSet<String>> {
+    ACTIVE_RUNS
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_millis(0))
+        .as_millis() as u64
+}
+
+fn is_safe_storage_id(value: &str) -> bool {
+    !value.is_empty()
+        && value != "."
+        && value != ".."
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn runs_store_path(workspace_id: &str) -> Result<PathBuf, String> {
+    if !is_safe_storage_id(workspace_id) {
+        return Err(format!("Invalid workspace id: {workspace_id}"));
+    }
+    Ok(app_paths::app_home_dir()?
+        .join(AB_TESTS_DIRNAME)
+        .join(format!("{workspace_id}.json")))
+}
+
+fn read_runs(path: &Path) -> Result<Vec<AbTestRun>, String> {
+    Ok(read_json_file::<Vec<AbTestRun>>(path)?.unwrap_or_default())
+}
+
+fn update_runs<T>(
+    path: &Path,
+    op: impl FnOnce(&mut Vec<AbTestRun>) -> Result<T, String>,
+) -> Result<T, String> {
+    with_storage_lock(path, || {
+        let mut runs = read_runs(path)?;
+        let result = op(&mut runs)?;
+        let data = serde_json::to_string_pretty(&runs)
+            .map_err(|error| format!("failed to serialize {}: {error}", path.display()))?;
+        write_string_atomically(path, &data)?;
+        Ok(result)
+    })
+}
+
+fn update_run(path: &Path, run_id: &str, op: impl FnOnce(&mut AbTestRun)) -> Result<(), String> {
+    update_runs(path, |runs| {
+        if let Some(run) = runs.iter_mut().find(|run| run.id == run_id) {
+            op(run);
+        }
+        Ok(())
+    })
+}
+
+fn truncate_chars(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(limit).collect();
+    truncated.push_str("\n[truncated]");
+    truncated
+}
+
+fn tail_chars(text: &str, limit: usize) -> String {
+    let count = text.chars().count();
+    if count <= limit {
+        return text.to_string();
+    }
+    text.chars().skip(count - limit).collect()
+}
+
+fn is_safe_relative_path(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+fn task_id(task: &AbTestTask, index: usize) -> String {
+    task.id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("task-{}", index + 1))
+}
+
+fn validate_run(
+    variants: &[PromptVariant],
+    tasks: &[AbTestTask],
+    models: &[AbTestModel],
+) -> Result<(), String> {
+    if variants.len() != 2 {
+        return Err("An A/B test needs exactly two prompt variants".to_string());
+    }
+    if variants
+        .iter()
+        .any(|variant| variant.label.trim().is_empty())
+    {
+        return Err("Prompt variants need a label".to_string());
+    }
+    if variants[0].label.trim() == variants[1].label.trim() {
+        return Err("Prompt variant labels must differ".to_string());
+    }
+    if tasks.is_empty() || tasks.len() > MAX_TASKS {
+        return Err(format!("An A/B test needs between 1 and {MAX_TASKS} tasks"));
+    }
+    if tasks.iter().any(|task| task.instruction.trim().is_empty()) {
+        return Err("Every task needs an instruction".to_string());
+    }
+    if let Some(path) = tasks
+        .iter()
+        .flat_map(|task| task.files.iter())
+        .find(|path| !is_safe_relative_path(path))
+    {
+        return Err(format!("Task files must be workspace-relative: {path}"));
+    }
+    if models.is_empty() || models.len() > MAX_MODELS {
+        return Err(format!(
+            "An A/B test needs between 1 and {MAX_MODELS} models"
+        ));
+    }
+    Ok(())
+}
+
+fn render_files_block(root: &Path, files: &[String]) -> String {
+    files
+        .iter()
+        .map(|path| {
+            let body = match std::fs::read(root.join(path)) {
+                Ok(bytes) => {
+                    let text = String::from_utf8_lossy(&bytes);
+                    if bytes.len() > MAX_TASK_FILE_BYTES {
+                        let mut cut = MAX_TASK_FILE_BYTES;
+                        while !text.is_char_boundary(cut) {
+                            cut -= 1;
+                        }
+                        format!("{}\n[truncated]", &text[..cut])
+                    } else {
+                        text.to_string()
+                    }
+                }
+                Err(error) => format!("[unreadable: {error}]"),
+            };
+            format!("File: {path}\n```\n{}\n```", body.trim_end())
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Fills the variant template. Placeholders the template omits are appended
+/// so every variant sees the same task.
+fn render_prompt(template: &str, instruction: &str, files_block: &str) -> String {
+    let mut prompt = template
+        .replace(INSTRUCTION_PLACEHOLDER, instruction)
+        .replace(FILES_PLACEHOLDER, files_block)
+        .trim_end()
+        .to_string();
+    if !template.contains(INSTRUCTION_PLACEHOLDER) {
+        prompt.push_str("\n\n");
+        prompt.push_str(instruction);
+    }
+    if !files_block.is_empty() && !template.contains(FILES_PLACEHOLDER) {
+        prompt.push_str("\n\n");
+        prompt.push_str(files_block);
+    }
+    prompt.trim().to_string()
+}
+
+fn measure_diff(root: &Path) -> Result<AbTestDiffScore, String> {
+    let repo = Repository::open(root).map_err(|error| error.to_string())?;
+    let head_tree = repo
+        .head()
+        .and_then(|head| head.peel_to_tree())
+        .map_err(|error| error.to_string())?;
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&head_tree), Some(&mut options))
+        .map_err(|error| error.to_string())?;
+    let stats = diff.stats().map_err(|error| error.to_string())?;
+    Ok(AbTestDiffScore {
+        files_changed: stats.files_changed(),
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+    })
+}
+
+async fn run_test_command(root: &Path, command: &str, timeout_secs: u64) -> AbTestTestScore {
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = crate::utils::async_command("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    };
+    #[cfg(not(windows))]
+    let mut cmd = {
+        let mut cmd = crate::utils::async_command("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    };
+    cmd.current_dir(root)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+    match tokio::time::timeout(Duration::from_secs(timeout_secs), cmd.output()).await {
+        Ok(Ok(output)) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            AbTestTestScore {
+                passed: output.status.success(),
+                exit_code: output.status.code(),
+                output_tail: tail_chars(&text, MAX_TEST_OUTPUT_CHARS),
+            }
+        }
+        Ok(Err(error)) => AbTestTestScore {
+            passed: false,
+            exit_code: None,
+            output_tail: format!("failed to run test command: {error}"),
+        },
+        Err(_) => AbTestTestScore {
+            passed: false,
+            exit_code: None,
+            output_tail: format!("test command timed out after {timeout_secs}s"),
+        },
+    }
+}
+
+fn summarize(run: &AbTestRun) -> Vec<AbTestVariantSummary> {
+    run.variants
+        .iter()
+        .map(|variant| {
+            let cases: Vec<&AbTestCaseResult> = run
+                .cases
+                .iter()
+                .filter(|case| case.variant == variant.label)
+                .collect();
+            let completed: Vec<&&AbTestCaseResult> =
+                cases.iter().filter(|case| case.error.is_none()).collect();
+            let tests: Vec<&AbTestTestScore> = cases
+                .iter()
+                .filter_map(|case| case.tests.as_ref())
+                .collect();
+            let diffs: Vec<usize> = cases
+                .iter()
+                .filter_map(|case| case.diff.as_ref())
+                .map(|diff| diff.insertions + diff.deletions)
+                .collect();
+            AbTestVariantSummary {
+                variant: variant.label.clone(),
+                cases: cases.len(),
+                failures: cases.len() - completed.len(),
+                tests_run: tests.len(),
+                tests_passed: tests.iter().filter(|score| score.passed).count(),
+                mean_duration_ms: (!completed.is_empty()).then(|| {
+                    completed.iter().map(|case| case.duration_ms).sum::<u64>()
+                        / completed.len() as u64
+                }),
+                mean_diff_lines: (!diffs.is_empty())
+                    .then(|| diffs.iter().sum::<usize>() as f64 / diffs.len() as f64),
+            }
+        })
+        .collect()
+}
+
+fn emit_progress(app: &AppHandle, run: &AbTestRun) {
+    let _ = app.emit(
+        AB_TEST_PROGRESS_EVENT,
+        json!({
+            "workspaceId": run.workspace_id,
+            "runId": run.id,
+            "status": run.status,
+            "completedCases": run.cases.len(),
+            "totalCases": run.total_cases,
+        }),
+    );
+}
+
+fn response_text(response: &Value) -> Option<String> {
+    response
+        .get("text")
+        .or_else(|| response.pointer("/result/text"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+struct CaseSpec<'a> {
+    run_id: &'a str,
+    index: usize,
+    task_id: String,
+    task: &'a AbTestTask,
+    variant: &'a PromptVariant,
+    target: &'a AbTestModel,
+}
+
+async fn run_case(
+    app: &AppHandle,
+    workspace_id: &str,
+    spec: CaseSpec<'_>,
+    scoring: &AbTestScoring,
+    access_mode: Option<String>,
+    keep_worktrees: bool,
+) -> AbTestCaseResult {
+    let mut result = AbTestCaseResult {
+        task_id: spec.task_id,
+        variant: spec.variant.label.clone(),
+        engine: spec.target.engine,
+        model: spec.target.model.clone(),
+        output: None,
+        error: None,
+        duration_ms: 0,
+        tests: None,
+        diff: None,
+        worktree_path: None,
+    };
+    let branch = format!(
+        "{AB_TEST_BRANCH_PREFIX}{}-{}",
+        &spec.run_id[..8.min(spec.run_id.len())],
+        spec.index + 1
+    );
+    let worktree = match crate::workspaces::add_worktree(
+        workspace_id.to_string(),
+        branch,
+        None,
+        None,
+        Some(false),
+        app.state::<AppState>(),
+        app.clone(),
+    )
+    .await
+    {
+        Ok(worktree) => worktree,
+        Err(error) => {
+            result.error = Some(format!("Failed to create scratch worktree: {error}"));
+            return result;
+        }
+    };
+    let root = PathBuf::from(&worktree.path);
+    let files_block = render_files_block(&root, &spec.task.files);
+    let prompt = render_prompt(
+        &spec.variant.template,
+        spec.task.instruction.trim(),
+        &files_block,
+    );
+
+    let started = Instant::now();
+    let response = engine::engine_send_message_sync(
+        worktree.id.clone(),
+        prompt,
+        Some(spec.target.engine),
+        spec.target.model.clone(),
+        None,
+        None,
+        access_mode,
+        None,
+        false,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        app.clone(),
+        app.state::<AppState>(),
+    )
+    .await;
+    result.duration_ms = started.elapsed().as_millis() as u64;
+    match response {
+        Ok(response) => {
+            result.output =
+                response_text(&response).map(|text| truncate_chars(&text, MAX_STORED_OUTPUT_CHARS));
+        }
+        Err(error) => result.error = Some(error),
+    }
+
+    if result.error.is_none() {
+        if let Some(command) = scoring
+            .test_command
+            .as_deref()
+            .map(str::trim)
+            .filter(|command| !command.is_empty())
+        {
+            let timeout_secs = scoring
+                .test_timeout_secs
+                .unwrap_or(DEFAULT_TEST_TIMEOUT_SECS)
+                .clamp(1, MAX_TEST_TIMEOUT_SECS);
+            result.tests = Some(run_test_command(&root, command, timeout_secs).await);
+        }
+        if scoring.diff_size {
+            let diff_root = root.clone();
+            match tokio::task::spawn_blocking(move || measure_diff(&diff_root)).await {
+                Ok(Ok(diff)) => result.diff = Some(diff),
+                Ok(Err(error)) => log::warn!("[prompt_ab_test] diff measurement failed: {error}"),
+                Err(error) => log::warn!("[prompt_ab_test] diff measurement panicked: {error}"),
+            }
+        }
+    }
+
+    if keep_worktrees {
+        result.worktree_path = Some(worktree.path);
+    } else if let Err(error) =
+        crate::workspaces::remove_worktree(worktree.id, app.state::<AppState>(), app.clone()).await
+    {
+        log::warn!("[prompt_ab_test] failed to remove scratch worktree: {error}");
+    }
+    result
+}
+
+async fn execute_run(app: AppHandle, run: AbTestRun, keep_worktrees: bool) {
+    let store_path = match runs_store_path(&run.workspace_id) {
+        Ok(path) => path,
+        Err(error) => {
+            log::warn!("[prompt_ab_test] {error}");
+            return;
+        }
+    };
+    let mut progress = run.clone();
+    let mut index = 0;
+    for (task_index, task) in run.tasks.iter().enumerate() {
+        for target in &run.models {
+            for variant in &run.variants {
+                let spec = CaseSpec {
+                    run_id: &run.id,
+                    index,
+                    task_id: task_id(task, task_index),
+                    task,
+                    variant,
+                    target,
+                };
+                index += 1;
+                let case = run_case(
+                    &app,
+                    &run.workspace_id,
+                    spec,
+                    &run.scoring,
+                    run.access_mode.clone(),
+                    keep_worktrees,
+                )
+                .await;
+                progress.cases.push(case.clone());
+                if let Err(error) = update_run(&store_path, &run.id, |stored| {
+                    stored.cases.push(case);
+                }) {
+                    log::warn!("[prompt_ab_test] failed to store case result: {error}");
+                }
+                emit_progress(&app, &progress);
+            }
+        }
+    }
+    progress.status = AbTestRunStatus::Completed;
+    progress.finished_at = Some(now_millis());
+    let finished_at = progress.finished_at;
+    if let Err(error) = update_run(&store_path, &run.id, |stored| {
+        stored.status = AbTestRunStatus::Completed;
+        stored.finished_at = finished_at;
+    }) {
+        log::warn!("[prompt_ab_test] failed to finish run: {error}");
+    }
+    active_runs().remove(&run.id);
+    emit_progress(&app, &progress);
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestRunRequest {
+    #[serde(default)]
+    name: Option<String>,
+    variants: Vec<PromptVariant>,
+    tasks: Vec<AbTestTask>,
+    models: Vec<AbTestModel>,
+    #[serde(default)]
+    scoring: Option<AbTestScoring>,
+    #[serde(default)]
+    access_mode: Option<String>,
+    /// Keep each case's scratch worktree for inspection instead of removing
+    /// it once the case is scored.
+    #[serde(default)]
+    keep_worktrees: bool,
+}
+
+/// Starts an A/B run in the background and returns it immediately; progress
+/// is reported through `ab-test-progress` events. Cases run one at a time,
+/// each in a scratch worktree branched from the workspace HEAD.
+#[tauri::command]
+pub(crate) async fn ab_test_run(
+    workspace_id: String,
+    request: AbTestRunRequest,
+    app: AppHandle,
+) -> Result<AbTestRun, String> {
+    let AbTestRunRequest {
+        name,
+        variants,
+        tasks,
+        models,
+        scoring,
+        access_mode,
+        keep_worktrees,
+    } = request;
+    validate_run(&variants, &tasks, &models)?;
+    let store_path = runs_store_path(&workspace_id)?;
+    {
+        let state = app.state::<AppState>();
+        let workspaces = state.workspaces.lock().await;
+        if !workspaces.contains_key(&workspace_id) {
+            return Err("workspace not found".to_string());
+        }
+    }
+    let run = AbTestRun {
+        id: Uuid::new_v4().to_string(),
+        workspace_id,
+        name: name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty()),
+        status: AbTestRunStatus::Running,
+        total_cases: tasks.len() * models.len() * variants.len(),
+        variants,
+        tasks,
+        models,
+        scoring: scoring.unwrap_or_default(),
+        access_mode,
+        cases: Vec::new(),
+        created_at: now_millis(),
+        finished_at: None,
+    };
+    let record = run.clone();
+    update_runs(&store_path, move |runs| {
+        runs.push(record);
+        if runs.len() > MAX_RUNS_PER_WORKSPACE {
+            let excess = runs.len() - MAX_RUNS_PER_WORKSPACE;
+            runs.drain(..excess);
+        }
+        Ok(())
+    })?;
+    active_runs().insert(run.id.clone());
+    let background = run.clone();
+    tauri::async_runtime::spawn(execute_run(app, background, keep_worktrees));
+    Ok(run)
+}
+
+/// Stored runs of a workspace, newest first, with per-variant summaries.
+#[tauri::command]
+pub(crate) async fn ab_test_results(
+    workspace_id: String,
+    run_id: Option<String>,
+) -> Result<Vec<AbTestRunReport>, String> {
+    let store_path = runs_store_path(&workspace_id)?;
+    let mut runs = read_runs(&store_path)?;
+    if let Some(run_id) = run_id {
+        runs.retain(|run| run.id == run_id);
+    }
+    let active = active_runs().clone();
+    runs.sort_by_key(|run| std::cmp::Reverse(run.created_at));
+    Ok(runs
+        .into_iter()
+        .map(|mut run| {
+            if run.status == AbTestRunStatus::Running && !active.contains(&run.id) {
+                run.status = AbTestRunStatus::Interrupted;
+            }
+            let summaries = summarize(&run);
+            AbTestRunReport { run, summaries }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(label: &str, template: &str) -> PromptVariant {
+        PromptVariant {
+            label: label.to_string(),
+            template: template.to_string(),
+        }
+    }
+
+    #[test]
+    fn render_prompt_fills_or_appends_placeholders() {
+        assert_eq!(
+            render_prompt("Do this: {{instruction}}\n{{files}}", "fix it", "File: a"),
+            "Do this: fix it\nFile: a"
+        );
+        assert_eq!(
+            render_prompt("Be terse.", "fix it", "File: a"),
+            "Be terse.\n\nfix it\n\nFile: a"
+        );
+        assert_eq!(render_prompt("{{instruction}}", "fix it", ""), "fix it");
+    }
+
+    #[test]
+    fn summaries_compare_variants_and_validation_rejects_bad_runs() {
+        let variants = vec![variant("a", "{{instruction}}"), variant("b", "x")];
+        let case =
+            |variant: &str, passed: bool, lines: usize, error: Option<&str>| AbTestCaseResult {
+                task_id: "task-1".to_string(),
+                variant: variant.to_string(),
+                engine: EngineType::Claude,
+                model: None,
+                output: None,
+                error: error.map(str::to_string),
+                duration_ms: 100,
+                tests: Some(AbTestTestScore {
+                    passed,
+                    exit_code: Some(if passed { 0 } else { 1 }),
+                    output_tail: String::new(),
+                }),
+                diff: Some(AbTestDiffScore {
+                    files_changed: 1,
+                    insertions: lines,
+                    deletions: 0,
+                }),
+                worktree_path: None,
+            };
+        let run = AbTestRun {
+            id: "run".to_string(),
+            workspace_id: "ws".to_string(),
+            name: None,
+            status: AbTestRunStatus::Completed,
+            variants: variants.clone(),
+            tasks: Vec::new(),
+            models: Vec::new(),
+            scoring: AbTestScoring::default(),
+            access_mode: None,
+            total_cases: 3,
+            cases: vec![
+                case("a", true, 10, None),
+                case("a", false, 30, None),
+                case("b", true, 4, None),
+            ],
+            created_at: 1,
+            finished_at: Some(2),
+        };
+        let summaries = summarize(&run);
+        assert_eq!(summaries[0].tests_passed, 1);
+        assert_eq!(summaries[0].tests_run, 2);
+        assert_eq!(summaries[0].mean_diff_lines, Some(20.0));
+        assert_eq!(summaries[1].cases, 1);
+        assert_eq!(summaries[1].mean_duration_ms, Some(100));
+
+        let task = AbTestTask {
+            id: None,
+            instruction: "do it".to_string(),
+            files: vec!["../secret".to_string()],
+        };
+        let model = AbTestModel {
+            engine: EngineType::Claude,
+            model: None,
+        };
+        let mut tasks = vec![task];
+        let models = vec![model];
+        assert!(validate_run(&variants[..1], &tasks, &models).is_err());
+        assert!(validate_run(&variants, &tasks, &models)
+            .unwrap_err()
+            .contains("workspace-relative"));
+        tasks[0].files = vec!["src/lib.rs".to_string()];
+        assert!(validate_run(&variants, &tasks, &models).is_ok());
+    }
+}
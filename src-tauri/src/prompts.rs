@@ -1,5 +1,5 @@
-use serde::Serialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::State;
@@ -19,6 +19,9 @@ pub(crate) struct CustomPromptEntry {
     pub(crate) content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) scope: Option<String>,
+    /// Placeholder names (`{{name}}`) found in `content`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) variables: Vec<String>,
 }
 
 fn resolve_codex_home_for_workspace(
@@ -227,6 +230,62 @@ fn sanitize_prompt_name(name: &str) -> Result<String, String> {
     Ok(trimmed.to_string())
 }
 
+fn is_template_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+}
+
+/// Substitutes `{{name}}` placeholders with values from `lookup`. `\{{`
+/// renders a literal `{{`. Placeholders without a value stay in the text and
+/// are returned, in order of first appearance.
+fn render_prompt_template<'a>(
+    template: &str,
+    lookup: impl Fn(&str) -> Option<&'a str>,
+) -> (String, Vec<String>) {
+    let mut output = String::with_capacity(template.len());
+    let mut missing: Vec<String> = Vec::new();
+    let mut rest = template;
+    while let Some(index) = rest.find("{{") {
+        let (before, after) = rest.split_at(index);
+        if let Some(escaped) = before.strip_suffix('\\') {
+            output.push_str(escaped);
+            output.push_str("{{");
+            rest = &after[2..];
+            continue;
+        }
+        output.push_str(before);
+        let Some(end) = after[2..].find("}}") else {
+            output.push_str(after);
+            rest = "";
+            break;
+        };
+        let placeholder = &after[..end + 4];
+        let name = after[2..end + 2].trim();
+        rest = &after[end + 4..];
+        if !is_template_variable_name(name) {
+            output.push_str(placeholder);
+            continue;
+        }
+        match lookup(name) {
+            Some(value) => output.push_str(value),
+            None => {
+                if !missing.iter().any(|existing| existing == name) {
+                    missing.push(name.to_string());
+                }
+                output.push_str(placeholder);
+            }
+        }
+    }
+    output.push_str(rest);
+    (output, missing)
+}
+
+fn prompt_template_variables(content: &str) -> Vec<String> {
+    render_prompt_template(content, |_| None).1
+}
+
 fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry> {
     let mut out: Vec<CustomPromptEntry> = Vec::new();
     let entries = match fs::read_dir(dir) {
@@ -265,6 +324,7 @@ fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry
             path: path.to_string_lossy().to_string(),
             description,
             argument_hint,
+            variables: prompt_template_variables(&body),
             content: body,
             scope: scope.map(|value| value.to_string()),
         });
@@ -375,6 +435,7 @@ pub(crate) async fn prompts_create(
         path: path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        variables: prompt_template_variables(&content),
         content,
         scope: Some(resolved_scope.to_string()),
     })
@@ -428,6 +489,7 @@ pub(crate) async fn prompts_update(
         path: next_path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        variables: prompt_template_variables(&content),
         content,
         scope,
     })
@@ -506,7 +568,504 @@ pub(crate) async fn prompts_move(
         path: next_path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        variables: prompt_template_variables(&body),
         content: body,
         scope: Some(scope),
     })
 }
+
+#[derive(Serialize)]
+pub(crate) struct PromptRenderResult {
+    text: String,
+    /// Placeholders left in `text` because no value was supplied or known.
+    missing: Vec<String>,
+}
+
+/// Values for the well-known placeholders, derived from the workspace and the
+/// active engine. Caller-supplied variables take precedence.
+async fn builtin_prompt_variables(
+    state: &State<'_, AppState>,
+    entry: Option<&WorkspaceEntry>,
+) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    values.insert(
+        "engine".to_string(),
+        state
+            .engine_manager
+            .get_active_engine()
+            .await
+            .icon()
+            .to_string(),
+    );
+    let Some(entry) = entry else {
+        return values;
+    };
+    values.insert("workspace_path".to_string(), entry.path.clone());
+    values.insert("workspace_name".to_string(), entry.name.clone());
+    if let Some(branch) = crate::git_utils::resolve_git_root(entry)
+        .ok()
+        .and_then(|root| crate::git::current_local_branch(&root).ok().flatten())
+    {
+        values.insert("branch".to_string(), branch);
+    }
+    values
+}
+
+/// Renders the prompt at `prompt_id` (its path) with `variables` and the
+/// well-known values (`branch`, `workspace_path`, `workspace_name`,
+/// `engine`). The stored file is only read.
+#[tauri::command]
+pub(crate) async fn prompts_render(
+    state: State<'_, AppState>,
+    prompt_id: String,
+    variables: HashMap<String, String>,
+    workspace_id: Option<String>,
+) -> Result<PromptRenderResult, String> {
+    let target = PathBuf::from(&prompt_id);
+    let (entry, roots) = {
+        let workspaces = state.workspaces.lock().await;
+        match workspace_id.as_deref() {
+            Some(workspace_id) => {
+                let entry = require_workspace_entry(&workspaces, workspace_id)?;
+                let roots = prompt_roots_for_workspace(&state, &workspaces, &entry)?;
+                (Some(entry), roots)
+            }
+            None => (
+                None,
+                resolve_default_codex_home()
+                    .map(|home| vec![home.join("prompts")])
+                    .unwrap_or_default(),
+            ),
+        }
+    };
+    ensure_path_within_roots(&target, &roots)?;
+    let contents = fs::read_to_string(&target).map_err(|_| "Prompt not found.".to_string())?;
+    let (_, _, body) = parse_frontmatter(&contents);
+    let builtins = builtin_prompt_variables(&state, entry.as_ref()).await;
+    let (text, missing) = render_prompt_template(&body, |name| {
+        variables
+            .get(name)
+            .or_else(|| builtins.get(name))
+            .map(String::as_str)
+    });
+    Ok(PromptRenderResult { text, missing })
+}
+
+const PROMPT_BUNDLE_FORMAT: &str = "ccgui-prompts";
+const PROMPT_BUNDLE_VERSION: u32 = 1;
+
+/// A portable set of prompts. Each entry keeps the scope folder it was
+/// exported from so an import can restore the same layout.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptBundle {
+    format: String,
+    version: u32,
+    prompts: Vec<PromptBundleEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct PromptBundleEntry {
+    name: String,
+    scope: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    argument_hint: Option<String>,
+    content: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct PromptExportResult {
+    path: String,
+    count: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub(crate) struct PromptRename {
+    from: String,
+    to: String,
+}
+
+#[derive(Serialize, Default, Debug, PartialEq)]
+pub(crate) struct PromptImportSummary {
+    created: Vec<String>,
+    overwritten: Vec<String>,
+    skipped: Vec<String>,
+    renamed: Vec<PromptRename>,
+}
+
+#[derive(Clone, Copy)]
+enum PromptConflictStrategy {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+impl PromptConflictStrategy {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "skip" => Ok(Self::Skip),
+            "overwrite" => Ok(Self::Overwrite),
+            "rename" => Ok(Self::Rename),
+            _ => Err("Invalid conflict strategy.".to_string()),
+        }
+    }
+}
+
+struct PlannedPromptWrite {
+    path: PathBuf,
+    contents: String,
+}
+
+fn scope_prompts_dir(
+    state: &State<'_, AppState>,
+    workspaces: &HashMap<String, WorkspaceEntry>,
+    entry: &WorkspaceEntry,
+    scope: &str,
+) -> Result<PathBuf, String> {
+    match scope {
+        "workspace" => workspace_prompts_dir(state, entry),
+        "global" => default_prompts_dir_for_workspace(workspaces, entry)
+            .ok_or("Unable to resolve CODEX_HOME".to_string()),
+        _ => Err("Invalid scope.".to_string()),
+    }
+}
+
+/// Parses and validates the whole bundle up front so a bad entry rejects the
+/// import before anything is written.
+fn parse_prompt_bundle(data: &str) -> Result<PromptBundle, String> {
+    let bundle: PromptBundle =
+        serde_json::from_str(data).map_err(|err| format!("Invalid prompt bundle: {err}"))?;
+    if bundle.format != PROMPT_BUNDLE_FORMAT {
+        return Err("Not a prompt bundle.".to_string());
+    }
+    if bundle.version > PROMPT_BUNDLE_VERSION {
+        return Err(format!(
+            "Prompt bundle version {} is newer than supported.",
+            bundle.version
+        ));
+    }
+    let mut seen = HashSet::new();
+    for prompt in &bundle.prompts {
+        let name = sanitize_prompt_name(&prompt.name)
+            .map_err(|err| format!("Invalid prompt bundle entry \"{}\": {err}", prompt.name))?;
+        if name != prompt.name {
+            return Err(format!(
+                "Invalid prompt bundle entry \"{}\": name has surrounding whitespace.",
+                prompt.name
+            ));
+        }
+        if prompt.scope != "workspace" && prompt.scope != "global" {
+            return Err(format!(
+                "Invalid prompt bundle entry \"{}\": unknown scope \"{}\".",
+                prompt.name, prompt.scope
+            ));
+        }
+        if !seen.insert((prompt.scope.as_str(), prompt.name.as_str())) {
+            return Err(format!(
+                "Invalid prompt bundle: \"{}\" appears twice in {} scope.",
+                prompt.name, prompt.scope
+            ));
+        }
+    }
+    Ok(bundle)
+}
+
+/// Decides where each entry goes without touching the disk beyond existence
+/// checks. `dir_for_scope` maps an entry's scope to its target directory.
+fn plan_prompt_import(
+    prompts: &[PromptBundleEntry],
+    dir_for_scope: impl Fn(&str) -> Result<PathBuf, String>,
+    strategy: PromptConflictStrategy,
+) -> Result<(Vec<PlannedPromptWrite>, PromptImportSummary), String> {
+    let mut writes: Vec<PlannedPromptWrite> = Vec::new();
+    let mut summary = PromptImportSummary::default();
+    for prompt in prompts {
+        let dir = dir_for_scope(&prompt.scope)?;
+        let taken = |path: &Path| path.exists() || writes.iter().any(|write| write.path == path);
+        let mut name = prompt.name.clone();
+        let mut path = dir.join(format!("{name}.md"));
+        if taken(&path) {
+            match strategy {
+                PromptConflictStrategy::Skip => {
+                    summary.skipped.push(name);
+                    continue;
+                }
+                PromptConflictStrategy::Overwrite => {
+                    summary.overwritten.push(name.clone());
+                }
+                PromptConflictStrategy::Rename => {
+                    let mut suffix = 2;
+                    loop {
+                        name = format!("{}-{suffix}", prompt.name);
+                        path = dir.join(format!("{name}.md"));
+                        if !taken(&path) {
+                            break;
+                        }
+                        suffix += 1;
+                    }
+                    summary.renamed.push(PromptRename {
+                        from: prompt.name.clone(),
+                        to: name.clone(),
+                    });
+                }
+            }
+        } else {
+            summary.created.push(name);
+        }
+        writes.retain(|write| write.path != path);
+        writes.push(PlannedPromptWrite {
+            path,
+            contents: build_prompt_contents(
+                prompt.description.clone(),
+                prompt.argument_hint.clone(),
+                prompt.content.clone(),
+            ),
+        });
+    }
+    Ok((writes, summary))
+}
+
+/// Stages every file next to its destination before renaming any into
+/// place, so a write failure leaves the prompt folders as they were.
+fn apply_prompt_writes(writes: &[PlannedPromptWrite]) -> Result<(), String> {
+    let mut staged: Vec<(PathBuf, &Path)> = Vec::new();
+    let cleanup = |staged: &[(PathBuf, &Path)]| {
+        for (temp, _) in staged {
+            let _ = fs::remove_file(temp);
+        }
+    };
+    for write in writes {
+        let result = (|| {
+            let dir = write
+                .path
+                .parent()
+                .ok_or("Unable to resolve prompt directory.".to_string())?;
+            fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+            let file_name = write
+                .path
+                .file_name()
+                .and_then(|value| value.to_str())
+                .unwrap_or("prompt.md");
+            let temp = dir.join(format!(".{file_name}.import-{}", uuid::Uuid::new_v4()));
+            fs::write(&temp, &write.contents).map_err(|err| err.to_string())?;
+            Ok::<_, String>(temp)
+        })();
+        match result {
+            Ok(temp) => staged.push((temp, &write.path)),
+            Err(err) => {
+                cleanup(&staged);
+                return Err(format!("Failed to import prompts: {err}"));
+            }
+        }
+    }
+    for (index, (temp, path)) in staged.iter().enumerate() {
+        if let Err(err) = fs::rename(temp, path) {
+            cleanup(&staged[index..]);
+            return Err(format!("Failed to import prompts: {err}"));
+        }
+    }
+    Ok(())
+}
+
+/// Writes the prompts of `scope` (all, or only those named in `ids`) to a
+/// bundle file at `path`.
+#[tauri::command]
+pub(crate) async fn prompts_export(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    scope: String,
+    ids: Option<Vec<String>>,
+    path: String,
+) -> Result<PromptExportResult, String> {
+    let dir = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = require_workspace_entry(&workspaces, &workspace_id)?;
+        scope_prompts_dir(&state, &workspaces, &entry, &scope)?
+    };
+    let mut prompts = discover_prompts_in(&dir, Some(&scope));
+    if let Some(ids) = ids {
+        if let Some(missing) = ids
+            .iter()
+            .find(|id| !prompts.iter().any(|prompt| &prompt.name == *id))
+        {
+            return Err(format!("Prompt not found: {missing}"));
+        }
+        prompts.retain(|prompt| ids.contains(&prompt.name));
+    }
+    let bundle = PromptBundle {
+        format: PROMPT_BUNDLE_FORMAT.to_string(),
+        version: PROMPT_BUNDLE_VERSION,
+        prompts: prompts
+            .into_iter()
+            .map(|prompt| PromptBundleEntry {
+                name: prompt.name,
+                scope: scope.clone(),
+                description: prompt.description,
+                argument_hint: prompt.argument_hint,
+                content: prompt.content,
+            })
+            .collect(),
+    };
+    let count = bundle.prompts.len();
+    let data = serde_json::to_string_pretty(&bundle).map_err(|err| err.to_string())?;
+    let target = PathBuf::from(&path);
+    if let Some(parent) = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    fs::write(&target, data).map_err(|err| err.to_string())?;
+    Ok(PromptExportResult { path, count })
+}
+
+/// Imports a bundle written by `prompts_export`. Entries go back to the scope
+/// they were exported from unless `target_scope` is given; name clashes are
+/// resolved by `conflict_strategy` ("skip", "overwrite" or "rename").
+#[tauri::command]
+pub(crate) async fn prompts_import(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    path: String,
+    target_scope: Option<String>,
+    conflict_strategy: String,
+) -> Result<PromptImportSummary, String> {
+    let strategy = PromptConflictStrategy::parse(&conflict_strategy)?;
+    let data = fs::read_to_string(&path).map_err(|err| format!("Failed to read bundle: {err}"))?;
+    let bundle = parse_prompt_bundle(&data)?;
+    let (workspace_dir, global_dir) = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = require_workspace_entry(&workspaces, &workspace_id)?;
+        (
+            scope_prompts_dir(&state, &workspaces, &entry, "workspace")?,
+            scope_prompts_dir(&state, &workspaces, &entry, "global").ok(),
+        )
+    };
+    let (writes, summary) = plan_prompt_import(
+        &bundle.prompts,
+        |scope| match target_scope.as_deref().unwrap_or(scope) {
+            "workspace" => Ok(workspace_dir.clone()),
+            "global" => global_dir
+                .clone()
+                .ok_or("Unable to resolve CODEX_HOME".to_string()),
+            _ => Err("Invalid scope.".to_string()),
+        },
+        strategy,
+    )?;
+    task::spawn_blocking(move || apply_prompt_writes(&writes))
+        .await
+        .map_err(|_| "prompt import failed".to_string())??;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle_entry(name: &str, scope: &str) -> PromptBundleEntry {
+        PromptBundleEntry {
+            name: name.to_string(),
+            scope: scope.to_string(),
+            description: Some("Review a diff".to_string()),
+            argument_hint: None,
+            content: "Review $1".to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_prompt_templates() {
+        let values = HashMap::from([
+            ("file".to_string(), "src/main.rs".to_string()),
+            ("branch".to_string(), "feature/x".to_string()),
+        ]);
+        let (text, missing) = render_prompt_template(
+            "Review {{file}} on {{ branch }}; keep \\{{file}} and {{selection}} {{selection}} {{not valid}} {{open",
+            |name| values.get(name).map(String::as_str),
+        );
+        assert_eq!(
+            text,
+            "Review src/main.rs on feature/x; keep {{file}} and {{selection}} {{selection}} {{not valid}} {{open"
+        );
+        assert_eq!(missing, vec!["selection"]);
+        assert_eq!(
+            prompt_template_variables("{{a}} \\{{b}} {{c}} {{a}}"),
+            vec!["a", "c"]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_bundles() {
+        assert!(parse_prompt_bundle("{").is_err());
+        assert!(parse_prompt_bundle(r#"{"format":"other","version":1,"prompts":[]}"#).is_err());
+        assert!(parse_prompt_bundle(
+            r#"{"format":"ccgui-prompts","version":1,"prompts":[{"name":"../x","scope":"global","content":""}]}"#
+        )
+        .is_err());
+        assert!(parse_prompt_bundle(
+            r#"{"format":"ccgui-prompts","version":1,"prompts":[{"name":"a","scope":"team","content":""}]}"#
+        )
+        .is_err());
+        let bundle = parse_prompt_bundle(
+            r#"{"format":"ccgui-prompts","version":1,"prompts":[{"name":"review","scope":"global","argumentHint":"[file]","content":"x"}]}"#,
+        )
+        .expect("valid bundle");
+        assert_eq!(bundle.prompts[0].argument_hint.as_deref(), Some("[file]"));
+    }
+
+    #[test]
+    fn plans_imports_per_conflict_strategy() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        let workspace_dir = root.join("workspace");
+        let global_dir = root.join("global");
+        fs::create_dir_all(&workspace_dir).expect("create workspace dir");
+        fs::write(workspace_dir.join("review.md"), "old").expect("write existing");
+        fs::write(workspace_dir.join("review-2.md"), "old").expect("write existing");
+        let prompts = vec![
+            bundle_entry("review", "workspace"),
+            bundle_entry("review", "global"),
+        ];
+        let dir_for_scope = |scope: &str| {
+            Ok(if scope == "workspace" {
+                workspace_dir.clone()
+            } else {
+                global_dir.clone()
+            })
+        };
+
+        let (writes, summary) =
+            plan_prompt_import(&prompts, dir_for_scope, PromptConflictStrategy::Skip)
+                .expect("plan skip");
+        assert_eq!(summary.skipped, vec!["review"]);
+        assert_eq!(summary.created, vec!["review"]);
+        assert_eq!(writes.len(), 1);
+
+        let (writes, summary) =
+            plan_prompt_import(&prompts, dir_for_scope, PromptConflictStrategy::Rename)
+                .expect("plan rename");
+        assert_eq!(
+            summary.renamed,
+            vec![PromptRename {
+                from: "review".to_string(),
+                to: "review-3".to_string(),
+            }]
+        );
+        apply_prompt_writes(&writes).expect("apply writes");
+        let written = fs::read_to_string(workspace_dir.join("review-3.md")).expect("read renamed");
+        assert!(written.starts_with("---\ndescription: \"Review a diff\"\n---\n"));
+        assert!(global_dir.join("review.md").exists());
+        assert_eq!(
+            fs::read_to_string(workspace_dir.join("review.md")).expect("read original"),
+            "old"
+        );
+
+        let (_, summary) =
+            plan_prompt_import(&prompts, dir_for_scope, PromptConflictStrategy::Overwrite)
+                .expect("plan overwrite");
+        assert_eq!(summary.overwritten, vec!["review", "review"]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,610 @@
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::app_paths;
+use crate::engine::{self, EngineType};
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const REPRO_SANDBOXES_DIRNAME: &str = "repro-sandboxes";
+const REPRO_BRANCH_PREFIX: &str = "repro/issue-";
+const MAX_CONTEXT_FILES: usize = 5;
+const MAX_CONTEXT_FILE_LINES: usize = 120;
+const MAX_CONTEXT_FILE_BYTES: usize = 8 * 1024;
+const MAX_ISSUE_BODY_CHARS: usize = 12_000;
+
+#[derive(Debug, Clone, Deserialize)]
+struct IssueLabel {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReproIssue {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: String,
+    url: String,
+    #[serde(default)]
+    labels: Vec<IssueLabel>,
+}
+
+#[derive(Debug, Clone)]
+struct ContextFile {
+    path: String,
+    excerpt: String,
+    truncated: bool,
+}
+
+/// Linkage between a GitHub issue, the scratch worktree branch created for it
+/// and the engine session that tries to reproduce and fix it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IssueReproSandbox {
+    id: String,
+    workspace_id: String,
+    sandbox_workspace_id: String,
+    sandbox_path: String,
+    issue_number: u64,
+    issue_title: String,
+    issue_url: String,
+    branch: String,
+    engine: EngineType,
+    #[serde(default)]
+    thread_id: Option<String>,
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    turn_id: Option<String>,
+    #[serde(default)]
+    context_files: Vec<String>,
+    created_at: u64,
+    updated_at: u64,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_millis(0))
+        .as_millis() as u64
+}
+
+fn is_safe_storage_id(value: &str) -> bool {
+    !value.is_empty()
+        && value != "."
+        && value != ".."
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn repro_store_path(workspace_id: &str) -> Result<PathBuf, String> {
+    if !is_safe_storage_id(workspace_id) {
+        return Err(format!("Invalid workspace id: {workspace_id}"));
+    }
+    Ok(app_paths::app_home_dir()?
+        .join(REPRO_SANDBOXES_DIRNAME)
+        .join(format!("{workspace_id}.json")))
+}
+
+fn read_repro_sandboxes(path: &Path) -> Result<Vec<IssueReproSandbox>, String> {
+    Ok(read_json_file::<Vec<IssueReproSandbox>>(path)?.unwrap_or_default())
+}
+
+fn write_repro_sandboxes(path: &Path, entries: &[IssueReproSandbox]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(entries)
+        .map_err(|error| format!("failed to serialize {}: {error}", path.display()))?;
+    write_string_atomically(path, &data)
+}
+
+fn update_repro_sandboxes<T>(
+    path: &Path,
+    op: impl FnOnce(&mut Vec<IssueReproSandbox>) -> Result<T, String>,
+) -> Result<T, String> {
+    with_storage_lock(path, || {
+        let mut entries = read_repro_sandboxes(path)?;
+        let result = op(&mut entries)?;
+        write_repro_sandboxes(path, &entries)?;
+        Ok(result)
+    })
+}
+
+/// First free `repro/issue-<n>[-k]` name, skipping names recorded in the
+/// store and branches that already exist in the repository.
+fn next_repro_branch_name(
+    issue_number: u64,
+    existing: &[IssueReproSandbox],
+    branch_exists: impl Fn(&str) -> bool,
+) -> String {
+    let base = format!("{REPRO_BRANCH_PREFIX}{issue_number}");
+    let taken = |candidate: &str| {
+        existing.iter().any(|entry| entry.branch == candidate) || branch_exists(candidate)
+    };
+    if !taken(&base) {
+        return base;
+    }
+    let mut index = 2;
+    loop {
+        let candidate = format!("{base}-{index}");
+        if !taken(&candidate) {
+            return candidate;
+        }
+        index += 1;
+    }
+}
+
+fn is_safe_relative_path(value: &str) -> bool {
+    let path = Path::new(value);
+    !value.is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Collects repo-relative file paths mentioned in issue text, e.g.
+/// `src/foo.rs`, `src/foo.rs:42` or `./lib/bar.ts`, in order of appearance.
+fn extract_issue_path_candidates(text: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    for raw in text.split(|ch: char| {
+        ch.is_whitespace()
+            || matches!(
+                ch,
+                '`' | '"' | '\'' | '(' | ')' | '[' | ']' | '<' | '>' | ','
+            )
+    }) {
+        let token = raw.trim_end_matches(['.', ':', ';', '!', '?']);
+        let token = token.strip_prefix("./").unwrap_or(token);
+        let token = match token.split_once(':') {
+            Some((path, rest)) if rest.chars().all(|ch| ch.is_ascii_digit() || ch == ':') => path,
+            Some(_) => continue,
+            None => token,
+        };
+        if token.contains("://") || !token.contains('/') {
+            continue;
+        }
+        let has_extension = Path::new(token)
+            .extension()
+            .and_then(|value| value.to_str())
+            .is_some_and(|value| !value.is_empty() && value.len() <= 8);
+        if !has_extension || !is_safe_relative_path(token) {
+            continue;
+        }
+        if !candidates.iter().any(|entry| entry == token) {
+            candidates.push(token.to_string());
+        }
+    }
+    candidates
+}
+
+fn read_context_excerpt(path: &Path) -> Option<(String, bool)> {
+    let bytes = std::fs::read(path).ok()?;
+    let limit = bytes.len().min(MAX_CONTEXT_FILE_BYTES);
+    let text = String::from_utf8_lossy(&bytes[..limit]);
+    let mut truncated = bytes.len() > MAX_CONTEXT_FILE_BYTES;
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.len() > MAX_CONTEXT_FILE_LINES {
+        lines.truncate(MAX_CONTEXT_FILE_LINES);
+        truncated = true;
+    }
+    Some((lines.join("\n"), truncated))
+}
+
+fn collect_context_files(root: &Path, issue: &ReproIssue) -> Vec<ContextFile> {
+    let text = format!("{}\n{}", issue.title, issue.body);
+    extract_issue_path_candidates(&text)
+        .into_iter()
+        .filter_map(|relative| {
+            let full_path = root.join(&relative);
+            if !full_path.is_file() {
+                return None;
+            }
+            let (excerpt, truncated) = read_context_excerpt(&full_path)?;
+            Some(ContextFile {
+                path: relative,
+                excerpt,
+                truncated,
+            })
+        })
+        .take(MAX_CONTEXT_FILES)
+        .collect()
+}
+
+fn build_repro_prompt(issue: &ReproIssue, branch: &str, context_files: &[ContextFile]) -> String {
+    let mut prompt = format!(
+        "You are working in a scratch worktree on branch `{branch}` created to reproduce and fix GitHub issue #{number}.\n\n\
+Goal:\n\
+1. Reproduce the problem described below (add a failing test or a minimal script when possible).\n\
+2. Identify the root cause.\n\
+3. Fix it and verify the reproduction now passes.\n\
+Keep changes focused on this issue.\n\n\
+## Issue #{number}: {title}\n{url}\n",
+        number = issue.number,
+        title = issue.title.trim(),
+        url = issue.url,
+    );
+    if !issue.labels.is_empty() {
+        let labels: Vec<&str> = issue
+            .labels
+            .iter()
+            .map(|label| label.name.as_str())
+            .collect();
+        prompt.push_str(&format!("Labels: {}\n", labels.join(", ")));
+    }
+    let body = issue.body.trim();
+    if body.is_empty() {
+        prompt.push_str("\n(The issue has no description.)\n");
+    } else {
+        let clipped: String = body.chars().take(MAX_ISSUE_BODY_CHARS).collect();
+        prompt.push('\n');
+        prompt.push_str(&clipped);
+        if clipped.len() < body.len() {
+            prompt.push_str("\n\n(Issue description truncated.)");
+        }
+        prompt.push('\n');
+    }
+    if !context_files.is_empty() {
+        prompt.push_str("\n## Related code referenced by the issue\n");
+        for file in context_files {
+            prompt.push_str(&format!(
+                "\n### {}\n```\n{}\n```\n",
+                file.path, file.excerpt
+            ));
+            if file.truncated {
+                prompt.push_str("(excerpt truncated)\n");
+            }
+        }
+    }
+    prompt
+}
+
+async fn fetch_repro_issue(repo_root: &Path, issue_number: u64) -> Result<ReproIssue, String> {
+    let repo_name = crate::git::github_repo_from_path(repo_root)?;
+    let issue_arg = issue_number.to_string();
+    let output = crate::utils::async_command("gh")
+        .args([
+            "issue",
+            "view",
+            &issue_arg,
+            "--repo",
+            &repo_name,
+            "--json",
+            "number,title,body,url,labels",
+        ])
+        .current_dir(repo_root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.trim();
+        if detail.is_empty() {
+            return Err(format!("Failed to load GitHub issue #{issue_number}."));
+        }
+        return Err(detail.to_string());
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|error| format!("Failed to parse GitHub issue #{issue_number}: {error}"))
+}
+
+/// Creates a scratch worktree for a GitHub issue, seeds an engine session with
+/// the issue text and referenced code, and records the linkage.
+#[tauri::command]
+pub async fn create_issue_repro_sandbox(
+    workspace_id: String,
+    issue_number: u64,
+    engine: Option<EngineType>,
+    model: Option<String>,
+    access_mode: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<IssueReproSandbox, String> {
+    if issue_number == 0 {
+        return Err("Issue number must be positive".to_string());
+    }
+    let store_path = repro_store_path(&workspace_id)?;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let issue = fetch_repro_issue(&repo_root, issue_number).await?;
+    let engine = match engine {
+        Some(engine) => engine,
+        None => state.engine_manager.get_active_engine().await,
+    };
+
+    let now = now_millis();
+    let mut sandbox = IssueReproSandbox {
+        id: Uuid::new_v4().to_string(),
+        workspace_id: workspace_id.clone(),
+        sandbox_workspace_id: String::new(),
+        sandbox_path: String::new(),
+        issue_number: issue.number,
+        issue_title: issue.title.clone(),
+        issue_url: issue.url.clone(),
+        branch: String::new(),
+        engine,
+        thread_id: None,
+        session_id: None,
+        turn_id: None,
+        context_files: Vec::new(),
+        created_at: now,
+        updated_at: now,
+    };
+    // Pick and record the branch under the store lock so concurrent requests
+    // for the same issue cannot both claim it.
+    let reservation = sandbox.clone();
+    sandbox.branch = update_repro_sandboxes(&store_path, move |entries| {
+        let repo = git2::Repository::open(&repo_root).ok();
+        let branch = next_repro_branch_name(issue_number, entries, |name| {
+            repo.as_ref()
+                .is_some_and(|repo| repo.find_branch(name, git2::BranchType::Local).is_ok())
+        });
+        entries.push(IssueReproSandbox {
+            branch: branch.clone(),
+            ..reservation
+        });
+        Ok(branch)
+    })?;
+    let worktree = match crate::workspaces::add_worktree(
+        workspace_id.clone(),
+        sandbox.branch.clone(),
+        None,
+        None,
+        Some(false),
+        state.clone(),
+        app.clone(),
+    )
+    .await
+    {
+        Ok(worktree) => worktree,
+        Err(error) => {
+            let sandbox_id = sandbox.id.clone();
+            let _ = update_repro_sandboxes(&store_path, move |entries| {
+                entries.retain(|entry| entry.id != sandbox_id);
+                Ok(())
+            });
+            return Err(error);
+        }
+    };
+
+    let context_files = collect_context_files(Path::new(&worktree.path), &issue);
+    let prompt = build_repro_prompt(&issue, &sandbox.branch, &context_files);
+    sandbox.sandbox_workspace_id = worktree.id.clone();
+    sandbox.sandbox_path = worktree.path.clone();
+    sandbox.context_files = context_files.iter().map(|file| file.path.clone()).collect();
+    sandbox.updated_at = now_millis();
+    // Persist before starting the engine so a failed send still leaves the
+    // worktree discoverable from the issue.
+    let record = sandbox.clone();
+    update_repro_sandboxes(&store_path, move |entries| {
+        if let Some(existing) = entries.iter_mut().find(|entry| entry.id == record.id) {
+            *existing = record;
+        }
+        Ok(())
+    })?;
+
+    let linkage =
+        engine::start_engine_turn(&worktree.id, engine, prompt, model, access_mode, state, app)
+            .await?;
+    sandbox.thread_id = linkage.thread_id;
+    sandbox.session_id = linkage.session_id;
+    sandbox.turn_id = linkage.turn_id;
+    sandbox.updated_at = now_millis();
+    let record = sandbox.clone();
+    update_repro_sandboxes(&store_path, move |entries| {
+        if let Some(existing) = entries.iter_mut().find(|entry| entry.id == record.id) {
+            *existing = record;
+        }
+        Ok(())
+    })?;
+    Ok(sandbox)
+}
+
+#[tauri::command]
+pub async fn list_issue_repro_sandboxes(
+    workspace_id: String,
+    issue_number: Option<u64>,
+) -> Result<Vec<IssueReproSandbox>, String> {
+    let store_path = repro_store_path(&workspace_id)?;
+    let mut entries = read_repro_sandboxes(&store_path)?;
+    // Reservations whose worktree is still being created.
+    entries.retain(|entry| !entry.sandbox_workspace_id.is_empty());
+    if let Some(issue_number) = issue_number {
+        entries.retain(|entry| entry.issue_number == issue_number);
+    }
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.updated_at));
+    Ok(entries)
+}
+
+/// Updates the engine identifiers once the frontend learns them (e.g. the
+/// Claude session id only arrives with the first streamed event).
+#[tauri::command]
+pub async fn link_issue_repro_sandbox_session(
+    workspace_id: String,
+    sandbox_id: String,
+    thread_id: Option<String>,
+    session_id: Option<String>,
+) -> Result<IssueReproSandbox, String> {
+    let store_path = repro_store_path(&workspace_id)?;
+    let normalize = |value: Option<String>| {
+        value
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+    };
+    let thread_id = normalize(thread_id);
+    let session_id = normalize(session_id);
+    update_repro_sandboxes(&store_path, move |entries| {
+        let entry = entries
+            .iter_mut()
+            .find(|entry| entry.id == sandbox_id)
+            .ok_or_else(|| format!("Repro sandbox not found: {sandbox_id}"))?;
+        if thread_id.is_some() {
+            entry.thread_id = thread_id;
+        }
+        if session_id.is_some() {
+            entry.session_id = session_id;
+        }
+        entry.updated_at = now_millis();
+        Ok(entry.clone())
+    })
+}
+
+/// Forgets a sandbox record. The worktree itself is removed through
+/// `remove_worktree` so the usual cleanup path stays in one place.
+#[tauri::command]
+pub async fn delete_issue_repro_sandbox(
+    workspace_id: String,
+    sandbox_id: String,
+) -> Result<bool, String> {
+    let store_path = repro_store_path(&workspace_id)?;
+    update_repro_sandboxes(&store_path, move |entries| {
+        let before = entries.len();
+        entries.retain(|entry| entry.id != sandbox_id);
+        Ok(entries.len() != before)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_repro_prompt, collect_context_files, extract_issue_path_candidates,
+        next_repro_branch_name, read_repro_sandboxes, update_repro_sandboxes, ContextFile,
+        IssueLabel, IssueReproSandbox, ReproIssue,
+    };
+    use crate::engine::EngineType;
+    use uuid::Uuid;
+
+    fn issue(body: &str) -> ReproIssue {
+        ReproIssue {
+            number: 42,
+            title: "Crash when opening src/app.rs".to_string(),
+            body: body.to_string(),
+            url: "https://github.com/acme/demo/issues/42".to_string(),
+            labels: vec![IssueLabel {
+                name: "bug".to_string(),
+            }],
+        }
+    }
+
+    fn sandbox(branch: &str) -> IssueReproSandbox {
+        IssueReproSandbox {
+            id: Uuid::new_v4().to_string(),
+            workspace_id: "ws-1".to_string(),
+            sandbox_workspace_id: "ws-2".to_string(),
+            sandbox_path: "/tmp/ws-2".to_string(),
+            issue_number: 42,
+            issue_title: "Crash".to_string(),
+            issue_url: "https://github.com/acme/demo/issues/42".to_string(),
+            branch: branch.to_string(),
+            engine: EngineType::Claude,
+            thread_id: None,
+            session_id: None,
+            turn_id: None,
+            context_files: Vec::new(),
+            created_at: 1,
+            updated_at: 1,
+        }
+    }
+
+    #[test]
+    fn extracts_repo_relative_paths_from_issue_text() {
+        let paths = extract_issue_path_candidates(
+            "Panics in `src/app.rs:120` and ./lib/util.ts, see https://x.dev/a/b.html or ../etc/passwd.txt (src/app.rs).",
+        );
+        assert_eq!(paths, vec!["src/app.rs", "lib/util.ts"]);
+    }
+
+    #[test]
+    fn picks_unique_branch_names_per_issue() {
+        assert_eq!(next_repro_branch_name(42, &[], |_| false), "repro/issue-42");
+        let existing = vec![sandbox("repro/issue-42"), sandbox("repro/issue-42-2")];
+        assert_eq!(
+            next_repro_branch_name(42, &existing, |_| false),
+            "repro/issue-42-3"
+        );
+    }
+
+    #[test]
+    fn skips_branches_that_already_exist_in_git() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        let repo = git2::Repository::init(&root).expect("init repo");
+        let signature = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let tree_id = repo.index().expect("index").write_tree().expect("tree");
+        let tree = repo.find_tree(tree_id).expect("find tree");
+        let commit_id = repo
+            .commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .expect("commit");
+        let commit = repo.find_commit(commit_id).expect("find commit");
+        repo.branch("repro/issue-42", &commit, false)
+            .expect("create branch");
+
+        let branch_exists = |name: &str| repo.find_branch(name, git2::BranchType::Local).is_ok();
+        assert_eq!(
+            next_repro_branch_name(42, &[], branch_exists),
+            "repro/issue-42-2"
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn prompt_includes_issue_and_code_context() {
+        let files = vec![ContextFile {
+            path: "src/app.rs".to_string(),
+            excerpt: "fn main() {}".to_string(),
+            truncated: true,
+        }];
+        let prompt = build_repro_prompt(&issue("Steps: run it"), "repro/issue-42", &files);
+        assert!(prompt.contains("GitHub issue #42"));
+        assert!(prompt.contains("repro/issue-42"));
+        assert!(prompt.contains("Labels: bug"));
+        assert!(prompt.contains("Steps: run it"));
+        assert!(prompt.contains("### src/app.rs"));
+        assert!(prompt.contains("(excerpt truncated)"));
+    }
+
+    #[test]
+    fn collects_only_existing_context_files() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("src")).expect("create temp dir");
+        std::fs::write(root.join("src/app.rs"), "fn main() {}\n").expect("write file");
+
+        let files = collect_context_files(&root, &issue("Also touches src/missing.rs"));
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "src/app.rs");
+        assert_eq!(files[0].excerpt, "fn main() {}");
+        assert!(!files[0].truncated);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn store_round_trips_records() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        let path = root.join("ws-1.json");
+        assert!(read_repro_sandboxes(&path).expect("read empty").is_empty());
+
+        let record = sandbox("repro/issue-42");
+        let inserted = record.clone();
+        update_repro_sandboxes(&path, move |entries| {
+            entries.push(inserted);
+            Ok(())
+        })
+        .expect("write store");
+        assert_eq!(
+            read_repro_sandboxes(&path).expect("read back"),
+            vec![record]
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
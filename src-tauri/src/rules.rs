@@ -44,6 +44,65 @@ pub(crate) fn append_prefix_rule(path: &Path, pattern: &[String]) -> Result<(),
     fs::write(path, updated).map_err(|err| err.to_string())
 }
 
+/// Patterns of the `decision = "allow"` prefix rules in `path`, in file
+/// order. A missing file has no rules.
+pub(crate) fn read_prefix_rules(path: &Path) -> Result<Vec<Vec<String>>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.to_string()),
+    };
+    Ok(allow_rule_blocks(&contents)
+        .into_iter()
+        .map(|block| block.pattern)
+        .collect())
+}
+
+/// Removes the allow prefix rules whose pattern is in `patterns` and
+/// returns how many were removed. Other rules and comments are kept as is.
+pub(crate) fn remove_prefix_rules(path: &Path, patterns: &[Vec<String>]) -> Result<usize, String> {
+    if patterns.is_empty() || !path.exists() {
+        return Ok(0);
+    }
+
+    let _lock = acquire_rules_lock(path)?;
+    let existing = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let removed: Vec<RuleBlock> = allow_rule_blocks(&existing)
+        .into_iter()
+        .filter(|block| patterns.contains(&block.pattern))
+        .collect();
+    if removed.is_empty() {
+        return Ok(0);
+    }
+
+    let mut kept: Vec<&str> = Vec::new();
+    for (index, line) in existing.lines().enumerate() {
+        // Drop the blank separator `append_prefix_rule` put before a block.
+        if removed.iter().any(|block| block.start == index)
+            && kept.last().is_some_and(|last| last.trim().is_empty())
+        {
+            kept.pop();
+        }
+        if removed
+            .iter()
+            .any(|block| (block.start..=block.end).contains(&index))
+        {
+            continue;
+        }
+        kept.push(line);
+    }
+    while kept.first().is_some_and(|line| line.trim().is_empty()) {
+        kept.remove(0);
+    }
+
+    let mut updated = kept.join("\n");
+    if !updated.is_empty() {
+        updated.push('\n');
+    }
+    fs::write(path, updated).map_err(|err| err.to_string())?;
+    Ok(removed.len())
+}
+
 struct RulesFileLock {
     path: PathBuf,
 }
@@ -148,6 +207,82 @@ fn rule_already_present(contents: &str, pattern: &[String]) -> bool {
     false
 }
 
+/// Line span (inclusive) and pattern of one allow `prefix_rule(...)` block.
+struct RuleBlock {
+    start: usize,
+    end: usize,
+    pattern: Vec<String>,
+}
+
+fn allow_rule_blocks(contents: &str) -> Vec<RuleBlock> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut pattern = None;
+    let mut decision_allows = false;
+
+    for (index, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("prefix_rule(") {
+            start = Some(index);
+            pattern = None;
+            decision_allows = false;
+            continue;
+        }
+        let Some(block_start) = start else {
+            continue;
+        };
+        if trimmed.starts_with("pattern") {
+            if let Some((_, value)) = trimmed.split_once('=') {
+                pattern = parse_string_list(value.trim().trim_end_matches(','));
+            }
+        } else if trimmed.starts_with("decision") {
+            if let Some((_, value)) = trimmed.split_once('=') {
+                let candidate = value.trim().trim_end_matches(',');
+                decision_allows = candidate.contains("\"allow\"") || candidate.contains("'allow'");
+            }
+        } else if trimmed.starts_with(')') {
+            if let (Some(pattern), true) = (pattern.take(), decision_allows) {
+                blocks.push(RuleBlock {
+                    start: block_start,
+                    end: index,
+                    pattern,
+                });
+            }
+            start = None;
+        }
+    }
+    blocks
+}
+
+/// Parses a `["a", 'b']` list of string literals, undoing `escape_string`.
+fn parse_string_list(value: &str) -> Option<Vec<String>> {
+    let inner = value.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let mut items = Vec::new();
+    let mut chars = inner.chars();
+    loop {
+        let quote = match chars.by_ref().find(|ch| !ch.is_whitespace() && *ch != ',') {
+            Some(quote @ ('"' | '\'')) => quote,
+            Some(_) => return None,
+            None => break,
+        };
+        let mut item = String::new();
+        loop {
+            match chars.next()? {
+                ch if ch == quote => break,
+                '\\' => item.push(match chars.next()? {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    other => other,
+                }),
+                ch => item.push(ch),
+            }
+        }
+        items.push(item);
+    }
+    (!items.is_empty()).then_some(items)
+}
+
 fn normalize_rule_value(value: &str) -> String {
     value.chars().filter(|ch| !ch.is_whitespace()).collect()
 }
@@ -160,3 +295,36 @@ fn escape_string(value: &str) -> String {
         .replace('\r', "\\r")
         .replace('\t', "\\t")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_only_the_named_allow_rules() {
+        let dir = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        let path = default_rules_path(&dir);
+        let quoted = vec!["echo".to_string(), "say \"hi\"".to_string()];
+        append_prefix_rule(&path, &["git".to_string(), "status".to_string()]).expect("append");
+        append_prefix_rule(&path, &quoted).expect("append");
+        let mut contents = fs::read_to_string(&path).expect("read");
+        contents
+            .push_str("\nprefix_rule(\n    pattern = ['rm'],\n    decision = \"forbidden\",\n)\n");
+        fs::write(&path, contents).expect("write");
+
+        assert_eq!(
+            read_prefix_rules(&path).expect("rules"),
+            vec![
+                vec!["git".to_string(), "status".to_string()],
+                quoted.clone()
+            ]
+        );
+        assert_eq!(remove_prefix_rules(&path, &[quoted]).expect("remove"), 1);
+        let remaining = fs::read_to_string(&path).expect("read");
+        assert!(remaining.contains("\"git\", \"status\""));
+        assert!(remaining.contains("['rm']"));
+        assert!(!remaining.contains("say"));
+        assert_eq!(read_prefix_rules(&path).expect("rules").len(), 1);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
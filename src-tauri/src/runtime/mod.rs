@@ -1096,6 +1096,24 @@ impl RuntimeManager {
             .collect()
     }
 
+    /// Returns `(engine, workspace_id, pid)` for every runtime with a known root process.
+    pub(crate) async fn tracked_runtime_processes(&self) -> Vec<(String, String, u32)> {
+        self.entries
+            .lock()
+            .await
+            .values()
+            .filter_map(|entry| {
+                entry.pid.map(|pid| {
+                    (
+                        normalize_engine(&entry.engine),
+                        entry.workspace_id.clone(),
+                        pid,
+                    )
+                })
+            })
+            .collect()
+    }
+
     pub(crate) async fn has_pending_acquire_for_engine(&self, engine: &str) -> bool {
         let prefix = format!("{}::", normalize_engine(engine));
         self.startup_gates
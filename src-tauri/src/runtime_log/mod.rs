@@ -896,6 +896,9 @@ pub(crate) async fn runtime_log_start(
             runtime_terminal_id.clone(),
             DEFAULT_TERMINAL_COLS,
             DEFAULT_TERMINAL_ROWS,
+            None,
+            None,
+            None,
             app.state::<AppState>(),
             app.clone(),
         )
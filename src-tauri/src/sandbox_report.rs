@@ -0,0 +1,783 @@
+//! Per-turn sandbox reports for engine-run commands.
+//!
+//! Command executions, file changes and approval requests seen on the
+//! app-server event stream are buffered per thread while a turn runs. When
+//! the turn ends they are checked for network access, writes outside the
+//! workspace and privilege escalation, and the resulting report is stored in
+//! `~/.ccgui/sandbox-reports/<workspace>.json` and emitted as
+//! `sandbox-report` for the turn timeline. The checks read the commands as
+//! issued, so they flag what was attempted rather than prove what happened.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::app_paths;
+use crate::backend::events::AppServerEvent;
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const REPORTS_DIR_NAME: &str = "sandbox-reports";
+const SANDBOX_REPORT_EVENT: &str = "sandbox-report";
+const MAX_REPORTS_PER_WORKSPACE: usize = 500;
+const MAX_FINDINGS_PER_KIND: usize = 100;
+/// Only the head of a command's output is kept for failure-message checks.
+const MAX_OUTPUT_SAMPLE_CHARS: usize = 4_000;
+
+const NETWORK_TOOLS: &[&str] = &[
+    "curl", "wget", "ssh", "scp", "sftp", "rsync", "nc", "ncat", "netcat", "telnet", "ftp", "ping",
+    "nslookup", "dig", "http", "https",
+];
+/// Package managers and VCS subcommands that reach the network.
+const NETWORK_SUBCOMMANDS: &[(&str, &[&str])] = &[
+    (
+        "git",
+        &["clone", "fetch", "pull", "push", "ls-remote", "submodule"],
+    ),
+    ("npm", &["install", "i", "ci", "add", "publish", "update"]),
+    ("pnpm", &["install", "i", "add", "update", "publish"]),
+    ("yarn", &["install", "add", "upgrade", "publish"]),
+    ("pip", &["install", "download"]),
+    ("pip3", &["install", "download"]),
+    ("cargo", &["install", "fetch", "publish", "update", "add"]),
+    ("go", &["get", "install", "mod"]),
+    ("gem", &["install"]),
+    ("brew", &["install", "update", "upgrade"]),
+    ("apt", &["install", "update", "upgrade"]),
+    ("apt-get", &["install", "update", "upgrade"]),
+    ("docker", &["pull", "push", "login"]),
+];
+const NETWORK_OUTPUT_HINTS: &[&str] = &[
+    "could not resolve host",
+    "name or service not known",
+    "temporary failure in name resolution",
+    "network is unreachable",
+    "connection refused",
+    "connection timed out",
+    "failed to connect to",
+];
+const ELEVATION_TOOLS: &[&str] = &["sudo", "su", "doas", "pkexec", "runas", "gsudo"];
+const SHELL_WRAPPERS: &[&str] = &["bash", "sh", "zsh", "dash"];
+/// Commands whose non-flag arguments are all written or removed.
+const WRITE_ALL_ARGS_TOOLS: &[&str] = &["touch", "mkdir", "rm", "rmdir", "truncate"];
+/// Commands whose last argument is the written destination.
+const WRITE_DEST_TOOLS: &[&str] = &["cp", "mv", "install", "ln", "rsync"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SandboxFinding {
+    #[serde(default)]
+    item_id: Option<String>,
+    #[serde(default)]
+    command: Option<String>,
+    detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SandboxReport {
+    workspace_id: String,
+    thread_id: String,
+    #[serde(default)]
+    turn_id: Option<String>,
+    completed_at_ms: i64,
+    commands_run: usize,
+    network_attempts: Vec<SandboxFinding>,
+    outside_workspace_writes: Vec<SandboxFinding>,
+    elevated_requests: Vec<SandboxFinding>,
+}
+
+impl SandboxReport {
+    fn is_empty(&self) -> bool {
+        self.commands_run == 0
+            && self.network_attempts.is_empty()
+            && self.outside_workspace_writes.is_empty()
+            && self.elevated_requests.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Observation {
+    Command {
+        item_id: Option<String>,
+        command: String,
+        cwd: Option<String>,
+        output: String,
+    },
+    FileChange {
+        item_id: Option<String>,
+        path: String,
+    },
+    Approval {
+        method: String,
+        command: Option<String>,
+        escalated: bool,
+    },
+}
+
+#[derive(Default)]
+struct PendingTurn {
+    turn_id: Option<String>,
+    observations: Vec<Observation>,
+}
+
+static PENDING_TURNS: OnceLock<Mutex<HashMap<String, PendingTurn>>> = OnceLock::new();
+
+fn pending_turns() -> std::sync::MutexGuard<'static, HashMap<String, PendingTurn>> {
+    let pending = PENDING_TURNS.get_or_init(|| Mutex::new(HashMap::new()));
+    match pending.lock() {
+        Ok(pending) => pending,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn turn_key(workspace_id: &str, thread_id: &str) -> String {
+    format!("{workspace_id}\u{0}{thread_id}")
+}
+
+fn reports_path(workspace_id: &str) -> Result<PathBuf, String> {
+    if workspace_id.is_empty()
+        || !workspace_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err("invalid workspace id".to_string());
+    }
+    Ok(app_paths::app_home_dir()?
+        .join(REPORTS_DIR_NAME)
+        .join(format!("{workspace_id}.json")))
+}
+
+fn read_reports(path: &Path) -> Result<Vec<SandboxReport>, String> {
+    Ok(read_json_file(path)?.unwrap_or_default())
+}
+
+fn params_string(params: &Value, pointers: &[&str]) -> Option<String> {
+    pointers.iter().find_map(|pointer| {
+        params
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// Command text of a `commandExecution` item or approval request; Codex
+/// sends a string or an argv array, Claude nests it in the tool input.
+pub(crate) fn command_text(value: &Value) -> Option<String> {
+    for pointer in ["/command", "/arguments/command", "/input/command", "/cmd"] {
+        match value.pointer(pointer) {
+            Some(Value::String(command)) if !command.trim().is_empty() => {
+                return Some(command.trim().to_string())
+            }
+            Some(Value::Array(parts)) if !parts.is_empty() => {
+                let parts: Vec<&str> = parts.iter().filter_map(Value::as_str).collect();
+                return Some(parts.join(" "));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn output_sample(item: &Value) -> String {
+    ["aggregatedOutput", "output", "error"]
+        .iter()
+        .filter_map(|key| item.get(*key).and_then(Value::as_str))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .chars()
+        .take(MAX_OUTPUT_SAMPLE_CHARS)
+        .collect()
+}
+
+fn observe_item(item: &Value) -> Vec<Observation> {
+    let item_id = item.get("id").and_then(Value::as_str).map(str::to_string);
+    match item.get("type").and_then(Value::as_str) {
+        Some("commandExecution") => command_text(item)
+            .map(|command| {
+                vec![Observation::Command {
+                    item_id,
+                    command,
+                    cwd: params_string(item, &["/cwd", "/arguments/cwd"]),
+                    output: output_sample(item),
+                }]
+            })
+            .unwrap_or_default(),
+        Some("fileChange") => {
+            let mut paths: Vec<String> = item
+                .get("changes")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|change| change.get("path").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect();
+            paths.extend(params_string(
+                item,
+                &[
+                    "/arguments/file_path",
+                    "/arguments/path",
+                    "/input/file_path",
+                ],
+            ));
+            paths
+                .into_iter()
+                .map(|path| Observation::FileChange {
+                    item_id: item_id.clone(),
+                    path,
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Splits a shell line into simple commands on `;`, `&&`, `||`, `|` and
+/// newlines, each as whitespace-separated words with quotes stripped.
+pub(crate) fn simple_commands(command: &str) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    let mut current = Vec::new();
+    for word in command.split_whitespace() {
+        let mut word = word;
+        let mut terminated = false;
+        for separator in ["&&", "||", ";", "|"] {
+            if word == separator {
+                word = "";
+                terminated = true;
+                break;
+            }
+            if let Some(stripped) = word.strip_suffix(separator) {
+                word = stripped;
+                terminated = true;
+                break;
+            }
+        }
+        let word = word.trim_matches(|ch| ch == '"' || ch == '\'' || ch == '(' || ch == ')');
+        if !word.is_empty() {
+            current.push(word.to_string());
+        }
+        if terminated && !current.is_empty() {
+            commands.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        commands.push(current);
+    }
+    commands
+}
+
+fn program_name(word: &str) -> &str {
+    let name = word.rsplit(['/', '\\']).next().unwrap_or(word);
+    name.strip_suffix(".exe").unwrap_or(name)
+}
+
+/// Splits off leading `VAR=value` assignments, `env`, `bash -c` style
+/// shell wrappers and elevation wrappers so the program actually run is
+/// first in the second slice.
+fn split_wrappers(words: &[String]) -> (&[String], &[String]) {
+    let mut index = 0;
+    while index < words.len() {
+        let word = &words[index];
+        let program = program_name(word);
+        let is_assignment = word.contains('=') && !word.starts_with('-') && !word.contains('/');
+        let wrapped_by_sudo = words[..index].iter().any(|word| word == "sudo");
+        if is_assignment || program == "env" || ELEVATION_TOOLS.contains(&program) {
+            index += 1;
+        } else if SHELL_WRAPPERS.contains(&program)
+            && words
+                .get(index + 1)
+                .is_some_and(|flag| flag.starts_with('-') && flag.ends_with('c'))
+        {
+            index += 2;
+        } else if index > 0 && wrapped_by_sudo && word.starts_with('-') {
+            index += 1;
+        } else {
+            break;
+        }
+    }
+    words.split_at(index)
+}
+
+pub(crate) fn effective_words(words: &[String]) -> &[String] {
+    split_wrappers(words).1
+}
+
+fn network_reason(words: &[String]) -> Option<String> {
+    let words = effective_words(words);
+    let program = program_name(words.first()?);
+    if NETWORK_TOOLS.contains(&program) {
+        return Some(format!("runs `{program}`"));
+    }
+    if let Some((_, subcommands)) = NETWORK_SUBCOMMANDS
+        .iter()
+        .find(|(tool, _)| *tool == program)
+    {
+        if let Some(subcommand) = words[1..]
+            .iter()
+            .find(|word| !word.starts_with('-'))
+            .filter(|word| subcommands.contains(&word.as_str()))
+        {
+            return Some(format!("runs `{program} {subcommand}`"));
+        }
+    }
+    words
+        .iter()
+        .find(|word| word.starts_with("http://") || word.starts_with("https://"))
+        .map(|url| format!("references {url}"))
+}
+
+fn network_findings(command: &str, output: &str) -> Vec<String> {
+    let mut reasons: Vec<String> = simple_commands(command)
+        .iter()
+        .filter_map(|words| network_reason(words))
+        .collect();
+    let output = output.to_lowercase();
+    if let Some(hint) = NETWORK_OUTPUT_HINTS
+        .iter()
+        .find(|hint| output.contains(**hint))
+    {
+        reasons.push(format!("output reports \"{hint}\""));
+    }
+    reasons
+}
+
+fn elevation_findings(command: &str) -> Vec<String> {
+    let mut reasons = Vec::new();
+    for words in simple_commands(command) {
+        let (wrappers, words) = split_wrappers(&words);
+        if let Some(tool) = wrappers
+            .iter()
+            .map(|word| program_name(word))
+            .find(|name| ELEVATION_TOOLS.contains(name))
+        {
+            reasons.push(format!("runs `{tool}`"));
+            continue;
+        }
+        let Some(program) = words.first().map(|word| program_name(word)) else {
+            continue;
+        };
+        let args = &words[1..];
+        if program == "chmod"
+            && args.iter().any(|arg| {
+                (arg.contains('+') && arg.contains('s')) || arg == "4755" || arg == "777"
+            })
+        {
+            reasons.push("changes permissions to setuid or world-writable".to_string());
+        } else if program == "chown" {
+            reasons.push("changes file ownership".to_string());
+        } else if args.iter().any(|arg| arg.eq_ignore_ascii_case("runas")) {
+            // PowerShell `Start-Process -Verb RunAs`.
+            reasons.push("starts an elevated process".to_string());
+        }
+    }
+    reasons
+}
+
+/// Paths a command writes, as written in the command.
+fn write_targets(command: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    for words in simple_commands(command) {
+        let mut iter = words.iter().peekable();
+        while let Some(word) = iter.next() {
+            let redirect = word
+                .trim_start_matches(|ch: char| ch.is_ascii_digit() || ch == '&')
+                .strip_prefix('>')
+                .map(|rest| rest.trim_start_matches('>'));
+            match redirect {
+                Some("") => {
+                    if let Some(target) = iter.next() {
+                        targets.push(target.clone());
+                    }
+                }
+                Some(target) if !target.starts_with('&') => targets.push(target.to_string()),
+                _ => {}
+            }
+        }
+        let words = effective_words(&words);
+        let Some(program) = words.first().map(|word| program_name(word)) else {
+            continue;
+        };
+        if program == "curl" || program == "wget" {
+            targets.extend(
+                words
+                    .windows(2)
+                    .filter(|pair| matches!(pair[0].as_str(), "-o" | "-O" | "--output"))
+                    .filter(|pair| program == "curl" || pair[0] != "-o")
+                    .map(|pair| pair[1].clone()),
+            );
+            continue;
+        }
+        let args: Vec<&String> = words[1..]
+            .iter()
+            .take_while(|word| !word.contains('>'))
+            .filter(|word| !word.starts_with('-'))
+            .collect();
+        if program == "tee" || WRITE_ALL_ARGS_TOOLS.contains(&program) {
+            targets.extend(args.iter().map(|arg| arg.to_string()));
+        } else if WRITE_DEST_TOOLS.contains(&program) && args.len() >= 2 {
+            targets.extend(args.last().map(|arg| arg.to_string()));
+        } else if program == "dd" {
+            targets.extend(
+                args.iter()
+                    .filter_map(|arg| arg.strip_prefix("of="))
+                    .map(str::to_string),
+            );
+        }
+    }
+    targets
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+/// Resolves `target` against `cwd` and reports whether it lands outside
+/// `root`. Device files are never counted.
+fn is_outside_workspace(target: &str, cwd: &Path, root: &Path, home: Option<&Path>) -> bool {
+    if target.starts_with("/dev/") || target == "nul" || target == "NUL" || target.contains('$') {
+        return false;
+    }
+    let resolved = if let Some(rest) = target.strip_prefix("~/").or((target == "~").then_some("")) {
+        match home {
+            Some(home) => home.join(rest),
+            None => return true,
+        }
+    } else if Path::new(target).is_absolute() {
+        PathBuf::from(target)
+    } else {
+        cwd.join(target)
+    };
+    !normalize(&resolved).starts_with(normalize(root))
+}
+
+fn build_report(
+    workspace_id: &str,
+    thread_id: &str,
+    pending: PendingTurn,
+    root: &Path,
+) -> SandboxReport {
+    let home = dirs::home_dir();
+    let mut report = SandboxReport {
+        workspace_id: workspace_id.to_string(),
+        thread_id: thread_id.to_string(),
+        turn_id: pending.turn_id,
+        completed_at_ms: chrono::Utc::now().timestamp_millis(),
+        ..SandboxReport::default()
+    };
+    for observation in pending.observations {
+        match observation {
+            Observation::Command {
+                item_id,
+                command,
+                cwd,
+                output,
+            } => {
+                report.commands_run += 1;
+                let finding = |detail: String| SandboxFinding {
+                    item_id: item_id.clone(),
+                    command: Some(command.clone()),
+                    detail,
+                };
+                let cwd = cwd.map(PathBuf::from).unwrap_or_else(|| root.to_path_buf());
+                report
+                    .network_attempts
+                    .extend(network_findings(&command, &output).into_iter().map(finding));
+                report
+                    .elevated_requests
+                    .extend(elevation_findings(&command).into_iter().map(finding));
+                report.outside_workspace_writes.extend(
+                    write_targets(&command)
+                        .into_iter()
+                        .filter(|target| is_outside_workspace(target, &cwd, root, home.as_deref()))
+                        .map(|target| finding(format!("writes {target}"))),
+                );
+            }
+            Observation::FileChange { item_id, path } => {
+                if is_outside_workspace(&path, root, root, home.as_deref()) {
+                    report.outside_workspace_writes.push(SandboxFinding {
+                        item_id,
+                        command: None,
+                        detail: format!("edits {path}"),
+                    });
+                }
+            }
+            Observation::Approval {
+                method,
+                command,
+                escalated,
+            } => {
+                let detail = if escalated {
+                    format!("{method} asked to run outside the sandbox")
+                } else {
+                    format!("{method} asked for approval")
+                };
+                report.elevated_requests.push(SandboxFinding {
+                    item_id: None,
+                    command,
+                    detail,
+                });
+            }
+        }
+    }
+    for findings in [
+        &mut report.network_attempts,
+        &mut report.outside_workspace_writes,
+        &mut report.elevated_requests,
+    ] {
+        findings.dedup();
+        findings.truncate(MAX_FINDINGS_PER_KIND);
+    }
+    report
+}
+
+fn store_report(path: &Path, report: &SandboxReport) -> Result<(), String> {
+    with_storage_lock(path, || {
+        let mut reports = read_reports(path)?;
+        reports.retain(|existing| {
+            !(existing.thread_id == report.thread_id
+                && report.turn_id.is_some()
+                && existing.turn_id == report.turn_id)
+        });
+        reports.push(report.clone());
+        if reports.len() > MAX_REPORTS_PER_WORKSPACE {
+            let excess = reports.len() - MAX_REPORTS_PER_WORKSPACE;
+            reports.drain(..excess);
+        }
+        let data = serde_json::to_string_pretty(&reports).map_err(|error| error.to_string())?;
+        write_string_atomically(path, &data)
+    })
+}
+
+fn is_escalation_request(params: &Value) -> bool {
+    params.to_string().to_ascii_lowercase().contains("escalat")
+}
+
+/// Buffers command, file-change and approval events per thread and files
+/// the report when the turn ends.
+pub(crate) fn observe_app_server_event(app: &AppHandle, event: &AppServerEvent) {
+    let Some(method) = event.message.get("method").and_then(Value::as_str) else {
+        return;
+    };
+    let Some(params) = event.message.get("params") else {
+        return;
+    };
+    let Some(thread_id) = params_string(params, &["/threadId", "/thread/id"]) else {
+        return;
+    };
+    let key = turn_key(&event.workspace_id, &thread_id);
+    let turn_id = params_string(params, &["/turnId", "/turn/id"]);
+    let observations = match method {
+        "turn/started" => {
+            pending_turns().insert(
+                key,
+                PendingTurn {
+                    turn_id,
+                    observations: Vec::new(),
+                },
+            );
+            return;
+        }
+        "turn/completed" | "turn/error" => {
+            let Some(mut pending) = pending_turns().remove(&key) else {
+                return;
+            };
+            pending.turn_id = pending.turn_id.or(turn_id);
+            spawn_report(app, event.workspace_id.clone(), thread_id, pending);
+            return;
+        }
+        "item/completed" => params.get("item").map(observe_item).unwrap_or_default(),
+        _ if method.ends_with("/requestApproval") => vec![Observation::Approval {
+            method: method.to_string(),
+            command: command_text(params),
+            escalated: is_escalation_request(params),
+        }],
+        _ => return,
+    };
+    if observations.is_empty() {
+        return;
+    }
+    pending_turns()
+        .entry(key)
+        .or_default()
+        .observations
+        .extend(observations);
+}
+
+fn spawn_report(app: &AppHandle, workspace_id: String, thread_id: String, pending: PendingTurn) {
+    if pending.observations.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let entry = {
+            let state = app.state::<AppState>();
+            let workspaces = state.workspaces.lock().await;
+            workspaces.get(&workspace_id).cloned()
+        };
+        let Some(entry) = entry else {
+            return;
+        };
+        let Ok(path) = reports_path(&workspace_id) else {
+            return;
+        };
+        let root = PathBuf::from(&entry.path);
+        let result = tokio::task::spawn_blocking(move || {
+            let report = build_report(&workspace_id, &thread_id, pending, &root);
+            if report.is_empty() {
+                return Ok(None);
+            }
+            store_report(&path, &report).map(|_| Some(report))
+        })
+        .await;
+        match result {
+            Ok(Ok(Some(report))) => {
+                let _ = app.emit(SANDBOX_REPORT_EVENT, report);
+            }
+            Ok(Err(error)) => log::warn!("[sandbox-report] failed to store report: {error}"),
+            _ => {}
+        }
+    });
+}
+
+/// Sandbox reports for a workspace, newest first, optionally narrowed to a
+/// thread and turn.
+#[tauri::command]
+pub(crate) async fn list_sandbox_reports(
+    workspace_id: String,
+    thread_id: Option<String>,
+    turn_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SandboxReport>, String> {
+    if !state.workspaces.lock().await.contains_key(&workspace_id) {
+        return Err("workspace not found".to_string());
+    }
+    let path = reports_path(&workspace_id)?;
+    let mut reports = tokio::task::spawn_blocking(move || read_reports(&path))
+        .await
+        .map_err(|error| error.to_string())??;
+    reports.retain(|report| {
+        thread_id
+            .as_deref()
+            .is_none_or(|thread_id| report.thread_id == thread_id)
+            && turn_id
+                .as_deref()
+                .is_none_or(|turn_id| report.turn_id.as_deref() == Some(turn_id))
+    });
+    reports.reverse();
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn flags_network_and_elevation_in_compound_commands() {
+        assert_eq!(
+            network_findings("cd web && npm install && curl -s https://x.dev/a", ""),
+            vec!["runs `npm install`", "runs `curl`"]
+        );
+        assert_eq!(
+            network_findings("cargo test", "error: Could not resolve host: crates.io"),
+            vec!["output reports \"could not resolve host\""]
+        );
+        assert!(network_findings("git status; ls -la", "").is_empty());
+
+        assert_eq!(
+            elevation_findings("sudo apt-get install jq"),
+            vec!["runs `sudo`"]
+        );
+        assert_eq!(
+            elevation_findings("chmod u+s ./bin/tool"),
+            vec!["changes permissions to setuid or world-writable"]
+        );
+        assert!(elevation_findings("chmod +x script.sh").is_empty());
+    }
+
+    #[test]
+    fn detects_writes_outside_the_workspace() {
+        let root = Path::new("/work/app");
+        let home = Path::new("/home/dev");
+        let outside = |command: &str| -> Vec<String> {
+            write_targets(command)
+                .into_iter()
+                .filter(|target| is_outside_workspace(target, root, root, Some(home)))
+                .collect()
+        };
+
+        assert_eq!(
+            outside("echo hi > notes.txt && echo x >> ~/.bashrc"),
+            vec!["~/.bashrc"]
+        );
+        assert_eq!(
+            outside("cp build/app /usr/local/bin/app"),
+            vec!["/usr/local/bin/app"]
+        );
+        assert_eq!(outside("tee -a ../shared.log"), vec!["../shared.log"]);
+        assert!(outside("cargo build 2>/dev/null > target/log.txt").is_empty());
+    }
+
+    #[test]
+    fn builds_reports_from_buffered_observations() {
+        let command = json!({
+            "id": "item-1",
+            "type": "commandExecution",
+            "command": ["bash", "-lc", "wget https://example.com/x -O /tmp/x"],
+            "aggregatedOutput": "",
+        });
+        let file_change = json!({
+            "id": "item-2",
+            "type": "fileChange",
+            "changes": [{ "path": "/etc/hosts" }, { "path": "src/main.rs" }],
+        });
+        let mut observations = observe_item(&command);
+        observations.extend(observe_item(&file_change));
+        observations.push(Observation::Approval {
+            method: "item/commandExecution/requestApproval".to_string(),
+            command: Some("rm -rf /".to_string()),
+            escalated: true,
+        });
+
+        let report = build_report(
+            "ws",
+            "thread-1",
+            PendingTurn {
+                turn_id: Some("turn-1".to_string()),
+                observations,
+            },
+            Path::new("/work/app"),
+        );
+
+        assert_eq!(report.commands_run, 1);
+        assert_eq!(report.network_attempts.len(), 1);
+        assert_eq!(
+            report
+                .outside_workspace_writes
+                .iter()
+                .map(|finding| finding.detail.as_str())
+                .collect::<Vec<_>>(),
+            vec!["writes /tmp/x", "edits /etc/hosts"]
+        );
+        assert_eq!(report.elevated_requests.len(), 1);
+        assert!(report.elevated_requests[0]
+            .detail
+            .contains("outside the sandbox"));
+    }
+}
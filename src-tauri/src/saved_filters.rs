@@ -0,0 +1,512 @@
+//! Named, persisted filters over sessions and commits.
+//!
+//! Definitions live in `~/.ccgui/saved-filters.json` and are evaluated on the
+//! backend: session filters run against the workspace session catalog, commit
+//! filters walk the git history of the workspace repository.
+
+use std::path::PathBuf;
+
+use git2::{DiffOptions, Repository, Sort};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use uuid::Uuid;
+
+use crate::app_paths;
+use crate::git_utils::{commit_to_entry, resolve_git_root};
+use crate::session_management::{
+    list_workspace_sessions_core, WorkspaceSessionCatalogEntry, WorkspaceSessionCatalogQuery,
+};
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+use crate::types::GitLogEntry;
+
+const SAVED_FILTERS_FILE: &str = "saved-filters.json";
+const DEFAULT_RESULT_LIMIT: usize = 50;
+const MAX_RESULT_LIMIT: usize = 500;
+const MAX_SCANNED_COMMITS: usize = 5_000;
+const MAX_SCANNED_SESSIONS: usize = 2_000;
+const SESSION_PAGE_SIZE: u32 = 200;
+/// Author placeholder resolved to the repository's configured user.
+const AUTHOR_ME: &str = "me";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SavedFilterTarget {
+    Sessions,
+    Commits,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SavedFilterCriteria {
+    /// Case-insensitive match on session titles or commit messages.
+    #[serde(default)]
+    pub(crate) keyword: Option<String>,
+    /// Session engine (`claude`, `codex`, ...).
+    #[serde(default)]
+    pub(crate) engine: Option<String>,
+    /// Session status: `active` (default), `archived` or `all`.
+    #[serde(default)]
+    pub(crate) status: Option<String>,
+    /// Commit author name or email; `me` means the configured git user.
+    #[serde(default)]
+    pub(crate) author: Option<String>,
+    /// Repo-relative path prefix. Commits must change it; sessions must be the
+    /// last AI author of a file under it.
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+    /// Only include items updated within the last N days.
+    #[serde(default)]
+    pub(crate) within_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SavedFilter {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) target: SavedFilterTarget,
+    /// Workspace the filter is pinned to; `None` evaluates against the
+    /// workspace passed at evaluation time.
+    #[serde(default)]
+    pub(crate) workspace_id: Option<String>,
+    #[serde(default)]
+    pub(crate) criteria: SavedFilterCriteria,
+    pub(crate) created_at: i64,
+    pub(crate) updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase", tag = "target")]
+pub(crate) enum SavedFilterResult {
+    Sessions {
+        items: Vec<WorkspaceSessionCatalogEntry>,
+        truncated: bool,
+    },
+    Commits {
+        items: Vec<GitLogEntry>,
+        truncated: bool,
+    },
+}
+
+fn saved_filters_path() -> Result<PathBuf, String> {
+    Ok(app_paths::app_home_dir()?.join(SAVED_FILTERS_FILE))
+}
+
+fn read_saved_filters() -> Result<Vec<SavedFilter>, String> {
+    Ok(read_json_file(&saved_filters_path()?)?.unwrap_or_default())
+}
+
+fn write_saved_filters(filters: &[SavedFilter]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(filters).map_err(|error| error.to_string())?;
+    write_string_atomically(&saved_filters_path()?, &data)
+}
+
+fn normalized(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn normalize_criteria(criteria: SavedFilterCriteria) -> SavedFilterCriteria {
+    SavedFilterCriteria {
+        keyword: normalized(&criteria.keyword),
+        engine: normalized(&criteria.engine).map(|value| value.to_lowercase()),
+        status: normalized(&criteria.status).map(|value| value.to_lowercase()),
+        author: normalized(&criteria.author),
+        path: normalized(&criteria.path)
+            .map(|value| value.replace('\\', "/").trim_matches('/').to_string())
+            .filter(|value| !value.is_empty()),
+        within_days: criteria.within_days.filter(|days| *days > 0),
+    }
+}
+
+fn cutoff_seconds(within_days: Option<u32>, now_seconds: i64) -> Option<i64> {
+    within_days.map(|days| now_seconds - i64::from(days) * 24 * 60 * 60)
+}
+
+fn path_matches_prefix(path: &str, prefix: &str) -> bool {
+    path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn resolve_author_filter(repo: &Repository, author: &str) -> Vec<String> {
+    if !author.eq_ignore_ascii_case(AUTHOR_ME) {
+        return vec![author.to_lowercase()];
+    }
+    let Ok(config) = repo.config() else {
+        return Vec::new();
+    };
+    ["user.email", "user.name"]
+        .iter()
+        .filter_map(|key| config.get_string(key).ok())
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+fn commit_touches_path(
+    repo: &Repository,
+    commit: &git2::Commit,
+    prefix: &str,
+) -> Result<bool, String> {
+    let tree = commit.tree().map_err(|error| error.to_string())?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().map_err(|error| error.to_string())?),
+        Err(_) => None,
+    };
+    let mut options = DiffOptions::new();
+    options.pathspec(prefix);
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))
+        .map_err(|error| error.to_string())?;
+    Ok(diff.deltas().any(|delta| {
+        [delta.new_file().path(), delta.old_file().path()]
+            .into_iter()
+            .flatten()
+            .any(|path| path_matches_prefix(&path.to_string_lossy().replace('\\', "/"), prefix))
+    }))
+}
+
+fn evaluate_commits(
+    repo: &Repository,
+    criteria: &SavedFilterCriteria,
+    limit: usize,
+    now_seconds: i64,
+) -> Result<(Vec<GitLogEntry>, bool), String> {
+    let authors = criteria
+        .author
+        .as_deref()
+        .map(|author| resolve_author_filter(repo, author));
+    if authors.as_ref().is_some_and(|authors| authors.is_empty()) {
+        return Err("git user.name / user.email is not configured for author \"me\"".to_string());
+    }
+    let keyword = criteria.keyword.as_deref().map(str::to_lowercase);
+    let cutoff = cutoff_seconds(criteria.within_days, now_seconds);
+
+    let mut revwalk = repo.revwalk().map_err(|error| error.to_string())?;
+    revwalk.push_head().map_err(|error| error.to_string())?;
+    revwalk
+        .set_sorting(Sort::TIME)
+        .map_err(|error| error.to_string())?;
+
+    let mut items = Vec::new();
+    for oid in revwalk.take(MAX_SCANNED_COMMITS) {
+        let oid = oid.map_err(|error| error.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|error| error.to_string())?;
+        if cutoff.is_some_and(|cutoff| commit.time().seconds() < cutoff) {
+            // Time-sorted walk: everything after this is older.
+            break;
+        }
+        if let Some(authors) = &authors {
+            let signature = commit.author();
+            let name = signature.name().unwrap_or("").to_lowercase();
+            let email = signature.email().unwrap_or("").to_lowercase();
+            if !authors
+                .iter()
+                .any(|author| name.contains(author) || email.contains(author))
+            {
+                continue;
+            }
+        }
+        if let Some(keyword) = &keyword {
+            if !commit
+                .message()
+                .unwrap_or("")
+                .to_lowercase()
+                .contains(keyword)
+            {
+                continue;
+            }
+        }
+        if let Some(prefix) = &criteria.path {
+            if !commit_touches_path(repo, &commit, prefix)? {
+                continue;
+            }
+        }
+        if items.len() == limit {
+            return Ok((items, true));
+        }
+        items.push(commit_to_entry(commit));
+    }
+    Ok((items, false))
+}
+
+async fn evaluate_sessions(
+    workspace_id: &str,
+    criteria: &SavedFilterCriteria,
+    limit: usize,
+    state: &State<'_, AppState>,
+) -> Result<(Vec<WorkspaceSessionCatalogEntry>, bool), String> {
+    let touching_threads = match &criteria.path {
+        Some(prefix) => Some(crate::file_attribution::threads_touching_path(
+            workspace_id,
+            prefix,
+        )?),
+        None => None,
+    };
+    // Catalog `updatedAt` values are epoch milliseconds.
+    let cutoff_ms = cutoff_seconds(criteria.within_days, chrono::Utc::now().timestamp())
+        .map(|seconds| seconds * 1000);
+    let query = WorkspaceSessionCatalogQuery {
+        keyword: criteria.keyword.clone(),
+        engine: criteria.engine.clone(),
+        status: criteria.status.clone(),
+    };
+
+    let mut items = Vec::new();
+    let mut scanned = 0usize;
+    let mut cursor = None;
+    loop {
+        let page = list_workspace_sessions_core(
+            &state.workspaces,
+            &state.sessions,
+            &state.engine_manager,
+            state.storage_path.as_path(),
+            workspace_id.to_string(),
+            Some(query.clone()),
+            cursor,
+            Some(SESSION_PAGE_SIZE),
+        )
+        .await?;
+        scanned += page.data.len();
+        for entry in page.data {
+            if cutoff_ms.is_some_and(|cutoff| entry.updated_at < cutoff) {
+                continue;
+            }
+            if let Some(threads) = &touching_threads {
+                let matches = threads.contains(&entry.session_id)
+                    || entry
+                        .canonical_session_id
+                        .as_ref()
+                        .is_some_and(|id| threads.contains(id));
+                if !matches {
+                    continue;
+                }
+            }
+            if items.len() == limit {
+                return Ok((items, true));
+            }
+            items.push(entry);
+        }
+        match page.next_cursor {
+            Some(next) if scanned < MAX_SCANNED_SESSIONS => cursor = Some(next),
+            Some(_) => return Ok((items, true)),
+            None => return Ok((items, false)),
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn list_saved_filters(
+    target: Option<SavedFilterTarget>,
+) -> Result<Vec<SavedFilter>, String> {
+    let mut filters = read_saved_filters()?;
+    if let Some(target) = target {
+        filters.retain(|filter| filter.target == target);
+    }
+    filters.sort_by_key(|filter| filter.name.to_lowercase());
+    Ok(filters)
+}
+
+/// Creates a filter, or replaces the definition of `id` when it exists.
+#[tauri::command]
+pub(crate) async fn save_saved_filter(
+    id: Option<String>,
+    name: String,
+    target: SavedFilterTarget,
+    workspace_id: Option<String>,
+    criteria: Option<SavedFilterCriteria>,
+) -> Result<SavedFilter, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Filter name is required".to_string());
+    }
+    let criteria = normalize_criteria(criteria.unwrap_or_default());
+    let workspace_id = normalized(&workspace_id);
+    let path = saved_filters_path()?;
+    with_storage_lock(&path, || {
+        let mut filters = read_saved_filters()?;
+        let now = chrono::Utc::now().timestamp_millis();
+        let existing = id
+            .as_deref()
+            .and_then(|id| filters.iter().position(|filter| filter.id == id));
+        let saved = match existing {
+            Some(index) => {
+                let filter = &mut filters[index];
+                filter.name = name;
+                filter.target = target;
+                filter.workspace_id = workspace_id;
+                filter.criteria = criteria;
+                filter.updated_at = now;
+                filter.clone()
+            }
+            None => {
+                let filter = SavedFilter {
+                    id: id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    name,
+                    target,
+                    workspace_id,
+                    criteria,
+                    created_at: now,
+                    updated_at: now,
+                };
+                filters.push(filter.clone());
+                filter
+            }
+        };
+        write_saved_filters(&filters)?;
+        Ok(saved)
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn delete_saved_filter(id: String) -> Result<bool, String> {
+    let path = saved_filters_path()?;
+    with_storage_lock(&path, || {
+        let mut filters = read_saved_filters()?;
+        let before = filters.len();
+        filters.retain(|filter| filter.id != id);
+        if filters.len() == before {
+            return Ok(false);
+        }
+        write_saved_filters(&filters)?;
+        Ok(true)
+    })
+}
+
+/// Evaluates a saved filter. `workspace_id` is used when the filter is not
+/// pinned to a workspace.
+#[tauri::command]
+pub(crate) async fn evaluate_saved_filter(
+    id: String,
+    workspace_id: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<SavedFilterResult, String> {
+    let filter = read_saved_filters()?
+        .into_iter()
+        .find(|filter| filter.id == id)
+        .ok_or("saved filter not found")?;
+    let workspace_id = filter
+        .workspace_id
+        .clone()
+        .or_else(|| normalized(&workspace_id))
+        .ok_or("workspace is required to evaluate this filter")?;
+    let limit = limit
+        .unwrap_or(DEFAULT_RESULT_LIMIT)
+        .clamp(1, MAX_RESULT_LIMIT);
+
+    match filter.target {
+        SavedFilterTarget::Sessions => {
+            let (items, truncated) =
+                evaluate_sessions(&workspace_id, &filter.criteria, limit, &state).await?;
+            Ok(SavedFilterResult::Sessions { items, truncated })
+        }
+        SavedFilterTarget::Commits => {
+            let entry = {
+                let workspaces = state.workspaces.lock().await;
+                workspaces
+                    .get(&workspace_id)
+                    .cloned()
+                    .ok_or("workspace not found")?
+            };
+            let repo_root = resolve_git_root(&entry)?;
+            let criteria = filter.criteria;
+            let (items, truncated) = tokio::task::spawn_blocking(move || {
+                let repo = Repository::open(&repo_root).map_err(|error| error.to_string())?;
+                evaluate_commits(&repo, &criteria, limit, chrono::Utc::now().timestamp())
+            })
+            .await
+            .map_err(|error| error.to_string())??;
+            Ok(SavedFilterResult::Commits { items, truncated })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_file(repo: &Repository, path: &str, content: &str, author: &str, time: i64) {
+        let root = repo.workdir().expect("workdir").to_path_buf();
+        let file = root.join(path);
+        std::fs::create_dir_all(file.parent().expect("parent")).expect("create dir");
+        std::fs::write(&file, content).expect("write file");
+        let mut index = repo.index().expect("index");
+        index.add_path(std::path::Path::new(path)).expect("add");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("tree");
+        let signature = git2::Signature::new(
+            author,
+            &format!("{author}@example.com"),
+            &git2::Time::new(time, 0),
+        )
+        .expect("signature");
+        let parents = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect::<Vec<_>>();
+        let parent_refs = parents.iter().collect::<Vec<_>>();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &format!("update {path}"),
+            &tree,
+            &parent_refs,
+        )
+        .expect("commit");
+    }
+
+    #[test]
+    fn filters_commits_by_author_path_and_age() {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).expect("create root");
+        let repo = Repository::init(&root).expect("init repo");
+        repo.config()
+            .expect("config")
+            .set_str("user.email", "alice@example.com")
+            .expect("set email");
+        let now = 1_700_000_000;
+        let day = 24 * 60 * 60;
+        commit_file(&repo, "src/git/mod.rs", "a", "alice", now - 10 * day);
+        commit_file(&repo, "src/git/log.rs", "b", "alice", now - day);
+        commit_file(&repo, "src/gitignore.rs", "c", "alice", now - day);
+        commit_file(&repo, "src/git/mod.rs", "d", "bob", now - day);
+
+        let criteria = normalize_criteria(SavedFilterCriteria {
+            author: Some("me".into()),
+            path: Some("/src/git/".into()),
+            within_days: Some(7),
+            ..Default::default()
+        });
+        let (items, truncated) = evaluate_commits(&repo, &criteria, 10, now).expect("evaluate");
+        assert!(!truncated);
+        assert_eq!(
+            items
+                .iter()
+                .map(|item| item.summary.as_str())
+                .collect::<Vec<_>>(),
+            vec!["update src/git/log.rs"]
+        );
+
+        let criteria = SavedFilterCriteria {
+            keyword: Some("MOD.RS".into()),
+            ..Default::default()
+        };
+        let (items, truncated) = evaluate_commits(&repo, &criteria, 1, now).expect("evaluate");
+        assert!(truncated);
+        assert_eq!(items[0].author, "bob");
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,402 @@
+//! Message-level bookmarks and automatic jump anchors for long sessions.
+//!
+//! Bookmarks are stored with the workspace session metadata. Anchors are
+//! derived from the session history whenever the list is requested: where a
+//! plan was produced, where a test run first failed, and the last file change.
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+
+use crate::session_issues::{
+    codex_thread_items, load_session_history, workspace_root, SessionHistory,
+};
+use crate::session_management::{
+    add_workspace_session_bookmark_core, read_workspace_session_bookmarks,
+    remove_workspace_session_bookmark_core, SessionBookmark,
+};
+use crate::state::AppState;
+
+const MAX_NOTE_CHARS: usize = 500;
+const MAX_LABEL_COMMAND_CHARS: usize = 60;
+const TEST_RUNNER_TOKENS: &[&str] = &[
+    "test", "tests", "pytest", "jest", "vitest", "mocha", "rspec", "phpunit", "ctest",
+];
+const TEST_FAILURE_MARKERS: &[&str] = &[
+    "test result: FAILED",
+    "FAILED",
+    "failures:",
+    "Tests failed",
+    "failing",
+    "AssertionError",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum SessionAnchorKind {
+    PlanProduced,
+    FirstFailingTest,
+    FinalDiff,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionAnchor {
+    kind: SessionAnchorKind,
+    message_id: String,
+    label: String,
+    message_index: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionBookmarkTarget {
+    #[serde(flatten)]
+    bookmark: SessionBookmark,
+    /// Position of the message in the session history; `None` when the
+    /// message is no longer there (for example after a rewind).
+    message_index: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionNavigation {
+    session_id: String,
+    bookmarks: Vec<SessionBookmarkTarget>,
+    anchors: Vec<SessionAnchor>,
+    message_count: usize,
+}
+
+/// One history entry reduced to what anchor detection needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HistoryMarker {
+    Plan,
+    Command {
+        command: String,
+        failed: bool,
+    },
+    /// Output of an earlier command (Claude reports results separately).
+    CommandResult {
+        command_id: String,
+        failed: bool,
+    },
+    FileChange(String),
+    Other,
+}
+
+fn string_field<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| value.get(*key).and_then(Value::as_str))
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+fn is_test_command(command: &str) -> bool {
+    command
+        .to_ascii_lowercase()
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .any(|token| TEST_RUNNER_TOKENS.contains(&token))
+}
+
+fn looks_like_test_failure(text: &str) -> bool {
+    TEST_FAILURE_MARKERS
+        .iter()
+        .any(|marker| text.contains(marker))
+}
+
+fn history_message_markers(messages: &[Value]) -> Vec<(String, HistoryMarker)> {
+    messages
+        .iter()
+        .map(|message| {
+            let id = string_field(message, &["id"]).unwrap_or("").to_string();
+            if string_field(message, &["kind"]) != Some("tool") {
+                return (id, HistoryMarker::Other);
+            }
+            let tool_type = string_field(message, &["toolType"])
+                .unwrap_or("")
+                .to_ascii_lowercase();
+            let input = message.get("toolInput").unwrap_or(&Value::Null);
+            let marker = if tool_type.contains("exitplanmode") || tool_type == "plan" {
+                HistoryMarker::Plan
+            } else if let Some(command) = string_field(input, &["command", "cmd"]) {
+                HistoryMarker::Command {
+                    command: command.to_string(),
+                    failed: false,
+                }
+            } else if let Some(command_id) = id.strip_suffix("-result") {
+                let text = string_field(message, &["text"]).unwrap_or("");
+                HistoryMarker::CommandResult {
+                    command_id: command_id.to_string(),
+                    failed: tool_type == "error" || looks_like_test_failure(text),
+                }
+            } else if ["edit", "write", "replace", "patch"]
+                .iter()
+                .any(|marker| tool_type.contains(marker))
+            {
+                string_field(input, &["file_path", "filePath", "path"])
+                    .map(|path| HistoryMarker::FileChange(path.to_string()))
+                    .unwrap_or(HistoryMarker::Other)
+            } else {
+                HistoryMarker::Other
+            };
+            (id, marker)
+        })
+        .collect()
+}
+
+fn codex_thread_markers(response: &Value) -> Vec<(String, HistoryMarker)> {
+    codex_thread_items(response)
+        .map(|item| {
+            let id = string_field(item, &["id"]).unwrap_or("").to_string();
+            let marker = match item.get("type").and_then(Value::as_str).unwrap_or("") {
+                "plan" => HistoryMarker::Plan,
+                "commandExecution" => HistoryMarker::Command {
+                    command: string_field(item, &["command"]).unwrap_or("").to_string(),
+                    failed: item.get("status").and_then(Value::as_str) == Some("failed")
+                        || item
+                            .get("exitCode")
+                            .and_then(Value::as_i64)
+                            .is_some_and(|code| code != 0),
+                },
+                "fileChange" => item
+                    .get("changes")
+                    .and_then(Value::as_array)
+                    .and_then(|changes| changes.first())
+                    .and_then(|change| string_field(change, &["path"]))
+                    .map(|path| HistoryMarker::FileChange(path.to_string()))
+                    .unwrap_or(HistoryMarker::Other),
+                _ => HistoryMarker::Other,
+            };
+            (id, marker)
+        })
+        .collect()
+}
+
+fn short_command(command: &str) -> String {
+    let line = command.lines().next().unwrap_or("").trim();
+    if line.chars().count() <= MAX_LABEL_COMMAND_CHARS {
+        return line.to_string();
+    }
+    let mut shortened = line
+        .chars()
+        .take(MAX_LABEL_COMMAND_CHARS)
+        .collect::<String>();
+    shortened.push('…');
+    shortened
+}
+
+fn detect_anchors(markers: &[(String, HistoryMarker)]) -> Vec<SessionAnchor> {
+    let index_of = |message_id: &str| markers.iter().position(|(id, _)| id == message_id);
+    let mut anchors = Vec::new();
+
+    if let Some(index) = markers
+        .iter()
+        .position(|(id, marker)| !id.is_empty() && *marker == HistoryMarker::Plan)
+    {
+        anchors.push(SessionAnchor {
+            kind: SessionAnchorKind::PlanProduced,
+            message_id: markers[index].0.clone(),
+            label: "Plan produced".to_string(),
+            message_index: index,
+        });
+    }
+
+    let first_failing_test = markers
+        .iter()
+        .enumerate()
+        .find_map(|(index, (id, marker))| match marker {
+            HistoryMarker::Command { command, failed } if *failed && is_test_command(command) => {
+                Some((index, id.clone(), command.clone()))
+            }
+            HistoryMarker::CommandResult { command_id, failed } if *failed => {
+                let command_index = index_of(command_id)?;
+                match &markers[command_index].1 {
+                    HistoryMarker::Command { command, .. } if is_test_command(command) => {
+                        Some((command_index, command_id.clone(), command.clone()))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .filter(|(_, id, _)| !id.is_empty());
+    if let Some((index, message_id, command)) = first_failing_test {
+        anchors.push(SessionAnchor {
+            kind: SessionAnchorKind::FirstFailingTest,
+            message_id,
+            label: format!("First failing test: {}", short_command(&command)),
+            message_index: index,
+        });
+    }
+
+    if let Some((index, (message_id, path))) =
+        markers
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, (id, marker))| match marker {
+                HistoryMarker::FileChange(path) if !id.is_empty() => Some((index, (id, path))),
+                _ => None,
+            })
+    {
+        anchors.push(SessionAnchor {
+            kind: SessionAnchorKind::FinalDiff,
+            message_id: message_id.clone(),
+            label: format!("Final diff: {path}"),
+            message_index: index,
+        });
+    }
+
+    anchors.sort_by_key(|anchor| anchor.message_index);
+    anchors
+}
+
+fn normalize_required(value: String, field: &str) -> Result<String, String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(format!("{field} is required"));
+    }
+    Ok(value)
+}
+
+/// Bookmarks a message of a session (catalog id, e.g. `claude:<id>`).
+#[tauri::command]
+pub(crate) async fn bookmark_add(
+    workspace_id: String,
+    session_id: String,
+    message_id: String,
+    note: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SessionBookmark, String> {
+    let session_id = normalize_required(session_id, "session_id")?;
+    let message_id = normalize_required(message_id, "message_id")?;
+    let note = note
+        .map(|note| note.trim().chars().take(MAX_NOTE_CHARS).collect::<String>())
+        .filter(|note| !note.is_empty());
+    add_workspace_session_bookmark_core(
+        &state.storage_path,
+        &workspace_id,
+        &session_id,
+        SessionBookmark {
+            id: uuid::Uuid::new_v4().to_string(),
+            message_id,
+            note,
+            created_at: chrono::Utc::now().timestamp_millis(),
+        },
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn bookmark_remove(
+    workspace_id: String,
+    session_id: String,
+    bookmark_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let session_id = normalize_required(session_id, "session_id")?;
+    remove_workspace_session_bookmark_core(
+        &state.storage_path,
+        &workspace_id,
+        &session_id,
+        bookmark_id.trim(),
+    )
+}
+
+/// Lists bookmarks and automatic anchors of a session with the message
+/// positions the UI jumps to.
+#[tauri::command]
+pub(crate) async fn bookmark_list(
+    workspace_id: String,
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<SessionNavigation, String> {
+    let session_id = normalize_required(session_id, "session_id")?;
+    let bookmarks =
+        read_workspace_session_bookmarks(&state.storage_path, &workspace_id, &session_id)?;
+    let workspace_path = workspace_root(&state, &workspace_id).await?;
+    let markers =
+        match load_session_history(&state, &workspace_id, &workspace_path, &session_id).await {
+            Ok((_, SessionHistory::Messages(messages))) => history_message_markers(&messages),
+            Ok((_, SessionHistory::CodexThread(response))) => codex_thread_markers(&response),
+            Err(error) => {
+                log::warn!(
+                    "[session_bookmarks] history unavailable for {}: {}",
+                    session_id,
+                    error
+                );
+                Vec::new()
+            }
+        };
+
+    let mut bookmarks = bookmarks
+        .into_iter()
+        .map(|bookmark| SessionBookmarkTarget {
+            message_index: markers
+                .iter()
+                .position(|(id, _)| *id == bookmark.message_id),
+            bookmark,
+        })
+        .collect::<Vec<_>>();
+    bookmarks.sort_by_key(|target| (target.message_index.is_none(), target.message_index));
+    Ok(SessionNavigation {
+        session_id,
+        anchors: detect_anchors(&markers),
+        message_count: markers.len(),
+        bookmarks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn detects_anchors_in_claude_history() {
+        let messages = vec![
+            json!({ "id": "u1", "role": "user", "kind": "message", "text": "fix login" }),
+            json!({ "id": "p1", "role": "assistant", "kind": "tool", "toolType": "ExitPlanMode", "text": "" }),
+            json!({ "id": "b1", "role": "assistant", "kind": "tool", "toolType": "Bash", "text": "", "toolInput": { "command": "ls" } }),
+            json!({ "id": "b1-result", "role": "assistant", "kind": "tool", "toolType": "error", "text": "no such file" }),
+            json!({ "id": "b2", "role": "assistant", "kind": "tool", "toolType": "Bash", "text": "", "toolInput": { "command": "cargo test auth" } }),
+            json!({ "id": "b2-result", "role": "assistant", "kind": "tool", "toolType": "result", "text": "test result: FAILED. 1 passed; 1 failed" }),
+            json!({ "id": "e1", "role": "assistant", "kind": "tool", "toolType": "Edit", "text": "", "toolInput": { "file_path": "src/a.rs" } }),
+            json!({ "id": "e2", "role": "assistant", "kind": "tool", "toolType": "Write", "text": "", "toolInput": { "file_path": "src/b.rs" } }),
+        ];
+        let anchors = detect_anchors(&history_message_markers(&messages));
+        assert_eq!(
+            anchors
+                .iter()
+                .map(|anchor| (
+                    anchor.kind,
+                    anchor.message_id.as_str(),
+                    anchor.message_index
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (SessionAnchorKind::PlanProduced, "p1", 1),
+                (SessionAnchorKind::FirstFailingTest, "b2", 4),
+                (SessionAnchorKind::FinalDiff, "e2", 7),
+            ]
+        );
+        assert_eq!(anchors[1].label, "First failing test: cargo test auth");
+    }
+
+    #[test]
+    fn detects_anchors_in_codex_threads() {
+        let response = json!({
+            "result": { "thread": { "turns": [{ "items": [
+                { "id": "i1", "type": "userMessage", "content": [] },
+                { "id": "i2", "type": "commandExecution", "command": "npm run build", "exitCode": 1 },
+                { "id": "i3", "type": "commandExecution", "command": "npm run test:unit", "exitCode": 1 },
+                { "id": "i4", "type": "fileChange", "changes": [{ "path": "src/app.ts" }] }
+            ] }] } }
+        });
+        let anchors = detect_anchors(&codex_thread_markers(&response));
+        assert_eq!(anchors.len(), 2);
+        assert_eq!(anchors[0].kind, SessionAnchorKind::FirstFailingTest);
+        assert_eq!(anchors[0].message_id, "i3");
+        assert_eq!(anchors[1].label, "Final diff: src/app.ts");
+        assert!(detect_anchors(&[]).is_empty());
+    }
+}
@@ -0,0 +1,484 @@
+//! Named checkpoints within a session.
+//!
+//! A checkpoint records where the conversation stands (the number of user
+//! messages for Codex threads, the last transcript entry for Claude
+//! sessions) and, in git workspaces, snapshots the working tree under
+//! `refs/mossx/checkpoints/`. Restoring rewinds the conversation to that
+//! point through the engines' existing rewind/fork paths and can put the
+//! files changed since back as they were. Checkpoints are stored in
+//! `~/.ccgui/session-checkpoints/<workspace>.json`.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, State};
+
+use crate::app_paths;
+use crate::engine::claude_history;
+use crate::engine::claude_history_rewind;
+use crate::engine::EngineType;
+use crate::git_utils::{path_has_git_repository_marker, resolve_git_root};
+use crate::shared::codex_core;
+use crate::shared::workspace_read_only::ensure_workspace_writable;
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+use crate::types::WorkspaceEntry;
+
+const CHECKPOINTS_DIR_NAME: &str = "session-checkpoints";
+const MAX_CHECKPOINTS_PER_WORKSPACE: usize = 200;
+const MAX_CHECKPOINT_NAME_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionCheckpoint {
+    id: String,
+    name: String,
+    workspace_id: String,
+    thread_id: String,
+    engine: String,
+    /// Codex: user messages in the thread when the checkpoint was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    user_message_count: Option<usize>,
+    /// Claude: uuid of the last transcript entry when the checkpoint was
+    /// made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    transcript_cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    snapshot_sha: Option<String>,
+    /// Why no working tree snapshot was taken; the checkpoint then only
+    /// restores the conversation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    snapshot_error: Option<String>,
+    created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CheckpointRestoreResult {
+    checkpoint: SessionCheckpoint,
+    /// Thread to continue in; differs from the checkpoint's thread when the
+    /// conversation was rewound.
+    thread_id: String,
+    rewound: bool,
+    restored_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CheckpointEngine {
+    Codex,
+    Claude { session_id: String },
+}
+
+fn checkpoint_engine(thread_id: &str) -> Result<CheckpointEngine, String> {
+    if let Some(session_id) = thread_id.strip_prefix("claude:") {
+        return Ok(CheckpointEngine::Claude {
+            session_id: session_id.to_string(),
+        });
+    }
+    match thread_id.split_once(':') {
+        Some((engine, _)) => Err(format!(
+            "Checkpoints are not supported for {engine} sessions"
+        )),
+        None => Ok(CheckpointEngine::Codex),
+    }
+}
+
+fn normalize_checkpoint_name(name: &str) -> Result<String, String> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err("Checkpoint name is required".to_string());
+    }
+    Ok(name.chars().take(MAX_CHECKPOINT_NAME_CHARS).collect())
+}
+
+fn checkpoints_path(workspace_id: &str) -> Result<PathBuf, String> {
+    if workspace_id.is_empty()
+        || !workspace_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err("invalid workspace id".to_string());
+    }
+    Ok(app_paths::app_home_dir()?
+        .join(CHECKPOINTS_DIR_NAME)
+        .join(format!("{workspace_id}.json")))
+}
+
+fn read_checkpoints(path: &Path) -> Result<Vec<SessionCheckpoint>, String> {
+    Ok(read_json_file(path)?.unwrap_or_default())
+}
+
+fn mutate_checkpoints<T>(
+    path: &Path,
+    mutate: impl FnOnce(&mut Vec<SessionCheckpoint>) -> T,
+) -> Result<T, String> {
+    with_storage_lock(path, || {
+        let mut checkpoints = read_checkpoints(path)?;
+        let result = mutate(&mut checkpoints);
+        let data = serde_json::to_string_pretty(&checkpoints).map_err(|error| error.to_string())?;
+        write_string_atomically(path, &data)?;
+        Ok(result)
+    })
+}
+
+/// After a rewind, checkpoints made up to the restored one also exist in
+/// the rewound thread, so they follow it; later ones stay with the old
+/// thread.
+fn carry_checkpoints_to_thread(
+    checkpoints: &mut [SessionCheckpoint],
+    restored: &SessionCheckpoint,
+    new_thread_id: &str,
+) {
+    for checkpoint in checkpoints.iter_mut() {
+        if checkpoint.thread_id == restored.thread_id
+            && checkpoint.created_at <= restored.created_at
+        {
+            checkpoint.thread_id = new_thread_id.to_string();
+        }
+    }
+}
+
+/// Number of user messages in a Codex thread; rewinding to this ordinal
+/// later drops everything sent after now.
+async fn codex_user_message_count(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Result<usize, String> {
+    let response = codex_core::resume_thread_core(
+        &state.sessions,
+        workspace_id.to_string(),
+        thread_id.to_string(),
+    )
+    .await?;
+    Ok(crate::codex::rewind::collect_user_messages_from_thread(&response).len())
+}
+
+async fn workspace_entry(state: &AppState, workspace_id: &str) -> Result<WorkspaceEntry, String> {
+    state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not found".to_string())
+}
+
+fn workspace_repo_root(entry: &WorkspaceEntry) -> Result<PathBuf, String> {
+    let root = resolve_git_root(entry)?;
+    if !path_has_git_repository_marker(&root) {
+        return Err("Workspace is not a git repository".to_string());
+    }
+    Ok(root)
+}
+
+/// Records the conversation position of `thread_id` and a snapshot of the
+/// working tree under `name`.
+#[tauri::command]
+pub(crate) async fn checkpoint_create(
+    workspace_id: String,
+    thread_id: String,
+    name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SessionCheckpoint, String> {
+    let name = normalize_checkpoint_name(&name)?;
+    let thread_id = thread_id.trim().to_string();
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let path = checkpoints_path(&workspace_id)?;
+
+    let mut checkpoint = SessionCheckpoint {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        workspace_id: workspace_id.clone(),
+        thread_id: thread_id.clone(),
+        engine: String::new(),
+        user_message_count: None,
+        transcript_cursor: None,
+        snapshot_sha: None,
+        snapshot_error: None,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+    match checkpoint_engine(&thread_id)? {
+        CheckpointEngine::Codex => {
+            crate::codex::ensure_codex_session(&workspace_id, &state, &app).await?;
+            checkpoint.engine = "codex".to_string();
+            checkpoint.user_message_count =
+                Some(codex_user_message_count(&state, &workspace_id, &thread_id).await?);
+        }
+        CheckpointEngine::Claude { session_id } => {
+            let config = state
+                .engine_manager
+                .get_engine_config(EngineType::Claude)
+                .await;
+            checkpoint.engine = "claude".to_string();
+            checkpoint.transcript_cursor =
+                claude_history_rewind::claude_session_cursor_with_config(
+                    Path::new(&entry.path),
+                    &session_id,
+                    config.as_ref(),
+                )
+                .await?;
+        }
+    }
+
+    let message = format!("Checkpoint: {}\n\nThread: {}\n", checkpoint.name, thread_id);
+    let snapshot = match workspace_repo_root(&entry) {
+        Ok(root) => crate::git::snapshot_working_tree(&root, &checkpoint.id, &message).await,
+        Err(error) => Err(error),
+    };
+    match snapshot {
+        Ok(sha) => checkpoint.snapshot_sha = Some(sha),
+        Err(error) => {
+            log::warn!("[checkpoints] no working tree snapshot for {workspace_id}: {error}");
+            checkpoint.snapshot_error = Some(error);
+        }
+    }
+
+    let stored = checkpoint.clone();
+    let evicted = mutate_checkpoints(&path, move |checkpoints| {
+        checkpoints.push(stored);
+        let excess = checkpoints
+            .len()
+            .saturating_sub(MAX_CHECKPOINTS_PER_WORKSPACE);
+        checkpoints.drain(..excess).collect::<Vec<_>>()
+    })?;
+    if let Ok(root) = workspace_repo_root(&entry) {
+        for old in evicted {
+            let _ = crate::git::delete_working_tree_snapshot(&root, &old.id);
+        }
+    }
+    Ok(checkpoint)
+}
+
+/// Checkpoints of a workspace, newest first, optionally for one thread.
+#[tauri::command]
+pub(crate) async fn checkpoint_list(
+    workspace_id: String,
+    thread_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SessionCheckpoint>, String> {
+    workspace_entry(&state, &workspace_id).await?;
+    let mut checkpoints = read_checkpoints(&checkpoints_path(&workspace_id)?)?;
+    checkpoints.retain(|checkpoint| {
+        thread_id
+            .as_deref()
+            .is_none_or(|thread_id| checkpoint.thread_id == thread_id)
+    });
+    checkpoints.reverse();
+    Ok(checkpoints)
+}
+
+/// Rewinds the checkpoint's conversation to where it was when the
+/// checkpoint was made and, with `restoreFiles`, restores the files changed
+/// since from the snapshot.
+#[tauri::command]
+pub(crate) async fn checkpoint_restore(
+    workspace_id: String,
+    checkpoint_id: String,
+    restore_files: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CheckpointRestoreResult, String> {
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let path = checkpoints_path(&workspace_id)?;
+    let checkpoint = read_checkpoints(&path)?
+        .into_iter()
+        .find(|checkpoint| checkpoint.id == checkpoint_id)
+        .ok_or_else(|| format!("Checkpoint not found: {checkpoint_id}"))?;
+    let restore_files = restore_files.unwrap_or(false);
+    let repo_root = if restore_files {
+        ensure_workspace_writable(&entry, "checkpoint_restore")?;
+        let Some(snapshot_sha) = checkpoint.snapshot_sha.clone() else {
+            return Err(format!(
+                "Checkpoint \"{}\" has no file snapshot: {}",
+                checkpoint.name,
+                checkpoint
+                    .snapshot_error
+                    .as_deref()
+                    .unwrap_or("snapshot unavailable")
+            ));
+        };
+        Some((workspace_repo_root(&entry)?, snapshot_sha))
+    } else {
+        None
+    };
+
+    let mut thread_id = checkpoint.thread_id.clone();
+    match checkpoint_engine(&checkpoint.thread_id)? {
+        CheckpointEngine::Codex => {
+            let target = checkpoint.user_message_count.unwrap_or(0);
+            crate::codex::ensure_codex_session(&workspace_id, &state, &app).await?;
+            let current =
+                codex_user_message_count(&state, &workspace_id, &checkpoint.thread_id).await?;
+            if current > target {
+                if target == 0 {
+                    return Err(
+                        "This checkpoint was made before the first message; start a new conversation instead."
+                            .to_string(),
+                    );
+                }
+                let response = crate::codex::rewind_codex_thread(
+                    workspace_id.clone(),
+                    checkpoint.thread_id.clone(),
+                    None,
+                    target as u32,
+                    None,
+                    None,
+                    None,
+                    state.clone(),
+                    app.clone(),
+                )
+                .await?;
+                thread_id = response
+                    .pointer("/thread/id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .ok_or_else(|| "codex rewind response missing thread id".to_string())?;
+            }
+        }
+        CheckpointEngine::Claude { session_id } => {
+            let config = state
+                .engine_manager
+                .get_engine_config(EngineType::Claude)
+                .await;
+            let workspace_path = Path::new(&entry.path);
+            let fork_from = claude_history_rewind::claude_user_prompt_after_cursor_with_config(
+                workspace_path,
+                &session_id,
+                checkpoint.transcript_cursor.as_deref(),
+                config.as_ref(),
+            )
+            .await?;
+            if let Some(message_id) = fork_from {
+                let forked_session_id =
+                    claude_history::fork_claude_session_from_message_with_config(
+                        workspace_path,
+                        &session_id,
+                        &message_id,
+                        config.as_ref(),
+                    )
+                    .await?;
+                thread_id = format!("claude:{forked_session_id}");
+            }
+        }
+    }
+
+    let rewound = thread_id != checkpoint.thread_id;
+    if rewound {
+        let restored = checkpoint.clone();
+        let new_thread_id = thread_id.clone();
+        mutate_checkpoints(&path, move |checkpoints| {
+            carry_checkpoints_to_thread(checkpoints, &restored, &new_thread_id)
+        })?;
+    }
+
+    let restored_files = match repo_root {
+        Some((root, snapshot_sha)) => {
+            crate::git::restore_working_tree_snapshot(&root, &snapshot_sha).await?
+        }
+        None => Vec::new(),
+    };
+    log::info!(
+        "[checkpoints] restored \"{}\" in {workspace_id} (rewound: {rewound}, files: {})",
+        checkpoint.name,
+        restored_files.len()
+    );
+    Ok(CheckpointRestoreResult {
+        checkpoint,
+        thread_id,
+        rewound,
+        restored_files,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn checkpoint_delete(
+    workspace_id: String,
+    checkpoint_id: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let path = checkpoints_path(&workspace_id)?;
+    let removed = mutate_checkpoints(&path, |checkpoints| {
+        let before = checkpoints.len();
+        checkpoints.retain(|checkpoint| checkpoint.id != checkpoint_id);
+        before != checkpoints.len()
+    })?;
+    if removed {
+        if let Ok(root) = workspace_repo_root(&entry) {
+            crate::git::delete_working_tree_snapshot(&root, &checkpoint_id)?;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(id: &str, thread_id: &str, created_at: i64) -> SessionCheckpoint {
+        SessionCheckpoint {
+            id: id.to_string(),
+            name: id.to_string(),
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            engine: "codex".to_string(),
+            user_message_count: Some(1),
+            transcript_cursor: None,
+            snapshot_sha: None,
+            snapshot_error: None,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn resolves_checkpoint_engine_from_thread_id() {
+        assert_eq!(
+            checkpoint_engine("claude:abc"),
+            Ok(CheckpointEngine::Claude {
+                session_id: "abc".to_string()
+            })
+        );
+        assert_eq!(checkpoint_engine("thr_123"), Ok(CheckpointEngine::Codex));
+        assert!(checkpoint_engine("gemini:abc").is_err());
+    }
+
+    #[test]
+    fn normalizes_checkpoint_names() {
+        assert_eq!(
+            normalize_checkpoint_name("  before\n refactor "),
+            Ok("before refactor".to_string())
+        );
+        assert!(normalize_checkpoint_name("   ").is_err());
+        assert_eq!(
+            normalize_checkpoint_name(&"x".repeat(200))
+                .expect("long name")
+                .len(),
+            MAX_CHECKPOINT_NAME_CHARS
+        );
+    }
+
+    #[test]
+    fn earlier_checkpoints_follow_the_rewound_thread() {
+        let mut checkpoints = vec![
+            checkpoint("a", "thread-1", 1),
+            checkpoint("b", "thread-1", 2),
+            checkpoint("c", "thread-1", 3),
+            checkpoint("other", "thread-2", 1),
+        ];
+        let restored = checkpoints[1].clone();
+
+        carry_checkpoints_to_thread(&mut checkpoints, &restored, "thread-3");
+
+        let threads: Vec<&str> = checkpoints
+            .iter()
+            .map(|checkpoint| checkpoint.thread_id.as_str())
+            .collect();
+        assert_eq!(
+            threads,
+            vec!["thread-3", "thread-3", "thread-1", "thread-2"]
+        );
+    }
+}
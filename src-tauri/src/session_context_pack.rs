@@ -0,0 +1,768 @@
+//! Context packs: a manifest of what an engine session started from.
+//!
+//! When a new session starts we record the prompt, model, access mode, the
+//! relevant settings, the git HEAD and the fingerprints of the files that fed
+//! the first turn (instruction files, files mentioned in the prompt, attached
+//! images and dirty working-tree files). The pack is written once per session
+//! and never updated, so it can later be used to audit a result or to re-run
+//! the session against the same starting point.
+//!
+//! Claude, Gemini and OpenCode only learn their session id from the first
+//! `thread/started` event, so `engine_send_message` parks the launch under its
+//! turn id and the pack is written when that event is observed. Codex threads
+//! are created up front and captured on their first message.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use git2::{Repository, StatusOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+use crate::app_paths;
+use crate::backend::events::AppServerEvent;
+use crate::engine::{self, EngineTurnLinkage, EngineType};
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+use crate::types::WorkspaceEntry;
+
+const CONTEXT_PACKS_DIRNAME: &str = "session-context-packs";
+const RERUN_BRANCH_PREFIX: &str = "rerun/";
+const MAX_PACKS_PER_WORKSPACE: usize = 500;
+const MAX_HASHED_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_DIRTY_FILES: usize = 200;
+const MAX_MENTIONED_FILES: usize = 50;
+const PENDING_LAUNCH_TTL_MS: u64 = 10 * 60 * 1000;
+const MENTION_PUNCTUATION: &str = "`'\"()[]{}<>,;:!?";
+const INSTRUCTION_FILES: &[&str] = &["CLAUDE.md", ".claude/CLAUDE.md", "AGENTS.md", "GEMINI.md"];
+
+/// What the user asked for when the session started.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextPackLaunch {
+    pub(crate) engine: EngineType,
+    pub(crate) prompt: String,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+    #[serde(default)]
+    pub(crate) access_mode: Option<String>,
+    #[serde(default)]
+    pub(crate) images: Vec<String>,
+    #[serde(default)]
+    pub(crate) agent: Option<String>,
+    #[serde(default)]
+    pub(crate) variant: Option<String>,
+    #[serde(default)]
+    pub(crate) custom_spec_root: Option<String>,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    #[serde(default)]
+    pub(crate) fork_session_id: Option<String>,
+}
+
+/// Settings that change how an engine behaves for the same prompt.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextPackSettings {
+    default_access_mode: String,
+    #[serde(default)]
+    workspace_access_mode_preset: Option<String>,
+    #[serde(default)]
+    claude_bin: Option<String>,
+    #[serde(default)]
+    codex_bin: Option<String>,
+    #[serde(default)]
+    codex_args: Option<String>,
+    #[serde(default)]
+    workspace_codex_args: Option<String>,
+    #[serde(default)]
+    workspace_codex_home: Option<String>,
+    codex_mode_enforcement_enabled: bool,
+    experimental_collaboration_modes_enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextPackGit {
+    #[serde(default)]
+    sha: Option<String>,
+    #[serde(default)]
+    branch: Option<String>,
+    dirty: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ContextPackFileRole {
+    Instruction,
+    Mentioned,
+    Image,
+    Dirty,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextPackFile {
+    /// Repo-relative for workspace files, absolute for attached images.
+    path: String,
+    role: ContextPackFileRole,
+    /// `None` when the file did not exist (e.g. a deleted dirty file).
+    #[serde(default)]
+    size: Option<u64>,
+    /// `None` for missing files and files above the hashing limit.
+    #[serde(default)]
+    sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionContextPack {
+    session_id: String,
+    workspace_id: String,
+    workspace_path: String,
+    created_at: u64,
+    launch: ContextPackLaunch,
+    settings: ContextPackSettings,
+    #[serde(default)]
+    git: Option<ContextPackGit>,
+    #[serde(default)]
+    files: Vec<ContextPackFile>,
+    #[serde(default)]
+    dirty_files_truncated: bool,
+    /// Files left out because they fall in a workspace privacy zone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    withheld_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ContextPackDriftKind {
+    HeadMoved,
+    FileChanged,
+    FileMissing,
+}
+
+/// A difference between the pack and the tree a re-run starts from.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextPackDrift {
+    kind: ContextPackDriftKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    expected: Option<String>,
+    actual: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextPackRerun {
+    /// Workspace the re-run executes in; a fresh worktree when pinned.
+    workspace_id: String,
+    workspace_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    linkage: EngineTurnLinkage,
+    drift: Vec<ContextPackDrift>,
+}
+
+struct PendingLaunch {
+    workspace_id: String,
+    launch: ContextPackLaunch,
+    queued_at: u64,
+}
+
+static PENDING_LAUNCHES: OnceLock<Mutex<HashMap<String, PendingLaunch>>> = OnceLock::new();
+static FRESH_CODEX_THREADS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+fn pending_launches() -> std::sync::MutexGuard<'static, HashMap<String, PendingLaunch>> {
+    PENDING_LAUNCHES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn fresh_codex_threads() -> std::sync::MutexGuard<'static, HashSet<String>> {
+    FRESH_CODEX_THREADS
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_millis(0))
+        .as_millis() as u64
+}
+
+fn is_safe_storage_id(value: &str) -> bool {
+    !value.is_empty()
+        && value != "."
+        && value != ".."
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn packs_store_path(workspace_id: &str) -> Result<PathBuf, String> {
+    if !is_safe_storage_id(workspace_id) {
+        return Err(format!("Invalid workspace id: {workspace_id}"));
+    }
+    Ok(app_paths::app_home_dir()?
+        .join(CONTEXT_PACKS_DIRNAME)
+        .join(format!("{workspace_id}.json")))
+}
+
+fn read_packs(path: &Path) -> Result<Vec<SessionContextPack>, String> {
+    Ok(read_json_file::<Vec<SessionContextPack>>(path)?.unwrap_or_default())
+}
+
+/// Stores `pack` unless its session already has one; returns whether it was
+/// written. The oldest packs are dropped beyond the per-workspace cap.
+fn insert_pack_if_absent(path: &Path, pack: SessionContextPack) -> Result<bool, String> {
+    with_storage_lock(path, || {
+        let mut packs = read_packs(path)?;
+        if packs
+            .iter()
+            .any(|existing| existing.session_id == pack.session_id)
+        {
+            return Ok(false);
+        }
+        packs.push(pack);
+        if packs.len() > MAX_PACKS_PER_WORKSPACE {
+            packs.sort_by_key(|entry| entry.created_at);
+            let excess = packs.len() - MAX_PACKS_PER_WORKSPACE;
+            packs.drain(..excess);
+        }
+        let data = serde_json::to_string_pretty(&packs)
+            .map_err(|error| format!("failed to serialize {}: {error}", path.display()))?;
+        write_string_atomically(path, &data)?;
+        Ok(true)
+    })
+}
+
+fn hash_file(path: &Path) -> Option<(u64, Option<String>)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    if metadata.len() > MAX_HASHED_FILE_BYTES {
+        return Some((metadata.len(), None));
+    }
+    let bytes = std::fs::read(path).ok()?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Some((metadata.len(), Some(format!("{:x}", hasher.finalize()))))
+}
+
+fn fingerprint(root: &Path, path: String, role: ContextPackFileRole) -> ContextPackFile {
+    let absolute = if Path::new(&path).is_absolute() {
+        PathBuf::from(&path)
+    } else {
+        root.join(&path)
+    };
+    let (size, sha256) = match hash_file(&absolute) {
+        Some((size, sha256)) => (Some(size), sha256),
+        None => (None, None),
+    };
+    ContextPackFile {
+        path,
+        role,
+        size,
+        sha256,
+    }
+}
+
+/// Repo-relative paths of existing files referenced in the prompt, either as
+/// `@path` mentions or as bare tokens that look like paths.
+fn mentioned_paths(prompt: &str, root: &Path) -> Vec<String> {
+    let Ok(canonical_root) = root.canonicalize() else {
+        return Vec::new();
+    };
+    let mut seen = HashSet::new();
+    let mut paths = Vec::new();
+    for token in prompt.split_whitespace() {
+        let candidate = token
+            .trim_start_matches(|ch: char| ch == '@' || MENTION_PUNCTUATION.contains(ch))
+            .trim_end_matches(|ch: char| ch == '.' || MENTION_PUNCTUATION.contains(ch));
+        if candidate.is_empty()
+            || candidate.contains("://")
+            || !(candidate.contains('/') || candidate.contains('.'))
+        {
+            continue;
+        }
+        let Ok(resolved) = canonical_root.join(candidate).canonicalize() else {
+            continue;
+        };
+        if !resolved.is_file() {
+            continue;
+        }
+        let Ok(relative) = resolved.strip_prefix(&canonical_root) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if seen.insert(relative.clone()) {
+            paths.push(relative);
+            if paths.len() >= MAX_MENTIONED_FILES {
+                break;
+            }
+        }
+    }
+    paths
+}
+
+fn head_state(repo: &Repository) -> (Option<String>, Option<String>) {
+    let Ok(head) = repo.head() else {
+        return (None, None);
+    };
+    let sha = head.target().map(|oid| oid.to_string());
+    let branch = if head.is_branch() {
+        head.shorthand().map(str::to_string)
+    } else {
+        None
+    };
+    (sha, branch)
+}
+
+fn dirty_paths(repo: &Repository) -> Result<Vec<String>, String> {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|error| error.to_string())?;
+    let mut paths: Vec<String> = statuses
+        .iter()
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn build_context_pack(
+    entry: &WorkspaceEntry,
+    settings: &crate::types::AppSettings,
+    session_id: String,
+    launch: ContextPackLaunch,
+) -> SessionContextPack {
+    let workspace_root = PathBuf::from(&entry.path);
+    let repo_root = resolve_git_root(entry).unwrap_or_else(|_| workspace_root.clone());
+    let repo = Repository::open(&repo_root).ok();
+
+    let privacy_zones = crate::privacy_zones::PrivacyZones::for_workspace(entry);
+    let mut withheld_files = Vec::new();
+    let mut files = Vec::new();
+    let mut recorded = HashSet::new();
+    let mut push = |files: &mut Vec<ContextPackFile>, path: String, role| {
+        if !recorded.insert(path.clone()) {
+            return;
+        }
+        let absolute = repo_root.join(&path);
+        if privacy_zones
+            .as_ref()
+            .is_some_and(|zones| zones.is_private(&absolute.to_string_lossy()))
+        {
+            withheld_files.push(path);
+            return;
+        }
+        files.push(fingerprint(&repo_root, path, role));
+    };
+    for name in INSTRUCTION_FILES {
+        for base in [&workspace_root, &repo_root] {
+            let candidate = base.join(name);
+            if candidate.is_file() {
+                if let Ok(relative) = candidate.strip_prefix(&repo_root) {
+                    let relative = relative.to_string_lossy().replace('\\', "/");
+                    push(&mut files, relative, ContextPackFileRole::Instruction);
+                }
+            }
+        }
+    }
+    for path in mentioned_paths(&launch.prompt, &repo_root) {
+        push(&mut files, path, ContextPackFileRole::Mentioned);
+    }
+    for image in &launch.images {
+        push(&mut files, image.clone(), ContextPackFileRole::Image);
+    }
+
+    let mut git = None;
+    let mut dirty_files_truncated = false;
+    if let Some(repo) = repo.as_ref() {
+        let (sha, branch) = head_state(repo);
+        let dirty = dirty_paths(repo).unwrap_or_default();
+        dirty_files_truncated = dirty.len() > MAX_DIRTY_FILES;
+        git = Some(ContextPackGit {
+            sha,
+            branch,
+            dirty: !dirty.is_empty(),
+        });
+        for path in dirty.into_iter().take(MAX_DIRTY_FILES) {
+            // Deleted files fingerprint as missing.
+            push(&mut files, path, ContextPackFileRole::Dirty);
+        }
+    }
+
+    SessionContextPack {
+        session_id,
+        workspace_id: entry.id.clone(),
+        workspace_path: entry.path.clone(),
+        created_at: now_millis(),
+        settings: ContextPackSettings {
+            default_access_mode: settings.default_access_mode.clone(),
+            workspace_access_mode_preset: entry.settings.access_mode_preset.clone(),
+            claude_bin: settings.claude_bin.clone(),
+            codex_bin: settings.codex_bin.clone(),
+            codex_args: settings.codex_args.clone(),
+            workspace_codex_args: entry.settings.codex_args.clone(),
+            workspace_codex_home: entry.settings.codex_home.clone(),
+            codex_mode_enforcement_enabled: settings.codex_mode_enforcement_enabled,
+            experimental_collaboration_modes_enabled: settings
+                .experimental_collaboration_modes_enabled,
+        },
+        launch,
+        git,
+        files,
+        dirty_files_truncated,
+        withheld_files,
+    }
+}
+
+/// Compares a pack with the tree at `root`: HEAD and every fingerprinted file.
+fn detect_drift(pack: &SessionContextPack, root: &Path) -> Vec<ContextPackDrift> {
+    let mut drift = Vec::new();
+    if let Some(expected) = pack.git.as_ref().and_then(|git| git.sha.clone()) {
+        let actual = Repository::open(root)
+            .ok()
+            .and_then(|repo| head_state(&repo).0);
+        if actual.as_deref() != Some(expected.as_str()) {
+            drift.push(ContextPackDrift {
+                kind: ContextPackDriftKind::HeadMoved,
+                path: None,
+                expected: Some(expected),
+                actual,
+            });
+        }
+    }
+    for file in &pack.files {
+        let current = fingerprint(root, file.path.clone(), file.role);
+        let kind = match (file.size, current.size) {
+            (None, None) => continue,
+            (Some(_), None) => ContextPackDriftKind::FileMissing,
+            _ if current.size == file.size && current.sha256 == file.sha256 => continue,
+            _ => ContextPackDriftKind::FileChanged,
+        };
+        drift.push(ContextPackDrift {
+            kind,
+            path: Some(file.path.clone()),
+            expected: file.sha256.clone(),
+            actual: current.sha256,
+        });
+    }
+    drift
+}
+
+fn spawn_record(
+    app: AppHandle,
+    workspace_id: String,
+    session_id: String,
+    launch: ContextPackLaunch,
+) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let entry = {
+            let workspaces = state.workspaces.lock().await;
+            workspaces.get(&workspace_id).cloned()
+        };
+        let Some(entry) = entry else {
+            return;
+        };
+        let settings = state.app_settings.lock().await.clone();
+        let Ok(store_path) = packs_store_path(&workspace_id) else {
+            return;
+        };
+        let result = tokio::task::spawn_blocking(move || {
+            let pack = build_context_pack(&entry, &settings, session_id, launch);
+            insert_pack_if_absent(&store_path, pack)
+        })
+        .await;
+        if let Ok(Err(error)) = result {
+            log::warn!("[session_context_pack] failed to record pack: {error}");
+        }
+    });
+}
+
+/// Session ids as the session catalog lists them; Codex thread ids are bare.
+fn catalog_session_id(engine: EngineType, raw_id: &str) -> String {
+    match engine {
+        EngineType::Claude => format!("claude:{raw_id}"),
+        EngineType::Gemini => format!("gemini:{raw_id}"),
+        EngineType::OpenCode => format!("opencode:{raw_id}"),
+        EngineType::Codex => raw_id.to_string(),
+    }
+}
+
+/// Parks the launch of a brand-new session until its engine reports the
+/// session id for `turn_id`.
+pub(crate) fn queue_session_launch(workspace_id: &str, turn_id: &str, launch: ContextPackLaunch) {
+    let now = now_millis();
+    let mut pending = pending_launches();
+    pending.retain(|_, entry| now.saturating_sub(entry.queued_at) < PENDING_LAUNCH_TTL_MS);
+    pending.insert(
+        turn_id.to_string(),
+        PendingLaunch {
+            workspace_id: workspace_id.to_string(),
+            launch,
+            queued_at: now,
+        },
+    );
+}
+
+/// Watches for the `thread/started` event that carries the real session id of
+/// a queued launch and records its context pack.
+pub(crate) fn observe_app_server_event(app: &AppHandle, event: &AppServerEvent) {
+    if event.message.get("method").and_then(Value::as_str) != Some("thread/started") {
+        return;
+    }
+    let params = event.message.get("params").unwrap_or(&Value::Null);
+    let field = |key: &str| {
+        params
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let (Some(turn_id), Some(session_id)) = (field("turnId"), field("sessionId")) else {
+        return;
+    };
+    if session_id == "pending" {
+        return;
+    }
+    let Some(pending) = pending_launches().remove(turn_id) else {
+        return;
+    };
+    let session_id = catalog_session_id(pending.launch.engine, session_id);
+    spawn_record(
+        app.clone(),
+        pending.workspace_id,
+        session_id,
+        pending.launch,
+    );
+}
+
+/// Marks a Codex thread created by this app so its first message is captured.
+/// Threads that were only resumed never get a pack.
+pub(crate) fn mark_fresh_codex_thread(thread_id: &str) {
+    fresh_codex_threads().insert(thread_id.to_string());
+}
+
+/// Records the pack of a fresh Codex thread on its first message.
+pub(crate) fn record_codex_first_message(
+    app: &AppHandle,
+    workspace_id: &str,
+    thread_id: &str,
+    launch: ContextPackLaunch,
+) {
+    if !fresh_codex_threads().remove(thread_id) {
+        return;
+    }
+    spawn_record(
+        app.clone(),
+        workspace_id.to_string(),
+        thread_id.to_string(),
+        launch,
+    );
+}
+
+#[tauri::command]
+pub(crate) async fn get_session_context_pack(
+    workspace_id: String,
+    session_id: String,
+) -> Result<Option<SessionContextPack>, String> {
+    let store_path = packs_store_path(&workspace_id)?;
+    Ok(read_packs(&store_path)?
+        .into_iter()
+        .find(|pack| pack.session_id == session_id))
+}
+
+/// Starts a new session with the prompt, model and access mode of a recorded
+/// pack. With `pinned` the session runs in a fresh worktree checked out at the
+/// pack's commit; otherwise it runs in the current workspace. Either way the
+/// differences from the recorded tree are reported so the caller can judge
+/// how faithful the re-run is.
+#[tauri::command]
+pub(crate) async fn rerun_session_from_context_pack(
+    workspace_id: String,
+    session_id: String,
+    pinned: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ContextPackRerun, String> {
+    let pack = get_session_context_pack(workspace_id.clone(), session_id.clone())
+        .await?
+        .ok_or_else(|| format!("No context pack recorded for session {session_id}"))?;
+    let mut target_workspace_id = workspace_id.clone();
+    let mut target_path = pack.workspace_path.clone();
+    let mut branch = None;
+    if pinned.unwrap_or(false) {
+        let sha = pack
+            .git
+            .as_ref()
+            .and_then(|git| git.sha.clone())
+            .ok_or("The context pack has no commit to pin to")?;
+        let name = format!(
+            "{RERUN_BRANCH_PREFIX}{}",
+            &Uuid::new_v4().simple().to_string()[..8]
+        );
+        let worktree = crate::workspaces::add_worktree(
+            workspace_id.clone(),
+            name.clone(),
+            None,
+            Some(sha),
+            Some(false),
+            state.clone(),
+            app.clone(),
+        )
+        .await?;
+        target_workspace_id = worktree.id;
+        target_path = worktree.path;
+        branch = Some(name);
+    }
+
+    let root = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&target_workspace_id)
+            .ok_or("workspace not found")?;
+        resolve_git_root(entry).unwrap_or_else(|_| PathBuf::from(&entry.path))
+    };
+    let drift_pack = pack.clone();
+    let drift = tokio::task::spawn_blocking(move || detect_drift(&drift_pack, &root))
+        .await
+        .map_err(|error| error.to_string())?;
+
+    let launch = pack.launch;
+    let linkage = engine::start_engine_turn(
+        &target_workspace_id,
+        launch.engine,
+        launch.prompt.clone(),
+        launch.model.clone(),
+        launch.access_mode.clone(),
+        state,
+        app.clone(),
+    )
+    .await?;
+    // Codex turns started here bypass `send_user_message`, so capture the new
+    // thread's pack directly.
+    if let (EngineType::Codex, Some(thread_id)) = (linkage.engine, linkage.thread_id.as_deref()) {
+        record_codex_first_message(&app, &target_workspace_id, thread_id, launch);
+    }
+    Ok(ContextPackRerun {
+        workspace_id: target_workspace_id,
+        workspace_path: target_path,
+        branch,
+        linkage,
+        drift,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(prompt: &str) -> ContextPackLaunch {
+        ContextPackLaunch {
+            engine: EngineType::Claude,
+            prompt: prompt.to_string(),
+            model: Some("sonnet".to_string()),
+            effort: None,
+            access_mode: Some("full-access".to_string()),
+            images: Vec::new(),
+            agent: None,
+            variant: None,
+            custom_spec_root: None,
+            cwd: None,
+            fork_session_id: None,
+        }
+    }
+
+    #[test]
+    fn mentioned_paths_resolve_existing_files_inside_root() {
+        let root = std::env::temp_dir().join(format!("ctx-pack-mentions-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("src")).expect("create src");
+        std::fs::write(root.join("src/main.rs"), "fn main() {}").expect("write main");
+        std::fs::write(root.join("README.md"), "# readme").expect("write readme");
+
+        let paths = mentioned_paths(
+            "Fix @src/main.rs, then update `README.md`. See https://example.com/a.rs and ../etc/passwd missing.rs",
+            &root,
+        );
+        assert_eq!(
+            paths,
+            vec!["src/main.rs".to_string(), "README.md".to_string()]
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn drift_reports_changed_and_missing_files() {
+        let root = std::env::temp_dir().join(format!("ctx-pack-drift-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).expect("create root");
+        std::fs::write(root.join("AGENTS.md"), "rules").expect("write agents");
+        std::fs::write(root.join("notes.txt"), "v1").expect("write notes");
+        std::fs::write(root.join("gone.txt"), "bye").expect("write gone");
+
+        let files = ["AGENTS.md", "notes.txt", "gone.txt"]
+            .iter()
+            .map(|path| fingerprint(&root, path.to_string(), ContextPackFileRole::Mentioned))
+            .collect();
+        let pack = SessionContextPack {
+            session_id: "claude:abc".to_string(),
+            workspace_id: "ws".to_string(),
+            workspace_path: root.to_string_lossy().to_string(),
+            created_at: 1,
+            launch: launch("hello"),
+            settings: ContextPackSettings {
+                default_access_mode: "current".to_string(),
+                workspace_access_mode_preset: None,
+                claude_bin: None,
+                codex_bin: None,
+                codex_args: None,
+                workspace_codex_args: None,
+                workspace_codex_home: None,
+                codex_mode_enforcement_enabled: false,
+                experimental_collaboration_modes_enabled: false,
+            },
+            git: None,
+            files,
+            dirty_files_truncated: false,
+            withheld_files: Vec::new(),
+        };
+        assert!(detect_drift(&pack, &root).is_empty());
+
+        std::fs::write(root.join("notes.txt"), "v2").expect("rewrite notes");
+        std::fs::remove_file(root.join("gone.txt")).expect("remove gone");
+        let drift = detect_drift(&pack, &root);
+        let kinds: Vec<_> = drift
+            .iter()
+            .map(|entry| (entry.kind, entry.path.clone().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ContextPackDriftKind::FileChanged, "notes.txt".to_string()),
+                (ContextPackDriftKind::FileMissing, "gone.txt".to_string()),
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,401 @@
+//! Estimated cost of engine turns, with running totals per session.
+//!
+//! Token usage seen on the app-server event stream is priced through
+//! [`crate::model_pricing`] when a turn completes. The estimate is attached
+//! to the `turn/completed` params as `costEstimate` and added to the
+//! session's totals, which `get_session_cost` returns for the turns observed
+//! since the app started.
+//!
+//! Codex reports a running thread total, so a Codex turn costs the growth of
+//! that total; the other engines report the turn's own usage, so their last
+//! report before completion is used. Turns on a model without a price are
+//! counted as unpriced instead of free.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::backend::events::AppServerEvent;
+use crate::engine::EngineType;
+use crate::session_stats::{params_string, reported_tokens};
+
+const MAX_TRACKED_SESSIONS: usize = 256;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PricingStatus {
+    /// Every turn was priced.
+    #[default]
+    Estimated,
+    /// Some turns ran on a model without a price.
+    Partial,
+    /// No turn could be priced.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnCostEstimate {
+    pub(crate) model: Option<String>,
+    /// Input tokens not served from the prompt cache.
+    pub(crate) input_tokens: u64,
+    pub(crate) cached_input_tokens: u64,
+    pub(crate) output_tokens: u64,
+    /// `None` when the model has no price.
+    pub(crate) cost_usd: Option<f64>,
+    pub(crate) pricing: PricingStatus,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionCost {
+    pub(crate) engine: EngineType,
+    pub(crate) session_id: String,
+    pub(crate) turns: u32,
+    pub(crate) unpriced_turns: u32,
+    /// Models of the unpriced turns, in first-seen order.
+    pub(crate) unpriced_models: Vec<String>,
+    pub(crate) input_tokens: u64,
+    pub(crate) cached_input_tokens: u64,
+    pub(crate) output_tokens: u64,
+    /// Sum over the priced turns.
+    pub(crate) cost_usd: f64,
+    pub(crate) pricing: PricingStatus,
+    pub(crate) updated_at_ms: i64,
+}
+
+impl SessionCost {
+    fn add_turn(&mut self, turn: &TurnCostEstimate, now_ms: i64) {
+        self.turns += 1;
+        self.input_tokens += turn.input_tokens;
+        self.cached_input_tokens += turn.cached_input_tokens;
+        self.output_tokens += turn.output_tokens;
+        match turn.cost_usd {
+            Some(cost) => self.cost_usd += cost,
+            None => {
+                self.unpriced_turns += 1;
+                if let Some(model) = &turn.model {
+                    if !self.unpriced_models.contains(model) {
+                        self.unpriced_models.push(model.clone());
+                    }
+                }
+            }
+        }
+        self.pricing = if self.unpriced_turns == 0 {
+            PricingStatus::Estimated
+        } else if self.unpriced_turns == self.turns {
+            PricingStatus::Unknown
+        } else {
+            PricingStatus::Partial
+        };
+        self.updated_at_ms = now_ms;
+    }
+}
+
+/// Token reports of one thread between turn boundaries.
+#[derive(Debug, Default)]
+struct ThreadUsage {
+    model: Option<String>,
+    /// Latest input, output and cached-input totals reported.
+    reported: Option<[u64; 3]>,
+    /// Codex thread total when the running turn started.
+    turn_baseline: Option<[u64; 3]>,
+    updated_at_ms: i64,
+}
+
+#[derive(Default)]
+struct CostLedger {
+    threads: HashMap<String, ThreadUsage>,
+    sessions: HashMap<(EngineType, String), SessionCost>,
+}
+
+fn ledger() -> std::sync::MutexGuard<'static, CostLedger> {
+    static LEDGER: OnceLock<Mutex<CostLedger>> = OnceLock::new();
+    LEDGER
+        .get_or_init(|| Mutex::new(CostLedger::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn thread_key(workspace_id: &str, thread_id: &str) -> String {
+    format!("{workspace_id}\u{0}{thread_id}")
+}
+
+/// Engine and native session id behind an app-server thread id.
+fn engine_session(thread_id: &str) -> (EngineType, &str) {
+    match thread_id.split_once(':') {
+        Some(("claude", session_id)) => (EngineType::Claude, session_id),
+        Some(("gemini", session_id)) => (EngineType::Gemini, session_id),
+        Some(("opencode", session_id)) => (EngineType::OpenCode, session_id),
+        _ => (EngineType::Codex, thread_id),
+    }
+}
+
+fn evict_stalest<K: Clone + Eq + std::hash::Hash, V>(
+    entries: &mut HashMap<K, V>,
+    updated_at_ms: impl Fn(&V) -> i64,
+) {
+    if entries.len() < MAX_TRACKED_SESSIONS {
+        return;
+    }
+    let stalest = entries
+        .iter()
+        .min_by_key(|(_, value)| updated_at_ms(value))
+        .map(|(key, _)| key.clone());
+    if let Some(stalest) = stalest {
+        entries.remove(&stalest);
+    }
+}
+
+/// Model named by a turn result, e.g. Claude's single-model `modelUsage`.
+fn result_model(params: &Value) -> Option<String> {
+    let result = params.get("result")?;
+    if let Some(model) = result.get("model").and_then(Value::as_str) {
+        return Some(model.to_string());
+    }
+    let usage = result.get("modelUsage")?.as_object()?;
+    if usage.len() == 1 {
+        usage.keys().next().cloned()
+    } else {
+        None
+    }
+}
+
+fn normalize_model(model: Option<&str>) -> Option<String> {
+    model
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+        .map(str::to_string)
+}
+
+/// Folds one event into its thread's usage. On `turn/completed`, prices the
+/// turn, attaches the estimate to the params and returns it.
+fn apply_event(
+    workspace_id: &str,
+    message: &mut Value,
+    model: Option<&str>,
+    now_ms: i64,
+) -> Option<TurnCostEstimate> {
+    let method = message.get("method").and_then(Value::as_str)?.to_string();
+    if !matches!(
+        method.as_str(),
+        "turn/started" | "turn/completed" | "thread/tokenUsage/updated" | "token_count"
+    ) {
+        return None;
+    }
+    let params = message.get_mut("params")?;
+    let thread_id = params_string(params, &["/threadId", "/thread/id", "/thread_id"])?;
+    let (engine, session_id) = engine_session(&thread_id);
+    let key = thread_key(workspace_id, &thread_id);
+
+    let mut ledger = ledger();
+    let ledger = &mut *ledger;
+    if !ledger.threads.contains_key(&key) {
+        evict_stalest(&mut ledger.threads, |thread| thread.updated_at_ms);
+    }
+    let thread = ledger.threads.entry(key).or_default();
+    thread.updated_at_ms = now_ms;
+    if let Some(model) = normalize_model(model) {
+        thread.model = Some(model);
+    }
+    match method.as_str() {
+        "turn/started" => {
+            if engine == EngineType::Codex {
+                thread.turn_baseline = thread.reported;
+            } else {
+                thread.reported = None;
+            }
+            return None;
+        }
+        "turn/completed" => {}
+        _ => {
+            // Context-only reports (Claude's `/context` refresh) carry no
+            // token counts and must not replace the turn's usage.
+            if let Some(reported) =
+                reported_tokens(&method, params).filter(|tokens| tokens[0] > 0 || tokens[1] > 0)
+            {
+                thread.reported = Some(reported);
+            }
+            return None;
+        }
+    }
+
+    let [input, output, cached] = if engine == EngineType::Codex {
+        let total = thread.reported?;
+        let baseline = thread.turn_baseline.replace(total).unwrap_or_default();
+        let turn = [0, 1, 2].map(|index| total[index].saturating_sub(baseline[index]));
+        // Codex counts cached input inside the input total.
+        [turn[0].saturating_sub(turn[2]), turn[1], turn[2]]
+    } else {
+        thread.reported.take()?
+    };
+    if input == 0 && output == 0 && cached == 0 {
+        return None;
+    }
+    let model = thread.model.clone().or_else(|| result_model(params));
+    let cost_usd = model
+        .as_deref()
+        .and_then(crate::model_pricing::model_price)
+        .map(|price| price.cost_usd(input, cached, output));
+    let estimate = TurnCostEstimate {
+        model,
+        input_tokens: input,
+        cached_input_tokens: cached,
+        output_tokens: output,
+        cost_usd,
+        pricing: if cost_usd.is_some() {
+            PricingStatus::Estimated
+        } else {
+            PricingStatus::Unknown
+        },
+    };
+
+    let session_key = (engine, session_id.to_string());
+    if !ledger.sessions.contains_key(&session_key) {
+        evict_stalest(&mut ledger.sessions, |session| session.updated_at_ms);
+    }
+    ledger
+        .sessions
+        .entry(session_key)
+        .or_insert_with(|| SessionCost {
+            engine,
+            session_id: session_id.to_string(),
+            ..SessionCost::default()
+        })
+        .add_turn(&estimate, now_ms);
+
+    if let (Some(params), Ok(value)) = (params.as_object_mut(), serde_json::to_value(&estimate)) {
+        params.insert("costEstimate".to_string(), value);
+    }
+    Some(estimate)
+}
+
+/// Tracks token usage from an app-server event and, when it completes a
+/// turn, attaches the turn's `costEstimate`. `model` is the model the turn
+/// runs on when the caller knows it.
+pub(crate) fn annotate_app_server_event(event: &mut AppServerEvent, model: Option<&str>) {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    apply_event(&event.workspace_id, &mut event.message, model, now_ms);
+}
+
+/// Records the model a thread's turns run on, for engines whose events do
+/// not name it.
+pub(crate) fn note_thread_model(workspace_id: &str, thread_id: &str, model: Option<&str>) {
+    let Some(model) = normalize_model(model) else {
+        return;
+    };
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let mut ledger = ledger();
+    let key = thread_key(workspace_id, thread_id);
+    if !ledger.threads.contains_key(&key) {
+        evict_stalest(&mut ledger.threads, |thread| thread.updated_at_ms);
+    }
+    let thread = ledger.threads.entry(key).or_default();
+    thread.model = Some(model);
+    thread.updated_at_ms = now_ms;
+}
+
+/// Estimated cost of a session so far, `None` before its first priced or
+/// unpriced turn. Accepts the native session id or the `engine:`-prefixed
+/// thread id.
+#[tauri::command]
+pub(crate) fn get_session_cost(engine: EngineType, session_id: String) -> Option<SessionCost> {
+    let session_id = session_id.trim();
+    let session_id = match engine_session(session_id) {
+        (prefixed, native) if prefixed == engine => native,
+        _ => session_id,
+    };
+    ledger()
+        .sessions
+        .get(&(engine, session_id.to_string()))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn workspace() -> String {
+        format!("ws-{}", uuid::Uuid::new_v4())
+    }
+
+    fn usage(thread_id: &str, input: u64, output: u64, cached: u64) -> Value {
+        json!({"method": "thread/tokenUsage/updated", "params": {"threadId": thread_id, "tokenUsage": {
+            "total": {"inputTokens": input, "outputTokens": output, "cachedInputTokens": cached}
+        }}})
+    }
+
+    fn turn(method: &str, thread_id: &str) -> Value {
+        json!({"method": method, "params": {"threadId": thread_id}})
+    }
+
+    #[test]
+    fn codex_turns_cost_the_growth_of_the_thread_total() {
+        let workspace_id = workspace();
+        let thread_id = uuid::Uuid::new_v4().to_string();
+        let event = |mut message: Value| {
+            let estimate = apply_event(&workspace_id, &mut message, None, 1);
+            (estimate, message)
+        };
+        note_thread_model(&workspace_id, &thread_id, Some("gpt-5-codex"));
+
+        event(turn("turn/started", &thread_id));
+        event(usage(&thread_id, 1_000, 100, 200));
+        let (first, message) = event(turn("turn/completed", &thread_id));
+        let first = first.expect("first turn priced");
+        assert_eq!(
+            (
+                first.input_tokens,
+                first.cached_input_tokens,
+                first.output_tokens
+            ),
+            (800, 200, 100)
+        );
+        assert!((first.cost_usd.expect("cost") - 0.002025).abs() < 1e-12);
+        assert_eq!(message["params"]["costEstimate"]["pricing"], "estimated");
+
+        event(turn("turn/started", &thread_id));
+        event(usage(&thread_id, 3_000, 300, 200));
+        let (second, _) = event(turn("turn/completed", &thread_id));
+        let second = second.expect("second turn priced");
+        assert_eq!((second.input_tokens, second.output_tokens), (2_000, 200));
+
+        let session = get_session_cost(EngineType::Codex, thread_id).expect("session cost");
+        assert_eq!(session.turns, 2);
+        assert_eq!(session.output_tokens, 300);
+        assert_eq!(session.pricing, PricingStatus::Estimated);
+    }
+
+    #[test]
+    fn unknown_models_are_reported_as_unpriced() {
+        let workspace_id = workspace();
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let thread_id = format!("claude:{session_id}");
+        let event = |mut message: Value, model: Option<&str>| {
+            apply_event(&workspace_id, &mut message, model, 1)
+        };
+
+        event(turn("turn/started", &thread_id), Some("mystery-model"));
+        event(usage(&thread_id, 50, 10, 5), None);
+        // Claude's post-turn `/context` refresh reports no token counts.
+        event(
+            json!({"method": "thread/tokenUsage/updated", "params": {"threadId": &thread_id, "tokenUsage": {
+                "total": {"inputTokens": null, "outputTokens": null}
+            }}}),
+            None,
+        );
+        let estimate = event(turn("turn/completed", &thread_id), None).expect("turn estimated");
+        assert_eq!(estimate.cost_usd, None);
+        assert_eq!(estimate.pricing, PricingStatus::Unknown);
+        assert_eq!((estimate.input_tokens, estimate.output_tokens), (50, 10));
+
+        let session = get_session_cost(EngineType::Claude, thread_id).expect("session cost");
+        assert_eq!(session.session_id, session_id);
+        assert_eq!(session.unpriced_models, vec!["mystery-model".to_string()]);
+        assert_eq!(session.cost_usd, 0.0);
+        assert_eq!(session.pricing, PricingStatus::Unknown);
+    }
+}
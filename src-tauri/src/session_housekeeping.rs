@@ -0,0 +1,464 @@
+//! Size and age reports for engine session stores, and retention-based
+//! archiving of old sessions.
+//!
+//! The analyzer groups the workspace's session catalog by engine and picks
+//! archive candidates from the workspace's `SessionRetentionPolicy`. Running
+//! housekeeping only archives through `archive_workspace_sessions_core`, so
+//! nothing is deleted and every session can be unarchived again; archived
+//! sessions drop out of the default history listing.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::session_management::{
+    self, WorkspaceSessionBatchMutationResult, WorkspaceSessionCatalogEntry,
+};
+use crate::state::AppState;
+use crate::types::SessionRetentionPolicy;
+
+const DEFAULT_KEEP_RECENT: u32 = 20;
+const OLDEST_ENTRIES_PER_STORE: usize = 5;
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HousekeepingSession {
+    session_id: String,
+    workspace_id: String,
+    title: String,
+    updated_at: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
+    /// Why the policy selects this session: `"age"` or `"size"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+impl HousekeepingSession {
+    fn from_entry(entry: &WorkspaceSessionCatalogEntry, reason: Option<&str>) -> Self {
+        Self {
+            session_id: entry.session_id.clone(),
+            workspace_id: entry.workspace_id.clone(),
+            title: entry.title.clone(),
+            updated_at: entry.updated_at,
+            size_bytes: entry.size_bytes,
+            reason: reason.map(str::to_string),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionStoreReport {
+    engine: String,
+    session_count: usize,
+    active_count: usize,
+    archived_count: usize,
+    total_bytes: u64,
+    active_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    oldest_updated_at: Option<i64>,
+    /// Oldest active sessions, oldest first.
+    oldest_sessions: Vec<HousekeepingSession>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionHousekeepingReport {
+    workspace_id: String,
+    generated_at: i64,
+    policy: SessionRetentionPolicy,
+    stores: Vec<SessionStoreReport>,
+    /// Sessions the policy would archive, oldest first.
+    candidates: Vec<HousekeepingSession>,
+    candidate_bytes: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    partial_sources: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionHousekeepingRun {
+    results: Vec<WorkspaceSessionBatchMutationResult>,
+    /// Previewed sessions that no longer match the policy and were left
+    /// alone.
+    skipped_session_ids: Vec<String>,
+    report: SessionHousekeepingReport,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionHousekeepingRequest {
+    /// Overrides the saved policy, e.g. to preview unsaved settings.
+    #[serde(default)]
+    policy: Option<SessionRetentionPolicy>,
+    /// Sessions the user confirmed from a preview; only these are archived.
+    #[serde(default)]
+    session_ids: Option<Vec<String>>,
+}
+
+fn is_shared(entry: &WorkspaceSessionCatalogEntry) -> bool {
+    entry.session_id.starts_with("shared:")
+}
+
+fn policy_applies_to(policy: &SessionRetentionPolicy, engine: &str) -> bool {
+    policy.engines.is_empty()
+        || policy
+            .engines
+            .iter()
+            .any(|wanted| wanted.eq_ignore_ascii_case(engine))
+}
+
+/// Drops duplicate catalog rows for the same session, keeping the first.
+fn unique_entries(entries: &[WorkspaceSessionCatalogEntry]) -> Vec<&WorkspaceSessionCatalogEntry> {
+    let mut seen = HashSet::new();
+    entries
+        .iter()
+        .filter(|entry| seen.insert(entry.session_id.as_str()))
+        .collect()
+}
+
+fn build_store_reports(entries: &[&WorkspaceSessionCatalogEntry]) -> Vec<SessionStoreReport> {
+    let mut by_engine: BTreeMap<&str, Vec<&WorkspaceSessionCatalogEntry>> = BTreeMap::new();
+    for entry in entries {
+        by_engine
+            .entry(entry.engine.as_str())
+            .or_default()
+            .push(*entry);
+    }
+    by_engine
+        .into_iter()
+        .map(|(engine, entries)| {
+            let mut report = SessionStoreReport {
+                engine: engine.to_string(),
+                session_count: entries.len(),
+                ..SessionStoreReport::default()
+            };
+            let mut active = Vec::new();
+            for entry in entries {
+                let size = entry.size_bytes.unwrap_or(0);
+                report.total_bytes += size;
+                if entry.archived_at.is_some() {
+                    report.archived_count += 1;
+                } else {
+                    report.active_bytes += size;
+                    active.push(entry);
+                }
+            }
+            report.active_count = active.len();
+            active.sort_by_key(|entry| entry.updated_at);
+            report.oldest_updated_at = active.first().map(|entry| entry.updated_at);
+            report.oldest_sessions = active
+                .iter()
+                .take(OLDEST_ENTRIES_PER_STORE)
+                .map(|entry| HousekeepingSession::from_entry(entry, None))
+                .collect();
+            report
+        })
+        .collect()
+}
+
+/// Active sessions the policy would archive. Per engine the newest
+/// `keep_recent` sessions are protected; older ones past the age cap are
+/// selected, then the oldest remaining ones until the engine's active
+/// sessions fit under the size cap.
+fn select_candidates(
+    entries: &[&WorkspaceSessionCatalogEntry],
+    policy: &SessionRetentionPolicy,
+    now_ms: i64,
+) -> Vec<HousekeepingSession> {
+    let keep_recent = policy.keep_recent.unwrap_or(DEFAULT_KEEP_RECENT) as usize;
+    let age_cutoff = policy
+        .max_age_days
+        .map(|days| now_ms - i64::from(days) * DAY_MS);
+    let mut by_engine: HashMap<&str, Vec<&WorkspaceSessionCatalogEntry>> = HashMap::new();
+    for entry in entries {
+        if entry.archived_at.is_none()
+            && !is_shared(entry)
+            && policy_applies_to(policy, &entry.engine)
+        {
+            by_engine
+                .entry(entry.engine.as_str())
+                .or_default()
+                .push(*entry);
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for mut active in by_engine.into_values() {
+        // Newest first, so the protected sessions lead.
+        active.sort_by_key(|entry| std::cmp::Reverse(entry.updated_at));
+        let mut remaining_bytes: u64 = active
+            .iter()
+            .map(|entry| entry.size_bytes.unwrap_or(0))
+            .sum();
+        let mut kept = Vec::new();
+        for entry in active.iter().skip(keep_recent) {
+            if age_cutoff.is_some_and(|cutoff| entry.updated_at < cutoff) {
+                remaining_bytes -= entry.size_bytes.unwrap_or(0);
+                candidates.push(HousekeepingSession::from_entry(entry, Some("age")));
+            } else {
+                kept.push(*entry);
+            }
+        }
+        if let Some(cap) = policy.max_store_bytes {
+            for entry in kept.iter().rev() {
+                if remaining_bytes <= cap {
+                    break;
+                }
+                remaining_bytes -= entry.size_bytes.unwrap_or(0);
+                candidates.push(HousekeepingSession::from_entry(entry, Some("size")));
+            }
+        }
+    }
+    candidates.sort_by(|left, right| {
+        left.updated_at
+            .cmp(&right.updated_at)
+            .then_with(|| left.session_id.cmp(&right.session_id))
+    });
+    candidates
+}
+
+async fn build_report(
+    state: &AppState,
+    workspace_id: &str,
+    policy: Option<SessionRetentionPolicy>,
+) -> Result<SessionHousekeepingReport, String> {
+    let policy = match policy {
+        Some(policy) => policy,
+        None => state
+            .workspaces
+            .lock()
+            .await
+            .get(workspace_id)
+            .map(|entry| entry.settings.session_retention.clone())
+            .ok_or_else(|| "workspace not found".to_string())?,
+    };
+    let (entries, partial_sources) = session_management::list_workspace_catalog_entries_core(
+        &state.workspaces,
+        &state.engine_manager,
+        state.storage_path.as_path(),
+        workspace_id,
+    )
+    .await?;
+    let entries = unique_entries(&entries);
+    let candidates = select_candidates(&entries, &policy, chrono::Utc::now().timestamp_millis());
+    Ok(SessionHousekeepingReport {
+        workspace_id: workspace_id.to_string(),
+        generated_at: chrono::Utc::now().timestamp_millis(),
+        stores: build_store_reports(&entries),
+        candidate_bytes: candidates
+            .iter()
+            .map(|candidate| candidate.size_bytes.unwrap_or(0))
+            .sum(),
+        candidates,
+        policy,
+        partial_sources,
+    })
+}
+
+/// Store sizes per engine and a preview of what the retention policy would
+/// archive. Changes nothing.
+#[tauri::command]
+pub(crate) async fn analyze_session_housekeeping(
+    workspace_id: String,
+    policy: Option<SessionRetentionPolicy>,
+    state: State<'_, AppState>,
+) -> Result<SessionHousekeepingReport, String> {
+    build_report(&state, &workspace_id, policy).await
+}
+
+/// Archives the sessions the retention policy selects. Candidates are
+/// recomputed first, and when `sessionIds` is given only previewed sessions
+/// that still match are archived.
+#[tauri::command]
+pub(crate) async fn run_session_housekeeping(
+    workspace_id: String,
+    request: Option<SessionHousekeepingRequest>,
+    state: State<'_, AppState>,
+) -> Result<SessionHousekeepingRun, String> {
+    let request = request.unwrap_or_default();
+    let report = build_report(&state, &workspace_id, request.policy.clone()).await?;
+    let mut skipped_session_ids = Vec::new();
+    let mut selected: Vec<&HousekeepingSession> = report.candidates.iter().collect();
+    if let Some(confirmed) = &request.session_ids {
+        let candidate_ids: HashSet<&str> = report
+            .candidates
+            .iter()
+            .map(|candidate| candidate.session_id.as_str())
+            .collect();
+        skipped_session_ids = confirmed
+            .iter()
+            .filter(|session_id| !candidate_ids.contains(session_id.as_str()))
+            .cloned()
+            .collect();
+        selected.retain(|candidate| confirmed.contains(&candidate.session_id));
+    }
+
+    // Archive metadata lives with the owning workspace, which differs from
+    // `workspace_id` for sessions of its worktrees.
+    let mut by_owner: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for candidate in selected {
+        by_owner
+            .entry(candidate.workspace_id.as_str())
+            .or_default()
+            .push(candidate.session_id.clone());
+    }
+    let mut results = Vec::new();
+    for (owner_id, session_ids) in by_owner {
+        let response = session_management::archive_workspace_sessions_core(
+            &state.workspaces,
+            &state.sessions,
+            state.storage_path.as_path(),
+            owner_id.to_string(),
+            session_ids,
+        )
+        .await?;
+        results.extend(response.results);
+    }
+    log::info!(
+        "[session-housekeeping] archived {} session(s) for workspace {}",
+        results.iter().filter(|result| result.ok).count(),
+        workspace_id
+    );
+
+    let report = build_report(&state, &workspace_id, request.policy).await?;
+    Ok(SessionHousekeepingRun {
+        results,
+        skipped_session_ids,
+        report,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 100 * DAY_MS;
+
+    fn entry(id: &str, engine: &str, age_days: i64, size: u64) -> WorkspaceSessionCatalogEntry {
+        WorkspaceSessionCatalogEntry {
+            session_id: id.to_string(),
+            canonical_session_id: None,
+            parent_session_id: None,
+            workspace_id: "ws".to_string(),
+            workspace_label: None,
+            engine: engine.to_string(),
+            title: id.to_string(),
+            updated_at: NOW - age_days * DAY_MS,
+            archived_at: None,
+            thread_kind: "native".to_string(),
+            source: None,
+            source_label: None,
+            size_bytes: Some(size),
+            cwd: None,
+            attribution_status: None,
+            attribution_reason: None,
+            attribution_confidence: None,
+            matched_workspace_id: None,
+            matched_workspace_label: None,
+            folder_id: None,
+            linked_issues: None,
+        }
+    }
+
+    fn selected(candidates: &[HousekeepingSession]) -> Vec<(&str, &str)> {
+        candidates
+            .iter()
+            .map(|candidate| {
+                (
+                    candidate.session_id.as_str(),
+                    candidate.reason.as_deref().unwrap_or(""),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn age_cap_spares_recent_and_protected_sessions() {
+        let mut archived = entry("claude:archived", "claude", 90, 10);
+        archived.archived_at = Some(NOW);
+        let entries = [
+            entry("new", "codex", 1, 10),
+            entry("old", "codex", 40, 10),
+            entry("oldest", "codex", 60, 10),
+            entry("claude:old", "claude", 50, 10),
+            entry("shared:old", "codex", 80, 10),
+            archived,
+        ];
+        let entries: Vec<_> = entries.iter().collect();
+        let policy = SessionRetentionPolicy {
+            max_age_days: Some(30),
+            keep_recent: Some(1),
+            ..SessionRetentionPolicy::default()
+        };
+
+        assert_eq!(
+            selected(&select_candidates(&entries, &policy, NOW)),
+            vec![("oldest", "age"), ("old", "age")]
+        );
+
+        let codex_only = SessionRetentionPolicy {
+            engines: vec!["Codex".to_string()],
+            keep_recent: Some(0),
+            ..policy
+        };
+        assert_eq!(
+            selected(&select_candidates(&entries, &codex_only, NOW)),
+            vec![("oldest", "age"), ("old", "age")]
+        );
+    }
+
+    #[test]
+    fn size_cap_archives_oldest_until_store_fits() {
+        let entries = [
+            entry("a", "codex", 1, 400),
+            entry("b", "codex", 2, 300),
+            entry("c", "codex", 3, 200),
+            entry("d", "codex", 4, 100),
+        ];
+        let entries: Vec<_> = entries.iter().collect();
+        let policy = SessionRetentionPolicy {
+            max_store_bytes: Some(700),
+            keep_recent: Some(1),
+            ..SessionRetentionPolicy::default()
+        };
+
+        assert_eq!(
+            selected(&select_candidates(&entries, &policy, NOW)),
+            vec![("d", "size"), ("c", "size")]
+        );
+        assert!(select_candidates(&entries, &SessionRetentionPolicy::default(), NOW).is_empty());
+    }
+
+    #[test]
+    fn store_reports_split_active_and_archived_usage() {
+        let mut archived = entry("claude:x", "claude", 9, 50);
+        archived.archived_at = Some(NOW);
+        let entries = [
+            entry("claude:y", "claude", 2, 30),
+            entry("claude:z", "claude", 5, 20),
+            archived,
+            entry("c1", "codex", 1, 5),
+        ];
+        let entries: Vec<_> = entries.iter().collect();
+
+        let reports = build_store_reports(&entries);
+        let claude = &reports[0];
+        assert_eq!(claude.engine, "claude");
+        assert_eq!(
+            (
+                claude.session_count,
+                claude.active_count,
+                claude.archived_count
+            ),
+            (3, 2, 1)
+        );
+        assert_eq!((claude.total_bytes, claude.active_bytes), (100, 50));
+        assert_eq!(claude.oldest_updated_at, Some(NOW - 5 * DAY_MS));
+        assert_eq!(claude.oldest_sessions[0].session_id, "claude:z");
+        assert_eq!(reports[1].engine, "codex");
+    }
+}
@@ -0,0 +1,589 @@
+//! Turns a conversation into a GitHub issue.
+//!
+//! `export_session_to_issue` condenses a session transcript into an issue
+//! draft the user can edit; `create_session_issue` files it with `gh` and
+//! links the new issue back to the session in the workspace session metadata.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+use tokio::io::AsyncWriteExt;
+
+use crate::engine::EngineType;
+use crate::git_utils::resolve_git_root;
+use crate::session_management::{
+    link_workspace_session_issue_core, split_catalog_session_id, SessionLinkedIssue,
+};
+use crate::state::AppState;
+
+const MAX_TITLE_CHARS: usize = 80;
+const MAX_PROBLEM_CHARS: usize = 1_500;
+const MAX_FIX_CHARS: usize = 2_500;
+const MAX_FINDING_CHARS: usize = 240;
+const MAX_FINDINGS: usize = 6;
+const MAX_REPRO_STEPS: usize = 10;
+const MAX_LISTED_FILES: usize = 15;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TranscriptEntry {
+    User(String),
+    Assistant(String),
+    Command { command: String, failed: bool },
+    FileChange(String),
+    Error(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionIssueDraft {
+    session_id: String,
+    engine: String,
+    title: String,
+    problem: String,
+    findings: Vec<String>,
+    proposed_fix: String,
+    reproduction_steps: Vec<String>,
+    changed_files: Vec<String>,
+    /// Markdown body assembled from the sections above.
+    body: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionIssueResult {
+    issue: SessionLinkedIssue,
+    repo: String,
+    /// Every issue now linked to the session, including this one.
+    linked_issues: Vec<SessionLinkedIssue>,
+}
+
+fn truncate_chars(value: &str, max_chars: usize) -> String {
+    let value = value.trim();
+    if value.chars().count() <= max_chars {
+        return value.to_string();
+    }
+    let truncated: String = value.chars().take(max_chars).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+fn first_line(value: &str) -> &str {
+    value
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("")
+}
+
+fn push_unique(items: &mut Vec<String>, value: String) {
+    if !value.is_empty() && !items.contains(&value) {
+        items.push(value);
+    }
+}
+
+fn string_field<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| value.get(*key).and_then(Value::as_str))
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+/// Claude and Gemini history messages share one shape (`role`, `text`,
+/// `kind`, `toolType`, `toolInput`).
+fn transcript_from_history_messages(messages: &[Value]) -> Vec<TranscriptEntry> {
+    let mut entries = Vec::new();
+    for message in messages {
+        let text = string_field(message, &["text"]).unwrap_or("").to_string();
+        let kind = string_field(message, &["kind"]).unwrap_or("message");
+        match kind {
+            "message" => match string_field(message, &["role"]) {
+                Some("user") if !text.is_empty() => entries.push(TranscriptEntry::User(text)),
+                Some("assistant") if !text.is_empty() => {
+                    entries.push(TranscriptEntry::Assistant(text))
+                }
+                _ => {}
+            },
+            "tool" => {
+                let tool_type = string_field(message, &["toolType"]).unwrap_or("");
+                let input = message.get("toolInput").unwrap_or(&Value::Null);
+                if tool_type == "error" {
+                    entries.push(TranscriptEntry::Error(text));
+                } else if let Some(command) = string_field(input, &["command", "cmd"]) {
+                    entries.push(TranscriptEntry::Command {
+                        command: command.to_string(),
+                        failed: false,
+                    });
+                } else if let Some(path) =
+                    string_field(input, &["file_path", "filePath", "path", "absolute_path"])
+                {
+                    let lowered = tool_type.to_ascii_lowercase();
+                    if ["edit", "write", "replace", "patch"]
+                        .iter()
+                        .any(|marker| lowered.contains(marker))
+                    {
+                        entries.push(TranscriptEntry::FileChange(path.to_string()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// Codex `thread/resume` responses carry `thread.turns[].items[]`.
+pub(crate) fn codex_thread_items(response: &Value) -> impl Iterator<Item = &Value> {
+    response
+        .get("result")
+        .unwrap_or(response)
+        .get("thread")
+        .and_then(|thread| thread.get("turns"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|turn| turn.get("items").and_then(Value::as_array))
+        .flatten()
+}
+
+fn transcript_from_codex_thread(response: &Value) -> Vec<TranscriptEntry> {
+    let mut entries = Vec::new();
+    for item in codex_thread_items(response) {
+        match item.get("type").and_then(Value::as_str).unwrap_or("") {
+            "userMessage" => {
+                let text = item
+                    .get("content")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|part| part.get("text").and_then(Value::as_str))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if !text.trim().is_empty() {
+                    entries.push(TranscriptEntry::User(text));
+                }
+            }
+            "agentMessage" => {
+                if let Some(text) = string_field(item, &["text"]) {
+                    entries.push(TranscriptEntry::Assistant(text.to_string()));
+                }
+            }
+            "commandExecution" => {
+                if let Some(command) = string_field(item, &["command"]) {
+                    let failed = item.get("status").and_then(Value::as_str) == Some("failed")
+                        || item
+                            .get("exitCode")
+                            .and_then(Value::as_i64)
+                            .is_some_and(|code| code != 0);
+                    entries.push(TranscriptEntry::Command {
+                        command: command.to_string(),
+                        failed,
+                    });
+                }
+            }
+            "fileChange" => {
+                for change in item
+                    .get("changes")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    if let Some(path) = string_field(change, &["path"]) {
+                        entries.push(TranscriptEntry::FileChange(path.to_string()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+fn workspace_relative(path: &str, workspace_root: &Path) -> String {
+    Path::new(path)
+        .strip_prefix(workspace_root)
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| path.to_string())
+}
+
+fn condense_transcript(
+    session_id: &str,
+    engine: &str,
+    entries: &[TranscriptEntry],
+    workspace_root: &Path,
+) -> Result<SessionIssueDraft, String> {
+    let problem_source = entries
+        .iter()
+        .find_map(|entry| match entry {
+            TranscriptEntry::User(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .ok_or_else(|| "Session has no user message to draft an issue from".to_string())?;
+    let title = truncate_chars(first_line(problem_source), MAX_TITLE_CHARS);
+    let problem = truncate_chars(problem_source, MAX_PROBLEM_CHARS);
+
+    let assistant_messages = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            TranscriptEntry::Assistant(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let proposed_fix = assistant_messages
+        .last()
+        .map(|text| truncate_chars(text, MAX_FIX_CHARS))
+        .unwrap_or_default();
+
+    let mut findings = Vec::new();
+    for entry in entries {
+        let finding = match entry {
+            TranscriptEntry::Error(text) => format!("Error: {}", first_line(text)),
+            TranscriptEntry::Command {
+                command,
+                failed: true,
+            } => format!("Command failed: `{}`", first_line(command)),
+            _ => continue,
+        };
+        push_unique(&mut findings, truncate_chars(&finding, MAX_FINDING_CHARS));
+    }
+    let intermediate = assistant_messages.len().saturating_sub(1);
+    for text in &assistant_messages[..intermediate] {
+        push_unique(
+            &mut findings,
+            truncate_chars(first_line(text), MAX_FINDING_CHARS),
+        );
+    }
+    findings.truncate(MAX_FINDINGS);
+
+    let mut reproduction_steps = Vec::new();
+    let mut changed_files = Vec::new();
+    for entry in entries {
+        match entry {
+            TranscriptEntry::Command { command, .. } => {
+                push_unique(&mut reproduction_steps, first_line(command).to_string())
+            }
+            TranscriptEntry::FileChange(path) => {
+                push_unique(&mut changed_files, workspace_relative(path, workspace_root))
+            }
+            _ => {}
+        }
+    }
+    reproduction_steps.truncate(MAX_REPRO_STEPS);
+    changed_files.truncate(MAX_LISTED_FILES);
+
+    let mut body = format!("## Problem\n\n{problem}\n");
+    if !findings.is_empty() {
+        body.push_str("\n## Findings\n\n");
+        for finding in &findings {
+            body.push_str(&format!("- {finding}\n"));
+        }
+    }
+    if !proposed_fix.is_empty() || !changed_files.is_empty() {
+        body.push_str("\n## Proposed fix\n\n");
+        if !proposed_fix.is_empty() {
+            body.push_str(&format!("{proposed_fix}\n"));
+        }
+        if !changed_files.is_empty() {
+            body.push_str("\nFiles touched in the session:\n\n");
+            for path in &changed_files {
+                body.push_str(&format!("- `{path}`\n"));
+            }
+        }
+    }
+    if !reproduction_steps.is_empty() {
+        body.push_str("\n## Reproduction steps\n\n");
+        for (index, step) in reproduction_steps.iter().enumerate() {
+            body.push_str(&format!("{}. `{}`\n", index + 1, step));
+        }
+    }
+    body.push_str(&format!(
+        "\n---\n_Drafted from {engine} session `{session_id}`._\n"
+    ));
+
+    Ok(SessionIssueDraft {
+        session_id: session_id.to_string(),
+        engine: engine.to_string(),
+        title,
+        problem,
+        findings,
+        proposed_fix,
+        reproduction_steps,
+        changed_files,
+        body,
+    })
+}
+
+pub(crate) async fn workspace_root(
+    state: &AppState,
+    workspace_id: &str,
+) -> Result<PathBuf, String> {
+    let workspaces = state.workspaces.lock().await;
+    workspaces
+        .get(workspace_id)
+        .map(|entry| PathBuf::from(&entry.path))
+        .ok_or_else(|| "workspace not found".to_string())
+}
+
+/// Raw history of a session, in the shape its engine reports it.
+pub(crate) enum SessionHistory {
+    /// Claude and Gemini history messages.
+    Messages(Vec<Value>),
+    /// Codex `thread/resume` response.
+    CodexThread(Value),
+}
+
+pub(crate) async fn load_session_history(
+    state: &AppState,
+    workspace_id: &str,
+    workspace_path: &Path,
+    session_id: &str,
+) -> Result<(&'static str, SessionHistory), String> {
+    let (engine, raw_id) = split_catalog_session_id(session_id);
+    let history = match engine {
+        "claude" => {
+            let config = state
+                .engine_manager
+                .get_engine_config(EngineType::Claude)
+                .await;
+            let result = crate::engine::claude_history::load_claude_session_with_config(
+                workspace_path,
+                &raw_id,
+                config.as_ref(),
+            )
+            .await?;
+            let messages = serde_json::to_value(result.messages).map_err(|e| e.to_string())?;
+            SessionHistory::Messages(messages.as_array().cloned().unwrap_or_default())
+        }
+        "gemini" => {
+            let config = state
+                .engine_manager
+                .get_engine_config(EngineType::Gemini)
+                .await;
+            let result = crate::engine::gemini_history::load_gemini_session(
+                workspace_path,
+                &raw_id,
+                config
+                    .as_ref()
+                    .and_then(|config| config.home_dir.as_deref()),
+            )
+            .await?;
+            let messages = serde_json::to_value(result.messages).map_err(|e| e.to_string())?;
+            SessionHistory::Messages(messages.as_array().cloned().unwrap_or_default())
+        }
+        "codex" => SessionHistory::CodexThread(
+            crate::shared::codex_core::resume_thread_core(
+                &state.sessions,
+                workspace_id.to_string(),
+                raw_id,
+            )
+            .await?,
+        ),
+        other => return Err(format!("Reading {other} session history is not supported")),
+    };
+    Ok((engine, history))
+}
+
+async fn load_transcript(
+    state: &AppState,
+    workspace_id: &str,
+    workspace_path: &Path,
+    session_id: &str,
+) -> Result<(&'static str, Vec<TranscriptEntry>), String> {
+    let (engine, history) =
+        load_session_history(state, workspace_id, workspace_path, session_id).await?;
+    let entries = match history {
+        SessionHistory::Messages(messages) => transcript_from_history_messages(&messages),
+        SessionHistory::CodexThread(response) => transcript_from_codex_thread(&response),
+    };
+    Ok((engine, entries))
+}
+
+/// Condenses a session (catalog id, e.g. `claude:<id>`) into an editable
+/// GitHub issue draft. Nothing is sent to GitHub.
+#[tauri::command]
+pub(crate) async fn export_session_to_issue(
+    workspace_id: String,
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<SessionIssueDraft, String> {
+    let session_id = session_id.trim().to_string();
+    if session_id.is_empty() {
+        return Err("session_id is required".to_string());
+    }
+    let workspace_path = workspace_root(&state, &workspace_id).await?;
+    let (engine, entries) =
+        load_transcript(&state, &workspace_id, &workspace_path, &session_id).await?;
+    condense_transcript(&session_id, engine, &entries, &workspace_path)
+}
+
+fn parse_issue_number(url: &str) -> Option<u64> {
+    url.trim_end_matches('/').rsplit('/').next()?.parse().ok()
+}
+
+/// Creates the (possibly edited) draft as a GitHub issue with `gh` and links
+/// it to the session.
+#[tauri::command]
+pub(crate) async fn create_session_issue(
+    workspace_id: String,
+    session_id: String,
+    title: String,
+    body: String,
+    labels: Option<Vec<String>>,
+    assignees: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<SessionIssueResult, String> {
+    let session_id = session_id.trim().to_string();
+    let title = title.trim().to_string();
+    if session_id.is_empty() {
+        return Err("session_id is required".to_string());
+    }
+    if title.is_empty() {
+        return Err("Issue title is required".to_string());
+    }
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = crate::git::github_repo_from_path(&repo_root)?;
+
+    let mut args = vec![
+        "issue".to_string(),
+        "create".to_string(),
+        "--repo".to_string(),
+        repo.clone(),
+        "--title".to_string(),
+        title.clone(),
+        "--body-file".to_string(),
+        "-".to_string(),
+    ];
+    for (flag, values) in [("--label", labels), ("--assignee", assignees)] {
+        for value in values.unwrap_or_default() {
+            let value = value.trim();
+            if !value.is_empty() {
+                args.push(flag.to_string());
+                args.push(value.to_string());
+            }
+        }
+    }
+    let mut child = crate::utils::async_command("gh")
+        .args(&args)
+        .current_dir(&repo_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(body.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to send issue body to gh: {e}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.trim();
+        if detail.is_empty() {
+            return Err("Failed to create GitHub issue.".to_string());
+        }
+        return Err(detail.to_string());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let url = stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| line.starts_with("http"))
+        .ok_or_else(|| format!("Unexpected gh output: {}", stdout.trim()))?
+        .to_string();
+    let issue = SessionLinkedIssue {
+        number: parse_issue_number(&url).unwrap_or_default(),
+        url,
+        title,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+    let linked_issues = link_workspace_session_issue_core(
+        &state.storage_path,
+        &workspace_id,
+        &session_id,
+        issue.clone(),
+    )?;
+    Ok(SessionIssueResult {
+        issue,
+        repo,
+        linked_issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn condenses_claude_transcript_into_issue_sections() {
+        let messages = vec![
+            json!({ "role": "user", "kind": "message", "text": "Login fails with 500\nSteps: open /login" }),
+            json!({ "role": "assistant", "kind": "tool", "toolType": "Bash", "text": "", "toolInput": { "command": "cargo test auth" } }),
+            json!({ "role": "assistant", "kind": "tool", "toolType": "error", "text": "thread panicked at auth.rs:42\nmore" }),
+            json!({ "role": "assistant", "kind": "message", "text": "The token parser rejects empty scopes." }),
+            json!({ "role": "assistant", "kind": "tool", "toolType": "Edit", "text": "", "toolInput": { "file_path": "/repo/src/auth.rs" } }),
+            json!({ "role": "assistant", "kind": "message", "text": "Default empty scopes to an empty list." }),
+        ];
+        let entries = transcript_from_history_messages(&messages);
+        let draft = condense_transcript("claude:abc", "claude", &entries, Path::new("/repo"))
+            .expect("draft");
+
+        assert_eq!(draft.title, "Login fails with 500");
+        assert_eq!(
+            draft.findings,
+            vec![
+                "Error: thread panicked at auth.rs:42".to_string(),
+                "The token parser rejects empty scopes.".to_string(),
+            ]
+        );
+        assert_eq!(draft.proposed_fix, "Default empty scopes to an empty list.");
+        assert_eq!(
+            draft.reproduction_steps,
+            vec!["cargo test auth".to_string()]
+        );
+        assert_eq!(draft.changed_files, vec!["src/auth.rs".to_string()]);
+        assert!(draft
+            .body
+            .contains("## Reproduction steps\n\n1. `cargo test auth`"));
+        assert!(draft.body.contains("session `claude:abc`"));
+    }
+
+    #[test]
+    fn reads_codex_thread_items_and_flags_failed_commands() {
+        let response = json!({
+            "result": { "thread": { "turns": [{ "items": [
+                { "type": "userMessage", "content": [{ "type": "text", "text": "Build breaks" }] },
+                { "type": "commandExecution", "command": "npm run build", "exitCode": 1 },
+                { "type": "fileChange", "changes": [{ "path": "src/app.ts" }] },
+                { "type": "agentMessage", "text": "Fixed the import path." }
+            ] }] } }
+        });
+        let entries = transcript_from_codex_thread(&response);
+        assert_eq!(entries.len(), 4);
+        let draft =
+            condense_transcript("t-1", "codex", &entries, Path::new("/repo")).expect("draft");
+        assert_eq!(
+            draft.findings,
+            vec!["Command failed: `npm run build`".to_string()]
+        );
+        assert_eq!(draft.changed_files, vec!["src/app.ts".to_string()]);
+        assert_eq!(
+            parse_issue_number("https://github.com/o/r/issues/42"),
+            Some(42)
+        );
+        assert!(condense_transcript("t-2", "codex", &[], Path::new("/repo")).is_err());
+    }
+}
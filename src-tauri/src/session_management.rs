@@ -63,6 +63,29 @@ pub(crate) struct WorkspaceSessionCatalogEntry {
     pub(crate) matched_workspace_label: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) folder_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) linked_issues: Option<Vec<SessionLinkedIssue>>,
+}
+
+/// A GitHub issue created from a session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionLinkedIssue {
+    pub(crate) number: u64,
+    pub(crate) url: String,
+    pub(crate) title: String,
+    pub(crate) created_at: i64,
+}
+
+/// A user bookmark on one message of a session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionBookmark {
+    pub(crate) id: String,
+    pub(crate) message_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) note: Option<String>,
+    pub(crate) created_at: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -167,6 +190,10 @@ struct WorkspaceSessionCatalogMetadata {
     folders: Vec<WorkspaceSessionFolder>,
     #[serde(default)]
     folder_id_by_session_id: HashMap<String, String>,
+    #[serde(default)]
+    linked_issues_by_session_id: HashMap<String, Vec<SessionLinkedIssue>>,
+    #[serde(default)]
+    bookmarks_by_session_id: HashMap<String, Vec<SessionBookmark>>,
 }
 
 #[derive(Debug, Clone)]
@@ -576,6 +603,26 @@ pub(crate) async fn get_workspace_session_projection_summary_core(
     })
 }
 
+/// Every catalog entry in the workspace's scope, archived ones included,
+/// plus the history sources that could not be read.
+pub(crate) async fn list_workspace_catalog_entries_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    engine_manager: &engine::EngineManager,
+    storage_path: &Path,
+    workspace_id: &str,
+) -> Result<(Vec<WorkspaceSessionCatalogEntry>, Vec<String>), String> {
+    let workspace_id = normalize_workspace_id(workspace_id)?;
+    let scope_catalog = build_workspace_scope_catalog_data(
+        workspaces,
+        engine_manager,
+        storage_path,
+        &workspace_id,
+        SessionCatalogScanMode::Exhaustive,
+    )
+    .await?;
+    Ok((scope_catalog.entries, scope_catalog.partial_sources))
+}
+
 pub(crate) async fn list_global_codex_sessions_core(
     engine_manager: &engine::EngineManager,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
@@ -971,6 +1018,7 @@ pub(crate) async fn delete_workspace_sessions_core(
         with_catalog_metadata_mutation(storage_path, &workspace_id, |metadata| {
             for session_id in metadata_cleanup_ids {
                 metadata.archived_at_by_session_id.remove(&session_id);
+                metadata.bookmarks_by_session_id.remove(&session_id);
                 let engine = parse_catalog_identity(&session_id).engine_name();
                 remove_folder_assignment_for_session(metadata, &session_id, engine);
             }
@@ -1285,12 +1333,18 @@ fn apply_folder_assignment(
     entry: &mut WorkspaceSessionCatalogEntry,
     metadata_by_workspace_id: &HashMap<String, WorkspaceSessionCatalogMetadata>,
 ) {
-    entry.folder_id = metadata_by_workspace_id
-        .get(&entry.workspace_id)
+    let metadata = metadata_by_workspace_id.get(&entry.workspace_id);
+    entry.folder_id = metadata
         .and_then(|metadata| {
             folder_assignment_for_session(metadata, &entry.session_id, &entry.engine)
         })
         .cloned();
+    entry.linked_issues = metadata.and_then(|metadata| {
+        folder_assignment_keys_for_session(&entry.session_id, &entry.engine)
+            .into_iter()
+            .find_map(|key| metadata.linked_issues_by_session_id.get(&key))
+            .cloned()
+    });
 }
 
 fn folder_assignment_keys_for_session(session_id: &str, engine: &str) -> Vec<String> {
@@ -1547,6 +1601,103 @@ pub(crate) async fn assign_workspace_session_folder_core(
     })
 }
 
+/// Records a GitHub issue created from `session_id` in the workspace session
+/// metadata and returns every issue linked to that session.
+pub(crate) fn link_workspace_session_issue_core(
+    storage_path: &Path,
+    workspace_id: &str,
+    session_id: &str,
+    issue: SessionLinkedIssue,
+) -> Result<Vec<SessionLinkedIssue>, String> {
+    let workspace_id = normalize_workspace_id(workspace_id)?;
+    with_catalog_metadata_mutation(storage_path, &workspace_id, |metadata| {
+        let issues = metadata
+            .linked_issues_by_session_id
+            .entry(session_id.to_string())
+            .or_default();
+        issues.retain(|existing| existing.url != issue.url);
+        issues.push(issue);
+        Ok(issues.clone())
+    })
+}
+
+/// Bookmarks of `session_id`, oldest first.
+pub(crate) fn read_workspace_session_bookmarks(
+    storage_path: &Path,
+    workspace_id: &str,
+    session_id: &str,
+) -> Result<Vec<SessionBookmark>, String> {
+    let workspace_id = normalize_workspace_id(workspace_id)?;
+    Ok(read_catalog_metadata(storage_path, &workspace_id)?
+        .bookmarks_by_session_id
+        .remove(session_id)
+        .unwrap_or_default())
+}
+
+/// Adds a bookmark to `session_id`. A message holds at most one bookmark, so
+/// bookmarking it again replaces the note.
+pub(crate) fn add_workspace_session_bookmark_core(
+    storage_path: &Path,
+    workspace_id: &str,
+    session_id: &str,
+    bookmark: SessionBookmark,
+) -> Result<SessionBookmark, String> {
+    let workspace_id = normalize_workspace_id(workspace_id)?;
+    with_catalog_metadata_mutation(storage_path, &workspace_id, |metadata| {
+        let bookmarks = metadata
+            .bookmarks_by_session_id
+            .entry(session_id.to_string())
+            .or_default();
+        if let Some(existing) = bookmarks
+            .iter_mut()
+            .find(|existing| existing.message_id == bookmark.message_id)
+        {
+            existing.note = bookmark.note;
+            return Ok(existing.clone());
+        }
+        bookmarks.push(bookmark.clone());
+        Ok(bookmark)
+    })
+}
+
+pub(crate) fn remove_workspace_session_bookmark_core(
+    storage_path: &Path,
+    workspace_id: &str,
+    session_id: &str,
+    bookmark_id: &str,
+) -> Result<(), String> {
+    let workspace_id = normalize_workspace_id(workspace_id)?;
+    with_catalog_metadata_mutation(storage_path, &workspace_id, |metadata| {
+        let Some(bookmarks) = metadata.bookmarks_by_session_id.get_mut(session_id) else {
+            return Err("bookmark not found".to_string());
+        };
+        let before = bookmarks.len();
+        bookmarks.retain(|bookmark| bookmark.id != bookmark_id);
+        if bookmarks.len() == before {
+            return Err("bookmark not found".to_string());
+        }
+        if bookmarks.is_empty() {
+            metadata.bookmarks_by_session_id.remove(session_id);
+        }
+        Ok(())
+    })
+}
+
+/// Splits a catalog session id (`claude:<id>`, `<codex thread id>`, ...) into
+/// its engine name and the engine-native id.
+pub(crate) fn split_catalog_session_id(session_id: &str) -> (&'static str, String) {
+    let identity = parse_catalog_identity(session_id);
+    let engine = identity.engine_name();
+    let raw_id = match identity {
+        SessionCatalogIdentity::Codex { session_id }
+        | SessionCatalogIdentity::Claude { session_id }
+        | SessionCatalogIdentity::Gemini { session_id }
+        | SessionCatalogIdentity::OpenCode { session_id }
+        | SessionCatalogIdentity::Shared { session_id } => session_id,
+    };
+    (engine, raw_id)
+}
+
 async fn ensure_session_belongs_to_workspace_scope(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     engine_manager: &engine::EngineManager,
@@ -1776,6 +1927,7 @@ async fn build_global_engine_catalog_entries(
                         matched_workspace_id: Some(workspace.id.clone()),
                         matched_workspace_label: Some(workspace.name.clone()),
                         folder_id: None,
+                        linked_issues: None,
                     };
                     let attribution =
                         resolve_catalog_entry_attribution(&workspaces_snapshot, &entry);
@@ -1854,6 +2006,7 @@ async fn build_global_engine_catalog_entries(
                         matched_workspace_id: Some(workspace.id.clone()),
                         matched_workspace_label: Some(workspace.name.clone()),
                         folder_id: None,
+                        linked_issues: None,
                     };
                     apply_folder_assignment(&mut entry, &metadata_by_workspace_id);
                     entries.push(entry);
@@ -1917,6 +2070,7 @@ fn build_global_codex_catalog_entry(
         matched_workspace_id: None,
         matched_workspace_label: None,
         folder_id: None,
+        linked_issues: None,
     };
     let attribution = resolve_catalog_entry_attribution(workspaces_snapshot, &unresolved_entry);
     let mut entry = apply_attribution_to_entry(unresolved_entry, attribution);
@@ -2359,6 +2513,7 @@ async fn build_workspace_scope_catalog_data(
                         matched_workspace_id: Some(owner_workspace_id.clone()),
                         matched_workspace_label: Some(workspace.name.clone()),
                         folder_id: None,
+                        linked_issues: None,
                     };
                     apply_folder_assignment(&mut entry, &metadata_by_workspace_id);
                     entry
@@ -2418,6 +2573,7 @@ async fn build_workspace_scope_catalog_data(
                         matched_workspace_id: Some(owner_workspace_id.clone()),
                         matched_workspace_label: Some(workspace.name.clone()),
                         folder_id: None,
+                        linked_issues: None,
                     };
                     apply_folder_assignment(&mut entry, &metadata_by_workspace_id);
                     entry
@@ -2473,6 +2629,7 @@ async fn build_workspace_scope_catalog_data(
                         matched_workspace_id: Some(owner_workspace_id.clone()),
                         matched_workspace_label: Some(workspace.name.clone()),
                         folder_id: None,
+                        linked_issues: None,
                     };
                     apply_folder_assignment(&mut entry, &metadata_by_workspace_id);
                     entry
@@ -2526,6 +2683,7 @@ async fn build_workspace_scope_catalog_data(
                         matched_workspace_id: Some(owner_workspace_id.clone()),
                         matched_workspace_label: Some(workspace.name.clone()),
                         folder_id: None,
+                        linked_issues: None,
                     };
                     apply_folder_assignment(&mut entry, &metadata_by_workspace_id);
                     entry
@@ -60,6 +60,7 @@
             matched_workspace_id: None,
             matched_workspace_label: None,
             folder_id: None,
+            linked_issues: None,
         }
     }
 
@@ -480,6 +481,84 @@
         assert_eq!(entry.folder_id.as_deref(), Some("folder-1"));
     }
 
+    #[test]
+    fn linked_issues_are_deduped_by_url_and_applied_to_catalog_entries() {
+        let base = std::env::temp_dir().join(format!("session-issues-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&base).expect("create temp dir");
+        let storage_path = base.join("workspaces.json");
+        std::fs::write(&storage_path, "[]").expect("seed storage path");
+        let issue = |title: &str| SessionLinkedIssue {
+            number: 7,
+            url: "https://github.com/o/r/issues/7".to_string(),
+            title: title.to_string(),
+            created_at: 1,
+        };
+
+        link_workspace_session_issue_core(&storage_path, "ws-1", "claude:1", issue("first"))
+            .expect("link issue");
+        let linked =
+            link_workspace_session_issue_core(&storage_path, "ws-1", "claude:1", issue("edited"))
+                .expect("relink issue");
+        assert_eq!(linked.len(), 1);
+        assert_eq!(linked[0].title, "edited");
+
+        let metadata = read_catalog_metadata(&storage_path, "ws-1").expect("read metadata");
+        let mut entry = catalog_entry("claude:1", "ws-1", Some("Workspace"), None);
+        apply_folder_assignment(&mut entry, &HashMap::from([("ws-1".to_string(), metadata)]));
+        assert_eq!(entry.linked_issues.map(|issues| issues.len()), Some(1));
+
+        std::fs::remove_dir_all(base).ok();
+    }
+
+    #[test]
+    fn bookmarks_are_unique_per_message_and_removable() {
+        let base = std::env::temp_dir().join(format!("session-bookmarks-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&base).expect("create temp dir");
+        let storage_path = base.join("workspaces.json");
+        std::fs::write(&storage_path, "[]").expect("seed storage path");
+        let bookmark = |id: &str, note: &str| SessionBookmark {
+            id: id.to_string(),
+            message_id: "m-1".to_string(),
+            note: Some(note.to_string()),
+            created_at: 1,
+        };
+
+        add_workspace_session_bookmark_core(
+            &storage_path,
+            "ws-1",
+            "claude:1",
+            bookmark("b-1", "first"),
+        )
+        .expect("add bookmark");
+        let updated = add_workspace_session_bookmark_core(
+            &storage_path,
+            "ws-1",
+            "claude:1",
+            bookmark("b-2", "edited"),
+        )
+        .expect("re-add bookmark");
+        assert_eq!(updated.id, "b-1");
+        assert_eq!(updated.note.as_deref(), Some("edited"));
+        assert_eq!(
+            read_workspace_session_bookmarks(&storage_path, "ws-1", "claude:1")
+                .expect("read bookmarks")
+                .len(),
+            1
+        );
+
+        remove_workspace_session_bookmark_core(&storage_path, "ws-1", "claude:1", "b-1")
+            .expect("remove bookmark");
+        assert!(
+            remove_workspace_session_bookmark_core(&storage_path, "ws-1", "claude:1", "b-1")
+                .is_err()
+        );
+        assert!(read_workspace_session_bookmarks(&storage_path, "ws-1", "claude:1")
+            .expect("read bookmarks")
+            .is_empty());
+
+        std::fs::remove_dir_all(base).ok();
+    }
+
     #[test]
     fn codex_folder_assignment_accepts_raw_and_prefixed_session_keys() {
         let mut raw_entry = catalog_entry("codex-1", "ws-1", Some("Workspace"), None);
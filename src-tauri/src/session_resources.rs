@@ -0,0 +1,448 @@
+//! Per-process CPU and memory sampling for engine runtimes and terminals.
+//!
+//! Each tracked root process is expanded to its whole process tree so that
+//! helpers spawned by a CLI (node workers, MCP servers, shells) are charged to
+//! the session that started them.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::state::AppState;
+
+const SESSION_RESOURCES_EVENT: &str = "session-resources";
+const DEFAULT_SAMPLE_INTERVAL_MS: u64 = 3_000;
+const MIN_SAMPLE_INTERVAL_MS: u64 = 1_000;
+const MAX_SAMPLE_INTERVAL_MS: u64 = 60_000;
+/// A session above either threshold is flagged so the UI can surface it.
+const HIGH_CPU_PERCENT: f64 = 90.0;
+const HIGH_MEMORY_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+struct ProcessUsageRow {
+    pid: u32,
+    ppid: u32,
+    /// Cumulative CPU time in seconds, when the platform reports it.
+    cpu_time_secs: Option<f64>,
+    /// Instantaneous (or lifetime average) CPU percent reported by the OS.
+    reported_cpu_percent: f64,
+    memory_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionResourceUsage {
+    kind: &'static str,
+    workspace_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    engine: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    terminal_id: Option<String>,
+    root_pid: u32,
+    process_count: u32,
+    cpu_percent: f64,
+    memory_bytes: u64,
+    high_usage: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionResourcesSnapshot {
+    sampled_at_ms: i64,
+    sessions: Vec<SessionResourceUsage>,
+    total_cpu_percent: f64,
+    total_memory_bytes: u64,
+}
+
+struct SampleTarget {
+    kind: &'static str,
+    workspace_id: String,
+    engine: Option<String>,
+    terminal_id: Option<String>,
+    root_pid: u32,
+}
+
+/// CPU time observed at the previous sample, used to turn cumulative CPU time
+/// into a percentage over the sampling window.
+static PREVIOUS_CPU_TIMES: OnceLock<Mutex<(Instant, HashMap<u32, f64>)>> = OnceLock::new();
+static MONITOR_TASK: OnceLock<Mutex<Option<JoinHandle<()>>>> = OnceLock::new();
+
+/// Parses `ps` cumulative CPU time: `[[dd-]hh:]mm:ss[.frac]`.
+fn parse_cpu_time(value: &str) -> Option<f64> {
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<f64>().ok()?, clock),
+        None => (0.0, value),
+    };
+    let mut seconds = 0.0;
+    for part in clock.split(':') {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(days * 86_400.0 + seconds)
+}
+
+fn parse_unix_usage_rows(stdout: &str) -> Vec<ProcessUsageRow> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid = parts.next()?.parse().ok()?;
+            let ppid = parts.next()?.parse().ok()?;
+            let reported_cpu_percent = parts.next()?.parse().ok()?;
+            let rss_kib: u64 = parts.next()?.parse().ok()?;
+            let cpu_time_secs = parts.next().and_then(parse_cpu_time);
+            Some(ProcessUsageRow {
+                pid,
+                ppid,
+                cpu_time_secs,
+                reported_cpu_percent,
+                memory_bytes: rss_kib.saturating_mul(1024),
+            })
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn snapshot_usage_rows() -> Option<Vec<ProcessUsageRow>> {
+    let output = crate::utils::std_command("ps")
+        .args(["-axo", "pid=,ppid=,pcpu=,rss=,time="])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_unix_usage_rows(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+#[cfg(windows)]
+fn snapshot_usage_rows() -> Option<Vec<ProcessUsageRow>> {
+    use serde_json::Value;
+
+    let output = crate::utils::std_command("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_PerfFormattedData_PerfProc_Process | Select-Object IDProcess,CreatingProcessID,PercentProcessorTime,WorkingSetPrivate | ConvertTo-Json -Compress",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let payload: Value = serde_json::from_slice(&output.stdout).ok()?;
+    let rows = payload
+        .as_array()
+        .cloned()
+        .unwrap_or_else(|| vec![payload.clone()]);
+    let cores = std::thread::available_parallelism()
+        .map(|count| count.get() as f64)
+        .unwrap_or(1.0);
+    Some(
+        rows.iter()
+            .filter_map(|row| {
+                let pid = u32::try_from(row.get("IDProcess")?.as_u64()?).ok()?;
+                if pid == 0 {
+                    return None;
+                }
+                Some(ProcessUsageRow {
+                    pid,
+                    ppid: row
+                        .get("CreatingProcessID")
+                        .and_then(Value::as_u64)
+                        .and_then(|value| u32::try_from(value).ok())
+                        .unwrap_or(0),
+                    cpu_time_secs: None,
+                    // Perf counters report per-core percentages.
+                    reported_cpu_percent: row
+                        .get("PercentProcessorTime")
+                        .and_then(Value::as_f64)
+                        .unwrap_or(0.0)
+                        / cores,
+                    memory_bytes: row
+                        .get("WorkingSetPrivate")
+                        .and_then(Value::as_u64)
+                        .unwrap_or(0),
+                })
+            })
+            .collect(),
+    )
+}
+
+#[cfg(not(any(unix, windows)))]
+fn snapshot_usage_rows() -> Option<Vec<ProcessUsageRow>> {
+    None
+}
+
+/// Converts cumulative CPU time into a percentage using the previous sample;
+/// falls back to the OS-reported figure for processes seen for the first time.
+fn cpu_percent_by_pid(rows: &[ProcessUsageRow], now: Instant) -> HashMap<u32, f64> {
+    let cache = PREVIOUS_CPU_TIMES.get_or_init(|| Mutex::new((now, HashMap::new())));
+    let mut previous = match cache.lock() {
+        Ok(previous) => previous,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let elapsed = now.duration_since(previous.0).as_secs_f64();
+    let mut current_times = HashMap::new();
+    let percents = rows
+        .iter()
+        .map(|row| {
+            let percent = match (row.cpu_time_secs, previous.1.get(&row.pid)) {
+                (Some(current), Some(before)) if elapsed > 0.0 && current >= *before => {
+                    (current - before) / elapsed * 100.0
+                }
+                _ => row.reported_cpu_percent,
+            };
+            if let Some(current) = row.cpu_time_secs {
+                current_times.insert(row.pid, current);
+            }
+            (row.pid, percent)
+        })
+        .collect();
+    *previous = (now, current_times);
+    percents
+}
+
+fn summarize_targets(
+    targets: Vec<SampleTarget>,
+    rows: &[ProcessUsageRow],
+    cpu_by_pid: &HashMap<u32, f64>,
+) -> Vec<SessionResourceUsage> {
+    let rows_by_pid = rows
+        .iter()
+        .map(|row| (row.pid, row))
+        .collect::<HashMap<_, _>>();
+    let mut parent_to_children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for row in rows {
+        if row.ppid != row.pid {
+            parent_to_children
+                .entry(row.ppid)
+                .or_default()
+                .push(row.pid);
+        }
+    }
+
+    targets
+        .into_iter()
+        .filter(|target| rows_by_pid.contains_key(&target.root_pid))
+        .map(|target| {
+            let mut stack = vec![target.root_pid];
+            let mut visited = HashSet::new();
+            let mut cpu_percent = 0.0;
+            let mut memory_bytes = 0u64;
+            while let Some(pid) = stack.pop() {
+                if !visited.insert(pid) {
+                    continue;
+                }
+                if let Some(row) = rows_by_pid.get(&pid) {
+                    cpu_percent += cpu_by_pid.get(&pid).copied().unwrap_or(0.0);
+                    memory_bytes = memory_bytes.saturating_add(row.memory_bytes);
+                }
+                if let Some(children) = parent_to_children.get(&pid) {
+                    stack.extend(children.iter().copied());
+                }
+            }
+            let cpu_percent = (cpu_percent * 10.0).round() / 10.0;
+            SessionResourceUsage {
+                kind: target.kind,
+                workspace_id: target.workspace_id,
+                engine: target.engine,
+                terminal_id: target.terminal_id,
+                root_pid: target.root_pid,
+                process_count: visited.len() as u32,
+                cpu_percent,
+                memory_bytes,
+                high_usage: cpu_percent >= HIGH_CPU_PERCENT || memory_bytes >= HIGH_MEMORY_BYTES,
+            }
+        })
+        .collect()
+}
+
+async fn collect_sample_targets(state: &AppState) -> Vec<SampleTarget> {
+    let mut targets = state
+        .runtime_manager
+        .tracked_runtime_processes()
+        .await
+        .into_iter()
+        .map(|(engine, workspace_id, pid)| SampleTarget {
+            kind: "engine",
+            workspace_id,
+            engine: Some(engine),
+            terminal_id: None,
+            root_pid: pid,
+        })
+        .collect::<Vec<_>>();
+
+    let terminals = state
+        .terminal_sessions
+        .lock()
+        .await
+        .iter()
+        .map(|(key, session)| (key.clone(), session.clone()))
+        .collect::<Vec<_>>();
+    for (key, session) in terminals {
+        let Some(pid) = session.child.lock().await.process_id() else {
+            continue;
+        };
+        let workspace_id = key
+            .strip_suffix(&format!(":{}", session.id))
+            .unwrap_or(&key)
+            .to_string();
+        targets.push(SampleTarget {
+            kind: "terminal",
+            workspace_id,
+            engine: None,
+            terminal_id: Some(session.id.clone()),
+            root_pid: pid,
+        });
+    }
+    targets
+}
+
+async fn sample_session_resources(state: &AppState) -> Result<SessionResourcesSnapshot, String> {
+    let targets = collect_sample_targets(state).await;
+    let sampled_at_ms = chrono::Utc::now().timestamp_millis();
+    if targets.is_empty() {
+        return Ok(SessionResourcesSnapshot {
+            sampled_at_ms,
+            sessions: Vec::new(),
+            total_cpu_percent: 0.0,
+            total_memory_bytes: 0,
+        });
+    }
+    let rows = tokio::task::spawn_blocking(snapshot_usage_rows)
+        .await
+        .map_err(|error| format!("Failed to sample processes: {error}"))?
+        .ok_or("Process sampling is unavailable on this platform")?;
+    let cpu_by_pid = cpu_percent_by_pid(&rows, Instant::now());
+    let sessions = summarize_targets(targets, &rows, &cpu_by_pid);
+    Ok(SessionResourcesSnapshot {
+        sampled_at_ms,
+        total_cpu_percent: sessions.iter().map(|session| session.cpu_percent).sum(),
+        total_memory_bytes: sessions.iter().map(|session| session.memory_bytes).sum(),
+        sessions,
+    })
+}
+
+fn monitor_slot() -> std::sync::MutexGuard<'static, Option<JoinHandle<()>>> {
+    let slot = MONITOR_TASK.get_or_init(|| Mutex::new(None));
+    match slot.lock() {
+        Ok(slot) => slot,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_session_resources(
+    state: State<'_, AppState>,
+) -> Result<SessionResourcesSnapshot, String> {
+    sample_session_resources(&state).await
+}
+
+/// Starts (or restarts with a new interval) the background sampler that emits
+/// `session-resources` events.
+#[tauri::command]
+pub(crate) async fn start_session_resources_monitor(
+    interval_ms: Option<u64>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let interval = Duration::from_millis(
+        interval_ms
+            .unwrap_or(DEFAULT_SAMPLE_INTERVAL_MS)
+            .clamp(MIN_SAMPLE_INTERVAL_MS, MAX_SAMPLE_INTERVAL_MS),
+    );
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            match sample_session_resources(&state).await {
+                Ok(snapshot) => {
+                    let _ = app.emit(SESSION_RESOURCES_EVENT, snapshot);
+                }
+                Err(error) => {
+                    log::warn!("[session-resources] sampling failed: {error}");
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
+    if let Some(previous) = monitor_slot().replace(task) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn stop_session_resources_monitor() -> Result<(), String> {
+    if let Some(task) = monitor_slot().take() {
+        task.abort();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pid: u32, ppid: u32, memory_bytes: u64) -> ProcessUsageRow {
+        ProcessUsageRow {
+            pid,
+            ppid,
+            cpu_time_secs: None,
+            reported_cpu_percent: 10.0,
+            memory_bytes,
+        }
+    }
+
+    #[test]
+    fn parses_ps_rows_and_cpu_time_formats() {
+        let rows = parse_unix_usage_rows(
+            "  42     1  12.5  2048 01:02:03\n  43    42   0.0   512 1-00:00:01\nbogus line\n",
+        );
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].memory_bytes, 2048 * 1024);
+        assert_eq!(rows[0].cpu_time_secs, Some(3723.0));
+        assert_eq!(rows[1].cpu_time_secs, Some(86_401.0));
+        assert_eq!(parse_cpu_time("0:01.50"), Some(1.5));
+        assert_eq!(parse_cpu_time("x:01"), None);
+    }
+
+    #[test]
+    fn aggregates_usage_across_process_tree() {
+        let rows = vec![
+            row(10, 1, 100),
+            row(11, 10, 50),
+            row(12, 11, 25),
+            row(20, 1, 1000),
+        ];
+        let cpu = rows
+            .iter()
+            .map(|row| (row.pid, row.reported_cpu_percent))
+            .collect();
+        let targets = vec![
+            SampleTarget {
+                kind: "engine",
+                workspace_id: "ws".into(),
+                engine: Some("codex".into()),
+                terminal_id: None,
+                root_pid: 10,
+            },
+            SampleTarget {
+                kind: "terminal",
+                workspace_id: "ws".into(),
+                engine: None,
+                terminal_id: Some("t1".into()),
+                root_pid: 99,
+            },
+        ];
+        let usage = summarize_targets(targets, &rows, &cpu);
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].process_count, 3);
+        assert_eq!(usage[0].memory_bytes, 175);
+        assert_eq!(usage[0].cpu_percent, 30.0);
+        assert!(!usage[0].high_usage);
+    }
+}
@@ -0,0 +1,366 @@
+//! Live per-session statistics for the status bar.
+//!
+//! Token usage, turn boundaries, streamed assistant text and file changes
+//! seen on the app-server event stream are folded into one running record
+//! per thread. Updates are emitted as `session-stats`, throttled while text
+//! streams, and `session_stats` returns the current snapshot, so the UI never
+//! has to re-derive the numbers from the raw events.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::backend::events::AppServerEvent;
+
+const SESSION_STATS_EVENT: &str = "session-stats";
+/// Minimum gap between emits caused by streamed text alone.
+const DELTA_EMIT_INTERVAL_MS: i64 = 500;
+const MAX_TRACKED_SESSIONS: usize = 256;
+const TRACKED_METHODS: &[&str] = &[
+    "turn/started",
+    "turn/completed",
+    "turn/error",
+    "item/agentMessage/delta",
+    "item/completed",
+    "thread/tokenUsage/updated",
+    "token_count",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionStats {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) input_tokens: u64,
+    pub(crate) output_tokens: u64,
+    pub(crate) cached_input_tokens: u64,
+    /// Words in streamed assistant text, counted as the deltas arrive.
+    pub(crate) output_words: u64,
+    pub(crate) turns: u32,
+    pub(crate) turn_active: bool,
+    pub(crate) files_touched: usize,
+    pub(crate) started_at_ms: i64,
+    pub(crate) updated_at_ms: i64,
+    /// Wall-clock time since the first event of the session.
+    pub(crate) elapsed_ms: i64,
+    /// Time spent inside turns, including the running one.
+    pub(crate) busy_ms: i64,
+}
+
+/// Running counters behind a `SessionStats` snapshot.
+#[derive(Debug, Default)]
+struct SessionTally {
+    stats: SessionStats,
+    /// Engines report either a running thread total or a per-turn total that
+    /// restarts from zero; a drop in the reported value folds the previous
+    /// value into the baseline so both add up.
+    token_baseline: [u64; 3],
+    token_reported: [u64; 3],
+    touched_paths: HashSet<String>,
+    in_word: bool,
+    turn_started_at_ms: Option<i64>,
+    completed_busy_ms: i64,
+    last_emitted_at_ms: i64,
+}
+
+impl SessionTally {
+    fn record_tokens(&mut self, reported: [u64; 3]) {
+        for (index, value) in reported.into_iter().enumerate() {
+            if value < self.token_reported[index] {
+                self.token_baseline[index] += self.token_reported[index];
+            }
+            self.token_reported[index] = value;
+        }
+        self.stats.input_tokens = self.token_baseline[0] + self.token_reported[0];
+        self.stats.output_tokens = self.token_baseline[1] + self.token_reported[1];
+        self.stats.cached_input_tokens = self.token_baseline[2] + self.token_reported[2];
+    }
+
+    fn record_text(&mut self, text: &str) {
+        for ch in text.chars() {
+            let is_word = !ch.is_whitespace();
+            if is_word && !self.in_word {
+                self.stats.output_words += 1;
+            }
+            self.in_word = is_word;
+        }
+    }
+
+    fn start_turn(&mut self, now_ms: i64) {
+        if self.turn_started_at_ms.is_none() {
+            self.stats.turns += 1;
+            self.turn_started_at_ms = Some(now_ms);
+        }
+        self.in_word = false;
+    }
+
+    fn finish_turn(&mut self, now_ms: i64) {
+        if let Some(started_at) = self.turn_started_at_ms.take() {
+            self.completed_busy_ms += (now_ms - started_at).max(0);
+        }
+        self.in_word = false;
+    }
+
+    fn snapshot(&self, now_ms: i64) -> SessionStats {
+        let running = self
+            .turn_started_at_ms
+            .map(|started_at| (now_ms - started_at).max(0))
+            .unwrap_or(0);
+        SessionStats {
+            turn_active: self.turn_started_at_ms.is_some(),
+            files_touched: self.touched_paths.len(),
+            elapsed_ms: (now_ms - self.stats.started_at_ms).max(0),
+            busy_ms: self.completed_busy_ms + running,
+            ..self.stats.clone()
+        }
+    }
+}
+
+fn sessions() -> std::sync::MutexGuard<'static, HashMap<String, SessionTally>> {
+    static SESSIONS: OnceLock<Mutex<HashMap<String, SessionTally>>> = OnceLock::new();
+    SESSIONS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn session_key(workspace_id: &str, thread_id: &str) -> String {
+    format!("{workspace_id}\u{0}{thread_id}")
+}
+
+pub(crate) fn params_string(params: &Value, pointers: &[&str]) -> Option<String> {
+    pointers.iter().find_map(|pointer| {
+        params
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    })
+}
+
+fn read_count(value: &Value, keys: &[&str]) -> u64 {
+    keys.iter()
+        .find_map(|key| {
+            let field = value.get(*key)?;
+            field
+                .as_u64()
+                .or_else(|| field.as_f64().map(|number| number.max(0.0) as u64))
+        })
+        .unwrap_or(0)
+}
+
+/// Input, output and cached-input totals from a `thread/tokenUsage/updated`
+/// or Codex `token_count` event.
+pub(crate) fn reported_tokens(method: &str, params: &Value) -> Option<[u64; 3]> {
+    let total = match method {
+        "thread/tokenUsage/updated" => params
+            .get("tokenUsage")
+            .or_else(|| params.get("token_usage"))?
+            .get("total"),
+        "token_count" => {
+            let info = params.get("info")?;
+            info.get("total_token_usage")
+                .or_else(|| info.get("totalTokenUsage"))
+        }
+        _ => None,
+    }
+    .filter(|value| value.is_object())?;
+    Some([
+        read_count(total, &["inputTokens", "input_tokens"]),
+        read_count(total, &["outputTokens", "output_tokens"]),
+        read_count(
+            total,
+            &[
+                "cachedInputTokens",
+                "cached_input_tokens",
+                "cacheReadInputTokens",
+                "cache_read_input_tokens",
+            ],
+        ),
+    ])
+}
+
+fn changed_paths(item: &Value) -> Vec<String> {
+    if item.get("type").and_then(Value::as_str) != Some("fileChange") {
+        return Vec::new();
+    }
+    let mut paths: Vec<String> = item
+        .get("changes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|change| change.get("path").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect();
+    paths.extend(params_string(
+        item,
+        &[
+            "/arguments/file_path",
+            "/arguments/path",
+            "/input/file_path",
+        ],
+    ));
+    paths
+}
+
+/// Folds one event into its session's tally. Returns the snapshot to emit,
+/// or `None` when nothing changed or a text-only update is throttled.
+fn apply_event(workspace_id: &str, message: &Value, now_ms: i64) -> Option<SessionStats> {
+    let method = message
+        .get("method")
+        .and_then(Value::as_str)
+        .filter(|method| TRACKED_METHODS.contains(method))?;
+    let params = message.get("params")?;
+    let thread_id = params_string(params, &["/threadId", "/thread/id", "/thread_id"])?;
+    let mut text_only = false;
+    let mut sessions = sessions();
+    let key = session_key(workspace_id, &thread_id);
+    if !sessions.contains_key(&key) && sessions.len() >= MAX_TRACKED_SESSIONS {
+        let stalest = sessions
+            .iter()
+            .min_by_key(|(_, tally)| tally.stats.updated_at_ms)
+            .map(|(key, _)| key.clone());
+        if let Some(stalest) = stalest {
+            sessions.remove(&stalest);
+        }
+    }
+    let tally = sessions.entry(key).or_insert_with(|| SessionTally {
+        stats: SessionStats {
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.clone(),
+            started_at_ms: now_ms,
+            ..SessionStats::default()
+        },
+        ..SessionTally::default()
+    });
+    match method {
+        "turn/started" => tally.start_turn(now_ms),
+        "turn/completed" | "turn/error" => tally.finish_turn(now_ms),
+        "item/agentMessage/delta" => {
+            let delta = params.get("delta").and_then(Value::as_str)?;
+            tally.record_text(delta);
+            text_only = true;
+        }
+        "item/completed" => {
+            let paths = changed_paths(params.get("item")?);
+            if paths.is_empty() {
+                return None;
+            }
+            tally.touched_paths.extend(paths);
+        }
+        _ => tally.record_tokens(reported_tokens(method, params)?),
+    }
+    tally.stats.updated_at_ms = now_ms;
+    if text_only && now_ms - tally.last_emitted_at_ms < DELTA_EMIT_INTERVAL_MS {
+        return None;
+    }
+    tally.last_emitted_at_ms = now_ms;
+    Some(tally.snapshot(now_ms))
+}
+
+/// Updates the session's statistics from an app-server event and emits the
+/// new snapshot as `session-stats`.
+pub(crate) fn observe_app_server_event(app: &AppHandle, event: &AppServerEvent) {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    if let Some(stats) = apply_event(&event.workspace_id, &event.message, now_ms) {
+        let _ = app.emit(SESSION_STATS_EVENT, stats);
+    }
+}
+
+/// Current statistics of a session, `None` before its first event.
+#[tauri::command]
+pub(crate) fn session_stats(workspace_id: String, thread_id: String) -> Option<SessionStats> {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    sessions()
+        .get(&session_key(&workspace_id, &thread_id))
+        .map(|tally| tally.snapshot(now_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn workspace() -> String {
+        format!("ws-{}", uuid::Uuid::new_v4())
+    }
+
+    #[test]
+    fn tallies_turns_tokens_words_and_files() {
+        let workspace_id = workspace();
+        let event = |message: Value, now_ms: i64| apply_event(&workspace_id, &message, now_ms);
+
+        event(
+            json!({"method": "turn/started", "params": {"threadId": "t1"}}),
+            1_000,
+        )
+        .expect("turn start emits");
+        event(
+            json!({"method": "item/agentMessage/delta", "params": {"threadId": "t1", "delta": "Hello wor"}}),
+            1_600,
+        )
+        .expect("first delta emits");
+        // Throttled, but still counted; the split word is counted once.
+        assert!(event(
+            json!({"method": "item/agentMessage/delta", "params": {"threadId": "t1", "delta": "ld again"}}),
+            1_700,
+        )
+        .is_none());
+        event(
+            json!({"method": "item/completed", "params": {"threadId": "t1", "item": {
+                "type": "fileChange",
+                "changes": [{"path": "src/a.rs"}, {"path": "src/b.rs"}]
+            }}}),
+            1_800,
+        )
+        .expect("file change emits");
+        event(
+            json!({"method": "thread/tokenUsage/updated", "params": {"threadId": "t1", "tokenUsage": {
+                "total": {"inputTokens": 100, "outputTokens": 20, "cachedInputTokens": 5}
+            }}}),
+            1_900,
+        )
+        .expect("usage emits");
+        let stats = event(
+            json!({"method": "turn/completed", "params": {"threadId": "t1"}}),
+            3_000,
+        )
+        .expect("turn completion emits");
+
+        assert_eq!(stats.turns, 1);
+        assert!(!stats.turn_active);
+        assert_eq!(stats.output_words, 3);
+        assert_eq!(stats.files_touched, 2);
+        assert_eq!(
+            (
+                stats.input_tokens,
+                stats.output_tokens,
+                stats.cached_input_tokens
+            ),
+            (100, 20, 5)
+        );
+        assert_eq!(stats.busy_ms, 2_000);
+        assert_eq!(stats.elapsed_ms, 2_000);
+    }
+
+    #[test]
+    fn per_turn_token_totals_accumulate_across_turns() {
+        let workspace_id = workspace();
+        let usage = |input: u64, output: u64| {
+            json!({"method": "thread/tokenUsage/updated", "params": {"threadId": "t1", "tokenUsage": {
+                "total": {"inputTokens": input, "outputTokens": output}
+            }}})
+        };
+        apply_event(&workspace_id, &usage(50, 10), 1);
+        apply_event(&workspace_id, &usage(80, 15), 2);
+        let stats = apply_event(&workspace_id, &usage(30, 5), 3).expect("usage emits");
+        assert_eq!((stats.input_tokens, stats.output_tokens), (110, 20));
+
+        let snapshot = session_stats(workspace_id, "t1".to_string()).expect("snapshot");
+        assert_eq!(snapshot.input_tokens, 110);
+    }
+}
@@ -3,12 +3,14 @@ use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tauri::{Manager, State, Window};
+use tauri::{AppHandle, Manager, State, Window};
 
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
 use crate::event_sink::TauriEventSink;
 use crate::remote_backend;
+use crate::shared::access_presets::{self, AccessModePreset, AccessModePresetInfo};
+use crate::shared::config_journal::{self, ConfigJournalEntry};
 use crate::shared::settings_core::{
     app_settings_change_requires_codex_restart, get_app_settings_core, get_codex_config_path_core,
     get_codex_unified_exec_external_status_core, resolve_window_theme_preference,
@@ -29,9 +31,12 @@ pub(crate) struct CodexRuntimeReloadResult {
     message: Option<String>,
 }
 
-async fn spawn_reloaded_codex_sessions(
+/// Spawns fresh Codex sessions for the connected workspaces, or only for
+/// `workspace_ids` when given, without swapping them in yet.
+pub(crate) async fn spawn_reloaded_codex_sessions(
     state: &AppState,
-    window: &Window,
+    app: &AppHandle,
+    workspace_ids: Option<&[String]>,
 ) -> Result<Vec<(String, Arc<crate::backend::app_server::WorkspaceSession>)>, String> {
     let (connected_entries, workspace_index): (
         Vec<WorkspaceEntry>,
@@ -42,6 +47,7 @@ async fn spawn_reloaded_codex_sessions(
         let connected_entries = workspaces
             .values()
             .filter(|entry| sessions.contains_key(&entry.id))
+            .filter(|entry| workspace_ids.is_none_or(|ids| ids.contains(&entry.id)))
             .cloned()
             .collect::<Vec<_>>();
         (connected_entries, workspaces.clone())
@@ -75,7 +81,7 @@ async fn spawn_reloaded_codex_sessions(
             env!("CARGO_PKG_VERSION").to_string(),
             f64::from(app_settings_snapshot.codex_auto_compaction_threshold_percent),
             auto_compaction_enabled,
-            TauriEventSink::new(window.app_handle().clone()),
+            TauriEventSink::new(app.clone()),
         )
         .await
         {
@@ -202,7 +208,7 @@ pub(crate) async fn reload_codex_runtime_config(
     }
 
     let _reload_guard = state.codex_runtime_reload_lock.lock().await;
-    let staged_sessions = spawn_reloaded_codex_sessions(&state, &window).await?;
+    let staged_sessions = spawn_reloaded_codex_sessions(&state, window.app_handle(), None).await?;
     if staged_sessions.is_empty() {
         return Ok(CodexRuntimeReloadResult {
             status: "applied".to_string(),
@@ -232,3 +238,68 @@ pub(crate) async fn reload_codex_runtime_config(
         message: None,
     })
 }
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceAccessModePreset {
+    preset: AccessModePreset,
+    /// `workspace` when the workspace overrides the app default, else `app`.
+    source: &'static str,
+}
+
+#[tauri::command]
+pub(crate) async fn list_access_mode_presets() -> Result<Vec<AccessModePresetInfo>, String> {
+    Ok(access_presets::list_access_mode_presets())
+}
+
+#[tauri::command]
+pub(crate) async fn get_workspace_access_mode_preset(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceAccessModePreset, String> {
+    let workspace_preset = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .settings
+            .access_mode_preset
+            .as_deref()
+            .and_then(AccessModePreset::parse)
+    };
+    if let Some(preset) = workspace_preset {
+        return Ok(WorkspaceAccessModePreset {
+            preset,
+            source: "workspace",
+        });
+    }
+    let app_default = state.app_settings.lock().await.default_access_mode.clone();
+    Ok(WorkspaceAccessModePreset {
+        preset: AccessModePreset::resolve(Some(&app_default)),
+        source: "app",
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn list_config_versions(
+    path: Option<String>,
+) -> Result<Vec<ConfigJournalEntry>, String> {
+    config_journal::list_config_versions(path.as_deref())
+}
+
+/// Rolls a config file back to its previous journaled version. Restoring the
+/// app settings file also reloads the in-memory settings.
+#[tauri::command]
+pub(crate) async fn restore_previous_version(
+    path: String,
+    version_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ConfigJournalEntry, String> {
+    let restored = config_journal::restore_previous_version(&path, version_id.as_deref())?;
+    if std::path::Path::new(&restored.path) == state.settings_path.as_path() {
+        let settings = crate::storage::read_settings(&state.settings_path)?;
+        *state.app_settings.lock().await = settings;
+        state.sync_engine_configs_from_settings().await;
+    }
+    Ok(restored)
+}
@@ -0,0 +1,221 @@
+//! Structured access mode presets shared by the engine adapters.
+//!
+//! `access_mode` travels through the app as a plain string. Presets give those
+//! strings a fixed vocabulary and a single per-engine mapping, while still
+//! accepting the legacy values (`current`, `default`, `full-access`).
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum AccessModePreset {
+    ReadOnly,
+    AskBeforeWrite,
+    AutoEdit,
+    FullAuto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodexSandboxKind {
+    ReadOnly,
+    WorkspaceWrite,
+    DangerFullAccess,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AccessModePresetInfo {
+    id: AccessModePreset,
+    label: &'static str,
+    description: &'static str,
+    claude_permission_mode: &'static str,
+    codex_sandbox: &'static str,
+    codex_approval_policy: &'static str,
+    gemini_approval_mode: &'static str,
+}
+
+impl AccessModePreset {
+    pub(crate) const ALL: [AccessModePreset; 4] = [
+        AccessModePreset::ReadOnly,
+        AccessModePreset::AskBeforeWrite,
+        AccessModePreset::AutoEdit,
+        AccessModePreset::FullAuto,
+    ];
+
+    /// Fallback when neither the request nor the workspace names a mode;
+    /// matches the historical `current` behavior.
+    pub(crate) const FALLBACK: AccessModePreset = AccessModePreset::AutoEdit;
+
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            AccessModePreset::ReadOnly => "read-only",
+            AccessModePreset::AskBeforeWrite => "ask-before-write",
+            AccessModePreset::AutoEdit => "auto-edit",
+            AccessModePreset::FullAuto => "full-auto",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            AccessModePreset::ReadOnly => "Read only",
+            AccessModePreset::AskBeforeWrite => "Ask before write",
+            AccessModePreset::AutoEdit => "Auto edit",
+            AccessModePreset::FullAuto => "Full auto",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            AccessModePreset::ReadOnly => "Plan and read files only; nothing is written or executed.",
+            AccessModePreset::AskBeforeWrite => {
+                "Edits and commands ask for approval; Codex keeps its workspace sandbox."
+            }
+            AccessModePreset::AutoEdit => {
+                "File edits inside the workspace are applied automatically; risky commands still ask."
+            }
+            AccessModePreset::FullAuto => "No permission prompts and no sandbox restrictions.",
+        }
+    }
+
+    /// Parses a preset id or one of the legacy access mode strings.
+    pub(crate) fn parse(value: &str) -> Option<AccessModePreset> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "read-only" | "readonly" | "plan" | "suggest-edit" => Some(AccessModePreset::ReadOnly),
+            "ask-before-write" | "default" => Some(AccessModePreset::AskBeforeWrite),
+            "auto-edit" | "current" => Some(AccessModePreset::AutoEdit),
+            "full-auto" | "full-access" => Some(AccessModePreset::FullAuto),
+            _ => None,
+        }
+    }
+
+    /// Resolves an optional access mode string, falling back to
+    /// [`AccessModePreset::FALLBACK`] for missing or unknown values.
+    pub(crate) fn resolve(value: Option<&str>) -> AccessModePreset {
+        value
+            .and_then(AccessModePreset::parse)
+            .unwrap_or(AccessModePreset::FALLBACK)
+    }
+
+    pub(crate) fn claude_permission_mode(self) -> &'static str {
+        match self {
+            AccessModePreset::ReadOnly => "plan",
+            AccessModePreset::AskBeforeWrite => "default",
+            AccessModePreset::AutoEdit => "acceptEdits",
+            AccessModePreset::FullAuto => "bypassPermissions",
+        }
+    }
+
+    pub(crate) fn codex_sandbox(self) -> CodexSandboxKind {
+        match self {
+            AccessModePreset::ReadOnly => CodexSandboxKind::ReadOnly,
+            // Codex has no ask-on-every-write mode; keep the legacy `default`
+            // mapping so existing users keep workspace writes with on-request
+            // escalation.
+            AccessModePreset::AskBeforeWrite | AccessModePreset::AutoEdit => {
+                CodexSandboxKind::WorkspaceWrite
+            }
+            AccessModePreset::FullAuto => CodexSandboxKind::DangerFullAccess,
+        }
+    }
+
+    pub(crate) fn codex_approval_policy(self) -> &'static str {
+        match self {
+            // Read-only never escalates; the others escalate outside the
+            // sandbox through an approval request.
+            AccessModePreset::ReadOnly | AccessModePreset::FullAuto => "never",
+            AccessModePreset::AskBeforeWrite | AccessModePreset::AutoEdit => "on-request",
+        }
+    }
+
+    pub(crate) fn gemini_approval_mode(self) -> &'static str {
+        match self {
+            AccessModePreset::ReadOnly => "plan",
+            AccessModePreset::AskBeforeWrite => "default",
+            AccessModePreset::AutoEdit => "auto_edit",
+            AccessModePreset::FullAuto => "yolo",
+        }
+    }
+}
+
+impl CodexSandboxKind {
+    fn id(self) -> &'static str {
+        match self {
+            CodexSandboxKind::ReadOnly => "readOnly",
+            CodexSandboxKind::WorkspaceWrite => "workspaceWrite",
+            CodexSandboxKind::DangerFullAccess => "dangerFullAccess",
+        }
+    }
+}
+
+pub(crate) fn list_access_mode_presets() -> Vec<AccessModePresetInfo> {
+    AccessModePreset::ALL
+        .iter()
+        .map(|preset| AccessModePresetInfo {
+            id: *preset,
+            label: preset.label(),
+            description: preset.description(),
+            claude_permission_mode: preset.claude_permission_mode(),
+            codex_sandbox: preset.codex_sandbox().id(),
+            codex_approval_policy: preset.codex_approval_policy(),
+            gemini_approval_mode: preset.gemini_approval_mode(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{list_access_mode_presets, AccessModePreset, CodexSandboxKind};
+
+    #[test]
+    fn parses_presets_and_legacy_values() {
+        assert_eq!(
+            AccessModePreset::parse("read-only"),
+            Some(AccessModePreset::ReadOnly)
+        );
+        assert_eq!(
+            AccessModePreset::parse("default"),
+            Some(AccessModePreset::AskBeforeWrite)
+        );
+        assert_eq!(
+            AccessModePreset::parse(" current "),
+            Some(AccessModePreset::AutoEdit)
+        );
+        assert_eq!(
+            AccessModePreset::parse("full-access"),
+            Some(AccessModePreset::FullAuto)
+        );
+        assert_eq!(AccessModePreset::parse("bogus"), None);
+        assert_eq!(AccessModePreset::resolve(None), AccessModePreset::AutoEdit);
+        for preset in AccessModePreset::ALL {
+            let id = serde_json::to_value(preset).expect("serialize preset");
+            assert_eq!(AccessModePreset::parse(id.as_str().unwrap()), Some(preset));
+        }
+    }
+
+    #[test]
+    fn codex_sandbox_keeps_legacy_default_mapping() {
+        assert_eq!(
+            AccessModePreset::ReadOnly.codex_sandbox(),
+            CodexSandboxKind::ReadOnly
+        );
+        assert_eq!(
+            AccessModePreset::AskBeforeWrite.codex_sandbox(),
+            CodexSandboxKind::WorkspaceWrite
+        );
+        assert_eq!(
+            AccessModePreset::AskBeforeWrite.codex_approval_policy(),
+            "on-request"
+        );
+        assert_eq!(AccessModePreset::ReadOnly.codex_approval_policy(), "never");
+        assert_eq!(AccessModePreset::ReadOnly.claude_permission_mode(), "plan");
+    }
+
+    #[test]
+    fn lists_every_preset_with_engine_mappings() {
+        let listed = serde_json::to_value(list_access_mode_presets()).expect("serialize");
+        assert_eq!(listed.as_array().map(Vec::len), Some(4));
+        assert_eq!(listed[0]["id"], "read-only");
+        assert_eq!(listed[3]["codexSandbox"], "dangerFullAccess");
+        assert_eq!(listed[1]["claudePermissionMode"], "default");
+    }
+}
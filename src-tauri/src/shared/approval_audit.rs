@@ -0,0 +1,143 @@
+//! Append-only audit log of shell command approvals.
+//!
+//! Each decision is one JSON line in `~/.ccgui/approval-audit.jsonl`. When
+//! the user rewrites a command in the approval dialog, both the command the
+//! engine asked for and the one that actually ran are kept. Auditing is best
+//! effort: a failed write never blocks the approval itself.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app_paths;
+use crate::storage::with_storage_lock;
+
+pub(crate) const APPROVAL_AUDIT_FILE: &str = "approval-audit.jsonl";
+const APPROVAL_AUDIT_ROTATED_FILE: &str = "approval-audit.1.jsonl";
+const MAX_AUDIT_BYTES: u64 = 2 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalAuditEntry {
+    pub(crate) recorded_at_ms: i64,
+    pub(crate) workspace_id: String,
+    pub(crate) engine: String,
+    pub(crate) request_id: String,
+    pub(crate) tool_name: String,
+    /// `accept` or `decline`.
+    pub(crate) decision: String,
+    pub(crate) original_command: Option<String>,
+    /// Set only when the user changed the command before approving it.
+    pub(crate) edited_command: Option<String>,
+}
+
+/// Reads the optional `editedCommand` of an approval result. Blank or
+/// unchanged commands count as no edit.
+pub(crate) fn edited_command_from_result(result: &Value) -> Result<Option<String>, String> {
+    let Some(raw) = result.get("editedCommand") else {
+        return Ok(None);
+    };
+    match raw {
+        Value::Null => Ok(None),
+        Value::String(command) => {
+            let command = command.trim();
+            Ok((!command.is_empty()).then(|| command.to_string()))
+        }
+        _ => Err("editedCommand must be a string".to_string()),
+    }
+}
+
+fn audit_dir() -> Option<PathBuf> {
+    // Unit tests use the `_in` variant with temp dirs and must not touch the
+    // real app home.
+    if cfg!(test) {
+        return None;
+    }
+    app_paths::app_home_dir().ok()
+}
+
+fn record_approval_decision_in(dir: &Path, entry: &ApprovalAuditEntry) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+    let path = dir.join(APPROVAL_AUDIT_FILE);
+    let line = serde_json::to_string(entry).map_err(|error| error.to_string())?;
+    with_storage_lock(&path, || {
+        let oversized = std::fs::metadata(&path)
+            .map(|metadata| metadata.len() > MAX_AUDIT_BYTES)
+            .unwrap_or(false);
+        if oversized {
+            std::fs::rename(&path, dir.join(APPROVAL_AUDIT_ROTATED_FILE))
+                .map_err(|error| error.to_string())?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|error| error.to_string())?;
+        writeln!(file, "{line}").map_err(|error| error.to_string())
+    })
+}
+
+pub(crate) fn record_approval_decision(entry: &ApprovalAuditEntry) {
+    let Some(dir) = audit_dir() else {
+        return;
+    };
+    if let Err(error) = record_approval_decision_in(&dir, entry) {
+        log::warn!(
+            "[approval-audit] failed to record decision for {}: {error}",
+            entry.request_id
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_edited_command_from_result() {
+        assert_eq!(
+            edited_command_from_result(&json!({ "decision": "accept" })),
+            Ok(None)
+        );
+        assert_eq!(
+            edited_command_from_result(&json!({ "editedCommand": "  ls -la  " })),
+            Ok(Some("ls -la".to_string()))
+        );
+        assert_eq!(
+            edited_command_from_result(&json!({ "editedCommand": "   " })),
+            Ok(None)
+        );
+        assert_eq!(edited_command_from_result(&json!("accept")), Ok(None));
+        assert!(edited_command_from_result(&json!({ "editedCommand": ["ls"] })).is_err());
+    }
+
+    #[test]
+    fn appends_original_and_edited_commands() {
+        let dir = std::env::temp_dir().join(format!("moss-x-audit-{}", uuid::Uuid::new_v4()));
+        let entry = ApprovalAuditEntry {
+            recorded_at_ms: 1,
+            workspace_id: "ws".to_string(),
+            engine: "api".to_string(),
+            request_id: "api-approval-1".to_string(),
+            tool_name: "run_command".to_string(),
+            decision: "accept".to_string(),
+            original_command: Some("rm -rf build".to_string()),
+            edited_command: Some("rm -rf build/tmp".to_string()),
+        };
+        record_approval_decision_in(&dir, &entry).expect("first entry");
+        record_approval_decision_in(&dir, &entry).expect("second entry");
+
+        let content =
+            std::fs::read_to_string(dir.join(APPROVAL_AUDIT_FILE)).expect("read audit log");
+        let entries: Vec<ApprovalAuditEntry> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("parse entry"))
+            .collect();
+        assert_eq!(entries, vec![entry.clone(), entry]);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
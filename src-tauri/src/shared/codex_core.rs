@@ -16,7 +16,9 @@ use crate::codex::collaboration_policy::{apply_policy_to_collaboration_mode, res
 use crate::codex::config as codex_config;
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
 use crate::rules;
+use crate::shared::access_presets::{AccessModePreset, CodexSandboxKind};
 use crate::shared::account::{build_account_response, read_auth_account};
+use crate::shared::workspace_read_only::cap_access_mode_for_workspace;
 use crate::shared::workspace_snapshot::{
     resolve_workspace_and_parent, resolve_workspace_parent_and_settings,
 };
@@ -79,10 +81,11 @@ fn resolve_execution_policy(
     effective_mode: &str,
     mode_enforcement_enabled: bool,
 ) -> (Value, &'static str, Option<&'static str>) {
-    let mut sandbox_policy = match access_mode {
-        "full-access" => json!({ "type": "dangerFullAccess" }),
-        "read-only" => json!({ "type": "readOnly" }),
-        _ => {
+    let preset = AccessModePreset::resolve(Some(access_mode));
+    let mut sandbox_policy = match preset.codex_sandbox() {
+        CodexSandboxKind::DangerFullAccess => json!({ "type": "dangerFullAccess" }),
+        CodexSandboxKind::ReadOnly => json!({ "type": "readOnly" }),
+        CodexSandboxKind::WorkspaceWrite => {
             let writable_roots = build_writable_roots(workspace_path, custom_spec_root);
             json!({
                 "type": "workspaceWrite",
@@ -92,11 +95,7 @@ fn resolve_execution_policy(
         }
     };
 
-    let mut approval_policy = if access_mode == "full-access" {
-        "never"
-    } else {
-        "on-request"
-    };
+    let mut approval_policy = preset.codex_approval_policy();
 
     if mode_enforcement_enabled && effective_mode == "plan" {
         sandbox_policy = json!({ "type": "readOnly" });
@@ -542,7 +541,11 @@ pub(crate) async fn send_user_message_core(
     session.set_mode_enforcement_enabled(mode_enforcement_enabled);
     let normalized_language = normalize_preferred_language(preferred_language.as_deref());
     let normalized_custom_spec_root = normalize_custom_spec_root(custom_spec_root.as_deref());
-    let access_mode = access_mode.unwrap_or_else(|| "current".to_string());
+    let access_mode = cap_access_mode_for_workspace(
+        access_mode.or_else(|| session.entry.settings.access_mode_preset.clone()),
+        session.entry.settings.read_only,
+    )
+    .unwrap_or_else(|| "current".to_string());
     let trimmed_text = text.trim();
     let mut input: Vec<Value> = Vec::new();
     if !trimmed_text.is_empty() {
@@ -570,13 +573,18 @@ pub(crate) async fn send_user_message_core(
 
     let persisted_mode = session.get_thread_effective_mode(&thread_id).await;
     let policy = resolve_policy(collaboration_mode.as_ref(), persisted_mode.as_deref());
-    let (sandbox_policy, approval_policy, enforcement_reason) = resolve_execution_policy(
+    let (mut sandbox_policy, approval_policy, enforcement_reason) = resolve_execution_policy(
         access_mode.as_str(),
         &session.entry.path,
         normalized_custom_spec_root.as_deref(),
         &policy.effective_mode,
         mode_enforcement_enabled,
     );
+    // Ask-before-write keeps a writable sandbox for Codex, which would
+    // auto-apply edits in a read-only workspace.
+    if session.entry.settings.read_only {
+        sandbox_policy = json!({ "type": "readOnly" });
+    }
     if let Some(reason) = enforcement_reason {
         log::info!(
             "[collaboration_mode_enforcement] decision=override_execution_policy workspace_id={} thread_id={} effective_mode={} requested_access_mode={} sandbox_policy=readOnly approval_policy=on-request reason={}",
@@ -619,6 +627,9 @@ pub(crate) async fn send_user_message_core(
     session
         .set_thread_effective_mode(&thread_id, &policy.effective_mode)
         .await;
+    session
+        .set_thread_approval_policy(&thread_id, approval_policy)
+        .await;
     log::debug!(
         "[turn/start][collaboration_mode] workspace_id={} thread_id={} selected_mode={} effective_mode={} policy_version={} fallback_reason={}",
         workspace_id,
@@ -759,6 +770,22 @@ mod tests {
         assert_eq!(reason, None);
     }
 
+    #[test]
+    fn resolve_execution_policy_keeps_workspace_write_for_legacy_default() {
+        let (sandbox, approval, reason) =
+            resolve_execution_policy("default", "/workspace/repo", None, "code", true);
+        assert_eq!(
+            sandbox,
+            json!({
+                "type": "workspaceWrite",
+                "writableRoots": ["/workspace/repo"],
+                "networkAccess": true
+            })
+        );
+        assert_eq!(approval, "on-request");
+        assert_eq!(reason, None);
+    }
+
     #[test]
     fn resolve_execution_policy_enforces_plan_readonly_when_enabled() {
         let (sandbox, approval, reason) = resolve_execution_policy(
@@ -0,0 +1,263 @@
+//! Bounded undo journal for configuration files.
+//!
+//! Before a config file is overwritten its previous contents are copied into
+//! `~/.ccgui/config-journal/`, so a bad change (wrong base URL, broken TOML)
+//! can be rolled back with `restore_previous_version`. Journaling is best
+//! effort: a failure to snapshot never blocks the write itself.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::app_paths;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+pub(crate) const JOURNAL_DIR_NAME: &str = "config-journal";
+pub(crate) const JOURNAL_INDEX_FILE: &str = "index.json";
+const MAX_VERSIONS_PER_FILE: usize = 20;
+const MAX_SNAPSHOT_BYTES: u64 = 2 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigJournalEntry {
+    pub(crate) id: String,
+    /// Logical file kind, e.g. `app-settings`, `vendor-config`, `codex-config`.
+    pub(crate) kind: String,
+    pub(crate) path: String,
+    pub(crate) captured_at_ms: i64,
+    /// `false` when the file did not exist before the write; restoring such a
+    /// version removes the file again.
+    pub(crate) existed: bool,
+    pub(crate) size: u64,
+}
+
+fn journal_dir() -> Option<PathBuf> {
+    // Unit tests exercise the `_in` variants with temp dirs and must not touch
+    // the real app home.
+    if cfg!(test) {
+        return None;
+    }
+    app_paths::app_home_dir()
+        .ok()
+        .map(|home| home.join(JOURNAL_DIR_NAME))
+}
+
+fn read_index(dir: &Path) -> Result<Vec<ConfigJournalEntry>, String> {
+    Ok(read_json_file(&dir.join(JOURNAL_INDEX_FILE))?.unwrap_or_default())
+}
+
+fn write_index(dir: &Path, entries: &[ConfigJournalEntry]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(entries).map_err(|error| error.to_string())?;
+    write_string_atomically(&dir.join(JOURNAL_INDEX_FILE), &data)
+}
+
+pub(crate) fn snapshot_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{id}.snapshot"))
+}
+
+fn path_key(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+fn record_config_write_in(
+    dir: &Path,
+    kind: &str,
+    path: &Path,
+    next_content: &str,
+) -> Result<(), String> {
+    let previous = match std::fs::metadata(path) {
+        Ok(metadata) if metadata.len() > MAX_SNAPSHOT_BYTES => return Ok(()),
+        Ok(_) => Some(std::fs::read(path).map_err(|error| error.to_string())?),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+        Err(error) => return Err(error.to_string()),
+    };
+    if previous.as_deref() == Some(next_content.as_bytes()) {
+        return Ok(());
+    }
+    std::fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+    let index_path = dir.join(JOURNAL_INDEX_FILE);
+    with_storage_lock(&index_path, || {
+        let mut entries = read_index(dir)?;
+        let entry = ConfigJournalEntry {
+            id: Uuid::new_v4().to_string(),
+            kind: kind.to_string(),
+            path: path_key(path),
+            captured_at_ms: chrono::Utc::now().timestamp_millis(),
+            existed: previous.is_some(),
+            size: previous
+                .as_ref()
+                .map(|bytes| bytes.len() as u64)
+                .unwrap_or(0),
+        };
+        std::fs::write(
+            snapshot_path(dir, &entry.id),
+            previous.as_deref().unwrap_or_default(),
+        )
+        .map_err(|error| error.to_string())?;
+        entries.push(entry);
+
+        let key = path_key(path);
+        let versions = entries.iter().filter(|item| item.path == key).count();
+        if versions > MAX_VERSIONS_PER_FILE {
+            let mut excess = versions - MAX_VERSIONS_PER_FILE;
+            entries.retain(|item| {
+                if excess > 0 && item.path == key {
+                    excess -= 1;
+                    let _ = std::fs::remove_file(snapshot_path(dir, &item.id));
+                    return false;
+                }
+                true
+            });
+        }
+        write_index(dir, &entries)
+    })
+}
+
+fn list_config_versions_in(
+    dir: &Path,
+    path: Option<&str>,
+) -> Result<Vec<ConfigJournalEntry>, String> {
+    let mut entries = read_index(dir)?;
+    if let Some(path) = path {
+        entries.retain(|entry| entry.path == path);
+    }
+    entries.reverse();
+    Ok(entries)
+}
+
+/// Restores `version_id` (or the most recent version) of `path` and drops it
+/// together with every newer version from the journal, so repeated calls walk
+/// further back in history.
+fn restore_previous_version_in(
+    dir: &Path,
+    path: &str,
+    version_id: Option<&str>,
+) -> Result<ConfigJournalEntry, String> {
+    let index_path = dir.join(JOURNAL_INDEX_FILE);
+    with_storage_lock(&index_path, || {
+        let mut entries = read_index(dir)?;
+        let position = entries
+            .iter()
+            .rposition(|entry| entry.path == path && version_id.is_none_or(|id| entry.id == id))
+            .ok_or_else(|| format!("No previous version recorded for {path}"))?;
+        let entry = entries[position].clone();
+        let target = Path::new(&entry.path);
+        if entry.existed {
+            let content = std::fs::read_to_string(snapshot_path(dir, &entry.id))
+                .map_err(|error| format!("Failed to read saved version: {error}"))?;
+            write_string_atomically(target, &content)?;
+        } else if target.exists() {
+            std::fs::remove_file(target).map_err(|error| error.to_string())?;
+        }
+
+        let mut index = 0;
+        entries.retain(|item| {
+            let keep = index < position || item.path != path;
+            index += 1;
+            if !keep {
+                let _ = std::fs::remove_file(snapshot_path(dir, &item.id));
+            }
+            keep
+        });
+        write_index(dir, &entries)?;
+        Ok(entry)
+    })
+}
+
+/// Snapshots the current contents of `path` before it is replaced with
+/// `next_content`.
+pub(crate) fn record_config_write(kind: &str, path: &Path, next_content: &str) {
+    let Some(dir) = journal_dir() else {
+        return;
+    };
+    if let Err(error) = record_config_write_in(&dir, kind, path, next_content) {
+        log::warn!(
+            "[config-journal] failed to snapshot {} before write: {error}",
+            path.display()
+        );
+    }
+}
+
+pub(crate) fn list_config_versions(path: Option<&str>) -> Result<Vec<ConfigJournalEntry>, String> {
+    let dir = journal_dir().ok_or("Config journal is unavailable")?;
+    list_config_versions_in(&dir, path)
+}
+
+pub(crate) fn restore_previous_version(
+    path: &str,
+    version_id: Option<&str>,
+) -> Result<ConfigJournalEntry, String> {
+    let dir = journal_dir().ok_or("Config journal is unavailable")?;
+    restore_previous_version_in(&dir, path, version_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn restores_versions_newest_first() {
+        let root = temp_dir();
+        let journal = root.join("journal");
+        let file = root.join("config.toml");
+        let key = file.to_string_lossy().to_string();
+
+        record_config_write_in(&journal, "codex-config", &file, "a = 1\n").expect("record");
+        std::fs::write(&file, "a = 1\n").expect("write");
+        record_config_write_in(&journal, "codex-config", &file, "a = 2\n").expect("record");
+        std::fs::write(&file, "a = 2\n").expect("write");
+        // Unchanged writes are not journaled.
+        record_config_write_in(&journal, "codex-config", &file, "a = 2\n").expect("record");
+
+        let versions = list_config_versions_in(&journal, Some(&key)).expect("list");
+        assert_eq!(versions.len(), 2);
+        assert!(versions[0].existed);
+        assert!(!versions[1].existed);
+
+        restore_previous_version_in(&journal, &key, None).expect("restore");
+        assert_eq!(std::fs::read_to_string(&file).expect("read"), "a = 1\n");
+        restore_previous_version_in(&journal, &key, None).expect("restore");
+        assert!(!file.exists());
+        assert!(restore_previous_version_in(&journal, &key, None).is_err());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn keeps_a_bounded_number_of_versions_per_file() {
+        let root = temp_dir();
+        let journal = root.join("journal");
+        let file = root.join("settings.json");
+        for round in 0..(MAX_VERSIONS_PER_FILE + 5) {
+            let content = format!("{{\"round\":{round}}}");
+            record_config_write_in(&journal, "app-settings", &file, &content).expect("record");
+            std::fs::write(&file, content).expect("write");
+        }
+        let versions = list_config_versions_in(&journal, None).expect("list");
+        assert_eq!(versions.len(), MAX_VERSIONS_PER_FILE);
+        let snapshots = std::fs::read_dir(&journal)
+            .expect("read journal")
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .map(|entry| {
+                        entry
+                            .path()
+                            .extension()
+                            .is_some_and(|ext| ext == "snapshot")
+                    })
+                    .unwrap_or(false)
+            })
+            .count();
+        assert_eq!(snapshots, MAX_VERSIONS_PER_FILE);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -7,6 +7,7 @@ use crate::codex::home as codex_home;
 use crate::files::io::TextFileResponse;
 use crate::files::ops::{read_with_policy, write_with_policy};
 use crate::files::policy::{policy_for, FileKind, FileScope};
+use crate::shared::workspace_read_only::ensure_workspace_id_writable;
 use crate::types::WorkspaceEntry;
 
 fn resolve_default_codex_home() -> Result<PathBuf, String> {
@@ -105,6 +106,9 @@ pub(crate) async fn file_write_core(
     workspace_id: Option<String>,
     content: String,
 ) -> Result<(), String> {
+    if let (FileScope::Workspace, Some(workspace_id)) = (scope, workspace_id.as_deref()) {
+        ensure_workspace_id_writable(workspaces, workspace_id, "file_write").await?;
+    }
     let root = resolve_root_core(workspaces, scope, workspace_id.as_deref()).await?;
 
     // Special handling for CLAUDE.md in workspace scope
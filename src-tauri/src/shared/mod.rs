@@ -1,10 +1,17 @@
+pub(crate) mod access_presets;
 pub(crate) mod account;
+pub(crate) mod approval_audit;
 pub(crate) mod codex_core;
+pub(crate) mod config_journal;
 pub(crate) mod files_core;
 pub(crate) mod git_core;
+pub(crate) mod provider_tls;
 pub(crate) mod proxy_core;
 pub(crate) mod settings_core;
+pub(crate) mod state_store_core;
 pub(crate) mod thread_titles_core;
+pub(crate) mod workspace_read_only;
 pub(crate) mod workspace_snapshot;
 pub(crate) mod workspaces_core;
 pub(crate) mod worktree_core;
+pub(crate) mod worktree_naming;
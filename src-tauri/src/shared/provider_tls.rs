@@ -0,0 +1,216 @@
+//! Per-provider TLS options (custom CA bundle, insecure skip-verify) for
+//! self-hosted relays.
+//!
+//! The options live on each provider in `~/.ccgui/config.json`. Engine CLIs
+//! pick up the active provider's options through environment variables set
+//! at spawn time: Node's `NODE_EXTRA_CA_CERTS` /
+//! `NODE_TLS_REJECT_UNAUTHORIZED` for Claude, and `SSL_CERT_FILE` for Codex,
+//! which has no switch to skip verification.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::app_paths;
+use crate::types::ProviderTlsOptions;
+
+const PEM_CERTIFICATE_MARKER: &str = "-----BEGIN CERTIFICATE-----";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TlsProviderEngine {
+    Claude,
+    Codex,
+}
+
+impl TlsProviderEngine {
+    fn config_section(self) -> &'static str {
+        match self {
+            Self::Claude => "claude",
+            Self::Codex => "codex",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Claude => "Claude",
+            Self::Codex => "Codex",
+        }
+    }
+}
+
+fn ca_bundle_path(options: &ProviderTlsOptions) -> Option<&str> {
+    options
+        .ca_bundle_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+}
+
+/// TLS options of a provider entry in `config.json`, if it sets any.
+pub(crate) fn provider_tls_options(provider: &Value) -> Option<ProviderTlsOptions> {
+    let options: ProviderTlsOptions = serde_json::from_value(provider.get("tls")?.clone()).ok()?;
+    (ca_bundle_path(&options).is_some() || options.insecure_skip_verify).then_some(options)
+}
+
+fn active_provider_tls_from_config(
+    config: &Value,
+    engine: TlsProviderEngine,
+) -> Option<ProviderTlsOptions> {
+    let section = config.get(engine.config_section())?;
+    let current = section.get("current")?.as_str()?;
+    provider_tls_options(section.get("providers")?.get(current)?)
+}
+
+/// TLS options of the engine's active provider.
+pub(crate) fn active_provider_tls(engine: TlsProviderEngine) -> Option<ProviderTlsOptions> {
+    let path = app_paths::config_file_path().ok()?;
+    let content = std::fs::read_to_string(path).ok()?;
+    let config: Value = serde_json::from_str(&content).ok()?;
+    active_provider_tls_from_config(&config, engine)
+}
+
+/// Problems with the options worth showing next to the provider; insecure
+/// mode is always reported.
+pub(crate) fn provider_tls_warnings(
+    options: &ProviderTlsOptions,
+    engine: TlsProviderEngine,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if let Some(path) = ca_bundle_path(options) {
+        match std::fs::read_to_string(Path::new(path)) {
+            Ok(content) if content.contains(PEM_CERTIFICATE_MARKER) => {}
+            Ok(_) => warnings.push(format!(
+                "CA bundle {path} contains no PEM certificates; it will be ignored."
+            )),
+            Err(error) => warnings.push(format!("CA bundle {path} cannot be read: {error}")),
+        }
+    }
+    if options.insecure_skip_verify {
+        match engine {
+            TlsProviderEngine::Claude => warnings.push(
+                "Certificate verification is disabled for this provider; traffic to it can be intercepted."
+                    .to_string(),
+            ),
+            TlsProviderEngine::Codex => warnings.push(format!(
+                "{} cannot skip certificate verification; add the relay's CA bundle instead.",
+                engine.label()
+            )),
+        }
+    }
+    warnings
+}
+
+/// Environment variables that make the engine CLI apply `options`.
+pub(crate) fn provider_tls_env(
+    options: &ProviderTlsOptions,
+    engine: TlsProviderEngine,
+) -> Vec<(&'static str, String)> {
+    let mut env = Vec::new();
+    let ca_bundle = ca_bundle_path(options).filter(|path| Path::new(path).is_file());
+    match engine {
+        TlsProviderEngine::Claude => {
+            if let Some(path) = ca_bundle {
+                env.push(("NODE_EXTRA_CA_CERTS", path.to_string()));
+            }
+            if options.insecure_skip_verify {
+                env.push(("NODE_TLS_REJECT_UNAUTHORIZED", "0".to_string()));
+            }
+        }
+        TlsProviderEngine::Codex => {
+            if let Some(path) = ca_bundle {
+                env.push(("SSL_CERT_FILE", path.to_string()));
+            }
+        }
+    }
+    env
+}
+
+/// Applies the active provider's TLS options to an engine CLI command.
+pub(crate) fn apply_active_provider_tls_env(
+    command: &mut tokio::process::Command,
+    engine: TlsProviderEngine,
+) {
+    let Some(options) = active_provider_tls(engine) else {
+        return;
+    };
+    for warning in provider_tls_warnings(&options, engine) {
+        log::warn!("[provider-tls] {}: {warning}", engine.label());
+    }
+    for (key, value) in provider_tls_env(&options, engine) {
+        command.env(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_active_provider_tls_options() {
+        let config = json!({
+            "claude": {
+                "current": "relay",
+                "providers": {
+                    "relay": { "tls": { "caBundlePath": "/etc/relay-ca.pem", "insecureSkipVerify": false } },
+                    "plain": { "name": "plain" }
+                }
+            },
+            "codex": { "current": "plain", "providers": { "plain": { "tls": {} } } }
+        });
+
+        assert_eq!(
+            active_provider_tls_from_config(&config, TlsProviderEngine::Claude),
+            Some(ProviderTlsOptions {
+                ca_bundle_path: Some("/etc/relay-ca.pem".to_string()),
+                insecure_skip_verify: false,
+            })
+        );
+        assert_eq!(
+            active_provider_tls_from_config(&config, TlsProviderEngine::Codex),
+            None
+        );
+    }
+
+    #[test]
+    fn maps_options_to_engine_env_and_warnings() {
+        let bundle = std::env::temp_dir().join(format!("ccgui-ca-{}.pem", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &bundle,
+            format!("{PEM_CERTIFICATE_MARKER}\nMIIB\n-----END CERTIFICATE-----\n"),
+        )
+        .expect("write bundle");
+        let options = ProviderTlsOptions {
+            ca_bundle_path: Some(bundle.to_string_lossy().to_string()),
+            insecure_skip_verify: true,
+        };
+        let path = bundle.to_string_lossy().to_string();
+
+        assert_eq!(
+            provider_tls_env(&options, TlsProviderEngine::Claude),
+            vec![
+                ("NODE_EXTRA_CA_CERTS", path.clone()),
+                ("NODE_TLS_REJECT_UNAUTHORIZED", "0".to_string()),
+            ]
+        );
+        assert_eq!(
+            provider_tls_env(&options, TlsProviderEngine::Codex),
+            vec![("SSL_CERT_FILE", path)]
+        );
+        let codex_warnings = provider_tls_warnings(&options, TlsProviderEngine::Codex);
+        assert_eq!(codex_warnings.len(), 1);
+        assert!(codex_warnings[0].contains("cannot skip certificate verification"));
+
+        let missing = ProviderTlsOptions {
+            ca_bundle_path: Some("/nonexistent/ca.pem".to_string()),
+            insecure_skip_verify: false,
+        };
+        assert!(provider_tls_env(&missing, TlsProviderEngine::Claude).is_empty());
+        assert!(
+            provider_tls_warnings(&missing, TlsProviderEngine::Claude)[0]
+                .contains("cannot be read")
+        );
+
+        let _ = std::fs::remove_file(bundle);
+    }
+}
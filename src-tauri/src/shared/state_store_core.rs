@@ -0,0 +1,696 @@
+//! Pluggable persistence for the app state documents (`workspaces.json` and
+//! `settings.json` in the app data dir).
+//!
+//! The JSON backend keeps one file per document plus a `.bak` copy of the
+//! last version that parsed. The SQLite backend keeps the documents in
+//! `app-state.sqlite3` (WAL journal, one transaction per write) with a
+//! checksum per row. The selected backend is recorded in `state-backend.json`;
+//! without that file the JSON backend is used, so existing installs keep
+//! working unchanged.
+//!
+//! `repair_state_store_in` runs on startup: damaged documents are set aside
+//! and restored from the `.bak` copy or from the other backend, and a backend
+//! that was selected before it held any data is filled from the other one.
+
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+pub(crate) const STATE_DOCUMENTS: [&str; 2] = ["workspaces.json", "settings.json"];
+const BACKEND_MARKER_FILE: &str = "state-backend.json";
+const SQLITE_DATABASE_FILE: &str = "app-state.sqlite3";
+const SQLITE_BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StateStoreBackend {
+    #[default]
+    Json,
+    Sqlite,
+}
+
+impl StateStoreBackend {
+    fn other(self) -> Self {
+        match self {
+            Self::Json => Self::Sqlite,
+            Self::Sqlite => Self::Json,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackendMarker {
+    backend: StateStoreBackend,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum StateDocumentStatus {
+    Ok,
+    /// Never written; readers fall back to defaults.
+    Missing,
+    /// Unreadable and not repaired (dry run, or no intact copy was found).
+    Corrupt,
+    Restored,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StateDocumentIntegrity {
+    pub(crate) name: String,
+    pub(crate) status: StateDocumentStatus,
+    pub(crate) detail: Option<String>,
+    /// Where a restored document came from, e.g. `json-backup` or `sqlite`.
+    pub(crate) restored_from: Option<String>,
+    /// Where the damaged copy was moved to, when it was a file.
+    pub(crate) quarantined_to: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StateStoreIntegrityReport {
+    pub(crate) backend: StateStoreBackend,
+    pub(crate) repaired: bool,
+    /// Result of the backend's own consistency check (`PRAGMA quick_check`).
+    pub(crate) container_error: Option<String>,
+    pub(crate) container_quarantined_to: Option<String>,
+    pub(crate) documents: Vec<StateDocumentIntegrity>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StateStoreMigrationReport {
+    pub(crate) from: StateStoreBackend,
+    pub(crate) to: StateStoreBackend,
+    pub(crate) migrated_documents: Vec<String>,
+}
+
+pub(crate) trait StateStore {
+    fn backend(&self) -> StateStoreBackend;
+    /// Returns the stored text of a document, `None` when it was never written.
+    /// Rows that fail their checksum are reported as errors.
+    fn read_document(&self, name: &str) -> Result<Option<String>, String>;
+    /// Writes every document at once; the SQLite backend commits them in a
+    /// single transaction.
+    fn write_documents(&self, documents: &[(&str, &str)]) -> Result<(), String>;
+    /// The last intact copy the backend keeps on its own, if any.
+    fn backup_document(&self, name: &str) -> Option<String>;
+    /// Sets a damaged document aside so it is no longer read. Returns the
+    /// quarantine path when the document was moved to a file.
+    fn discard_document(&self, name: &str) -> Result<Option<String>, String>;
+    /// Checks the storage container itself. The JSON backend has none.
+    fn check_container(&self) -> Result<(), String>;
+    /// Moves a damaged container aside so the next write starts fresh.
+    fn reset_container(&self) -> Result<Option<String>, String>;
+}
+
+fn quarantine_suffix() -> String {
+    chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ").to_string()
+}
+
+fn validate_document(content: &str) -> Result<(), String> {
+    serde_json::from_str::<serde_json::Value>(content)
+        .map(|_| ())
+        .map_err(|error| error.to_string())
+}
+
+fn document_checksum(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+pub(crate) struct JsonStateStore {
+    data_dir: PathBuf,
+}
+
+impl JsonStateStore {
+    pub(crate) fn new(data_dir: &Path) -> Self {
+        Self {
+            data_dir: data_dir.to_path_buf(),
+        }
+    }
+
+    fn document_path(&self, name: &str) -> PathBuf {
+        self.data_dir.join(name)
+    }
+
+    fn backup_path(&self, name: &str) -> PathBuf {
+        self.data_dir.join(format!("{name}.bak"))
+    }
+}
+
+impl StateStore for JsonStateStore {
+    fn backend(&self) -> StateStoreBackend {
+        StateStoreBackend::Json
+    }
+
+    fn read_document(&self, name: &str) -> Result<Option<String>, String> {
+        let path = self.document_path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        std::fs::read_to_string(&path)
+            .map(Some)
+            .map_err(|error| format!("failed to read {}: {error}", path.display()))
+    }
+
+    fn write_documents(&self, documents: &[(&str, &str)]) -> Result<(), String> {
+        for (name, content) in documents {
+            let path = self.document_path(name);
+            // Only a copy that still parses is worth keeping as the backup.
+            if let Ok(previous) = std::fs::read_to_string(&path) {
+                if validate_document(&previous).is_ok() && previous != *content {
+                    write_string_atomically(&self.backup_path(name), &previous)?;
+                }
+            }
+            write_string_atomically(&path, content)?;
+        }
+        Ok(())
+    }
+
+    fn backup_document(&self, name: &str) -> Option<String> {
+        std::fs::read_to_string(self.backup_path(name)).ok()
+    }
+
+    fn discard_document(&self, name: &str) -> Result<Option<String>, String> {
+        let path = self.document_path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let target = self
+            .data_dir
+            .join(format!("{name}.corrupt-{}", quarantine_suffix()));
+        std::fs::rename(&path, &target)
+            .map_err(|error| format!("failed to move aside {}: {error}", path.display()))?;
+        Ok(Some(target.to_string_lossy().to_string()))
+    }
+
+    fn check_container(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn reset_container(&self) -> Result<Option<String>, String> {
+        Ok(None)
+    }
+}
+
+pub(crate) struct SqliteStateStore {
+    db_path: PathBuf,
+}
+
+impl SqliteStateStore {
+    pub(crate) fn new(data_dir: &Path) -> Self {
+        Self {
+            db_path: data_dir.join(SQLITE_DATABASE_FILE),
+        }
+    }
+
+    fn open(&self) -> Result<Connection, String> {
+        if let Some(parent) = self.db_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+        }
+        let connection = Connection::open(&self.db_path).map_err(|error| {
+            format!(
+                "failed to open state database {}: {error}",
+                self.db_path.display()
+            )
+        })?;
+        connection
+            .busy_timeout(SQLITE_BUSY_TIMEOUT)
+            .map_err(|error| error.to_string())?;
+        connection
+            .pragma_update(None, "journal_mode", "WAL")
+            .map_err(|error| format!("failed to enable WAL journal: {error}"))?;
+        connection
+            .pragma_update(None, "synchronous", "NORMAL")
+            .map_err(|error| error.to_string())?;
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS state_documents (
+                    name TEXT PRIMARY KEY,
+                    content TEXT NOT NULL,
+                    checksum TEXT NOT NULL,
+                    updated_at_ms INTEGER NOT NULL
+                );",
+            )
+            .map_err(|error| format!("failed to prepare state database: {error}"))?;
+        Ok(connection)
+    }
+
+    fn sidecar_paths(&self) -> Vec<PathBuf> {
+        let file_name = self.db_path.to_string_lossy().to_string();
+        vec![
+            self.db_path.clone(),
+            PathBuf::from(format!("{file_name}-wal")),
+            PathBuf::from(format!("{file_name}-shm")),
+        ]
+    }
+}
+
+impl StateStore for SqliteStateStore {
+    fn backend(&self) -> StateStoreBackend {
+        StateStoreBackend::Sqlite
+    }
+
+    fn read_document(&self, name: &str) -> Result<Option<String>, String> {
+        // Reading must not create a database that was never selected.
+        if !self.db_path.exists() {
+            return Ok(None);
+        }
+        let connection = self.open()?;
+        let row: Option<(String, String)> = connection
+            .query_row(
+                "SELECT content, checksum FROM state_documents WHERE name = ?1",
+                params![name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|error| format!("failed to read {name} from state database: {error}"))?;
+        match row {
+            Some((content, checksum)) if document_checksum(&content) != checksum => {
+                Err(format!("{name} does not match its stored checksum"))
+            }
+            Some((content, _)) => Ok(Some(content)),
+            None => Ok(None),
+        }
+    }
+
+    fn write_documents(&self, documents: &[(&str, &str)]) -> Result<(), String> {
+        let mut connection = self.open()?;
+        let transaction = connection
+            .transaction()
+            .map_err(|error| error.to_string())?;
+        let now = chrono::Utc::now().timestamp_millis();
+        for (name, content) in documents {
+            transaction
+                .execute(
+                    "INSERT INTO state_documents (name, content, checksum, updated_at_ms)
+                     VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT(name) DO UPDATE SET
+                        content = excluded.content,
+                        checksum = excluded.checksum,
+                        updated_at_ms = excluded.updated_at_ms",
+                    params![name, content, document_checksum(content), now],
+                )
+                .map_err(|error| format!("failed to write {name} to state database: {error}"))?;
+        }
+        transaction
+            .commit()
+            .map_err(|error| format!("failed to commit state database write: {error}"))
+    }
+
+    fn backup_document(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    fn discard_document(&self, name: &str) -> Result<Option<String>, String> {
+        let connection = self.open()?;
+        connection
+            .execute("DELETE FROM state_documents WHERE name = ?1", params![name])
+            .map_err(|error| error.to_string())?;
+        Ok(None)
+    }
+
+    fn check_container(&self) -> Result<(), String> {
+        if !self.db_path.exists() {
+            return Ok(());
+        }
+        let connection = self.open()?;
+        let result: String = connection
+            .query_row("PRAGMA quick_check", [], |row| row.get(0))
+            .map_err(|error| error.to_string())?;
+        if result == "ok" {
+            Ok(())
+        } else {
+            Err(result)
+        }
+    }
+
+    fn reset_container(&self) -> Result<Option<String>, String> {
+        if !self.db_path.exists() {
+            return Ok(None);
+        }
+        let suffix = quarantine_suffix();
+        let mut moved = None;
+        for path in self.sidecar_paths() {
+            if !path.exists() {
+                continue;
+            }
+            let target = PathBuf::from(format!("{}.corrupt-{suffix}", path.to_string_lossy()));
+            std::fs::rename(&path, &target)
+                .map_err(|error| format!("failed to move aside {}: {error}", path.display()))?;
+            if moved.is_none() {
+                moved = Some(target.to_string_lossy().to_string());
+            }
+        }
+        Ok(moved)
+    }
+}
+
+pub(crate) fn open_state_store(data_dir: &Path, backend: StateStoreBackend) -> Box<dyn StateStore> {
+    match backend {
+        StateStoreBackend::Json => Box::new(JsonStateStore::new(data_dir)),
+        StateStoreBackend::Sqlite => Box::new(SqliteStateStore::new(data_dir)),
+    }
+}
+
+fn marker_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(BACKEND_MARKER_FILE)
+}
+
+/// The backend selected for `data_dir`. An unreadable marker falls back to
+/// JSON, which is what every install used before the marker existed.
+pub(crate) fn selected_backend(data_dir: &Path) -> StateStoreBackend {
+    match read_json_file::<BackendMarker>(&marker_path(data_dir)) {
+        Ok(Some(marker)) => marker.backend,
+        Ok(None) => StateStoreBackend::Json,
+        Err(error) => {
+            log::warn!("[state-store] ignoring unreadable backend marker: {error}");
+            StateStoreBackend::Json
+        }
+    }
+}
+
+fn write_selected_backend(data_dir: &Path, backend: StateStoreBackend) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(&BackendMarker { backend })
+        .map_err(|error| error.to_string())?;
+    write_string_atomically(&marker_path(data_dir), &data)
+}
+
+fn split_state_path(path: &Path) -> Option<(&Path, &str)> {
+    let name = path.file_name()?.to_str()?;
+    if !STATE_DOCUMENTS.contains(&name) {
+        return None;
+    }
+    Some((path.parent()?, name))
+}
+
+/// `true` when `path` is read and written as a plain file, i.e. it is not a
+/// state document or the JSON backend is selected for its directory.
+pub(crate) fn uses_json_file(path: &Path) -> bool {
+    split_state_path(path)
+        .map(|(data_dir, _)| selected_backend(data_dir) == StateStoreBackend::Json)
+        .unwrap_or(true)
+}
+
+/// Reads a state document through the selected backend. Paths that are not
+/// state documents are read as plain files.
+pub(crate) fn read_state_document(path: &Path) -> Result<Option<String>, String> {
+    match split_state_path(path) {
+        Some((data_dir, name)) => {
+            open_state_store(data_dir, selected_backend(data_dir)).read_document(name)
+        }
+        None if !path.exists() => Ok(None),
+        None => std::fs::read_to_string(path)
+            .map(Some)
+            .map_err(|error| format!("failed to read {}: {error}", path.display())),
+    }
+}
+
+/// Writes a state document through the selected backend. Callers hold the
+/// storage lock for `path`.
+pub(crate) fn write_state_document(path: &Path, content: &str) -> Result<(), String> {
+    match split_state_path(path) {
+        Some((data_dir, name)) => open_state_store(data_dir, selected_backend(data_dir))
+            .write_documents(&[(name, content)]),
+        None => write_string_atomically(path, content),
+    }
+}
+
+fn find_intact_copy(
+    store: &dyn StateStore,
+    other: &dyn StateStore,
+    name: &str,
+) -> Option<(String, String)> {
+    if let Some(backup) = store
+        .backup_document(name)
+        .filter(|content| validate_document(content).is_ok())
+    {
+        return Some((backup, "json-backup".to_string()));
+    }
+    let source = match other.backend() {
+        StateStoreBackend::Json => "json",
+        StateStoreBackend::Sqlite => "sqlite",
+    };
+    match other.read_document(name) {
+        Ok(Some(content)) if validate_document(&content).is_ok() => {
+            Some((content, source.to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn inspect_document(
+    store: &dyn StateStore,
+    other: &dyn StateStore,
+    name: &str,
+    repair: bool,
+) -> Result<StateDocumentIntegrity, String> {
+    let mut integrity = StateDocumentIntegrity {
+        name: name.to_string(),
+        status: StateDocumentStatus::Ok,
+        detail: None,
+        restored_from: None,
+        quarantined_to: None,
+    };
+    let damage = match store.read_document(name) {
+        Ok(Some(content)) => match validate_document(&content) {
+            Ok(()) => return Ok(integrity),
+            Err(error) => Some(error),
+        },
+        Ok(None) => None,
+        Err(error) => Some(error),
+    };
+    integrity.status = if damage.is_some() {
+        StateDocumentStatus::Corrupt
+    } else {
+        StateDocumentStatus::Missing
+    };
+    integrity.detail = damage.clone();
+    if !repair {
+        return Ok(integrity);
+    }
+    let intact = find_intact_copy(store, other, name);
+    // A missing document with nothing to restore is simply unset.
+    if damage.is_none() && intact.is_none() {
+        return Ok(integrity);
+    }
+    if damage.is_some() {
+        integrity.quarantined_to = store.discard_document(name)?;
+    }
+    if let Some((content, source)) = intact {
+        store.write_documents(&[(name, &content)])?;
+        integrity.status = StateDocumentStatus::Restored;
+        integrity.restored_from = Some(source);
+    } else {
+        integrity.detail = Some(format!(
+            "{} (no intact copy found; defaults will be used)",
+            damage.unwrap_or_default()
+        ));
+    }
+    Ok(integrity)
+}
+
+/// Checks the selected backend and, with `repair`, moves damaged data aside
+/// and restores it from the best intact copy.
+pub(crate) fn repair_state_store_in(
+    data_dir: &Path,
+    repair: bool,
+) -> Result<StateStoreIntegrityReport, String> {
+    with_storage_lock(&marker_path(data_dir), || {
+        let backend = selected_backend(data_dir);
+        let store = open_state_store(data_dir, backend);
+        let other = open_state_store(data_dir, backend.other());
+        let mut report = StateStoreIntegrityReport {
+            backend,
+            repaired: false,
+            container_error: None,
+            container_quarantined_to: None,
+            documents: Vec::new(),
+        };
+        if let Err(error) = store.check_container() {
+            report.container_error = Some(error);
+            if !repair {
+                return Ok(report);
+            }
+            report.container_quarantined_to = store.reset_container()?;
+            report.repaired = true;
+        }
+        for name in STATE_DOCUMENTS {
+            let integrity = with_storage_lock(&data_dir.join(name), || {
+                inspect_document(store.as_ref(), other.as_ref(), name, repair)
+            })?;
+            if integrity.status == StateDocumentStatus::Restored {
+                report.repaired = true;
+            }
+            report.documents.push(integrity);
+        }
+        Ok(report)
+    })
+}
+
+/// Copies every state document into `target`, verifies the copy and then
+/// switches the selected backend. The previous backend's data is left in
+/// place as a fallback for repairs.
+pub(crate) fn migrate_state_store_in(
+    data_dir: &Path,
+    target: StateStoreBackend,
+) -> Result<StateStoreMigrationReport, String> {
+    let report = repair_state_store_in(data_dir, true)?;
+    if let Some(document) = report
+        .documents
+        .iter()
+        .find(|document| document.status == StateDocumentStatus::Corrupt)
+    {
+        return Err(format!(
+            "Refusing to migrate while {} is unreadable.",
+            document.name
+        ));
+    }
+    with_storage_lock(&marker_path(data_dir), || {
+        // Writers only take the per-document locks, so hold both while the
+        // documents are copied and the marker is switched.
+        with_storage_lock(&data_dir.join(STATE_DOCUMENTS[0]), || {
+            with_storage_lock(&data_dir.join(STATE_DOCUMENTS[1]), || {
+                copy_documents_and_switch(data_dir, target)
+            })
+        })
+    })
+}
+
+fn copy_documents_and_switch(
+    data_dir: &Path,
+    target: StateStoreBackend,
+) -> Result<StateStoreMigrationReport, String> {
+    let from = selected_backend(data_dir);
+    let mut migrated_documents = Vec::new();
+    if from != target {
+        let source = open_state_store(data_dir, from);
+        let destination = open_state_store(data_dir, target);
+        let mut documents = Vec::new();
+        for name in STATE_DOCUMENTS {
+            if let Some(content) = source.read_document(name)? {
+                documents.push((name, content));
+            }
+        }
+        let pairs: Vec<(&str, &str)> = documents
+            .iter()
+            .map(|(name, content)| (*name, content.as_str()))
+            .collect();
+        destination.write_documents(&pairs)?;
+        for (name, content) in &documents {
+            if destination.read_document(name)?.as_deref() != Some(content.as_str()) {
+                return Err(format!("Verification of {name} failed after migration."));
+            }
+            migrated_documents.push(name.to_string());
+        }
+        write_selected_backend(data_dir, target)?;
+    }
+    Ok(StateStoreMigrationReport {
+        from,
+        to: target,
+        migrated_documents,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_data_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn json_backend_restores_corrupt_document_from_backup() {
+        let data_dir = temp_data_dir();
+        let path = data_dir.join("settings.json");
+        write_state_document(&path, "{\"theme\":\"dark\"}").expect("first write");
+        write_state_document(&path, "{\"theme\":\"light\"}").expect("second write");
+        std::fs::write(&path, "{\"theme\":").expect("truncate");
+
+        let report = repair_state_store_in(&data_dir, true).expect("repair");
+        let settings = &report.documents[1];
+        assert_eq!(settings.status, StateDocumentStatus::Restored);
+        assert_eq!(settings.restored_from.as_deref(), Some("json-backup"));
+        assert!(settings.quarantined_to.is_some());
+        assert_eq!(
+            read_state_document(&path).expect("read").as_deref(),
+            Some("{\"theme\":\"dark\"}")
+        );
+    }
+
+    #[test]
+    fn migrates_documents_into_sqlite_and_reads_them_back() {
+        let data_dir = temp_data_dir();
+        let path = data_dir.join("workspaces.json");
+        std::fs::write(&path, "[]").expect("seed workspaces");
+
+        let report = migrate_state_store_in(&data_dir, StateStoreBackend::Sqlite).expect("migrate");
+        assert_eq!(
+            report.migrated_documents,
+            vec!["workspaces.json".to_string()]
+        );
+        assert_eq!(selected_backend(&data_dir), StateStoreBackend::Sqlite);
+        assert!(!uses_json_file(&path));
+
+        write_state_document(&path, "[{\"id\":\"w1\"}]").expect("write through sqlite");
+        assert_eq!(std::fs::read_to_string(&path).expect("json left"), "[]");
+        assert_eq!(
+            read_state_document(&path).expect("read").as_deref(),
+            Some("[{\"id\":\"w1\"}]")
+        );
+
+        migrate_state_store_in(&data_dir, StateStoreBackend::Json).expect("migrate back");
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("json restored"),
+            "[{\"id\":\"w1\"}]"
+        );
+    }
+
+    #[test]
+    fn sqlite_backend_refills_missing_database_from_json_files() {
+        let data_dir = temp_data_dir();
+        std::fs::write(data_dir.join("settings.json"), "{}").expect("seed settings");
+        write_selected_backend(&data_dir, StateStoreBackend::Sqlite).expect("select sqlite");
+
+        let report = repair_state_store_in(&data_dir, true).expect("repair");
+        assert!(report.repaired);
+        assert_eq!(report.documents[1].restored_from.as_deref(), Some("json"));
+        assert_eq!(report.documents[0].status, StateDocumentStatus::Missing);
+        assert_eq!(
+            SqliteStateStore::new(&data_dir)
+                .read_document("settings.json")
+                .expect("read")
+                .as_deref(),
+            Some("{}")
+        );
+    }
+
+    #[test]
+    fn sqlite_rows_with_bad_checksum_are_reported() {
+        let data_dir = temp_data_dir();
+        let store = SqliteStateStore::new(&data_dir);
+        store
+            .write_documents(&[("settings.json", "{}")])
+            .expect("write");
+        store
+            .open()
+            .expect("open")
+            .execute(
+                "UPDATE state_documents SET content = '{\"x\":1}' WHERE name = 'settings.json'",
+                [],
+            )
+            .expect("tamper");
+        assert!(store.read_document("settings.json").is_err());
+    }
+}
@@ -32,7 +32,7 @@ impl Default for ThreadTitlesStore {
     }
 }
 
-fn normalize_title(value: &str) -> String {
+pub(crate) fn normalize_title(value: &str) -> String {
     let collapsed = value.split_whitespace().collect::<Vec<_>>().join(" ");
     let trimmed = collapsed.trim();
     let mut normalized = String::new();
@@ -0,0 +1,203 @@
+//! Per-workspace read-only mode.
+//!
+//! A read-only workspace rejects file writes and git mutations with a
+//! structured `WORKSPACE_READ_ONLY:{json}` error, and engine turns started in
+//! it never run with more than ask-before-write access.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::shared::access_presets::AccessModePreset;
+use crate::types::WorkspaceEntry;
+
+pub(crate) const WORKSPACE_READ_ONLY_ERROR_PREFIX: &str = "WORKSPACE_READ_ONLY:";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceReadOnlyError<'a> {
+    code: &'static str,
+    workspace_id: &'a str,
+    workspace_name: &'a str,
+    operation: &'a str,
+    message: String,
+}
+
+/// Commands that modify a workspace's files or repository. The daemon checks
+/// RPC methods against this list before dispatching them.
+#[allow(dead_code)]
+pub(crate) fn is_workspace_write_command(command: &str) -> bool {
+    matches!(
+        command,
+        "file_write"
+            | "write_workspace_file"
+            | "create_workspace_directory"
+            | "trash_workspace_item"
+            | "copy_workspace_item"
+            | "apply_worktree_changes"
+            | "create_git_pr_workflow"
+            | "stage_git_file"
+            | "stage_git_hunk"
+            | "stage_git_all"
+            | "unstage_git_file"
+            | "revert_git_file"
+            | "revert_git_all"
+            | "commit_git"
+            | "push_git"
+            | "pull_git"
+            | "sync_git"
+            | "git_pull"
+            | "git_push"
+            | "git_sync"
+            | "git_fetch"
+            | "update_git_branch"
+            | "cherry_pick_commit"
+            | "revert_commit"
+            | "reset_git_commit"
+            | "checkout_git_branch"
+            | "checkout_github_pull_request"
+            | "add_git_remote"
+            | "remove_git_remote"
+            | "rename_git_remote"
+            | "create_git_branch"
+            | "create_git_branch_from_branch"
+            | "create_git_branch_from_commit"
+            | "delete_git_branch"
+            | "create_git_tag"
+            | "delete_git_tag"
+            | "push_git_tag"
+            | "rename_git_branch"
+            | "merge_git_branch"
+            | "rebase_git_branch"
+            | "continue_git_operation"
+            | "abort_git_operation"
+            | "skip_git_operation"
+            | "resolve_git_conflict"
+            | "stash_git_changes"
+            | "apply_git_stash"
+            | "pop_git_stash"
+            | "drop_git_stash"
+            | "commit_agent_batch"
+            | "squash_merge_agent_branch"
+            | "commit_turn_changes"
+    )
+}
+
+pub(crate) fn workspace_read_only_error(entry: &WorkspaceEntry, operation: &str) -> String {
+    let error = WorkspaceReadOnlyError {
+        code: "workspace_read_only",
+        workspace_id: &entry.id,
+        workspace_name: &entry.name,
+        operation,
+        message: format!(
+            "Workspace \"{}\" is read-only; {operation} is blocked. Turn off read-only mode in workspace settings to allow changes.",
+            entry.name
+        ),
+    };
+    match serde_json::to_string(&error) {
+        Ok(payload) => format!("{WORKSPACE_READ_ONLY_ERROR_PREFIX}{payload}"),
+        Err(_) => format!("{WORKSPACE_READ_ONLY_ERROR_PREFIX}{{\"code\":\"workspace_read_only\"}}"),
+    }
+}
+
+pub(crate) fn ensure_workspace_writable(
+    entry: &WorkspaceEntry,
+    operation: &str,
+) -> Result<(), String> {
+    if entry.settings.read_only {
+        return Err(workspace_read_only_error(entry, operation));
+    }
+    Ok(())
+}
+
+/// Like [`ensure_workspace_writable`] for callers holding only an id. Unknown
+/// ids pass so the command itself reports the missing workspace.
+pub(crate) async fn ensure_workspace_id_writable(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    operation: &str,
+) -> Result<(), String> {
+    let workspaces = workspaces.lock().await;
+    match workspaces.get(workspace_id) {
+        Some(entry) => ensure_workspace_writable(entry, operation),
+        None => Ok(()),
+    }
+}
+
+/// Caps a requested access mode for read-only workspaces so writes always go
+/// through approval (or are refused), never auto-applied.
+pub(crate) fn cap_access_mode_for_workspace(
+    access_mode: Option<String>,
+    read_only: bool,
+) -> Option<String> {
+    if !read_only {
+        return access_mode;
+    }
+    let capped = match AccessModePreset::resolve(access_mode.as_deref()) {
+        AccessModePreset::ReadOnly => AccessModePreset::ReadOnly,
+        _ => AccessModePreset::AskBeforeWrite,
+    };
+    Some(capped.id().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cap_access_mode_for_workspace, ensure_workspace_writable, is_workspace_write_command,
+        WORKSPACE_READ_ONLY_ERROR_PREFIX,
+    };
+    use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+
+    fn entry(read_only: bool) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: "ws-1".to_string(),
+            name: "prod".to_string(),
+            path: "/srv/prod".to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                read_only,
+                ..WorkspaceSettings::default()
+            },
+        }
+    }
+
+    #[test]
+    fn read_only_workspace_rejects_writes_with_structured_error() {
+        assert!(ensure_workspace_writable(&entry(false), "commit_git").is_ok());
+
+        let error = ensure_workspace_writable(&entry(true), "commit_git").unwrap_err();
+        let payload = error
+            .strip_prefix(WORKSPACE_READ_ONLY_ERROR_PREFIX)
+            .expect("prefixed error");
+        let value: serde_json::Value = serde_json::from_str(payload).expect("json payload");
+        assert_eq!(value["code"], "workspace_read_only");
+        assert_eq!(value["workspaceId"], "ws-1");
+        assert_eq!(value["operation"], "commit_git");
+        assert!(is_workspace_write_command("commit_git"));
+        assert!(!is_workspace_write_command("get_git_status"));
+    }
+
+    #[test]
+    fn read_only_workspace_caps_engine_access_mode() {
+        assert_eq!(
+            cap_access_mode_for_workspace(Some("full-access".to_string()), false),
+            Some("full-access".to_string())
+        );
+        assert_eq!(
+            cap_access_mode_for_workspace(Some("full-access".to_string()), true),
+            Some("ask-before-write".to_string())
+        );
+        assert_eq!(
+            cap_access_mode_for_workspace(None, true),
+            Some("ask-before-write".to_string())
+        );
+        assert_eq!(
+            cap_access_mode_for_workspace(Some("plan".to_string()), true),
+            Some("read-only".to_string())
+        );
+    }
+}
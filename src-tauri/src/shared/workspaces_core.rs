@@ -10,11 +10,15 @@ use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
 use crate::runtime::RuntimeAcquireDisposition;
+use crate::shared::workspace_read_only::ensure_workspace_id_writable;
 use crate::shared::workspace_snapshot::resolve_workspace_and_parent;
+use crate::shared::worktree_naming::{
+    render_worktree_branch_name, worktree_name_candidate, WORKTREE_NAME_MAX_SUFFIX,
+};
 use crate::storage::{write_workspaces, write_workspaces_preserving_existing};
 use crate::types::{
     AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
-    WorktreeSetupStatus,
+    WorktreeNamingContext, WorktreeSetupStatus,
 };
 use uuid::Uuid;
 
@@ -84,6 +88,41 @@ pub(crate) fn ensure_workspace_path_dir_core(path: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Resolves a per-turn working directory against the workspace root.
+///
+/// Relative paths are joined onto the root. The result must be an existing
+/// directory that stays inside the root after symlinks are resolved. Returns
+/// `None` when no override was requested.
+pub(crate) fn resolve_turn_cwd_core(
+    workspace_root: &Path,
+    cwd: Option<&str>,
+) -> Result<Option<PathBuf>, String> {
+    let Some(cwd) = cwd.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let root = workspace_root
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    let requested = Path::new(cwd);
+    let candidate = if requested.is_absolute() {
+        requested.to_path_buf()
+    } else {
+        root.join(requested)
+    };
+    let resolved = candidate
+        .canonicalize()
+        .map_err(|err| format!("Working directory `{cwd}` is not accessible: {err}"))?;
+    if !resolved.is_dir() {
+        return Err(format!("Working directory `{cwd}` is not a folder."));
+    }
+    if !resolved.starts_with(&root) {
+        return Err(format!(
+            "Working directory `{cwd}` is outside the workspace root."
+        ));
+    }
+    Ok(Some(resolved))
+}
+
 pub(crate) async fn list_workspaces_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
@@ -373,6 +412,108 @@ fn resolve_base_ref_to_commit(repo_path: &PathBuf, base_ref: &str) -> Result<Str
     Ok(commit.id().to_string())
 }
 
+fn repo_user_name(repo_path: &Path) -> Option<String> {
+    git2::Repository::open(repo_path)
+        .ok()?
+        .config()
+        .ok()?
+        .get_string("user.name")
+        .ok()
+        .filter(|name| !name.trim().is_empty())
+}
+
+/// Renders the branch template (the override, else the parent's setting) and
+/// returns the first candidate that names neither an existing branch nor an
+/// existing worktree directory.
+async fn resolve_templated_worktree_branch<FSanitize, FBranchExists, FutBranchExists>(
+    parent: &WorkspaceEntry,
+    template_override: Option<&str>,
+    naming: &WorktreeNamingContext,
+    worktree_root: &Path,
+    sanitize_worktree_name: &FSanitize,
+    git_branch_exists: &FBranchExists,
+) -> Result<String, String>
+where
+    FSanitize: Fn(&str) -> String,
+    FBranchExists: Fn(&PathBuf, &str) -> FutBranchExists,
+    FutBranchExists: Future<Output = Result<bool, String>>,
+{
+    let template = template_override
+        .or(parent.settings.worktree_branch_template.as_deref())
+        .map(str::trim)
+        .filter(|template| !template.is_empty())
+        .ok_or_else(|| {
+            validation_error(
+                "Branch name is required; this workspace has no worktree branch template.",
+            )
+        })?;
+    let repo_path = PathBuf::from(&parent.path);
+    let date = chrono::Local::now().format("%Y%m%d").to_string();
+    let base = render_worktree_branch_name(
+        template,
+        naming,
+        repo_user_name(&repo_path).as_deref(),
+        &date,
+    )
+    .map_err(validation_error)?;
+    if let Err(message) = validate_local_branch_name_for_worktree(&base) {
+        return Err(validation_error(format!(
+            "Worktree branch template produced an invalid name. {message}"
+        )));
+    }
+
+    for attempt in 0..WORKTREE_NAME_MAX_SUFFIX {
+        let candidate = worktree_name_candidate(&base, attempt);
+        if worktree_root
+            .join(sanitize_worktree_name(&candidate))
+            .exists()
+        {
+            continue;
+        }
+        if git_branch_exists(&repo_path, &candidate).await? {
+            continue;
+        }
+        return Ok(candidate);
+    }
+    Err(validation_error(format!(
+        "No free branch name for {base}; tried up to {base}-{WORKTREE_NAME_MAX_SUFFIX}."
+    )))
+}
+
+/// Branch name `add_worktree` would pick for `naming`, without creating it.
+pub(crate) async fn preview_worktree_branch_name_core<FSanitize, FBranchExists, FutBranchExists>(
+    parent_id: &str,
+    template: Option<String>,
+    naming: WorktreeNamingContext,
+    data_dir: &PathBuf,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sanitize_worktree_name: FSanitize,
+    git_branch_exists: FBranchExists,
+) -> Result<String, String>
+where
+    FSanitize: Fn(&str) -> String,
+    FBranchExists: Fn(&PathBuf, &str) -> FutBranchExists,
+    FutBranchExists: Future<Output = Result<bool, String>>,
+{
+    let parent = {
+        let workspaces = workspaces.lock().await;
+        workspaces
+            .get(parent_id)
+            .cloned()
+            .ok_or_else(|| "parent workspace not found".to_string())?
+    };
+    let worktree_root = data_dir.join("worktrees").join(&parent.id);
+    resolve_templated_worktree_branch(
+        &parent,
+        template.as_deref(),
+        &naming,
+        &worktree_root,
+        &sanitize_worktree_name,
+        &git_branch_exists,
+    )
+    .await
+}
+
 pub(crate) async fn add_worktree_core<
     FSpawn,
     FutSpawn,
@@ -387,6 +528,7 @@ pub(crate) async fn add_worktree_core<
 >(
     parent_id: String,
     branch: String,
+    naming: Option<WorktreeNamingContext>,
     base_ref: Option<String>,
     publish_to_origin: bool,
     data_dir: &PathBuf,
@@ -413,9 +555,29 @@ where
     FRunGit: Fn(&PathBuf, &[&str]) -> FutRunGit,
     FutRunGit: Future<Output = Result<(), String>>,
 {
-    let branch = branch.trim().to_string();
-    if branch.is_empty() {
-        return Err(validation_error("Branch name is required."));
+    let mut branch = branch.trim().to_string();
+    match (branch.is_empty(), naming.as_ref()) {
+        (true, Some(naming)) => {
+            let parent = {
+                let workspaces = workspaces.lock().await;
+                workspaces
+                    .get(&parent_id)
+                    .cloned()
+                    .ok_or_else(|| "parent workspace not found".to_string())?
+            };
+            let worktree_root = data_dir.join("worktrees").join(&parent.id);
+            branch = resolve_templated_worktree_branch(
+                &parent,
+                None,
+                naming,
+                &worktree_root,
+                &sanitize_worktree_name,
+                &git_branch_exists,
+            )
+            .await?;
+        }
+        (true, None) => return Err(validation_error("Branch name is required.")),
+        (false, _) => {}
     }
     if let Err(message) = validate_local_branch_name_for_worktree(&branch) {
         return Err(validation_error(message));
@@ -1249,6 +1411,10 @@ where
     FutSpawn: Future<Output = Result<Arc<WorkspaceSession>, String>>,
 {
     settings.worktree_setup_script = normalize_setup_script(settings.worktree_setup_script);
+    settings.worktree_branch_template = settings
+        .worktree_branch_template
+        .map(|template| template.trim().to_string())
+        .filter(|template| !template.is_empty());
     settings.visible_thread_root_count =
         normalize_visible_thread_root_count(settings.visible_thread_root_count);
 
@@ -1489,6 +1655,7 @@ pub(crate) async fn write_workspace_file_core<F>(
 where
     F: Fn(&PathBuf, &str, &str) -> Result<(), String>,
 {
+    ensure_workspace_id_writable(workspaces, workspace_id, "write_workspace_file").await?;
     let root = resolve_workspace_root(workspaces, workspace_id).await?;
     write_file(&root, path, content)
 }
@@ -1502,6 +1669,7 @@ pub(crate) async fn create_workspace_directory_core<F>(
 where
     F: Fn(&PathBuf, &str) -> Result<(), String>,
 {
+    ensure_workspace_id_writable(workspaces, workspace_id, "create_workspace_directory").await?;
     let root = resolve_workspace_root(workspaces, workspace_id).await?;
     create_directory(&root, path)
 }
@@ -1515,6 +1683,7 @@ pub(crate) async fn trash_workspace_item_core<F>(
 where
     F: Fn(&PathBuf, &str) -> Result<(), String>,
 {
+    ensure_workspace_id_writable(workspaces, workspace_id, "trash_workspace_item").await?;
     let root = resolve_workspace_root(workspaces, workspace_id).await?;
     trash_item(&root, path)
 }
@@ -1528,6 +1697,7 @@ pub(crate) async fn copy_workspace_item_core<F>(
 where
     F: Fn(&PathBuf, &str) -> Result<String, String>,
 {
+    ensure_workspace_id_writable(workspaces, workspace_id, "copy_workspace_item").await?;
     let root = resolve_workspace_root(workspaces, workspace_id).await?;
     copy_item(&root, path)
 }
@@ -1547,7 +1717,7 @@ fn sort_workspaces(workspaces: &mut [WorkspaceInfo]) {
 mod tests {
     use super::{
         connect_workspace_core, list_workspaces_core, normalize_visible_thread_root_count,
-        normalize_workspace_display_name, resolve_base_ref_to_commit,
+        normalize_workspace_display_name, resolve_base_ref_to_commit, resolve_turn_cwd_core,
         validate_local_branch_name_for_worktree, workspace_name_from_path,
         workspace_requires_persistent_session,
     };
@@ -1779,4 +1949,36 @@ mod tests {
             .await
             .is_none());
     }
+
+    #[test]
+    fn resolve_turn_cwd_core_accepts_subdirectories_and_rejects_escapes() {
+        let root = std::env::temp_dir().join(format!("mossx-turn-cwd-{}", Uuid::new_v4()));
+        let nested = root.join("packages").join("app");
+        std::fs::create_dir_all(&nested).expect("create nested dir");
+        std::fs::write(root.join("README.md"), "hello\n").expect("write fixture file");
+        let canonical_nested = nested.canonicalize().expect("canonicalize nested");
+
+        assert_eq!(
+            resolve_turn_cwd_core(&root, None).expect("no override"),
+            None
+        );
+        assert_eq!(
+            resolve_turn_cwd_core(&root, Some("  ")).expect("blank override"),
+            None
+        );
+        assert_eq!(
+            resolve_turn_cwd_core(&root, Some("packages/app")).expect("relative override"),
+            Some(canonical_nested.clone())
+        );
+        assert_eq!(
+            resolve_turn_cwd_core(&root, Some(nested.to_str().expect("utf8 path")))
+                .expect("absolute override"),
+            Some(canonical_nested)
+        );
+        assert!(resolve_turn_cwd_core(&root, Some("..")).is_err());
+        assert!(resolve_turn_cwd_core(&root, Some("README.md")).is_err());
+        assert!(resolve_turn_cwd_core(&root, Some("missing")).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
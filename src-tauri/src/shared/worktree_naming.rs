@@ -0,0 +1,223 @@
+//! Branch naming templates for new worktrees, e.g. `{user}/{ticket}-{slug}`.
+//!
+//! Templates are rendered from a `WorktreeNamingContext` (issue metadata or
+//! the prompt that started the work). Separators left dangling by empty
+//! variables are dropped, so `{user}/{ticket}-{slug}` without a ticket still
+//! yields `alice/fix-login-redirect`.
+
+use crate::types::WorktreeNamingContext;
+
+pub(crate) const WORKTREE_TEMPLATE_VARIABLES: &[&str] = &["user", "ticket", "slug", "date"];
+const SLUG_MAX_WORDS: usize = 6;
+const SLUG_MAX_CHARS: usize = 48;
+/// Highest numeric suffix tried before giving up on a free branch name.
+pub(crate) const WORKTREE_NAME_MAX_SUFFIX: usize = 99;
+
+/// Lowercase ASCII words joined by `-`, capped at `max_words`.
+pub(crate) fn slugify(text: &str, max_words: usize) -> String {
+    let mut slug = String::new();
+    let words = text
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(max_words);
+    for word in words {
+        if !slug.is_empty() {
+            if slug.len() + 1 + word.len() > SLUG_MAX_CHARS {
+                break;
+            }
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    slug.truncate(SLUG_MAX_CHARS);
+    slug
+}
+
+/// Keeps ticket ids such as `ABC-123` intact; `#42` becomes `42`.
+fn clean_ticket(ticket: &str) -> String {
+    ticket
+        .trim()
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'))
+        .collect()
+}
+
+fn context_slug(context: &WorktreeNamingContext) -> String {
+    [context.title.as_deref(), context.prompt.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(|text| slugify(text, SLUG_MAX_WORDS))
+        .find(|slug| !slug.is_empty())
+        .unwrap_or_default()
+}
+
+/// Drops empty path segments and separators left behind by empty variables.
+fn tidy_branch_name(raw: &str) -> String {
+    raw.split('/')
+        .map(|segment| {
+            let mut collapsed = String::with_capacity(segment.len());
+            for ch in segment.chars() {
+                if matches!(ch, '-' | '_' | '.') && collapsed.ends_with(ch) {
+                    continue;
+                }
+                collapsed.push(ch);
+            }
+            collapsed
+                .trim_matches(|ch: char| matches!(ch, '-' | '_' | '.'))
+                .to_string()
+        })
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Renders `template`. `user_fallback` stands in for a missing
+/// `context.user`, typically the repo's `user.name`.
+pub(crate) fn render_worktree_branch_name(
+    template: &str,
+    context: &WorktreeNamingContext,
+    user_fallback: Option<&str>,
+    date: &str,
+) -> Result<String, String> {
+    let user = context
+        .user
+        .as_deref()
+        .or(user_fallback)
+        .map(|user| slugify(user, SLUG_MAX_WORDS))
+        .unwrap_or_default();
+    let ticket = context
+        .ticket
+        .as_deref()
+        .map(clean_ticket)
+        .unwrap_or_default();
+    let slug = context_slug(context);
+
+    let mut rendered = String::new();
+    let mut rest = template.trim();
+    while let Some(open) = rest.find('{') {
+        rendered.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let close = after
+            .find('}')
+            .ok_or_else(|| format!("Unclosed variable in worktree branch template: {template}"))?;
+        let value = match after[..close].trim() {
+            "user" => user.as_str(),
+            "ticket" => ticket.as_str(),
+            "slug" => slug.as_str(),
+            "date" => date,
+            other => {
+                return Err(format!(
+                    "Unknown variable {{{other}}} in worktree branch template; supported: {}",
+                    WORKTREE_TEMPLATE_VARIABLES
+                        .iter()
+                        .map(|name| format!("{{{name}}}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            }
+        };
+        rendered.push_str(value);
+        rest = &after[close + 1..];
+    }
+    rendered.push_str(rest);
+
+    let name = tidy_branch_name(&rendered);
+    if name.is_empty() {
+        return Err(
+            "Worktree branch template produced an empty name; provide a ticket, title or prompt."
+                .to_string(),
+        );
+    }
+    Ok(name)
+}
+
+/// `base` for the first attempt, then `base-2`, `base-3`, ...
+pub(crate) fn worktree_name_candidate(base: &str, attempt: usize) -> String {
+    if attempt == 0 {
+        base.to_string()
+    } else {
+        format!("{base}-{}", attempt + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(
+        ticket: Option<&str>,
+        title: Option<&str>,
+        prompt: Option<&str>,
+    ) -> WorktreeNamingContext {
+        WorktreeNamingContext {
+            user: None,
+            ticket: ticket.map(str::to_string),
+            title: title.map(str::to_string),
+            prompt: prompt.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn renders_template_variables() {
+        let name = render_worktree_branch_name(
+            "{user}/{ticket}-{slug}",
+            &context(Some("#ABC-123"), Some("Fix login redirect loop!"), None),
+            Some("Alice Smith"),
+            "20260101",
+        )
+        .expect("render");
+        assert_eq!(name, "alice-smith/ABC-123-fix-login-redirect-loop");
+
+        let dated = render_worktree_branch_name(
+            "wt/{date}/{slug}",
+            &context(
+                None,
+                None,
+                Some("  please add   dark mode to settings page and more words here"),
+            ),
+            None,
+            "20260101",
+        )
+        .expect("render from prompt");
+        assert_eq!(dated, "wt/20260101/please-add-dark-mode-to-settings");
+    }
+
+    #[test]
+    fn drops_separators_of_empty_variables() {
+        let name = render_worktree_branch_name(
+            "{user}/{ticket}-{slug}",
+            &context(None, Some("Refactor parser"), None),
+            None,
+            "20260101",
+        )
+        .expect("render");
+        assert_eq!(name, "refactor-parser");
+    }
+
+    #[test]
+    fn rejects_unknown_variables_and_empty_names() {
+        let unknown = render_worktree_branch_name(
+            "{team}/{slug}",
+            &context(None, Some("x"), None),
+            None,
+            "d",
+        )
+        .expect_err("unknown variable");
+        assert!(unknown.contains("{team}"));
+        assert!(unknown.contains("{ticket}"));
+        assert!(
+            render_worktree_branch_name("{ticket}", &context(None, None, None), None, "d").is_err()
+        );
+        assert!(
+            render_worktree_branch_name("{slug", &context(None, Some("x"), None), None, "d")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn numbers_collision_candidates() {
+        assert_eq!(worktree_name_candidate("feat/x", 0), "feat/x");
+        assert_eq!(worktree_name_candidate("feat/x", 1), "feat/x-2");
+        assert_eq!(worktree_name_candidate("feat/x", 2), "feat/x-3");
+    }
+}
@@ -16,6 +16,11 @@ use crate::engine::{self, EngineType};
 use crate::shared::codex_core;
 use crate::state::AppState;
 
+#[path = "shared_sessions_import.rs"]
+mod web_import;
+
+pub(crate) use web_import::*;
+
 const SHARED_SESSIONS_DIRNAME: &str = "shared-sessions";
 const SHARED_STORE_LOCK_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
 const SHARED_STORE_LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(25);
@@ -980,6 +985,9 @@ pub async fn send_shared_session_message(
                 None,
                 None,
                 custom_spec_root,
+                None,
+                None,
+                None,
                 app,
                 state,
             )
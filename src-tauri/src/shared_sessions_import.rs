@@ -0,0 +1,509 @@
+//! Imports conversation exports from claude.ai and ChatGPT as shared sessions.
+//!
+//! Both services export a `conversations.json` array. Each conversation becomes
+//! a shared session bound to the closest local engine (Claude for claude.ai,
+//! Codex for ChatGPT); no native binding exists yet, so the first message sent
+//! replays the imported turns through the usual delta sync.
+
+use super::*;
+
+const IMPORTED_SESSION_ID_PREFIX: &str = "web";
+const IMPORTED_TITLE_FALLBACK: &str = "Imported conversation";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WebExportSource {
+    Claude,
+    ChatGpt,
+}
+
+impl WebExportSource {
+    fn key(self) -> &'static str {
+        match self {
+            Self::Claude => "claude",
+            Self::ChatGpt => "chatgpt",
+        }
+    }
+
+    fn engine(self) -> EngineType {
+        match self {
+            Self::Claude => EngineType::Claude,
+            Self::ChatGpt => EngineType::Codex,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct WebConversation {
+    source_id: Option<String>,
+    title: Option<String>,
+    created_at: Option<u64>,
+    updated_at: Option<u64>,
+    items: Vec<Value>,
+}
+
+fn iso_to_millis(value: Option<&Value>) -> Option<u64> {
+    let raw = value?.as_str()?;
+    chrono::DateTime::parse_from_rfc3339(raw.trim())
+        .ok()
+        .and_then(|time| u64::try_from(time.timestamp_millis()).ok())
+}
+
+fn epoch_seconds_to_millis(value: Option<&Value>) -> Option<u64> {
+    let seconds = value?.as_f64()?;
+    (seconds.is_finite() && seconds > 0.0).then(|| (seconds * 1000.0) as u64)
+}
+
+fn non_empty_string(value: Option<&Value>) -> Option<String> {
+    let text = value?.as_str()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn message_item(
+    source: WebExportSource,
+    index: usize,
+    role: &str,
+    text: String,
+    created_at: Option<u64>,
+) -> Value {
+    let mut item = json!({
+        "id": format!("import-{}-{index}", source.key()),
+        "kind": "message",
+        "role": role,
+        "text": text,
+    });
+    if role == "assistant" {
+        item["engineSource"] = json!(source.engine());
+    }
+    if let Some(created_at) = created_at {
+        item["createdAt"] = json!(created_at);
+    }
+    item
+}
+
+/// Picks the exporter from the shape of the first conversation.
+fn detect_web_export_source(conversations: &[Value]) -> Option<WebExportSource> {
+    conversations.iter().find_map(|conversation| {
+        if conversation.get("chat_messages").is_some() {
+            Some(WebExportSource::Claude)
+        } else if conversation.get("mapping").is_some() {
+            Some(WebExportSource::ChatGpt)
+        } else {
+            None
+        }
+    })
+}
+
+/// claude.ai messages carry `text` plus typed `content` blocks; only text
+/// blocks are kept, tool use and attachments are dropped.
+fn claude_message_text(message: &Value) -> String {
+    let blocks: Vec<&str> = message
+        .get("content")
+        .and_then(Value::as_array)
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter(|block| block.get("type").and_then(Value::as_str) == Some("text"))
+                .filter_map(|block| block.get("text").and_then(Value::as_str))
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    if blocks.is_empty() {
+        message
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    } else {
+        blocks.join("\n\n")
+    }
+}
+
+fn parse_claude_conversation(conversation: &Value) -> WebConversation {
+    let items = conversation
+        .get("chat_messages")
+        .and_then(Value::as_array)
+        .map(|messages| {
+            messages
+                .iter()
+                .filter_map(|message| {
+                    let role = match message.get("sender").and_then(Value::as_str)? {
+                        "human" => "user",
+                        "assistant" => "assistant",
+                        _ => return None,
+                    };
+                    let text = claude_message_text(message);
+                    (!text.is_empty())
+                        .then(|| (role, text, iso_to_millis(message.get("created_at"))))
+                })
+                .enumerate()
+                .map(|(index, (role, text, created_at))| {
+                    message_item(WebExportSource::Claude, index, role, text, created_at)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    WebConversation {
+        source_id: non_empty_string(conversation.get("uuid")),
+        title: non_empty_string(conversation.get("name")),
+        created_at: iso_to_millis(conversation.get("created_at")),
+        updated_at: iso_to_millis(conversation.get("updated_at")),
+        items,
+    }
+}
+
+fn chatgpt_message_text(message: &Value) -> String {
+    let content = message.get("content");
+    let parts: Vec<&str> = content
+        .and_then(|content| content.get("parts"))
+        .and_then(Value::as_array)
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    if parts.is_empty() {
+        content
+            .and_then(|content| content.get("text"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    } else {
+        parts.join("\n\n")
+    }
+}
+
+/// ChatGPT stores every edit and regeneration as a tree under `mapping`; the
+/// visible thread is the path from `current_node` back to the root.
+fn parse_chatgpt_conversation(conversation: &Value) -> WebConversation {
+    let mapping = conversation.get("mapping").and_then(Value::as_object);
+    let mut path = Vec::new();
+    if let Some(mapping) = mapping {
+        let mut cursor = conversation
+            .get("current_node")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        while let Some(node_id) = cursor.take() {
+            // A malformed export could contain a parent cycle.
+            if path.len() > mapping.len() {
+                break;
+            }
+            let Some(node) = mapping.get(&node_id) else {
+                break;
+            };
+            path.push(node);
+            cursor = node
+                .get("parent")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+        }
+    }
+    path.reverse();
+
+    let items = path
+        .into_iter()
+        .filter_map(|node| {
+            let message = node.get("message")?;
+            let hidden = message
+                .get("metadata")
+                .and_then(|metadata| metadata.get("is_visually_hidden_from_conversation"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            if hidden {
+                return None;
+            }
+            let role = match message
+                .get("author")
+                .and_then(|author| author.get("role"))
+                .and_then(Value::as_str)?
+            {
+                "user" => "user",
+                "assistant" => "assistant",
+                _ => return None,
+            };
+            let text = chatgpt_message_text(message);
+            (!text.is_empty()).then(|| {
+                (
+                    role,
+                    text,
+                    epoch_seconds_to_millis(message.get("create_time")),
+                )
+            })
+        })
+        .enumerate()
+        .map(|(index, (role, text, created_at))| {
+            message_item(WebExportSource::ChatGpt, index, role, text, created_at)
+        })
+        .collect();
+    WebConversation {
+        source_id: non_empty_string(conversation.get("conversation_id"))
+            .or_else(|| non_empty_string(conversation.get("id"))),
+        title: non_empty_string(conversation.get("title")),
+        created_at: epoch_seconds_to_millis(conversation.get("create_time")),
+        updated_at: epoch_seconds_to_millis(conversation.get("update_time")),
+        items,
+    }
+}
+
+/// Accepts a full `conversations.json` array or a single conversation object.
+fn parse_web_export(
+    raw: &str,
+    source: Option<WebExportSource>,
+) -> Result<(WebExportSource, Vec<WebConversation>), String> {
+    let parsed: Value =
+        serde_json::from_str(raw).map_err(|error| format!("Invalid export JSON: {error}"))?;
+    let conversations = match parsed {
+        Value::Array(conversations) => conversations,
+        Value::Object(_) => vec![parsed],
+        _ => return Err("Export must be a conversation or a list of conversations".to_string()),
+    };
+    let source = source
+        .or_else(|| detect_web_export_source(&conversations))
+        .ok_or_else(|| {
+            "Unrecognized export format; expected a claude.ai or ChatGPT conversations.json"
+                .to_string()
+        })?;
+    let parse = match source {
+        WebExportSource::Claude => parse_claude_conversation,
+        WebExportSource::ChatGpt => parse_chatgpt_conversation,
+    };
+    Ok((source, conversations.iter().map(parse).collect()))
+}
+
+/// Stable per source conversation so importing the same export twice skips
+/// conversations that are already present.
+fn imported_session_id(source: WebExportSource, source_id: Option<&str>) -> String {
+    let sanitized: String = source_id
+        .unwrap_or_default()
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '-' || *ch == '_')
+        .collect();
+    let suffix = if sanitized.is_empty() {
+        Uuid::new_v4().to_string()
+    } else {
+        sanitized
+    };
+    format!("{IMPORTED_SESSION_ID_PREFIX}-{}-{suffix}", source.key())
+}
+
+fn import_web_conversation(
+    workspace_id: &str,
+    source: WebExportSource,
+    conversation: WebConversation,
+) -> Result<Option<Value>, String> {
+    let shared_session_id = imported_session_id(source, conversation.source_id.as_deref());
+    if shared_session_meta_path(workspace_id, &shared_session_id)?.exists() {
+        return Ok(None);
+    }
+    let now = now_millis();
+    let created_at = conversation.created_at.unwrap_or(now);
+    let updated_at = conversation.updated_at.unwrap_or(created_at);
+    let title = conversation
+        .title
+        .or_else(|| extract_first_user_title(&conversation.items))
+        .unwrap_or_else(|| IMPORTED_TITLE_FALLBACK.to_string());
+    let selected_engine = source.engine();
+    let meta = SharedSessionMeta {
+        id: shared_session_id.clone(),
+        workspace_id: workspace_id.to_string(),
+        title,
+        created_at,
+        updated_at,
+        selected_engine,
+        last_turn_seq: count_user_turns(&conversation.items),
+        bindings_by_engine: HashMap::new(),
+    };
+    std::fs::create_dir_all(shared_session_dir(workspace_id, &shared_session_id)?)
+        .map_err(|error| error.to_string())?;
+    write_shared_session_meta(&meta)?;
+    let entry = SharedSessionSnapshotEntry {
+        kind: "snapshot".to_string(),
+        created_at: now,
+        selected_engine,
+        last_turn_seq: meta.last_turn_seq,
+        items: conversation.items,
+    };
+    append_shared_session_log_entry(workspace_id, &shared_session_id, &entry)?;
+    Ok(Some(json!({
+        "id": shared_thread_id(&meta.id),
+        "name": meta.title,
+        "updatedAt": meta.updated_at,
+        "threadKind": "shared",
+        "engineSource": meta.selected_engine,
+        "selectedEngine": meta.selected_engine,
+        "nativeThreadIds": Vec::<String>::new(),
+    })))
+}
+
+#[tauri::command]
+pub async fn import_web_conversation_export(
+    workspace_id: String,
+    path: String,
+    source: Option<WebExportSource>,
+    state: State<'_, AppState>,
+) -> Result<Value, String> {
+    ensure_known_workspace(&state.workspaces, &workspace_id).await?;
+    let raw = tokio::fs::read_to_string(path.trim())
+        .await
+        .map_err(|error| format!("Failed to read export {path}: {error}"))?;
+    let (source, conversations) = parse_web_export(&raw, source)?;
+
+    let mut threads = Vec::new();
+    let mut skipped = 0_usize;
+    for conversation in conversations {
+        if conversation.items.is_empty() {
+            skipped += 1;
+            continue;
+        }
+        match import_web_conversation(&workspace_id, source, conversation)? {
+            Some(thread) => threads.push(thread),
+            None => skipped += 1,
+        }
+    }
+    Ok(json!({
+        "source": source,
+        "imported": threads.len(),
+        "skipped": skipped,
+        "threads": threads,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(conversation: &WebConversation) -> Vec<(String, String)> {
+        conversation
+            .items
+            .iter()
+            .map(|item| {
+                (
+                    item["role"].as_str().unwrap_or_default().to_string(),
+                    item["text"].as_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_claude_ai_export() {
+        let raw = json!([{
+            "uuid": "c0ffee-1",
+            "name": "Parser plan",
+            "created_at": "2025-03-01T10:00:00.000000Z",
+            "updated_at": "2025-03-01T10:05:00Z",
+            "chat_messages": [
+                { "sender": "human", "text": "How should I parse this?", "content": [] },
+                {
+                    "sender": "assistant",
+                    "text": "ignored when blocks exist",
+                    "content": [
+                        { "type": "text", "text": "Use a recursive descent parser." },
+                        { "type": "tool_use", "name": "search" },
+                        { "type": "text", "text": "Start with the lexer." }
+                    ]
+                },
+                { "sender": "human", "text": "   " }
+            ]
+        }])
+        .to_string();
+
+        let (source, conversations) = parse_web_export(&raw, None).expect("parse export");
+        assert_eq!(source, WebExportSource::Claude);
+        let conversation = &conversations[0];
+        assert_eq!(conversation.source_id.as_deref(), Some("c0ffee-1"));
+        assert_eq!(conversation.title.as_deref(), Some("Parser plan"));
+        assert_eq!(conversation.created_at, Some(1_740_823_200_000));
+        assert_eq!(
+            texts(conversation),
+            vec![
+                ("user".to_string(), "How should I parse this?".to_string()),
+                (
+                    "assistant".to_string(),
+                    "Use a recursive descent parser.\n\nStart with the lexer.".to_string()
+                ),
+            ]
+        );
+        assert_eq!(conversation.items[1]["engineSource"], json!("claude"));
+        assert_eq!(count_user_turns(&conversation.items), 1);
+    }
+
+    #[test]
+    fn follows_current_branch_of_chatgpt_export() {
+        let raw = json!({
+            "title": "Regenerated",
+            "conversation_id": "abc-123",
+            "create_time": 1_700_000_000.5,
+            "current_node": "a2",
+            "mapping": {
+                "root": { "message": null, "parent": null, "children": ["sys"] },
+                "sys": {
+                    "parent": "root",
+                    "message": {
+                        "author": { "role": "system" },
+                        "content": { "content_type": "text", "parts": ["You are helpful"] }
+                    }
+                },
+                "u1": {
+                    "parent": "sys",
+                    "message": {
+                        "author": { "role": "user" },
+                        "create_time": 1_700_000_001.0,
+                        "content": { "content_type": "text", "parts": ["Name a color"] }
+                    }
+                },
+                "a1": {
+                    "parent": "u1",
+                    "message": {
+                        "author": { "role": "assistant" },
+                        "content": { "content_type": "text", "parts": ["Red"] }
+                    }
+                },
+                "a2": {
+                    "parent": "u1",
+                    "message": {
+                        "author": { "role": "assistant" },
+                        "content": { "content_type": "multimodal_text", "parts": [{ "asset": 1 }, "Blue"] }
+                    }
+                }
+            }
+        })
+        .to_string();
+
+        let (source, conversations) = parse_web_export(&raw, None).expect("parse export");
+        assert_eq!(source, WebExportSource::ChatGpt);
+        let conversation = &conversations[0];
+        assert_eq!(conversation.created_at, Some(1_700_000_000_500));
+        assert_eq!(
+            texts(conversation),
+            vec![
+                ("user".to_string(), "Name a color".to_string()),
+                ("assistant".to_string(), "Blue".to_string()),
+            ]
+        );
+        assert_eq!(
+            conversation.items[0]["createdAt"],
+            json!(1_700_000_001_000_u64)
+        );
+        assert_eq!(conversation.items[1]["engineSource"], json!("codex"));
+        assert_eq!(
+            imported_session_id(source, conversation.source_id.as_deref()),
+            "web-chatgpt-abc-123"
+        );
+    }
+
+    #[test]
+    fn rejects_unrecognized_exports() {
+        assert!(parse_web_export("[{\"foo\": 1}]", None).is_err());
+        assert!(parse_web_export("\"text\"", None).is_err());
+        assert!(parse_web_export("not json", None).is_err());
+    }
+}
@@ -7,6 +7,7 @@ use tokio::sync::Mutex;
 
 use crate::app_paths;
 use crate::dictation::DictationState;
+use crate::engine::claude::ClaudeWarmPoolConfig;
 use crate::engine::{EngineConfig, EngineManager, EngineType};
 use crate::shared::proxy_core;
 use crate::storage::{read_settings, read_workspaces};
@@ -30,6 +31,9 @@ pub(crate) struct AppState {
     pub(crate) dictation: Mutex<DictationState>,
     pub(crate) codex_login_cancels: Mutex<HashMap<String, oneshot::Sender<()>>>,
     pub(crate) detached_external_change_runtime: Mutex<DetachedExternalChangeRuntime>,
+    pub(crate) branch_status_runtime: Mutex<crate::git::BranchStatusRuntime>,
+    pub(crate) git_status_watch_runtime: Mutex<crate::git::GitStatusWatchRuntime>,
+    pub(crate) workspace_preload: Mutex<crate::workspaces::WorkspacePreloadRuntime>,
     pub(crate) runtime_manager: Arc<crate::runtime::RuntimeManager>,
     /// Multi-engine manager
     pub(crate) engine_manager: EngineManager,
@@ -60,6 +64,17 @@ impl AppState {
             )
             .await;
 
+        self.engine_manager
+            .claude_manager
+            .set_warm_pool_config(ClaudeWarmPoolConfig::from(&settings.engine_warm_pool))
+            .await;
+        self.engine_manager
+            .claude_manager
+            .set_user_input_timeout(std::time::Duration::from_secs(
+                settings.claude_question_timeout_secs,
+            ))
+            .await;
+
         if previous_claude_bin != new_claude_bin {
             let sessions = self.engine_manager.claude_manager.list_sessions().await;
             for (workspace_id, _session) in sessions {
@@ -80,15 +95,23 @@ impl AppState {
     }
 
     pub(crate) fn load(app: &AppHandle) -> Self {
-        let data_dir = app
+        let tauri_data_dir = app
             .path()
             .app_data_dir()
             .unwrap_or_else(|_| std::env::current_dir().unwrap_or_else(|_| ".".into()));
-        if let Err(error) = app_paths::prepare_app_data_dir(&data_dir) {
+        if let Err(error) = app_paths::prepare_app_data_dir(&tauri_data_dir) {
             eprintln!("[storage] failed to prepare app data dir migration: {error}");
         }
+        if let Err(error) = crate::data_dir::adopt_app_data(app) {
+            eprintln!("[storage] failed to move app data into the data directory: {error}");
+        }
+        if let Err(error) = crate::data_dir::remove_staged_data_dirs(app) {
+            eprintln!("[storage] failed to remove the previous data directory: {error}");
+        }
+        let data_dir = app_paths::resolve_app_data_dir(&tauri_data_dir);
         let storage_path = data_dir.join("workspaces.json");
         let settings_path = data_dir.join("settings.json");
+        crate::state_store::repair_state_store_on_startup(&data_dir);
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
         if let Err(error) = proxy_core::apply_app_proxy_settings(&app_settings) {
@@ -97,6 +120,7 @@ impl AppState {
         let runtime_manager = Arc::new(crate::runtime::RuntimeManager::new(&data_dir));
         runtime_manager.orphan_sweep_on_startup(app_settings.runtime_orphan_sweep_on_launch);
         let engine_manager = EngineManager::new();
+        crate::engine::register_builtin_engine_adapters(&engine_manager);
         Self {
             workspaces: Mutex::new(workspaces),
             sessions: Mutex::new(HashMap::new()),
@@ -112,6 +136,9 @@ impl AppState {
             dictation: Mutex::new(DictationState::default()),
             codex_login_cancels: Mutex::new(HashMap::new()),
             detached_external_change_runtime: Mutex::new(DetachedExternalChangeRuntime::default()),
+            branch_status_runtime: Mutex::new(crate::git::BranchStatusRuntime::default()),
+            git_status_watch_runtime: Mutex::new(crate::git::GitStatusWatchRuntime::default()),
+            workspace_preload: Mutex::new(crate::workspaces::WorkspacePreloadRuntime::default()),
             runtime_manager,
             engine_manager,
         }
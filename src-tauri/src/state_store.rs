@@ -0,0 +1,89 @@
+//! Commands for the app state storage backend (see
+//! `shared::state_store_core`): integrity status, repair, and switching
+//! between the JSON files and the SQLite database.
+
+use std::path::{Path, PathBuf};
+
+use tauri::State;
+
+use crate::shared::state_store_core::{
+    migrate_state_store_in, repair_state_store_in, StateStoreBackend, StateStoreIntegrityReport,
+    StateStoreMigrationReport,
+};
+use crate::state::AppState;
+
+fn data_dir_for_state(state: &AppState) -> Result<PathBuf, String> {
+    state
+        .storage_path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+/// Repairs damaged state documents before `AppState` reads them; called
+/// once during app load. Failures are logged and leave the files untouched.
+pub(crate) fn repair_state_store_on_startup(data_dir: &Path) {
+    let report = match repair_state_store_in(data_dir, true) {
+        Ok(report) => report,
+        Err(error) => {
+            log::warn!("[state-store] integrity check failed: {error}");
+            return;
+        }
+    };
+    if let Some(error) = report.container_error.as_deref() {
+        log::warn!(
+            "[state-store] {:?} database failed its check ({error}); moved to {}",
+            report.backend,
+            report
+                .container_quarantined_to
+                .as_deref()
+                .unwrap_or("<nowhere>")
+        );
+    }
+    for document in &report.documents {
+        match (&document.restored_from, &document.detail) {
+            (Some(source), _) => {
+                log::warn!("[state-store] restored {} from {source}", document.name)
+            }
+            (None, Some(detail)) => {
+                log::warn!("[state-store] {} is unreadable: {detail}", document.name)
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Reports the selected backend and the state of every document without
+/// changing anything.
+#[tauri::command]
+pub(crate) async fn get_state_store_status(
+    state: State<'_, AppState>,
+) -> Result<StateStoreIntegrityReport, String> {
+    let data_dir = data_dir_for_state(&state)?;
+    tokio::task::spawn_blocking(move || repair_state_store_in(&data_dir, false))
+        .await
+        .map_err(|error| error.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn repair_state_store(
+    state: State<'_, AppState>,
+) -> Result<StateStoreIntegrityReport, String> {
+    let data_dir = data_dir_for_state(&state)?;
+    tokio::task::spawn_blocking(move || repair_state_store_in(&data_dir, true))
+        .await
+        .map_err(|error| error.to_string())?
+}
+
+/// Copies the state documents into `backend` and selects it. The in-memory
+/// state is unchanged, so no reload is needed.
+#[tauri::command]
+pub(crate) async fn migrate_state_store(
+    state: State<'_, AppState>,
+    backend: StateStoreBackend,
+) -> Result<StateStoreMigrationReport, String> {
+    let data_dir = data_dir_for_state(&state)?;
+    tokio::task::spawn_blocking(move || migrate_state_store_in(&data_dir, backend))
+        .await
+        .map_err(|error| error.to_string())?
+}
@@ -6,6 +6,8 @@ use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant};
 
+use crate::shared::config_journal::record_config_write;
+use crate::shared::state_store_core::{read_state_document, uses_json_file, write_state_document};
 use crate::types::{AppSettings, WorkspaceEntry};
 use uuid::Uuid;
 
@@ -48,6 +50,7 @@ fn is_lock_file_stale(lock_path: &Path) -> bool {
 }
 
 fn acquire_storage_lock(path: &Path) -> Result<StorageFileLock, String> {
+    crate::app_paths::ensure_data_dir_writable(path)?;
     let lock_path = lock_file_path(path);
     if let Some(parent) = lock_path.parent() {
         std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
@@ -91,6 +94,7 @@ pub(crate) fn with_storage_lock<T>(
 }
 
 pub(crate) fn write_string_atomically(path: &Path, content: &str) -> Result<(), String> {
+    crate::app_paths::ensure_data_dir_writable(path)?;
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
     }
@@ -125,10 +129,9 @@ pub(crate) fn write_string_atomically(path: &Path, content: &str) -> Result<(),
 }
 
 fn read_workspace_list(path: &Path) -> Result<Vec<WorkspaceEntry>, String> {
-    if !path.exists() {
+    let Some(data) = read_state_document(path)? else {
         return Ok(Vec::new());
-    }
-    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    };
     serde_json::from_str(&data).map_err(|e| e.to_string())
 }
 
@@ -228,7 +231,7 @@ pub(crate) fn read_workspaces(path: &PathBuf) -> Result<HashMap<String, Workspac
 pub(crate) fn write_workspaces(path: &PathBuf, entries: &[WorkspaceEntry]) -> Result<(), String> {
     with_storage_lock(path, || {
         let data = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
-        write_string_atomically(path, &data)
+        write_state_document(path, &data)
     })
 }
 
@@ -240,16 +243,15 @@ pub(crate) fn write_workspaces_preserving_existing(
         let existing = read_workspace_list(path)?;
         let merged = merge_workspace_entries(existing, entries);
         let data = serde_json::to_string_pretty(&merged).map_err(|e| e.to_string())?;
-        write_string_atomically(path, &data)?;
+        write_state_document(path, &data)?;
         Ok(merged)
     })
 }
 
 pub(crate) fn read_settings(path: &PathBuf) -> Result<AppSettings, String> {
-    if !path.exists() {
+    let Some(data) = read_state_document(path)? else {
         return Ok(AppSettings::default());
-    }
-    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    };
     let mut settings: AppSettings = serde_json::from_str(&data).map_err(|e| e.to_string())?;
     settings.normalize_unified_exec_policy();
     settings.upgrade_runtime_pool_settings_for_startup();
@@ -260,7 +262,11 @@ pub(crate) fn read_settings(path: &PathBuf) -> Result<AppSettings, String> {
 pub(crate) fn write_settings(path: &PathBuf, settings: &AppSettings) -> Result<(), String> {
     with_storage_lock(path, || {
         let data = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
-        write_string_atomically(path, &data)
+        // The config journal snapshots files, so it only covers the JSON backend.
+        if uses_json_file(path) {
+            record_config_write("app-settings", path, &data);
+        }
+        write_state_document(path, &data)
     })
 }
 
@@ -0,0 +1,534 @@
+//! Schema versions and startup migrations for app-managed stores.
+//!
+//! The version of every store (thread titles, session organization, input
+//! usage history, config snapshots) is tracked in `~/.ccgui/store-versions.json`.
+//! On startup each store is brought up to its current version by running the
+//! pending migration steps in order; rewritten files are first copied to
+//! `~/.ccgui/store-migrations/`. `store_migration_report` shows what a run
+//! would change without touching anything.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tauri::State;
+
+use crate::app_paths;
+use crate::shared::config_journal;
+use crate::shared::thread_titles_core::normalize_title;
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const STORE_VERSIONS_FILE: &str = "store-versions.json";
+const BACKUP_DIR_NAME: &str = "store-migrations";
+
+/// Where the stores live: `~/.ccgui` and the Tauri app data dir.
+pub(crate) struct StoreRoots {
+    pub(crate) app_home: PathBuf,
+    pub(crate) data_dir: PathBuf,
+}
+
+struct StoreMigration {
+    store: &'static str,
+    version: u32,
+    description: &'static str,
+    files: fn(&StoreRoots) -> Vec<PathBuf>,
+    /// Returns the migrated document; files whose document is unchanged are
+    /// left alone.
+    migrate: fn(&Path, Value) -> Value,
+}
+
+const MIGRATIONS: &[StoreMigration] = &[
+    StoreMigration {
+        store: "thread-titles",
+        version: 1,
+        description: "Wrap legacy flat title maps and normalize titles",
+        files: thread_titles_files,
+        migrate: migrate_thread_titles_v1,
+    },
+    StoreMigration {
+        store: "session-organization",
+        version: 1,
+        description: "Drop folder links that point at deleted folders",
+        files: session_organization_files,
+        migrate: migrate_session_organization_v1,
+    },
+    StoreMigration {
+        store: "input-usage",
+        version: 1,
+        description: "Normalize input history items and usage counts",
+        files: input_usage_files,
+        migrate: migrate_input_usage_v1,
+    },
+    StoreMigration {
+        store: "config-snapshots",
+        version: 1,
+        description: "Remove journal entries whose snapshot file is missing",
+        files: config_snapshot_files,
+        migrate: migrate_config_snapshots_v1,
+    },
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoreMigrationStep {
+    pub(crate) version: u32,
+    pub(crate) description: String,
+    pub(crate) changed_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoreMigrationStatus {
+    pub(crate) store: String,
+    pub(crate) from_version: u32,
+    pub(crate) to_version: u32,
+    pub(crate) steps: Vec<StoreMigrationStep>,
+    /// Set when a step failed; the store then stays at the last version that
+    /// completed and is retried on the next run.
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoreMigrationReport {
+    pub(crate) dry_run: bool,
+    pub(crate) backup_dir: Option<String>,
+    pub(crate) stores: Vec<StoreMigrationStatus>,
+}
+
+fn thread_titles_files(roots: &StoreRoots) -> Vec<PathBuf> {
+    vec![roots.app_home.join("client").join("thread_titles.json")]
+}
+
+fn session_organization_files(roots: &StoreRoots) -> Vec<PathBuf> {
+    let dir = roots.data_dir.join("session-management").join("workspaces");
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|value| value.to_str()) == Some("json"))
+        .collect();
+    files.sort();
+    files
+}
+
+fn input_usage_files(roots: &StoreRoots) -> Vec<PathBuf> {
+    vec![roots.app_home.join("inputHistory.json")]
+}
+
+fn config_snapshot_files(roots: &StoreRoots) -> Vec<PathBuf> {
+    vec![roots
+        .app_home
+        .join(config_journal::JOURNAL_DIR_NAME)
+        .join(config_journal::JOURNAL_INDEX_FILE)]
+}
+
+fn migrate_thread_titles_v1(_path: &Path, value: Value) -> Value {
+    let source = match value {
+        Value::Object(mut object) if object.contains_key("titles") => {
+            object.remove("titles").unwrap_or_default()
+        }
+        other => other,
+    };
+    let mut titles = Map::new();
+    if let Value::Object(source) = source {
+        for (thread_id, title) in source {
+            let Some(title) = title.as_str().map(normalize_title) else {
+                continue;
+            };
+            if !thread_id.trim().is_empty() && !title.is_empty() {
+                titles.insert(thread_id, Value::String(title));
+            }
+        }
+    }
+    json!({ "version": 1, "titles": titles })
+}
+
+fn drop_dangling_folder_links(object: &mut Map<String, Value>) {
+    let folder_ids: HashSet<String> = object
+        .get("folders")
+        .and_then(Value::as_array)
+        .map(|folders| {
+            folders
+                .iter()
+                .filter_map(|folder| folder.get("id").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(folders) = object.get_mut("folders").and_then(Value::as_array_mut) {
+        for folder in folders.iter_mut().filter_map(Value::as_object_mut) {
+            let dangling = folder
+                .get("parentId")
+                .and_then(Value::as_str)
+                .map(|parent_id| !folder_ids.contains(parent_id))
+                .unwrap_or(false);
+            if dangling {
+                folder.remove("parentId");
+            }
+        }
+    }
+    if let Some(assignments) = object
+        .get_mut("folderIdBySessionId")
+        .and_then(Value::as_object_mut)
+    {
+        assignments.retain(|_, folder_id| {
+            folder_id
+                .as_str()
+                .map(|folder_id| folder_ids.contains(folder_id))
+                .unwrap_or(false)
+        });
+    }
+}
+
+fn migrate_session_organization_v1(_path: &Path, mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        drop_dangling_folder_links(object);
+    }
+    value
+}
+
+fn migrate_input_usage_v1(_path: &Path, value: Value) -> Value {
+    let items: Vec<Value> = value
+        .get("items")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter(|item| item.as_str().is_some_and(|text| !text.trim().is_empty()))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    let counts: Map<String, Value> = value
+        .get("counts")
+        .and_then(Value::as_object)
+        .map(|counts| {
+            counts
+                .iter()
+                .filter(|(_, count)| count.as_i64().is_some_and(|count| count > 0))
+                .map(|(key, count)| (key.clone(), count.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    json!({ "items": items, "counts": counts })
+}
+
+fn migrate_config_snapshots_v1(path: &Path, value: Value) -> Value {
+    let Some(journal_dir) = path.parent() else {
+        return value;
+    };
+    let Value::Array(entries) = value else {
+        return Value::Array(Vec::new());
+    };
+    Value::Array(
+        entries
+            .into_iter()
+            .filter(|entry| {
+                entry
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .map(|id| config_journal::snapshot_path(journal_dir, id).is_file())
+                    .unwrap_or(false)
+            })
+            .collect(),
+    )
+}
+
+fn versions_path(roots: &StoreRoots) -> PathBuf {
+    roots.app_home.join(STORE_VERSIONS_FILE)
+}
+
+fn read_versions(roots: &StoreRoots) -> Result<BTreeMap<String, u32>, String> {
+    Ok(read_json_file(&versions_path(roots))?.unwrap_or_default())
+}
+
+fn write_versions(roots: &StoreRoots, versions: &BTreeMap<String, u32>) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(versions).map_err(|error| error.to_string())?;
+    write_string_atomically(&versions_path(roots), &data)
+}
+
+fn current_version(store: &str) -> u32 {
+    MIGRATIONS
+        .iter()
+        .filter(|migration| migration.store == store)
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or(0)
+}
+
+fn store_names() -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = Vec::new();
+    for migration in MIGRATIONS {
+        if !names.contains(&migration.store) {
+            names.push(migration.store);
+        }
+    }
+    names
+}
+
+fn backup_file(backup_dir: &Path, store: &str, path: &Path) -> Result<(), String> {
+    let target_dir = backup_dir.join(store);
+    std::fs::create_dir_all(&target_dir).map_err(|error| error.to_string())?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid store file path: {}", path.display()))?;
+    std::fs::copy(path, target_dir.join(file_name))
+        .map(|_| ())
+        .map_err(|error| format!("Failed to back up {}: {error}", path.display()))
+}
+
+/// Runs one step over every file of its store. In a dry run the changed
+/// files are only reported.
+fn run_step(
+    roots: &StoreRoots,
+    migration: &StoreMigration,
+    backup_dir: Option<&Path>,
+) -> Result<Vec<String>, String> {
+    let mut changed = Vec::new();
+    for path in (migration.files)(roots) {
+        if !path.is_file() {
+            continue;
+        }
+        let rewritten = with_storage_lock(&path, || {
+            let raw = std::fs::read_to_string(&path)
+                .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+            let original: Value = if raw.trim().is_empty() {
+                Value::Null
+            } else {
+                serde_json::from_str(&raw)
+                    .map_err(|error| format!("Failed to parse {}: {error}", path.display()))?
+            };
+            let migrated = (migration.migrate)(&path, original.clone());
+            if migrated == original {
+                return Ok(false);
+            }
+            if let Some(backup_dir) = backup_dir {
+                backup_file(backup_dir, migration.store, &path)?;
+                let data = serde_json::to_string_pretty(&migrated)
+                    .map_err(|error| format!("Failed to serialize {}: {error}", path.display()))?;
+                write_string_atomically(&path, &data)?;
+            }
+            Ok(true)
+        })?;
+        if rewritten {
+            changed.push(path.to_string_lossy().to_string());
+        }
+    }
+    Ok(changed)
+}
+
+pub(crate) fn run_migrations_in(
+    roots: &StoreRoots,
+    dry_run: bool,
+) -> Result<StoreMigrationReport, String> {
+    let versions_lock = versions_path(roots);
+    with_storage_lock(&versions_lock, || {
+        let mut versions = read_versions(roots)?;
+        let backup_dir = roots
+            .app_home
+            .join(BACKUP_DIR_NAME)
+            .join(chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ").to_string());
+        let mut report = StoreMigrationReport {
+            dry_run,
+            backup_dir: None,
+            stores: Vec::new(),
+        };
+        for store in store_names() {
+            let from_version = versions.get(store).copied().unwrap_or(0);
+            let mut status = StoreMigrationStatus {
+                store: store.to_string(),
+                from_version,
+                to_version: current_version(store),
+                steps: Vec::new(),
+                error: None,
+            };
+            for migration in MIGRATIONS
+                .iter()
+                .filter(|migration| migration.store == store && migration.version > from_version)
+            {
+                match run_step(roots, migration, (!dry_run).then_some(backup_dir.as_path())) {
+                    Ok(changed_files) => {
+                        if !dry_run {
+                            versions.insert(store.to_string(), migration.version);
+                            if !changed_files.is_empty() {
+                                report.backup_dir = Some(backup_dir.to_string_lossy().to_string());
+                            }
+                        }
+                        status.steps.push(StoreMigrationStep {
+                            version: migration.version,
+                            description: migration.description.to_string(),
+                            changed_files,
+                        });
+                    }
+                    Err(error) => {
+                        status.error = Some(error);
+                        break;
+                    }
+                }
+            }
+            report.stores.push(status);
+        }
+        if !dry_run {
+            write_versions(roots, &versions)?;
+        }
+        Ok(report)
+    })
+}
+
+fn store_roots(data_dir: &Path) -> Result<StoreRoots, String> {
+    Ok(StoreRoots {
+        app_home: app_paths::app_home_dir()?,
+        data_dir: data_dir.to_path_buf(),
+    })
+}
+
+fn data_dir_for_state(state: &AppState) -> Result<PathBuf, String> {
+    state
+        .storage_path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+/// Brings every store up to date; called once during app setup. Failures are
+/// logged and retried on the next launch.
+pub(crate) fn run_startup_migrations(data_dir: &Path) {
+    let report = match store_roots(data_dir).and_then(|roots| run_migrations_in(&roots, false)) {
+        Ok(report) => report,
+        Err(error) => {
+            log::warn!("[store-migrations] failed to run: {error}");
+            return;
+        }
+    };
+    for status in &report.stores {
+        if let Some(error) = status.error.as_deref() {
+            log::warn!(
+                "[store-migrations] {} stuck below v{}: {error}",
+                status.store,
+                status.to_version
+            );
+        }
+        for step in status
+            .steps
+            .iter()
+            .filter(|step| !step.changed_files.is_empty())
+        {
+            log::info!(
+                "[store-migrations] {} v{} rewrote {} file(s)",
+                status.store,
+                step.version,
+                step.changed_files.len()
+            );
+        }
+    }
+}
+
+/// Reports the pending migration steps and the files they would rewrite.
+#[tauri::command]
+pub(crate) async fn store_migration_report(
+    state: State<'_, AppState>,
+) -> Result<StoreMigrationReport, String> {
+    let roots = store_roots(&data_dir_for_state(&state)?)?;
+    tokio::task::spawn_blocking(move || run_migrations_in(&roots, true))
+        .await
+        .map_err(|error| error.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn run_store_migrations(
+    state: State<'_, AppState>,
+) -> Result<StoreMigrationReport, String> {
+    let roots = store_roots(&data_dir_for_state(&state)?)?;
+    tokio::task::spawn_blocking(move || run_migrations_in(&roots, false))
+        .await
+        .map_err(|error| error.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_roots() -> StoreRoots {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        let roots = StoreRoots {
+            app_home: root.join("home"),
+            data_dir: root.join("data"),
+        };
+        std::fs::create_dir_all(roots.app_home.join("client")).expect("create client dir");
+        std::fs::create_dir_all(roots.data_dir.join("session-management").join("workspaces"))
+            .expect("create session dir");
+        roots
+    }
+
+    #[test]
+    fn dry_run_reports_without_writing() {
+        let roots = temp_roots();
+        let titles = roots.app_home.join("client").join("thread_titles.json");
+        std::fs::write(&titles, r#"{"thread-1":"  Fix   login  "}"#).expect("write titles");
+
+        let report = run_migrations_in(&roots, true).expect("dry run");
+        let status = report
+            .stores
+            .iter()
+            .find(|status| status.store == "thread-titles")
+            .expect("thread titles status");
+
+        assert!(report.dry_run);
+        assert_eq!((status.from_version, status.to_version), (0, 1));
+        assert_eq!(status.steps[0].changed_files.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(&titles).expect("read titles"),
+            r#"{"thread-1":"  Fix   login  "}"#
+        );
+        assert!(!versions_path(&roots).exists());
+    }
+
+    #[test]
+    fn migrates_stores_once_and_keeps_backups() {
+        let roots = temp_roots();
+        let titles = roots.app_home.join("client").join("thread_titles.json");
+        std::fs::write(&titles, r#"{"thread-1":"  Fix   login  ","thread-2":" "}"#)
+            .expect("write titles");
+        let organization = roots
+            .data_dir
+            .join("session-management")
+            .join("workspaces")
+            .join("ws-1.json");
+        std::fs::write(
+            &organization,
+            r#"{"folders":[{"id":"a","parentId":"gone"}],"folderIdBySessionId":{"s1":"a","s2":"gone"}}"#,
+        )
+        .expect("write organization");
+
+        let report = run_migrations_in(&roots, false).expect("migrate");
+        assert!(report.stores.iter().all(|status| status.error.is_none()));
+        assert!(report.backup_dir.is_some());
+
+        let titles_value: Value =
+            serde_json::from_str(&std::fs::read_to_string(&titles).expect("read titles"))
+                .expect("parse titles");
+        assert_eq!(
+            titles_value,
+            json!({ "version": 1, "titles": { "thread-1": "Fix login" } })
+        );
+        let organization_value: Value = serde_json::from_str(
+            &std::fs::read_to_string(&organization).expect("read organization"),
+        )
+        .expect("parse organization");
+        assert_eq!(
+            organization_value,
+            json!({ "folders": [{ "id": "a" }], "folderIdBySessionId": { "s1": "a" } })
+        );
+        assert_eq!(read_versions(&roots).expect("versions")["thread-titles"], 1);
+
+        let rerun = run_migrations_in(&roots, false).expect("rerun");
+        assert!(rerun.stores.iter().all(|status| status.steps.is_empty()));
+        assert!(rerun.backup_dir.is_none());
+    }
+}
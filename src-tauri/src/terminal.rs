@@ -1,13 +1,15 @@
+use std::collections::{HashMap, VecDeque};
 use std::io::{Read, Write};
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex, MutexGuard as StdMutexGuard};
+use std::time::Duration;
 
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use serde::Serialize;
 use tauri::{AppHandle, State};
 use tokio::sync::Mutex;
 
-use crate::backend::events::{EventSink, TerminalOutput};
+use crate::backend::events::{EventSink, TerminalExit, TerminalOutput};
 use crate::event_sink::TauriEventSink;
 use crate::state::AppState;
 use crate::types::AppSettings;
@@ -17,6 +19,9 @@ pub(crate) struct TerminalSession {
     pub(crate) master: Mutex<Box<dyn portable_pty::MasterPty + Send>>,
     pub(crate) writer: Mutex<Box<dyn Write + Send>>,
     pub(crate) child: Mutex<Box<dyn portable_pty::Child + Send>>,
+    /// Shared with the reader thread; a std mutex because that thread is not
+    /// a tokio task.
+    pub(crate) scrollback: Arc<StdMutex<TerminalScrollback>>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -24,6 +29,111 @@ pub(crate) struct TerminalSessionInfo {
     id: String,
 }
 
+/// How long the reader waits for the shell's status after its output closes.
+const TERMINAL_EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const TERMINAL_EXIT_POLL_ATTEMPTS: usize = 40;
+
+const TERMINAL_SCROLLBACK_MAX_LINES: usize = 2000;
+const TERMINAL_SCROLLBACK_MAX_BYTES: usize = 1024 * 1024;
+
+/// Recent output of one terminal, replayed when its view is reopened. Oldest
+/// output is dropped once either the line or the byte limit is exceeded.
+pub(crate) struct TerminalScrollback {
+    chunks: VecDeque<String>,
+    bytes: usize,
+    lines: usize,
+    max_bytes: usize,
+    max_lines: usize,
+    cols: u16,
+    rows: u16,
+}
+
+impl TerminalScrollback {
+    pub(crate) fn new(max_lines: usize, max_bytes: usize, cols: u16, rows: u16) -> Self {
+        Self {
+            chunks: VecDeque::new(),
+            bytes: 0,
+            lines: 0,
+            max_bytes,
+            max_lines,
+            cols,
+            rows,
+        }
+    }
+
+    fn push(&mut self, data: &str) {
+        if data.is_empty() {
+            return;
+        }
+        self.bytes += data.len();
+        self.lines += data.matches('\n').count();
+        self.chunks.push_back(data.to_string());
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.bytes > self.max_bytes || self.lines > self.max_lines {
+            let Some(front) = self.chunks.front_mut() else {
+                break;
+            };
+            let excess_bytes = self.bytes.saturating_sub(self.max_bytes);
+            let excess_lines = self.lines.saturating_sub(self.max_lines);
+            // Cut just past the last newline that must go, or far enough to
+            // get under the byte limit, whichever is further.
+            let mut cut = 0;
+            if excess_lines > 0 {
+                if let Some((index, _)) = front.match_indices('\n').nth(excess_lines - 1) {
+                    cut = index + 1;
+                } else {
+                    cut = front.len();
+                }
+            }
+            cut = cut.max(excess_bytes.min(front.len()));
+            while !front.is_char_boundary(cut) {
+                cut += 1;
+            }
+            if cut >= front.len() {
+                let removed = self.chunks.pop_front().unwrap_or_default();
+                self.bytes -= removed.len();
+                self.lines -= removed.matches('\n').count();
+            } else {
+                let removed: String = front.drain(..cut).collect();
+                self.bytes -= removed.len();
+                self.lines -= removed.matches('\n').count();
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.chunks = VecDeque::new();
+        self.bytes = 0;
+        self.lines = 0;
+    }
+
+    fn contents(&self) -> String {
+        let mut data = String::with_capacity(self.bytes);
+        for chunk in &self.chunks {
+            data.push_str(chunk);
+        }
+        data
+    }
+}
+
+fn lock_scrollback(
+    scrollback: &StdMutex<TerminalScrollback>,
+) -> StdMutexGuard<'_, TerminalScrollback> {
+    scrollback
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct TerminalSnapshot {
+    data: String,
+    cols: u16,
+    rows: u16,
+}
+
 fn terminal_key(workspace_id: &str, terminal_id: &str) -> String {
     format!("{workspace_id}:{terminal_id}")
 }
@@ -60,13 +170,93 @@ fn resolve_locale() -> String {
     "en_US.UTF-8".to_string()
 }
 
+/// Arguments that make the shell run `command` and exit with its status.
+fn shell_command_args(shell_path: &str, command: &str) -> Vec<String> {
+    #[cfg(windows)]
+    {
+        let shell_name = Path::new(shell_path)
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if shell_name == "pwsh" || shell_name == "powershell" {
+            return vec![
+                "-NoLogo".to_string(),
+                "-Command".to_string(),
+                command.to_string(),
+            ];
+        }
+        vec!["/C".to_string(), command.to_string()]
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = shell_path;
+        // -i so the same rc files load as in an interactive terminal.
+        vec!["-i".to_string(), "-c".to_string(), command.to_string()]
+    }
+}
+
+/// The terminal's working directory: the workspace itself, or `cwd` resolved
+/// against it. A missing directory is an error rather than a silent fallback.
+fn resolve_terminal_cwd(workspace_path: &Path, cwd: Option<&str>) -> Result<PathBuf, String> {
+    let Some(cwd) = cwd.map(str::trim).filter(|cwd| !cwd.is_empty()) else {
+        return Ok(workspace_path.to_path_buf());
+    };
+    let path = workspace_path.join(cwd);
+    if !path.is_dir() {
+        return Err(format!(
+            "Terminal working directory does not exist: {}",
+            path.display()
+        ));
+    }
+    Ok(path)
+}
+
+fn validate_terminal_env(env: &HashMap<String, String>) -> Result<(), String> {
+    for (key, value) in env {
+        if key.is_empty() || key.contains('=') || key.contains('\0') || value.contains('\0') {
+            return Err(format!("Invalid terminal environment variable: {key:?}"));
+        }
+    }
+    Ok(())
+}
+
+/// Exit code of the shell once its output has closed. Polls rather than
+/// blocking on `wait` so `terminal_close` can still take the child to kill it.
+fn wait_for_terminal_exit(session: &TerminalSession) -> Option<i32> {
+    for _ in 0..TERMINAL_EXIT_POLL_ATTEMPTS {
+        {
+            let mut child = session.child.blocking_lock();
+            match child.try_wait() {
+                Ok(Some(status)) => return Some(status.exit_code() as i32),
+                Ok(None) => {}
+                Err(_) => return None,
+            }
+        }
+        std::thread::sleep(TERMINAL_EXIT_POLL_INTERVAL);
+    }
+    None
+}
+
 fn spawn_terminal_reader(
     event_sink: impl EventSink,
     workspace_id: String,
     terminal_id: String,
+    session: Arc<TerminalSession>,
     mut reader: Box<dyn Read + Send>,
 ) {
     std::thread::spawn(move || {
+        // Record and emit under one lock so a snapshot never lands between
+        // the two and the reopened view neither drops nor repeats a chunk.
+        let emit = |data: String| {
+            let mut scrollback = lock_scrollback(&session.scrollback);
+            scrollback.push(&data);
+            event_sink.emit_terminal_output(TerminalOutput {
+                workspace_id: workspace_id.clone(),
+                terminal_id: terminal_id.clone(),
+                data,
+            });
+        };
         let mut buffer = [0u8; 8192];
         let mut pending: Vec<u8> = Vec::new();
         loop {
@@ -78,12 +268,7 @@ fn spawn_terminal_reader(
                         match std::str::from_utf8(&pending) {
                             Ok(decoded) => {
                                 if !decoded.is_empty() {
-                                    let payload = TerminalOutput {
-                                        workspace_id: workspace_id.clone(),
-                                        terminal_id: terminal_id.clone(),
-                                        data: decoded.to_string(),
-                                    };
-                                    event_sink.emit_terminal_output(payload);
+                                    emit(decoded.to_string());
                                 }
                                 pending.clear();
                                 break;
@@ -101,12 +286,7 @@ fn spawn_terminal_reader(
                                 let chunk =
                                     String::from_utf8_lossy(&pending[..valid_up_to]).to_string();
                                 if !chunk.is_empty() {
-                                    let payload = TerminalOutput {
-                                        workspace_id: workspace_id.clone(),
-                                        terminal_id: terminal_id.clone(),
-                                        data: chunk,
-                                    };
-                                    event_sink.emit_terminal_output(payload);
+                                    emit(chunk);
                                 }
                                 pending.drain(..valid_up_to);
                                 if error.error_len().is_none() {
@@ -121,12 +301,23 @@ fn spawn_terminal_reader(
                 Err(_) => break,
             }
         }
+        let exit_code = wait_for_terminal_exit(&session);
+        event_sink.emit_terminal_exit(TerminalExit {
+            workspace_id,
+            terminal_id,
+            exit_code,
+        });
     });
 }
 
 async fn kill_terminal_session(session: Arc<TerminalSession>) {
-    let mut child = session.child.lock().await;
-    let _ = child.kill();
+    {
+        let mut child = session.child.lock().await;
+        let _ = child.kill();
+    }
+    // The reader thread keeps its handle until the pty hits EOF; drop the
+    // buffered output now rather than waiting for it.
+    lock_scrollback(&session.scrollback).clear();
 }
 
 pub(crate) async fn cleanup_terminal_sessions_for_workspace(state: &AppState, workspace_id: &str) {
@@ -173,12 +364,19 @@ async fn get_workspace_path(
     Ok(PathBuf::from(&entry.path))
 }
 
+/// Opens a shell in the workspace, or returns the session already open under
+/// `terminal_id`. With `command` the shell runs it and exits; either way a
+/// `terminal-exit` event reports the exit code once the shell ends.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn terminal_open(
     workspace_id: String,
     terminal_id: String,
     cols: u16,
     rows: u16,
+    command: Option<String>,
+    env: Option<HashMap<String, String>>,
+    cwd: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<TerminalSessionInfo, String> {
@@ -195,7 +393,13 @@ pub(crate) async fn terminal_open(
         }
     }
 
-    let cwd = get_workspace_path(&workspace_id, &state).await?;
+    let workspace_path = get_workspace_path(&workspace_id, &state).await?;
+    let cwd = resolve_terminal_cwd(&workspace_path, cwd.as_deref())?;
+    let env = env.unwrap_or_default();
+    validate_terminal_env(&env)?;
+    let command = command
+        .map(|command| command.trim().to_string())
+        .filter(|command| !command.is_empty());
     let pty_system = native_pty_system();
     let size = PtySize {
         rows: rows.max(2),
@@ -211,16 +415,23 @@ pub(crate) async fn terminal_open(
         let settings = state.app_settings.lock().await;
         resolve_terminal_shell_path(&settings)
     };
-    let mut cmd = CommandBuilder::new(shell_path);
+    let mut cmd = CommandBuilder::new(&shell_path);
     cmd.cwd(cwd);
-    // On Unix, pass -i for interactive shell; cmd.exe on Windows doesn't support it
-    #[cfg(not(windows))]
-    cmd.arg("-i");
+    if let Some(command) = command.as_deref() {
+        cmd.args(shell_command_args(&shell_path, command));
+    } else {
+        // On Unix, pass -i for interactive shell; cmd.exe on Windows doesn't support it
+        #[cfg(not(windows))]
+        cmd.arg("-i");
+    }
     cmd.env("TERM", "xterm-256color");
     let locale = resolve_locale();
     cmd.env("LANG", &locale);
     cmd.env("LC_ALL", &locale);
     cmd.env("LC_CTYPE", &locale);
+    for (key, value) in &env {
+        cmd.env(key, value);
+    }
 
     let child = pair
         .slave
@@ -235,11 +446,18 @@ pub(crate) async fn terminal_open(
         .take_writer()
         .map_err(|e| format!("Failed to open pty writer: {e}"))?;
 
+    let scrollback = Arc::new(StdMutex::new(TerminalScrollback::new(
+        TERMINAL_SCROLLBACK_MAX_LINES,
+        TERMINAL_SCROLLBACK_MAX_BYTES,
+        size.cols,
+        size.rows,
+    )));
     let session = Arc::new(TerminalSession {
         id: terminal_id.clone(),
         master: Mutex::new(pair.master),
         writer: Mutex::new(writer),
         child: Mutex::new(child),
+        scrollback,
     });
     let session_id = session.id.clone();
 
@@ -252,10 +470,10 @@ pub(crate) async fn terminal_open(
                 id: existing.id.clone(),
             });
         }
-        sessions.insert(key, session);
+        sessions.insert(key, Arc::clone(&session));
     }
     let event_sink = TauriEventSink::new(app);
-    spawn_terminal_reader(event_sink, workspace_id, terminal_id, reader);
+    spawn_terminal_reader(event_sink, workspace_id, terminal_id, session, reader);
 
     Ok(TerminalSessionInfo { id: session_id })
 }
@@ -305,9 +523,33 @@ pub(crate) async fn terminal_resize(
     master
         .resize(size)
         .map_err(|e| format!("Failed to resize pty: {e}"))?;
+    let mut scrollback = lock_scrollback(&session.scrollback);
+    scrollback.cols = size.cols;
+    scrollback.rows = size.rows;
     Ok(())
 }
 
+/// Buffered output of an open terminal plus its last size, so a reopened view
+/// can restore what was on screen before following live output.
+#[tauri::command]
+pub(crate) async fn terminal_snapshot(
+    workspace_id: String,
+    terminal_id: String,
+    state: State<'_, AppState>,
+) -> Result<TerminalSnapshot, String> {
+    let key = terminal_key(&workspace_id, &terminal_id);
+    let sessions = state.terminal_sessions.lock().await;
+    let session = sessions
+        .get(&key)
+        .ok_or_else(|| "Terminal session not found".to_string())?;
+    let scrollback = lock_scrollback(&session.scrollback);
+    Ok(TerminalSnapshot {
+        data: scrollback.contents(),
+        cols: scrollback.cols,
+        rows: scrollback.rows,
+    })
+}
+
 #[tauri::command]
 pub(crate) async fn terminal_close(
     workspace_id: String,
@@ -325,8 +567,64 @@ pub(crate) async fn terminal_close(
 
 #[cfg(test)]
 mod tests {
-    use super::resolve_terminal_shell_path;
+    use super::{
+        resolve_terminal_cwd, resolve_terminal_shell_path, validate_terminal_env,
+        TerminalScrollback,
+    };
     use crate::types::AppSettings;
+    use std::collections::HashMap;
+
+    #[test]
+    fn resolves_terminal_cwd_overrides() {
+        let workspace = std::env::temp_dir().join(format!("moss-x-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(workspace.join("packages/app")).expect("create dirs");
+
+        assert_eq!(
+            resolve_terminal_cwd(&workspace, None),
+            Ok(workspace.clone())
+        );
+        assert_eq!(
+            resolve_terminal_cwd(&workspace, Some("  ")),
+            Ok(workspace.clone())
+        );
+        assert_eq!(
+            resolve_terminal_cwd(&workspace, Some("packages/app")),
+            Ok(workspace.join("packages/app"))
+        );
+        let absolute = workspace.join("packages");
+        assert_eq!(
+            resolve_terminal_cwd(&workspace, absolute.to_str()),
+            Ok(absolute)
+        );
+        assert!(resolve_terminal_cwd(&workspace, Some("missing")).is_err());
+
+        let mut env = HashMap::new();
+        env.insert("NODE_ENV".to_string(), "test".to_string());
+        assert!(validate_terminal_env(&env).is_ok());
+        env.insert("BAD=KEY".to_string(), "x".to_string());
+        assert!(validate_terminal_env(&env).is_err());
+
+        let _ = std::fs::remove_dir_all(workspace);
+    }
+
+    #[test]
+    fn scrollback_drops_oldest_output_past_limits() {
+        let mut scrollback = TerminalScrollback::new(3, 1024, 80, 24);
+        scrollback.push("one\ntwo\n");
+        scrollback.push("three\nfour\nfi");
+        scrollback.push("ve\n");
+        assert_eq!(scrollback.contents(), "three\nfour\nfive\n");
+
+        let mut scrollback = TerminalScrollback::new(100, 8, 80, 24);
+        scrollback.push("abcdef");
+        scrollback.push("ghij");
+        assert_eq!(scrollback.contents(), "cdefghij");
+        scrollback.push("终端");
+        assert_eq!(scrollback.contents(), "ij终端");
+
+        scrollback.clear();
+        assert_eq!(scrollback.contents(), "");
+    }
 
     #[test]
     fn resolve_terminal_shell_path_prefers_configured_path() {
@@ -21,9 +21,63 @@ pub(crate) fn decode_text_bytes(bytes: &[u8], file_context: &str) -> Result<Stri
     Ok(decoded.into_owned())
 }
 
+/// Text decoded for display together with the encoding it was read as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DecodedText {
+    pub(crate) text: String,
+    pub(crate) encoding: &'static str,
+    /// `true` when no encoding decoded the bytes cleanly and they were mapped
+    /// byte-for-byte as ISO-8859-1, which keeps every byte recoverable.
+    pub(crate) fallback: bool,
+}
+
+/// Decodes bytes that must always be shown (diffs, patches). Unlike
+/// [`decode_text_bytes`] this never fails: BOM-marked UTF-16 and detected
+/// legacy encodings are decoded, anything else falls back to ISO-8859-1.
+pub(crate) fn decode_text_lossless(bytes: &[u8]) -> DecodedText {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return DecodedText {
+            text: text.to_string(),
+            encoding: encoding_rs::UTF_8.name(),
+            fallback: false,
+        };
+    }
+
+    if let Some((encoding, bom_length)) = encoding_rs::Encoding::for_bom(bytes) {
+        let (decoded, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_length..]);
+        if !had_errors {
+            return DecodedText {
+                text: decoded.into_owned(),
+                encoding: encoding.name(),
+                fallback: false,
+            };
+        }
+    }
+
+    if !bytes.contains(&0) {
+        let mut detector = EncodingDetector::new();
+        detector.feed(bytes, true);
+        let encoding = detector.guess(None, true);
+        let (decoded, _, had_errors) = encoding.decode(bytes);
+        if !had_errors {
+            return DecodedText {
+                text: decoded.into_owned(),
+                encoding: encoding.name(),
+                fallback: false,
+            };
+        }
+    }
+
+    DecodedText {
+        text: bytes.iter().map(|&byte| char::from(byte)).collect(),
+        encoding: "ISO-8859-1",
+        fallback: true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::decode_text_bytes;
+    use super::{decode_text_bytes, decode_text_lossless};
 
     #[test]
     fn decodes_utf8_text_without_changes() {
@@ -45,4 +99,26 @@ mod tests {
         let error = decode_text_bytes(&[0x66, 0x00, 0x6f, 0x00], "file").expect_err("binary");
         assert!(error.contains("appears to be binary"));
     }
+
+    #[test]
+    fn lossless_decoding_labels_detected_and_fallback_encodings() {
+        let (encoded, _, _) = encoding_rs::GB18030.encode("usb异常断开");
+        let decoded = decode_text_lossless(&encoded);
+        assert_eq!(decoded.text, "usb异常断开");
+        assert!(!decoded.fallback);
+
+        let mut utf16 = vec![0xFF, 0xFE];
+        utf16.extend("hi".encode_utf16().flat_map(u16::to_le_bytes));
+        let decoded = decode_text_lossless(&utf16);
+        assert_eq!(
+            (decoded.text.as_str(), decoded.encoding),
+            ("hi", "UTF-16LE")
+        );
+
+        let bytes = [b'a', 0x00, 0xFF, 0x81];
+        let decoded = decode_text_lossless(&bytes);
+        assert!(decoded.fallback);
+        let roundtrip: Vec<u8> = decoded.text.chars().map(|ch| ch as u8).collect();
+        assert_eq!(roundtrip, bytes);
+    }
 }
@@ -6,6 +6,17 @@ pub(crate) struct GitFileStatus {
     pub(crate) status: String,
     pub(crate) additions: i64,
     pub(crate) deletions: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) submodule: Option<GitSubmoduleHeads>,
+}
+
+/// Commits a changed submodule moved between: `old_sha` is the commit the
+/// superproject records, `new_sha` the one checked out (or staged).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitSubmoduleHeads {
+    pub(crate) old_sha: Option<String>,
+    pub(crate) new_sha: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -24,6 +35,24 @@ pub(crate) struct GitFileDiff {
     pub(crate) old_image_mime: Option<String>,
     #[serde(rename = "newImageMime")]
     pub(crate) new_image_mime: Option<String>,
+    /// Set when the file is listed without its diff text (too large,
+    /// generated, or over the payload budget).
+    #[serde(default, rename = "summaryOnly")]
+    pub(crate) summary_only: bool,
+    #[serde(
+        default,
+        rename = "summaryReason",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) summary_reason: Option<String>,
+    #[serde(default, rename = "sizeBytes", skip_serializing_if = "Option::is_none")]
+    pub(crate) size_bytes: Option<u64>,
+    /// Encoding the diff text was decoded from when it was not UTF-8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) encoding: Option<String>,
+    /// `true` when the text is a byte-for-byte ISO-8859-1 fallback.
+    #[serde(default, rename = "encodingFallback")]
+    pub(crate) encoding_fallback: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -90,6 +119,9 @@ pub(crate) struct GitHistoryResponse {
     #[serde(rename = "snapshotId")]
     pub(crate) snapshot_id: String,
     pub(crate) total: usize,
+    /// `total` is a lower bound: the walk stopped before the end of history.
+    #[serde(rename = "totalIsEstimated", default)]
+    pub(crate) total_is_estimated: bool,
     pub(crate) offset: usize,
     pub(crate) limit: usize,
     #[serde(rename = "hasMore")]
@@ -136,6 +168,14 @@ pub(crate) struct GitPrWorkflowDefaults {
     pub(crate) body: String,
     #[serde(rename = "commentBody")]
     pub(crate) comment_body: String,
+    /// Suggested from the head branch prefix (`fix/` -> `bug`, ...).
+    #[serde(default)]
+    pub(crate) labels: Vec<String>,
+    #[serde(default)]
+    pub(crate) reviewers: Vec<String>,
+    /// True for `wip/` and `draft/` branches.
+    #[serde(default)]
+    pub(crate) draft: bool,
     #[serde(rename = "canCreate")]
     pub(crate) can_create: bool,
     #[serde(rename = "disabledReason")]
@@ -241,6 +281,9 @@ pub(crate) struct GitBranchListItem {
     pub(crate) behind: usize,
     #[serde(default)]
     pub(crate) upstream: Option<String>,
+    /// Another worktree that has this branch checked out.
+    #[serde(default, rename = "worktreePath")]
+    pub(crate) worktree_path: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -306,6 +349,33 @@ pub(crate) struct GitHubPullRequestDiff {
     pub(crate) path: String,
     pub(crate) status: String,
     pub(crate) diff: String,
+    /// The patch was dropped because the whole-PR diff hit its size cap;
+    /// fetch it on its own with the `path` filter.
+    #[serde(default)]
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubPullRequestFile {
+    pub(crate) path: String,
+    #[serde(rename = "previousPath")]
+    pub(crate) previous_path: Option<String>,
+    pub(crate) status: String,
+    pub(crate) additions: u64,
+    pub(crate) deletions: u64,
+    /// Omitted when GitHub returns none (binary or very large files) or it
+    /// exceeds the inline size cap.
+    pub(crate) patch: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubPullRequestFilesResponse {
+    pub(crate) page: u32,
+    #[serde(rename = "perPage")]
+    pub(crate) per_page: u32,
+    #[serde(rename = "hasMore")]
+    pub(crate) has_more: bool,
+    pub(crate) files: Vec<GitHubPullRequestFile>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -481,6 +551,30 @@ pub(crate) struct LocalUsageStatistics {
     pub(crate) last_updated: i64,
 }
 
+/// Token totals of one `local_usage_query` group.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageGroup {
+    pub(crate) key: String,
+    pub(crate) label: String,
+    pub(crate) input_tokens: i64,
+    pub(crate) cached_input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) total_tokens: i64,
+    pub(crate) cost: f64,
+    pub(crate) events: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageQueryResult {
+    pub(crate) updated_at: i64,
+    pub(crate) range: String,
+    pub(crate) group_by: String,
+    pub(crate) groups: Vec<LocalUsageGroup>,
+    pub(crate) totals: LocalUsageGroup,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct BranchInfo {
     pub(crate) name: String,
@@ -554,6 +648,20 @@ pub(crate) struct WorktreeInfo {
     pub(crate) publish_retry_command: Option<String>,
 }
 
+/// Values substituted into a workspace's `worktree_branch_template`. The slug
+/// comes from `title` (e.g. an issue title) or, failing that, `prompt`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct WorktreeNamingContext {
+    #[serde(default)]
+    pub(crate) user: Option<String>,
+    #[serde(default)]
+    pub(crate) ticket: Option<String>,
+    #[serde(default)]
+    pub(crate) title: Option<String>,
+    #[serde(default)]
+    pub(crate) prompt: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct WorkspaceGroup {
     pub(crate) id: String,
@@ -588,9 +696,109 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) launch_scripts: Option<Vec<LaunchScriptEntry>>,
     #[serde(default, rename = "worktreeSetupScript")]
     pub(crate) worktree_setup_script: Option<String>,
+    /// Branch naming convention for new worktrees, e.g.
+    /// `{user}/{ticket}-{slug}`; see `worktree_naming`.
+    #[serde(default, rename = "worktreeBranchTemplate")]
+    pub(crate) worktree_branch_template: Option<String>,
     /// Engine type for this workspace: "claude" or "codex". If not set, use app default.
     #[serde(default, rename = "engineType")]
     pub(crate) engine_type: Option<String>,
+    /// Default access mode preset for turns that do not specify one.
+    #[serde(default, rename = "accessModePreset")]
+    pub(crate) access_mode_preset: Option<String>,
+    /// Blocks file writes and git mutations and caps engine turns at
+    /// ask-before-write, for inspecting checkouts that must not change.
+    #[serde(default, rename = "readOnly")]
+    pub(crate) read_only: bool,
+    /// Commits every approved agent edit batch to the `mossx/agent` branch
+    /// instead of leaving it only in the working tree.
+    #[serde(default, rename = "agentAutoCommit")]
+    pub(crate) agent_auto_commit: bool,
+    /// Gitignore-style patterns for files whose contents are never sent to
+    /// an engine; see `privacy_zones`.
+    #[serde(default, rename = "privacyRules")]
+    pub(crate) privacy_rules: Vec<String>,
+    /// Default answers, countdown and unattended rules for engine questions.
+    #[serde(default, rename = "userInputPolicy")]
+    pub(crate) user_input_policy: UserInputPolicy,
+    /// Age and size caps for archiving old engine sessions; see
+    /// `session_housekeeping`.
+    #[serde(default, rename = "sessionRetention")]
+    pub(crate) session_retention: SessionRetentionPolicy,
+    /// App-managed Codex CLI version this workspace is pinned to; see
+    /// `codex::managed_versions`. `codex_bin` points at its binary.
+    #[serde(default, rename = "managedCodex")]
+    pub(crate) managed_codex: Option<ManagedCodexPin>,
+    /// PR workflow range gate; `false` skips the size and suspicious-file
+    /// checks. Unset keeps the gate on.
+    #[serde(default, rename = "prRangeGateEnabled")]
+    pub(crate) pr_range_gate_enabled: Option<bool>,
+    /// Changed files above which the range gate blocks; defaults to 240.
+    #[serde(default, rename = "prMaxChangedFiles")]
+    pub(crate) pr_max_changed_files: Option<u32>,
+    /// Changed files from which a touched README/LICENSE blocks the PR;
+    /// defaults to 32.
+    #[serde(default, rename = "prSuspiciousThreshold")]
+    pub(crate) pr_suspicious_threshold: Option<u32>,
+}
+
+/// A workspace's pinned Codex CLI version and what it replaced, so the pin
+/// can be rolled back one step.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ManagedCodexPin {
+    pub(crate) version: String,
+    /// SHA-256 of the installed binary, checked again on every switch.
+    #[serde(rename = "binSha256")]
+    pub(crate) bin_sha256: String,
+    #[serde(default, rename = "previousVersion")]
+    pub(crate) previous_version: Option<String>,
+    /// Manual `codex_bin` in use before the first pin.
+    #[serde(default, rename = "previousCodexBin")]
+    pub(crate) previous_codex_bin: Option<String>,
+}
+
+/// Which sessions housekeeping archives. With neither cap set nothing is
+/// archived.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SessionRetentionPolicy {
+    /// Archive sessions not updated for this many days.
+    #[serde(default, rename = "maxAgeDays")]
+    pub(crate) max_age_days: Option<u32>,
+    /// Archive the oldest sessions until an engine's active sessions fit in
+    /// this many bytes.
+    #[serde(default, rename = "maxStoreBytes")]
+    pub(crate) max_store_bytes: Option<u64>,
+    /// Most recent sessions per engine that are never archived; defaults
+    /// to 20.
+    #[serde(default, rename = "keepRecent")]
+    pub(crate) keep_recent: Option<u32>,
+    /// Engines the policy applies to; empty means all.
+    #[serde(default)]
+    pub(crate) engines: Vec<String>,
+}
+
+/// How AskUserQuestion / `requestUserInput` prompts are answered when nobody
+/// responds.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub(crate) struct UserInputPolicy {
+    /// Seconds before the default answers are submitted; `None` waits for
+    /// the user.
+    #[serde(default, rename = "timeoutSecs")]
+    pub(crate) timeout_secs: Option<u32>,
+    /// Answers immediately, without a dialog, whenever every question has a
+    /// default answer.
+    #[serde(default)]
+    pub(crate) unattended: bool,
+    #[serde(default)]
+    pub(crate) rules: Vec<UserInputAnswerRule>,
+}
+
+/// Predefined answer for questions whose header or text contains `pattern`
+/// (case-insensitive).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct UserInputAnswerRule {
+    pub(crate) pattern: String,
+    pub(crate) answer: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -724,6 +932,138 @@ fn default_email_sender_settings() -> EmailSenderSettings {
     EmailSenderSettings::default()
 }
 
+/// Fast and premium model ids used by the model router for one engine.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelRoutingTiers {
+    #[serde(default)]
+    pub(crate) fast_model: String,
+    #[serde(default)]
+    pub(crate) premium_model: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelRoutingSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Tiers keyed by engine id (`claude`, `codex`, `gemini`, `opencode`).
+    #[serde(default)]
+    pub(crate) engines: std::collections::BTreeMap<String, ModelRoutingTiers>,
+    /// Longest prompt, in characters, that may still go to the fast model.
+    #[serde(default = "default_model_routing_fast_max_chars")]
+    pub(crate) fast_max_chars: usize,
+    #[serde(default = "default_model_routing_premium_for_code")]
+    pub(crate) premium_for_code: bool,
+    #[serde(default = "default_model_routing_premium_for_tools")]
+    pub(crate) premium_for_tools: bool,
+}
+
+impl Default for ModelRoutingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            engines: Default::default(),
+            fast_max_chars: default_model_routing_fast_max_chars(),
+            premium_for_code: default_model_routing_premium_for_code(),
+            premium_for_tools: default_model_routing_premium_for_tools(),
+        }
+    }
+}
+
+fn default_model_routing_fast_max_chars() -> usize {
+    600
+}
+
+fn default_model_routing_premium_for_code() -> bool {
+    true
+}
+
+fn default_model_routing_premium_for_tools() -> bool {
+    true
+}
+
+/// Keeps an idle, pre-started CLI process per workspace so the next turn can
+/// skip process startup. Currently used by the Claude engine.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EngineWarmPoolSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Seconds an unused warm process is kept before it is killed.
+    #[serde(default = "default_engine_warm_pool_idle_timeout_secs")]
+    pub(crate) idle_timeout_secs: u64,
+}
+
+impl Default for EngineWarmPoolSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            idle_timeout_secs: default_engine_warm_pool_idle_timeout_secs(),
+        }
+    }
+}
+
+fn default_engine_warm_pool_idle_timeout_secs() -> u64 {
+    120
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ApiEngineProvider {
+    /// Anthropic Messages API.
+    #[default]
+    Anthropic,
+    /// OpenAI Chat Completions, or any endpoint compatible with it.
+    #[serde(rename = "openai")]
+    OpenAi,
+}
+
+/// CLI-free engine that calls the provider HTTP API directly.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApiEngineSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) provider: ApiEngineProvider,
+    /// Empty means the provider's public endpoint.
+    #[serde(default)]
+    pub(crate) base_url: String,
+    /// Empty means `ANTHROPIC_API_KEY` / `OPENAI_API_KEY` from the environment.
+    #[serde(default)]
+    pub(crate) api_key: String,
+    #[serde(default)]
+    pub(crate) model: String,
+    #[serde(default = "default_api_engine_max_output_tokens")]
+    pub(crate) max_output_tokens: u32,
+    /// Model/tool round trips allowed in one turn.
+    #[serde(default = "default_api_engine_max_tool_rounds")]
+    pub(crate) max_tool_rounds: u32,
+}
+
+impl Default for ApiEngineSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: ApiEngineProvider::default(),
+            base_url: String::new(),
+            api_key: String::new(),
+            model: String::new(),
+            max_output_tokens: default_api_engine_max_output_tokens(),
+            max_tool_rounds: default_api_engine_max_tool_rounds(),
+        }
+    }
+}
+
+fn default_api_engine_max_output_tokens() -> u32 {
+    8192
+}
+
+fn default_api_engine_max_tool_rounds() -> u32 {
+    24
+}
+
 fn default_engine_enabled() -> bool {
     true
 }
@@ -924,8 +1264,33 @@ pub(crate) struct AppSettings {
     pub(crate) system_notification_enabled: bool,
     #[serde(default = "default_email_sender_settings", rename = "emailSender")]
     pub(crate) email_sender: EmailSenderSettings,
+    #[serde(default, rename = "modelRouting")]
+    pub(crate) model_routing: ModelRoutingSettings,
+    #[serde(default, rename = "engineWarmPool")]
+    pub(crate) engine_warm_pool: EngineWarmPoolSettings,
+    /// Seconds a Claude turn waits for an AskUserQuestion answer before it
+    /// is cancelled.
+    #[serde(
+        default = "default_claude_question_timeout_secs",
+        rename = "claudeQuestionTimeoutSecs"
+    )]
+    pub(crate) claude_question_timeout_secs: u64,
+    #[serde(default, rename = "apiEngine")]
+    pub(crate) api_engine: ApiEngineSettings,
     #[serde(default = "default_preload_git_diffs", rename = "preloadGitDiffs")]
     pub(crate) preload_git_diffs: bool,
+    /// Upper bound, in KiB, for the working-tree diff text sent to the webview.
+    #[serde(
+        default = "default_git_diff_max_payload_kb",
+        rename = "gitDiffMaxPayloadKb"
+    )]
+    pub(crate) git_diff_max_payload_kb: u32,
+    /// Seconds between pull/merge request status refreshes of the current branch.
+    #[serde(
+        default = "default_branch_status_poll_interval_secs",
+        rename = "branchStatusPollIntervalSecs"
+    )]
+    pub(crate) branch_status_poll_interval_secs: u64,
     #[serde(
         default = "default_detached_external_change_awareness_enabled",
         rename = "detachedExternalChangeAwarenessEnabled"
@@ -1275,6 +1640,18 @@ fn default_preload_git_diffs() -> bool {
     true
 }
 
+fn default_git_diff_max_payload_kb() -> u32 {
+    2 * 1024
+}
+
+fn default_branch_status_poll_interval_secs() -> u64 {
+    60
+}
+
+fn default_claude_question_timeout_secs() -> u64 {
+    300
+}
+
 fn default_experimental_collab_enabled() -> bool {
     false
 }
@@ -1546,7 +1923,13 @@ impl Default for AppSettings {
             notification_sound_custom_path: default_notification_sound_custom_path(),
             system_notification_enabled: true,
             email_sender: EmailSenderSettings::default(),
+            model_routing: ModelRoutingSettings::default(),
+            engine_warm_pool: EngineWarmPoolSettings::default(),
+            claude_question_timeout_secs: default_claude_question_timeout_secs(),
+            api_engine: ApiEngineSettings::default(),
             preload_git_diffs: default_preload_git_diffs(),
+            git_diff_max_payload_kb: default_git_diff_max_payload_kb(),
+            branch_status_poll_interval_secs: default_branch_status_poll_interval_secs(),
             detached_external_change_awareness_enabled:
                 default_detached_external_change_awareness_enabled(),
             detached_external_change_watcher_enabled:
@@ -1595,6 +1978,19 @@ impl Default for AppSettings {
 
 // ==================== Vendor/Provider Types ====================
 
+/// TLS overrides for providers behind self-hosted relays.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProviderTlsOptions {
+    /// PEM bundle trusted in addition to the system roots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) ca_bundle_path: Option<String>,
+    /// Disables certificate verification entirely. Only honoured where the
+    /// engine supports it, and always reported as a warning.
+    #[serde(default)]
+    pub(crate) insecure_skip_verify: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct ProviderConfig {
@@ -1616,6 +2012,8 @@ pub(crate) struct ProviderConfig {
     pub(crate) is_local_provider: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) settings_config: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) tls: Option<ProviderTlsOptions>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1644,13 +2042,15 @@ pub(crate) struct CodexProviderConfig {
     pub(crate) auth_json: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) custom_models: Option<Vec<CodexCustomModel>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) tls: Option<ProviderTlsOptions>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        AppSettings, BackendMode, EmailSenderProvider, EmailSenderSecurity, WorkspaceEntry,
-        WorkspaceGroup, WorkspaceKind, WorkspaceSettings,
+        ApiEngineProvider, AppSettings, BackendMode, EmailSenderProvider, EmailSenderSecurity,
+        WorkspaceEntry, WorkspaceGroup, WorkspaceKind, WorkspaceSettings,
     };
 
     #[test]
@@ -1754,6 +2154,13 @@ mod tests {
         assert!(settings.email_sender.username.is_empty());
         assert!(settings.email_sender.recipient_email.is_empty());
         assert!(settings.preload_git_diffs);
+        assert_eq!(settings.git_diff_max_payload_kb, 2048);
+        assert_eq!(settings.branch_status_poll_interval_secs, 60);
+        assert!(!settings.engine_warm_pool.enabled);
+        assert_eq!(settings.engine_warm_pool.idle_timeout_secs, 120);
+        assert_eq!(settings.claude_question_timeout_secs, 300);
+        assert!(!settings.api_engine.enabled);
+        assert_eq!(settings.api_engine.provider, ApiEngineProvider::Anthropic);
         assert!(settings.detached_external_change_awareness_enabled);
         assert!(settings.detached_external_change_watcher_enabled);
         assert!(!settings.experimental_steer_enabled);
@@ -0,0 +1,539 @@
+//! Batching, countdown defaults and unattended answers for engine questions.
+//!
+//! Every `item/tool/requestUserInput` request (Claude AskUserQuestion or
+//! Codex `requestUserInput`) is collected per thread for a short window and
+//! re-emitted as one `user-input-batch` dialog payload, with each question's
+//! default answer from the workspace `userInputPolicy`. When the policy has a
+//! timeout, unanswered requests are submitted with their defaults once it
+//! expires; in unattended mode requests whose questions all have a default
+//! are answered right away so overnight runs keep going.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::backend::events::AppServerEvent;
+use crate::state::AppState;
+use crate::types::UserInputPolicy;
+
+const REQUEST_USER_INPUT_METHOD: &str = "item/tool/requestUserInput";
+const USER_INPUT_BATCH_EVENT: &str = "user-input-batch";
+const USER_INPUT_AUTO_ANSWERED_EVENT: &str = "user-input-auto-answered";
+/// Requests arriving within this window on one thread share a dialog.
+const BATCH_WINDOW: Duration = Duration::from_millis(400);
+const MIN_TIMEOUT_SECS: u64 = 5;
+/// Claude cancels a turn whose AskUserQuestion goes unanswered after
+/// `claudeQuestionTimeoutSecs`, so the countdown submits up to a fifth of
+/// that, at most this many seconds, before it.
+const MAX_TIMEOUT_MARGIN_SECS: u64 = 60;
+
+struct PendingRequest {
+    token: u64,
+    thread_id: Option<String>,
+    turn_id: Option<String>,
+    request_id: Value,
+    questions: Vec<Value>,
+    defaults: Vec<Option<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchedRequest {
+    request_id: Value,
+    turn_id: Option<String>,
+    /// The request's questions, each with a `defaultAnswer` (or `null`).
+    questions: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UserInputBatch {
+    workspace_id: String,
+    thread_id: Option<String>,
+    requests: Vec<BatchedRequest>,
+    /// Epoch millis at which the defaults are submitted; `None` waits.
+    deadline_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AutoAnsweredRequest {
+    workspace_id: String,
+    thread_id: Option<String>,
+    request_id: Value,
+    reason: &'static str,
+    result: Value,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UserInputBatchResponse {
+    request_id: Value,
+    result: Value,
+    #[serde(default)]
+    thread_id: Option<String>,
+    #[serde(default)]
+    turn_id: Option<String>,
+}
+
+fn pending_requests() -> std::sync::MutexGuard<'static, HashMap<String, PendingRequest>> {
+    static PENDING: OnceLock<StdMutex<HashMap<String, PendingRequest>>> = OnceLock::new();
+    match PENDING.get_or_init(|| StdMutex::new(HashMap::new())).lock() {
+        Ok(pending) => pending,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Pending keys waiting for their thread's batch window to close.
+fn open_batches() -> std::sync::MutexGuard<'static, HashMap<String, Vec<String>>> {
+    static BATCHES: OnceLock<StdMutex<HashMap<String, Vec<String>>>> = OnceLock::new();
+    match BATCHES.get_or_init(|| StdMutex::new(HashMap::new())).lock() {
+        Ok(batches) => batches,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn next_token() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_millis(0))
+        .as_millis() as u64
+}
+
+fn pending_key(workspace_id: &str, request_id: &Value) -> String {
+    match request_id {
+        Value::String(value) => format!("{workspace_id}:{value}"),
+        other => format!("{workspace_id}:{other}"),
+    }
+}
+
+fn batch_key(workspace_id: &str, thread_id: Option<&str>) -> String {
+    format!("{workspace_id}\u{0}{}", thread_id.unwrap_or_default())
+}
+
+fn effective_timeout(policy: &UserInputPolicy, question_timeout_secs: u64) -> Option<Duration> {
+    let margin = (question_timeout_secs / 5).min(MAX_TIMEOUT_MARGIN_SECS);
+    let max_secs = question_timeout_secs
+        .saturating_sub(margin)
+        .max(MIN_TIMEOUT_SECS);
+    policy
+        .timeout_secs
+        .map(|secs| Duration::from_secs(u64::from(secs).clamp(MIN_TIMEOUT_SECS, max_secs)))
+}
+
+fn question_id(question: &Value, index: usize) -> String {
+    question
+        .get("id")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("q-{index}"))
+}
+
+fn rule_answer(policy: &UserInputPolicy, question: &Value) -> Option<String> {
+    let text = ["header", "question"]
+        .iter()
+        .filter_map(|key| question.get(*key).and_then(Value::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    policy
+        .rules
+        .iter()
+        .find(|rule| {
+            let pattern = rule.pattern.trim().to_lowercase();
+            !pattern.is_empty() && text.contains(&pattern)
+        })
+        .map(|rule| rule.answer.trim().to_string())
+        .filter(|answer| !answer.is_empty())
+}
+
+/// A matching rule's answer, else the first option once a timeout or
+/// unattended mode is set (engines list the recommended option first).
+fn default_answer(policy: &UserInputPolicy, question: &Value) -> Option<String> {
+    rule_answer(policy, question).or_else(|| {
+        if policy.timeout_secs.is_none() && !policy.unattended {
+            return None;
+        }
+        question
+            .get("options")
+            .and_then(Value::as_array)?
+            .iter()
+            .find_map(|option| option.get("label").and_then(Value::as_str))
+            .map(str::to_string)
+    })
+}
+
+/// Builds the `{ answers: { <questionId>: { answers: [...] } } }` result the
+/// frontend submits, skipping questions without a default.
+fn answers_result(questions: &[Value], defaults: &[Option<String>]) -> Option<Value> {
+    let answers: Map<String, Value> = questions
+        .iter()
+        .zip(defaults)
+        .enumerate()
+        .filter_map(|(index, (question, answer))| {
+            let answer = answer.as_ref()?;
+            Some((question_id(question, index), json!({ "answers": [answer] })))
+        })
+        .collect();
+    (!answers.is_empty()).then(|| json!({ "answers": answers }))
+}
+
+fn annotate_questions(questions: &[Value], defaults: &[Option<String>]) -> Vec<Value> {
+    questions
+        .iter()
+        .zip(defaults)
+        .map(|(question, answer)| {
+            let mut question = question.clone();
+            if let Some(object) = question.as_object_mut() {
+                object.insert("defaultAnswer".to_string(), json!(answer));
+            }
+            question
+        })
+        .collect()
+}
+
+async fn submit_answer(
+    app: &AppHandle,
+    workspace_id: &str,
+    pending: PendingRequest,
+    result: Value,
+    reason: &'static str,
+) {
+    let notice = AutoAnsweredRequest {
+        workspace_id: workspace_id.to_string(),
+        thread_id: pending.thread_id.clone(),
+        request_id: pending.request_id.clone(),
+        reason,
+        result: result.clone(),
+    };
+    let response = crate::codex::respond_to_server_request(
+        workspace_id.to_string(),
+        pending.request_id,
+        result,
+        pending.thread_id,
+        pending.turn_id,
+        app.state::<AppState>(),
+        app.clone(),
+    )
+    .await;
+    match response {
+        Ok(()) => {
+            log::info!("[user-input-policy] {reason} answer submitted in {workspace_id}");
+            let _ = app.emit(USER_INPUT_AUTO_ANSWERED_EVENT, notice);
+        }
+        Err(error) => {
+            log::warn!("[user-input-policy] failed to submit {reason} answer: {error}");
+        }
+    }
+}
+
+/// Closes the thread's batch window and emits every request still waiting.
+async fn emit_batch(app: &AppHandle, workspace_id: &str, thread_id: Option<String>, key: String) {
+    tokio::time::sleep(BATCH_WINDOW).await;
+    let keys = open_batches().remove(&key).unwrap_or_default();
+    let requests: Vec<BatchedRequest> = {
+        let pending = pending_requests();
+        keys.iter()
+            .filter_map(|key| pending.get(key))
+            .map(|request| BatchedRequest {
+                request_id: request.request_id.clone(),
+                turn_id: request.turn_id.clone(),
+                questions: annotate_questions(&request.questions, &request.defaults),
+            })
+            .collect()
+    };
+    if requests.is_empty() {
+        return;
+    }
+    let state = app.state::<AppState>();
+    let policy = workspace_policy(&state, workspace_id).await;
+    let question_timeout_secs = question_timeout_secs(&state).await;
+    let batch = UserInputBatch {
+        workspace_id: workspace_id.to_string(),
+        thread_id,
+        requests,
+        deadline_ms: effective_timeout(&policy, question_timeout_secs)
+            .map(|timeout| now_millis() + timeout.as_millis() as u64),
+    };
+    let _ = app.emit(USER_INPUT_BATCH_EVENT, batch);
+}
+
+async fn workspace_policy(state: &AppState, workspace_id: &str) -> UserInputPolicy {
+    state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .map(|entry| entry.settings.user_input_policy.clone())
+        .unwrap_or_default()
+}
+
+async fn question_timeout_secs(state: &AppState) -> u64 {
+    state.app_settings.lock().await.claude_question_timeout_secs
+}
+
+async fn handle_request(
+    app: AppHandle,
+    workspace_id: String,
+    thread_id: Option<String>,
+    turn_id: Option<String>,
+    request_id: Value,
+    questions: Vec<Value>,
+) {
+    let policy = workspace_policy(&app.state::<AppState>(), &workspace_id).await;
+    let defaults: Vec<Option<String>> = questions
+        .iter()
+        .map(|question| default_answer(&policy, question))
+        .collect();
+    let token = next_token();
+    let key = pending_key(&workspace_id, &request_id);
+    let pending = PendingRequest {
+        token,
+        thread_id: thread_id.clone(),
+        turn_id,
+        request_id,
+        questions,
+        defaults,
+    };
+
+    if policy.unattended && pending.defaults.iter().all(Option::is_some) {
+        if let Some(result) = answers_result(&pending.questions, &pending.defaults) {
+            submit_answer(&app, &workspace_id, pending, result, "unattended").await;
+            return;
+        }
+    }
+    pending_requests().insert(key.clone(), pending);
+
+    let batch_key = batch_key(&workspace_id, thread_id.as_deref());
+    let opens_batch = {
+        let mut batches = open_batches();
+        let keys = batches.entry(batch_key.clone()).or_default();
+        keys.push(key.clone());
+        keys.len() == 1
+    };
+    if opens_batch {
+        emit_batch(&app, &workspace_id, thread_id, batch_key).await;
+    } else {
+        tokio::time::sleep(BATCH_WINDOW).await;
+    }
+
+    let question_timeout_secs = question_timeout_secs(&app.state::<AppState>()).await;
+    let Some(timeout) = effective_timeout(&policy, question_timeout_secs) else {
+        return;
+    };
+    tokio::time::sleep(timeout).await;
+    let expired = {
+        let mut pending = pending_requests();
+        match pending.get(&key) {
+            Some(request) if request.token == token => pending.remove(&key),
+            _ => None,
+        }
+    };
+    let Some(expired) = expired else {
+        return;
+    };
+    if let Some(result) = answers_result(&expired.questions, &expired.defaults) {
+        submit_answer(&app, &workspace_id, expired, result, "timeout").await;
+    }
+}
+
+/// Picks up `requestUserInput` requests from the event stream; turn
+/// completion drops the thread's unanswered requests.
+pub(crate) fn observe_app_server_event(app: &AppHandle, event: &AppServerEvent) {
+    let Some(method) = event.message.get("method").and_then(Value::as_str) else {
+        return;
+    };
+    let params = event.message.get("params").unwrap_or(&Value::Null);
+    let thread_id = params
+        .get("threadId")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    if matches!(method, "turn/completed" | "turn/error") {
+        if let Some(thread_id) = thread_id {
+            let prefix = format!("{}:", event.workspace_id);
+            pending_requests().retain(|key, pending| {
+                !(key.starts_with(&prefix)
+                    && pending.thread_id.as_deref() == Some(thread_id.as_str()))
+            });
+        }
+        return;
+    }
+    if method != REQUEST_USER_INPUT_METHOD {
+        return;
+    }
+    let Some(request_id) = event.message.get("id").cloned() else {
+        return;
+    };
+    let questions = params
+        .get("questions")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if questions.is_empty() {
+        return;
+    }
+    let turn_id = params
+        .get("turnId")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    tauri::async_runtime::spawn(handle_request(
+        app.clone(),
+        event.workspace_id.clone(),
+        thread_id,
+        turn_id,
+        request_id,
+        questions,
+    ));
+}
+
+/// Stops the countdown for a request the user (or a rule) answered.
+pub(crate) fn mark_request_resolved(workspace_id: &str, request_id: &Value) {
+    pending_requests().remove(&pending_key(workspace_id, request_id));
+}
+
+/// Submits the answers of a batched dialog, one server response per request.
+#[tauri::command]
+pub(crate) async fn respond_to_user_input_batch(
+    workspace_id: String,
+    responses: Vec<UserInputBatchResponse>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let mut failures = Vec::new();
+    for response in responses {
+        let request_id = response.request_id.clone();
+        if let Err(error) = crate::codex::respond_to_server_request(
+            workspace_id.clone(),
+            response.request_id,
+            response.result,
+            response.thread_id,
+            response.turn_id,
+            state.clone(),
+            app.clone(),
+        )
+        .await
+        {
+            failures.push(format!("{request_id}: {error}"));
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{answers_result, default_answer, effective_timeout, MIN_TIMEOUT_SECS};
+    use crate::types::{UserInputAnswerRule, UserInputPolicy};
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn question(id: &str, text: &str, options: &[&str]) -> serde_json::Value {
+        json!({
+            "id": id,
+            "header": "",
+            "question": text,
+            "options": options
+                .iter()
+                .map(|label| json!({ "label": label, "description": "" }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    #[test]
+    fn defaults_come_from_rules_then_first_option() {
+        let choice = question(
+            "q-0",
+            "Which database should I use?",
+            &["Postgres", "SQLite"],
+        );
+        let free_text = question("q-1", "Name the new module", &[]);
+        let rule = UserInputAnswerRule {
+            pattern: "DATABASE".to_string(),
+            answer: "SQLite".to_string(),
+        };
+
+        let waiting = UserInputPolicy::default();
+        assert_eq!(default_answer(&waiting, &choice), None);
+
+        let timed = UserInputPolicy {
+            timeout_secs: Some(60),
+            ..UserInputPolicy::default()
+        };
+        assert_eq!(default_answer(&timed, &choice).as_deref(), Some("Postgres"));
+        assert_eq!(default_answer(&timed, &free_text), None);
+
+        let ruled = UserInputPolicy {
+            rules: vec![rule],
+            ..timed
+        };
+        assert_eq!(default_answer(&ruled, &choice).as_deref(), Some("SQLite"));
+    }
+
+    #[test]
+    fn builds_results_for_answered_questions_only() {
+        let questions = vec![
+            question("q-0", "Proceed?", &["Yes", "No"]),
+            question("q-1", "Name?", &[]),
+        ];
+        let result = answers_result(&questions, &[Some("Yes".to_string()), None]);
+        assert_eq!(
+            result,
+            Some(json!({ "answers": { "q-0": { "answers": ["Yes"] } } }))
+        );
+        assert_eq!(answers_result(&questions, &[None, None]), None);
+    }
+
+    #[test]
+    fn clamps_timeouts_below_the_claude_wait_limit() {
+        let policy = UserInputPolicy {
+            timeout_secs: Some(3_600),
+            ..UserInputPolicy::default()
+        };
+        assert_eq!(
+            effective_timeout(&policy, 300),
+            Some(Duration::from_secs(240))
+        );
+        assert_eq!(effective_timeout(&UserInputPolicy::default(), 300), None);
+    }
+
+    #[test]
+    fn derives_the_clamp_from_the_configured_claude_wait() {
+        let policy = UserInputPolicy {
+            timeout_secs: Some(3_600),
+            ..UserInputPolicy::default()
+        };
+        assert_eq!(
+            effective_timeout(&policy, 1_800),
+            Some(Duration::from_secs(1_740))
+        );
+        assert_eq!(
+            effective_timeout(&policy, 60),
+            Some(Duration::from_secs(48))
+        );
+        assert_eq!(
+            effective_timeout(&policy, 0),
+            Some(Duration::from_secs(MIN_TIMEOUT_SECS))
+        );
+        let short = UserInputPolicy {
+            timeout_secs: Some(30),
+            ..UserInputPolicy::default()
+        };
+        assert_eq!(
+            effective_timeout(&short, 1_800),
+            Some(Duration::from_secs(30))
+        );
+    }
+}
@@ -9,11 +9,17 @@ use tokio::time::timeout;
 
 use crate::app_paths;
 use crate::backend::app_server::build_codex_path_env;
+use crate::shared::config_journal::record_config_write;
 use crate::types::{CodexProviderConfig, ProviderConfig};
 use crate::utils::async_command;
 #[cfg(windows)]
 use crate::utils::async_command_with_console_visibility;
 
+#[path = "transfer.rs"]
+mod transfer;
+
+pub(crate) use transfer::*;
+
 // ==================== Claude Settings Sync ====================
 
 /// Fields in ~/.claude/settings.json that are managed by the system and should NOT be overwritten
@@ -49,6 +55,9 @@ const LOCAL_PROVIDER_MODEL_MAPPING_KEYS: &[&str] = &[
     "ANTHROPIC_DEFAULT_OPUS_MODEL",
 ];
 const GEMINI_PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(8);
+const PROVIDER_HEALTH_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
 const GEMINI_DEFAULT_AUTH_MODE: &str = "login_google";
 
 fn default_enabled_true() -> bool {
@@ -103,6 +112,7 @@ fn write_claude_settings(settings: &serde_json::Map<String, Value>) -> Result<()
 
     let content = serde_json::to_string_pretty(&Value::Object(settings))
         .map_err(|e| format!("Failed to serialize claude settings: {}", e))?;
+    record_config_write("claude-settings", &path, &content);
 
     // Atomic write: write to temp file first, then rename
     let tmp_path = path.with_extension("json.tmp");
@@ -162,6 +172,7 @@ fn build_local_provider(is_active: bool) -> ProviderConfig {
         source: None,
         is_local_provider: Some(true),
         settings_config: extract_local_model_mapping_settings(),
+        tls: None,
     }
 }
 
@@ -296,6 +307,23 @@ pub(crate) struct GeminiVendorPreflightResult {
     pub(crate) checks: Vec<GeminiVendorPreflightCheck>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VendorProviderHealth {
+    pub(crate) ok: bool,
+    pub(crate) url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) status: Option<u16>,
+    pub(crate) latency_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+    /// Set when the failure looks like a certificate problem.
+    #[serde(default)]
+    pub(crate) tls_error: bool,
+    #[serde(default)]
+    pub(crate) warnings: Vec<String>,
+}
+
 // ==================== Helpers ====================
 
 fn config_path() -> PathBuf {
@@ -323,6 +351,7 @@ fn write_config(config: &CodemossConfig) -> Result<(), String> {
     }
     let content = serde_json::to_string_pretty(config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    record_config_write("vendor-config", &path, &content);
     std::fs::write(&path, content).map_err(|e| format!("Failed to write config: {}", e))
 }
 
@@ -369,6 +398,9 @@ fn value_to_claude_provider(
         .map(String::from);
     let is_local_provider = value.get("isLocalProvider").and_then(|v| v.as_bool());
     let settings_config = value.get("settingsConfig").cloned();
+    let tls = value
+        .get("tls")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
 
     Ok(ProviderConfig {
         id: id.to_string(),
@@ -381,6 +413,7 @@ fn value_to_claude_provider(
         source,
         is_local_provider,
         settings_config,
+        tls,
     })
 }
 
@@ -410,6 +443,11 @@ fn claude_provider_to_value(provider: &ProviderConfig) -> Value {
     if let Some(ref sc) = provider.settings_config {
         map.insert("settingsConfig".into(), sc.clone());
     }
+    if let Some(ref tls) = provider.tls {
+        if let Ok(v) = serde_json::to_value(tls) {
+            map.insert("tls".into(), v);
+        }
+    }
     Value::Object(map)
 }
 
@@ -439,6 +477,9 @@ fn value_to_codex_provider(
     let custom_models = value
         .get("customModels")
         .and_then(|v| serde_json::from_value(v.clone()).ok());
+    let tls = value
+        .get("tls")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
 
     Ok(CodexProviderConfig {
         id: id.to_string(),
@@ -449,6 +490,7 @@ fn value_to_codex_provider(
         config_toml,
         auth_json,
         custom_models,
+        tls,
     })
 }
 
@@ -473,6 +515,11 @@ fn codex_provider_to_value(provider: &CodexProviderConfig) -> Value {
             map.insert("customModels".into(), v);
         }
     }
+    if let Some(ref tls) = provider.tls {
+        if let Ok(v) = serde_json::to_value(tls) {
+            map.insert("tls".into(), v);
+        }
+    }
     Value::Object(map)
 }
 
@@ -837,6 +884,150 @@ pub(crate) async fn vendor_switch_codex_provider(id: String) -> Result<(), Strin
     write_config(&config)
 }
 
+// ==================== Provider Health Check ====================
+
+fn claude_provider_base_url(provider: &Value) -> String {
+    provider
+        .get("settingsConfig")
+        .and_then(|settings| settings.get("env"))
+        .and_then(|env| env.get("ANTHROPIC_BASE_URL"))
+        .and_then(|url| url.as_str())
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .unwrap_or(DEFAULT_ANTHROPIC_BASE_URL)
+        .to_string()
+}
+
+/// Base URL of the `model_provider` selected in a Codex provider's config.toml.
+fn codex_provider_base_url(provider: &Value) -> String {
+    let parsed = provider
+        .get("configToml")
+        .and_then(|config| config.as_str())
+        .and_then(|config| config.parse::<toml::Table>().ok());
+    parsed
+        .as_ref()
+        .and_then(|table| {
+            let name = table.get("model_provider")?.as_str()?;
+            table
+                .get("model_providers")?
+                .get(name)?
+                .get("base_url")?
+                .as_str()
+        })
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .unwrap_or(DEFAULT_OPENAI_BASE_URL)
+        .to_string()
+}
+
+fn build_provider_http_client(
+    tls: Option<&crate::types::ProviderTlsOptions>,
+) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder().timeout(PROVIDER_HEALTH_TIMEOUT);
+    if let Some(tls) = tls {
+        if let Some(path) = tls
+            .ca_bundle_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+        {
+            let pem = std::fs::read(path)
+                .map_err(|error| format!("Failed to read CA bundle {path}: {error}"))?;
+            let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+                .map_err(|error| format!("Invalid CA bundle {path}: {error}"))?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        if tls.insecure_skip_verify {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+    }
+    builder
+        .build()
+        .map_err(|error| format!("Failed to configure HTTP client: {error}"))
+}
+
+fn looks_like_tls_error(error: &reqwest::Error) -> bool {
+    let mut source: Option<&dyn std::error::Error> = Some(error);
+    while let Some(current) = source {
+        let text = current.to_string().to_ascii_lowercase();
+        if ["certificate", "tls", "handshake", "unknownissuer"]
+            .iter()
+            .any(|needle| text.contains(needle))
+        {
+            return true;
+        }
+        source = current.source();
+    }
+    false
+}
+
+/// Reaches the provider's endpoint with its TLS options applied. Any HTTP
+/// response counts as healthy; only connection and TLS failures do not.
+#[tauri::command]
+pub(crate) async fn vendor_check_provider_health(
+    engine: String,
+    provider_id: String,
+) -> Result<VendorProviderHealth, String> {
+    use crate::shared::provider_tls::{
+        provider_tls_options, provider_tls_warnings, TlsProviderEngine,
+    };
+
+    let config = read_config()?;
+    let (tls_engine, provider, url) = match engine.trim() {
+        "claude" => {
+            let provider = config
+                .claude
+                .providers
+                .get(&provider_id)
+                .ok_or_else(|| format!("Claude provider {} not found", provider_id))?;
+            let url = claude_provider_base_url(provider);
+            (TlsProviderEngine::Claude, provider, url)
+        }
+        "codex" => {
+            let provider = config
+                .codex
+                .providers
+                .get(&provider_id)
+                .ok_or_else(|| format!("Codex provider {} not found", provider_id))?;
+            let url = codex_provider_base_url(provider);
+            (TlsProviderEngine::Codex, provider, url)
+        }
+        other => return Err(format!("Unsupported provider engine: {}", other)),
+    };
+    let tls = provider_tls_options(provider);
+    let warnings = tls
+        .as_ref()
+        .map(|tls| provider_tls_warnings(tls, tls_engine))
+        .unwrap_or_default();
+    let client = build_provider_http_client(tls.as_ref())?;
+
+    let started_at = std::time::Instant::now();
+    let response = client.get(&url).send().await;
+    let latency_ms = started_at.elapsed().as_millis() as u64;
+    Ok(match response {
+        Ok(response) => VendorProviderHealth {
+            ok: true,
+            url,
+            status: Some(response.status().as_u16()),
+            latency_ms,
+            error: None,
+            tls_error: false,
+            warnings,
+        },
+        Err(error) => VendorProviderHealth {
+            ok: false,
+            url,
+            status: None,
+            latency_ms,
+            tls_error: looks_like_tls_error(&error),
+            error: Some(error.to_string()),
+            warnings,
+        },
+    })
+}
+
 // ==================== Gemini Vendor Commands ====================
 
 #[tauri::command]
@@ -0,0 +1,690 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+
+use super::*;
+
+const BUNDLE_FORMAT: &str = "ccgui-vendor-bundle";
+const BUNDLE_VERSION: u32 = 1;
+const PBKDF2_ITERATIONS: u32 = 210_000;
+/// Upper bound accepted from imported bundles so a crafted file cannot stall the app.
+const MAX_PBKDF2_ITERATIONS: u32 = 5_000_000;
+const SALT_LEN: usize = 16;
+const MIN_PASSPHRASE_LEN: usize = 8;
+/// Env keys inside a Claude provider's settingsConfig, and keys inside a
+/// Codex provider's configToml, that are treated as secrets.
+const SECRET_ENV_MARKERS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD"];
+/// configToml tables whose values are all secrets (e.g. `Authorization`).
+const SECRET_TOML_TABLES: &[&str] = &["http_headers"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum VendorSecretsMode {
+    Include,
+    Exclude,
+    Encrypt,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VendorBundleEncryption {
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VendorBundleSection {
+    #[serde(default)]
+    providers: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    current: Option<String>,
+}
+
+/// Portable provider configuration produced by `vendor_export`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VendorBundle {
+    format: String,
+    version: u32,
+    exported_at: i64,
+    secrets: VendorSecretsMode,
+    #[serde(default)]
+    claude: VendorBundleSection,
+    #[serde(default)]
+    codex: VendorBundleSection,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encryption: Option<VendorBundleEncryption>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VendorImportReport {
+    claude_imported: Vec<String>,
+    codex_imported: Vec<String>,
+    skipped: Vec<String>,
+    /// Providers imported without their credentials (bundle exported with
+    /// secrets excluded); the user has to fill these in.
+    missing_secrets: Vec<String>,
+    activated: Vec<String>,
+}
+
+/// Secret values lifted out of provider entries, keyed by
+/// `<engine>/<provider id>/<field>`.
+type SecretMap = BTreeMap<String, String>;
+
+fn is_secret_env_key(key: &str) -> bool {
+    let upper = key.to_ascii_uppercase();
+    SECRET_ENV_MARKERS
+        .iter()
+        .any(|marker| upper.contains(marker))
+}
+
+/// Removes secret strings from `table`, returning them with their key paths.
+fn take_toml_secrets(
+    table: &mut toml::Table,
+    path: &mut Vec<String>,
+    secret_table: bool,
+) -> Vec<(Vec<String>, String)> {
+    let mut secrets = Vec::new();
+    let keys: Vec<String> = table.keys().cloned().collect();
+    for key in keys {
+        path.push(key.clone());
+        match table.get_mut(&key) {
+            Some(toml::Value::Table(child)) => {
+                let child_is_secret = SECRET_TOML_TABLES.contains(&key.as_str());
+                secrets.extend(take_toml_secrets(child, path, child_is_secret));
+            }
+            Some(toml::Value::String(_)) if secret_table || is_secret_env_key(&key) => {
+                if let Some(toml::Value::String(secret)) = table.remove(&key) {
+                    secrets.push((path.clone(), secret));
+                }
+            }
+            _ => {}
+        }
+        path.pop();
+    }
+    secrets
+}
+
+/// Puts a secret taken by [`take_toml_secrets`] back into `table`.
+fn restore_toml_secret(table: &mut toml::Table, path: &[String], secret: &str) -> bool {
+    let Some((key, parents)) = path.split_last() else {
+        return false;
+    };
+    let mut table = table;
+    for parent in parents {
+        let child = table
+            .entry(parent.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        let toml::Value::Table(child) = child else {
+            return false;
+        };
+        table = child;
+    }
+    table.insert(key.clone(), toml::Value::String(secret.to_string()));
+    true
+}
+
+fn provider_id_of(value: &Value) -> Option<&str> {
+    value
+        .get("id")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+}
+
+/// Removes secrets from a provider entry in place and returns them.
+fn take_provider_secrets(engine: &str, provider: &mut Value) -> SecretMap {
+    let mut secrets = SecretMap::new();
+    let Some(id) = provider_id_of(provider).map(str::to_string) else {
+        return secrets;
+    };
+    match engine {
+        "claude" => {
+            let env = provider
+                .get_mut("settingsConfig")
+                .and_then(|config| config.get_mut("env"))
+                .and_then(Value::as_object_mut);
+            if let Some(env) = env {
+                let keys: Vec<String> = env
+                    .keys()
+                    .filter(|key| is_secret_env_key(key))
+                    .cloned()
+                    .collect();
+                for key in keys {
+                    if let Some(Value::String(secret)) = env.remove(&key) {
+                        secrets.insert(format!("claude/{id}/env/{key}"), secret);
+                    }
+                }
+            }
+        }
+        "codex" => {
+            if let Some(map) = provider.as_object_mut() {
+                if let Some(Value::String(auth)) = map.remove("authJson") {
+                    secrets.insert(format!("codex/{id}/authJson"), auth);
+                }
+                // Unparsable configToml is left alone; import rejects it anyway.
+                let config_table = map
+                    .get("configToml")
+                    .and_then(Value::as_str)
+                    .and_then(|text| text.parse::<toml::Table>().ok());
+                if let Some(mut table) = config_table {
+                    let taken = take_toml_secrets(&mut table, &mut Vec::new(), false);
+                    if !taken.is_empty() {
+                        for (path, secret) in taken {
+                            let path = serde_json::to_string(&path).unwrap_or_default();
+                            secrets.insert(format!("codex/{id}/configToml/{path}"), secret);
+                        }
+                        if let Ok(text) = toml::to_string(&table) {
+                            map.insert("configToml".to_string(), Value::String(text));
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    secrets
+}
+
+fn restore_provider_secrets(engine: &str, provider: &mut Value, secrets: &SecretMap) -> bool {
+    let Some(id) = provider_id_of(provider).map(str::to_string) else {
+        return false;
+    };
+    let prefix = format!("{engine}/{id}/");
+    let mut restored = false;
+    for (key, secret) in secrets.range(prefix.clone()..) {
+        let Some(field) = key.strip_prefix(&prefix) else {
+            break;
+        };
+        let Some(map) = provider.as_object_mut() else {
+            break;
+        };
+        if let Some(env_key) = field.strip_prefix("env/") {
+            let env = map
+                .entry("settingsConfig")
+                .or_insert_with(|| Value::Object(serde_json::Map::new()))
+                .as_object_mut()
+                .map(|config| {
+                    config
+                        .entry("env")
+                        .or_insert_with(|| Value::Object(serde_json::Map::new()))
+                });
+            if let Some(Value::Object(env)) = env {
+                env.insert(env_key.to_string(), Value::String(secret.clone()));
+                restored = true;
+            }
+        } else if let Some(path) = field.strip_prefix("configToml/") {
+            let Ok(path) = serde_json::from_str::<Vec<String>>(path) else {
+                continue;
+            };
+            let mut table = map
+                .get("configToml")
+                .and_then(Value::as_str)
+                .and_then(|text| text.parse::<toml::Table>().ok())
+                .unwrap_or_default();
+            if restore_toml_secret(&mut table, &path, secret) {
+                if let Ok(text) = toml::to_string(&table) {
+                    map.insert("configToml".to_string(), Value::String(text));
+                    restored = true;
+                }
+            }
+        } else {
+            map.insert(field.to_string(), Value::String(secret.clone()));
+            restored = true;
+        }
+    }
+    restored
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Key<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    key.into()
+}
+
+fn encrypt_secrets(
+    secrets: &SecretMap,
+    passphrase: &str,
+    iterations: u32,
+) -> Result<VendorBundleEncryption, String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt, iterations));
+    let plaintext = serde_json::to_vec(secrets)
+        .map_err(|e| format!("Failed to serialize provider secrets: {}", e))?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| "Failed to encrypt provider secrets".to_string())?;
+    Ok(VendorBundleEncryption {
+        kdf: "pbkdf2-sha256".to_string(),
+        iterations,
+        salt: engine.encode(salt),
+        nonce: engine.encode(nonce),
+        ciphertext: engine.encode(ciphertext),
+    })
+}
+
+fn decrypt_secrets(
+    encryption: &VendorBundleEncryption,
+    passphrase: &str,
+) -> Result<SecretMap, String> {
+    if encryption.kdf != "pbkdf2-sha256" {
+        return Err(format!("Unsupported key derivation: {}", encryption.kdf));
+    }
+    if encryption.iterations == 0 || encryption.iterations > MAX_PBKDF2_ITERATIONS {
+        return Err("Invalid bundle key derivation parameters".to_string());
+    }
+    let engine = base64::engine::general_purpose::STANDARD;
+    let decode = |field: &str, value: &str| {
+        engine
+            .decode(value)
+            .map_err(|e| format!("Invalid bundle {}: {}", field, e))
+    };
+    let salt = decode("salt", &encryption.salt)?;
+    let nonce = decode("nonce", &encryption.nonce)?;
+    let ciphertext = decode("ciphertext", &encryption.ciphertext)?;
+    if nonce.len() != 12 {
+        return Err("Invalid bundle nonce length".to_string());
+    }
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt, encryption.iterations));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "Wrong passphrase or corrupted bundle".to_string())?;
+    serde_json::from_slice(&plaintext).map_err(|e| format!("Invalid bundle secrets: {}", e))
+}
+
+fn build_bundle(
+    config: &CodemossConfig,
+    mode: VendorSecretsMode,
+    passphrase: Option<&str>,
+    iterations: u32,
+) -> Result<VendorBundle, String> {
+    let passphrase = match mode {
+        VendorSecretsMode::Encrypt => Some(validate_passphrase(passphrase)?),
+        _ => None,
+    };
+    let mut secrets = SecretMap::new();
+    let mut collect = |engine: &str, providers: &HashMap<String, Value>| {
+        let mut entries: Vec<Value> = providers
+            .iter()
+            .filter(|(id, _)| id.as_str() != LOCAL_SETTINGS_PROVIDER_ID)
+            .map(|(id, value)| {
+                let mut value = value.clone();
+                if let Some(map) = value.as_object_mut() {
+                    map.insert("id".into(), Value::String(id.clone()));
+                }
+                value
+            })
+            .collect();
+        entries.sort_by_key(|value| value.get("createdAt").and_then(Value::as_i64));
+        if mode != VendorSecretsMode::Include {
+            for entry in entries.iter_mut() {
+                secrets.extend(take_provider_secrets(engine, entry));
+            }
+        }
+        entries
+    };
+    let claude = VendorBundleSection {
+        providers: collect("claude", &config.claude.providers),
+        current: config
+            .claude
+            .current
+            .clone()
+            .filter(|id| id != LOCAL_SETTINGS_PROVIDER_ID),
+    };
+    let codex = VendorBundleSection {
+        providers: collect("codex", &config.codex.providers),
+        current: config.codex.current.clone(),
+    };
+    let encryption = match passphrase {
+        Some(passphrase) => Some(encrypt_secrets(&secrets, passphrase, iterations)?),
+        None => None,
+    };
+    Ok(VendorBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        secrets: mode,
+        claude,
+        codex,
+        encryption,
+    })
+}
+
+fn validate_passphrase(passphrase: Option<&str>) -> Result<&str, String> {
+    match passphrase {
+        Some(value) if value.chars().count() >= MIN_PASSPHRASE_LEN => Ok(value),
+        Some(_) => Err(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        )),
+        None => Err("A passphrase is required for encrypted bundles".to_string()),
+    }
+}
+
+fn validate_bundle_provider(engine: &str, provider: &Value) -> Result<String, String> {
+    let id = provider_id_of(provider).ok_or("provider without id")?;
+    if id == LOCAL_SETTINGS_PROVIDER_ID {
+        return Err(format!("{id}: reserved provider id"));
+    }
+    let has_name = provider
+        .get("name")
+        .and_then(Value::as_str)
+        .is_some_and(|name| !name.trim().is_empty());
+    if !has_name {
+        return Err(format!("{id}: provider name is required"));
+    }
+    match engine {
+        "claude" => {
+            if let Some(config) = provider.get("settingsConfig") {
+                if !config.is_object() {
+                    return Err(format!("{id}: settingsConfig must be an object"));
+                }
+            }
+            value_to_claude_provider(id, provider, false)?;
+        }
+        _ => {
+            if let Some(toml_text) = provider.get("configToml").and_then(Value::as_str) {
+                toml_text
+                    .parse::<toml::Table>()
+                    .map_err(|e| format!("{id}: invalid configToml: {}", e))?;
+            }
+            if let Some(auth) = provider.get("authJson").and_then(Value::as_str) {
+                serde_json::from_str::<Value>(auth)
+                    .map_err(|e| format!("{id}: invalid authJson: {}", e))?;
+            }
+            value_to_codex_provider(id, provider, false)?;
+        }
+    }
+    Ok(id.to_string())
+}
+
+/// Merges bundle providers into `config`. Existing providers are kept unless
+/// `overwrite` is set.
+fn merge_bundle_into_config(
+    config: &mut CodemossConfig,
+    bundle: &VendorBundle,
+    secrets: &SecretMap,
+    overwrite: bool,
+) -> VendorImportReport {
+    let mut report = VendorImportReport::default();
+    let sections = [
+        ("claude", &bundle.claude, &mut config.claude.providers),
+        ("codex", &bundle.codex, &mut config.codex.providers),
+    ];
+    for (engine, section, target) in sections {
+        for provider in &section.providers {
+            let id = match validate_bundle_provider(engine, provider) {
+                Ok(id) => id,
+                Err(error) => {
+                    report.skipped.push(format!("{engine}: {error}"));
+                    continue;
+                }
+            };
+            if target.contains_key(&id) && !overwrite {
+                report
+                    .skipped
+                    .push(format!("{engine}: {id}: provider already exists"));
+                continue;
+            }
+            let mut provider = provider.clone();
+            let restored = restore_provider_secrets(engine, &mut provider, secrets);
+            if bundle.secrets == VendorSecretsMode::Exclude && !restored {
+                report.missing_secrets.push(format!("{engine}/{id}"));
+            }
+            target.insert(id.clone(), provider);
+            match engine {
+                "claude" => report.claude_imported.push(id),
+                _ => report.codex_imported.push(id),
+            }
+        }
+    }
+    report
+}
+
+#[tauri::command]
+pub(crate) async fn vendor_export(
+    secrets: VendorSecretsMode,
+    passphrase: Option<String>,
+) -> Result<VendorBundle, String> {
+    let config = read_config()?;
+    build_bundle(&config, secrets, passphrase.as_deref(), PBKDF2_ITERATIONS)
+}
+
+#[tauri::command]
+pub(crate) async fn vendor_import(
+    bundle: VendorBundle,
+    passphrase: Option<String>,
+    overwrite: Option<bool>,
+    activate: Option<bool>,
+) -> Result<VendorImportReport, String> {
+    if bundle.format != BUNDLE_FORMAT {
+        return Err("Not a provider configuration bundle".to_string());
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "Bundle version {} is newer than supported version {}",
+            bundle.version, BUNDLE_VERSION
+        ));
+    }
+    let secrets = match (&bundle.secrets, &bundle.encryption) {
+        (VendorSecretsMode::Encrypt, Some(encryption)) => {
+            decrypt_secrets(encryption, validate_passphrase(passphrase.as_deref())?)?
+        }
+        (VendorSecretsMode::Encrypt, None) => {
+            return Err("Encrypted bundle is missing its secrets payload".to_string())
+        }
+        _ => SecretMap::new(),
+    };
+
+    let mut config = read_config()?;
+    let mut report =
+        merge_bundle_into_config(&mut config, &bundle, &secrets, overwrite.unwrap_or(false));
+    write_config(&config)?;
+
+    if activate.unwrap_or(false) {
+        if let Some(id) = bundle
+            .claude
+            .current
+            .filter(|id| report.claude_imported.contains(id))
+        {
+            vendor_switch_claude_provider(id.clone()).await?;
+            report.activated.push(format!("claude/{id}"));
+        }
+        if let Some(id) = bundle
+            .codex
+            .current
+            .filter(|id| report.codex_imported.contains(id))
+        {
+            vendor_switch_codex_provider(id.clone()).await?;
+            report.activated.push(format!("codex/{id}"));
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_config() -> CodemossConfig {
+        let mut config = CodemossConfig::default();
+        config.claude.providers.insert(
+            "team".into(),
+            json!({
+                "id": "team",
+                "name": "Team gateway",
+                "createdAt": 2,
+                "settingsConfig": { "env": {
+                    "ANTHROPIC_BASE_URL": "https://gateway.example",
+                    "ANTHROPIC_AUTH_TOKEN": "sk-secret"
+                } }
+            }),
+        );
+        config.claude.current = Some("team".into());
+        config.codex.providers.insert(
+            "relay".into(),
+            json!({
+                "id": "relay",
+                "name": "Relay",
+                "configToml": "model = \"gpt-5\"",
+                "authJson": "{\"OPENAI_API_KEY\":\"sk-codex\"}"
+            }),
+        );
+        config
+    }
+
+    fn config_with_toml_secrets() -> CodemossConfig {
+        let mut config = CodemossConfig::default();
+        config.codex.providers.insert(
+            "gateway".into(),
+            json!({
+                "id": "gateway",
+                "name": "Gateway",
+                "configToml": concat!(
+                    "model_provider = \"gateway\"\n",
+                    "[model_providers.gateway]\n",
+                    "base_url = \"https://gateway.example/v1\"\n",
+                    "env_key = \"sk-inline-key\"\n",
+                    "experimental_bearer_token = \"sk-bearer\"\n",
+                    "[model_providers.gateway.http_headers]\n",
+                    "X-Team = \"team-secret\"\n",
+                )
+            }),
+        );
+        config
+    }
+
+    #[test]
+    fn config_toml_secrets_are_stripped_and_restored() {
+        let bundle = build_bundle(
+            &config_with_toml_secrets(),
+            VendorSecretsMode::Exclude,
+            None,
+            1,
+        )
+        .expect("export");
+        let text = serde_json::to_string(&bundle).expect("serialize");
+        assert!(!text.contains("sk-inline-key"));
+        assert!(!text.contains("sk-bearer"));
+        assert!(!text.contains("team-secret"));
+        assert!(text.contains("https://gateway.example/v1"));
+
+        let mut target = CodemossConfig::default();
+        let report = merge_bundle_into_config(&mut target, &bundle, &SecretMap::new(), false);
+        assert_eq!(report.missing_secrets, vec!["codex/gateway".to_string()]);
+
+        let bundle = build_bundle(
+            &config_with_toml_secrets(),
+            VendorSecretsMode::Encrypt,
+            Some("correct horse"),
+            2,
+        )
+        .expect("export");
+        let secrets = decrypt_secrets(
+            bundle.encryption.as_ref().expect("encryption"),
+            "correct horse",
+        )
+        .expect("decrypt");
+        let mut target = CodemossConfig::default();
+        let report = merge_bundle_into_config(&mut target, &bundle, &secrets, false);
+        assert!(report.missing_secrets.is_empty());
+        let restored: toml::Table = target.codex.providers["gateway"]["configToml"]
+            .as_str()
+            .expect("configToml")
+            .parse()
+            .expect("valid toml");
+        let provider = &restored["model_providers"]["gateway"];
+        assert_eq!(provider["env_key"].as_str(), Some("sk-inline-key"));
+        assert_eq!(
+            provider["experimental_bearer_token"].as_str(),
+            Some("sk-bearer")
+        );
+        assert_eq!(
+            provider["http_headers"]["X-Team"].as_str(),
+            Some("team-secret")
+        );
+        assert_eq!(
+            provider["base_url"].as_str(),
+            Some("https://gateway.example/v1")
+        );
+    }
+
+    #[test]
+    fn excluded_secrets_are_stripped_and_reported_on_import() {
+        let bundle =
+            build_bundle(&sample_config(), VendorSecretsMode::Exclude, None, 1).expect("export");
+        let text = serde_json::to_string(&bundle).expect("serialize");
+        assert!(!text.contains("sk-secret"));
+        assert!(!text.contains("sk-codex"));
+        assert!(text.contains("https://gateway.example"));
+
+        let mut target = CodemossConfig::default();
+        let report = merge_bundle_into_config(&mut target, &bundle, &SecretMap::new(), false);
+        assert_eq!(report.claude_imported, vec!["team".to_string()]);
+        assert_eq!(report.missing_secrets.len(), 2);
+        assert!(target.codex.providers["relay"].get("authJson").is_none());
+    }
+
+    #[test]
+    fn encrypted_secrets_round_trip_with_passphrase() {
+        let bundle = build_bundle(
+            &sample_config(),
+            VendorSecretsMode::Encrypt,
+            Some("correct horse"),
+            2,
+        )
+        .expect("export");
+        let encryption = bundle.encryption.clone().expect("encryption");
+        assert!(decrypt_secrets(&encryption, "wrong horse").is_err());
+        let secrets = decrypt_secrets(&encryption, "correct horse").expect("decrypt");
+
+        let mut target = CodemossConfig::default();
+        let report = merge_bundle_into_config(&mut target, &bundle, &secrets, false);
+        assert!(report.missing_secrets.is_empty());
+        assert_eq!(
+            target.claude.providers["team"]["settingsConfig"]["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-secret"
+        );
+        assert_eq!(
+            target.codex.providers["relay"]["authJson"],
+            "{\"OPENAI_API_KEY\":\"sk-codex\"}"
+        );
+        assert!(build_bundle(
+            &sample_config(),
+            VendorSecretsMode::Encrypt,
+            Some("short"),
+            1
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn import_skips_invalid_and_existing_providers() {
+        let mut bundle =
+            build_bundle(&sample_config(), VendorSecretsMode::Include, None, 1).expect("export");
+        bundle.codex.providers.push(json!({
+            "id": "broken",
+            "name": "Broken",
+            "configToml": "model = "
+        }));
+        let mut target = sample_config();
+        let report = merge_bundle_into_config(&mut target, &bundle, &SecretMap::new(), false);
+        assert!(report.claude_imported.is_empty());
+        assert!(report.codex_imported.is_empty());
+        assert_eq!(report.skipped.len(), 3);
+        assert!(report
+            .skipped
+            .iter()
+            .any(|entry| entry.contains("invalid configToml")));
+    }
+}
@@ -54,7 +54,7 @@ pub(crate) async fn maybe_start_local_daemon_for_remote(
         command.arg("--insecure-no-auth");
     }
 
-    if let Ok(data_dir) = app.path().app_data_dir() {
+    if let Ok(data_dir) = crate::data_dir::app_data_dir(app) {
         command.arg("--data-dir").arg(data_dir);
     }
 
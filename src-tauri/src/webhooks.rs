@@ -0,0 +1,663 @@
+//! Opt-in local webhook listener for triggering automations.
+//!
+//! External tools call `POST /automations/{id}` on `127.0.0.1` with the
+//! webhook token as a bearer header (or `?token=`). Only automations listed
+//! in `~/.ccgui/webhooks.json` can be triggered. Prompts start an engine turn
+//! and git refreshes emit a fresh `git-status-changed` snapshot right here;
+//! scheduled tasks live in the frontend task center, so those are forwarded
+//! as a `webhook-automation` event that runs the task like its Run button.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use axum::extract::{Path as UrlPath, Query, State as UrlState};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::net::TcpListener;
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::app_paths;
+use crate::engine::{self, EngineTurnLinkage, EngineType};
+use crate::state::AppState;
+use crate::storage::{read_json_file, with_storage_lock, write_string_atomically};
+
+const WEBHOOKS_FILE: &str = "webhooks.json";
+const WEBHOOK_AUTOMATION_EVENT: &str = "webhook-automation";
+const DEFAULT_WEBHOOK_PORT: u16 = 3090;
+const MAX_AUTOMATIONS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum WebhookAction {
+    /// Send `prompt` in a new thread of the workspace.
+    #[serde(rename_all = "camelCase")]
+    RunPrompt {
+        workspace_id: String,
+        prompt: String,
+        #[serde(default)]
+        engine: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    RefreshGitStatus { workspace_id: String },
+    /// Run a task from the task center now, regardless of its schedule.
+    #[serde(rename_all = "camelCase")]
+    RunScheduledTask { task_id: String },
+}
+
+impl WebhookAction {
+    fn workspace_id(&self) -> Option<&str> {
+        match self {
+            WebhookAction::RunPrompt { workspace_id, .. }
+            | WebhookAction::RefreshGitStatus { workspace_id } => Some(workspace_id),
+            WebhookAction::RunScheduledTask { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookAutomation {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(default = "default_true")]
+    pub(crate) enabled: bool,
+    pub(crate) action: WebhookAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default = "default_webhook_port")]
+    pub(crate) port: u16,
+    #[serde(default)]
+    pub(crate) token: String,
+    /// The allowlist: only these automations can be triggered over HTTP.
+    #[serde(default)]
+    pub(crate) automations: Vec<WebhookAutomation>,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_WEBHOOK_PORT,
+            token: String::new(),
+            automations: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookStatus {
+    settings: WebhookSettings,
+    running: bool,
+    /// Base URL of the listener while it is running.
+    address: Option<String>,
+    last_error: Option<String>,
+}
+
+/// Sent to the frontend for `runScheduledTask` automations.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookAutomationEvent {
+    trigger_id: String,
+    automation_id: String,
+    automation_name: String,
+    action: WebhookAction,
+    /// JSON body sent with the request, if any.
+    payload: Value,
+    triggered_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+#[derive(Clone)]
+struct ListenerState {
+    app: AppHandle,
+    token: String,
+}
+
+struct RunningListener {
+    port: u16,
+    shutdown_tx: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct ListenerRuntime {
+    running: Option<RunningListener>,
+    last_error: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_webhook_port() -> u16 {
+    DEFAULT_WEBHOOK_PORT
+}
+
+fn listener_runtime() -> &'static Mutex<ListenerRuntime> {
+    static RUNTIME: OnceLock<Mutex<ListenerRuntime>> = OnceLock::new();
+    RUNTIME.get_or_init(|| Mutex::new(ListenerRuntime::default()))
+}
+
+fn webhooks_path() -> Result<PathBuf, String> {
+    Ok(app_paths::app_home_dir()?.join(WEBHOOKS_FILE))
+}
+
+fn generate_token() -> String {
+    Uuid::new_v4().as_simple().to_string()
+}
+
+/// Reads the settings, creating a token the first time one is needed.
+fn read_webhook_settings() -> Result<WebhookSettings, String> {
+    let path = webhooks_path()?;
+    with_storage_lock(&path, || {
+        let mut settings: WebhookSettings = read_json_file(&path)?.unwrap_or_default();
+        if settings.token.trim().is_empty() {
+            settings.token = generate_token();
+            write_webhook_settings(&path, &settings)?;
+        }
+        Ok(settings)
+    })
+}
+
+fn write_webhook_settings(
+    path: &std::path::Path,
+    settings: &WebhookSettings,
+) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(settings).map_err(|error| error.to_string())?;
+    write_string_atomically(path, &data)
+}
+
+fn normalize_automations(
+    automations: Vec<WebhookAutomation>,
+) -> Result<Vec<WebhookAutomation>, String> {
+    if automations.len() > MAX_AUTOMATIONS {
+        return Err(format!(
+            "At most {MAX_AUTOMATIONS} webhook automations are allowed"
+        ));
+    }
+    let mut normalized: Vec<WebhookAutomation> = Vec::with_capacity(automations.len());
+    for mut automation in automations {
+        automation.id = automation.id.trim().to_string();
+        automation.name = automation.name.trim().to_string();
+        if automation.id.is_empty() {
+            automation.id = Uuid::new_v4().to_string();
+        }
+        if !automation
+            .id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        {
+            return Err(format!("Invalid webhook automation id: {}", automation.id));
+        }
+        if normalized
+            .iter()
+            .any(|existing| existing.id == automation.id)
+        {
+            return Err(format!(
+                "Duplicate webhook automation id: {}",
+                automation.id
+            ));
+        }
+        if automation.name.is_empty() {
+            automation.name = automation.id.clone();
+        }
+        match &mut automation.action {
+            WebhookAction::RunPrompt {
+                workspace_id,
+                prompt,
+                engine,
+            } => {
+                *workspace_id = workspace_id.trim().to_string();
+                *engine = engine
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_lowercase);
+                if let Some(engine) = engine.as_deref() {
+                    parse_engine(engine)?;
+                }
+                if prompt.trim().is_empty() {
+                    return Err(format!("Automation {} has an empty prompt", automation.id));
+                }
+            }
+            WebhookAction::RefreshGitStatus { workspace_id } => {
+                *workspace_id = workspace_id.trim().to_string();
+            }
+            WebhookAction::RunScheduledTask { task_id } => {
+                *task_id = task_id.trim().to_string();
+                if task_id.is_empty() {
+                    return Err(format!("Automation {} has no task id", automation.id));
+                }
+            }
+        }
+        if automation
+            .action
+            .workspace_id()
+            .is_some_and(|workspace_id| workspace_id.is_empty())
+        {
+            return Err(format!("Automation {} has no workspace", automation.id));
+        }
+        normalized.push(automation);
+    }
+    Ok(normalized)
+}
+
+fn parse_engine(engine: &str) -> Result<EngineType, String> {
+    serde_json::from_value(Value::String(engine.to_string()))
+        .map_err(|_| format!("Unknown engine: {engine}"))
+}
+
+/// Sends `prompt` in a new thread, on the automation's engine or the
+/// workspace default.
+async fn run_prompt_automation(
+    app: &AppHandle,
+    workspace_id: &str,
+    prompt: String,
+    engine: Option<&str>,
+) -> Result<EngineTurnLinkage, String> {
+    let state = app.state::<AppState>();
+    let entry = state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .cloned()
+        .ok_or_else(|| format!("workspace not found: {workspace_id}"))?;
+    let engine = match engine {
+        Some(engine) => parse_engine(engine)?,
+        None => crate::cli_bridge::workspace_default_engine(&state, &entry).await,
+    };
+    engine::start_engine_turn(&entry.id, engine, prompt, None, None, state, app.clone()).await
+}
+
+/// Runs an automation and returns the turn it started, if any.
+async fn run_automation(
+    app: &AppHandle,
+    automation: &WebhookAutomation,
+    trigger_id: &str,
+    payload: Value,
+) -> Result<Option<EngineTurnLinkage>, String> {
+    match &automation.action {
+        WebhookAction::RunPrompt {
+            workspace_id,
+            prompt,
+            engine,
+        } => run_prompt_automation(app, workspace_id, prompt.clone(), engine.as_deref())
+            .await
+            .map(Some),
+        WebhookAction::RefreshGitStatus { workspace_id } => {
+            let state = app.state::<AppState>();
+            crate::git::emit_git_status_for_workspace(&state, app, workspace_id).await?;
+            Ok(None)
+        }
+        WebhookAction::RunScheduledTask { .. } => {
+            let event = WebhookAutomationEvent {
+                trigger_id: trigger_id.to_string(),
+                automation_id: automation.id.clone(),
+                automation_name: automation.name.clone(),
+                action: automation.action.clone(),
+                payload,
+                triggered_at: chrono::Utc::now().timestamp_millis(),
+            };
+            app.emit(WEBHOOK_AUTOMATION_EVENT, &event)
+                .map_err(|error| error.to_string())?;
+            Ok(None)
+        }
+    }
+}
+
+fn token_matches(expected: &str, headers: &HeaderMap, query: &TokenQuery) -> bool {
+    let bearer = headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            let (scheme, token) = value.trim().split_once(' ')?;
+            scheme
+                .eq_ignore_ascii_case("bearer")
+                .then(|| token.trim().to_string())
+        });
+    !expected.is_empty()
+        && [bearer.as_deref(), query.token.as_deref()]
+            .into_iter()
+            .flatten()
+            .any(|token| token == expected)
+}
+
+/// Looks up an allowlisted, enabled automation.
+fn find_automation<'a>(
+    settings: &'a WebhookSettings,
+    id: &str,
+) -> Result<&'a WebhookAutomation, (StatusCode, String)> {
+    match settings
+        .automations
+        .iter()
+        .find(|automation| automation.id == id)
+    {
+        Some(automation) if automation.enabled => Ok(automation),
+        Some(_) => Err((
+            StatusCode::FORBIDDEN,
+            format!("Automation {id} is disabled"),
+        )),
+        None => Err((StatusCode::NOT_FOUND, format!("Unknown automation: {id}"))),
+    }
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    (status, Json(json!({ "ok": false, "error": message }))).into_response()
+}
+
+async fn list_automations_route(
+    UrlState(listener): UrlState<ListenerState>,
+    headers: HeaderMap,
+    Query(query): Query<TokenQuery>,
+) -> Response {
+    if !token_matches(&listener.token, &headers, &query) {
+        return error_response(
+            StatusCode::UNAUTHORIZED,
+            "Invalid webhook token".to_string(),
+        );
+    }
+    match read_webhook_settings() {
+        Ok(settings) => {
+            let automations = settings
+                .automations
+                .iter()
+                .filter(|automation| automation.enabled)
+                .map(|automation| json!({ "id": automation.id, "name": automation.name }))
+                .collect::<Vec<_>>();
+            Json(json!({ "ok": true, "automations": automations })).into_response()
+        }
+        Err(error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, error),
+    }
+}
+
+async fn trigger_automation_route(
+    UrlState(listener): UrlState<ListenerState>,
+    UrlPath(id): UrlPath<String>,
+    headers: HeaderMap,
+    Query(query): Query<TokenQuery>,
+    body: Option<Json<Value>>,
+) -> Response {
+    if !token_matches(&listener.token, &headers, &query) {
+        return error_response(
+            StatusCode::UNAUTHORIZED,
+            "Invalid webhook token".to_string(),
+        );
+    }
+    let settings = match read_webhook_settings() {
+        Ok(settings) => settings,
+        Err(error) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, error),
+    };
+    let automation = match find_automation(&settings, &id) {
+        Ok(automation) => automation,
+        Err((status, message)) => return error_response(status, message),
+    };
+    if let Some(workspace_id) = automation.action.workspace_id() {
+        let state = listener.app.state::<AppState>();
+        let known = state.workspaces.lock().await.contains_key(workspace_id);
+        if !known {
+            return error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("workspace not found: {workspace_id}"),
+            );
+        }
+    }
+
+    let trigger_id = Uuid::new_v4().to_string();
+    let payload = body.map(|Json(value)| value).unwrap_or(Value::Null);
+    let turn = match run_automation(&listener.app, automation, &trigger_id, payload).await {
+        Ok(turn) => turn,
+        Err(error) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, error),
+    };
+    log::info!(
+        "[webhooks] triggered automation {} ({trigger_id})",
+        automation.id
+    );
+    (
+        StatusCode::ACCEPTED,
+        Json(json!({ "ok": true, "triggerId": trigger_id, "turn": turn })),
+    )
+        .into_response()
+}
+
+async fn stop_listener(runtime: &mut ListenerRuntime) {
+    if let Some(running) = runtime.running.take() {
+        let _ = running.shutdown_tx.send(());
+        let _ = running.task.await;
+    }
+}
+
+/// Starts, restarts or stops the listener to match `settings`.
+async fn apply_listener_settings(app: &AppHandle, settings: &WebhookSettings) {
+    let mut runtime = listener_runtime().lock().await;
+    stop_listener(&mut runtime).await;
+    runtime.last_error = None;
+    if !settings.enabled {
+        return;
+    }
+    // Loopback only: the listener is meant for tools on this machine.
+    let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), settings.port);
+    let listener = match TcpListener::bind(address).await {
+        Ok(listener) => listener,
+        Err(error) => {
+            runtime.last_error = Some(format!("Failed to bind {address}: {error}"));
+            return;
+        }
+    };
+    let port = listener
+        .local_addr()
+        .map(|address| address.port())
+        .unwrap_or(settings.port);
+    let router = Router::new()
+        .route("/automations", get(list_automations_route))
+        .route("/automations/:id", post(trigger_automation_route))
+        .with_state(ListenerState {
+            app: app.clone(),
+            token: settings.token.clone(),
+        });
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let task = tokio::spawn(async move {
+        let server = axum::serve(listener, router).with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        });
+        if let Err(error) = server.await {
+            log::warn!("[webhooks] listener exited with error: {error}");
+        }
+    });
+    runtime.running = Some(RunningListener {
+        port,
+        shutdown_tx,
+        task,
+    });
+}
+
+async fn current_status(settings: WebhookSettings) -> WebhookStatus {
+    let runtime = listener_runtime().lock().await;
+    let running = runtime
+        .running
+        .as_ref()
+        .filter(|running| !running.task.is_finished());
+    WebhookStatus {
+        settings,
+        running: running.is_some(),
+        address: running.map(|running| format!("http://127.0.0.1:{}", running.port)),
+        last_error: runtime.last_error.clone(),
+    }
+}
+
+/// Starts the listener on launch when webhooks were left enabled.
+pub(crate) async fn restore_webhook_listener(app: AppHandle) {
+    match read_webhook_settings() {
+        Ok(settings) if settings.enabled => apply_listener_settings(&app, &settings).await,
+        Ok(_) => {}
+        Err(error) => log::warn!("[webhooks] failed to read settings: {error}"),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_webhook_status() -> Result<WebhookStatus, String> {
+    Ok(current_status(read_webhook_settings()?).await)
+}
+
+/// Saves the listener switch, port and automation allowlist, and restarts
+/// the listener to apply them.
+#[tauri::command]
+pub(crate) async fn update_webhook_settings(
+    enabled: bool,
+    port: Option<u16>,
+    automations: Vec<WebhookAutomation>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WebhookStatus, String> {
+    let port = port.unwrap_or(DEFAULT_WEBHOOK_PORT);
+    if port < 1024 {
+        return Err(format!("Webhook port must be 1024 or higher, got {port}"));
+    }
+    let automations = normalize_automations(automations)?;
+    {
+        let workspaces = state.workspaces.lock().await;
+        if let Some(missing) = automations
+            .iter()
+            .filter_map(|automation| automation.action.workspace_id())
+            .find(|workspace_id| !workspaces.contains_key(*workspace_id))
+        {
+            return Err(format!("workspace not found: {missing}"));
+        }
+    }
+    let path = webhooks_path()?;
+    let settings = with_storage_lock(&path, || {
+        let current: WebhookSettings = read_json_file(&path)?.unwrap_or_default();
+        let settings = WebhookSettings {
+            enabled,
+            port,
+            token: if current.token.trim().is_empty() {
+                generate_token()
+            } else {
+                current.token
+            },
+            automations,
+        };
+        write_webhook_settings(&path, &settings)?;
+        Ok(settings)
+    })?;
+    apply_listener_settings(&app, &settings).await;
+    Ok(current_status(settings).await)
+}
+
+/// Replaces the webhook token; callers holding the old one are rejected.
+#[tauri::command]
+pub(crate) async fn rotate_webhook_token(app: AppHandle) -> Result<WebhookStatus, String> {
+    let path = webhooks_path()?;
+    let settings = with_storage_lock(&path, || {
+        let mut settings: WebhookSettings = read_json_file(&path)?.unwrap_or_default();
+        settings.token = generate_token();
+        write_webhook_settings(&path, &settings)?;
+        Ok(settings)
+    })?;
+    apply_listener_settings(&app, &settings).await;
+    Ok(current_status(settings).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn automation(id: &str, enabled: bool) -> WebhookAutomation {
+        WebhookAutomation {
+            id: id.to_string(),
+            name: String::new(),
+            enabled,
+            action: WebhookAction::RefreshGitStatus {
+                workspace_id: " ws-1 ".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn only_enabled_allowlisted_automations_are_found() {
+        let settings = WebhookSettings {
+            automations: normalize_automations(vec![
+                automation("refresh", true),
+                automation("nightly", false),
+            ])
+            .expect("normalize"),
+            ..WebhookSettings::default()
+        };
+        let found = find_automation(&settings, "refresh").expect("allowlisted");
+        assert_eq!(found.name, "refresh");
+        assert_eq!(found.action.workspace_id(), Some("ws-1"));
+        assert_eq!(
+            find_automation(&settings, "nightly").unwrap_err().0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            find_automation(&settings, "other").unwrap_err().0,
+            StatusCode::NOT_FOUND
+        );
+        assert!(normalize_automations(vec![automation("a/b", true)]).is_err());
+        assert!(normalize_automations(vec![automation("x", true), automation("x", true)]).is_err());
+    }
+
+    #[test]
+    fn prompt_automations_must_name_a_known_engine() {
+        let prompt = |engine: &str| WebhookAutomation {
+            id: "ask".to_string(),
+            name: String::new(),
+            enabled: true,
+            action: WebhookAction::RunPrompt {
+                workspace_id: "ws-1".to_string(),
+                prompt: "Summarize the open issues".to_string(),
+                engine: Some(engine.to_string()),
+            },
+        };
+        let normalized = normalize_automations(vec![prompt(" Codex ")]).expect("known engine");
+        assert_eq!(
+            normalized[0].action,
+            WebhookAction::RunPrompt {
+                workspace_id: "ws-1".to_string(),
+                prompt: "Summarize the open issues".to_string(),
+                engine: Some("codex".to_string()),
+            }
+        );
+        assert_eq!(parse_engine("opencode"), Ok(EngineType::OpenCode));
+        assert!(normalize_automations(vec![prompt("gpt")]).is_err());
+    }
+
+    #[test]
+    fn token_is_accepted_from_bearer_header_or_query() {
+        let mut headers = HeaderMap::new();
+        let empty_query = TokenQuery::default();
+        assert!(!token_matches("secret", &headers, &empty_query));
+        headers.insert("authorization", HeaderValue::from_static("Bearer secret"));
+        assert!(token_matches("secret", &headers, &empty_query));
+        assert!(!token_matches("other", &headers, &empty_query));
+        assert!(!token_matches(
+            "",
+            &HeaderMap::new(),
+            &TokenQuery::default()
+        ));
+        let query = TokenQuery {
+            token: Some("secret".to_string()),
+        };
+        assert!(token_matches("secret", &HeaderMap::new(), &query));
+    }
+}
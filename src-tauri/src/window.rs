@@ -39,6 +39,21 @@ fn apply_macos_window_appearance(window: &Window, theme: &str) -> Result<(), Str
     Ok(())
 }
 
+/// Whether the main webview may navigate to `url` itself instead of handing
+/// it to the system browser.
+pub(crate) fn is_in_app_navigation(url: &tauri::Url) -> bool {
+    // Allow tauri internal protocol
+    if matches!(url.scheme(), "tauri" | "asset") {
+        return true;
+    }
+    // Allow localhost (dev server, memory iframe, workspace previews)
+    // Windows uses http://tauri.localhost/ as the internal webview origin
+    matches!(
+        url.host_str().unwrap_or(""),
+        "localhost" | "127.0.0.1" | "tauri.localhost"
+    )
+}
+
 pub(crate) fn apply_window_appearance(window: &Window, theme: &str) -> Result<(), String> {
     #[cfg(test)]
     if let Some(handler) = WINDOW_APPEARANCE_OVERRIDE
@@ -6,7 +6,7 @@ use std::time::Duration;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, State};
 use tokio::io::AsyncWriteExt;
 use uuid::Uuid;
 
@@ -33,6 +33,7 @@ use super::git::{
 };
 #[cfg(target_os = "macos")]
 use super::macos::get_open_app_icon_inner;
+use super::preview::{preview_workspace_file_inner, WorkspaceFilePreview};
 use super::settings::apply_workspace_settings_update;
 use super::worktree::{
     build_clone_destination_path, null_device_path, sanitize_worktree_name, unique_worktree_path,
@@ -47,11 +48,13 @@ use crate::codex::spawn_workspace_session;
 use crate::engine::{resolve_engine_type, EngineType};
 use crate::git_utils::resolve_git_root;
 use crate::remote_backend;
+use crate::shared::workspace_read_only::ensure_workspace_writable;
 use crate::shared::workspaces_core;
 use crate::state::AppState;
 use crate::storage::write_workspaces_preserving_existing;
 use crate::types::{
-    WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeSetupStatus,
+    WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeNamingContext,
+    WorktreeSetupStatus,
 };
 use crate::utils::{git_env_path, resolve_git_binary};
 
@@ -540,6 +543,7 @@ async fn collect_workspace_cleanup_ids(
 
 async fn cleanup_engine_sessions_for_workspace(state: &AppState, workspace_id: &str) {
     crate::terminal::cleanup_terminal_sessions_for_workspace(state, workspace_id).await;
+    crate::git::stop_git_status_watch_for_workspace(state, workspace_id).await;
     crate::engine::commands::clear_mcp_toggle_state(workspace_id);
     state
         .engine_manager
@@ -792,6 +796,33 @@ pub(crate) async fn resolve_file_preview_handle(
     }
 }
 
+#[tauri::command]
+pub(crate) async fn preview_file(
+    workspace_id: String,
+    path: String,
+    kind: Option<String>,
+    page: Option<usize>,
+    state: State<'_, AppState>,
+    _app: AppHandle,
+) -> Result<WorkspaceFilePreview, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("preview_file is not supported in remote mode yet.".to_string());
+    }
+
+    let root = workspaces_core::read_workspace_file_core(
+        &state.workspaces,
+        &workspace_id,
+        &path,
+        |root, _| Ok(root.clone()),
+    )
+    .await?;
+    tokio::task::spawn_blocking(move || {
+        preview_workspace_file_inner(&root, &path, kind.as_deref(), page.unwrap_or(0))
+    })
+    .await
+    .map_err(|err| format!("Preview task failed: {err}"))?
+}
+
 #[tauri::command]
 pub(crate) async fn write_external_spec_file(
     workspace_id: String,
@@ -927,7 +958,14 @@ pub(crate) async fn run_workspace_command(
         PathBuf::from(&entry.path)
     };
 
-    run_command_with_cwd(command, &workspace_root, timeout_ms).await
+    let result = run_command_with_cwd(command, &workspace_root, timeout_ms).await?;
+    crate::flaky_tests::record_command_output(
+        &workspace_id,
+        &workspace_root,
+        &result.command,
+        &format!("{}\n{}", result.stdout, result.stderr),
+    );
+    Ok(result)
 }
 
 #[tauri::command]
@@ -1318,10 +1356,55 @@ pub(crate) async fn add_clone(
     })
 }
 
+/// Branch name `add_worktree` would derive from `naming` with the parent's
+/// template (or `template`), including any collision suffix.
+#[tauri::command]
+pub(crate) async fn preview_worktree_branch_name(
+    parent_id: String,
+    naming: WorktreeNamingContext,
+    template: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "preview_worktree_branch_name",
+            json!({
+                "parentId": parent_id,
+                "naming": naming,
+                "template": template
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = crate::data_dir::app_data_dir(&app)?;
+    workspaces_core::preview_worktree_branch_name_core(
+        &parent_id,
+        template,
+        naming,
+        &data_dir,
+        &state.workspaces,
+        |value| sanitize_worktree_name(value),
+        |root, branch| {
+            let root = root.clone();
+            let branch = branch.to_string();
+            async move { git_branch_exists(&root, &branch).await }
+        },
+    )
+    .await
+}
+
+/// With an empty `branch` and a `naming` context, the branch name comes from
+/// the parent workspace's `worktreeBranchTemplate`.
 #[tauri::command]
 pub(crate) async fn add_worktree(
     parent_id: String,
     branch: String,
+    naming: Option<WorktreeNamingContext>,
     base_ref: Option<String>,
     publish_to_origin: Option<bool>,
     state: State<'_, AppState>,
@@ -1336,6 +1419,7 @@ pub(crate) async fn add_worktree(
             json!({
                 "parentId": parent_id,
                 "branch": branch,
+                "naming": naming,
                 "baseRef": base_ref,
                 "publishToOrigin": publish_to_origin
             }),
@@ -1344,14 +1428,12 @@ pub(crate) async fn add_worktree(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    let data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let data_dir = crate::data_dir::app_data_dir(&app)?;
 
     workspaces_core::add_worktree_core(
         parent_id,
         branch,
+        naming,
         base_ref,
         publish_to_origin,
         &data_dir,
@@ -1396,10 +1478,7 @@ pub(crate) async fn worktree_setup_status(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    let data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let data_dir = crate::data_dir::app_data_dir(&app)?;
     workspaces_core::worktree_setup_status_core(&state.workspaces, &workspace_id, &data_dir).await
 }
 
@@ -1420,10 +1499,7 @@ pub(crate) async fn worktree_setup_mark_ran(
         return Ok(());
     }
 
-    let data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let data_dir = crate::data_dir::app_data_dir(&app)?;
     workspaces_core::worktree_setup_mark_ran_core(&state.workspaces, &workspace_id, &data_dir).await
 }
 
@@ -1519,10 +1595,7 @@ pub(crate) async fn rename_worktree(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    let data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let data_dir = crate::data_dir::app_data_dir(&app)?;
 
     workspaces_core::rename_worktree_core(
         id,
@@ -1630,6 +1703,7 @@ pub(crate) async fn apply_worktree_changes(
             .get(&parent_id)
             .cloned()
             .ok_or("worktree parent not found")?;
+        ensure_workspace_writable(&parent, "apply_worktree_changes")?;
         (entry, parent)
     };
 
@@ -3,12 +3,15 @@ mod external_changes;
 mod files;
 mod git;
 mod macos;
+mod preload;
+mod preview;
 mod rewind_export;
 mod settings;
 mod worktree;
 
 pub(crate) use commands::*;
 pub(crate) use external_changes::DetachedExternalChangeRuntime;
+pub(crate) use preload::*;
 pub(crate) use rewind_export::*;
 
 #[cfg(test)]
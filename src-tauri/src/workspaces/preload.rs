@@ -0,0 +1,279 @@
+//! Workspace switcher preloading.
+//!
+//! `workspace_snapshot` returns git status, branches and recent sessions in a
+//! single round trip. Snapshots are cached for a short while and the most
+//! recently used workspaces are re-captured in the background, so switching
+//! back to one of them is served without a burst of cold calls.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::remote_backend;
+use crate::session_management::{list_workspace_sessions_core, WorkspaceSessionCatalogPage};
+use crate::state::AppState;
+
+const DEFAULT_SNAPSHOT_MAX_AGE_MS: u64 = 30_000;
+const MAX_RECENT_WORKSPACES: usize = 6;
+const SNAPSHOT_SESSION_LIMIT: u32 = 20;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceSnapshot {
+    pub(crate) workspace_id: String,
+    pub(crate) git_status: Option<serde_json::Value>,
+    pub(crate) git_status_error: Option<String>,
+    pub(crate) branches: Option<serde_json::Value>,
+    pub(crate) branches_error: Option<String>,
+    pub(crate) recent_sessions: Option<WorkspaceSessionCatalogPage>,
+    pub(crate) recent_sessions_error: Option<String>,
+    pub(crate) captured_at: u64,
+    pub(crate) from_cache: bool,
+}
+
+/// Most-recently-used workspace order plus their cached snapshots. Only the
+/// workspaces in `recent` keep a snapshot.
+#[derive(Default)]
+pub(crate) struct WorkspacePreloadRuntime {
+    recent: VecDeque<String>,
+    snapshots: HashMap<String, WorkspaceSnapshot>,
+    in_flight: HashSet<String>,
+}
+
+impl WorkspacePreloadRuntime {
+    fn touch(&mut self, workspace_id: &str) {
+        self.recent.retain(|id| id != workspace_id);
+        self.recent.push_front(workspace_id.to_string());
+        while self.recent.len() > MAX_RECENT_WORKSPACES {
+            if let Some(evicted) = self.recent.pop_back() {
+                self.snapshots.remove(&evicted);
+            }
+        }
+    }
+
+    fn fresh_snapshot(
+        &self,
+        workspace_id: &str,
+        max_age_ms: u64,
+        now: u64,
+    ) -> Option<WorkspaceSnapshot> {
+        self.snapshots
+            .get(workspace_id)
+            .filter(|snapshot| now.saturating_sub(snapshot.captured_at) <= max_age_ms)
+            .cloned()
+    }
+
+    fn store(&mut self, snapshot: WorkspaceSnapshot) {
+        self.in_flight.remove(&snapshot.workspace_id);
+        if self.recent.contains(&snapshot.workspace_id) {
+            self.snapshots
+                .insert(snapshot.workspace_id.clone(), snapshot);
+        }
+    }
+
+    /// Claims the recent workspaces (other than `skip`) whose snapshot is
+    /// missing or stale and not already being captured.
+    fn claim_stale(&mut self, skip: &str, now: u64) -> Vec<String> {
+        let stale: Vec<String> = self
+            .recent
+            .iter()
+            .filter(|id| id.as_str() != skip && !self.in_flight.contains(id.as_str()))
+            .filter(|id| {
+                self.fresh_snapshot(id, DEFAULT_SNAPSHOT_MAX_AGE_MS, now)
+                    .is_none()
+            })
+            .cloned()
+            .collect();
+        self.in_flight.extend(stale.iter().cloned());
+        stale
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_millis(0))
+        .as_millis() as u64
+}
+
+async fn capture_snapshot(workspace_id: &str, state: State<'_, AppState>) -> WorkspaceSnapshot {
+    let (git_status, branches, recent_sessions) = tokio::join!(
+        crate::git::get_git_status(workspace_id.to_string(), state.clone()),
+        crate::git::list_git_branches(workspace_id.to_string(), state.clone()),
+        list_workspace_sessions_core(
+            &state.workspaces,
+            &state.sessions,
+            &state.engine_manager,
+            state.storage_path.as_path(),
+            workspace_id.to_string(),
+            None,
+            None,
+            Some(SNAPSHOT_SESSION_LIMIT),
+        ),
+    );
+    WorkspaceSnapshot {
+        workspace_id: workspace_id.to_string(),
+        git_status_error: git_status.as_ref().err().cloned(),
+        git_status: git_status.ok(),
+        branches_error: branches.as_ref().err().cloned(),
+        branches: branches.ok(),
+        recent_sessions_error: recent_sessions.as_ref().err().cloned(),
+        recent_sessions: recent_sessions.ok(),
+        captured_at: now_millis(),
+        from_cache: false,
+    }
+}
+
+/// Captures snapshots for the stale recent workspaces one at a time, so the
+/// warm-up never competes with the active workspace for more than one slot.
+async fn warm_recent_workspaces(skip: &str, state: &AppState, app: &AppHandle) {
+    let stale = state
+        .workspace_preload
+        .lock()
+        .await
+        .claim_stale(skip, now_millis());
+    if stale.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        for workspace_id in stale {
+            let snapshot = capture_snapshot(&workspace_id, state.clone()).await;
+            state.workspace_preload.lock().await.store(snapshot);
+        }
+    });
+}
+
+async fn ensure_workspace_exists(state: &AppState, workspace_id: &str) -> Result<(), String> {
+    if state.workspaces.lock().await.contains_key(workspace_id) {
+        Ok(())
+    } else {
+        Err(format!("Workspace not found: {workspace_id}"))
+    }
+}
+
+/// Returns git status, branches and recent sessions for a workspace, served
+/// from the preload cache when younger than `max_age_ms` (pass `0` to force a
+/// fresh capture). Also marks the workspace as most recently used and warms
+/// the other recent workspaces in the background.
+#[tauri::command]
+pub(crate) async fn workspace_snapshot(
+    workspace_id: String,
+    max_age_ms: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceSnapshot, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("workspace_snapshot is not supported in remote mode yet.".to_string());
+    }
+    ensure_workspace_exists(&state, &workspace_id).await?;
+
+    let cached = {
+        let mut runtime = state.workspace_preload.lock().await;
+        runtime.touch(&workspace_id);
+        runtime.fresh_snapshot(
+            &workspace_id,
+            max_age_ms.unwrap_or(DEFAULT_SNAPSHOT_MAX_AGE_MS),
+            now_millis(),
+        )
+    };
+    let snapshot = match cached {
+        Some(snapshot) => WorkspaceSnapshot {
+            from_cache: true,
+            ..snapshot
+        },
+        None => {
+            let snapshot = capture_snapshot(&workspace_id, state.clone()).await;
+            state.workspace_preload.lock().await.store(snapshot.clone());
+            snapshot
+        }
+    };
+    warm_recent_workspaces(&workspace_id, &state, &app).await;
+    Ok(snapshot)
+}
+
+/// Seeds the recently used list (most recent first) and warms snapshots for
+/// those workspaces in the background. Unknown workspace ids are ignored.
+#[tauri::command]
+pub(crate) async fn preload_workspace_snapshots(
+    workspace_ids: Vec<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Ok(());
+    }
+    let known: Vec<String> = {
+        let workspaces = state.workspaces.lock().await;
+        workspace_ids
+            .into_iter()
+            .filter(|id| workspaces.contains_key(id))
+            .take(MAX_RECENT_WORKSPACES)
+            .collect()
+    };
+    {
+        let mut runtime = state.workspace_preload.lock().await;
+        for workspace_id in known.iter().rev() {
+            runtime.touch(workspace_id);
+        }
+    }
+    warm_recent_workspaces("", &state, &app).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WorkspacePreloadRuntime, WorkspaceSnapshot, MAX_RECENT_WORKSPACES};
+
+    fn snapshot(workspace_id: &str, captured_at: u64) -> WorkspaceSnapshot {
+        WorkspaceSnapshot {
+            workspace_id: workspace_id.to_string(),
+            git_status: None,
+            git_status_error: None,
+            branches: None,
+            branches_error: None,
+            recent_sessions: None,
+            recent_sessions_error: None,
+            captured_at,
+            from_cache: false,
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used_snapshots() {
+        let mut runtime = WorkspacePreloadRuntime::default();
+        for index in 0..=MAX_RECENT_WORKSPACES {
+            let id = format!("ws-{index}");
+            runtime.touch(&id);
+            runtime.store(snapshot(&id, 1_000));
+        }
+
+        assert_eq!(runtime.recent.len(), MAX_RECENT_WORKSPACES);
+        assert!(runtime.fresh_snapshot("ws-0", u64::MAX, 1_000).is_none());
+        assert!(runtime.fresh_snapshot("ws-1", u64::MAX, 1_000).is_some());
+        assert_eq!(
+            runtime.recent.front().map(String::as_str),
+            Some(format!("ws-{MAX_RECENT_WORKSPACES}").as_str())
+        );
+    }
+
+    #[test]
+    fn claims_only_stale_idle_recent_workspaces() {
+        let mut runtime = WorkspacePreloadRuntime::default();
+        for id in ["old", "fresh", "active"] {
+            runtime.touch(id);
+        }
+        runtime.store(snapshot("old", 0));
+        runtime.store(snapshot("fresh", 100_000));
+
+        assert_eq!(runtime.claim_stale("active", 100_000), vec!["old"]);
+        assert!(runtime.claim_stale("active", 100_000).is_empty());
+
+        runtime.store(snapshot("old", 100_000));
+        assert!(runtime.in_flight.is_empty());
+        assert!(runtime.fresh_snapshot("old", 0, 100_000).is_some());
+    }
+}
@@ -0,0 +1,508 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::NaiveDate;
+use image::{ImageFormat, ImageReader};
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::files::resolve_workspace_preview_handle_inner;
+use crate::text_encoding::decode_text_bytes;
+
+const MAX_MARKDOWN_PREVIEW_BYTES: u64 = 1024 * 1024;
+const MAX_IMAGE_PREVIEW_BYTES: u64 = 32 * 1024 * 1024;
+const IMAGE_THUMBNAIL_MAX_EDGE: u32 = 320;
+const TABLE_PREVIEW_PAGE_SIZE: usize = 100;
+const MAX_TABLE_RECORD_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PreviewKind {
+    Markdown,
+    Image,
+    Csv,
+    Tsv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ColumnType {
+    Integer,
+    Number,
+    Boolean,
+    Date,
+    String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "lowercase",
+    rename_all_fields = "camelCase"
+)]
+pub(crate) enum WorkspaceFilePreview {
+    Markdown {
+        html: String,
+        truncated: bool,
+    },
+    Image {
+        data_url: String,
+        source_format: String,
+        width: u32,
+        height: u32,
+        thumbnail_width: u32,
+        thumbnail_height: u32,
+    },
+    Table {
+        delimiter: String,
+        headers: Vec<String>,
+        column_types: Vec<ColumnType>,
+        rows: Vec<Vec<Value>>,
+        page: usize,
+        page_size: usize,
+        has_more: bool,
+    },
+}
+
+fn resolve_preview_kind(path: &Path, requested: Option<&str>) -> Result<PreviewKind, String> {
+    let requested = requested
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty() && value != "auto");
+    let key = match requested {
+        Some(value) => value,
+        None => path
+            .extension()
+            .and_then(|value| value.to_str())
+            .map(|value| value.to_ascii_lowercase())
+            .unwrap_or_default(),
+    };
+    match key.as_str() {
+        "markdown" | "md" | "markdown-html" | "mdx" => Ok(PreviewKind::Markdown),
+        "image" | "png" | "jpg" | "jpeg" | "gif" | "webp" | "bmp" => Ok(PreviewKind::Image),
+        "csv" => Ok(PreviewKind::Csv),
+        "tsv" | "tab" => Ok(PreviewKind::Tsv),
+        _ => Err(format!("Unsupported preview kind: {key}")),
+    }
+}
+
+fn is_unsafe_url(url: &str) -> bool {
+    let normalized: String = url
+        .trim()
+        .chars()
+        .filter(|ch| !ch.is_whitespace() && !ch.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    ["javascript:", "vbscript:", "data:", "file:"]
+        .iter()
+        .any(|scheme| normalized.starts_with(scheme))
+}
+
+fn sanitize_markdown_event(event: Event<'_>) -> Event<'_> {
+    match event {
+        // Raw HTML is rendered as escaped text so the webview never receives
+        // author-controlled markup.
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) if is_unsafe_url(&dest_url) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: CowStr::Borrowed("#"),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) if is_unsafe_url(&dest_url) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: CowStr::Borrowed(""),
+            title,
+            id,
+        }),
+        other => other,
+    }
+}
+
+fn render_markdown_html(source: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let parser = Parser::new_ext(source, options).map(sanitize_markdown_event);
+    let mut output = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut output, parser);
+    output
+}
+
+fn preview_markdown(path: &Path) -> Result<WorkspaceFilePreview, String> {
+    let file = File::open(path).map_err(|err| format!("Failed to open file: {err}"))?;
+    let mut buffer = Vec::new();
+    file.take(MAX_MARKDOWN_PREVIEW_BYTES + 1)
+        .read_to_end(&mut buffer)
+        .map_err(|err| format!("Failed to read file: {err}"))?;
+    let truncated = buffer.len() > MAX_MARKDOWN_PREVIEW_BYTES as usize;
+    if truncated {
+        buffer.truncate(MAX_MARKDOWN_PREVIEW_BYTES as usize);
+    }
+    let source = match decode_text_bytes(&buffer, "File") {
+        Ok(source) => source,
+        // A byte-limit cut may split a multi-byte sequence; fall back to lossy.
+        Err(_) if truncated => String::from_utf8_lossy(&buffer).to_string(),
+        Err(err) => return Err(err),
+    };
+    Ok(WorkspaceFilePreview::Markdown {
+        html: render_markdown_html(&source),
+        truncated,
+    })
+}
+
+fn preview_image(path: &Path, byte_length: u64) -> Result<WorkspaceFilePreview, String> {
+    if byte_length > MAX_IMAGE_PREVIEW_BYTES {
+        return Err("Image is too large to preview".to_string());
+    }
+    let reader = ImageReader::open(path)
+        .map_err(|err| format!("Failed to open image: {err}"))?
+        .with_guessed_format()
+        .map_err(|err| format!("Failed to read image: {err}"))?;
+    let source_format = reader
+        .format()
+        .and_then(|format| format.extensions_str().first().copied())
+        .unwrap_or("unknown")
+        .to_string();
+    let image = reader
+        .decode()
+        .map_err(|err| format!("Failed to decode image: {err}"))?;
+    let thumbnail = image.thumbnail(IMAGE_THUMBNAIL_MAX_EDGE, IMAGE_THUMBNAIL_MAX_EDGE);
+    let mut encoded = Vec::new();
+    thumbnail
+        .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Png)
+        .map_err(|err| format!("Failed to encode thumbnail: {err}"))?;
+    Ok(WorkspaceFilePreview::Image {
+        data_url: format!("data:image/png;base64,{}", STANDARD.encode(encoded)),
+        source_format,
+        width: image.width(),
+        height: image.height(),
+        thumbnail_width: thumbnail.width(),
+        thumbnail_height: thumbnail.height(),
+    })
+}
+
+/// Reads one delimited record, following quoted fields across line breaks.
+/// Returns `Ok(None)` at end of input.
+fn read_delimited_record<R: BufRead>(
+    reader: &mut R,
+    delimiter: char,
+) -> Result<Option<Vec<String>>, String> {
+    let mut raw = Vec::new();
+    loop {
+        let read = reader
+            .read_until(b'\n', &mut raw)
+            .map_err(|err| format!("Failed to read file: {err}"))?;
+        if read == 0 {
+            if raw.is_empty() {
+                return Ok(None);
+            }
+            break;
+        }
+        if raw.len() > MAX_TABLE_RECORD_BYTES {
+            return Err("Table record exceeds preview size limit".to_string());
+        }
+        let quotes = raw.iter().filter(|byte| **byte == b'"').count();
+        if quotes % 2 == 0 {
+            break;
+        }
+    }
+
+    let line = String::from_utf8_lossy(&raw);
+    let line = line.trim_end_matches(['\n', '\r']);
+    let line = line.strip_prefix('\u{feff}').unwrap_or(line);
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            if ch == '"' {
+                if chars.peek() == Some(&'"') {
+                    current.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                current.push(ch);
+            }
+        } else if ch == '"' && current.is_empty() {
+            in_quotes = true;
+        } else if ch == delimiter {
+            fields.push(std::mem::take(&mut current));
+        } else {
+            current.push(ch);
+        }
+    }
+    fields.push(current);
+    Ok(Some(fields))
+}
+
+fn classify_cell(value: &str) -> Option<ColumnType> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if value.parse::<i64>().is_ok() {
+        return Some(ColumnType::Integer);
+    }
+    if value.parse::<f64>().is_ok_and(f64::is_finite) {
+        return Some(ColumnType::Number);
+    }
+    if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+        return Some(ColumnType::Boolean);
+    }
+    if NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok() {
+        return Some(ColumnType::Date);
+    }
+    Some(ColumnType::String)
+}
+
+fn merge_column_type(current: Option<ColumnType>, next: ColumnType) -> ColumnType {
+    match (current, next) {
+        (None, next) => next,
+        (Some(current), next) if current == next => current,
+        (Some(ColumnType::Integer), ColumnType::Number)
+        | (Some(ColumnType::Number), ColumnType::Integer) => ColumnType::Number,
+        _ => ColumnType::String,
+    }
+}
+
+fn infer_column_types(column_count: usize, rows: &[Vec<String>]) -> Vec<ColumnType> {
+    (0..column_count)
+        .map(|index| {
+            rows.iter()
+                .filter_map(|row| row.get(index).and_then(|cell| classify_cell(cell)))
+                .fold(None, |acc, next| Some(merge_column_type(acc, next)))
+                .unwrap_or(ColumnType::String)
+        })
+        .collect()
+}
+
+fn typed_cell(value: &str, column_type: ColumnType) -> Value {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    match column_type {
+        ColumnType::Integer => trimmed
+            .parse::<i64>()
+            .map(Value::from)
+            .unwrap_or_else(|_| Value::from(value)),
+        ColumnType::Number => trimmed
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::from(value)),
+        ColumnType::Boolean => Value::Bool(trimmed.eq_ignore_ascii_case("true")),
+        ColumnType::Date | ColumnType::String => Value::from(value),
+    }
+}
+
+fn preview_table(
+    path: &Path,
+    delimiter: char,
+    page: usize,
+) -> Result<WorkspaceFilePreview, String> {
+    let file = File::open(path).map_err(|err| format!("Failed to open file: {err}"))?;
+    let mut reader = BufReader::new(file);
+    let headers = read_delimited_record(&mut reader, delimiter)?.unwrap_or_default();
+
+    let skip = page.saturating_mul(TABLE_PREVIEW_PAGE_SIZE);
+    for _ in 0..skip {
+        if read_delimited_record(&mut reader, delimiter)?.is_none() {
+            break;
+        }
+    }
+    let mut raw_rows = Vec::with_capacity(TABLE_PREVIEW_PAGE_SIZE);
+    while raw_rows.len() < TABLE_PREVIEW_PAGE_SIZE {
+        match read_delimited_record(&mut reader, delimiter)? {
+            Some(row) => raw_rows.push(row),
+            None => break,
+        }
+    }
+    let has_more = raw_rows.len() == TABLE_PREVIEW_PAGE_SIZE
+        && read_delimited_record(&mut reader, delimiter)?.is_some();
+
+    let column_count = raw_rows
+        .iter()
+        .map(Vec::len)
+        .chain(std::iter::once(headers.len()))
+        .max()
+        .unwrap_or(0);
+    let column_types = infer_column_types(column_count, &raw_rows);
+    let rows = raw_rows
+        .iter()
+        .map(|row| {
+            column_types
+                .iter()
+                .enumerate()
+                .map(|(index, column_type)| {
+                    row.get(index)
+                        .map(|cell| typed_cell(cell, *column_type))
+                        .unwrap_or(Value::Null)
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(WorkspaceFilePreview::Table {
+        delimiter: delimiter.to_string(),
+        headers,
+        column_types,
+        rows,
+        page,
+        page_size: TABLE_PREVIEW_PAGE_SIZE,
+        has_more,
+    })
+}
+
+pub(crate) fn preview_workspace_file_inner(
+    root: &PathBuf,
+    relative_path: &str,
+    kind: Option<&str>,
+    page: usize,
+) -> Result<WorkspaceFilePreview, String> {
+    let handle = resolve_workspace_preview_handle_inner(root, relative_path)?;
+    let path = PathBuf::from(&handle.absolute_path);
+    match resolve_preview_kind(&path, kind)? {
+        PreviewKind::Markdown => preview_markdown(&path),
+        PreviewKind::Image => preview_image(&path, handle.byte_length),
+        PreviewKind::Csv => preview_table(&path, ',', page),
+        PreviewKind::Tsv => preview_table(&path, '\t', page),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_root() -> PathBuf {
+        let root = std::env::temp_dir().join(format!("moss-x-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).expect("create temp dir");
+        root
+    }
+
+    #[test]
+    fn markdown_preview_escapes_raw_html_and_unsafe_links() {
+        let html = render_markdown_html(
+            "# Title\n\n<script>alert(1)</script>\n\n[x](javascript:alert(1)) [ok](https://a.dev)\n\n| a |\n|---|\n| 1 |\n",
+        );
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("href=\"#\""));
+        assert!(html.contains("href=\"https://a.dev\""));
+        assert!(html.contains("<table>"));
+    }
+
+    #[test]
+    fn resolves_kind_from_extension_or_request() {
+        assert_eq!(
+            resolve_preview_kind(Path::new("a/README.md"), None),
+            Ok(PreviewKind::Markdown)
+        );
+        assert_eq!(
+            resolve_preview_kind(Path::new("a/data.txt"), Some("tsv")),
+            Ok(PreviewKind::Tsv)
+        );
+        assert!(resolve_preview_kind(Path::new("a/main.rs"), Some("auto")).is_err());
+    }
+
+    #[test]
+    fn csv_preview_pages_rows_and_infers_types() {
+        let root = temp_root();
+        let mut content = String::from("id,price,active,day,note\n");
+        for index in 0..150 {
+            content.push_str(&format!(
+                "{index},{}.5,true,2024-01-02,\"line, \"\"{index}\"\"\nnext\"\n",
+                index
+            ));
+        }
+        std::fs::write(root.join("data.csv"), content).expect("write csv");
+
+        let first = preview_workspace_file_inner(&root, "data.csv", None, 0).expect("page 0");
+        let WorkspaceFilePreview::Table {
+            headers,
+            column_types,
+            rows,
+            has_more,
+            ..
+        } = first
+        else {
+            panic!("expected table preview");
+        };
+        assert_eq!(headers, vec!["id", "price", "active", "day", "note"]);
+        assert_eq!(
+            column_types,
+            vec![
+                ColumnType::Integer,
+                ColumnType::Number,
+                ColumnType::Boolean,
+                ColumnType::Date,
+                ColumnType::String
+            ]
+        );
+        assert_eq!(rows.len(), TABLE_PREVIEW_PAGE_SIZE);
+        assert_eq!(rows[3][0], Value::from(3));
+        assert_eq!(rows[3][4], Value::from("line, \"3\"\nnext"));
+        assert!(has_more);
+
+        let second = preview_workspace_file_inner(&root, "data.csv", None, 1).expect("page 1");
+        let WorkspaceFilePreview::Table { rows, has_more, .. } = second else {
+            panic!("expected table preview");
+        };
+        assert_eq!(rows.len(), 50);
+        assert_eq!(rows[0][0], Value::from(100));
+        assert!(!has_more);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn image_preview_returns_png_thumbnail() {
+        let root = temp_root();
+        let image = image::RgbImage::from_pixel(640, 320, image::Rgb([10, 20, 30]));
+        image.save(root.join("wide.png")).expect("write png");
+
+        let preview = preview_workspace_file_inner(&root, "wide.png", None, 0).expect("preview");
+        let WorkspaceFilePreview::Image {
+            data_url,
+            width,
+            height,
+            thumbnail_width,
+            thumbnail_height,
+            ..
+        } = preview
+        else {
+            panic!("expected image preview");
+        };
+        assert!(data_url.starts_with("data:image/png;base64,"));
+        assert_eq!((width, height), (640, 320));
+        assert_eq!((thumbnail_width, thumbnail_height), (320, 160));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn rejects_paths_outside_workspace() {
+        let root = temp_root();
+        assert!(preview_workspace_file_inner(&root, "../outside.md", None, 0).is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -66,7 +66,18 @@ fn workspace_with_id_and_kind(
             launch_script: None,
             launch_scripts: None,
             worktree_setup_script: None,
+            worktree_branch_template: None,
             engine_type: None,
+            access_mode_preset: None,
+            read_only: false,
+            agent_auto_commit: false,
+            privacy_rules: Vec::new(),
+            user_input_policy: Default::default(),
+            session_retention: Default::default(),
+            managed_codex: None,
+            pr_range_gate_enabled: None,
+            pr_max_changed_files: None,
+            pr_suspicious_threshold: None,
         },
     }
 }
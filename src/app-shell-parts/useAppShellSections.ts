@@ -16,6 +16,7 @@ import {
 } from "../features/kanban/utils/contextMode";
 import { deriveKanbanTaskTitle } from "../features/kanban/utils/taskTitle";
 import { findTaskDownstream } from "../features/kanban/utils/chaining";
+import { useWebhookTaskTriggers } from "../features/kanban/hooks/useWebhookTaskTriggers";
 import { buildChainedPromptPrefix, extractKanbanResultSnapshot } from "../features/kanban/utils/resultSnapshot";
 import {
   beginKanbanTaskRunLifecycle,
@@ -1236,6 +1237,7 @@ export function useAppShellSections(ctx: any) {
       patchTaskRunAndProjectToKanban,
     ],
   );
+  useWebhookTaskTriggers(launchKanbanTaskExecution);
 
   // --- Kanban conversation handlers ---
   const handleOpenTaskConversation = useCallback(
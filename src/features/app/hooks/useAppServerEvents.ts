@@ -1630,7 +1630,11 @@ export function useAppServerEvents(
         return;
       }
 
-      if (method === "turn/error") {
+      if (
+        method === "turn/error" ||
+        method === "turn/timeout" ||
+        method === "turn/disconnected"
+      ) {
         const params = message.params as Record<string, unknown>;
         const threadId = sharedBridge?.sharedThreadId ?? String(params.threadId ?? params.thread_id ?? "");
         const turnId = String(params.turnId ?? params.turn_id ?? "");
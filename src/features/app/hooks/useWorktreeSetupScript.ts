@@ -5,7 +5,6 @@ import {
   getWorktreeSetupStatus,
   markWorktreeSetupRan,
   openTerminalSession,
-  writeTerminalSession,
 } from "../../../services/tauri";
 
 const WORKTREE_SETUP_TERMINAL_ID = "worktree-setup";
@@ -66,8 +65,8 @@ export function useWorktreeSetupScript({
           terminalId,
           DEFAULT_TERMINAL_COLS,
           DEFAULT_TERMINAL_ROWS,
+          { command: script },
         );
-        await writeTerminalSession(worktree.id, terminalId, `${script}\n`);
         await markWorktreeSetupRan(worktree.id);
       } catch (error) {
         onDebug?.(buildErrorDebugEntry("worktree setup script error", error));
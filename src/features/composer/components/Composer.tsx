@@ -1307,7 +1307,10 @@ export const Composer = memo(function Composer({
       }
       if (trimmed) {
         recordHistory(trimmed);
-        recordInputHistory(trimmed);
+        recordInputHistory(trimmed, {
+          workspaceId: activeWorkspaceId,
+          engine: selectedEngine,
+        });
       }
       inlineCompletion.clear();
       const finalText = shouldAssemblePrompt({
@@ -1409,6 +1412,8 @@ export const Composer = memo(function Composer({
       onSend,
       inlineCompletion,
       recordHistory,
+      activeWorkspaceId,
+      selectedEngine,
       resetHistoryNavigation,
       setComposerText,
       selectedCommonsNames,
@@ -117,6 +117,38 @@ export function loadHistoryCounts(): Record<string, number> {
   return cachedCounts;
 }
 
+export type HistoryItemMeta = {
+  workspaceIds?: string[];
+  engine?: string;
+  updatedAt?: number;
+};
+
+export type HistorySearchPage = {
+  items: string[];
+  counts: Record<string, number>;
+  meta: Record<string, HistoryItemMeta>;
+  total: number;
+};
+
+/**
+ * Searches the persisted history in the backend: case-insensitive substring
+ * or fuzzy match, optionally limited to prompts sent from one workspace.
+ * Results are ranked best match first.
+ */
+export async function searchHistory(options: {
+  query?: string | null;
+  workspaceId?: string | null;
+  limit?: number;
+  offset?: number;
+}): Promise<HistorySearchPage> {
+  return invoke<HistorySearchPage>("input_history_read", {
+    query: options.query ?? null,
+    workspaceId: options.workspaceId ?? null,
+    limit: options.limit ?? 50,
+    offset: options.offset ?? 0,
+  });
+}
+
 // ─── History completion enabled setting ───
 
 const ENABLED_KEY = "historyCompletionEnabled";
@@ -159,7 +191,15 @@ export function setHistoryCompletionEnabled(enabled: boolean): void {
 
 // ─── Write APIs ───
 
-export function recordHistory(text: string): void {
+export type HistoryRecordContext = {
+  workspaceId?: string | null;
+  engine?: string | null;
+};
+
+export function recordHistory(
+  text: string,
+  context: HistoryRecordContext = {},
+): void {
   const sanitized = text.replace(INVISIBLE_CHARS_RE, "");
   if (!sanitized.trim()) return;
 
@@ -181,7 +221,11 @@ export function recordHistory(text: string): void {
   syncToLocalStorage(cachedItems, cachedCounts);
 
   // Persist to backend (async, fire-and-forget)
-  invoke("input_history_record", { fragments }).catch(() => {});
+  invoke("input_history_record", {
+    fragments,
+    workspaceId: context.workspaceId ?? null,
+    engine: context.engine ?? null,
+  }).catch(() => {});
 }
 
 export function deleteHistoryItem(item: string): void {
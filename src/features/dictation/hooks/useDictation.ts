@@ -5,12 +5,17 @@ import type {
   DictationTranscript,
 } from "../../../types";
 import { cancelDictation, startDictation, stopDictation } from "../../../services/tauri";
-import { subscribeDictationEvents } from "../../../services/events";
+import {
+  subscribeDictationEvents,
+  subscribeDictationPartial,
+} from "../../../services/events";
 
 type UseDictationResult = {
   state: DictationSessionState;
   level: number;
   transcript: DictationTranscript | null;
+  /** Interim text while listening; cleared when the session ends. */
+  partial: string | null;
   error: string | null;
   hint: string | null;
   start: (preferredLanguage: string | null) => Promise<void>;
@@ -25,6 +30,7 @@ export function useDictation(): UseDictationResult {
   const [state, setState] = useState<DictationSessionState>("idle");
   const [level, setLevel] = useState(0);
   const [transcript, setTranscript] = useState<DictationTranscript | null>(null);
+  const [partial, setPartial] = useState<string | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [hint, setHint] = useState<string | null>(null);
   const hintTimeoutRef = useRef<number | null>(null);
@@ -39,6 +45,7 @@ export function useDictation(): UseDictationResult {
         setState(event.state);
         if (event.state === "idle") {
           setLevel(0);
+          setPartial(null);
         }
         return;
       }
@@ -47,6 +54,7 @@ export function useDictation(): UseDictationResult {
         return;
       }
       if (event.type === "transcript") {
+        setPartial(null);
         setTranscript({
           id: `${Date.now()}-${Math.random().toString(36).slice(2, 8)}`,
           text: event.text,
@@ -70,9 +78,16 @@ export function useDictation(): UseDictationResult {
       }
     });
 
+    const unlistenPartial = subscribeDictationPartial((event) => {
+      if (active) {
+        setPartial(event.text);
+      }
+    });
+
     return () => {
       active = false;
       unlisten();
+      unlistenPartial();
       if (hintTimeoutRef.current) {
         window.clearTimeout(hintTimeoutRef.current);
         hintTimeoutRef.current = null;
@@ -83,6 +98,7 @@ export function useDictation(): UseDictationResult {
   const start = useCallback(async (preferredLanguage: string | null) => {
     setError(null);
     setHint(null);
+    setPartial(null);
     await startDictation(preferredLanguage);
   }, []);
 
@@ -117,6 +133,7 @@ export function useDictation(): UseDictationResult {
     state,
     level,
     transcript,
+    partial,
     error,
     hint,
     start,
@@ -38,6 +38,9 @@ vi.mock("../../../services/clientStorage", () => ({
   getClientStoreSync: vi.fn(),
   writeClientStoreValue: vi.fn(),
 }));
+vi.mock("../../../services/events", () => ({
+  subscribeEngineStatusUpdates: vi.fn(() => () => {}),
+}));
 
 const detectEnginesMock = vi.mocked(detectEngines);
 const getActiveEngineMock = vi.mocked(getActiveEngine);
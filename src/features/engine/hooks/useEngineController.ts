@@ -18,6 +18,7 @@ import {
   getClientStoreSync,
   writeClientStoreValue,
 } from "../../../services/clientStorage";
+import { subscribeEngineStatusUpdates } from "../../../services/events";
 import { pushGlobalRuntimeNotice } from "../../../services/globalRuntimeNotices";
 import {
   STORAGE_KEYS as PROVIDER_STORAGE_KEYS,
@@ -608,6 +609,19 @@ export function useEngineController({
     return await detectPromise;
   }, [enabledEngineTypes, loadModelsForEngine, onDebug]);
 
+  /**
+   * Apply statuses from probes that outlived the detection budget
+   */
+  useEffect(() => {
+    return subscribeEngineStatusUpdates((status) => {
+      setEngineStatuses((currentStatuses) =>
+        currentStatuses.map((entry) =>
+          entry.engineType === status.engineType ? status : entry,
+        ),
+      );
+    });
+  }, []);
+
   /**
    * Switch to a different engine
    */
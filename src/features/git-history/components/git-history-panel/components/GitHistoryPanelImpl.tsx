@@ -593,9 +593,13 @@ export const GitHistoryPanel = memo(function GitHistoryPanel({
           snapshotId: append ? historySnapshotIdRef.current : null,
           offset,
           limit: PAGE_SIZE,
+          // The total is counted on the first page; later pages stop early.
+          includeTotal: !append,
         });
 
-        setHistoryTotal(response.total);
+        if (!append) {
+          setHistoryTotal(response.total);
+        }
         setHistoryHasMore(response.hasMore);
         applyHistorySnapshotId(response.snapshotId);
         setCommits((prev) => {
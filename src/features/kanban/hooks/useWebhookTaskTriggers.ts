@@ -0,0 +1,34 @@
+import { useCallback } from "react";
+import { useTauriEvent } from "../../app/hooks/useTauriEvent";
+import {
+  subscribeWebhookAutomations,
+  type WebhookAutomationEvent,
+} from "../../../services/events";
+import type { KanbanTaskExecutionSource } from "../types";
+
+type LaunchTask = (params: {
+  taskId: string;
+  source: KanbanTaskExecutionSource;
+  activate?: boolean;
+}) => Promise<unknown>;
+
+/**
+ * Runs task-center tasks triggered by `runScheduledTask` webhook automations,
+ * the same way as the task's Run button.
+ */
+export function useWebhookTaskTriggers(launchTask: LaunchTask) {
+  const handleAutomation = useCallback(
+    (event: WebhookAutomationEvent) => {
+      if (event.action.kind !== "runScheduledTask") {
+        return;
+      }
+      void launchTask({
+        taskId: event.action.taskId,
+        source: "manual",
+        activate: false,
+      });
+    },
+    [launchTask],
+  );
+  useTauriEvent(subscribeWebhookAutomations, handleAutomation);
+}
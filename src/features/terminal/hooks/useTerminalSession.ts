@@ -9,6 +9,7 @@ import { subscribeTerminalOutput, type TerminalOutputEvent } from "../../../serv
 import {
   openTerminalSession,
   resizeTerminalSession,
+  terminalSnapshot,
   writeTerminalSession,
 } from "../../../services/tauri";
 import { isThemeMutationAttribute } from "../../theme/utils/themeAppearance";
@@ -321,6 +322,17 @@ export function useTerminalSession({
       if (!openedSessionsRef.current.has(key)) {
         await openTerminalSession(activeWorkspace.id, activeTerminalId, cols, rows);
         openedSessionsRef.current.add(key);
+        // The session may outlive our cached buffer (evicted or reloaded);
+        // restore its output from the backend scrollback.
+        if (!outputBuffersRef.current.get(key)) {
+          const snapshot = await terminalSnapshot(activeWorkspace.id, activeTerminalId).catch(
+            () => null,
+          );
+          if (snapshot?.data && !outputBuffersRef.current.get(key)) {
+            outputBuffersRef.current.set(key, appendBuffer(undefined, snapshot.data));
+            renderedKeyRef.current = null;
+          }
+        }
       }
       setStatus("ready");
       setMessage("Terminal ready.");
@@ -3,9 +3,11 @@ import type {
   AppServerEvent,
   DictationEvent,
   DictationModelStatus,
+  DictationPartial,
+  EngineStatus,
 } from "../types";
 import type { CliInstallProgressEvent } from "../types";
-import type { RuntimeLogSessionSnapshot } from "./tauri";
+import type { GitStatusSnapshot, RuntimeLogSessionSnapshot } from "./tauri";
 
 export type Unsubscribe = () => void;
 export const WEB_SERVICE_RECONNECTED_EVENT =
@@ -17,6 +19,12 @@ export type TerminalOutputEvent = {
   data: string;
 };
 
+export type TerminalExitEvent = {
+  workspaceId: string;
+  terminalId: string;
+  exitCode: number | null;
+};
+
 export type RuntimeLogLineEvent = TerminalOutputEvent;
 
 export type DetachedExternalFileChangeEvent = {
@@ -31,6 +39,28 @@ export type DetachedExternalFileChangeEvent = {
   fallbackReason?: string | null;
 };
 
+export type GitStatusChangedEvent = GitStatusSnapshot & {
+  workspaceId: string;
+};
+
+/** A `runScheduledTask` automation triggered through the local webhook. */
+export type WebhookAutomationEvent = {
+  triggerId: string;
+  automationId: string;
+  automationName: string;
+  action:
+    | {
+        kind: "runPrompt";
+        workspaceId: string;
+        prompt: string;
+        engine?: string | null;
+      }
+    | { kind: "refreshGitStatus"; workspaceId: string }
+    | { kind: "runScheduledTask"; taskId: string };
+  payload: unknown;
+  triggeredAt: number;
+};
+
 type SubscriptionOptions = {
   onError?: (error: unknown) => void;
 };
@@ -102,8 +132,10 @@ const appServerHub = createEventHub<AppServerEvent>("app-server-event");
 const dictationDownloadHub =
   createEventHub<DictationModelStatus>("dictation-download");
 const dictationEventHub = createEventHub<DictationEvent>("dictation-event");
+const dictationPartialHub = createEventHub<DictationPartial>("dictation-partial");
 const terminalOutputHub =
   createEventHub<TerminalOutputEvent>("terminal-output");
+const terminalExitHub = createEventHub<TerminalExitEvent>("terminal-exit");
 const runtimeLogLineHub = createEventHub<RuntimeLogLineEvent>(
   "runtime-log:line-appended",
 );
@@ -116,10 +148,15 @@ const runtimeLogExitedHub = createEventHub<RuntimeLogSessionSnapshot>(
 const cliInstallerHub = createEventHub<CliInstallProgressEvent>(
   "cli-installer-event",
 );
+const engineStatusHub = createEventHub<EngineStatus>("engine-status-updated");
 const detachedExternalFileChangeHub =
   createEventHub<DetachedExternalFileChangeEvent>(
     "detached-external-file-change",
   );
+const gitStatusChangedHub =
+  createEventHub<GitStatusChangedEvent>("git-status-changed");
+const webhookAutomationHub =
+  createEventHub<WebhookAutomationEvent>("webhook-automation");
 const updaterCheckHub = createEventHub<void>("updater-check");
 const menuNewAgentHub = createEventHub<void>("menu-new-agent");
 const menuNewWorktreeAgentHub = createEventHub<void>("menu-new-worktree-agent");
@@ -198,6 +235,13 @@ export function subscribeDictationEvents(
   return dictationEventHub.subscribe(onEvent, options);
 }
 
+export function subscribeDictationPartial(
+  onEvent: (event: DictationPartial) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return dictationPartialHub.subscribe(onEvent, options);
+}
+
 export function subscribeTerminalOutput(
   onEvent: (event: TerminalOutputEvent) => void,
   options?: SubscriptionOptions,
@@ -205,6 +249,13 @@ export function subscribeTerminalOutput(
   return terminalOutputHub.subscribe(onEvent, options);
 }
 
+export function subscribeTerminalExit(
+  onEvent: (event: TerminalExitEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return terminalExitHub.subscribe(onEvent, options);
+}
+
 export function subscribeRuntimeLogLine(
   onEvent: (event: RuntimeLogLineEvent) => void,
   options?: SubscriptionOptions,
@@ -226,6 +277,13 @@ export function subscribeCliInstallerEvents(
   return cliInstallerHub.subscribe(onEvent, options);
 }
 
+export function subscribeEngineStatusUpdates(
+  onEvent: (event: EngineStatus) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return engineStatusHub.subscribe(onEvent, options);
+}
+
 export function subscribeRuntimeLogExited(
   onEvent: (event: RuntimeLogSessionSnapshot) => void,
   options?: SubscriptionOptions,
@@ -240,6 +298,13 @@ export function subscribeDetachedExternalFileChanges(
   return detachedExternalFileChangeHub.subscribe(onEvent, options);
 }
 
+export function subscribeGitStatusChanges(
+  onEvent: (event: GitStatusChangedEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return gitStatusChangedHub.subscribe(onEvent, options);
+}
+
 export function subscribeUpdaterCheck(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -462,3 +527,10 @@ export function subscribeOpenPaths(
 ): Unsubscribe {
   return openPathsHub.subscribe(onEvent, options);
 }
+
+export function subscribeWebhookAutomations(
+  onEvent: (event: WebhookAutomationEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return webhookAutomationHub.subscribe(onEvent, options);
+}
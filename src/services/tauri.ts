@@ -9,8 +9,11 @@ import type {
   SendConversationCompletionEmailRequest,
   SendTestEmailRequest,
   UpdateEmailSenderSettingsRequest,
+  LocalUsageGroupBy,
+  LocalUsageQueryResult,
   LocalUsageSnapshot,
   LocalUsageStatistics,
+  SessionCost,
   WorkspaceInfo,
   EngineStatus,
   EngineType,
@@ -23,8 +26,13 @@ import type {
   GitHistoryResponse,
   GitCommitDetails,
   GitCommitDiff,
+  GitFileAtRef,
+  GitFileRefComparison,
+  GitReflogResponse,
   GitBranchCompareCommitSets,
   GitBranchListResponse,
+  GitWorktreeInfo,
+  GitRemoteInfo,
   GitBranchUpdateResult,
   GitPrWorkflowDefaults,
   GitPrWorkflowResult,
@@ -34,6 +42,9 @@ import type {
   GitHubPullRequestsResponse,
   GitLogResponse,
   GitPushPreviewResponse,
+  GitIdentity,
+  GitSigningStatus,
+  GitSubmodule,
   ReviewTarget,
 } from "../types";
 export type {
@@ -63,8 +74,8 @@ export {
   renameWorkspaceSessionFolder,
   unarchiveWorkspaceSessions,
 } from "./tauri/sessionManagement";
-export type { CodexRuntimeReloadResult } from "./tauri/settings";
-export { getCodexConfigPath, getCodexUnifiedExecExternalStatus, reloadCodexRuntimeConfig, restoreCodexUnifiedExecOfficialDefault, setCodexUnifiedExecOfficialOverride } from "./tauri/settings";
+export type { CodexRuntimeReloadResult, DataDirMigrationReport, DataDirStatus } from "./tauri/settings";
+export { getCodexConfigPath, getCodexUnifiedExecExternalStatus, getDataDirStatus, migrateDataDir, reloadCodexRuntimeConfig, restoreCodexUnifiedExecOfficialDefault, setCodexUnifiedExecOfficialOverride } from "./tauri/settings";
 export type {
   AgentMdResponse,
   ClaudeMdResponse,
@@ -86,7 +97,16 @@ export {
 } from "./tauri/textFiles";
 export { getComputerUseBridgeStatus, runComputerUseActivationProbe, runComputerUseCodexBroker, runComputerUseHostContractDiagnostics } from "./tauri/computerUse";
 export { runClaudeDoctor, runCodexDoctor } from "./tauri/doctor";
-export { getCliInstallPlan, runCliInstaller } from "./tauri/cliInstaller";
+export type { ApprovalRule, ApprovalRuleRemoval, ApprovalRuleScope, ClaudeUserQuestion } from "./tauri/approvals";
+export { clearApprovalRules, deleteApprovalRule, listApprovalRules, rememberApprovalRule, respondToClaudeQuestion } from "./tauri/approvals";
+export {
+  getCliInstallPlan,
+  installManagedCodexVersion,
+  listManagedCodexVersions,
+  pinWorkspaceCodexVersion,
+  rollbackWorkspaceCodexVersion,
+  runCliInstaller,
+} from "./tauri/cliInstaller";
 export type {
   ComputerUseActivationFailureKind,
   ComputerUseActivationOutcome,
@@ -129,9 +149,10 @@ export {
   runtimeLogMarkExit,
   runtimeLogStart,
   runtimeLogStop,
+  terminalSnapshot,
   writeTerminalSession,
 } from "./tauri/terminalRuntime";
-export type { RuntimeLogSessionSnapshot, RuntimeLogSessionStatus, RuntimeProfileDescriptor } from "./tauri/terminalRuntime";
+export type { RuntimeLogSessionSnapshot, RuntimeLogSessionStatus, RuntimeProfileDescriptor, TerminalOpenOptions, TerminalSnapshot } from "./tauri/terminalRuntime";
 export {
   projectMemoryCaptureAuto,
   projectMemoryCaptureTurnInput,
@@ -160,6 +181,7 @@ export type {
 } from "./tauri/projectMemory";
 export {
   noteCardArchive,
+  noteCardBacklinks,
   noteCardCreate,
   noteCardDelete,
   noteCardGet,
@@ -169,6 +191,8 @@ export {
 } from "./tauri/noteCards";
 export type {
   NoteCardAttachment,
+  NoteCardLink,
+  NoteCardLinkKind,
   NoteCardPreviewAttachment,
   WorkspaceNoteCard,
   WorkspaceNoteCardListResult,
@@ -439,6 +463,45 @@ export async function engineInterruptTurn(workspaceId: string, turnId: string, e
   });
 }
 
+export type EngineTurnCwd = {
+  engine: EngineType;
+  turnId: string;
+  sessionId: string | null;
+  /** Directory the turn ran in; null is the workspace root. */
+  cwd: string | null;
+  startedAtMs: number;
+};
+
+export async function getEngineSessionTurnCwds(
+  workspaceId: string,
+  sessionId: string,
+): Promise<EngineTurnCwd[]> {
+  return invoke<EngineTurnCwd[]>("engine_session_turn_cwds", {
+    workspaceId,
+    sessionId,
+  });
+}
+
+export type EngineForwarderStats = {
+  turnId: string;
+  engine: EngineType;
+  workspaceId: string;
+  startedAtMs: number;
+  ageMs: number;
+  lastEventAtMs: number | null;
+  eventCount: number;
+  processExited: boolean;
+};
+
+export type EngineForwarderSnapshot = {
+  active: number;
+  forwarders: EngineForwarderStats[];
+};
+
+export async function getEngineForwarderStats(): Promise<EngineForwarderSnapshot> {
+  return invoke<EngineForwarderSnapshot>("engine_forwarder_stats");
+}
+
 export async function compactThreadContext(workspaceId: string, threadId: string) {
   return invoke("thread_compact", { workspaceId, threadId });
 }
@@ -474,11 +537,7 @@ export async function respondToUserInputRequest(
   });
 }
 
-export async function rememberApprovalRule(workspaceId: string, command: string[]) {
-  return invoke("remember_approval_rule", { workspaceId, command });
-}
-
-export async function getGitStatus(workspace_id: string): Promise<{
+export type GitStatusSnapshot = {
   isGitRepository?: boolean;
   branchName: string;
   files: GitFileStatus[];
@@ -486,19 +545,60 @@ export async function getGitStatus(workspace_id: string): Promise<{
   unstagedFiles: GitFileStatus[];
   totalAdditions: number;
   totalDeletions: number;
-}> {
+};
+
+export async function getGitStatus(workspace_id: string): Promise<GitStatusSnapshot> {
   return traceStartupInvoke("get_git_status", workspaceScope(workspace_id), () =>
     invoke("get_git_status", { workspaceId: workspace_id }),
   );
 }
 
+/**
+ * Starts watching the workspace and returns its current status; changes are
+ * delivered through `subscribeGitStatusChanges`.
+ */
+export async function gitStatusWatchStart(workspaceId: string): Promise<GitStatusSnapshot> {
+  return invoke<GitStatusSnapshot>("git_status_watch_start", { workspaceId });
+}
+
+export async function gitStatusWatchStop(workspaceId: string): Promise<void> {
+  return invoke("git_status_watch_stop", { workspaceId });
+}
+
+export async function listGitSubmodules(workspace_id: string): Promise<GitSubmodule[]> {
+  return invoke("list_git_submodules", { workspaceId: workspace_id });
+}
+
+export async function updateGitSubmodules(
+  workspace_id: string,
+  options?: { init?: boolean; recursive?: boolean },
+): Promise<void> {
+  return invoke("update_git_submodules", {
+    workspaceId: workspace_id,
+    init: options?.init ?? true,
+    recursive: options?.recursive ?? true,
+  });
+}
+
 export async function listGitRoots(workspace_id: string, depth: number): Promise<string[]> {
   return invoke("list_git_roots", { workspaceId: workspace_id, depth });
 }
 
-export async function getGitDiffs(workspace_id: string): Promise<GitFileDiff[]> {
+export type GitDiffsOptions = {
+  /** Pathspecs to restrict the diff to, relative to the repository root. */
+  paths?: string[];
+  /** Files past this count are listed as summary-only entries. */
+  maxFiles?: number;
+  /** Patches larger than this are listed as summary-only entries. */
+  maxBytesPerFile?: number;
+};
+
+export async function getGitDiffs(
+  workspace_id: string,
+  options?: GitDiffsOptions,
+): Promise<GitFileDiff[]> {
   return traceStartupInvoke("get_git_diffs", workspaceScope(workspace_id), () =>
-    invoke("get_git_diffs", { workspaceId: workspace_id }),
+    invoke("get_git_diffs", { workspaceId: workspace_id, ...options }),
   );
 }
 
@@ -521,6 +621,7 @@ export async function getGitCommitHistory(
     snapshotId?: string | null;
     offset?: number;
     limit?: number;
+    includeTotal?: boolean;
   },
 ): Promise<GitHistoryResponse> {
   return invoke("get_git_commit_history", {
@@ -533,6 +634,7 @@ export async function getGitCommitHistory(
     snapshotId: options?.snapshotId ?? null,
     offset: options?.offset ?? 0,
     limit: options?.limit ?? 100,
+    includeTotal: options?.includeTotal ?? true,
   });
 }
 
@@ -561,6 +663,11 @@ export type CreateGitPrWorkflowOptions = {
   body?: string | null;
   commentAfterCreate?: boolean;
   commentBody?: string | null;
+  draft?: boolean;
+  labels?: string[];
+  reviewers?: string[];
+  /** Create the PR even if the range gate blocks it. */
+  overrideRangeGate?: boolean;
 };
 
 export async function getGitPrWorkflowDefaults(workspaceId: string): Promise<GitPrWorkflowDefaults> {
@@ -580,6 +687,10 @@ export async function createGitPrWorkflow(workspaceId: string, options: CreateGi
     body: options.body ?? null,
     commentAfterCreate: options.commentAfterCreate ?? null,
     commentBody: options.commentBody ?? null,
+    draft: options.draft ?? null,
+    labels: options.labels ?? null,
+    reviewers: options.reviewers ?? null,
+    overrideRangeGate: options.overrideRangeGate ?? null,
   });
 }
 
@@ -618,6 +729,22 @@ export async function getGitRemote(workspace_id: string): Promise<string | null>
   return invoke("get_git_remote", { workspaceId: workspace_id });
 }
 
+export async function listGitRemotes(workspace_id: string): Promise<GitRemoteInfo[]> {
+  return invoke("list_git_remotes", { workspaceId: workspace_id });
+}
+
+export async function addGitRemote(workspace_id: string, name: string, url: string): Promise<void> {
+  return invoke("add_git_remote", { workspaceId: workspace_id, name, url });
+}
+
+export async function removeGitRemote(workspace_id: string, name: string): Promise<void> {
+  return invoke("remove_git_remote", { workspaceId: workspace_id, name });
+}
+
+export async function renameGitRemote(workspace_id: string, name: string, newName: string): Promise<void> {
+  return invoke("rename_git_remote", { workspaceId: workspace_id, name, newName });
+}
+
 export async function stageGitFile(workspaceId: string, path: string) {
   return invoke("stage_git_file", { workspaceId, path });
 }
@@ -638,8 +765,54 @@ export async function revertGitAll(workspaceId: string) {
   return invoke("revert_git_all", { workspaceId });
 }
 
-export async function commitGit(workspaceId: string, message: string): Promise<void> {
-  return invoke("commit_git", { workspaceId, message });
+export type GitCommitOptions = {
+  sign?: boolean | null;
+  /** Amend HEAD; an empty message keeps the existing one. */
+  amend?: boolean;
+  /** Amend even when HEAD has already been pushed upstream. */
+  force?: boolean;
+};
+
+export async function commitGit(
+  workspaceId: string,
+  message: string,
+  options?: GitCommitOptions,
+): Promise<void> {
+  return invoke("commit_git", {
+    workspaceId,
+    message,
+    sign: options?.sign ?? null,
+    amend: options?.amend ?? false,
+    force: options?.force ?? false,
+  });
+}
+
+export async function fixupGitCommit(
+  workspaceId: string,
+  targetSha: string,
+  sign?: boolean | null,
+): Promise<void> {
+  return invoke("fixup_git_commit", { workspaceId, targetSha, sign: sign ?? null });
+}
+
+export async function getGitSigningStatus(workspaceId: string): Promise<GitSigningStatus> {
+  return invoke("get_git_signing_status", { workspaceId });
+}
+
+/** Prefix of the `commitGit` error raised when user.name/user.email are unset. */
+export const GIT_IDENTITY_MISSING_ERROR_PREFIX = "GIT_IDENTITY_MISSING:";
+
+export async function getGitIdentity(workspaceId: string): Promise<GitIdentity> {
+  return invoke("get_git_identity", { workspaceId });
+}
+
+export async function setGitIdentity(
+  workspaceId: string,
+  name: string,
+  email: string,
+  scope: "local" | "global",
+): Promise<GitIdentity> {
+  return invoke("set_git_identity", { workspaceId, name, email, scope });
 }
 
 export type GitPushOptions = {
@@ -724,10 +897,45 @@ export async function getGitHubPullRequests(workspace_id: string): Promise<GitHu
   return invoke("get_github_pull_requests", { workspaceId: workspace_id });
 }
 
-export async function getGitHubPullRequestDiff(workspace_id: string, prNumber: number): Promise<GitHubPullRequestDiff[]> {
+/**
+ * Without `path` the whole diff is returned, with `truncated` set on files
+ * past the size cap; pass `path` to fetch one of those on its own.
+ */
+export async function getGitHubPullRequestDiff(
+  workspace_id: string,
+  prNumber: number,
+  options?: { path?: string | null },
+): Promise<GitHubPullRequestDiff[]> {
   return invoke("get_github_pull_request_diff", {
     workspaceId: workspace_id,
     prNumber,
+    path: options?.path ?? null,
+  });
+}
+
+export async function getGitHubPullRequestFiles(
+  workspace_id: string,
+  prNumber: number,
+  options?: { page?: number; perPage?: number },
+): Promise<GitHubPullRequestFilesResponse> {
+  return invoke("get_github_pull_request_files", {
+    workspaceId: workspace_id,
+    prNumber,
+    page: options?.page ?? null,
+    perPage: options?.perPage ?? null,
+  });
+}
+
+/** Returns the checked-out branch, or null when `detach` was set. */
+export async function checkoutGitHubPullRequest(
+  workspace_id: string,
+  prNumber: number,
+  options?: { detach?: boolean },
+): Promise<string | null> {
+  return invoke("checkout_github_pull_request", {
+    workspaceId: workspace_id,
+    prNumber,
+    detach: options?.detach ?? null,
   });
 }
 
@@ -762,6 +970,25 @@ export async function localUsageStatistics(input: {
   });
 }
 
+export async function localUsageQuery(input: {
+  groupBy: LocalUsageGroupBy;
+  range?: "all" | `${number}d`;
+  workspaceId?: string | null;
+}): Promise<LocalUsageQueryResult> {
+  return invoke<LocalUsageQueryResult>("local_usage_query", {
+    groupBy: input.groupBy,
+    range: input.range ?? "30d",
+    workspaceId: input.workspaceId ?? null,
+  });
+}
+
+export async function getSessionCost(
+  engine: EngineType,
+  sessionId: string,
+): Promise<SessionCost | null> {
+  return invoke<SessionCost | null>("get_session_cost", { engine, sessionId });
+}
+
 export async function getModelList(workspaceId: string) {
   return traceStartupInvoke("model_list", workspaceScope(workspaceId), () =>
     invoke<{
@@ -911,7 +1138,10 @@ export async function getOpenCodeProviderHealth(workspaceId: string, provider?:
   });
 }
 
-export async function getOpenCodeProviderCatalog(workspaceId: string) {
+export async function getOpenCodeProviderCatalog(
+  workspaceId: string,
+  refresh?: boolean,
+) {
   return invoke<
     Array<{
       id: string;
@@ -920,7 +1150,7 @@ export async function getOpenCodeProviderCatalog(workspaceId: string) {
       category: "popular" | "other";
       recommended: boolean;
     }>
-  >("opencode_provider_catalog", { workspaceId });
+  >("opencode_provider_catalog", { workspaceId, refresh: refresh ?? null });
 }
 
 export async function connectOpenCodeProvider(workspaceId: string, providerId?: string | null) {
@@ -1182,6 +1412,65 @@ export async function movePrompt(workspaceId: string, data: { path: string; scop
   });
 }
 
+export type PromptRenderResult = {
+  text: string;
+  missing: string[];
+};
+
+export async function renderPrompt(
+  promptPath: string,
+  variables: Record<string, string>,
+  workspaceId?: string | null,
+): Promise<PromptRenderResult> {
+  return invoke<PromptRenderResult>("prompts_render", {
+    promptId: promptPath,
+    variables,
+    workspaceId: workspaceId ?? null,
+  });
+}
+
+export type PromptConflictStrategy = "skip" | "overwrite" | "rename";
+
+export type PromptExportResult = {
+  path: string;
+  count: number;
+};
+
+export type PromptImportSummary = {
+  created: string[];
+  overwritten: string[];
+  skipped: string[];
+  renamed: { from: string; to: string }[];
+};
+
+export async function exportPrompts(
+  workspaceId: string,
+  data: { scope: "workspace" | "global"; ids?: string[] | null; path: string },
+): Promise<PromptExportResult> {
+  return invoke<PromptExportResult>("prompts_export", {
+    workspaceId,
+    scope: data.scope,
+    ids: data.ids ?? null,
+    path: data.path,
+  });
+}
+
+export async function importPrompts(
+  workspaceId: string,
+  data: {
+    path: string;
+    targetScope?: "workspace" | "global" | null;
+    conflictStrategy: PromptConflictStrategy;
+  },
+): Promise<PromptImportSummary> {
+  return invoke<PromptImportSummary>("prompts_import", {
+    workspaceId,
+    path: data.path,
+    targetScope: data.targetScope ?? null,
+    conflictStrategy: data.conflictStrategy,
+  });
+}
+
 export async function getAppSettings(): Promise<AppSettings> {
   return invoke<AppSettings>("get_app_settings");
 }
@@ -1578,6 +1867,10 @@ export async function listGitBranches(workspaceId: string): Promise<GitBranchLis
   return invoke<GitBranchListResponse>("list_git_branches", { workspaceId });
 }
 
+export async function listGitWorktrees(workspaceId: string): Promise<GitWorktreeInfo[]> {
+  return invoke<GitWorktreeInfo[]>("list_git_worktrees", { workspaceId });
+}
+
 export async function checkoutGitBranch(workspaceId: string, name: string) {
   return invoke("checkout_git_branch", { workspaceId, name });
 }
@@ -1671,6 +1964,51 @@ export async function getGitWorktreeDiffFileAgainstBranch(workspaceId: string, b
   });
 }
 
+export async function getFileAtRef(
+  workspaceId: string,
+  path: string,
+  refName: string,
+  followRenames?: boolean,
+): Promise<GitFileAtRef> {
+  return invoke<GitFileAtRef>("get_file_at_ref", {
+    workspaceId,
+    path,
+    refName,
+    followRenames: followRenames ?? null,
+  });
+}
+
+export async function compareFileWithRef(
+  workspaceId: string,
+  path: string,
+  refName: string,
+  followRenames?: boolean,
+): Promise<GitFileRefComparison> {
+  return invoke<GitFileRefComparison>("compare_file_with_ref", {
+    workspaceId,
+    path,
+    refName,
+    followRenames: followRenames ?? null,
+  });
+}
+
+export async function getGitReflog(
+  workspaceId: string,
+  offset = 0,
+  limit = 100,
+): Promise<GitReflogResponse> {
+  return invoke<GitReflogResponse>("get_git_reflog", { workspaceId, offset, limit });
+}
+
+/** Creates `newBranchName` at `sha` and returns the branch name. */
+export async function recoverGitCommit(
+  workspaceId: string,
+  sha: string,
+  newBranchName: string,
+): Promise<string> {
+  return invoke<string>("recover_git_commit", { workspaceId, sha, newBranchName });
+}
+
 export async function listThreads(workspaceId: string, cursor?: string | null, limit?: number | null) {
   return traceStartupInvoke("list_threads", workspaceScope(workspaceId), () =>
     invoke<ThreadListPayload | null | undefined>("list_threads", {
@@ -1747,6 +2085,30 @@ export async function deleteOpenCodeSession(workspaceId: string, sessionId: stri
   return invoke<{ deleted: boolean; method: "cli" | "filesystem" }>("opencode_delete_session", { workspaceId, sessionId });
 }
 
+export async function renameOpenCodeSession(
+  workspaceId: string,
+  sessionId: string,
+  title: string,
+) {
+  return invoke<{ sessionId: string; title: string }>("opencode_rename_session", {
+    workspaceId,
+    sessionId,
+    title,
+  });
+}
+
+export async function generateOpenCodeSessionTitle(
+  workspaceId: string,
+  sessionId: string,
+  preferredLanguage?: "zh" | "en",
+) {
+  return invoke<{ sessionId: string; title: string }>("opencode_generate_session_title", {
+    workspaceId,
+    sessionId,
+    preferredLanguage: preferredLanguage ?? null,
+  });
+}
+
 export type CommitMessageLanguage = "zh" | "en";
 export type CommitMessageEngine = EngineType;
 
@@ -1754,16 +2116,18 @@ export async function getCommitMessagePrompt(
   workspaceId: string,
   language: CommitMessageLanguage = "zh",
   selectedPaths?: string[],
+  stagedOnly?: boolean,
 ): Promise<string> {
-  return invoke("get_commit_message_prompt", { workspaceId, language, selectedPaths });
+  return invoke("get_commit_message_prompt", { workspaceId, language, selectedPaths, stagedOnly });
 }
 
 export async function generateCommitMessage(
   workspaceId: string,
   language: CommitMessageLanguage = "zh",
   selectedPaths?: string[],
+  stagedOnly?: boolean,
 ): Promise<string> {
-  return invoke("generate_commit_message", { workspaceId, language, selectedPaths });
+  return invoke("generate_commit_message", { workspaceId, language, selectedPaths, stagedOnly });
 }
 
 export async function generateCommitMessageWithEngine(
@@ -1771,11 +2135,12 @@ export async function generateCommitMessageWithEngine(
   language: CommitMessageLanguage = "zh",
   engine: CommitMessageEngine = "codex",
   selectedPaths?: string[],
+  stagedOnly?: boolean,
 ): Promise<string> {
   if (engine === "codex") {
-    return generateCommitMessage(workspaceId, language, selectedPaths);
+    return generateCommitMessage(workspaceId, language, selectedPaths, stagedOnly);
   }
-  const prompt = await getCommitMessagePrompt(workspaceId, language, selectedPaths);
+  const prompt = await getCommitMessagePrompt(workspaceId, language, selectedPaths, stagedOnly);
   const response = await engineSendMessageSync(workspaceId, {
     text: prompt,
     engine,
@@ -1954,6 +2319,8 @@ export async function engineSendMessage(
     agent?: string | null;
     variant?: string | null;
     customSpecRoot?: string | null;
+    /** Interrupt the turn if it has not finished after this many seconds. */
+    timeoutSecs?: number | null;
   },
 ): Promise<Record<string, unknown>> {
   if (isEngineRpcFallbackMode() && params.engine && params.engine !== "codex") {
@@ -1980,6 +2347,7 @@ export async function engineSendMessage(
       agent: params.agent ?? null,
       variant: params.variant ?? null,
       customSpecRoot: params.customSpecRoot ?? null,
+      timeoutSecs: params.timeoutSecs ?? null,
     });
   } catch (error) {
     if (isUnknownMethodError(error, "engine_send_message")) {
@@ -2070,6 +2438,51 @@ export async function listClaudeSessions(workspacePath: string, limit?: number |
   );
 }
 
+export type SessionSearchHit = {
+  engine: EngineType;
+  sessionId: string;
+  title: string | null;
+  snippet: string;
+  /** Match ranges in `snippet` (UTF-16 offsets, usable with `String.slice`). */
+  matches: { start: number; end: number }[];
+  timestamp: number | null;
+};
+
+/**
+ * Search message text across local Claude, Codex and OpenCode session
+ * histories. `limit` applies per engine.
+ */
+export async function searchSessions(
+  query: string,
+  options?: { engines?: EngineType[] | null; workspaceId?: string | null; limit?: number | null },
+): Promise<SessionSearchHit[]> {
+  return invoke<SessionSearchHit[]>("search_sessions", {
+    query,
+    engines: options?.engines ?? null,
+    workspaceId: options?.workspaceId ?? null,
+    limit: options?.limit ?? null,
+  });
+}
+
+/**
+ * Export a Claude Code session as Markdown or JSON. Defaults to the downloads
+ * folder; Markdown images are written next to the file unless `embedImages`.
+ */
+export async function exportClaudeSession(
+  workspaceId: string,
+  sessionId: string,
+  format: "markdown" | "json",
+  options?: { outputPath?: string | null; embedImages?: boolean | null },
+) {
+  return invoke<{ sessionId: string; filePath: string }>("export_claude_session", {
+    workspaceId,
+    sessionId,
+    format,
+    outputPath: options?.outputPath ?? null,
+    embedImages: options?.embedImages ?? null,
+  });
+}
+
 /**
  * Load full message history for a specific Claude Code session.
  */
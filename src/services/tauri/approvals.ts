@@ -0,0 +1,65 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { RequestUserInputQuestion } from "../../types";
+
+/** Payload of the `claude-user-question` event. */
+export type ClaudeUserQuestion = {
+  workspaceId: string;
+  sessionId: string | null;
+  threadId: string;
+  turnId: string;
+  questionId: string;
+  prompt: string;
+  questions: RequestUserInputQuestion[];
+  timeoutSecs: number;
+  expiresAtMs: number;
+};
+
+export async function respondToClaudeQuestion(
+  sessionId: string,
+  questionId: string,
+  answer: string | string[] | Record<string, string | string[]>,
+): Promise<void> {
+  return invoke("claude_respond_to_question", { sessionId, questionId, answer });
+}
+
+export async function rememberApprovalRule(workspaceId: string, command: string[]) {
+  return invoke("remember_approval_rule", { workspaceId, command });
+}
+
+export type ApprovalRuleScope = "workspace" | "global";
+
+export type ApprovalRule = {
+  id: string;
+  pattern: string[];
+  scope: ApprovalRuleScope;
+  workspaceIds: string[];
+  rulesPath: string;
+  createdAtMs: number;
+  hitCount: number;
+  lastHitAtMs: number | null;
+};
+
+export async function listApprovalRules(): Promise<ApprovalRule[]> {
+  return invoke<ApprovalRule[]>("list_approval_rules");
+}
+
+export type ApprovalRuleRemoval = {
+  removed: number;
+  restartedWorkspaceIds: string[];
+  // Workspaces still running with the deleted rules.
+  pendingRestarts: {
+    workspaceId: string;
+    threadIds: string[];
+    error: string | null;
+  }[];
+};
+
+export async function deleteApprovalRule(ruleId: string): Promise<ApprovalRuleRemoval> {
+  return invoke<ApprovalRuleRemoval>("delete_approval_rule", { ruleId });
+}
+
+export async function clearApprovalRules(
+  scope: ApprovalRuleScope,
+): Promise<ApprovalRuleRemoval> {
+  return invoke<ApprovalRuleRemoval>("clear_approval_rules", { scope });
+}
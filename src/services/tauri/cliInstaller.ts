@@ -5,6 +5,8 @@ import type {
   CliInstallPlan,
   CliInstallResult,
   CliInstallStrategy,
+  ManagedCodexVersion,
+  WorkspaceInfo,
 } from "../../types";
 
 export async function getCliInstallPlan(
@@ -32,3 +34,39 @@ export async function runCliInstaller(
     runId,
   });
 }
+
+export async function listManagedCodexVersions(): Promise<ManagedCodexVersion[]> {
+  return invoke<ManagedCodexVersion[]>("list_managed_codex_versions");
+}
+
+/**
+ * Downloads a Codex release. `sha256` is the expected archive checksum; it is
+ * required when the release does not publish one.
+ */
+export async function installManagedCodexVersion(
+  version: string,
+  sha256?: string | null,
+): Promise<ManagedCodexVersion> {
+  return invoke<ManagedCodexVersion>("install_managed_codex_version", {
+    version,
+    sha256: sha256 ?? null,
+  });
+}
+
+export async function pinWorkspaceCodexVersion(
+  workspaceId: string,
+  version: string,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("pin_workspace_codex_version", {
+    workspaceId,
+    version,
+  });
+}
+
+export async function rollbackWorkspaceCodexVersion(
+  workspaceId: string,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("rollback_workspace_codex_version", {
+    workspaceId,
+  });
+}
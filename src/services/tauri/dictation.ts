@@ -52,8 +52,20 @@ export async function removeDictationModel(
 
 export async function startDictation(
   preferredLanguage: string | null,
+  options?: {
+    /** Forces this language; `preferredLanguage` only breaks detection ties. */
+    language?: string | null;
+    modelId?: string | null;
+    /** Emit `dictation-partial` interim text while listening (default on). */
+    partialResults?: boolean | null;
+  },
 ): Promise<DictationSessionState> {
-  return invoke("dictation_start", { preferredLanguage });
+  return invoke("dictation_start", {
+    preferredLanguage,
+    language: options?.language ?? null,
+    modelId: options?.modelId ?? null,
+    partialResults: options?.partialResults ?? null,
+  });
 }
 
 export async function requestDictationPermission(): Promise<boolean> {
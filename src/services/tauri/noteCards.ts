@@ -14,6 +14,14 @@ export type NoteCardPreviewAttachment = Pick<
   "id" | "fileName" | "contentType" | "absolutePath"
 >;
 
+export type NoteCardLinkKind = "session" | "commit";
+
+export type NoteCardLink = {
+  kind: NoteCardLinkKind;
+  target: string;
+  label?: string | null;
+};
+
 export type WorkspaceNoteCard = {
   id: string;
   workspaceId: string;
@@ -27,6 +35,7 @@ export type WorkspaceNoteCard = {
   createdAt: number;
   updatedAt: number;
   archivedAt?: number | null;
+  links?: NoteCardLink[];
 };
 
 export type WorkspaceNoteCardSummary = {
@@ -40,6 +49,7 @@ export type WorkspaceNoteCardSummary = {
   archived: boolean;
   imageCount: number;
   previewAttachments: NoteCardPreviewAttachment[];
+  links?: NoteCardLink[];
 };
 
 export type WorkspaceNoteCardListResult = {
@@ -88,6 +98,7 @@ export async function noteCardCreate(input: {
   title?: string | null;
   bodyMarkdown: string;
   attachmentInputs?: string[] | null;
+  links?: NoteCardLink[] | null;
 }): Promise<WorkspaceNoteCard> {
   return invoke<WorkspaceNoteCard>("note_card_create", {
     input: {
@@ -97,6 +108,7 @@ export async function noteCardCreate(input: {
       title: input.title ?? null,
       bodyMarkdown: input.bodyMarkdown,
       attachmentInputs: input.attachmentInputs ?? null,
+      links: input.links ?? null,
     },
   });
 }
@@ -110,6 +122,7 @@ export async function noteCardUpdate(
     title?: string | null;
     bodyMarkdown?: string | null;
     attachmentInputs?: string[] | null;
+    links?: NoteCardLink[] | null;
   },
 ): Promise<WorkspaceNoteCard> {
   return invoke<WorkspaceNoteCard>("note_card_update", {
@@ -121,6 +134,7 @@ export async function noteCardUpdate(
       title: patch.title ?? null,
       bodyMarkdown: patch.bodyMarkdown ?? null,
       attachmentInputs: patch.attachmentInputs ?? null,
+      links: patch.links ?? null,
     },
   });
 }
@@ -166,3 +180,19 @@ export async function noteCardDelete(params: {
     workspacePath: params.workspacePath ?? null,
   });
 }
+
+export async function noteCardBacklinks(params: {
+  workspaceId: string;
+  workspaceName?: string | null;
+  workspacePath?: string | null;
+  kind: NoteCardLinkKind;
+  target: string;
+}): Promise<WorkspaceNoteCardSummary[]> {
+  return invoke<WorkspaceNoteCardSummary[]>("note_card_backlinks", {
+    workspaceId: params.workspaceId,
+    workspaceName: params.workspaceName ?? null,
+    workspacePath: params.workspacePath ?? null,
+    kind: params.kind,
+    target: params.target,
+  });
+}
@@ -36,3 +36,34 @@ export async function setCodexUnifiedExecOfficialOverride(
 export async function reloadCodexRuntimeConfig(): Promise<CodexRuntimeReloadResult> {
   return invoke<CodexRuntimeReloadResult>("reload_codex_runtime_config");
 }
+
+export interface DataDirStatus {
+  dataPath: string;
+  defaultPath: string;
+  configuredPath?: string | null;
+  available: boolean;
+}
+
+export interface DataDirMigrationReport {
+  previousPath: string;
+  dataPath: string;
+  fileCount: number;
+  totalBytes: number;
+  cleanupError?: string | null;
+}
+
+export async function getDataDirStatus(): Promise<DataDirStatus> {
+  return invoke<DataDirStatus>("get_data_dir_status");
+}
+
+/**
+ * Moves app data to `target`, or back to the default location when omitted.
+ * The app should be restarted afterwards.
+ */
+export async function migrateDataDir(
+  target?: string | null,
+): Promise<DataDirMigrationReport> {
+  return invoke<DataDirMigrationReport>("migrate_data_dir", {
+    target: target ?? null,
+  });
+}
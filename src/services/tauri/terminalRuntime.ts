@@ -1,12 +1,29 @@
 import { invoke } from "@tauri-apps/api/core";
 
+export type TerminalOpenOptions = {
+  /** Run this instead of an interactive shell; the terminal exits with it. */
+  command?: string | null;
+  env?: Record<string, string> | null;
+  /** Absolute, or relative to the workspace; must exist. */
+  cwd?: string | null;
+};
+
 export async function openTerminalSession(
   workspaceId: string,
   terminalId: string,
   cols: number,
   rows: number,
+  options?: TerminalOpenOptions,
 ): Promise<{ id: string }> {
-  return invoke("terminal_open", { workspaceId, terminalId, cols, rows });
+  return invoke("terminal_open", {
+    workspaceId,
+    terminalId,
+    cols,
+    rows,
+    command: options?.command ?? null,
+    env: options?.env ?? null,
+    cwd: options?.cwd ?? null,
+  });
 }
 
 export async function writeTerminalSession(
@@ -26,6 +43,19 @@ export async function resizeTerminalSession(
   return invoke("terminal_resize", { workspaceId, terminalId, cols, rows });
 }
 
+export type TerminalSnapshot = {
+  data: string;
+  cols: number;
+  rows: number;
+};
+
+export async function terminalSnapshot(
+  workspaceId: string,
+  terminalId: string,
+): Promise<TerminalSnapshot> {
+  return invoke("terminal_snapshot", { workspaceId, terminalId });
+}
+
 export async function closeTerminalSession(
   workspaceId: string,
   terminalId: string,
@@ -10,6 +10,18 @@ export type WorkspaceSettings = {
   launchScript?: string | null;
   launchScripts?: LaunchScriptEntry[] | null;
   worktreeSetupScript?: string | null;
+  managedCodex?: ManagedCodexPin | null;
+  prRangeGateEnabled?: boolean | null;
+  prMaxChangedFiles?: number | null;
+  prSuspiciousThreshold?: number | null;
+};
+
+/** App-managed Codex CLI version a workspace is pinned to. */
+export type ManagedCodexPin = {
+  version: string;
+  binSha256: string;
+  previousVersion?: string | null;
+  previousCodexBin?: string | null;
 };
 
 export type LaunchScriptIconId =
@@ -617,6 +629,7 @@ export type AppSettings = {
   systemNotificationEnabled: boolean;
   emailSender: EmailSenderSettings;
   preloadGitDiffs: boolean;
+  claudeQuestionTimeoutSecs?: number;
   detachedExternalChangeAwarenessEnabled?: boolean;
   detachedExternalChangeWatcherEnabled?: boolean;
   experimentalCollabEnabled: boolean;
@@ -894,6 +907,15 @@ export type CliInstallResult = {
   doctorResult: CodexDoctorResult | null;
 };
 
+export type ManagedCodexVersion = {
+  version: string;
+  asset: string;
+  archiveSha256: string;
+  binSha256: string;
+  binPath: string;
+  installedAtMs: number;
+};
+
 export type CliInstallProgressPhase =
   | "started"
   | "stdout"
@@ -992,6 +1014,40 @@ export type GitFileStatus = {
   status: string;
   additions: number;
   deletions: number;
+  submodule?: GitSubmoduleHeads;
+};
+
+export type GitSubmoduleHeads = {
+  oldSha: string | null;
+  newSha: string | null;
+};
+
+export type GitSigningStatus = {
+  enabled: boolean;
+  format: "openpgp" | "ssh" | "x509" | string;
+  signingKey: string | null;
+  program: string | null;
+};
+
+export type GitConfigScope = "system" | "global" | "local";
+
+export type GitIdentityValue = {
+  value: string;
+  scope: GitConfigScope;
+};
+
+export type GitIdentity = {
+  name: GitIdentityValue | null;
+  email: GitIdentityValue | null;
+};
+
+export type GitSubmodule = {
+  name: string;
+  path: string;
+  url: string | null;
+  recordedSha: string | null;
+  currentSha: string | null;
+  initialized: boolean;
 };
 
 export type GitFileDiff = {
@@ -1017,6 +1073,50 @@ export type GitCommitDiff = {
   newImageMime?: string | null;
 };
 
+/** One side of a file comparison; `text` is omitted for binary or too-large files. */
+export type GitFileContent = {
+  path: string;
+  exists: boolean;
+  size: number;
+  isBinary: boolean;
+  tooLarge: boolean;
+  text?: string | null;
+  encoding?: string | null;
+};
+
+export type GitFileAtRef = GitFileContent & {
+  refName: string;
+  commitSha: string;
+  /** Set when the file had a different path (`path`) at the ref. */
+  renamedFrom?: string | null;
+};
+
+export type GitFileRefComparison = {
+  base: GitFileAtRef;
+  working: GitFileContent;
+};
+
+export type GitReflogEntry = {
+  index: number;
+  sha: string;
+  shortSha: string;
+  previousSha: string | null;
+  message: string;
+  committer: string;
+  committerEmail: string;
+  timestamp: number;
+  /** False once the commit has been garbage-collected. */
+  commitExists: boolean;
+};
+
+export type GitReflogResponse = {
+  total: number;
+  offset: number;
+  limit: number;
+  hasMore: boolean;
+  entries: GitReflogEntry[];
+};
+
 export type GitLogEntry = {
   sha: string;
   summary: string;
@@ -1049,6 +1149,7 @@ export type GitHistoryCommit = {
 export type GitHistoryResponse = {
   snapshotId: string;
   total: number;
+  totalIsEstimated?: boolean;
   offset: number;
   limit: number;
   hasMore: boolean;
@@ -1078,6 +1179,9 @@ export type GitPrWorkflowDefaults = {
   title: string;
   body: string;
   commentBody: string;
+  labels?: string[];
+  reviewers?: string[];
+  draft?: boolean;
   canCreate: boolean;
   disabledReason?: string | null;
 };
@@ -1159,6 +1263,29 @@ export type GitBranchListItem = {
   headSha?: string | null;
   ahead: number;
   behind: number;
+  /** Another worktree that has this branch checked out. */
+  worktreePath?: string | null;
+};
+
+export type GitWorktreeInfo = {
+  path: string;
+  branch: string | null;
+  headSha: string | null;
+  isMain: boolean;
+  locked: boolean;
+  lockReason: string | null;
+  prunable: boolean;
+};
+
+export type GitRemoteInfo = {
+  name: string;
+  fetchUrl: string | null;
+  /** Only set when it differs from `fetchUrl`. */
+  pushUrl: string | null;
+  /** The current branch's upstream is on this remote. */
+  isUpstream: boolean;
+  ahead: number | null;
+  behind: number | null;
 };
 
 export type GitBranchListResponse = {
@@ -1224,6 +1351,24 @@ export type GitHubPullRequestDiff = {
   path: string;
   status: string;
   diff: string;
+  /** Patch dropped by the whole-PR size cap; refetch with `path`. */
+  truncated?: boolean;
+};
+
+export type GitHubPullRequestFile = {
+  path: string;
+  previousPath?: string | null;
+  status: string;
+  additions: number;
+  deletions: number;
+  patch?: string | null;
+};
+
+export type GitHubPullRequestFilesResponse = {
+  page: number;
+  perPage: number;
+  hasMore: boolean;
+  files: GitHubPullRequestFile[];
 };
 
 export type GitHubPullRequestComment = {
@@ -1388,6 +1533,52 @@ export type LocalUsageStatistics = {
   lastUpdated: number;
 };
 
+export type LocalUsageGroupBy = "engine" | "model" | "workspace" | "day";
+
+export type LocalUsageGroup = {
+  key: string;
+  label: string;
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+  totalTokens: number;
+  cost: number;
+  events: number;
+};
+
+export type LocalUsageQueryResult = {
+  updatedAt: number;
+  range: string;
+  groupBy: LocalUsageGroupBy;
+  groups: LocalUsageGroup[];
+  totals: LocalUsageGroup;
+};
+
+export type CostPricingStatus = "estimated" | "partial" | "unknown";
+
+export type TurnCostEstimate = {
+  model: string | null;
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+  costUsd: number | null;
+  pricing: CostPricingStatus;
+};
+
+export type SessionCost = {
+  engine: EngineType;
+  sessionId: string;
+  turns: number;
+  unpricedTurns: number;
+  unpricedModels: string[];
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+  costUsd: number;
+  pricing: CostPricingStatus;
+  updatedAtMs: number;
+};
+
 export type TurnPlanStepStatus = "pending" | "inProgress" | "completed";
 
 export type TurnPlanStep = {
@@ -1526,6 +1717,7 @@ export type CustomPromptOption = {
   argumentHint?: string;
   content: string;
   scope?: "workspace" | "global";
+  variables?: string[];
 };
 
 export type CustomCommandOption = {
@@ -1571,6 +1763,15 @@ export type DictationModelStatus = {
   progress?: DictationDownloadProgress | null;
   error?: string | null;
   path?: string | null;
+  /** Every catalog model; only present on `dictation_model_status` replies. */
+  models?: DictationModelVariant[];
+};
+
+export type DictationModelVariant = {
+  id: string;
+  multilingual: boolean;
+  state: DictationModelState;
+  progress?: DictationDownloadProgress | null;
 };
 
 export type DictationSessionState = "idle" | "listening" | "processing";
@@ -1582,6 +1783,12 @@ export type DictationEvent =
   | { type: "error"; message: string }
   | { type: "canceled"; message: string };
 
+export type DictationPartial = {
+  text: string;
+  /** Two consecutive interim passes produced the same text. */
+  stable: boolean;
+};
+
 export type DictationTranscript = {
   id: string;
   text: string;
@@ -1628,6 +1835,8 @@ export type EngineStatus = {
   features: EngineFeatures;
   models: EngineModelInfo[];
   error: string | null;
+  /** Detection overran its budget; the final status arrives via `engine-status-updated`. */
+  detecting?: boolean;
 };
 
 /**